@@ -0,0 +1,234 @@
+//! Admin API authentication.
+//!
+//! Credentials are static and fixed at startup: any number of API keys and any number of
+//! basic-auth `user:password` pairs. A request is let through when its `Authorization` header
+//! carries one of them, in one of these forms:
+//!
+//! - `<key>`: the bare key, which is what Mountebank's `--apikey` expects;
+//! - `Bearer <key>`;
+//! - `Basic <base64(user:password)>`.
+//!
+//! Every configured credential is compared in constant time, and all of them are compared on every
+//! request, so response timing reveals neither how close a guess came nor which credential it was
+//! close to.
+//!
+//! A credential carries an [`AdminRole`]. A read-only one may read everything but is refused any
+//! mutation with `403`, as every caller is when the server runs with `--readonly`.
+
+use base64::Engine;
+use hyper::Method;
+use subtle::{Choice, ConstantTimeEq};
+
+/// What an authenticated admin API caller may do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdminRole {
+    /// Read and change everything.
+    Admin,
+    /// Read everything; every mutation is refused with `403`.
+    ReadOnly,
+}
+
+/// The credentials the admin API accepts. Empty (the default) means the API is open.
+#[derive(Clone, Default)]
+pub struct AdminAuth {
+    api_keys: Vec<(String, AdminRole)>,
+    basic: Vec<String>,
+}
+
+impl AdminAuth {
+    /// Accept `key` as a bare or `Bearer` token.
+    #[must_use]
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_keys.push((key.into(), AdminRole::Admin));
+        self
+    }
+
+    /// Accept `key` as a bare or `Bearer` token that may only read.
+    #[must_use]
+    pub fn with_read_only_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_keys.push((key.into(), AdminRole::ReadOnly));
+        self
+    }
+
+    /// Accept `user`/`password` as HTTP basic credentials.
+    #[must_use]
+    pub fn with_basic_credentials(mut self, user: &str, password: &str) -> Self {
+        self.basic.push(format!("{user}:{password}"));
+        self
+    }
+
+    /// Whether any credential is configured, i.e. whether requests must authenticate.
+    pub fn is_enabled(&self) -> bool {
+        !self.api_keys.is_empty() || !self.basic.is_empty()
+    }
+
+    /// The role of the credential an `Authorization` header value carries; `None` when it carries
+    /// none of the configured ones.
+    pub(crate) fn authenticate(&self, authorization: &str) -> Option<AdminRole> {
+        let (scheme, credentials) = authorization.split_once(' ').unwrap_or(("", ""));
+        let bearer = scheme
+            .eq_ignore_ascii_case("bearer")
+            .then(|| credentials.trim());
+        let basic = scheme
+            .eq_ignore_ascii_case("basic")
+            .then(|| {
+                base64::engine::general_purpose::STANDARD
+                    .decode(credentials.trim())
+                    .ok()
+            })
+            .flatten();
+
+        let (mut admin, mut read_only) = (Choice::from(0), Choice::from(0));
+        for (key, role) in &self.api_keys {
+            let mut matched = constant_time_eq(authorization.as_bytes(), key.as_bytes());
+            if let Some(token) = bearer {
+                matched |= constant_time_eq(token.as_bytes(), key.as_bytes());
+            }
+            match role {
+                AdminRole::Admin => admin |= matched,
+                AdminRole::ReadOnly => read_only |= matched,
+            }
+        }
+        if let Some(decoded) = &basic {
+            for pair in &self.basic {
+                admin |= constant_time_eq(decoded, pair.as_bytes());
+            }
+        }
+        if bool::from(admin) {
+            Some(AdminRole::Admin)
+        } else if bool::from(read_only) {
+            Some(AdminRole::ReadOnly)
+        } else {
+            None
+        }
+    }
+}
+
+/// Whether a request changes server state. Reads are `GET`/`HEAD`/`OPTIONS`, plus `POST
+/// /imposters/:port/verify`, which only inspects the recorded requests.
+pub(crate) fn is_mutation(method: &Method, path: &str) -> bool {
+    let read = matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+        || (*method == Method::POST
+            && path.starts_with("/imposters/")
+            && path.trim_end_matches('/').ends_with("/verify"));
+    !read
+}
+
+/// Constant-time equality for admin credentials.
+///
+/// A plain `!=` short-circuits at the first differing byte, letting a network
+/// attacker recover a key byte-by-byte from response-timing differences
+/// (issue #548). `ConstantTimeEq` compares every byte regardless of where the
+/// mismatch is; the length check it performs first is not secret.
+fn constant_time_eq(provided: &[u8], expected: &[u8]) -> Choice {
+    provided.ct_eq(expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_key_matches(provided: &str, expected: &str) -> bool {
+        AdminAuth::default()
+            .with_api_key(expected)
+            .authenticate(provided)
+            .is_some()
+    }
+
+    fn basic(user_password: &str) -> String {
+        format!(
+            "Basic {}",
+            base64::engine::general_purpose::STANDARD.encode(user_password)
+        )
+    }
+
+    #[test]
+    fn api_key_matches_accepts_correct() {
+        assert!(api_key_matches("s3cret-token", "s3cret-token"));
+    }
+
+    #[test]
+    fn api_key_matches_rejects_wrong() {
+        assert!(!api_key_matches("s3cret-tokeX", "s3cret-token"));
+        // Differ in the first byte — a short-circuiting compare would return
+        // fastest here; the constant-time compare must still reject it.
+        assert!(!api_key_matches("Xs3cret-token", "s3cret-token"));
+    }
+
+    #[test]
+    fn api_key_matches_rejects_wrong_length() {
+        assert!(!api_key_matches("s3cret", "s3cret-token"));
+        assert!(!api_key_matches("s3cret-token-extra", "s3cret-token"));
+    }
+
+    #[test]
+    fn api_key_matches_rejects_empty_against_nonempty() {
+        assert!(!api_key_matches("", "s3cret-token"));
+        // Two empty strings are trivially equal — no key configured is handled
+        // by the `is_enabled` guard at the call site, not here.
+        assert!(api_key_matches("", ""));
+    }
+
+    #[test]
+    fn any_configured_key_is_accepted_bare_or_as_a_bearer_token() {
+        let auth = AdminAuth::default()
+            .with_api_key("ci-key")
+            .with_api_key("dev-key");
+        assert!(auth.is_enabled());
+        assert_eq!(auth.authenticate("ci-key"), Some(AdminRole::Admin));
+        assert_eq!(auth.authenticate("dev-key"), Some(AdminRole::Admin));
+        assert_eq!(auth.authenticate("Bearer dev-key"), Some(AdminRole::Admin));
+        assert_eq!(auth.authenticate("bearer ci-key"), Some(AdminRole::Admin));
+        assert_eq!(auth.authenticate("Bearer other-key"), None);
+        assert_eq!(auth.authenticate(&basic("ci-key:ci-key")), None);
+    }
+
+    #[test]
+    fn basic_credentials_must_match_user_and_password() {
+        let auth = AdminAuth::default().with_basic_credentials("admin", "pa:ss");
+        assert_eq!(
+            auth.authenticate(&basic("admin:pa:ss")),
+            Some(AdminRole::Admin)
+        );
+        assert_eq!(auth.authenticate(&basic("admin:pass")), None);
+        assert_eq!(auth.authenticate(&basic("root:pa:ss")), None);
+        assert_eq!(auth.authenticate("Basic not-base64!"), None);
+        assert_eq!(
+            auth.authenticate("admin:pa:ss"),
+            None,
+            "credentials must be encoded"
+        );
+    }
+
+    #[test]
+    fn a_read_only_key_authenticates_with_its_role() {
+        let auth = AdminAuth::default()
+            .with_api_key("ops-key")
+            .with_read_only_api_key("dev-key");
+        assert_eq!(auth.authenticate("ops-key"), Some(AdminRole::Admin));
+        assert_eq!(
+            auth.authenticate("Bearer dev-key"),
+            Some(AdminRole::ReadOnly)
+        );
+        assert_eq!(auth.authenticate("other"), None);
+    }
+
+    #[test]
+    fn reads_and_verify_are_not_mutations() {
+        assert!(!is_mutation(&Method::GET, "/imposters"));
+        assert!(!is_mutation(&Method::HEAD, "/imposters/4545"));
+        assert!(!is_mutation(&Method::POST, "/imposters/4545/verify"));
+        assert!(is_mutation(&Method::POST, "/imposters"));
+        assert!(is_mutation(&Method::PUT, "/imposters/4545/stubs/0"));
+        assert!(is_mutation(
+            &Method::DELETE,
+            "/imposters/4545/savedRequests"
+        ));
+        assert!(is_mutation(&Method::POST, "/admin/reload"));
+    }
+
+    #[test]
+    fn no_credentials_means_authentication_is_off() {
+        assert!(!AdminAuth::default().is_enabled());
+    }
+}
@@ -0,0 +1,623 @@
+//! The admin API over gRPC: `rift.admin.v1.AdminService` from `proto/rift/admin/v1/admin.proto`,
+//! served on the admin listener next to REST. Calls are unary, so the framing is done here (a
+//! 5-byte length prefix, `grpc-status` in the trailers) instead of pulling in a gRPC stack.
+//!
+//! Every call runs the REST handler it mirrors, so validation, the injection gate, script
+//! resolution and persistence are shared, and a REST error becomes the gRPC status below with the
+//! REST message. Definitions travel as REST JSON except a plain `is` response, whose body is
+//! carried as raw bytes so a large binary body skips the base64 round-trip.
+
+pub mod proto;
+
+use crate::admin_api::handlers::events::AdminBody;
+use crate::admin_api::handlers::{imposters, stubs};
+use crate::admin_api::types::{AddStubRequest, collect_body};
+use crate::cluster::Change;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use bytes::{BufMut, Bytes, BytesMut};
+use http_body_util::{BodyExt, Empty, Full, StreamBody};
+use hyper::body::{Frame, Incoming};
+use hyper::header::{CONTENT_TYPE, HeaderMap, HeaderValue};
+use hyper::{Request, Response, StatusCode};
+use prost::Message;
+use rift_mock_core::imposter::{Imposter, ImposterConfig, ImposterError, ImposterManager, Stub};
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::info;
+
+const SERVICE_PREFIX: &str = "/rift.admin.v1.AdminService/";
+
+/// The calls that only read, allowed under `--readonly` and to read-only keys.
+const READ_METHODS: [&str; 3] = ["GetImposter", "ListImposters", "GetMetrics"];
+
+/// gRPC status codes this service answers with.
+mod code {
+    pub const OK: u32 = 0;
+    pub const INVALID_ARGUMENT: u32 = 3;
+    pub const NOT_FOUND: u32 = 5;
+    pub const ALREADY_EXISTS: u32 = 6;
+    pub const PERMISSION_DENIED: u32 = 7;
+    pub const RESOURCE_EXHAUSTED: u32 = 8;
+    pub const UNIMPLEMENTED: u32 = 12;
+    pub const INTERNAL: u32 = 13;
+    pub const UNAVAILABLE: u32 = 14;
+    pub const UNAUTHENTICATED: u32 = 16;
+}
+
+/// A failed call: its gRPC code and a message for `grpc-message`.
+#[derive(Debug)]
+struct Status {
+    code: u32,
+    message: String,
+}
+
+impl Status {
+    fn new(code: u32, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn invalid(message: impl Into<String>) -> Self {
+        Self::new(code::INVALID_ARGUMENT, message)
+    }
+}
+
+/// The gRPC code for a REST status, so a gRPC caller sees the same failure REST would report.
+fn code_for(status: StatusCode) -> u32 {
+    match status {
+        StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => code::INVALID_ARGUMENT,
+        StatusCode::NOT_FOUND => code::NOT_FOUND,
+        StatusCode::CONFLICT => code::ALREADY_EXISTS,
+        StatusCode::UNAUTHORIZED => code::UNAUTHENTICATED,
+        StatusCode::FORBIDDEN => code::PERMISSION_DENIED,
+        StatusCode::PAYLOAD_TOO_LARGE => code::RESOURCE_EXHAUSTED,
+        StatusCode::SERVICE_UNAVAILABLE => code::UNAVAILABLE,
+        _ => code::INTERNAL,
+    }
+}
+
+/// Whether a request is a gRPC call rather than REST.
+pub(crate) fn is_grpc(req: &Request<Incoming>) -> bool {
+    req.headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/grpc"))
+}
+
+/// Whether a gRPC call changes server state, for `--readonly` and read-only keys. Unknown
+/// methods count as mutations, so a read-only caller is refused before learning they don't exist.
+pub(crate) fn is_mutation(path: &str) -> bool {
+    !path
+        .strip_prefix(SERVICE_PREFIX)
+        .is_some_and(|method| READ_METHODS.contains(&method))
+}
+
+/// Serve one gRPC call, along with the change it made to an imposter, so the caller can replicate
+/// it to cluster peers.
+pub(crate) async fn handle(
+    req: Request<Incoming>,
+    manager: Arc<ImposterManager>,
+    allow_injection: bool,
+    scripts_dir: Option<Arc<PathBuf>>,
+) -> (Response<AdminBody>, Option<Change>) {
+    let method = req
+        .uri()
+        .path()
+        .strip_prefix(SERVICE_PREFIX)
+        .map(str::to_string);
+    let result = match &method {
+        Some(method) => match collect_body(req).await {
+            Ok(body) => match unframe(&body) {
+                Ok(message) => call(method, message, manager, allow_injection, scripts_dir).await,
+                Err(status) => Err(status),
+            },
+            Err(e) => Err(Status::new(code_for(e.status_code()), e.to_string())),
+        },
+        None => Err(Status::new(
+            code::UNIMPLEMENTED,
+            format!("Unknown service for {}", req.uri().path()),
+        )),
+    };
+    let change = match (&method, &result) {
+        (Some(method), Ok(message)) => cluster_change(method, message),
+        _ => None,
+    };
+    (reply(result), change)
+}
+
+/// The imposter change a successful call made. Every mutating call replies with the imposter it
+/// touched.
+fn cluster_change(method: &str, reply: &[u8]) -> Option<Change> {
+    let port = u16::try_from(proto::Imposter::decode(reply).ok()?.port).ok()?;
+    match method {
+        "CreateImposter" => Some(Change::Created(port)),
+        "DeleteImposter" => Some(Change::Deleted(port)),
+        "AddStub" | "ReplaceStub" | "DeleteStub" => Some(Change::Stubs(port)),
+        _ => None,
+    }
+}
+
+async fn call(
+    method: &str,
+    message: &[u8],
+    manager: Arc<ImposterManager>,
+    allow_injection: bool,
+    scripts_dir: Option<Arc<PathBuf>>,
+) -> Result<Vec<u8>, Status> {
+    match method {
+        "CreateImposter" => {
+            let req: proto::CreateImposterRequest = decode(message)?;
+            let config = imposter_from_proto(req.imposter.unwrap_or_default())?;
+            let created = rest_outcome(
+                imposters::create_imposter(
+                    config,
+                    "",
+                    Arc::clone(&manager),
+                    allow_injection,
+                    scripts_dir,
+                )
+                .await,
+            )
+            .await?;
+            let port = created["port"]
+                .as_u64()
+                .and_then(|p| u16::try_from(p).ok())
+                .ok_or_else(|| Status::new(code::INTERNAL, "Created imposter has no port"))?;
+            Ok(live_imposter(&manager, port).await?.encode_to_vec())
+        }
+        "GetImposter" => {
+            let req: proto::GetImposterRequest = decode(message)?;
+            Ok(live_imposter(&manager, port(req.port)?)
+                .await?
+                .encode_to_vec())
+        }
+        "ListImposters" => {
+            let _: proto::ListImpostersRequest = decode(message)?;
+            let imposters = manager
+                .list_imposters()
+                .iter()
+                .map(|imposter| imposter_to_proto(imposter))
+                .collect::<Result<_, _>>()?;
+            Ok(proto::ListImpostersResponse { imposters }.encode_to_vec())
+        }
+        "DeleteImposter" => {
+            let req: proto::DeleteImposterRequest = decode(message)?;
+            let port = port(req.port)?;
+            // Taken first: the config `delete_imposter` hands back is the one it was created with.
+            let deleted = live_imposter(&manager, port).await?;
+            if let Err(e) = manager.delete_imposter(port).await {
+                return Err(imposter_error(e).await);
+            }
+            info!("Deleted imposter on port {} (gRPC)", port);
+            Ok(deleted.encode_to_vec())
+        }
+        "AddStub" => {
+            let req: proto::AddStubRequest = decode(message)?;
+            let port = port(req.port)?;
+            let add_req = AddStubRequest {
+                index: req.index.map(|i| i as usize),
+                stub: stub_from_proto(req.stub.unwrap_or_default())?,
+            };
+            rest_outcome(
+                stubs::add_stub(
+                    port,
+                    add_req,
+                    "",
+                    Arc::clone(&manager),
+                    allow_injection,
+                    scripts_dir,
+                )
+                .await,
+            )
+            .await?;
+            Ok(live_imposter(&manager, port).await?.encode_to_vec())
+        }
+        "ReplaceStub" => {
+            let req: proto::ReplaceStubRequest = decode(message)?;
+            let port = port(req.port)?;
+            let stub = stub_from_proto(req.stub.unwrap_or_default())?;
+            rest_outcome(
+                stubs::replace_stub(
+                    port,
+                    req.index as usize,
+                    stub,
+                    "",
+                    Arc::clone(&manager),
+                    allow_injection,
+                    scripts_dir,
+                )
+                .await,
+            )
+            .await?;
+            Ok(live_imposter(&manager, port).await?.encode_to_vec())
+        }
+        "DeleteStub" => {
+            let req: proto::DeleteStubRequest = decode(message)?;
+            let port = port(req.port)?;
+            rest_outcome(
+                stubs::handle_delete(port, req.index as usize, "", Arc::clone(&manager)).await,
+            )
+            .await?;
+            Ok(live_imposter(&manager, port).await?.encode_to_vec())
+        }
+        "GetMetrics" => {
+            let _: proto::GetMetricsRequest = decode(message)?;
+            let per_imposter: Vec<_> = manager
+                .list_imposters()
+                .iter()
+                .map(|imposter| proto::ImposterMetrics {
+                    port: imposter.config.port.map_or(0, u32::from),
+                    number_of_requests: imposter.get_request_count(),
+                })
+                .collect();
+            Ok(proto::Metrics {
+                imposters: per_imposter.len() as u32,
+                per_imposter,
+            }
+            .encode_to_vec())
+        }
+        _ => Err(Status::new(
+            code::UNIMPLEMENTED,
+            format!("Unknown method {method}"),
+        )),
+    }
+}
+
+fn decode<M: Message + Default>(message: &[u8]) -> Result<M, Status> {
+    M::decode(message).map_err(|e| Status::invalid(format!("Invalid request message: {e}")))
+}
+
+fn port(port: u32) -> Result<u16, Status> {
+    u16::try_from(port).map_err(|_| Status::invalid(format!("Port {port} is out of range")))
+}
+
+/// The JSON body of a successful REST response, or its failure as a gRPC status carrying the
+/// REST error message.
+async fn rest_outcome(response: Response<Full<Bytes>>) -> Result<Value, Status> {
+    let status = response.status();
+    let Ok(body) = response.into_body().collect().await;
+    let body = serde_json::from_slice::<Value>(&body.to_bytes()).unwrap_or(Value::Null);
+    if status.is_success() {
+        return Ok(body);
+    }
+    let message = body["errors"][0]["message"]
+        .as_str()
+        .map_or_else(|| status.to_string(), str::to_string);
+    Err(Status::new(code_for(status), message))
+}
+
+async fn live_imposter(manager: &ImposterManager, port: u16) -> Result<proto::Imposter, Status> {
+    match manager.get_imposter(port) {
+        Ok(imposter) => imposter_to_proto(&imposter),
+        Err(e) => Err(imposter_error(e).await),
+    }
+}
+
+/// An imposter error as the status its REST response maps to.
+async fn imposter_error(e: ImposterError) -> Status {
+    match rest_outcome(e.into()).await {
+        Err(status) => status,
+        Ok(_) => Status::new(code::INTERNAL, "Unexpected success"),
+    }
+}
+
+/// The message inside a request's single length-prefixed frame.
+fn unframe(body: &[u8]) -> Result<&[u8], Status> {
+    let [compressed, a, b, c, d, message @ ..] = body else {
+        return Err(Status::invalid("Request is not a gRPC message frame"));
+    };
+    if *compressed != 0 {
+        return Err(Status::new(
+            code::UNIMPLEMENTED,
+            "Compressed messages are not supported",
+        ));
+    }
+    if message.len() != u32::from_be_bytes([*a, *b, *c, *d]) as usize {
+        return Err(Status::invalid("Request must carry exactly one message"));
+    }
+    Ok(message)
+}
+
+/// A gRPC response: the reply message in one frame with `grpc-status: 0` in the trailers, or, on
+/// failure, no body and the status in the headers ("trailers-only").
+fn reply(result: Result<Vec<u8>, Status>) -> Response<AdminBody> {
+    let builder = Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, "application/grpc");
+    let response = match result {
+        Ok(message) => {
+            let mut frame = BytesMut::with_capacity(5 + message.len());
+            frame.put_u8(0);
+            frame.put_u32(message.len() as u32);
+            frame.put_slice(&message);
+            let mut trailers = HeaderMap::new();
+            trailers.insert("grpc-status", HeaderValue::from(code::OK));
+            let frames = [
+                Ok::<_, hyper::Error>(Frame::data(frame.freeze())),
+                Ok(Frame::trailers(trailers)),
+            ];
+            builder.body(StreamBody::new(futures::stream::iter(frames)).boxed())
+        }
+        Err(status) => builder
+            .header("grpc-status", status.code)
+            .header(
+                "grpc-message",
+                urlencoding::encode(&status.message).into_owned(),
+            )
+            .body(Empty::new().map_err(|never| match never {}).boxed()),
+    };
+    response.expect("gRPC response headers are valid")
+}
+
+fn json_object(bytes: &[u8], what: &str) -> Result<Map<String, Value>, Status> {
+    if bytes.is_empty() {
+        return Ok(Map::new());
+    }
+    match serde_json::from_slice(bytes) {
+        Ok(Value::Object(fields)) => Ok(fields),
+        Ok(_) => Err(Status::invalid(format!("{what} must be a JSON object"))),
+        Err(e) => Err(Status::invalid(format!("{what} is not valid JSON: {e}"))),
+    }
+}
+
+fn imposter_from_proto(imposter: proto::Imposter) -> Result<ImposterConfig, Status> {
+    let mut fields = json_object(&imposter.options, "Imposter options")?;
+    if imposter.port != 0 {
+        fields.insert("port".into(), imposter.port.into());
+    }
+    if !imposter.protocol.is_empty() {
+        fields.insert("protocol".into(), imposter.protocol.into());
+    }
+    if !imposter.name.is_empty() {
+        fields.insert("name".into(), imposter.name.into());
+    }
+    if imposter.record_requests {
+        fields.insert("recordRequests".into(), true.into());
+    }
+    let stubs = imposter
+        .stubs
+        .into_iter()
+        .map(stub_json)
+        .collect::<Result<Vec<_>, _>>()?;
+    fields.insert("stubs".into(), stubs.into());
+    serde_json::from_value(Value::Object(fields))
+        .map_err(|e| Status::invalid(format!("Invalid imposter: {e}")))
+}
+
+fn stub_from_proto(stub: proto::Stub) -> Result<Stub, Status> {
+    serde_json::from_value(stub_json(stub)?)
+        .map_err(|e| Status::invalid(format!("Invalid stub: {e}")))
+}
+
+fn stub_json(stub: proto::Stub) -> Result<Value, Status> {
+    let mut fields = json_object(&stub.definition, "Stub definition")?;
+    let responses = stub
+        .responses
+        .into_iter()
+        .map(response_json)
+        .collect::<Result<Vec<_>, _>>()?;
+    fields.insert("responses".into(), responses.into());
+    Ok(Value::Object(fields))
+}
+
+fn response_json(response: proto::Response) -> Result<Value, Status> {
+    match response.kind {
+        Some(proto::response::Kind::Json(json)) => {
+            Ok(Value::Object(json_object(&json, "Response")?))
+        }
+        Some(proto::response::Kind::Is(is)) => {
+            let mut headers = Map::new();
+            for header in is.headers {
+                let values = headers
+                    .entry(header.name)
+                    .or_insert_with(|| Value::Array(Vec::new()));
+                if let Value::Array(values) = values {
+                    values.push(header.value.into());
+                }
+            }
+            let status_code = if is.status_code == 0 {
+                200
+            } else {
+                is.status_code
+            };
+            let mut fields = Map::new();
+            fields.insert("statusCode".into(), status_code.into());
+            fields.insert("headers".into(), Value::Object(headers));
+            if !is.body.is_empty() {
+                match String::from_utf8(is.body) {
+                    Ok(text) => {
+                        fields.insert("body".into(), text.into());
+                    }
+                    Err(binary) => {
+                        fields.insert("body".into(), BASE64.encode(binary.as_bytes()).into());
+                        fields.insert("_mode".into(), "binary".into());
+                    }
+                }
+            }
+            Ok(serde_json::json!({ "is": fields }))
+        }
+        None => Err(Status::invalid("Response sets neither `is` nor `json`")),
+    }
+}
+
+fn imposter_to_proto(imposter: &Imposter) -> Result<proto::Imposter, Status> {
+    config_to_proto(
+        &imposter.current_config(),
+        imposter.get_request_count(),
+        imposter.is_enabled(),
+    )
+}
+
+fn config_to_proto(
+    config: &ImposterConfig,
+    number_of_requests: u64,
+    enabled: bool,
+) -> Result<proto::Imposter, Status> {
+    let Ok(Value::Object(mut fields)) = serde_json::to_value(config) else {
+        return Err(Status::new(code::INTERNAL, "Imposter is not serializable"));
+    };
+    let port = fields.remove("port").and_then(|p| p.as_u64()).unwrap_or(0) as u32;
+    let protocol = take_string(&mut fields, "protocol");
+    let name = take_string(&mut fields, "name");
+    let record_requests = fields
+        .remove("recordRequests")
+        .and_then(|r| r.as_bool())
+        .unwrap_or(false);
+    fields.remove("enabled");
+    let stubs = match fields.remove("stubs") {
+        Some(Value::Array(stubs)) => stubs.into_iter().map(stub_to_proto).collect(),
+        _ => Vec::new(),
+    };
+    Ok(proto::Imposter {
+        port,
+        protocol,
+        name,
+        number_of_requests,
+        enabled,
+        record_requests,
+        stubs,
+        options: object_bytes(fields),
+    })
+}
+
+fn take_string(fields: &mut Map<String, Value>, key: &str) -> String {
+    match fields.remove(key) {
+        Some(Value::String(value)) => value,
+        _ => String::new(),
+    }
+}
+
+/// A JSON object as bytes, with an empty object as no bytes at all.
+fn object_bytes(fields: Map<String, Value>) -> Vec<u8> {
+    if fields.is_empty() {
+        return Vec::new();
+    }
+    serde_json::to_vec(&Value::Object(fields)).unwrap_or_default()
+}
+
+fn stub_to_proto(stub: Value) -> proto::Stub {
+    let Value::Object(mut fields) = stub else {
+        return proto::Stub::default();
+    };
+    let responses = match fields.remove("responses") {
+        Some(Value::Array(responses)) => responses.into_iter().map(response_to_proto).collect(),
+        _ => Vec::new(),
+    };
+    proto::Stub {
+        definition: object_bytes(fields),
+        responses,
+    }
+}
+
+fn response_to_proto(response: Value) -> proto::Response {
+    let kind = match typed_is(&response) {
+        Some(is) => proto::response::Kind::Is(is),
+        None => proto::response::Kind::Json(serde_json::to_vec(&response).unwrap_or_default()),
+    };
+    proto::Response { kind: Some(kind) }
+}
+
+/// A response as the typed `IsResponse` when that loses nothing: an `is` with nothing beside it
+/// (no `_behaviors`, `_rift`) and only a status, headers and a string body.
+fn typed_is(response: &Value) -> Option<proto::IsResponse> {
+    let fields = response.as_object().filter(|fields| fields.len() == 1)?;
+    let is = fields.get("is")?.as_object()?;
+    if is
+        .keys()
+        .any(|key| !matches!(key.as_str(), "statusCode" | "headers" | "body" | "_mode"))
+    {
+        return None;
+    }
+    let binary = is.get("_mode").and_then(Value::as_str) == Some("binary");
+    let body = match is.get("body") {
+        None => Vec::new(),
+        Some(Value::String(body)) if binary => BASE64.decode(body).ok()?,
+        Some(Value::String(body)) => body.clone().into_bytes(),
+        Some(_) => return None,
+    };
+    let mut headers = Vec::new();
+    for (name, values) in is
+        .get("headers")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        let values = match values {
+            Value::Array(values) => values.iter().collect(),
+            value => vec![value],
+        };
+        for value in values {
+            headers.push(proto::Header {
+                name: name.clone(),
+                value: value.as_str()?.to_string(),
+            });
+        }
+    }
+    let status_code = is.get("statusCode").and_then(Value::as_u64).unwrap_or(200) as u32;
+    Some(proto::IsResponse {
+        status_code,
+        headers,
+        body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_body_round_trips_as_raw_bytes() {
+        let body = vec![0x89, b'P', b'N', b'G', 0xff, 0x00];
+        let stub = proto::Stub {
+            definition: br#"{"predicates":[{"equals":{"path":"/logo"}}]}"#.to_vec(),
+            responses: vec![proto::Response {
+                kind: Some(proto::response::Kind::Is(proto::IsResponse {
+                    status_code: 200,
+                    headers: vec![proto::Header {
+                        name: "Content-Type".into(),
+                        value: "image/png".into(),
+                    }],
+                    body: body.clone(),
+                })),
+            }],
+        };
+        let json = stub_json(stub).unwrap();
+        assert_eq!(json["responses"][0]["is"]["_mode"], "binary");
+
+        let back = stub_to_proto(json);
+        let Some(proto::response::Kind::Is(is)) = &back.responses[0].kind else {
+            panic!("expected a typed is response, got {back:?}");
+        };
+        assert_eq!(is.body, body);
+        assert_eq!(is.headers[0].value, "image/png");
+    }
+
+    #[test]
+    fn responses_with_behaviors_travel_as_json() {
+        let response = serde_json::json!({ "is": { "body": "hi" }, "_behaviors": { "wait": 10 } });
+        let converted = response_to_proto(response.clone());
+        let Some(proto::response::Kind::Json(json)) = converted.kind else {
+            panic!("expected JSON, got {converted:?}");
+        };
+        assert_eq!(serde_json::from_slice::<Value>(&json).unwrap(), response);
+    }
+
+    #[test]
+    fn frames_must_be_uncompressed_and_complete() {
+        assert_eq!(unframe(&[0, 0, 0, 0, 2, 8, 1]).unwrap(), &[8, 1]);
+        assert_eq!(
+            unframe(&[1, 0, 0, 0, 0]).unwrap_err().code,
+            code::UNIMPLEMENTED
+        );
+        assert_eq!(
+            unframe(&[0, 0, 0, 0, 3, 8]).unwrap_err().code,
+            code::INVALID_ARGUMENT
+        );
+        assert!(is_mutation("/rift.admin.v1.AdminService/DeleteImposter"));
+        assert!(!is_mutation("/rift.admin.v1.AdminService/GetMetrics"));
+    }
+}
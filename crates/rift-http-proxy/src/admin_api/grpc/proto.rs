@@ -0,0 +1,144 @@
+//! Messages of `proto/rift/admin/v1/admin.proto`, derived by hand with `prost` so neither the
+//! build nor a Rust client needs `protoc`. Field numbers and types must match the published proto.
+
+/// An imposter: the fields a gRPC client commonly reads are typed, the rest ride in `options`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Imposter {
+    #[prost(uint32, tag = "1")]
+    pub port: u32,
+    #[prost(string, tag = "2")]
+    pub protocol: String,
+    #[prost(string, tag = "3")]
+    pub name: String,
+    #[prost(uint64, tag = "4")]
+    pub number_of_requests: u64,
+    #[prost(bool, tag = "5")]
+    pub enabled: bool,
+    #[prost(bool, tag = "6")]
+    pub record_requests: bool,
+    #[prost(message, repeated, tag = "7")]
+    pub stubs: Vec<Stub>,
+    /// Every other imposter field as a UTF-8 JSON object; empty for none.
+    #[prost(bytes = "vec", tag = "8")]
+    pub options: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Stub {
+    /// Every stub field except `responses` as a UTF-8 JSON object; empty for none.
+    #[prost(bytes = "vec", tag = "1")]
+    pub definition: Vec<u8>,
+    #[prost(message, repeated, tag = "2")]
+    pub responses: Vec<Response>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Response {
+    #[prost(oneof = "response::Kind", tags = "1, 2")]
+    pub kind: Option<response::Kind>,
+}
+
+pub mod response {
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum Kind {
+        /// A plain `is` response, its body as raw bytes.
+        #[prost(message, tag = "1")]
+        Is(super::IsResponse),
+        /// Any other response as its REST JSON object.
+        #[prost(bytes = "vec", tag = "2")]
+        Json(Vec<u8>),
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct IsResponse {
+    #[prost(uint32, tag = "1")]
+    pub status_code: u32,
+    #[prost(message, repeated, tag = "2")]
+    pub headers: Vec<Header>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub body: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Header {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, tag = "2")]
+    pub value: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CreateImposterRequest {
+    #[prost(message, optional, tag = "1")]
+    pub imposter: Option<Imposter>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetImposterRequest {
+    #[prost(uint32, tag = "1")]
+    pub port: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListImpostersRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListImpostersResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub imposters: Vec<Imposter>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DeleteImposterRequest {
+    #[prost(uint32, tag = "1")]
+    pub port: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AddStubRequest {
+    #[prost(uint32, tag = "1")]
+    pub port: u32,
+    #[prost(message, optional, tag = "2")]
+    pub stub: Option<Stub>,
+    /// Where to insert the stub; appended when unset.
+    #[prost(uint32, optional, tag = "3")]
+    pub index: Option<u32>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ReplaceStubRequest {
+    #[prost(uint32, tag = "1")]
+    pub port: u32,
+    #[prost(uint32, tag = "2")]
+    pub index: u32,
+    #[prost(message, optional, tag = "3")]
+    pub stub: Option<Stub>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DeleteStubRequest {
+    #[prost(uint32, tag = "1")]
+    pub port: u32,
+    #[prost(uint32, tag = "2")]
+    pub index: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetMetricsRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Metrics {
+    #[prost(uint32, tag = "1")]
+    pub imposters: u32,
+    #[prost(message, repeated, tag = "2")]
+    pub per_imposter: Vec<ImposterMetrics>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ImposterMetrics {
+    #[prost(uint32, tag = "1")]
+    pub port: u32,
+    #[prost(uint64, tag = "2")]
+    pub number_of_requests: u64,
+}
@@ -0,0 +1,74 @@
+//! Drain admin handlers: stop an imposter taking new requests while the ones in flight finish.
+//!
+//! Every handler answers with the imposter's drain status (`draining`, `retryAfter` while
+//! draining, and `inFlight`), so a rollover script can poll `GET` until `inFlight` reaches zero.
+
+use crate::admin_api::types::{collect_body, error_response, json_response};
+use crate::imposter::{DEFAULT_DRAIN_RETRY_AFTER_SECS, ImposterManager};
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::body::Incoming;
+use hyper::{Request, Response, StatusCode};
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// `POST /imposters/:port/_drain` body; an empty body drains with the default delay.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct DrainRequest {
+    /// Seconds advertised in `Retry-After` on refused requests.
+    retry_after: Option<u64>,
+}
+
+/// GET /imposters/:port/_drain → `{draining, retryAfter?, inFlight}`
+pub async fn handle_status(port: u16, manager: Arc<ImposterManager>) -> Response<Full<Bytes>> {
+    match manager.get_imposter(port) {
+        Ok(imposter) => json_response(StatusCode::OK, &imposter.drain_status()),
+        Err(e) => e.into(),
+    }
+}
+
+/// POST /imposters/:port/_drain — optional body `{"retryAfter": 5}`. Draining again only updates
+/// the advertised delay.
+pub async fn handle_start(
+    port: u16,
+    req: Request<Incoming>,
+    manager: Arc<ImposterManager>,
+) -> Response<Full<Bytes>> {
+    let body = match collect_body(req).await {
+        Ok(b) => b,
+        Err(e) => return error_response(e.status_code(), &e.to_string()),
+    };
+    let request: DrainRequest = if body.iter().all(u8::is_ascii_whitespace) {
+        DrainRequest::default()
+    } else {
+        match serde_json::from_slice(&body) {
+            Ok(request) => request,
+            Err(e) => {
+                return error_response(StatusCode::BAD_REQUEST, &format!("Invalid JSON: {e}"));
+            }
+        }
+    };
+    match manager.get_imposter(port) {
+        Ok(imposter) => {
+            imposter.start_drain(
+                request
+                    .retry_after
+                    .unwrap_or(DEFAULT_DRAIN_RETRY_AFTER_SECS),
+            );
+            json_response(StatusCode::OK, &imposter.drain_status())
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// DELETE /imposters/:port/_drain — serve new requests again.
+pub async fn handle_stop(port: u16, manager: Arc<ImposterManager>) -> Response<Full<Bytes>> {
+    match manager.get_imposter(port) {
+        Ok(imposter) => {
+            imposter.stop_drain();
+            json_response(StatusCode::OK, &imposter.drain_status())
+        }
+        Err(e) => e.into(),
+    }
+}
@@ -12,7 +12,7 @@ use http_body_util::{
     combinators::BoxBody,
 };
 use hyper::{Response, StatusCode};
-use rift_mock_core::imposter::{AdminEvent, AdminEventKind, ImposterManager};
+use rift_mock_core::imposter::{AdminEvent, AdminEventKind, ImposterManager, TrafficOutcome};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::broadcast::error::RecvError;
@@ -33,6 +33,8 @@ const CHANNEL_BUFFER: usize = 16;
 struct StreamParams {
     requests: bool,
     lifecycle: bool,
+    /// No-match and fault events; opt-in (`types=traffic`), so existing streams are unchanged.
+    traffic: bool,
     port: Option<u16>,
     clauses: Vec<MatchClause>,
 }
@@ -150,6 +152,7 @@ fn parse_params(
 
     let mut requests = true;
     let mut lifecycle = true;
+    let mut traffic = false;
     let mut port = forced_port;
     let alias = forced_port.is_some();
 
@@ -163,11 +166,12 @@ fn parse_params(
                     match t {
                         "requests" => requests = true,
                         "lifecycle" => lifecycle = true,
+                        "traffic" => traffic = true,
                         other => {
                             return Err((
                                 StatusCode::BAD_REQUEST,
                                 format!(
-                                    "unknown types value '{other}' (expected requests|lifecycle)"
+                                    "unknown types value '{other}' (expected requests|lifecycle|traffic)"
                                 ),
                             ));
                         }
@@ -190,6 +194,7 @@ fn parse_params(
     Ok(StreamParams {
         requests,
         lifecycle,
+        traffic,
         port,
         clauses,
     })
@@ -203,6 +208,9 @@ fn type_list(params: &StreamParams) -> Vec<&'static str> {
     if params.lifecycle {
         out.push("lifecycle");
     }
+    if params.traffic {
+        out.push("traffic");
+    }
     out
 }
 
@@ -252,6 +260,21 @@ fn render(event: &AdminEvent, params: &StreamParams) -> Option<Bytes> {
             }
             Some(sse_frame("request", Some(event.seq), &data))
         }
+        AdminEventKind::Traffic {
+            port,
+            method,
+            path,
+            outcome,
+        } => {
+            if !params.traffic || params.port.is_some_and(|filter| filter != *port) {
+                return None;
+            }
+            Some(sse_frame(
+                "traffic",
+                Some(event.seq),
+                &traffic_payload(*port, method, path, outcome),
+            ))
+        }
     }
 }
 
@@ -277,6 +300,26 @@ fn event_matches(
     })
 }
 
+/// The JSON body of a traffic event, shared by the SSE `traffic` frame and the webhooks:
+/// `{port, method, path, outcome}`, plus `fault` (the `x-rift-fault` value) for a fault.
+pub(crate) fn traffic_payload(
+    port: u16,
+    method: &str,
+    path: &str,
+    outcome: &TrafficOutcome,
+) -> serde_json::Value {
+    let mut data = serde_json::json!({
+        "port": port,
+        "method": method,
+        "path": path,
+        "outcome": outcome.as_str(),
+    });
+    if let TrafficOutcome::Fault(fault) = outcome {
+        data["fault"] = serde_json::json!(fault);
+    }
+    data
+}
+
 fn sse_frame(event: &str, id: Option<u64>, data: &serde_json::Value) -> Bytes {
     let mut frame = format!("event: {event}\n");
     if let Some(id) = id {
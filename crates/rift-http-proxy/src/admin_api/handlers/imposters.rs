@@ -2,20 +2,22 @@
 
 use crate::admin_api::request_filter::{parse_match_clauses, parse_since, request_matches};
 use crate::admin_api::types::{
-    ImposterDetail, ImposterListEntry, ImposterQueryParams, ImposterSummary, ListImpostersResponse,
+    ImposterDetail, ImposterListEntry, ImposterListQuery, ImposterQueryParams, ImposterSummary,
     RiftImposterExtensions, StubWithLinks, build_response_with_headers, collect_body,
     error_response, json_response, make_imposter_links, make_stub_links, serialize_or_500,
 };
 use crate::extensions::decorate::backend_error_response;
 use crate::imposter::RecordedRequest;
+use crate::imposter::stubs_from_wiremock;
 use crate::imposter::{
     Imposter, ImposterConfig, ImposterError, ImposterManager, ScriptBaseDir, Stub, StubResponse,
     VerifyOptions, resolve_scripts,
 };
+use crate::recording::har::{Har, stubs_from_har};
 use crate::response::ErrorKind;
 use crate::scripting::validate_stubs;
 use bytes::Bytes;
-use http_body_util::Full;
+use http_body_util::{BodyExt, Full};
 use hyper::body::Incoming;
 use hyper::{Request, Response, StatusCode};
 use serde::Deserialize;
@@ -34,17 +36,17 @@ pub(crate) fn admin_script_base(scripts_dir: &Option<Arc<PathBuf>>) -> ScriptBas
     }
 }
 
-/// The `_rift.scripts` registry of the imposter on `port` (already resolved when the imposter was
-/// created), for resolving a newly-added stub's `{ "ref": "name" }` against. Empty when the
-/// imposter doesn't exist or declares no registry.
+/// The `_rift.scripts` registry and script libraries of the imposter on `port` (already resolved
+/// when the imposter was created), for resolving a newly-added stub's `{ "ref": "name" }` and
+/// `// @include` lines against. Empty when the imposter doesn't exist or declares neither.
 pub(crate) fn imposter_script_registry(
     manager: &ImposterManager,
     port: u16,
-) -> std::collections::HashMap<String, crate::imposter::RiftScriptConfig> {
+) -> crate::imposter::ScriptRegistry {
     manager
         .get_imposter(port)
         .ok()
-        .and_then(|imposter| imposter.config.rift.as_ref().map(|r| r.scripts.clone()))
+        .map(|imposter| crate::imposter::ScriptRegistry::of(&imposter.config))
         .unwrap_or_default()
 }
 
@@ -81,13 +83,16 @@ pub(crate) fn injection_disallowed_response() -> Response<Full<Bytes>> {
     )
 }
 
-/// Reject a whole imposter config when its stubs carry a scripting surface and `--allowInjection`
-/// is off. Delegates to [`reject_stubs_if_injection_disallowed`].
+/// Reject a whole imposter config when its stubs or `defaultBehaviors` carry a scripting surface
+/// and `--allowInjection` is off — the same classifier the `--configfile` and reload doors ask.
 fn reject_if_injection_disallowed(
     config: &ImposterConfig,
     allow_injection: bool,
 ) -> Option<Response<Full<Bytes>>> {
-    reject_stubs_if_injection_disallowed(&config.stubs, allow_injection)
+    if allow_injection || !crate::injection_gate::config_uses_script_surface(config) {
+        return None;
+    }
+    Some(injection_disallowed_response())
 }
 
 /// POST /imposters - Create a new imposter
@@ -103,7 +108,7 @@ pub async fn handle_create(
         Err(e) => return error_response(e.status_code(), &e.to_string()),
     };
 
-    let mut config: ImposterConfig = match serde_json::from_slice(&body) {
+    let config: ImposterConfig = match serde_json::from_slice(&body) {
         Ok(c) => c,
         Err(e) => {
             return error_response(
@@ -113,6 +118,18 @@ pub async fn handle_create(
         }
     };
 
+    create_imposter(config, base_url, manager, allow_injection, scripts_dir).await
+}
+
+/// The checks and creation behind `POST /imposters`, shared with creating an imposter from a
+/// template: the injection gate, script resolution and validation, then `201` with the imposter.
+pub async fn create_imposter(
+    mut config: ImposterConfig,
+    base_url: &str,
+    manager: Arc<ImposterManager>,
+    allow_injection: bool,
+    scripts_dir: Option<Arc<PathBuf>>,
+) -> Response<Full<Bytes>> {
     if let Some(rejection) = reject_if_injection_disallowed(&config, allow_injection) {
         return rejection;
     }
@@ -160,24 +177,31 @@ pub async fn handle_list(
     base_url: &str,
 ) -> Response<Full<Bytes>> {
     let params = ImposterQueryParams::parse(query);
-    let imposters = manager.list_imposters();
+    let selection = match ImposterListQuery::parse(query) {
+        Ok(selection) => selection,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &e),
+    };
+    let mut imposters = manager.list_imposters();
+    imposters.retain(|i| selection.selects(i.config.port, i.config.name.as_deref()));
+    let total = imposters.len();
+    let imposters = selection.page_of(imposters);
 
-    if params.replayable {
-        let configs: Vec<ImposterConfig> = imposters
+    let listed: Vec<serde_json::Value> = if params.replayable {
+        imposters
             .iter()
             .map(|i| {
+                let config = i.current_config();
                 if params.remove_proxies {
-                    filter_proxy_responses(&i.config)
+                    serde_json::to_value(filter_proxy_responses(&config))
                 } else {
-                    i.config.clone()
+                    serde_json::to_value(&config)
                 }
             })
-            .collect();
-        let body = serde_json::json!({ "imposters": configs });
-        json_response(StatusCode::OK, &body)
+            .collect::<Result<_, _>>()
+            .unwrap_or_default()
     } else if params.list {
         // Mountebank-compatible abbreviated listing: port, protocol, name, numberOfRequests, _links
-        let entries: Vec<ImposterListEntry> = imposters
+        imposters
             .iter()
             .filter_map(|i| {
                 i.config.port.map(|port| ImposterListEntry {
@@ -188,10 +212,10 @@ pub async fn handle_list(
                     links: make_imposter_links(base_url, port),
                 })
             })
-            .collect();
-        json_response(StatusCode::OK, &serde_json::json!({ "imposters": entries }))
+            .filter_map(|entry| serde_json::to_value(entry).ok())
+            .collect()
     } else {
-        let summaries: Vec<ImposterSummary> = imposters
+        imposters
             .iter()
             .filter_map(|i| {
                 i.config.port.map(|port| ImposterSummary {
@@ -205,18 +229,25 @@ pub async fn handle_list(
                     links: make_imposter_links(base_url, port),
                 })
             })
-            .collect();
+            .filter_map(|summary| serde_json::to_value(summary).ok())
+            .collect()
+    };
 
-        let response = ListImpostersResponse {
-            imposters: summaries,
-        };
-        json_response(StatusCode::OK, &response)
-    }
+    let mut response = json_response(
+        StatusCode::OK,
+        &serde_json::json!({ "imposters": selection.project(listed) }),
+    );
+    // How many imposters matched before paging, so a client can tell how many pages there are.
+    response
+        .headers_mut()
+        .insert("x-rift-total-count", total.into());
+    response
 }
 
 /// PUT /imposters - Replace all imposters
 pub async fn handle_replace_all(
     req: Request<Incoming>,
+    query: Option<&str>,
     base_url: &str,
     manager: Arc<ImposterManager>,
     allow_injection: bool,
@@ -226,13 +257,25 @@ pub async fn handle_replace_all(
         Ok(b) => b,
         Err(e) => return error_response(e.status_code(), &e.to_string()),
     };
-    replace_all_from_bytes(&body, base_url, manager, allow_injection, scripts_dir).await
+    replace_all_from_bytes(
+        &body,
+        query,
+        base_url,
+        manager,
+        allow_injection,
+        scripts_dir,
+    )
+    .await
 }
 
 /// Parse, validate, and apply a `PUT /imposters` batch. Split out from `handle_replace_all` so
 /// the path is unit-testable without a `Request<Incoming>` (same seam as `verify_response`).
+///
+/// As in Mountebank, a body without `imposters` (or no body at all) is the empty set, and the
+/// resulting list honours the same query options as `GET /imposters` (`replayable`, ...).
 async fn replace_all_from_bytes(
     body: &[u8],
+    query: Option<&str>,
     base_url: &str,
     manager: Arc<ImposterManager>,
     allow_injection: bool,
@@ -240,9 +283,15 @@ async fn replace_all_from_bytes(
 ) -> Response<Full<Bytes>> {
     #[derive(Deserialize)]
     struct BatchRequest {
+        #[serde(default)]
         imposters: Vec<ImposterConfig>,
     }
 
+    let body = if body.iter().all(u8::is_ascii_whitespace) {
+        b"{}".as_slice()
+    } else {
+        body
+    };
     let mut batch: BatchRequest = match serde_json::from_slice(body) {
         Ok(b) => b,
         Err(e) => {
@@ -295,7 +344,7 @@ async fn replace_all_from_bytes(
     // anything, only replaces what actually changed (an unchanged imposter keeps its runtime
     // state, like POST /admin/reload), and reports per-port failures instead of log-and-continue.
     match manager.apply_config(batch.imposters).await {
-        Ok(report) if report.failed.is_empty() => handle_list(manager, None, base_url).await,
+        Ok(report) if report.failed.is_empty() => handle_list(manager, query, base_url).await,
         // Residual per-port apply failures (e.g. a bind race): the other ports are already
         // reconciled, so carry the full report — a partial failure is exactly when the client
         // needs to know what did apply (same contract as POST /admin/reload).
@@ -712,10 +761,293 @@ pub async fn handle_clear_proxy_responses(
     }
 }
 
+/// GET /imposters/:port/export?format=har — the imposter's recorded traffic (request journal
+/// plus saved proxy responses) as a HAR 1.2 document. `har` is the default and only format.
+pub async fn handle_export(
+    port: u16,
+    query: Option<&str>,
+    manager: Arc<ImposterManager>,
+) -> Response<Full<Bytes>> {
+    if let Some(rejection) = reject_unsupported_har_format(query, "export") {
+        return rejection;
+    }
+    match manager.get_imposter(port) {
+        Ok(imposter) => json_response(StatusCode::OK, &imposter.export_har()),
+        Err(e) => e.into(),
+    }
+}
+
+/// GET /imposters/:port/openapi — an OpenAPI 3 document inferred from the imposter's recorded
+/// traffic: paths, methods, status codes, and schemas of the JSON bodies seen.
+pub async fn handle_openapi(port: u16, manager: Arc<ImposterManager>) -> Response<Full<Bytes>> {
+    match manager.get_imposter(port) {
+        Ok(imposter) => json_response(StatusCode::OK, &imposter.export_openapi()),
+        Err(e) => e.into(),
+    }
+}
+
+/// GET /imposters/:port/fuzzyMatches — the requests a `closestMatch` imposter answered with a
+/// near-miss stub, oldest first, each with the predicate clauses it failed.
+pub async fn handle_get_fuzzy_matches(
+    port: u16,
+    manager: Arc<ImposterManager>,
+) -> Response<Full<Bytes>> {
+    match manager.get_imposter(port) {
+        Ok(imposter) => json_response(
+            StatusCode::OK,
+            &serde_json::json!({ "fuzzyMatches": imposter.fuzzy_matches() }),
+        ),
+        Err(e) => e.into(),
+    }
+}
+
+/// DELETE /imposters/:port/fuzzyMatches — forget the fuzzy-match history.
+pub async fn handle_clear_fuzzy_matches(
+    port: u16,
+    manager: Arc<ImposterManager>,
+) -> Response<Full<Bytes>> {
+    match manager.get_imposter(port) {
+        Ok(imposter) => {
+            imposter.clear_fuzzy_matches();
+            json_response(StatusCode::OK, &serde_json::json!({ "fuzzyMatches": [] }))
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// GET /imposters/:port/shadowDiffs — `{compared, drifted, diffs}`: how many requests a
+/// `proxyShadow` proxy compared with the stub that would have answered them, how many drifted, and
+/// the most recent drifted ones with their structural differences.
+pub async fn handle_get_shadow_diffs(
+    port: u16,
+    manager: Arc<ImposterManager>,
+) -> Response<Full<Bytes>> {
+    match manager.get_imposter(port) {
+        Ok(imposter) => json_response(StatusCode::OK, &imposter.shadow_report()),
+        Err(e) => e.into(),
+    }
+}
+
+/// DELETE /imposters/:port/shadowDiffs — forget the shadow comparisons and reset their counts.
+pub async fn handle_clear_shadow_diffs(
+    port: u16,
+    manager: Arc<ImposterManager>,
+) -> Response<Full<Bytes>> {
+    match manager.get_imposter(port) {
+        Ok(imposter) => {
+            imposter.clear_shadow_report();
+            json_response(StatusCode::OK, &imposter.shadow_report())
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// GET /imposters/:port/proxiedRequests — `{count, proxiedRequests}`: how many calls the
+/// imposter's proxies forwarded upstream and the most recent ones, each with the upstream status
+/// (or error) and latency. `proxyOnce` replays are not upstream calls and are not listed.
+pub async fn handle_get_proxied_requests(
+    port: u16,
+    manager: Arc<ImposterManager>,
+) -> Response<Full<Bytes>> {
+    match manager.get_imposter(port) {
+        Ok(imposter) => json_response(StatusCode::OK, &imposter.proxied_requests()),
+        Err(e) => e.into(),
+    }
+}
+
+/// DELETE /imposters/:port/proxiedRequests — forget the upstream calls and reset the count.
+pub async fn handle_clear_proxied_requests(
+    port: u16,
+    manager: Arc<ImposterManager>,
+) -> Response<Full<Bytes>> {
+    match manager.get_imposter(port) {
+        Ok(imposter) => {
+            imposter.clear_proxied_requests();
+            json_response(StatusCode::OK, &imposter.proxied_requests())
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// GET /imposters/:port/servedRequests[?since=<seq>] — `{count, servedRequests}`: every request the
+/// imposter answered, newest last, with its status, the stub that answered and its latency. Only
+/// entries with a `seq` above `since` are returned, so a poller can tail the log.
+pub async fn handle_get_served_requests(
+    port: u16,
+    query: Option<&str>,
+    manager: Arc<ImposterManager>,
+) -> Response<Full<Bytes>> {
+    let since = match parse_since(query) {
+        Ok(s) => s,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &e.to_string()),
+    };
+    match manager.get_imposter(port) {
+        Ok(imposter) => json_response(StatusCode::OK, &imposter.served_requests(since)),
+        Err(e) => e.into(),
+    }
+}
+
+/// GET /imposters/:port/servedRequests/:seq — one served request with the headers and bodies of
+/// the request and response, while it is among the most recent ones kept in detail.
+pub async fn handle_get_served_request(
+    port: u16,
+    seq: u64,
+    manager: Arc<ImposterManager>,
+) -> Response<Full<Bytes>> {
+    match manager.get_imposter(port) {
+        Ok(imposter) => match imposter.served_request(seq) {
+            Some(detail) => json_response(StatusCode::OK, &detail),
+            None => error_response(
+                StatusCode::NOT_FOUND,
+                &format!("served request {seq} is no longer kept in detail"),
+            ),
+        },
+        Err(e) => e.into(),
+    }
+}
+
+/// DELETE /imposters/:port/servedRequests — forget the served requests and restart the sequence.
+pub async fn handle_clear_served_requests(
+    port: u16,
+    manager: Arc<ImposterManager>,
+) -> Response<Full<Bytes>> {
+    match manager.get_imposter(port) {
+        Ok(imposter) => {
+            imposter.clear_served_requests();
+            json_response(StatusCode::OK, &imposter.served_requests(None))
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// POST /imposters/:port/import?format=har[&headers=accept,x-tenant] — create an HTTP imposter on
+/// `:port` whose stubs are generated from the uploaded HAR document's entries. `headers` names the
+/// request headers to add to each generated predicate.
+///
+/// `format=wiremock` takes WireMock stub mappings instead (`{"mappings": [...]}`, an array, or a
+/// single mapping). Whatever did not convert exactly is listed in a `warnings` array added to the
+/// returned imposter.
+pub async fn handle_import(
+    port: u16,
+    query: Option<&str>,
+    req: Request<Incoming>,
+    base_url: &str,
+    manager: Arc<ImposterManager>,
+) -> Response<Full<Bytes>> {
+    let format = query_value(query, "format").unwrap_or_else(|| "har".to_string());
+    let wiremock = format.eq_ignore_ascii_case("wiremock");
+    if !wiremock && !format.eq_ignore_ascii_case("har") {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            &format!("Unsupported import format '{format}' (supported: har, wiremock)"),
+        );
+    }
+    let body = match collect_body(req).await {
+        Ok(b) => b,
+        Err(e) => return error_response(e.status_code(), &e.to_string()),
+    };
+    let (stubs, warnings) = if wiremock {
+        let document: serde_json::Value = match serde_json::from_slice(&body) {
+            Ok(d) => d,
+            Err(e) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    &format!("Invalid WireMock JSON: {e}"),
+                );
+            }
+        };
+        match stubs_from_wiremock(&document) {
+            Ok(import) => (import.stubs, import.warnings),
+            Err(e) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    &format!("Invalid WireMock mappings: {e}"),
+                );
+            }
+        }
+    } else {
+        let har: Har = match serde_json::from_slice(&body) {
+            Ok(h) => h,
+            Err(e) => {
+                return error_response(StatusCode::BAD_REQUEST, &format!("Invalid HAR JSON: {e}"));
+            }
+        };
+        let match_headers: Vec<String> = query_value(query, "headers")
+            .map(|h| {
+                h.split(',')
+                    .map(str::trim)
+                    .filter(|h| !h.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        (stubs_from_har(&har, &match_headers), Vec::new())
+    };
+
+    let config = ImposterConfig {
+        port: Some(port),
+        stubs,
+        ..Default::default()
+    };
+    let stub_count = config.stubs.len();
+    match manager.create_imposter(config).await {
+        Ok(assigned_port) => {
+            info!(
+                "Imported {stub_count} stub(s) from {format} into imposter on port {assigned_port}"
+            );
+            for warning in &warnings {
+                warn!("WireMock import into port {assigned_port}: {warning}");
+            }
+            let response = handle_get(assigned_port, None, base_url, manager).await;
+            let (mut parts, body) = response.into_parts();
+            parts.status = StatusCode::CREATED;
+            if warnings.is_empty() {
+                return Response::from_parts(parts, body);
+            }
+            let bytes = body
+                .collect()
+                .await
+                .map(|b| b.to_bytes())
+                .unwrap_or_default();
+            let mut imposter: serde_json::Value =
+                serde_json::from_slice(&bytes).unwrap_or_default();
+            imposter["warnings"] = serde_json::json!(warnings);
+            json_response(StatusCode::CREATED, &imposter)
+        }
+        Err(e) => e.into(),
+    }
+}
+
 // =============================================================================
 // Helper functions
 // =============================================================================
 
+/// Percent-decoded value of `key` in a query string.
+fn query_value(query: Option<&str>, key: &str) -> Option<String> {
+    query?.split('&').find_map(|pair| {
+        let (k, v) = pair.split_once('=')?;
+        (k == key).then(|| {
+            urlencoding::decode(v)
+                .map(|d| d.into_owned())
+                .unwrap_or_else(|_| v.to_string())
+        })
+    })
+}
+
+/// A 400 when `format=` names anything but `har` (the default) for HAR `operation`.
+fn reject_unsupported_har_format(
+    query: Option<&str>,
+    operation: &str,
+) -> Option<Response<Full<Bytes>>> {
+    let format = query_value(query, "format")?;
+    (!format.eq_ignore_ascii_case("har")).then(|| {
+        error_response(
+            StatusCode::BAD_REQUEST,
+            &format!("Unsupported {operation} format '{format}' (supported: har)"),
+        )
+    })
+}
+
 /// Filter out proxy responses from stubs. `pub` (not just `pub(crate)`) so the FFI layer
 /// (issue #491) can apply the SAME `removeProxies` projection the admin handlers use, instead of
 /// re-implementing it and risking drift.
@@ -1068,6 +1400,46 @@ mod allow_injection_tests {
         }
     }
 
+    // An ordered `_behaviors` array executes every entry, so a scripted entry anywhere in it must
+    // trip the gate exactly like the object form.
+    #[test]
+    fn ordered_behaviors_array_with_script_is_gated() {
+        for behaviors in [
+            json!([{ "wait": 500 }, { "decorate": "function(config) { }" }]),
+            json!([{ "shellTransform": "cat" }]),
+            json!([{ "wait": "function() { return 10; }" }]),
+        ] {
+            let config = cfg(json!({
+                "protocol": "http",
+                "stubs": [{
+                    "responses": [{ "is": { "statusCode": 200 }, "_behaviors": behaviors }]
+                }]
+            }));
+            assert!(
+                reject_if_injection_disallowed(&config, false).is_some(),
+                "{behaviors} runs a script — the injection gate must reject it"
+            );
+        }
+    }
+
+    // Imposter-level `defaultBehaviors` run on every response, so a scripted default is gated the
+    // same as one written on a stub; a plain default wait stays admissible.
+    #[test]
+    fn scripted_default_behaviors_are_gated() {
+        let config = cfg(json!({
+            "protocol": "http",
+            "defaultBehaviors": { "wait": 50, "decorate": "function(config) { }" },
+            "stubs": [{ "responses": [{ "is": { "statusCode": 200 } }] }]
+        }));
+        assert!(reject_if_injection_disallowed(&config, false).is_some());
+        let config = cfg(json!({
+            "protocol": "http",
+            "defaultBehaviors": { "wait": 50 },
+            "stubs": [{ "responses": [{ "is": { "statusCode": 200 } }] }]
+        }));
+        assert!(reject_if_injection_disallowed(&config, false).is_none());
+    }
+
     // AC 610-5: guard against over-closing — numeric waits carry no script surface and must stay
     // admissible with injection off.
     #[test]
@@ -1135,6 +1507,7 @@ mod requests_filter_tests {
             headers,
             body: None,
             timestamp: "2026-01-01T00:00:00Z".to_string(),
+            tls: None,
         }
     }
 
@@ -1901,6 +2274,7 @@ mod verify_tests {
             headers: HashMap::new(),
             body: None,
             timestamp: "2026-01-01T00:00:00Z".to_string(),
+            tls: None,
         }
     }
 
@@ -2014,8 +2388,15 @@ mod replace_all_tests {
             {"port": 19761, "protocol": "https", "cert": "not a pem", "key": "not a pem", "stubs": []}
         ]})
         .to_string();
-        let resp =
-            replace_all_from_bytes(body.as_bytes(), BASE, Arc::clone(&manager), false, None).await;
+        let resp = replace_all_from_bytes(
+            body.as_bytes(),
+            None,
+            BASE,
+            Arc::clone(&manager),
+            false,
+            None,
+        )
+        .await;
 
         assert!(
             !resp.status().is_success(),
@@ -2053,8 +2434,15 @@ mod replace_all_tests {
         let body =
             serde_json::json!({"imposters": [{"port": 19763, "protocol": "http", "stubs": []}]})
                 .to_string();
-        let resp =
-            replace_all_from_bytes(body.as_bytes(), BASE, Arc::clone(&manager), false, None).await;
+        let resp = replace_all_from_bytes(
+            body.as_bytes(),
+            None,
+            BASE,
+            Arc::clone(&manager),
+            false,
+            None,
+        )
+        .await;
 
         assert_eq!(resp.status(), StatusCode::OK);
         let json = body_json(resp).await;
@@ -2091,11 +2479,19 @@ mod replace_all_tests {
                 headers: std::collections::HashMap::new(),
                 body: None,
                 timestamp: "2026-01-01T00:00:00Z".to_string(),
+                tls: None,
             });
 
         let body = serde_json::json!({"imposters": [cfg]}).to_string();
-        let resp =
-            replace_all_from_bytes(body.as_bytes(), BASE, Arc::clone(&manager), false, None).await;
+        let resp = replace_all_from_bytes(
+            body.as_bytes(),
+            None,
+            BASE,
+            Arc::clone(&manager),
+            false,
+            None,
+        )
+        .await;
         assert_eq!(resp.status(), StatusCode::OK);
 
         let recorded = manager
@@ -2110,6 +2506,47 @@ mod replace_all_tests {
         manager.delete_all().await;
     }
 
+    // Mountebank parity: a body without `imposters` is the empty set, so an orchestration script's
+    // bare `PUT /imposters` clears everything, and the list reply takes GET's query options.
+    #[tokio::test]
+    async fn put_without_imposters_clears_the_set() {
+        let manager = manager_with_http(19767).await;
+        for body in ["", "{}"] {
+            let resp = replace_all_from_bytes(
+                body.as_bytes(),
+                None,
+                BASE,
+                Arc::clone(&manager),
+                false,
+                None,
+            )
+            .await;
+            assert_eq!(resp.status(), StatusCode::OK, "body {body:?}");
+            assert_eq!(body_json(resp).await["imposters"], serde_json::json!([]));
+        }
+        assert!(manager.get_imposter(19767).is_err());
+
+        let body = serde_json::json!({"imposters": [{"port": 19768, "protocol": "http", "stubs": [
+            {"responses": [{"is": {"body": "replayed"}}]}
+        ]}]})
+        .to_string();
+        let resp = replace_all_from_bytes(
+            body.as_bytes(),
+            Some("replayable=true"),
+            BASE,
+            Arc::clone(&manager),
+            false,
+            None,
+        )
+        .await;
+        let json = body_json(resp).await;
+        assert_eq!(
+            json["imposters"][0]["stubs"][0]["responses"][0]["is"]["body"], "replayed",
+            "replayable returns the full configs: {json}"
+        );
+        manager.delete_all().await;
+    }
+
     // Issue #549: a set that fails validation (duplicate port) must be rejected as a client error
     // with the running imposters completely untouched — under the old wholesale pre-delete they
     // were already gone by the time the creates started failing.
@@ -2121,8 +2558,15 @@ mod replace_all_tests {
             {"port": 19765, "protocol": "http", "stubs": []}
         ]})
         .to_string();
-        let resp =
-            replace_all_from_bytes(body.as_bytes(), BASE, Arc::clone(&manager), false, None).await;
+        let resp = replace_all_from_bytes(
+            body.as_bytes(),
+            None,
+            BASE,
+            Arc::clone(&manager),
+            false,
+            None,
+        )
+        .await;
 
         assert_eq!(
             resp.status(),
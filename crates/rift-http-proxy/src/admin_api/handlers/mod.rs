@@ -1,8 +1,13 @@
 //! Request handlers for the Admin API.
 
+pub mod drain;
 pub mod events;
 pub mod imposters;
 pub mod intercept;
+pub mod readiness;
+pub mod recording;
 pub mod scenarios;
+pub mod scripts;
 pub mod stubs;
 pub mod system;
+pub mod templates;
@@ -0,0 +1,208 @@
+//! `GET /readyz`: whether the server can serve its mocks, for Kubernetes readiness probes. Each
+//! check is reported by name, and any failure turns the answer into a `503`. Liveness
+//! (`GET /healthz`) deliberately checks nothing: a Redis outage should take the pod out of
+//! rotation, not get it restarted.
+
+use crate::admin_api::handlers::system::SystemInfo;
+use crate::admin_api::types::json_response;
+use crate::imposter::ImposterManager;
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::{Response, StatusCode};
+use serde_json::{Value, json};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long a single dependency check may take before it counts as failed.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The file `/readyz` writes and removes to prove the datadir is writable.
+const DATADIR_PROBE: &str = ".rift-readyz";
+
+/// GET /readyz - Readiness with per-check detail
+pub async fn handle_readyz(
+    manager: Arc<ImposterManager>,
+    info: &SystemInfo,
+) -> Response<Full<Bytes>> {
+    let mut checks = serde_json::Map::new();
+    checks.insert(
+        "imposters".to_string(),
+        check_imposters(&manager, &info.failed_imposters),
+    );
+    if let Some(check) = check_flow_stores(&manager).await {
+        checks.insert("flowStore".to_string(), check);
+    }
+    if let Some(datadir) = manager.datadir() {
+        checks.insert(
+            "datadir".to_string(),
+            check_datadir(datadir.to_path_buf()).await,
+        );
+    }
+
+    let ready = checks.values().all(|check| check["status"] == "ok");
+    let (status, label) = if ready {
+        (StatusCode::OK, "ready")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "not ready")
+    };
+    json_response(status, &json!({ "status": label, "checks": checks }))
+}
+
+fn failed(message: String) -> Value {
+    json!({ "status": "failed", "message": message })
+}
+
+fn port_list(ports: &[u16]) -> String {
+    ports
+        .iter()
+        .map(u16::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Every imposter is taking connections, and every imposter the startup config declared but
+/// could not create has since been brought up.
+fn check_imposters(manager: &ImposterManager, failed_at_startup: &[u16]) -> Value {
+    let imposters = manager.list_imposters();
+    let stopped: Vec<u16> = imposters
+        .iter()
+        .filter(|imposter| !imposter.is_serving())
+        .filter_map(|imposter| imposter.config.port)
+        .collect();
+    let missing: Vec<u16> = failed_at_startup
+        .iter()
+        .copied()
+        .filter(|&port| {
+            !manager
+                .get_imposter(port)
+                .is_ok_and(|imposter| imposter.is_serving())
+        })
+        .collect();
+
+    let mut problems = Vec::new();
+    if !stopped.is_empty() {
+        problems.push(format!(
+            "not accepting connections on port(s) {}",
+            port_list(&stopped)
+        ));
+    }
+    if !missing.is_empty() {
+        problems.push(format!(
+            "configured imposter(s) failed to start on port(s) {}",
+            port_list(&missing)
+        ));
+    }
+    if problems.is_empty() {
+        json!({ "status": "ok", "serving": imposters.len() })
+    } else {
+        failed(problems.join("; "))
+    }
+}
+
+/// Ping every flow store backed by an external service (Redis), concurrently. `None` when no
+/// imposter uses one.
+async fn check_flow_stores(manager: &ImposterManager) -> Option<Value> {
+    let probes: Vec<_> = manager
+        .list_imposters()
+        .into_iter()
+        .filter(|imposter| imposter.flow_store.is_blocking())
+        .map(|imposter| async move {
+            let port = imposter.config.port.unwrap_or_default();
+            let store = Arc::clone(&imposter.flow_store);
+            let ping = tokio::task::spawn_blocking(move || store.ping());
+            match tokio::time::timeout(CHECK_TIMEOUT, ping).await {
+                Ok(Ok(Ok(()))) => None,
+                Ok(Ok(Err(e))) => Some(format!("port {port}: {e:#}")),
+                Ok(Err(e)) => Some(format!("port {port}: {e}")),
+                Err(_) => Some(format!(
+                    "port {port}: no answer within {}s",
+                    CHECK_TIMEOUT.as_secs()
+                )),
+            }
+        })
+        .collect();
+    if probes.is_empty() {
+        return None;
+    }
+    let stores = probes.len();
+    let errors: Vec<String> = futures::future::join_all(probes)
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+    Some(if errors.is_empty() {
+        json!({ "status": "ok", "stores": stores })
+    } else {
+        failed(errors.join("; "))
+    })
+}
+
+/// Write and remove a probe file, so a read-only or full volume shows up before a persist fails.
+async fn check_datadir(datadir: PathBuf) -> Value {
+    let probe = datadir.join(DATADIR_PROBE);
+    let write = tokio::task::spawn_blocking(move || {
+        std::fs::write(&probe, b"ok")?;
+        std::fs::remove_file(&probe)
+    });
+    match tokio::time::timeout(CHECK_TIMEOUT, write).await {
+        Ok(Ok(Ok(()))) => json!({ "status": "ok" }),
+        Ok(Ok(Err(e))) => failed(format!("cannot write to {}: {e}", datadir.display())),
+        Ok(Err(e)) => failed(format!("cannot write to {}: {e}", datadir.display())),
+        Err(_) => failed(format!(
+            "no write to {} completed within {}s",
+            datadir.display(),
+            CHECK_TIMEOUT.as_secs()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_body_util::BodyExt;
+
+    fn info(failed_imposters: Vec<u16>) -> SystemInfo {
+        SystemInfo {
+            port: 2525,
+            started: std::time::Instant::now(),
+            options: serde_json::Map::new(),
+            logs: None,
+            failed_imposters,
+        }
+    }
+
+    async fn body(resp: Response<Full<Bytes>>) -> Value {
+        serde_json::from_slice(&resp.into_body().collect().await.unwrap().to_bytes()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn an_imposter_that_failed_to_start_keeps_the_server_unready() {
+        let manager = Arc::new(ImposterManager::new());
+        let resp = handle_readyz(Arc::clone(&manager), &info(vec![])).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(body(resp).await["checks"]["imposters"]["serving"], 0);
+
+        let resp = handle_readyz(manager, &info(vec![4545])).await;
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = body(resp).await;
+        assert_eq!(body["status"], "not ready");
+        assert!(
+            body["checks"]["imposters"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("4545")
+        );
+    }
+
+    #[tokio::test]
+    async fn an_unwritable_datadir_fails_its_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let check = check_datadir(dir.path().to_path_buf()).await;
+        assert_eq!(check["status"], "ok");
+        assert!(!dir.path().join(DATADIR_PROBE).exists());
+
+        let check = check_datadir(dir.path().join("missing")).await;
+        assert_eq!(check["status"], "failed");
+    }
+}
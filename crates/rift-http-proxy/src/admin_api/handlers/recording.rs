@@ -0,0 +1,113 @@
+//! Recording-session admin handlers: start, pause and stop proxy recording under a session name,
+//! and export or discard a session's generated stubs as a unit.
+//!
+//! Every handler answers with the imposter's recording status (`state`, current `session`, and
+//! each session's `stubCount`), except the session export, which returns the stubs themselves.
+
+use crate::admin_api::types::{collect_body, error_response, json_response};
+use crate::imposter::ImposterManager;
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::body::Incoming;
+use hyper::{Request, Response, StatusCode};
+use std::sync::Arc;
+
+fn unknown_session(name: &str) -> Response<Full<Bytes>> {
+    error_response(
+        StatusCode::NOT_FOUND,
+        &format!("No recording session named '{name}'"),
+    )
+}
+
+/// GET /imposters/:port/_recording → `{state, session?, sessions:[{name, stubCount}]}`
+pub async fn handle_status(port: u16, manager: Arc<ImposterManager>) -> Response<Full<Bytes>> {
+    match manager.get_imposter(port) {
+        Ok(imposter) => json_response(StatusCode::OK, &imposter.recording_status()),
+        Err(e) => e.into(),
+    }
+}
+
+/// POST /imposters/:port/_recording/start — body `{"name":"…"}`. Starting a paused or stopped
+/// session resumes it.
+pub async fn handle_start(
+    port: u16,
+    req: Request<Incoming>,
+    manager: Arc<ImposterManager>,
+) -> Response<Full<Bytes>> {
+    let body = match collect_body(req).await {
+        Ok(b) => b,
+        Err(e) => return error_response(e.status_code(), &e.to_string()),
+    };
+    let payload: serde_json::Value = match serde_json::from_slice(&body) {
+        Ok(v) => v,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &format!("Invalid JSON: {e}")),
+    };
+    let Some(name) = payload
+        .get("name")
+        .and_then(|v| v.as_str())
+        .filter(|name| !name.is_empty())
+    else {
+        return error_response(StatusCode::BAD_REQUEST, "missing required field: name");
+    };
+    match manager.get_imposter(port) {
+        Ok(imposter) => json_response(StatusCode::OK, &imposter.start_recording_session(name)),
+        Err(e) => e.into(),
+    }
+}
+
+/// POST /imposters/:port/_recording/pause — 409 when no session is current.
+pub async fn handle_pause(port: u16, manager: Arc<ImposterManager>) -> Response<Full<Bytes>> {
+    match manager.get_imposter(port) {
+        Ok(imposter) => match imposter.pause_recording_session() {
+            Some(status) => json_response(StatusCode::OK, &status),
+            None => error_response(StatusCode::CONFLICT, "No recording session to pause"),
+        },
+        Err(e) => e.into(),
+    }
+}
+
+/// POST /imposters/:port/_recording/stop — 409 when no session is current.
+pub async fn handle_stop(port: u16, manager: Arc<ImposterManager>) -> Response<Full<Bytes>> {
+    match manager.get_imposter(port) {
+        Ok(imposter) => match imposter.stop_recording_session() {
+            Some(status) => json_response(StatusCode::OK, &status),
+            None => error_response(StatusCode::CONFLICT, "No recording session to stop"),
+        },
+        Err(e) => e.into(),
+    }
+}
+
+/// GET /imposters/:port/_recording/sessions/:name → `{name, stubs}`, the stubs the session
+/// generated in imposter order — ready to `PUT` onto another imposter's `/stubs`.
+pub async fn handle_export_session(
+    port: u16,
+    name: &str,
+    manager: Arc<ImposterManager>,
+) -> Response<Full<Bytes>> {
+    match manager.get_imposter(port) {
+        Ok(imposter) => match imposter.recording_session_stubs(name) {
+            Some(stubs) => json_response(
+                StatusCode::OK,
+                &serde_json::json!({ "name": name, "stubs": stubs }),
+            ),
+            None => unknown_session(name),
+        },
+        Err(e) => e.into(),
+    }
+}
+
+/// DELETE /imposters/:port/_recording/sessions/:name — remove the session and every stub it
+/// generated. Discarding the current session stops recording.
+pub async fn handle_discard_session(
+    port: u16,
+    name: &str,
+    manager: Arc<ImposterManager>,
+) -> Response<Full<Bytes>> {
+    match manager.get_imposter(port) {
+        Ok(imposter) => match imposter.discard_recording_session(name) {
+            Some(_) => json_response(StatusCode::OK, &imposter.recording_status()),
+            None => unknown_session(name),
+        },
+        Err(e) => e.into(),
+    }
+}
@@ -0,0 +1,513 @@
+//! Script handlers: check or run a stub's `_rift.script`, `inject` or `decorate` body against a
+//! sample request without adding it to an imposter — the admin API counterpart of `rift script
+//! check`/`run`, for editors such as rift-tui.
+//!
+//! Both endpoints compile (and `run` executes) caller-supplied code, so they sit behind
+//! `--allowInjection` like every other scripting surface (issue #355).
+
+use crate::admin_api::handlers::imposters::injection_disallowed_response;
+use crate::admin_api::types::{collect_body, error_response, json_response};
+use crate::behaviors::{RequestContext, is_js_config_decorate};
+use crate::extensions::template::StubMetadata;
+use crate::script_cli::{RequestFixture, fixture_to_script_request};
+use crate::scripting::ScriptValidator;
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::body::Incoming;
+use hyper::{Request, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Imposter state slot a test `inject` runs with, so it never reads or writes a real imposter's
+/// state; it is cleared around each run.
+const SCRIPT_RUN_STATE_PORT: u16 = 0;
+
+/// Flow id a test `_rift.script` run uses unless the body names one
+const SCRIPT_RUN_FLOW_ID: &str = "script-run";
+
+/// Where in a stub the script comes from, which decides how it is called
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ScriptKind {
+    /// A `_rift.script` `respond` script
+    #[default]
+    Script,
+    /// A Mountebank `inject` response function
+    Inject,
+    /// A Mountebank `decorate` behavior
+    Decorate,
+}
+
+/// A response as a script sees or produces it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct SampleResponse {
+    status_code: u16,
+    headers: HashMap<String, String>,
+    body: String,
+}
+
+impl Default for SampleResponse {
+    fn default() -> Self {
+        Self {
+            status_code: 200,
+            headers: HashMap::new(),
+            body: String::new(),
+        }
+    }
+}
+
+/// `POST /_scripts/check` and `POST /_scripts/run` body
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ScriptBody {
+    #[serde(default)]
+    kind: ScriptKind,
+    /// `_rift.script` engine (`rhai`/`javascript`); `rhai` when omitted
+    engine: Option<String>,
+    code: String,
+    /// The request to run against, in `rift script run --request` fixture form
+    #[serde(default)]
+    request: RequestFixture,
+    /// The response a `decorate` script is given
+    #[serde(default)]
+    response: SampleResponse,
+    /// Flow state seeded before a `_rift.script` run
+    #[serde(default)]
+    state: serde_json::Map<String, serde_json::Value>,
+    flow_id: Option<String>,
+}
+
+impl ScriptBody {
+    /// The engine that runs the script: a `decorate` is JavaScript when it looks like a function or
+    /// uses the `config` convention, and Rhai otherwise — the same routing the decorate runner does
+    fn engine(&self) -> &str {
+        match self.kind {
+            ScriptKind::Script => self.engine.as_deref().unwrap_or("rhai"),
+            ScriptKind::Inject => "javascript",
+            ScriptKind::Decorate => {
+                if self.code.trim_start().starts_with("function")
+                    || is_js_config_decorate(&self.code)
+                {
+                    "javascript"
+                } else {
+                    "rhai"
+                }
+            }
+        }
+    }
+
+    /// Everything wrong with the script that can be found without running it
+    fn check(&self) -> Vec<String> {
+        let error = match self.kind {
+            ScriptKind::Script => {
+                crate::scripting::check_entrypoint(self.engine(), &self.code, "respond")
+                    .err()
+                    .map(|e| e.to_string())
+            }
+            ScriptKind::Inject => check_js_function(&self.code),
+            ScriptKind::Decorate if self.engine() == "rhai" => {
+                crate::scripting::RhaiValidator::new()
+                    .validate(&self.code)
+                    .err()
+                    .map(|e| e.to_string())
+            }
+            // Without the JS engine a JS decorate runs through its textual Rhai rewrite, which has
+            // nothing to check up front
+            ScriptKind::Decorate if !cfg!(feature = "javascript") => None,
+            ScriptKind::Decorate => check_js_function(&decorate_function(&self.code)),
+        };
+        error.into_iter().collect()
+    }
+}
+
+/// A bare `config` decorate body wrapped into the function the decorate runner would call
+fn decorate_function(code: &str) -> String {
+    let trimmed = code.trim_start();
+    let function_value = [
+        "function",
+        "config =>",
+        "config=>",
+        "(config) =>",
+        "(config)=>",
+    ]
+    .iter()
+    .any(|prefix| trimmed.starts_with(prefix));
+    if function_value || !is_js_config_decorate(code) {
+        code.to_string()
+    } else {
+        format!("function(config) {{ {code} }}")
+    }
+}
+
+/// Syntax-check a JavaScript function expression the way it is compiled at request time
+#[cfg(feature = "javascript")]
+fn check_js_function(code: &str) -> Option<String> {
+    crate::scripting::JsValidator::validate_static(&format!("var __fn = ({code});"))
+        .err()
+        .map(|e| format!("Syntax error: {e}"))
+}
+
+#[cfg(not(feature = "javascript"))]
+fn check_js_function(_code: &str) -> Option<String> {
+    Some("JavaScript engine is not enabled (requires the 'javascript' feature)".to_string())
+}
+
+/// What running a script produced
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RunOutcome {
+    engine: String,
+    /// A `_rift.script`'s rendered decision (`pass()`, `http(503) ...`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    decision: Option<String>,
+    /// The response an `inject` built or a `decorate` left
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response: Option<SampleResponse>,
+    duration_ms: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    logs: Vec<String>,
+    /// A `_rift.script`'s flow state after the run
+    #[serde(skip_serializing_if = "serde_json::Map::is_empty")]
+    state: serde_json::Map<String, serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// POST /_scripts/check → `{engine, valid, errors}`
+pub async fn handle_check(req: Request<Incoming>, allow_injection: bool) -> Response<Full<Bytes>> {
+    let body = match collect_body(req).await {
+        Ok(b) => b,
+        Err(e) => return error_response(e.status_code(), &e.to_string()),
+    };
+    check_response(&body, allow_injection)
+}
+
+/// The body of [`handle_check`] over already-collected bytes
+fn check_response(body: &[u8], allow_injection: bool) -> Response<Full<Bytes>> {
+    if !allow_injection {
+        return injection_disallowed_response();
+    }
+    let script: ScriptBody = match serde_json::from_slice(body) {
+        Ok(script) => script,
+        Err(e) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("Invalid script JSON: {e}"),
+            );
+        }
+    };
+    let errors = script.check();
+    json_response(
+        StatusCode::OK,
+        &serde_json::json!({
+            "engine": script.engine(),
+            "valid": errors.is_empty(),
+            "errors": errors,
+        }),
+    )
+}
+
+/// POST /_scripts/run → what the script decided or responded. A script that fails its check or
+/// errors while running is still `200`, with `error` saying why.
+pub async fn handle_run(req: Request<Incoming>, allow_injection: bool) -> Response<Full<Bytes>> {
+    let body = match collect_body(req).await {
+        Ok(b) => b,
+        Err(e) => return error_response(e.status_code(), &e.to_string()),
+    };
+    run_response(&body, allow_injection).await
+}
+
+/// The body of [`handle_run`] over already-collected bytes
+async fn run_response(body: &[u8], allow_injection: bool) -> Response<Full<Bytes>> {
+    if !allow_injection {
+        return injection_disallowed_response();
+    }
+    let script: ScriptBody = match serde_json::from_slice(body) {
+        Ok(script) => script,
+        Err(e) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("Invalid script JSON: {e}"),
+            );
+        }
+    };
+    json_response(StatusCode::OK, &run(script).await)
+}
+
+async fn run(script: ScriptBody) -> RunOutcome {
+    let mut outcome = RunOutcome {
+        engine: script.engine().to_string(),
+        ..Default::default()
+    };
+    let errors = script.check();
+    if !errors.is_empty() {
+        outcome.error = Some(errors.join("; "));
+        return outcome;
+    }
+    let timeout = Duration::from_millis(crate::scripting::DEFAULT_SCRIPT_TIMEOUT_MS);
+    match script.kind {
+        ScriptKind::Script => {
+            let flow_id = script.flow_id.as_deref().unwrap_or(SCRIPT_RUN_FLOW_ID);
+            let result = crate::script_cli::run_script(
+                outcome.engine.clone(),
+                script.code,
+                fixture_to_script_request(script.request),
+                script.state.into_iter().collect(),
+                flow_id,
+            )
+            .await;
+            match result {
+                Ok(report) => {
+                    outcome.decision = Some(report.decision);
+                    outcome.duration_ms = report.duration_ms;
+                    outcome.logs = report.logs;
+                    outcome.state = report.state.into_iter().collect();
+                    outcome.error = report.error;
+                }
+                Err(e) => outcome.error = Some(e.to_string()),
+            }
+        }
+        ScriptKind::Inject => {
+            let started = Instant::now();
+            let result = run_inject(script.code, &script.request, timeout).await;
+            outcome.duration_ms = elapsed_ms(started);
+            match result {
+                Ok(response) => outcome.response = Some(response),
+                Err(e) => outcome.error = Some(e),
+            }
+        }
+        ScriptKind::Decorate => {
+            let started = Instant::now();
+            let request = &script.request;
+            let result = crate::imposter::apply_decorate_bounded(
+                script.code,
+                RequestContext {
+                    method: request.method.clone().unwrap_or_else(|| "GET".to_string()),
+                    path: request.path.clone().unwrap_or_else(|| "/".to_string()),
+                    query: request.query.clone(),
+                    headers: request.headers.clone(),
+                    body: fixture_body(request),
+                },
+                script.response.body,
+                script.response.status_code,
+                script.response.headers,
+                StubMetadata::default(),
+                timeout,
+                crate::scripting::ScriptLimits::default(),
+            )
+            .await;
+            outcome.duration_ms = elapsed_ms(started);
+            match result {
+                Ok((body, status_code, headers)) => {
+                    outcome.response = Some(SampleResponse {
+                        status_code,
+                        headers,
+                        body,
+                    })
+                }
+                Err(e) => outcome.error = Some(e.to_string()),
+            }
+        }
+    }
+    outcome
+}
+
+/// A fixture's body as the raw text an `inject`/`decorate` script reads: a JSON string as is,
+/// anything else serialized
+fn fixture_body(request: &RequestFixture) -> Option<String> {
+    match &request.body {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(text) => Some(text.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(feature = "javascript")]
+async fn run_inject(
+    code: String,
+    request: &RequestFixture,
+    timeout: Duration,
+) -> Result<SampleResponse, String> {
+    let mb_request = crate::scripting::MountebankRequest {
+        method: request.method.clone().unwrap_or_else(|| "GET".to_string()),
+        path: request.path.clone().unwrap_or_else(|| "/".to_string()),
+        query: request.query.clone(),
+        headers: request.headers.clone(),
+        body: fixture_body(request),
+        mode: Some(crate::imposter::ResponseMode::Text),
+    };
+    crate::scripting::clear_imposter_state(SCRIPT_RUN_STATE_PORT);
+    let result = crate::scripting::execute_mountebank_inject_bounded(
+        code,
+        mb_request,
+        SCRIPT_RUN_STATE_PORT,
+        None,
+        timeout,
+    )
+    .await;
+    crate::scripting::clear_imposter_state(SCRIPT_RUN_STATE_PORT);
+    result
+        .map(|response| SampleResponse {
+            status_code: response.status_code,
+            headers: response.headers,
+            body: response.body,
+        })
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "javascript"))]
+async fn run_inject(
+    _code: String,
+    _request: &RequestFixture,
+    _timeout: Duration,
+) -> Result<SampleResponse, String> {
+    Err("JavaScript engine is not enabled (requires the 'javascript' feature)".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_body_util::BodyExt;
+
+    async fn body_json(resp: Response<Full<Bytes>>) -> serde_json::Value {
+        let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn scripts_need_allow_injection() {
+        let body = br#"{"code": "fn respond(ctx) { pass() }"}"#;
+        assert_eq!(
+            check_response(body, false).status(),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            run_response(body, false).await.status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[tokio::test]
+    async fn check_reports_the_engine_and_what_is_wrong() {
+        let ok = body_json(check_response(
+            br#"{"code": "fn respond(ctx) { pass() }"}"#,
+            true,
+        ))
+        .await;
+        assert_eq!(ok["engine"], "rhai");
+        assert_eq!(ok["valid"], true);
+
+        let misnamed = body_json(check_response(
+            br#"{"code": "fn respnod(ctx) { pass() }"}"#,
+            true,
+        ))
+        .await;
+        assert_eq!(misnamed["valid"], false);
+        assert!(
+            misnamed["errors"][0].as_str().unwrap().contains("respond"),
+            "{misnamed}"
+        );
+
+        let lua = body_json(check_response(
+            br#"{"engine": "lua", "code": "return 1"}"#,
+            true,
+        ))
+        .await;
+        assert!(
+            lua["errors"][0].as_str().unwrap().contains("removed"),
+            "{lua}"
+        );
+
+        let decorate = body_json(check_response(
+            br#"{"kind": "decorate", "code": "response.body = "}"#,
+            true,
+        ))
+        .await;
+        assert_eq!(decorate["engine"], "rhai");
+        assert_eq!(decorate["valid"], false);
+    }
+
+    #[tokio::test]
+    async fn run_renders_a_script_decision_with_its_state() {
+        let outcome = body_json(
+            run_response(
+                br#"{
+                    "code": "fn respond(ctx) { if ctx.request.path == \"/down\" { http(503, \"down\") } else { pass() } }",
+                    "request": {"path": "/down"},
+                    "state": {"seen": 1}
+                }"#,
+                true,
+            )
+            .await,
+        )
+        .await;
+        assert!(
+            outcome["decision"]
+                .as_str()
+                .unwrap()
+                .starts_with("http(503)"),
+            "{outcome}"
+        );
+        assert_eq!(outcome["state"]["seen"], 1);
+        assert!(outcome.get("error").is_none(), "{outcome}");
+    }
+
+    #[tokio::test]
+    async fn run_applies_a_decorate_to_the_sample_response() {
+        let outcome = body_json(
+            run_response(
+                br#"{
+                    "kind": "decorate",
+                    "code": "response.statusCode = 201;",
+                    "response": {"statusCode": 200, "body": "hi"}
+                }"#,
+                true,
+            )
+            .await,
+        )
+        .await;
+        assert_eq!(outcome["response"]["statusCode"], 201, "{outcome}");
+        assert_eq!(outcome["response"]["body"], "hi");
+    }
+
+    #[cfg(feature = "javascript")]
+    #[tokio::test]
+    async fn run_builds_an_inject_response_from_the_request() {
+        let outcome = body_json(
+            run_response(
+                br#"{
+                    "kind": "inject",
+                    "code": "function (config) { return { statusCode: 202, body: config.request.path }; }",
+                    "request": {"method": "POST", "path": "/orders"}
+                }"#,
+                true,
+            )
+            .await,
+        )
+        .await;
+        assert_eq!(outcome["response"]["statusCode"], 202, "{outcome}");
+        assert_eq!(outcome["response"]["body"], "/orders");
+
+        let broken = body_json(
+            run_response(
+                br#"{"kind": "inject", "code": "function (config) {"}"#,
+                true,
+            )
+            .await,
+        )
+        .await;
+        assert!(
+            broken["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("Syntax error"),
+            "{broken}"
+        );
+    }
+}
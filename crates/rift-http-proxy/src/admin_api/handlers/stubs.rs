@@ -5,8 +5,8 @@ use crate::admin_api::handlers::imposters::{
     admin_script_base, imposter_script_registry, reject_stubs_if_injection_disallowed,
 };
 use crate::admin_api::types::{
-    AddStubRequest, ReplaceStubsRequest, StubWithLinks, collect_body, error_response,
-    json_response, make_stub_links,
+    AddStubRequest, MoveStubRequest, ReplaceStubsRequest, StubBatchRequest, StubOperationRequest,
+    StubWithLinks, collect_body, error_response, json_response, make_stub_links,
 };
 use crate::extensions::stub_analysis::analyze_new_stub;
 use crate::imposter::{ImposterManager, Stub, resolve_stub_scripts};
@@ -54,13 +54,33 @@ pub async fn handle_add(
         Err(e) => return error_response(e.status_code(), &e.to_string()),
     };
 
-    let mut add_req: AddStubRequest = match serde_json::from_slice(&body) {
+    let add_req: AddStubRequest = match serde_json::from_slice(&body) {
         Ok(r) => r,
         Err(e) => {
             return error_response(StatusCode::BAD_REQUEST, &format!("Invalid stub JSON: {e}"));
         }
     };
+    add_stub(
+        port,
+        add_req,
+        base_url,
+        manager,
+        allow_injection,
+        scripts_dir,
+    )
+    .await
+}
 
+/// The checks and insertion behind `POST /imposters/:port/stubs`, shared with the gRPC
+/// `AddStub`: the injection gate, script resolution and validation, then the imposter.
+pub async fn add_stub(
+    port: u16,
+    mut add_req: AddStubRequest,
+    base_url: &str,
+    manager: Arc<ImposterManager>,
+    allow_injection: bool,
+    scripts_dir: Option<Arc<PathBuf>>,
+) -> Response<Full<Bytes>> {
     // Gate any scripting surface behind --allowInjection before mutating state (B3, issue #355).
     if let Some(rejection) =
         reject_stubs_if_injection_disallowed(std::slice::from_ref(&add_req.stub), allow_injection)
@@ -234,7 +254,8 @@ pub async fn handle_get(
     }
 }
 
-/// PUT /imposters/:port/stubs/:index - Replace a specific stub
+/// PUT /imposters/:port/stubs/:index - Replace a specific stub, or move it when the body is
+/// `{"newIndex": n}`
 pub async fn handle_replace(
     port: u16,
     index: usize,
@@ -249,13 +270,57 @@ pub async fn handle_replace(
         Err(e) => return error_response(e.status_code(), &e.to_string()),
     };
 
-    let mut stub: Stub = match serde_json::from_slice(&body) {
+    let body: serde_json::Value = match serde_json::from_slice(&body) {
+        Ok(v) => v,
+        Err(e) => {
+            return error_response(StatusCode::BAD_REQUEST, &format!("Invalid stub JSON: {e}"));
+        }
+    };
+    // `newIndex` is not a stub field, so its presence makes this a move. The stub keeps its
+    // response-cycling state, and matching never sees the list without it, which a delete and
+    // re-add would expose to live traffic.
+    if body.get("newIndex").is_some() {
+        return match serde_json::from_value::<MoveStubRequest>(body) {
+            Ok(mv) => match manager.move_stub(port, index, mv.new_index).await {
+                Ok(()) => handle_get_imposter(port, None, base_url, manager).await,
+                Err(e) => e.into(),
+            },
+            Err(e) => error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("Invalid move request (expected only `newIndex`): {e}"),
+            ),
+        };
+    }
+
+    let stub: Stub = match serde_json::from_value(body) {
         Ok(s) => s,
         Err(e) => {
             return error_response(StatusCode::BAD_REQUEST, &format!("Invalid stub JSON: {e}"));
         }
     };
+    replace_stub(
+        port,
+        index,
+        stub,
+        base_url,
+        manager,
+        allow_injection,
+        scripts_dir,
+    )
+    .await
+}
 
+/// The checks and replacement behind `PUT /imposters/:port/stubs/:index`, shared with the gRPC
+/// `ReplaceStub`.
+pub async fn replace_stub(
+    port: u16,
+    index: usize,
+    mut stub: Stub,
+    base_url: &str,
+    manager: Arc<ImposterManager>,
+    allow_injection: bool,
+    scripts_dir: Option<Arc<PathBuf>>,
+) -> Response<Full<Bytes>> {
     // Gate any scripting surface behind --allowInjection before mutating state (B3, issue #355).
     if let Some(rejection) =
         reject_stubs_if_injection_disallowed(std::slice::from_ref(&stub), allow_injection)
@@ -381,3 +446,75 @@ pub async fn handle_delete_by_id(
         Err(e) => e.into(),
     }
 }
+
+/// POST /imposters/:port/stubs/_batch — apply a list of add/replace/delete/move operations as one
+/// atomic change, answering with one result per operation. Every stub in the batch passes the same
+/// injection gate, script resolution and validation as a single-stub write before anything is
+/// applied; if any operation then fails, none of them is.
+pub async fn handle_batch(
+    port: u16,
+    req: Request<Incoming>,
+    manager: Arc<ImposterManager>,
+    allow_injection: bool,
+    scripts_dir: Option<Arc<PathBuf>>,
+) -> Response<Full<Bytes>> {
+    let body = match collect_body(req).await {
+        Ok(b) => b,
+        Err(e) => return error_response(e.status_code(), &e.to_string()),
+    };
+    let batch: StubBatchRequest = match serde_json::from_slice(&body) {
+        Ok(b) => b,
+        Err(e) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("Invalid stub batch JSON: {e}"),
+            );
+        }
+    };
+
+    let mut operations = Vec::with_capacity(batch.operations.len());
+    for (position, mut op) in batch.operations.into_iter().enumerate() {
+        if let Some(stub) = op.stub_mut() {
+            if let Some(rejection) =
+                reject_stubs_if_injection_disallowed(std::slice::from_ref(stub), allow_injection)
+            {
+                return rejection;
+            }
+            if let Some(rejection) =
+                resolve_admin_stubs(std::slice::from_mut(stub), &manager, port, &scripts_dir)
+            {
+                return rejection;
+            }
+            let validation_result = validate_stub(stub, position);
+            if !validation_result.is_valid() {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    &format!(
+                        "Script validation failed: {}",
+                        validation_result.into_error_message().unwrap_or_default()
+                    ),
+                );
+            }
+        }
+        // Added stubs get a stable id like `POST /stubs` gives them (issue #202).
+        if let StubOperationRequest::Add { stub, .. } = &mut op
+            && stub.id.is_none()
+        {
+            stub.id = Some(uuid::Uuid::new_v4().to_string());
+        }
+        match op.into_operation() {
+            Ok(operation) => operations.push(operation),
+            Err(e) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    &format!("Stub operation {position} is invalid: {e}"),
+                );
+            }
+        }
+    }
+
+    match manager.apply_stub_operations(port, operations).await {
+        Ok(results) => json_response(StatusCode::OK, &serde_json::json!({ "results": results })),
+        Err(e) => e.into(),
+    }
+}
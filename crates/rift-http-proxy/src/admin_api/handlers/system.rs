@@ -1,13 +1,15 @@
 //! System handlers: health, metrics, config, logs.
 
+use crate::admin_api::logs::LogBuffer;
 use crate::admin_api::types::*;
-use crate::imposter::ImposterManager;
+use crate::imposter::{ImposterManager, LATENCY_BUCKETS_MS, TrafficStats};
 use crate::response::ErrorKind;
 use bytes::Bytes;
 use http_body_util::Full;
 use hyper::{Response, StatusCode};
 use std::sync::Arc;
-use tracing::warn;
+use std::time::Instant;
+use tracing::{info, warn};
 
 /// GET / - Root endpoint (Mountebank-compatible format)
 pub fn handle_root(base_url: &str) -> Response<Full<Bytes>> {
@@ -47,6 +49,85 @@ pub async fn handle_metrics(manager: Arc<ImposterManager>) -> Response<Full<Byte
         }
     }
 
+    // Each family's samples must follow its own HELP/TYPE lines, so take every imposter's
+    // totals first and write one family at a time.
+    let stats: Vec<(u16, TrafficStats)> = imposters
+        .iter()
+        .filter_map(|imposter| Some((imposter.config.port?, imposter.traffic_stats())))
+        .collect();
+
+    metrics.push_str(
+        "# HELP rift_imposter_request_duration_ms Time to answer imposter requests in milliseconds\n",
+    );
+    metrics.push_str("# TYPE rift_imposter_request_duration_ms histogram\n");
+    for (port, stats) in &stats {
+        let mut cumulative = 0;
+        for (i, count) in stats.latency_buckets.iter().enumerate() {
+            cumulative += count;
+            let le = LATENCY_BUCKETS_MS
+                .get(i)
+                .map_or_else(|| "+Inf".to_string(), u64::to_string);
+            metrics.push_str(&format!(
+                "rift_imposter_request_duration_ms_bucket{{port=\"{port}\",le=\"{le}\"}} {cumulative}\n"
+            ));
+        }
+        metrics.push_str(&format!(
+            "rift_imposter_request_duration_ms_sum{{port=\"{port}\"}} {}\n",
+            stats.latency_sum_ms
+        ));
+        metrics.push_str(&format!(
+            "rift_imposter_request_duration_ms_count{{port=\"{port}\"}} {}\n",
+            stats.count
+        ));
+    }
+
+    metrics
+        .push_str("# HELP rift_imposter_responses_total Responses per imposter by status code\n");
+    metrics.push_str("# TYPE rift_imposter_responses_total counter\n");
+    for (port, stats) in &stats {
+        for (status, count) in &stats.status_codes {
+            metrics.push_str(&format!(
+                "rift_imposter_responses_total{{port=\"{port}\",status=\"{status}\"}} {count}\n"
+            ));
+        }
+    }
+
+    metrics.push_str("# HELP rift_imposter_faults_total Faults injected per imposter by kind\n");
+    metrics.push_str("# TYPE rift_imposter_faults_total counter\n");
+    for (port, stats) in &stats {
+        for (fault, count) in &stats.faults {
+            metrics.push_str(&format!(
+                "rift_imposter_faults_total{{port=\"{port}\",fault=\"{}\"}} {count}\n",
+                escape_label(fault)
+            ));
+        }
+    }
+
+    metrics.push_str("# HELP rift_imposter_stub_hits_total Requests answered per stub\n");
+    metrics.push_str("# TYPE rift_imposter_stub_hits_total counter\n");
+    for (port, stats) in &stats {
+        for (index, (id, count)) in &stats.stub_hits {
+            let id = id
+                .as_deref()
+                .map(|id| format!(",stub_id=\"{}\"", escape_label(id)))
+                .unwrap_or_default();
+            metrics.push_str(&format!(
+                "rift_imposter_stub_hits_total{{port=\"{port}\",stub=\"{index}\"{id}}} {count}\n"
+            ));
+        }
+    }
+
+    metrics.push_str(
+        "# HELP rift_imposter_unmatched_requests_total Requests per imposter no stub matched\n",
+    );
+    metrics.push_str("# TYPE rift_imposter_unmatched_requests_total counter\n");
+    for (port, stats) in &stats {
+        metrics.push_str(&format!(
+            "rift_imposter_unmatched_requests_total{{port=\"{port}\"}} {}\n",
+            stats.unmatched
+        ));
+    }
+
     Response::builder()
         .status(StatusCode::OK)
         .header("Content-Type", "text/plain; version=0.0.4")
@@ -54,30 +135,67 @@ pub async fn handle_metrics(manager: Arc<ImposterManager>) -> Response<Full<Byte
         .unwrap()
 }
 
+/// A Prometheus label value with its backslashes, quotes and newlines escaped.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// What `GET /config` and `GET /logs` report about the running server beyond the imposters.
+pub struct SystemInfo {
+    /// The admin API's bound port.
+    pub port: u16,
+    /// When the admin API started; `process.uptime` counts from here.
+    pub started: Instant,
+    /// Startup options to report under `options`, overriding the defaults (e.g. from the CLI).
+    pub options: serde_json::Map<String, serde_json::Value>,
+    /// Recent log entries for `GET /logs`; without one the log is always empty.
+    pub logs: Option<LogBuffer>,
+    /// Ports the startup config declared whose imposters could not be created; `GET /readyz`
+    /// stays not ready until each is served.
+    pub failed_imposters: Vec<u16>,
+}
+
 /// GET /config - Mountebank-compatible config endpoint
 ///
 /// `allow_injection` is threaded in explicitly (issue #342) rather than read from
 /// `MB_ALLOW_INJECTION`, so an embedded host can set it without mutating process env.
-pub fn handle_config(allow_injection: bool) -> Response<Full<Bytes>> {
+pub fn handle_config(allow_injection: bool, info: &SystemInfo) -> Response<Full<Bytes>> {
+    let mut options = serde_json::json!({
+        "port": info.port,
+        "allowInjection": allow_injection,
+        "localOnly": false,
+        "ipWhitelist": ["*"]
+    });
+    if let Some(map) = options.as_object_mut() {
+        for (key, value) in &info.options {
+            map.insert(key.clone(), value.clone());
+        }
+        // The live gate wins over whatever the startup options said.
+        map.insert("allowInjection".to_string(), allow_injection.into());
+    }
     let config = serde_json::json!({
         "version": env!("CARGO_PKG_VERSION"),
         // Build identity (issue #344), stamped by build.rs — the same value rift_build_info
         // reports over FFI, so one version-coherence preflight works for process and FFI modes.
         "commit": option_env!("RIFT_COMMIT"),
-        "options": {
-            "port": crate::admin_api::DEFAULT_ADMIN_PORT,
-            "allowInjection": allow_injection,
-            "localOnly": false,
-            "ipWhitelist": ["*"]
+        // Optional parts compiled into this build, so a client (e.g. `rift-lint --against`) can
+        // tell whether a config relying on them will work here.
+        "features": {
+            "javascript": cfg!(feature = "javascript"),
+            "redisBackend": cfg!(feature = "redis-backend"),
         },
+        "options": options,
         "process": {
             "nodeVersion": "N/A (Rust)",
             "architecture": std::env::consts::ARCH,
             "platform": std::env::consts::OS,
-            "rss": 0,
+            "rss": resident_set_bytes(),
             "heapTotal": 0,
             "heapUsed": 0,
-            "uptime": 0,
+            "uptime": info.started.elapsed().as_secs_f64(),
             "cwd": std::env::current_dir()
                 .map(|p| p.display().to_string())
                 .unwrap_or_default()
@@ -86,10 +204,33 @@ pub fn handle_config(allow_injection: bool) -> Response<Full<Bytes>> {
     json_response(StatusCode::OK, &config)
 }
 
+/// Resident set size in bytes, as Node's `process.memoryUsage().rss`; 0 where `/proc` is absent.
+fn resident_set_bytes() -> u64 {
+    std::fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| {
+            status
+                .lines()
+                .find_map(|line| line.strip_prefix("VmRSS:"))
+                .and_then(|value| {
+                    value
+                        .trim()
+                        .trim_end_matches("kB")
+                        .trim()
+                        .parse::<u64>()
+                        .ok()
+                })
+        })
+        .map_or(0, |kib| kib * 1024)
+}
+
 /// GET /logs - Mountebank-compatible logs endpoint
-pub fn handle_logs(query: Option<&str>) -> Response<Full<Bytes>> {
+///
+/// `startIndex`/`endIndex` select an inclusive range of entries, counted from the first entry the
+/// process logged; `endIndex` defaults to the newest entry.
+pub fn handle_logs(query: Option<&str>, logs: Option<&LogBuffer>) -> Response<Full<Bytes>> {
     let mut start_index = 0;
-    let mut end_index = 100;
+    let mut end_index = None;
 
     if let Some(q) = query {
         for param in q.split('&') {
@@ -102,7 +243,7 @@ pub fn handle_logs(query: Option<&str>) -> Response<Full<Bytes>> {
                     }
                     "endIndex" => {
                         if let Ok(v) = value.parse::<usize>() {
-                            end_index = v;
+                            end_index = Some(v);
                         }
                     }
                     _ => {}
@@ -111,15 +252,32 @@ pub fn handle_logs(query: Option<&str>) -> Response<Full<Bytes>> {
         }
     }
 
+    let page = logs.map(|logs| logs.page(start_index, end_index));
+    let entries = page.as_ref().map_or(&[][..], |page| &page.entries[..]);
+    let href = match end_index {
+        Some(end) => format!("/logs?startIndex={start_index}&endIndex={end}"),
+        None => format!("/logs?startIndex={start_index}"),
+    };
     let logs = serde_json::json!({
-        "logs": [],
+        "logs": entries,
         "_links": {
             "self": {
-                "href": format!("/logs?startIndex={}&endIndex={}", start_index, end_index)
+                "href": href
             }
         }
     });
-    json_response(StatusCode::OK, &logs)
+    // Without a buffer there is nothing to resume from, so no cursor is advertised
+    let Some(page) = page else {
+        return json_response(StatusCode::OK, &logs);
+    };
+    let mut headers = vec![
+        ("Content-Type".to_string(), "application/json".to_string()),
+        ("x-rift-next-index".to_string(), page.next_index.to_string()),
+    ];
+    if page.truncated {
+        headers.push(("x-rift-truncated".to_string(), "true".to_string()));
+    }
+    build_response_with_headers(StatusCode::OK, headers, logs.to_string())
 }
 
 /// True when a reload's configs carry a scripting surface that `--allowInjection` has not
@@ -200,6 +358,10 @@ pub async fn handle_reload(
     let count = configs.len();
     match manager.apply_config(configs).await {
         Ok(report) if report.failed.is_empty() => {
+            info!(
+                "Reloaded {count} imposter(s): created {:?}, replaced {:?}, stub-patched {:?}, deleted {:?}",
+                report.created, report.replaced, report.stub_patched, report.deleted
+            );
             let mut body = serde_json::json!({
                 "message": format!("Reloaded {count} imposter(s)"),
                 "created": report.created,
@@ -257,6 +419,26 @@ pub async fn handle_reload(
 mod tests {
     use super::*;
 
+    fn info() -> SystemInfo {
+        SystemInfo {
+            port: 2525,
+            started: Instant::now(),
+            options: serde_json::Map::new(),
+            logs: None,
+            failed_imposters: Vec::new(),
+        }
+    }
+
+    fn body_json(resp: Response<Full<Bytes>>) -> serde_json::Value {
+        use http_body_util::BodyExt;
+        let bytes = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(resp.into_body().collect())
+            .unwrap()
+            .to_bytes();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
     #[test]
     fn test_handle_root() {
         let resp = handle_root("http://localhost:2525");
@@ -271,7 +453,7 @@ mod tests {
 
     #[test]
     fn test_handle_config() {
-        let resp = handle_config(false);
+        let resp = handle_config(false, &info());
         assert_eq!(resp.status(), StatusCode::OK);
     }
 
@@ -280,7 +462,7 @@ mod tests {
     #[test]
     fn handle_config_reports_commit() {
         use http_body_util::BodyExt;
-        let resp = handle_config(false);
+        let resp = handle_config(false, &info());
         let bytes = tokio::runtime::Runtime::new()
             .unwrap()
             .block_on(resp.into_body().collect())
@@ -307,13 +489,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn handle_config_reports_build_features() {
+        use http_body_util::BodyExt;
+        let resp = handle_config(false, &info());
+        let bytes = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(resp.into_body().collect())
+            .unwrap()
+            .to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            json["features"]["javascript"].as_bool(),
+            Some(cfg!(feature = "javascript"))
+        );
+        assert_eq!(
+            json["features"]["redisBackend"].as_bool(),
+            Some(cfg!(feature = "redis-backend"))
+        );
+    }
+
     // AC7 (#342): the injection flag is threaded explicitly, not read from process env —
     // an embedder sets it without mutating the environment.
     #[test]
     fn handle_config_reports_explicit_injection_flag() {
         use http_body_util::BodyExt;
         let read_flag = |allow: bool| {
-            let resp = handle_config(allow);
+            let resp = handle_config(allow, &info());
             let bytes = tokio::runtime::Runtime::new()
                 .unwrap()
                 .block_on(resp.into_body().collect())
@@ -439,16 +641,69 @@ mod tests {
 
     #[test]
     fn test_handle_logs_no_query() {
-        let resp = handle_logs(None);
+        let resp = handle_logs(None, None);
         assert_eq!(resp.status(), StatusCode::OK);
     }
 
     #[test]
     fn test_handle_logs_with_pagination() {
-        let resp = handle_logs(Some("startIndex=10&endIndex=50"));
+        let resp = handle_logs(Some("startIndex=10&endIndex=50"), None);
         assert_eq!(resp.status(), StatusCode::OK);
     }
 
+    #[test]
+    fn handle_config_reports_options_and_process() {
+        let mut info = info();
+        info.port = 2626;
+        info.options
+            .insert("loglevel".to_string(), serde_json::json!("debug"));
+        info.options
+            .insert("allowInjection".to_string(), serde_json::json!(true));
+        let json = body_json(handle_config(false, &info));
+        assert_eq!(json["options"]["port"], 2626);
+        assert_eq!(json["options"]["loglevel"], "debug");
+        assert_eq!(
+            json["options"]["allowInjection"], false,
+            "the live injection gate wins"
+        );
+        assert!(json["process"]["uptime"].as_f64().unwrap() >= 0.0);
+        if cfg!(target_os = "linux") {
+            assert!(json["process"]["rss"].as_u64().unwrap() > 0);
+        }
+    }
+
+    #[test]
+    fn handle_logs_pages_the_buffer() {
+        let logs = LogBuffer::default();
+        for message in ["first", "second", "third"] {
+            logs.push(crate::admin_api::logs::LogEntry {
+                level: "info".to_string(),
+                message: message.to_string(),
+                timestamp: "2026-01-01T00:00:00.000Z".to_string(),
+            });
+        }
+        let all = body_json(handle_logs(None, Some(&logs)));
+        assert_eq!(all["logs"].as_array().unwrap().len(), 3);
+        assert_eq!(all["logs"][0]["level"], "info");
+        let page = body_json(handle_logs(Some("startIndex=1&endIndex=1"), Some(&logs)));
+        assert_eq!(page["logs"].as_array().unwrap().len(), 1);
+        assert_eq!(page["logs"][0]["message"], "second");
+        assert_eq!(
+            page["_links"]["self"]["href"],
+            "/logs?startIndex=1&endIndex=1"
+        );
+
+        let resp = handle_logs(Some("startIndex=1"), Some(&logs));
+        assert_eq!(resp.headers()["x-rift-next-index"], "3");
+        assert!(resp.headers().get("x-rift-truncated").is_none());
+        assert!(
+            handle_logs(None, None)
+                .headers()
+                .get("x-rift-next-index")
+                .is_none()
+        );
+    }
+
     // ===== Issue #612: POST /admin/reload is the third config door and must gate too =====
 
     /// Reload from `path`, then read back whether port 19481's stub carries a decorate behavior.
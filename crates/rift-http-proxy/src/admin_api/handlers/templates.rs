@@ -0,0 +1,115 @@
+//! Imposter template handlers: register parameterized imposter definitions and create imposters
+//! from them.
+//!
+//! A template is created through the same checks as `POST /imposters` once its `${var}`
+//! placeholders are filled in, so a template cannot smuggle past the injection gate.
+
+use crate::admin_api::handlers::imposters::create_imposter;
+use crate::admin_api::types::{collect_body, error_response, json_response};
+use crate::imposter::{ImposterManager, ImposterTemplate};
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::body::Incoming;
+use hyper::{Request, Response, StatusCode};
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// `POST /imposters/from-template/:name` body; an empty body uses the template's defaults.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InstantiateRequest {
+    #[serde(default)]
+    variables: serde_json::Map<String, serde_json::Value>,
+}
+
+/// GET /_templates → `{"templates": [...]}`
+pub async fn handle_list(manager: Arc<ImposterManager>) -> Response<Full<Bytes>> {
+    json_response(
+        StatusCode::OK,
+        &serde_json::json!({ "templates": manager.list_templates() }),
+    )
+}
+
+/// POST /_templates — register a template, replacing one of the same name. `201` when new, `200`
+/// when replaced.
+pub async fn handle_put(
+    req: Request<Incoming>,
+    manager: Arc<ImposterManager>,
+) -> Response<Full<Bytes>> {
+    let body = match collect_body(req).await {
+        Ok(b) => b,
+        Err(e) => return error_response(e.status_code(), &e.to_string()),
+    };
+    let template: ImposterTemplate = match serde_json::from_slice(&body) {
+        Ok(t) => t,
+        Err(e) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("Invalid template JSON: {e}"),
+            );
+        }
+    };
+    match manager.put_template(template.clone()) {
+        Ok(replaced) => {
+            let status = if replaced {
+                StatusCode::OK
+            } else {
+                StatusCode::CREATED
+            };
+            json_response(status, &template)
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// GET /_templates/:name
+pub async fn handle_get(name: &str, manager: Arc<ImposterManager>) -> Response<Full<Bytes>> {
+    match manager.get_template(name) {
+        Ok(template) => json_response(StatusCode::OK, &*template),
+        Err(e) => e.into(),
+    }
+}
+
+/// DELETE /_templates/:name → the removed template
+pub async fn handle_delete(name: &str, manager: Arc<ImposterManager>) -> Response<Full<Bytes>> {
+    match manager.delete_template(name) {
+        Ok(template) => json_response(StatusCode::OK, &*template),
+        Err(e) => e.into(),
+    }
+}
+
+/// POST /imposters/from-template/:name — optional body `{"variables": {...}}`. Answers like
+/// `POST /imposters`.
+pub async fn handle_instantiate(
+    name: &str,
+    req: Request<Incoming>,
+    base_url: &str,
+    manager: Arc<ImposterManager>,
+    allow_injection: bool,
+    scripts_dir: Option<Arc<PathBuf>>,
+) -> Response<Full<Bytes>> {
+    let body = match collect_body(req).await {
+        Ok(b) => b,
+        Err(e) => return error_response(e.status_code(), &e.to_string()),
+    };
+    let request: InstantiateRequest = if body.is_empty() {
+        InstantiateRequest::default()
+    } else {
+        match serde_json::from_slice(&body) {
+            Ok(r) => r,
+            Err(e) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    &format!("Invalid template variables JSON: {e}"),
+                );
+            }
+        }
+    };
+    match manager.render_template(name, &request.variables) {
+        Ok(config) => {
+            create_imposter(config, base_url, manager, allow_injection, scripts_dir).await
+        }
+        Err(e) => e.into(),
+    }
+}
@@ -0,0 +1,236 @@
+//! In-memory log ring buffer behind Mountebank's `GET /logs`.
+//!
+//! [`LogBuffer`] is a `tracing` layer: installed next to the console/file layers it keeps the most
+//! recent events, already filtered by the subscriber's level, in a bounded queue. Entries are
+//! indexed from the first event the process logged, so `startIndex`/`endIndex` paging stays stable
+//! while old entries are evicted; an evicted range is simply empty.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+
+/// Entries kept by [`LogBuffer::default`].
+pub const DEFAULT_LOG_CAPACITY: usize = 1000;
+
+/// One log line, in Mountebank's `GET /logs` shape.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LogEntry {
+    pub level: String,
+    pub message: String,
+    pub timestamp: String,
+}
+
+/// A page of [`LogBuffer::page`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogPage {
+    pub entries: Vec<LogEntry>,
+    /// Index of the entry after the last one returned, to pass as the next `startIndex`.
+    pub next_index: usize,
+    /// Some of the requested entries were evicted before they were read.
+    pub truncated: bool,
+}
+
+/// The most recent log events, shared between the `tracing` layer and the admin API.
+#[derive(Clone)]
+pub struct LogBuffer {
+    inner: Arc<Mutex<Ring>>,
+}
+
+struct Ring {
+    capacity: usize,
+    /// Index of `entries[0]` among every entry ever pushed.
+    first_index: usize,
+    entries: VecDeque<LogEntry>,
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_LOG_CAPACITY)
+    }
+}
+
+impl LogBuffer {
+    /// A buffer keeping the last `capacity` entries (at least one).
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            inner: Arc::new(Mutex::new(Ring {
+                capacity,
+                first_index: 0,
+                entries: VecDeque::with_capacity(capacity),
+            })),
+        }
+    }
+
+    /// Append an entry, evicting the oldest when full.
+    pub fn push(&self, entry: LogEntry) {
+        let mut ring = self.inner.lock().expect("log buffer mutex poisoned");
+        if ring.entries.len() == ring.capacity {
+            ring.entries.pop_front();
+            ring.first_index += 1;
+        }
+        ring.entries.push_back(entry);
+    }
+
+    /// The retained entries with indices `start..=end` (`end` defaults to the newest).
+    pub fn range(&self, start: usize, end: Option<usize>) -> Vec<LogEntry> {
+        self.page(start, end).entries
+    }
+
+    /// [`range`](Self::range), with the index to resume from and whether entries from `start` on
+    /// were already evicted.
+    pub fn page(&self, start: usize, end: Option<usize>) -> LogPage {
+        let ring = self.inner.lock().expect("log buffer mutex poisoned");
+        let last = ring.first_index + ring.entries.len();
+        let end = end.map_or(last, |end| end.saturating_add(1).min(last));
+        let truncated = start < ring.first_index;
+        let start = start.max(ring.first_index);
+        if start >= end {
+            // A start past the newest entry is from before a restart: resume from the newest
+            return LogPage {
+                entries: Vec::new(),
+                next_index: start.min(last),
+                truncated,
+            };
+        }
+        LogPage {
+            entries: ring
+                .entries
+                .range(start - ring.first_index..end - ring.first_index)
+                .cloned()
+                .collect(),
+            next_index: end,
+            truncated,
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogBuffer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut message = MessageVisitor::default();
+        event.record(&mut message);
+        self.push(LogEntry {
+            level: level_name(*event.metadata().level()).to_string(),
+            message: message.finish(),
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        });
+    }
+}
+
+/// Mountebank's level names, which are lower-case.
+fn level_name(level: Level) -> &'static str {
+    match level {
+        Level::ERROR => "error",
+        Level::WARN => "warn",
+        Level::INFO => "info",
+        Level::DEBUG => "debug",
+        Level::TRACE => "trace",
+    }
+}
+
+/// The event's message followed by its other fields as ` key=value`, as the console prints them.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(mut self) -> String {
+        self.message.push_str(&self.fields);
+        self.message
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={value}", field.name());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::prelude::*;
+
+    fn entry(message: &str) -> LogEntry {
+        LogEntry {
+            level: "info".to_string(),
+            message: message.to_string(),
+            timestamp: String::new(),
+        }
+    }
+
+    fn messages(entries: Vec<LogEntry>) -> Vec<String> {
+        entries.into_iter().map(|e| e.message).collect()
+    }
+
+    #[test]
+    fn keeps_the_newest_entries_at_stable_indices() {
+        let buffer = LogBuffer::with_capacity(3);
+        for i in 0..5 {
+            buffer.push(entry(&i.to_string()));
+        }
+        assert_eq!(messages(buffer.range(0, None)), ["2", "3", "4"]);
+        assert_eq!(messages(buffer.range(3, Some(3))), ["3"]);
+        assert_eq!(messages(buffer.range(0, Some(2))), ["2"], "0 and 1 evicted");
+        assert!(buffer.range(5, None).is_empty());
+        assert!(buffer.range(4, Some(3)).is_empty());
+    }
+
+    #[test]
+    fn a_page_says_where_to_resume_and_whether_entries_were_missed() {
+        let buffer = LogBuffer::with_capacity(3);
+        for i in 0..5 {
+            buffer.push(entry(&i.to_string()));
+        }
+        let page = buffer.page(1, None);
+        assert_eq!(messages(page.entries), ["2", "3", "4"]);
+        assert_eq!(page.next_index, 5);
+        assert!(page.truncated, "entry 1 was evicted");
+
+        let page = buffer.page(3, Some(3));
+        assert_eq!((page.next_index, page.truncated), (4, false));
+
+        let caught_up = buffer.page(5, None);
+        assert!(caught_up.entries.is_empty());
+        assert_eq!(caught_up.next_index, 5);
+        assert_eq!(
+            buffer.page(40, None).next_index,
+            5,
+            "cursor from before a restart"
+        );
+    }
+
+    #[test]
+    fn records_events_with_their_level_and_fields() {
+        let buffer = LogBuffer::default();
+        let subscriber = tracing_subscriber::registry().with(buffer.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(port = 4545, "imposter stopped");
+        });
+        let logged = buffer.range(0, None);
+        assert_eq!(logged.len(), 1);
+        assert_eq!(logged[0].level, "warn");
+        assert_eq!(logged[0].message, "imposter stopped port=4545");
+        assert!(logged[0].timestamp.ends_with('Z'));
+    }
+}
@@ -6,15 +6,24 @@
 //! - Clearing recorded requests and proxy responses
 //! - Health and metrics endpoints
 //!
+//! The same port serves the imposter and stub operations over gRPC (see [`grpc`]).
+//!
 //! The API listens on a configurable port (default: 2525).
 
+mod auth;
+pub mod grpc;
 mod handlers;
+mod logs;
 mod request_filter;
 mod router;
 mod server;
 pub mod types;
 
+pub use auth::AdminAuth;
+pub(crate) use handlers::events::traffic_payload;
 pub use handlers::imposters::{filter_proxy_responses, filter_proxy_stubs};
+pub(crate) use handlers::system::handle_reload;
+pub use logs::{DEFAULT_LOG_CAPACITY, LogBuffer, LogEntry, LogPage};
 pub use server::{AdminApiServer, RunningAdminApi};
 
 /// Default port for the Mountebank-compatible admin API.
@@ -166,6 +166,7 @@ mod tests {
             headers,
             body: None,
             timestamp: "2026-01-01T00:00:00Z".to_string(),
+            tls: None,
         }
     }
 
@@ -2,7 +2,10 @@
 //!
 //! This module provides routing
 
-use crate::admin_api::handlers::{imposters, intercept, scenarios, stubs, system};
+use crate::admin_api::handlers::system::SystemInfo;
+use crate::admin_api::handlers::{
+    drain, imposters, intercept, readiness, recording, scenarios, scripts, stubs, system, templates,
+};
 use crate::admin_api::types::{error_response, get_base_url, not_found};
 use crate::config_loader::ConfigSource;
 use crate::imposter::ImposterManager;
@@ -21,6 +24,8 @@ enum ImposterRoute {
     Root,
     /// POST/PUT/GET /imposters/:port/stubs
     Stubs,
+    /// POST /imposters/:port/stubs/_batch
+    StubBatch,
     /// GET/PUT/DELETE /imposters/:port/stubs/:index
     StubByIndex(usize),
     /// GET/PUT/DELETE /imposters/:port/stubs/by-id/:id (issue #202)
@@ -31,10 +36,38 @@ enum ImposterRoute {
     Verify,
     /// DELETE /imposters/:port/savedProxyResponses
     SavedProxyResponses,
+    /// GET /imposters/:port/export
+    Export,
+    /// POST /imposters/:port/import
+    Import,
+    /// GET /imposters/:port/openapi
+    OpenApi,
+    /// GET/DELETE /imposters/:port/fuzzyMatches
+    FuzzyMatches,
+    /// GET/DELETE /imposters/:port/shadowDiffs
+    ShadowDiffs,
+    /// GET/DELETE /imposters/:port/proxiedRequests
+    ProxiedRequests,
+    /// GET/DELETE /imposters/:port/servedRequests
+    ServedRequests,
+    /// GET /imposters/:port/servedRequests/:seq
+    ServedRequest(u64),
+    /// GET /imposters/:port/_recording
+    Recording,
+    /// POST /imposters/:port/_recording/start
+    RecordingStart,
+    /// POST /imposters/:port/_recording/pause
+    RecordingPause,
+    /// POST /imposters/:port/_recording/stop
+    RecordingStop,
+    /// GET/DELETE /imposters/:port/_recording/sessions/:name
+    RecordingSession(String),
     /// POST /imposters/:port/enable
     Enable,
     /// POST /imposters/:port/disable
     Disable,
+    /// GET/POST/DELETE /imposters/:port/_drain
+    Drain,
     /// GET /imposters/:port/scenarios
     Scenarios,
     /// PUT /imposters/:port/scenarios/:name/state
@@ -53,13 +86,30 @@ impl ImposterRoute {
         match segments {
             [] => Some(ImposterRoute::Root),
             ["stubs"] => Some(ImposterRoute::Stubs),
+            ["stubs", "_batch"] => Some(ImposterRoute::StubBatch),
             ["stubs", "by-id", id] => Some(ImposterRoute::StubById((*id).to_string())),
             ["stubs", index_str] => index_str.parse().ok().map(ImposterRoute::StubByIndex),
             ["savedRequests"] | ["requests"] => Some(ImposterRoute::SavedRequests),
             ["verify"] => Some(ImposterRoute::Verify),
             ["savedProxyResponses"] => Some(ImposterRoute::SavedProxyResponses),
+            ["export"] => Some(ImposterRoute::Export),
+            ["import"] => Some(ImposterRoute::Import),
+            ["openapi"] => Some(ImposterRoute::OpenApi),
+            ["fuzzyMatches"] => Some(ImposterRoute::FuzzyMatches),
+            ["shadowDiffs"] => Some(ImposterRoute::ShadowDiffs),
+            ["proxiedRequests"] => Some(ImposterRoute::ProxiedRequests),
+            ["servedRequests"] => Some(ImposterRoute::ServedRequests),
+            ["servedRequests", seq] => seq.parse().ok().map(ImposterRoute::ServedRequest),
+            ["_recording"] => Some(ImposterRoute::Recording),
+            ["_recording", "start"] => Some(ImposterRoute::RecordingStart),
+            ["_recording", "pause"] => Some(ImposterRoute::RecordingPause),
+            ["_recording", "stop"] => Some(ImposterRoute::RecordingStop),
+            ["_recording", "sessions", name] => {
+                Some(ImposterRoute::RecordingSession((*name).to_string()))
+            }
             ["enable"] => Some(ImposterRoute::Enable),
             ["disable"] => Some(ImposterRoute::Disable),
+            ["_drain"] => Some(ImposterRoute::Drain),
             ["scenarios"] => Some(ImposterRoute::Scenarios),
             ["scenarios", "reset"] => Some(ImposterRoute::ScenariosReset),
             ["scenarios", name, "state"] => Some(ImposterRoute::ScenarioState((*name).to_string())),
@@ -79,6 +129,7 @@ pub async fn route_request(
     allow_injection: bool,
     intercept: Option<InterceptControl>,
     scripts_dir: Option<Arc<PathBuf>>,
+    system_info: Arc<SystemInfo>,
 ) -> Result<Response<Full<Bytes>>, hyper::Error> {
     let method = req.method().clone();
     let path = req.uri().path().to_string();
@@ -119,6 +170,7 @@ pub async fn route_request(
         config_source,
         allow_injection,
         scripts_dir,
+        &system_info,
     )
     .await;
     Ok(response)
@@ -149,6 +201,7 @@ async fn route_by_path(
     config_source: Option<Arc<ConfigSource>>,
     allow_injection: bool,
     scripts_dir: Option<Arc<PathBuf>>,
+    system_info: &SystemInfo,
 ) -> Response<Full<Bytes>> {
     // Single-port gateway (issue #212): `/__rift/:port/<path>` dispatches to that imposter,
     // so a containerized Rift only needs the one admin port published.
@@ -159,9 +212,10 @@ async fn route_by_path(
     // Fast path for common routes
     match (method, path) {
         (&Method::GET, "/") => return system::handle_root(base_url),
-        (&Method::GET, "/health") => return system::handle_health(),
-        (&Method::GET, "/config") => return system::handle_config(allow_injection),
-        (&Method::GET, "/logs") => return system::handle_logs(query),
+        (&Method::GET, "/health" | "/healthz") => return system::handle_health(),
+        (&Method::GET, "/readyz") => return readiness::handle_readyz(manager, system_info).await,
+        (&Method::GET, "/config") => return system::handle_config(allow_injection, system_info),
+        (&Method::GET, "/logs") => return system::handle_logs(query, system_info.logs.as_ref()),
         (&Method::POST, "/admin/reload") => {
             return system::handle_reload(manager, config_source, allow_injection).await;
         }
@@ -177,19 +231,70 @@ async fn route_by_path(
                 imposters::handle_create(req, base_url, manager, allow_injection, scripts_dir).await
             }
             Method::PUT => {
-                imposters::handle_replace_all(req, base_url, manager, allow_injection, scripts_dir)
-                    .await
+                imposters::handle_replace_all(
+                    req,
+                    query,
+                    base_url,
+                    manager,
+                    allow_injection,
+                    scripts_dir,
+                )
+                .await
             }
             Method::DELETE => imposters::handle_delete_all(manager, base_url).await,
             _ => not_found(),
         };
     }
 
+    // Imposter templates: /_templates[/:name]
+    if path == "/_templates" {
+        return match *method {
+            Method::GET => templates::handle_list(manager).await,
+            Method::POST => templates::handle_put(req, manager).await,
+            _ => not_found(),
+        };
+    }
+    if let Some(name) = path.strip_prefix("/_templates/") {
+        return match *method {
+            Method::GET => templates::handle_get(name, manager).await,
+            Method::DELETE => templates::handle_delete(name, manager).await,
+            _ => not_found(),
+        };
+    }
+
+    // Script check/run against a sample request: /_scripts/check, /_scripts/run
+    if let Some(action) = path.strip_prefix("/_scripts/") {
+        return match (method, action) {
+            (&Method::POST, "check") => scripts::handle_check(req, allow_injection).await,
+            (&Method::POST, "run") => scripts::handle_run(req, allow_injection).await,
+            _ => not_found(),
+        };
+    }
+
     // Admin flow-state inspection routes: /admin/imposters/:port/flow-state/:flow_id[/:key]
     if let Some(rest) = path.strip_prefix("/admin/imposters/") {
         return route_admin_flow_state(method, rest, req, manager).await;
     }
 
+    // POST /imposters/from-template/:name — matched before the `:port` routes it would
+    // otherwise fail to parse as.
+    if let Some(name) = path.strip_prefix("/imposters/from-template/") {
+        return match *method {
+            Method::POST => {
+                templates::handle_instantiate(
+                    name,
+                    req,
+                    base_url,
+                    manager,
+                    allow_injection,
+                    scripts_dir,
+                )
+                .await
+            }
+            _ => not_found(),
+        };
+    }
+
     // Individual imposter routes
     if let Some(rest) = path.strip_prefix("/imposters/") {
         return route_imposter(
@@ -299,6 +404,11 @@ async fn route_imposter(
                 .await
         }
 
+        // /imposters/:port/stubs/_batch
+        (&Method::POST, ImposterRoute::StubBatch) => {
+            stubs::handle_batch(port, req, manager, allow_injection, scripts_dir).await
+        }
+
         // /imposters/:port/stubs/:index
         (&Method::GET, ImposterRoute::StubByIndex(index)) => {
             stubs::handle_get(port, index, base_url, manager).await
@@ -357,9 +467,75 @@ async fn route_imposter(
             imposters::handle_clear_proxy_responses(port, base_url, manager).await
         }
 
+        // /imposters/:port/export?format=har
+        (&Method::GET, ImposterRoute::Export) => {
+            imposters::handle_export(port, query, manager).await
+        }
+
+        // /imposters/:port/import?format=har
+        (&Method::POST, ImposterRoute::Import) => {
+            imposters::handle_import(port, query, req, base_url, manager).await
+        }
+
+        // /imposters/:port/openapi
+        (&Method::GET, ImposterRoute::OpenApi) => imposters::handle_openapi(port, manager).await,
+
+        // /imposters/:port/fuzzyMatches — requests served by closestMatch, with their diffs
+        (&Method::GET, ImposterRoute::FuzzyMatches) => {
+            imposters::handle_get_fuzzy_matches(port, manager).await
+        }
+        (&Method::DELETE, ImposterRoute::FuzzyMatches) => {
+            imposters::handle_clear_fuzzy_matches(port, manager).await
+        }
+        // /imposters/:port/shadowDiffs — proxyShadow responses that drifted from their stubs
+        (&Method::GET, ImposterRoute::ShadowDiffs) => {
+            imposters::handle_get_shadow_diffs(port, manager).await
+        }
+        (&Method::DELETE, ImposterRoute::ShadowDiffs) => {
+            imposters::handle_clear_shadow_diffs(port, manager).await
+        }
+        // /imposters/:port/proxiedRequests — calls the imposter's proxies forwarded upstream
+        (&Method::GET, ImposterRoute::ProxiedRequests) => {
+            imposters::handle_get_proxied_requests(port, manager).await
+        }
+        (&Method::DELETE, ImposterRoute::ProxiedRequests) => {
+            imposters::handle_clear_proxied_requests(port, manager).await
+        }
+        // /imposters/:port/servedRequests — every request answered, with its outcome
+        (&Method::GET, ImposterRoute::ServedRequests) => {
+            imposters::handle_get_served_requests(port, query, manager).await
+        }
+        (&Method::DELETE, ImposterRoute::ServedRequests) => {
+            imposters::handle_clear_served_requests(port, manager).await
+        }
+        (&Method::GET, ImposterRoute::ServedRequest(seq)) => {
+            imposters::handle_get_served_request(port, seq, manager).await
+        }
+
+        // /imposters/:port/_recording — named recording sessions
+        (&Method::GET, ImposterRoute::Recording) => recording::handle_status(port, manager).await,
+        (&Method::POST, ImposterRoute::RecordingStart) => {
+            recording::handle_start(port, req, manager).await
+        }
+        (&Method::POST, ImposterRoute::RecordingPause) => {
+            recording::handle_pause(port, manager).await
+        }
+        (&Method::POST, ImposterRoute::RecordingStop) => {
+            recording::handle_stop(port, manager).await
+        }
+        (&Method::GET, ImposterRoute::RecordingSession(name)) => {
+            recording::handle_export_session(port, &name, manager).await
+        }
+        (&Method::DELETE, ImposterRoute::RecordingSession(name)) => {
+            recording::handle_discard_session(port, &name, manager).await
+        }
+
         // /imposters/:port/enable, /imposters/:port/disable
         (&Method::POST, ImposterRoute::Enable) => imposters::handle_enable(port, manager).await,
         (&Method::POST, ImposterRoute::Disable) => imposters::handle_disable(port, manager).await,
+        (&Method::GET, ImposterRoute::Drain) => drain::handle_status(port, manager).await,
+        (&Method::POST, ImposterRoute::Drain) => drain::handle_start(port, req, manager).await,
+        (&Method::DELETE, ImposterRoute::Drain) => drain::handle_stop(port, manager).await,
 
         // /imposters/:port/scenarios — declarative FSM state inspection/arrangement
         (&Method::GET, ImposterRoute::Scenarios) => {
@@ -420,6 +596,10 @@ mod tests {
             ImposterRoute::parse(&["stubs", "5"]),
             Some(ImposterRoute::StubByIndex(5))
         ));
+        assert!(matches!(
+            ImposterRoute::parse(&["stubs", "_batch"]),
+            Some(ImposterRoute::StubBatch)
+        ));
         assert!(matches!(
             ImposterRoute::parse(&["savedRequests"]),
             Some(ImposterRoute::SavedRequests)
@@ -432,6 +612,51 @@ mod tests {
             ImposterRoute::parse(&["savedProxyResponses"]),
             Some(ImposterRoute::SavedProxyResponses)
         ));
+        assert!(matches!(
+            ImposterRoute::parse(&["export"]),
+            Some(ImposterRoute::Export)
+        ));
+        assert!(matches!(
+            ImposterRoute::parse(&["import"]),
+            Some(ImposterRoute::Import)
+        ));
+        assert!(matches!(
+            ImposterRoute::parse(&["openapi"]),
+            Some(ImposterRoute::OpenApi)
+        ));
+        assert!(matches!(
+            ImposterRoute::parse(&["fuzzyMatches"]),
+            Some(ImposterRoute::FuzzyMatches)
+        ));
+        assert!(matches!(
+            ImposterRoute::parse(&["shadowDiffs"]),
+            Some(ImposterRoute::ShadowDiffs)
+        ));
+        assert!(matches!(
+            ImposterRoute::parse(&["proxiedRequests"]),
+            Some(ImposterRoute::ProxiedRequests)
+        ));
+        assert!(matches!(
+            ImposterRoute::parse(&["servedRequests"]),
+            Some(ImposterRoute::ServedRequests)
+        ));
+        assert!(matches!(
+            ImposterRoute::parse(&["servedRequests", "42"]),
+            Some(ImposterRoute::ServedRequest(42))
+        ));
+        assert!(ImposterRoute::parse(&["servedRequests", "latest"]).is_none());
+        assert!(matches!(
+            ImposterRoute::parse(&["_recording"]),
+            Some(ImposterRoute::Recording)
+        ));
+        assert!(matches!(
+            ImposterRoute::parse(&["_recording", "start"]),
+            Some(ImposterRoute::RecordingStart)
+        ));
+        assert!(matches!(
+            ImposterRoute::parse(&["_recording", "sessions", "checkout"]),
+            Some(ImposterRoute::RecordingSession(name)) if name == "checkout"
+        ));
         assert!(matches!(
             ImposterRoute::parse(&["enable"]),
             Some(ImposterRoute::Enable)
@@ -440,6 +665,10 @@ mod tests {
             ImposterRoute::parse(&["disable"]),
             Some(ImposterRoute::Disable)
         ));
+        assert!(matches!(
+            ImposterRoute::parse(&["_drain"]),
+            Some(ImposterRoute::Drain)
+        ));
 
         // Invalid routes
         assert!(ImposterRoute::parse(&["unknown"]).is_none());
@@ -1,7 +1,12 @@
 //! Admin API server.
 
+use crate::admin_api::auth::{AdminAuth, AdminRole, is_mutation};
+use crate::admin_api::grpc;
 use crate::admin_api::handlers::events::{self, AdminBody};
+use crate::admin_api::handlers::system::SystemInfo;
+use crate::admin_api::logs::LogBuffer;
 use crate::admin_api::router::route_request;
+use crate::cluster::{Cluster, REPLICATED_HEADER};
 use crate::config_loader::ConfigSource;
 use crate::extensions::decorate::{ResponsePhase, with_annotation_scope};
 use crate::imposter::ImposterManager;
@@ -19,7 +24,6 @@ use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use subtle::ConstantTimeEq;
 use tokio::net::TcpListener;
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
@@ -33,27 +37,53 @@ const SHUTDOWN_GRACE: Duration = Duration::from_millis(500);
 pub struct AdminApiServer {
     addr: SocketAddr,
     manager: Arc<ImposterManager>,
-    api_key: Option<Arc<String>>,
+    auth: Option<Arc<AdminAuth>>,
+    read_only: bool,
     config_source: Option<Arc<ConfigSource>>,
     allow_injection: bool,
     intercept: Option<InterceptControl>,
     scripts_dir: Option<Arc<PathBuf>>,
+    logs: Option<LogBuffer>,
+    reported_options: serde_json::Map<String, serde_json::Value>,
+    cluster: Option<Cluster>,
+    failed_imposters: Vec<u16>,
 }
 
 impl AdminApiServer {
-    /// Create a new admin API server
+    /// Create a new admin API server, requiring `api_key` on every request when one is given
     pub fn new(addr: SocketAddr, manager: Arc<ImposterManager>, api_key: Option<String>) -> Self {
         Self {
             addr,
             manager,
-            api_key: api_key.map(Arc::new),
+            auth: api_key.map(|key| Arc::new(AdminAuth::default().with_api_key(key))),
+            read_only: false,
             config_source: None,
             allow_injection: false,
             intercept: None,
             scripts_dir: None,
+            logs: None,
+            reported_options: serde_json::Map::new(),
+            cluster: None,
+            failed_imposters: Vec::new(),
         }
     }
 
+    /// Replace the accepted credentials: several API keys and/or basic-auth pairs (`--api-key`,
+    /// `--admin-basic-auth`). An empty [`AdminAuth`] turns authentication off.
+    #[must_use]
+    pub fn with_auth(mut self, auth: AdminAuth) -> Self {
+        self.auth = auth.is_enabled().then(|| Arc::new(auth));
+        self
+    }
+
+    /// Refuse every mutation with `403`, whatever the caller's credentials (`--readonly`), so a
+    /// shared environment can be inspected but not changed.
+    #[must_use]
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
     /// Set the config source (`--configfile`/`--datadir`) so `POST /admin/reload` can re-read it
     /// (issue #197). Without it, reload is a no-op.
     #[must_use]
@@ -90,6 +120,41 @@ impl AdminApiServer {
         self
     }
 
+    /// Serve `GET /logs` from `logs`, a buffer also installed as a `tracing` layer. Without it the
+    /// log is always empty.
+    #[must_use]
+    pub fn with_log_buffer(mut self, logs: LogBuffer) -> Self {
+        self.logs = Some(logs);
+        self
+    }
+
+    /// Startup options `GET /config` reports under `options` (Mountebank's names, e.g.
+    /// `loglevel`, `datadir`), on top of the bound port and the injection flag it always reports.
+    #[must_use]
+    pub fn with_reported_options(
+        mut self,
+        options: serde_json::Map<String, serde_json::Value>,
+    ) -> Self {
+        self.reported_options = options;
+        self
+    }
+
+    /// Replicate imposter mutations to the cluster's peers and aggregate their request counts into
+    /// `GET /imposters` (`--peer`). Requests from a peer are served as on a standalone node.
+    #[must_use]
+    pub fn with_cluster(mut self, cluster: Cluster) -> Self {
+        self.cluster = Some(cluster);
+        self
+    }
+
+    /// Ports the startup config declared whose imposters could not be created. `GET /readyz`
+    /// reports the server not ready until each of them is served.
+    #[must_use]
+    pub fn with_failed_imposters(mut self, ports: Vec<u16>) -> Self {
+        self.failed_imposters = ports;
+        self
+    }
+
     /// Bind the listener (`:0` is fine) and start serving on the current runtime, returning a
     /// handle that reports the bound address and can be shut down gracefully (issue #342).
     pub async fn bind(self) -> anyhow::Result<RunningAdminApi> {
@@ -100,9 +165,19 @@ impl AdminApiServer {
             local_addr
         );
 
-        if self.api_key.is_some() {
-            info!("Admin API authentication enabled (--apikey)");
+        if self.auth.is_some() {
+            info!("Admin API authentication enabled");
         }
+        if self.read_only {
+            info!("Admin API is read-only (--readonly)");
+        }
+        let system_info = Arc::new(SystemInfo {
+            port: local_addr.port(),
+            started: std::time::Instant::now(),
+            options: self.reported_options,
+            logs: self.logs,
+            failed_imposters: self.failed_imposters,
+        });
 
         let cancel = CancellationToken::new();
         let tracker = TaskTracker::new();
@@ -127,11 +202,14 @@ impl AdminApiServer {
             let result = accept_loop(
                 listener,
                 self.manager,
-                self.api_key,
+                self.auth,
+                self.read_only,
                 self.config_source,
                 self.allow_injection,
                 self.intercept,
                 self.scripts_dir,
+                self.cluster,
+                system_info,
                 loop_cancel,
                 loop_tracker,
             )
@@ -336,11 +414,14 @@ fn take_task<T>(slot: &Mutex<Option<JoinHandle<T>>>) -> Option<JoinHandle<T>> {
 async fn accept_loop(
     listener: TcpListener,
     manager: Arc<ImposterManager>,
-    api_key: Option<Arc<String>>,
+    auth: Option<Arc<AdminAuth>>,
+    read_only: bool,
     config_source: Option<Arc<ConfigSource>>,
     allow_injection: bool,
     intercept: Option<InterceptControl>,
     scripts_dir: Option<Arc<PathBuf>>,
+    cluster: Option<Cluster>,
+    system_info: Arc<SystemInfo>,
     cancel: CancellationToken,
     tracker: TaskTracker,
 ) -> anyhow::Result<()> {
@@ -448,10 +529,12 @@ async fn accept_loop(
         };
         let io = TokioIo::new(stream);
         let manager = Arc::clone(&manager);
-        let api_key = api_key.clone();
+        let auth = auth.clone();
         let config_source = config_source.clone();
         let intercept = intercept.clone();
         let scripts_dir = scripts_dir.clone();
+        let cluster = cluster.clone();
+        let system_info = Arc::clone(&system_info);
         let conn_cancel = cancel.clone();
 
         tracker.spawn(async move {
@@ -460,10 +543,12 @@ async fn accept_loop(
             let stream_cancel = conn_cancel.clone();
             let service = service_fn(move |req| {
                 let manager = Arc::clone(&manager);
-                let api_key = api_key.clone();
+                let auth = auth.clone();
                 let config_source = config_source.clone();
                 let intercept = intercept.clone();
                 let scripts_dir = scripts_dir.clone();
+                let cluster = cluster.clone();
+                let system_info = Arc::clone(&system_info);
                 let stream_cancel = stream_cancel.clone();
                 async move {
                     // Per-request annotation scope + response decorator (issue #318):
@@ -477,18 +562,38 @@ async fn accept_loop(
                         // (which would otherwise force app-under-test traffic to carry the admin
                         // key and would leak that Authorization header into imposter predicates).
                         let is_gateway = req.uri().path().starts_with("/__rift/");
-                        if let Some(ref key) = api_key
+                        // Kubernetes probes carry no credentials; they only ever read status.
+                        let is_probe = matches!(req.uri().path(), "/healthz" | "/readyz");
+                        let mut role = AdminRole::Admin;
+                        if let Some(ref auth) = auth
                             && !is_gateway
+                            && !is_probe
                         {
-                            let auth = req
+                            let header = req
                                 .headers()
                                 .get("authorization")
                                 .and_then(|v| v.to_str().ok())
                                 .unwrap_or("");
-                            if !api_key_matches(auth, key.as_str()) {
-                                return Ok::<_, hyper::Error>(box_full(unauthorized_response()));
+                            match auth.authenticate(header) {
+                                Some(authenticated) => role = authenticated,
+                                None => {
+                                    return Ok::<_, hyper::Error>(
+                                        box_full(unauthorized_response()),
+                                    );
+                                }
                             }
                         }
+                        // `--readonly` and read-only keys (like the key check above) guard the
+                        // control plane only; gateway traffic is never a mutation of the server.
+                        let is_grpc = grpc::is_grpc(&req);
+                        let mutation = if is_grpc {
+                            grpc::is_mutation(req.uri().path())
+                        } else {
+                            is_mutation(req.method(), req.uri().path())
+                        };
+                        if !is_gateway && (read_only || role == AdminRole::ReadOnly) && mutation {
+                            return Ok::<_, hyper::Error>(box_full(forbidden_response(read_only)));
+                        }
                         // Admin SSE stream (issue #461): `/events` + the
                         // `/imposters/{port}/savedRequests/stream` alias. Runs AFTER the auth gate
                         // above, and BEFORE the `Full<Bytes>` router so the streaming body type never
@@ -501,16 +606,38 @@ async fn accept_loop(
                                 stream_cancel,
                             ));
                         }
-                        route_request(
+                        // Cluster sync: a request a peer sent is applied like any other but
+                        // neither replicated onward nor aggregated, so pushes cannot loop.
+                        let cluster = cluster
+                            .filter(|_| !req.headers().contains_key(REPLICATED_HEADER))
+                            .map(|cluster| {
+                                (cluster, req.method().clone(), req.uri().path().to_string())
+                            });
+                        if is_grpc {
+                            let (response, change) =
+                                grpc::handle(req, manager, allow_injection, scripts_dir).await;
+                            if let (Some(change), Some((cluster, ..))) = (change, cluster) {
+                                cluster.replicate(change);
+                            }
+                            return Ok(response);
+                        }
+                        let response = route_request(
                             req,
                             manager,
                             config_source,
                             allow_injection,
                             intercept,
                             scripts_dir,
+                            system_info,
                         )
-                        .await
-                        .map(box_full)
+                        .await?;
+                        let response = match cluster {
+                            Some((cluster, method, path)) => {
+                                cluster.after_admin_request(&method, &path, response).await
+                            }
+                            None => response,
+                        };
+                        Ok(box_full(response))
                     })
                     .await;
                     let mut response = result?;
@@ -572,16 +699,6 @@ async fn accept_loop(
     Ok(())
 }
 
-/// Constant-time equality for the admin API key.
-///
-/// A plain `!=` short-circuits at the first differing byte, letting a network
-/// attacker recover the key byte-by-byte from response-timing differences
-/// (issue #548). `ConstantTimeEq` compares every byte regardless of where the
-/// mismatch is; the length check it performs first is not secret.
-fn api_key_matches(provided: &str, expected: &str) -> bool {
-    provided.as_bytes().ct_eq(expected.as_bytes()).into()
-}
-
 /// Box a `Full<Bytes>` response into the streaming-unified `AdminBody` (issue #461), so the normal
 /// router path and the SSE stream path share one response type. `Full`'s error is `Infallible`, so
 /// the `map_err` closure is unreachable.
@@ -589,6 +706,16 @@ fn box_full(resp: Response<Full<Bytes>>) -> Response<AdminBody> {
     resp.map(|body| body.map_err(|never| match never {}).boxed())
 }
 
+/// `403` for a mutation refused by `--readonly` (`server_read_only`) or a read-only credential.
+fn forbidden_response(server_read_only: bool) -> Response<Full<Bytes>> {
+    let message = if server_read_only {
+        "The admin API is read-only (--readonly)"
+    } else {
+        "This credential is read-only"
+    };
+    crate::response::error_response(StatusCode::FORBIDDEN, message)
+}
+
 fn unauthorized_response() -> Response<Full<Bytes>> {
     let body = r#"{"errors":[{"code":"unauthorized","type":"unauthorized","message":"Invalid authorization token"}]}"#;
     Response::builder()
@@ -636,8 +763,9 @@ mod tests {
         let manager = Arc::new(ImposterManager::new());
         let addr: SocketAddr = "127.0.0.1:9999".parse().unwrap();
         let server = AdminApiServer::new(addr, manager, Some("secret".to_string()));
-        assert!(server.api_key.is_some());
-        assert_eq!(server.api_key.unwrap().as_str(), "secret");
+        let auth = server.auth.expect("authentication enabled");
+        assert_eq!(auth.authenticate("secret"), Some(AdminRole::Admin));
+        assert_eq!(auth.authenticate("other"), None);
     }
 
     #[test]
@@ -645,34 +773,7 @@ mod tests {
         let manager = Arc::new(ImposterManager::new());
         let addr: SocketAddr = "127.0.0.1:9999".parse().unwrap();
         let server = AdminApiServer::new(addr, manager, None);
-        assert!(server.api_key.is_none());
-    }
-
-    #[test]
-    fn api_key_matches_accepts_correct() {
-        assert!(api_key_matches("s3cret-token", "s3cret-token"));
-    }
-
-    #[test]
-    fn api_key_matches_rejects_wrong() {
-        assert!(!api_key_matches("s3cret-tokeX", "s3cret-token"));
-        // Differ in the first byte — a short-circuiting compare would return
-        // fastest here; the constant-time compare must still reject it.
-        assert!(!api_key_matches("Xs3cret-token", "s3cret-token"));
-    }
-
-    #[test]
-    fn api_key_matches_rejects_wrong_length() {
-        assert!(!api_key_matches("s3cret", "s3cret-token"));
-        assert!(!api_key_matches("s3cret-token-extra", "s3cret-token"));
-    }
-
-    #[test]
-    fn api_key_matches_rejects_empty_against_nonempty() {
-        assert!(!api_key_matches("", "s3cret-token"));
-        // Two empty strings are trivially equal — no key configured is handled
-        // by the `Some(key)` guard at the call site, not here.
-        assert!(api_key_matches("", ""));
+        assert!(server.auth.is_none());
     }
 }
 
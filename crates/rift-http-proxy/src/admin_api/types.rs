@@ -1,7 +1,7 @@
 //! Response types and HATEOAS structures for the Admin API.
 
 use crate::extensions::stub_analysis::StubWarning;
-use crate::imposter::{RecordedRequest, Stub};
+use crate::imposter::{RecordedRequest, Stub, StubOperation, StubRef};
 use bytes::Bytes;
 use http_body_util::Full;
 use hyper::body::Incoming;
@@ -121,6 +121,85 @@ pub struct ReplaceStubsRequest {
     pub stubs: Vec<Stub>,
 }
 
+/// Request to move a stub to another position (`PUT /imposters/:port/stubs/:index` with a
+/// `newIndex` body instead of a stub)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MoveStubRequest {
+    pub new_index: usize,
+}
+
+/// Request to apply several stub operations as one atomic change
+/// (`POST /imposters/:port/stubs/_batch`)
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StubBatchRequest {
+    pub operations: Vec<StubOperationRequest>,
+}
+
+/// One operation of a [`StubBatchRequest`], tagged by `op`. `replace` and `delete` address their
+/// stub by exactly one of `index` or `id`.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase", deny_unknown_fields)]
+pub enum StubOperationRequest {
+    Add {
+        #[serde(default)]
+        index: Option<usize>,
+        stub: Stub,
+    },
+    Replace {
+        #[serde(default)]
+        index: Option<usize>,
+        #[serde(default)]
+        id: Option<String>,
+        stub: Stub,
+    },
+    Delete {
+        #[serde(default)]
+        index: Option<usize>,
+        #[serde(default)]
+        id: Option<String>,
+    },
+    Move {
+        from: usize,
+        to: usize,
+    },
+}
+
+impl StubOperationRequest {
+    /// The stub this operation writes, if any.
+    pub fn stub_mut(&mut self) -> Option<&mut Stub> {
+        match self {
+            StubOperationRequest::Add { stub, .. } | StubOperationRequest::Replace { stub, .. } => {
+                Some(stub)
+            }
+            StubOperationRequest::Delete { .. } | StubOperationRequest::Move { .. } => None,
+        }
+    }
+
+    /// The core operation, or why the request does not describe one.
+    pub fn into_operation(self) -> Result<StubOperation, String> {
+        fn target(index: Option<usize>, id: Option<String>) -> Result<StubRef, String> {
+            match (index, id) {
+                (Some(index), None) => Ok(StubRef::Index(index)),
+                (None, Some(id)) => Ok(StubRef::Id(id)),
+                _ => Err("exactly one of `index` or `id` is required".to_string()),
+            }
+        }
+        Ok(match self {
+            StubOperationRequest::Add { index, stub } => StubOperation::Add { stub, index },
+            StubOperationRequest::Replace { index, id, stub } => StubOperation::Replace {
+                target: target(index, id)?,
+                stub,
+            },
+            StubOperationRequest::Delete { index, id } => StubOperation::Delete {
+                target: target(index, id)?,
+            },
+            StubOperationRequest::Move { from, to } => StubOperation::Move { from, to },
+        })
+    }
+}
+
 /// Query parameters for imposter endpoints
 #[derive(Debug, Default)]
 pub struct ImposterQueryParams {
@@ -142,6 +221,119 @@ impl ImposterQueryParams {
     }
 }
 
+/// Page size of a `GET /imposters?page=` request that gives no `size`.
+pub const DEFAULT_IMPOSTER_PAGE_SIZE: usize = 50;
+
+/// Largest `GET /imposters?size=` accepted.
+pub const MAX_IMPOSTER_PAGE_SIZE: usize = 1000;
+
+/// `GET /imposters` selection on top of [`ImposterQueryParams`]: filter by `name` (a
+/// case-insensitive substring) and `port` (a comma-separated list), page with `page` (1-based)
+/// and `size`, and project each imposter to the top-level `fields` listed.
+#[derive(Debug, Default, PartialEq)]
+pub struct ImposterListQuery {
+    pub name: Option<String>,
+    pub ports: Option<Vec<u16>>,
+    /// `(page, size)`; `None` lists every match.
+    pub page: Option<(usize, usize)>,
+    pub fields: Option<Vec<String>>,
+}
+
+impl ImposterListQuery {
+    /// Parse the listing parameters; a malformed number is an error naming the parameter.
+    pub fn parse(query: Option<&str>) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let (mut page, mut size) = (None, None);
+        for pair in query.unwrap_or("").split('&').filter(|s| !s.is_empty()) {
+            let (key, raw) = pair.split_once('=').unwrap_or((pair, ""));
+            let value = urlencoding::decode(raw)
+                .map(|v| v.into_owned())
+                .unwrap_or_else(|_| raw.to_string());
+            let list = || {
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            };
+            match key {
+                "name" => parsed.name = Some(value.to_lowercase()),
+                "port" => {
+                    let ports = list()
+                        .iter()
+                        .map(|p| p.parse::<u16>().map_err(|_| format!("invalid port: {p}")))
+                        .collect::<Result<_, _>>()?;
+                    parsed.ports = Some(ports);
+                }
+                "page" => match value.parse::<usize>() {
+                    Ok(n) if n >= 1 => page = Some(n),
+                    _ => return Err(format!("invalid page: {value} (expected 1 or more)")),
+                },
+                "size" => match value.parse::<usize>() {
+                    Ok(n) if (1..=MAX_IMPOSTER_PAGE_SIZE).contains(&n) => size = Some(n),
+                    _ => {
+                        return Err(format!(
+                            "invalid size: {value} (expected 1 to {MAX_IMPOSTER_PAGE_SIZE})"
+                        ));
+                    }
+                },
+                "fields" => parsed.fields = Some(list()),
+                _ => {}
+            }
+        }
+        if page.is_some() || size.is_some() {
+            parsed.page = Some((
+                page.unwrap_or(1),
+                size.unwrap_or(DEFAULT_IMPOSTER_PAGE_SIZE),
+            ));
+        }
+        Ok(parsed)
+    }
+
+    /// Whether an imposter passes the `name` and `port` filters.
+    pub fn selects(&self, port: Option<u16>, name: Option<&str>) -> bool {
+        let port_ok = self
+            .ports
+            .as_ref()
+            .is_none_or(|ports| port.is_some_and(|p| ports.contains(&p)));
+        let name_ok = self.name.as_ref().is_none_or(|needle| {
+            name.is_some_and(|name| name.to_lowercase().contains(needle.as_str()))
+        });
+        port_ok && name_ok
+    }
+
+    /// The requested page of `items` (all of them when not paging).
+    pub fn page_of<T>(&self, items: Vec<T>) -> Vec<T> {
+        match self.page {
+            Some((page, size)) => items
+                .into_iter()
+                .skip((page - 1).saturating_mul(size))
+                .take(size)
+                .collect(),
+            None => items,
+        }
+    }
+
+    /// Keep only the requested `fields` of each listed imposter object.
+    pub fn project(&self, imposters: Vec<serde_json::Value>) -> Vec<serde_json::Value> {
+        let Some(fields) = &self.fields else {
+            return imposters;
+        };
+        imposters
+            .into_iter()
+            .map(|imposter| match imposter {
+                serde_json::Value::Object(map) => serde_json::Value::Object(
+                    map.into_iter()
+                        .filter(|(key, _)| fields.iter().any(|f| f == key))
+                        .collect(),
+                ),
+                other => other,
+            })
+            .collect()
+    }
+}
+
 /// Minimal imposter listing entry (Mountebank ?list=true response shape)
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -457,6 +649,46 @@ mod tests {
         assert!(params.list);
     }
 
+    #[test]
+    fn imposter_list_query_parses_filters_paging_and_fields() {
+        let query = ImposterListQuery::parse(Some(
+            "replayable=true&name=Auth%20Service&port=4545,4546&page=2&fields=port,name",
+        ))
+        .unwrap();
+        assert_eq!(query.name.as_deref(), Some("auth service"));
+        assert_eq!(query.ports, Some(vec![4545, 4546]));
+        assert_eq!(query.page, Some((2, DEFAULT_IMPOSTER_PAGE_SIZE)));
+        assert_eq!(
+            query.fields,
+            Some(vec!["port".to_string(), "name".to_string()])
+        );
+        assert!(query.selects(Some(4546), Some("Prod auth service")));
+        assert!(!query.selects(Some(4547), Some("auth service")));
+        assert!(!query.selects(Some(4545), None));
+
+        assert_eq!(
+            ImposterListQuery::parse(None).unwrap(),
+            ImposterListQuery::default()
+        );
+        assert_eq!(
+            ImposterListQuery::parse(Some("size=10")).unwrap().page,
+            Some((1, 10))
+        );
+        for bad in ["page=0", "size=0", "size=1001", "port=x", "page=two"] {
+            assert!(ImposterListQuery::parse(Some(bad)).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn imposter_list_query_pages_and_projects() {
+        let query = ImposterListQuery::parse(Some("page=2&size=2&fields=port")).unwrap();
+        assert_eq!(query.page_of(vec![1, 2, 3, 4, 5]), [3, 4]);
+        assert_eq!(
+            query.project(vec![serde_json::json!({"port": 1, "name": "a"})]),
+            [serde_json::json!({"port": 1})]
+        );
+    }
+
     #[test]
     fn test_imposter_list_entry_excludes_enabled() {
         let entry = ImposterListEntry {
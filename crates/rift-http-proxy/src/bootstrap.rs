@@ -8,6 +8,8 @@
 //! shared implementation instead.
 
 use crate::admin_api::DEFAULT_ADMIN_PORT;
+use crate::recording::har::{Har, build_har};
+use crate::recording::postman::postman_from_har;
 use crate::server::Cli;
 use std::path::Path;
 use tracing::{info, warn};
@@ -183,6 +185,10 @@ pub fn stop_server(pidfile: &Path) -> Result<(), anyhow::Error> {
 /// `savefile`. This is the form to call from an embedder's own async runtime — it awaits rather
 /// than driving a nested runtime, so it is safe on an async worker thread. Sync callers (the `save`
 /// subcommand) should use [`save_imposters`], which wraps this.
+///
+/// With `remove_proxies`, the saved file is the replay half of record-then-replay: the config is
+/// fetched with `removeProxies=true` and then passed through [`strip_proxies`], so the file holds
+/// only the recorded stubs even when the server ignores the parameter.
 pub async fn save_imposters_async(
     host: &str,
     port: u16,
@@ -199,7 +205,13 @@ pub async fn save_imposters_async(
     // `error_for_status` before `.text()` so a 401/500 response is a value error, not a body
     // silently written to the user's savefile. The error carries the status and URL.
     let response = client.get(&url).send().await?.error_for_status()?;
-    let content = response.text().await?;
+    let mut content = response.text().await?;
+    if remove_proxies {
+        let mut config: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("admin API returned invalid imposter JSON: {e}"))?;
+        strip_proxies(&mut config);
+        content = serde_json::to_string_pretty(&config)?;
+    }
 
     // `tokio::fs::write` so the shared body never blocks a caller's async worker thread.
     tokio::fs::write(savefile, &content).await?;
@@ -208,6 +220,41 @@ pub async fn save_imposters_async(
     Ok(())
 }
 
+/// Strip everything from a saved `{"imposters": [...]}` document that would reach a live upstream
+/// on replay: proxy responses (and stubs left with no response), `defaultForward`, and the
+/// `_rift.proxy` upstream. Each proxy's `predicateGenerators` and `recording` options go with it.
+pub fn strip_proxies(config: &mut serde_json::Value) {
+    let imposters = config
+        .get_mut("imposters")
+        .and_then(|i| i.as_array_mut())
+        .into_iter()
+        .flatten();
+    for imposter in imposters {
+        let Some(imposter) = imposter.as_object_mut() else {
+            continue;
+        };
+        imposter.remove("defaultForward");
+        if let Some(rift) = imposter.get_mut("_rift").and_then(|r| r.as_object_mut()) {
+            rift.remove("proxy");
+            if rift.is_empty() {
+                imposter.remove("_rift");
+            }
+        }
+        if let Some(stubs) = imposter.get_mut("stubs").and_then(|s| s.as_array_mut()) {
+            for stub in stubs.iter_mut() {
+                if let Some(responses) = stub.get_mut("responses").and_then(|r| r.as_array_mut()) {
+                    responses.retain(|response| response.get("proxy").is_none());
+                }
+            }
+            stubs.retain(|stub| {
+                stub.get("responses")
+                    .and_then(|r| r.as_array())
+                    .is_some_and(|r| !r.is_empty())
+            });
+        }
+    }
+}
+
 /// Save imposters to a file (blocking form).
 ///
 /// Builds its own tokio runtime and drives [`save_imposters_async`], exactly like the CLI's `save`
@@ -223,3 +270,365 @@ pub fn save_imposters(
     let runtime = tokio::runtime::Runtime::new()?;
     runtime.block_on(save_imposters_async(host, port, savefile, remove_proxies))
 }
+
+/// Export recorded traffic from a running server (`rift export`).
+///
+/// With `imposter`, fetches that imposter's `GET /imposters/:port/export`; otherwise lists every
+/// imposter and merges their HAR entries, in time order, into one log. `format` is `har`, or
+/// `postman` for a Postman collection of the same entries (see [`postman_from_har`]). Writes to
+/// `output`, or to stdout when unset; a Postman export also writes its environment next to
+/// `output`, as `<name>.postman_environment.json`.
+pub async fn export_recordings_async(
+    host: &str,
+    port: u16,
+    format: &str,
+    imposter: Option<u16>,
+    output: Option<&Path>,
+) -> Result<(), anyhow::Error> {
+    let postman = format.eq_ignore_ascii_case("postman");
+    if !postman && !format.eq_ignore_ascii_case("har") {
+        anyhow::bail!("Unsupported export format '{format}' (supported: har, postman)");
+    }
+    let client = reqwest::Client::new();
+    let base = format!("http://{host}:{port}");
+    let ports = match imposter {
+        Some(p) => vec![p],
+        None => {
+            let listing: serde_json::Value = client
+                .get(format!("{base}/imposters?list=true"))
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            listing["imposters"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|i| i["port"].as_u64().and_then(|p| u16::try_from(p).ok()))
+                .collect()
+        }
+    };
+
+    let mut merged: Option<Har> = None;
+    for p in ports {
+        let har: Har = client
+            .get(format!("{base}/imposters/{p}/export"))
+            .query(&[("format", "har")])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        match merged.as_mut() {
+            Some(all) => all.log.entries.extend(har.log.entries),
+            None => merged = Some(har),
+        }
+    }
+    let mut har = merged.unwrap_or_else(|| build_har("", &[], Vec::new()));
+    // RFC 3339 timestamps in UTC sort chronologically as strings.
+    har.log
+        .entries
+        .sort_by(|a, b| a.started_date_time.cmp(&b.started_date_time));
+
+    if postman {
+        let name = match imposter {
+            Some(p) => format!("Rift imposter {p}"),
+            None => "Rift recordings".to_string(),
+        };
+        let export = postman_from_har(&har, &name);
+        let content = serde_json::to_string_pretty(&export.collection)?;
+        match output {
+            Some(path) => {
+                tokio::fs::write(path, &content).await?;
+                let environment_path = postman_environment_path(path);
+                tokio::fs::write(
+                    &environment_path,
+                    serde_json::to_string_pretty(&export.environment)?,
+                )
+                .await?;
+                info!(
+                    "Exported {} requests as a Postman collection to {:?} (environment: {:?})",
+                    har.log.entries.len(),
+                    path,
+                    environment_path
+                );
+            }
+            None => println!("{content}"),
+        }
+        return Ok(());
+    }
+
+    let content = serde_json::to_string_pretty(&har)?;
+    match output {
+        Some(path) => {
+            tokio::fs::write(path, &content).await?;
+            info!(
+                "Exported {} HAR entries to {:?}",
+                har.log.entries.len(),
+                path
+            );
+        }
+        None => println!("{content}"),
+    }
+    Ok(())
+}
+
+/// Where a Postman export writes its environment: beside the collection, `orders.json` giving
+/// `orders.postman_environment.json`.
+fn postman_environment_path(collection: &Path) -> std::path::PathBuf {
+    let stem = collection
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let stem = stem.strip_suffix(".postman_collection").unwrap_or(&stem);
+    collection.with_file_name(format!("{stem}.postman_environment.json"))
+}
+
+/// Export recorded traffic (blocking form); see [`save_imposters`] for the runtime caveat.
+pub fn export_recordings(
+    host: &str,
+    port: u16,
+    format: &str,
+    imposter: Option<u16>,
+    output: Option<&Path>,
+) -> Result<(), anyhow::Error> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(export_recordings_async(
+        host, port, format, imposter, output,
+    ))
+}
+
+/// Create an imposter on `imposter_port` from the HAR file at `har` (`rift import`), by uploading
+/// it to the running server's `POST /imposters/:port/import`. `headers` names the request headers
+/// the generated predicates also match on.
+pub async fn import_har_async(
+    host: &str,
+    port: u16,
+    har: &Path,
+    imposter_port: u16,
+    headers: &[String],
+) -> Result<(), anyhow::Error> {
+    let content = tokio::fs::read(har).await?;
+    let mut query = vec![("format", "har".to_string())];
+    if !headers.is_empty() {
+        query.push(("headers", headers.join(",")));
+    }
+    let response = reqwest::Client::new()
+        .post(format!(
+            "http://{host}:{port}/imposters/{imposter_port}/import"
+        ))
+        .query(&query)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(content)
+        .send()
+        .await?;
+    // Surface the server's error body (e.g. "Invalid HAR JSON: ...") rather than just the status.
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("import failed ({status}): {body}");
+    }
+    let imposter: serde_json::Value = response.json().await?;
+    let stubs = imposter["stubs"].as_array().map_or(0, Vec::len);
+    info!(
+        "Imported {:?} into imposter {imposter_port} ({stubs} stubs)",
+        har
+    );
+    Ok(())
+}
+
+/// Import a HAR file (blocking form); see [`save_imposters`] for the runtime caveat.
+pub fn import_har(
+    host: &str,
+    port: u16,
+    har: &Path,
+    imposter_port: u16,
+    headers: &[String],
+) -> Result<(), anyhow::Error> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(import_har_async(host, port, har, imposter_port, headers))
+}
+
+/// Read WireMock stub mappings from `path` for `rift import --wiremock`: a mapping file, a
+/// `mappings` directory (searched recursively, files in name order), or a WireMock root holding
+/// `mappings/` and `__files/`. A `bodyFileName` is inlined from the sibling `__files` directory,
+/// since the server cannot read the caller's files; one that is missing is left for the server to
+/// report. Returns `{"mappings": [...]}`.
+pub fn collect_wiremock_mappings(path: &Path) -> Result<serde_json::Value, anyhow::Error> {
+    let (mappings_dir, files_dir) = if path.join("mappings").is_dir() {
+        (path.join("mappings"), path.join("__files"))
+    } else {
+        let parent = path.parent().unwrap_or(Path::new("."));
+        let root = if path.is_dir() {
+            parent
+        } else {
+            parent.parent().unwrap_or(Path::new("."))
+        };
+        (path.to_path_buf(), root.join("__files"))
+    };
+    let mut files = Vec::new();
+    if mappings_dir.is_dir() {
+        json_files(&mappings_dir, &mut files)?;
+    } else {
+        files.push(mappings_dir);
+    }
+
+    let mut mappings = Vec::new();
+    for file in files {
+        let document: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&file)?)
+            .map_err(|e| anyhow::anyhow!("{}: {e}", file.display()))?;
+        match document {
+            serde_json::Value::Object(mut map) if map.contains_key("mappings") => {
+                match map.remove("mappings") {
+                    Some(serde_json::Value::Array(items)) => mappings.extend(items),
+                    _ => anyhow::bail!("{}: `mappings` must be an array", file.display()),
+                }
+            }
+            serde_json::Value::Array(items) => mappings.extend(items),
+            other => mappings.push(other),
+        }
+    }
+    for mapping in &mut mappings {
+        inline_body_file(mapping, &files_dir);
+    }
+    Ok(serde_json::json!({ "mappings": mappings }))
+}
+
+/// Every `*.json` file under `dir`, recursively, in path order.
+fn json_files(dir: &Path, out: &mut Vec<std::path::PathBuf>) -> std::io::Result<()> {
+    let mut entries: Vec<_> = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+    entries.sort();
+    for entry in entries {
+        if entry.is_dir() {
+            json_files(&entry, out)?;
+        } else if entry.extension().is_some_and(|ext| ext == "json") {
+            out.push(entry);
+        }
+    }
+    Ok(())
+}
+
+/// Replace a mapping's `response.bodyFileName` with the file's content: `body` when it is UTF-8,
+/// `base64Body` otherwise.
+fn inline_body_file(mapping: &mut serde_json::Value, files_dir: &Path) {
+    let Some(response) = mapping
+        .get_mut("response")
+        .and_then(serde_json::Value::as_object_mut)
+    else {
+        return;
+    };
+    let Some(name) = response
+        .get("bodyFileName")
+        .and_then(serde_json::Value::as_str)
+    else {
+        return;
+    };
+    let Ok(content) = std::fs::read(files_dir.join(name)) else {
+        return;
+    };
+    response.remove("bodyFileName");
+    match String::from_utf8(content) {
+        Ok(text) => response.insert("body".to_string(), text.into()),
+        Err(e) => {
+            use base64::Engine;
+            let encoded = base64::engine::general_purpose::STANDARD.encode(e.into_bytes());
+            response.insert("base64Body".to_string(), encoded.into())
+        }
+    };
+}
+
+/// Create an imposter on `imposter_port` from the WireMock mappings at `mappings` (`rift import
+/// --wiremock`), via the running server's `POST /imposters/:port/import?format=wiremock`. Each
+/// conversion warning the server reports is logged.
+pub async fn import_wiremock_async(
+    host: &str,
+    port: u16,
+    mappings: &Path,
+    imposter_port: u16,
+) -> Result<(), anyhow::Error> {
+    let document = collect_wiremock_mappings(mappings)?;
+    let count = document["mappings"].as_array().map_or(0, Vec::len);
+    let response = reqwest::Client::new()
+        .post(format!(
+            "http://{host}:{port}/imposters/{imposter_port}/import"
+        ))
+        .query(&[("format", "wiremock")])
+        .json(&document)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("import failed ({status}): {body}");
+    }
+    let imposter: serde_json::Value = response.json().await?;
+    for warning in imposter["warnings"].as_array().into_iter().flatten() {
+        warn!("{}", warning.as_str().unwrap_or_default());
+    }
+    let stubs = imposter["stubs"].as_array().map_or(0, Vec::len);
+    info!(
+        "Imported {count} WireMock mapping(s) from {:?} into imposter {imposter_port} ({stubs} stubs)",
+        mappings
+    );
+    Ok(())
+}
+
+/// Import WireMock mappings (blocking form); see [`save_imposters`] for the runtime caveat.
+pub fn import_wiremock(
+    host: &str,
+    port: u16,
+    mappings: &Path,
+    imposter_port: u16,
+) -> Result<(), anyhow::Error> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(import_wiremock_async(host, port, mappings, imposter_port))
+}
+
+/// Infer an OpenAPI document from an imposter's recorded traffic (`rift openapi generate`) via
+/// `GET /imposters/:port/openapi`, rendered as `format` (`json` or `yaml`) to `output` or stdout.
+pub async fn generate_openapi_async(
+    host: &str,
+    port: u16,
+    imposter: u16,
+    format: &str,
+    output: Option<&Path>,
+) -> Result<(), anyhow::Error> {
+    let document: serde_json::Value = reqwest::Client::new()
+        .get(format!("http://{host}:{port}/imposters/{imposter}/openapi"))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let content = match format.to_ascii_lowercase().as_str() {
+        "json" => serde_json::to_string_pretty(&document)? + "\n",
+        "yaml" | "yml" => serde_yaml::to_string(&document)?,
+        other => anyhow::bail!("unsupported OpenAPI format '{other}' (supported: json, yaml)"),
+    };
+    match output {
+        Some(path) => {
+            tokio::fs::write(path, &content).await?;
+            info!(
+                "Wrote OpenAPI document for imposter {imposter} to {:?}",
+                path
+            );
+        }
+        None => print!("{content}"),
+    }
+    Ok(())
+}
+
+/// Generate an OpenAPI document (blocking form); see [`save_imposters`] for the runtime caveat.
+pub fn generate_openapi(
+    host: &str,
+    port: u16,
+    imposter: u16,
+    format: &str,
+    output: Option<&Path>,
+) -> Result<(), anyhow::Error> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(generate_openapi_async(host, port, imposter, format, output))
+}
@@ -0,0 +1,629 @@
+//! Cluster sync: keep the imposters of several Rift nodes (e.g. behind one load balancer) in step.
+//!
+//! Every node names the others with `--peer`. After an admin mutation under `/imposters` succeeds,
+//! the node repeats that one change on each peer as the REST request that makes it: a created
+//! imposter is `POST`ed with its current definition, a deleted one is `DELETE`d, a stub change
+//! sends the imposter's current stubs with `PUT /imposters/{port}/stubs`, enabling and disabling
+//! are forwarded. Imposters created from a template or by an import are `POST`ed like any other.
+//! Only a `PUT /imposters` (which the operator meant to replace the whole set) and a reload of the
+//! config source (`POST /admin/reload`, `--watch-configfile`, `SIGHUP`) push the whole set. Imposters a peer created meanwhile are left alone, so concurrent creates
+//! on two nodes both survive. gRPC mutations are replicated the same way. The requests carry
+//! [`REPLICATED_HEADER`], and a request with that header is applied but never replicated onward,
+//! so every node must list every other node. Runtime-only mutations (clearing recorded requests,
+//! recording sessions, scenario state, drain) stay per node.
+//!
+//! Each peer has one delivery task that sends its changes in the order this node finished them.
+//! A change the peer could not be reached for, or answered with a 5xx, is retried with exponential
+//! backoff (eight attempts over about a minute) before it is dropped, holding back the changes
+//! behind it so the peer never sees them out of order. A change the peer refuses (a 4xx, e.g. a
+//! create for a port it already serves) is logged and dropped, and a peer more than 1024 changes
+//! behind drops new ones. Nothing reconciles a peer that missed a change: restart it with no
+//! imposters of its own and it adopts the set again. There are no versions either, so concurrent
+//! edits to the same imposter on two nodes can leave them with different stubs.
+//!
+//! A node that starts with no imposters of its own adopts the set of the first peer that answers,
+//! so a replacement node serves the same imposters before the next change reaches it.
+//!
+//! `GET /imposters` on a clustered node also asks each peer for the same imposters and sums
+//! `numberOfRequests` per port, reporting the split under `requestsByNode`.
+
+use crate::imposter::{ImposterConfig, ImposterManager};
+use crate::injection_gate::config_uses_script_surface;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::{Method, Response, header};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_util::sync::{CancellationToken, DropGuard};
+use tracing::{debug, info, warn};
+
+/// Marks an admin request sent by a peer: it is applied locally but not replicated, and a
+/// `GET /imposters` carrying it reports only this node's counts.
+pub const REPLICATED_HEADER: &str = "x-rift-replicated";
+
+/// Bound on one peer request, so a hung peer cannot stall its sync task or a listing.
+const PEER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Changes a peer may fall behind by before new ones are dropped.
+const QUEUE_CAPACITY: usize = 1024;
+
+/// Tries per change before a peer that cannot take it is skipped: the backoff between them
+/// doubles from [`FIRST_RETRY`] up to [`MAX_RETRY`], about a minute in all.
+const DELIVERY_ATTEMPTS: u32 = 8;
+const FIRST_RETRY: Duration = Duration::from_millis(500);
+const MAX_RETRY: Duration = Duration::from_secs(30);
+
+/// The `requestsByNode` key for the answering node's own count.
+const LOCAL_NODE: &str = "local";
+
+/// Parse a `--peer` value: the absolute `http` or `https` URL of another node's admin API.
+pub fn parse_peer_url(value: &str) -> Result<String, String> {
+    match reqwest::Url::parse(value) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => {
+            Ok(value.trim_end_matches('/').to_string())
+        }
+        Ok(url) => Err(format!(
+            "unsupported scheme '{}' (expected http or https)",
+            url.scheme()
+        )),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// A node's view of its peers. Cheap to clone; the sync tasks stop once every clone is dropped.
+#[derive(Clone)]
+pub struct Cluster {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    manager: Weak<ImposterManager>,
+    peers: Vec<Peer>,
+    client: reqwest::Client,
+    authorization: Option<String>,
+    _stop: DropGuard,
+}
+
+struct Peer {
+    url: String,
+    changes: mpsc::Sender<Mutation>,
+}
+
+/// An admin change to an imposter, to be repeated on every peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Change {
+    /// The imposter on this port was created.
+    Created(u16),
+    /// The imposter on this port was deleted.
+    Deleted(u16),
+    /// The imposter's stubs were added to, replaced or deleted.
+    Stubs(u16),
+    /// The imposter was enabled (`true`) or disabled.
+    Enabled(u16, bool),
+    /// The whole imposter set was replaced (`PUT /imposters`, or a reload of the config source).
+    Replaced,
+    /// Every imposter was deleted (`DELETE /imposters`).
+    Cleared,
+}
+
+/// The admin request that repeats a [`Change`] on a peer.
+#[derive(Debug, Clone)]
+struct Mutation {
+    method: Method,
+    path: String,
+    body: Option<serde_json::Value>,
+}
+
+impl Mutation {
+    fn new(method: Method, path: String, body: Option<serde_json::Value>) -> Self {
+        Self { method, path, body }
+    }
+}
+
+/// How one attempt to deliver a [`Mutation`] went.
+enum Delivery {
+    Done,
+    /// The peer answered with a client error; sending it again would get the same answer.
+    Refused(String),
+    /// The peer could not be reached or failed; worth another try.
+    Failed(String),
+}
+
+impl Cluster {
+    /// Start syncing `manager`'s imposters to `peers` (admin base URLs), sending `api_key` as a
+    /// bearer token when the peers require one.
+    pub fn spawn(
+        manager: &Arc<ImposterManager>,
+        peers: &[String],
+        api_key: Option<String>,
+    ) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(PEER_TIMEOUT)
+            .build()
+            .map_err(|e| anyhow::anyhow!("building cluster client: {e}"))?;
+        let authorization = api_key.map(|key| format!("Bearer {key}"));
+        let stop = CancellationToken::new();
+        let peers = peers
+            .iter()
+            .map(|url| {
+                let (changes, queue) = mpsc::channel(QUEUE_CAPACITY);
+                tokio::spawn(sync_peer(
+                    client.clone(),
+                    authorization.clone(),
+                    url.clone(),
+                    queue,
+                    stop.clone(),
+                ));
+                Peer {
+                    url: url.clone(),
+                    changes,
+                }
+            })
+            .collect();
+        Ok(Self {
+            inner: Arc::new(Inner {
+                manager: Arc::downgrade(manager),
+                peers,
+                client,
+                authorization,
+                _stop: stop.drop_guard(),
+            }),
+        })
+    }
+
+    /// Adopt the imposter set of the first peer that answers. Without `allow_injection`, scripted
+    /// imposters are left out, as any other door would refuse them.
+    pub async fn join(&self, manager: &ImposterManager, allow_injection: bool) {
+        for peer in &self.inner.peers {
+            let Some(listing) = self.fetch_listing(&peer.url, "replayable=true").await else {
+                continue;
+            };
+            let configs: Vec<ImposterConfig> =
+                match serde_json::from_value(listing["imposters"].clone()) {
+                    Ok(configs) => configs,
+                    Err(e) => {
+                        warn!("cluster peer {} listed unreadable imposters: {e}", peer.url);
+                        continue;
+                    }
+                };
+            let (configs, gated): (Vec<_>, Vec<_>) = configs
+                .into_iter()
+                .partition(|config| allow_injection || !config_uses_script_surface(config));
+            if !gated.is_empty() {
+                warn!(
+                    "not adopting {} scripted imposter(s) from {} without --allowInjection",
+                    gated.len(),
+                    peer.url
+                );
+            }
+            match manager.apply_config(configs).await {
+                Ok(report) => {
+                    info!(
+                        "joined the cluster with {} imposter(s) from {}",
+                        report.created.len(),
+                        peer.url
+                    );
+                    for (port, e) in report.failed {
+                        warn!("adopting imposter {port} from {} failed: {e}", peer.url);
+                    }
+                    return;
+                }
+                Err(e) => warn!("adopting imposters from {} failed: {e}", peer.url),
+            }
+        }
+    }
+
+    /// Queue `change` for every peer, as the request that makes it with this node's current
+    /// definition of the imposter.
+    pub(crate) fn replicate(&self, change: Change) {
+        let Some(manager) = self.inner.manager.upgrade() else {
+            return;
+        };
+        let Some(mutation) = mutation_for(&manager, change) else {
+            return;
+        };
+        drop(manager);
+        for peer in &self.inner.peers {
+            match peer.changes.try_send(mutation.clone()) {
+                Ok(()) => {}
+                Err(mpsc::error::TrySendError::Full(_)) => warn!(
+                    "cluster peer {} is {QUEUE_CAPACITY} changes behind; dropping {} {}",
+                    peer.url, mutation.method, mutation.path
+                ),
+                // The delivery task only stops once the cluster is dropped.
+                Err(mpsc::error::TrySendError::Closed(_)) => {}
+            }
+        }
+    }
+
+    /// Apply cluster behaviour to the response of an admin request that did not come from a peer:
+    /// a successful change to an imposter is replicated, and a `GET /imposters` listing gets
+    /// cluster-wide request counts. Anything else passes through untouched.
+    pub(crate) async fn after_admin_request(
+        &self,
+        method: &Method,
+        path: &str,
+        response: Response<Full<Bytes>>,
+    ) -> Response<Full<Bytes>> {
+        if !response.status().is_success() {
+            return response;
+        }
+        match (method, path) {
+            (&Method::GET, "/imposters") => self.aggregate(response).await,
+            // The created imposter's port may have been assigned, so it comes from the reply.
+            (&Method::POST, created)
+                if created == "/imposters" || created.starts_with("/imposters/from-template/") =>
+            {
+                let (parts, body) = response.into_parts();
+                let Ok(collected) = body.collect().await;
+                let bytes = collected.to_bytes();
+                let port = serde_json::from_slice::<serde_json::Value>(&bytes)
+                    .ok()
+                    .and_then(|created| created["port"].as_u64())
+                    .and_then(|port| u16::try_from(port).ok());
+                if let Some(port) = port {
+                    self.replicate(Change::Created(port));
+                }
+                Response::from_parts(parts, Full::new(bytes))
+            }
+            _ => {
+                if let Some(change) = rest_change(method, path) {
+                    self.replicate(change);
+                }
+                response
+            }
+        }
+    }
+
+    /// Replace each listed imposter's `numberOfRequests` with the sum across nodes.
+    async fn aggregate(&self, response: Response<Full<Bytes>>) -> Response<Full<Bytes>> {
+        let (mut parts, body) = response.into_parts();
+        let Ok(collected) = body.collect().await;
+        let bytes = collected.to_bytes();
+        let Ok(mut listing) = serde_json::from_slice::<serde_json::Value>(&bytes) else {
+            return Response::from_parts(parts, Full::new(bytes));
+        };
+        let ports: Vec<String> = listing["imposters"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|i| i["numberOfRequests"].is_u64())
+            .filter_map(|i| i["port"].as_u64())
+            .map(|port| port.to_string())
+            .collect();
+        // Replayable listings and field selections without counts have nothing to aggregate.
+        if ports.is_empty() {
+            return Response::from_parts(parts, Full::new(bytes));
+        }
+        let query = format!("port={}&fields=port,numberOfRequests", ports.join(","));
+        let peers = futures::future::join_all(
+            self.inner
+                .peers
+                .iter()
+                .map(|peer| self.fetch_listing(&peer.url, &query)),
+        )
+        .await;
+        let peers: Vec<(&str, Option<serde_json::Value>)> = self
+            .inner
+            .peers
+            .iter()
+            .map(|peer| peer.url.as_str())
+            .zip(peers)
+            .collect();
+        merge_request_counts(&mut listing, &peers);
+        parts.headers.remove(header::CONTENT_LENGTH);
+        let body = serde_json::to_vec(&listing).unwrap_or_else(|_| bytes.to_vec());
+        Response::from_parts(parts, Full::new(Bytes::from(body)))
+    }
+
+    /// A peer's `GET /imposters?{query}`, or `None` (logged) when it cannot be had.
+    async fn fetch_listing(&self, url: &str, query: &str) -> Option<serde_json::Value> {
+        let mut request = self
+            .inner
+            .client
+            .get(format!("{url}/imposters?{query}"))
+            .header(REPLICATED_HEADER, "true");
+        if let Some(authorization) = &self.inner.authorization {
+            request = request.header(header::AUTHORIZATION, authorization);
+        }
+        let result = match request.send().await {
+            Ok(response) if response.status().is_success() => {
+                response.json().await.map_err(|e| e.to_string())
+            }
+            Ok(response) => Err(format!("answered {}", response.status())),
+            Err(e) => Err(e.to_string()),
+        };
+        result
+            .inspect_err(|e| warn!("cluster peer {url} request counts unavailable: {e}"))
+            .ok()
+    }
+}
+
+/// Sum each imposter's `numberOfRequests` in `listing` with the peers' counts for the same port,
+/// recording every node's share under `requestsByNode` (`null` for a peer that did not answer or
+/// does not have the imposter).
+fn merge_request_counts(
+    listing: &mut serde_json::Value,
+    peers: &[(&str, Option<serde_json::Value>)],
+) {
+    let Some(imposters) = listing["imposters"].as_array_mut() else {
+        return;
+    };
+    for imposter in imposters {
+        let (Some(port), Some(local)) = (
+            imposter["port"].as_u64(),
+            imposter["numberOfRequests"].as_u64(),
+        ) else {
+            continue;
+        };
+        let mut total = local;
+        let mut by_node = serde_json::Map::new();
+        by_node.insert(LOCAL_NODE.to_string(), local.into());
+        for (url, peer) in peers {
+            let count = peer
+                .as_ref()
+                .and_then(|listing| listing["imposters"].as_array())
+                .and_then(|list| list.iter().find(|i| i["port"].as_u64() == Some(port)))
+                .and_then(|i| i["numberOfRequests"].as_u64());
+            total += count.unwrap_or(0);
+            by_node.insert((*url).to_string(), count.into());
+        }
+        imposter["numberOfRequests"] = total.into();
+        imposter["requestsByNode"] = by_node.into();
+    }
+}
+
+/// The [`Change`] a successful REST mutation other than a create from a body or template made, if
+/// peers should repeat it.
+fn rest_change(method: &Method, path: &str) -> Option<Change> {
+    let path = path.trim_end_matches('/');
+    if *method == Method::POST && path == "/admin/reload" {
+        return Some(Change::Replaced);
+    }
+    if path == "/imposters" {
+        return match *method {
+            Method::PUT => Some(Change::Replaced),
+            Method::DELETE => Some(Change::Cleared),
+            _ => None,
+        };
+    }
+    let (port, rest) = match path.strip_prefix("/imposters/")?.split_once('/') {
+        Some((port, rest)) => (port, rest),
+        None => (path.strip_prefix("/imposters/")?, ""),
+    };
+    let port = port.parse().ok()?;
+    match (method, rest) {
+        (&Method::DELETE, "") => Some(Change::Deleted(port)),
+        (&Method::POST, "enable") => Some(Change::Enabled(port, true)),
+        (&Method::POST, "disable") => Some(Change::Enabled(port, false)),
+        // An import creates the imposter on the port it names.
+        (&Method::POST, "import") => Some(Change::Created(port)),
+        (&Method::POST | &Method::PUT | &Method::DELETE, stubs)
+            if stubs == "stubs" || stubs.starts_with("stubs/") =>
+        {
+            Some(Change::Stubs(port))
+        }
+        _ => None,
+    }
+}
+
+/// The request that repeats `change` on a peer, carrying `manager`'s current definition. `None`
+/// when there is nothing to send, e.g. the imposter was deleted again before it was queued.
+fn mutation_for(manager: &ImposterManager, change: Change) -> Option<Mutation> {
+    let mutation = match change {
+        Change::Created(port) => {
+            let config = manager.get_imposter(port).ok()?.current_config();
+            Mutation::new(
+                Method::POST,
+                "/imposters".to_string(),
+                Some(serde_json::to_value(config).ok()?),
+            )
+        }
+        Change::Deleted(port) => Mutation::new(Method::DELETE, format!("/imposters/{port}"), None),
+        Change::Stubs(port) => {
+            let stubs = manager.get_imposter(port).ok()?.get_stubs();
+            Mutation::new(
+                Method::PUT,
+                format!("/imposters/{port}/stubs"),
+                Some(serde_json::json!({ "stubs": stubs })),
+            )
+        }
+        Change::Enabled(port, enabled) => {
+            let action = if enabled { "enable" } else { "disable" };
+            Mutation::new(Method::POST, format!("/imposters/{port}/{action}"), None)
+        }
+        Change::Replaced => {
+            // Ports are always assigned once an imposter runs; a port-less config would be
+            // created afresh on every push, so it is never sent.
+            let imposters: Vec<_> = manager
+                .list_imposters()
+                .iter()
+                .map(|imposter| imposter.current_config())
+                .filter(|config| config.port.is_some())
+                .collect();
+            Mutation::new(
+                Method::PUT,
+                "/imposters".to_string(),
+                Some(serde_json::json!({ "imposters": imposters })),
+            )
+        }
+        Change::Cleared => Mutation::new(Method::DELETE, "/imposters".to_string(), None),
+    };
+    Some(mutation)
+}
+
+/// Deliver each change queued for `url` in order, retrying one that fails with backoff, until
+/// `stop` fires or the cluster is dropped.
+async fn sync_peer(
+    client: reqwest::Client,
+    authorization: Option<String>,
+    url: String,
+    mut queue: mpsc::Receiver<Mutation>,
+    stop: CancellationToken,
+) {
+    loop {
+        let mutation = tokio::select! {
+            _ = stop.cancelled() => return,
+            next = queue.recv() => match next {
+                Some(mutation) => mutation,
+                None => return,
+            },
+        };
+        let mut backoff = FIRST_RETRY;
+        for attempt in 1..=DELIVERY_ATTEMPTS {
+            match deliver(&client, authorization.as_deref(), &url, &mutation).await {
+                Delivery::Done => {
+                    debug!(
+                        "cluster peer {url} applied {} {}",
+                        mutation.method, mutation.path
+                    );
+                    break;
+                }
+                Delivery::Refused(e) => {
+                    warn!(
+                        "cluster peer {url} refused {} {}: {e}",
+                        mutation.method, mutation.path
+                    );
+                    break;
+                }
+                Delivery::Failed(e) if attempt < DELIVERY_ATTEMPTS => {
+                    debug!(
+                        "cluster sync of {} {} to {url} failed (attempt {attempt}): {e}; retrying in {backoff:?}",
+                        mutation.method, mutation.path
+                    );
+                    tokio::select! {
+                        _ = stop.cancelled() => return,
+                        _ = tokio::time::sleep(backoff) => {}
+                    }
+                    backoff = (backoff * 2).min(MAX_RETRY);
+                }
+                Delivery::Failed(e) => warn!(
+                    "cluster sync of {} {} to {url} failed {DELIVERY_ATTEMPTS} times, dropping it: {e}",
+                    mutation.method, mutation.path
+                ),
+            }
+        }
+    }
+}
+
+/// Send `mutation` to the peer at `url` once.
+async fn deliver(
+    client: &reqwest::Client,
+    authorization: Option<&str>,
+    url: &str,
+    mutation: &Mutation,
+) -> Delivery {
+    let mut request = client
+        .request(mutation.method.clone(), format!("{url}{}", mutation.path))
+        .header(REPLICATED_HEADER, "true");
+    if let Some(body) = &mutation.body {
+        request = request.json(body);
+    }
+    if let Some(authorization) = authorization {
+        request = request.header(header::AUTHORIZATION, authorization);
+    }
+    match request.send().await {
+        Ok(response) if response.status().is_success() => Delivery::Done,
+        Ok(response) if response.status().is_client_error() => {
+            Delivery::Refused(format!("answered {}", response.status()))
+        }
+        Ok(response) => Delivery::Failed(format!("answered {}", response.status())),
+        Err(e) => Delivery::Failed(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn peer_urls_are_validated_and_normalized() {
+        assert_eq!(
+            parse_peer_url("http://rift-b:2525/").unwrap(),
+            "http://rift-b:2525"
+        );
+        assert!(parse_peer_url("rift-b:2525").is_err());
+        assert!(parse_peer_url("ftp://rift-b").is_err());
+    }
+
+    #[test]
+    fn imposter_changes_are_replicated_and_runtime_state_is_not() {
+        assert_eq!(
+            rest_change(&Method::PUT, "/imposters"),
+            Some(Change::Replaced)
+        );
+        assert_eq!(
+            rest_change(&Method::DELETE, "/imposters/"),
+            Some(Change::Cleared)
+        );
+        assert_eq!(
+            rest_change(&Method::POST, "/admin/reload"),
+            Some(Change::Replaced)
+        );
+        assert_eq!(
+            rest_change(&Method::POST, "/imposters/4545/import"),
+            Some(Change::Created(4545))
+        );
+        assert_eq!(
+            rest_change(&Method::DELETE, "/imposters/4545"),
+            Some(Change::Deleted(4545))
+        );
+        assert_eq!(
+            rest_change(&Method::POST, "/imposters/4545/disable"),
+            Some(Change::Enabled(4545, false))
+        );
+        for (method, path) in [
+            (Method::POST, "/imposters/4545/stubs"),
+            (Method::PUT, "/imposters/4545/stubs/2"),
+            (Method::DELETE, "/imposters/4545/stubs/by-id/login"),
+            (Method::POST, "/imposters/4545/stubs/_batch"),
+        ] {
+            assert_eq!(
+                rest_change(&method, path),
+                Some(Change::Stubs(4545)),
+                "{method} {path}"
+            );
+        }
+        for (method, path) in [
+            (Method::DELETE, "/imposters/4545/savedRequests"),
+            (Method::POST, "/imposters/4545/_recording/start"),
+            (Method::POST, "/imposters/4545/_drain"),
+            (Method::POST, "/imposters/4545/scenarios/reset"),
+            (Method::DELETE, "/imposters/4545/stubsx"),
+            (Method::DELETE, "/imposters/nope"),
+        ] {
+            assert_eq!(rest_change(&method, path), None, "{method} {path}");
+        }
+    }
+
+    #[test]
+    fn request_counts_are_summed_per_port() {
+        let mut listing = json!({"imposters": [
+            {"port": 4545, "numberOfRequests": 2},
+            {"port": 4546, "numberOfRequests": 1},
+            {"port": 4547}
+        ]});
+        let peers = [
+            (
+                "http://b:2525",
+                Some(json!({"imposters": [{"port": 4545, "numberOfRequests": 5}]})),
+            ),
+            ("http://c:2525", None),
+        ];
+        merge_request_counts(&mut listing, &peers);
+
+        assert_eq!(listing["imposters"][0]["numberOfRequests"], 7);
+        assert_eq!(
+            listing["imposters"][0]["requestsByNode"],
+            json!({"local": 2, "http://b:2525": 5, "http://c:2525": null})
+        );
+        assert_eq!(listing["imposters"][1]["numberOfRequests"], 1);
+        assert_eq!(
+            listing["imposters"][1]["requestsByNode"]["http://b:2525"],
+            json!(null)
+        );
+        assert!(listing["imposters"][2].get("requestsByNode").is_none());
+    }
+}
@@ -4,44 +4,9 @@
 
 use crate::imposter::{ImposterConfig, ScriptBaseDir, resolve_scripts};
 use crate::intercept_control::InterceptStartOptions;
-use regex::Regex;
 use std::path::{Path, PathBuf};
-use std::sync::LazyLock;
 use tracing::warn;
 
-// Fixed EJS tag patterns (issue #560): compile once at first use rather than on every
-// `preprocess_ejs` call — that runs per config file at startup, on every `POST /admin/reload`, and
-// from the script CLI. All are compile-time-constant patterns, so a compile failure is a
-// programming error caught immediately by tests, not a data-dependent runtime error.
-
-/// `<% include 'path' %>` — quoted or bare path.
-static EJS_INCLUDE_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r#"<%\s*include\s+['"]?([^'">\s]+)['"]?\s*%>"#)
-        .expect("EJS include pattern is a valid constant regex")
-});
-
-/// `<%- stringify('relative/path') %>` (issue #355 Item 7).
-static EJS_STRINGIFY_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r#"<%-\s*stringify\(\s*['"]([^'"]+)['"]\s*\)\s*%>"#)
-        .expect("EJS stringify pattern is a valid constant regex")
-});
-
-/// `<%= expr %>` expression tag.
-static EJS_EXPR_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"<%=\s*(.*?)\s*%>").expect("EJS expression pattern is a valid constant regex")
-});
-
-/// The only supported expression body: `process.env.VAR` with an optional `|| 'default'`.
-static EJS_ENV_VAR_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r#"^process\.env\.([A-Za-z_][A-Za-z0-9_]*)(?:\s*\|\|\s*['"]([^'"]*)['"]\s*)?$"#)
-        .expect("EJS env-var pattern is a valid constant regex")
-});
-
-/// Remaining `<% ... %>` control blocks (non-expression tags); `(?s)` enables dotall.
-static EJS_STMT_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?s)<%[^=].*?%>").expect("EJS statement pattern is a valid constant regex")
-});
-
 /// Where the running imposters were loaded from, retained so reload can re-read the same source.
 #[derive(Debug, Clone)]
 pub enum ConfigSource {
@@ -195,109 +160,25 @@ fn load_dir(dir: &Path) -> anyhow::Result<Vec<ImposterConfig>> {
     Ok(configs)
 }
 
-/// Pre-process EJS tokens in a config file before JSON/YAML parsing.
+/// Pre-process EJS tokens in a config file before JSON/YAML parsing, with [`rift_lint::render_ejs`]
+/// so the server and `rift-lint` render a template identically.
 ///
 /// Handles the patterns emitted by Mountebank and compatible tooling:
-/// - `<% include 'path' %>` — inline the referenced file (relative to the config file)
+/// - `<% include 'path' %>` / `<%- include('path') %>` — inline the referenced file, relative to
+///   the file that includes it, expanding its own includes
+/// - `<%- stringify(filename, 'path') %>` — inline a file (relative to the config file) as the
+///   inside of a JSON string
 /// - `<%= process.env.VAR %>` — substitute with the env var value (empty string if unset)
 /// - `<%= process.env.VAR || 'default' %>` — substitute with env var or the literal default
 ///
-/// Any other `<%= expr %>` token is replaced with an empty string and logged as a warning.
-/// `<% expr %>` (without `=`) statements (e.g., `<% for (...) %>`) are removed and logged.
+/// Any other `<%= expr %>` token is replaced with an empty string, and `<% expr %>` (without `=`)
+/// statements (e.g., `<% for (...) %>`) are removed; each is logged as a warning.
 fn preprocess_ejs(content: &str, config_path: &Path) -> anyhow::Result<String> {
-    if !content.contains("<%") {
-        return Ok(content.to_string());
-    }
-
-    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
-
-    // Process include directives first:
-    // `<% include 'path' %>`, `<% include "path" %>`, or `<% include path %>`
-    let mut result = String::new();
-    let mut last = 0;
-    for cap in EJS_INCLUDE_RE.captures_iter(content) {
-        let full = cap.get(0).unwrap();
-        let include_path = cap.get(1).unwrap().as_str();
-        result.push_str(&content[last..full.start()]);
-        let abs_path = config_dir.join(include_path);
-        match std::fs::read_to_string(&abs_path) {
-            Ok(included) => result.push_str(&included),
-            Err(e) => {
-                return Err(anyhow::anyhow!(
-                    "EJS include file '{}' not found ({}): {}",
-                    include_path,
-                    abs_path.display(),
-                    e
-                ));
-            }
-        }
-        last = full.end();
-    }
-    result.push_str(&content[last..]);
-    let content = result;
-
-    // Process `<%- stringify('relative/path') %>` (issue #355 Item 7): inline the referenced
-    // file's contents as a JSON-string-safe body. Must run BEFORE the final `<% ... %>` strip
-    // below — that catch-all matches `<%[^=].*?%>`, which would otherwise eat `<%-` tokens too.
-    // `<%-` is EJS's "unescaped output" tag; here the template already supplies the surrounding
-    // quotes (e.g. `"inject": "<%- stringify('inject.js') %>"`), so only the escaped INNER
-    // content is substituted — `serde_json::to_string` then stripping its own wrapping quotes —
-    // keeping the surrounding JSON valid.
-    let mut result = String::new();
-    let mut last = 0;
-    for cap in EJS_STRINGIFY_RE.captures_iter(&content) {
-        let full = cap.get(0).unwrap();
-        let rel_path = cap.get(1).unwrap().as_str();
-        result.push_str(&content[last..full.start()]);
-        let abs_path = config_dir.join(rel_path);
-        let file_contents = std::fs::read_to_string(&abs_path).map_err(|e| {
-            anyhow::anyhow!(
-                "EJS stringify file '{}' not found ({}): {}",
-                rel_path,
-                abs_path.display(),
-                e
-            )
-        })?;
-        let json_quoted = serde_json::to_string(&file_contents).map_err(|e| {
-            anyhow::anyhow!("failed to JSON-encode stringify file '{rel_path}': {e}")
-        })?;
-        // Strip the wrapping quotes serde_json added; the template's own quotes surround the tag.
-        let inner = &json_quoted[1..json_quoted.len() - 1];
-        result.push_str(inner);
-        last = full.end();
-    }
-    result.push_str(&content[last..]);
-    let content = result;
-
-    // Process expression tags: `<%= expr %>`
-    let mut result = String::new();
-    let mut last = 0;
-    for cap in EJS_EXPR_RE.captures_iter(&content) {
-        let full = cap.get(0).unwrap();
-        let expr = cap.get(1).unwrap().as_str().trim();
-        result.push_str(&content[last..full.start()]);
-
-        if let Some(env_cap) = EJS_ENV_VAR_RE.captures(expr) {
-            let var_name = env_cap.get(1).unwrap().as_str();
-            let default_val = env_cap.get(2).map(|m| m.as_str()).unwrap_or("");
-            let value = std::env::var(var_name).unwrap_or_else(|_| default_val.to_string());
-            result.push_str(&value);
-        } else {
-            warn!(
-                "EJS expression '{}' is not supported; substituting empty string",
-                expr
-            );
-        }
-        last = full.end();
-    }
-    result.push_str(&content[last..]);
-    let content = result;
-
-    // Strip remaining `<% ... %>` control blocks (non-expression tags).
-    if EJS_STMT_RE.is_match(&content) {
-        warn!("EJS statement blocks (<% ... %>) are not supported and will be removed");
+    let rendered = rift_lint::render_ejs(content, config_path)?;
+    for tag in &rendered.unsupported {
+        warn!("EJS tag '{tag}' is not supported; substituting empty string");
     }
-    Ok(EJS_STMT_RE.replace_all(&content, "").to_string())
+    Ok(rendered.text)
 }
 
 #[cfg(test)]
@@ -719,6 +600,51 @@ mod tests {
         assert_eq!(result, "[1,2,3]");
     }
 
+    #[test]
+    fn ejs_include_call_syntax() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("partial.json"), r#"{"port": 4545}"#).unwrap();
+        let content = r#"{"imposters": [<%- include('partial.json') %>]}"#;
+        let config_path = dir.path().join("config.ejs");
+        assert_eq!(
+            preprocess_ejs(content, &config_path).unwrap(),
+            r#"{"imposters": [{"port": 4545}]}"#
+        );
+    }
+
+    /// A nested include is relative to the file that includes it, as in EJS.
+    #[test]
+    fn ejs_nested_include_is_relative_to_including_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("imposters")).unwrap();
+        std::fs::write(
+            dir.path().join("imposters/api.ejs"),
+            r#"{"port": 4545, "stubs": [<% include stubs.json %>]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("imposters/stubs.json"),
+            r#"{"responses": []}"#,
+        )
+        .unwrap();
+        let content = r#"<% include imposters/api.ejs %>"#;
+        let config_path = dir.path().join("config.ejs");
+        assert_eq!(
+            preprocess_ejs(content, &config_path).unwrap(),
+            r#"{"port": 4545, "stubs": [{"responses": []}]}"#
+        );
+    }
+
+    #[test]
+    fn ejs_self_include_is_an_error_not_a_stack_overflow() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("loop.ejs"), "<% include loop.ejs %>").unwrap();
+        let content = "<% include loop.ejs %>";
+        let config_path = dir.path().join("config.ejs");
+        let err = preprocess_ejs(content, &config_path).unwrap_err();
+        assert!(err.to_string().contains("include itself"), "{err}");
+    }
+
     #[test]
     fn test_ejs_missing_include_is_fatal_error() {
         let content = r#"<% include 'nonexistent.json' %>"#;
@@ -770,6 +696,19 @@ mod tests {
         );
     }
 
+    /// Mountebank's own form passes the config's `filename` first.
+    #[test]
+    fn ejs_stringify_accepts_mountebank_filename_argument() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("inject.js"), "a\nb").unwrap();
+        let content = r#"{"inject": "<%- stringify(filename, 'inject.js') %>"}"#;
+        let config_path = dir.path().join("config.ejs");
+        assert_eq!(
+            preprocess_ejs(content, &config_path).unwrap(),
+            r#"{"inject": "a\nb"}"#
+        );
+    }
+
     #[test]
     fn ejs_stringify_missing_file_is_fatal_error() {
         let content = r#"{"inject": "<%- stringify('nope-355.js') %>"}"#;
@@ -783,7 +722,7 @@ mod tests {
     }
 
     /// Pins the ordering invariant documented at the `<%- stringify %>` step: it must run BEFORE
-    /// the `EJS_STMT_RE` catch-all, which also matches `<%- ... %>` and would otherwise silently
+    /// the statement catch-all, which also matches `<%- ... %>` and would otherwise silently
     /// eat the tag — wrong output, no error. Neither tag type alone catches a reordering.
     #[test]
     fn ejs_stringify_survives_when_statement_blocks_present() {
@@ -810,53 +749,4 @@ mod tests {
         let path = PathBuf::from("config.json");
         assert_eq!(preprocess_ejs(content, &path).unwrap(), r#"{"a": 1}"#);
     }
-
-    #[test]
-    fn ejs_statics_match_their_tags() {
-        use super::{EJS_ENV_VAR_RE, EJS_EXPR_RE, EJS_INCLUDE_RE, EJS_STMT_RE, EJS_STRINGIFY_RE};
-
-        assert_eq!(
-            EJS_INCLUDE_RE
-                .captures(r#"<% include 'a/b.json' %>"#)
-                .unwrap()[1]
-                .to_string(),
-            "a/b.json"
-        );
-        assert_eq!(
-            EJS_INCLUDE_RE.captures("<% include bare.json %>").unwrap()[1].to_string(),
-            "bare.json"
-        );
-
-        assert_eq!(
-            EJS_STRINGIFY_RE
-                .captures(r#"<%- stringify('inject.js') %>"#)
-                .unwrap()[1]
-                .to_string(),
-            "inject.js"
-        );
-
-        assert_eq!(
-            EJS_EXPR_RE.captures("<%= process.env.HOST %>").unwrap()[1].to_string(),
-            "process.env.HOST"
-        );
-
-        let env_cap = EJS_ENV_VAR_RE
-            .captures("process.env.PORT || '4545'")
-            .unwrap();
-        assert_eq!(env_cap[1].to_string(), "PORT");
-        assert_eq!(env_cap[2].to_string(), "4545");
-        assert!(
-            EJS_ENV_VAR_RE
-                .captures("process.env.HOST")
-                .unwrap()
-                .get(2)
-                .is_none()
-        );
-        assert!(EJS_ENV_VAR_RE.captures("someOtherExpr()").is_none());
-
-        // (?s) dotall: a statement block spanning newlines is one match.
-        assert!(EJS_STMT_RE.is_match("<% if (x) {\n y();\n} %>"));
-        // `<%=` is an expression tag, not a statement — the catch-all must not eat it.
-        assert!(!EJS_STMT_RE.is_match("<%= process.env.HOST %>"));
-    }
 }
@@ -0,0 +1,160 @@
+//! Live reload of the startup config source. `--watch-configfile` polls the `--configfile` for
+//! edits, and on Unix a `SIGHUP` asks for a reload of whichever source the server started from.
+//! Both run the same incremental reload as `POST /admin/reload`, which logs what it changed.
+
+use crate::admin_api::handle_reload;
+use crate::cluster::{Change, Cluster};
+use crate::config_loader::ConfigSource;
+use crate::imposter::ImposterManager;
+use http_body_util::BodyExt;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+/// How often a watched config file is checked for changes.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Pause between seeing a change and reading the file, so an editor that saves in several writes
+/// is read once it is done.
+const SETTLE: Duration = Duration::from_millis(200);
+
+/// What identifies a version of the watched file: its modification time and length (the length
+/// catches a rewrite within a coarse mtime tick). `None` while the file is missing.
+type Fingerprint = Option<(Option<SystemTime>, u64)>;
+
+fn fingerprint(path: &Path) -> Fingerprint {
+    std::fs::metadata(path)
+        .ok()
+        .map(|meta| (meta.modified().ok(), meta.len()))
+}
+
+/// Reload `source` on `SIGHUP` and, with `watch`, whenever a `--configfile` source changes on
+/// disk, until `stop` fires. A reload that succeeds pushes the new imposter set to `cluster`'s
+/// peers.
+pub fn spawn_config_reloader(
+    manager: &Arc<ImposterManager>,
+    source: Arc<ConfigSource>,
+    allow_injection: bool,
+    watch: bool,
+    cluster: Option<Cluster>,
+    stop: CancellationToken,
+) {
+    let watched = match (&*source, watch) {
+        (ConfigSource::File { path, .. }, true) => Some(path.clone()),
+        _ => None,
+    };
+    let manager = Arc::clone(manager);
+    tokio::spawn(async move {
+        let mut hangup = listen_for_hangup();
+        let mut seen = watched.as_deref().map(fingerprint);
+        let mut tick = tokio::time::interval(WATCH_INTERVAL);
+        tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        if let Some(path) = &watched {
+            info!("Watching {path:?} for changes");
+        }
+        loop {
+            let trigger = tokio::select! {
+                () = stop.cancelled() => return,
+                () = hangup_received(&mut hangup) => "SIGHUP",
+                _ = tick.tick(), if watched.is_some() => {
+                    let path = watched.as_deref().expect("guarded by the select condition");
+                    if Some(fingerprint(path)) == seen {
+                        continue;
+                    }
+                    tokio::time::sleep(SETTLE).await;
+                    seen = Some(fingerprint(path));
+                    "config file change"
+                }
+            };
+            reload(
+                &manager,
+                &source,
+                allow_injection,
+                cluster.as_ref(),
+                trigger,
+            )
+            .await;
+        }
+    });
+}
+
+/// Run one reload, logging why it ran and, when it fails, why.
+async fn reload(
+    manager: &Arc<ImposterManager>,
+    source: &Arc<ConfigSource>,
+    allow_injection: bool,
+    cluster: Option<&Cluster>,
+    trigger: &str,
+) {
+    info!("Reloading the config source ({trigger})");
+    let response = handle_reload(
+        Arc::clone(manager),
+        Some(Arc::clone(source)),
+        allow_injection,
+    )
+    .await;
+    if response.status().is_success() {
+        if let Some(cluster) = cluster {
+            cluster.replicate(Change::Replaced);
+        }
+        return;
+    }
+    let status = response.status();
+    let Ok(body) = response.into_body().collect().await;
+    let message = serde_json::from_slice::<serde_json::Value>(&body.to_bytes())
+        .ok()
+        .and_then(|body| body["errors"][0]["message"].as_str().map(str::to_string))
+        .unwrap_or_else(|| status.to_string());
+    warn!("Config reload ({trigger}) failed: {message}");
+}
+
+#[cfg(unix)]
+type Hangup = Option<tokio::signal::unix::Signal>;
+#[cfg(not(unix))]
+type Hangup = ();
+
+#[cfg(unix)]
+fn listen_for_hangup() -> Hangup {
+    use tokio::signal::unix::{SignalKind, signal};
+    signal(SignalKind::hangup())
+        .inspect_err(|e| warn!("SIGHUP reloads are unavailable: {e}"))
+        .ok()
+}
+
+#[cfg(not(unix))]
+fn listen_for_hangup() -> Hangup {}
+
+/// Resolves on the next `SIGHUP`; never, where there is none.
+#[cfg(unix)]
+async fn hangup_received(hangup: &mut Hangup) {
+    if let Some(signal) = hangup
+        && signal.recv().await.is_some()
+    {
+        return;
+    }
+    std::future::pending().await
+}
+
+#[cfg(not(unix))]
+async fn hangup_received(_: &mut Hangup) {
+    std::future::pending().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprint_tracks_length_and_absence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("imposters.json");
+        assert_eq!(fingerprint(&path), None);
+        std::fs::write(&path, "{}").unwrap();
+        let first = fingerprint(&path);
+        assert!(first.is_some());
+        std::fs::write(&path, r#"{"imposters":[]}"#).unwrap();
+        assert_ne!(fingerprint(&path), first);
+    }
+}
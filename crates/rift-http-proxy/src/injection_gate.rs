@@ -34,14 +34,19 @@ pub const GATED_SCRIPT_SURFACES: &str = "inject/decorate/shellTransform/JS-funct
 
 /// True if `config`'s stubs carry a scripting surface gated by `--allowInjection`: an inject
 /// response, a `decorate` behavior, a `shellTransform`, a `wait` expressed as a JS function, a
-/// predicate `inject`, a `predicateGenerators.inject`, or `_rift.script`.
+/// predicate `inject`, a `predicateGenerators.inject`, or `_rift.script` — or imposter-level
+/// `defaultBehaviors` carrying any of the behavior surfaces.
 ///
 /// The classifier behind every `allowInjection` door. A door calls this to decide admission and
 /// supplies its own failure semantics — this only answers the question, and answers it identically
 /// for all of them. Classification fails **closed**: a `_behaviors` block that cannot be parsed is
 /// treated as scripted rather than admitted as safe.
 pub fn config_uses_script_surface(config: &ImposterConfig) -> bool {
-    stubs_contain_script_surface(&config.stubs)
+    config
+        .default_behaviors
+        .as_ref()
+        .is_some_and(raw_behaviors_are_scripted)
+        || stubs_contain_script_surface(&config.stubs)
 }
 
 /// The explicit ports of every config in `configs` that trips [`config_uses_script_surface`], as a
@@ -135,8 +140,12 @@ fn response_has_script_surface(response: &StubResponse) -> bool {
 /// Fail-closed lives in `wait_is_plainly_numeric`: a `wait` is waved through only when it is
 /// provably a delay, never merely because it failed to parse.
 fn raw_behaviors_are_scripted(behaviors: &serde_json::Value) -> bool {
+    // An ordered `_behaviors` array executes each entry, so it is scripted if any entry is.
+    if let Some(entries) = behaviors.as_array() {
+        return entries.iter().any(raw_behaviors_are_scripted);
+    }
     let Some(obj) = behaviors.as_object() else {
-        // Not an object (e.g. an array) — no key this gate recognizes, so nothing it can
+        // Neither an object nor an array — no key this gate recognizes, so nothing it can
         // classify as executable. Such a block does not parse into `ResponseBehaviors` either,
         // so it is inert: dropped at construction, with `new_is` logging the drop.
         return false;
@@ -26,6 +26,15 @@ pub mod injection_gate;
 // ===== Admin HTTP server (control plane — server crate only) =====
 pub mod admin_api;
 
+// Outgoing webhooks fed by the admin event bus: lifecycle, recorded-stub and traffic notifications
+pub mod webhooks;
+
+// Cluster sync: replicate admin mutations to peer nodes and aggregate their request counts
+pub mod cluster;
+
+// Config-source hot reload: --watch-configfile polling and SIGHUP
+pub mod config_watch;
+
 // Inbound forward-proxy intercept listener (TLS-MITM, epic #394 slice 3)
 pub mod intercept;
 
@@ -42,13 +42,15 @@ const ACTIVE_ALLOCATOR: &str = "jemalloc";
 const ACTIVE_ALLOCATOR: &str = "system";
 
 use clap::Parser;
+use rift_http_proxy::admin_api::LogBuffer;
 use rift_http_proxy::bootstrap::{
-    DEFAULT_PIDFILE, apply_rcfile_defaults, save_imposters, stop_for_restart, stop_server,
+    DEFAULT_PIDFILE, apply_rcfile_defaults, export_recordings, generate_openapi, import_har,
+    import_wiremock, save_imposters, stop_for_restart, stop_server,
 };
 use rift_http_proxy::healthcheck;
 use rift_http_proxy::runtime;
 use rift_http_proxy::script_cli;
-use rift_http_proxy::server::{Cli, Commands, ServerBuilder};
+use rift_http_proxy::server::{Cli, Commands, OpenapiAction, ServerBuilder};
 use tracing::{info, warn};
 use tracing_subscriber::{EnvFilter, Layer, fmt, prelude::*};
 
@@ -117,10 +119,13 @@ fn main() -> Result<(), anyhow::Error> {
         None
     };
 
+    // Recent entries for the admin API's `GET /logs`, filtered like the console.
+    let log_buffer = LogBuffer::default();
     tracing_subscriber::registry()
         .with(fmt::layer())
         .with(env_filter)
         .with(file_layer)
+        .with(log_buffer.clone())
         .init();
 
     // Handle subcommands
@@ -139,12 +144,45 @@ fn main() -> Result<(), anyhow::Error> {
         }) => {
             return save_imposters(&cli.host, cli.port, savefile, *remove_proxies);
         }
+        Some(Commands::Export {
+            format,
+            imposter,
+            output,
+        }) => {
+            return export_recordings(&cli.host, cli.port, format, *imposter, output.as_deref());
+        }
+        Some(Commands::Import {
+            har,
+            wiremock,
+            port,
+            headers,
+        }) => {
+            return match (har, wiremock) {
+                (Some(har), _) => import_har(&cli.host, cli.port, har, *port, headers),
+                (None, Some(mappings)) => import_wiremock(&cli.host, cli.port, mappings, *port),
+                // clap requires exactly one of the two.
+                (None, None) => unreachable!("rift import needs --har or --wiremock"),
+            };
+        }
+        Some(Commands::Openapi {
+            action:
+                OpenapiAction::Generate {
+                    imposter,
+                    format,
+                    output,
+                },
+        }) => {
+            return generate_openapi(&cli.host, cli.port, *imposter, format, output.as_deref());
+        }
         Some(Commands::Replay { configfile }) => {
             // Load the config file and start
-            return run_mountebank_mode(Cli {
-                configfile: Some(configfile.clone()),
-                ..cli
-            });
+            return run_mountebank_mode(
+                Cli {
+                    configfile: Some(configfile.clone()),
+                    ..cli
+                },
+                log_buffer,
+            );
         }
         // Already handled (and returned) above, before the server bootstrap; kept here so the
         // match stays exhaustive and correct if that ever changes.
@@ -172,7 +210,7 @@ fn main() -> Result<(), anyhow::Error> {
             "off"
         }
     );
-    run_mountebank_mode(cli)
+    run_mountebank_mode(cli, log_buffer)
 }
 
 /// The PID file `stop`/`restart` act on: the single global `--pidfile` binding, falling back to
@@ -184,7 +222,7 @@ fn pidfile_or_default(cli: &Cli) -> std::path::PathBuf {
 }
 
 /// Run in Mountebank-compatible mode
-fn run_mountebank_mode(cli: Cli) -> Result<(), anyhow::Error> {
+fn run_mountebank_mode(cli: Cli, log_buffer: LogBuffer) -> Result<(), anyhow::Error> {
     // Write the PID file here — the one place every serving entry converges (plain start, the
     // `restart` fall-through, and `Replay`'s re-entry). Writing it before the subcommand dispatch
     // meant `rift --pidfile p restart` recorded its OWN pid and then SIGTERMed itself, and a
@@ -212,7 +250,7 @@ fn run_mountebank_mode(cli: Cli) -> Result<(), anyhow::Error> {
             let runtime = tokio::runtime::Builder::new_multi_thread()
                 .enable_all()
                 .build()?;
-            runtime.block_on(ServerBuilder::from_cli(cli).run())
+            runtime.block_on(ServerBuilder::from_cli(cli).log_buffer(log_buffer).run())
         }
         runtime::RuntimeTopology::PerCore { workers } => {
             // Control plane: admin API, metrics, savefile machinery, and imposter mutations
@@ -239,6 +277,7 @@ fn run_mountebank_mode(cli: Cli) -> Result<(), anyhow::Error> {
             let result = control.block_on(
                 ServerBuilder::from_cli(cli)
                     .accept_runtimes(workers.handles())
+                    .log_buffer(log_buffer)
                     .run(),
             );
             workers.shutdown();
@@ -200,18 +200,20 @@ pub struct RunReport {
 
 /// The request-object shape scripts see (issue #360 Item 2's `--request` fixture): the same
 /// fields `ScriptRequest` carries, all optional so a minimal fixture (or none at all) is valid.
-#[derive(Debug, Default, serde::Deserialize)]
+#[derive(Debug, Clone, Default, serde::Deserialize)]
 #[serde(rename_all = "camelCase", default)]
-struct RequestFixture {
-    method: Option<String>,
-    path: Option<String>,
-    headers: HashMap<String, String>,
-    query: HashMap<String, String>,
-    path_params: HashMap<String, String>,
-    body: serde_json::Value,
+pub(crate) struct RequestFixture {
+    pub(crate) method: Option<String>,
+    pub(crate) path: Option<String>,
+    pub(crate) headers: HashMap<String, String>,
+    pub(crate) query: HashMap<String, String>,
+    pub(crate) path_params: HashMap<String, String>,
+    pub(crate) body: serde_json::Value,
 }
 
-fn fixture_to_script_request(fixture: RequestFixture) -> crate::scripting::ScriptRequest {
+pub(crate) fn fixture_to_script_request(
+    fixture: RequestFixture,
+) -> crate::scripting::ScriptRequest {
     let raw_body = if fixture.body.is_null() {
         None
     } else {
@@ -295,24 +297,52 @@ pub fn run_run(
         None => RequestFixture::default(),
     };
     let script_request = fixture_to_script_request(fixture);
+    let state = state_entries
+        .iter()
+        .map(|entry| parse_state_entry(entry))
+        .collect::<Result<Vec<_>>>()?;
 
+    // Surface a genuine compile/syntax error as a clean CLI error up front (rather than an
+    // "error:" decision in the report).
+    crate::scripting::ScriptEngine::new(&engine_type, &code, "cli-run")
+        .with_context(|| format!("compiling {}", target.display()))?;
+
+    // A fresh current-thread runtime is built here because the `script` subcommand runs
+    // before/without the server's tokio runtime.
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("building CLI tokio runtime")?;
+    runtime.block_on(run_script(
+        engine_type,
+        code,
+        script_request,
+        state,
+        flow_id,
+    ))
+}
+
+/// Execute a `respond` script against `request`, with `state` seeded into `flow_id` — the core
+/// of `rift script run`, shared with the admin API's `POST /_scripts/run`.
+pub async fn run_script(
+    engine_type: String,
+    code: String,
+    request: crate::scripting::ScriptRequest,
+    state: Vec<(String, serde_json::Value)>,
+    flow_id: &str,
+) -> Result<RunReport> {
     // A fresh, disposable in-memory store per run — never persisted, exactly like a real
     // request's auto-provisioned flow store when no `_rift.flowState` backend is configured. A
     // generous TTL keeps this run's seeded state alive for the run itself; nothing outlives the
-    // process either way.
+    // run either way.
     let store = Arc::new(crate::backends::InMemoryFlowStore::new(3600));
-    for entry in state_entries {
-        let (key, value) = parse_state_entry(entry)?;
+    for (key, value) in state {
         store
             .set(flow_id, &key, value)
-            .map_err(|e| anyhow!("seeding --state {entry}: {e}"))?;
+            .map_err(|e| anyhow!("seeding state {key}: {e}"))?;
     }
     let store_dyn: Arc<dyn FlowStore> = store.clone();
 
-    // Surface a genuine compile/syntax error as a clean CLI error up front (rather than an
-    // "error:" decision in the report).
-    crate::scripting::ScriptEngine::new(&engine_type, &code, "cli-run")
-        .with_context(|| format!("compiling {}", target.display()))?;
     let extras = crate::scripting::ScriptCtxExtras {
         flow_id: Some(flow_id.to_string()),
         ..Default::default()
@@ -320,24 +350,20 @@ pub fn run_run(
 
     // Run through the SAME bounded path the real proxy uses (issue #360): `spawn_blocking` +
     // wall-clock timeout with the Rhai abort flag wired in, so an infinite-loop script
-    // TERMINATES the CLI at the deadline instead of hanging it. This also captures `ctx.logger`
-    // output and the duration for free. A fresh current-thread runtime is built here because the
-    // `script` subcommand runs before/without the server's tokio runtime.
-    let runtime = tokio::runtime::Builder::new_current_thread()
-        .enable_all()
-        .build()
-        .context("building CLI tokio runtime")?;
+    // TERMINATES at the deadline instead of hanging. This also captures `ctx.logger` output and
+    // the duration for free.
     let timeout = std::time::Duration::from_millis(crate::scripting::DEFAULT_SCRIPT_TIMEOUT_MS);
-    let (result, entry) =
-        runtime.block_on(crate::scripting::should_inject_bounded_with_ctx_traced(
-            engine_type,
-            code,
-            "cli-run".to_string(),
-            script_request,
-            store_dyn,
-            timeout,
-            extras,
-        ));
+    let (result, entry) = crate::scripting::should_inject_bounded_with_ctx_traced(
+        engine_type,
+        code,
+        "script-run".to_string(),
+        request,
+        store_dyn,
+        timeout,
+        crate::scripting::ScriptLimits::default(),
+        extras,
+    )
+    .await;
 
     let mut keys = store.keys_for_flow(flow_id);
     keys.sort();
@@ -4,15 +4,18 @@
 //! config loading, and metrics around their own `ImposterManager` without forking the
 //! binary.
 
-use crate::admin_api::{AdminApiServer, DEFAULT_ADMIN_PORT, RunningAdminApi};
+use crate::admin_api::{AdminApiServer, AdminAuth, DEFAULT_ADMIN_PORT, LogBuffer, RunningAdminApi};
+use crate::cluster::{Cluster, parse_peer_url};
 use crate::config_loader::{self, ConfigSource};
+use crate::config_watch::spawn_config_reloader;
 use crate::extensions::metrics;
 use crate::front_door::{CompiledRoutes, RouteTable, RunningFrontDoor, bind_front_door};
 use crate::imposter::{
-    ImposterConfig, ImposterManager, ScriptBaseDir, TlsDefaults, resolve_scripts,
+    ImposterConfig, ImposterManager, ProxyTap, ScriptBaseDir, TlsDefaults, resolve_scripts,
 };
 use crate::injection_gate::GATED_SCRIPT_SURFACES;
 use crate::intercept_control::{InterceptControl, InterceptStartOptions};
+use crate::webhooks::{parse_webhook_event, parse_webhook_url, spawn_webhooks};
 use arc_swap::ArcSwap;
 use clap::{Parser, Subcommand};
 use std::net::SocketAddr;
@@ -58,10 +61,25 @@ pub struct Cli {
     #[arg(long, value_name = "FILE", env = "MB_CONFIGFILE")]
     pub configfile: Option<PathBuf>,
 
+    /// Watch --configfile and apply edits live (SIGHUP reloads it too, watched or not)
+    #[arg(long, requires = "configfile", env = "RIFT_WATCH_CONFIGFILE")]
+    pub watch_configfile: bool,
+
     /// Directory for persistent imposter storage
     #[arg(long, value_name = "DIR", env = "MB_DATADIR")]
     pub datadir: Option<PathBuf>,
 
+    /// Write recorded proxy response bodies larger than this many bytes to files under
+    /// `--datadir` instead of keeping them in memory. Ignored without `--datadir`.
+    #[arg(long, value_name = "BYTES", env = "RIFT_RECORDING_SPILL_THRESHOLD")]
+    pub recording_spill_threshold: Option<usize>,
+
+    /// Append every proxied request/response pair, with its timing and the stub that routed it,
+    /// as one NDJSON line to this file (`-` for stdout). Independent of recording: transparent
+    /// proxies and `defaultForward` are written too.
+    #[arg(long, value_name = "PATH", env = "RIFT_PROXY_TAP")]
+    pub proxy_tap: Option<String>,
+
     /// Root directory `_rift.script` `file:` references resolve under for admin-API-created
     /// imposters (issue #356). A resolved path that escapes this root is rejected. Without it,
     /// admin-API `file:` script references are rejected outright (`--configfile`/`--datadir`
@@ -147,9 +165,27 @@ pub struct Cli {
     #[arg(long, value_name = "FILE")]
     pub protofile: Option<PathBuf>,
 
-    /// Require this token in the Authorization header for all admin API requests
+    /// Require this token in the Authorization header for all admin API requests (repeatable;
+    /// any of the given tokens is accepted, bare or as `Bearer <TOKEN>`)
     #[arg(long, value_name = "TOKEN", env = "MB_APIKEY")]
-    pub api_key: Option<String>,
+    pub api_key: Vec<String>,
+
+    /// Accept HTTP basic credentials on the admin API (repeatable); combines with --api-key
+    #[arg(
+        long,
+        value_name = "USER:PASSWORD",
+        env = "RIFT_ADMIN_BASIC_AUTH",
+        value_parser = parse_basic_credentials
+    )]
+    pub admin_basic_auth: Vec<(String, String)>,
+
+    /// Accept this token on the admin API for reads only; mutations get 403 (repeatable)
+    #[arg(long, value_name = "TOKEN", env = "RIFT_READONLY_API_KEY")]
+    pub readonly_api_key: Vec<String>,
+
+    /// Serve the admin API read-only: every mutation gets 403, whatever the credentials
+    #[arg(long, env = "RIFT_READONLY")]
+    pub readonly: bool,
 
     /// RC file with default flag values (Mountebank compatibility; partial support — port/host/loglevel only)
     #[arg(long, value_name = "FILE")]
@@ -168,6 +204,45 @@ pub struct Cli {
     #[arg(long, env = "RIFT_NO_SELF_SIGNED_TLS")]
     pub no_self_signed_tls: bool,
 
+    /// POST a JSON notification to this URL on imposter changes, recorded stubs, unmatched requests
+    /// and injected faults. Repeatable; `RIFT_WEBHOOK` takes a comma-separated list.
+    #[arg(
+        long = "webhook",
+        value_name = "URL",
+        env = "RIFT_WEBHOOK",
+        value_delimiter = ',',
+        value_parser = parse_webhook_url
+    )]
+    pub webhooks: Vec<String>,
+
+    /// Limit `--webhook` deliveries to these events (comma-separated, e.g.
+    /// `imposter.created,request.noMatch`). Every event is sent when unset.
+    #[arg(
+        long,
+        value_name = "EVENTS",
+        env = "RIFT_WEBHOOK_EVENTS",
+        value_delimiter = ',',
+        value_parser = parse_webhook_event
+    )]
+    pub webhook_events: Vec<String>,
+
+    /// Admin API URL of another Rift node to keep in sync: imposter changes made here are pushed
+    /// to it, and `GET /imposters` sums request counts across nodes. Repeatable; every node should
+    /// list every other. `RIFT_PEERS` takes a comma-separated list.
+    #[arg(
+        long = "peer",
+        value_name = "URL",
+        env = "RIFT_PEERS",
+        value_delimiter = ',',
+        value_parser = parse_peer_url
+    )]
+    pub peers: Vec<String>,
+
+    /// Token sent as `Bearer <TOKEN>` to `--peer` nodes that require an API key; defaults to the
+    /// first `--api-key`
+    #[arg(long, value_name = "TOKEN", env = "RIFT_PEER_API_KEY")]
+    pub peer_api_key: Option<String>,
+
     /// Start a TLS-MITM intercept/redirect proxy listener on this port (epic #394). Off when
     /// unset. Configure rules and export the CA via the admin API's `/intercept/*` routes.
     #[arg(long, value_name = "PORT", env = "RIFT_INTERCEPT_PORT")]
@@ -223,11 +298,62 @@ pub enum Commands {
         #[arg(long, default_value = "mb.json")]
         savefile: PathBuf,
 
-        /// Strip proxy-recorded stubs (those with recordedFrom set) from the output
+        /// Save only what replays offline: drop proxy responses (and stubs left with none), and
+        /// each imposter's `defaultForward` and `_rift.proxy` upstream
         #[arg(long)]
         remove_proxies: bool,
     },
 
+    /// Export recorded traffic (request journal plus saved proxy responses) from a running
+    /// server as a HAR file, for browser devtools, Charles, or k6, or as a Postman collection.
+    Export {
+        /// Output format: `har` or `postman` (a collection, plus its environment beside
+        /// `--output`)
+        #[arg(long, default_value = "har")]
+        format: String,
+
+        /// Export only this imposter (default: every imposter, merged into one file)
+        #[arg(long, value_name = "PORT")]
+        imposter: Option<u16>,
+
+        /// Output file path (default: stdout)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+
+    /// Create an imposter on a running server from a HAR capture (one stub per distinct request:
+    /// method, path, query, selected headers, answering with the recorded responses) or from
+    /// WireMock stub mappings.
+    Import {
+        /// HAR file to import
+        #[arg(
+            long,
+            value_name = "FILE",
+            required_unless_present = "wiremock",
+            conflicts_with = "wiremock"
+        )]
+        har: Option<PathBuf>,
+
+        /// WireMock mappings to import: a mapping file, a `mappings` directory, or a WireMock
+        /// root holding `mappings/` and `__files/`
+        #[arg(long, value_name = "PATH")]
+        wiremock: Option<PathBuf>,
+
+        /// Port of the imposter to create
+        #[arg(long, value_name = "PORT", required = true)]
+        port: u16,
+
+        /// Request header to add to the generated predicates (repeatable; HAR only)
+        #[arg(long = "header", value_name = "NAME", conflicts_with = "wiremock")]
+        headers: Vec<String>,
+    },
+
+    /// OpenAPI tools for recorded traffic
+    Openapi {
+        #[command(subcommand)]
+        action: OpenapiAction,
+    },
+
     /// Replay saved imposters
     Replay {
         /// Input file path
@@ -256,6 +382,26 @@ pub enum Commands {
     },
 }
 
+/// `rift openapi <generate>`: work with OpenAPI documents on a running server.
+#[derive(Subcommand, Debug, Clone)]
+pub enum OpenapiAction {
+    /// Infer an OpenAPI 3 document (paths, methods, status codes, representative schemas) from
+    /// an imposter's recorded requests and responses.
+    Generate {
+        /// Imposter whose recorded traffic to describe
+        #[arg(long, value_name = "PORT", required = true)]
+        imposter: u16,
+
+        /// Output format (`json` or `yaml`)
+        #[arg(long, default_value = "yaml")]
+        format: String,
+
+        /// Output file path (default: stdout)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+}
+
 /// `rift script <check|run>` (issue #360): scripting DX tools that need neither an admin API nor
 /// a running imposter — everything runs synchronously, in-process, against a fixture.
 #[derive(Subcommand, Debug, Clone)]
@@ -316,6 +462,7 @@ pub struct ServerBuilder {
     cli: Cli,
     manager: Option<Arc<ImposterManager>>,
     accept_runtimes: Vec<tokio::runtime::Handle>,
+    logs: Option<LogBuffer>,
 }
 
 impl ServerBuilder {
@@ -326,6 +473,7 @@ impl ServerBuilder {
             cli,
             manager: None,
             accept_runtimes: Vec::new(),
+            logs: None,
         }
     }
 
@@ -347,6 +495,13 @@ impl ServerBuilder {
         self
     }
 
+    /// Serve `GET /logs` from `logs`, the buffer the caller installed as a `tracing` layer.
+    #[must_use]
+    pub fn log_buffer(mut self, logs: LogBuffer) -> Self {
+        self.logs = Some(logs);
+        self
+    }
+
     /// Load configs, spawn the metrics server, and run the admin API server — the
     /// binary's Mountebank-mode behavior. Runs until the admin server stops or fails.
     pub async fn run(self) -> anyhow::Result<()> {
@@ -387,16 +542,32 @@ impl ServerBuilder {
                     default_key,
                     allow_self_signed: !cli.no_self_signed_tls,
                 };
+                let proxy_tap = cli.proxy_tap.as_deref().map(ProxyTap::open).transpose()?;
                 Arc::new(
                     ImposterManager::with_datadir(cli.datadir.clone())
+                        .with_recording_spill_threshold(cli.recording_spill_threshold)
+                        .with_proxy_tap(proxy_tap)
                         .with_tls_defaults(tls_defaults)
                         .with_accept_runtimes(self.accept_runtimes),
                 )
             }
         };
 
+        // Subscribe before any config is loaded so the imposters it creates are announced too.
+        spawn_webhooks(&manager, &cli.webhooks, &cli.webhook_events)?;
+        let cluster = if cli.peers.is_empty() {
+            None
+        } else {
+            let api_key = cli
+                .peer_api_key
+                .clone()
+                .or_else(|| cli.api_key.first().cloned());
+            Some(Cluster::spawn(&manager, &cli.peers, api_key)?)
+        };
+
         let mut intercept_block = None;
         let mut routes_block: Option<RouteTable> = None;
+        let mut failed_imposters = Vec::new();
         if let Some(ref configfile) = cli.configfile {
             let loaded = load_imposters_from_file(
                 &manager,
@@ -408,9 +579,17 @@ impl ServerBuilder {
             .await?;
             intercept_block = loaded.intercept;
             routes_block = loaded.routes;
+            failed_imposters = loaded.failed_ports;
         }
         if let Some(ref datadir) = cli.datadir {
-            load_imposters_from_datadir(&manager, datadir, cli.allow_injection).await?;
+            failed_imposters
+                .extend(load_imposters_from_datadir(&manager, datadir, cli.allow_injection).await?);
+        }
+        // A node with nothing of its own to serve takes the cluster's imposters.
+        if let Some(ref cluster) = cluster
+            && manager.count() == 0
+        {
+            cluster.join(&manager, cli.allow_injection).await;
         }
 
         // Bind the metrics server now so a `:0` request can report its port. A bind failure
@@ -478,20 +657,49 @@ impl ServerBuilder {
             warn!("--protofile is not supported; custom protocols are not yet implemented");
         }
 
+        let options = reported_options(&cli);
+        let auth = cli
+            .api_key
+            .into_iter()
+            .fold(AdminAuth::default(), AdminAuth::with_api_key);
+        let auth = cli
+            .readonly_api_key
+            .into_iter()
+            .fold(auth, AdminAuth::with_read_only_api_key);
+        let auth = cli
+            .admin_basic_auth
+            .iter()
+            .fold(auth, |auth, (user, password)| {
+                auth.with_basic_credentials(user, password)
+            });
         // Retain the config source so POST /admin/reload can re-read it (issue #197).
         // Injection gating is threaded explicitly (issue #342) rather than read from env.
-        let mut server = AdminApiServer::new(addr, manager, cli.api_key)
-            .with_allow_injection(cli.allow_injection);
+        let reload_manager = Arc::clone(&manager);
+        let mut server = AdminApiServer::new(addr, manager, None)
+            .with_auth(auth)
+            .with_read_only(cli.readonly)
+            .with_allow_injection(cli.allow_injection)
+            .with_reported_options(options)
+            .with_failed_imposters(failed_imposters);
+        if let Some(logs) = self.logs {
+            server = server.with_log_buffer(logs);
+        }
         if let Some(scripts_dir) = cli.scripts_dir {
             server = server.with_scripts_dir(scripts_dir);
         }
-        if let Some(configfile) = cli.configfile {
-            server = server.with_config_source(ConfigSource::File {
+        if let Some(cluster) = &cluster {
+            server = server.with_cluster(cluster.clone());
+        }
+        let config_source = match (cli.configfile, cli.datadir) {
+            (Some(configfile), _) => Some(ConfigSource::File {
                 path: configfile,
                 no_parse: cli.no_parse,
-            });
-        } else if let Some(datadir) = cli.datadir {
-            server = server.with_config_source(ConfigSource::Dir(datadir));
+            }),
+            (None, Some(datadir)) => Some(ConfigSource::Dir(datadir)),
+            (None, None) => None,
+        };
+        if let Some(source) = config_source.clone() {
+            server = server.with_config_source(source);
         }
 
         // Intercept/TLS-MITM listener (epic #394 + runtime lifecycle #493). The control slot is
@@ -564,11 +772,25 @@ impl ServerBuilder {
                 return Err(e);
             }
         };
+        // Started last, so a failed start leaves no reloader behind. SIGHUP re-reads whichever
+        // source the server started from; `--watch-configfile` adds polling of the file.
+        let reloader = CancellationToken::new();
+        if let Some(source) = config_source {
+            spawn_config_reloader(
+                &reload_manager,
+                Arc::new(source),
+                cli.allow_injection,
+                cli.watch_configfile,
+                cluster,
+                reloader.clone(),
+            );
+        }
         Ok(RunningServer {
             admin,
             metrics,
             intercept,
             front_door,
+            reloader,
         })
     }
 }
@@ -580,6 +802,7 @@ pub struct RunningServer {
     metrics: Option<RunningMetrics>,
     intercept: InterceptControl,
     front_door: Option<RunningFrontDoor>,
+    reloader: CancellationToken,
 }
 
 impl RunningServer {
@@ -603,6 +826,7 @@ impl RunningServer {
             metrics: None,
             intercept: InterceptControl::default(),
             front_door: None,
+            reloader: CancellationToken::new(),
         }
     }
     /// The bound admin API address (resolves a `:0` request to the assigned port).
@@ -665,6 +889,7 @@ impl RunningServer {
     /// Takes `&self` (issue #806) so it composes with [`wait`](Self::wait) and can be called
     /// through a shared handle; every underlying shutdown is already idempotent.
     pub async fn shutdown(&self) {
+        self.reloader.cancel();
         self.admin.shutdown().await;
         if let Some(metrics) = &self.metrics {
             metrics.shutdown().await;
@@ -1011,6 +1236,37 @@ fn parse_front_door_addr(value: &str) -> anyhow::Result<SocketAddr> {
     )
 }
 
+/// The startup options `GET /config` reports, under Mountebank's option names.
+fn reported_options(cli: &Cli) -> serde_json::Map<String, serde_json::Value> {
+    let path = |p: &Option<PathBuf>| p.as_ref().map(|p| p.display().to_string());
+    serde_json::json!({
+        "host": cli.host,
+        "localOnly": cli.local_only,
+        "ipWhitelist": cli.ip_whitelist.clone().unwrap_or_else(|| vec!["*".to_string()]),
+        "loglevel": cli.loglevel,
+        "debug": cli.debug,
+        "mock": cli.mock,
+        "configfile": path(&cli.configfile),
+        "datadir": path(&cli.datadir),
+        "pidfile": path(&cli.pidfile),
+        "logfile": path(&cli.log),
+        "nologfile": cli.nologfile,
+        "origin": cli.origin,
+        "readonly": cli.readonly,
+    })
+    .as_object()
+    .cloned()
+    .unwrap_or_default()
+}
+
+/// Parse an `--admin-basic-auth` value, `USER:PASSWORD`. The password may itself contain `:`.
+fn parse_basic_credentials(value: &str) -> anyhow::Result<(String, String)> {
+    match value.split_once(':') {
+        Some((user, password)) if !user.is_empty() => Ok((user.to_string(), password.to_string())),
+        _ => anyhow::bail!("--admin-basic-auth expects USER:PASSWORD"),
+    }
+}
+
 /// The `--intercept-*` flags the operator supplied, by long name; empty when none were.
 fn cli_intercept_flags(cli: &Cli) -> Vec<&'static str> {
     [
@@ -1087,6 +1343,8 @@ fn configfile_intercept_injection_error(
 struct ConfigFileStartOptions {
     intercept: Option<InterceptStartOptions>,
     routes: Option<RouteTable>,
+    /// Ports of the file's imposters that could not be created, reported by `GET /readyz`.
+    failed_ports: Vec<u16>,
 }
 
 /// Load imposters from a JSON config file, returning the file's optional `intercept` and `routes`
@@ -1123,24 +1381,29 @@ async fn load_imposters_from_file(
         }
     }
 
+    let mut failed_ports = Vec::new();
     for config in loaded.imposters {
         info!(
             "Creating imposter on port {:?} from configfile",
             config.port
         );
+        let port = config.port;
         match manager.create_imposter(config).await {
             Ok(port) => info!("Created imposter on port {}", port),
-            Err(e) => error!("Failed to create imposter: {}", e),
+            Err(e) => {
+                error!("Failed to create imposter: {}", e);
+                failed_ports.extend(port);
+            }
         }
     }
 
     Ok(ConfigFileStartOptions {
         intercept: loaded.intercept,
         routes: loaded.routes,
+        failed_ports,
     })
 }
 
-/// Load imposters from a data directory
 /// A datadir `*.json` file that could not be turned into a served imposter, kept so the loader can
 /// surface all of them together instead of dropping each with only a per-file log line (issue #532).
 struct SkippedImposterFile {
@@ -1228,16 +1491,17 @@ fn read_and_parse_datadir(
     Ok((parsed, skipped))
 }
 
+/// Load imposters from a data directory, returning the ports of those that could not be created.
 async fn load_imposters_from_datadir(
     manager: &Arc<ImposterManager>,
     datadir: &PathBuf,
     allow_injection: bool,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<Vec<u16>> {
     info!("Loading imposters from datadir: {:?}", datadir);
 
     if !datadir.exists() {
         std::fs::create_dir_all(datadir)?;
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     // `file:`/`ref:` scripts in a datadir-loaded imposter resolve relative to the datadir itself,
@@ -1249,15 +1513,20 @@ async fn load_imposters_from_datadir(
     let (parsed, gated) = partition_gated_datadir(parsed, allow_injection);
     skipped.extend(gated);
 
+    let mut failed = Vec::new();
     for (path, config) in parsed {
         info!("Loading imposter on port {:?} from {:?}", config.port, path);
+        let port = config.port;
         match manager.create_imposter(config).await {
             Ok(port) => info!("Created imposter on port {} from {:?}", port, path),
             // Surfaced once, via the aggregated summary below, uniform with the other skip reasons.
-            Err(e) => skipped.push(SkippedImposterFile {
-                path,
-                reason: format!("imposter creation failed: {e}"),
-            }),
+            Err(e) => {
+                failed.extend(port);
+                skipped.push(SkippedImposterFile {
+                    path,
+                    reason: format!("imposter creation failed: {e}"),
+                });
+            }
         }
     }
 
@@ -1265,7 +1534,7 @@ async fn load_imposters_from_datadir(
         error!("{summary}");
     }
 
-    Ok(())
+    Ok(failed)
 }
 
 #[cfg(test)]
@@ -1488,6 +1757,141 @@ mod tests {
         assert_eq!(none.scripts_dir, None);
     }
 
+    #[test]
+    fn admin_credentials_flags_repeat() {
+        let cli = Cli::try_parse_from([
+            "rift",
+            "--api-key",
+            "ci-key",
+            "--api-key",
+            "dev-key",
+            "--admin-basic-auth",
+            "admin:pa:ss",
+        ])
+        .expect("parse");
+        assert_eq!(cli.api_key, ["ci-key", "dev-key"]);
+        assert_eq!(
+            cli.admin_basic_auth,
+            [("admin".to_string(), "pa:ss".to_string())]
+        );
+        assert!(Cli::try_parse_from(["rift", "--admin-basic-auth", "admin"]).is_err());
+        assert!(Cli::try_parse_from(["rift", "--admin-basic-auth", ":secret"]).is_err());
+    }
+
+    #[test]
+    fn import_takes_exactly_one_source() {
+        let cli = Cli::try_parse_from([
+            "rift",
+            "import",
+            "--wiremock",
+            "./mappings",
+            "--port",
+            "8080",
+        ])
+        .expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Import {
+                har: None,
+                wiremock: Some(_),
+                port: 8080,
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["rift", "import", "--port", "8080"]).is_err());
+        assert!(
+            Cli::try_parse_from([
+                "rift",
+                "import",
+                "--har",
+                "a.har",
+                "--wiremock",
+                "m",
+                "--port",
+                "8080"
+            ])
+            .is_err()
+        );
+        assert!(
+            Cli::try_parse_from([
+                "rift",
+                "import",
+                "--wiremock",
+                "m",
+                "--port",
+                "8080",
+                "--header",
+                "accept"
+            ])
+            .is_err(),
+            "--header only applies to HAR"
+        );
+    }
+
+    #[test]
+    fn webhook_flags_repeat_and_validate() {
+        let cli = Cli::try_parse_from([
+            "rift",
+            "--webhook",
+            "http://127.0.0.1:9000/hook",
+            "--webhook",
+            "https://hooks.example.com/rift",
+            "--webhook-events",
+            "imposter.created,request.noMatch",
+        ])
+        .expect("parse");
+        assert_eq!(
+            cli.webhooks,
+            [
+                "http://127.0.0.1:9000/hook",
+                "https://hooks.example.com/rift"
+            ]
+        );
+        assert_eq!(cli.webhook_events, ["imposter.created", "request.noMatch"]);
+        assert!(Cli::try_parse_from(["rift", "--webhook", "ftp://example.com"]).is_err());
+        assert!(Cli::try_parse_from(["rift", "--webhook-events", "request.matched"]).is_err());
+    }
+
+    #[test]
+    fn peer_flags_repeat_and_validate() {
+        let cli = Cli::try_parse_from([
+            "rift",
+            "--peer",
+            "http://rift-b:2525/",
+            "--peer",
+            "http://rift-c:2525",
+            "--peer-api-key",
+            "cluster-key",
+        ])
+        .unwrap();
+        assert_eq!(cli.peers, ["http://rift-b:2525", "http://rift-c:2525"]);
+        assert_eq!(cli.peer_api_key.as_deref(), Some("cluster-key"));
+        assert!(Cli::try_parse_from(["rift", "--peer", "rift-b:2525"]).is_err());
+    }
+
+    #[test]
+    fn watch_configfile_requires_a_configfile() {
+        let cli = Cli::try_parse_from([
+            "rift",
+            "--configfile",
+            "imposters.json",
+            "--watch-configfile",
+        ])
+        .unwrap();
+        assert!(cli.watch_configfile);
+        assert!(Cli::try_parse_from(["rift", "--watch-configfile"]).is_err());
+    }
+
+    #[test]
+    fn readonly_flags_parse() {
+        let cli = Cli::try_parse_from(["rift", "--readonly", "--readonly-api-key", "dev-key"])
+            .expect("parse");
+        assert!(cli.readonly);
+        assert_eq!(cli.readonly_api_key, ["dev-key"]);
+        let none = Cli::try_parse_from(["rift"]).expect("parse");
+        assert!(!none.readonly);
+    }
+
     #[test]
     fn load_or_generate_ca_rules() {
         use std::path::Path;
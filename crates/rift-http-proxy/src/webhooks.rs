@@ -0,0 +1,233 @@
+//! Outgoing webhooks: POST a JSON notification to each `--webhook` URL when an imposter changes, a
+//! proxy records a stub, or a request goes unmatched or gets an injected fault.
+//!
+//! Each URL gets its own subscriber on the manager's [`AdminEventBus`], so a slow receiver only
+//! delays its own deliveries. Delivery is best effort: a failed POST is logged and not retried,
+//! and a receiver that falls behind the bus loses the oldest events (logged as lagged), exactly
+//! like an SSE client. Recorded-request events are not sent; `GET /events` streams those.
+//!
+//! [`AdminEventBus`]: crate::imposter::AdminEventBus
+
+use crate::admin_api::traffic_payload;
+use crate::imposter::{
+    AdminEvent, AdminEventKind, ImposterAction, ImposterManager, TrafficOutcome,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, warn};
+
+/// Bound on one delivery, so a hung receiver cannot stall its subscriber indefinitely.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Every event name a webhook can be limited to with `--webhook-events`.
+pub const WEBHOOK_EVENTS: &[&str] = &[
+    "imposter.created",
+    "imposter.replaced",
+    "imposter.deleted",
+    "imposter.allDeleted",
+    "imposter.stubsChanged",
+    "imposter.enabledChanged",
+    "stub.recorded",
+    "request.noMatch",
+    "request.fault",
+];
+
+/// Parse a `--webhook` value: an absolute `http` or `https` URL.
+pub fn parse_webhook_url(value: &str) -> Result<String, String> {
+    match reqwest::Url::parse(value) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(value.to_string()),
+        Ok(url) => Err(format!(
+            "unsupported scheme '{}' (expected http or https)",
+            url.scheme()
+        )),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Parse one `--webhook-events` entry, which must name one of [`WEBHOOK_EVENTS`].
+pub fn parse_webhook_event(value: &str) -> Result<String, String> {
+    if WEBHOOK_EVENTS.contains(&value) {
+        Ok(value.to_string())
+    } else {
+        Err(format!(
+            "unknown webhook event '{value}' (expected one of {})",
+            WEBHOOK_EVENTS.join(", ")
+        ))
+    }
+}
+
+/// The webhook event name for a bus event, or `None` for events webhooks do not carry.
+fn event_name(kind: &AdminEventKind) -> Option<&'static str> {
+    match kind {
+        AdminEventKind::Lifecycle { action, .. } => Some(match action {
+            ImposterAction::Created => "imposter.created",
+            ImposterAction::Replaced => "imposter.replaced",
+            ImposterAction::Deleted => "imposter.deleted",
+            ImposterAction::AllDeleted => "imposter.allDeleted",
+            ImposterAction::StubsChanged => "imposter.stubsChanged",
+            ImposterAction::EnabledChanged => "imposter.enabledChanged",
+            ImposterAction::StubRecorded => "stub.recorded",
+        }),
+        AdminEventKind::Traffic { outcome, .. } => Some(match outcome {
+            TrafficOutcome::NoMatch => "request.noMatch",
+            TrafficOutcome::Fault(_) => "request.fault",
+        }),
+        AdminEventKind::Request { .. } => None,
+    }
+}
+
+/// The JSON body POSTed for `event`, or `None` when `events` (empty meaning all) filters it out.
+fn payload(event: &AdminEvent, events: &[String]) -> Option<serde_json::Value> {
+    let name = event_name(&event.kind)?;
+    if !events.is_empty() && !events.iter().any(|e| e == name) {
+        return None;
+    }
+    let mut body = match &event.kind {
+        AdminEventKind::Lifecycle { port, .. } => match port {
+            Some(port) => serde_json::json!({ "port": port }),
+            None => serde_json::json!({}),
+        },
+        AdminEventKind::Traffic {
+            port,
+            method,
+            path,
+            outcome,
+        } => traffic_payload(*port, method, path, outcome),
+        AdminEventKind::Request { .. } => return None,
+    };
+    body["event"] = serde_json::json!(name);
+    body["seq"] = serde_json::json!(event.seq);
+    body["timestamp"] =
+        serde_json::json!(chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true));
+    Some(body)
+}
+
+/// Start delivering `events` (all of them when empty) to every URL in `urls`. Each delivery task
+/// ends when the manager, and with it the event bus, is dropped.
+pub fn spawn_webhooks(
+    manager: &ImposterManager,
+    urls: &[String],
+    events: &[String],
+) -> anyhow::Result<()> {
+    if urls.is_empty() {
+        return Ok(());
+    }
+    let client = reqwest::Client::builder()
+        .timeout(DELIVERY_TIMEOUT)
+        .build()
+        .map_err(|e| anyhow::anyhow!("building webhook client: {e}"))?;
+    let events: Arc<[String]> = events.into();
+    for url in urls {
+        let rx = manager.event_bus().subscribe();
+        tokio::spawn(deliver(
+            rx,
+            client.clone(),
+            url.clone(),
+            Arc::clone(&events),
+        ));
+    }
+    Ok(())
+}
+
+async fn deliver(
+    mut rx: tokio::sync::broadcast::Receiver<Arc<AdminEvent>>,
+    client: reqwest::Client,
+    url: String,
+    events: Arc<[String]>,
+) {
+    loop {
+        let event = match rx.recv().await {
+            Ok(event) => event,
+            Err(RecvError::Lagged(missed)) => {
+                warn!("webhook {url} fell behind and dropped {missed} event(s)");
+                continue;
+            }
+            Err(RecvError::Closed) => return,
+        };
+        let Some(body) = payload(&event, &events) else {
+            continue;
+        };
+        match client.post(&url).json(&body).send().await {
+            Ok(response) if response.status().is_success() => {
+                debug!("webhook {url} accepted event {}", event.seq);
+            }
+            Ok(response) => warn!(
+                "webhook {url} answered {} to event {}",
+                response.status(),
+                event.seq
+            ),
+            Err(e) => warn!("webhook {url} delivery of event {} failed: {e}", event.seq),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: AdminEventKind) -> AdminEvent {
+        AdminEvent { seq: 7, kind }
+    }
+
+    #[test]
+    fn every_event_kind_maps_to_a_listed_name() {
+        let kinds = [
+            AdminEventKind::Lifecycle {
+                action: ImposterAction::StubRecorded,
+                port: Some(4545),
+            },
+            AdminEventKind::Traffic {
+                port: 4545,
+                method: "GET".to_string(),
+                path: "/".to_string(),
+                outcome: TrafficOutcome::Fault("error".to_string()),
+            },
+        ];
+        for kind in kinds {
+            let name = event_name(&kind).unwrap();
+            assert!(WEBHOOK_EVENTS.contains(&name), "{name}");
+        }
+    }
+
+    #[test]
+    fn payload_carries_the_event_name_and_its_fields() {
+        let body = payload(
+            &event(AdminEventKind::Traffic {
+                port: 4545,
+                method: "POST".to_string(),
+                path: "/orders".to_string(),
+                outcome: TrafficOutcome::Fault("CONNECTION_RESET_BY_PEER".to_string()),
+            }),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(body["event"], "request.fault");
+        assert_eq!(body["seq"], 7);
+        assert_eq!(body["port"], 4545);
+        assert_eq!(body["path"], "/orders");
+        assert_eq!(body["fault"], "CONNECTION_RESET_BY_PEER");
+        assert!(body["timestamp"].is_string());
+    }
+
+    #[test]
+    fn the_event_filter_drops_unlisted_events() {
+        let deleted = event(AdminEventKind::Lifecycle {
+            action: ImposterAction::Deleted,
+            port: Some(4545),
+        });
+        let only_created = ["imposter.created".to_string()];
+        assert!(payload(&deleted, &only_created).is_none());
+        let body = payload(&deleted, &[]).unwrap();
+        assert_eq!(body["event"], "imposter.deleted");
+    }
+
+    #[test]
+    fn flag_values_are_validated() {
+        assert!(parse_webhook_url("https://hooks.example.com/rift").is_ok());
+        assert!(parse_webhook_url("ftp://hooks.example.com").is_err());
+        assert!(parse_webhook_url("not a url").is_err());
+        assert!(parse_webhook_event("request.noMatch").is_ok());
+        assert!(parse_webhook_event("request.matched").is_err());
+    }
+}
@@ -494,6 +494,474 @@ async fn space_stub_registration_and_inspection_endpoints() {
     let _ = manager.delete_imposter(19775).await;
 }
 
+// POST /imposters/:port/_drain refuses new requests with 503 + Retry-After while one already in
+// flight completes; DELETE resumes serving.
+#[tokio::test]
+async fn drain_refuses_new_requests_and_lets_in_flight_ones_finish() {
+    let manager = std::sync::Arc::new(ImposterManager::new());
+    let config = serde_json::from_value(serde_json::json!({
+        "port": 19794, "protocol": "http",
+        "stubs": [{ "responses": [{ "is": { "statusCode": 200, "body": "done" }, "_behaviors": { "wait": 800 } }] }]
+    }))
+    .unwrap();
+    manager.create_imposter(config).await.expect("create");
+    let server = rift_http_proxy::admin_api::AdminApiServer::new(
+        "127.0.0.1:12765".parse().unwrap(),
+        manager.clone(),
+        None,
+    );
+    tokio::spawn(server.run());
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let c = reqwest::Client::new();
+    let admin = "http://127.0.0.1:12765/imposters/19794/_drain";
+
+    let slow = tokio::spawn(reqwest::get("http://127.0.0.1:19794/slow"));
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let status: serde_json::Value = c
+        .post(admin)
+        .body(r#"{"retryAfter": 3}"#)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        status,
+        serde_json::json!({"draining": true, "retryAfter": 3, "inFlight": 1})
+    );
+
+    let refused = reqwest::get("http://127.0.0.1:19794/new").await.unwrap();
+    assert_eq!(refused.status(), 503);
+    assert_eq!(refused.headers()["retry-after"], "3");
+    assert_eq!(refused.headers()["x-rift-imposter-draining"], "true");
+
+    let finished = slow.await.unwrap().unwrap();
+    assert_eq!(finished.status(), 200);
+    assert_eq!(finished.text().await.unwrap(), "done");
+    assert_eq!(json(&c, admin.to_string()).await["inFlight"], 0);
+
+    let resumed: serde_json::Value = c.delete(admin).send().await.unwrap().json().await.unwrap();
+    assert_eq!(resumed["draining"], false);
+    let served = reqwest::get("http://127.0.0.1:19794/again").await.unwrap();
+    assert_eq!(served.status(), 200);
+    let _ = manager.delete_imposter(19794).await;
+}
+
+// PUT /imposters/:port/stubs overwrites the stub list, and a `newIndex` body on
+// PUT /imposters/:port/stubs/:index moves one stub, re-prioritizing it for matching.
+#[tokio::test]
+async fn stub_overwrite_and_move_admin_endpoints() {
+    let manager = std::sync::Arc::new(ImposterManager::new());
+    let config = serde_json::from_value(serde_json::json!({
+        "port": 19786, "protocol": "http", "stubs": []
+    }))
+    .unwrap();
+    manager.create_imposter(config).await.expect("create");
+
+    let admin_addr = "127.0.0.1:12627".parse().unwrap();
+    let server = rift_http_proxy::admin_api::AdminApiServer::new(admin_addr, manager.clone(), None);
+    tokio::spawn(server.run());
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let c = reqwest::Client::new();
+    let admin = "http://127.0.0.1:12627";
+    let stub = |id: &str, path: &str| {
+        serde_json::json!({
+            "id": id,
+            "predicates": [{ "startsWith": { "path": path } }],
+            "responses": [{ "is": { "statusCode": 200, "body": id } }]
+        })
+    };
+    let put = |url: String, body: serde_json::Value| {
+        c.put(url)
+            .header("content-type", "application/json")
+            .body(body.to_string())
+            .send()
+    };
+
+    let r = put(
+        format!("{admin}/imposters/19786/stubs"),
+        serde_json::json!({ "stubs": [stub("orders", "/orders"), stub("catch-all", "/"), stub("order-7", "/orders/7")] }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(r.status(), 200);
+    assert_eq!(
+        text(&c, "http://127.0.0.1:19786/orders/7".to_string()).await,
+        "orders",
+        "the broader stub shadows the specific one"
+    );
+
+    let r = put(
+        format!("{admin}/imposters/19786/stubs/2"),
+        serde_json::json!({ "newIndex": 0 }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(r.status(), 200);
+    let ids: Vec<String> = manager
+        .get_imposter(19786)
+        .unwrap()
+        .get_stubs()
+        .iter()
+        .map(|s| s.id.clone().unwrap())
+        .collect();
+    assert_eq!(ids, ["order-7", "orders", "catch-all"]);
+    assert_eq!(
+        text(&c, "http://127.0.0.1:19786/orders/7".to_string()).await,
+        "order-7"
+    );
+
+    let out_of_range = put(
+        format!("{admin}/imposters/19786/stubs/0"),
+        serde_json::json!({ "newIndex": 3 }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(out_of_range.status(), 404);
+    let mixed = put(
+        format!("{admin}/imposters/19786/stubs/0"),
+        serde_json::json!({ "newIndex": 1, "responses": [] }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(mixed.status(), 400, "a move carries nothing but newIndex");
+
+    let _ = manager.delete_imposter(19786).await;
+}
+
+// POST /imposters/:port/stubs/_batch applies every operation or none of them.
+#[tokio::test]
+async fn stub_batch_applies_all_operations_or_none() {
+    let manager = std::sync::Arc::new(ImposterManager::new());
+    let stub = |id: &str| {
+        serde_json::json!({
+            "id": id,
+            "predicates": [{ "startsWith": { "path": format!("/{id}") } }],
+            "responses": [{ "is": { "statusCode": 200, "body": id } }]
+        })
+    };
+    let config = serde_json::from_value(serde_json::json!({
+        "port": 19795, "protocol": "http", "stubs": [stub("a"), stub("b")]
+    }))
+    .unwrap();
+    manager.create_imposter(config).await.expect("create");
+    let server = rift_http_proxy::admin_api::AdminApiServer::new(
+        "127.0.0.1:12766".parse().unwrap(),
+        manager.clone(),
+        None,
+    );
+    tokio::spawn(server.run());
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let c = reqwest::Client::new();
+    let batch = |operations: serde_json::Value| {
+        c.post("http://127.0.0.1:12766/imposters/19795/stubs/_batch")
+            .body(serde_json::json!({ "operations": operations }).to_string())
+            .send()
+    };
+    let ids = || -> Vec<String> {
+        manager
+            .get_imposter(19795)
+            .unwrap()
+            .get_stubs()
+            .iter()
+            .map(|s| s.id.clone().unwrap())
+            .collect()
+    };
+
+    let r = batch(serde_json::json!([
+        { "op": "add", "stub": stub("c") },
+        { "op": "replace", "id": "a", "stub": stub("ignored") },
+        { "op": "move", "from": 2, "to": 0 },
+        { "op": "delete", "index": 2 }
+    ]))
+    .await
+    .unwrap();
+    assert_eq!(r.status(), 200);
+    let body: serde_json::Value = r.json().await.unwrap();
+    assert_eq!(
+        body["results"],
+        serde_json::json!([
+            { "op": "add", "index": 2, "id": "c" },
+            { "op": "replace", "index": 0, "id": "a" },
+            { "op": "move", "index": 0, "id": "c" },
+            { "op": "delete", "index": 2, "id": "b" }
+        ])
+    );
+    assert_eq!(ids(), ["c", "a"]);
+    assert_eq!(
+        text(&c, "http://127.0.0.1:19795/ignored".to_string()).await,
+        "ignored"
+    );
+
+    let r = batch(serde_json::json!([
+        { "op": "delete", "id": "c" },
+        { "op": "delete", "id": "missing" }
+    ]))
+    .await
+    .unwrap();
+    assert_eq!(r.status(), 404);
+    let body: serde_json::Value = r.json().await.unwrap();
+    assert_eq!(
+        body["errors"][0]["message"],
+        "Stub operation 1 failed: No stub with id 'missing'"
+    );
+    assert_eq!(ids(), ["c", "a"], "the first delete was rolled back");
+
+    let r = batch(serde_json::json!([{ "op": "delete", "index": 0, "id": "c" }]))
+        .await
+        .unwrap();
+    assert_eq!(r.status(), 400, "a target is an index or an id, not both");
+
+    let _ = manager.delete_imposter(19795).await;
+}
+
+// A registered template creates one imposter per set of variables.
+#[tokio::test]
+async fn imposters_are_created_from_a_registered_template() {
+    let manager = std::sync::Arc::new(ImposterManager::new());
+    let server = rift_http_proxy::admin_api::AdminApiServer::new(
+        "127.0.0.1:12767".parse().unwrap(),
+        manager.clone(),
+        None,
+    );
+    tokio::spawn(server.run());
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let c = reqwest::Client::new();
+    let admin = "http://127.0.0.1:12767";
+    let post = |url: String, body: serde_json::Value| c.post(url).body(body.to_string()).send();
+
+    let template = serde_json::json!({
+        "name": "auth-service",
+        "variables": ["port", "tenantId"],
+        "imposter": {
+            "port": "${port}",
+            "protocol": "http",
+            "stubs": [{
+                "predicates": [{ "equals": { "path": "/tenants/${tenantId}/token" } }],
+                "responses": [{ "is": { "statusCode": 200, "body": "token for ${tenantId}" } }]
+            }]
+        }
+    });
+    let r = post(format!("{admin}/_templates"), template.clone())
+        .await
+        .unwrap();
+    assert_eq!(r.status(), 201);
+    let r = post(format!("{admin}/_templates"), template).await.unwrap();
+    assert_eq!(r.status(), 200, "re-registering replaces");
+
+    for (port, tenant) in [(19796, "acme"), (19797, "globex")] {
+        let r = post(
+            format!("{admin}/imposters/from-template/auth-service"),
+            serde_json::json!({ "variables": { "port": port, "tenantId": tenant } }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(r.status(), 201);
+        assert_eq!(
+            text(
+                &c,
+                format!("http://127.0.0.1:{port}/tenants/{tenant}/token")
+            )
+            .await,
+            format!("token for {tenant}")
+        );
+    }
+
+    let r = post(
+        format!("{admin}/imposters/from-template/auth-service"),
+        serde_json::json!({ "variables": { "port": 19798 } }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(r.status(), 400, "tenantId is required");
+    let r = post(
+        format!("{admin}/imposters/from-template/missing"),
+        serde_json::json!({}),
+    )
+    .await
+    .unwrap();
+    assert_eq!(r.status(), 404);
+
+    let listed = json(&c, format!("{admin}/_templates")).await;
+    assert_eq!(listed["templates"][0]["name"], "auth-service");
+    let r = c
+        .delete(format!("{admin}/_templates/auth-service"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(r.status(), 200);
+    let r = c
+        .get(format!("{admin}/_templates/auth-service"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(r.status(), 404);
+
+    let _ = manager.delete_imposter(19796).await;
+    let _ = manager.delete_imposter(19797).await;
+}
+
+#[tokio::test]
+async fn wiremock_mappings_directory_is_imported_as_an_imposter() {
+    let manager = std::sync::Arc::new(ImposterManager::new());
+    let server = rift_http_proxy::admin_api::AdminApiServer::new(
+        "127.0.0.1:12768".parse().unwrap(),
+        manager.clone(),
+        None,
+    );
+    tokio::spawn(server.run());
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let c = reqwest::Client::new();
+
+    // A WireMock root: mappings/ (one file per mapping, nested dirs allowed) plus __files/.
+    let root = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(root.path().join("mappings/orders")).unwrap();
+    std::fs::create_dir_all(root.path().join("__files")).unwrap();
+    std::fs::write(root.path().join("__files/order.json"), r#"{"id":"42"}"#).unwrap();
+    std::fs::write(
+        root.path().join("mappings/orders/get-order.json"),
+        serde_json::json!({
+            "request": {"method": "GET", "urlPath": "/orders/42"},
+            "response": {"status": 200, "bodyFileName": "order.json"}
+        })
+        .to_string(),
+    )
+    .unwrap();
+    std::fs::write(
+        root.path().join("mappings/echo.json"),
+        serde_json::json!({"mappings": [{
+            "request": {"method": "GET", "urlPathTemplate": "/echo/{word}"},
+            "response": {
+                "body": "{{request.path.word}}{{randomValue}}",
+                "transformers": ["response-template"]
+            }
+        }]})
+        .to_string(),
+    )
+    .unwrap();
+
+    rift_http_proxy::bootstrap::import_wiremock_async("127.0.0.1", 12768, root.path(), 19799)
+        .await
+        .expect("import");
+    assert_eq!(
+        text(&c, "http://127.0.0.1:19799/orders/42".to_string()).await,
+        r#"{"id":"42"}"#
+    );
+    assert_eq!(
+        text(&c, "http://127.0.0.1:19799/echo/hello".to_string()).await,
+        "hello{{randomValue}}",
+        "an unsupported helper is left as written"
+    );
+    let r = c
+        .get("http://127.0.0.1:19799/orders/43")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        r.status(),
+        200,
+        "unmatched requests get the default response"
+    );
+    assert_eq!(r.text().await.unwrap(), "");
+
+    // The endpoint reports what it could not convert.
+    let r = c
+        .post("http://127.0.0.1:12768/imposters/19800/import?format=wiremock")
+        .body(
+            serde_json::json!({
+                "request": {"urlPath": "/a", "cookies": {"session": {"equalTo": "x"}}},
+                "response": {"status": 204}
+            })
+            .to_string(),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(r.status(), 201);
+    let imposter: serde_json::Value = r.json().await.unwrap();
+    assert!(
+        imposter["warnings"][0]
+            .as_str()
+            .unwrap()
+            .contains("`cookies` is not supported"),
+        "{imposter}"
+    );
+    let r = c
+        .post("http://127.0.0.1:12768/imposters/19800/import?format=wiremock")
+        .body("{\"stubs\": []}")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(r.status(), 400);
+}
+
+#[tokio::test]
+async fn imposter_listing_filters_pages_and_projects() {
+    let manager = std::sync::Arc::new(ImposterManager::new());
+    for (port, name) in [
+        (19811, "Auth service"),
+        (19812, "Payments"),
+        (19813, "auth admin"),
+    ] {
+        let config = serde_json::from_value(serde_json::json!({
+            "port": port, "protocol": "http", "name": name, "stubs": []
+        }))
+        .unwrap();
+        manager.create_imposter(config).await.unwrap();
+    }
+    let server = rift_http_proxy::admin_api::AdminApiServer::new(
+        "127.0.0.1:12769".parse().unwrap(),
+        manager.clone(),
+        None,
+    );
+    tokio::spawn(server.run());
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let c = reqwest::Client::new();
+    let ports = |body: &serde_json::Value| -> Vec<u64> {
+        body["imposters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| i["port"].as_u64().unwrap())
+            .collect()
+    };
+
+    let r = c
+        .get("http://127.0.0.1:12769/imposters?name=AUTH&page=2&size=1&fields=port,name")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(r.headers()["x-rift-total-count"], "2");
+    let body: serde_json::Value = r.json().await.unwrap();
+    assert_eq!(
+        body["imposters"],
+        serde_json::json!([{"port": 19813, "name": "auth admin"}])
+    );
+
+    let body = json(
+        &c,
+        "http://127.0.0.1:12769/imposters?port=19812,19813&replayable=true".to_string(),
+    )
+    .await;
+    assert_eq!(ports(&body), [19812, 19813]);
+    assert!(body["imposters"][0]["stubs"].is_array(), "{body}");
+
+    let body = json(&c, "http://127.0.0.1:12769/imposters?page=9".to_string()).await;
+    assert_eq!(
+        ports(&body),
+        Vec::<u64>::new(),
+        "a page past the end is empty"
+    );
+
+    let r = c
+        .get("http://127.0.0.1:12769/imposters?size=0")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(r.status(), 400);
+}
+
 // Issue #202: id-addressed stub operations over the admin HTTP API.
 #[tokio::test]
 async fn stub_by_id_admin_endpoints() {
@@ -849,6 +1317,74 @@ mod https {
             Err(other) => panic!("unexpected non-Tls error: {other:?}"),
         }
     }
+
+    #[tokio::test]
+    async fn mutual_auth_records_and_matches_the_client_certificate() {
+        let manager = Arc::new(ImposterManager::new());
+        serve(
+            &manager,
+            serde_json::json!({
+                "port": 19849, "protocol": "https", "mutualAuth": true, "recordRequests": true,
+                "stubs": [
+                    {
+                        "predicates": [{"equals": {"tls": {"clientCert": {"commonName": "tenant-a"}}}}],
+                        "responses": [{"is": {"statusCode": 200, "body": "tenant-a"}}]
+                    },
+                    {"responses": [{"is": {"statusCode": 200, "body": "anonymous"}}]}
+                ]
+            }),
+        )
+        .await;
+
+        let mut params = rcgen::CertificateParams::new(Vec::new()).unwrap();
+        params
+            .distinguished_name
+            .push(rcgen::DnType::CommonName, "tenant-a");
+        let key = rcgen::KeyPair::generate().unwrap();
+        let client_cert = params.self_signed(&key).unwrap();
+        let identity = reqwest::Identity::from_pem(
+            format!("{}{}", client_cert.pem(), key.serialize_pem()).as_bytes(),
+        )
+        .unwrap();
+        let with_cert = reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .identity(identity)
+            .timeout(Duration::from_secs(3))
+            .build()
+            .unwrap();
+
+        let body = with_cert
+            .get("https://localhost:19849/accounts")
+            .send()
+            .await
+            .expect("mTLS request should succeed")
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(body, "tenant-a", "matched on the client certificate CN");
+        let body = tls_client()
+            .get("https://localhost:19849/accounts")
+            .send()
+            .await
+            .expect("a client without a certificate is still served")
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(body, "anonymous");
+
+        let recorded = manager.get_imposter(19849).unwrap().get_recorded_requests();
+        assert_eq!(recorded.len(), 2);
+        let tls = recorded[0].tls.as_ref().expect("HTTPS requests record tls");
+        assert_eq!(tls.sni.as_deref(), Some("localhost"));
+        assert!(tls.version.starts_with("TLSv1."), "{}", tls.version);
+        assert!(!tls.cipher.is_empty());
+        let cert = tls.client_cert.as_ref().expect("client certificate");
+        assert_eq!(cert.common_name.as_deref(), Some("tenant-a"));
+        assert_eq!(cert.subject, "CN=tenant-a");
+        assert!(recorded[1].tls.as_ref().unwrap().client_cert.is_none());
+
+        let _ = manager.delete_imposter(19849).await;
+    }
 }
 
 // Issue #239: _rift.fault.tcp must produce a REAL client-observable transport failure (not a 502).
@@ -1601,6 +2137,148 @@ mod gateway {
         );
         let _ = manager.delete_imposter(19857).await;
     }
+
+    #[tokio::test]
+    async fn admin_accepts_any_configured_key_or_basic_credentials() {
+        use base64::Engine;
+        let auth = rift_http_proxy::admin_api::AdminAuth::default()
+            .with_api_key("ci-key")
+            .with_api_key("dev-key")
+            .with_basic_credentials("admin", "hunter2");
+        let server = rift_http_proxy::admin_api::AdminApiServer::new(
+            "127.0.0.1:12758".parse().unwrap(),
+            Arc::new(ImposterManager::new()),
+            None,
+        )
+        .with_auth(auth);
+        tokio::spawn(server.run());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let basic = |credentials: &str| {
+            format!(
+                "Basic {}",
+                base64::engine::general_purpose::STANDARD.encode(credentials)
+            )
+        };
+        let client = reqwest::Client::new();
+        for (authorization, expected) in [
+            (Some("ci-key".to_string()), 200),
+            (Some("Bearer dev-key".to_string()), 200),
+            (Some(basic("admin:hunter2")), 200),
+            (Some(basic("admin:wrong")), 401),
+            (Some("Bearer other-key".to_string()), 401),
+            (None, 401),
+        ] {
+            let mut request = client.get("http://127.0.0.1:12758/imposters");
+            if let Some(value) = &authorization {
+                request = request.header("authorization", value);
+            }
+            let resp = request.send().await.unwrap();
+            assert_eq!(resp.status(), expected, "Authorization: {authorization:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn read_only_credentials_and_server_refuse_mutations() {
+        let manager = Arc::new(ImposterManager::new());
+        let auth = rift_http_proxy::admin_api::AdminAuth::default()
+            .with_api_key("ops-key")
+            .with_read_only_api_key("dev-key");
+        let server = rift_http_proxy::admin_api::AdminApiServer::new(
+            "127.0.0.1:12759".parse().unwrap(),
+            manager.clone(),
+            None,
+        )
+        .with_auth(auth);
+        tokio::spawn(server.run());
+        let locked = rift_http_proxy::admin_api::AdminApiServer::new(
+            "127.0.0.1:12761".parse().unwrap(),
+            manager.clone(),
+            None,
+        )
+        .with_read_only(true);
+        tokio::spawn(locked.run());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let client = reqwest::Client::new();
+        let create = |admin: &str, key: &str| {
+            client
+                .post(format!("http://127.0.0.1:{admin}/imposters"))
+                .header("authorization", key)
+                .json(&serde_json::json!({"port": 19793, "protocol": "http"}))
+                .send()
+        };
+
+        let refused = create("12759", "dev-key").await.unwrap();
+        assert_eq!(refused.status(), 403);
+        let body: serde_json::Value = refused.json().await.unwrap();
+        assert_eq!(body["errors"][0]["type"], "insufficient access");
+        let read = client
+            .get("http://127.0.0.1:12759/imposters")
+            .header("authorization", "dev-key")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(read.status(), 200, "a read-only key still reads");
+        assert_eq!(create("12759", "ops-key").await.unwrap().status(), 201);
+
+        let verify = client
+            .post("http://127.0.0.1:12761/imposters/19793/verify")
+            .json(&serde_json::json!({"predicates": []}))
+            .send()
+            .await
+            .unwrap();
+        assert_ne!(verify.status(), 403, "verify only reads");
+        let delete = client
+            .delete("http://127.0.0.1:12761/imposters/19793")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(delete.status(), 403, "--readonly refuses every mutation");
+        assert!(manager.get_imposter(19793).is_ok());
+        let _ = manager.delete_imposter(19793).await;
+    }
+}
+
+// GET /config reports the bound port and startup options; GET /logs pages the log ring buffer.
+#[tokio::test]
+async fn config_and_logs_report_the_running_server() {
+    use rift_http_proxy::admin_api::{AdminApiServer, LogBuffer, LogEntry};
+    let logs = LogBuffer::default();
+    for message in ["Rift starting", "imposter created"] {
+        logs.push(LogEntry {
+            level: "info".to_string(),
+            message: message.to_string(),
+            timestamp: "2026-01-01T00:00:00.000Z".to_string(),
+        });
+    }
+    let mut options = serde_json::Map::new();
+    options.insert("loglevel".to_string(), serde_json::json!("debug"));
+    let server = AdminApiServer::new(
+        "127.0.0.1:12762".parse().unwrap(),
+        std::sync::Arc::new(ImposterManager::new()),
+        None,
+    )
+    .with_log_buffer(logs)
+    .with_reported_options(options);
+    tokio::spawn(server.run());
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+    let c = reqwest::Client::new();
+    let config = json(&c, "http://127.0.0.1:12762/config".to_string()).await;
+    assert_eq!(config["options"]["port"], 12762);
+    assert_eq!(config["options"]["loglevel"], "debug");
+    assert!(config["process"]["uptime"].is_number());
+
+    let all = json(&c, "http://127.0.0.1:12762/logs".to_string()).await;
+    assert_eq!(all["logs"][1]["message"], "imposter created");
+    let page = json(
+        &c,
+        "http://127.0.0.1:12762/logs?startIndex=0&endIndex=0".to_string(),
+    )
+    .await;
+    assert_eq!(page["logs"].as_array().unwrap().len(), 1);
+    assert_eq!(page["logs"][0]["message"], "Rift starting");
 }
 
 // Issue #260: GET /imposters/:port exposes the imposter's flowState (so tools can read
@@ -1643,3 +2321,245 @@ async fn get_imposter_exposes_flowstate_redacted() {
         "the redis credential must not survive anywhere in the GET response"
     );
 }
+
+#[tokio::test]
+async fn proxied_requests_lists_upstream_calls_but_not_replays() {
+    let manager = std::sync::Arc::new(ImposterManager::new());
+    let server = rift_http_proxy::admin_api::AdminApiServer::new(
+        "127.0.0.1:12770".parse().unwrap(),
+        manager.clone(),
+        None,
+    );
+    tokio::spawn(server.run());
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let c = reqwest::Client::new();
+    let admin = "http://127.0.0.1:12770";
+    for imposter in [
+        serde_json::json!({ "port": 18821, "protocol": "http", "stubs": [{
+            "responses": [{ "is": { "statusCode": 202, "body": "upstream" } }]
+        }]}),
+        serde_json::json!({ "port": 18822, "protocol": "http", "stubs": [{
+            "responses": [{ "proxy": { "to": "http://127.0.0.1:18821", "mode": "proxyOnce" } }]
+        }]}),
+    ] {
+        let r = c
+            .post(format!("{admin}/imposters"))
+            .body(imposter.to_string())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(r.status(), 201);
+    }
+
+    // The second /orders is replayed from the recording, so only two calls go upstream.
+    for path in ["/orders?page=1", "/orders?page=1", "/customers"] {
+        assert_eq!(get(&c, 18822, path, None).await.status(), 202);
+    }
+    let report = json(&c, format!("{admin}/imposters/18822/proxiedRequests")).await;
+    assert_eq!(report["count"], 2, "{report}");
+    let calls = report["proxiedRequests"].as_array().unwrap();
+    assert_eq!(calls[0]["path"], "/orders");
+    assert_eq!(calls[0]["query"], "page=1");
+    assert_eq!(calls[0]["url"], "http://127.0.0.1:18821/orders?page=1");
+    assert_eq!(calls[0]["statusCode"], 202);
+    assert!(calls[0]["latencyMs"].is_u64());
+    assert_eq!(calls[1]["path"], "/customers");
+
+    let cleared: serde_json::Value = c
+        .delete(format!("{admin}/imposters/18822/proxiedRequests"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(cleared["count"], 0);
+    assert_eq!(cleared["proxiedRequests"], serde_json::json!([]));
+
+    for port in [18821, 18822] {
+        let _ = manager.delete_imposter(port).await;
+    }
+}
+
+#[tokio::test]
+async fn served_requests_tails_outcomes_from_a_cursor() {
+    let manager = std::sync::Arc::new(ImposterManager::new());
+    let server = rift_http_proxy::admin_api::AdminApiServer::new(
+        "127.0.0.1:12772".parse().unwrap(),
+        manager.clone(),
+        None,
+    );
+    tokio::spawn(server.run());
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let c = reqwest::Client::new();
+    let admin = "http://127.0.0.1:12772";
+    let imposter = serde_json::json!({ "port": 18824, "protocol": "http", "stubs": [
+        { "id": "orders", "predicates": [{ "equals": { "path": "/orders" } }],
+          "responses": [{ "is": { "statusCode": 201 } }] },
+        { "predicates": [{ "equals": { "path": "/health" } }],
+          "responses": [{ "is": { "statusCode": 204 } }] }
+    ]});
+    let r = c
+        .post(format!("{admin}/imposters"))
+        .body(imposter.to_string())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(r.status(), 201);
+
+    for path in ["/orders?page=2", "/health", "/missing"] {
+        get(&c, 18824, path, None).await;
+    }
+    let report = json(&c, format!("{admin}/imposters/18824/servedRequests")).await;
+    assert_eq!(report["count"], 3, "{report}");
+    let served = report["servedRequests"].as_array().unwrap();
+    assert_eq!(served[0]["seq"], 1);
+    assert_eq!(served[0]["path"], "/orders");
+    assert_eq!(served[0]["query"], "page=2");
+    assert_eq!(served[0]["statusCode"], 201);
+    assert_eq!(served[0]["stubIndex"], 0);
+    assert_eq!(served[0]["stubId"], "orders");
+    assert!(served[0]["latencyMs"].is_u64());
+    assert_eq!(served[1]["stubIndex"], 1);
+    assert!(served[2].get("stubIndex").is_none(), "{report}");
+
+    let tail = json(
+        &c,
+        format!("{admin}/imposters/18824/servedRequests?since=2"),
+    )
+    .await;
+    assert_eq!(tail["count"], 3);
+    assert_eq!(tail["servedRequests"].as_array().unwrap().len(), 1);
+    assert_eq!(tail["servedRequests"][0]["path"], "/missing");
+
+    let bad = c
+        .get(format!("{admin}/imposters/18824/servedRequests?since=x"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(bad.status(), 400);
+    let cleared: serde_json::Value = c
+        .delete(format!("{admin}/imposters/18824/servedRequests"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(cleared["count"], 0);
+
+    let _ = manager.delete_imposter(18824).await;
+}
+
+#[tokio::test]
+async fn served_request_detail_keeps_headers_and_bodies() {
+    let manager = std::sync::Arc::new(ImposterManager::new());
+    let server = rift_http_proxy::admin_api::AdminApiServer::new(
+        "127.0.0.1:12773".parse().unwrap(),
+        manager.clone(),
+        None,
+    );
+    tokio::spawn(server.run());
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let c = reqwest::Client::new();
+    let admin = "http://127.0.0.1:12773";
+    let imposter = serde_json::json!({ "port": 18825, "protocol": "http", "stubs": [
+        { "responses": [{ "is": { "statusCode": 202,
+            "headers": { "X-Order": "42" }, "body": "accepted" } }] }
+    ]});
+    let r = c
+        .post(format!("{admin}/imposters"))
+        .body(imposter.to_string())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(r.status(), 201);
+
+    let sent = c
+        .post("http://127.0.0.1:18825/orders?dry=1")
+        .header("X-Tenant", "acme")
+        .body(r#"{"item":"book"}"#)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(sent.status(), 202);
+
+    let detail = json(&c, format!("{admin}/imposters/18825/servedRequests/1")).await;
+    assert_eq!(detail["seq"], 1, "{detail}");
+    assert_eq!(detail["method"], "POST");
+    assert_eq!(detail["query"], "dry=1");
+    assert_eq!(detail["request"]["headers"]["X-Tenant"][0], "acme");
+    assert_eq!(detail["request"]["body"], r#"{"item":"book"}"#);
+    assert_eq!(detail["response"]["headers"]["X-Order"][0], "42");
+    assert_eq!(detail["response"]["body"], "accepted");
+
+    let missing = c
+        .get(format!("{admin}/imposters/18825/servedRequests/2"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), 404);
+
+    let _ = manager.delete_imposter(18825).await;
+}
+
+#[tokio::test]
+async fn metrics_report_latency_statuses_faults_and_stub_hits() {
+    let manager = std::sync::Arc::new(ImposterManager::new());
+    let server = rift_http_proxy::admin_api::AdminApiServer::new(
+        "127.0.0.1:12774".parse().unwrap(),
+        manager.clone(),
+        None,
+    );
+    tokio::spawn(server.run());
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let c = reqwest::Client::new();
+    let admin = "http://127.0.0.1:12774";
+    let imposter = serde_json::json!({ "port": 18826, "protocol": "http", "stubs": [
+        { "id": "orders", "predicates": [{ "equals": { "path": "/orders" } }],
+          "responses": [{ "is": { "statusCode": 200 } }] },
+        { "predicates": [{ "equals": { "path": "/flaky" } }],
+          "responses": [{ "is": { "statusCode": 200 },
+            "_rift": { "fault": { "error": { "probability": 1.0, "status": 503 } } } }] }
+    ]});
+    let r = c
+        .post(format!("{admin}/imposters"))
+        .body(imposter.to_string())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(r.status(), 201);
+
+    for path in ["/orders", "/orders", "/flaky", "/missing"] {
+        get(&c, 18826, path, None).await;
+    }
+    let metrics = text(&c, format!("{admin}/metrics")).await;
+    for line in [
+        r#"rift_imposter_request_duration_ms_bucket{port="18826",le="+Inf"} 4"#,
+        r#"rift_imposter_request_duration_ms_count{port="18826"} 4"#,
+        // An unmatched request gets the default empty 200
+        r#"rift_imposter_responses_total{port="18826",status="200"} 3"#,
+        r#"rift_imposter_responses_total{port="18826",status="503"} 1"#,
+        r#"rift_imposter_faults_total{port="18826",fault="error"} 1"#,
+        r#"rift_imposter_stub_hits_total{port="18826",stub="0",stub_id="orders"} 2"#,
+        r#"rift_imposter_stub_hits_total{port="18826",stub="1"} 1"#,
+        r#"rift_imposter_unmatched_requests_total{port="18826"} 1"#,
+    ] {
+        assert!(
+            metrics.lines().any(|l| l == line),
+            "{line} missing:\n{metrics}"
+        );
+    }
+    assert_eq!(
+        metrics
+            .lines()
+            .filter(|l| l.starts_with("# TYPE rift_imposter_request_duration_ms "))
+            .count(),
+        1
+    );
+
+    let served = json(&c, format!("{admin}/imposters/18826/servedRequests")).await;
+    assert_eq!(served["servedRequests"][2]["fault"], "error", "{served}");
+
+    let _ = manager.delete_imposter(18826).await;
+}
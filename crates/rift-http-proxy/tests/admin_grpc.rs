@@ -0,0 +1,167 @@
+//! The gRPC admin API (`rift.admin.v1.AdminService`) over a real HTTP/2 connection: imposters and
+//! stubs created over gRPC serve binary bodies byte for byte, and failures come back as gRPC
+//! statuses rather than HTTP errors.
+
+use bytes::{Buf, Bytes};
+use clap::Parser;
+use http_body_util::{BodyExt, Full};
+use hyper_util::client::legacy::Client;
+use hyper_util::rt::TokioExecutor;
+use prost::Message;
+use rift_http_proxy::admin_api::grpc::proto;
+use rift_http_proxy::server::{Cli, ServerBuilder};
+
+type GrpcClient = Client<hyper_util::client::legacy::connect::HttpConnector, Full<Bytes>>;
+
+/// Make one unary call, returning the decoded reply or the `grpc-status` and `grpc-message`.
+async fn call<Req: Message, Rep: Message + Default>(
+    client: &GrpcClient,
+    admin: &str,
+    method: &str,
+    request: &Req,
+) -> Result<Rep, (String, String)> {
+    let message = request.encode_to_vec();
+    let mut frame = vec![0];
+    frame.extend_from_slice(&(message.len() as u32).to_be_bytes());
+    frame.extend_from_slice(&message);
+    let req = hyper::Request::post(format!("{admin}/rift.admin.v1.AdminService/{method}"))
+        .header("content-type", "application/grpc")
+        .header("te", "trailers")
+        .body(Full::new(Bytes::from(frame)))
+        .unwrap();
+    let response = client.request(req).await.expect("gRPC call");
+    assert_eq!(response.status(), 200);
+    let (parts, body) = response.into_parts();
+    let collected = body.collect().await.unwrap();
+    let trailers = collected.trailers().cloned().unwrap_or_default();
+    let header = |name: &str| {
+        trailers
+            .get(name)
+            .or_else(|| parts.headers.get(name))
+            .map(|v| v.to_str().unwrap().to_string())
+            .unwrap_or_default()
+    };
+    if header("grpc-status") != "0" {
+        return Err((header("grpc-status"), header("grpc-message")));
+    }
+    let mut body = collected.to_bytes();
+    assert_eq!(body.get_u8(), 0, "uncompressed reply");
+    let len = body.get_u32() as usize;
+    assert_eq!(body.len(), len);
+    Ok(Rep::decode(body).expect("reply message"))
+}
+
+fn png_stub(path: &str, body: &[u8]) -> proto::Stub {
+    proto::Stub {
+        definition: serde_json::to_vec(
+            &serde_json::json!({ "predicates": [{ "equals": { "path": path } }] }),
+        )
+        .unwrap(),
+        responses: vec![proto::Response {
+            kind: Some(proto::response::Kind::Is(proto::IsResponse {
+                status_code: 200,
+                headers: vec![proto::Header {
+                    name: "Content-Type".into(),
+                    value: "image/png".into(),
+                }],
+                body: body.to_vec(),
+            })),
+        }],
+    }
+}
+
+#[tokio::test]
+async fn grpc_admin_api_manages_imposters_with_binary_bodies() {
+    let cli = Cli::try_parse_from(["rift", "--port", "0", "--metrics-port", "0"]).expect("parse");
+    let server = ServerBuilder::from_cli(cli).start().await.expect("start");
+    let admin = format!("http://{}", server.admin_addr());
+    let client: GrpcClient = Client::builder(TokioExecutor::new())
+        .http2_only(true)
+        .build_http();
+    let logo = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0xff, 0x00];
+
+    let created: proto::Imposter = call(
+        &client,
+        &admin,
+        "CreateImposter",
+        &proto::CreateImposterRequest {
+            imposter: Some(proto::Imposter {
+                port: 18823,
+                protocol: "http".into(),
+                name: "assets".into(),
+                stubs: vec![png_stub("/logo.png", &logo)],
+                ..Default::default()
+            }),
+        },
+    )
+    .await
+    .expect("create");
+    assert_eq!(created.name, "assets");
+    assert!(created.enabled);
+
+    let served = reqwest::get("http://127.0.0.1:18823/logo.png")
+        .await
+        .unwrap()
+        .bytes()
+        .await
+        .unwrap();
+    assert_eq!(served.as_ref(), logo);
+
+    let with_two: proto::Imposter = call(
+        &client,
+        &admin,
+        "AddStub",
+        &proto::AddStubRequest {
+            port: 18823,
+            stub: Some(png_stub("/icon.png", &logo[..4])),
+            index: None,
+        },
+    )
+    .await
+    .expect("add stub");
+    assert_eq!(with_two.stubs.len(), 2);
+    let Some(proto::response::Kind::Is(is)) = &with_two.stubs[0].responses[0].kind else {
+        panic!("expected a typed is response");
+    };
+    assert_eq!(is.body, logo);
+
+    let metrics: proto::Metrics = call(&client, &admin, "GetMetrics", &proto::GetMetricsRequest {})
+        .await
+        .expect("metrics");
+    assert_eq!(metrics.imposters, 1);
+    assert_eq!(metrics.per_imposter[0].number_of_requests, 1);
+
+    let missing = call::<_, proto::Imposter>(
+        &client,
+        &admin,
+        "DeleteStub",
+        &proto::DeleteStubRequest {
+            port: 18823,
+            index: 5,
+        },
+    )
+    .await
+    .expect_err("no stub at index 5");
+    assert_eq!(missing.0, "5", "NOT_FOUND");
+
+    let deleted: proto::Imposter = call(
+        &client,
+        &admin,
+        "DeleteImposter",
+        &proto::DeleteImposterRequest { port: 18823 },
+    )
+    .await
+    .expect("delete");
+    assert_eq!(deleted.stubs.len(), 2);
+    let gone = call::<_, proto::Imposter>(
+        &client,
+        &admin,
+        "GetImposter",
+        &proto::GetImposterRequest { port: 18823 },
+    )
+    .await
+    .expect_err("deleted");
+    assert_eq!(gone.0, "5");
+
+    server.shutdown().await;
+}
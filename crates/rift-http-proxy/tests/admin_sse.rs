@@ -324,6 +324,46 @@ async fn types_lifecycle_suppresses_request_events() {
     running.shutdown().await;
 }
 
+#[tokio::test]
+async fn types_traffic_streams_no_match_and_fault_outcomes() {
+    let (addr, mgr, running) = start_server(None).await;
+    let iport = create(
+        &mgr,
+        serde_json::json!({"port":18816,"protocol":"http","stubs":[{
+            "predicates":[{"equals":{"path":"/boom"}}],
+            "responses":[{"fault":"CONNECTION_RESET_BY_PEER"}]}]}),
+    )
+    .await;
+    let (mut sse, _s) = Sse::connect(addr, "/events?types=traffic", None).await;
+    sse.wait_for("hello", Duration::from_secs(5))
+        .await
+        .expect("hello");
+
+    reqwest::get(format!("http://127.0.0.1:{iport}/miss"))
+        .await
+        .expect("unmatched request");
+    let no_match = json(
+        &sse.wait_for("traffic", Duration::from_secs(5))
+            .await
+            .expect("noMatch event"),
+    );
+    assert_eq!(no_match["outcome"], "noMatch");
+    assert_eq!(no_match["path"], "/miss");
+    assert_eq!(no_match["port"], iport);
+
+    // The connection is reset, so the client sees an error; the event still reports the fault.
+    let _ = reqwest::get(format!("http://127.0.0.1:{iport}/boom")).await;
+    let fault = json(
+        &sse.wait_for("traffic", Duration::from_secs(5))
+            .await
+            .expect("fault event"),
+    );
+    assert_eq!(fault["outcome"], "fault");
+    assert_eq!(fault["fault"], "CONNECTION_RESET_BY_PEER");
+    assert_eq!(fault["method"], "GET");
+    running.shutdown().await;
+}
+
 #[tokio::test]
 async fn port_filter_scopes_request_events() {
     let (addr, mgr, running) = start_server(None).await;
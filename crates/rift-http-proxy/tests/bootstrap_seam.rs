@@ -413,6 +413,52 @@ async fn save_imposters_async_remove_proxies_strips_proxy_stubs() {
     server.shutdown().await;
 }
 
+// `save --remove-proxies` leaves a file that replays offline even when the server ignored
+// `removeProxies`: proxy responses, stubs left empty, `defaultForward` and the `_rift.proxy`
+// upstream are all stripped client-side, while recorded stubs and other `_rift` settings stay.
+#[test]
+fn strip_proxies_keeps_only_what_replays_offline() {
+    let mut saved = serde_json::json!({
+        "imposters": [{
+            "protocol": "http",
+            "port": 4545,
+            "defaultForward": "http://live",
+            "_rift": {
+                "proxy": {"upstream": {"host": "live", "port": 80}},
+                "scripts": {"hello": {"code": "1"}}
+            },
+            "stubs": [
+                {"responses": [{"proxy": {"to": "http://live", "mode": "proxyAlways",
+                                          "recording": {"collapseDuplicates": true}}}]},
+                {
+                    "predicates": [{"equals": {"path": "/users"}}],
+                    "responses": [
+                        {"is": {"statusCode": 200, "body": "recorded"}},
+                        {"proxy": {"to": "http://live"}}
+                    ],
+                    "recordedFrom": "http://live"
+                }
+            ]
+        }]
+    });
+    bootstrap::strip_proxies(&mut saved);
+    assert_eq!(
+        saved,
+        serde_json::json!({
+            "imposters": [{
+                "protocol": "http",
+                "port": 4545,
+                "_rift": {"scripts": {"hello": {"code": "1"}}},
+                "stubs": [{
+                    "predicates": [{"equals": {"path": "/users"}}],
+                    "responses": [{"is": {"statusCode": 200, "body": "recorded"}}],
+                    "recordedFrom": "http://live"
+                }]
+            }]
+        })
+    );
+}
+
 // AC1 (issue #816): a non-2xx admin response is an error, and nothing is written to the savefile —
 // the data-path swallow this fixes is a 401/500 body written verbatim and logged as a success.
 #[tokio::test]
@@ -421,7 +467,7 @@ async fn save_imposters_rejects_non_2xx_and_writes_nothing() {
 
     // Admin API guarded by an api key — a request with no Authorization header gets 401.
     let mut guarded = cli(&["--host", "127.0.0.1", "--port", "0"]);
-    guarded.api_key = Some("secret".to_string());
+    guarded.api_key = vec!["secret".to_string()];
     let server = ServerBuilder::from_cli(guarded)
         .manager(Arc::clone(&manager))
         .start()
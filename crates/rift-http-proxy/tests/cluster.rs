@@ -0,0 +1,327 @@
+//! `--peer`: a clustered node adopts a peer's imposters on startup, repeats each imposter change
+//! on the peer and sums the peer's request counts into `GET /imposters`. The peer here is a
+//! stand-in on a local listener, since two nodes in one process cannot bind the same imposter port.
+
+use clap::Parser;
+use rift_http_proxy::server::{Cli, RunningServer, ServerBuilder};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+
+/// A request the stand-in peer received: its request line, whether it carried
+/// `x-rift-replicated`, and its JSON body.
+#[derive(Debug)]
+struct PeerRequest {
+    line: String,
+    replicated: bool,
+    body: serde_json::Value,
+}
+
+/// Answer like a Rift admin API holding imposter `port` with 4 requests: a replayable listing to
+/// a joining node, request counts to a listing, `503` to the first `failures` changes and
+/// `200 {}` to the rest.
+async fn peer(port: u16, failures: usize) -> (String, mpsc::UnboundedReceiver<PeerRequest>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind peer");
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::unbounded_channel();
+    let failures = Arc::new(AtomicUsize::new(failures));
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                return;
+            };
+            let tx = tx.clone();
+            let failures = Arc::clone(&failures);
+            tokio::spawn(async move {
+                let mut buf = Vec::new();
+                let mut chunk = [0u8; 4096];
+                loop {
+                    let n = stream.read(&mut chunk).await.unwrap_or(0);
+                    if n == 0 {
+                        return;
+                    }
+                    buf.extend_from_slice(&chunk[..n]);
+                    let text = String::from_utf8_lossy(&buf).into_owned();
+                    let Some((head, body)) = text.split_once("\r\n\r\n") else {
+                        continue;
+                    };
+                    let header = |name: &str| {
+                        head.lines().find_map(|l| {
+                            let (k, v) = l.split_once(':')?;
+                            k.eq_ignore_ascii_case(name).then(|| v.trim().to_string())
+                        })
+                    };
+                    let length = header("content-length")
+                        .and_then(|v| v.parse::<usize>().ok())
+                        .unwrap_or(0);
+                    if body.len() < length {
+                        continue;
+                    }
+                    let line = head.lines().next().unwrap_or_default().to_string();
+                    let mut status = "200 OK";
+                    let answer = if line.starts_with("GET /imposters?replayable=true") {
+                        format!(
+                            r#"{{"imposters":[{{"port":{port},"protocol":"http","recordRequests":true,"stubs":[]}}]}}"#
+                        )
+                    } else if line.starts_with("GET ") {
+                        format!(r#"{{"imposters":[{{"port":{port},"numberOfRequests":4}}]}}"#)
+                    } else {
+                        if failures
+                            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                            .is_ok()
+                        {
+                            status = "503 Service Unavailable";
+                        }
+                        "{}".to_string()
+                    };
+                    let _ = tx.send(PeerRequest {
+                        line,
+                        replicated: header("x-rift-replicated").is_some(),
+                        body: serde_json::from_str(&body[..length]).unwrap_or_default(),
+                    });
+                    let response = format!(
+                        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{answer}",
+                        answer.len()
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                    return;
+                }
+            });
+        }
+    });
+    (url, rx)
+}
+
+async fn next(rx: &mut mpsc::UnboundedReceiver<PeerRequest>) -> PeerRequest {
+    tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("peer request timed out")
+        .expect("peer closed")
+}
+
+/// The next change the peer received, skipping listings.
+async fn next_change(rx: &mut mpsc::UnboundedReceiver<PeerRequest>) -> PeerRequest {
+    loop {
+        let request = next(rx).await;
+        if !request.line.starts_with("GET ") {
+            return request;
+        }
+    }
+}
+
+/// Start a node that lists `peer` as its peer.
+async fn node(peer: &str) -> RunningServer {
+    let cli = Cli::try_parse_from(["rift", "--port", "0", "--metrics-port", "0", "--peer", peer])
+        .expect("parse");
+    ServerBuilder::from_cli(cli).start().await.expect("start")
+}
+
+#[tokio::test]
+async fn clustered_node_joins_replicates_and_aggregates_counts() {
+    let (url, mut rx) = peer(18818, 0).await;
+    let server = node(&url).await;
+    let admin = format!("http://{}", server.admin_addr());
+    let c = reqwest::Client::new();
+
+    // Started empty, so it took the peer's imposter.
+    let join = next(&mut rx).await;
+    assert!(join.replicated, "{join:?}");
+    c.get("http://127.0.0.1:18818/orders")
+        .send()
+        .await
+        .expect("adopted imposter serves");
+
+    let listing: serde_json::Value = c
+        .get(format!("{admin}/imposters"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(listing["imposters"][0]["numberOfRequests"], 5, "{listing}");
+    assert_eq!(
+        listing["imposters"][0]["requestsByNode"],
+        serde_json::json!({ "local": 1, url.as_str(): 4 })
+    );
+    let counts = next(&mut rx).await;
+    assert!(counts.line.contains("port=18818"), "{counts:?}");
+
+    // A peer's own listing request reports this node alone.
+    let local: serde_json::Value = c
+        .get(format!("{admin}/imposters"))
+        .header("x-rift-replicated", "true")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(local["imposters"][0]["numberOfRequests"], 1, "{local}");
+    assert!(local["imposters"][0].get("requestsByNode").is_none());
+
+    let added = c
+        .post(format!("{admin}/imposters/18818/stubs"))
+        .json(&serde_json::json!({"stub": {"responses": [{"is": {"statusCode": 201}}]}}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(added.status(), 200);
+    let push = next(&mut rx).await;
+    assert!(
+        push.line.starts_with("PUT /imposters/18818/stubs "),
+        "{push:?}"
+    );
+    assert!(push.replicated);
+    assert_eq!(
+        push.body["stubs"].as_array().map(Vec::len),
+        Some(1),
+        "{}",
+        push.body
+    );
+
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn concurrent_creates_on_two_nodes_both_survive() {
+    let (url, mut rx) = peer(18819, 0).await;
+    let server = node(&url).await;
+    let admin = format!("http://{}", server.admin_addr());
+    let c = reqwest::Client::new();
+    next(&mut rx).await; // the join
+
+    // An operator creates 18820 here while the peer replicates the 18821 it just created.
+    let (local, replicated) = tokio::join!(
+        c.post(format!("{admin}/imposters"))
+            .json(&serde_json::json!({"port": 18820, "protocol": "http"}))
+            .send(),
+        c.post(format!("{admin}/imposters"))
+            .header("x-rift-replicated", "true")
+            .json(&serde_json::json!({"port": 18821, "protocol": "http"}))
+            .send(),
+    );
+    assert_eq!(local.unwrap().status(), 201);
+    assert_eq!(replicated.unwrap().status(), 201);
+
+    // Only the local create goes to the peer, as a create: nothing it holds is replaced.
+    let push = next_change(&mut rx).await;
+    assert!(push.line.starts_with("POST /imposters "), "{push:?}");
+    assert_eq!(push.body["port"], 18820, "{}", push.body);
+
+    let listing: serde_json::Value = c
+        .get(format!("{admin}/imposters"))
+        .header("x-rift-replicated", "true")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let mut ports: Vec<_> = listing["imposters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|i| i["port"].as_u64())
+        .collect();
+    ports.sort_unstable();
+    assert_eq!(ports, [18819, 18820, 18821]);
+
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn a_failed_change_is_retried_before_the_next() {
+    let (url, mut rx) = peer(18822, 1).await;
+    let server = node(&url).await;
+    let admin = format!("http://{}", server.admin_addr());
+    let c = reqwest::Client::new();
+    next(&mut rx).await; // the join
+
+    for path in ["disable", "enable"] {
+        let response = c
+            .post(format!("{admin}/imposters/18822/{path}"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+    }
+    // The peer answered 503 to the first, so it comes again before the second.
+    let mut seen = Vec::new();
+    for _ in 0..3 {
+        seen.push(next_change(&mut rx).await.line);
+    }
+    assert!(
+        seen[0].starts_with("POST /imposters/18822/disable "),
+        "{seen:?}"
+    );
+    assert!(
+        seen[1].starts_with("POST /imposters/18822/disable "),
+        "{seen:?}"
+    );
+    assert!(
+        seen[2].starts_with("POST /imposters/18822/enable "),
+        "{seen:?}"
+    );
+
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn imported_and_templated_imposters_reach_the_peer() {
+    let (url, mut rx) = peer(18823, 0).await;
+    let server = node(&url).await;
+    let admin = format!("http://{}", server.admin_addr());
+    let c = reqwest::Client::new();
+    next(&mut rx).await; // the join
+
+    let imported = c
+        .post(format!("{admin}/imposters/18824/import?format=wiremock"))
+        .body(
+            serde_json::json!({
+                "request": {"method": "GET", "urlPath": "/orders"},
+                "response": {"status": 204}
+            })
+            .to_string(),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(imported.status(), 201);
+    let push = next_change(&mut rx).await;
+    assert!(push.line.starts_with("POST /imposters "), "{push:?}");
+    assert_eq!(push.body["port"], 18824, "{}", push.body);
+    assert_eq!(
+        push.body["stubs"].as_array().map(Vec::len),
+        Some(1),
+        "{}",
+        push.body
+    );
+
+    let registered = c
+        .post(format!("{admin}/_templates"))
+        .json(&serde_json::json!({
+            "name": "orders",
+            "variables": ["port"],
+            "imposter": {"port": "${port}", "protocol": "http"}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(registered.status(), 201);
+    let created = c
+        .post(format!("{admin}/imposters/from-template/orders"))
+        .json(&serde_json::json!({"variables": {"port": 18825}}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(created.status(), 201);
+    let push = next_change(&mut rx).await;
+    assert!(push.line.starts_with("POST /imposters "), "{push:?}");
+    assert_eq!(push.body["port"], 18825, "{}", push.body);
+
+    server.shutdown().await;
+}
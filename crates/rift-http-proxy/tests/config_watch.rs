@@ -0,0 +1,75 @@
+//! `--watch-configfile`: edits to the `--configfile` are applied to the running server — a new
+//! imposter is created, a removed one deleted — without a restart or an admin call.
+
+use clap::Parser;
+use rift_http_proxy::server::{Cli, ServerBuilder};
+use std::time::Duration;
+
+fn config(ports: &[u16]) -> String {
+    let imposters: Vec<_> = ports
+        .iter()
+        .map(|port| serde_json::json!({"port": port, "protocol": "http", "stubs": []}))
+        .collect();
+    serde_json::json!({ "imposters": imposters }).to_string()
+}
+
+/// Poll the admin listing until its ports equal `expected`, or fail after a few seconds.
+async fn wait_for_ports(client: &reqwest::Client, admin: &str, expected: &[u16]) {
+    let mut ports = Vec::new();
+    for _ in 0..50 {
+        let listing: serde_json::Value = client
+            .get(format!("{admin}/imposters"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        ports = listing["imposters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|i| i["port"].as_u64())
+            .collect::<Vec<_>>();
+        ports.sort_unstable();
+        if ports
+            .iter()
+            .copied()
+            .eq(expected.iter().map(|&p| u64::from(p)))
+        {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("expected imposters {expected:?}, still serving {ports:?}");
+}
+
+#[tokio::test]
+async fn watched_configfile_edits_are_applied_live() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("imposters.json");
+    std::fs::write(&path, config(&[18819])).unwrap();
+    let cli = Cli::try_parse_from([
+        "rift",
+        "--port",
+        "0",
+        "--metrics-port",
+        "0",
+        "--configfile",
+        path.to_str().unwrap(),
+        "--watch-configfile",
+    ])
+    .expect("parse");
+    let server = ServerBuilder::from_cli(cli).start().await.expect("start");
+    let admin = format!("http://{}", server.admin_addr());
+    let client = reqwest::Client::new();
+    wait_for_ports(&client, &admin, &[18819]).await;
+
+    std::fs::write(&path, config(&[18819, 18820])).unwrap();
+    wait_for_ports(&client, &admin, &[18819, 18820]).await;
+
+    std::fs::write(&path, config(&[18820])).unwrap();
+    wait_for_ports(&client, &admin, &[18820]).await;
+
+    server.shutdown().await;
+}
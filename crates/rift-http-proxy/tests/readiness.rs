@@ -0,0 +1,59 @@
+//! `GET /healthz` and `GET /readyz`: a configured imposter whose port is taken keeps the server
+//! unready until it is served, while liveness stays up and neither probe needs the API key.
+
+use clap::Parser;
+use rift_http_proxy::server::{Cli, ServerBuilder};
+
+#[tokio::test]
+async fn readyz_reports_a_configured_imposter_that_could_not_bind() {
+    let squatter = std::net::TcpListener::bind("0.0.0.0:0").unwrap();
+    let port = squatter.local_addr().unwrap().port();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("imposters.json");
+    let config = serde_json::json!({
+        "imposters": [{ "port": port, "protocol": "http", "stubs": [] }]
+    });
+    std::fs::write(&path, config.to_string()).unwrap();
+    let cli = Cli::try_parse_from([
+        "rift",
+        "--port",
+        "0",
+        "--metrics-port",
+        "0",
+        "--api-key",
+        "secret",
+        "--configfile",
+        path.to_str().unwrap(),
+    ])
+    .expect("parse");
+    let server = ServerBuilder::from_cli(cli).start().await.expect("start");
+    let admin = format!("http://{}", server.admin_addr());
+    let client = reqwest::Client::new();
+
+    let live = client.get(format!("{admin}/healthz")).send().await.unwrap();
+    assert_eq!(live.status(), 200);
+
+    let ready = client.get(format!("{admin}/readyz")).send().await.unwrap();
+    assert_eq!(ready.status(), 503);
+    let body: serde_json::Value = ready.json().await.unwrap();
+    assert_eq!(body["status"], "not ready");
+    let message = body["checks"]["imposters"]["message"].as_str().unwrap();
+    assert!(message.contains(&port.to_string()), "{message}");
+
+    drop(squatter);
+    let created = client
+        .post(format!("{admin}/imposters"))
+        .header("Authorization", "secret")
+        .json(&config["imposters"][0])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(created.status(), 201);
+
+    let ready = client.get(format!("{admin}/readyz")).send().await.unwrap();
+    assert_eq!(ready.status(), 200);
+    let body: serde_json::Value = ready.json().await.unwrap();
+    assert_eq!(body["checks"]["imposters"]["serving"], 1);
+
+    server.shutdown().await;
+}
@@ -0,0 +1,111 @@
+//! `--webhook`: the server POSTs a JSON notification per bus event to each configured URL. These
+//! tests start the full server through `ServerBuilder` and capture deliveries on a local listener.
+
+use clap::Parser;
+use rift_http_proxy::server::{Cli, ServerBuilder};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+
+/// Accept webhook POSTs on an ephemeral port, answering 204 and forwarding each JSON body.
+async fn receiver() -> (String, mpsc::UnboundedReceiver<serde_json::Value>) {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind receiver");
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                return;
+            };
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                let mut buf = Vec::new();
+                let mut chunk = [0u8; 4096];
+                // One request per connection is enough here: read the head, then the body.
+                loop {
+                    let n = stream.read(&mut chunk).await.unwrap_or(0);
+                    if n == 0 {
+                        return;
+                    }
+                    buf.extend_from_slice(&chunk[..n]);
+                    let text = String::from_utf8_lossy(&buf).into_owned();
+                    let Some((head, body)) = text.split_once("\r\n\r\n") else {
+                        continue;
+                    };
+                    let length = head
+                        .lines()
+                        .find_map(|l| {
+                            let (k, v) = l.split_once(':')?;
+                            k.eq_ignore_ascii_case("content-length")
+                                .then(|| v.trim().parse::<usize>().ok())?
+                        })
+                        .unwrap_or(0);
+                    if body.len() < length {
+                        continue;
+                    }
+                    let _ = tx.send(serde_json::from_str(&body[..length]).unwrap_or_default());
+                    let _ = stream
+                        .write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n")
+                        .await;
+                    return;
+                }
+            });
+        }
+    });
+    (url, rx)
+}
+
+async fn next(rx: &mut mpsc::UnboundedReceiver<serde_json::Value>) -> serde_json::Value {
+    tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("webhook delivery timed out")
+        .expect("receiver closed")
+}
+
+#[tokio::test]
+async fn webhooks_announce_config_imposters_and_unmatched_requests() {
+    let (url, mut rx) = receiver().await;
+    let dir = tempfile::tempdir().unwrap();
+    let configfile = dir.path().join("imposters.json");
+    std::fs::write(
+        &configfile,
+        r#"{"imposters":[{"port":18817,"protocol":"http","stubs":[]}]}"#,
+    )
+    .unwrap();
+    let cli = Cli::try_parse_from([
+        "rift",
+        "--port",
+        "0",
+        "--metrics-port",
+        "0",
+        "--configfile",
+        configfile.to_str().unwrap(),
+        "--webhook",
+        &url,
+        "--webhook-events",
+        "imposter.created,request.noMatch",
+    ])
+    .expect("parse");
+    let server = ServerBuilder::from_cli(cli).start().await.expect("start");
+
+    let created = next(&mut rx).await;
+    assert_eq!(created["event"], "imposter.created");
+    assert_eq!(created["port"], 18817);
+    assert!(created["timestamp"].is_string());
+
+    reqwest::get("http://127.0.0.1:18817/nowhere")
+        .await
+        .expect("unmatched request");
+    let no_match = next(&mut rx).await;
+    assert_eq!(no_match["event"], "request.noMatch");
+    assert_eq!(no_match["path"], "/nowhere");
+    assert!(
+        no_match["seq"].as_u64() > created["seq"].as_u64(),
+        "{no_match}"
+    );
+
+    server.shutdown().await;
+}
@@ -0,0 +1,97 @@
+//! Baseline files: the issues a project already has, so only new ones fail CI while it adopts
+//! the linter incrementally.
+//!
+//! An issue is matched by file, rule code and location, not by message, so rewording a message
+//! doesn't invalidate a baseline. Each entry absorbs one issue: a second copy of a baselined
+//! issue is new.
+
+use crate::types::{LintIssue, LintResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Errors reading or writing a baseline file.
+#[derive(Debug, thiserror::Error)]
+pub enum BaselineError {
+    #[error("{0}: {1}")]
+    Io(PathBuf, #[source] std::io::Error),
+    #[error("{0}: {1}")]
+    Json(PathBuf, #[source] serde_json::Error),
+}
+
+/// One recorded issue.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub file: String,
+    pub code: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// The message when the baseline was written, for people reading the file.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub message: String,
+}
+
+impl BaselineEntry {
+    fn of(issue: &LintIssue) -> Self {
+        Self {
+            file: issue.file.to_string_lossy().into_owned(),
+            code: issue.code.clone(),
+            location: issue.location.clone(),
+            message: issue.message.clone(),
+        }
+    }
+
+    /// What an issue is matched on.
+    fn key(self) -> (String, String, Option<String>) {
+        (self.file, self.code, self.location)
+    }
+}
+
+/// The issues recorded in a baseline file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub issues: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    /// A baseline of every issue in `result`.
+    pub fn from_result(result: &LintResult) -> Self {
+        Self {
+            issues: result.issues.iter().map(BaselineEntry::of).collect(),
+        }
+    }
+
+    /// Read a baseline file.
+    pub fn load(path: &Path) -> Result<Self, BaselineError> {
+        let text =
+            std::fs::read_to_string(path).map_err(|e| BaselineError::Io(path.to_path_buf(), e))?;
+        serde_json::from_str(&text).map_err(|e| BaselineError::Json(path.to_path_buf(), e))
+    }
+
+    /// Write this baseline as pretty-printed JSON.
+    pub fn save(&self, path: &Path) -> Result<(), BaselineError> {
+        let text = serde_json::to_string_pretty(self)
+            .map_err(|e| BaselineError::Json(path.to_path_buf(), e))?;
+        std::fs::write(path, text + "\n").map_err(|e| BaselineError::Io(path.to_path_buf(), e))
+    }
+
+    /// `result` without the issues this baseline records, recounting its errors and warnings.
+    pub fn filter(&self, result: LintResult) -> LintResult {
+        let mut remaining: HashMap<_, usize> = HashMap::new();
+        for entry in &self.issues {
+            *remaining.entry(entry.clone().key()).or_default() += 1;
+        }
+        let mut filtered = LintResult {
+            files_checked: result.files_checked,
+            durations: result.durations,
+            ..LintResult::default()
+        };
+        for issue in result.issues {
+            match remaining.get_mut(&BaselineEntry::of(&issue).key()) {
+                Some(count) if *count > 0 => *count -= 1,
+                _ => filtered.add_issue(issue),
+            }
+        }
+        filtered
+    }
+}
@@ -0,0 +1,144 @@
+//! Per-project lint settings, from a `.riftlint.toml` at the repository root.
+//!
+//! ```toml
+//! strict = true
+//! errors-only = false
+//! max-warnings = 10
+//! allowed-proxy-hosts = ["api.example.com", "*.staging.example.com"]
+//!
+//! [rules]
+//! W012 = "off"      # don't report this rule at all
+//! E010 = "warning"  # downgrade
+//! W003 = "error"    # promote
+//! ```
+
+use crate::types::{LintResult, Severity};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The file name looked for by [`LintConfig::discover`].
+pub const CONFIG_FILE_NAME: &str = ".riftlint.toml";
+
+/// What a rule is set to in `[rules]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    /// Drop the rule's issues.
+    Off,
+    /// Report the rule's issues as errors.
+    Error,
+    /// Report the rule's issues as warnings.
+    Warning,
+    /// Report the rule's issues as info.
+    Info,
+}
+
+/// Errors reading a `.riftlint.toml`.
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("{0}: {1}")]
+    Io(PathBuf, #[source] std::io::Error),
+    #[error("{0}: {1}")]
+    Parse(PathBuf, #[source] toml::de::Error),
+    #[error("{0}: '{1}' is not a rule code (expected e.g. E010, W012 or ORG001)")]
+    UnknownRule(PathBuf, String),
+}
+
+/// Project settings: rule severity overrides and defaults for CLI options.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct LintConfig {
+    /// Treat warnings as errors, as `--strict` does.
+    #[serde(default)]
+    pub strict: bool,
+    /// Only show errors, as `--errors-only` does.
+    #[serde(default)]
+    pub errors_only: bool,
+    /// Fail on more warnings than this, as `--max-warnings` does; the flag takes precedence.
+    #[serde(default)]
+    pub max_warnings: Option<usize>,
+    /// External hosts proxies may forward to without a warning (W033).
+    #[serde(default)]
+    pub allowed_proxy_hosts: Vec<String>,
+    /// Severity overrides by rule code.
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleLevel>,
+}
+
+impl LintConfig {
+    /// Parse a config file's contents; `path` is only used in errors.
+    pub fn parse(text: &str, path: &Path) -> Result<Self, ConfigError> {
+        let config: Self =
+            toml::from_str(text).map_err(|e| ConfigError::Parse(path.to_path_buf(), e))?;
+        if let Some(code) = config.rules.keys().find(|code| !is_rule_code(code)) {
+            return Err(ConfigError::UnknownRule(path.to_path_buf(), code.clone()));
+        }
+        Ok(config)
+    }
+
+    /// Read and parse a config file.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text =
+            std::fs::read_to_string(path).map_err(|e| ConfigError::Io(path.to_path_buf(), e))?;
+        Self::parse(&text, path)
+    }
+
+    /// Find the `.riftlint.toml` that applies to `path`: the nearest one in `path` (or its
+    /// directory, for a file) and its ancestors, stopping at the repository root (the first
+    /// directory with a `.git`).
+    pub fn discover(path: &Path) -> Option<PathBuf> {
+        let path = path.canonicalize().ok()?;
+        let start = if path.is_dir() { &path } else { path.parent()? };
+        for dir in start.ancestors() {
+            let candidate = dir.join(CONFIG_FILE_NAME);
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+            if dir.join(".git").exists() {
+                break;
+            }
+        }
+        None
+    }
+
+    /// The severity `[rules]` gives `code`, `None` when it is turned off.
+    pub fn severity_for(&self, code: &str, severity: Severity) -> Option<Severity> {
+        match self.rules.get(code) {
+            None => Some(severity),
+            Some(RuleLevel::Off) => None,
+            Some(RuleLevel::Error) => Some(Severity::Error),
+            Some(RuleLevel::Warning) => Some(Severity::Warning),
+            Some(RuleLevel::Info) => Some(Severity::Info),
+        }
+    }
+
+    /// Apply the `[rules]` overrides to a result, recounting its errors and warnings.
+    pub fn apply(&self, result: LintResult) -> LintResult {
+        if self.rules.is_empty() {
+            return result;
+        }
+        let mut applied = LintResult {
+            files_checked: result.files_checked,
+            durations: result.durations,
+            ..LintResult::default()
+        };
+        for mut issue in result.issues {
+            if let Some(severity) = self.severity_for(&issue.code, issue.severity) {
+                issue.severity = severity;
+                applied.add_issue(issue);
+            }
+        }
+        applied
+    }
+}
+
+/// `E010`, `W012`, `I001`, or a custom rule's `ORG001`: capital letters and three digits.
+fn is_rule_code(code: &str) -> bool {
+    let bytes = code.as_bytes();
+    let (prefix, digits) = bytes.split_at(bytes.len().saturating_sub(3));
+    !prefix.is_empty()
+        && prefix.iter().all(u8::is_ascii_uppercase)
+        && digits.len() == 3
+        && digits.iter().all(u8::is_ascii_digit)
+}
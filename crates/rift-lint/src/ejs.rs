@@ -0,0 +1,192 @@
+//! EJS rendering for Mountebank configfiles, so a templated config is linted as `rift
+//! --configfile` loads it rather than rejected as invalid JSON.
+//!
+//! The server's `--configfile` loader renders with this too, so the two cannot disagree. Supported
+//! tags:
+//! - `<% include 'path' %>` / `<%- include('path') %>`: the file, relative to the file that
+//!   includes it, with its own includes expanded
+//! - `<%- stringify(filename, 'path') %>` / `<%- stringify('path') %>`: a file, relative to the
+//!   config, as the inside of a JSON string
+//! - `<%= process.env.VAR %>` / `<%= process.env.VAR || 'default' %>`
+//!
+//! Any other `<%= %>` renders as nothing and any other `<% %>` is dropped; both are listed in
+//! [`RenderedEjs::unsupported`] so the linter can say the output may not be what was meant.
+
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// `<% include 'path' %>` (quoted or bare path) or EJS 3's `<%- include('path') %>`.
+static INCLUDE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"<%-?\s*include(?:\s+|\s*\(\s*)['"]?([^'"()>\s]+)['"]?\s*\)?\s*-?%>"#)
+        .expect("valid include pattern")
+});
+
+/// `<%- stringify('path') %>`, optionally with Mountebank's leading `filename` argument.
+static STRINGIFY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"<%-\s*stringify\(\s*(?:filename\s*,\s*)?['"]([^'"]+)['"]\s*\)\s*-?%>"#)
+        .expect("valid stringify pattern")
+});
+
+/// `<%= expr %>` expression tag.
+static EXPR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<%=\s*(.*?)\s*%>").expect("valid expression pattern"));
+
+/// The only supported expression: `process.env.VAR` with an optional `|| 'default'`.
+static ENV_VAR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^process\.env\.([A-Za-z_][A-Za-z0-9_]*)(?:\s*\|\|\s*['"]([^'"]*)['"]\s*)?$"#)
+        .expect("valid env-var pattern")
+});
+
+/// Remaining `<% ... %>` statement blocks.
+static STMT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<%[^=].*?%>").expect("valid statement pattern"));
+
+/// How deep includes may nest before a config is taken to include itself.
+const MAX_INCLUDE_DEPTH: usize = 32;
+
+/// Errors rendering a template: the files it pulls in must exist.
+#[derive(Debug, thiserror::Error)]
+pub enum EjsError {
+    #[error("cannot read included file '{0}' ({path}): {2}", path = .1.display())]
+    Include(String, PathBuf, #[source] std::io::Error),
+    #[error(
+        "includes nest more than {MAX_INCLUDE_DEPTH} deep at '{0}' (does a file include itself?)"
+    )]
+    TooDeep(String),
+}
+
+/// A rendered template.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderedEjs {
+    pub text: String,
+    /// Tags that were rendered as nothing because only the forms above are supported.
+    pub unsupported: Vec<String>,
+}
+
+/// Render the EJS in `content`, read from `path` (which relative paths are resolved against).
+/// Content without `<%` is returned as is.
+pub fn render_ejs(content: &str, path: &Path) -> Result<RenderedEjs, EjsError> {
+    if !content.contains("<%") {
+        return Ok(RenderedEjs {
+            text: content.to_string(),
+            unsupported: Vec::new(),
+        });
+    }
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let content = expand_includes(content, dir, 0)?;
+
+    // Before the statement strip, which would also match `<%-`.
+    let content = replace_all(&content, &STRINGIFY_RE, |cap| {
+        let name = &cap[1];
+        let file = dir.join(name);
+        let text = std::fs::read_to_string(&file)
+            .map_err(|e| EjsError::Include(name.to_string(), file, e))?;
+        let quoted = serde_json::Value::String(text).to_string();
+        Ok(quoted[1..quoted.len() - 1].to_string())
+    })?;
+
+    let mut unsupported = Vec::new();
+    let content = replace_all(&content, &EXPR_RE, |cap| {
+        Ok(match ENV_VAR_RE.captures(&cap[1]) {
+            Some(env) => std::env::var(&env[1])
+                .unwrap_or_else(|_| env.get(2).map_or("", |m| m.as_str()).to_string()),
+            None => {
+                unsupported.push(cap[0].to_string());
+                String::new()
+            }
+        })
+    })?;
+
+    unsupported.extend(STMT_RE.find_iter(&content).map(|m| m.as_str().to_string()));
+    Ok(RenderedEjs {
+        text: STMT_RE.replace_all(&content, "").into_owned(),
+        unsupported,
+    })
+}
+
+/// Inline includes, each relative to the file containing it, as EJS resolves them.
+fn expand_includes(content: &str, dir: &Path, depth: usize) -> Result<String, EjsError> {
+    replace_all(content, &INCLUDE_RE, |cap| {
+        let name = &cap[1];
+        if depth == MAX_INCLUDE_DEPTH {
+            return Err(EjsError::TooDeep(name.to_string()));
+        }
+        let file = dir.join(name);
+        let included = std::fs::read_to_string(&file)
+            .map_err(|e| EjsError::Include(name.to_string(), file.clone(), e))?;
+        expand_includes(&included, file.parent().unwrap_or(dir), depth + 1)
+    })
+}
+
+/// `content` with each match of `re` replaced by `with`, stopping at the first error.
+fn replace_all(
+    content: &str,
+    re: &Regex,
+    mut with: impl FnMut(&regex::Captures<'_>) -> Result<String, EjsError>,
+) -> Result<String, EjsError> {
+    let mut result = String::with_capacity(content.len());
+    let mut last = 0;
+    for cap in re.captures_iter(content) {
+        let full = cap.get(0).expect("group 0 always matches");
+        result.push_str(&content[last..full.start()]);
+        result.push_str(&with(&cap)?);
+        last = full.end();
+    }
+    result.push_str(&content[last..]);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statics_match_their_tags() {
+        assert_eq!(
+            INCLUDE_RE.captures(r#"<% include 'a/b.json' %>"#).unwrap()[1].to_string(),
+            "a/b.json"
+        );
+        assert_eq!(
+            INCLUDE_RE.captures("<% include bare.json %>").unwrap()[1].to_string(),
+            "bare.json"
+        );
+        assert_eq!(
+            INCLUDE_RE
+                .captures(r#"<%- include("a/b.ejs") -%>"#)
+                .unwrap()[1]
+                .to_string(),
+            "a/b.ejs"
+        );
+
+        assert_eq!(
+            STRINGIFY_RE
+                .captures(r#"<%- stringify('inject.js') %>"#)
+                .unwrap()[1]
+                .to_string(),
+            "inject.js"
+        );
+
+        assert_eq!(
+            EXPR_RE.captures("<%= process.env.HOST %>").unwrap()[1].to_string(),
+            "process.env.HOST"
+        );
+
+        let env_cap = ENV_VAR_RE.captures("process.env.PORT || '4545'").unwrap();
+        assert_eq!(env_cap[1].to_string(), "PORT");
+        assert_eq!(env_cap[2].to_string(), "4545");
+        assert!(
+            ENV_VAR_RE
+                .captures("process.env.HOST")
+                .unwrap()
+                .get(2)
+                .is_none()
+        );
+        assert!(ENV_VAR_RE.captures("someOtherExpr()").is_none());
+
+        // (?s) dotall: a statement block spanning newlines is one match.
+        assert!(STMT_RE.is_match("<% if (x) {\n y();\n} %>"));
+        // `<%=` is an expression tag, not a statement — the catch-all must not eat it.
+        assert!(!STMT_RE.is_match("<%= process.env.HOST %>"));
+    }
+}
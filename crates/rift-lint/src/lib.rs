@@ -1,7 +1,7 @@
 //! Configuration linting library for Rift HTTP Proxy.
 //!
 //! This library provides validation capabilities for Mountebank-compatible
-//! imposter configurations. It can be used as a standalone library or through
+//! imposter configurations, in JSON or YAML. It can be used as a standalone library or through
 //! the `rift-lint` CLI binary.
 //!
 //! # Example
@@ -21,13 +21,32 @@
 //! }
 //! ```
 
+mod baseline;
+mod config;
+mod ejs;
+mod live;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+mod spans;
 mod types;
 mod validator;
 
-use std::path::Path;
+use rayon::prelude::*;
+use spans::SourceMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 // Re-export public types
-pub use types::{LintIssue, LintOptions, LintResult, Severity};
+pub use baseline::{Baseline, BaselineEntry, BaselineError};
+pub use config::{CONFIG_FILE_NAME, ConfigError, LintConfig, RuleLevel};
+pub use ejs::{EjsError, RenderedEjs, render_ejs};
+pub use live::ServerState;
+pub use types::{
+    EmbeddedScript, LintIssue, LintOptions, LintResult, LintRule, ScriptCheck, ScriptKind,
+    Severity, Span,
+};
 
 // Re-export validation functions for advanced usage
 pub use validator::{
@@ -38,30 +57,151 @@ pub use validator::{
 /// Validate a parsed config value, accepting the same shapes `rift --configfile` accepts:
 /// a single imposter object, a `{"imposters": [...]}` wrapper, or a bare `[...]` array.
 /// Each imposter is validated individually so the wrapper itself isn't mistaken for one.
+///
+/// With `source`, the JSON text `value` was parsed from, each issue gets the [`Span`] of its
+/// location, or of its imposter when it has none.
 fn validate_config(
     path: &Path,
     value: &serde_json::Value,
+    source: Option<&str>,
     result: &mut LintResult,
     options: &LintOptions,
 ) {
+    let source_map = source.and_then(SourceMap::new);
+    let wrapper = if value.get("imposters").is_some_and(|i| i.is_array()) {
+        "imposters"
+    } else {
+        ""
+    };
+    for (index, imposter) in config_imposters(value).into_iter().enumerate() {
+        let first_issue = result.issues.len();
+        validate_imposter(path, imposter, result, options);
+        let Some(source_map) = &source_map else {
+            continue;
+        };
+        // Locations are relative to the imposter; the map's paths to the document.
+        let root = if value.is_array() || !wrapper.is_empty() {
+            format!("{wrapper}[{index}]")
+        } else {
+            String::new()
+        };
+        for issue in &mut result.issues[first_issue..] {
+            let path = match issue.location.as_deref() {
+                None | Some("") => Cow::Borrowed(root.as_str()),
+                Some(location) if root.is_empty() => Cow::Borrowed(location),
+                Some(location) if location.starts_with('[') => {
+                    Cow::Owned(format!("{root}{location}"))
+                }
+                Some(location) => Cow::Owned(format!("{root}.{location}")),
+            };
+            issue.span = source_map.span(&path);
+        }
+    }
+}
+
+/// The imposters in a config value, in any of the shapes [`validate_config`] accepts.
+fn config_imposters(value: &serde_json::Value) -> Vec<&serde_json::Value> {
     let imposters = value
         .get("imposters")
         .and_then(serde_json::Value::as_array)
         .or_else(|| value.as_array());
     match imposters {
-        Some(arr) => {
-            for imposter in arr {
-                validate_imposter(path, imposter, result, options);
+        Some(arr) => arr.iter().collect(),
+        None => vec![value],
+    }
+}
+
+/// A config file's contents with its EJS rendered, as `rift --configfile` renders it, unless
+/// `options.no_parse`; borrowed when there was nothing to render. A template that can't be
+/// rendered is reported as E058 and gives `None`; each tag Rift doesn't support is reported as
+/// W021.
+fn render_config<'a>(
+    path: &Path,
+    content: &'a str,
+    options: &LintOptions,
+    result: &mut LintResult,
+) -> Option<Cow<'a, str>> {
+    if options.no_parse || !content.contains("<%") {
+        return Some(Cow::Borrowed(content));
+    }
+    match render_ejs(content, path) {
+        Ok(rendered) => {
+            for tag in rendered.unsupported {
+                result.add_issue(
+                    LintIssue::warning(
+                        "W021",
+                        format!("EJS tag '{tag}' is not supported and renders as nothing"),
+                        path.to_path_buf(),
+                    )
+                    .with_suggestion(
+                        "Rift renders include, stringify and process.env expressions; \
+                         inline anything else",
+                    ),
+                );
             }
+            Some(Cow::Owned(rendered.text))
         }
-        None => validate_imposter(path, value, result, options),
+        Err(e) => {
+            result.add_issue(
+                LintIssue::error(
+                    "E058",
+                    format!("Failed to render EJS: {e}"),
+                    path.to_path_buf(),
+                )
+                .with_suggestion("Check the paths of include and stringify tags"),
+            );
+            None
+        }
+    }
+}
+
+/// Parse a rendered config file the way `rift --configfile` does: a `.yaml`/`.yml` file not written
+/// as JSON is YAML, which the server reads as a list of imposters; anything else is JSON. An error
+/// is the format's name and what is wrong.
+fn parse_config(path: &Path, content: &str) -> Result<serde_json::Value, (&'static str, String)> {
+    if !is_yaml(path, content) {
+        return serde_json::from_str(content).map_err(|e| ("JSON", e.to_string()));
+    }
+    let value: serde_json::Value =
+        serde_yaml::from_str(content).map_err(|e| ("YAML", e.to_string()))?;
+    if !value.is_array() {
+        return Err((
+            "YAML",
+            "expected a list of imposters (the server reads the `imposters` wrapper only from \
+             JSON)"
+                .to_string(),
+        ));
     }
+    Ok(value)
 }
 
-/// Lint a single imposter configuration file.
+fn is_yaml(path: &Path, content: &str) -> bool {
+    let trimmed = content.trim_start();
+    path.extension()
+        .is_some_and(|ext| ext == "yaml" || ext == "yml")
+        && !trimmed.starts_with('{')
+        && !trimmed.starts_with('[')
+}
+
+/// The text issue spans can point into: a config's content when it is the file's own JSON, not
+/// YAML or the output of EJS.
+fn span_source<'a>(path: &Path, content: &'a str, rendered: bool) -> Option<&'a str> {
+    (!rendered && !is_yaml(path, content)).then_some(content)
+}
+
+/// Lint a single imposter configuration file, JSON or YAML, rendering any EJS in it first.
 ///
 /// Returns a `LintResult` containing all issues found.
 pub fn lint_file(path: &Path, options: &LintOptions) -> LintResult {
+    let started = Instant::now();
+    let mut result = lint_file_untimed(path, options);
+    result
+        .durations
+        .push((path.to_path_buf(), started.elapsed()));
+    result
+}
+
+fn lint_file_untimed(path: &Path, options: &LintOptions) -> LintResult {
     let mut result = LintResult::new();
     result.files_checked = 1;
 
@@ -76,32 +216,35 @@ pub fn lint_file(path: &Path, options: &LintOptions) -> LintResult {
             return result;
         }
     };
+    let Some(content) = render_config(path, &content, options, &mut result) else {
+        return result;
+    };
 
-    let value: serde_json::Value = match serde_json::from_str(&content) {
+    let value = match parse_config(path, &content) {
         Ok(v) => v,
-        Err(e) => {
+        Err((format, e)) => {
             result.add_issue(LintIssue::error(
                 "E002",
-                format!("Invalid JSON: {e}"),
+                format!("Invalid {format}: {e}"),
                 path.to_path_buf(),
             ));
             return result;
         }
     };
 
-    validate_config(path, &value, &mut result, options);
+    let source = span_source(path, &content, matches!(content, Cow::Owned(_)));
+    validate_config(path, &value, source, &mut result, options);
     result
 }
 
-/// Lint all JSON files in a directory (non-recursive).
+/// Lint all JSON and YAML files in a directory (non-recursive), together, as [`lint_files`] does.
 ///
 /// Returns a `LintResult` containing all issues found across all files.
 pub fn lint_directory(path: &Path, options: &LintOptions) -> LintResult {
-    let mut result = LintResult::new();
-
     let entries = match std::fs::read_dir(path) {
         Ok(e) => e,
         Err(e) => {
+            let mut result = LintResult::new();
             result.add_issue(LintIssue::error(
                 "E001",
                 format!("Failed to read directory: {e}"),
@@ -111,17 +254,214 @@ pub fn lint_directory(path: &Path, options: &LintOptions) -> LintResult {
         }
     };
 
-    for entry in entries.flatten() {
-        let file_path = entry.path();
-        if file_path.extension().map(|e| e == "json").unwrap_or(false) {
-            let file_result = lint_file(&file_path, options);
-            result.merge(file_result);
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|file| {
+            file.extension()
+                .is_some_and(|e| e == "json" || e == "yaml" || e == "yml")
+        })
+        .collect();
+    files.sort();
+    lint_files(&files, options)
+}
+
+/// Lint a set of imposter files together: each one as [`lint_file`] does, plus the checks that
+/// span files — two imposters on one port (E002) or with one name (W020), a stub repeated in
+/// another imposter (I005) — and, with
+/// [`LintOptions::with_server`], against a running server (E059, W022–W024).
+///
+/// A file that can't be read or parsed is reported as E001, as the CLI reports it.
+///
+/// Files are linted in parallel on rayon's current thread pool; issues (and
+/// [`LintResult::durations`]) are still reported in the order of `paths`.
+pub fn lint_files(paths: &[PathBuf], options: &LintOptions) -> LintResult {
+    let linted: Vec<_> = paths
+        .par_iter()
+        .map(|path| {
+            let started = Instant::now();
+            let (mut result, value) = lint_one_of_many(path, options);
+            result.durations.push((path.clone(), started.elapsed()));
+            (result, value)
+        })
+        .collect();
+
+    let mut result = LintResult::new();
+    let mut configs = Vec::new();
+    for (path, (file_result, value)) in paths.iter().zip(linted) {
+        result.merge(file_result);
+        if let Some(value) = value {
+            configs.push((path, value));
         }
     }
 
+    let imposters: Vec<_> = configs
+        .iter()
+        .flat_map(|(path, value)| config_imposters(value).into_iter().map(move |i| (*path, i)))
+        .collect();
+    check_port_conflicts(&imposters, &mut result);
+    check_duplicate_names(&imposters, &mut result);
+    check_stubs_repeated_across_imposters(&imposters, &mut result);
+    if let Some(server) = &options.server {
+        live::check_against_server(&imposters, server, &mut result);
+    }
     result
 }
 
+/// One file of [`lint_files`]: its own issues, and its config when it parsed.
+fn lint_one_of_many(
+    path: &PathBuf,
+    options: &LintOptions,
+) -> (LintResult, Option<serde_json::Value>) {
+    let mut result = LintResult::new();
+    result.files_checked = 1;
+    let raw = match std::fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(e) => {
+            result.add_issue(
+                LintIssue::error("E001", format!("Failed to read file: {e}"), path.clone())
+                    .with_suggestion("Check for JSON syntax errors"),
+            );
+            return (result, None);
+        }
+    };
+    let Some(content) = render_config(path, &raw, options, &mut result) else {
+        return (result, None);
+    };
+    match parse_config(path, &content) {
+        Ok(value) => {
+            let source = span_source(path, &content, matches!(content, Cow::Owned(_)));
+            validate_config(path, &value, source, &mut result, options);
+            (result, Some(value))
+        }
+        Err((format, e)) => {
+            result.add_issue(
+                LintIssue::error(
+                    "E001",
+                    format!("Failed to parse {format}: {e}"),
+                    path.clone(),
+                )
+                .with_suggestion(format!("Check for {format} syntax errors")),
+            );
+            (result, None)
+        }
+    }
+}
+
+/// E002: more than one imposter on a port, reported once per port against the first of them.
+fn check_port_conflicts(imposters: &[(&PathBuf, &serde_json::Value)], result: &mut LintResult) {
+    let mut by_port: BTreeMap<u64, Vec<&PathBuf>> = BTreeMap::new();
+    for (path, imposter) in imposters {
+        if let Some(port) = imposter.get("port").and_then(serde_json::Value::as_u64) {
+            by_port.entry(port).or_default().push(path);
+        }
+    }
+    for (port, files) in by_port.into_iter().filter(|(_, files)| files.len() > 1) {
+        let file_names: Vec<String> = files
+            .iter()
+            .map(|f| {
+                f.file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        result.add_issue(
+            LintIssue::error(
+                "E002",
+                format!(
+                    "Port {port} is used by {} imposters: {}",
+                    files.len(),
+                    file_names.join(", ")
+                ),
+                files[0].clone(),
+            )
+            .with_location("port")
+            .with_suggestion(format!(
+                "Assign unique ports to each imposter. Consider using ports {}+",
+                port + 1
+            )),
+        );
+    }
+}
+
+/// W020: an imposter sharing its `name` with an earlier one, which makes the two hard to tell
+/// apart in logs, `rift-tui` and the admin API.
+fn check_duplicate_names(imposters: &[(&PathBuf, &serde_json::Value)], result: &mut LintResult) {
+    let mut first_with_name: BTreeMap<&str, &PathBuf> = BTreeMap::new();
+    for (path, imposter) in imposters {
+        let Some(name) = imposter.get("name").and_then(serde_json::Value::as_str) else {
+            continue;
+        };
+        match first_with_name.get(name) {
+            Some(first) => result.add_issue(
+                LintIssue::warning(
+                    "W020",
+                    format!(
+                        "Imposter name '{name}' is already used by an imposter in {}",
+                        first.file_name().unwrap_or_default().to_string_lossy()
+                    ),
+                    (*path).clone(),
+                )
+                .with_location("name")
+                .with_suggestion("Give each imposter a distinct name"),
+            ),
+            None => {
+                first_with_name.insert(name, path);
+            }
+        }
+    }
+}
+
+/// I005: a stub the same as one in an earlier imposter, which could be kept in one place (an
+/// EJS partial, say) instead of in each. Stubs without predicates are left out: a catch-all
+/// default response is expected to repeat. Repeats within an imposter are W034.
+fn check_stubs_repeated_across_imposters(
+    imposters: &[(&PathBuf, &serde_json::Value)],
+    result: &mut LintResult,
+) {
+    let mut first_with: HashMap<String, (usize, usize)> = HashMap::new();
+    for (imposter_idx, (path, imposter)) in imposters.iter().enumerate() {
+        let Some(stubs) = imposter.get("stubs").and_then(serde_json::Value::as_array) else {
+            continue;
+        };
+        for (stub_idx, stub) in stubs.iter().enumerate() {
+            let has_predicates = stub
+                .get("predicates")
+                .and_then(serde_json::Value::as_array)
+                .is_some_and(|predicates| !predicates.is_empty());
+            if !has_predicates {
+                continue;
+            }
+            let (first_imposter, first_stub) = *first_with
+                .entry(validator::stub_fingerprint(stub))
+                .or_insert((imposter_idx, stub_idx));
+            if first_imposter == imposter_idx {
+                continue;
+            }
+            let (first_path, first) = imposters[first_imposter];
+            let imposter_name = match first.get("port").and_then(serde_json::Value::as_u64) {
+                Some(port) => format!("the imposter on port {port}"),
+                None => "an imposter".to_string(),
+            };
+            result.add_issue(
+                LintIssue::info(
+                    "I005",
+                    format!(
+                        "Stub is the same as stubs[{first_stub}] of {imposter_name} in {}",
+                        first_path.file_name().unwrap_or_default().to_string_lossy()
+                    ),
+                    (*path).clone(),
+                )
+                .with_location(format!("stubs[{stub_idx}]"))
+                .with_suggestion(
+                    "Keep shared stubs in one place, such as an EJS partial both files include",
+                ),
+            );
+        }
+    }
+}
+
 /// Lint a JSON string directly (useful for in-memory validation).
 ///
 /// Returns a `LintResult` containing all issues found.
@@ -143,7 +483,7 @@ pub fn lint_json(json: &str, source_name: &str, options: &LintOptions) -> LintRe
         }
     };
 
-    validate_config(path, &value, &mut result, options);
+    validate_config(path, &value, Some(json), &mut result, options);
     result
 }
 
@@ -159,6 +499,6 @@ pub fn lint_value(
     result.files_checked = 1;
 
     let path = Path::new(source_name);
-    validate_config(path, value, &mut result, options);
+    validate_config(path, value, None, &mut result, options);
     result
 }
@@ -0,0 +1,373 @@
+//! Checks against a running server (`rift-lint --against`): what the files would meet if they were
+//! loaded into it now.
+//!
+//! - E059: the server would refuse the imposter: its port is the admin port, or it runs scripts
+//!   and the server has injection turned off.
+//! - W022: the port already serves a different imposter (another name or protocol).
+//! - W023: the deployed imposter has drifted from the file: other stubs or predicates.
+//! - W024: the server lacks something the imposter uses (JavaScript, the Redis flow-state
+//!   backend), or is older than the linter.
+
+use crate::types::{LintIssue, LintResult};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// What a running server reports about itself and its imposters.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerState {
+    /// Where it was read from; issues about the server itself are reported against this.
+    pub url: String,
+    /// `GET /config`.
+    pub config: Value,
+    /// The `imposters` of `GET /imposters?replayable=true`.
+    pub imposters: Vec<Value>,
+}
+
+impl ServerState {
+    fn admin_port(&self) -> Option<u64> {
+        self.config.pointer("/options/port").and_then(Value::as_u64)
+    }
+
+    fn allows_injection(&self) -> bool {
+        self.config
+            .pointer("/options/allowInjection")
+            .and_then(Value::as_bool)
+            .unwrap_or(true)
+    }
+
+    /// Whether the server was built with `feature`; `true` when it doesn't say, as servers
+    /// before `features` was reported (and Mountebank) don't.
+    fn has_feature(&self, feature: &str) -> bool {
+        self.config
+            .get("features")
+            .and_then(|features| features.get(feature))
+            .and_then(Value::as_bool)
+            .unwrap_or(true)
+    }
+
+    fn deployed(&self, port: u64) -> Option<&Value> {
+        self.imposters
+            .iter()
+            .find(|imposter| imposter.get("port").and_then(Value::as_u64) == Some(port))
+    }
+}
+
+/// Check each imposter against `server`, and the server's version against the linter's.
+pub(crate) fn check_against_server(
+    imposters: &[(&PathBuf, &Value)],
+    server: &ServerState,
+    result: &mut LintResult,
+) {
+    check_version(server, result);
+    for (file, imposter) in imposters {
+        check_capabilities(file, imposter, server, result);
+        let Some(port) = imposter.get("port").and_then(Value::as_u64) else {
+            continue;
+        };
+        if server.admin_port() == Some(port) {
+            result.add_issue(
+                LintIssue::error(
+                    "E059",
+                    format!("Port {port} is the server's admin port"),
+                    file.to_path_buf(),
+                )
+                .with_location("port")
+                .with_suggestion("Move the imposter to another port"),
+            );
+        } else if let Some(deployed) = server.deployed(port) {
+            check_deployed(file, imposter, deployed, port, result);
+        }
+    }
+}
+
+/// W024 once for a server older than this linter: rules pass for fields it may not read.
+fn check_version(server: &ServerState, result: &mut LintResult) {
+    let Some(version) = server.config.get("version").and_then(Value::as_str) else {
+        return;
+    };
+    if version_tuple(version) < version_tuple(env!("CARGO_PKG_VERSION")) {
+        result.add_issue(
+            LintIssue::warning(
+                "W024",
+                format!(
+                    "Server is version {version}, older than rift-lint {}; it may not support \
+                     everything the linter accepts",
+                    env!("CARGO_PKG_VERSION")
+                ),
+                PathBuf::from(&server.url),
+            )
+            .with_suggestion("Lint with the rift-lint release matching the server"),
+        );
+    }
+}
+
+/// `1.2.3` as comparable numbers; pre-release and build suffixes are ignored.
+fn version_tuple(version: &str) -> Vec<u64> {
+    version
+        .split(['-', '+'])
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+fn check_capabilities(
+    file: &Path,
+    imposter: &Value,
+    server: &ServerState,
+    result: &mut LintResult,
+) {
+    if !server.allows_injection() && uses_script_surface(imposter) {
+        result.add_issue(
+            LintIssue::error(
+                "E059",
+                "The server has injection turned off and refuses imposters with scripts \
+                 (inject/decorate/shellTransform/JS-function wait/_rift.script)",
+                file.to_path_buf(),
+            )
+            .with_suggestion("Start the server with --allowInjection, or remove the scripts"),
+        );
+    }
+    if !server.has_feature("javascript") && uses_javascript(imposter) {
+        result.add_issue(
+            LintIssue::warning(
+                "W024",
+                "The server was built without JavaScript, which this imposter's scripts need",
+                file.to_path_buf(),
+            )
+            .with_suggestion("Use a server built with the `javascript` feature"),
+        );
+    }
+    let backend = imposter.pointer("/_rift/flowState/backend");
+    if !server.has_feature("redisBackend") && backend.and_then(Value::as_str) == Some("redis") {
+        result.add_issue(
+            LintIssue::warning(
+                "W024",
+                "The server was built without the Redis flow-state backend",
+                file.to_path_buf(),
+            )
+            .with_location("_rift.flowState.backend")
+            .with_suggestion("Use a server built with the `redis-backend` feature"),
+        );
+    }
+}
+
+/// W022 for a port serving another imposter, W023 for a deployed copy that has drifted.
+fn check_deployed(
+    file: &Path,
+    imposter: &Value,
+    deployed: &Value,
+    port: u64,
+    result: &mut LintResult,
+) {
+    let differs = |field: &str| {
+        let ours = imposter.get(field).and_then(Value::as_str);
+        let theirs = deployed.get(field).and_then(Value::as_str);
+        ours.is_some() && ours != theirs
+    };
+    if let Some(field) = ["name", "protocol"].into_iter().find(|f| differs(f)) {
+        result.add_issue(
+            LintIssue::warning(
+                "W022",
+                format!(
+                    "Port {port} on the server already serves a different imposter ({field} {})",
+                    deployed.get(field).unwrap_or(&Value::Null)
+                ),
+                file.to_path_buf(),
+            )
+            .with_location("port")
+            .with_suggestion("Use a free port, or delete the deployed imposter first"),
+        );
+        return;
+    }
+
+    let stubs = stubs_of(imposter);
+    let deployed_stubs = stubs_of(deployed);
+    let drift = if stubs.len() != deployed_stubs.len() {
+        Some((
+            "stubs".to_string(),
+            format!(
+                "the server has {} stubs, the file {}",
+                deployed_stubs.len(),
+                stubs.len()
+            ),
+        ))
+    } else {
+        stubs
+            .iter()
+            .zip(deployed_stubs)
+            .enumerate()
+            .find_map(|(i, (stub, deployed_stub))| {
+                stub_drift(stub, deployed_stub).map(|what| (format!("stubs[{i}]"), what))
+            })
+    };
+    if let Some((location, what)) = drift {
+        result.add_issue(
+            LintIssue::warning(
+                "W023",
+                format!("The imposter deployed on port {port} differs from this file: {what}"),
+                file.to_path_buf(),
+            )
+            .with_location(location)
+            .with_suggestion("Reload the file into the server, or save the deployed imposter"),
+        );
+    }
+}
+
+fn stubs_of(imposter: &Value) -> &[Value] {
+    imposter
+        .get("stubs")
+        .and_then(Value::as_array)
+        .map_or(&[], Vec::as_slice)
+}
+
+/// How a deployed stub differs from the file's. Only predicates and the number of responses are
+/// compared: the server rewrites responses (`_behaviors`, status codes, defaults) when it stores
+/// them.
+fn stub_drift(stub: &Value, deployed: &Value) -> Option<String> {
+    let empty = Value::Array(Vec::new());
+    let predicates = stub.get("predicates").unwrap_or(&empty);
+    if !matches_deployed(predicates, deployed.get("predicates").unwrap_or(&empty)) {
+        return Some("other predicates".to_string());
+    }
+    let responses = |s: &Value| {
+        s.get("responses")
+            .and_then(Value::as_array)
+            .map_or(0, Vec::len)
+    };
+    let (ours, theirs) = (responses(stub), responses(deployed));
+    (ours != theirs).then(|| format!("the server has {theirs} responses, the file {ours}"))
+}
+
+/// Whether `deployed` is what the server stores for `file`, which fills in fields the file leaves
+/// empty or out and writes some numbers as strings.
+fn matches_deployed(file: &Value, deployed: &Value) -> bool {
+    match (file, deployed) {
+        (Value::Object(ours), Value::Object(theirs)) => ours.iter().all(|(key, value)| {
+            theirs
+                .get(key)
+                .map_or_else(|| is_empty(value), |t| matches_deployed(value, t))
+        }),
+        (Value::Array(ours), Value::Array(theirs)) => {
+            ours.len() == theirs.len()
+                && ours.iter().zip(theirs).all(|(o, t)| matches_deployed(o, t))
+        }
+        (Value::Number(n), Value::String(s)) | (Value::String(s), Value::Number(n)) => {
+            n.to_string() == *s
+        }
+        _ => file == deployed,
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null | Value::Bool(false) => true,
+        Value::String(s) => s.is_empty(),
+        Value::Array(a) => a.is_empty(),
+        Value::Object(o) => o.is_empty(),
+        Value::Number(_) | Value::Bool(true) => false,
+    }
+}
+
+/// Whether `imposter` has anything the server's `--allowInjection` gate refuses: an `inject`
+/// predicate, response or predicate generator, a `decorate` or `shellTransform` behavior, a
+/// `wait` that isn't a number or `{min, max}`, a proxy's `addDecorateBehavior`, or
+/// `_rift.script`.
+fn uses_script_surface(imposter: &Value) -> bool {
+    imposter
+        .get("defaultBehaviors")
+        .is_some_and(behaviors_are_scripted)
+        || stubs_of(imposter).iter().any(|stub| {
+            predicates_of(stub).any(predicate_has_inject)
+                || responses_of(stub).any(|response| {
+                    response.get("inject").is_some()
+                        || response.pointer("/_rift/script").is_some()
+                        || response
+                            .get("_behaviors")
+                            .or_else(|| response.get("behaviors"))
+                            .is_some_and(behaviors_are_scripted)
+                        || response.pointer("/proxy/addDecorateBehavior").is_some()
+                        || generator_injects(response)
+                })
+        })
+}
+
+/// Whether `imposter` runs JavaScript: an `inject`, a function `wait`, or a JavaScript
+/// `_rift.script`. A `decorate` may be Rhai, so it doesn't count.
+fn uses_javascript(imposter: &Value) -> bool {
+    stubs_of(imposter).iter().any(|stub| {
+        predicates_of(stub).any(predicate_has_inject)
+            || responses_of(stub).any(|response| {
+                let script_engine = response
+                    .pointer("/_rift/script/engine")
+                    .and_then(Value::as_str);
+                response.get("inject").is_some()
+                    || generator_injects(response)
+                    || matches!(script_engine, Some("javascript" | "js"))
+                    || response.get("_behaviors").is_some_and(waits_for_function)
+            })
+    })
+}
+
+/// A `_behaviors` block, object or ordered array, with a JavaScript function `wait`.
+fn waits_for_function(behaviors: &Value) -> bool {
+    match behaviors.as_array() {
+        Some(entries) => entries.iter().any(waits_for_function),
+        None => behaviors.get("wait").is_some_and(Value::is_string),
+    }
+}
+
+fn predicates_of(stub: &Value) -> impl Iterator<Item = &Value> {
+    stub.get("predicates")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+}
+
+fn responses_of(stub: &Value) -> impl Iterator<Item = &Value> {
+    stub.get("responses")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+}
+
+fn predicate_has_inject(predicate: &Value) -> bool {
+    predicate.get("inject").is_some()
+        || predicate.get("not").is_some_and(predicate_has_inject)
+        || ["and", "or"].iter().any(|op| {
+            predicate
+                .get(*op)
+                .and_then(Value::as_array)
+                .is_some_and(|preds| preds.iter().any(predicate_has_inject))
+        })
+}
+
+fn generator_injects(response: &Value) -> bool {
+    response
+        .pointer("/proxy/predicateGenerators")
+        .and_then(Value::as_array)
+        .is_some_and(|generators| generators.iter().any(|g| g.get("inject").is_some()))
+}
+
+/// A `_behaviors` block, object or ordered array, with `decorate`, `shellTransform` or a `wait`
+/// that could run code.
+fn behaviors_are_scripted(behaviors: &Value) -> bool {
+    if let Some(entries) = behaviors.as_array() {
+        return entries.iter().any(behaviors_are_scripted);
+    }
+    let Some(obj) = behaviors.as_object() else {
+        return false;
+    };
+    let wait_is_delay = |wait: &Value| {
+        wait.is_number()
+            || wait.as_object().is_some_and(|o| {
+                o.len() == 2
+                    && o.get("min").is_some_and(Value::is_number)
+                    && o.get("max").is_some_and(Value::is_number)
+            })
+    };
+    obj.contains_key("decorate")
+        || obj.contains_key("shellTransform")
+        || obj.get("wait").is_some_and(|wait| !wait_is_delay(wait))
+}
@@ -6,15 +6,19 @@
 //! Usage:
 //!   rift-lint <directory_or_file> [OPTIONS]
 
-use clap::Parser;
-use rift_lint::{LintIssue, LintOptions, LintResult, Severity, lint_value};
+use clap::{Parser, ValueEnum};
+use rift_lint::{
+    Baseline, LintConfig, LintIssue, LintOptions, LintResult, RuleLevel, ServerState, Severity,
+    lint_files,
+};
+use serde::Serialize;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
-/// Runtime ANSI color codes. Resolved once in `main` from `NO_COLOR`/TTY/json-mode via
+/// Runtime ANSI color codes. Resolved once in `main` from `NO_COLOR`/TTY/output format via
 /// `Palette::detect`, then read anywhere via `palette()`. Fields are empty strings when color is
 /// disabled, so `{green}`-style interpolation becomes a no-op instead of requiring call-site branching.
 #[derive(Debug, Clone, Copy)]
@@ -39,11 +43,12 @@ impl Palette {
         reset: "",
     };
 
-    /// Color is on only for an interactive text-mode session: never in `-o json` (stdout must be
-    /// pure JSON), never with `NO_COLOR` set, and never when stdout is piped/redirected.
-    fn detect(json_mode: bool) -> Self {
-        let color =
-            !json_mode && std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal();
+    /// Color is on only for an interactive text-mode session: never in `-o json`/`-o junit` (stdout
+    /// must be pure JSON or XML), never with `NO_COLOR` set, and never when stdout is piped/redirected.
+    fn detect(machine_output: bool) -> Self {
+        let color = !machine_output
+            && std::env::var_os("NO_COLOR").is_none()
+            && std::io::stdout().is_terminal();
         if color {
             Palette {
                 green: "\x1b[32m",
@@ -68,6 +73,17 @@ fn palette() -> Palette {
     PALETTE.get().copied().unwrap_or(Palette::PLAIN)
 }
 
+/// How lint results are written to stdout.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    /// Human-readable report
+    Text,
+    /// The full `LintResult` as a versioned JSON report
+    Json,
+    /// JUnit XML: one test case per file, one failure per error
+    Junit,
+}
+
 /// Rift Imposter Configuration Linter
 #[derive(Parser, Debug)]
 #[command(name = "rift-lint")]
@@ -77,7 +93,7 @@ fn palette() -> Palette {
     about = "Validate imposter configuration files for Rift compatibility"
 )]
 struct Args {
-    /// Path to imposter file or directory containing imposter files
+    /// Path to imposter file (JSON, YAML or EJS) or directory containing imposter files
     #[arg(required = true)]
     path: PathBuf,
 
@@ -85,23 +101,55 @@ struct Args {
     #[arg(short, long)]
     fix: bool,
 
-    /// Output format: text (default), json
-    #[arg(short, long, default_value = "text")]
-    output: String,
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
 
     /// Only show errors (hide warnings)
     #[arg(short = 'e', long)]
     errors_only: bool,
 
-    /// Strict mode - treat warnings as errors
+    /// Strict mode - fail on any warning, as --max-warnings 0 does
     #[arg(short, long)]
     strict: bool,
+
+    /// Fail (exit code 3) when there are more than N warnings
+    #[arg(long, value_name = "N")]
+    max_warnings: Option<usize>,
+
+    /// Project settings file (default: the nearest .riftlint.toml, up to the repository root)
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Only report issues not recorded in this baseline file
+    #[arg(short, long)]
+    baseline: Option<PathBuf>,
+
+    /// Record the current issues in the --baseline file instead of failing on them
+    #[arg(long, requires = "baseline")]
+    update_baseline: bool,
+
+    /// Lint files as written, without rendering EJS first (as `rift --no-parse` loads them)
+    #[arg(long, visible_alias = "noParse")]
+    no_parse: bool,
+
+    /// Also check the files against a running server's admin API, e.g. http://localhost:2525
+    #[arg(long, value_name = "URL")]
+    against: Option<String>,
+
+    /// API key for --against, for an admin API started with --api-key
+    #[arg(long, env = "RIFT_API_KEY", hide_env_values = true)]
+    api_key: Option<String>,
+
+    /// Files to lint at once (default: one per CPU)
+    #[arg(short, long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: Option<u16>,
 }
 
-/// Print to stdout in text mode, or stderr in json mode. In `-o json`, stdout is reserved
-/// exclusively for the final `print_results_json` payload — every other message is decoration.
-fn emit(json_mode: bool, msg: &str) {
-    if json_mode {
+/// Print to stdout in text mode, or stderr in json/junit mode. There, stdout is reserved
+/// exclusively for the final report — every other message is decoration.
+fn emit(machine_output: bool, msg: &str) {
+    if machine_output {
         eprintln!("{msg}");
     } else {
         println!("{msg}");
@@ -109,9 +157,9 @@ fn emit(json_mode: bool, msg: &str) {
 }
 
 fn main() {
-    let args = Args::parse();
-    let json_mode = args.output == "json";
-    let _ = PALETTE.set(Palette::detect(json_mode));
+    let mut args = Args::parse();
+    let machine_output = args.output != OutputFormat::Text;
+    let _ = PALETTE.set(Palette::detect(machine_output));
     let Palette {
         yellow,
         cyan,
@@ -122,28 +170,94 @@ fn main() {
     } = palette();
 
     // The banner and scan progress are decoration, not data: always on stderr so stdout stays
-    // clean in both json mode (pure JSON) and piped text mode (no banner noise).
+    // clean in json/junit mode (pure JSON or XML) and piped text mode (no banner noise).
     eprintln!("{bold}{cyan}Rift Imposter Linter{reset}");
     eprintln!("{dim}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━{reset}");
 
-    let mut result = LintResult::default();
-    let options = LintOptions::default();
+    // `.riftlint.toml` can only turn options on; the flags still work on top of it.
+    let config_path = args
+        .config
+        .clone()
+        .or_else(|| LintConfig::discover(&args.path));
+    let config = match &config_path {
+        Some(path) => match LintConfig::load(path) {
+            Ok(config) => {
+                eprintln!("{dim}Config:{reset}   {cyan}{}{reset}", path.display());
+                config
+            }
+            Err(e) => {
+                eprintln!("{yellow}Error:{reset} invalid lint config {e}");
+                std::process::exit(2);
+            }
+        },
+        None => LintConfig::default(),
+    };
+    args.strict |= config.strict;
+    args.errors_only |= config.errors_only;
+    args.max_warnings = if args.strict {
+        Some(0)
+    } else {
+        args.max_warnings.or(config.max_warnings)
+    };
+    let settings = ReportSettings {
+        config_file: config_path.as_deref(),
+        strict: args.strict,
+        errors_only: args.errors_only,
+        max_warnings: args.max_warnings,
+        baseline: args.baseline.as_deref(),
+        allowed_proxy_hosts: &config.allowed_proxy_hosts,
+        rules: &config.rules,
+    };
+
+    // Built with `scripting`, embedded scripts are parsed by the server's own validators.
+    let options = LintOptions {
+        no_parse: args.no_parse,
+        allowed_proxy_hosts: config.allowed_proxy_hosts.clone(),
+        ..LintOptions::default()
+    };
+    #[cfg(feature = "scripting")]
+    let options = options.with_script_check(rift_lint::scripting::server_script_check);
+    let options = match &args.against {
+        Some(url) => match fetch_server_state(url, args.api_key.as_deref()) {
+            Ok(server) => {
+                eprintln!("{dim}Against:{reset}  {cyan}{}{reset}", server.url);
+                options.with_server(server)
+            }
+            Err(e) => {
+                eprintln!("{yellow}Error:{reset} could not read the server at {url}: {e}");
+                std::process::exit(2);
+            }
+        },
+        None => options,
+    };
+
+    if let Some(jobs) = args.jobs
+        && let Err(e) = rayon::ThreadPoolBuilder::new()
+            .num_threads(usize::from(jobs))
+            .build_global()
+    {
+        eprintln!("{yellow}Error:{reset} could not start {jobs} lint threads: {e}");
+        std::process::exit(2);
+    }
 
     // Collect all imposter files
     let files = collect_imposter_files(&args.path);
 
     if files.is_empty() {
         emit(
-            json_mode,
+            machine_output,
             &format!(
-                "{yellow}Warning:{reset} No JSON files found in {:?}",
+                "{yellow}Warning:{reset} No JSON or YAML files found in {:?}",
                 args.path
             ),
         );
-        // In json mode still emit a (zero) result so stdout is always valid JSON — a consumer
+        // In json/junit mode still emit a (zero) report so stdout is always valid — a consumer
         // piping to `jq` shouldn't get empty input for the no-files case (issue #347).
-        if json_mode {
-            print_results_json(&result);
+        let result = LintResult::default();
+        match args.output {
+            OutputFormat::Json => print_results_json(&result, &settings),
+            OutputFormat::Junit => print_results_junit(&result, &files, false),
+            OutputFormat::Text => {}
         }
         std::process::exit(0);
     }
@@ -153,64 +267,135 @@ fn main() {
         "{dim}Found:{reset}    {bold}{}{reset} imposter file(s)\n",
         files.len()
     );
-    result.files_checked = files.len();
-
-    // First pass: Load all files and check for port conflicts
-    let mut port_map: HashMap<u16, Vec<PathBuf>> = HashMap::new();
-    let mut imposters: Vec<(PathBuf, Value)> = Vec::new();
-
-    for file in &files {
-        match load_imposter_file(file) {
-            Ok(imposter) => {
-                if let Some(port) = imposter.get("port").and_then(|v| v.as_u64()) {
-                    port_map.entry(port as u16).or_default().push(file.clone());
-                }
-                imposters.push((file.clone(), imposter));
+    // Validate every file, and the checks that span them (ports, names), using the library
+    let mut result = config.apply(lint_files(&files, &options));
+
+    if let Some(path) = &args.baseline {
+        if args.update_baseline {
+            if let Err(e) = Baseline::from_result(&result).save(path) {
+                eprintln!("{yellow}Error:{reset} could not write baseline {e}");
+                std::process::exit(2);
             }
-            Err(e) => {
-                result.add_issue(
-                    LintIssue::error("E001", format!("Failed to parse JSON: {e}"), file.clone())
-                        .with_suggestion("Check for JSON syntax errors"),
+            eprintln!(
+                "{dim}Baseline:{reset} recorded {bold}{}{reset} issue(s) in {cyan}{}{reset}",
+                result.issues.len(),
+                path.display()
+            );
+            std::process::exit(0);
+        }
+        match Baseline::load(path) {
+            Ok(baseline) => {
+                let total = result.issues.len();
+                result = baseline.filter(result);
+                eprintln!(
+                    "{dim}Baseline:{reset} {bold}{}{reset} known issue(s) hidden by {cyan}{}{reset}",
+                    total - result.issues.len(),
+                    path.display()
                 );
             }
+            Err(e) => {
+                eprintln!("{yellow}Error:{reset} invalid baseline {e}");
+                std::process::exit(2);
+            }
         }
     }
 
-    // Check for port conflicts
-    check_port_conflicts(&port_map, &mut result);
+    // Print results
+    let outcome = Outcome::of(&result, args.max_warnings);
+    match args.output {
+        OutputFormat::Json => print_results_json(&result, &settings),
+        OutputFormat::Junit => print_results_junit(
+            &result,
+            &files,
+            args.max_warnings.is_some_and(|max| result.warnings > max),
+        ),
+        OutputFormat::Text => print_results(&result, &args, outcome),
+    }
 
-    // Second pass: Validate each parsed imposter using the library
-    for (file, value) in &imposters {
-        let file_result = lint_value(value, &file.to_string_lossy(), &options);
-        // Merge without double-counting files_checked (we already counted)
-        result.issues.extend(file_result.issues);
-        result.errors += file_result.errors;
-        result.warnings += file_result.warnings;
+    // Apply fixes if requested
+    let fixable = result.errors > 0 || result.issues.iter().any(|issue| issue.code == "W034");
+    if args.fix && fixable {
+        emit(machine_output, &format!("\n{bold}Applying fixes...{reset}"));
+        let imposters: Vec<(PathBuf, Value)> = files
+            .iter()
+            .filter_map(|file| Some((file.clone(), load_imposter_file(file).ok()?)))
+            .collect();
+        apply_fixes(&imposters, machine_output);
     }
 
-    // Print results
-    if json_mode {
-        print_results_json(&result);
-    } else {
-        print_results(&result, &args);
+    std::process::exit(outcome.exit_code());
+}
+
+/// How a run ends, each with its own exit code so CI can tell them apart. Usage errors (a bad
+/// flag or config, an unreadable baseline or server) exit with 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    /// No errors, and no more warnings than allowed: 0.
+    Passed,
+    /// Errors: 1.
+    Errors,
+    /// No errors, but more warnings than `--max-warnings` (or any under `--strict`): 3.
+    TooManyWarnings,
+}
+
+impl Outcome {
+    fn of(result: &LintResult, max_warnings: Option<usize>) -> Self {
+        if result.errors > 0 {
+            Self::Errors
+        } else if max_warnings.is_some_and(|max| result.warnings > max) {
+            Self::TooManyWarnings
+        } else {
+            Self::Passed
+        }
     }
 
-    // Apply fixes if requested
-    if args.fix && result.errors > 0 {
-        emit(json_mode, &format!("\n{bold}Applying fixes...{reset}"));
-        apply_fixes(&imposters, json_mode);
+    fn exit_code(self) -> i32 {
+        match self {
+            Self::Passed => 0,
+            Self::Errors => 1,
+            Self::TooManyWarnings => 3,
+        }
     }
+}
 
-    // Exit with error code if there were errors (or warnings in strict mode)
-    let has_errors = result.errors > 0 || (args.strict && result.warnings > 0);
-    std::process::exit(if has_errors { 1 } else { 0 });
+/// Read what the server at `url` is running, for `--against`.
+fn fetch_server_state(url: &str, api_key: Option<&str>) -> Result<ServerState, reqwest::Error> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()?;
+    let base = url.trim_end_matches('/');
+    let get = |path: &str| -> Result<Value, reqwest::Error> {
+        let mut request = client.get(format!("{base}{path}"));
+        if let Some(key) = api_key {
+            request = request.header(reqwest::header::AUTHORIZATION, key);
+        }
+        request.send()?.error_for_status()?.json()
+    };
+    let config = get("/config")?;
+    let imposters = get("/imposters?replayable=true")?
+        .get("imposters")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    Ok(ServerState {
+        url: base.to_string(),
+        config,
+        imposters,
+    })
 }
 
+/// `.json`, `.yaml` and `.yml` configs. A file named on the command line may also be an `.ejs`
+/// template; a directory's `.ejs` files are left out, since they are usually partials included by
+/// a config.
 fn collect_imposter_files(path: &Path) -> Vec<PathBuf> {
+    let is_config = |path: &Path| {
+        path.extension()
+            .is_some_and(|ext| ext == "json" || ext == "yaml" || ext == "yml")
+    };
     let mut files = Vec::new();
 
     if path.is_file() {
-        if path.extension().is_some_and(|ext| ext == "json") {
+        if is_config(path) || path.extension().is_some_and(|ext| ext == "ejs") {
             files.push(path.to_path_buf());
         }
     } else if path.is_dir()
@@ -218,7 +403,7 @@ fn collect_imposter_files(path: &Path) -> Vec<PathBuf> {
     {
         for entry in entries.flatten() {
             let entry_path = entry.path();
-            if entry_path.is_file() && entry_path.extension().is_some_and(|ext| ext == "json") {
+            if entry_path.is_file() && is_config(&entry_path) {
                 files.push(entry_path);
             }
         }
@@ -242,45 +427,217 @@ fn load_imposter_file(path: &Path) -> Result<Value, LoadError> {
     Ok(serde_json::from_str(&content)?)
 }
 
-fn check_port_conflicts(port_map: &HashMap<u16, Vec<PathBuf>>, result: &mut LintResult) {
-    for (port, files) in port_map {
-        if files.len() > 1 {
-            let file_names: Vec<String> = files
-                .iter()
-                .map(|f| {
-                    f.file_name()
-                        .unwrap_or_default()
-                        .to_string_lossy()
-                        .to_string()
-                })
-                .collect();
-
-            result.add_issue(
-                LintIssue::error(
-                    "E002",
-                    format!(
-                        "Port {port} is used by {} files: {}",
-                        files.len(),
-                        file_names.join(", ")
-                    ),
-                    files[0].clone(),
-                )
-                .with_location("port")
-                .with_suggestion(format!(
-                    "Assign unique ports to each imposter. Consider using ports {}+",
-                    port + 1
-                )),
-            );
+/// The version of the `-o json` report's layout. Adding a field keeps it; renaming, removing or
+/// changing the meaning of one bumps it, so a consumer can tell a report it can't read.
+const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// The `-o json` report: the [`LintResult`]'s own fields (`files_checked`, `errors`, `warnings`,
+/// `issues`) plus totals by rule, per-file timings and the settings the run used.
+#[derive(Debug, Serialize)]
+struct JsonReport<'a> {
+    schema_version: u32,
+    tool: Tool,
+    #[serde(flatten)]
+    result: &'a LintResult,
+    infos: usize,
+    /// Issues by rule code.
+    rules: BTreeMap<&'a str, usize>,
+    files: Vec<FileReport<'a>>,
+    config: &'a ReportSettings<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct Tool {
+    name: &'static str,
+    version: &'static str,
+}
+
+/// One linted file in the JSON report.
+#[derive(Debug, Serialize)]
+struct FileReport<'a> {
+    path: &'a Path,
+    duration_ms: f64,
+    errors: usize,
+    warnings: usize,
+}
+
+/// The options a run used, after `.riftlint.toml` and the flags are combined.
+#[derive(Debug, Serialize)]
+struct ReportSettings<'a> {
+    config_file: Option<&'a Path>,
+    strict: bool,
+    errors_only: bool,
+    max_warnings: Option<usize>,
+    baseline: Option<&'a Path>,
+    allowed_proxy_hosts: &'a [String],
+    rules: &'a BTreeMap<String, RuleLevel>,
+}
+
+fn json_report<'a>(result: &'a LintResult, settings: &'a ReportSettings<'a>) -> JsonReport<'a> {
+    let mut rules = BTreeMap::new();
+    let mut by_file: HashMap<&Path, (usize, usize)> = HashMap::new();
+    for issue in &result.issues {
+        *rules.entry(issue.code.as_str()).or_default() += 1;
+        let (errors, warnings) = by_file.entry(issue.file.as_path()).or_default();
+        match issue.severity {
+            Severity::Error => *errors += 1,
+            Severity::Warning => *warnings += 1,
+            Severity::Info => {}
         }
     }
+    let files = result
+        .durations
+        .iter()
+        .map(|(path, duration)| {
+            let (errors, warnings) = by_file.get(path.as_path()).copied().unwrap_or_default();
+            FileReport {
+                path,
+                duration_ms: duration.as_secs_f64() * 1000.0,
+                errors,
+                warnings,
+            }
+        })
+        .collect();
+    JsonReport {
+        schema_version: REPORT_SCHEMA_VERSION,
+        tool: Tool {
+            name: env!("CARGO_PKG_NAME"),
+            version: env!("CARGO_PKG_VERSION"),
+        },
+        result,
+        infos: result
+            .issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Info)
+            .count(),
+        rules,
+        files,
+        config: settings,
+    }
 }
 
-fn print_results_json(result: &LintResult) {
-    let output = serde_json::to_string_pretty(&result).unwrap();
+fn print_results_json(result: &LintResult, settings: &ReportSettings) {
+    let output = serde_json::to_string_pretty(&json_report(result, settings)).unwrap();
     println!("{output}");
 }
 
-fn print_results(result: &LintResult, args: &Args) {
+/// Write a JUnit XML report so CI test reporters (Jenkins, GitLab) show lint results natively.
+/// Every checked file is a test case and every error a `<failure>` in it; warnings become
+/// failures too when they fail the run (`--strict`, or over `--max-warnings`), otherwise they are
+/// listed in the case's `<system-out>`.
+fn print_results_junit(result: &LintResult, files: &[PathBuf], warnings_fail: bool) {
+    print!("{}", junit_report(result, files, warnings_fail));
+}
+
+fn junit_report(result: &LintResult, files: &[PathBuf], warnings_fail: bool) -> String {
+    let is_failure = |issue: &LintIssue| {
+        issue.severity == Severity::Error || (warnings_fail && issue.severity == Severity::Warning)
+    };
+
+    // Files that only show up in issues (none today, but cheap to be safe) still get a case.
+    let mut cases: Vec<&PathBuf> = files.iter().collect();
+    for issue in &result.issues {
+        if !cases.contains(&&issue.file) {
+            cases.push(&issue.file);
+        }
+    }
+    cases.sort();
+
+    let mut failed_cases = 0;
+    let mut body = String::new();
+    for file in &cases {
+        let issues: Vec<&LintIssue> = result.issues.iter().filter(|i| &i.file == *file).collect();
+        let name = xml_escape(&file.display().to_string());
+        if issues.is_empty() {
+            body.push_str(&format!(
+                "    <testcase classname=\"rift-lint\" name=\"{name}\"/>\n"
+            ));
+            continue;
+        }
+        if issues.iter().any(|i| is_failure(i)) {
+            failed_cases += 1;
+        }
+        body.push_str(&format!(
+            "    <testcase classname=\"rift-lint\" name=\"{name}\">\n"
+        ));
+        let mut other = String::new();
+        for issue in issues {
+            let detail = junit_issue_text(issue);
+            if is_failure(issue) {
+                body.push_str(&format!(
+                    "      <failure type=\"{}\" message=\"{}\">{}</failure>\n",
+                    issue.code,
+                    xml_escape(&issue.message),
+                    xml_escape(&detail)
+                ));
+            } else {
+                other.push_str(&detail);
+                other.push('\n');
+            }
+        }
+        if !other.is_empty() {
+            body.push_str(&format!(
+                "      <system-out>{}</system-out>\n",
+                xml_escape(&other)
+            ));
+        }
+        body.push_str("    </testcase>\n");
+    }
+
+    let tests = cases.len();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"rift-lint\" tests=\"{tests}\" failures=\"{failed_cases}\" errors=\"0\">\n"
+    ));
+    xml.push_str(&format!(
+        "  <testsuite name=\"rift-lint\" tests=\"{tests}\" failures=\"{failed_cases}\" errors=\"0\" skipped=\"0\">\n"
+    ));
+    xml.push_str(&body);
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+/// One issue as plain text: `error E006 [stubs[0]]: message`, then the suggestion if any.
+fn junit_issue_text(issue: &LintIssue) -> String {
+    let location = issue
+        .location
+        .as_ref()
+        .map(|l| format!(" [{l}]"))
+        .unwrap_or_default();
+    let position = issue
+        .span
+        .map(|span| format!(" {}:{}", span.line, span.column))
+        .unwrap_or_default();
+    let mut text = format!(
+        "{} {}{position}{location}: {}",
+        issue.severity.label(),
+        issue.code,
+        issue.message
+    );
+    if let Some(suggestion) = &issue.suggestion {
+        text.push_str(&format!("\n  -> {suggestion}"));
+    }
+    text
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // XML 1.0 has no representation for most control characters; drop them.
+            c if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn print_results(result: &LintResult, args: &Args, outcome: Outcome) {
     let Palette {
         green,
         red,
@@ -376,6 +733,12 @@ fn print_results(result: &LintResult, args: &Args) {
                     .map(|l| format!("{dim}[{reset}{cyan}{l}{reset}{dim}]{reset}"))
                     .unwrap_or_default();
 
+                // `line:column`, when the issue was found in JSON text.
+                let position_str = issue
+                    .span
+                    .map(|span| format!("{dim}{}:{}{reset} ", span.line, span.column))
+                    .unwrap_or_default();
+
                 let code_str = format!(
                     "{dim}({}{}{dim}){reset}",
                     severity_color(&issue.severity),
@@ -383,7 +746,7 @@ fn print_results(result: &LintResult, args: &Args) {
                 );
 
                 println!(
-                    "  {severity_marker} {location_str} {severity_str}: {} {code_str}",
+                    "  {severity_marker} {position_str}{location_str} {severity_str}: {} {code_str}",
                     issue.message
                 );
 
@@ -426,12 +789,16 @@ fn print_results(result: &LintResult, args: &Args) {
 
     println!();
 
-    if result.errors == 0 && result.warnings == 0 {
-        println!("{green}{bold}All checks passed!{reset}");
-    } else if result.errors == 0 {
-        println!("{yellow}{bold}Passed with warnings{reset}");
-    } else {
-        println!("{red}{bold}Linting failed with errors{reset}");
+    match outcome {
+        Outcome::Passed if result.warnings == 0 => {
+            println!("{green}{bold}All checks passed!{reset}");
+        }
+        Outcome::Passed => println!("{yellow}{bold}Passed with warnings{reset}"),
+        Outcome::Errors => println!("{red}{bold}Linting failed with errors{reset}"),
+        Outcome::TooManyWarnings => println!(
+            "{red}{bold}Linting failed with more than {} warning(s){reset}",
+            args.max_warnings.unwrap_or_default()
+        ),
     }
 }
 
@@ -477,18 +844,63 @@ fn fix_header_value(value: &mut Value) -> Option<&'static str> {
     }
 }
 
-fn apply_fixes(imposters: &[(PathBuf, Value)], json_mode: bool) {
+/// The imposters in a config file: the file itself, or each one in an `{"imposters": [...]}`
+/// wrapper or a bare array.
+fn config_imposters_mut(config: &mut Value) -> Vec<&mut Value> {
+    if config.get("imposters").is_some_and(Value::is_array) {
+        return config["imposters"]
+            .as_array_mut()
+            .unwrap()
+            .iter_mut()
+            .collect();
+    }
+    match config {
+        Value::Array(imposters) => imposters.iter_mut().collect(),
+        imposter => vec![imposter],
+    }
+}
+
+/// Remove the stubs that are byte for byte the same as an earlier one (W034), keeping the first.
+/// Returns how many were removed.
+fn drop_duplicate_stubs(stubs: &mut Vec<Value>) -> usize {
+    let before = stubs.len();
+    let mut kept: Vec<Value> = Vec::with_capacity(before);
+    for stub in stubs.drain(..) {
+        if !kept.contains(&stub) {
+            kept.push(stub);
+        }
+    }
+    *stubs = kept;
+    before - stubs.len()
+}
+
+fn apply_fixes(imposters: &[(PathBuf, Value)], machine_output: bool) {
     let Palette {
         green, red, reset, ..
     } = palette();
     let mut fixes_applied = 0;
 
-    for (file, imposter) in imposters {
-        let mut modified = imposter.clone();
+    for (file, config) in imposters {
+        let mut modified = config.clone();
         let mut file_fixed = false;
 
-        // Fix header values
-        if let Some(stubs) = modified.get_mut("stubs").and_then(|v| v.as_array_mut()) {
+        for imposter in config_imposters_mut(&mut modified) {
+            let Some(stubs) = imposter.get_mut("stubs").and_then(|v| v.as_array_mut()) else {
+                continue;
+            };
+
+            // Drop exact duplicate stubs (W034)
+            let dropped = drop_duplicate_stubs(stubs);
+            if dropped > 0 {
+                file_fixed = true;
+                fixes_applied += dropped;
+                emit(
+                    machine_output,
+                    &format!("  Removed {dropped} duplicate stub(s)"),
+                );
+            }
+
+            // Fix header values
             for stub in stubs {
                 if let Some(responses) = stub.get_mut("responses").and_then(|v| v.as_array_mut()) {
                     for response in responses {
@@ -501,7 +913,10 @@ fn apply_fixes(imposters: &[(PathBuf, Value)], json_mode: bool) {
                                 if let Some(kind) = fix_header_value(value) {
                                     file_fixed = true;
                                     fixes_applied += 1;
-                                    emit(json_mode, &format!("  Fixed header '{name}' {kind}"));
+                                    emit(
+                                        machine_output,
+                                        &format!("  Fixed header '{name}' {kind}"),
+                                    );
                                 }
                             }
                         }
@@ -516,19 +931,19 @@ fn apply_fixes(imposters: &[(PathBuf, Value)], json_mode: bool) {
                 Ok(content) => {
                     if let Err(e) = std::fs::write(file, content) {
                         emit(
-                            json_mode,
+                            machine_output,
                             &format!("{red}Error writing {}: {e}{reset}", file.display()),
                         );
                     } else {
                         emit(
-                            json_mode,
+                            machine_output,
                             &format!("{green}Fixed: {}{reset}", file.display()),
                         );
                     }
                 }
                 Err(e) => {
                     emit(
-                        json_mode,
+                        machine_output,
                         &format!("{red}Error serializing {}: {e}{reset}", file.display()),
                     );
                 }
@@ -537,15 +952,113 @@ fn apply_fixes(imposters: &[(PathBuf, Value)], json_mode: bool) {
     }
 
     emit(
-        json_mode,
+        machine_output,
         &format!("\n{green}Applied {fixes_applied} fixes{reset}"),
     );
 }
 
 #[cfg(test)]
 mod tests {
-    use super::fix_header_value;
+    use super::{Outcome, ReportSettings, fix_header_value, json_report, junit_report, xml_escape};
+    use rift_lint::{LintIssue, LintResult};
     use serde_json::{Value, json};
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn junit_result() -> (LintResult, Vec<PathBuf>) {
+        let file = PathBuf::from("a.json");
+        let mut result = LintResult::new();
+        result.add_issue(
+            LintIssue::error("E006", "Stub has no responses", file.clone())
+                .with_location("stubs[0]"),
+        );
+        result.add_issue(LintIssue::warning(
+            "W012",
+            "Unknown field 'x'",
+            file.clone(),
+        ));
+        (result, vec![file, PathBuf::from("b.json")])
+    }
+
+    #[test]
+    fn outcome_tells_errors_from_too_many_warnings() {
+        let (with_error, _) = junit_result();
+        assert_eq!(Outcome::of(&with_error, None), Outcome::Errors);
+        assert_eq!(Outcome::of(&with_error, Some(5)).exit_code(), 1);
+
+        let mut warnings = LintResult::new();
+        for code in ["W012", "W013"] {
+            warnings.add_issue(LintIssue::warning(code, "w", PathBuf::from("a.json")));
+        }
+        assert_eq!(Outcome::of(&warnings, None), Outcome::Passed);
+        assert_eq!(Outcome::of(&warnings, Some(2)), Outcome::Passed);
+        assert_eq!(Outcome::of(&warnings, Some(1)), Outcome::TooManyWarnings);
+        assert_eq!(Outcome::of(&warnings, Some(0)).exit_code(), 3);
+        assert_eq!(Outcome::of(&LintResult::new(), Some(0)).exit_code(), 0);
+    }
+
+    #[test]
+    fn json_report_counts_rules_and_files() {
+        let (mut result, files) = junit_result();
+        result.add_issue(LintIssue::warning(
+            "W012",
+            "Unknown field 'y'",
+            files[1].clone(),
+        ));
+        result.durations = vec![
+            (files[0].clone(), Duration::from_millis(3)),
+            (files[1].clone(), Duration::from_micros(500)),
+        ];
+        let rules = BTreeMap::new();
+        let settings = ReportSettings {
+            config_file: None,
+            strict: false,
+            errors_only: false,
+            max_warnings: Some(10),
+            baseline: None,
+            allowed_proxy_hosts: &[],
+            rules: &rules,
+        };
+        let report = serde_json::to_value(json_report(&result, &settings)).unwrap();
+
+        assert_eq!(report["schema_version"], 1);
+        assert_eq!(report["tool"]["name"], "rift-lint");
+        assert_eq!(report["errors"], 1);
+        assert_eq!(report["warnings"], 2);
+        assert_eq!(report["rules"], json!({"E006": 1, "W012": 2}));
+        assert_eq!(
+            report["files"],
+            json!([
+                {"path": "a.json", "duration_ms": 3.0, "errors": 1, "warnings": 1},
+                {"path": "b.json", "duration_ms": 0.5, "errors": 0, "warnings": 1},
+            ])
+        );
+        assert_eq!(report["config"]["max_warnings"], 10);
+        assert!(report.get("durations").is_none());
+    }
+
+    #[test]
+    fn junit_report_lists_warnings_as_output_unless_strict() {
+        let (result, files) = junit_result();
+        let xml = junit_report(&result, &files, false);
+        assert!(xml.contains(r#"tests="2" failures="1""#));
+        assert_eq!(xml.matches("<failure ").count(), 1);
+        assert!(xml.contains("<system-out>warning W012: Unknown field &apos;x&apos;"));
+        assert!(xml.contains(r#"<testcase classname="rift-lint" name="b.json"/>"#));
+
+        let strict = junit_report(&result, &files, true);
+        assert_eq!(strict.matches("<failure ").count(), 2);
+        assert!(!strict.contains("<system-out>"));
+    }
+
+    #[test]
+    fn xml_escape_covers_markup_and_control_characters() {
+        assert_eq!(
+            xml_escape("<a href=\"x\">&'\u{1}</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&apos;&lt;/a&gt;"
+        );
+    }
 
     #[test]
     fn fix_header_value_leaves_valid_string_array() {
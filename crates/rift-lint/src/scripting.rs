@@ -0,0 +1,137 @@
+//! Script checks backed by the server's own validators (the `scripting` feature).
+//!
+//! ```no_run
+//! use rift_lint::{LintOptions, lint_file, scripting::server_script_check};
+//! use std::path::Path;
+//!
+//! let options = LintOptions::default().with_script_check(server_script_check);
+//! let result = lint_file(Path::new("imposter.json"), &options);
+//! ```
+
+use crate::types::{EmbeddedScript, ScriptKind};
+use rift_mock_core::scripting::{JsValidator, RhaiValidator, ScriptValidator};
+
+/// Parse `script` the way the server will run it. Parse only: nothing is executed, so a script
+/// that loops forever can't hang the linter.
+///
+/// - `inject` and function `wait`s are JavaScript function expressions.
+/// - `decorate` runs as JavaScript (a function, or a Mountebank `config` body) or, failing that,
+///   as Rhai, so it is an error only when it parses as neither.
+/// - `_rift.script` is parsed by its engine's validator. Unknown engines are left to E052.
+pub fn server_script_check(script: &EmbeddedScript<'_>) -> Result<(), String> {
+    let code = script.code;
+    match script.kind {
+        ScriptKind::Inject => parse_js_function(code),
+        ScriptKind::Decorate => {
+            let js = parse_js_function(code).or_else(|_| parse_js_config_body(code));
+            match (js, RhaiValidator::new().validate(code)) {
+                (Ok(()), _) | (_, Ok(())) => Ok(()),
+                (Err(js), Err(_)) if looks_like_js(code) => Err(js),
+                (Err(_), Err(rhai)) => Err(rhai.to_string()),
+            }
+        }
+        ScriptKind::Rift => match script.engine {
+            "rhai" => RhaiValidator::new()
+                .validate(code)
+                .map_err(|e| e.to_string()),
+            "javascript" | "js" => JsValidator::new().validate(code).map_err(|e| e.to_string()),
+            _ => Ok(()),
+        },
+    }
+}
+
+/// An anonymous `function (…) {…}` only parses as an expression, as the server binds it.
+fn parse_js_function(code: &str) -> Result<(), String> {
+    JsValidator::new()
+        .validate(&format!("var __fn = {};", code.trim()))
+        .map_err(|e| e.to_string())
+}
+
+/// A bare `config.response.body = …;` decorate body, which the server wraps in a function.
+fn parse_js_config_body(code: &str) -> Result<(), String> {
+    JsValidator::new()
+        .validate(&format!("var __fn = function (config) {{\n{code}\n}};"))
+        .map_err(|e| e.to_string())
+}
+
+fn looks_like_js(code: &str) -> bool {
+    let code = code.trim_start();
+    code.starts_with("function")
+        || code.starts_with("async")
+        || code.starts_with("config")
+        || code.starts_with('(')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(kind: ScriptKind, engine: &str, code: &str) -> Result<(), String> {
+        server_script_check(&EmbeddedScript { kind, engine, code })
+    }
+
+    #[test]
+    fn inject_parses_as_a_function_expression() {
+        assert!(
+            check(
+                ScriptKind::Inject,
+                "javascript",
+                "function (config) { return {}; }"
+            )
+            .is_ok()
+        );
+        assert!(
+            check(
+                ScriptKind::Inject,
+                "javascript",
+                "function (config) { return {; }"
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn decorate_accepts_js_functions_config_bodies_and_rhai() {
+        for code in [
+            "function (request, response) { response.body = 'x'; }",
+            "config => { config.response.body = JSON.stringify({ a: 1 }); }",
+            "config.response.body = 'x';",
+            "let n = 1; response.body = `${n}`;",
+        ] {
+            assert!(
+                check(ScriptKind::Decorate, "javascript/rhai", code).is_ok(),
+                "{code}"
+            );
+        }
+        assert!(
+            check(
+                ScriptKind::Decorate,
+                "javascript/rhai",
+                "function (r) { r.body = ; }"
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn rift_scripts_use_their_engine() {
+        assert!(check(ScriptKind::Rift, "rhai", "fn respond(ctx) { http(200) }").is_ok());
+        assert!(check(ScriptKind::Rift, "rhai", "fn respond(ctx) { http(200 }").is_err());
+        assert!(
+            check(
+                ScriptKind::Rift,
+                "javascript",
+                "function respond(ctx) { return 1; }"
+            )
+            .is_ok()
+        );
+        assert!(
+            check(
+                ScriptKind::Rift,
+                "js",
+                "function respond(ctx) { return 1 }}"
+            )
+            .is_err()
+        );
+    }
+}
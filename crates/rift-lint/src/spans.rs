@@ -0,0 +1,157 @@
+//! Where each value of a JSON document is in its text, so an issue's location can be given as a
+//! line and column as well as a path.
+
+use crate::types::Span;
+use std::collections::HashMap;
+
+/// The byte ranges of a JSON document's values, by the location paths issues use
+/// (`stubs[0].responses[0].is`).
+pub(crate) struct SourceMap<'a> {
+    text: &'a str,
+    ranges: HashMap<String, (usize, usize)>,
+}
+
+impl<'a> SourceMap<'a> {
+    /// Map `text`; `None` when it isn't JSON.
+    pub(crate) fn new(text: &'a str) -> Option<Self> {
+        let mut scanner = Scanner {
+            bytes: text.as_bytes(),
+            pos: 0,
+            ranges: HashMap::new(),
+        };
+        scanner.value(String::new())?;
+        Some(Self {
+            text,
+            ranges: scanner.ranges,
+        })
+    }
+
+    /// The span of the value at `path`, or of the nearest value containing it when `path` names
+    /// something that isn't in the document (a field that is missing, or a description).
+    pub(crate) fn span(&self, path: &str) -> Option<Span> {
+        let mut path = path;
+        loop {
+            if let Some(&(start, end)) = self.ranges.get(path) {
+                return Some(self.span_at(start, end));
+            }
+            if path.is_empty() {
+                return None;
+            }
+            path = &path[..path.rfind(['.', '[']).unwrap_or(0)];
+        }
+    }
+
+    fn span_at(&self, start: usize, end: usize) -> Span {
+        let before = &self.text[..start];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Span {
+            line: before.matches('\n').count() + 1,
+            column: self.text[line_start..start].chars().count() + 1,
+            start,
+            end,
+        }
+    }
+}
+
+/// A scan over JSON text recording where each value starts and ends. It trusts the text to be
+/// well-formed (it has already been parsed) and only gives up on truncated input.
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    ranges: HashMap<String, (usize, usize)>,
+}
+
+impl Scanner<'_> {
+    fn value(&mut self, path: String) -> Option<()> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match *self.bytes.get(self.pos)? {
+            b'{' => {
+                self.pos += 1;
+                self.skip_whitespace();
+                if !self.eat(b'}') {
+                    loop {
+                        self.skip_whitespace();
+                        let key_start = self.pos;
+                        self.string()?;
+                        let key: String =
+                            serde_json::from_slice(&self.bytes[key_start..self.pos]).ok()?;
+                        self.skip_whitespace();
+                        self.expect(b':')?;
+                        let child = if path.is_empty() {
+                            key
+                        } else {
+                            format!("{path}.{key}")
+                        };
+                        self.value(child)?;
+                        self.skip_whitespace();
+                        if !self.eat(b',') {
+                            self.expect(b'}')?;
+                            break;
+                        }
+                    }
+                }
+            }
+            b'[' => {
+                self.pos += 1;
+                self.skip_whitespace();
+                if !self.eat(b']') {
+                    for index in 0.. {
+                        self.value(format!("{path}[{index}]"))?;
+                        self.skip_whitespace();
+                        if !self.eat(b',') {
+                            self.expect(b']')?;
+                            break;
+                        }
+                    }
+                }
+            }
+            b'"' => self.string()?,
+            _ => {
+                while self.bytes.get(self.pos).is_some_and(|b| {
+                    !matches!(b, b',' | b'}' | b']' | b' ' | b'\t' | b'\n' | b'\r')
+                }) {
+                    self.pos += 1;
+                }
+            }
+        }
+        self.ranges.insert(path, (start, self.pos));
+        Some(())
+    }
+
+    fn string(&mut self) -> Option<()> {
+        self.expect(b'"')?;
+        loop {
+            match *self.bytes.get(self.pos)? {
+                b'\\' => self.pos += 2,
+                b'"' => {
+                    self.pos += 1;
+                    return Some(());
+                }
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(|b| matches!(b, b' ' | b'\t' | b'\n' | b'\r'))
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        let found = self.bytes.get(self.pos) == Some(&byte);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        self.eat(byte).then_some(())
+    }
+}
@@ -1,7 +1,10 @@
 //! Core types for the linting library.
 
+use crate::live::ServerState;
 use serde::Serialize;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Severity level of a lint issue.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
@@ -42,6 +45,21 @@ pub struct LintIssue {
     pub location: Option<String>,
     /// Suggested fix for the issue.
     pub suggestion: Option<String>,
+    /// Where `location` is in the file's text, when the linter read it from JSON text.
+    pub span: Option<Span>,
+}
+
+/// A value's place in a JSON file's text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Span {
+    /// 1-based line.
+    pub line: usize,
+    /// 1-based column, in characters.
+    pub column: usize,
+    /// Byte offset of the value's first byte.
+    pub start: usize,
+    /// Byte offset just past the value.
+    pub end: usize,
 }
 
 fn serialize_path<S>(path: &Path, serializer: S) -> Result<S::Ok, S::Error>
@@ -61,6 +79,7 @@ impl LintIssue {
             file,
             location: None,
             suggestion: None,
+            span: None,
         }
     }
 
@@ -73,6 +92,7 @@ impl LintIssue {
             file,
             location: None,
             suggestion: None,
+            span: None,
         }
     }
 
@@ -85,6 +105,7 @@ impl LintIssue {
             file,
             location: None,
             suggestion: None,
+            span: None,
         }
     }
 
@@ -112,6 +133,10 @@ pub struct LintResult {
     pub errors: usize,
     /// Number of warnings found.
     pub warnings: usize,
+    /// How long each file took to lint, in the order they were linted. Filled in by
+    /// [`lint_file`](crate::lint_file) and [`lint_files`](crate::lint_files).
+    #[serde(skip)]
+    pub durations: Vec<(PathBuf, Duration)>,
 }
 
 impl LintResult {
@@ -151,9 +176,104 @@ impl LintResult {
         self.files_checked += other.files_checked;
         self.errors += other.errors;
         self.warnings += other.warnings;
+        self.durations.extend(other.durations);
+    }
+}
+
+/// What an embedded script is, which decides how it has to parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptKind {
+    /// A Mountebank JavaScript function expression: a response or predicate `inject`, or a
+    /// function `wait`.
+    Inject,
+    /// A `decorate` behavior: a JavaScript function or `config` body, or a Rhai script.
+    Decorate,
+    /// A `_rift.script` program for its `engine`.
+    Rift,
+}
+
+/// A script found in a config, handed to a [`ScriptCheck`].
+#[derive(Debug, Clone, Copy)]
+pub struct EmbeddedScript<'a> {
+    pub kind: ScriptKind,
+    /// `"javascript"` for `inject` and function `wait`s, `"javascript/rhai"` for `decorate`, the
+    /// resolved engine for `_rift.script`.
+    pub engine: &'a str,
+    pub code: &'a str,
+}
+
+/// Checks an embedded script, returning the syntax error to report (E057).
+pub type ScriptCheck = Arc<dyn Fn(&EmbeddedScript<'_>) -> Result<(), String> + Send + Sync>;
+
+/// A rule of a project's own, run on every imposter after the built-in rules, e.g. a naming
+/// convention. Its issues are covered by `x-rift-lint-ignore` and `.riftlint.toml` like the
+/// built-in ones; give them codes with a prefix of their own, such as `ORG001`, so they don't
+/// clash with the linter's `E`, `W` and `I` codes.
+///
+/// Any `Fn(&Path, &Value, &mut LintResult)` is a rule.
+pub trait LintRule: Send + Sync {
+    /// Report `imposter`'s issues, read from `file`. Locations are relative to the imposter, as
+    /// the built-in rules' are (`stubs[0].responses[0]`).
+    fn check_imposter(&self, file: &Path, imposter: &serde_json::Value, result: &mut LintResult);
+}
+
+impl<F> LintRule for F
+where
+    F: Fn(&Path, &serde_json::Value, &mut LintResult) + Send + Sync,
+{
+    fn check_imposter(&self, file: &Path, imposter: &serde_json::Value, result: &mut LintResult) {
+        self(file, imposter, result)
     }
 }
 
 /// Options for validation.
-#[derive(Debug, Clone, Default)]
-pub struct LintOptions {}
+#[derive(Clone, Default)]
+pub struct LintOptions {
+    /// Run every embedded script through this check instead of the built-in JavaScript parse.
+    /// The `scripting` feature provides one backed by the server's own validators.
+    pub script_check: Option<ScriptCheck>,
+    /// Lint files as written instead of rendering their EJS first, as `rift --no-parse` loads them.
+    pub no_parse: bool,
+    /// Check [`lint_files`](crate::lint_files) against this running server as well.
+    pub server: Option<Arc<ServerState>>,
+    /// External hosts proxies may forward to without a warning (W033): exact names, or
+    /// `*.example.com` for its subdomains.
+    pub allowed_proxy_hosts: Vec<String>,
+    /// Rules of the project's own, in the order they were added with [`Self::with_rule`].
+    pub rules: Vec<Arc<dyn LintRule>>,
+}
+
+impl LintOptions {
+    /// Check embedded scripts with `check`.
+    pub fn with_script_check(
+        mut self,
+        check: impl Fn(&EmbeddedScript<'_>) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.script_check = Some(Arc::new(check));
+        self
+    }
+
+    /// Check files against what `server` is running.
+    pub fn with_server(mut self, server: ServerState) -> Self {
+        self.server = Some(Arc::new(server));
+        self
+    }
+
+    /// Run `rule` on every imposter as well.
+    pub fn with_rule(mut self, rule: impl LintRule + 'static) -> Self {
+        self.rules.push(Arc::new(rule));
+        self
+    }
+}
+
+impl std::fmt::Debug for LintOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LintOptions")
+            .field("script_check", &self.script_check.is_some())
+            .field("no_parse", &self.no_parse)
+            .field("server", &self.server.as_ref().map(|s| &s.url))
+            .field("allowed_proxy_hosts", &self.allowed_proxy_hosts)
+            .field("rules", &self.rules.len())
+            .finish()
+    }
+}
@@ -1,9 +1,9 @@
 //! Core validation logic for imposter configurations.
 
-use crate::types::{LintIssue, LintOptions, LintResult};
+use crate::types::{EmbeddedScript, LintIssue, LintOptions, LintResult, ScriptKind, Severity};
 use regex::Regex;
 use serde_json::Value;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::LazyLock;
 
@@ -95,9 +95,13 @@ pub fn validate_imposter(
     result: &mut LintResult,
     options: &LintOptions,
 ) {
+    let first_issue = result.issues.len();
     check_required_fields(file, imposter, result);
     check_protocol(file, imposter, result);
     check_port_range(file, imposter, result);
+    check_unknown_fields(file, imposter, IMPOSTER_FIELDS, "imposter", "", result);
+    check_field_types(file, imposter, IMPOSTER_FIELD_TYPES, "E050", "", result);
+    check_default_response(file, imposter, result);
     check_state_without_flow_state(file, imposter, result);
 
     // Named script registry (`_rift.scripts`, issue #356): validated once up front (each entry
@@ -108,147 +112,1479 @@ pub fn validate_imposter(
         .and_then(|r| r.get("scripts"))
         .cloned()
         .unwrap_or(Value::Null);
-    validate_script_registry(file, &registry, result);
+    validate_script_registry(file, &registry, result, options);
+    if let Some(rift) = imposter.get("_rift") {
+        validate_rift_config(file, rift, result);
+    }
+
+    if let Some(defaults) = imposter.get("defaultBehaviors") {
+        validate_behaviors_block(file, defaults, "defaultBehaviors", result, options);
+        check_default_behaviors_repeat(file, defaults, result);
+    }
 
     if let Some(stubs) = imposter.get("stubs").and_then(|v| v.as_array()) {
         for (idx, stub) in stubs.iter().enumerate() {
             validate_stub(file, stub, idx, result, options, &registry);
         }
+        let duplicates = check_duplicate_stubs(file, stubs, result);
+        check_shadowed_stubs(file, stubs, &duplicates, result);
+        for (idx, stub) in stubs.iter().enumerate() {
+            check_contradictory_predicates(file, stub, &format!("stubs[{idx}]"), result);
+        }
+        check_stub_count(file, stubs, result);
+    }
+    check_wildcard_cors(file, imposter, result);
+
+    for rule in &options.rules {
+        rule.check_imposter(file, imposter, result);
+    }
+
+    apply_ignore_annotations(file, imposter, first_issue, result);
+}
+
+/// Warn (W032) about CORS that lets any origin call the imposter: `allowCORS` on its own, or a
+/// `cors` block without `allowOrigins` (or with `*`), which with `allowCredentials` echoes every
+/// origin back as allowed to send cookies.
+fn check_wildcard_cors(file: &Path, imposter: &Value, result: &mut LintResult) {
+    let (message, location) = match imposter.get("cors").filter(|cors| cors.is_object()) {
+        Some(cors) => {
+            let any_origin = match cors.get("allowOrigins").and_then(Value::as_array) {
+                Some(origins) => origins.is_empty() || origins.iter().any(|o| o == "*"),
+                None => true,
+            };
+            if !any_origin {
+                return;
+            }
+            if cors.get("allowCredentials") == Some(&Value::Bool(true)) {
+                (
+                    "CORS allows credentialed requests from every origin",
+                    "cors.allowOrigins",
+                )
+            } else {
+                ("CORS allows every origin", "cors.allowOrigins")
+            }
+        }
+        None if imposter.get("allowCORS") == Some(&Value::Bool(true)) => {
+            ("allowCORS allows every origin", "allowCORS")
+        }
+        None => return,
+    };
+    result.add_issue(
+        LintIssue::warning("W032", message, file.to_path_buf())
+            .with_location(location)
+            .with_suggestion(
+                "List the origins that may call it in \"cors\": {\"allowOrigins\": [...]}",
+            ),
+    );
+}
+
+/// More stubs than this in one imposter is a warning (W027).
+const MAX_STUBS: usize = 1000;
+
+/// Warn (W027) about an imposter with thousands of stubs: the server rebuilds its match index on
+/// every stub change, and every request is checked against each stub the index can't rule out by
+/// method or path.
+fn check_stub_count(file: &Path, stubs: &[Value], result: &mut LintResult) {
+    if stubs.len() > MAX_STUBS {
+        result.add_issue(
+            LintIssue::warning(
+                "W027",
+                format!(
+                    "Imposter has {} stubs; above {MAX_STUBS}, matching and stub changes slow down",
+                    stubs.len()
+                ),
+                file.to_path_buf(),
+            )
+            .with_location("stubs")
+            .with_suggestion(
+                "Split it into imposters per service or path, or fold similar stubs into one with \
+                 broader predicates and a templated response",
+            ),
+        );
+    }
+}
+
+/// Above this many stubs the pairwise shadowing check (W018) is skipped, as rift-mock-core's
+/// `stub_analysis` skips its own: it is advisory, and quadratic on large imposters.
+const SHADOW_CHECK_MAX_STUBS: usize = 200;
+
+/// Warn (W018) about each stub an earlier stub shadows: first match wins, so a stub is
+/// unreachable when an earlier one matches every request it does. The check is conservative and
+/// only reports what it can prove from the predicates. `duplicates`, already reported as W034,
+/// are skipped.
+fn check_shadowed_stubs(
+    file: &Path,
+    stubs: &[Value],
+    duplicates: &HashSet<usize>,
+    result: &mut LintResult,
+) {
+    if stubs.len() > SHADOW_CHECK_MAX_STUBS {
+        return;
+    }
+    let predicates: Vec<Option<Vec<&Value>>> = stubs.iter().map(stub_predicates).collect();
+    for (later, later_predicates) in predicates.iter().enumerate() {
+        let Some(later_predicates) = later_predicates else {
+            continue;
+        };
+        if duplicates.contains(&later) {
+            continue;
+        }
+        let shadowing = (0..later).find(|&earlier| {
+            // A stub waiting on a scenario state or a flow's space only matches some of the
+            // time, so later stubs still get the requests it skips.
+            let gated = ["requiredScenarioState", "space"]
+                .iter()
+                .any(|field| stubs[earlier].get(field).is_some_and(|v| !v.is_null()));
+            !gated
+                && predicates[earlier]
+                    .as_ref()
+                    .is_some_and(|earlier_predicates| {
+                        earlier_predicates
+                            .iter()
+                            .all(|predicate| predicate_implied(predicate, later_predicates))
+                    })
+        });
+        let Some(earlier) = shadowing else {
+            continue;
+        };
+        let message = if predicates[earlier].as_ref().is_some_and(Vec::is_empty) {
+            format!(
+                "Stub is unreachable: stub {earlier} before it has no predicates and matches every request"
+            )
+        } else {
+            format!(
+                "Stub is unreachable: stub {earlier} before it matches every request it matches"
+            )
+        };
+        result.add_issue(
+            LintIssue::warning("W018", message, file.to_path_buf())
+                .with_location(format!("stubs[{later}]"))
+                .with_suggestion(format!(
+                    "Move it before stub {earlier}, or make stub {earlier}'s predicates more specific"
+                )),
+        );
+    }
+}
+
+/// Warn (W034) about each stub that repeats an earlier one, as repeated proxy recordings leave
+/// behind: byte for byte, or apart from what doesn't change how it behaves (see
+/// [`stub_fingerprint`]). The earlier stub takes all its requests. Returns the duplicates' indexes.
+fn check_duplicate_stubs(file: &Path, stubs: &[Value], result: &mut LintResult) -> HashSet<usize> {
+    let mut first_with: HashMap<String, usize> = HashMap::new();
+    let mut duplicates = HashSet::new();
+    for (idx, stub) in stubs.iter().enumerate() {
+        let earlier = *first_with.entry(stub_fingerprint(stub)).or_insert(idx);
+        if earlier == idx {
+            continue;
+        }
+        duplicates.insert(idx);
+        let issue = if stubs[earlier] == *stub {
+            LintIssue::warning(
+                "W034",
+                format!("Stub is a duplicate of stub {earlier}"),
+                file.to_path_buf(),
+            )
+            .with_suggestion(format!(
+                "Remove it: stub {earlier} answers all its requests (rift-lint --fix drops exact duplicates)"
+            ))
+        } else {
+            LintIssue::warning(
+                "W034",
+                format!(
+                    "Stub is the same as stub {earlier} apart from its _links or the order of its fields or predicates"
+                ),
+                file.to_path_buf(),
+            )
+            .with_suggestion(format!("Remove it: stub {earlier} answers all its requests"))
+        };
+        result.add_issue(issue.with_location(format!("stubs[{idx}]")));
+    }
+    duplicates
+}
+
+/// Stub fields Mountebank adds when it saves imposters (`mb save`, `GET /imposters`), which differ
+/// between otherwise identical stubs.
+const STUB_METADATA_FIELDS: &[&str] = &["_links"];
+
+/// A stub's content as a string that is the same for two stubs that behave the same: object keys
+/// sorted, predicates (which must all match) sorted, and [`STUB_METADATA_FIELDS`] left out.
+pub(crate) fn stub_fingerprint(stub: &Value) -> String {
+    let Some(fields) = stub.as_object() else {
+        return canonical_json(stub);
+    };
+    let mut fields: Vec<_> = fields
+        .iter()
+        .filter(|(name, _)| !STUB_METADATA_FIELDS.contains(&name.as_str()))
+        .collect();
+    fields.sort_by_key(|(name, _)| *name);
+    let fields: Vec<String> = fields
+        .into_iter()
+        .map(|(name, value)| {
+            let value = match (name.as_str(), value) {
+                ("predicates", Value::Array(predicates)) => {
+                    let mut predicates: Vec<String> =
+                        predicates.iter().map(canonical_json).collect();
+                    predicates.sort();
+                    format!("[{}]", predicates.join(","))
+                }
+                _ => canonical_json(value),
+            };
+            format!("{}:{value}", Value::from(name.as_str()))
+        })
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+/// `value` as compact JSON with its object keys sorted, so equal values give equal strings.
+fn canonical_json(value: &Value) -> String {
+    match value {
+        Value::Object(fields) => {
+            let mut fields: Vec<_> = fields.iter().collect();
+            fields.sort_by_key(|(name, _)| *name);
+            let fields: Vec<String> = fields
+                .into_iter()
+                .map(|(name, value)| {
+                    format!("{}:{}", Value::from(name.as_str()), canonical_json(value))
+                })
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        scalar => scalar.to_string(),
+    }
+}
+
+/// Request fields that hold a single value, so two different expectations on one can't both hold.
+/// Query parameters and headers can repeat, and match when any of their values does.
+const SINGLE_VALUED_FIELDS: &[&str] = &["method", "path"];
+
+/// Warn (W019) when a stub's predicates contradict each other, so it can never match: two
+/// different expectations on `method` or `path`, or a predicate next to its own `not`.
+fn check_contradictory_predicates(
+    file: &Path,
+    stub: &Value,
+    location: &str,
+    result: &mut LintResult,
+) {
+    let Some(predicates) = stub_predicates(stub) else {
+        return;
+    };
+    for (idx, first) in predicates.iter().enumerate() {
+        for second in &predicates[idx + 1..] {
+            let Some(reason) = contradiction(first, second) else {
+                continue;
+            };
+            result.add_issue(
+                LintIssue::warning(
+                    "W019",
+                    format!("Stub can never match: {reason}"),
+                    file.to_path_buf(),
+                )
+                .with_location(location)
+                .with_suggestion(
+                    "Predicates must all match; use 'or' for alternatives, or separate stubs",
+                ),
+            );
+            return;
+        }
+    }
+}
+
+/// Why two predicates can't both match a request, if they can't.
+fn contradiction(first: &Value, second: &Value) -> Option<String> {
+    let negates = |p: &Value, q: &Value| {
+        p.as_object()
+            .filter(|p| p.len() == 1)
+            .and_then(|p| p.get("not"))
+            == Some(q)
+    };
+    if negates(first, second) || negates(second, first) {
+        return Some("a predicate is combined with its own 'not'".to_string());
+    }
+    let (first, second) = (first.as_object()?, second.as_object()?);
+    let parameters = predicate_parameters(first);
+    // Selectors and `except` change what is compared, so only plain field matches are checked.
+    if parameters != predicate_parameters(second)
+        || parameters
+            .keys()
+            .any(|key| !matches!(key.as_str(), "caseSensitive" | "keyCaseSensitive"))
+    {
+        return None;
+    }
+    let case_sensitive = parameters.get("caseSensitive") == Some(&Value::Bool(true));
+    let (first_operator, first_atoms) = field_atoms(first)?;
+    let (second_operator, second_atoms) = field_atoms(second)?;
+    for (field, first_value) in &first_atoms {
+        if !SINGLE_VALUED_FIELDS.contains(&field.as_str()) {
+            continue;
+        }
+        for (other_field, second_value) in &second_atoms {
+            if other_field != field {
+                continue;
+            }
+            let (Some(a), Some(b)) = (first_value.as_str(), second_value.as_str()) else {
+                continue;
+            };
+            let (a, b) = if case_sensitive {
+                (a.to_string(), b.to_string())
+            } else {
+                (a.to_lowercase(), b.to_lowercase())
+            };
+            let conflicts = |op_a: &str, a: &str, op_b: &str, b: &str| match (op_a, op_b) {
+                ("equals", "equals") => a != b,
+                ("equals", "startsWith") => !a.starts_with(b),
+                ("equals", "endsWith") => !a.ends_with(b),
+                ("equals", "contains") => !a.contains(b),
+                ("startsWith", "startsWith") => !a.starts_with(b) && !b.starts_with(a),
+                ("endsWith", "endsWith") => !a.ends_with(b) && !b.ends_with(a),
+                _ => false,
+            };
+            if conflicts(first_operator, &a, second_operator, &b)
+                || conflicts(second_operator, &b, first_operator, &a)
+            {
+                return Some(format!(
+                    "'{field}' can't both {} {first_value} and {} {second_value}",
+                    operator_phrase(first_operator),
+                    operator_phrase(second_operator)
+                ));
+            }
+        }
+    }
+    None
+}
+
+/// `equals` as it reads in a sentence: "equal", "start with", ...
+fn operator_phrase(operator: &str) -> &str {
+    match operator {
+        "equals" => "equal",
+        "startsWith" => "start with",
+        "endsWith" => "end with",
+        "contains" => "contain",
+        other => other,
+    }
+}
+
+/// A stub's predicates as the server reads them (`rules` when `predicates` is empty), with
+/// top-level `and`s spread out, or `None` when they aren't an array.
+fn stub_predicates(stub: &Value) -> Option<Vec<&Value>> {
+    let stub = stub.as_object()?;
+    let list = match stub.get("predicates") {
+        Some(Value::Array(predicates)) if !predicates.is_empty() => predicates,
+        Some(Value::Array(_)) | None => match stub.get("rules") {
+            Some(Value::Array(rules)) => rules,
+            Some(_) => return None,
+            None => return Some(Vec::new()),
+        },
+        Some(_) => return None,
+    };
+    fn spread<'a>(predicate: &'a Value, out: &mut Vec<&'a Value>) {
+        match predicate
+            .as_object()
+            .and_then(|p| p.get("and").filter(|_| p.len() == 1))
+        {
+            Some(Value::Array(children)) => children.iter().for_each(|c| spread(c, out)),
+            _ => out.push(predicate),
+        }
+    }
+    let mut predicates = Vec::new();
+    list.iter().for_each(|p| spread(p, &mut predicates));
+    Some(predicates)
+}
+
+/// Whether every request matching all of `later` also matches `earlier`.
+fn predicate_implied(earlier: &Value, later: &[&Value]) -> bool {
+    if later.contains(&earlier) {
+        return true;
+    }
+    let Some(obj) = earlier.as_object() else {
+        return false;
+    };
+    if obj.len() == 1 {
+        match obj.get("or") {
+            Some(Value::Array(branches)) => {
+                return branches.iter().any(|b| predicate_implied(b, later));
+            }
+            Some(_) => return false,
+            None => {}
+        }
+        if let Some(Value::Array(children)) = obj.get("and") {
+            return children.iter().all(|c| predicate_implied(c, later));
+        }
+    }
+    let Some((operator, atoms)) = field_atoms(obj) else {
+        return false;
+    };
+    let parameters = predicate_parameters(obj);
+    // `except` rewrites the value before comparing, so only identical expectations are safe.
+    let loose = !parameters.contains_key("except");
+    let case_sensitive = parameters.get("caseSensitive") == Some(&Value::Bool(true));
+    atoms.iter().all(|(path, expected)| {
+        later.iter().filter_map(|l| l.as_object()).any(|l| {
+            predicate_parameters(l) == parameters
+                && field_atoms(l).is_some_and(|(later_operator, later_atoms)| {
+                    later_atoms.iter().any(|(later_path, actual)| {
+                        later_path == path
+                            && ((later_operator == operator && actual == expected)
+                                || (loose
+                                    && string_implied(
+                                        later_operator,
+                                        actual,
+                                        operator,
+                                        expected,
+                                        case_sensitive,
+                                    )))
+                    })
+                })
+        })
+    })
+}
+
+/// A predicate's one field operator and the `(field path, expected value)` pairs it checks, with
+/// nested objects spread into dotted paths (except under `deepEquals`, which compares them whole).
+fn field_atoms(
+    predicate: &serde_json::Map<String, Value>,
+) -> Option<(&str, Vec<(String, &Value)>)> {
+    let mut operators = predicate
+        .iter()
+        .filter(|(key, _)| FIELD_OPERATORS.contains(&key.as_str()));
+    let (operator, fields) = operators.next()?;
+    if operators.next().is_some() {
+        return None;
+    }
+    fn spread<'a>(path: String, value: &'a Value, whole: bool, out: &mut Vec<(String, &'a Value)>) {
+        match value.as_object() {
+            Some(obj) if !whole && !obj.is_empty() => {
+                for (key, nested) in obj {
+                    spread(format!("{path}.{key}"), nested, false, out);
+                }
+            }
+            _ => out.push((path, value)),
+        }
+    }
+    let mut atoms = Vec::new();
+    for (field, value) in fields.as_object()? {
+        spread(field.clone(), value, operator == "deepEquals", &mut atoms);
+    }
+    Some((operator, atoms))
+}
+
+/// Everything on a predicate besides its operators: `caseSensitive`, `except`, selectors.
+fn predicate_parameters(
+    predicate: &serde_json::Map<String, Value>,
+) -> serde_json::Map<String, Value> {
+    predicate
+        .iter()
+        .filter(|(key, _)| !FIELD_OPERATORS.contains(&key.as_str()) && *key != IGNORE_ANNOTATION)
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+/// Whether a field matching `later_operator` `actual` always matches `operator` `expected`, for
+/// string values: e.g. `equals "/api/users"` always matches `startsWith "/api"`.
+fn string_implied(
+    later_operator: &str,
+    actual: &Value,
+    operator: &str,
+    expected: &Value,
+    case_sensitive: bool,
+) -> bool {
+    let (Some(actual), Some(expected)) = (actual.as_str(), expected.as_str()) else {
+        return false;
+    };
+    let (actual, expected) = if case_sensitive {
+        (actual.to_string(), expected.to_string())
+    } else {
+        (actual.to_lowercase(), expected.to_lowercase())
+    };
+    match (operator, later_operator) {
+        ("equals", "equals") => actual == expected,
+        ("startsWith", "equals" | "startsWith") => actual.starts_with(&expected),
+        ("endsWith", "equals" | "endsWith") => actual.ends_with(&expected),
+        ("contains", "equals" | "startsWith" | "endsWith" | "contains") => {
+            actual.contains(&expected)
+        }
+        _ => false,
+    }
+}
+
+/// Field naming rule codes not to report for the object it's on and everything inside it.
+pub(crate) const IGNORE_ANNOTATION: &str = "x-rift-lint-ignore";
+
+/// Drop the issues from `first_issue` on that an `x-rift-lint-ignore` annotation covers, and
+/// report malformed annotations (W017).
+fn apply_ignore_annotations(
+    file: &Path,
+    imposter: &Value,
+    first_issue: usize,
+    result: &mut LintResult,
+) {
+    let mut ignores = Vec::new();
+    collect_ignore_annotations(file, imposter, "", &mut ignores, result);
+    if ignores.is_empty() {
+        return;
+    }
+    for issue in result.issues.split_off(first_issue) {
+        let location = issue.location.as_deref().unwrap_or("");
+        let ignored = ignores.iter().any(|(scope, codes)| {
+            codes.contains(&issue.code.as_str()) && location_within(location, scope)
+        });
+        if !ignored {
+            result.issues.push(issue);
+            continue;
+        }
+        match issue.severity {
+            Severity::Error => result.errors -= 1,
+            Severity::Warning => result.warnings -= 1,
+            Severity::Info => {}
+        }
+    }
+}
+
+/// Every annotation in `value` with its location, in the validator's `stubs[0].responses[1]`
+/// notation.
+fn collect_ignore_annotations<'a>(
+    file: &Path,
+    value: &'a Value,
+    location: &str,
+    ignores: &mut Vec<(String, Vec<&'a str>)>,
+    result: &mut LintResult,
+) {
+    match value {
+        Value::Object(obj) => {
+            if let Some(annotation) = obj.get(IGNORE_ANNOTATION) {
+                let codes = annotation
+                    .as_array()
+                    .map(|codes| codes.iter().filter_map(Value::as_str).collect::<Vec<_>>())
+                    .filter(|codes| Some(codes.len()) == annotation.as_array().map(Vec::len));
+                match codes {
+                    Some(codes) => ignores.push((location.to_string(), codes)),
+                    None => result.add_issue(
+                        LintIssue::warning(
+                            "W017",
+                            format!("'{IGNORE_ANNOTATION}' must be an array of rule codes"),
+                            file.to_path_buf(),
+                        )
+                        .with_location(field_location(location, IGNORE_ANNOTATION))
+                        .with_suggestion(format!(r#"e.g. "{IGNORE_ANNOTATION}": ["W012"]"#)),
+                    ),
+                }
+            }
+            for (key, nested) in obj {
+                collect_ignore_annotations(
+                    file,
+                    nested,
+                    &field_location(location, key),
+                    ignores,
+                    result,
+                );
+            }
+        }
+        Value::Array(items) => {
+            for (idx, nested) in items.iter().enumerate() {
+                collect_ignore_annotations(
+                    file,
+                    nested,
+                    &format!("{location}[{idx}]"),
+                    ignores,
+                    result,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether `location` is `scope` or inside it; the imposter's scope (`""`) covers everything.
+fn location_within(location: &str, scope: &str) -> bool {
+    scope.is_empty()
+        || location
+            .strip_prefix(scope)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+}
+
+/// Validate a `_behaviors`-shaped block: one behavior object, or an ordered array of them. Shared
+/// by response `_behaviors` and imposter-level `defaultBehaviors`.
+fn validate_behaviors_block(
+    file: &Path,
+    b: &Value,
+    location: &str,
+    result: &mut LintResult,
+    options: &LintOptions,
+) {
+    if b.is_object() {
+        validate_behavior(file, b, location, result, options);
+    } else if let Some(entries) = b.as_array() {
+        for (idx, behavior) in entries.iter().enumerate() {
+            let entry_location = format!("{location}[{idx}]");
+            if !behavior.is_object() {
+                // The server rejects the whole block over one bad entry, so every
+                // behavior in it would be silently dropped.
+                result.add_issue(
+                    LintIssue::error(
+                        "E043",
+                        "Each entry of an ordered _behaviors array must be a behavior object",
+                        file.to_path_buf(),
+                    )
+                    .with_location(entry_location)
+                    .with_suggestion("Write each entry as an object, e.g. {\"wait\": 100}"),
+                );
+                continue;
+            }
+            validate_behavior(file, behavior, &entry_location, result, options);
+        }
+    }
+}
+
+/// `repeat` drives a single response's cycling, so the server rejects it in `defaultBehaviors`
+/// (the whole imposter create fails with a 400).
+fn check_default_behaviors_repeat(file: &Path, defaults: &Value, result: &mut LintResult) {
+    let entries: Vec<&Value> = match defaults.as_array() {
+        Some(entries) => entries.iter().collect(),
+        None => vec![defaults],
+    };
+    if entries.iter().any(|entry| entry.get("repeat").is_some()) {
+        result.add_issue(
+            LintIssue::error(
+                "E044",
+                "repeat cannot be set in defaultBehaviors",
+                file.to_path_buf(),
+            )
+            .with_location("defaultBehaviors")
+            .with_suggestion("Move repeat onto the individual responses that should repeat"),
+        );
+    }
+}
+
+/// Infer a script's effective engine: explicit `engine`, else inferred from a `file` path's
+/// extension (`.rhai`/`.js`), else the "rhai" default — mirrors
+/// `rift_mock_core::imposter::RiftScriptConfig`'s resolution rule. A `.lua`/`"lua"` engine still
+/// infers as `"lua"` here so [`check_script_engine`] can report it as removed (issue #450).
+fn infer_script_engine(explicit: Option<&str>, file_field: Option<&str>) -> String {
+    if let Some(e) = explicit {
+        return e.to_string();
+    }
+    let ext = file_field
+        .and_then(|f| Path::new(f).extension())
+        .and_then(|e| e.to_str());
+    match ext {
+        Some("rhai") => "rhai",
+        Some("lua") => "lua",
+        Some("js") => "javascript",
+        _ => "rhai",
+    }
+    .to_string()
+}
+
+/// Read a `file:` script path relative to the linted config file's own directory.
+fn read_script_file_relative(config_file: &Path, rel: &str) -> std::io::Result<String> {
+    let dir = config_file.parent().unwrap_or_else(|| Path::new("."));
+    std::fs::read_to_string(dir.join(rel))
+}
+
+/// Syntax-check a resolved script's content at the same level inline `code:` would get for its
+/// engine. Only "javascript" has an embedded syntax checker in this crate (`js_validator`,
+/// gated behind the `javascript` feature) — rhai scripts (and any other engine string) get the
+/// structural checks above (exactly-one-source, ref resolution, file existence) but no deep
+/// syntax check here.
+fn check_script_syntax(
+    file: &Path,
+    code: &str,
+    engine: &str,
+    location: &str,
+    result: &mut LintResult,
+    options: &LintOptions,
+) {
+    let script = EmbeddedScript {
+        kind: ScriptKind::Rift,
+        engine,
+        code,
+    };
+    if run_script_check(file, &script, location, result, options) {
+        return;
+    }
+    if (engine == "javascript" || engine == "js")
+        && let Err(e) = js_validator::validate_javascript(code)
+    {
+        result.add_issue(
+            LintIssue::error(
+                "E040",
+                format!("JavaScript syntax error in _rift.script: {e}"),
+                file.to_path_buf(),
+            )
+            .with_location(location),
+        );
+    }
+}
+
+/// Run `script` through [`LintOptions::script_check`], reporting its error as E057. Returns false
+/// when no check is configured, so the caller falls back to its built-in one.
+fn run_script_check(
+    file: &Path,
+    script: &EmbeddedScript<'_>,
+    location: &str,
+    result: &mut LintResult,
+    options: &LintOptions,
+) -> bool {
+    let Some(check) = &options.script_check else {
+        return false;
+    };
+    if let Err(e) = check(script) {
+        result.add_issue(
+            LintIssue::error(
+                "E057",
+                format!("Script does not compile ({}): {e}", script.engine),
+                file.to_path_buf(),
+            )
+            .with_location(location),
+        );
+    }
+    true
+}
+
+/// A response or predicate `inject` is only parsed when a script check is configured.
+fn check_inject_script(
+    file: &Path,
+    code: &str,
+    location: &str,
+    result: &mut LintResult,
+    options: &LintOptions,
+) {
+    result.add_issue(
+        LintIssue::info(
+            "I004",
+            "inject runs JavaScript on the server, which refuses it unless started with --allow-injection",
+            file.to_path_buf(),
+        )
+        .with_location(location)
+        .with_suggestion("Only load it into servers you control, started with --allow-injection"),
+    );
+    let script = EmbeddedScript {
+        kind: ScriptKind::Inject,
+        engine: "javascript",
+        code,
+    };
+    run_script_check(file, &script, location, result, options);
+}
+
+/// The `_rift.fault.tcp` fault kinds Rift accepts, canonical names plus short aliases — mirrors
+/// `TcpFaultKind::parse` (rift-mock-core `imposter/fault_io.rs`). Kept in sync by hand because
+/// rift-lint does not depend on rift-mock-core.
+const TCP_FAULT_KINDS: &[&str] = &[
+    "reset",
+    "CONNECTION_RESET_BY_PEER",
+    "empty",
+    "EMPTY_RESPONSE",
+    "garbage",
+    "random",
+    "RANDOM_DATA_THEN_CLOSE",
+    "malformed",
+    "MALFORMED_RESPONSE_CHUNK",
+];
+
+/// Validate a `_rift.fault.tcp` value (issue #531). Accepts the bare kind string and the
+/// probabilistic object form `{ probability, type }`. The object form must carry a numeric
+/// `probability` in `[0, 1]` (E041) — it exists solely to express that probability. An unknown
+/// fault type is a warning (W011), mirroring the serve-time `warn!` rather than failing the config.
+fn validate_tcp_fault(file: &Path, tcp: &Value, location: &str, result: &mut LintResult) {
+    let warn_unknown_kind = |result: &mut LintResult, kind: &str| {
+        if !TCP_FAULT_KINDS.contains(&kind) {
+            result.add_issue(
+                LintIssue::warning(
+                    "W011",
+                    format!("Unknown TCP fault type '{kind}' — the fault will not fire at runtime"),
+                    file.to_path_buf(),
+                )
+                .with_location(location.to_string())
+                .with_suggestion(format!("Use one of: {}", TCP_FAULT_KINDS.join(", "))),
+            );
+        }
+    };
+
+    match tcp {
+        Value::String(kind) => warn_unknown_kind(result, kind),
+        Value::Object(_) => {
+            match tcp.get("probability").and_then(Value::as_f64) {
+                Some(p) if (0.0..=1.0).contains(&p) => {}
+                Some(_) => result.add_issue(
+                    LintIssue::error(
+                        "E041",
+                        "_rift.fault.tcp 'probability' must be between 0.0 and 1.0",
+                        file.to_path_buf(),
+                    )
+                    .with_location(location.to_string()),
+                ),
+                None => result.add_issue(
+                    LintIssue::error(
+                        "E041",
+                        "_rift.fault.tcp object form requires a numeric 'probability' (use the bare string form for an always-firing fault)",
+                        file.to_path_buf(),
+                    )
+                    .with_location(location.to_string())
+                    .with_suggestion(
+                        "Add \"probability\": <0.0-1.0>, or replace the object with the bare fault-type string",
+                    ),
+                ),
+            }
+            match tcp.get("type").and_then(Value::as_str) {
+                Some(kind) => warn_unknown_kind(result, kind),
+                None => result.add_issue(
+                    LintIssue::error(
+                        "E041",
+                        "_rift.fault.tcp object form requires a string 'type'",
+                        file.to_path_buf(),
+                    )
+                    .with_location(location.to_string()),
+                ),
+            }
+        }
+        _ => result.add_issue(
+            LintIssue::error(
+                "E041",
+                "_rift.fault.tcp must be a fault-type string or an object { probability, type }",
+                file.to_path_buf(),
+            )
+            .with_location(location.to_string()),
+        ),
+    }
+}
+
+/// Validate `_rift.fetch` (composite responses): an object of named sub-requests, each naming
+/// exactly one target — `url` or `imposter` (a port) — and a `url` with a fixed scheme and host,
+/// or the response fails at serve time with a 502 (E045).
+fn validate_fetch(file: &Path, fetch: &Value, location: &str, result: &mut LintResult) {
+    let Some(entries) = fetch.as_object() else {
+        result.add_issue(
+            LintIssue::error(
+                "E045",
+                "_rift.fetch must be an object of named sub-requests",
+                file.to_path_buf(),
+            )
+            .with_location(location.to_string())
+            .with_suggestion(
+                "e.g. \"fetch\": {\"user\": {\"imposter\": 4546, \"path\": \"/users/1\"}}",
+            ),
+        );
+        return;
+    };
+    for (name, entry) in entries {
+        let has_url = entry.get("url").is_some_and(Value::is_string);
+        let has_imposter = entry.get("imposter").is_some_and(Value::is_u64);
+        if has_url == has_imposter {
+            result.add_issue(
+                LintIssue::error(
+                    "E045",
+                    format!("_rift.fetch '{name}' must set exactly one of 'url' or 'imposter'"),
+                    file.to_path_buf(),
+                )
+                .with_location(format!("{location}.{name}"))
+                .with_suggestion(
+                    "Use \"url\" for an absolute URL, or \"imposter\" (a port) plus \"path\"",
+                ),
+            );
+        }
+        if let Some(url) = entry.get("url").and_then(Value::as_str)
+            && !fetch_url_has_fixed_host(url)
+        {
+            result.add_issue(
+                LintIssue::error(
+                    "E045",
+                    format!(
+                        "_rift.fetch '{name}' url must name a fixed scheme and host; only its path and query may use ${{}} tokens"
+                    ),
+                    file.to_path_buf(),
+                )
+                .with_location(format!("{location}.{name}.url"))
+                .with_suggestion(
+                    "e.g. \"url\": \"https://api.example.com/users/${request.query.id}\"",
+                ),
+            );
+        }
+    }
+}
+
+/// Whether a `_rift.fetch` url is absolute with no `${}` token in its scheme or host — mirrors
+/// `split_origin` in rift-mock-core `imposter/fetch.rs`. Tokens may follow the host directly, as in
+/// `https://api.example.com${request.path}`.
+fn fetch_url_has_fixed_host(url: &str) -> bool {
+    let Some((scheme, authority)) = url.split_once("://") else {
+        return false;
+    };
+    let authority = &authority[..authority.find(['/', '?', '#']).unwrap_or(authority.len())];
+    let host_len = authority.find("${").unwrap_or(authority.len());
+    let mut tail = &authority[host_len..];
+    while let Some(token) = tail.strip_prefix("${") {
+        tail = token.split_once('}').map_or("", |(_, after)| after);
+    }
+    !scheme.contains("${") && host_len > 0 && tail.is_empty()
+}
+
+/// Fields of an imposter's `_rift` block.
+const RIFT_CONFIG_FIELDS: &[&str] = &["flowState", "metrics", "proxy", "scriptEngine", "scripts"];
+
+/// Fields of a response's `_rift` block.
+const RIFT_RESPONSE_FIELDS: &[&str] = &["fault", "script", "templated", "fetch", "stream"];
+
+const RIFT_FAULT_FIELDS: &[&str] = &["latency", "error", "tcp"];
+const RIFT_LATENCY_FIELDS: &[&str] = &["probability", "minMs", "maxMs", "ms"];
+const RIFT_ERROR_FIELDS: &[&str] = &["probability", "status", "body", "headers"];
+const RIFT_SCRIPT_FIELDS: &[&str] = &["engine", "code", "file", "ref"];
+const RIFT_FETCH_FIELDS: &[&str] = &["url", "imposter", "path", "method", "headers", "body"];
+const RIFT_REDIS_FIELDS: &[&str] = &["url", "poolSize", "keyPrefix"];
+const RIFT_METRICS_FIELDS: &[&str] = &["enabled", "port"];
+const RIFT_PROXY_FIELDS: &[&str] = &["upstream", "connectionPool"];
+const RIFT_UPSTREAM_FIELDS: &[&str] = &["host", "port", "protocol"];
+const RIFT_CONNECTION_POOL_FIELDS: &[&str] = &["maxIdlePerHost", "idleTimeoutSecs"];
+const RIFT_SCRIPT_ENGINE_FIELDS: &[&str] = &[
+    "defaultEngine",
+    "timeoutMs",
+    "maxOperations",
+    "maxMemoryBytes",
+    "libraries",
+];
+
+/// Script engines Rift runs — mirrors `ScriptEngine::new` (rift-mock-core `scripting/mod.rs`).
+const SCRIPT_ENGINES: &[&str] = &["rhai", "javascript", "js"];
+
+/// `flowState` backends built into Rift. Any other name needs an embedder's `FlowStoreProvider`.
+const FLOW_STATE_BACKENDS: &[&str] = &["inmemory", "redis"];
+
+fn is_port(value: &Value) -> bool {
+    value
+        .as_u64()
+        .is_some_and(|port| port <= u64::from(u16::MAX))
+}
+
+fn is_status_code(value: &Value) -> bool {
+    value
+        .as_u64()
+        .is_some_and(|code| (100..=599).contains(&code))
+}
+
+const RIFT_RESPONSE_TYPES: &[FieldType] = &[
+    (
+        "fault",
+        Value::is_object,
+        "an object of latency, error and tcp faults",
+    ),
+    (
+        "script",
+        Value::is_object,
+        "an object {\"engine\", \"code\"|\"file\"|\"ref\"}",
+    ),
+    ("templated", Value::is_boolean, "a boolean"),
+    ("stream", Value::is_object, "an object {\"chunks\": [...]}"),
+];
+const RIFT_LATENCY_TYPES: &[FieldType] = &[
+    ("minMs", Value::is_u64, "a non-negative integer"),
+    ("maxMs", Value::is_u64, "a non-negative integer"),
+    ("ms", Value::is_u64, "a non-negative integer"),
+];
+const RIFT_ERROR_TYPES: &[FieldType] = &[
+    ("status", is_status_code, "an HTTP status code (100-599)"),
+    ("body", Value::is_string, "a string"),
+    (
+        "headers",
+        is_string_map,
+        "an object of string header values",
+    ),
+];
+const RIFT_SCRIPT_TYPES: &[FieldType] = &[
+    ("engine", Value::is_string, "an engine name string"),
+    ("code", Value::is_string, "a string"),
+    ("file", Value::is_string, "a path string"),
+    ("ref", Value::is_string, "a _rift.scripts entry name"),
+];
+const RIFT_FETCH_TYPES: &[FieldType] = &[
+    ("url", Value::is_string, "a URL string"),
+    ("imposter", is_port, "an imposter port"),
+    ("path", Value::is_string, "a string"),
+    ("method", Value::is_string, "a string"),
+    (
+        "headers",
+        is_string_map,
+        "an object of string header values",
+    ),
+    ("body", Value::is_string, "a string"),
+];
+const RIFT_CONFIG_TYPES: &[FieldType] = &[
+    ("flowState", Value::is_object, "an object"),
+    ("metrics", Value::is_object, "an object"),
+    ("proxy", Value::is_object, "an object"),
+    ("scriptEngine", Value::is_object, "an object"),
+    ("scripts", Value::is_object, "an object of named scripts"),
+];
+const RIFT_FLOW_STATE_TYPES: &[FieldType] = &[
+    ("backend", Value::is_string, "a backend name string"),
+    ("ttlSeconds", Value::is_i64, "an integer number of seconds"),
+    ("redis", Value::is_object, "an object"),
+    (
+        "flowIdSource",
+        Value::is_string,
+        "\"imposter_port\" or \"header:<Name>\"",
+    ),
+];
+const RIFT_REDIS_TYPES: &[FieldType] = &[
+    ("url", Value::is_string, "a redis:// URL string"),
+    ("poolSize", Value::is_u64, "a positive integer"),
+    ("keyPrefix", Value::is_string, "a string"),
+];
+const RIFT_METRICS_TYPES: &[FieldType] = &[
+    ("enabled", Value::is_boolean, "a boolean"),
+    ("port", is_port, "a port number"),
+];
+const RIFT_PROXY_TYPES: &[FieldType] = &[
+    (
+        "upstream",
+        Value::is_object,
+        "an object {\"host\", \"port\"}",
+    ),
+    ("connectionPool", Value::is_object, "an object"),
+];
+const RIFT_UPSTREAM_TYPES: &[FieldType] = &[
+    ("host", Value::is_string, "a host name string"),
+    ("port", is_port, "a port number"),
+    ("protocol", Value::is_string, "a string"),
+];
+const RIFT_CONNECTION_POOL_TYPES: &[FieldType] = &[
+    ("maxIdlePerHost", Value::is_u64, "a non-negative integer"),
+    ("idleTimeoutSecs", Value::is_u64, "a non-negative integer"),
+];
+const RIFT_SCRIPT_ENGINE_TYPES: &[FieldType] = &[
+    ("defaultEngine", Value::is_string, "an engine name string"),
+    ("timeoutMs", Value::is_u64, "a non-negative integer"),
+    ("maxOperations", Value::is_u64, "a non-negative integer"),
+    ("maxMemoryBytes", Value::is_u64, "a non-negative integer"),
+    ("libraries", Value::is_object, "an object of named scripts"),
+];
+
+/// Check one `_rift` object: unknown keys warn (W014), wrong types are errors (E054).
+fn check_rift_object(
+    file: &Path,
+    value: &Value,
+    known: &[&str],
+    types: &[FieldType],
+    what: &str,
+    location: &str,
+    result: &mut LintResult,
+) {
+    report_unknown_fields(file, value, known, what, "W014", location, result);
+    check_field_types(file, value, types, "E054", location, result);
+}
+
+/// Validate an imposter's `_rift` block: flow state, metrics, the upstream proxy and the script
+/// engine defaults. `scripts` is checked by [`validate_script_registry`].
+fn validate_rift_config(file: &Path, rift: &Value, result: &mut LintResult) {
+    check_rift_object(
+        file,
+        rift,
+        RIFT_CONFIG_FIELDS,
+        RIFT_CONFIG_TYPES,
+        "_rift",
+        "_rift",
+        result,
+    );
+    if let Some(flow_state) = rift.get("flowState").filter(|f| f.is_object()) {
+        validate_flow_state(file, flow_state, result);
+    }
+    if let Some(metrics) = rift.get("metrics").filter(|m| m.is_object()) {
+        check_rift_object(
+            file,
+            metrics,
+            RIFT_METRICS_FIELDS,
+            RIFT_METRICS_TYPES,
+            "_rift.metrics",
+            "_rift.metrics",
+            result,
+        );
+    }
+    if let Some(proxy) = rift.get("proxy").filter(|p| p.is_object()) {
+        check_rift_object(
+            file,
+            proxy,
+            RIFT_PROXY_FIELDS,
+            RIFT_PROXY_TYPES,
+            "_rift.proxy",
+            "_rift.proxy",
+            result,
+        );
+        if let Some(upstream) = proxy.get("upstream").filter(|u| u.is_object()) {
+            check_rift_object(
+                file,
+                upstream,
+                RIFT_UPSTREAM_FIELDS,
+                RIFT_UPSTREAM_TYPES,
+                "_rift.proxy.upstream",
+                "_rift.proxy.upstream",
+                result,
+            );
+            for field in ["host", "port"] {
+                if upstream.get(field).is_none() {
+                    result.add_issue(
+                        LintIssue::error(
+                            "E054",
+                            format!("_rift.proxy.upstream requires '{field}'"),
+                            file.to_path_buf(),
+                        )
+                        .with_location("_rift.proxy.upstream"),
+                    );
+                }
+            }
+        }
+        if let Some(pool) = proxy.get("connectionPool").filter(|p| p.is_object()) {
+            check_rift_object(
+                file,
+                pool,
+                RIFT_CONNECTION_POOL_FIELDS,
+                RIFT_CONNECTION_POOL_TYPES,
+                "_rift.proxy.connectionPool",
+                "_rift.proxy.connectionPool",
+                result,
+            );
+        }
+    }
+    if let Some(engine) = rift.get("scriptEngine").filter(|e| e.is_object()) {
+        check_rift_object(
+            file,
+            engine,
+            RIFT_SCRIPT_ENGINE_FIELDS,
+            RIFT_SCRIPT_ENGINE_TYPES,
+            "_rift.scriptEngine",
+            "_rift.scriptEngine",
+            result,
+        );
+        if let Some(name) = engine.get("defaultEngine").and_then(Value::as_str) {
+            check_script_engine(file, name, "_rift.scriptEngine.defaultEngine", result);
+        }
+        if let Some(libraries) = engine.get("libraries").and_then(Value::as_object) {
+            for (name, library) in libraries.iter().filter(|(_, l)| l.is_object()) {
+                check_rift_object(
+                    file,
+                    library,
+                    RIFT_SCRIPT_FIELDS,
+                    RIFT_SCRIPT_TYPES,
+                    "_rift.script",
+                    &format!("_rift.scriptEngine.libraries.{name}"),
+                    result,
+                );
+            }
+        }
+    }
+}
+
+/// Validate `_rift.flowState`. Unknown keys are left alone: they belong to a provider-supplied
+/// store. A `redis` backend needs `redis.url` or the imposter fails to start (E053).
+fn validate_flow_state(file: &Path, flow_state: &Value, result: &mut LintResult) {
+    let location = "_rift.flowState";
+    check_field_types(
+        file,
+        flow_state,
+        RIFT_FLOW_STATE_TYPES,
+        "E054",
+        location,
+        result,
+    );
+    let backend = flow_state
+        .get("backend")
+        .and_then(Value::as_str)
+        .unwrap_or("inmemory");
+    let redis = flow_state.get("redis").filter(|r| r.is_object());
+
+    if !FLOW_STATE_BACKENDS.contains(&backend) {
+        result.add_issue(
+            LintIssue::warning(
+                "W016",
+                format!(
+                    "flowState backend '{backend}' is not built in; the imposter only starts if an embedder provides it"
+                ),
+                file.to_path_buf(),
+            )
+            .with_location(format!("{location}.backend"))
+            .with_suggestion(format!("Built-in backends: {}", FLOW_STATE_BACKENDS.join(", "))),
+        );
+    }
+    if backend != "redis" && redis.is_some() {
+        result.add_issue(
+            LintIssue::warning(
+                "W016",
+                format!("flowState 'redis' settings are ignored by the '{backend}' backend"),
+                file.to_path_buf(),
+            )
+            .with_location(format!("{location}.redis"))
+            .with_suggestion("Set \"backend\": \"redis\", or remove the redis block"),
+        );
+    }
+
+    if backend == "redis" {
+        match redis {
+            None => result.add_issue(
+                LintIssue::error(
+                    "E053",
+                    "flowState backend 'redis' requires a 'redis' block with a 'url'",
+                    file.to_path_buf(),
+                )
+                .with_location(location)
+                .with_suggestion("Add \"redis\": {\"url\": \"redis://localhost:6379\"}"),
+            ),
+            Some(redis) => validate_redis(file, redis, &format!("{location}.redis"), result),
+        }
+    }
+
+    if let Some(ttl) = flow_state.get("ttlSeconds").and_then(Value::as_i64)
+        && ttl <= 0
+    {
+        result.add_issue(
+            LintIssue::error(
+                "E053",
+                "flowState 'ttlSeconds' must be positive",
+                file.to_path_buf(),
+            )
+            .with_location(format!("{location}.ttlSeconds")),
+        );
+    }
+
+    if let Some(source) = flow_state.get("flowIdSource").and_then(Value::as_str) {
+        let valid = source == "imposter_port"
+            || source
+                .strip_prefix("header:")
+                .is_some_and(|name| !name.trim().is_empty());
+        if !valid {
+            result.add_issue(
+                LintIssue::error(
+                    "E053",
+                    format!("Unknown flowIdSource '{source}'"),
+                    file.to_path_buf(),
+                )
+                .with_location(format!("{location}.flowIdSource"))
+                .with_suggestion("Use \"imposter_port\" or \"header:<Name>\""),
+            );
+        }
     }
 }
 
-/// Infer a script's effective engine: explicit `engine`, else inferred from a `file` path's
-/// extension (`.rhai`/`.js`), else the "rhai" default — mirrors
-/// `rift_mock_core::imposter::RiftScriptConfig`'s resolution rule. A `.lua`/`"lua"` engine still
-/// infers as `"lua"` here (structural checks only; rift-mock-core rejects it at validate/run time —
-/// issue #450).
-fn infer_script_engine(explicit: Option<&str>, file_field: Option<&str>) -> String {
-    if let Some(e) = explicit {
-        return e.to_string();
+fn validate_redis(file: &Path, redis: &Value, location: &str, result: &mut LintResult) {
+    check_rift_object(
+        file,
+        redis,
+        RIFT_REDIS_FIELDS,
+        RIFT_REDIS_TYPES,
+        "_rift.flowState.redis",
+        location,
+        result,
+    );
+    match redis.get("url").and_then(Value::as_str) {
+        Some(url) if url.starts_with("redis://") || url.starts_with("rediss://") => {}
+        Some(url) => result.add_issue(
+            LintIssue::error(
+                "E053",
+                format!("Redis 'url' must start with redis:// or rediss://: {url}"),
+                file.to_path_buf(),
+            )
+            .with_location(format!("{location}.url")),
+        ),
+        None => result.add_issue(
+            LintIssue::error("E053", "Redis config requires a 'url'", file.to_path_buf())
+                .with_location(location)
+                .with_suggestion("e.g. \"url\": \"redis://localhost:6379\""),
+        ),
     }
-    let ext = file_field
-        .and_then(|f| Path::new(f).extension())
-        .and_then(|e| e.to_str());
-    match ext {
-        Some("rhai") => "rhai",
-        Some("lua") => "lua",
-        Some("js") => "javascript",
-        _ => "rhai",
+    if redis.get("poolSize").and_then(Value::as_u64) == Some(0) {
+        result.add_issue(
+            LintIssue::error(
+                "E053",
+                "Redis 'poolSize' must be at least 1",
+                file.to_path_buf(),
+            )
+            .with_location(format!("{location}.poolSize")),
+        );
     }
-    .to_string()
 }
 
-/// Read a `file:` script path relative to the linted config file's own directory.
-fn read_script_file_relative(config_file: &Path, rel: &str) -> std::io::Result<String> {
-    let dir = config_file.parent().unwrap_or_else(|| Path::new("."));
-    std::fs::read_to_string(dir.join(rel))
+/// Report a script engine Rift can't run (E052). Lua was removed, so it gets its own message.
+fn check_script_engine(file: &Path, engine: &str, location: &str, result: &mut LintResult) {
+    if SCRIPT_ENGINES.contains(&engine) {
+        return;
+    }
+    let message = if engine == "lua" {
+        "The Lua script engine was removed".to_string()
+    } else {
+        format!("Unknown script engine '{engine}'")
+    };
+    result.add_issue(
+        LintIssue::error("E052", message, file.to_path_buf())
+            .with_location(location)
+            .with_suggestion("Use \"rhai\" or \"javascript\""),
+    );
 }
 
-/// Syntax-check a resolved script's content at the same level inline `code:` would get for its
-/// engine. Only "javascript" has an embedded syntax checker in this crate (`js_validator`,
-/// gated behind the `javascript` feature) — rhai scripts (and any other engine string) get the
-/// structural checks above (exactly-one-source, ref resolution, file existence) but no deep
-/// syntax check here.
-fn check_script_syntax(
-    file: &Path,
-    code: &str,
-    engine: &str,
-    location: &str,
-    result: &mut LintResult,
-) {
-    if (engine == "javascript" || engine == "js")
-        && let Err(e) = js_validator::validate_javascript(code)
+/// Validate a response's `_rift` block beyond the script, TCP fault and fetch checks: field
+/// types, fault probabilities (E051) and latency ranges (W015), and stream chunks.
+fn validate_rift_response(file: &Path, rift: &Value, location: &str, result: &mut LintResult) {
+    check_rift_object(
+        file,
+        rift,
+        RIFT_RESPONSE_FIELDS,
+        RIFT_RESPONSE_TYPES,
+        "_rift",
+        location,
+        result,
+    );
+    if let Some(script) = rift.get("script").filter(|s| s.is_object()) {
+        check_rift_object(
+            file,
+            script,
+            RIFT_SCRIPT_FIELDS,
+            RIFT_SCRIPT_TYPES,
+            "_rift.script",
+            &format!("{location}.script"),
+            result,
+        );
+    }
+    if let Some(fetch) = rift.get("fetch").and_then(Value::as_object) {
+        for (name, entry) in fetch {
+            check_rift_object(
+                file,
+                entry,
+                RIFT_FETCH_FIELDS,
+                RIFT_FETCH_TYPES,
+                "_rift.fetch",
+                &format!("{location}.fetch.{name}"),
+                result,
+            );
+        }
+    }
+    if let Some(stream) = rift.get("stream").filter(|s| s.is_object()) {
+        validate_rift_stream(file, stream, &format!("{location}.stream"), result);
+    }
+
+    let Some(fault) = rift.get("fault").filter(|f| f.is_object()) else {
+        return;
+    };
+    let fault_location = format!("{location}.fault");
+    report_unknown_fields(
+        file,
+        fault,
+        RIFT_FAULT_FIELDS,
+        "_rift.fault",
+        "W014",
+        &fault_location,
+        result,
+    );
+    if let Some(latency) = fault.get("latency") {
+        let latency_location = format!("{fault_location}.latency");
+        check_rift_object(
+            file,
+            latency,
+            RIFT_LATENCY_FIELDS,
+            RIFT_LATENCY_TYPES,
+            "_rift.fault.latency",
+            &latency_location,
+            result,
+        );
+        check_fault_probability(file, latency, &latency_location, result);
+        check_latency_range(file, latency, &latency_location, result);
+    }
+    if let Some(error) = fault.get("error") {
+        let error_location = format!("{fault_location}.error");
+        check_rift_object(
+            file,
+            error,
+            RIFT_ERROR_FIELDS,
+            RIFT_ERROR_TYPES,
+            "_rift.fault.error",
+            &error_location,
+            result,
+        );
+        check_fault_probability(file, error, &error_location, result);
+    }
+}
+
+/// A latency or error fault's `probability`, when set, must be a number in `[0, 1]` (E051).
+fn check_fault_probability(file: &Path, fault: &Value, location: &str, result: &mut LintResult) {
+    let Some(probability) = fault.get("probability") else {
+        return;
+    };
+    if probability
+        .as_f64()
+        .is_none_or(|p| !(0.0..=1.0).contains(&p))
     {
         result.add_issue(
             LintIssue::error(
-                "E040",
-                format!("JavaScript syntax error in _rift.script: {e}"),
+                "E051",
+                format!(
+                    "Fault 'probability' must be a number between 0.0 and 1.0, got {probability}"
+                ),
                 file.to_path_buf(),
             )
-            .with_location(location),
+            .with_location(format!("{location}.probability"))
+            .with_suggestion("Use 0.5 for half of requests, 1.0 for all"),
         );
     }
 }
 
-/// The `_rift.fault.tcp` fault kinds Rift accepts, canonical names plus short aliases — mirrors
-/// `TcpFaultKind::parse` (rift-mock-core `imposter/fault_io.rs`). Kept in sync by hand because
-/// rift-lint does not depend on rift-mock-core.
-const TCP_FAULT_KINDS: &[&str] = &[
-    "reset",
-    "CONNECTION_RESET_BY_PEER",
-    "empty",
-    "EMPTY_RESPONSE",
-    "garbage",
-    "random",
-    "RANDOM_DATA_THEN_CLOSE",
-    "malformed",
-    "MALFORMED_RESPONSE_CHUNK",
-];
-
-/// Validate a `_rift.fault.tcp` value (issue #531). Accepts the bare kind string and the
-/// probabilistic object form `{ probability, type }`. The object form must carry a numeric
-/// `probability` in `[0, 1]` (E041) — it exists solely to express that probability. An unknown
-/// fault type is a warning (W011), mirroring the serve-time `warn!` rather than failing the config.
-fn validate_tcp_fault(file: &Path, tcp: &Value, location: &str, result: &mut LintResult) {
-    let warn_unknown_kind = |result: &mut LintResult, kind: &str| {
-        if !TCP_FAULT_KINDS.contains(&kind) {
-            result.add_issue(
-                LintIssue::warning(
-                    "W011",
-                    format!("Unknown TCP fault type '{kind}' — the fault will not fire at runtime"),
-                    file.to_path_buf(),
-                )
+/// A latency fault waits `ms` when set, else a random time in `[minMs, maxMs]` — or exactly
+/// `minMs` when `maxMs` isn't above it, so an inverted range or an `ms` next to a range means the
+/// range isn't what's served (W015).
+fn check_latency_range(file: &Path, latency: &Value, location: &str, result: &mut LintResult) {
+    let min = latency.get("minMs").and_then(Value::as_u64);
+    let max = latency.get("maxMs").and_then(Value::as_u64);
+    let warn = |result: &mut LintResult, message: String, suggestion: &str| {
+        result.add_issue(
+            LintIssue::warning("W015", message, file.to_path_buf())
                 .with_location(location.to_string())
-                .with_suggestion(format!("Use one of: {}", TCP_FAULT_KINDS.join(", "))),
+                .with_suggestion(suggestion.to_string()),
+        );
+    };
+    if latency.get("ms").is_some() {
+        if min.is_some() || max.is_some() {
+            warn(
+                result,
+                "Latency 'ms' is set, so 'minMs'/'maxMs' are ignored".to_string(),
+                "Use either a fixed 'ms' or a 'minMs'/'maxMs' range",
             );
         }
-    };
+        return;
+    }
+    match (min.unwrap_or(0), max.unwrap_or(0)) {
+        (0, 0) => warn(
+            result,
+            "Latency fault has no 'ms' or 'maxMs', so it adds no delay".to_string(),
+            "Set \"ms\" or a \"minMs\"/\"maxMs\" range",
+        ),
+        (min, max) if min > max => warn(
+            result,
+            format!(
+                "Latency 'minMs' ({min}) is above 'maxMs' ({max}); every request waits {min}ms"
+            ),
+            "Swap 'minMs' and 'maxMs'",
+        ),
+        _ => {}
+    }
+}
 
-    match tcp {
-        Value::String(kind) => warn_unknown_kind(result, kind),
-        Value::Object(_) => {
-            match tcp.get("probability").and_then(Value::as_f64) {
-                Some(p) if (0.0..=1.0).contains(&p) => {}
-                Some(_) => result.add_issue(
-                    LintIssue::error(
-                        "E041",
-                        "_rift.fault.tcp 'probability' must be between 0.0 and 1.0",
-                        file.to_path_buf(),
-                    )
-                    .with_location(location.to_string()),
-                ),
-                None => result.add_issue(
-                    LintIssue::error(
-                        "E041",
-                        "_rift.fault.tcp object form requires a numeric 'probability' (use the bare string form for an always-firing fault)",
-                        file.to_path_buf(),
-                    )
-                    .with_location(location.to_string())
-                    .with_suggestion(
-                        "Add \"probability\": <0.0-1.0>, or replace the object with the bare fault-type string",
-                    ),
-                ),
-            }
-            match tcp.get("type").and_then(Value::as_str) {
-                Some(kind) => warn_unknown_kind(result, kind),
-                None => result.add_issue(
-                    LintIssue::error(
-                        "E041",
-                        "_rift.fault.tcp object form requires a string 'type'",
-                        file.to_path_buf(),
-                    )
-                    .with_location(location.to_string()),
-                ),
-            }
-        }
-        _ => result.add_issue(
+/// `_rift.stream.chunks`: a non-empty array of `{size, delayMs?}`.
+fn validate_rift_stream(file: &Path, stream: &Value, location: &str, result: &mut LintResult) {
+    let valid = stream
+        .get("chunks")
+        .and_then(Value::as_array)
+        .is_some_and(|chunks| {
+            !chunks.is_empty()
+                && chunks.iter().all(|chunk| {
+                    chunk.get("size").is_some_and(Value::is_u64)
+                        && chunk.get("delayMs").is_none_or(Value::is_u64)
+                })
+        });
+    if !valid {
+        result.add_issue(
             LintIssue::error(
-                "E041",
-                "_rift.fault.tcp must be a fault-type string or an object { probability, type }",
+                "E054",
+                "_rift.stream needs 'chunks': a non-empty array of {\"size\", \"delayMs\"}",
                 file.to_path_buf(),
             )
-            .with_location(location.to_string()),
-        ),
+            .with_location(format!("{location}.chunks")),
+        );
     }
 }
 
@@ -263,6 +1599,7 @@ fn validate_script_source(
     location: &str,
     result: &mut LintResult,
     registry: &Value,
+    options: &LintOptions,
 ) {
     let code_field = script.get("code").and_then(|v| v.as_str());
     let file_field = script.get("file").and_then(|v| v.as_str());
@@ -291,6 +1628,12 @@ fn validate_script_source(
         );
         return;
     }
+    check_script_engine(
+        config_file,
+        &infer_script_engine(engine_field, file_field),
+        location,
+        result,
+    );
 
     if let Some(ref_name) = ref_field {
         let Some(target) = registry.get(ref_name) else {
@@ -335,7 +1678,7 @@ fn validate_script_source(
         };
         if let Some(code) = resolved {
             let engine = infer_script_engine(target_engine, target_file);
-            check_script_syntax(config_file, &code, &engine, location, result);
+            check_script_syntax(config_file, &code, &engine, location, result, options);
         }
         return;
     }
@@ -344,7 +1687,7 @@ fn validate_script_source(
         match read_script_file_relative(config_file, f) {
             Ok(content) => {
                 let engine = infer_script_engine(engine_field, Some(f));
-                check_script_syntax(config_file, &content, &engine, location, result);
+                check_script_syntax(config_file, &content, &engine, location, result, options);
             }
             Err(e) => {
                 result.add_issue(
@@ -363,14 +1706,19 @@ fn validate_script_source(
 
     if let Some(code) = code_field {
         let engine = infer_script_engine(engine_field, None);
-        check_script_syntax(config_file, code, &engine, location, result);
+        check_script_syntax(config_file, code, &engine, location, result, options);
     }
 }
 
 /// Validate every entry in the `_rift.scripts` registry: each must be a `code:`/`file:` leaf
 /// script (never `ref:` — no chains), and its source resolved/checked exactly like an inline
 /// stub response script.
-fn validate_script_registry(file: &Path, registry: &Value, result: &mut LintResult) {
+fn validate_script_registry(
+    file: &Path,
+    registry: &Value,
+    result: &mut LintResult,
+    options: &LintOptions,
+) {
     let Some(entries) = registry.as_object() else {
         return;
     };
@@ -389,7 +1737,7 @@ fn validate_script_registry(file: &Path, registry: &Value, result: &mut LintResu
             );
             continue;
         }
-        validate_script_source(file, entry, &location, result, &Value::Null);
+        validate_script_source(file, entry, &location, result, &Value::Null, options);
     }
 }
 
@@ -464,6 +1812,431 @@ fn check_port_range(file: &Path, imposter: &Value, result: &mut LintResult) {
     }
 }
 
+// Field schema. Rift's config types ignore fields they don't know, so a misspelled key loads
+// without complaint and silently does nothing: each level's known fields are listed here and
+// anything else is a warning (W012). A known field of the wrong type fails to load, so that is an
+// error, with a code per area.
+
+/// Fields of an imposter, including the read-only ones `GET /imposters/:port` adds, so an
+/// exported config lints clean.
+const IMPOSTER_FIELDS: &[&str] = &[
+    "port",
+    "host",
+    "protocol",
+    "cert",
+    "key",
+    "mutualAuth",
+    "name",
+    "recordRequests",
+    "enabled",
+    "recordMatches",
+    "stubs",
+    "defaultResponse",
+    "defaultForward",
+    "allowCors",
+    "allowCORS",
+    "cors",
+    "strictBehaviors",
+    "closestMatch",
+    "randomSeed",
+    "defaultBehaviors",
+    "serviceName",
+    "service_name",
+    "serviceInfo",
+    "service_info",
+    "_rift",
+    "numberOfRequests",
+    "requests",
+    "_links",
+];
+
+/// Fields of a stub; `rules` is read as `predicates`, `delayRange` as a `wait` on each response.
+const STUB_FIELDS: &[&str] = &[
+    "scenarioName",
+    "requiredScenarioState",
+    "newScenarioState",
+    "space",
+    "id",
+    "routePattern",
+    "predicates",
+    "rules",
+    "responses",
+    "recordedFrom",
+    "delayRange",
+    "_verify",
+    "_links",
+];
+
+/// Fields of a stub response, including the flat `statusCode`/`headers`/`body` form.
+const RESPONSE_FIELDS: &[&str] = &[
+    "is",
+    "proxy",
+    "inject",
+    "fault",
+    "_behaviors",
+    "behaviors",
+    "_rift",
+    "statusCode",
+    "headers",
+    "body",
+    "_mode",
+];
+
+/// Fields of an `is` response, and of `defaultResponse`.
+const IS_FIELDS: &[&str] = &["statusCode", "headers", "body", "bodyFile", "_mode"];
+
+/// Fields of a `proxy` response.
+const PROXY_FIELDS: &[&str] = &[
+    "to",
+    "mode",
+    "predicateGenerators",
+    "addWaitBehavior",
+    "injectHeaders",
+    "addDecorateBehavior",
+    "pathRewrite",
+    "recording",
+];
+
+/// Fields of a proxy's `recording` options.
+const PROXY_RECORDING_FIELDS: &[&str] = &[
+    "collapseDuplicates",
+    "maxResponsesPerStub",
+    "latencyJitter",
+    "streamCaptureMs",
+];
+
+/// Fields of a `predicateGenerators` entry.
+const PREDICATE_GENERATOR_FIELDS: &[&str] = &[
+    "matches",
+    "caseSensitive",
+    "predicateOperator",
+    "except",
+    "ignore",
+    "jsonpath",
+    "xpath",
+    "inject",
+];
+
+/// Keys of one behavior object.
+const BEHAVIOR_FIELDS: &[&str] = &[
+    "wait",
+    "repeat",
+    "copy",
+    "lookup",
+    "shellTransform",
+    "decorate",
+];
+
+/// Request fields a predicate can match on.
+const REQUEST_FIELDS: &[&str] = &[
+    "method",
+    "path",
+    "query",
+    "headers",
+    "body",
+    "form",
+    "ip",
+    "requestFrom",
+    "tls",
+];
+
+/// Request fields a `predicateGenerators` entry can build predicates from.
+const GENERATED_REQUEST_FIELDS: &[&str] = &["method", "path", "query", "headers", "body"];
+
+/// Predicate operators that take an object of request fields.
+const FIELD_OPERATORS: &[&str] = &[
+    "equals",
+    "deepEquals",
+    "contains",
+    "startsWith",
+    "endsWith",
+    "matches",
+    "exists",
+];
+
+/// `location.field`, or just `field` at the top of an imposter.
+fn field_location(location: &str, field: &str) -> String {
+    if location.is_empty() {
+        field.to_string()
+    } else {
+        format!("{location}.{field}")
+    }
+}
+
+/// The known field `key` was most likely meant to be: one differing only in case, or by at most
+/// two edits.
+fn closest_field<'a>(key: &str, known: &[&'a str]) -> Option<&'a str> {
+    if let Some(same) = known.iter().find(|k| k.eq_ignore_ascii_case(key)) {
+        return Some(same);
+    }
+    known
+        .iter()
+        .map(|k| (edit_distance(key, k), *k))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, k)| k)
+}
+
+/// Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(previous + 1).min(row[j] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Warn about every field of `value` not in `known` (W012): Rift ignores it.
+fn check_unknown_fields(
+    file: &Path,
+    value: &Value,
+    known: &[&str],
+    what: &str,
+    location: &str,
+    result: &mut LintResult,
+) {
+    report_unknown_fields(file, value, known, what, "W012", location, result);
+}
+
+fn report_unknown_fields(
+    file: &Path,
+    value: &Value,
+    known: &[&str],
+    what: &str,
+    code: &str,
+    location: &str,
+    result: &mut LintResult,
+) {
+    let Some(obj) = value.as_object() else {
+        return;
+    };
+    for key in obj
+        .keys()
+        .filter(|key| !known.contains(&key.as_str()) && *key != IGNORE_ANNOTATION)
+    {
+        let issue = LintIssue::warning(
+            code,
+            format!("Unknown {what} field '{key}' is ignored when the config loads"),
+            file.to_path_buf(),
+        )
+        .with_location(field_location(location, key));
+        result.add_issue(match closest_field(key, known) {
+            Some(field) => issue.with_suggestion(format!("Did you mean '{field}'?")),
+            None => issue.with_suggestion(format!("Known fields: {}", known.join(", "))),
+        });
+    }
+}
+
+/// A field's expected JSON type, checked by [`check_field_types`].
+type FieldType = (&'static str, fn(&Value) -> bool, &'static str);
+
+fn is_string_map(value: &Value) -> bool {
+    value
+        .as_object()
+        .is_some_and(|map| map.values().all(Value::is_string))
+}
+
+/// Report each field of `value` listed in `types` whose JSON type is wrong, as `code`.
+fn check_field_types(
+    file: &Path,
+    value: &Value,
+    types: &[FieldType],
+    code: &str,
+    location: &str,
+    result: &mut LintResult,
+) {
+    for (field, is_valid, expected) in types {
+        if let Some(field_value) = value.get(field)
+            && !field_value.is_null()
+            && !is_valid(field_value)
+        {
+            result.add_issue(
+                LintIssue::error(
+                    code,
+                    format!("'{field}' must be {expected}"),
+                    file.to_path_buf(),
+                )
+                .with_location(field_location(location, field)),
+            );
+        }
+    }
+}
+
+/// Types of an imposter's own fields.
+const IMPOSTER_FIELD_TYPES: &[FieldType] = &[
+    ("host", Value::is_string, "a string"),
+    ("cert", Value::is_string, "a PEM string"),
+    ("key", Value::is_string, "a PEM string"),
+    ("mutualAuth", Value::is_boolean, "a boolean"),
+    ("name", Value::is_string, "a string"),
+    ("recordRequests", Value::is_boolean, "a boolean"),
+    ("enabled", Value::is_boolean, "a boolean"),
+    ("recordMatches", Value::is_boolean, "a boolean"),
+    ("stubs", Value::is_array, "an array of stubs"),
+    (
+        "defaultResponse",
+        Value::is_object,
+        "an object like an 'is' response",
+    ),
+    ("defaultForward", Value::is_string, "a URL string"),
+    ("allowCors", Value::is_boolean, "a boolean"),
+    ("allowCORS", Value::is_boolean, "a boolean"),
+    ("cors", Value::is_object, "an object"),
+    ("strictBehaviors", Value::is_boolean, "a boolean"),
+    ("closestMatch", Value::is_boolean, "a boolean"),
+    ("randomSeed", Value::is_u64, "a non-negative integer"),
+    ("serviceName", Value::is_string, "a string"),
+    ("service_name", Value::is_string, "a string"),
+];
+
+/// Types of a stub's own fields.
+const STUB_FIELD_TYPES: &[FieldType] = &[
+    ("scenarioName", Value::is_string, "a string"),
+    ("requiredScenarioState", Value::is_string, "a string"),
+    ("newScenarioState", Value::is_string, "a string"),
+    ("space", Value::is_string, "a string"),
+    ("id", Value::is_string, "a string"),
+    ("routePattern", Value::is_string, "a string"),
+    ("predicates", Value::is_array, "an array of predicates"),
+    ("rules", Value::is_array, "an array of predicates"),
+    ("responses", Value::is_array, "an array of responses"),
+    ("recordedFrom", Value::is_string, "a URL string"),
+    (
+        "delayRange",
+        Value::is_array,
+        "an array of {min, max} objects",
+    ),
+];
+
+/// Types of a response's own fields, besides the response types validated in depth.
+const RESPONSE_FIELD_TYPES: &[FieldType] = &[
+    ("is", Value::is_object, "an object"),
+    ("inject", Value::is_string, "a JavaScript function string"),
+    ("fault", Value::is_string, "a fault name string"),
+];
+
+/// Types of an `is` response's fields.
+const IS_FIELD_TYPES: &[FieldType] = &[
+    ("bodyFile", Value::is_string, "a path string"),
+    ("_mode", is_response_mode, "\"text\" or \"binary\""),
+];
+
+fn is_response_mode(value: &Value) -> bool {
+    matches!(value.as_str(), Some("text" | "binary"))
+}
+
+/// Types of a proxy's fields.
+const PROXY_FIELD_TYPES: &[FieldType] = &[
+    ("mode", Value::is_string, "a string"),
+    (
+        "predicateGenerators",
+        Value::is_array,
+        "an array of generator objects",
+    ),
+    ("addWaitBehavior", Value::is_boolean, "a boolean"),
+    (
+        "injectHeaders",
+        is_string_map,
+        "an object of string header values",
+    ),
+    ("addDecorateBehavior", Value::is_string, "a script string"),
+    (
+        "pathRewrite",
+        is_path_rewrite,
+        "an object {\"from\": ..., \"to\": ...} of strings",
+    ),
+    ("recording", Value::is_object, "an object"),
+];
+
+fn is_path_rewrite(value: &Value) -> bool {
+    value.get("from").is_some_and(Value::is_string) && value.get("to").is_some_and(Value::is_string)
+}
+
+/// Types of a proxy's `recording` options.
+const PROXY_RECORDING_TYPES: &[FieldType] = &[
+    ("collapseDuplicates", Value::is_boolean, "a boolean"),
+    (
+        "maxResponsesPerStub",
+        Value::is_u64,
+        "a non-negative integer",
+    ),
+    ("latencyJitter", Value::is_boolean, "a boolean"),
+    ("streamCaptureMs", Value::is_u64, "a non-negative integer"),
+];
+
+/// Types of a `predicateGenerators` entry's fields.
+const PREDICATE_GENERATOR_TYPES: &[FieldType] = &[
+    ("matches", Value::is_object, "an object of request fields"),
+    ("caseSensitive", Value::is_boolean, "a boolean"),
+    ("predicateOperator", Value::is_string, "an operator name"),
+    ("except", Value::is_string, "a regex string"),
+    (
+        "ignore",
+        Value::is_object,
+        "an object of query or header keys",
+    ),
+    ("jsonpath", is_selector, "an object {\"selector\": ...}"),
+    (
+        "xpath",
+        is_selector,
+        "an object {\"selector\": ..., \"ns\": {...}}",
+    ),
+    ("inject", Value::is_string, "a JavaScript function string"),
+];
+
+/// A `jsonpath`/`xpath` parameter: a string `selector`, and for xpath optional `ns` prefixes.
+fn is_selector(value: &Value) -> bool {
+    value.get("selector").is_some_and(Value::is_string) && value.get("ns").is_none_or(is_string_map)
+}
+
+/// Types of a predicate's parameters.
+const PREDICATE_PARAMETER_TYPES: &[FieldType] = &[
+    ("caseSensitive", Value::is_boolean, "a boolean"),
+    ("keyCaseSensitive", Value::is_boolean, "a boolean"),
+    ("except", Value::is_string, "a regex string"),
+    (
+        "xpath",
+        is_selector,
+        "an object {\"selector\": ..., \"ns\": {...}}",
+    ),
+];
+
+/// Validate `defaultResponse`, served when no stub matches, like an `is` response, and
+/// `defaultForward`'s URL.
+fn check_default_response(file: &Path, imposter: &Value, result: &mut LintResult) {
+    if let Some(default) = imposter.get("defaultResponse").filter(|d| d.is_object()) {
+        check_unknown_fields(
+            file,
+            default,
+            IS_FIELDS,
+            "defaultResponse",
+            "defaultResponse",
+            result,
+        );
+        validate_is_response(file, default, "defaultResponse", result);
+    }
+    if let Some(url) = imposter.get("defaultForward").and_then(Value::as_str)
+        && !url.starts_with("http://")
+        && !url.starts_with("https://")
+    {
+        result.add_issue(
+            LintIssue::error(
+                "E050",
+                format!("'defaultForward' must be an http:// or https:// URL: {url}"),
+                file.to_path_buf(),
+            )
+            .with_location("defaultForward"),
+        );
+    }
+}
+
 /// Best-effort resolve of a `{ engine?, code?, file?, ref? }` script object's source text, for the
 /// E042 heuristic below. Unlike [`validate_script_source`] this doesn't itself report issues on a
 /// resolution failure (unreadable file, unknown ref) — those are already reported elsewhere by the
@@ -544,13 +2317,24 @@ pub fn validate_stub(
     registry: &Value,
 ) {
     let location = format!("stubs[{idx}]");
+    check_unknown_fields(file, stub, STUB_FIELDS, "stub", &location, result);
+    check_field_types(file, stub, STUB_FIELD_TYPES, "E050", &location, result);
+    check_delay_range(file, stub, &location, result);
 
-    if let Some(predicates) = stub.get("predicates").and_then(|v| v.as_array()) {
+    // `rules` is read as `predicates` when there are none
+    let predicates = stub
+        .get("predicates")
+        .filter(|p| p.as_array().is_some_and(|p| !p.is_empty()))
+        .map(|p| ("predicates", p))
+        .or_else(|| stub.get("rules").map(|r| ("rules", r)));
+    if let Some((field, predicates)) = predicates
+        && let Some(predicates) = predicates.as_array()
+    {
         for (pred_idx, predicate) in predicates.iter().enumerate() {
             validate_predicate(
                 file,
                 predicate,
-                &format!("{location}.predicates[{pred_idx}]"),
+                &format!("{location}.{field}[{pred_idx}]"),
                 result,
                 options,
             );
@@ -562,7 +2346,10 @@ pub fn validate_stub(
             result.add_issue(
                 LintIssue::warning("W002", "Stub has no responses defined", file.to_path_buf())
                     .with_location(&location)
-                    .with_suggestion("Add at least one response"),
+                    .with_suggestion(
+                        "Add at least one response: matching requests get the default response \
+                         and never reach later stubs",
+                    ),
             );
         }
 
@@ -576,7 +2363,8 @@ pub fn validate_stub(
                 registry,
             );
         }
-    } else {
+        check_response_count(file, responses, &location, result);
+    } else if stub.get("responses").is_none() {
         result.add_issue(
             LintIssue::error("E006", "Stub missing 'responses' field", file.to_path_buf())
                 .with_location(location),
@@ -584,6 +2372,71 @@ pub fn validate_stub(
     }
 }
 
+/// More responses than this in one stub, none of them using `repeat`, is a warning (W029).
+const MAX_RESPONSES_WITHOUT_REPEAT: usize = 100;
+
+/// Warn (W029) about a stub listing hundreds of responses without `repeat`: the server holds
+/// every one of them, and a run of identical responses is one response with `repeat`.
+fn check_response_count(file: &Path, responses: &[Value], location: &str, result: &mut LintResult) {
+    if responses.len() <= MAX_RESPONSES_WITHOUT_REPEAT || responses.iter().any(uses_repeat) {
+        return;
+    }
+    let repeated = responses
+        .windows(2)
+        .filter(|pair| pair[0] == pair[1])
+        .count();
+    let message = if repeated > 0 {
+        format!(
+            "Stub has {} responses and no repeat; {repeated} of them repeat the response before",
+            responses.len()
+        )
+    } else {
+        format!("Stub has {} responses and no repeat", responses.len())
+    };
+    result.add_issue(
+        LintIssue::warning("W029", message, file.to_path_buf())
+            .with_location(format!("{location}.responses"))
+            .with_suggestion(
+                "List each response once with \"_behaviors\": {\"repeat\": N} for how many \
+                 times it is returned in a row",
+            ),
+    );
+}
+
+/// Whether a response sets `repeat`, in `_behaviors` (object or array) or `behaviors`.
+fn uses_repeat(response: &Value) -> bool {
+    ["_behaviors", "behaviors"]
+        .iter()
+        .filter_map(|field| response.get(field))
+        .any(|behaviors| match behaviors {
+            Value::Array(entries) => entries.iter().any(|e| e.get("repeat").is_some()),
+            behaviors => behaviors.get("repeat").is_some(),
+        })
+}
+
+/// Each `delayRange` entry needs `min` and `max`, as numbers or numeric strings.
+fn check_delay_range(file: &Path, stub: &Value, location: &str, result: &mut LintResult) {
+    let Some(ranges) = stub.get("delayRange").and_then(Value::as_array) else {
+        return;
+    };
+    let is_millis = |v: Option<&Value>| {
+        v.is_some_and(|v| v.is_u64() || v.as_str().is_some_and(|s| s.parse::<u64>().is_ok()))
+    };
+    for (idx, range) in ranges.iter().enumerate() {
+        if !is_millis(range.get("min")) || !is_millis(range.get("max")) {
+            result.add_issue(
+                LintIssue::error(
+                    "E050",
+                    "Each 'delayRange' entry needs 'min' and 'max' milliseconds",
+                    file.to_path_buf(),
+                )
+                .with_location(format!("{location}.delayRange[{idx}]"))
+                .with_suggestion("e.g. {\"min\": 50, \"max\": 100}"),
+            );
+        }
+    }
+}
+
 /// Validate a predicate.
 pub fn validate_predicate(
     file: &Path,
@@ -614,8 +2467,14 @@ pub fn validate_predicate(
         return;
     };
 
-    let modifier_keys: HashSet<&str> =
-        HashSet::from(["jsonpath", "xpath", "caseSensitive", "except"]);
+    let modifier_keys: HashSet<&str> = HashSet::from([
+        "jsonpath",
+        "xpath",
+        "caseSensitive",
+        "keyCaseSensitive",
+        "except",
+        IGNORE_ANNOTATION,
+    ]);
     let operator_names: Vec<&str> = pred_obj
         .keys()
         .map(|k| k.as_str())
@@ -661,9 +2520,40 @@ pub fn validate_predicate(
         );
     }
 
+    for operator in &operator_names {
+        check_operator_value(file, operator, &pred_obj[*operator], location, result);
+    }
+    check_field_types(
+        file,
+        predicate,
+        PREDICATE_PARAMETER_TYPES,
+        "E047",
+        location,
+        result,
+    );
+    if let Some(except) = predicate.get("except").and_then(Value::as_str) {
+        check_regex(file, except, &format!("{location}.except"), result);
+    }
+
+    if let Some(code) = predicate.get("inject").and_then(Value::as_str) {
+        check_inject_script(file, code, &format!("{location}.inject"), result, options);
+    }
+
     if let Some(jsonpath) = predicate.get("jsonpath") {
         validate_jsonpath(file, jsonpath, location, result);
     }
+    if let Some(selector) = predicate
+        .get("xpath")
+        .and_then(|xpath| xpath.get("selector"))
+        .and_then(Value::as_str)
+    {
+        check_xpath_selector(
+            file,
+            selector,
+            &format!("{location}.xpath.selector"),
+            result,
+        );
+    }
 
     if let Some(matches) = predicate.get("matches") {
         validate_regex_patterns(file, matches, location, result, options);
@@ -690,6 +2580,97 @@ pub fn validate_predicate(
     }
 }
 
+/// Check what a predicate operator is given: an object of request fields for the field operators
+/// (booleans for `exists`), an array of predicates for `and`/`or`, a script for `inject`. A
+/// request field the matcher doesn't know is ignored, so the predicate matches without it.
+fn check_operator_value(
+    file: &Path,
+    operator: &str,
+    value: &Value,
+    location: &str,
+    result: &mut LintResult,
+) {
+    let operator_location = format!("{location}.{operator}");
+    let wrong_type = |result: &mut LintResult, expected: &str| {
+        result.add_issue(
+            LintIssue::error(
+                "E046",
+                format!("'{operator}' must be {expected}"),
+                file.to_path_buf(),
+            )
+            .with_location(operator_location.clone()),
+        );
+    };
+    match operator {
+        "and" | "or" if !value.is_array() => wrong_type(result, "an array of predicates"),
+        "inject" if !value.is_string() => wrong_type(result, "a JavaScript function string"),
+        _ if FIELD_OPERATORS.contains(&operator) => {
+            let Some(fields) = value.as_object() else {
+                wrong_type(
+                    result,
+                    "an object of request fields, e.g. {\"path\": \"/\"}",
+                );
+                return;
+            };
+            for (field, expected) in fields {
+                if !REQUEST_FIELDS.contains(&field.as_str()) {
+                    let issue = LintIssue::warning(
+                        "W013",
+                        format!(
+                            "'{field}' is not a request field; the predicate matches without it"
+                        ),
+                        file.to_path_buf(),
+                    )
+                    .with_location(format!("{operator_location}.{field}"));
+                    result.add_issue(match closest_field(field, REQUEST_FIELDS) {
+                        Some(known) => issue.with_suggestion(format!("Did you mean '{known}'?")),
+                        None => issue.with_suggestion(format!(
+                            "Request fields: {}",
+                            REQUEST_FIELDS.join(", ")
+                        )),
+                    });
+                }
+                if operator == "exists" && !is_exists_value(expected) {
+                    result.add_issue(
+                        LintIssue::error(
+                            "E046",
+                            format!(
+                                "'exists' takes true or false for '{field}', or an object of them"
+                            ),
+                            file.to_path_buf(),
+                        )
+                        .with_location(format!("{operator_location}.{field}")),
+                    );
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `true`/`false`, or an object of them for keys of `query`, `headers` or a JSON body.
+fn is_exists_value(value: &Value) -> bool {
+    value.is_boolean()
+        || value
+            .as_object()
+            .is_some_and(|o| o.values().all(is_exists_value))
+}
+
+/// Report `pattern` if it does not compile (E013).
+fn check_regex(file: &Path, pattern: &str, location: &str, result: &mut LintResult) {
+    if let Err(e) = Regex::new(pattern) {
+        result.add_issue(
+            LintIssue::error(
+                "E013",
+                format!("Invalid regex pattern: {e}"),
+                file.to_path_buf(),
+            )
+            .with_location(location)
+            .with_suggestion("Check regex syntax"),
+        );
+    }
+}
+
 /// Validate JSONPath selector.
 fn validate_jsonpath(file: &Path, jsonpath: &Value, location: &str, result: &mut LintResult) {
     if let Some(selector) = jsonpath.get("selector").and_then(|v| v.as_str()) {
@@ -705,18 +2686,12 @@ fn validate_jsonpath(file: &Path, jsonpath: &Value, location: &str, result: &mut
             );
         }
 
-        let open_brackets = selector.chars().filter(|c| *c == '[').count();
-        let close_brackets = selector.chars().filter(|c| *c == ']').count();
-        if open_brackets != close_brackets {
-            result.add_issue(
-                LintIssue::error(
-                    "E010",
-                    "Unbalanced brackets in JSONPath selector",
-                    file.to_path_buf(),
-                )
-                .with_location(format!("{location}.jsonpath.selector")),
-            );
-        }
+        check_jsonpath_selector(
+            file,
+            selector,
+            &format!("{location}.jsonpath.selector"),
+            result,
+        );
     } else {
         result.add_issue(
             LintIssue::error(
@@ -737,21 +2712,167 @@ fn validate_regex_patterns(
     result: &mut LintResult,
     _options: &LintOptions,
 ) {
-    if let Some(obj) = matches.as_object() {
-        for (field, pattern) in obj {
-            if let Some(pattern_str) = pattern.as_str()
-                && let Err(e) = Regex::new(pattern_str)
-            {
-                result.add_issue(
-                    LintIssue::error(
-                        "E013",
-                        format!("Invalid regex pattern in '{field}': {e}"),
-                        file.to_path_buf(),
-                    )
-                    .with_location(format!("{location}.matches.{field}"))
-                    .with_suggestion("Check regex syntax"),
-                );
+    check_regex_values(file, matches, &format!("{location}.matches"), result);
+    if let Some(body) = matches.get("body") {
+        check_body_wildcards(file, body, &format!("{location}.matches.body"), result);
+    }
+}
+
+/// Warn (W026) about `.*` or `.+` in a body regex, down through JSON body keys. `matches` searches
+/// the whole body, so the wildcard only adds a scan to the end of the body on every request that
+/// reaches the predicate, and it stops at the first newline of a multi-line body.
+fn check_body_wildcards(file: &Path, body: &Value, location: &str, result: &mut LintResult) {
+    match body {
+        Value::String(pattern) if has_unbounded_wildcard(pattern) => {
+            result.add_issue(
+                LintIssue::warning(
+                    "W026",
+                    format!("Body regex has an unbounded wildcard: {pattern}"),
+                    file.to_path_buf(),
+                )
+                .with_location(location)
+                .with_suggestion(
+                    "Drop a leading or trailing .* (matches already searches the whole body), \
+                     use a narrower class such as [^\"]*, or match one field with a jsonpath \
+                     selector",
+                ),
+            );
+        }
+        Value::Object(fields) => {
+            for (field, value) in fields {
+                check_body_wildcards(file, value, &format!("{location}.{field}"), result);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether `pattern` has an unescaped `.` outside a character class followed by `*` or `+`.
+fn has_unbounded_wildcard(pattern: &str) -> bool {
+    let mut chars = pattern.chars().peekable();
+    let mut in_class = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '.' if !in_class && matches!(chars.peek(), Some('*' | '+')) => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Compile every string in a `matches` value, down through `query`, `headers` and JSON body keys.
+fn check_regex_values(file: &Path, value: &Value, location: &str, result: &mut LintResult) {
+    let Some(obj) = value.as_object() else {
+        return;
+    };
+    for (field, pattern) in obj {
+        let field_location = format!("{location}.{field}");
+        match pattern {
+            Value::String(pattern_str) => {
+                if let Err(e) = Regex::new(pattern_str) {
+                    result.add_issue(
+                        LintIssue::error(
+                            "E013",
+                            format!("Invalid regex pattern in '{field}': {e}"),
+                            file.to_path_buf(),
+                        )
+                        .with_location(field_location)
+                        .with_suggestion("Check regex syntax"),
+                    );
+                }
             }
+            Value::Object(_) => check_regex_values(file, pattern, &field_location, result),
+            _ => {}
+        }
+    }
+}
+
+/// Compile a JSONPath selector as the matcher does, rooting a bare `user.name` at `$` first.
+/// Unbalanced brackets get their own error (E010); anything else that doesn't parse is E055.
+fn check_jsonpath_selector(file: &Path, selector: &str, location: &str, result: &mut LintResult) {
+    let open_brackets = selector.chars().filter(|c| *c == '[').count();
+    let close_brackets = selector.chars().filter(|c| *c == ']').count();
+    if open_brackets != close_brackets {
+        result.add_issue(
+            LintIssue::error(
+                "E010",
+                "Unbalanced brackets in JSONPath selector",
+                file.to_path_buf(),
+            )
+            .with_location(location),
+        );
+        return;
+    }
+
+    let trimmed = selector.trim();
+    let rooted = if trimmed.starts_with('$') {
+        trimmed.to_string()
+    } else if trimmed.starts_with('[') {
+        format!("${trimmed}")
+    } else {
+        format!("$.{trimmed}")
+    };
+    if let Err(e) = serde_json_path::JsonPath::parse(&rooted) {
+        result.add_issue(
+            LintIssue::error(
+                "E055",
+                format!("Invalid JSONPath selector '{selector}': {e}"),
+                file.to_path_buf(),
+            )
+            .with_location(location)
+            .with_suggestion("e.g. $.user.name or $.items[0].id"),
+        );
+    }
+}
+
+/// Compile an XPath expression (E056). An empty expression compiles to nothing and never matches.
+fn check_xpath_selector(file: &Path, selector: &str, location: &str, result: &mut LintResult) {
+    let error = match sxd_xpath::Factory::new().build(selector) {
+        Ok(Some(_)) => return,
+        Ok(None) => "it is empty".to_string(),
+        Err(e) => e.to_string(),
+    };
+    result.add_issue(
+        LintIssue::error(
+            "E056",
+            format!("Invalid XPath expression '{selector}': {error}"),
+            file.to_path_buf(),
+        )
+        .with_location(location)
+        .with_suggestion("e.g. //order/id or /root/item[1]/@name"),
+    );
+}
+
+/// Compile the `selector` of a `jsonpath` or `xpath` parameter, if it is a string.
+fn check_selector_parameter(
+    file: &Path,
+    parameters: &Value,
+    location: &str,
+    result: &mut LintResult,
+) {
+    for (parameter, check) in [
+        (
+            "jsonpath",
+            check_jsonpath_selector as fn(&Path, &str, &str, &mut LintResult),
+        ),
+        ("xpath", check_xpath_selector),
+    ] {
+        if let Some(selector) = parameters
+            .get(parameter)
+            .and_then(|p| p.get("selector"))
+            .and_then(Value::as_str)
+        {
+            check(
+                file,
+                selector,
+                &format!("{location}.{parameter}.selector"),
+                result,
+            );
         }
     }
 }
@@ -773,6 +2894,32 @@ pub fn validate_response(
     let has_inject = response.get("inject").is_some();
     let has_fault = response.get("fault").is_some();
     let has_rift = response.get("_rift").is_some();
+    // The flat form: `statusCode`/`headers`/`body` with no `is` around them
+    let has_flat = !has_is
+        && ["statusCode", "headers", "body"]
+            .iter()
+            .any(|field| response.get(field).is_some());
+
+    check_unknown_fields(
+        file,
+        response,
+        RESPONSE_FIELDS,
+        "response",
+        location,
+        result,
+    );
+    check_field_types(
+        file,
+        response,
+        RESPONSE_FIELD_TYPES,
+        "E050",
+        location,
+        result,
+    );
+
+    if let Some(code) = response.get("inject").and_then(Value::as_str) {
+        check_inject_script(file, code, &format!("{location}.inject"), result, options);
+    }
 
     if has_rift {
         result.add_issue(
@@ -794,6 +2941,7 @@ pub fn validate_response(
             &format!("{location}._rift.script"),
             result,
             registry,
+            options,
         );
     }
 
@@ -807,7 +2955,15 @@ pub fn validate_response(
         validate_tcp_fault(file, tcp, &format!("{location}._rift.fault.tcp"), result);
     }
 
-    let response_types = [has_is, has_proxy, has_inject, has_fault, has_rift];
+    if let Some(fetch) = response.get("_rift").and_then(|rift| rift.get("fetch")) {
+        validate_fetch(file, fetch, &format!("{location}._rift.fetch"), result);
+    }
+
+    if let Some(rift) = response.get("_rift") {
+        validate_rift_response(file, rift, &format!("{location}._rift"), result);
+    }
+
+    let response_types = [has_is, has_proxy, has_inject, has_fault, has_rift, has_flat];
     let active_types = response_types.iter().filter(|&&t| t).count();
 
     if active_types == 0 {
@@ -838,22 +2994,35 @@ pub fn validate_response(
     }
 
     if let Some(is_response) = response.get("is") {
-        validate_is_response(file, is_response, &format!("{location}.is"), result);
+        let is_location = format!("{location}.is");
+        check_unknown_fields(
+            file,
+            is_response,
+            IS_FIELDS,
+            "'is' response",
+            &is_location,
+            result,
+        );
+        validate_is_response(file, is_response, &is_location, result);
+    } else if has_flat {
+        validate_is_response(file, response, location, result);
     }
 
     if let Some(proxy) = response.get("proxy")
         && !proxy.is_null()
     {
         validate_proxy_response(file, proxy, &format!("{location}.proxy"), result);
+        if let Some(to) = proxy.get("to").and_then(Value::as_str) {
+            check_proxy_host(file, to, &format!("{location}.proxy.to"), result, options);
+        }
     }
 
-    // Rift/Mountebank write behaviors as `_behaviors: { wait, repeat, ... }` (object).
-    // Rift also accepts and serializes `behaviors: [...]` (array) for MB compatibility.
-    // Validate whichever form is present.
+    // Rift/Mountebank write behaviors as `_behaviors: { wait, repeat, ... }` (object), or as an
+    // ordered `_behaviors: [...]` array applied in the listed order. Rift also accepts and
+    // serializes `behaviors: [...]` (array) for MB compatibility. Validate whichever form is
+    // present.
     if let Some(b) = response.get("_behaviors") {
-        if b.is_object() {
-            validate_behavior(file, b, &format!("{location}._behaviors"), result, options);
-        }
+        validate_behaviors_block(file, b, &format!("{location}._behaviors"), result, options);
     } else if let Some(behaviors) = response.get("behaviors").and_then(|v| v.as_array()) {
         for (idx, behavior) in behaviors.iter().enumerate() {
             validate_behavior(
@@ -874,6 +3043,8 @@ pub fn validate_is_response(
     location: &str,
     result: &mut LintResult,
 ) {
+    check_field_types(file, is_response, IS_FIELD_TYPES, "E050", location, result);
+
     if let Some(status) = is_response.get("statusCode") {
         let status_num = status
             .as_u64()
@@ -906,7 +3077,27 @@ pub fn validate_is_response(
     }
 
     if let Some(headers) = is_response.get("headers") {
-        validate_headers(file, headers, &format!("{location}.headers"), result);
+        let headers_location = format!("{location}.headers");
+        validate_headers(file, headers, &headers_location, result);
+        check_plaintext_credentials(file, headers, &headers_location, result);
+        if let Some((name, _)) = headers.as_object().and_then(|headers| {
+            headers.iter().find(|(name, value)| {
+                name.eq_ignore_ascii_case("access-control-allow-origin") && *value == "*"
+            })
+        }) {
+            result.add_issue(
+                LintIssue::warning("W032", "Response allows every origin", file.to_path_buf())
+                    .with_location(format!("{headers_location}.{name}"))
+                    .with_suggestion(
+                        "Set the origin that may call it, or configure the imposter's \"cors\" \
+                         block",
+                    ),
+            );
+        }
+    }
+
+    if let Some(body) = is_response.get("body") {
+        check_inline_body_size(file, body, &format!("{location}.body"), result);
     }
 
     // Check if body is valid JSON when Content-Type is application/json
@@ -937,6 +3128,82 @@ pub fn validate_is_response(
     }
 }
 
+/// Headers that carry a credential.
+const CREDENTIAL_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "x-api-key",
+    "api-key",
+    "apikey",
+    "x-auth-token",
+    "x-access-token",
+];
+
+/// Warn (W031) about a credential written into a config's headers, where it is committed and
+/// returned by `GET /imposters`. Templated values are left alone.
+fn check_plaintext_credentials(
+    file: &Path,
+    headers: &Value,
+    location: &str,
+    result: &mut LintResult,
+) {
+    let Some(headers) = headers.as_object() else {
+        return;
+    };
+    for (name, value) in headers {
+        let lower = name.to_ascii_lowercase();
+        let Some(value) = value.as_str() else {
+            continue;
+        };
+        let templated = ["${", "{{"].iter().any(|marker| value.contains(marker));
+        if !CREDENTIAL_HEADERS.contains(&lower.as_str()) || value.trim().is_empty() || templated {
+            continue;
+        }
+        result.add_issue(
+            LintIssue::warning(
+                "W031",
+                format!("Header '{name}' holds a plaintext credential"),
+                file.to_path_buf(),
+            )
+            .with_location(format!("{location}.{name}"))
+            .with_suggestion(
+                "Use a dummy value for tests, or read it from the environment with \
+                 <%= process.env.VAR %>",
+            ),
+        );
+    }
+}
+
+/// Inline bodies larger than this many bytes are a warning (W025).
+const MAX_INLINE_BODY_BYTES: usize = 256 * 1024;
+
+/// Warn (W025) about a large inline body: the server keeps it in memory with the imposter and
+/// returns it from every `GET /imposters`, where a `bodyFile` is read from disk when served.
+fn check_inline_body_size(file: &Path, body: &Value, location: &str, result: &mut LintResult) {
+    let size = match body {
+        Value::String(text) => text.len(),
+        Value::Null => 0,
+        body => body.to_string().len(),
+    };
+    if size > MAX_INLINE_BODY_BYTES {
+        result.add_issue(
+            LintIssue::warning(
+                "W025",
+                format!(
+                    "Inline body is {} KiB, above {} KiB",
+                    size / 1024,
+                    MAX_INLINE_BODY_BYTES / 1024
+                ),
+                file.to_path_buf(),
+            )
+            .with_location(location)
+            .with_suggestion(
+                "Move it to a file and serve it with \"bodyFile\" (relative to --datadir)",
+            ),
+        );
+    }
+}
+
 /// Validate response headers.
 pub fn validate_headers(file: &Path, headers: &Value, location: &str, result: &mut LintResult) {
     let Some(headers_obj) = headers.as_object() else {
@@ -1029,6 +3296,41 @@ pub fn validate_proxy_response(
     location: &str,
     result: &mut LintResult,
 ) {
+    check_unknown_fields(file, proxy, PROXY_FIELDS, "proxy", location, result);
+    check_field_types(file, proxy, PROXY_FIELD_TYPES, "E049", location, result);
+    if let Some(recording) = proxy.get("recording").filter(|r| r.is_object()) {
+        let recording_location = format!("{location}.recording");
+        check_unknown_fields(
+            file,
+            recording,
+            PROXY_RECORDING_FIELDS,
+            "proxy recording",
+            &recording_location,
+            result,
+        );
+        check_field_types(
+            file,
+            recording,
+            PROXY_RECORDING_TYPES,
+            "E049",
+            &recording_location,
+            result,
+        );
+    }
+    if let Some(headers) = proxy.get("injectHeaders") {
+        check_plaintext_credentials(file, headers, &format!("{location}.injectHeaders"), result);
+    }
+    if let Some(generators) = proxy.get("predicateGenerators").and_then(Value::as_array) {
+        for (idx, generator) in generators.iter().enumerate() {
+            validate_predicate_generator(
+                file,
+                generator,
+                &format!("{location}.predicateGenerators[{idx}]"),
+                result,
+            );
+        }
+    }
+
     if let Some(to) = proxy.get("to") {
         if let Some(url) = to.as_str() {
             if !url.starts_with("http://") && !url.starts_with("https://") {
@@ -1079,7 +3381,12 @@ pub fn validate_proxy_response(
     }
 
     if let Some(mode) = proxy.get("mode").and_then(|v| v.as_str()) {
-        let valid_modes = ["proxyOnce", "proxyAlways", "proxyTransparent"];
+        let valid_modes = [
+            "proxyOnce",
+            "proxyAlways",
+            "proxyTransparent",
+            "proxyShadow",
+        ];
         if !valid_modes.contains(&mode) {
             result.add_issue(
                 LintIssue::warning(
@@ -1091,7 +3398,209 @@ pub fn validate_proxy_response(
                 .with_suggestion(format!("Use one of: {}", valid_modes.join(", "))),
             );
         }
+        if mode == "proxyAlways" {
+            check_proxy_always_recording(file, proxy, location, result);
+        }
+    }
+}
+
+/// Warn (W028) about a `proxyAlways` proxy that records without bound. Without
+/// `predicateGenerators` each request records a new stub, since a recorded stub is only appended
+/// to when it has predicates; without `recording.maxResponsesPerStub` each stub grows by one
+/// response per request.
+fn check_proxy_always_recording(
+    file: &Path,
+    proxy: &Value,
+    location: &str,
+    result: &mut LintResult,
+) {
+    let has_generators = proxy
+        .get("predicateGenerators")
+        .and_then(Value::as_array)
+        .is_some_and(|generators| !generators.is_empty());
+    let capped = proxy
+        .get("recording")
+        .and_then(|recording| recording.get("maxResponsesPerStub"))
+        .is_some_and(Value::is_u64);
+    let (message, suggestion) = if !has_generators {
+        (
+            "proxyAlways without predicateGenerators records a new stub for every request",
+            "Add predicateGenerators so requests that match the same fields share a stub",
+        )
+    } else if !capped {
+        (
+            "proxyAlways without recording.maxResponsesPerStub adds a response to a stub on every request",
+            "Set \"recording\": {\"maxResponsesPerStub\": N}, and collapseDuplicates to skip repeats",
+        )
+    } else {
+        return;
+    };
+    result.add_issue(
+        LintIssue::warning("W028", message, file.to_path_buf())
+            .with_location(location)
+            .with_suggestion(suggestion),
+    );
+}
+
+/// Warn (W033) about a proxy to an external host that isn't in
+/// [`LintOptions::allowed_proxy_hosts`]: requests to the imposter, with their headers, are
+/// forwarded there.
+fn check_proxy_host(
+    file: &Path,
+    to: &str,
+    location: &str,
+    result: &mut LintResult,
+    options: &LintOptions,
+) {
+    let Some(host) = url_host(to) else {
+        return;
+    };
+    let allowed = options.allowed_proxy_hosts.iter().any(|pattern| {
+        let pattern = pattern.to_ascii_lowercase();
+        match pattern.strip_prefix("*.") {
+            Some(domain) => host.ends_with(&format!(".{domain}")),
+            None => host == pattern,
+        }
+    });
+    if allowed || !is_external_host(&host) {
+        return;
+    }
+    result.add_issue(
+        LintIssue::warning(
+            "W033",
+            format!("Proxy forwards requests to an external host: {host}"),
+            file.to_path_buf(),
+        )
+        .with_location(location)
+        .with_suggestion(format!(
+            "Add \"{host}\" to allowed-proxy-hosts in .riftlint.toml if it is meant to be called"
+        )),
+    );
+}
+
+/// The lowercased host of an `http://` or `https://` URL.
+fn url_host(url: &str) -> Option<String> {
+    let rest = url
+        .strip_prefix("http://")
+        .or_else(|| url.strip_prefix("https://"))?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host_port = authority.rsplit('@').next()?;
+    let host = match host_port.strip_prefix('[') {
+        Some(v6) => v6.split(']').next()?,
+        None => host_port.split(':').next()?,
+    };
+    (!host.is_empty()).then(|| host.to_ascii_lowercase())
+}
+
+/// Whether `host` is outside the local network: not loopback or a private address, and not a
+/// single-label or `.local`/`.internal`/`.localhost` name.
+fn is_external_host(host: &str) -> bool {
+    match host.parse::<std::net::IpAddr>() {
+        Ok(std::net::IpAddr::V4(ip)) => {
+            !(ip.is_loopback() || ip.is_private() || ip.is_link_local() || ip.is_unspecified())
+        }
+        Ok(std::net::IpAddr::V6(ip)) => {
+            // fc00::/7 is unique local, fe80::/10 link local
+            let segment = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || segment & 0xfe00 == 0xfc00
+                || segment & 0xffc0 == 0xfe80)
+        }
+        Err(_) => {
+            host.contains('.')
+                && ![".local", ".internal", ".localhost"]
+                    .iter()
+                    .any(|suffix| host.ends_with(suffix))
+        }
+    }
+}
+
+/// Validate one `predicateGenerators` entry. The proxy skips an entry with neither `matches` nor
+/// `inject`, so it records no predicates from it.
+fn validate_predicate_generator(
+    file: &Path,
+    generator: &Value,
+    location: &str,
+    result: &mut LintResult,
+) {
+    let Some(obj) = generator.as_object() else {
+        result.add_issue(
+            LintIssue::error(
+                "E049",
+                "Predicate generator must be an object",
+                file.to_path_buf(),
+            )
+            .with_location(location),
+        );
+        return;
+    };
+    check_unknown_fields(
+        file,
+        generator,
+        PREDICATE_GENERATOR_FIELDS,
+        "predicate generator",
+        location,
+        result,
+    );
+    check_field_types(
+        file,
+        generator,
+        PREDICATE_GENERATOR_TYPES,
+        "E049",
+        location,
+        result,
+    );
+
+    if !obj.contains_key("matches") && !obj.contains_key("inject") {
+        result.add_issue(
+            LintIssue::error(
+                "E049",
+                "Predicate generator needs 'matches' or 'inject'; without them it is skipped",
+                file.to_path_buf(),
+            )
+            .with_location(location)
+            .with_suggestion("e.g. {\"matches\": {\"method\": true, \"path\": true}}"),
+        );
+    }
+
+    if let Some(matches) = obj.get("matches").and_then(Value::as_object) {
+        for field in matches.keys() {
+            if !GENERATED_REQUEST_FIELDS.contains(&field.as_str()) {
+                result.add_issue(
+                    LintIssue::warning(
+                        "W013",
+                        format!("Predicate generators can't match on '{field}'; it is ignored"),
+                        file.to_path_buf(),
+                    )
+                    .with_location(format!("{location}.matches.{field}"))
+                    .with_suggestion(format!(
+                        "Generators match on: {}",
+                        GENERATED_REQUEST_FIELDS.join(", ")
+                    )),
+                );
+            }
+        }
+    }
+
+    if let Some(operator) = obj.get("predicateOperator").and_then(Value::as_str)
+        && !FIELD_OPERATORS.contains(&operator)
+    {
+        result.add_issue(
+            LintIssue::error(
+                "E049",
+                format!("Unknown predicateOperator: {operator}"),
+                file.to_path_buf(),
+            )
+            .with_location(format!("{location}.predicateOperator"))
+            .with_suggestion(format!("Use one of: {}", FIELD_OPERATORS.join(", "))),
+        );
+    }
+
+    if let Some(except) = obj.get("except").and_then(Value::as_str) {
+        check_regex(file, except, &format!("{location}.except"), result);
     }
+    check_selector_parameter(file, generator, location, result);
 }
 
 /// Validate a behavior.
@@ -1105,6 +3614,14 @@ pub fn validate_behavior(
     let Some(obj) = behavior.as_object() else {
         return;
     };
+    check_unknown_fields(
+        file,
+        behavior,
+        BEHAVIOR_FIELDS,
+        "behavior",
+        location,
+        result,
+    );
 
     if let Some(wait) = obj.get("wait") {
         if let Some(script) = wait.as_str() {
@@ -1120,6 +3637,8 @@ pub fn validate_behavior(
             // fixed millisecond delay — valid
         } else if is_valid_wait_range(wait) {
             // {min, max} range object — valid Rift extension
+        } else if is_valid_wait_jitter(wait) {
+            // {mean, jitter} recorded-latency distribution — valid Rift extension
         } else if let Some(script) = wait_inject_script(wait) {
             // {inject: "function(){...}"} — the object spelling of a function wait (issue #608);
             // validate the inner script exactly as the bare-string form.
@@ -1135,7 +3654,7 @@ pub fn validate_behavior(
             result.add_issue(
                 LintIssue::error(
                     "E025",
-                    "Wait behavior must be a number, JavaScript function string, {min, max} object, or {inject: \"function(){...}\"}",
+                    "Wait behavior must be a number, JavaScript function string, {min, max} object, {mean, jitter} object, or {inject: \"function(){...}\"}",
                     file.to_path_buf(),
                 )
                 .with_location(format!("{location}.wait"))
@@ -1159,34 +3678,68 @@ pub fn validate_behavior(
         }
     }
 
-    if let Some(decorate) = obj.get("decorate")
-        && let Some(script) = decorate.as_str()
-    {
-        validate_javascript_behavior(
-            file,
-            script,
-            &format!("{location}.decorate"),
-            result,
-            options,
-            true,
-        );
+    if let Some(decorate) = obj.get("decorate") {
+        if let Some(script) = decorate.as_str() {
+            validate_javascript_behavior(
+                file,
+                script,
+                &format!("{location}.decorate"),
+                result,
+                options,
+                true,
+            );
+        } else {
+            behavior_type_error(
+                file,
+                "'decorate' must be a script string",
+                format!("{location}.decorate"),
+                result,
+            );
+        }
     }
 
-    if let Some(shell) = obj.get("shellTransform")
-        && let Some(cmd) = shell.as_str()
-    {
+    if let Some(shell) = obj.get("shellTransform") {
+        let commands: Option<Vec<&str>> = match shell {
+            Value::String(cmd) => Some(vec![cmd.as_str()]),
+            Value::Array(cmds) => cmds.iter().map(Value::as_str).collect(),
+            _ => None,
+        };
+        if commands.is_none() {
+            behavior_type_error(
+                file,
+                "'shellTransform' must be a command string or an array of them",
+                format!("{location}.shellTransform"),
+                result,
+            );
+        }
+        result.add_issue(
+            LintIssue::warning(
+                "W030",
+                "shellTransform runs shell commands on the server's host",
+                file.to_path_buf(),
+            )
+            .with_location(format!("{location}.shellTransform"))
+            .with_suggestion(
+                "Use a decorate script instead; the server refuses both unless started with \
+                 --allow-injection",
+            ),
+        );
         let dangerous_patterns = ["rm ", "rm -", "sudo ", "chmod ", "dd ", "> /dev/"];
-        for pattern in dangerous_patterns {
-            if cmd.contains(pattern) {
-                result.add_issue(
-                    LintIssue::warning(
-                        "W008",
-                        format!("shellTransform contains potentially dangerous command: {pattern}"),
-                        file.to_path_buf(),
-                    )
-                    .with_location(format!("{location}.shellTransform"))
-                    .with_suggestion("Review this command for safety"),
-                );
+        for cmd in commands.into_iter().flatten() {
+            for pattern in dangerous_patterns {
+                if cmd.contains(pattern) {
+                    result.add_issue(
+                        LintIssue::warning(
+                            "W008",
+                            format!(
+                                "shellTransform contains potentially dangerous command: {pattern}"
+                            ),
+                            file.to_path_buf(),
+                        )
+                        .with_location(format!("{location}.shellTransform"))
+                        .with_suggestion("Review this command for safety"),
+                    );
+                }
             }
         }
     }
@@ -1210,7 +3763,7 @@ fn validate_javascript_behavior(
     script: &str,
     location: &str,
     result: &mut LintResult,
-    _options: &LintOptions,
+    options: &LintOptions,
     allow_rhai: bool,
 ) {
     let script_trimmed = script.trim();
@@ -1253,18 +3806,30 @@ fn validate_javascript_behavior(
         );
     }
 
-    #[cfg(feature = "javascript")]
-    {
-        if let Err(e) = js_validator::validate_javascript(script) {
-            result.add_issue(
-                LintIssue::error(
-                    "E028",
-                    format!("JavaScript syntax error: {e}"),
-                    file.to_path_buf(),
-                )
-                .with_location(location),
-            );
+    let embedded = if allow_rhai {
+        EmbeddedScript {
+            kind: ScriptKind::Decorate,
+            engine: "javascript/rhai",
+            code: script,
+        }
+    } else {
+        EmbeddedScript {
+            kind: ScriptKind::Inject,
+            engine: "javascript",
+            code: script,
         }
+    };
+    if !run_script_check(file, &embedded, location, result, options)
+        && let Err(e) = js_validator::validate_javascript(script)
+    {
+        result.add_issue(
+            LintIssue::error(
+                "E028",
+                format!("JavaScript syntax error: {e}"),
+                file.to_path_buf(),
+            )
+            .with_location(location),
+        );
     }
 }
 
@@ -1277,76 +3842,245 @@ fn is_valid_wait_range(wait: &Value) -> bool {
         && obj.get("max").and_then(|v| v.as_u64()).is_some()
 }
 
+fn is_valid_wait_jitter(wait: &Value) -> bool {
+    let Some(obj) = wait.as_object() else {
+        return false;
+    };
+    obj.get("mean").and_then(|v| v.as_u64()).is_some()
+        && obj.get("jitter").and_then(|v| v.as_u64()).is_some()
+}
+
 /// The inner script of a `{"inject": "function(){...}"}` wait (issue #608), or `None` for any
 /// other object — so a malformed wait object still reaches E025 rather than being waved through.
 fn wait_inject_script(wait: &Value) -> Option<&str> {
     wait.as_object()?.get("inject")?.as_str()
 }
 
+/// A behavior that takes one object or an array of them, with each item's location.
+fn behavior_items<'a>(value: &'a Value, location: &str) -> Vec<(&'a Value, String)> {
+    match value {
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| (item, format!("{location}[{idx}]")))
+            .collect(),
+        item => vec![(item, location.to_string())],
+    }
+}
+
+fn behavior_type_error(
+    file: &Path,
+    message: impl Into<String>,
+    location: String,
+    result: &mut LintResult,
+) {
+    result.add_issue(LintIssue::error("E048", message, file.to_path_buf()).with_location(location));
+}
+
+/// Check a copy `from` or lookup `key.from`: a request field name, or `{"query": "name"}`.
+fn check_copy_source(file: &Path, from: &Value, location: String, result: &mut LintResult) {
+    if !from.is_string() && !is_string_map(from) {
+        behavior_type_error(
+            file,
+            "'from' must be a request field name or an object like {\"query\": \"name\"}",
+            location,
+            result,
+        );
+    }
+}
+
+/// Check an extraction `using`: a `method` of regex, jsonpath or xpath, and a string `selector`.
+fn check_extraction(file: &Path, using: &Value, location: String, result: &mut LintResult) {
+    let method = using.get("method").and_then(Value::as_str);
+    if !matches!(method, Some("regex" | "jsonpath" | "xpath")) {
+        result.add_issue(
+            LintIssue::error(
+                "E048",
+                "'using' needs a 'method' of regex, jsonpath or xpath",
+                file.to_path_buf(),
+            )
+            .with_location(format!("{location}.method"))
+            .with_suggestion("e.g. {\"method\": \"regex\", \"selector\": \".*\"}"),
+        );
+    }
+    let selector_location = format!("{location}.selector");
+    match (method, using.get("selector").and_then(Value::as_str)) {
+        (Some("regex"), Some(selector)) => check_regex(file, selector, &selector_location, result),
+        (Some("jsonpath"), Some(selector)) => {
+            check_jsonpath_selector(file, selector, &selector_location, result);
+        }
+        (Some("xpath"), Some(selector)) => {
+            check_xpath_selector(file, selector, &selector_location, result);
+        }
+        (_, Some(_)) => {}
+        (_, None) => behavior_type_error(
+            file,
+            "'using' needs a string 'selector'",
+            selector_location,
+            result,
+        ),
+    }
+}
+
 /// Validate copy behavior.
 fn validate_copy_behavior(file: &Path, copy: &Value, location: &str, result: &mut LintResult) {
-    if let Some(arr) = copy.as_array() {
-        for (idx, item) in arr.iter().enumerate() {
-            if let Some(obj) = item.as_object() {
-                if obj.get("from").is_none() {
-                    result.add_issue(
-                        LintIssue::error(
-                            "E029",
-                            "Copy behavior item missing 'from' field",
-                            file.to_path_buf(),
-                        )
-                        .with_location(format!("{location}[{idx}]")),
-                    );
-                }
-                if obj.get("into").is_none() {
-                    result.add_issue(
-                        LintIssue::error(
-                            "E030",
-                            "Copy behavior item missing 'into' field",
-                            file.to_path_buf(),
-                        )
-                        .with_location(format!("{location}[{idx}]")),
-                    );
-                }
-            }
+    for (item, item_location) in behavior_items(copy, location) {
+        let Some(obj) = item.as_object() else {
+            behavior_type_error(
+                file,
+                "Copy behavior item must be an object",
+                item_location,
+                result,
+            );
+            continue;
+        };
+        match obj.get("from") {
+            Some(from) => check_copy_source(file, from, format!("{item_location}.from"), result),
+            None => result.add_issue(
+                LintIssue::error(
+                    "E029",
+                    "Copy behavior item missing 'from' field",
+                    file.to_path_buf(),
+                )
+                .with_location(item_location.clone()),
+            ),
+        }
+        match obj.get("into") {
+            Some(into) if !into.is_string() => behavior_type_error(
+                file,
+                "'into' must be a token string",
+                format!("{item_location}.into"),
+                result,
+            ),
+            Some(_) => {}
+            None => result.add_issue(
+                LintIssue::error(
+                    "E030",
+                    "Copy behavior item missing 'into' field",
+                    file.to_path_buf(),
+                )
+                .with_location(item_location.clone()),
+            ),
+        }
+        match obj.get("using") {
+            Some(using) => check_extraction(file, using, format!("{item_location}.using"), result),
+            None => behavior_type_error(
+                file,
+                "Copy behavior item missing 'using' field",
+                item_location,
+                result,
+            ),
         }
     }
 }
 
 /// Validate lookup behavior.
 fn validate_lookup_behavior(file: &Path, lookup: &Value, location: &str, result: &mut LintResult) {
-    if let Some(obj) = lookup.as_object() {
-        if obj.get("key").is_none() {
-            result.add_issue(
+    for (item, item_location) in behavior_items(lookup, location) {
+        let Some(obj) = item.as_object() else {
+            behavior_type_error(
+                file,
+                "Lookup behavior item must be an object",
+                item_location,
+                result,
+            );
+            continue;
+        };
+        match obj.get("key") {
+            Some(key) => check_lookup_key(file, key, format!("{item_location}.key"), result),
+            None => result.add_issue(
                 LintIssue::error(
                     "E031",
                     "Lookup behavior missing 'key' field",
                     file.to_path_buf(),
                 )
-                .with_location(location),
-            );
+                .with_location(item_location.clone()),
+            ),
         }
-        if obj.get("fromDataSource").is_none() {
-            result.add_issue(
+        match obj.get("fromDataSource") {
+            Some(source) => check_lookup_source(
+                file,
+                source,
+                format!("{item_location}.fromDataSource"),
+                result,
+            ),
+            None => result.add_issue(
                 LintIssue::error(
                     "E032",
                     "Lookup behavior missing 'fromDataSource' field",
                     file.to_path_buf(),
                 )
-                .with_location(location),
-            );
+                .with_location(item_location.clone()),
+            ),
         }
-        if obj.get("into").is_none() {
-            result.add_issue(
+        match obj.get("into") {
+            Some(into) if !into.is_string() => behavior_type_error(
+                file,
+                "'into' must be a token string",
+                format!("{item_location}.into"),
+                result,
+            ),
+            Some(_) => {}
+            None => result.add_issue(
                 LintIssue::error(
                     "E033",
                     "Lookup behavior missing 'into' field",
                     file.to_path_buf(),
                 )
-                .with_location(location),
+                .with_location(item_location),
+            ),
+        }
+    }
+}
+
+/// A lookup `key` is `{from, using}`, like a copy item without `into`.
+fn check_lookup_key(file: &Path, key: &Value, location: String, result: &mut LintResult) {
+    match (key.get("from"), key.get("using")) {
+        (Some(from), Some(using)) => {
+            check_copy_source(file, from, format!("{location}.from"), result);
+            check_extraction(file, using, format!("{location}.using"), result);
+        }
+        _ => behavior_type_error(
+            file,
+            "Lookup 'key' needs 'from' and 'using'",
+            location,
+            result,
+        ),
+    }
+}
+
+/// A lookup `fromDataSource` is `{"csv": {path, keyColumn, delimiter?}}`.
+fn check_lookup_source(file: &Path, source: &Value, location: String, result: &mut LintResult) {
+    let Some(csv) = source.get("csv") else {
+        behavior_type_error(
+            file,
+            "'fromDataSource' needs a 'csv' data source",
+            location,
+            result,
+        );
+        return;
+    };
+    let csv_location = format!("{location}.csv");
+    for field in ["path", "keyColumn"] {
+        if !csv.get(field).is_some_and(Value::is_string) {
+            behavior_type_error(
+                file,
+                format!("CSV data source needs a string '{field}'"),
+                format!("{csv_location}.{field}"),
+                result,
             );
         }
     }
+    if let Some(delimiter) = csv.get("delimiter")
+        && delimiter.as_str().is_none_or(|d| d.chars().count() != 1)
+    {
+        behavior_type_error(
+            file,
+            "CSV 'delimiter' must be a single character",
+            format!("{csv_location}.delimiter"),
+            result,
+        );
+    }
 }
 
 #[cfg(test)]
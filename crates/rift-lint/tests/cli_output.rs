@@ -1,6 +1,6 @@
 //! Issue #347: rift-lint honors NO_COLOR / non-TTY stdout and emits pure JSON with `-o json`.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 const BIN: &str = env!("CARGO_BIN_EXE_rift-lint");
@@ -131,6 +131,270 @@ fn lint_json_directory_attributes_parse_and_validation_errors() {
     );
 }
 
+#[test]
+fn lint_junit_reports_one_case_per_file_and_a_failure_per_error() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(
+        dir.path().join("clean.json"),
+        r#"{"port":8001,"protocol":"http","stubs":[]}"#,
+    )
+    .expect("write clean");
+    std::fs::write(dir.path().join("broken.json"), r#"{"port":8002"#).expect("write broken");
+
+    let out = Command::new(BIN)
+        .args([dir.path().to_str().unwrap(), "-o", "junit"])
+        .output()
+        .expect("run rift-lint");
+    assert!(!out.status.success(), "lint errors keep the failure exit");
+
+    let stdout = String::from_utf8(out.stdout).expect("utf8");
+    assert!(
+        stdout.starts_with("<?xml"),
+        "junit stdout must be pure XML, got: {stdout:?}"
+    );
+    assert!(!stdout.contains('\x1b'));
+    assert!(stdout.contains(r#"<testsuite name="rift-lint" tests="2" failures="1""#));
+    assert!(
+        stdout.contains("clean.json\"/>"),
+        "clean file is a passing case"
+    );
+    assert_eq!(stdout.matches("<failure type=\"E001\"").count(), 1);
+}
+
+#[test]
+fn lint_riftlint_toml_overrides_rule_severity() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::create_dir(dir.path().join(".git")).expect("mkdir .git");
+    let imposters = dir.path().join("imposters");
+    std::fs::create_dir(&imposters).expect("mkdir imposters");
+    // Missing port: E003.
+    std::fs::write(
+        imposters.join("no-port.json"),
+        r#"{"protocol":"http","stubs":[]}"#,
+    )
+    .expect("write imposter");
+
+    let run = || {
+        Command::new(BIN)
+            .args([imposters.to_str().unwrap(), "-o", "json"])
+            .output()
+            .expect("run rift-lint")
+    };
+    assert!(!run().status.success(), "E003 fails without a config");
+
+    std::fs::write(
+        dir.path().join(".riftlint.toml"),
+        "[rules]\nE003 = \"warning\"\n",
+    )
+    .expect("write config");
+    let out = run();
+    assert!(out.status.success(), "E003 downgraded to a warning passes");
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).expect("json");
+    assert_eq!(report["errors"].as_u64(), Some(0));
+    assert_eq!(report["warnings"].as_u64(), Some(1));
+
+    std::fs::write(
+        dir.path().join(".riftlint.toml"),
+        "strict = true\n[rules]\nE003 = \"warning\"\n",
+    )
+    .expect("write config");
+    assert!(
+        !run().status.success(),
+        "strict in the config fails on warnings"
+    );
+
+    std::fs::write(dir.path().join(".riftlint.toml"), "[rules]\nE3 = \"off\"\n")
+        .expect("write config");
+    let out = run();
+    assert_eq!(
+        out.status.code(),
+        Some(2),
+        "a broken config is a usage error"
+    );
+    assert!(String::from_utf8_lossy(&out.stderr).contains("'E3' is not a rule code"));
+}
+
+#[test]
+fn lint_riftlint_toml_allows_proxy_hosts() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::create_dir(dir.path().join(".git")).expect("mkdir .git");
+    std::fs::write(
+        dir.path().join("proxy.json"),
+        r#"{"port":4545,"protocol":"http","stubs":[{"responses":[{"proxy":{"to":"https://api.example.com"}}]}]}"#,
+    )
+    .expect("write imposter");
+    let run = || {
+        let out = Command::new(BIN)
+            .args([dir.path().to_str().unwrap(), "-o", "json"])
+            .output()
+            .expect("run rift-lint");
+        let report: serde_json::Value = serde_json::from_slice(&out.stdout).expect("json");
+        report["issues"]
+            .as_array()
+            .expect("issues")
+            .iter()
+            .map(|issue| issue["code"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(run(), ["W033"]);
+
+    std::fs::write(
+        dir.path().join(".riftlint.toml"),
+        "allowed-proxy-hosts = [\"api.example.com\"]\n",
+    )
+    .expect("write config");
+    assert!(run().is_empty());
+}
+
+#[test]
+fn lint_renders_an_ejs_config_file() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(
+        dir.path().join("imposter.json"),
+        r#"{"port":8000,"protocol":"http","stubs":[]}"#,
+    )
+    .expect("write include");
+    let config = dir.path().join("imposters.ejs");
+    std::fs::write(
+        &config,
+        r#"{"imposters":[<%- include('imposter.json') %>]}"#,
+    )
+    .expect("write config");
+
+    let run = |extra: &[&str]| {
+        Command::new(BIN)
+            .arg(&config)
+            .args(["-o", "json"])
+            .args(extra)
+            .output()
+            .expect("run rift-lint")
+    };
+    let out = run(&[]);
+    assert!(out.status.success(), "the rendered config is valid");
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).expect("json");
+    assert_eq!(report["files_checked"].as_u64(), Some(1));
+
+    assert!(
+        !run(&["--no-parse"]).status.success(),
+        "unrendered, the template isn't JSON"
+    );
+}
+
+/// An admin API answering `GET /config` and `GET /imposters` with canned bodies, on a free port.
+fn serve_admin_api(config: &'static str, imposters: &'static str) -> String {
+    use std::io::{BufRead, BufReader, Write};
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let mut reader = BufReader::new(&stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).expect("request line");
+            let mut line = String::new();
+            while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+                line.clear();
+            }
+            let body = if request_line.starts_with("GET /config") {
+                config
+            } else {
+                imposters
+            };
+            let mut stream = &stream;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .expect("respond");
+        }
+    });
+    url
+}
+
+#[test]
+fn lint_against_a_running_server() {
+    let url = serve_admin_api(
+        r#"{"version":"0.0.1","options":{"port":2525,"allowInjection":true}}"#,
+        r#"{"imposters":[{"port":8000,"protocol":"http","name":"other","stubs":[]}]}"#,
+    );
+    let file = write_tmp();
+    let out = Command::new(BIN)
+        .arg(&file)
+        .args(["-o", "json", "--against", &url])
+        .output()
+        .expect("run rift-lint");
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).expect("json");
+    let codes: Vec<&str> = report["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|issue| issue["code"].as_str())
+        .collect();
+    assert!(codes.contains(&"W024"), "older server: {codes:?}");
+
+    let out = Command::new(BIN)
+        .arg(&file)
+        .args(["--against", "http://127.0.0.1:1"])
+        .output()
+        .expect("run rift-lint");
+    let _ = std::fs::remove_file(&file);
+    assert_eq!(
+        out.status.code(),
+        Some(2),
+        "an unreachable server is a usage error"
+    );
+}
+
+#[test]
+fn lint_baseline_fails_only_on_new_issues() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let imposters = dir.path().join("imposters");
+    std::fs::create_dir(&imposters).expect("mkdir imposters");
+    let baseline = dir.path().join("baseline.json");
+    // Missing port: E003.
+    std::fs::write(
+        imposters.join("old.json"),
+        r#"{"protocol":"http","stubs":[]}"#,
+    )
+    .expect("write imposter");
+
+    let run = |extra: &[&str]| {
+        Command::new(BIN)
+            .args([imposters.to_str().unwrap(), "-o", "json", "--baseline"])
+            .arg(&baseline)
+            .args(extra)
+            .output()
+            .expect("run rift-lint")
+    };
+    assert_eq!(
+        run(&[]).status.code(),
+        Some(2),
+        "a missing baseline is an error"
+    );
+    assert!(run(&["--update-baseline"]).status.success());
+    assert!(baseline.exists());
+
+    let out = run(&[]);
+    assert!(out.status.success(), "baselined issues don't fail");
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).expect("json");
+    assert_eq!(report["errors"].as_u64(), Some(0));
+
+    std::fs::write(
+        imposters.join("new.json"),
+        r#"{"protocol":"http","stubs":[]}"#,
+    )
+    .expect("write imposter");
+    let out = run(&[]);
+    assert!(!out.status.success(), "a new issue fails");
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).expect("json");
+    assert_eq!(report["errors"].as_u64(), Some(1));
+    assert!(
+        report["issues"][0]["file"]
+            .as_str()
+            .is_some_and(|f| f.ends_with("new.json"))
+    );
+}
+
 // AC1: NO_COLOR is honored regardless of TTY.
 #[test]
 fn lint_no_color_env_disables_ansi() {
@@ -147,3 +411,141 @@ fn lint_no_color_env_disables_ansi() {
     );
     let _ = std::fs::remove_file(f);
 }
+
+#[test]
+fn lint_jobs_keeps_the_report_the_same() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    for n in 0..20 {
+        std::fs::write(
+            dir.path().join(format!("{n:02}.json")),
+            format!(r#"{{"port":{},"protocol":"http","stubs":[{{"responses":[{{"is":{{"statusCode":9}}}}]}}]}}"#, 4000 + n),
+        )
+        .expect("write imposter");
+    }
+    // The report without its timings, which differ from run to run
+    let run = |jobs: &str| {
+        let out = Command::new(BIN)
+            .args([dir.path().to_str().unwrap(), "-o", "json", "--jobs", jobs])
+            .output()
+            .expect("run rift-lint");
+        let mut report: serde_json::Value = serde_json::from_slice(&out.stdout).expect("json");
+        for file in report["files"].as_array_mut().expect("files") {
+            file.as_object_mut().expect("file").remove("duration_ms");
+        }
+        report
+    };
+    let one = run("1");
+    assert_eq!(one["errors"].as_u64(), Some(20));
+    assert_eq!(one["files"].as_array().map(Vec::len), Some(20));
+    assert_eq!(run("8"), one);
+
+    let out = Command::new(BIN)
+        .args([dir.path().to_str().unwrap(), "--jobs", "0"])
+        .output()
+        .expect("run rift-lint");
+    assert_eq!(out.status.code(), Some(2), "--jobs 0 is a usage error");
+}
+
+#[test]
+fn lint_exit_codes_tell_errors_from_warnings() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::create_dir(dir.path().join(".git")).expect("mkdir .git");
+    // One W002 warning (a stub without responses), no errors
+    let warnings = dir.path().join("warnings.json");
+    std::fs::write(
+        &warnings,
+        r#"{"port":4545,"protocol":"http","stubs":[{"responses":[]}]}"#,
+    )
+    .expect("write imposter");
+    let errors = dir.path().join("errors.json");
+    std::fs::write(&errors, r#"{"protocol":"http","stubs":[]}"#).expect("write imposter");
+    let code = |file: &Path, extra: &[&str]| {
+        Command::new(BIN)
+            .arg(file)
+            .args(extra)
+            .output()
+            .expect("run rift-lint")
+            .status
+            .code()
+    };
+
+    assert_eq!(code(&warnings, &[]), Some(0), "warnings pass by default");
+    assert_eq!(code(&warnings, &["--max-warnings", "1"]), Some(0));
+    assert_eq!(code(&warnings, &["--max-warnings", "0"]), Some(3));
+    assert_eq!(code(&warnings, &["--strict"]), Some(3));
+    assert_eq!(code(&errors, &["--max-warnings", "5"]), Some(1));
+
+    std::fs::write(dir.path().join(".riftlint.toml"), "max-warnings = 0\n").expect("config");
+    assert_eq!(
+        code(&warnings, &[]),
+        Some(3),
+        "max-warnings from the config"
+    );
+    assert_eq!(
+        code(&warnings, &["--max-warnings", "9"]),
+        Some(0),
+        "the flag wins"
+    );
+}
+
+#[test]
+fn lint_json_report_is_versioned() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::create_dir(dir.path().join(".git")).expect("mkdir .git");
+    std::fs::write(
+        dir.path().join(".riftlint.toml"),
+        "[rules]\nW002 = \"error\"\n",
+    )
+    .expect("config");
+    let file = dir.path().join("imposter.json");
+    std::fs::write(
+        &file,
+        r#"{"port":4545,"protocol":"http","stubs":[{"responses":[]}]}"#,
+    )
+    .expect("write imposter");
+
+    let out = Command::new(BIN)
+        .args([file.to_str().unwrap(), "-o", "json", "--max-warnings", "4"])
+        .output()
+        .expect("run rift-lint");
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).expect("json");
+    assert_eq!(report["schema_version"], 1);
+    assert_eq!(report["tool"]["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(report["errors"], 1);
+    assert_eq!(report["rules"], serde_json::json!({"W002": 1}));
+    let files = report["files"].as_array().expect("files");
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0]["path"], file.to_str().unwrap());
+    assert_eq!(files[0]["errors"], 1);
+    assert!(files[0]["duration_ms"].as_f64().is_some());
+    assert_eq!(report["config"]["max_warnings"], 4);
+    assert_eq!(report["config"]["rules"]["W002"], "error");
+    assert!(report["config"]["config_file"].as_str().is_some());
+}
+
+#[test]
+fn lint_fix_drops_exact_duplicate_stubs() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let file = dir.path().join("recorded.json");
+    let stub =
+        r#"{"predicates":[{"equals":{"path":"/users"}}],"responses":[{"is":{"statusCode":200}}]}"#;
+    let reordered = r#"{"responses":[{"is":{"statusCode":200}}],"predicates":[{"equals":{"path":"/users"}}],"_links":{}}"#;
+    std::fs::write(
+        &file,
+        format!(r#"{{"imposters":[{{"port":4545,"protocol":"http","stubs":[{stub},{stub},{reordered}]}}]}}"#),
+    )
+    .expect("write imposter");
+
+    let out = Command::new(BIN)
+        .arg(&file)
+        .arg("--fix")
+        .output()
+        .expect("run rift-lint");
+    assert!(String::from_utf8_lossy(&out.stdout).contains("Removed 1 duplicate stub(s)"));
+
+    let fixed: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&file).expect("read")).expect("json");
+    let stubs = fixed["imposters"][0]["stubs"].as_array().expect("stubs");
+    assert_eq!(stubs.len(), 2, "only the exact duplicate is dropped");
+    assert!(stubs[1].get("_links").is_some());
+}
@@ -1,7 +1,8 @@
 use rift_lint::{
-    LintOptions, LintResult, Severity, lint_directory, lint_file, lint_json, lint_value,
-    validate_behavior, validate_imposter, validate_is_response, validate_predicate,
-    validate_proxy_response, validate_response, validate_stub,
+    Baseline, ConfigError, EmbeddedScript, LintConfig, LintIssue, LintOptions, LintResult,
+    LintRule, RuleLevel, ServerState, Severity, lint_directory, lint_file, lint_files, lint_json,
+    lint_value, render_ejs, validate_behavior, validate_imposter, validate_is_response,
+    validate_predicate, validate_proxy_response, validate_response, validate_stub,
 };
 use serde_json::{Value, json};
 use std::path::Path;
@@ -234,6 +235,89 @@ fn e013_invalid_regex_in_matches() {
     assert!(has_code(&r, "E013"));
 }
 
+#[test]
+fn e013_invalid_regex_in_nested_query() {
+    let pred = json!({ "matches": { "query": { "q": "(unclosed" } } });
+    let mut r = LintResult::new();
+    validate_predicate(path(), &pred, "loc", &mut r, &opts());
+    let issue = r
+        .issues
+        .iter()
+        .find(|i| i.code == "E013")
+        .expect("E013 must fire");
+    assert_eq!(issue.location.as_deref(), Some("loc.matches.query.q"));
+}
+
+#[test]
+fn e055_jsonpath_selector_does_not_compile() {
+    let pred =
+        json!({ "equals": { "body": "x" }, "jsonpath": { "selector": "$.items[?@.price <]" } });
+    let mut r = LintResult::new();
+    validate_predicate(path(), &pred, "loc", &mut r, &opts());
+    let issue = r
+        .issues
+        .iter()
+        .find(|i| i.code == "E055")
+        .expect("E055 must fire");
+    assert_eq!(issue.location.as_deref(), Some("loc.jsonpath.selector"));
+}
+
+#[test]
+fn e055_not_fired_for_bare_or_sliced_selectors() {
+    for selector in ["searchValue", "user.name", "[0].id", "$.items[:2]", "$..id"] {
+        let pred = json!({ "equals": { "body": "x" }, "jsonpath": { "selector": selector } });
+        let mut r = LintResult::new();
+        validate_predicate(path(), &pred, "loc", &mut r, &opts());
+        assert!(!has_code(&r, "E055"), "{selector}: unexpected E055");
+    }
+}
+
+#[test]
+fn e056_xpath_selector_does_not_compile() {
+    for selector in ["//order[", ""] {
+        let pred = json!({ "equals": { "body": "x" }, "xpath": { "selector": selector } });
+        let mut r = LintResult::new();
+        validate_predicate(path(), &pred, "loc", &mut r, &opts());
+        assert!(
+            has_code(&r, "E056"),
+            "{selector:?}: expected E056, got {:?}",
+            codes(&r)
+        );
+    }
+    let pred = json!({ "equals": { "body": "1" }, "xpath": { "selector": "//order/@id" } });
+    let mut r = LintResult::new();
+    validate_predicate(path(), &pred, "loc", &mut r, &opts());
+    assert!(!has_code(&r, "E056"), "unexpected E056: {:?}", codes(&r));
+}
+
+#[test]
+fn selectors_compile_in_behaviors_and_generators() {
+    let behavior = json!({ "copy": [
+        { "from": "body", "into": "${A}", "using": { "method": "jsonpath", "selector": "$.[" } },
+        { "from": "body", "into": "${B}", "using": { "method": "xpath", "selector": "//a[" } },
+        { "from": "path", "into": "${C}", "using": { "method": "regex", "selector": "(" } }
+    ] });
+    let mut r = LintResult::new();
+    validate_behavior(path(), &behavior, "loc", &mut r, &opts());
+    assert_eq!(codes(&r), ["E010", "E056", "E013"]);
+
+    let proxy = json!({
+        "to": "http://localhost:8080",
+        "predicateGenerators": [{ "matches": { "body": true }, "xpath": { "selector": "//a[" } }]
+    });
+    let mut r = LintResult::new();
+    validate_proxy_response(path(), &proxy, "loc", &mut r);
+    let issue = r
+        .issues
+        .iter()
+        .find(|i| i.code == "E056")
+        .expect("E056 must fire");
+    assert_eq!(
+        issue.location.as_deref(),
+        Some("loc.predicateGenerators[0].xpath.selector")
+    );
+}
+
 #[test]
 fn e013_not_fired_for_valid_regex() {
     let pred = json!({ "matches": { "path": "^/api/.*" } });
@@ -801,6 +885,122 @@ fn e035_via_response_underscore_behaviors_object() {
     );
 }
 
+#[test]
+fn ordered_underscore_behaviors_array_dispatches_each_entry() {
+    let resp = json!({
+        "is": { "statusCode": 200 },
+        "_behaviors": [{ "decorate": "x" }, { "repeat": 0 }, 500]
+    });
+    let mut r = LintResult::new();
+    validate_response(
+        path(),
+        &resp,
+        "loc",
+        &mut r,
+        &opts(),
+        &serde_json::Value::Null,
+    );
+    assert!(
+        has_code(&r, "E035"),
+        "E035 must fire for an ordered entry, got {:?}",
+        codes(&r)
+    );
+    assert!(
+        has_code(&r, "E043"),
+        "E043 must fire for a non-object entry, got {:?}",
+        codes(&r)
+    );
+}
+
+#[test]
+fn default_behaviors_are_validated_and_reject_repeat() {
+    let v = json!({
+        "port": 3000,
+        "protocol": "http",
+        "defaultBehaviors": [{ "wait": 50 }, { "repeat": 0 }, "decorate"],
+        "stubs": []
+    });
+    let mut r = LintResult::new();
+    validate_imposter(path(), &v, &mut r, &opts());
+    for code in ["E035", "E043", "E044"] {
+        assert!(has_code(&r, code), "expected {code}, got {:?}", codes(&r));
+    }
+
+    let v = json!({
+        "port": 3000,
+        "protocol": "http",
+        "defaultBehaviors": { "wait": 50 },
+        "stubs": []
+    });
+    let mut r = LintResult::new();
+    validate_imposter(path(), &v, &mut r, &opts());
+    assert!(r.issues.is_empty(), "got {:?}", codes(&r));
+}
+
+#[test]
+fn rift_fetch_entries_need_exactly_one_target() {
+    let resp = json!({
+        "is": { "body": "{{ fetch.user.body }}" },
+        "_rift": { "fetch": {
+            "user": { "imposter": 4546, "path": "/users/1" },
+            "both": { "imposter": 4546, "url": "http://localhost:4546" },
+            "neither": { "path": "/users/1" }
+        }}
+    });
+    let mut r = LintResult::new();
+    validate_response(
+        path(),
+        &resp,
+        "loc",
+        &mut r,
+        &opts(),
+        &serde_json::Value::Null,
+    );
+    let e045: Vec<_> = r.issues.iter().filter(|i| i.code == "E045").collect();
+    assert_eq!(e045.len(), 2, "got {:?}", codes(&r));
+    assert!(e045.iter().all(|i| !i.message.contains("'user'")));
+}
+
+#[test]
+fn rift_fetch_url_cannot_template_its_host() {
+    let resp = json!({
+        "is": { "body": "{{ fetch.a.body }}" },
+        "_rift": { "fetch": {
+            "path": { "url": "https://api.example.com/users/${request.query.id}" },
+            "suffix": { "url": "https://api.example.com${request.path}" },
+            "host": { "url": "http://${request.query.host}/users" },
+            "whole": { "url": "${request.query.target}" },
+            "partial": { "url": "https://${request.query.env}.example.com/users" }
+        }}
+    });
+    let mut r = LintResult::new();
+    validate_response(
+        path(),
+        &resp,
+        "loc",
+        &mut r,
+        &opts(),
+        &serde_json::Value::Null,
+    );
+    let mut flagged: Vec<_> = r
+        .issues
+        .iter()
+        .filter(|i| i.code == "E045")
+        .filter_map(|i| i.location.clone())
+        .collect();
+    flagged.sort();
+    assert_eq!(
+        flagged,
+        [
+            "loc._rift.fetch.host.url",
+            "loc._rift.fetch.partial.url",
+            "loc._rift.fetch.whole.url"
+        ],
+        "got {:?}",
+        codes(&r)
+    );
+}
+
 #[test]
 fn behaviors_array_format_still_dispatches() {
     // Rift also serializes responses with `behaviors: [...]` (array, no underscore)
@@ -1254,3 +1454,1928 @@ fn e042_is_a_warning_not_an_error() {
         "E042 must be a hint, not a hard error"
     );
 }
+
+// ─── Full schema: unknown fields (W012/W013) and field types (E046–E050) ─────
+
+#[test]
+fn w012_unknown_stub_field_suggests_the_close_one() {
+    let v = make_imposter(json!([{
+        "predicate": [{ "equals": { "path": "/" } }],
+        "responses": [{ "is": { "statusCode": 200 } }]
+    }]));
+    let mut r = LintResult::new();
+    validate_imposter(path(), &v, &mut r, &opts());
+    let issue = r
+        .issues
+        .iter()
+        .find(|i| i.code == "W012")
+        .expect("W012 must fire");
+    assert_eq!(issue.location.as_deref(), Some("stubs[0].predicate"));
+    assert_eq!(
+        issue.suggestion.as_deref(),
+        Some("Did you mean 'predicates'?")
+    );
+}
+
+#[test]
+fn w012_unknown_fields_at_every_level() {
+    let v = json!({
+        "port": 3000,
+        "protocol": "http",
+        "recordRequest": true,
+        "stubs": [{
+            "responses": [{
+                "is": { "statusCode": 200, "header": {} },
+                "_behaviours": { "wait": 10 }
+            }, {
+                "proxy": { "to": "http://localhost:8080", "predicateGenerator": [] }
+            }, {
+                "is": { "statusCode": 200 },
+                "_behaviors": { "delay": 10 }
+            }]
+        }]
+    });
+    let mut r = LintResult::new();
+    validate_imposter(path(), &v, &mut r, &opts());
+    let mut locations: Vec<_> = r
+        .issues
+        .iter()
+        .filter(|i| i.code == "W012")
+        .filter_map(|i| i.location.as_deref())
+        .collect();
+    locations.sort_unstable();
+    assert_eq!(
+        locations,
+        [
+            "recordRequest",
+            "stubs[0].responses[0]._behaviours",
+            "stubs[0].responses[0].is.header",
+            "stubs[0].responses[1].proxy.predicateGenerator",
+            "stubs[0].responses[2]._behaviors.delay",
+        ]
+    );
+}
+
+#[test]
+fn w012_not_fired_for_the_full_schema() {
+    let v = json!({
+        "port": 3000,
+        "protocol": "http",
+        "name": "orders",
+        "recordRequests": true,
+        "defaultResponse": { "statusCode": 404, "headers": {}, "body": "" },
+        "allowCORS": true,
+        "cors": { "allowOrigins": ["https://app.example.com"] },
+        "stubs": [{
+            "scenarioName": "checkout",
+            "id": "create-order",
+            "predicates": [{
+                "equals": { "method": "POST", "path": "/orders" },
+                "caseSensitive": true,
+                "keyCaseSensitive": false,
+                "except": "\\d+"
+            }],
+            "responses": [{
+                "is": { "statusCode": 201, "headers": {}, "body": "{}", "_mode": "text" },
+                "_behaviors": { "wait": 10, "repeat": 2 }
+            }, {
+                "proxy": {
+                    "to": "http://localhost:8080",
+                    "mode": "proxyOnce",
+                    "predicateGenerators": [{ "matches": { "path": true } }],
+                    "addWaitBehavior": true,
+                    "recording": { "collapseDuplicates": true }
+                }
+            }]
+        }]
+    });
+    let mut r = LintResult::new();
+    validate_imposter(path(), &v, &mut r, &opts());
+    assert!(r.issues.is_empty(), "unexpected issues: {:?}", codes(&r));
+}
+
+#[test]
+fn w013_unknown_request_field_in_predicate() {
+    let pred = json!({ "equals": { "paht": "/orders" } });
+    let mut r = LintResult::new();
+    validate_predicate(path(), &pred, "loc", &mut r, &opts());
+    let issue = r
+        .issues
+        .iter()
+        .find(|i| i.code == "W013")
+        .expect("W013 must fire");
+    assert_eq!(issue.suggestion.as_deref(), Some("Did you mean 'path'?"));
+}
+
+#[test]
+fn e046_predicate_operator_value_types() {
+    for pred in [
+        json!({ "equals": "/orders" }),
+        json!({ "and": { "equals": { "path": "/" } } }),
+        json!({ "inject": true }),
+        json!({ "exists": { "body": "yes" } }),
+    ] {
+        let mut r = LintResult::new();
+        validate_predicate(path(), &pred, "loc", &mut r, &opts());
+        assert!(
+            has_code(&r, "E046"),
+            "{pred}: expected E046, got {:?}",
+            codes(&r)
+        );
+    }
+}
+
+#[test]
+fn e046_not_fired_for_nested_exists() {
+    let pred = json!({ "exists": { "query": { "q": true }, "body": false } });
+    let mut r = LintResult::new();
+    validate_predicate(path(), &pred, "loc", &mut r, &opts());
+    assert!(r.issues.is_empty(), "unexpected issues: {:?}", codes(&r));
+}
+
+#[test]
+fn e047_predicate_parameter_types() {
+    let pred = json!({ "equals": { "path": "/" }, "caseSensitive": "yes" });
+    let mut r = LintResult::new();
+    validate_predicate(path(), &pred, "loc", &mut r, &opts());
+    assert!(has_code(&r, "E047"), "expected E047, got {:?}", codes(&r));
+}
+
+#[test]
+fn e009_not_fired_for_key_case_sensitive() {
+    let pred = json!({ "equals": { "headers": { "X-Id": "1" } }, "keyCaseSensitive": true });
+    let mut r = LintResult::new();
+    validate_predicate(path(), &pred, "loc", &mut r, &opts());
+    assert!(r.issues.is_empty(), "unexpected issues: {:?}", codes(&r));
+}
+
+#[test]
+fn e013_invalid_except_regex() {
+    let pred = json!({ "equals": { "path": "/" }, "except": "[" });
+    let mut r = LintResult::new();
+    validate_predicate(path(), &pred, "loc", &mut r, &opts());
+    assert!(has_code(&r, "E013"));
+}
+
+#[test]
+fn e048_behavior_types() {
+    for behavior in [
+        json!({ "decorate": 1 }),
+        json!({ "shellTransform": ["ok", 2] }),
+        json!({ "copy": { "from": "path", "into": "${P}", "using": { "method": "glob", "selector": "*" } } }),
+        json!({ "copy": [{ "from": 1, "into": "${P}", "using": { "method": "regex", "selector": ".*" } }] }),
+        json!({ "lookup": {
+            "key": { "from": "path", "using": { "method": "regex", "selector": ".*" } },
+            "fromDataSource": { "csv": { "path": "data.csv", "keyColumn": "id", "delimiter": ";;" } },
+            "into": "${ROW}"
+        } }),
+    ] {
+        let mut r = LintResult::new();
+        validate_behavior(path(), &behavior, "loc", &mut r, &opts());
+        assert!(
+            has_code(&r, "E048"),
+            "{behavior}: expected E048, got {:?}",
+            codes(&r)
+        );
+    }
+}
+
+#[test]
+fn e048_not_fired_for_valid_copy_and_lookup() {
+    let behavior = json!({
+        "copy": { "from": { "query": "id" }, "into": "${ID}", "using": { "method": "regex", "selector": "\\d+" } },
+        "lookup": [{
+            "key": { "from": "path", "using": { "method": "jsonpath", "selector": "$.id" } },
+            "fromDataSource": { "csv": { "path": "data.csv", "keyColumn": "id", "delimiter": ";" } },
+            "into": "${ROW}"
+        }],
+        "shellTransform": ["transform-a", "transform-b"]
+    });
+    let mut r = LintResult::new();
+    validate_behavior(path(), &behavior, "loc", &mut r, &opts());
+    // Only the shellTransform security warning
+    assert_eq!(codes(&r), ["W030"]);
+}
+
+#[test]
+fn e049_proxy_field_types() {
+    let proxy = json!({
+        "to": "http://localhost:8080",
+        "addWaitBehavior": "yes",
+        "recording": { "maxResponsesPerStub": -1 }
+    });
+    let mut r = LintResult::new();
+    validate_proxy_response(path(), &proxy, "loc", &mut r);
+    let locations: Vec<_> = r
+        .issues
+        .iter()
+        .filter(|i| i.code == "E049")
+        .filter_map(|i| i.location.as_deref())
+        .collect();
+    assert_eq!(
+        locations,
+        ["loc.addWaitBehavior", "loc.recording.maxResponsesPerStub"]
+    );
+}
+
+#[test]
+fn e049_predicate_generator_without_matches_or_inject() {
+    let proxy = json!({
+        "to": "http://localhost:8080",
+        "predicateGenerators": [{ "caseSensitive": true }]
+    });
+    let mut r = LintResult::new();
+    validate_proxy_response(path(), &proxy, "loc", &mut r);
+    assert!(has_code(&r, "E049"), "expected E049, got {:?}", codes(&r));
+}
+
+#[test]
+fn e049_unknown_predicate_operator_in_generator() {
+    let proxy = json!({
+        "to": "http://localhost:8080",
+        "predicateGenerators": [{ "matches": { "path": true }, "predicateOperator": "like" }]
+    });
+    let mut r = LintResult::new();
+    validate_proxy_response(path(), &proxy, "loc", &mut r);
+    assert!(has_code(&r, "E049"), "expected E049, got {:?}", codes(&r));
+}
+
+#[test]
+fn w013_generator_matches_on_unsupported_field() {
+    let proxy = json!({
+        "to": "http://localhost:8080",
+        "predicateGenerators": [{ "matches": { "form": true } }]
+    });
+    let mut r = LintResult::new();
+    validate_proxy_response(path(), &proxy, "loc", &mut r);
+    assert!(has_code(&r, "W013"), "expected W013, got {:?}", codes(&r));
+}
+
+#[test]
+fn e050_imposter_stub_and_response_types() {
+    let v = json!({
+        "port": 3000,
+        "protocol": "http",
+        "recordRequests": "true",
+        "defaultResponse": { "statusCode": 404, "bodyFile": 7 },
+        "stubs": [{
+            "scenarioName": 1,
+            "delayRange": [{ "min": 10 }],
+            "responses": [{ "is": { "statusCode": 200, "_mode": "hex" } }]
+        }]
+    });
+    let mut r = LintResult::new();
+    validate_imposter(path(), &v, &mut r, &opts());
+    let mut locations: Vec<_> = r
+        .issues
+        .iter()
+        .filter(|i| i.code == "E050")
+        .filter_map(|i| i.location.as_deref())
+        .collect();
+    locations.sort_unstable();
+    assert_eq!(
+        locations,
+        [
+            "defaultResponse.bodyFile",
+            "recordRequests",
+            "stubs[0].delayRange[0]",
+            "stubs[0].responses[0].is._mode",
+            "stubs[0].scenarioName",
+        ]
+    );
+}
+
+#[test]
+fn e050_default_forward_must_be_http() {
+    let mut v = make_imposter(json!([minimal_stub()]));
+    v["defaultForward"] = json!("localhost:8080");
+    let mut r = LintResult::new();
+    validate_imposter(path(), &v, &mut r, &opts());
+    assert!(has_code(&r, "E050"), "expected E050, got {:?}", codes(&r));
+}
+
+#[test]
+fn e014_not_fired_for_flat_response() {
+    let resp = json!({ "statusCode": 204, "headers": { "X-Id": "1" } });
+    let mut r = LintResult::new();
+    validate_response(path(), &resp, "loc", &mut r, &opts(), &Value::Null);
+    assert!(r.issues.is_empty(), "unexpected issues: {:?}", codes(&r));
+}
+
+#[test]
+fn rules_are_validated_like_predicates() {
+    let stub = json!({
+        "rules": [{ "fuzzy": { "path": "/" } }],
+        "responses": [{ "is": { "statusCode": 200 } }]
+    });
+    let mut r = LintResult::new();
+    validate_stub(path(), &stub, 0, &mut r, &opts(), &Value::Null);
+    let issue = r
+        .issues
+        .iter()
+        .find(|i| i.code == "E009")
+        .expect("E009 must fire");
+    assert_eq!(issue.location.as_deref(), Some("stubs[0].rules[0]"));
+}
+
+// ─── _rift extension blocks (E051–E054, W014–W016) ──────────────────────────
+
+fn rift_imposter(rift: Value) -> Value {
+    let mut v = make_imposter(json!([minimal_stub()]));
+    v["_rift"] = rift;
+    v
+}
+
+fn rift_response(rift: Value) -> LintResult {
+    let resp = json!({ "is": { "statusCode": 200 }, "_rift": rift });
+    let mut r = LintResult::new();
+    validate_response(path(), &resp, "loc", &mut r, &opts(), &Value::Null);
+    r
+}
+
+#[test]
+fn e051_fault_probability_out_of_range() {
+    for fault in [
+        json!({ "latency": { "probability": 1.5, "ms": 100 } }),
+        json!({ "error": { "probability": -0.1 } }),
+        json!({ "error": { "probability": "half" } }),
+    ] {
+        let r = rift_response(json!({ "fault": fault }));
+        assert!(
+            has_code(&r, "E051"),
+            "{fault}: expected E051, got {:?}",
+            codes(&r)
+        );
+    }
+}
+
+#[test]
+fn e052_unknown_or_removed_script_engine() {
+    for script in [
+        json!({ "engine": "python", "code": "x" }),
+        json!({ "engine": "lua", "code": "return 1" }),
+        json!({ "file": "respond.lua" }),
+    ] {
+        let r = rift_response(json!({ "script": script }));
+        assert!(
+            has_code(&r, "E052"),
+            "{script}: expected E052, got {:?}",
+            codes(&r)
+        );
+    }
+
+    let v = rift_imposter(json!({ "scriptEngine": { "defaultEngine": "ruby" } }));
+    let mut r = LintResult::new();
+    validate_imposter(path(), &v, &mut r, &opts());
+    assert!(has_code(&r, "E052"), "expected E052, got {:?}", codes(&r));
+}
+
+#[test]
+fn e052_not_fired_for_js_alias() {
+    let r = rift_response(json!({
+        "script": { "engine": "js", "code": "function respond(ctx) { return {}; }" }
+    }));
+    assert!(!has_code(&r, "E052"), "unexpected E052: {:?}", codes(&r));
+}
+
+#[test]
+fn e053_redis_backend_needs_a_url() {
+    for flow_state in [
+        json!({ "backend": "redis" }),
+        json!({ "backend": "redis", "redis": { "poolSize": 4 } }),
+        json!({ "backend": "redis", "redis": { "url": "localhost:6379" } }),
+        json!({ "backend": "redis", "redis": { "url": "redis://r:6379", "poolSize": 0 } }),
+    ] {
+        let v = rift_imposter(json!({ "flowState": flow_state }));
+        let mut r = LintResult::new();
+        validate_imposter(path(), &v, &mut r, &opts());
+        assert!(
+            has_code(&r, "E053"),
+            "{flow_state}: expected E053, got {:?}",
+            codes(&r)
+        );
+    }
+}
+
+#[test]
+fn e053_flow_id_source_and_ttl() {
+    for flow_state in [
+        json!({ "flowIdSource": "cookie:session" }),
+        json!({ "flowIdSource": "header:" }),
+        json!({ "ttlSeconds": 0 }),
+    ] {
+        let v = rift_imposter(json!({ "flowState": flow_state }));
+        let mut r = LintResult::new();
+        validate_imposter(path(), &v, &mut r, &opts());
+        assert!(
+            has_code(&r, "E053"),
+            "{flow_state}: expected E053, got {:?}",
+            codes(&r)
+        );
+    }
+}
+
+#[test]
+fn complete_rift_config_is_clean() {
+    let v = rift_imposter(json!({
+        "flowState": {
+            "backend": "redis",
+            "ttlSeconds": 600,
+            "flowIdSource": "header:X-Flow-Id",
+            "redis": { "url": "redis://localhost:6379", "poolSize": 4, "keyPrefix": "t:" }
+        },
+        "metrics": { "enabled": true, "port": 9090 },
+        "proxy": { "upstream": { "host": "api", "port": 8080 } },
+        "scriptEngine": {
+            "defaultEngine": "javascript",
+            "timeoutMs": 500,
+            "maxOperations": 1000000,
+            "maxMemoryBytes": 1048576,
+            "libraries": { "auth": { "code": "fn bearer(h) { h }" } }
+        }
+    }));
+    let mut r = LintResult::new();
+    validate_imposter(path(), &v, &mut r, &opts());
+    assert!(r.issues.is_empty(), "unexpected issues: {:?}", codes(&r));
+}
+
+#[test]
+fn e054_rift_field_types() {
+    let r = rift_response(json!({
+        "templated": "yes",
+        "fault": { "error": { "status": 700 }, "latency": { "minMs": "10", "maxMs": 20 } },
+        "stream": { "chunks": [] }
+    }));
+    let mut locations: Vec<_> = r
+        .issues
+        .iter()
+        .filter(|i| i.code == "E054")
+        .filter_map(|i| i.location.as_deref())
+        .collect();
+    locations.sort_unstable();
+    assert_eq!(
+        locations,
+        [
+            "loc._rift.fault.error.status",
+            "loc._rift.fault.latency.minMs",
+            "loc._rift.stream.chunks",
+            "loc._rift.templated",
+        ]
+    );
+}
+
+#[test]
+fn e054_upstream_needs_host_and_port() {
+    let v = rift_imposter(json!({ "proxy": { "upstream": { "host": "api" } } }));
+    let mut r = LintResult::new();
+    validate_imposter(path(), &v, &mut r, &opts());
+    assert!(has_code(&r, "E054"), "expected E054, got {:?}", codes(&r));
+}
+
+#[test]
+fn w014_unknown_rift_fields() {
+    let r =
+        rift_response(json!({ "faults": {}, "fault": { "latency": { "ms": 5, "jitter": 2 } } }));
+    let locations: Vec<_> = r
+        .issues
+        .iter()
+        .filter(|i| i.code == "W014")
+        .filter_map(|i| i.location.as_deref())
+        .collect();
+    assert_eq!(
+        locations,
+        ["loc._rift.faults", "loc._rift.fault.latency.jitter"]
+    );
+    assert!(!has_code(&r, "W012"), "_rift fields use their own code");
+}
+
+#[test]
+fn w015_latency_range_sanity() {
+    for latency in [
+        json!({ "minMs": 500, "maxMs": 100 }),
+        json!({ "ms": 100, "maxMs": 200 }),
+        json!({ "probability": 0.5 }),
+    ] {
+        let r = rift_response(json!({ "fault": { "latency": latency } }));
+        assert!(
+            has_code(&r, "W015"),
+            "{latency}: expected W015, got {:?}",
+            codes(&r)
+        );
+    }
+    let r = rift_response(json!({ "fault": { "latency": { "minMs": 100, "maxMs": 500 } } }));
+    assert!(!has_code(&r, "W015"), "unexpected W015: {:?}", codes(&r));
+}
+
+#[test]
+fn w016_flow_state_backend_not_built_in() {
+    for flow_state in [
+        json!({ "backend": "dynamo" }),
+        json!({ "backend": "inmemory", "redis": { "url": "redis://r:6379" } }),
+    ] {
+        let v = rift_imposter(json!({ "flowState": flow_state }));
+        let mut r = LintResult::new();
+        validate_imposter(path(), &v, &mut r, &opts());
+        assert!(
+            has_code(&r, "W016"),
+            "{flow_state}: expected W016, got {:?}",
+            codes(&r)
+        );
+    }
+}
+
+// ─── Embedded scripts through LintOptions::script_check (E057) ──────────────
+
+fn failing_check() -> LintOptions {
+    LintOptions::default()
+        .with_script_check(|script: &EmbeddedScript<'_>| Err(format!("{:?} rejected", script.kind)))
+}
+
+#[test]
+fn e057_script_check_sees_every_embedded_script() {
+    let v = make_imposter(json!([{
+        "predicates": [{ "inject": "function (config) { return true; }" }],
+        "responses": [
+            { "inject": "function (config) { return {}; }" },
+            { "is": { "statusCode": 200 }, "_behaviors": { "decorate": "config.response.body = 'x';" } },
+            { "_rift": { "script": { "engine": "rhai", "code": "fn respond(ctx) { http(200) }" } } }
+        ]
+    }]));
+    let mut r = LintResult::new();
+    validate_imposter(path(), &v, &mut r, &failing_check());
+    let found: Vec<_> = r
+        .issues
+        .iter()
+        .filter(|i| i.code == "E057")
+        .map(|i| (i.location.as_deref().unwrap_or(""), i.message.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            (
+                "stubs[0].predicates[0].inject",
+                "Script does not compile (javascript): Inject rejected"
+            ),
+            (
+                "stubs[0].responses[0].inject",
+                "Script does not compile (javascript): Inject rejected"
+            ),
+            (
+                "stubs[0].responses[1]._behaviors.decorate",
+                "Script does not compile (javascript/rhai): Decorate rejected"
+            ),
+            (
+                "stubs[0].responses[2]._rift.script",
+                "Script does not compile (rhai): Rift rejected"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn e057_not_fired_when_the_check_passes() {
+    let options = LintOptions::default().with_script_check(|_: &EmbeddedScript<'_>| Ok(()));
+    let resp = json!({ "inject": "function (config) { return {; }" });
+    let mut r = LintResult::new();
+    validate_response(path(), &resp, "loc", &mut r, &options, &Value::Null);
+    // Only the note that inject needs --allow-injection
+    assert_eq!(codes(&r), ["I004"]);
+}
+
+// ─── .riftlint.toml (LintConfig) ────────────────────────────────────────────
+
+#[test]
+fn lint_config_parses_rules_and_options() {
+    let config = LintConfig::parse(
+        "strict = true\nerrors-only = true\n\n[rules]\nW012 = \"off\"\nE010 = \"warning\"\n",
+        path(),
+    )
+    .unwrap();
+    assert!(config.strict && config.errors_only);
+    assert!(config.allowed_proxy_hosts.is_empty());
+    assert_eq!(config.rules["W012"], RuleLevel::Off);
+    assert_eq!(config.rules["E010"], RuleLevel::Warning);
+}
+
+#[test]
+fn lint_config_rejects_unknown_rules_and_keys() {
+    assert!(matches!(
+        LintConfig::parse("[rules]\nunknown-field = \"off\"\n", path()),
+        Err(ConfigError::UnknownRule(_, code)) if code == "unknown-field"
+    ));
+    assert!(matches!(
+        LintConfig::parse("strictt = true\n", path()),
+        Err(ConfigError::Parse(..))
+    ));
+    assert!(matches!(
+        LintConfig::parse("[rules]\nW012 = \"loud\"\n", path()),
+        Err(ConfigError::Parse(..))
+    ));
+}
+
+#[test]
+fn lint_config_disables_downgrades_and_promotes_rules() {
+    let imposter = make_imposter(json!([{
+        "predicates": [{ "matches": { "path": "[unclosed" } }],
+        "responses": [{ "is": { "statusCode": 200 } }],
+        "name": "unknown stub field"
+    }]));
+    let result = lint_value(&imposter, "<test>", &opts());
+    assert!(has_code(&result, "E013"));
+    assert!(has_code(&result, "W012"));
+
+    let mut config = LintConfig::default();
+    config.rules.insert("E013".to_string(), RuleLevel::Warning);
+    config.rules.insert("W012".to_string(), RuleLevel::Off);
+    let applied = config.apply(lint_value(&imposter, "<test>", &opts()));
+    assert!(!has_code(&applied, "W012"));
+    let issue = applied.issues.iter().find(|i| i.code == "E013").unwrap();
+    assert_eq!(issue.severity, Severity::Warning);
+    assert_eq!(applied.errors, result.errors - 1);
+    assert_eq!(applied.warnings, result.warnings);
+
+    let mut promote = LintConfig::default();
+    promote.rules.insert("W012".to_string(), RuleLevel::Error);
+    let promoted = promote.apply(lint_value(&imposter, "<test>", &opts()));
+    assert_eq!(promoted.errors, result.errors + 1);
+}
+
+#[test]
+fn lint_config_discovered_up_to_the_repository_root() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    let nested = repo.join("imposters").join("team");
+    std::fs::create_dir_all(&nested).unwrap();
+    std::fs::create_dir(repo.join(".git")).unwrap();
+    assert_eq!(LintConfig::discover(&nested), None);
+
+    // Above the repository root: not picked up.
+    std::fs::write(dir.path().join(".riftlint.toml"), "").unwrap();
+    assert_eq!(LintConfig::discover(&nested), None);
+
+    std::fs::write(repo.join(".riftlint.toml"), "").unwrap();
+    let file = nested.join("a.json");
+    std::fs::write(&file, "{}").unwrap();
+    let found = LintConfig::discover(&file).unwrap();
+    assert_eq!(found, repo.canonicalize().unwrap().join(".riftlint.toml"));
+}
+
+// ─── Inline suppressions (x-rift-lint-ignore, W017) and baselines ──────────
+
+fn stub_with_unknown_field(extra: Value) -> Value {
+    let mut stub = json!({
+        "responses": [{ "is": { "statusCode": 200, "bodyy": "typo" } }],
+        "name": "unknown"
+    });
+    stub.as_object_mut()
+        .unwrap()
+        .extend(extra.as_object().unwrap().clone());
+    stub
+}
+
+#[test]
+fn ignore_annotation_suppresses_codes_in_its_object() {
+    let stub = stub_with_unknown_field(json!({ "x-rift-lint-ignore": ["W012"] }));
+    let r = lint_value(&make_imposter(json!([stub])), "<test>", &opts());
+    assert!(!has_code(&r, "W012"), "unexpected: {:?}", codes(&r));
+    assert_eq!(
+        r.warnings,
+        r.issues
+            .iter()
+            .filter(|i| i.severity == Severity::Warning)
+            .count()
+    );
+}
+
+#[test]
+fn ignore_annotation_covers_only_its_own_subtree() {
+    let annotated = json!({
+        "responses": [{ "is": { "statusCode": 200 }, "x-rift-lint-ignore": ["W012"] }],
+        "name": "still reported"
+    });
+    let r = lint_value(&make_imposter(json!([annotated])), "<test>", &opts());
+    let w012: Vec<_> = r.issues.iter().filter(|i| i.code == "W012").collect();
+    assert_eq!(w012.len(), 1, "{:?}", codes(&r));
+    assert_eq!(w012[0].location.as_deref(), Some("stubs[0].name"));
+
+    // `stubs[1]`'s annotation doesn't reach `stubs[0]`, and one for another code changes nothing.
+    let mut imposter = make_imposter(json!([
+        stub_with_unknown_field(json!({})),
+        stub_with_unknown_field(json!({ "x-rift-lint-ignore": ["W012"] }))
+    ]));
+    imposter["x-rift-lint-ignore"] = json!(["W999"]);
+    let r = lint_value(&imposter, "<test>", &opts());
+    assert!(r.issues.iter().all(|i| {
+        i.code != "W012"
+            || i.location
+                .as_deref()
+                .is_some_and(|l| l.starts_with("stubs[0]"))
+    }));
+    assert!(has_code(&r, "W012"));
+}
+
+#[test]
+fn ignore_annotation_at_the_imposter_covers_everything() {
+    let mut imposter = make_imposter(json!([stub_with_unknown_field(json!({}))]));
+    imposter["x-rift-lint-ignore"] = json!(["W012", "E003"]);
+    imposter.as_object_mut().unwrap().remove("port");
+    let r = lint_value(&imposter, "<test>", &opts());
+    assert!(
+        !has_code(&r, "W012") && !has_code(&r, "E003"),
+        "{:?}",
+        codes(&r)
+    );
+}
+
+#[test]
+fn w017_malformed_ignore_annotation() {
+    let mut imposter = make_imposter(json!([]));
+    imposter["x-rift-lint-ignore"] = json!("W012");
+    let r = lint_value(&imposter, "<test>", &opts());
+    assert!(has_code(&r, "W017"));
+    assert!(
+        !has_code(&r, "W012"),
+        "the annotation itself is not an unknown field"
+    );
+}
+
+#[test]
+fn baseline_hides_recorded_issues_only() {
+    let imposter = make_imposter(json!([stub_with_unknown_field(json!({}))]));
+    let before = lint_value(&imposter, "<test>", &opts());
+    assert!(before.warnings >= 2);
+    let baseline = Baseline::from_result(&before);
+    let filtered = baseline.filter(lint_value(&imposter, "<test>", &opts()));
+    assert!(filtered.issues.is_empty());
+    assert_eq!((filtered.errors, filtered.warnings), (0, 0));
+
+    // A new stub with the same problems is new: its locations differ.
+    let grown = make_imposter(json!([
+        stub_with_unknown_field(json!({})),
+        stub_with_unknown_field(json!({}))
+    ]));
+    let filtered = baseline.filter(lint_value(&grown, "<test>", &opts()));
+    assert!(!filtered.issues.is_empty());
+    assert!(filtered.issues.iter().all(|i| {
+        i.location
+            .as_deref()
+            .is_some_and(|l| l.starts_with("stubs[1]"))
+    }));
+}
+
+#[test]
+fn baseline_round_trips_through_a_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("baseline.json");
+    let result = lint_value(
+        &make_imposter(json!([stub_with_unknown_field(json!({}))])),
+        "<test>",
+        &opts(),
+    );
+    let baseline = Baseline::from_result(&result);
+    baseline.save(&file).unwrap();
+    assert_eq!(Baseline::load(&file).unwrap(), baseline);
+}
+
+// ─── Stub shadowing (W018) ─────────────────────────────────────────────────
+
+fn stub_matching(predicates: Value) -> Value {
+    json!({ "predicates": predicates, "responses": [{ "is": { "statusCode": 200 } }] })
+}
+
+fn shadowed(stubs: Value) -> Vec<String> {
+    let r = lint_value(&make_imposter(stubs), "<test>", &opts());
+    r.issues
+        .iter()
+        .filter(|i| i.code == "W018")
+        .map(|i| i.location.clone().unwrap_or_default())
+        .collect()
+}
+
+#[test]
+fn w018_catch_all_shadows_every_later_stub() {
+    let stubs = json!([
+        { "responses": [{ "is": { "statusCode": 404 } }] },
+        stub_matching(json!([{ "equals": { "path": "/orders" } }])),
+        stub_matching(json!([]))
+    ]);
+    assert_eq!(shadowed(stubs), ["stubs[1]", "stubs[2]"]);
+}
+
+#[test]
+fn w018_duplicate_and_more_general_predicates() {
+    let stubs = json!([
+        stub_matching(json!([{ "startsWith": { "path": "/api" } }])),
+        stub_matching(json!([{ "equals": { "path": "/API/users", "method": "GET" } }])),
+        // The same predicates with another response (the same response too would be W034)
+        json!({
+            "predicates": [{ "startsWith": { "path": "/api" } }],
+            "responses": [{ "is": { "statusCode": 201 } }]
+        }),
+        stub_matching(json!([{ "equals": { "path": "/health" } }]))
+    ]);
+    assert_eq!(shadowed(stubs), ["stubs[1]", "stubs[2]"]);
+}
+
+#[test]
+fn w018_nested_fields_and_combinators() {
+    let stubs = json!([
+        stub_matching(json!([{ "equals": { "query": { "page": "1" } } }])),
+        stub_matching(json!([{
+            "and": [
+                { "equals": { "query": { "page": "1", "size": "10" } } },
+                { "equals": { "method": "GET" } }
+            ]
+        }])),
+        stub_matching(json!([{ "or": [
+            { "equals": { "path": "/a" } },
+            { "equals": { "path": "/b" } }
+        ] }])),
+        stub_matching(json!([{ "equals": { "path": "/b", "method": "POST" } }]))
+    ]);
+    assert_eq!(shadowed(stubs), ["stubs[1]", "stubs[3]"]);
+}
+
+#[test]
+fn w018_not_reported_when_the_earlier_stub_is_narrower() {
+    let stubs = json!([
+        // Both fields must match, so `/orders` with any method still reaches stub 1.
+        stub_matching(json!([{ "equals": { "path": "/orders", "method": "POST" } }])),
+        stub_matching(json!([{ "equals": { "path": "/orders" } }])),
+        // Case-sensitive matching accepts fewer requests than the default.
+        stub_matching(json!([{ "equals": { "path": "/Items" }, "caseSensitive": true }])),
+        stub_matching(json!([{ "equals": { "path": "/items" } }])),
+        // `matches` is only compared for identical patterns.
+        stub_matching(json!([{ "matches": { "path": "^/users" } }])),
+        stub_matching(json!([{ "equals": { "path": "/users/1" } }]))
+    ]);
+    assert!(shadowed(stubs).is_empty());
+}
+
+#[test]
+fn w018_not_reported_after_a_gated_stub() {
+    let stubs = json!([
+        {
+            "scenarioName": "checkout",
+            "requiredScenarioState": "Started",
+            "responses": [{ "is": { "statusCode": 200 } }]
+        },
+        { "space": "flow-1", "responses": [{ "is": { "statusCode": 200 } }] },
+        stub_matching(json!([{ "equals": { "path": "/cart" } }]))
+    ]);
+    assert!(shadowed(stubs).is_empty());
+}
+
+#[test]
+fn w018_suppressed_on_the_shadowed_stub() {
+    let mut later = stub_matching(json!([{ "equals": { "path": "/a" } }]));
+    later["x-rift-lint-ignore"] = json!(["W018"]);
+    let stubs = json!([{ "responses": [{ "is": { "statusCode": 404 } }] }, later]);
+    assert!(shadowed(stubs).is_empty());
+}
+
+// ─── Contradictory predicates (W019) ───────────────────────────────────────
+
+fn contradictions(predicates: Value) -> Vec<String> {
+    let r = lint_value(
+        &make_imposter(json!([stub_matching(predicates)])),
+        "<test>",
+        &opts(),
+    );
+    r.issues
+        .iter()
+        .filter(|i| i.code == "W019")
+        .map(|i| i.message.clone())
+        .collect()
+}
+
+#[test]
+fn w019_different_methods_or_paths() {
+    let found = contradictions(json!([
+        { "equals": { "method": "GET" } },
+        { "equals": { "method": "POST" } }
+    ]));
+    assert_eq!(
+        found,
+        [r#"Stub can never match: 'method' can't both equal "GET" and equal "POST""#]
+    );
+
+    assert_eq!(
+        contradictions(json!([{
+            "and": [
+                { "startsWith": { "path": "/orders" } },
+                { "equals": { "path": "/users/1" } }
+            ]
+        }]))
+        .len(),
+        1
+    );
+    assert_eq!(
+        contradictions(json!([
+            { "startsWith": { "path": "/a" } },
+            { "startsWith": { "path": "/b" } }
+        ]))
+        .len(),
+        1
+    );
+}
+
+#[test]
+fn w019_predicate_next_to_its_own_not() {
+    let found = contradictions(json!([
+        { "contains": { "body": "x" } },
+        { "not": { "contains": { "body": "x" } } }
+    ]));
+    assert_eq!(found.len(), 1, "{found:?}");
+}
+
+#[test]
+fn w019_not_reported_for_compatible_or_multi_valued_fields() {
+    for predicates in [
+        // Case-insensitive by default.
+        json!([{ "equals": { "method": "GET" } }, { "equals": { "method": "get" } }]),
+        json!([{ "startsWith": { "path": "/api" } }, { "equals": { "path": "/api/users" } }]),
+        json!([{ "startsWith": { "path": "/api" } }, { "startsWith": { "path": "/api/v2" } }]),
+        // A repeated query parameter can satisfy both.
+        json!([{ "equals": { "query": { "tag": "a" } } }, { "equals": { "query": { "tag": "b" } } }]),
+        // Different comparison settings aren't compared.
+        json!([
+            { "equals": { "path": "/A" }, "caseSensitive": true },
+            { "equals": { "path": "/a" } }
+        ]),
+        json!([{ "equals": { "method": "GET" } }, { "not": { "equals": { "method": "POST" } } }]),
+    ] {
+        assert!(
+            contradictions(predicates.clone()).is_empty(),
+            "{predicates}"
+        );
+    }
+}
+
+#[test]
+fn w002_empty_responses_explains_the_fall_through() {
+    let r = lint_value(
+        &make_imposter(json!([{ "predicates": [], "responses": [] }])),
+        "<test>",
+        &opts(),
+    );
+    let issue = r.issues.iter().find(|i| i.code == "W002").unwrap();
+    assert!(
+        issue
+            .suggestion
+            .as_deref()
+            .unwrap()
+            .contains("default response")
+    );
+}
+
+// ─── Checks across files: lint_files (E002, W020) ──────────────────────────
+
+fn write_config(dir: &Path, name: &str, config: &Value) -> std::path::PathBuf {
+    let file = dir.join(name);
+    std::fs::write(&file, serde_json::to_string(config).unwrap()).unwrap();
+    file
+}
+
+#[test]
+fn lint_files_reports_a_port_shared_across_files_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut other = make_imposter(json!([minimal_stub()]));
+    other["name"] = json!("other");
+    let files = [
+        write_config(
+            dir.path(),
+            "a.json",
+            &make_imposter(json!([minimal_stub()])),
+        ),
+        write_config(dir.path(), "b.json", &other),
+    ];
+    let r = lint_files(&files, &opts());
+    assert_eq!(r.files_checked, 2);
+    let conflicts: Vec<_> = r.issues.iter().filter(|i| i.code == "E002").collect();
+    assert_eq!(conflicts.len(), 1, "{:?}", codes(&r));
+    assert_eq!(conflicts[0].file, files[0]);
+    assert!(conflicts[0].message.contains("a.json, b.json"));
+}
+
+#[test]
+fn lint_files_reports_in_the_order_files_are_given() {
+    let dir = tempfile::tempdir().unwrap();
+    // Bad statuses, ports and names, in reverse name order so the order given is not sorted
+    let files: Vec<_> = (0..64u64)
+        .rev()
+        .map(|n| {
+            let mut imposter = make_imposter(json!([{
+                "responses": [{ "is": { "statusCode": 1000 + n } }]
+            }]));
+            imposter["port"] = json!(4000 + n);
+            write_config(dir.path(), &format!("{n:02}.json"), &imposter)
+        })
+        .collect();
+    let r = lint_files(&files, &opts());
+    assert_eq!(r.files_checked, 64);
+
+    let mut sequential = LintResult::new();
+    for file in &files {
+        sequential.merge(lint_file(file, &opts()));
+    }
+    let order = |r: &LintResult| {
+        r.issues
+            .iter()
+            .map(|i| (i.file.clone(), i.code.clone(), i.message.clone()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(order(&r), order(&sequential));
+    assert_eq!(r.issues[0].file, files[0]);
+    let timed: Vec<_> = r.durations.iter().map(|(file, _)| file.clone()).collect();
+    assert_eq!(timed, files);
+}
+
+#[test]
+fn lint_files_sees_imposters_inside_a_wrapper() {
+    let dir = tempfile::tempdir().unwrap();
+    let imposter = make_imposter(json!([minimal_stub()]));
+    let files = [write_config(
+        dir.path(),
+        "all.json",
+        &json!({ "imposters": [imposter.clone(), imposter] }),
+    )];
+    let r = lint_files(&files, &opts());
+    assert!(has_code(&r, "E002"), "{:?}", codes(&r));
+}
+
+#[test]
+fn w020_duplicate_imposter_names() {
+    let dir = tempfile::tempdir().unwrap();
+    let named = |port: u16| {
+        let mut imposter = make_imposter(json!([minimal_stub()]));
+        imposter["port"] = json!(port);
+        imposter["name"] = json!("orders");
+        imposter
+    };
+    let files = [
+        write_config(dir.path(), "a.json", &named(4545)),
+        write_config(dir.path(), "b.json", &named(4546)),
+    ];
+    let r = lint_files(&files, &opts());
+    let w020: Vec<_> = r.issues.iter().filter(|i| i.code == "W020").collect();
+    assert_eq!(w020.len(), 1, "{:?}", codes(&r));
+    assert_eq!(w020[0].file, files[1]);
+    assert!(!has_code(&r, "E002"));
+}
+
+#[test]
+fn lint_files_reports_unparsable_files_as_e001() {
+    let dir = tempfile::tempdir().unwrap();
+    let broken = dir.path().join("broken.json");
+    std::fs::write(&broken, "{ not json").unwrap();
+    let missing = dir.path().join("missing.json");
+    let r = lint_files(&[broken, missing], &opts());
+    assert_eq!(r.files_checked, 2);
+    assert_eq!(codes(&r), ["E001", "E001"]);
+}
+
+#[test]
+fn lint_directory_checks_ports_across_files() {
+    let dir = tempfile::tempdir().unwrap();
+    write_config(
+        dir.path(),
+        "a.json",
+        &make_imposter(json!([minimal_stub()])),
+    );
+    write_config(
+        dir.path(),
+        "b.json",
+        &make_imposter(json!([minimal_stub()])),
+    );
+    let r = lint_directory(dir.path(), &opts());
+    assert!(has_code(&r, "E002"), "{:?}", codes(&r));
+}
+
+// ─── EJS templates (E058, W021) ─────────────────────────────────────────────
+
+/// A Mountebank-style template: a config including an imposter, which includes its stubs and
+/// stringifies its inject function.
+fn write_ejs_project(dir: &Path) -> std::path::PathBuf {
+    std::fs::create_dir(dir.join("imposters")).unwrap();
+    std::fs::write(
+        dir.join("imposters/orders.ejs"),
+        r#"{"port": 4545, "protocol": "http", "stubs": [<%- include('stubs.ejs') %>]}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("imposters/stubs.ejs"),
+        r#"{"responses": [{"inject": "<%- stringify(filename, 'inject.js') %>"}]}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("inject.js"),
+        "function (config) {\n  return { statusCode: \"201\" };\n}",
+    )
+    .unwrap();
+    let config = dir.join("imposters.ejs");
+    std::fs::write(
+        &config,
+        r#"{"imposters": [<% include imposters/orders.ejs %>]}"#,
+    )
+    .unwrap();
+    config
+}
+
+#[test]
+fn ejs_config_is_rendered_before_linting() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = write_ejs_project(dir.path());
+    let r = lint_file(&config, &opts());
+    assert!(!r.has_errors(), "{:?}", codes(&r));
+
+    let r = lint_files(&[config], &opts());
+    assert!(!r.has_errors(), "{:?}", codes(&r));
+}
+
+#[test]
+fn ejs_is_not_rendered_with_no_parse() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = write_ejs_project(dir.path());
+    let options = LintOptions {
+        no_parse: true,
+        ..opts()
+    };
+    assert!(has_code(&lint_file(&config, &options), "E002"));
+    assert!(has_code(&lint_files(&[config], &options), "E001"));
+}
+
+#[test]
+fn e058_missing_include() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("imposters.ejs");
+    std::fs::write(&config, r#"{"imposters": [<% include missing.ejs %>]}"#).unwrap();
+    let r = lint_files(&[config], &opts());
+    assert_eq!(codes(&r), ["E058"]);
+    assert!(r.issues[0].message.contains("missing.ejs"));
+}
+
+#[test]
+fn e058_self_include() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("loop.ejs");
+    std::fs::write(&config, "<% include loop.ejs %>").unwrap();
+    let r = lint_file(&config, &opts());
+    assert_eq!(codes(&r), ["E058"]);
+}
+
+#[test]
+fn w021_unsupported_ejs_tags() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("imposters.ejs");
+    std::fs::write(
+        &config,
+        r#"{"port": 4545, "protocol": "http", "name": "<%= name %>", "stubs": []<% if (x) { %><% } %>}"#,
+    )
+    .unwrap();
+    let r = lint_file(&config, &opts());
+    let w021: Vec<_> = r.issues.iter().filter(|i| i.code == "W021").collect();
+    assert_eq!(w021.len(), 3, "{:?}", codes(&r));
+    assert!(w021[0].message.contains("<%= name %>"));
+}
+
+#[test]
+fn render_ejs_substitutes_env_vars_with_defaults() {
+    let rendered = render_ejs(
+        r#"{"port": <%= process.env.RIFT_LINT_TEST_UNSET_PORT || '4545' %>}"#,
+        Path::new("config.ejs"),
+    )
+    .unwrap();
+    assert_eq!(rendered.text, r#"{"port": 4545}"#);
+    assert!(rendered.unsupported.is_empty());
+}
+
+// ─── YAML configs ──────────────────────────────────────────────────────────
+
+const YAML_IMPOSTERS: &str = "\
+- port: 4545
+  protocol: http
+  stubs:
+    - responses:
+        - is:
+            statusCode: 200
+            headers:
+              X-Count: 1
+";
+
+#[test]
+fn yaml_config_is_validated_with_the_same_locations() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("imposters.yaml");
+    std::fs::write(&file, YAML_IMPOSTERS).unwrap();
+    let r = lint_file(&file, &opts());
+    let header = r
+        .issues
+        .iter()
+        .find(|i| i.severity == Severity::Error)
+        .expect("a numeric header value is an error");
+    assert_eq!(
+        header.location.as_deref(),
+        Some("stubs[0].responses[0].is.headers.X-Count")
+    );
+}
+
+#[test]
+fn yaml_mapping_is_rejected_as_the_server_rejects_it() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("imposters.yml");
+    std::fs::write(&file, "imposters:\n  - port: 4545\n    protocol: http\n").unwrap();
+    let r = lint_file(&file, &opts());
+    assert_eq!(codes(&r), ["E002"]);
+    assert!(r.issues[0].message.contains("list of imposters"));
+}
+
+#[test]
+fn yaml_file_written_as_json_is_read_as_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let imposter = make_imposter(json!([minimal_stub()]));
+    let file = write_config(
+        dir.path(),
+        "imposters.yaml",
+        &json!({ "imposters": [imposter] }),
+    );
+    let r = lint_file(&file, &opts());
+    assert!(!r.has_errors(), "{:?}", codes(&r));
+}
+
+#[test]
+fn lint_directory_includes_yaml_files() {
+    let dir = tempfile::tempdir().unwrap();
+    write_config(
+        dir.path(),
+        "a.json",
+        &make_imposter(json!([minimal_stub()])),
+    );
+    std::fs::write(
+        dir.path().join("b.yml"),
+        YAML_IMPOSTERS.replace("4545", "3000"),
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("c.yaml"), "- port: [").unwrap();
+    let r = lint_directory(dir.path(), &opts());
+    assert_eq!(r.files_checked, 3);
+    assert!(has_code(&r, "E002"), "{:?}", codes(&r));
+    assert!(
+        r.issues
+            .iter()
+            .any(|i| i.code == "E001" && i.message.starts_with("Failed to parse YAML:"))
+    );
+}
+
+// ─── Checks against a running server (E059, W022–W024) ─────────────────────
+
+/// A server on admin port 2525 running `imposters`, as `GET /config` and replayable
+/// `GET /imposters` report it.
+fn server(imposters: Vec<Value>) -> ServerState {
+    ServerState {
+        url: "http://localhost:2525".to_string(),
+        config: json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "features": { "javascript": true, "redisBackend": true },
+            "options": { "port": 2525, "allowInjection": true }
+        }),
+        imposters,
+    }
+}
+
+fn lint_against(imposter: &Value, server: ServerState) -> LintResult {
+    let dir = tempfile::tempdir().unwrap();
+    let file = write_config(dir.path(), "imposter.json", imposter);
+    lint_files(&[file], &opts().with_server(server))
+}
+
+/// `make_imposter(json!([minimal_stub()]))` as the server stores it.
+fn deployed_minimal() -> Value {
+    json!({
+        "port": 3000, "protocol": "http", "recordRequests": false,
+        "stubs": [{
+            "predicates": [],
+            "responses": [{ "is": { "statusCode": "200", "headers": {} } }]
+        }]
+    })
+}
+
+#[test]
+fn deployed_copy_of_the_file_is_clean() {
+    let mut imposter = make_imposter(json!([minimal_stub()]));
+    imposter["stubs"][0]["predicates"] = json!([{ "equals": { "path": "/a" } }]);
+    let mut deployed = deployed_minimal();
+    deployed["stubs"][0]["predicates"] = json!([{ "equals": { "path": "/a" } }]);
+    let r = lint_against(&imposter, server(vec![deployed]));
+    assert!(r.issues.is_empty(), "{:?}", codes(&r));
+}
+
+#[test]
+fn e059_admin_port() {
+    let mut imposter = make_imposter(json!([minimal_stub()]));
+    imposter["port"] = json!(2525);
+    let r = lint_against(&imposter, server(vec![]));
+    assert_eq!(codes(&r), ["E059"]);
+}
+
+#[test]
+fn e059_scripts_with_injection_off() {
+    let imposter = make_imposter(json!([{
+        "responses": [{ "is": { "statusCode": 200 }, "_behaviors": { "wait": "function () { return 5; }" } }]
+    }]));
+    let mut server = server(vec![]);
+    server.config["options"]["allowInjection"] = json!(false);
+    let r = lint_against(&imposter, server);
+    assert!(has_code(&r, "E059"), "{:?}", codes(&r));
+
+    let delay = make_imposter(json!([{
+        "responses": [{ "is": { "statusCode": 200 }, "_behaviors": { "wait": { "min": 1, "max": 5 } } }]
+    }]));
+    let mut server = self::server(vec![]);
+    server.config["options"]["allowInjection"] = json!(false);
+    assert!(!has_code(&lint_against(&delay, server), "E059"));
+}
+
+#[test]
+fn w022_port_serves_another_imposter() {
+    let mut imposter = make_imposter(json!([minimal_stub()]));
+    imposter["name"] = json!("orders");
+    let mut deployed = deployed_minimal();
+    deployed["name"] = json!("payments");
+    let r = lint_against(&imposter, server(vec![deployed]));
+    assert_eq!(codes(&r), ["W022"]);
+    assert!(r.issues[0].message.contains("payments"));
+}
+
+#[test]
+fn w023_deployed_imposter_has_drifted() {
+    let imposter = make_imposter(json!([minimal_stub()]));
+    let mut extra_stub = deployed_minimal();
+    extra_stub["stubs"]
+        .as_array_mut()
+        .unwrap()
+        .push(json!({ "predicates": [], "responses": [] }));
+    let r = lint_against(&imposter, server(vec![extra_stub]));
+    assert_eq!(codes(&r), ["W023"]);
+    assert_eq!(r.issues[0].location.as_deref(), Some("stubs"));
+
+    let mut other_predicate = deployed_minimal();
+    other_predicate["stubs"][0]["predicates"] = json!([{ "equals": { "path": "/b" } }]);
+    let r = lint_against(&imposter, server(vec![other_predicate]));
+    assert_eq!(codes(&r), ["W023"]);
+    assert_eq!(r.issues[0].location.as_deref(), Some("stubs[0]"));
+}
+
+#[test]
+fn w024_server_lacks_what_the_imposter_uses() {
+    let mut imposter = make_imposter(json!([{
+        "responses": [{ "inject": "function (config) { return {}; }" }]
+    }]));
+    imposter["_rift"] =
+        json!({ "flowState": { "backend": "redis", "redis": { "url": "redis://r" } } });
+    let mut server = server(vec![]);
+    server.config["features"] = json!({ "javascript": false, "redisBackend": false });
+    let r = lint_against(&imposter, server);
+    let w024 = r.issues.iter().filter(|i| i.code == "W024").count();
+    assert_eq!(w024, 2, "{:?}", codes(&r));
+}
+
+#[test]
+fn w024_older_server() {
+    let mut server = server(vec![]);
+    server.config["version"] = json!("0.0.1");
+    let r = lint_against(&make_imposter(json!([minimal_stub()])), server);
+    assert_eq!(codes(&r), ["W024"]);
+    assert_eq!(r.issues[0].file, Path::new("http://localhost:2525"));
+}
+
+// ─── Performance rules ───────────────────────────────────────────────────────
+
+#[test]
+fn w025_large_inline_body() {
+    let mut r = LintResult::new();
+    let big = json!({ "statusCode": 200, "body": "x".repeat(300 * 1024) });
+    validate_is_response(path(), &big, "stubs[0].responses[0].is", &mut r);
+    assert_eq!(codes(&r), ["W025"]);
+    assert_eq!(
+        r.issues[0].location.as_deref(),
+        Some("stubs[0].responses[0].is.body")
+    );
+
+    let mut r = LintResult::new();
+    let items: Vec<Value> = (0..30_000)
+        .map(|i| json!({ "id": i, "name": "item" }))
+        .collect();
+    validate_is_response(path(), &json!({ "body": items }), "is", &mut r);
+    assert!(has_code(&r, "W025"), "{:?}", codes(&r));
+
+    let mut r = LintResult::new();
+    let small = json!({ "body": "x".repeat(1024) });
+    validate_is_response(path(), &small, "is", &mut r);
+    assert!(!has_code(&r, "W025"));
+}
+
+#[test]
+fn w026_unbounded_wildcard_in_body_regex() {
+    let lint = |matches: Value| {
+        let mut r = LintResult::new();
+        validate_predicate(path(), &json!({ "matches": matches }), "p", &mut r, &opts());
+        r
+    };
+    let r = lint(json!({ "body": ".*\"id\": 1.*" }));
+    assert_eq!(codes(&r), ["W026"]);
+    assert_eq!(r.issues[0].location.as_deref(), Some("p.matches.body"));
+    let r = lint(json!({ "body": { "order": { "note": "rush.+" } } }));
+    assert_eq!(codes(&r), ["W026"]);
+    assert_eq!(
+        r.issues[0].location.as_deref(),
+        Some("p.matches.body.order.note")
+    );
+
+    // Escaped dots, classes, bounded repeats and other fields are fine
+    for matches in [
+        json!({ "body": "\\.*" }),
+        json!({ "body": "[.*]" }),
+        json!({ "body": "a.{0,20}b" }),
+        json!({ "path": "/api/.*" }),
+    ] {
+        let r = lint(matches.clone());
+        assert!(r.issues.is_empty(), "{matches}: {:?}", codes(&r));
+    }
+}
+
+#[test]
+fn w027_thousands_of_stubs() {
+    let stubs: Vec<Value> = (0..1001)
+        .map(|n| stub_matching(json!([{ "equals": { "path": format!("/{n}") } }])))
+        .collect();
+    let mut r = LintResult::new();
+    validate_imposter(path(), &make_imposter(json!(stubs)), &mut r, &opts());
+    assert_eq!(codes(&r), ["W027"]);
+
+    let stubs: Vec<Value> = (0..1000)
+        .map(|n| stub_matching(json!([{ "equals": { "path": format!("/{n}") } }])))
+        .collect();
+    let mut r = LintResult::new();
+    validate_imposter(path(), &make_imposter(json!(stubs)), &mut r, &opts());
+    assert!(!has_code(&r, "W027"));
+}
+
+#[test]
+fn w028_proxy_always_records_without_bound() {
+    let lint = |proxy: Value| {
+        let mut r = LintResult::new();
+        validate_proxy_response(path(), &proxy, "proxy", &mut r);
+        r
+    };
+    let r = lint(json!({ "to": "http://upstream", "mode": "proxyAlways" }));
+    assert_eq!(codes(&r), ["W028"]);
+    assert!(r.issues[0].message.contains("predicateGenerators"));
+
+    let generators = json!([{ "matches": { "path": true } }]);
+    let r = lint(json!({
+        "to": "http://upstream", "mode": "proxyAlways", "predicateGenerators": generators
+    }));
+    assert_eq!(codes(&r), ["W028"]);
+    assert!(r.issues[0].message.contains("maxResponsesPerStub"));
+
+    let r = lint(json!({
+        "to": "http://upstream", "mode": "proxyAlways", "predicateGenerators": generators,
+        "recording": { "maxResponsesPerStub": 5 }
+    }));
+    assert!(r.issues.is_empty(), "{:?}", codes(&r));
+    let r = lint(json!({ "to": "http://upstream", "mode": "proxyOnce" }));
+    assert!(r.issues.is_empty(), "{:?}", codes(&r));
+}
+
+#[test]
+fn w029_many_responses_without_repeat() {
+    let ok = json!({ "is": { "statusCode": 200 } });
+    let stub = json!({ "responses": vec![ok.clone(); 101] });
+    let mut r = LintResult::new();
+    validate_stub(path(), &stub, 0, &mut r, &opts(), &Value::Null);
+    assert_eq!(codes(&r), ["W029"]);
+    assert!(
+        r.issues[0].message.contains("100 of them"),
+        "{}",
+        r.issues[0].message
+    );
+    assert_eq!(r.issues[0].location.as_deref(), Some("stubs[0].responses"));
+
+    let mut responses = vec![ok; 101];
+    responses[0]["_behaviors"] = json!([{ "repeat": 2 }]);
+    let mut r = LintResult::new();
+    validate_stub(
+        path(),
+        &json!({ "responses": responses }),
+        0,
+        &mut r,
+        &opts(),
+        &Value::Null,
+    );
+    assert!(!has_code(&r, "W029"), "{:?}", codes(&r));
+}
+
+// ─── Security rules ──────────────────────────────────────────────────────────
+
+#[test]
+fn w030_shell_transform() {
+    let mut r = LintResult::new();
+    validate_behavior(
+        path(),
+        &json!({ "shellTransform": "./add-timestamp" }),
+        "b",
+        &mut r,
+        &opts(),
+    );
+    assert_eq!(codes(&r), ["W030"]);
+    assert_eq!(r.issues[0].location.as_deref(), Some("b.shellTransform"));
+}
+
+#[test]
+fn i004_inject_needs_allow_injection() {
+    let stub = json!({
+        "predicates": [{ "inject": "function (config) { return true; }" }],
+        "responses": [{ "inject": "function (config) { return {}; }" }]
+    });
+    let mut r = LintResult::new();
+    validate_stub(path(), &stub, 0, &mut r, &opts(), &Value::Null);
+    let locations: Vec<_> = r
+        .issues
+        .iter()
+        .filter(|i| i.code == "I004")
+        .map(|i| i.location.as_deref().unwrap())
+        .collect();
+    assert_eq!(
+        locations,
+        [
+            "stubs[0].predicates[0].inject",
+            "stubs[0].responses[0].inject"
+        ]
+    );
+    assert!(r.issues[0].message.contains("--allow-injection"));
+}
+
+#[test]
+fn w031_plaintext_credentials() {
+    let mut r = LintResult::new();
+    let is = json!({ "headers": { "Authorization": "Bearer abc123", "X-Trace": "1" } });
+    validate_is_response(path(), &is, "is", &mut r);
+    assert_eq!(codes(&r), ["W031"]);
+    assert_eq!(
+        r.issues[0].location.as_deref(),
+        Some("is.headers.Authorization")
+    );
+
+    let mut r = LintResult::new();
+    let proxy = json!({ "to": "http://upstream", "injectHeaders": { "x-api-key": "k-42" } });
+    validate_proxy_response(path(), &proxy, "proxy", &mut r);
+    assert_eq!(codes(&r), ["W031"]);
+    assert_eq!(
+        r.issues[0].location.as_deref(),
+        Some("proxy.injectHeaders.x-api-key")
+    );
+
+    // Templated and empty values aren't credentials
+    let mut r = LintResult::new();
+    let is = json!({ "headers": { "Authorization": "${TOKEN}", "X-Api-Key": "" } });
+    validate_is_response(path(), &is, "is", &mut r);
+    assert!(r.issues.is_empty(), "{:?}", codes(&r));
+}
+
+#[test]
+fn w032_wildcard_cors() {
+    let lint = |extra: Value| {
+        let mut imposter = make_imposter(json!([minimal_stub()]));
+        imposter
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        let mut r = LintResult::new();
+        validate_imposter(path(), &imposter, &mut r, &opts());
+        r
+    };
+    let r = lint(json!({ "allowCORS": true }));
+    assert_eq!(codes(&r), ["W032"]);
+    assert_eq!(r.issues[0].location.as_deref(), Some("allowCORS"));
+
+    let r = lint(json!({ "cors": { "allowOrigins": ["*"], "allowCredentials": true } }));
+    assert_eq!(codes(&r), ["W032"]);
+    assert!(r.issues[0].message.contains("credentialed"));
+
+    let r = lint(json!({ "allowCORS": true, "cors": { "allowOrigins": ["https://app.test"] } }));
+    assert!(r.issues.is_empty(), "{:?}", codes(&r));
+
+    let mut r = LintResult::new();
+    let is = json!({ "headers": { "Access-Control-Allow-Origin": "*" } });
+    validate_is_response(path(), &is, "is", &mut r);
+    assert_eq!(codes(&r), ["W032"]);
+}
+
+#[test]
+fn w033_proxy_to_an_external_host() {
+    let lint = |to: &str, options: &LintOptions| {
+        let response = json!({ "proxy": { "to": to, "mode": "proxyOnce" } });
+        let mut r = LintResult::new();
+        validate_response(path(), &response, "r", &mut r, options, &Value::Null);
+        r
+    };
+    let r = lint("https://api.example.com/v1", &opts());
+    assert_eq!(codes(&r), ["W033"]);
+    assert_eq!(r.issues[0].location.as_deref(), Some("r.proxy.to"));
+    assert!(r.issues[0].message.ends_with("api.example.com"));
+
+    for internal in [
+        "http://localhost:8080",
+        "http://127.0.0.1:9000",
+        "http://10.0.3.7",
+        "http://[::1]:8080",
+        "http://orders:8080",
+        "http://orders.internal",
+    ] {
+        assert!(lint(internal, &opts()).issues.is_empty(), "{internal}");
+    }
+
+    let allowed = LintOptions {
+        allowed_proxy_hosts: vec!["API.example.com".into(), "*.staging.test".into()],
+        ..LintOptions::default()
+    };
+    assert!(
+        lint("https://user:pw@api.example.com:443/", &allowed)
+            .issues
+            .is_empty()
+    );
+    assert!(
+        lint("https://orders.staging.test", &allowed)
+            .issues
+            .is_empty()
+    );
+    assert_eq!(codes(&lint("https://staging.test", &allowed)), ["W033"]);
+}
+
+// ─── Custom rules ────────────────────────────────────────────────────────────
+
+/// Imposter names must be kebab-case.
+struct KebabCaseNames;
+
+impl LintRule for KebabCaseNames {
+    fn check_imposter(&self, file: &Path, imposter: &Value, result: &mut LintResult) {
+        let Some(name) = imposter.get("name").and_then(Value::as_str) else {
+            return;
+        };
+        if !name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+        {
+            result.add_issue(
+                LintIssue::warning(
+                    "ORG001",
+                    format!("Name '{name}' isn't kebab-case"),
+                    file.to_path_buf(),
+                )
+                .with_location("name"),
+            );
+        }
+    }
+}
+
+/// Every stub must match on the `x-team` header.
+fn requires_team_header(file: &Path, imposter: &Value, result: &mut LintResult) {
+    let stubs = imposter.get("stubs").and_then(Value::as_array);
+    for (idx, stub) in stubs.into_iter().flatten().enumerate() {
+        let matches_team = stub
+            .get("predicates")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .any(|p| p.pointer("/equals/headers/x-team").is_some());
+        if !matches_team {
+            result.add_issue(
+                LintIssue::error("ORG002", "Stub doesn't match on x-team", file.to_path_buf())
+                    .with_location(format!("stubs[{idx}]")),
+            );
+        }
+    }
+}
+
+#[test]
+fn custom_rules_run_on_every_imposter() {
+    let options = opts()
+        .with_rule(KebabCaseNames)
+        .with_rule(requires_team_header);
+    let config = json!({ "imposters": [
+        { "port": 3000, "protocol": "http", "name": "Orders", "stubs": [minimal_stub()] },
+        { "port": 3001, "protocol": "http", "name": "users", "stubs": [{
+            "predicates": [{ "equals": { "headers": { "x-team": "payments" } } }],
+            "responses": [{ "is": { "statusCode": 200 } }]
+        }] }
+    ] });
+    let r = lint_json(
+        &serde_json::to_string_pretty(&config).unwrap(),
+        "team.json",
+        &options,
+    );
+    assert_eq!(codes(&r), ["ORG001", "ORG002"]);
+    assert_eq!(r.errors, 1);
+    assert_eq!(r.issues[0].file, Path::new("team.json"));
+    assert_eq!(r.issues[1].location.as_deref(), Some("stubs[0]"));
+    assert!(r.issues[1].span.is_some());
+}
+
+#[test]
+fn custom_rule_issues_can_be_ignored_and_configured() {
+    let options = opts().with_rule(KebabCaseNames);
+    let mut imposter = make_imposter(json!([minimal_stub()]));
+    imposter["name"] = json!("Orders");
+    imposter["x-rift-lint-ignore"] = json!(["ORG001"]);
+    let mut r = LintResult::new();
+    validate_imposter(path(), &imposter, &mut r, &options);
+    assert!(r.issues.is_empty(), "{:?}", codes(&r));
+
+    let config = LintConfig::parse("[rules]\nORG001 = \"error\"\n", path()).unwrap();
+    imposter
+        .as_object_mut()
+        .unwrap()
+        .remove("x-rift-lint-ignore");
+    let mut r = LintResult::new();
+    validate_imposter(path(), &imposter, &mut r, &options);
+    let r = config.apply(r);
+    assert_eq!(r.errors, 1, "{:?}", codes(&r));
+
+    for code in ["org001", "ORG01", "001", "ÉRG001"] {
+        assert!(
+            LintConfig::parse(&format!("[rules]\n\"{code}\" = \"off\"\n"), path()).is_err(),
+            "{code}"
+        );
+    }
+}
+
+// ─── Duplicate stubs ─────────────────────────────────────────────────────────
+
+#[test]
+fn w034_exact_duplicate_stub() {
+    let users = stub_matching(json!([{ "equals": { "path": "/users" } }]));
+    let stubs = json!([
+        users.clone(),
+        stub_matching(json!([{ "equals": { "path": "/orders" } }])),
+        users
+    ]);
+    let r = lint_value(&make_imposter(stubs), "<test>", &opts());
+    assert_eq!(codes(&r), ["W034"], "and not W018 as well");
+    assert_eq!(r.issues[0].location.as_deref(), Some("stubs[2]"));
+    assert_eq!(r.issues[0].message, "Stub is a duplicate of stub 0");
+}
+
+#[test]
+fn w034_same_stub_apart_from_links_and_predicate_order() {
+    let stubs = json!([
+        {
+            "predicates": [{ "equals": { "method": "GET" } }, { "equals": { "path": "/users" } }],
+            "responses": [{ "is": { "statusCode": 200, "body": "[]" } }],
+            "_links": { "self": { "href": "http://localhost:2525/imposters/4545/stubs/0" } }
+        },
+        {
+            "responses": [{ "is": { "body": "[]", "statusCode": 200 } }],
+            "predicates": [{ "equals": { "path": "/users" } }, { "equals": { "method": "GET" } }],
+            "_links": { "self": { "href": "http://localhost:2525/imposters/4545/stubs/1" } }
+        }
+    ]);
+    let r = lint_value(&make_imposter(stubs), "<test>", &opts());
+    assert_eq!(codes(&r), ["W034"]);
+    assert!(
+        r.issues[0].message.contains("apart from"),
+        "{}",
+        r.issues[0].message
+    );
+}
+
+#[test]
+fn w034_ignores_stubs_that_differ() {
+    let stubs = json!([
+        stub_matching(json!([{ "equals": { "path": "/users" } }])),
+        stub_matching(json!([{ "equals": { "path": "/users/1" } }])),
+        stub_matching(json!([
+            { "equals": { "path": "/users" } },
+            { "equals": { "method": "POST" } }
+        ]))
+    ]);
+    let r = lint_value(&make_imposter(stubs), "<test>", &opts());
+    assert!(!has_code(&r, "W034"), "{:?}", codes(&r));
+}
+
+#[test]
+fn i005_stub_repeated_in_another_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let catch_all = json!({ "responses": [{ "is": { "statusCode": 404 } }] });
+    let shared = stub_matching(json!([{ "equals": { "path": "/health" } }]));
+    let mut first = make_imposter(json!([shared.clone(), catch_all.clone()]));
+    first["port"] = json!(4545);
+    let mut second = make_imposter(json!([
+        stub_matching(json!([{ "equals": { "path": "/orders" } }])),
+        shared,
+        catch_all
+    ]));
+    second["port"] = json!(4546);
+    let files = [
+        write_config(dir.path(), "users.json", &first),
+        write_config(dir.path(), "orders.json", &second),
+    ];
+
+    let r = lint_files(&files, &opts());
+    let repeated: Vec<_> = r.issues.iter().filter(|i| i.code == "I005").collect();
+    assert_eq!(
+        repeated.len(),
+        1,
+        "the catch-all isn't reported: {repeated:?}"
+    );
+    assert_eq!(repeated[0].file, files[1]);
+    assert_eq!(repeated[0].location.as_deref(), Some("stubs[1]"));
+    assert_eq!(
+        repeated[0].message,
+        "Stub is the same as stubs[0] of the imposter on port 4545 in users.json"
+    );
+}
+
+// ─── Issue spans ─────────────────────────────────────────────────────────────
+
+#[test]
+fn spans_point_at_the_offending_value() {
+    let config = r#"{
+  "imposters": [
+    {
+      "port": 3000,
+      "protocol": "http",
+      "stubs": [{ "responses": [{ "is": { "statusCode": 99 } }] }]
+    }
+  ]
+}"#;
+    let r = lint_json(config, "config.json", &opts());
+    let issue = r.issues.iter().find(|i| i.code == "E015").expect("E015");
+    let span = issue.span.expect("span");
+    assert_eq!((span.line, span.column), (6, 57));
+    assert_eq!(&config[span.start..span.end], "99");
+}
+
+#[test]
+fn spans_in_a_root_array_config() {
+    let config = "[\n  { \"port\": 70000, \"protocol\": \"http\", \"stubs\": [] }\n]";
+    let r = lint_json(config, "config.json", &opts());
+    let issue = r.issues.iter().find(|i| i.code == "E005").expect("E005");
+    let span = issue.span.expect("span");
+    assert_eq!((span.line, span.column), (2, 13));
+    assert_eq!(&config[span.start..span.end], "70000");
+}
+
+#[test]
+fn spans_fall_back_to_the_enclosing_value() {
+    let config = "{\n  \"port\": 3000,\n  \"stubs\": []\n}";
+    let r = lint_json(config, "config.json", &opts());
+    let issue = r.issues.iter().find(|i| i.code == "E003").expect("E003");
+    let span = issue.span.expect("span");
+    assert_eq!((span.line, span.column, span.start), (1, 1, 0));
+    assert_eq!(span.end, config.len());
+}
+
+#[test]
+fn span_columns_count_characters() {
+    let config = r#"{ "name": "café", "port": 0, "protocol": "http", "stubs": [] }"#;
+    let r = lint_json(config, "config.json", &opts());
+    let span = r
+        .issues
+        .iter()
+        .find(|i| i.code == "E005")
+        .unwrap()
+        .span
+        .unwrap();
+    assert_eq!(span.column, 27);
+    assert_eq!(&config[span.start..span.end], "0");
+}
+
+#[test]
+fn no_spans_for_yaml_rendered_ejs_or_values() {
+    let dir = tempfile::tempdir().unwrap();
+    let yaml = dir.path().join("imposters.yaml");
+    std::fs::write(&yaml, "- port: 0\n  protocol: http\n  stubs: []\n").unwrap();
+    let r = lint_file(&yaml, &opts());
+    assert!(has_code(&r, "E005"), "{:?}", codes(&r));
+    assert!(r.issues.iter().all(|i| i.span.is_none()));
+
+    let ejs = dir.path().join("imposters.ejs");
+    std::fs::write(
+        &ejs,
+        r#"{ "imposters": [{ "port": 0, "protocol": "http", "stubs": [] }] }<% /* x */ %>"#,
+    )
+    .unwrap();
+    let r = lint_file(&ejs, &opts());
+    assert!(has_code(&r, "E005"), "{:?}", codes(&r));
+    assert!(
+        r.issues
+            .iter()
+            .filter(|i| i.code == "E005")
+            .all(|i| i.span.is_none())
+    );
+
+    let value = json!({ "port": 0, "protocol": "http", "stubs": [] });
+    let r = lint_value(&value, "config.json", &opts());
+    assert!(r.issues.iter().all(|i| i.span.is_none()));
+}
@@ -0,0 +1,79 @@
+//! Criterion bench for the proxy recording store under concurrent `proxyAlways` load.
+//!
+//! Every proxied request in `proxyAlways` mode records its response, and `proxyOnce` replays read
+//! it back, so `record` and `get_recorded` run once per request from every connection task. One
+//! iteration is a burst of 10,000 such requests spread over [`THREADS`] threads and 1,000 distinct
+//! signatures; criterion's element throughput is then requests per second, to be read against the
+//! 10k rps a load test drives.
+//!
+//! `shards/1` puts the whole store behind one lock (the layout before sharding); `shards/32` is
+//! the default.
+
+use std::sync::Arc;
+
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use rift_mock_core::recording::{ProxyMode, RecordedResponse, RecordingStore, RequestSignature};
+
+/// Requests per iteration.
+const REQUESTS: usize = 10_000;
+/// Concurrent request threads.
+const THREADS: usize = 8;
+/// Distinct request signatures the requests spread over.
+const SIGNATURES: usize = 1_000;
+
+fn signatures() -> Vec<RequestSignature> {
+    (0..SIGNATURES)
+        .map(|i| {
+            RequestSignature::new(
+                "GET",
+                &format!("/orders/{i}"),
+                Some("expand=items"),
+                &[("accept".to_string(), "application/json".to_string())],
+            )
+        })
+        .collect()
+}
+
+fn response() -> RecordedResponse {
+    RecordedResponse {
+        status: 200,
+        headers: vec![("content-type".to_string(), "application/json".to_string())],
+        body: br#"{"id":42,"status":"shipped","items":[1,2,3]}"#.to_vec(),
+        latency_ms: Some(12),
+        timestamp_secs: 0,
+        body_file: None,
+    }
+}
+
+/// One burst: each thread records and replays its share of the requests.
+fn burst(store: &RecordingStore, signatures: &[RequestSignature], response: &RecordedResponse) {
+    std::thread::scope(|scope| {
+        for thread in 0..THREADS {
+            scope.spawn(move || {
+                for request in (thread..REQUESTS).step_by(THREADS) {
+                    let signature = &signatures[request % SIGNATURES];
+                    store.record(signature.clone(), response.clone());
+                    black_box(store.get_recorded(signature));
+                }
+            });
+        }
+    });
+}
+
+fn bench_recording_store(c: &mut Criterion) {
+    let signatures = signatures();
+    let response = response();
+
+    let mut group = c.benchmark_group("recording_store_proxy_always");
+    group.throughput(Throughput::Elements(REQUESTS as u64));
+    for shards in [1, 32] {
+        let store = Arc::new(RecordingStore::new(ProxyMode::ProxyAlways).with_shards(shards));
+        group.bench_with_input(BenchmarkId::new("shards", shards), &store, |b, store| {
+            b.iter(|| burst(store, &signatures, &response))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_recording_store);
+criterion_main!(benches);
@@ -29,6 +29,7 @@ fn fixture() -> (IsResponse, Arc<str>) {
         status_code: 200,
         headers,
         body: Some(body),
+        body_file: None,
         mode: ResponseMode::Text,
     };
     (is, rendered)
@@ -263,6 +263,17 @@ return v
         true
     }
 
+    fn ping(&self) -> Result<()> {
+        let conn = self
+            .pool
+            .get()
+            .map_err(|e| backend_err("flowStore.pool", e))?;
+        let _: String = redis::cmd("PING")
+            .query(&mut *lock_recover(&conn))
+            .map_err(|e| backend_err("flowStore.ping", e))?;
+        Ok(())
+    }
+
     /// Single-round-trip atomic CAS via a server-side Lua script (issue #311): compare
     /// and SETEX happen inside one EVAL, so no WATCH/MULTI and no interleaving window.
     /// Values compare as their canonical JSON strings — the same encoding `set` writes.
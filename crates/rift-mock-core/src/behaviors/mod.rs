@@ -5,12 +5,15 @@
 //!
 //! # Supported Behaviors
 //!
-//! - `wait` - Add latency before response (fixed ms or {min, max} range)
+//! - `wait` - Add latency before response (fixed ms, {min, max} range, or {mean, jitter})
 //! - `repeat` - Repeat response N times before cycling to next
 //! - `copy` - Copy request fields into response using regex/jsonpath/xpath
 //! - `lookup` - Query external CSV data source
 //! - `shellTransform` - External program transforms response
 //! - `decorate` - Rhai script to post-process response
+//!
+//! `_behaviors` is an object (applied in the fixed order wait → copy → lookup → decorate →
+//! shellTransform) or an array of behavior objects applied in the listed order.
 
 // Allow dead code for now as behaviors are designed for future integration
 #![allow(dead_code)]
@@ -44,10 +47,11 @@ pub use lookup::{
 };
 pub use request::{RequestContext, header_to_title_case};
 pub use sequencer::{LocalSequencer, ResponseSequencer, SequenceKey};
+pub(crate) use transform::apply_decorate_within;
 pub use transform::{
-    DecorateError, apply_decorate, apply_shell_transform, is_js_config_decorate,
-    rewrite_js_config_to_rhai,
+    DecorateError, apply_decorate, apply_decorate_with_stub, apply_shell_transform,
+    is_js_config_decorate, rewrite_js_config_to_rhai,
 };
-pub use types::ResponseBehaviors;
+pub use types::{BehaviorStep, ResponseBehaviors};
 #[allow(unused_imports)]
 pub use wait::WaitBehavior;
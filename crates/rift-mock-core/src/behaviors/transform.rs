@@ -1,7 +1,11 @@
 //! Shell transform and decorate behaviors.
 
 use super::request::RequestContext;
+use crate::extensions::StubMetadata;
+use crate::scripting::ScriptLimits;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Error executing a `decorate` behavior script.
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
@@ -15,6 +19,9 @@ pub enum DecorateError {
     /// The script exceeded its wall-clock deadline on the bounded execution path (issue #476).
     #[error("decorate script timed out after {0}ms")]
     Timeout(u64),
+    /// The Rhai script hit its operation or memory limit (see [`ScriptLimits`]).
+    #[error("decorate script exceeded its {0}")]
+    Limit(String),
 }
 
 /// Execute shell transform command
@@ -115,10 +122,62 @@ pub fn apply_decorate(
     response_body: &str,
     response_status: u16,
     response_headers: &mut HashMap<String, String>,
+) -> Result<(String, u16), DecorateError> {
+    apply_decorate_with_stub(
+        script,
+        request,
+        response_body,
+        response_status,
+        response_headers,
+        None,
+    )
+}
+
+/// [`apply_decorate`] for a stub-served response: `stub` (`#{ id, index }`) and `imposter`
+/// (`#{ port }`) are also in scope, so the script can report which stub served the request.
+pub fn apply_decorate_with_stub(
+    script: &str,
+    request: &RequestContext,
+    response_body: &str,
+    response_status: u16,
+    response_headers: &mut HashMap<String, String>,
+    stub: Option<&StubMetadata>,
+) -> Result<(String, u16), DecorateError> {
+    apply_decorate_within(
+        script,
+        request,
+        response_body,
+        response_status,
+        response_headers,
+        stub,
+        &ScriptLimits::default(),
+        None,
+    )
+}
+
+/// [`apply_decorate_with_stub`] under the imposter's `limits`, stopping as soon as `abort` is set
+/// (by the caller's wall-clock deadline), so a runaway script doesn't keep its thread.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn apply_decorate_within(
+    script: &str,
+    request: &RequestContext,
+    response_body: &str,
+    response_status: u16,
+    response_headers: &mut HashMap<String, String>,
+    stub: Option<&StubMetadata>,
+    limits: &ScriptLimits,
+    abort: Option<&Arc<AtomicBool>>,
 ) -> Result<(String, u16), DecorateError> {
     use rhai::{Dynamic, Engine, Map, Scope};
 
-    let engine = Engine::new();
+    let mut engine = Engine::new();
+    // base64/URL/SHA-256/HMAC/JWT helpers, so a decorate can sign or inspect without shelling out.
+    crate::extensions::encoding::register_rhai_fns(&mut engine);
+    limits.apply_to_rhai(&mut engine);
+    if let Some(abort) = abort {
+        let abort = Arc::clone(abort);
+        engine.on_progress(move |_ops| abort.load(Ordering::Relaxed).then_some(Dynamic::TRUE));
+    }
     let mut scope = Scope::new();
 
     // Create request map for Rhai
@@ -155,6 +214,18 @@ pub fn apply_decorate(
 
     scope.push("request", req_map);
     scope.push("response", resp_map);
+    if let Some(stub) = stub {
+        let mut stub_map = Map::new();
+        stub_map.insert(
+            "id".into(),
+            stub.id.clone().map_or(Dynamic::UNIT, Dynamic::from),
+        );
+        stub_map.insert("index".into(), Dynamic::from(stub.index as i64));
+        let mut imposter_map = Map::new();
+        imposter_map.insert("port".into(), Dynamic::from(i64::from(stub.imposter_port)));
+        scope.push("stub", stub_map);
+        scope.push("imposter", imposter_map);
+    }
 
     // Execute the decoration script
     match engine.eval_with_scope::<Dynamic>(&mut scope, script) {
@@ -189,7 +260,10 @@ pub fn apply_decorate(
                 _ => Ok((response_body.to_string(), response_status)),
             }
         }
-        Err(e) => Err(DecorateError::Rhai(e.to_string())),
+        Err(e) => Err(match limits.exceeded_by(&e) {
+            Some(exceeded) => DecorateError::Limit(exceeded.limit),
+            None => DecorateError::Rhai(e.to_string()),
+        }),
     }
 }
 
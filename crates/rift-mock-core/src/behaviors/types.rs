@@ -48,6 +48,207 @@ pub struct ResponseBehaviors {
     /// Script receives `request` and `response` variables and can modify response
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub decorate: Option<String>,
+
+    /// Array form (`"_behaviors": [{"copy": ...}, {"decorate": ...}]`): the entries in their
+    /// listed order, applied in that order by [`Self::steps`]. Empty for the object form, which
+    /// applies in the fixed default order. The fields above still hold the merged view (later
+    /// entries win for single-valued behaviors; lists concatenate) for callers that only ask
+    /// *whether* a behavior is configured. Built by [`Self::from_json`], never by serde.
+    #[serde(skip)]
+    pub ordered: Vec<ResponseBehaviors>,
+}
+
+/// One behavior application, as yielded by [`ResponseBehaviors::steps`].
+#[derive(Debug, Clone, Copy)]
+pub enum BehaviorStep<'a> {
+    Wait(&'a WaitBehavior),
+    Copy(&'a [CopyBehavior]),
+    Lookup(&'a [LookupBehavior]),
+    Decorate(&'a str),
+    ShellTransform(&'a [String]),
+}
+
+impl ResponseBehaviors {
+    /// Parse a `_behaviors` value in either form: an object (today's default — behaviors apply in
+    /// the fixed order wait → copy → lookup → decorate → shellTransform) or an array of
+    /// behavior objects applied in the listed order.
+    pub fn from_json(value: &serde_json::Value) -> Result<Self, serde_json::Error> {
+        use serde::de::Error;
+
+        let serde_json::Value::Array(entries) = value else {
+            return Self::deserialize(value);
+        };
+        let ordered = entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| {
+                if entry.is_object() {
+                    Self::deserialize(entry)
+                } else {
+                    Err(serde_json::Error::custom(format!(
+                        "_behaviors[{idx}] must be an object like {{\"wait\": 100}}"
+                    )))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut merged = Self::default();
+        for entry in &ordered {
+            merged.wait = entry.wait.clone().or(merged.wait);
+            merged.repeat = entry.repeat.or(merged.repeat);
+            merged.copy.extend(entry.copy.iter().cloned());
+            merged.lookup.extend(entry.lookup.iter().cloned());
+            merged
+                .shell_transform
+                .extend(entry.shell_transform.iter().cloned());
+            merged.decorate = entry.decorate.clone().or(merged.decorate);
+        }
+        merged.ordered = ordered;
+        Ok(merged)
+    }
+
+    /// The behaviors to apply, in application order: the listed order for the array form, the
+    /// fixed default order for the object form. `repeat` is not a step — it drives response
+    /// cycling, not response content.
+    pub fn steps(&self) -> Vec<BehaviorStep<'_>> {
+        if self.ordered.is_empty() {
+            self.default_order_steps()
+        } else {
+            self.ordered
+                .iter()
+                .flat_map(Self::default_order_steps)
+                .collect()
+        }
+    }
+
+    /// This block with the imposter-level `defaultBehaviors` filled in: every behavior type this
+    /// block sets itself (`wait`, `copy`, `lookup`, `decorate`, `shellTransform`) overrides the
+    /// default of that type; the rest are inherited.
+    ///
+    /// When both are objects the result is one merged object (fixed default order). When either
+    /// is an ordered array, the inherited wait/copy/lookup run before this block's own steps and
+    /// the inherited decorate/shellTransform after them — the positions the fixed order gives
+    /// them — while this block's listed order is kept as written.
+    #[must_use]
+    pub fn with_defaults(&self, defaults: &ResponseBehaviors) -> ResponseBehaviors {
+        let mut merged = self.clone();
+        if self.ordered.is_empty() && defaults.ordered.is_empty() {
+            merged.fill_from(&defaults.inherited_by(self));
+            return merged;
+        }
+
+        let default_entries: Vec<&ResponseBehaviors> = if defaults.ordered.is_empty() {
+            vec![defaults]
+        } else {
+            defaults.ordered.iter().collect()
+        };
+        let mut before = Vec::new();
+        let mut after = Vec::new();
+        for entry in default_entries {
+            let (pre, post) = entry.inherited_by(self).split_around_content();
+            before.extend(pre);
+            after.extend(post);
+        }
+        for entry in before.iter().chain(&after) {
+            merged.fill_from(entry);
+        }
+        let own: Vec<ResponseBehaviors> = if self.ordered.is_empty() {
+            vec![ResponseBehaviors {
+                ordered: Vec::new(),
+                ..self.clone()
+            }]
+        } else {
+            self.ordered.clone()
+        };
+        merged.ordered = before.into_iter().chain(own).chain(after).collect();
+        merged
+    }
+
+    /// The part of this (default) entry that `overrides` does not replace. `repeat` is never
+    /// inherited: it drives response cycling, which defaults do not take part in.
+    fn inherited_by(&self, overrides: &ResponseBehaviors) -> ResponseBehaviors {
+        ResponseBehaviors {
+            wait: self.wait.clone().filter(|_| overrides.wait.is_none()),
+            repeat: None,
+            copy: if overrides.copy.is_empty() {
+                self.copy.clone()
+            } else {
+                Vec::new()
+            },
+            lookup: if overrides.lookup.is_empty() {
+                self.lookup.clone()
+            } else {
+                Vec::new()
+            },
+            shell_transform: if overrides.shell_transform.is_empty() {
+                self.shell_transform.clone()
+            } else {
+                Vec::new()
+            },
+            decorate: self
+                .decorate
+                .clone()
+                .filter(|_| overrides.decorate.is_none()),
+            ordered: Vec::new(),
+        }
+    }
+
+    /// Split into the behaviors that run before a response's own content behaviors (wait, copy,
+    /// lookup) and those that post-process it (decorate, shellTransform); empty halves dropped.
+    fn split_around_content(self) -> (Option<ResponseBehaviors>, Option<ResponseBehaviors>) {
+        let pre = ResponseBehaviors {
+            wait: self.wait,
+            copy: self.copy,
+            lookup: self.lookup,
+            ..Default::default()
+        };
+        let post = ResponseBehaviors {
+            decorate: self.decorate,
+            shell_transform: self.shell_transform,
+            ..Default::default()
+        };
+        let non_empty = |b: ResponseBehaviors| (!b.default_order_steps().is_empty()).then_some(b);
+        (non_empty(pre), non_empty(post))
+    }
+
+    /// Fill each behavior type this block leaves unset from `other` (the merged flat view).
+    fn fill_from(&mut self, other: &ResponseBehaviors) {
+        if self.wait.is_none() {
+            self.wait.clone_from(&other.wait);
+        }
+        if self.copy.is_empty() {
+            self.copy.clone_from(&other.copy);
+        }
+        if self.lookup.is_empty() {
+            self.lookup.clone_from(&other.lookup);
+        }
+        if self.shell_transform.is_empty() {
+            self.shell_transform.clone_from(&other.shell_transform);
+        }
+        if self.decorate.is_none() {
+            self.decorate.clone_from(&other.decorate);
+        }
+    }
+
+    fn default_order_steps(&self) -> Vec<BehaviorStep<'_>> {
+        let mut steps = Vec::new();
+        if let Some(wait) = &self.wait {
+            steps.push(BehaviorStep::Wait(wait));
+        }
+        if !self.copy.is_empty() {
+            steps.push(BehaviorStep::Copy(&self.copy));
+        }
+        if !self.lookup.is_empty() {
+            steps.push(BehaviorStep::Lookup(&self.lookup));
+        }
+        if let Some(decorate) = &self.decorate {
+            steps.push(BehaviorStep::Decorate(decorate));
+        }
+        if !self.shell_transform.is_empty() {
+            steps.push(BehaviorStep::ShellTransform(&self.shell_transform));
+        }
+        steps
+    }
 }
 
 /// Deserialize shellTransform accepting a single string or an array of strings.
@@ -250,4 +451,92 @@ decorate: "response.body = 'decorated';"
             Some("response.body = 'decorated';".to_string())
         );
     }
+
+    fn step_names(behaviors: &ResponseBehaviors) -> Vec<&'static str> {
+        behaviors
+            .steps()
+            .iter()
+            .map(|step| match step {
+                BehaviorStep::Wait(_) => "wait",
+                BehaviorStep::Copy(_) => "copy",
+                BehaviorStep::Lookup(_) => "lookup",
+                BehaviorStep::Decorate(_) => "decorate",
+                BehaviorStep::ShellTransform(_) => "shellTransform",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn object_form_applies_in_default_order() {
+        let behaviors = ResponseBehaviors::from_json(&serde_json::json!({
+            "decorate": "x",
+            "shellTransform": "cat",
+            "copy": {"from": "path", "into": "${P}", "using": {"method": "regex", "selector": ".*"}},
+            "wait": 10
+        }))
+        .unwrap();
+        assert_eq!(
+            step_names(&behaviors),
+            ["wait", "copy", "decorate", "shellTransform"]
+        );
+    }
+
+    #[test]
+    fn array_form_applies_in_listed_order_and_merges_flat_view() {
+        let copy = serde_json::json!({"from": "path", "into": "${P}", "using": {"method": "regex", "selector": ".*"}});
+        let behaviors = ResponseBehaviors::from_json(&serde_json::json!([
+            {"decorate": "first"},
+            {"copy": copy},
+            {"wait": 5, "repeat": 2},
+            {"copy": [copy]},
+            {"decorate": "second"}
+        ]))
+        .unwrap();
+        assert_eq!(
+            step_names(&behaviors),
+            ["decorate", "copy", "wait", "copy", "decorate"]
+        );
+        assert_eq!(behaviors.repeat, Some(2));
+        assert_eq!(behaviors.copy.len(), 2);
+        assert_eq!(behaviors.decorate.as_deref(), Some("second"));
+    }
+
+    #[test]
+    fn array_form_rejects_non_object_entries() {
+        let err = ResponseBehaviors::from_json(&serde_json::json!([{"wait": 1}, 500])).unwrap_err();
+        assert!(err.to_string().contains("_behaviors[1]"), "{err}");
+    }
+
+    #[test]
+    fn defaults_fill_unset_types_and_stub_values_override() {
+        let defaults = ResponseBehaviors::from_json(&serde_json::json!({
+            "wait": 50, "decorate": "standard", "repeat": 4
+        }))
+        .unwrap();
+        let stub = ResponseBehaviors::from_json(&serde_json::json!({"wait": 5})).unwrap();
+        let merged = stub.with_defaults(&defaults);
+        assert!(matches!(merged.wait, Some(WaitBehavior::Fixed(5))));
+        assert_eq!(merged.decorate.as_deref(), Some("standard"));
+        assert_eq!(merged.repeat, None, "repeat is never inherited");
+        assert_eq!(step_names(&merged), ["wait", "decorate"]);
+    }
+
+    #[test]
+    fn defaults_wrap_an_ordered_stub_block() {
+        let defaults = ResponseBehaviors::from_json(&serde_json::json!({
+            "wait": 50, "decorate": "standard"
+        }))
+        .unwrap();
+        let copy = serde_json::json!({"from": "path", "into": "${P}", "using": {"method": "regex", "selector": ".*"}});
+        let stub = ResponseBehaviors::from_json(&serde_json::json!([
+            {"shellTransform": "cat"},
+            {"copy": copy}
+        ]))
+        .unwrap();
+        let merged = stub.with_defaults(&defaults);
+        assert_eq!(
+            step_names(&merged),
+            ["wait", "shellTransform", "copy", "decorate"]
+        );
+    }
 }
@@ -1,5 +1,7 @@
 //! Wait behavior - add latency before response.
 
+use crate::extensions::random::ImposterRng;
+use rand::{Rng, RngCore};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::sync::LazyLock;
@@ -29,6 +31,15 @@ static WAIT_SOLO_MAX_RE: LazyLock<Regex> = LazyLock::new(|| {
         .expect("wait solo-max pattern is a valid constant regex")
 });
 
+/// mulberry32 as a JS factory: `(<this>)(seed)` yields a `Math.random` replacement returning
+/// `[0, 1)` floats from a 32-bit seed. Used only when the imposter has a `randomSeed`.
+#[cfg(feature = "javascript")]
+const SEEDED_MATH_RANDOM: &str = "function (a) { return function () { \
+    a |= 0; a = (a + 0x6D2B79F5) | 0; \
+    var t = Math.imul(a ^ (a >>> 15), 1 | a); \
+    t = (t + Math.imul(t ^ (t >>> 7), 61 | t)) ^ t; \
+    return ((t ^ (t >>> 14)) >>> 0) / 4294967296; }; }";
+
 /// Wait behavior - add latency before response
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(untagged)]
@@ -42,6 +53,19 @@ pub enum WaitBehavior {
         #[serde(rename = "max")]
         max_ms: u64,
     },
+    /// Random delay within `jitter` of `mean` — the latency distribution a proxy with
+    /// `recording.latencyJitter` records. `p50`, `p95` and `samples` describe the recorded
+    /// latencies; they round-trip but don't affect the delay.
+    Jitter {
+        mean: u64,
+        jitter: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        p50: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        p95: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        samples: Option<usize>,
+    },
     /// JavaScript function that returns delay — the Mountebank-compatible spelling.
     Function(String),
     /// The same JavaScript function in Rift's object spelling (issue #608), as written by
@@ -55,11 +79,19 @@ pub enum WaitBehavior {
 impl WaitBehavior {
     /// Get the wait duration in milliseconds
     pub fn get_duration_ms(&self) -> u64 {
+        self.get_duration_ms_with(&ImposterRng::unseeded())
+    }
+
+    /// Get the wait duration in milliseconds, drawing any randomness from `rng` — a seeded
+    /// imposter RNG (`randomSeed`) makes range and function waits reproducible.
+    pub fn get_duration_ms_with(&self, rng: &ImposterRng) -> u64 {
         match self {
             WaitBehavior::Fixed(ms) => *ms,
-            WaitBehavior::Range { min_ms, max_ms } => {
-                use rand::Rng;
-                rand::thread_rng().gen_range(*min_ms..=*max_ms)
+            WaitBehavior::Range { min_ms, max_ms } => rng.with(|r| r.gen_range(*min_ms..=*max_ms)),
+            WaitBehavior::Jitter { mean, jitter, .. } => {
+                let low = mean.saturating_sub(*jitter);
+                let high = mean.saturating_add(*jitter).max(low);
+                rng.with(|r| r.gen_range(low..=high)).min(MAX_WAIT_MS)
             }
             // Both spellings of a JS-function wait run the identical path (issue #608): same Boa
             // execution, same cap, same loud fallback.
@@ -70,7 +102,7 @@ impl WaitBehavior {
                 // distinguishable from a genuine 100ms wait (B4, issue #355).
                 // Cap here (issue #490) so both the Boa path and the no-`javascript` regex
                 // fallback share the one bound — the fallback used to return the raw parsed value.
-                Self::execute_js_wait_function(js_func)
+                Self::execute_js_wait_function(js_func, rng)
                     .map(|ms| ms.min(MAX_WAIT_MS))
                     .unwrap_or_else(|| {
                         tracing::warn!(
@@ -90,7 +122,7 @@ impl WaitBehavior {
     /// `Math.random` shapes — so any wait function (not just the ones the old regex recognized)
     /// produces a correct value. Without the feature, falls back to the original regex-based
     /// extraction so `--no-default-features` still builds and works for the common patterns.
-    fn execute_js_wait_function(js_func: &str) -> Option<u64> {
+    fn execute_js_wait_function(js_func: &str, rng: &ImposterRng) -> Option<u64> {
         let trimmed = js_func.trim();
         if !trimmed.starts_with("function") {
             return None;
@@ -98,20 +130,29 @@ impl WaitBehavior {
 
         #[cfg(feature = "javascript")]
         {
-            if let Some(ms) = Self::execute_js_wait_function_boa(trimmed) {
+            if let Some(ms) = Self::execute_js_wait_function_boa(trimmed, rng.derive_seed()) {
                 return Some(ms);
             }
         }
 
-        Self::execute_js_wait_function_regex(trimmed)
+        rng.with(|r| Self::execute_js_wait_function_regex(trimmed, r))
     }
 
     /// Run the wait function body for real on a bounded Boa `Context`: evaluate `(<js_func>)()`
     /// and coerce the numeric result to a `u64` delay, floored and capped at 60s so a
     /// pathological/negative/huge result can't turn into an enormous or nonsensical sleep.
+    ///
+    /// With a `seed` (the imposter has a `randomSeed`), `Math.random` is replaced by a small
+    /// seeded generator (mulberry32) before the function runs, so its delay is reproducible.
     #[cfg(feature = "javascript")]
-    fn execute_js_wait_function_boa(js_func: &str) -> Option<u64> {
+    fn execute_js_wait_function_boa(js_func: &str, seed: Option<u32>) -> Option<u64> {
         let mut context = crate::scripting::bounded_js_context();
+        if let Some(seed) = seed {
+            let prelude = format!("Math.random = ({SEEDED_MATH_RANDOM})({seed});");
+            if let Err(e) = context.eval(boa_engine::Source::from_bytes(prelude.as_bytes())) {
+                tracing::warn!(target: "rift::script", "failed to seed Math.random: {e}");
+            }
+        }
         let wrapped = format!("({js_func})()");
         // A script/runtime error must not be swallowed silently (B4, issue #355): log it, then
         // return None so the caller's regex safety net / 100ms fallback still applies.
@@ -145,12 +186,12 @@ impl WaitBehavior {
 
     /// Regex-based fallback: used as the sole path when the `javascript` feature is disabled,
     /// and as a safety net if the Boa path above fails to produce a value while it's enabled.
-    fn execute_js_wait_function_regex(trimmed: &str) -> Option<u64> {
+    fn execute_js_wait_function_regex(trimmed: &str, rng: &mut dyn RngCore) -> Option<u64> {
         if let Some(body) = extract_function_body(trimmed) {
             // Handle Solo pattern:
             // var min = Math.ceil(N); var max = Math.floor(M); var num = Math.floor(Math.random() * (max - min + 1)); var wait = (num + min); return wait;
             if body.contains("var min") && body.contains("var max") {
-                return Self::parse_solo_wait_pattern(&body);
+                return Self::parse_solo_wait_pattern(&body, rng);
             }
 
             // Look for patterns like "Math.floor(Math.random() * 100) + 50"
@@ -162,18 +203,17 @@ impl WaitBehavior {
 
             // Parse: Math.floor(Math.random() * N) + M
             if body.contains("Math.random()") {
-                use rand::Rng;
                 // Extract multiplier and offset using the cached constant patterns.
                 if let Some(caps) = WAIT_FLOOR_OFFSET_RE.captures(&body) {
                     let range = caps.get(1)?.as_str().parse::<u64>().ok()?;
                     let offset = caps.get(2)?.as_str().parse::<u64>().ok()?;
-                    return Some(rand::thread_rng().gen_range(offset..=offset + range));
+                    return Some(rng.gen_range(offset..=offset + range));
                 }
 
                 // Simpler pattern: Math.random() * N
                 if let Some(caps) = WAIT_RANDOM_RE.captures(&body) {
                     let range = caps.get(1)?.as_str().parse::<u64>().ok()?;
-                    return Some(rand::thread_rng().gen_range(0..=range));
+                    return Some(rng.gen_range(0..=range));
                 }
             }
 
@@ -188,9 +228,7 @@ impl WaitBehavior {
 
     /// Parse Solo wait pattern:
     /// var min = Math.ceil(N); var max = Math.floor(M); var num = Math.floor(Math.random() * (max - min + 1)); var wait = (num + min); return wait;
-    fn parse_solo_wait_pattern(body: &str) -> Option<u64> {
-        use rand::Rng;
-
+    fn parse_solo_wait_pattern(body: &str, rng: &mut dyn RngCore) -> Option<u64> {
         // Extract min value: var min = Math.ceil(N)
         let min_val = WAIT_SOLO_MIN_RE
             .captures(body)
@@ -207,7 +245,7 @@ impl WaitBehavior {
 
         // Generate random value in range [min, max]
         if max_val >= min_val {
-            Some(rand::thread_rng().gen_range(min_val..=max_val))
+            Some(rng.gen_range(min_val..=max_val))
         } else {
             Some(min_val)
         }
@@ -260,6 +298,10 @@ mod tests {
         let range: WaitBehavior = serde_json::from_str(r#"{"min":1,"max":2}"#).expect("range");
         assert!(matches!(range, WaitBehavior::Range { .. }));
 
+        let jitter: WaitBehavior =
+            serde_json::from_str(r#"{"mean":1,"jitter":2}"#).expect("jitter");
+        assert!(matches!(jitter, WaitBehavior::Jitter { .. }));
+
         // A wait object that is neither shape is still rejected — the new variant must not turn
         // the enum into a catch-all that silently accepts nonsense.
         assert!(serde_json::from_str::<WaitBehavior>(r#"{"bogus":true}"#).is_err());
@@ -301,6 +343,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn wait_jitter_round_trips_its_stats_and_stays_within_the_band() {
+        let raw = r#"{"mean":120,"jitter":30,"p50":110,"p95":170,"samples":12}"#;
+        let wait: WaitBehavior = serde_json::from_str(raw).expect("jitter");
+        assert_eq!(
+            serde_json::to_value(&wait).unwrap(),
+            serde_json::from_str::<serde_json::Value>(raw).unwrap()
+        );
+        for _ in 0..20 {
+            assert!((90..=150).contains(&wait.get_duration_ms()));
+        }
+
+        let below_zero = WaitBehavior::Jitter {
+            mean: 10,
+            jitter: 50,
+            p50: None,
+            p95: None,
+            samples: None,
+        };
+        assert!(below_zero.get_duration_ms() <= 60);
+    }
+
     #[test]
     fn test_wait_behavior_fixed() {
         let wait = WaitBehavior::Fixed(100);
@@ -398,21 +462,25 @@ mod tests {
     // fires and the patterns still recognize each shape.
     #[test]
     fn wait_regex_fallback_parses_all_patterns() {
+        let mut rng = rand::thread_rng();
         // Math.floor(Math.random() * N) + M
         let floor = WaitBehavior::execute_js_wait_function_regex(
             "function() { return Math.floor(Math.random() * 100) + 50; }",
+            &mut rng,
         );
         assert!(matches!(floor, Some(d) if (50..=150).contains(&d)));
 
         // Math.random() * N (no offset)
         let random = WaitBehavior::execute_js_wait_function_regex(
             "function() { return Math.floor(Math.random() * 30); }",
+            &mut rng,
         );
         assert!(matches!(random, Some(d) if d <= 30));
 
         // Solo pattern (var min = Math.ceil(N); var max = Math.floor(M); ...)
         let solo = WaitBehavior::execute_js_wait_function_regex(
             "function() { var min = Math.ceil(50); var max = Math.floor(100); var num = Math.floor(Math.random() * (max - min + 1)); var wait = (num + min); return wait; }",
+            &mut rng,
         );
         assert!(matches!(solo, Some(d) if (50..=100).contains(&d)));
 
@@ -421,8 +489,29 @@ mod tests {
         // get_duration_ms boundary (see wait_function_huge_value_is_capped, which exercises this
         // fallback under --no-default-features).
         assert_eq!(
-            WaitBehavior::execute_js_wait_function_regex("function() { return -5; }"),
+            WaitBehavior::execute_js_wait_function_regex("function() { return -5; }", &mut rng),
             Some(0)
         );
     }
+
+    #[test]
+    fn seeded_waits_replay_identically() {
+        let waits = [
+            WaitBehavior::Range {
+                min_ms: 0,
+                max_ms: 10_000,
+            },
+            WaitBehavior::Function(
+                "function() { return Math.floor(Math.random() * 10000); }".to_string(),
+            ),
+        ];
+        let run = |seed| {
+            let rng = ImposterRng::new(Some(seed));
+            (0..5)
+                .flat_map(|_| waits.iter().map(|w| w.get_duration_ms_with(&rng)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(run(7), run(7));
+        assert_ne!(run(7), run(8));
+    }
 }
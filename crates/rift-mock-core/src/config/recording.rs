@@ -1,7 +1,8 @@
 //! Recording configuration for proxy record/replay.
 
-use crate::recording::ProxyMode;
+use crate::recording::{EvictionPolicy, ProxyMode};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Recording configuration for proxy record/replay (Mountebank-compatible)
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
@@ -22,6 +23,27 @@ pub struct RecordingConfig {
     /// Persistence configuration for recordings
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub persistence: Option<RecordingPersistence>,
+
+    /// Most distinct requests to keep recordings for; recording a new one past this evicts the
+    /// least recently recorded or replayed. Unset keeps the built-in cap of 10,000, past which new
+    /// requests are not recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_entries: Option<usize>,
+
+    /// Evict a request's recordings once they have gone this many seconds without being recorded
+    /// or replayed. Unset keeps them for the life of the proxy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_seconds: Option<u64>,
+}
+
+impl RecordingConfig {
+    /// The recording store's eviction policy.
+    pub fn eviction_policy(&self) -> EvictionPolicy {
+        EvictionPolicy {
+            max_entries: self.max_entries,
+            ttl: self.ttl_seconds.map(Duration::from_secs),
+        }
+    }
 }
 
 /// Predicate generator for auto-generating stubs from recorded requests
@@ -0,0 +1,261 @@
+//! Encoding and crypto helpers shared by `{{ }}` templating and decorate scripts.
+//!
+//! Mocking a signature-verifying service needs the same handful of primitives everywhere: base64,
+//! URL encoding, SHA-256, HMAC-SHA256, and reading a JWT's claims. They live here once and are
+//! exposed as `{{ }}` filters (`template_fn`), Rhai functions (`register_rhai_fns`, used by the
+//! Rhai decorate engine and `_rift.script`), and the JS `rift` helper object (`js_engine`), so the
+//! three surfaces cannot disagree on, say, padding or hex case.
+//!
+//! JWT support is decode-only: the signature is never verified — a mock reads claims, it does not
+//! authenticate anyone.
+
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use serde_json::Value;
+
+/// Standard (padded) base64 of `input`.
+#[must_use]
+pub fn base64_encode(input: &[u8]) -> String {
+    STANDARD.encode(input)
+}
+
+/// Decode standard or URL-safe base64, padded or not, into a UTF-8 string (invalid UTF-8 is
+/// replaced lossily — the result is destined for a text body or header).
+pub fn base64_decode(input: &str) -> Result<String, String> {
+    decode_base64_bytes(input).map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn decode_base64_bytes(input: &str) -> Result<Vec<u8>, String> {
+    // Normalize the URL-safe alphabet and strip padding so one decoder accepts every variant.
+    let normalized: String = input
+        .trim()
+        .trim_end_matches('=')
+        .chars()
+        .map(|c| match c {
+            '+' => '-',
+            '/' => '_',
+            c => c,
+        })
+        .collect();
+    URL_SAFE_NO_PAD
+        .decode(normalized.as_bytes())
+        .map_err(|e| format!("invalid base64: {e}"))
+}
+
+/// Percent-encode `input` for use in a URL component.
+#[must_use]
+pub fn url_encode(input: &str) -> String {
+    urlencoding::encode(input).into_owned()
+}
+
+/// Decode a percent-encoded string (`+` is left as-is, matching `decodeURIComponent`).
+pub fn url_decode(input: &str) -> Result<String, String> {
+    urlencoding::decode(input)
+        .map(std::borrow::Cow::into_owned)
+        .map_err(|e| format!("invalid url encoding: {e}"))
+}
+
+/// Lowercase hex SHA-256 digest of `input`.
+#[must_use]
+pub fn sha256_hex(input: &[u8]) -> String {
+    to_hex(ring::digest::digest(&ring::digest::SHA256, input).as_ref())
+}
+
+/// Raw HMAC-SHA256 of `message` under `key`.
+#[must_use]
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, key);
+    ring::hmac::sign(&key, message).as_ref().to_vec()
+}
+
+/// How a binary digest is rendered as text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestEncoding {
+    Hex,
+    Base64,
+    Base64Url,
+}
+
+impl DigestEncoding {
+    /// Parse `hex` / `base64` / `base64url` (the only spellings the template filter accepts).
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "hex" => Ok(Self::Hex),
+            "base64" => Ok(Self::Base64),
+            "base64url" => Ok(Self::Base64Url),
+            other => Err(format!(
+                "unknown digest encoding '{other}' (expected hex, base64, or base64url)"
+            )),
+        }
+    }
+
+    #[must_use]
+    pub fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            Self::Hex => to_hex(bytes),
+            Self::Base64 => STANDARD.encode(bytes),
+            Self::Base64Url => URL_SAFE_NO_PAD.encode(bytes),
+        }
+    }
+}
+
+/// HMAC-SHA256 of `message` under `key`, rendered with `encoding`.
+#[must_use]
+pub fn hmac_sha256_encoded(key: &str, message: &str, encoding: DigestEncoding) -> String {
+    encoding.encode(&hmac_sha256(key.as_bytes(), message.as_bytes()))
+}
+
+/// Decode a compact JWT (`header.payload.signature`) WITHOUT verifying the signature, returning
+/// `{"header": {...}, "payload": {...}}`. A leading `Bearer ` is tolerated so an `Authorization`
+/// header value can be passed straight through.
+pub fn jwt_decode(token: &str) -> Result<Value, String> {
+    let token = token.trim();
+    let token = token
+        .strip_prefix("Bearer ")
+        .or_else(|| token.strip_prefix("bearer "))
+        .unwrap_or(token)
+        .trim();
+    let mut parts = token.split('.');
+    let (Some(header), Some(payload), Some(_signature), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err("invalid JWT: expected three dot-separated segments".to_string());
+    };
+    let decode_segment = |name: &str, segment: &str| -> Result<Value, String> {
+        let bytes = decode_base64_bytes(segment).map_err(|e| format!("invalid JWT {name}: {e}"))?;
+        serde_json::from_slice(&bytes).map_err(|e| format!("invalid JWT {name}: {e}"))
+    };
+    Ok(serde_json::json!({
+        "header": decode_segment("header", header)?,
+        "payload": decode_segment("payload", payload)?,
+    }))
+}
+
+/// A single JWT payload claim as text (strings unquoted, anything else as compact JSON).
+pub fn jwt_claim(token: &str, claim: &str) -> Result<String, String> {
+    let decoded = jwt_decode(token)?;
+    match decoded["payload"].get(claim) {
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(other) => Ok(other.to_string()),
+        None => Err(format!("JWT payload has no claim '{claim}'")),
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut out, b| {
+            let _ = write!(out, "{b:02x}");
+            out
+        })
+}
+
+/// Register the helpers as Rhai functions: `base64_encode`, `base64_decode`, `url_encode`,
+/// `url_decode`, `sha256_hex`, `hmac_sha256_hex`, `hmac_sha256_base64`, and `jwt_decode` (returns
+/// a map with `header`/`payload`). Decode failures raise a Rhai runtime error.
+pub fn register_rhai_fns(engine: &mut rhai::Engine) {
+    use rhai::{Dynamic, EvalAltResult};
+
+    fn rhai_err(msg: String) -> Box<EvalAltResult> {
+        msg.into()
+    }
+
+    engine
+        .register_fn("base64_encode", |s: &str| base64_encode(s.as_bytes()))
+        .register_fn("base64_decode", |s: &str| {
+            base64_decode(s).map_err(rhai_err)
+        })
+        .register_fn("url_encode", url_encode)
+        .register_fn("url_decode", |s: &str| url_decode(s).map_err(rhai_err))
+        .register_fn("sha256_hex", |s: &str| sha256_hex(s.as_bytes()))
+        .register_fn("hmac_sha256_hex", |key: &str, msg: &str| {
+            hmac_sha256_encoded(key, msg, DigestEncoding::Hex)
+        })
+        .register_fn("hmac_sha256_base64", |key: &str, msg: &str| {
+            hmac_sha256_encoded(key, msg, DigestEncoding::Base64)
+        })
+        .register_fn(
+            "jwt_decode",
+            |token: &str| -> Result<Dynamic, Box<EvalAltResult>> {
+                let decoded = jwt_decode(token).map_err(rhai_err)?;
+                rhai::serde::to_dynamic(decoded)
+            },
+        );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // HS256 token for {"alg":"HS256","typ":"JWT"} / {"sub":"1234567890","name":"John Doe","admin":true}
+    const TOKEN: &str = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.\
+        eyJzdWIiOiIxMjM0NTY3ODkwIiwibmFtZSI6IkpvaG4gRG9lIiwiYWRtaW4iOnRydWV9.\
+        TJVA95OrM7E2cBab30RMHrHDcEfxjoYZgeFONFh7HgQ";
+
+    #[test]
+    fn base64_round_trips_and_accepts_url_safe_unpadded() {
+        assert_eq!(base64_encode(b"hello?>"), "aGVsbG8/Pg==");
+        assert_eq!(base64_decode("aGVsbG8/Pg==").unwrap(), "hello?>");
+        assert_eq!(base64_decode("aGVsbG8_Pg").unwrap(), "hello?>");
+        assert!(base64_decode("not base64!").is_err());
+    }
+
+    #[test]
+    fn url_encoding_round_trips() {
+        assert_eq!(url_encode("a b&c=d"), "a%20b%26c%3Dd");
+        assert_eq!(url_decode("a%20b%26c%3Dd").unwrap(), "a b&c=d");
+    }
+
+    #[test]
+    fn sha256_and_hmac_match_known_vectors() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        // RFC 4231 test case 2.
+        assert_eq!(
+            hmac_sha256_encoded("Jefe", "what do ya want for nothing?", DigestEncoding::Hex),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            hmac_sha256_encoded(
+                "Jefe",
+                "what do ya want for nothing?",
+                DigestEncoding::Base64
+            ),
+            "W9zBRr9gdU5qBCQmCJV1x1oAPwidJzmDnexYuWTsOEM="
+        );
+    }
+
+    #[test]
+    fn jwt_decode_reads_header_and_claims() {
+        let decoded = jwt_decode(&format!("Bearer {TOKEN}")).unwrap();
+        assert_eq!(decoded["header"]["alg"], "HS256");
+        assert_eq!(decoded["payload"]["name"], "John Doe");
+        assert_eq!(jwt_claim(TOKEN, "sub").unwrap(), "1234567890");
+        assert_eq!(jwt_claim(TOKEN, "admin").unwrap(), "true");
+        assert!(jwt_claim(TOKEN, "missing").is_err());
+        assert!(jwt_decode("only.two").is_err());
+    }
+
+    #[test]
+    fn rhai_functions_are_registered() {
+        let mut engine = rhai::Engine::new();
+        register_rhai_fns(&mut engine);
+        let out: String = engine
+            .eval(r#"base64_encode("hi") + ":" + hmac_sha256_hex("k", "m").sub_string(0, 8)"#)
+            .unwrap();
+        assert_eq!(
+            out,
+            format!(
+                "aGk=:{}",
+                &hmac_sha256_encoded("k", "m", DigestEncoding::Hex)[..8]
+            )
+        );
+        let name: String = engine
+            .eval(&format!(r#"jwt_decode("{TOKEN}").payload.name"#))
+            .unwrap();
+        assert_eq!(name, "John Doe");
+    }
+}
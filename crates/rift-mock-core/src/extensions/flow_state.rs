@@ -37,6 +37,12 @@ pub trait FlowStore: Send + Sync {
         false
     }
 
+    /// Check that the backend answers, for the admin API's `/readyz`. The default reads a key no
+    /// flow uses, which is a real round trip for any backend; the Redis backend sends `PING`.
+    fn ping(&self) -> Result<()> {
+        self.exists("__rift_ping__", "ping").map(|_| ())
+    }
+
     /// Increment a numeric value (returns new value)
     fn increment(&self, flow_id: &str, key: &str) -> Result<i64>;
 
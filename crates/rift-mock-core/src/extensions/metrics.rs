@@ -122,6 +122,23 @@ lazy_static! {
     )
     .unwrap();
 
+    /// Size of the proxy recording store: `kind` is `signatures` (distinct recorded requests) or
+    /// `responses` (recorded responses across them). Bounded by `recording.maxEntries`.
+    pub static ref RECORDING_STORE_SIZE: GaugeVec = register_gauge_vec!(
+        "rift_recording_store_size",
+        "Current size of the proxy recording store",
+        &["kind"]  // kind: signatures|responses
+    )
+    .unwrap();
+
+    /// Recorded signatures evicted from the proxy recording store
+    pub static ref RECORDING_EVICTIONS_TOTAL: CounterVec = register_counter_vec!(
+        "rift_recording_evictions_total",
+        "Total number of recorded signatures evicted from the proxy recording store",
+        &["reason"]  // reason: capacity|ttl
+    )
+    .unwrap();
+
     /// Script compilation errors
     pub static ref SCRIPT_ERRORS_TOTAL: CounterVec = register_counter_vec!(
         "rift_script_errors_total",
@@ -200,6 +217,23 @@ pub fn record_flow_state_op(operation: &str, success: bool) {
         .inc();
 }
 
+/// Helper to set the recording store size gauges
+pub fn set_recording_store_size(signatures: usize, responses: usize) {
+    RECORDING_STORE_SIZE
+        .with_label_values(&["signatures"])
+        .set(signatures as f64);
+    RECORDING_STORE_SIZE
+        .with_label_values(&["responses"])
+        .set(responses as f64);
+}
+
+/// Helper to count recording store evictions
+pub fn record_recording_evictions(reason: &str, count: usize) {
+    RECORDING_EVICTIONS_TOTAL
+        .with_label_values(&[reason])
+        .inc_by(count as f64);
+}
+
 /// Helper to set active flows gauge
 pub fn set_active_flows(backend: &str, count: i64) {
     ACTIVE_FLOWS.with_label_values(&[backend]).set(count as f64);
@@ -8,7 +8,10 @@
 //! - **Flow State** (`flow_state`): Stateful testing with in-memory or Redis backends
 //! - **Rule Matching** (`matcher`): Enhanced request matching with compiled predicates
 //! - **Metrics** (`metrics`): Prometheus metrics for observability
+//! - **Random** (`random`): Imposter-scoped RNG, reproducible when `randomSeed` is set
 //! - **Stub Analysis** (`stub_analysis`): Conflict detection and overlap warnings
+//! - **Encoding** (`encoding`): base64/URL/SHA-256/HMAC/JWT helpers shared by templating and
+//!   decorate scripts
 //! - **Template** (`template`): Response body templating with request data
 //! - **Template Functions** (`template_fn`): Declarative `{{ function args | filter }}`
 //!   response templating (issue #359)
@@ -17,11 +20,13 @@
 //!   defaultForward/defaultResponse/empty-200 fallthrough (issue #819)
 
 pub mod decorate;
+pub mod encoding;
 pub mod fault;
 pub mod flow_state;
 pub mod matcher;
 pub mod metrics;
 pub mod no_match;
+pub mod random;
 pub mod routing;
 pub mod stub_analysis;
 pub mod template;
@@ -45,6 +50,8 @@ pub use stub_analysis::{
     StubAnalysisResult, StubWarning, WarningType, analyze_new_stub, analyze_stubs,
 };
 #[allow(unused_imports)]
-pub use template::{RequestData, apply_date_templates, contains_date_templates, process_template};
+pub use template::{
+    RequestData, StubMetadata, apply_date_templates, contains_date_templates, process_template,
+};
 #[allow(unused_imports)]
-pub use template_fn::{TemplateContext, render_templated};
+pub use template_fn::{FetchedResponse, TemplateContext, render_templated};
@@ -0,0 +1,104 @@
+//! Imposter-scoped randomness with an optional fixed seed.
+//!
+//! `wait` ranges and JS wait functions, the `{{ randomInt }}` / `{{ uuid }}` template functions,
+//! and `_rift.fault` probabilities all draw from the imposter's [`ImposterRng`]. Unseeded it is
+//! the thread-local RNG, exactly as before; with the imposter-level `randomSeed` set it is a
+//! single `StdRng` seeded from that value, so replaying the same requests in the same order
+//! reproduces every delay, fault roll, and generated value bit-for-bit.
+//!
+//! Determinism holds for a serial request sequence: concurrent requests still interleave their
+//! draws in arrival order, which no seed can pin down.
+//!
+//! JavaScript `inject` and `decorate` scripts are outside it: they run on the shared script
+//! workers, and their `Math.random` is left unseeded.
+
+use parking_lot::Mutex;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+/// The random source for one imposter: seeded (reproducible) or thread-local (the default).
+#[derive(Default)]
+pub struct ImposterRng {
+    seeded: Option<Mutex<StdRng>>,
+}
+
+impl ImposterRng {
+    /// The thread-local RNG (no seed). `const` so callers without an imposter can borrow a
+    /// `static` one.
+    #[must_use]
+    pub const fn unseeded() -> Self {
+        Self { seeded: None }
+    }
+
+    /// A seeded RNG when `seed` is set, otherwise the thread-local RNG.
+    #[must_use]
+    pub fn new(seed: Option<u64>) -> Self {
+        Self {
+            seeded: seed.map(|s| Mutex::new(StdRng::seed_from_u64(s))),
+        }
+    }
+
+    /// Whether draws are reproducible (a `randomSeed` was configured).
+    #[must_use]
+    pub fn is_seeded(&self) -> bool {
+        self.seeded.is_some()
+    }
+
+    /// Run `f` with the underlying RNG. Draw everything a decision needs inside one call: the
+    /// seeded RNG is locked for its duration, and the thread-local one is not `Send`, so neither
+    /// may be held across an `.await`.
+    pub fn with<R>(&self, f: impl FnOnce(&mut dyn RngCore) -> R) -> R {
+        match &self.seeded {
+            Some(rng) => f(&mut *rng.lock()),
+            None => f(&mut rand::thread_rng()),
+        }
+    }
+
+    /// A fresh seed for a nested generator (e.g. the JS `Math.random` of a wait function), drawn
+    /// from this RNG. `None` when unseeded, so the nested generator keeps its own entropy.
+    #[must_use]
+    pub fn derive_seed(&self) -> Option<u32> {
+        self.seeded.as_ref().map(|rng| rng.lock().next_u32())
+    }
+}
+
+impl std::fmt::Debug for ImposterRng {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImposterRng")
+            .field("seeded", &self.is_seeded())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn draws(rng: &ImposterRng) -> Vec<u64> {
+        (0..8)
+            .map(|_| rng.with(|r| r.gen_range(0..1_000_000)))
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_replays_the_same_sequence() {
+        assert_eq!(
+            draws(&ImposterRng::new(Some(42))),
+            draws(&ImposterRng::new(Some(42)))
+        );
+        assert_ne!(
+            draws(&ImposterRng::new(Some(42))),
+            draws(&ImposterRng::new(Some(43)))
+        );
+    }
+
+    #[test]
+    fn derive_seed_only_when_seeded() {
+        assert_eq!(ImposterRng::unseeded().derive_seed(), None);
+        assert_eq!(
+            ImposterRng::new(Some(7)).derive_seed(),
+            ImposterRng::new(Some(7)).derive_seed()
+        );
+    }
+}
@@ -11,6 +11,22 @@
 //! - `${request.headers.<name>}` - Header value (case-insensitive)
 //! - `${request.pathParams.<name>}` - Path parameter from route pattern
 //! - `${request.body}` - The raw request body
+//! - `${stub.id}` / `${stub.index}` - The `id` (empty when unset) and zero-based position of the
+//!   stub serving the request
+//! - `${imposter.port}` - The port of the imposter serving the request
+//!
+//! # Date/Time Helpers
+//!
+//! Times are relative to the request instant (captured once, so every token in a response agrees):
+//!
+//! - `${now()}` - RFC3339 / ISO-8601 UTC timestamp
+//! - `${now(+2h)}`, `${now(-30m, epochSeconds)}` - shifted by a `±N` `s`/`m`/`h`/`d` offset, in an
+//!   optional format: `iso8601` (default), `epochMillis`, `epochSeconds`, `rfc1123` (HTTP date),
+//!   or a strftime string such as `'%Y-%m-%d'` (quotes optional)
+//! - `${epochMillis()}` / `${epochSeconds()}` - Unix time, optionally with an offset:
+//!   `${epochMillis(+15m)}`
+//!
+//! A malformed offset or format leaves the token in place and logs a warning.
 //!
 //! # Example
 //!
@@ -27,16 +43,51 @@ use regex::Regex;
 use std::collections::HashMap;
 use std::sync::OnceLock;
 
-/// Regex for matching template variables: ${request.path}, ${request.query.name}, etc.
+/// Regex for matching template variables: ${request.path}, ${request.query.name}, ${stub.id},
+/// etc. (group 1 the namespace — `request`/`stub`/`imposter` — and group 2 the path within it),
+/// the date/time helpers ${now(..)}, ${epochMillis(..)}, ${epochSeconds(..)} and the encoding
+/// helpers ${base64(..)}, ${sha256(..)}, ${jwtClaim(..)} etc. (group 3 the helper, group 4 its
+/// raw argument list). One regex, so substitution is a single pass and reflected request data is
+/// never re-scanned for helpers.
 static TEMPLATE_REGEX: OnceLock<Regex> = OnceLock::new();
 
 fn get_template_regex() -> &'static Regex {
     TEMPLATE_REGEX.get_or_init(|| {
-        Regex::new(r"\$\{request\.([a-zA-Z_][a-zA-Z0-9_]*(?:\.[a-zA-Z_][a-zA-Z0-9_-]*)?)\}")
-            .unwrap()
+        Regex::new(concat!(
+            r"\$\{(?:(request|stub|imposter)\.([a-zA-Z_][a-zA-Z0-9_]*(?:\.[a-zA-Z_][a-zA-Z0-9_-]*)?)",
+            r"|(now|epochMillis|epochSeconds|base64|base64Decode|urlEncode|urlDecode|sha256",
+            r"|hmacSha256|jwtClaim)\(([^()}]*)\))\}",
+        ))
+        .unwrap()
     })
 }
 
+/// Which stub — and which imposter — is serving a request. Exposed to `${stub.*}` /
+/// `${imposter.*}` substitution, `{{ stub.id }}`-style templates, and the `stub` / `imposter`
+/// objects of decorate scripts, so a response can say where it came from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StubMetadata {
+    /// The stub's `id`, if it has one.
+    pub id: Option<String>,
+    /// Zero-based position of the stub in the imposter's stub list.
+    pub index: usize,
+    /// Port of the serving imposter.
+    pub imposter_port: u16,
+}
+
+impl StubMetadata {
+    /// Get a value by namespace and field: `("stub", "id")`, `("stub", "index")`,
+    /// `("imposter", "port")`. A stub without an `id` yields an empty string.
+    pub fn get(&self, namespace: &str, field: &str) -> Option<String> {
+        match (namespace, field) {
+            ("stub", "id") => Some(self.id.clone().unwrap_or_default()),
+            ("stub", "index") => Some(self.index.to_string()),
+            ("imposter", "port") => Some(self.imposter_port.to_string()),
+            _ => None,
+        }
+    }
+}
+
 /// Parsed request data for template substitution
 #[derive(Debug, Clone, Default)]
 pub struct RequestData {
@@ -55,6 +106,11 @@ pub struct RequestData {
     pub path_params: FastMap<String, String>,
     /// Raw request body
     pub body: String,
+    /// The stub serving the request, for `${stub.*}` / `${imposter.*}`. `None` outside stub
+    /// serving (e.g. proxy fault bodies), where those variables render empty.
+    pub stub: Option<StubMetadata>,
+    /// The request instant that `${now()}` / `{{ now }}` and friends are relative to.
+    pub received_at: chrono::DateTime<chrono::Utc>,
 }
 
 impl RequestData {
@@ -84,9 +140,18 @@ impl RequestData {
             headers: headers_map,
             path_params: FastMap::default(),
             body: body.unwrap_or("").to_string(),
+            stub: None,
+            received_at: chrono::Utc::now(),
         }
     }
 
+    /// Attach the serving stub's metadata, enabling `${stub.*}` / `${imposter.*}`.
+    #[must_use]
+    pub fn with_stub(mut self, stub: StubMetadata) -> Self {
+        self.stub = Some(stub);
+        self
+    }
+
     /// Populate `path_params` by matching the request path against a route `pattern` (issue #433),
     /// e.g. `/users/:id`. A `None` pattern — or one whose shape doesn't match the path — leaves the
     /// map empty (the unchanged default), so callers can pass a stub's optional pattern directly.
@@ -142,7 +207,8 @@ pub(crate) fn extract_path_params(pattern: &str, path: &str) -> FastMap<String,
 /// Process a template string, substituting variables with request data
 ///
 /// # Arguments
-/// * `template` - The template string containing `${request.*}` variables
+/// * `template` - The template string containing `${request.*}` (and `${stub.*}` /
+///   `${imposter.*}`) variables
 /// * `request_data` - The request data to use for substitution
 ///
 /// # Returns
@@ -150,12 +216,221 @@ pub(crate) fn extract_path_params(pattern: &str, path: &str) -> FastMap<String,
 pub fn process_template(template: &str, request_data: &RequestData) -> String {
     get_template_regex()
         .replace_all(template, |caps: &regex::Captures| {
-            let var_path = &caps[1];
-            request_data.get(var_path).unwrap_or_default()
+            if let (Some(helper), Some(args)) = (caps.get(3), caps.get(4)) {
+                let result = match helper.as_str() {
+                    "now" | "epochMillis" | "epochSeconds" => {
+                        eval_time_helper(helper.as_str(), args.as_str(), request_data.received_at)
+                    }
+                    helper => eval_encoding_helper(helper, args.as_str(), request_data),
+                };
+                return result.unwrap_or_else(|e| {
+                    tracing::warn!(target: "rift::template", "{}: {e}", &caps[0]);
+                    caps[0].to_string()
+                });
+            }
+            let var_path = &caps[2];
+            match &caps[1] {
+                "request" => request_data.get(var_path),
+                namespace => request_data
+                    .stub
+                    .as_ref()
+                    .and_then(|stub| stub.get(namespace, var_path)),
+            }
+            .unwrap_or_default()
         })
         .to_string()
 }
 
+/// Evaluate `${now(offset, format)}` / `${epochMillis(offset)}` / `${epochSeconds(offset)}` at
+/// `base`. Both arguments are optional; an empty one is the same as an omitted one.
+fn eval_time_helper(
+    helper: &str,
+    args: &str,
+    base: chrono::DateTime<chrono::Utc>,
+) -> Result<String, String> {
+    let (offset, format) = match args.split_once(',') {
+        Some((offset, format)) => (offset.trim(), unquote(format.trim())),
+        None => (args.trim(), ""),
+    };
+    let dt = if offset.is_empty() {
+        base
+    } else {
+        offset_instant(base, offset)?
+    };
+    let format = match helper {
+        "now" if format.is_empty() => "iso8601",
+        "now" => format,
+        _ if !format.is_empty() => {
+            return Err(format!(
+                "{helper}() takes only an offset, got format '{format}'"
+            ));
+        }
+        other => other,
+    };
+    format_instant(dt, format)
+}
+
+/// Evaluate an encoding helper (see `extensions::encoding`): `${base64(x)}`, `${base64Decode(x)}`,
+/// `${urlEncode(x)}`, `${urlDecode(x)}`, `${sha256(x)}`, `${hmacSha256(key, x, encoding?)}`
+/// (`hex` by default; key first, as in `rift.hmacSha256` and Rhai's `hmac_sha256_hex`) and
+/// `${jwtClaim(token, claim)}`. Each argument is a `request.*`/`stub.*`/`imposter.*` reference
+/// (empty when absent, like `${request.*}` itself) or a literal, quoted or not.
+fn eval_encoding_helper(
+    helper: &str,
+    args: &str,
+    request_data: &RequestData,
+) -> Result<String, String> {
+    use super::encoding;
+    let args: Vec<String> = split_helper_args(args)
+        .into_iter()
+        .map(|arg| resolve_helper_arg(arg, request_data))
+        .collect();
+    let (min, max) = match helper {
+        "hmacSha256" => (2, 3),
+        "jwtClaim" => (2, 2),
+        _ => (1, 1),
+    };
+    if args.len() < min || args.len() > max {
+        return Err(format!(
+            "{helper}() takes {} argument(s), got {}",
+            if min == max {
+                min.to_string()
+            } else {
+                format!("{min} to {max}")
+            },
+            args.len()
+        ));
+    }
+    match helper {
+        "base64" => Ok(encoding::base64_encode(args[0].as_bytes())),
+        "base64Decode" => encoding::base64_decode(&args[0]),
+        "urlEncode" => Ok(encoding::url_encode(&args[0])),
+        "urlDecode" => encoding::url_decode(&args[0]),
+        "sha256" => Ok(encoding::sha256_hex(args[0].as_bytes())),
+        "hmacSha256" => {
+            let digest = match args.get(2) {
+                Some(name) => encoding::DigestEncoding::parse(name)?,
+                None => encoding::DigestEncoding::Hex,
+            };
+            Ok(encoding::hmac_sha256_encoded(&args[0], &args[1], digest))
+        }
+        "jwtClaim" => encoding::jwt_claim(&args[0], &args[1]),
+        other => Err(format!("unknown helper '{other}'")),
+    }
+}
+
+/// Split a helper's argument list on the commas outside quotes. An empty list has no arguments.
+fn split_helper_args(args: &str) -> Vec<&str> {
+    if args.trim().is_empty() {
+        return Vec::new();
+    }
+    let mut parts = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in args.char_indices() {
+        match (quote, c) {
+            (None, '\'' | '"') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, ',') => {
+                parts.push(args[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(args[start..].trim());
+    parts
+}
+
+/// A helper argument's value: a quoted literal unquoted, a `request.*`/`stub.*`/`imposter.*`
+/// reference resolved, anything else as written.
+fn resolve_helper_arg(arg: &str, request_data: &RequestData) -> String {
+    let unquoted = unquote(arg);
+    if unquoted.len() != arg.len() {
+        return unquoted.to_string();
+    }
+    match arg.split_once('.') {
+        Some(("request", path)) => request_data.get(path).unwrap_or_default(),
+        Some((namespace @ ("stub" | "imposter"), path)) => request_data
+            .stub
+            .as_ref()
+            .and_then(|stub| stub.get(namespace, path))
+            .unwrap_or_default(),
+        _ => arg.to_string(),
+    }
+}
+
+fn unquote(s: &str) -> &str {
+    ['\'', '"']
+        .iter()
+        .find_map(|q| s.strip_prefix(*q).and_then(|rest| rest.strip_suffix(*q)))
+        .unwrap_or(s)
+}
+
+/// Shift `base` by an offset like `-36h`/`+2d`/`90m`/`30s`. Shared with `{{ now offset=.. }}`.
+pub(crate) fn offset_instant(
+    base: chrono::DateTime<chrono::Utc>,
+    offset: &str,
+) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    base.checked_add_signed(parse_offset(offset)?)
+        .ok_or_else(|| format!("offset '{offset}' overflows the representable date range"))
+}
+
+/// Parse an offset like `-36h`/`+2d`/`90m`/`30s` into a `chrono::Duration`.
+fn parse_offset(s: &str) -> Result<chrono::Duration, String> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err("empty offset".to_string());
+    }
+    let (sign, rest) = match s.chars().next() {
+        Some('+') => (1i64, &s[1..]),
+        Some('-') => (-1i64, &s[1..]),
+        _ => (1i64, s),
+    };
+    let unit = rest
+        .chars()
+        .last()
+        .ok_or_else(|| format!("empty offset magnitude: '{s}'"))?;
+    let num_str = &rest[..rest.len() - unit.len_utf8()];
+    let num: i64 = num_str
+        .parse()
+        .map_err(|_| format!("invalid offset number '{num_str}' in '{s}'"))?;
+    let magnitude = match unit {
+        's' => chrono::Duration::try_seconds(num),
+        'm' => chrono::Duration::try_minutes(num),
+        'h' => chrono::Duration::try_hours(num),
+        'd' => chrono::Duration::try_days(num),
+        other => return Err(format!("invalid offset unit '{other}' (expected s/m/h/d)")),
+    }
+    .ok_or_else(|| format!("offset overflows the representable range: '{s}'"))?;
+    Ok(if sign < 0 { -magnitude } else { magnitude })
+}
+
+/// Render `dt` in a named format — `iso8601` (RFC3339), `epochMillis`, `epochSeconds`, `rfc1123`
+/// (the HTTP-date form) — or, failing those, as a strftime string. Shared with `{{ now format=.. }}`.
+pub(crate) fn format_instant(
+    dt: chrono::DateTime<chrono::Utc>,
+    format: &str,
+) -> Result<String, String> {
+    match format {
+        "iso8601" => Ok(dt.to_rfc3339()),
+        "epochMillis" => Ok(dt.timestamp_millis().to_string()),
+        "epochSeconds" => Ok(dt.timestamp().to_string()),
+        "rfc1123" => Ok(dt.format("%a, %d %b %Y %H:%M:%S GMT").to_string()),
+        // Issue #359 B2 (no-panic): `chrono::DateTime::format` PANICS on a malformed strftime
+        // string (e.g. `'100%'`, `'%'`, `'%-'`). Validate first by walking the parsed format
+        // items; an `Item::Error` becomes an ordinary error for the caller's error policy instead
+        // of unwinding the connection task.
+        fmt => {
+            use chrono::format::{Item, StrftimeItems};
+            if StrftimeItems::new(fmt).any(|item| matches!(item, Item::Error)) {
+                return Err(format!("invalid strftime format string '{fmt}'"));
+            }
+            Ok(dt.format(fmt).to_string())
+        }
+    }
+}
+
 /// Check if a string contains template variables
 pub fn has_template_variables(s: &str) -> bool {
     get_template_regex().is_match(s)
@@ -340,6 +615,106 @@ mod tests {
         assert_eq!(result, r#"{"path": "/users/123"}"#);
     }
 
+    #[test]
+    fn test_process_template_stub_metadata() {
+        let template = "${stub.id}#${stub.index}@${imposter.port}";
+        let data = create_test_request_data().with_stub(StubMetadata {
+            id: Some("get-user".to_string()),
+            index: 3,
+            imposter_port: 4545,
+        });
+        assert_eq!(process_template(template, &data), "get-user#3@4545");
+        // No `id` renders empty; no metadata at all renders every variable empty.
+        let unnamed = create_test_request_data().with_stub(StubMetadata {
+            index: 0,
+            imposter_port: 4545,
+            ..Default::default()
+        });
+        assert_eq!(process_template(template, &unnamed), "#0@4545");
+        assert_eq!(
+            process_template(template, &create_test_request_data()),
+            "#@"
+        );
+        assert!(has_template_variables("${imposter.port}"));
+        assert!(!has_template_variables("${stub}"));
+    }
+
+    #[test]
+    fn test_process_template_time_helpers() {
+        let mut data = create_test_request_data();
+        data.received_at = chrono::DateTime::parse_from_rfc3339("2026-03-01T10:00:00Z")
+            .unwrap()
+            .to_utc();
+        let render = |t: &str| process_template(t, &data);
+
+        assert_eq!(render("${now()}"), "2026-03-01T10:00:00+00:00");
+        assert_eq!(render("${now(+2h, iso8601)}"), "2026-03-01T12:00:00+00:00");
+        assert_eq!(render("${now(-1d, '%Y-%m-%d')}"), "2026-02-28");
+        assert_eq!(render("${now(, epochSeconds)}"), "1772359200");
+        assert_eq!(
+            render("${now(+0s, rfc1123)}"),
+            "Sun, 01 Mar 2026 10:00:00 GMT"
+        );
+        assert_eq!(render("${epochMillis()}"), "1772359200000");
+        assert_eq!(render("${epochSeconds(+1m)}"), "1772359260");
+        // Every token in one render shares the request instant.
+        assert_eq!(
+            render(r#"{"iat": ${epochSeconds()}, "exp": ${epochSeconds(+1h)}}"#),
+            r#"{"iat": 1772359200, "exp": 1772362800}"#
+        );
+        // Malformed offsets/formats leave the token in place.
+        assert_eq!(render("${now(+2x)}"), "${now(+2x)}");
+        assert_eq!(render("${now(, '%')}"), "${now(, '%')}");
+        assert_eq!(
+            render("${epochMillis(+1h, iso8601)}"),
+            "${epochMillis(+1h, iso8601)}"
+        );
+        assert!(has_template_variables("exp=${epochMillis(+15m)}"));
+        assert!(!has_template_variables("${later()}"));
+    }
+
+    #[test]
+    fn test_process_template_encoding_helpers() {
+        let data = create_test_request_data();
+        let render = |t: &str| process_template(t, &data);
+
+        assert_eq!(render("${base64(request.path)}"), "L3VzZXJzLzEyMw==");
+        assert_eq!(render("${base64Decode('aGk=')}"), "hi");
+        assert_eq!(render("${urlEncode('a b&c')}"), "a%20b%26c");
+        assert_eq!(render("${urlDecode(a%20b)}"), "a b");
+        assert_eq!(
+            render("${sha256('abc')}"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            render("${hmacSha256('key', 'hello')}"),
+            "9307b3b915efb5171ff14d8cb55fbcc798c6c0ef1456d66ded1a6aa723a58b7b"
+        );
+        assert_eq!(
+            render("${hmacSha256('key', 'hello', base64)}"),
+            "kwezuRXvtRcf8U2MtV+8x5jGwO8UVtZt7RpqpyOli3s="
+        );
+        // {"sub":"user-1"} as the payload; the signature is never checked.
+        assert_eq!(
+            render("${jwtClaim('e30.eyJzdWIiOiJ1c2VyLTEifQ.sig', sub)}"),
+            "user-1"
+        );
+        // A quoted argument may hold a comma.
+        assert_eq!(render("${urlEncode('a,b')}"), "a%2Cb");
+        // A helper fed a missing variable encodes the empty string, like `${request.*}` itself.
+        assert_eq!(render("${base64(request.query.missing)}"), "");
+        // A failing helper leaves the token in place.
+        assert_eq!(render("${base64Decode('***')}"), "${base64Decode('***')}");
+        assert_eq!(render("${sha256()}"), "${sha256()}");
+        assert_eq!(
+            render("${hmacSha256(k, x, octal)}"),
+            "${hmacSha256(k, x, octal)}"
+        );
+        assert!(has_template_variables(
+            "sig=${hmacSha256('k', request.body)}"
+        ));
+    }
+
     #[test]
     fn test_process_template_query() {
         let data = create_test_request_data();
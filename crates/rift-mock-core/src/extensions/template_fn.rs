@@ -20,20 +20,31 @@
 //!
 //! # Function set (v1)
 //!
-//! - `request.method`, `request.path`
+//! - `request.method`, `request.path`, `request.body`
 //! - `request.query.<name>`
 //! - `request.header '<Name>'` (case-insensitive)
 //! - `request.json '<jsonpath>'` — `$`, dotted keys, `[<index>]` array indexing over the parsed
 //!   request body
-//! - `now [offset='±Nh|m|s|d'] [format='<strftime>']` — default format is RFC3339
+//! - `now [offset='±Nh|m|s|d'] [format='<strftime>']` — the request instant; default format is
+//!   RFC3339, and the named formats of `${now(..)}` (`epochMillis`, `rfc1123`, ...) also work
 //! - `uuid` — a random UUID v4
 //! - `randomInt <a> <b>` — random integer in `[a, b]`
 //! - `state.<key>` — read-only flow-state lookup for the request's resolved flow id
+//! - `stub.id`, `stub.index`, `imposter.port` — which stub (and imposter) served the request
+//! - `fetch.<name>.status`, `fetch.<name>.body`, `fetch.<name>.header '<Name>'`,
+//!   `fetch.<name>.json '<jsonpath>'` — the result of the `_rift.fetch` sub-request `<name>`
+//!
+//! `uuid` and `randomInt` draw from the imposter's RNG, so a `randomSeed` makes them reproducible.
 //!
 //! Filters: `| last_segment` (trailing `/`-segment), `| regex '<pattern>' <group>` (capture group
 //! `<group>` of the first match), `| json` (JSON-string-escape the value so it is safe to place
 //! inside a JSON string literal — always apply it when a substituted value goes into `"..."`).
 //!
+//! Encoding filters (shared with decorate scripts via `extensions::encoding`): `| base64`,
+//! `| base64_decode`, `| url_encode`, `| url_decode`, `| sha256` (hex), `| hmac_sha256 '<key>'
+//! [hex|base64|base64url]` (hex by default), `| jwt_claim '<name>'` and `| jwt_payload` (decode
+//! only — the signature is never verified; a leading `Bearer ` is accepted).
+//!
 //! # Error policy (AC3)
 //!
 //! An unknown function/filter, a malformed token, or a failed lookup (missing query param/header/
@@ -41,11 +52,14 @@
 //! it fails the whole render (surfaced by the caller as a request-time error); otherwise the token
 //! is replaced with an empty string and a `tracing::warn!(target: "rift::template", ..)` names it.
 
+use crate::extensions::encoding;
 use crate::extensions::flow_state::FlowStore;
-use crate::extensions::template::RequestData;
+use crate::extensions::random::ImposterRng;
+use crate::extensions::template::{self, RequestData};
 use rand::Rng;
 use regex::Regex;
 use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::OnceLock;
 
 /// Everything a `{{ }}` expression may read: the parsed request and a read-only view onto the
@@ -57,6 +71,20 @@ pub struct TemplateContext<'a> {
     pub flow_id: &'a str,
     /// Read-only flow-state backend.
     pub flow_store: &'a dyn FlowStore,
+    /// The imposter's random source for `uuid` / `randomInt` (seeded by `randomSeed`).
+    pub rng: &'a ImposterRng,
+    /// Results of the response's `_rift.fetch` sub-requests, by name, for `fetch.*`.
+    pub fetched: Option<&'a BTreeMap<String, FetchedResponse>>,
+}
+
+/// One completed `_rift.fetch` sub-request, as `{{ fetch.<name>.* }}` sees it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FetchedResponse {
+    pub status: u16,
+    /// Response headers, keys lowercased (repeated headers keep the first value).
+    pub headers: HashMap<String, String>,
+    /// Response body, decoded lossily as UTF-8.
+    pub body: String,
 }
 
 /// Render the full `{{ }}` template surface for a `templated: true` response: first expands the
@@ -206,6 +234,7 @@ fn eval_base(head: &str, args: &[String], ctx: &TemplateContext<'_>) -> Result<S
     match head {
         "request.method" => Ok(ctx.request.method.clone()),
         "request.path" => Ok(ctx.request.path.clone()),
+        "request.body" => Ok(ctx.request.body.clone()),
         "request.header" => {
             let name = args.first().ok_or_else(|| {
                 "request.header requires a quoted header name, e.g. request.header 'Name'"
@@ -240,9 +269,18 @@ fn eval_base(head: &str, args: &[String], ctx: &TemplateContext<'_>) -> Result<S
                     ));
                 }
             }
-            eval_now(offset.as_deref(), format.as_deref())
+            eval_now(
+                ctx.request.received_at,
+                offset.as_deref(),
+                format.as_deref(),
+            )
+        }
+        "uuid" => {
+            let bytes: [u8; 16] = ctx.rng.with(|r| r.r#gen());
+            Ok(uuid::Builder::from_random_bytes(bytes)
+                .into_uuid()
+                .to_string())
         }
-        "uuid" => Ok(uuid::Uuid::new_v4().to_string()),
         "randomInt" => {
             let lo_str = args.first().ok_or_else(|| {
                 "randomInt requires two integer arguments: randomInt a b".to_string()
@@ -261,7 +299,15 @@ fn eval_base(head: &str, args: &[String], ctx: &TemplateContext<'_>) -> Result<S
                     "randomInt: lower bound {lo} is greater than upper bound {hi}"
                 ));
             }
-            Ok(rand::thread_rng().gen_range(lo..=hi).to_string())
+            Ok(ctx.rng.with(|r| r.gen_range(lo..=hi)).to_string())
+        }
+        "stub.id" | "stub.index" | "imposter.port" => {
+            let (namespace, field) = head.split_once('.').unwrap_or_default();
+            ctx.request
+                .stub
+                .as_ref()
+                .and_then(|stub| stub.get(namespace, field))
+                .ok_or_else(|| format!("{head} is only available while serving a stub"))
         }
         _ => {
             if let Some(name) = head.strip_prefix("request.query.") {
@@ -272,6 +318,9 @@ fn eval_base(head: &str, args: &[String], ctx: &TemplateContext<'_>) -> Result<S
                     .cloned()
                     .ok_or_else(|| format!("no such query parameter: '{name}'"));
             }
+            if let Some(rest) = head.strip_prefix("fetch.") {
+                return eval_fetch(rest, args, ctx);
+            }
             if let Some(key) = head.strip_prefix("state.") {
                 if key.is_empty() {
                     return Err("state requires a key, e.g. state.myKey".to_string());
@@ -289,6 +338,42 @@ fn eval_base(head: &str, args: &[String], ctx: &TemplateContext<'_>) -> Result<S
     }
 }
 
+/// Evaluate `fetch.<name>.<field>` (`rest` is everything after `fetch.`).
+fn eval_fetch(rest: &str, args: &[String], ctx: &TemplateContext<'_>) -> Result<String, String> {
+    let (name, field) = rest
+        .rsplit_once('.')
+        .ok_or_else(|| format!("fetch.{rest}: expected fetch.<name>.status|body|header|json"))?;
+    let fetched = ctx
+        .fetched
+        .and_then(|all| all.get(name))
+        .ok_or_else(|| format!("no such fetch: '{name}'"))?;
+    match field {
+        "status" => Ok(fetched.status.to_string()),
+        "body" => Ok(fetched.body.clone()),
+        "header" => {
+            let header = args.first().ok_or_else(|| {
+                format!("fetch.{name}.header requires a quoted header name, e.g. 'Content-Type'")
+            })?;
+            fetched
+                .headers
+                .get(&header.to_lowercase())
+                .cloned()
+                .ok_or_else(|| format!("fetch '{name}' has no header '{header}'"))
+        }
+        "json" => {
+            let path = args.first().ok_or_else(|| {
+                format!("fetch.{name}.json requires a quoted jsonpath, e.g. '$.a.b'")
+            })?;
+            let json: Value = serde_json::from_str(&fetched.body)
+                .map_err(|e| format!("fetch '{name}' body is not valid JSON: {e}"))?;
+            Ok(value_to_string(&eval_jsonpath(&json, path)?))
+        }
+        other => Err(format!(
+            "fetch.{name}.{other}: unknown field (expected status, body, header, or json)"
+        )),
+    }
+}
+
 /// Apply a `| filter` to the string result of the base expression (or a prior filter).
 fn apply_filter(value: &str, name: &str, args: &[String]) -> Result<String, String> {
     match name {
@@ -342,6 +427,33 @@ fn apply_filter(value: &str, name: &str, args: &[String]) -> Result<String, Stri
                 .unwrap_or(&quoted);
             Ok(interior.to_string())
         }
+        "base64" => Ok(encoding::base64_encode(value.as_bytes())),
+        "base64_decode" => {
+            encoding::base64_decode(value).map_err(|e| format!("base64_decode: {e}"))
+        }
+        "url_encode" => Ok(encoding::url_encode(value)),
+        "url_decode" => encoding::url_decode(value).map_err(|e| format!("url_decode: {e}")),
+        "sha256" => Ok(encoding::sha256_hex(value.as_bytes())),
+        "hmac_sha256" => {
+            let key = args.first().ok_or_else(|| {
+                "hmac_sha256 filter requires a quoted key, e.g. | hmac_sha256 'secret'".to_string()
+            })?;
+            let digest_encoding = match args.get(1) {
+                Some(name) => encoding::DigestEncoding::parse(name)
+                    .map_err(|e| format!("hmac_sha256: {e}"))?,
+                None => encoding::DigestEncoding::Hex,
+            };
+            Ok(encoding::hmac_sha256_encoded(key, value, digest_encoding))
+        }
+        "jwt_claim" => {
+            let claim = args.first().ok_or_else(|| {
+                "jwt_claim filter requires a quoted claim name, e.g. | jwt_claim 'sub'".to_string()
+            })?;
+            encoding::jwt_claim(value, claim).map_err(|e| format!("jwt_claim: {e}"))
+        }
+        "jwt_payload" => encoding::jwt_decode(value)
+            .map(|decoded| decoded["payload"].to_string())
+            .map_err(|e| format!("jwt_payload: {e}")),
         _ => Err(format!("unknown template filter: '{name}'")),
     }
 }
@@ -437,59 +549,18 @@ fn value_to_string(v: &Value) -> String {
     }
 }
 
-/// Parse an offset like `-36h`/`+2d`/`90m`/`30s` into a `chrono::Duration`.
-fn parse_offset(s: &str) -> Result<chrono::Duration, String> {
-    let s = s.trim();
-    if s.is_empty() {
-        return Err("empty offset".to_string());
-    }
-    let (sign, rest) = match s.chars().next() {
-        Some('+') => (1i64, &s[1..]),
-        Some('-') => (-1i64, &s[1..]),
-        _ => (1i64, s),
+/// Evaluate `now` with an optional `offset` and `format`, relative to the request instant
+/// (`base`). Default format is RFC3339.
+fn eval_now(
+    base: chrono::DateTime<chrono::Utc>,
+    offset: Option<&str>,
+    format: Option<&str>,
+) -> Result<String, String> {
+    let dt = match offset {
+        Some(off) => template::offset_instant(base, off).map_err(|e| format!("now: {e}"))?,
+        None => base,
     };
-    let unit = rest
-        .chars()
-        .last()
-        .ok_or_else(|| format!("empty offset magnitude: '{s}'"))?;
-    let num_str = &rest[..rest.len() - unit.len_utf8()];
-    let num: i64 = num_str
-        .parse()
-        .map_err(|_| format!("invalid offset number '{num_str}' in '{s}'"))?;
-    let magnitude = match unit {
-        's' => chrono::Duration::try_seconds(num),
-        'm' => chrono::Duration::try_minutes(num),
-        'h' => chrono::Duration::try_hours(num),
-        'd' => chrono::Duration::try_days(num),
-        other => return Err(format!("invalid offset unit '{other}' (expected s/m/h/d)")),
-    }
-    .ok_or_else(|| format!("offset overflows the representable range: '{s}'"))?;
-    Ok(if sign < 0 { -magnitude } else { magnitude })
-}
-
-/// Evaluate `now` with an optional `offset` and `format`. Default format is RFC3339.
-fn eval_now(offset: Option<&str>, format: Option<&str>) -> Result<String, String> {
-    let mut dt = chrono::Utc::now();
-    if let Some(off) = offset {
-        let duration = parse_offset(off)?;
-        dt = dt
-            .checked_add_signed(duration)
-            .ok_or_else(|| format!("now: offset '{off}' overflows the representable date range"))?;
-    }
-    match format {
-        // Issue #359 B2 (no-panic): `chrono::DateTime::format` PANICS on a malformed strftime
-        // string (e.g. `format='100%'`, `'%'`, `'%-'`). Validate first by walking the parsed
-        // format items; if any is an `Item::Error`, route the failure through the normal AC3 error
-        // policy (debug -> 500, else empty + warn) instead of unwinding the connection task.
-        Some(fmt) => {
-            use chrono::format::{Item, StrftimeItems};
-            if StrftimeItems::new(fmt).any(|item| matches!(item, Item::Error)) {
-                return Err(format!("now: invalid strftime format string '{fmt}'"));
-            }
-            Ok(dt.format(fmt).to_string())
-        }
-        None => Ok(dt.to_rfc3339()),
-    }
+    template::format_instant(dt, format.unwrap_or("iso8601")).map_err(|e| format!("now: {e}"))
 }
 
 #[cfg(test)]
@@ -521,10 +592,13 @@ mod tests {
         flow_id: &'a str,
         store: &'a InMemoryFlowStore,
     ) -> TemplateContext<'a> {
+        static RNG: ImposterRng = ImposterRng::unseeded();
         TemplateContext {
             request,
             flow_id,
             flow_store: store,
+            rng: &RNG,
+            fetched: None,
         }
     }
 
@@ -535,6 +609,53 @@ mod tests {
         // with `{{now}}` is unrelated to that gate. Covered end-to-end in imposter tests.
     }
 
+    #[test]
+    fn stub_metadata_functions() {
+        let data = request_data().with_stub(crate::extensions::template::StubMetadata {
+            id: Some("orders".to_string()),
+            index: 2,
+            imposter_port: 4545,
+        });
+        let s = store();
+        let tctx = ctx(&data, "flow-1", &s);
+        assert_eq!(
+            render_templated("{{stub.id}}:{{stub.index}}:{{imposter.port}}", &tctx, false).unwrap(),
+            "orders:2:4545"
+        );
+        // Outside stub serving there is nothing to report: a lookup failure (error in debug mode).
+        let bare = request_data();
+        let tctx = ctx(&bare, "flow-1", &s);
+        assert!(render_templated("{{stub.index}}", &tctx, true).is_err());
+    }
+
+    #[test]
+    fn fetch_functions_read_sub_request_results() {
+        let data = request_data();
+        let s = store();
+        let mut fetched = BTreeMap::new();
+        fetched.insert(
+            "user".to_string(),
+            FetchedResponse {
+                status: 200,
+                headers: HashMap::from([("etag".to_string(), "v7".to_string())]),
+                body: r#"{"name":"Ada","roles":["admin"]}"#.to_string(),
+            },
+        );
+        let mut tctx = ctx(&data, "flow-1", &s);
+        tctx.fetched = Some(&fetched);
+        assert_eq!(
+            render_templated(
+                "{{fetch.user.status}} {{fetch.user.json '$.name'}} {{fetch.user.json '$.roles[0]'}} {{fetch.user.header 'ETag'}}",
+                &tctx,
+                true
+            )
+            .unwrap(),
+            "200 Ada admin v7"
+        );
+        assert!(render_templated("{{fetch.missing.body}}", &tctx, true).is_err());
+        assert!(render_templated("{{fetch.user.bogus}}", &tctx, true).is_err());
+    }
+
     #[test]
     fn request_method_and_path() {
         let data = request_data();
@@ -581,6 +702,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn encoding_filters() {
+        let mut data = request_data();
+        data.headers.insert(
+            "authorization".to_string(),
+            "Bearer eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiJ1LTEifQ.sig".to_string(),
+        );
+        let s = store();
+        let tctx = ctx(&data, "flow-1", &s);
+        let render = |t: &str| render_templated(t, &tctx, true).unwrap();
+        assert_eq!(render("{{request.query.name | base64}}"), "Sm9obg==");
+        assert_eq!(
+            render("{{request.query.name | base64 | base64_decode}}"),
+            "John"
+        );
+        assert_eq!(
+            render("{{request.path | url_encode}}"),
+            "%2Forders%2F11111111-1111-1111-1111-111111111111"
+        );
+        assert_eq!(
+            render("{{request.query.name | sha256}}"),
+            crate::extensions::encoding::sha256_hex(b"John")
+        );
+        assert_eq!(
+            render("{{request.body | hmac_sha256 'secret' base64}}"),
+            crate::extensions::encoding::hmac_sha256_encoded(
+                "secret",
+                &data.body,
+                crate::extensions::encoding::DigestEncoding::Base64
+            )
+        );
+        assert_eq!(
+            render("{{request.header 'Authorization' | jwt_claim 'sub'}}"),
+            "u-1"
+        );
+        assert_eq!(
+            render("{{request.header 'Authorization' | jwt_payload}}"),
+            r#"{"sub":"u-1"}"#
+        );
+        assert!(render_templated("{{request.body | hmac_sha256 'k' octal}}", &tctx, true).is_err());
+    }
+
     #[test]
     fn request_json_nested_and_array_index() {
         let data = request_data();
@@ -698,6 +861,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn seeded_rng_makes_random_functions_reproducible() {
+        let data = request_data();
+        let s = store();
+        let render = |seed| {
+            let rng = ImposterRng::new(Some(seed));
+            let tctx = TemplateContext {
+                request: &data,
+                flow_id: "flow-1",
+                flow_store: &s,
+                rng: &rng,
+                fetched: None,
+            };
+            render_templated("{{uuid}} {{randomInt 0 1000000}}", &tctx, false).unwrap()
+        };
+        assert_eq!(render(1), render(1));
+        assert_ne!(render(1), render(2));
+    }
+
     #[test]
     fn state_present_and_missing() {
         let data = request_data();
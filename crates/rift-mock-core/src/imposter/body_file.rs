@@ -0,0 +1,190 @@
+//! `is.bodyFile` responses: a response body served from a file under the datadir.
+//!
+//! Large JSON/XML payloads no longer need to be inlined (and escaped) in the stub itself — the
+//! stub carries only the relative path, so `GET /imposters` stays small. The file is resolved
+//! strictly under the datadir root with the same escape rules as datadir-relative `_rift.script`
+//! files (`script_resolve::resolve_within_root`): an absolute path, a `..` climb, or a symlink
+//! pointing outside the root is rejected, never read.
+//!
+//! Hot reload is stat-driven: every serve compares the file's modification time and length with
+//! the cached copy and re-reads only when either changed, so editing the file on disk takes effect
+//! on the next request without recreating the stub.
+
+use super::script_resolve::{ScriptResolveError, resolve_within_root};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Errors from loading an `is.bodyFile`.
+#[derive(Debug, thiserror::Error)]
+pub enum BodyFileError {
+    #[error("bodyFile '{0}' cannot be served: no --datadir is configured")]
+    NoDatadir(String),
+    #[error(transparent)]
+    Resolve(#[from] ScriptResolveError),
+    #[error("failed to read bodyFile '{path}': {source}")]
+    Read {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// One cached file: the vetted on-disk path plus the content as of `modified`/`len`.
+struct CachedBody {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    len: u64,
+    content: Arc<[u8]>,
+}
+
+/// Per-imposter cache of `bodyFile` contents, keyed by the path as written in the stub.
+///
+/// The root is the manager's datadir; `None` (no `--datadir`) makes every load fail with
+/// [`BodyFileError::NoDatadir`] rather than guessing a base directory.
+#[derive(Default)]
+pub(crate) struct BodyFileCache {
+    root: Option<Arc<PathBuf>>,
+    entries: Mutex<HashMap<String, CachedBody>>,
+}
+
+impl BodyFileCache {
+    pub(crate) fn new(root: Option<Arc<PathBuf>>) -> Self {
+        Self {
+            root,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The current content of `file`, re-read from disk only when its mtime or length changed
+    /// since the cached copy. The path is escape-checked once, on first load; later loads only
+    /// `stat` the vetted path.
+    pub(crate) fn load(&self, file: &str) -> Result<Arc<[u8]>, BodyFileError> {
+        let root = self
+            .root
+            .as_deref()
+            .ok_or_else(|| BodyFileError::NoDatadir(file.to_string()))?;
+
+        let cached_path = self.entries.lock().get(file).map(|c| c.path.clone());
+        let path = match cached_path {
+            Some(path) => path,
+            None => resolve_within_root(root, file)?,
+        };
+        let meta = std::fs::metadata(&path).map_err(|source| read_error(file, source))?;
+        let modified = meta.modified().ok();
+        let len = meta.len();
+
+        if let Some(cached) = self.entries.lock().get(file)
+            && cached.modified == modified
+            && cached.len == len
+            && modified.is_some()
+        {
+            return Ok(Arc::clone(&cached.content));
+        }
+
+        let content: Arc<[u8]> = std::fs::read(&path)
+            .map_err(|source| read_error(file, source))?
+            .into();
+        self.entries.lock().insert(
+            file.to_string(),
+            CachedBody {
+                path,
+                modified,
+                len,
+                content: Arc::clone(&content),
+            },
+        );
+        Ok(content)
+    }
+
+    /// Read `file` fresh from disk without caching it: for one-off reads (a spilled recorded body
+    /// replayed or exported) that should not stay resident. Escape-checked like [`Self::load`].
+    pub(crate) fn read_uncached(&self, file: &str) -> Result<Vec<u8>, BodyFileError> {
+        let root = self
+            .root
+            .as_deref()
+            .ok_or_else(|| BodyFileError::NoDatadir(file.to_string()))?;
+        let path = resolve_within_root(root, file)?;
+        std::fs::read(&path).map_err(|source| read_error(file, source))
+    }
+}
+
+fn read_error(file: &str, source: std::io::Error) -> BodyFileError {
+    BodyFileError::Read {
+        path: file.to_string(),
+        source,
+    }
+}
+
+/// Whether `file` names a JSON document, so a served body gets the same `application/json`
+/// default an inline object body gets.
+pub(crate) fn is_json_file(file: &str) -> bool {
+    Path::new(file)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_in(dir: &Path) -> BodyFileCache {
+        BodyFileCache::new(Some(Arc::new(dir.to_path_buf())))
+    }
+
+    #[test]
+    fn loads_file_relative_to_root() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("bodies")).unwrap();
+        std::fs::write(dir.path().join("bodies/big.json"), r#"{"a":1}"#).unwrap();
+
+        let body = cache_in(dir.path()).load("bodies/big.json").unwrap();
+        assert_eq!(&*body, br#"{"a":1}"#);
+    }
+
+    #[test]
+    fn serves_updated_content_after_the_file_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("body.txt");
+        std::fs::write(&path, "first").unwrap();
+        let cache = cache_in(dir.path());
+        assert_eq!(&*cache.load("body.txt").unwrap(), b"first");
+
+        // A different length is detected even on filesystems with coarse mtime granularity.
+        std::fs::write(&path, "second version").unwrap();
+        assert_eq!(&*cache.load("body.txt").unwrap(), b"second version");
+    }
+
+    #[test]
+    fn rejects_paths_escaping_the_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = cache_in(dir.path());
+        assert!(matches!(
+            cache.load("../outside.json"),
+            Err(BodyFileError::Resolve(ScriptResolveError::PathEscape(_)))
+        ));
+        assert!(matches!(
+            cache.load("/etc/passwd"),
+            Err(BodyFileError::Resolve(ScriptResolveError::PathEscape(_)))
+        ));
+    }
+
+    #[test]
+    fn missing_datadir_is_an_error() {
+        let cache = BodyFileCache::default();
+        assert!(matches!(
+            cache.load("body.json"),
+            Err(BodyFileError::NoDatadir(_))
+        ));
+    }
+
+    #[test]
+    fn json_extension_detection() {
+        assert!(is_json_file("a/b.JSON"));
+        assert!(!is_json_file("a/b.xml"));
+        assert!(!is_json_file("json"));
+    }
+}
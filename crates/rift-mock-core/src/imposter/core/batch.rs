@@ -0,0 +1,262 @@
+//! Batch stub mutations: a list of add/replace/delete/move operations applied as one change.
+//!
+//! The operations run in order against a working copy of the stub list, each seeing the result of
+//! the ones before it, inside a single `mutate_stubs` critical section. If any of them fails the
+//! copy is dropped and the live list is untouched, so matching only ever sees the stubs from
+//! before the batch or from after all of it, never a state in between.
+
+use super::*;
+use crate::imposter::reconcile::stub_key;
+use serde::Serialize;
+
+/// Addresses one stub in a [`StubOperation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StubRef {
+    /// The stub at this position, as the list stands when the operation runs.
+    Index(usize),
+    /// The stub with this `id`.
+    Id(String),
+}
+
+/// One step of a [`Imposter::apply_stub_operations`] batch.
+#[derive(Debug, Clone)]
+pub enum StubOperation {
+    /// Insert `stub` at `index` (the end when `None`). A duplicate `id` is a conflict.
+    Add { stub: Stub, index: Option<usize> },
+    /// Replace a stub in place, keeping its position and response-cycling state.
+    Replace { target: StubRef, stub: Stub },
+    /// Remove a stub.
+    Delete { target: StubRef },
+    /// Move the stub at `from` to position `to`.
+    Move { from: usize, to: usize },
+}
+
+impl StubOperation {
+    fn name(&self) -> &'static str {
+        match self {
+            StubOperation::Add { .. } => "add",
+            StubOperation::Replace { .. } => "replace",
+            StubOperation::Delete { .. } => "delete",
+            StubOperation::Move { .. } => "move",
+        }
+    }
+}
+
+/// What one operation of a batch did: where the stub it touched ended up (for `delete`, where it
+/// was) and that stub's `id`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StubOperationResult {
+    pub op: &'static str,
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+}
+
+/// A successful batch: the per-operation results, plus the keys of the stubs it deleted so the
+/// manager can drop their sequencer cursors.
+pub(crate) struct StubBatch {
+    pub(crate) results: Vec<StubOperationResult>,
+    pub(crate) deleted_keys: Vec<String>,
+}
+
+impl Imposter {
+    /// Apply `operations` in order as one atomic change. On failure nothing is applied and the
+    /// error is [`ImposterError::StubOperationFailed`], naming the (zero-based) operation.
+    pub(crate) fn apply_stub_operations(
+        &self,
+        operations: Vec<StubOperation>,
+    ) -> Result<StubBatch, ImposterError> {
+        self.mutate_stubs(|stubs| {
+            // `mutate_stubs` commits whatever the closure leaves behind, so work on a copy and
+            // only swap it in once every operation has succeeded.
+            let mut next = stubs.clone();
+            let mut batch = StubBatch {
+                results: Vec::with_capacity(operations.len()),
+                deleted_keys: Vec::new(),
+            };
+            for (operation, op) in operations.into_iter().enumerate() {
+                let result = apply_one(&mut next, op, &mut batch.deleted_keys).map_err(|e| {
+                    ImposterError::StubOperationFailed {
+                        operation,
+                        source: Box::new(e),
+                    }
+                })?;
+                batch.results.push(result);
+            }
+            *stubs = next;
+            Ok(batch)
+        })
+    }
+}
+
+fn apply_one(
+    stubs: &mut Vec<Arc<StubState>>,
+    op: StubOperation,
+    deleted_keys: &mut Vec<String>,
+) -> Result<StubOperationResult, ImposterError> {
+    let name = op.name();
+    let (index, id) = match op {
+        StubOperation::Add { stub, index } => {
+            let index = index.unwrap_or(stubs.len());
+            if index > stubs.len() {
+                return Err(ImposterError::StubIndexOutOfBounds(index));
+            }
+            if let Some(id) = stub.id.as_deref()
+                && stubs.iter().any(|s| s.stub.id.as_deref() == Some(id))
+            {
+                return Err(ImposterError::StubIdConflict(id.to_string()));
+            }
+            let id = stub.id.clone();
+            stubs.insert(index, Arc::new(StubState::new(stub)));
+            (index, id)
+        }
+        StubOperation::Replace { target, mut stub } => {
+            let index = resolve(stubs, &target)?;
+            if let StubRef::Id(id) = target {
+                stub.id = Some(id);
+            }
+            let id = stub.id.clone();
+            // Reuse the slot's cycler + slot token (issue #287), as `replace_stub` does.
+            stubs[index] = Arc::new(stubs[index].with_stub(stub));
+            (index, id)
+        }
+        StubOperation::Delete { target } => {
+            let index = resolve(stubs, &target)?;
+            let removed = stubs.remove(index);
+            deleted_keys.push(stub_key(&removed.stub, 0));
+            (index, removed.stub.id.clone())
+        }
+        StubOperation::Move { from, to } => {
+            for position in [from, to] {
+                if position >= stubs.len() {
+                    return Err(ImposterError::StubIndexOutOfBounds(position));
+                }
+            }
+            let state = stubs.remove(from);
+            let id = state.stub.id.clone();
+            stubs.insert(to, state);
+            (to, id)
+        }
+    };
+    Ok(StubOperationResult {
+        op: name,
+        index,
+        id,
+    })
+}
+
+fn resolve(stubs: &[Arc<StubState>], target: &StubRef) -> Result<usize, ImposterError> {
+    match target {
+        StubRef::Index(index) if *index < stubs.len() => Ok(*index),
+        StubRef::Index(index) => Err(ImposterError::StubIndexOutOfBounds(*index)),
+        StubRef::Id(id) => stubs
+            .iter()
+            .position(|s| s.stub.id.as_deref() == Some(id.as_str()))
+            .ok_or_else(|| ImposterError::StubNotFound(id.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn imposter_with(ids: &[&str]) -> Imposter {
+        let stubs: Vec<_> = ids
+            .iter()
+            .map(|id| json!({"id": id, "responses": [{"is": {"body": id}}]}))
+            .collect();
+        Imposter::new(
+            serde_json::from_value(json!({"port": 4545, "protocol": "http", "stubs": stubs}))
+                .unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn stub(id: &str) -> Stub {
+        serde_json::from_value(json!({"id": id, "responses": [{"is": {"body": id}}]})).unwrap()
+    }
+
+    fn ids(imposter: &Imposter) -> Vec<String> {
+        imposter
+            .get_stubs()
+            .into_iter()
+            .map(|s| s.id.unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn operations_apply_in_order_and_report_where_each_stub_went() {
+        let imposter = imposter_with(&["a", "b", "c"]);
+        let batch = imposter
+            .apply_stub_operations(vec![
+                StubOperation::Add {
+                    stub: stub("d"),
+                    index: Some(0),
+                },
+                StubOperation::Delete {
+                    target: StubRef::Id("b".to_string()),
+                },
+                StubOperation::Move { from: 2, to: 0 },
+                StubOperation::Replace {
+                    target: StubRef::Index(2),
+                    stub: stub("ignored"),
+                },
+            ])
+            .unwrap();
+        assert_eq!(ids(&imposter), ["c", "d", "ignored"]);
+        let summary: Vec<_> = batch
+            .results
+            .iter()
+            .map(|r| (r.op, r.index, r.id.clone().unwrap_or_default()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("add", 0, "d".to_string()),
+                ("delete", 2, "b".to_string()),
+                ("move", 0, "c".to_string()),
+                ("replace", 2, "ignored".to_string()),
+            ]
+        );
+        assert_eq!(batch.deleted_keys, ["b"]);
+    }
+
+    #[test]
+    fn a_failing_operation_leaves_the_stubs_untouched() {
+        let imposter = imposter_with(&["a", "b"]);
+        let err = imposter
+            .apply_stub_operations(vec![
+                StubOperation::Delete {
+                    target: StubRef::Index(0),
+                },
+                StubOperation::Add {
+                    stub: stub("b"),
+                    index: None,
+                },
+            ])
+            .err()
+            .unwrap();
+        assert!(
+            matches!(
+                &err,
+                ImposterError::StubOperationFailed { operation: 1, source }
+                    if matches!(**source, ImposterError::StubIdConflict(ref id) if id == "b")
+            ),
+            "{err}"
+        );
+        assert_eq!(ids(&imposter), ["a", "b"]);
+    }
+
+    #[test]
+    fn replace_by_id_keeps_the_addressed_id() {
+        let imposter = imposter_with(&["a"]);
+        imposter
+            .apply_stub_operations(vec![StubOperation::Replace {
+                target: StubRef::Id("a".to_string()),
+                stub: stub("z"),
+            }])
+            .unwrap();
+        assert_eq!(ids(&imposter), ["a"]);
+    }
+}
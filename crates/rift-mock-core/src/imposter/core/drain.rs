@@ -0,0 +1,115 @@
+//! Drain mode: an imposter that stops taking new requests while the ones in flight finish.
+//!
+//! A draining imposter answers every new request, on new and kept-alive connections alike, with
+//! `503`, a `Retry-After` header and `Connection: close`, so clients back off and reconnect
+//! elsewhere. Requests that were already being served when the drain started complete normally,
+//! and their count is reported so a test-bed rollover can wait for it to reach zero before tearing
+//! the imposter down. Unlike `enabled`, drain state is runtime-only: it is never persisted and a
+//! replaced imposter starts undrained.
+
+use super::*;
+use serde::Serialize;
+use std::sync::atomic::AtomicUsize;
+
+/// `Retry-After` seconds a drain advertises when the caller names none.
+pub const DEFAULT_DRAIN_RETRY_AFTER_SECS: u64 = 5;
+
+#[derive(Debug, Default)]
+pub(crate) struct DrainState {
+    draining: AtomicBool,
+    retry_after_secs: AtomicU64,
+    in_flight: AtomicUsize,
+}
+
+/// `GET /imposters/:port/_drain`: whether the imposter is draining and how many requests it is
+/// still serving.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DrainStatus {
+    pub draining: bool,
+    /// Seconds sent in `Retry-After`; absent while not draining.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<u64>,
+    pub in_flight: usize,
+}
+
+/// Counts one request as in flight until dropped.
+pub(crate) struct InFlightRequest<'a>(&'a AtomicUsize);
+
+impl Drop for InFlightRequest<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Imposter {
+    /// Start draining: new requests get `503` with `Retry-After: retry_after_secs`. Draining an
+    /// already-draining imposter only updates the advertised delay.
+    pub fn start_drain(&self, retry_after_secs: u64) {
+        self.drain
+            .retry_after_secs
+            .store(retry_after_secs, Ordering::SeqCst);
+        self.drain.draining.store(true, Ordering::SeqCst);
+    }
+
+    /// Stop draining and serve new requests again.
+    pub fn stop_drain(&self) {
+        self.drain.draining.store(false, Ordering::SeqCst);
+    }
+
+    /// Current drain state and in-flight request count.
+    pub fn drain_status(&self) -> DrainStatus {
+        DrainStatus {
+            draining: self.drain.draining.load(Ordering::SeqCst),
+            retry_after: self.draining_retry_after(),
+            in_flight: self.drain.in_flight.load(Ordering::SeqCst),
+        }
+    }
+
+    /// The `Retry-After` seconds to refuse a new request with, or `None` when not draining.
+    pub(crate) fn draining_retry_after(&self) -> Option<u64> {
+        self.drain
+            .draining
+            .load(Ordering::SeqCst)
+            .then(|| self.drain.retry_after_secs.load(Ordering::SeqCst))
+    }
+
+    /// Count a request as in flight for as long as the returned guard lives.
+    pub(crate) fn begin_request(&self) -> InFlightRequest<'_> {
+        self.drain.in_flight.fetch_add(1, Ordering::SeqCst);
+        InFlightRequest(&self.drain.in_flight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drain_tracks_state_and_in_flight_requests() {
+        let imposter = Imposter::new(
+            serde_json::from_value(serde_json::json!({"port": 4545, "protocol": "http"})).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(imposter.draining_retry_after(), None);
+
+        let first = imposter.begin_request();
+        let second = imposter.begin_request();
+        imposter.start_drain(7);
+        assert_eq!(
+            imposter.drain_status(),
+            DrainStatus {
+                draining: true,
+                retry_after: Some(7),
+                in_flight: 2
+            }
+        );
+        drop(first);
+        drop(second);
+        assert_eq!(imposter.drain_status().in_flight, 0);
+
+        imposter.stop_drain();
+        assert_eq!(imposter.draining_retry_after(), None);
+        assert_eq!(imposter.drain_status().retry_after, None);
+    }
+}
@@ -0,0 +1,291 @@
+//! Closest-match replay (`closestMatch: true`): when no stub matches a request, serve the stub
+//! whose predicates it comes nearest to satisfying instead of falling through to no-match.
+//!
+//! Each stub's predicates are split into independently scorable clauses — one per field of a
+//! field operation (`equals`, `contains`, …) and, except under `deepEquals`, one per key of an
+//! object-valued field such as `query` or `headers` — and a stub scores the fraction of its clauses
+//! the request satisfies. The best score wins, ties going to the earlier stub as in normal
+//! matching; a stub satisfying no clause at all is never served. The response carries an
+//! `x-rift-fuzzy-match` header, and the failed clauses with the request's actual values are kept
+//! for `GET /imposters/{port}/fuzzyMatches`, so a drifted client shows up as a readable diff.
+//!
+//! Only stubs that could have matched are candidates: a stub scoped to another `space` or gated by
+//! `requiredScenarioState` is skipped, and an `inject` clause counts as failed without running the
+//! script.
+
+use super::stub_index::predicate_contains_inject;
+use super::verify::{FailedPredicate, actual_projection, client_ip_of, collapse_headers};
+use super::{Imposter, StubState};
+use crate::imposter::predicates::stub_matches_inner;
+use crate::imposter::types::{Predicate, PredicateOperation, RecordedRequest};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+/// Most recent fuzzy matches an imposter keeps for the admin API.
+pub(super) const FUZZY_MATCH_HISTORY: usize = 100;
+
+/// One request served by closest match: the stub that answered, how close the request came, and
+/// the clauses it failed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FuzzyMatch {
+    /// Per-imposter sequence number, echoed in the `x-rift-fuzzy-match` header.
+    pub id: u64,
+    pub request: RecordedRequest,
+    pub stub_index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stub_id: Option<String>,
+    /// Fraction of the stub's predicate clauses the request satisfied.
+    pub score: f64,
+    pub failed_predicates: Vec<FailedPredicate>,
+}
+
+impl FuzzyMatch {
+    /// The `x-rift-fuzzy-match` header value, e.g. `id=3; stub=1; score=0.67`.
+    pub fn header_value(&self) -> String {
+        format!(
+            "id={}; stub={}; score={:.2}",
+            self.id, self.stub_index, self.score
+        )
+    }
+}
+
+impl Imposter {
+    /// The stub closest to `request`, which no stub matched, with the diff that gets recorded in
+    /// the fuzzy-match history. `None` when no candidate stub satisfies a single clause. An error
+    /// from a backend consulted by a predicate propagates, as it does from normal matching.
+    pub(crate) fn closest_stub(
+        &self,
+        request: RecordedRequest,
+    ) -> anyhow::Result<Option<(Arc<StubState>, usize, FuzzyMatch)>> {
+        let headers = collapse_headers(&request.headers);
+        let form = Self::parse_form_data(&headers, request.body.as_deref());
+        let body_json = request
+            .body
+            .as_deref()
+            .and_then(|b| serde_json::from_str::<Value>(b).ok());
+        let client_ip = client_ip_of(&request);
+        let query_map: crate::util::FastMap<String, String> = request
+            .query
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        let xml_dom = request
+            .body
+            .as_deref()
+            .map(crate::behaviors::LazyXmlDom::new);
+        let flow_id = self.resolve_flow_id(&headers);
+        let satisfies = |clause: &Predicate| -> anyhow::Result<bool> {
+            if predicate_contains_inject(clause) {
+                return Ok(false);
+            }
+            stub_matches_inner(
+                std::slice::from_ref(clause),
+                &request.method,
+                &request.path,
+                None,
+                &headers,
+                request.body.as_deref(),
+                Some(&request.request_from),
+                client_ip.as_deref(),
+                form.as_ref(),
+                self.script_state_key(),
+                body_json.as_ref(),
+                xml_dom.as_ref(),
+                Some(&query_map),
+                request.tls.as_ref(),
+            )
+        };
+
+        let snapshot = self.snapshot();
+        let mut best: Option<(f64, usize, Vec<Predicate>)> = None;
+        for (index, state) in snapshot.stubs().iter().enumerate() {
+            let stub = &state.stub;
+            if stub.predicates.is_empty()
+                || stub.required_scenario_state.is_some()
+                || stub.space.as_ref().is_some_and(|space| *space != flow_id)
+            {
+                continue;
+            }
+            let clauses: Vec<Predicate> = stub.predicates.iter().flat_map(clauses).collect();
+            let mut failed = Vec::new();
+            for clause in &clauses {
+                if !satisfies(clause)? {
+                    failed.push(clause.clone());
+                }
+            }
+            let score = (clauses.len() - failed.len()) as f64 / clauses.len() as f64;
+            if score > 0.0 && best.as_ref().is_none_or(|(best, ..)| score > *best) {
+                best = Some((score, index, failed));
+            }
+        }
+
+        let Some((score, index, failed)) = best else {
+            return Ok(None);
+        };
+        let state = Arc::clone(&snapshot.stubs()[index]);
+        let fuzzy = FuzzyMatch {
+            id: self.fuzzy_match_seq.fetch_add(1, Ordering::Relaxed) + 1,
+            stub_index: index,
+            stub_id: state.stub.id.clone(),
+            score,
+            failed_predicates: failed
+                .into_iter()
+                .map(|predicate| FailedPredicate {
+                    actual: actual_projection(&request, &predicate),
+                    predicate,
+                })
+                .collect(),
+            request,
+        };
+        let mut history = self.fuzzy_matches.lock();
+        if history.len() == FUZZY_MATCH_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(fuzzy.clone());
+        Ok(Some((state, index, fuzzy)))
+    }
+
+    /// Requests served by closest match, oldest first (at most the last 100).
+    pub fn fuzzy_matches(&self) -> Vec<FuzzyMatch> {
+        self.fuzzy_matches.lock().iter().cloned().collect()
+    }
+
+    /// Forget the fuzzy-match history.
+    pub fn clear_fuzzy_matches(&self) {
+        self.fuzzy_matches.lock().clear();
+    }
+}
+
+/// Split `predicate` into the clauses it is the conjunction of (see the module docs). Compound,
+/// `inject` and single-field predicates are one clause each.
+fn clauses(predicate: &Predicate) -> Vec<Predicate> {
+    type Rebuild = fn(HashMap<String, Value>) -> PredicateOperation;
+    let (fields, rebuild, split_keys): (_, Rebuild, bool) = match &predicate.operation {
+        PredicateOperation::Equals(f) => (f, PredicateOperation::Equals, true),
+        PredicateOperation::DeepEquals(f) => (f, PredicateOperation::DeepEquals, false),
+        PredicateOperation::Contains(f) => (f, PredicateOperation::Contains, true),
+        PredicateOperation::StartsWith(f) => (f, PredicateOperation::StartsWith, true),
+        PredicateOperation::EndsWith(f) => (f, PredicateOperation::EndsWith, true),
+        PredicateOperation::Matches(f) => (f, PredicateOperation::Matches, true),
+        PredicateOperation::Exists(f) => (f, PredicateOperation::Exists, true),
+        PredicateOperation::Not(_)
+        | PredicateOperation::Or(_)
+        | PredicateOperation::And(_)
+        | PredicateOperation::Inject(_) => return vec![predicate.clone()],
+    };
+    // A selector applies to the body as a whole, so its keys are not independent.
+    let split_keys = split_keys && predicate.parameters.selector.is_none();
+    let clause = |field: &str, value: Value| Predicate {
+        parameters: predicate.parameters.clone(),
+        operation: rebuild(HashMap::from([(field.to_string(), value)])),
+    };
+
+    let mut out = Vec::new();
+    for (field, value) in fields {
+        match value {
+            Value::Object(keys) if split_keys && keys.len() > 1 => {
+                for (key, value) in keys {
+                    let single = serde_json::Map::from_iter([(key.clone(), value.clone())]);
+                    out.push(clause(field, Value::Object(single)));
+                }
+            }
+            _ => out.push(clause(field, value.clone())),
+        }
+    }
+    if out.is_empty() {
+        out.push(predicate.clone());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::imposter::ResponseMode;
+    use crate::imposter::types::ImposterConfig;
+    use serde_json::json;
+
+    fn imposter(stubs: Value) -> Imposter {
+        let config: ImposterConfig = serde_json::from_value(json!({
+            "port": 0,
+            "protocol": "http",
+            "closestMatch": true,
+            "stubs": stubs,
+        }))
+        .expect("config");
+        Imposter::new(config).expect("imposter")
+    }
+
+    fn request(method: &str, path: &str, query: &[(&str, &str)]) -> RecordedRequest {
+        RecordedRequest {
+            mode: ResponseMode::Text,
+            request_from: "127.0.0.1:5000".to_string(),
+            method: method.to_string(),
+            path: path.to_string(),
+            query: query
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+            headers: HashMap::new(),
+            body: None,
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            tls: None,
+        }
+    }
+
+    #[test]
+    fn object_fields_split_into_one_clause_per_key() {
+        let predicate: Predicate = serde_json::from_value(json!({
+            "equals": { "path": "/x", "query": { "a": "1", "b": "2" } }
+        }))
+        .unwrap();
+        assert_eq!(clauses(&predicate).len(), 3);
+
+        let deep: Predicate =
+            serde_json::from_value(json!({ "deepEquals": { "query": { "a": "1", "b": "2" } } }))
+                .unwrap();
+        assert_eq!(clauses(&deep).len(), 1, "deepEquals compares the whole map");
+    }
+
+    #[test]
+    fn picks_the_stub_with_the_most_satisfied_clauses_and_records_the_diff() {
+        let imposter = imposter(json!([
+            { "predicates": [{ "equals": { "method": "POST", "path": "/orders" } }],
+              "responses": [{ "is": { "body": "post" } }] },
+            { "predicates": [{ "equals": {
+                  "method": "GET", "path": "/orders", "query": { "page": "1", "size": "10" } } }],
+              "responses": [{ "is": { "body": "page" } }] },
+        ]));
+
+        let (_, index, fuzzy) = imposter
+            .closest_stub(request("GET", "/orders", &[("page", "2"), ("size", "10")]))
+            .unwrap()
+            .expect("a stub is close enough");
+        assert_eq!(index, 1);
+        assert_eq!(fuzzy.score, 0.75);
+        assert_eq!(fuzzy.failed_predicates.len(), 1);
+        assert_eq!(
+            serde_json::to_value(&fuzzy.failed_predicates[0].predicate).unwrap(),
+            json!({ "equals": { "query": { "page": "1" } } })
+        );
+        assert_eq!(fuzzy.header_value(), "id=1; stub=1; score=0.75");
+        assert_eq!(imposter.fuzzy_matches().len(), 1);
+    }
+
+    #[test]
+    fn a_request_satisfying_no_clause_is_not_served() {
+        let imposter = imposter(json!([
+            { "predicates": [{ "equals": { "method": "POST", "path": "/orders" } }],
+              "responses": [{ "is": { "body": "post" } }] },
+        ]));
+        let closest = imposter
+            .closest_stub(request("GET", "/users", &[]))
+            .unwrap();
+        assert!(closest.is_none());
+        assert!(imposter.fuzzy_matches().is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+//! Recorded latency distributions (`recording.latencyJitter`, with `addWaitBehavior`).
+//!
+//! A plain `addWaitBehavior` stamps each generated response with the one latency it was recorded
+//! with, so replay is suspiciously constant. With `latencyJitter`, every latency recorded into a
+//! generated stub is kept, one per occurrence, and all of the stub's responses wait
+//! `{mean, jitter, p50, p95, samples}`: a random delay within one standard deviation (`jitter`)
+//! of the mean latency, redrawn on every replay.
+//!
+//! Samples are kept in memory, at most the last [`MAX_LATENCY_SAMPLES`] per stub, and follow the
+//! stub's slot token. A stub restored from the datadir keeps its saved distribution until the next
+//! recording into it, which starts a fresh sample set.
+
+use super::*;
+
+/// Most recent latencies kept per generated stub.
+const MAX_LATENCY_SAMPLES: usize = 1000;
+
+impl Imposter {
+    /// Add `latency_ms` to the samples of the generated stub in `slot` and rewrite the stub's
+    /// waits to the updated distribution. A stub deleted in the meantime is left alone.
+    pub(crate) fn note_recorded_latency(&self, slot: u64, latency_ms: u64) {
+        self.mutate_stubs(|stubs| {
+            let Some(index) = stubs.iter().position(|state| state.slot == slot) else {
+                return;
+            };
+            let mut samples = self.latency_samples.lock();
+            // Drop the samples of stubs that no longer exist before adding to this one.
+            if samples.len() >= stubs.len() {
+                samples.retain(|slot, _| stubs.iter().any(|state| state.slot == *slot));
+            }
+            let recorded = samples.entry(slot).or_default();
+            if recorded.len() == MAX_LATENCY_SAMPLES {
+                recorded.pop_front();
+            }
+            recorded.push_back(latency_ms);
+            let wait = latency_distribution(recorded);
+            drop(samples);
+
+            let mut stub = stubs[index].stub.clone();
+            stub.responses = stub
+                .responses
+                .into_iter()
+                .map(|response| with_wait(response, wait.clone()))
+                .collect();
+            stubs[index] = Arc::new(stubs[index].with_stub(stub));
+        });
+    }
+}
+
+/// The `wait` a set of latency samples replays as: `{mean, jitter, p50, p95, samples}`, with
+/// `jitter` the population standard deviation and the percentiles nearest-rank.
+fn latency_distribution(samples: &std::collections::VecDeque<u64>) -> serde_json::Value {
+    let mut sorted: Vec<u64> = samples.iter().copied().collect();
+    sorted.sort_unstable();
+    let count = sorted.len().max(1) as f64;
+    let mean = sorted.iter().sum::<u64>() as f64 / count;
+    let variance = sorted
+        .iter()
+        .map(|&ms| (ms as f64 - mean).powi(2))
+        .sum::<f64>()
+        / count;
+    let percentile = |p: f64| {
+        let rank = ((p * sorted.len() as f64).ceil() as usize).clamp(1, sorted.len().max(1));
+        sorted.get(rank - 1).copied().unwrap_or(0)
+    };
+    serde_json::json!({
+        "mean": mean.round() as u64,
+        "jitter": variance.sqrt().round() as u64,
+        "p50": percentile(0.50),
+        "p95": percentile(0.95),
+        "samples": sorted.len(),
+    })
+}
+
+/// `response` with its `wait` behavior set to `wait`, replacing the recorded fixed latency.
+fn with_wait(response: StubResponse, wait: serde_json::Value) -> StubResponse {
+    match response {
+        StubResponse::Is {
+            is,
+            behaviors,
+            rift,
+            ..
+        } => {
+            let behaviors = match behaviors {
+                Some(serde_json::Value::Object(mut obj)) => {
+                    obj.insert("wait".to_string(), wait);
+                    serde_json::Value::Object(obj)
+                }
+                Some(serde_json::Value::Array(mut entries)) => {
+                    entries.retain(|entry| entry.get("wait").is_none());
+                    entries.insert(0, serde_json::json!({ "wait": wait }));
+                    serde_json::Value::Array(entries)
+                }
+                _ => serde_json::json!({ "wait": wait }),
+            };
+            StubResponse::new_is(is, Some(behaviors), rift)
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn every_recorded_occurrence_feeds_the_stubs_distribution() {
+        let imposter = Imposter::new(
+            serde_json::from_value(json!({
+                "port": 0,
+                "protocol": "http",
+                "stubs": [{ "responses": [{ "proxy": {
+                    "to": "http://upstream", "mode": "proxyAlways", "addWaitBehavior": true,
+                    "recording": { "latencyJitter": true } } }] }],
+            }))
+            .expect("config"),
+        )
+        .expect("imposter");
+        let mut slot = 0;
+        for latency in [100, 120, 140, 400] {
+            let stub = create_stub_from_proxy_response(
+                vec![json!({ "equals": { "path": "/slow" } })],
+                200,
+                &[],
+                latency.to_string().as_bytes(),
+                Some(latency),
+                None,
+                None,
+            );
+            slot = imposter.insert_or_append_recorded_stub(
+                stub,
+                "http://upstream",
+                "proxyAlways",
+                &ProxyRecordingOptions::default(),
+            );
+            imposter.note_recorded_latency(slot, latency);
+        }
+
+        let snapshot = imposter.snapshot();
+        let state = snapshot.stubs().iter().find(|s| s.slot == slot).unwrap();
+        assert_eq!(state.stub.responses.len(), 4);
+        for response in &state.stub.responses {
+            let StubResponse::Is { behaviors, .. } = response else {
+                panic!("recorded responses are `is` responses");
+            };
+            assert_eq!(
+                behaviors.as_ref().unwrap()["wait"],
+                json!({ "mean": 190, "jitter": 122, "p50": 120, "p95": 400, "samples": 4 })
+            );
+        }
+    }
+}
@@ -147,4 +147,22 @@ impl Imposter {
     pub fn is_enabled(&self) -> bool {
         self.enabled.load(Ordering::SeqCst)
     }
+
+    /// Whether the imposter is taking connections: it was bound and none of its accept loops has
+    /// ended. `false` for an imposter built outside the manager, which never binds.
+    pub fn is_serving(&self) -> bool {
+        let handles = self.serve_handles.lock();
+        !handles.is_empty() && handles.iter().all(|handle| !handle.is_finished())
+    }
+
+    /// The imposter's current definition: its retained config with the live stubs (generated
+    /// ones included) and the current enabled flag. This is what `--datadir` persists and what
+    /// cluster sync pushes to peers.
+    pub fn current_config(&self) -> ImposterConfig {
+        let mut config = self.config.clone();
+        config.stubs = self.get_stubs();
+        // The atomic is the runtime truth; the retained config only holds the boot value.
+        config.enabled = self.is_enabled();
+        config
+    }
 }
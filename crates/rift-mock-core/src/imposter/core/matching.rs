@@ -37,6 +37,35 @@ impl Imposter {
         request_from: Option<&str>,
         client_ip: Option<&str>,
     ) -> anyhow::Result<Option<(Arc<StubState>, usize)>>
+    where
+        SH: BuildHasher,
+    {
+        self.find_matching_stub_with_tls(
+            method,
+            path,
+            headers_map,
+            query,
+            body,
+            request_from,
+            client_ip,
+            None,
+        )
+    }
+
+    /// As [`Self::find_matching_stub_with_client`], for a request that arrived over TLS: `tls` is
+    /// what the connection negotiated, matched by the `tls` predicate field.
+    #[allow(clippy::too_many_arguments)]
+    pub fn find_matching_stub_with_tls<SH>(
+        &self,
+        method: &str,
+        path: &str,
+        headers_map: &HashMap<String, String, SH>,
+        query: Option<&str>,
+        body: Option<&str>,
+        request_from: Option<&str>,
+        client_ip: Option<&str>,
+        tls: Option<&RecordedTls>,
+    ) -> anyhow::Result<Option<(Arc<StubState>, usize)>>
     where
         SH: BuildHasher,
     {
@@ -102,6 +131,7 @@ impl Imposter {
                 body_json.as_ref(),
                 xml_dom.as_ref(),
                 Some(&query_map),
+                tls,
             )? {
                 // Bump the refcount instead of deep-cloning the whole `StubState` (issue #287).
                 // The caller (`handler.rs`) holds the returned `Arc<StubState>` across `.await`
@@ -135,6 +165,7 @@ impl Imposter {
         body: Option<&str>,
         request_from: Option<&str>,
         client_ip: Option<&str>,
+        tls: Option<&RecordedTls>,
         timeout: std::time::Duration,
     ) -> anyhow::Result<Option<(Arc<StubState>, usize)>>
     where
@@ -151,7 +182,7 @@ impl Imposter {
             has_inject || (snapshot.has_scenario_gate() && self.flow_store.is_blocking());
         drop(snapshot);
         if !needs_offload {
-            return self.find_matching_stub_with_client(
+            return self.find_matching_stub_with_tls(
                 method,
                 path,
                 headers_map,
@@ -159,6 +190,7 @@ impl Imposter {
                 body,
                 request_from,
                 client_ip,
+                tls,
             );
         }
 
@@ -170,8 +202,9 @@ impl Imposter {
         let body = body.map(str::to_string);
         let request_from = request_from.map(str::to_string);
         let client_ip = client_ip.map(str::to_string);
+        let tls = tls.cloned();
         let handle = tokio::task::spawn_blocking(move || {
-            this.find_matching_stub_with_client(
+            this.find_matching_stub_with_tls(
                 &method,
                 &path,
                 &headers_map,
@@ -179,6 +212,7 @@ impl Imposter {
                 body.as_deref(),
                 request_from.as_deref(),
                 client_ip.as_deref(),
+                tls.as_ref(),
             )
         });
         // The wall-clock deadline exists to bound a runaway inject *script*. A blocking flow-store
@@ -261,6 +295,7 @@ impl Imposter {
                 body_json.as_ref(),
                 xml_dom.as_ref(),
                 Some(&query_map),
+                None,
             )? {
                 return Ok(Some((Arc::clone(stub_state), index)));
             }
@@ -803,6 +838,7 @@ mod bounded_matching_tests {
                 None,
                 None,
                 None,
+                None,
                 std::time::Duration::from_secs(5),
             )
             .await
@@ -836,6 +872,7 @@ mod bounded_matching_tests {
                 // tests park every worker until the 10M-iteration loop cap throws — tens of seconds
                 // on a loaded/unoptimized-build runner. A genuine hang is still bounded (the CI job
                 // timeout backstops); don't tidy this back down. See #726.
+                None,
                 Duration::from_millis(600_000),
             )
             .await
@@ -854,6 +891,7 @@ mod bounded_matching_tests {
                 None,
                 None,
                 None,
+                None,
                 Duration::from_millis(600_000), // never-fire sentinel, see the note above (#726)
             )
             .await
@@ -883,6 +921,7 @@ mod bounded_matching_tests {
                 None,
                 None,
                 None,
+                None,
                 Duration::from_millis(25),
             )
             .await;
@@ -928,6 +967,7 @@ mod bounded_matching_tests {
                 None,
                 // Scriptless: takes the inline path and never arms the deadline. Kept at the same
                 // 600s never-fire sentinel as the inject tests above for uniformity (#726).
+                None,
                 Duration::from_millis(600_000),
             )
             .await
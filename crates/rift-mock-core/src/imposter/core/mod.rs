@@ -12,7 +12,8 @@ use super::response::{
 };
 use super::types::{
     DebugImposter, DebugResponsePreview, DebugStubInfo, ImposterConfig, ImposterError,
-    ProxyResponse, RecordedRequest, ResponseMode, RiftResponseExtension, Stub, StubResponse,
+    ProxyRecordingOptions, ProxyResponse, RecordedRequest, RecordedTls, ResponseMode,
+    RiftResponseExtension, RiftStreamChunk, RiftStreamConfig, Stub, StubResponse,
 };
 use crate::backends::InMemoryFlowStore;
 use crate::behaviors::{HasRepeatBehavior, RuleCycler};
@@ -32,7 +33,7 @@ use tokio::sync::broadcast;
 use tracing::{debug, error, info, warn};
 
 /// Maximum allowed proxy response body size (10 MB)
-const MAX_PROXY_RESPONSE_BODY_SIZE: usize = 10 * 1024 * 1024;
+pub(super) const MAX_PROXY_RESPONSE_BODY_SIZE: usize = 10 * 1024 * 1024;
 
 /// Request timeout for the shared proxy HTTP client.
 const PROXY_HTTP_CLIENT_TIMEOUT: Duration = Duration::from_secs(30);
@@ -44,7 +45,7 @@ const DEFAULT_FLOW_STATE_TTL_SECS: u64 = 300;
 /// Global HTTP client for proxy requests
 static HTTP_CLIENT: std::sync::OnceLock<reqwest::Client> = std::sync::OnceLock::new();
 
-fn get_http_client() -> &'static reqwest::Client {
+pub(super) fn get_http_client() -> &'static reqwest::Client {
     HTTP_CLIENT.get_or_init(|| {
         reqwest::Client::builder()
             .timeout(PROXY_HTTP_CLIENT_TIMEOUT)
@@ -147,6 +148,8 @@ pub struct Imposter {
     pub(crate) journal: Arc<dyn crate::imposter::journal::RequestJournal>,
     /// Whether imposter is enabled
     pub enabled: AtomicBool,
+    /// Drain mode and the in-flight request count (`POST /imposters/:port/_drain`); runtime-only.
+    pub(crate) drain: drain::DrainState,
     /// Creation timestamp (for future metrics/admin display)
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// Shutdown signal sender (for future graceful shutdown)
@@ -170,6 +173,45 @@ pub struct Imposter {
     /// next [`Self::stub_warnings`] read and reused until the next `mutate_stubs`. Keeps the O(n)
     /// analysis off the per-`GET` hot path and gives HTTP and embedded/FFI one shared code path.
     stub_warnings: ArcSwapOption<Vec<crate::extensions::stub_analysis::StubWarning>>,
+    /// `is.bodyFile` contents, re-read when the file changes. Rooted at the manager's datadir
+    /// (injected at create time); a standalone imposter has no root and rejects `bodyFile`.
+    pub(crate) body_files: super::body_file::BodyFileCache,
+    /// Source for waits, template randomness, and `_rift.fault` rolls; seeded from
+    /// `config.random_seed` so a configured seed makes them reproducible.
+    pub(crate) rng: crate::extensions::random::ImposterRng,
+    /// `config.default_behaviors`, parsed once; merged under each response's own `_behaviors`.
+    pub(crate) default_behaviors: Option<Arc<crate::behaviors::ResponseBehaviors>>,
+    /// Wakes the `--datadir` recording flusher (see `imposter::persist`) after a proxy response
+    /// is saved or a stub is generated from one. `None` without a datadir: recordings then live
+    /// in memory only.
+    pub(crate) recordings_changed: Option<Arc<tokio::sync::Notify>>,
+    /// Serializes `--datadir` writes of `{port}.json` (see `persist::write_snapshot`) and holds
+    /// the change generation the file last captured.
+    pub(crate) persisted: tokio::sync::Mutex<u64>,
+    /// Generation of the latest change queued for a `--datadir` write.
+    pub(crate) persist_requested: AtomicU64,
+    /// Writes recorded proxy bodies over `--recording-spill-threshold` to datadir files. `None`
+    /// without a datadir or a threshold: every recorded body stays in memory.
+    pub(crate) body_spill: Option<super::persist::BodySpill>,
+    /// The manager's `--proxy-tap`, written after every proxy step (see `imposter::tap`). `None`
+    /// without one.
+    pub(crate) tap: Option<Arc<super::tap::ProxyTap>>,
+    /// Requests served by `closestMatch`, newest last and bounded (see `fuzzy`), with the source
+    /// of their ids.
+    fuzzy_matches: Mutex<std::collections::VecDeque<fuzzy::FuzzyMatch>>,
+    fuzzy_match_seq: AtomicU64,
+    /// Recording sessions started through the admin API (see `session`); until the first one,
+    /// proxies record unconditionally.
+    recording_sessions: Mutex<session::RecordingSessions>,
+    /// `proxyShadow` comparisons: counts and the most recent drifted requests (see `shadow`).
+    shadow: Mutex<shadow::ShadowLog>,
+    /// Calls forwarded upstream: a count and the most recent ones (see `proxied`).
+    proxied: Mutex<proxied::ProxiedLog>,
+    /// Every request answered, with its outcome: a count and the most recent ones (see `served`).
+    served: Mutex<served::ServedLog>,
+    /// Latencies recorded into each generated stub under `recording.latencyJitter`, by slot token
+    /// (see `latency`).
+    latency_samples: Mutex<HashMap<u64, std::collections::VecDeque<u64>>>,
 }
 
 impl Imposter {
@@ -225,6 +267,8 @@ impl Imposter {
         let flow_store = Self::create_flow_store(&config, provider)?;
 
         let enabled = config.enabled;
+        let rng = crate::extensions::random::ImposterRng::new(config.random_seed);
+        let default_behaviors = Self::parse_default_behaviors(&config);
         Ok(Self {
             config,
             stubs_snapshot: ArcSwap::from_pointee(StubSnapshot::build(stubs)),
@@ -235,6 +279,7 @@ impl Imposter {
             journal: journal
                 .unwrap_or_else(|| Arc::new(crate::imposter::journal::LocalJournal::default())),
             enabled: AtomicBool::new(enabled),
+            drain: drain::DrainState::default(),
             created_at: chrono::Utc::now(),
             shutdown_tx: None,
             serve_handles: Mutex::new(Vec::new()),
@@ -242,9 +287,54 @@ impl Imposter {
             flow_store,
             sequencer,
             stub_warnings: ArcSwapOption::empty(),
+            body_files: super::body_file::BodyFileCache::default(),
+            rng,
+            default_behaviors,
+            recordings_changed: None,
+            persisted: tokio::sync::Mutex::new(0),
+            persist_requested: AtomicU64::new(0),
+            body_spill: None,
+            tap: None,
+            fuzzy_matches: Mutex::new(std::collections::VecDeque::new()),
+            fuzzy_match_seq: AtomicU64::new(0),
+            recording_sessions: Mutex::new(session::RecordingSessions::default()),
+            shadow: Mutex::new(shadow::ShadowLog::default()),
+            proxied: Mutex::new(proxied::ProxiedLog::default()),
+            served: Mutex::new(served::ServedLog::default()),
+            latency_samples: Mutex::new(HashMap::new()),
         })
     }
 
+    /// Parse `config.default_behaviors`. Invalid JSON is logged and ignored, like an unparsable
+    /// response `_behaviors`; the manager rejects it up front with a 400 (see
+    /// [`Self::validate_default_behaviors`]).
+    fn parse_default_behaviors(
+        config: &ImposterConfig,
+    ) -> Option<Arc<crate::behaviors::ResponseBehaviors>> {
+        let raw = config.default_behaviors.as_ref()?;
+        match crate::behaviors::ResponseBehaviors::from_json(raw) {
+            Ok(parsed) => Some(Arc::new(parsed)),
+            Err(e) => {
+                tracing::error!("Failed to parse defaultBehaviors: {e}");
+                None
+            }
+        }
+    }
+
+    /// Check `config.default_behaviors` parses and carries no `repeat` (response cycling never
+    /// consults imposter defaults, so a default `repeat` would be silently ignored).
+    pub fn validate_default_behaviors(config: &ImposterConfig) -> Result<(), String> {
+        let Some(raw) = &config.default_behaviors else {
+            return Ok(());
+        };
+        let parsed =
+            crate::behaviors::ResponseBehaviors::from_json(raw).map_err(|e| e.to_string())?;
+        if parsed.repeat.is_some() {
+            return Err("'repeat' cannot be set as a default behavior".to_string());
+        }
+        Ok(())
+    }
+
     /// The current stub snapshot: stubs, the index over them, and the matching gates, from a single
     /// wait-free load (issue #707). The match hot path calls this exactly once per request.
     pub(crate) fn snapshot(&self) -> arc_swap::Guard<Arc<StubSnapshot>> {
@@ -267,8 +357,10 @@ impl Imposter {
         let result = f(&mut next);
         // One store of stubs+index together (issue #707): the index is built from the very vector
         // it is stored with, so the two cannot diverge by construction.
-        self.stubs_snapshot
-            .store(Arc::new(StubSnapshot::build(next)));
+        let snapshot = Arc::new(StubSnapshot::build(next));
+        // Hits of stubs this removed would otherwise be kept forever.
+        self.served.lock().stats.retain_stubs(snapshot.stubs());
+        self.stubs_snapshot.store(snapshot);
         // Invalidate the cached stub-analysis warnings (issue #423). This is O(1) — the actual
         // O(n) recompute is deferred to the next `stub_warnings()` read — so high-frequency
         // mutations (e.g. proxy recording) don't pay analysis cost on every recorded stub.
@@ -533,6 +625,8 @@ impl Imposter {
                     return match proxy.mode.to_lowercase().as_str() {
                         "proxyonce" => ProxyMode::ProxyOnce,
                         "proxyalways" => ProxyMode::ProxyAlways,
+                        // A shadow proxy compares rather than records (see `shadow`).
+                        "proxyshadow" => ProxyMode::ProxyTransparent,
                         "proxytransparent" | "" => ProxyMode::ProxyTransparent,
                         _ => ProxyMode::ProxyTransparent,
                     };
@@ -543,15 +637,32 @@ impl Imposter {
     }
 }
 
+mod batch;
 mod bitset;
+mod drain;
+mod fuzzy;
+mod latency;
 mod lifecycle;
 mod matching;
 mod stub_index;
 use stub_index::StubSnapshot;
+mod proxied;
 mod proxy;
 mod recording;
 mod responses;
+mod served;
+mod session;
+mod shadow;
+mod traffic;
 mod verify;
+pub use batch::{StubOperation, StubOperationResult, StubRef};
+pub use drain::{DEFAULT_DRAIN_RETRY_AFTER_SECS, DrainStatus};
+pub use fuzzy::FuzzyMatch;
+pub use proxied::{ProxiedRequest, ProxiedRequestReport};
+pub use served::{ServedMessage, ServedRequest, ServedRequestDetail, ServedRequestReport};
+pub use session::{RecordingSessionSummary, RecordingState, RecordingStatus};
+pub use shadow::{ShadowDiff, ShadowDifference, ShadowDifferenceKind, ShadowReport};
+pub use traffic::{LATENCY_BUCKETS_MS, TrafficStats};
 pub use verify::{ClosestMatch, FailedPredicate, VerifyOptions, VerifyOutcome};
 
 #[cfg(test)]
@@ -1328,6 +1439,98 @@ mod tests {
         assert_eq!(query_obj["page"].as_str().unwrap(), "1");
     }
 
+    // Mountebank's generator matrix: `matches.query`/`matches.headers` select individual keys,
+    // and `ignore` drops keys from an all-keys selection.
+    #[test]
+    fn test_generator_selects_and_ignores_query_and_header_keys() {
+        let imposter = make_test_imposter();
+        let generators = vec![json!({
+            "matches": { "query": { "q": true, "page": false }, "headers": true },
+            "ignore": { "headers": ["Date", "x-request-id"] }
+        })];
+        let headers = HashMap::from([
+            ("accept".to_string(), "application/json".to_string()),
+            (
+                "date".to_string(),
+                "Tue, 01 Jan 2026 00:00:00 GMT".to_string(),
+            ),
+            ("x-request-id".to_string(), "abc".to_string()),
+        ]);
+        let predicates = imposter
+            .generate_predicates_from_request(
+                &generators,
+                "GET",
+                "/search",
+                &headers,
+                None,
+                Some("q=hello&page=1&ts=99"),
+            )
+            .expect("predicate generation succeeds");
+
+        let equals = &predicates[0]["equals"];
+        assert_eq!(equals["query"], json!({ "q": "hello" }));
+        assert_eq!(equals["headers"], json!({ "accept": "application/json" }));
+    }
+
+    #[test]
+    fn test_generator_jsonpath_and_xpath_select_part_of_the_body() {
+        let imposter = make_test_imposter();
+        let headers = HashMap::new();
+
+        let jsonpath = vec![json!({
+            "matches": { "body": true },
+            "jsonpath": { "selector": "$.order.id" }
+        })];
+        let predicates = imposter
+            .generate_predicates_from_request(
+                &jsonpath,
+                "POST",
+                "/orders",
+                &headers,
+                Some(r#"{"order":{"id":"A1","placedAt":"2026-01-01T00:00:00Z"}}"#),
+                None,
+            )
+            .expect("predicate generation succeeds");
+        assert_eq!(predicates[0]["equals"]["body"], "A1");
+        assert_eq!(predicates[0]["jsonpath"]["selector"], "$.order.id");
+
+        // The carried selector makes a later request with a different timestamp match.
+        let pred: crate::imposter::types::Predicate =
+            serde_json::from_value(predicates[0].clone()).unwrap();
+        assert!(
+            crate::imposter::predicate_matches(
+                &pred,
+                "POST",
+                "/orders",
+                None,
+                &headers,
+                Some(r#"{"order":{"id":"A1","placedAt":"2026-02-02T00:00:00Z"}}"#),
+                None,
+                None,
+                None,
+                0,
+            )
+            .unwrap()
+        );
+
+        let xpath = vec![json!({
+            "matches": { "body": true },
+            "xpath": { "selector": "//id" }
+        })];
+        let predicates = imposter
+            .generate_predicates_from_request(
+                &xpath,
+                "POST",
+                "/orders",
+                &headers,
+                Some("<order><id>B2</id><ts>1</ts></order>"),
+                None,
+            )
+            .expect("predicate generation succeeds");
+        assert_eq!(predicates[0]["equals"]["body"], "B2");
+        assert_eq!(predicates[0]["xpath"]["selector"], "//id");
+    }
+
     // =========================================================================
     // Gap 5.2: predicateGenerators.inject — JS function produces predicates
     // =========================================================================
@@ -1442,6 +1645,7 @@ mod tests {
             headers: std::collections::HashMap::new(),
             body: None,
             timestamp: "2026-01-01T00:00:00Z".to_string(),
+            tls: None,
         };
 
         use crate::imposter::journal::MAX_RECORDED_REQUESTS;
@@ -1481,6 +1685,7 @@ mod tests {
                 headers,
                 body: None,
                 timestamp: "2026-01-01T00:00:00Z".to_string(),
+                tls: None,
             }
         };
         imposter.record_request(req("A"));
@@ -0,0 +1,110 @@
+//! The calls an imposter's proxies forwarded upstream, for `GET /imposters/{port}/proxiedRequests`.
+//!
+//! Only real upstream calls are logged: a `proxyOnce` replay served from the recording store never
+//! leaves the process and is not counted, while `proxyAlways`, `proxyTransparent`, `proxyShadow`
+//! and `defaultForward` calls are, whether or not they produced a recording. A call that failed
+//! (refused, timed out, body too large) is logged with its error instead of a status, so a test can
+//! assert how many calls reached the upstream and how each one went.
+
+use super::Imposter;
+use serde::Serialize;
+use std::collections::VecDeque;
+
+/// Most recent upstream calls an imposter keeps for the admin API.
+const PROXIED_REQUEST_HISTORY: usize = 1000;
+
+/// One request forwarded upstream.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxiedRequest {
+    pub method: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// The upstream URL called, after any `pathRewrite`.
+    pub url: String,
+    /// The upstream's status; `None` when the call failed and `error` says why.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Time until the upstream's response head arrived, or until the call failed.
+    pub latency_ms: u64,
+    pub timestamp: String,
+}
+
+/// `GET /imposters/{port}/proxiedRequests`: how many calls were forwarded upstream and the most
+/// recent ones, oldest first.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxiedRequestReport {
+    pub count: u64,
+    pub proxied_requests: Vec<ProxiedRequest>,
+}
+
+/// Bookkeeping behind `Imposter::proxied`.
+#[derive(Debug, Default)]
+pub(super) struct ProxiedLog {
+    count: u64,
+    recent: VecDeque<ProxiedRequest>,
+}
+
+impl Imposter {
+    /// Log an upstream call.
+    pub(super) fn record_proxied(&self, request: ProxiedRequest) {
+        let mut log = self.proxied.lock();
+        log.count += 1;
+        if log.recent.len() == PROXIED_REQUEST_HISTORY {
+            log.recent.pop_front();
+        }
+        log.recent.push_back(request);
+    }
+
+    /// Upstream calls so far, with the most recent ones (at most the last 1000).
+    pub fn proxied_requests(&self) -> ProxiedRequestReport {
+        let log = self.proxied.lock();
+        ProxiedRequestReport {
+            count: log.count,
+            proxied_requests: log.recent.iter().cloned().collect(),
+        }
+    }
+
+    /// Forget the upstream calls and their count.
+    pub fn clear_proxied_requests(&self) {
+        *self.proxied.lock() = ProxiedLog::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::imposter::types::ImposterConfig;
+
+    fn call(path: &str) -> ProxiedRequest {
+        ProxiedRequest {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            query: None,
+            url: format!("http://upstream{path}"),
+            status_code: Some(200),
+            error: None,
+            latency_ms: 3,
+            timestamp: String::new(),
+        }
+    }
+
+    #[test]
+    fn history_is_bounded_but_the_count_is_not() {
+        let imposter = Imposter::new(ImposterConfig::default()).unwrap();
+        for i in 0..=PROXIED_REQUEST_HISTORY {
+            imposter.record_proxied(call(&format!("/{i}")));
+        }
+        let report = imposter.proxied_requests();
+        assert_eq!(report.count, PROXIED_REQUEST_HISTORY as u64 + 1);
+        assert_eq!(report.proxied_requests.len(), PROXIED_REQUEST_HISTORY);
+        assert_eq!(report.proxied_requests[0].path, "/1");
+
+        imposter.clear_proxied_requests();
+        assert_eq!(imposter.proxied_requests().count, 0);
+    }
+}
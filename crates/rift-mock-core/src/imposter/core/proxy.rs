@@ -7,8 +7,22 @@ use crate::imposter::predicates::regex_cache::cached_regex;
 use std::hash::BuildHasher;
 
 /// Parts read from a successful upstream proxy response, before recording:
-/// `(status, headers, body, latency_ms)`.
-type ForwardedResponse = (u16, Vec<(String, String)>, bytes::Bytes, u64);
+/// `(status, headers, body, latency_ms, chunks of a streamed body)`.
+type ForwardedResponse = (
+    u16,
+    Vec<(String, String)>,
+    bytes::Bytes,
+    u64,
+    Option<RiftStreamConfig>,
+);
+
+/// How long a streamed upstream response is read for when `recording.streamCaptureMs` is unset.
+const DEFAULT_STREAM_CAPTURE: Duration = Duration::from_secs(30);
+
+/// Streamed chunks arriving less than this long after the previous one are recorded as part of
+/// it: network reads split a burst arbitrarily, and only the pauses between bursts are worth
+/// replaying.
+const STREAM_CHUNK_GAP: Duration = Duration::from_millis(10);
 
 impl Imposter {
     /// Generate predicates from request based on predicateGenerators config.
@@ -99,7 +113,18 @@ impl Imposter {
                 .get("predicateOperator")
                 .and_then(|p| p.as_str())
                 .unwrap_or("equals");
-            let except_pattern = gen_obj.get("except").and_then(|e| e.as_str());
+            let except_pattern = gen_obj
+                .get("except")
+                .and_then(|e| e.as_str())
+                .filter(|p| !p.is_empty());
+            // `except` strips volatile parts (timestamps, UUIDs) from every generated value, and
+            // is carried onto the predicate so the matcher strips the same parts from later
+            // requests — which is what lets them match the normalized recording.
+            let except_regex = except_pattern.and_then(|pattern| cached_regex(pattern, false));
+            let normalize = |value: &str| match &except_regex {
+                Some(re) => re.replace_all(value, "").into_owned(),
+                None => value.to_string(),
+            };
 
             // Build predicate values
             let mut pred_values = serde_json::Map::new();
@@ -110,14 +135,10 @@ impl Imposter {
                 .and_then(|p| p.as_bool())
                 .unwrap_or(false)
             {
-                let mut path_val = path.to_string();
-                // Apply except pattern if present
-                if let Some(pattern) = except_pattern
-                    && let Some(re) = cached_regex(pattern, false)
-                {
-                    path_val = re.replace_all(&path_val, "").to_string();
-                }
-                pred_values.insert("path".to_string(), serde_json::Value::String(path_val));
+                pred_values.insert(
+                    "path".to_string(),
+                    serde_json::Value::String(normalize(path)),
+                );
             }
 
             // Handle method
@@ -126,45 +147,54 @@ impl Imposter {
                 .and_then(|m| m.as_bool())
                 .unwrap_or(false)
             {
-                let mut method_val = method.to_string();
-                if let Some(pattern) = except_pattern
-                    && let Some(re) = cached_regex(pattern, false)
-                {
-                    method_val = re.replace_all(&method_val, "").to_string();
-                }
-                pred_values.insert("method".to_string(), serde_json::Value::String(method_val));
+                pred_values.insert(
+                    "method".to_string(),
+                    serde_json::Value::String(normalize(method)),
+                );
             }
 
-            // Handle query
-            if matches
-                .get("query")
-                .and_then(|q| q.as_bool())
-                .unwrap_or(false)
+            // `ignore` drops keys from the generated query/headers, as a single key or a list:
+            // `{"query": "ts", "headers": ["date", "x-request-id"]}` (Mountebank).
+            let ignored = |field: &str| -> Vec<String> {
+                match gen_obj.get("ignore").and_then(|i| i.get(field)) {
+                    Some(serde_json::Value::String(key)) => vec![key.clone()],
+                    Some(serde_json::Value::Array(keys)) => keys
+                        .iter()
+                        .filter_map(|k| k.as_str().map(str::to_string))
+                        .collect(),
+                    _ => Vec::new(),
+                }
+            };
+
+            // Handle query: `true` takes every parameter, an object only those set to `true`
+            if let Some(selection) = matches.get("query")
                 && let Some(query_str) = query
             {
-                let query_map = crate::imposter::parse_query_string(query_str);
-                if !query_map.is_empty() {
-                    let query_json: serde_json::Map<String, serde_json::Value> = query_map
+                let ignore = ignored("query");
+                let query_json: serde_json::Map<String, serde_json::Value> =
+                    crate::imposter::parse_query_string(query_str)
                         .into_iter()
-                        .map(|(k, v)| (k, serde_json::Value::String(v)))
+                        .filter(|(k, _)| selects(selection, k) && !ignore.contains(k))
+                        .map(|(k, v)| (k, serde_json::Value::String(normalize(&v))))
                         .collect();
+                if !query_json.is_empty() {
                     pred_values.insert("query".to_string(), serde_json::Value::Object(query_json));
                 }
             }
 
-            // Handle headers
-            if let Some(header_matches) = matches.get("headers").and_then(|h| h.as_object()) {
-                let mut header_preds = serde_json::Map::new();
-                for (header_name, should_match) in header_matches {
-                    if should_match.as_bool().unwrap_or(false)
-                        && let Some(header_value) = headers.get(header_name)
-                    {
-                        header_preds.insert(
-                            header_name.clone(),
-                            serde_json::Value::String(header_value.clone()),
-                        );
-                    }
-                }
+            // Handle headers, selected the same way (names compare case-insensitively)
+            if let Some(selection) = matches.get("headers") {
+                let ignore = ignored("headers");
+                let header_preds: serde_json::Map<String, serde_json::Value> = headers
+                    .iter()
+                    .filter(|(name, _)| {
+                        selects(selection, name)
+                            && !ignore.iter().any(|i| i.eq_ignore_ascii_case(name))
+                    })
+                    .map(|(name, value)| {
+                        (name.clone(), serde_json::Value::String(normalize(value)))
+                    })
+                    .collect();
                 if !header_preds.is_empty() {
                     pred_values.insert(
                         "headers".to_string(),
@@ -173,21 +203,41 @@ impl Imposter {
                 }
             }
 
-            // Handle body
+            // Handle body — the whole body, or the value a `jsonpath`/`xpath` selector picks out
+            // of it. The selector is carried onto the predicate, so later requests are compared
+            // on the same sub-selection rather than their entire payload.
+            let jsonpath = gen_obj
+                .get("jsonpath")
+                .filter(|j| j.get("selector").is_some());
+            let xpath = gen_obj.get("xpath").filter(|x| x.get("selector").is_some());
             if matches
                 .get("body")
                 .and_then(|b| b.as_bool())
                 .unwrap_or(false)
                 && let Some(body_str) = body
             {
-                let mut body_val = body_str.to_string();
-                // Apply except pattern if present
-                if let Some(pattern) = except_pattern
-                    && let Some(re) = cached_regex(pattern, false)
+                let selected = if let Some(selector) = jsonpath
+                    .and_then(|j| j.get("selector"))
+                    .and_then(|s| s.as_str())
                 {
-                    body_val = re.replace_all(&body_val, "").to_string();
+                    crate::behaviors::extract_jsonpath(body_str, selector)
+                } else if let Some(selector) = xpath
+                    .and_then(|x| x.get("selector"))
+                    .and_then(|s| s.as_str())
+                {
+                    let ns: Option<HashMap<String, String>> = xpath
+                        .and_then(|x| x.get("ns"))
+                        .and_then(|ns| serde_json::from_value(ns.clone()).ok());
+                    crate::behaviors::extract_xpath_with_ns(body_str, selector, ns.as_ref())
+                } else {
+                    Some(body_str.to_string())
+                };
+                if let Some(value) = selected {
+                    pred_values.insert(
+                        "body".to_string(),
+                        serde_json::Value::String(normalize(&value)),
+                    );
                 }
-                pred_values.insert("body".to_string(), serde_json::Value::String(body_val));
             }
 
             if pred_values.is_empty() {
@@ -206,6 +256,17 @@ impl Imposter {
                 "caseSensitive".to_string(),
                 serde_json::Value::Bool(case_sensitive),
             );
+            if let Some(pattern) = except_pattern {
+                predicate.insert(
+                    "except".to_string(),
+                    serde_json::Value::String(pattern.to_string()),
+                );
+            }
+            if let Some(jsonpath) = jsonpath {
+                predicate.insert("jsonpath".to_string(), jsonpath.clone());
+            } else if let Some(xpath) = xpath {
+                predicate.insert("xpath".to_string(), xpath.clone());
+            }
 
             predicates.push(serde_json::Value::Object(predicate));
         }
@@ -232,6 +293,42 @@ impl Imposter {
     /// For proxyOnce: Insert new stub BEFORE the proxy stub (so it matches first next time)
     /// For proxyAlways: Append response to existing stub AFTER proxy stub, or insert new AFTER proxy
     pub fn insert_or_append_proxy_stub(&self, stub: Stub, proxy_to: &str, proxy_mode: &str) {
+        self.insert_or_append_recorded_stub(
+            stub,
+            proxy_to,
+            proxy_mode,
+            &ProxyRecordingOptions::default(),
+        );
+    }
+
+    /// [`Self::insert_or_append_proxy_stub`] honoring the proxy's `recording` options when a
+    /// proxyAlways response is appended: a duplicate (same status code and body) is skipped
+    /// under `collapseDuplicates`, and nothing is appended past `maxResponsesPerStub`. Returns
+    /// the slot token of the stub inserted or appended to.
+    pub fn insert_or_append_recorded_stub(
+        &self,
+        stub: Stub,
+        proxy_to: &str,
+        proxy_mode: &str,
+        options: &ProxyRecordingOptions,
+    ) -> u64 {
+        let slot = self.record_stub(stub, proxy_to, proxy_mode, options);
+        if let Some(bus) = &self.event_bus {
+            bus.publish_lifecycle(
+                crate::imposter::events::ImposterAction::StubRecorded,
+                self.config.port,
+            );
+        }
+        slot
+    }
+
+    fn record_stub(
+        &self,
+        stub: Stub,
+        proxy_to: &str,
+        proxy_mode: &str,
+        options: &ProxyRecordingOptions,
+    ) -> u64 {
         self.mutate_stubs(|stubs| {
             // Re-locate the proxy stub inside the write critical section to avoid stale-index races.
             let proxy_stub_index = stubs
@@ -268,33 +365,57 @@ impl Imposter {
                     // so rebuild the entry from a stub with the extended responses while reusing the
                     // slot's cycler + slot token.
                     let mut merged = stubs[idx].stub.clone();
-                    merged.responses.extend(stub.responses);
+                    for response in stub.responses {
+                        if options
+                            .max_responses_per_stub
+                            .is_some_and(|max| merged.responses.len() >= max)
+                        {
+                            debug!("Stub at index {idx} is full; dropping recorded response");
+                            break;
+                        }
+                        if options.collapse_duplicates
+                            && merged
+                                .responses
+                                .iter()
+                                .any(|existing| same_recorded_response(existing, &response))
+                        {
+                            debug!("Collapsed duplicate recorded response into stub {idx}");
+                            continue;
+                        }
+                        merged.responses.push(response);
+                    }
                     let total = merged.responses.len();
                     stubs[idx] = Arc::new(stubs[idx].with_stub(merged));
                     debug!(
                         "Appended response to existing stub at index {idx} (proxyAlways mode, {total} total responses)"
                     );
-                    return;
+                    return stubs[idx].slot;
                 }
 
                 // No matching stub found: insert new stub AFTER the proxy stub
                 let insert_index = (proxy_stub_index + 1).min(stubs.len());
-                stubs.insert(insert_index, Arc::new(StubState::new(stub)));
+                let state = StubState::new(stub);
+                let slot = state.slot;
+                stubs.insert(insert_index, Arc::new(state));
                 debug!(
                     "Inserted generated stub at index {} after proxy (proxyAlways mode)",
                     insert_index
                 );
+                slot
             } else {
                 // For proxyOnce: insert new stub BEFORE the proxy stub
                 // This ensures the recorded stub matches first on subsequent requests
                 let index = proxy_stub_index.min(stubs.len());
-                stubs.insert(index, Arc::new(StubState::new(stub)));
+                let state = StubState::new(stub);
+                let slot = state.slot;
+                stubs.insert(index, Arc::new(state));
                 debug!(
                     "Inserted generated stub at index {} before proxy (proxyOnce mode)",
                     index
                 );
+                slot
             }
-        });
+        })
     }
 
     /// Forward a request through proxy and optionally record the response
@@ -350,17 +471,23 @@ impl Imposter {
 
         // Consult the proxy-recording gate. `AlreadyRecorded` replays; `Claimed` grants the
         // right to record; `InFlight` (a concurrent proxyOnce loser) and an unavailable
-        // store proxy upstream without recording.
+        // store proxy upstream without recording. A paused or stopped recording session hands
+        // its claim straight back: the request is forwarded, and nothing new is recorded. So does a
+        // `proxyShadow` proxy, which compares instead of recording (see `shadow`).
+        let shadow = proxy_config.mode.eq_ignore_ascii_case("proxyShadow");
+        let recording = !shadow && self.recording_enabled();
         let claim_token = match self.proxy_store.try_claim(port, &signature) {
             Ok(ClaimOutcome::AlreadyRecorded) => {
                 if let Some(recorded) = self.proxy_store.lookup(port, &signature) {
                     debug!("Returning recorded proxy response (proxyOnce mode)");
-                    return Ok((
-                        recorded.status,
-                        recorded.headers,
-                        recorded.body,
-                        recorded.latency_ms,
-                    ));
+                    let body = match &recorded.body_file {
+                        Some(file) => self
+                            .body_files
+                            .read_uncached(file)
+                            .context("Failed to load spilled recorded proxy body")?,
+                        None => recorded.body,
+                    };
+                    return Ok((recorded.status, recorded.headers, body, recorded.latency_ms));
                 }
                 // AlreadyRecorded but nothing to replay: a race (concurrent clear) or a
                 // misbehaving backend. Forward without recording rather than fail, but leave
@@ -371,6 +498,10 @@ impl Imposter {
                 None
             }
             Ok(ClaimOutcome::InFlight) => None,
+            Ok(ClaimOutcome::Claimed(token)) if !recording => {
+                self.proxy_store.release_claim(port, &signature, token);
+                None
+            }
             Ok(ClaimOutcome::Claimed(token)) => Some(token),
             Err(e) => {
                 warn!("Proxy recording store unavailable; forwarding without recording: {e}");
@@ -410,11 +541,22 @@ impl Imposter {
                 request = request.body(body_str.to_string());
             }
 
+            // The client's timeout still bounds the response head; a streamed body may then be
+            // read for up to the capture window on top of it (see `read_upstream_body`).
+            let capture = proxy_config
+                .recording
+                .as_ref()
+                .and_then(|options| options.stream_capture_ms)
+                .map_or(DEFAULT_STREAM_CAPTURE, Duration::from_millis);
+            let request = request.timeout(PROXY_HTTP_CLIENT_TIMEOUT + capture);
+
             // Send request
-            let response = request
-                .send()
+            let response = tokio::time::timeout(PROXY_HTTP_CLIENT_TIMEOUT, request.send())
                 .await
-                .with_context(|| format!("Failed to send proxy request to {target_url}"))?;
+                .map_err(|_| anyhow::anyhow!("Timed out waiting for a response from {target_url}"))
+                .and_then(|sent| {
+                    sent.with_context(|| format!("Failed to send proxy request to {target_url}"))
+                })?;
             let latency_ms = start.elapsed().as_millis() as u64;
 
             let status = response.status().as_u16();
@@ -432,25 +574,27 @@ impl Imposter {
                 );
             }
 
-            let body_bytes = response
-                .bytes()
-                .await
-                .with_context(|| format!("Failed to read response body from {target_url}"))?;
-
-            if body_bytes.len() > MAX_PROXY_RESPONSE_BODY_SIZE {
-                anyhow::bail!(
-                    "Proxy response body from {} exceeds maximum size ({} > {} bytes)",
-                    target_url,
-                    body_bytes.len(),
-                    MAX_PROXY_RESPONSE_BODY_SIZE
-                );
-            }
+            let body_deadline = start + PROXY_HTTP_CLIENT_TIMEOUT;
+            let (body_bytes, stream) =
+                read_upstream_body(response, &target_url, body_deadline, capture).await?;
 
-            Ok((status, response_headers, body_bytes, latency_ms))
+            Ok((status, response_headers, body_bytes, latency_ms, stream))
         }
         .await;
 
-        let (status, mut response_headers, body_bytes, latency_ms) = match forwarded {
+        self.record_proxied(super::ProxiedRequest {
+            method: method.to_string(),
+            path: uri.path().to_string(),
+            query: uri.query().map(str::to_string),
+            url: target_url.clone(),
+            status_code: forwarded.as_ref().ok().map(|parts| parts.0),
+            error: forwarded.as_ref().err().map(|e| format!("{e:#}")),
+            latency_ms: forwarded
+                .as_ref()
+                .map_or_else(|_| start.elapsed().as_millis() as u64, |parts| parts.3),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        });
+        let (status, mut response_headers, body_bytes, latency_ms, stream) = match forwarded {
             Ok(parts) => parts,
             Err(e) => {
                 if let Some(token) = claim_token {
@@ -460,18 +604,50 @@ impl Imposter {
             }
         };
 
+        let generates_stub = recording
+            && (!proxy_config.predicate_generators.is_empty()
+                || proxy_config.add_wait_behavior
+                || proxy_config.add_decorate_behavior.is_some());
+
+        // A body over `--recording-spill-threshold` is recorded by reference: written to a datadir
+        // file once, with only its path kept in the proxy store and the generated stub. A failed
+        // write is not fatal — the body is recorded in memory as before.
+        let spilled_body = match &self.body_spill {
+            Some(spill)
+                if (claim_token.is_some() || generates_stub)
+                    && spill.applies_to(body_bytes.len()) =>
+            {
+                match spill.write(port, &body_bytes).await {
+                    Ok(file) => Some(file),
+                    Err(e) => {
+                        warn!(
+                            "Failed to spill {} byte recorded body to the datadir, keeping it in memory: {e:#}",
+                            body_bytes.len()
+                        );
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+
         // Record the response only if we hold a claim.
         if let Some(token) = claim_token {
             let recorded_response = RecordedResponse {
                 status,
                 headers: response_headers.clone(),
-                body: body_bytes.to_vec(),
+                body: if spilled_body.is_some() {
+                    Vec::new()
+                } else {
+                    body_bytes.to_vec()
+                },
                 latency_ms: if proxy_config.add_wait_behavior {
                     Some(latency_ms)
                 } else {
                     None
                 },
                 timestamp_secs: crate::util::unix_timestamp(),
+                body_file: spilled_body.clone(),
             };
 
             if let Err(e) =
@@ -484,15 +660,14 @@ impl Imposter {
                     "Failed to record proxy response, releasing claim so it stays retryable: {e}"
                 );
                 self.proxy_store.release_claim(port, &signature, token);
+            } else {
+                self.note_recordings_changed();
             }
         }
 
         // Generate and insert stub if predicateGenerators, addWaitBehavior, or addDecorateBehavior is configured
         // (Mountebank generates stubs automatically when these are enabled)
-        if !proxy_config.predicate_generators.is_empty()
-            || proxy_config.add_wait_behavior
-            || proxy_config.add_decorate_behavior.is_some()
-        {
+        if generates_stub {
             // An `inject` generator executes a JS script; run the generator pass off the async
             // worker under the script deadline (issue #476). Script-free generator lists (the
             // common case) keep the inline path — pure predicate building, no script pool.
@@ -561,15 +736,26 @@ impl Imposter {
                     // not applied to the first (live proxy) response. This matches Mountebank's
                     // behavior. The decoration will be applied when the saved stub is used for
                     // subsequent requests.
-                    let new_stub = create_stub_from_proxy_response(
+                    let mut new_stub = create_stub_from_proxy_response(
                         predicates,
                         status,
                         &response_headers,
-                        &body_bytes,
+                        if spilled_body.is_some() {
+                            &[]
+                        } else {
+                            &body_bytes
+                        },
                         latency_for_stub,
                         proxy_config.add_decorate_behavior.clone(),
                         Some(proxy_config.to.clone()),
                     );
+                    if let Some(file) = &spilled_body {
+                        let binary = std::str::from_utf8(&body_bytes).is_err();
+                        reference_spilled_body(&mut new_stub, file, binary);
+                    }
+                    if let Some(stream) = &stream {
+                        stream_recorded_body(&mut new_stub, stream, &body_bytes);
+                    }
 
                     // Insert or append the stub based on proxy mode
                     // proxyOnce: Insert new stub before the proxy stub
@@ -579,7 +765,18 @@ impl Imposter {
                     } else {
                         &proxy_config.mode
                     };
-                    self.insert_or_append_proxy_stub(new_stub, &proxy_config.to, mode);
+                    let options = proxy_config.recording.clone().unwrap_or_default();
+                    let slot = self.insert_or_append_recorded_stub(
+                        new_stub,
+                        &proxy_config.to,
+                        mode,
+                        &options,
+                    );
+                    if proxy_config.add_wait_behavior && options.latency_jitter {
+                        self.note_recorded_latency(slot, latency_ms);
+                    }
+                    self.note_session_stub(slot);
+                    self.note_recordings_changed();
                     debug!(
                         "Generated stub from proxy response for path {} (mode: {})",
                         uri.path(),
@@ -601,6 +798,23 @@ impl Imposter {
             }
         }
 
+        if shadow
+            && let Some(diff) = self.compare_shadow(
+                method,
+                uri.path(),
+                uri.query(),
+                headers,
+                body,
+                shadow::LiveResponse {
+                    status,
+                    headers: &response_headers,
+                    body: &body_bytes,
+                },
+            )
+        {
+            response_headers.push(("x-rift-shadow-diff".to_string(), diff.header_value()));
+        }
+
         Ok((
             status,
             response_headers,
@@ -610,6 +824,134 @@ impl Imposter {
     }
 }
 
+/// Whether a generator's `matches.query`/`matches.headers` selection takes `key`: `true` takes
+/// every key, an object takes the keys (case-insensitively) set to `true`.
+fn selects(selection: &serde_json::Value, key: &str) -> bool {
+    match selection {
+        serde_json::Value::Bool(all) => *all,
+        serde_json::Value::Object(keys) => keys
+            .iter()
+            .any(|(k, v)| k.eq_ignore_ascii_case(key) && v.as_bool().unwrap_or(false)),
+        _ => false,
+    }
+}
+
+/// Point a generated stub's `is` response at a spilled body file in place of an inline body.
+/// Binary content keeps `mode: binary`, so the file is served byte for byte.
+fn reference_spilled_body(stub: &mut Stub, file: &str, binary: bool) {
+    for response in &mut stub.responses {
+        if let StubResponse::Is {
+            is,
+            behaviors,
+            rift,
+            ..
+        } = response
+        {
+            let mut is = is.clone();
+            is.body = None;
+            is.body_file = Some(file.to_string());
+            is.mode = if binary {
+                ResponseMode::Binary
+            } else {
+                ResponseMode::Text
+            };
+            *response = StubResponse::new_is(is, behaviors.take(), rift.take());
+        }
+    }
+}
+
+/// Read an upstream response body. A body with a `Content-Length` is read whole, by
+/// `body_deadline`. One without is treated as a stream: it is read for at most `capture`, a
+/// stream still open then ends its recording there, and the chunks it arrived in are returned
+/// with the pause before each when there was more than one.
+async fn read_upstream_body(
+    mut response: reqwest::Response,
+    target_url: &str,
+    body_deadline: Instant,
+    capture: Duration,
+) -> anyhow::Result<(bytes::Bytes, Option<RiftStreamConfig>)> {
+    let streamed = response.content_length().is_none();
+    let deadline = if streamed {
+        Instant::now() + capture
+    } else {
+        body_deadline
+    };
+    let mut body = bytes::BytesMut::new();
+    let mut chunks: Vec<RiftStreamChunk> = Vec::new();
+    let mut previous = Instant::now();
+    loop {
+        let chunk = match tokio::time::timeout_at(deadline.into(), response.chunk()).await {
+            Ok(chunk) => {
+                chunk.with_context(|| format!("Failed to read response body from {target_url}"))?
+            }
+            Err(_) if streamed => {
+                info!(
+                    "Stream from {target_url} still open after {}ms; recording the {} bytes received",
+                    capture.as_millis(),
+                    body.len()
+                );
+                break;
+            }
+            Err(_) => anyhow::bail!("Timed out reading response body from {target_url}"),
+        };
+        let Some(chunk) = chunk else { break };
+        if body.len() + chunk.len() > MAX_PROXY_RESPONSE_BODY_SIZE {
+            anyhow::bail!(
+                "Proxy response body from {target_url} exceeds maximum size (> {MAX_PROXY_RESPONSE_BODY_SIZE} bytes)"
+            );
+        }
+        let gap = previous.elapsed();
+        previous = Instant::now();
+        match chunks.last_mut() {
+            Some(last) if gap < STREAM_CHUNK_GAP => last.size += chunk.len(),
+            _ => chunks.push(RiftStreamChunk {
+                size: chunk.len(),
+                delay_ms: gap.as_millis() as u64,
+            }),
+        }
+        body.extend_from_slice(&chunk);
+    }
+    let stream = (streamed && chunks.len() > 1).then_some(RiftStreamConfig { chunks });
+    Ok((body.freeze(), stream))
+}
+
+/// Give a generated stub's `is` response the recorded chunking of its body. An inline text body
+/// is kept verbatim rather than re-serialized JSON, so the chunk sizes still line up with it.
+fn stream_recorded_body(stub: &mut Stub, stream: &RiftStreamConfig, body: &[u8]) {
+    for response in &mut stub.responses {
+        if let StubResponse::Is {
+            is,
+            behaviors,
+            rift,
+            ..
+        } = response
+        {
+            let mut is = is.clone();
+            if is.mode == ResponseMode::Text
+                && is.body_file.is_none()
+                && let Ok(text) = std::str::from_utf8(body)
+            {
+                is.body = Some(serde_json::Value::String(text.to_string()));
+            }
+            let mut rift = rift.take().unwrap_or_default();
+            rift.stream = Some(stream.clone());
+            *response = StubResponse::new_is(is, behaviors.take(), Some(rift));
+        }
+    }
+}
+
+/// Whether two recorded `is` responses carry the same status code and body. Headers are ignored:
+/// upstreams stamp `Date` (and often request ids) on every response. Spilled bodies compare by
+/// file, which is named by content.
+fn same_recorded_response(a: &StubResponse, b: &StubResponse) -> bool {
+    match (a, b) {
+        (StubResponse::Is { is: a, .. }, StubResponse::Is { is: b, .. }) => {
+            a.status_code == b.status_code && a.body == b.body && a.body_file == b.body_file
+        }
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod proxy_dedup_tests {
     use super::*;
@@ -667,4 +1009,85 @@ mod proxy_dedup_tests {
             "both recorded responses must land on the single matching stub"
         );
     }
+
+    #[test]
+    fn recording_options_collapse_duplicates_and_cap_responses() {
+        let imposter = imposter_with_proxy("http://upstream");
+        let options = ProxyRecordingOptions {
+            collapse_duplicates: true,
+            max_responses_per_stub: Some(2),
+            ..Default::default()
+        };
+        for body in ["first", "first", "second", "third"] {
+            imposter.insert_or_append_recorded_stub(
+                multi_key_stub(body),
+                "http://upstream",
+                "proxyAlways",
+                &options,
+            );
+        }
+
+        let stubs = imposter.get_stubs();
+        assert_eq!(stubs.len(), 2);
+        let bodies: Vec<_> = stubs[1]
+            .responses
+            .iter()
+            .map(|r| match r {
+                StubResponse::Is { is, .. } => is.body.clone(),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(bodies, [Some(json!("first")), Some(json!("second"))]);
+    }
+
+    #[test]
+    fn except_normalizes_every_generated_value_and_carries_onto_the_predicate() {
+        let uuid = "[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}";
+        let generators = [json!({
+            "matches": { "path": true, "query": true, "headers": { "x-request-id": true } },
+            "except": uuid,
+        })];
+        let headers = HashMap::from([(
+            "x-request-id".to_string(),
+            "req-3f2504e0-4f89-11d3-9a0c-0305e82c3301".to_string(),
+        )]);
+        let predicates = Imposter::generate_predicates_impl(
+            &generators,
+            "GET",
+            "/orders/3f2504e0-4f89-11d3-9a0c-0305e82c3301",
+            &headers,
+            None,
+            Some("trace=9b2c4f1e-0000-4000-8000-000000000000"),
+        )
+        .unwrap();
+
+        let predicate = &predicates[0];
+        assert_eq!(predicate["equals"]["path"], "/orders/");
+        assert_eq!(predicate["equals"]["query"]["trace"], "");
+        assert_eq!(predicate["equals"]["headers"]["x-request-id"], "req-");
+        assert_eq!(predicate["except"], uuid);
+
+        // The matcher strips the same pattern from later requests, so a different id still matches.
+        let predicate: crate::imposter::Predicate =
+            serde_json::from_value(predicate.clone()).unwrap();
+        let later = HashMap::from([(
+            "x-request-id".to_string(),
+            "req-00000000-1111-4222-8333-444444444444".to_string(),
+        )]);
+        assert!(
+            crate::imposter::predicate_matches(
+                &predicate,
+                "GET",
+                "/orders/00000000-1111-4222-8333-444444444444",
+                Some("trace=11111111-2222-4333-8444-555555555555"),
+                &later,
+                None,
+                None,
+                None,
+                None,
+                0,
+            )
+            .unwrap()
+        );
+    }
 }
@@ -113,9 +113,56 @@ impl Imposter {
         self.journal.retain(self.journal_port(), &keep);
     }
 
+    /// The imposter's recorded traffic — journal entries paired with saved proxy responses — as
+    /// a HAR 1.2 document (see [`crate::recording::har`]).
+    pub fn export_har(&self) -> crate::recording::har::Har {
+        let scheme = if self.config.protocol == "https" {
+            "https"
+        } else {
+            "http"
+        };
+        let host = match self.config.host.as_deref() {
+            None | Some("0.0.0.0" | "::") => "localhost",
+            Some(host) => host,
+        };
+        let base_url = format!("{scheme}://{host}:{}", self.journal_port());
+        let mut saved = self.proxy_store.export(self.journal_port());
+        // Spilled bodies are read back so the archive is self-contained; one that can no longer
+        // be read exports as an empty body rather than failing the whole export.
+        for response in saved.iter_mut().flat_map(|(_, responses)| responses) {
+            if let Some(file) = response.body_file.take() {
+                match self.body_files.read_uncached(&file) {
+                    Ok(body) => response.body = body,
+                    Err(e) => warn!("Exporting recorded response without its body: {e}"),
+                }
+            }
+        }
+        crate::recording::har::build_har(&base_url, &self.get_recorded_requests(), saved)
+    }
+
+    /// An OpenAPI 3 document inferred from the imposter's recorded traffic (see
+    /// [`crate::recording::openapi`]), titled with the imposter's name.
+    pub fn export_openapi(&self) -> serde_json::Value {
+        let title = self
+            .config
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("Imposter {}", self.journal_port()));
+        crate::recording::openapi::openapi_from_har(&self.export_har(), &title)
+    }
+
     /// Clear saved proxy responses
     pub fn clear_proxy_responses(&self) {
         self.proxy_store.clear(self.journal_port());
+        self.note_recordings_changed();
+    }
+
+    /// Schedule a `--datadir` flush of recorded state (generated stubs and saved proxy
+    /// responses). A no-op without a datadir; bursts of changes coalesce into one write.
+    pub(crate) fn note_recordings_changed(&self) {
+        if let Some(changed) = &self.recordings_changed {
+            changed.notify_one();
+        }
     }
 
     /// Count this request toward `numberOfRequests` (fires even when recording is off).
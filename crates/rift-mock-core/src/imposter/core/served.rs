@@ -0,0 +1,306 @@
+//! Every request an imposter answered, with its outcome, for `GET /imposters/{port}/servedRequests`.
+//!
+//! Unlike the `savedRequests` journal this log is always on and records what happened rather than
+//! what arrived: the status sent, the stub that answered (if any) and how long it took. It is a
+//! short in-memory tail for watching traffic live, not a record to verify against — only the most
+//! recent requests are kept, each with a sequence number a poller passes back as `?since=`.
+//!
+//! The newest of them also keep the headers and bodies exchanged, for
+//! `GET /imposters/{port}/servedRequests/{seq}`: enough to inspect a request and send it again.
+
+use super::Imposter;
+use super::traffic::TrafficStats;
+use crate::behaviors::header_to_title_case;
+use crate::imposter::types::ResponseMode;
+use base64::Engine;
+use bytes::Bytes;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+/// Most recent served requests an imposter keeps for the admin API.
+const SERVED_REQUEST_HISTORY: usize = 1000;
+
+/// Most recent served requests whose headers and bodies are kept as well.
+const SERVED_EXCHANGE_HISTORY: usize = 100;
+
+/// Bodies larger than this are not kept; the detail reports their size instead.
+const MAX_SERVED_BODY_SIZE: usize = 64 * 1024;
+
+/// One request the imposter answered.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServedRequest {
+    /// Position in the imposter's traffic, from 1; never reused until the log is cleared.
+    pub seq: u64,
+    pub method: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    pub status_code: u16,
+    /// Index of the stub that answered; `None` when no stub matched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stub_index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stub_id: Option<String>,
+    /// Slot of the stub that answered: its identity for the traffic totals, kept while the stub
+    /// moves.
+    #[serde(skip)]
+    pub(crate) stub_slot: Option<u64>,
+    /// Time from the request reaching the imposter to its response being ready.
+    pub latency_ms: u64,
+    /// Kind of fault injected into the response, as its `x-rift-fault` header reported it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fault: Option<String>,
+    pub timestamp: String,
+}
+
+/// `GET /imposters/{port}/servedRequests`: how many requests were served and the most recent ones
+/// (newer than the caller's cursor), oldest first.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServedRequestReport {
+    pub count: u64,
+    pub served_requests: Vec<ServedRequest>,
+}
+
+/// The headers and body of one side of a served request.
+#[derive(Debug, Clone, Default)]
+pub struct ServedMessage {
+    pub headers: hyper::HeaderMap,
+    pub body: Bytes,
+    /// Size of a body too large to keep; `body` is then empty.
+    pub body_omitted: Option<usize>,
+}
+
+impl ServedMessage {
+    pub fn new(headers: hyper::HeaderMap, body: Bytes) -> Self {
+        if body.len() > MAX_SERVED_BODY_SIZE {
+            Self {
+                headers,
+                body: Bytes::new(),
+                body_omitted: Some(body.len()),
+            }
+        } else {
+            Self {
+                headers,
+                body,
+                body_omitted: None,
+            }
+        }
+    }
+}
+
+/// Wire form of a [`ServedMessage`]: Title-Case multi-value headers like a recorded request, and
+/// a body that is text, or base64 with `_mode: "binary"`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ServedMessageView {
+    headers: HashMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+    #[serde(rename = "_mode", skip_serializing_if = "is_text_mode")]
+    mode: ResponseMode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body_omitted: Option<usize>,
+}
+
+fn is_text_mode(mode: &ResponseMode) -> bool {
+    matches!(mode, ResponseMode::Text)
+}
+
+impl Serialize for ServedMessage {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut headers: HashMap<String, Vec<String>> = HashMap::new();
+        for (name, value) in &self.headers {
+            headers
+                .entry(header_to_title_case(name.as_str()))
+                .or_default()
+                .push(String::from_utf8_lossy(value.as_bytes()).into_owned());
+        }
+        let (body, mode) = if self.body.is_empty() {
+            (None, ResponseMode::Text)
+        } else {
+            match std::str::from_utf8(&self.body) {
+                Ok(text) => (Some(text.to_string()), ResponseMode::Text),
+                Err(_) => (
+                    Some(base64::engine::general_purpose::STANDARD.encode(&self.body)),
+                    ResponseMode::Binary,
+                ),
+            }
+        };
+        ServedMessageView {
+            headers,
+            body,
+            mode,
+            body_omitted: self.body_omitted,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// `GET /imposters/{port}/servedRequests/{seq}`: a served request with what was exchanged.
+#[derive(Debug, Clone, Serialize)]
+pub struct ServedRequestDetail {
+    #[serde(flatten)]
+    pub summary: ServedRequest,
+    pub request: ServedMessage,
+    pub response: ServedMessage,
+}
+
+/// Bookkeeping behind `Imposter::served`.
+#[derive(Debug, Default)]
+pub(super) struct ServedLog {
+    count: u64,
+    recent: VecDeque<ServedRequest>,
+    /// Request and response of the newest requests, by sequence number.
+    exchanges: VecDeque<(u64, Arc<(ServedMessage, ServedMessage)>)>,
+    pub(super) stats: TrafficStats,
+}
+
+impl Imposter {
+    /// Log a served request with the request and response exchanged, assigning its sequence
+    /// number.
+    pub(crate) fn record_served(
+        &self,
+        mut request: ServedRequest,
+        exchange: (ServedMessage, ServedMessage),
+    ) {
+        let mut log = self.served.lock();
+        log.stats.record(&request);
+        log.count += 1;
+        request.seq = log.count;
+        if log.recent.len() == SERVED_REQUEST_HISTORY {
+            log.recent.pop_front();
+        }
+        log.recent.push_back(request);
+        if log.exchanges.len() == SERVED_EXCHANGE_HISTORY {
+            log.exchanges.pop_front();
+        }
+        let seq = log.count;
+        log.exchanges.push_back((seq, Arc::new(exchange)));
+    }
+
+    /// A served request with its headers and bodies, while it is among the last 100 served.
+    pub fn served_request(&self, seq: u64) -> Option<ServedRequestDetail> {
+        let log = self.served.lock();
+        let (_, exchange) = log.exchanges.iter().find(|(s, _)| *s == seq)?;
+        let summary = log.recent.iter().find(|request| request.seq == seq)?;
+        Some(ServedRequestDetail {
+            summary: summary.clone(),
+            request: exchange.0.clone(),
+            response: exchange.1.clone(),
+        })
+    }
+
+    /// Requests served so far, with the most recent ones (at most the last 1000) whose sequence
+    /// number is above `since`.
+    pub fn served_requests(&self, since: Option<u64>) -> ServedRequestReport {
+        let log = self.served.lock();
+        let since = since.unwrap_or(0);
+        ServedRequestReport {
+            count: log.count,
+            served_requests: log
+                .recent
+                .iter()
+                .filter(|request| request.seq > since)
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Forget the served requests and restart the sequence. The traffic totals are kept.
+    pub fn clear_served_requests(&self) {
+        let mut log = self.served.lock();
+        let stats = std::mem::take(&mut log.stats);
+        *log = ServedLog {
+            stats,
+            ..ServedLog::default()
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::imposter::types::ImposterConfig;
+
+    fn served(path: &str) -> ServedRequest {
+        ServedRequest {
+            seq: 0,
+            method: "GET".to_string(),
+            path: path.to_string(),
+            query: None,
+            status_code: 200,
+            stub_index: Some(0),
+            stub_id: None,
+            stub_slot: None,
+            latency_ms: 1,
+            fault: None,
+            timestamp: String::new(),
+        }
+    }
+
+    fn exchange(body: &'static [u8]) -> (ServedMessage, ServedMessage) {
+        (
+            ServedMessage::new(hyper::HeaderMap::new(), Bytes::from_static(body)),
+            ServedMessage::default(),
+        )
+    }
+
+    #[test]
+    fn history_is_bounded_and_cut_by_the_cursor() {
+        let imposter = Imposter::new(ImposterConfig::default()).unwrap();
+        for i in 0..=SERVED_REQUEST_HISTORY {
+            imposter.record_served(served(&format!("/{i}")), exchange(b""));
+        }
+        let report = imposter.served_requests(None);
+        assert_eq!(report.count, SERVED_REQUEST_HISTORY as u64 + 1);
+        assert_eq!(report.served_requests.len(), SERVED_REQUEST_HISTORY);
+        assert_eq!(report.served_requests[0].path, "/1");
+        assert_eq!(report.served_requests[0].seq, 2);
+
+        let tail = imposter.served_requests(Some(SERVED_REQUEST_HISTORY as u64 - 1));
+        let seqs: Vec<u64> = tail.served_requests.iter().map(|r| r.seq).collect();
+        assert_eq!(
+            seqs,
+            [
+                SERVED_REQUEST_HISTORY as u64,
+                SERVED_REQUEST_HISTORY as u64 + 1
+            ]
+        );
+
+        imposter.clear_served_requests();
+        assert_eq!(imposter.served_requests(None).count, 0);
+    }
+
+    #[test]
+    fn detail_is_kept_for_the_newest_requests_only() {
+        let imposter = Imposter::new(ImposterConfig::default()).unwrap();
+        for _ in 0..SERVED_EXCHANGE_HISTORY {
+            imposter.record_served(served("/text"), exchange(b"{\"id\":1}"));
+        }
+        imposter.record_served(served("/binary"), exchange(&[0xff, 0x00]));
+        assert!(imposter.served_request(1).is_none());
+
+        let text = serde_json::to_value(imposter.served_request(2).unwrap()).unwrap();
+        assert_eq!(text["path"], "/text");
+        assert_eq!(text["request"]["body"], "{\"id\":1}");
+        assert!(text["request"].get("_mode").is_none());
+
+        let last = SERVED_EXCHANGE_HISTORY as u64 + 1;
+        let binary = serde_json::to_value(imposter.served_request(last).unwrap()).unwrap();
+        assert_eq!(binary["request"]["body"], "/wA=");
+        assert_eq!(binary["request"]["_mode"], "binary");
+    }
+
+    #[test]
+    fn large_bodies_are_reported_not_kept() {
+        let message = ServedMessage::new(
+            hyper::HeaderMap::new(),
+            Bytes::from(vec![b'x'; MAX_SERVED_BODY_SIZE + 1]),
+        );
+        assert!(message.body.is_empty());
+        assert_eq!(message.body_omitted, Some(MAX_SERVED_BODY_SIZE + 1));
+    }
+}
@@ -0,0 +1,281 @@
+//! Recording sessions: named, start/pause/stop-able spans of proxy recording.
+//!
+//! Until the first session is started, a proxy records for as long as its stub exists — the
+//! Mountebank behavior. Once the admin API starts a session, recording follows it: only while a
+//! session is recording are proxy responses saved and stubs generated, and each stub generated
+//! (or appended to, under `proxyAlways`) is tagged with the session so the session's stubs can be
+//! exported or discarded as a unit. A paused or stopped session still proxies, and still replays
+//! what was recorded before; it just records nothing new.
+//!
+//! Stubs are tagged by slot token, so a stub keeps its session through in-place replaces and drops
+//! out of it when deleted. Session membership is in-memory only: stubs restored from the datadir
+//! after a restart belong to no session.
+
+use super::*;
+use serde::Serialize;
+
+/// Whether, and into which session, proxy responses are being recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecordingState {
+    /// No session was ever started: proxies record as they always have.
+    Always,
+    /// The current session is recording.
+    Recording,
+    /// The current session is paused; starting it again resumes it.
+    Paused,
+    /// The last session was stopped; nothing records until another is started.
+    Stopped,
+}
+
+/// A recording session's name and how many of the imposter's stubs it generated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingSessionSummary {
+    pub name: String,
+    pub stub_count: usize,
+}
+
+/// `GET /imposters/:port/_recording`: the recording state, the current session (recording or
+/// paused), and every session still held, oldest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingStatus {
+    pub state: RecordingState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+    pub sessions: Vec<RecordingSessionSummary>,
+}
+
+/// Session bookkeeping behind `Imposter::recording_sessions`.
+#[derive(Debug)]
+pub(super) struct RecordingSessions {
+    state: RecordingState,
+    current: Option<String>,
+    /// Each session's stub slot tokens, in the order sessions were first started.
+    sessions: Vec<(String, Vec<u64>)>,
+}
+
+impl Default for RecordingSessions {
+    fn default() -> Self {
+        Self {
+            state: RecordingState::Always,
+            current: None,
+            sessions: Vec::new(),
+        }
+    }
+}
+
+impl RecordingSessions {
+    fn slots(&self, name: &str) -> Option<&Vec<u64>> {
+        self.sessions
+            .iter()
+            .find(|(session, _)| session == name)
+            .map(|(_, slots)| slots)
+    }
+}
+
+impl Imposter {
+    /// Whether a proxy should record right now: always before the first session, and afterwards
+    /// only while a session is recording.
+    pub(crate) fn recording_enabled(&self) -> bool {
+        matches!(
+            self.recording_sessions.lock().state,
+            RecordingState::Always | RecordingState::Recording
+        )
+    }
+
+    /// Tag the stub in `slot`, just generated or appended to from a proxy response, with the
+    /// recording session (if any).
+    pub(crate) fn note_session_stub(&self, slot: u64) {
+        let mut sessions = self.recording_sessions.lock();
+        if sessions.state != RecordingState::Recording {
+            return;
+        }
+        let Some(current) = sessions.current.clone() else {
+            return;
+        };
+        if let Some((_, slots)) = sessions.sessions.iter_mut().find(|(s, _)| *s == current)
+            && !slots.contains(&slot)
+        {
+            slots.push(slot);
+        }
+    }
+
+    /// Start recording into session `name`, creating it if new. Starting a paused or stopped
+    /// session resumes it, and its stubs accumulate; starting another session ends the current one.
+    pub fn start_recording_session(&self, name: &str) -> RecordingStatus {
+        let mut sessions = self.recording_sessions.lock();
+        if sessions.slots(name).is_none() {
+            sessions.sessions.push((name.to_string(), Vec::new()));
+        }
+        sessions.current = Some(name.to_string());
+        sessions.state = RecordingState::Recording;
+        drop(sessions);
+        self.recording_status()
+    }
+
+    /// Pause the current session. `None` when no session is current.
+    pub fn pause_recording_session(&self) -> Option<RecordingStatus> {
+        let mut sessions = self.recording_sessions.lock();
+        sessions.current.as_ref()?;
+        sessions.state = RecordingState::Paused;
+        drop(sessions);
+        Some(self.recording_status())
+    }
+
+    /// Stop the current session; its stubs stay in place and in the session. `None` when no
+    /// session is current.
+    pub fn stop_recording_session(&self) -> Option<RecordingStatus> {
+        let mut sessions = self.recording_sessions.lock();
+        sessions.current.take()?;
+        sessions.state = RecordingState::Stopped;
+        drop(sessions);
+        Some(self.recording_status())
+    }
+
+    /// The recording state and sessions. A session's stub count covers only stubs still present.
+    pub fn recording_status(&self) -> RecordingStatus {
+        let snapshot = self.snapshot();
+        let live: std::collections::HashSet<u64> =
+            snapshot.stubs().iter().map(|state| state.slot).collect();
+        let sessions = self.recording_sessions.lock();
+        RecordingStatus {
+            state: sessions.state,
+            session: sessions.current.clone(),
+            sessions: sessions
+                .sessions
+                .iter()
+                .map(|(name, slots)| RecordingSessionSummary {
+                    name: name.clone(),
+                    stub_count: slots.iter().filter(|slot| live.contains(slot)).count(),
+                })
+                .collect(),
+        }
+    }
+
+    /// The stubs session `name` generated, in imposter order. `None` for an unknown session.
+    pub fn recording_session_stubs(&self, name: &str) -> Option<Vec<Stub>> {
+        let slots = self.recording_sessions.lock().slots(name)?.clone();
+        Some(
+            self.snapshot()
+                .stubs()
+                .iter()
+                .filter(|state| slots.contains(&state.slot))
+                .map(|state| state.stub.clone())
+                .collect(),
+        )
+    }
+
+    /// Delete session `name` and every stub it generated, returning how many stubs were removed.
+    /// Discarding the current session also stops recording. `None` for an unknown session.
+    pub fn discard_recording_session(&self, name: &str) -> Option<usize> {
+        let slots = {
+            let mut sessions = self.recording_sessions.lock();
+            let position = sessions.sessions.iter().position(|(s, _)| s == name)?;
+            if sessions.current.as_deref() == Some(name) {
+                sessions.current = None;
+                sessions.state = RecordingState::Stopped;
+            }
+            sessions.sessions.remove(position).1
+        };
+        let removed = self.mutate_stubs(|stubs| {
+            let before = stubs.len();
+            stubs.retain(|state| !slots.contains(&state.slot));
+            before - stubs.len()
+        });
+        if removed > 0 {
+            self.note_recordings_changed();
+        }
+        Some(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const UPSTREAM: &str = "http://upstream";
+
+    fn imposter() -> Imposter {
+        let cfg = serde_json::from_value(json!({
+            "port": 0,
+            "protocol": "http",
+            "stubs": [{ "responses": [{ "proxy": { "to": UPSTREAM, "mode": "proxyOnce" } }] }],
+        }))
+        .expect("valid imposter config");
+        Imposter::new(cfg).expect("test imposter")
+    }
+
+    fn recorded(imposter: &Imposter, path: &str) {
+        let stub: Stub = serde_json::from_value(json!({
+            "predicates": [{ "equals": { "path": path } }],
+            "responses": [{ "is": { "body": path } }],
+        }))
+        .expect("valid stub");
+        let slot = imposter.insert_or_append_recorded_stub(
+            stub,
+            UPSTREAM,
+            "proxyOnce",
+            &ProxyRecordingOptions::default(),
+        );
+        imposter.note_session_stub(slot);
+    }
+
+    #[test]
+    fn recording_follows_the_session_once_one_is_started() {
+        let imposter = imposter();
+        assert!(imposter.recording_enabled());
+
+        imposter.start_recording_session("checkout");
+        assert!(imposter.recording_enabled());
+        imposter.pause_recording_session().unwrap();
+        assert!(!imposter.recording_enabled());
+        imposter.start_recording_session("checkout");
+        assert!(imposter.recording_enabled());
+        let status = imposter.stop_recording_session().unwrap();
+        assert!(!imposter.recording_enabled());
+        assert_eq!(status.state, RecordingState::Stopped);
+        assert_eq!(status.session, None);
+        assert!(imposter.stop_recording_session().is_none());
+    }
+
+    #[test]
+    fn a_session_exports_and_discards_only_its_own_stubs() {
+        let imposter = imposter();
+        recorded(&imposter, "/before");
+        imposter.start_recording_session("checkout");
+        recorded(&imposter, "/cart");
+        recorded(&imposter, "/pay");
+        imposter.start_recording_session("search");
+        recorded(&imposter, "/search");
+
+        let status = imposter.recording_status();
+        assert_eq!(status.session.as_deref(), Some("search"));
+        assert_eq!(
+            status.sessions,
+            [
+                RecordingSessionSummary {
+                    name: "checkout".to_string(),
+                    stub_count: 2
+                },
+                RecordingSessionSummary {
+                    name: "search".to_string(),
+                    stub_count: 1
+                },
+            ]
+        );
+
+        let checkout = imposter.recording_session_stubs("checkout").unwrap();
+        assert_eq!(checkout.len(), 2);
+        assert_eq!(imposter.discard_recording_session("checkout"), Some(2));
+        assert_eq!(
+            imposter.stub_count(),
+            3,
+            "the proxy, /before and /search remain"
+        );
+        assert!(imposter.recording_session_stubs("checkout").is_none());
+        assert_eq!(imposter.discard_recording_session("checkout"), None);
+    }
+}
@@ -0,0 +1,507 @@
+//! Shadow-traffic comparison (`"mode": "proxyShadow"`): a proxy that always forwards to the real
+//! backend and records nothing, but also works out which stub *would* have answered the request
+//! and compares that stub's response with the live one — a contract-drift detector for mocks kept
+//! in sync with a live service.
+//!
+//! The stub consulted is the first non-proxy stub whose predicates match, as normal matching would
+//! pick it (`space` and `requiredScenarioState` respected); its upcoming response is peeked, not
+//! advanced. Only `is` responses are compared:
+//!
+//! - the status code;
+//! - every header the stub sets (names case-insensitive) — headers only the live response carries
+//!   are ignored, as are `Content-Length` and hop-by-hop headers;
+//! - the body, structurally when both sides are JSON: fields the live body lacks (`missing`),
+//!   fields the stub lacks (`unexpected`) and fields whose JSON type changed (`type`). Scalar values
+//!   are not compared, and arrays are compared by their first element, the shape of their items.
+//!   Text bodies are compared verbatim.
+//!
+//! A request that no stub covers counts as drift too. Drifted requests are kept for
+//! `GET /imposters/{port}/shadowDiffs` and flagged on the response by an `x-rift-shadow-diff`
+//! header.
+
+use super::stub_index::predicate_contains_inject;
+use super::{Imposter, StubState};
+use crate::imposter::predicates::stub_matches_inner;
+use crate::imposter::response::truncate_with_ellipsis;
+use crate::imposter::types::{ResponseMode, StubResponse};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::hash::BuildHasher;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Most recent drifted requests an imposter keeps for the admin API.
+const SHADOW_DIFF_HISTORY: usize = 100;
+
+/// Longest text body kept verbatim in a `value` difference.
+const MAX_TEXT_DIFF_LEN: usize = 500;
+
+/// How a live response departs from the stub's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ShadowDifferenceKind {
+    /// The stub sets it; the live response doesn't.
+    Missing,
+    /// The live response has it; the stub doesn't.
+    Unexpected,
+    /// Both have it, as different JSON types (`expected`/`actual` name the types).
+    Type,
+    /// Both have it, with different values.
+    Value,
+}
+
+/// One difference between the stub's response and the live one.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShadowDifference {
+    /// `statusCode`, `headers.<name>`, `body`, or a path into a JSON body such as
+    /// `body.items[0].id`.
+    pub field: String,
+    pub kind: ShadowDifferenceKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<Value>,
+}
+
+/// A shadowed request whose live response drifted from the stub that would have answered it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShadowDiff {
+    /// Per-imposter sequence number, echoed in the `x-rift-shadow-diff` header.
+    pub id: u64,
+    pub method: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// The stub that would have answered; `None` when no stub covers the request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stub_index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stub_id: Option<String>,
+    pub differences: Vec<ShadowDifference>,
+    pub timestamp: String,
+}
+
+impl ShadowDiff {
+    /// The `x-rift-shadow-diff` header value, e.g. `id=3; stub=1; differences=2` (`stub=none` when
+    /// no stub covers the request).
+    pub fn header_value(&self) -> String {
+        let stub = self
+            .stub_index
+            .map_or_else(|| "none".to_string(), |index| index.to_string());
+        format!(
+            "id={}; stub={stub}; differences={}",
+            self.id,
+            self.differences.len()
+        )
+    }
+}
+
+/// `GET /imposters/{port}/shadowDiffs`: how many requests were compared, how many drifted, and the
+/// most recent drifted ones, oldest first.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShadowReport {
+    pub compared: u64,
+    pub drifted: u64,
+    pub diffs: Vec<ShadowDiff>,
+}
+
+/// Shadow bookkeeping behind `Imposter::shadow`.
+#[derive(Debug, Default)]
+pub(super) struct ShadowLog {
+    compared: u64,
+    drifted: u64,
+    diffs: VecDeque<ShadowDiff>,
+}
+
+/// The response a shadowed request got back from the live backend.
+pub(crate) struct LiveResponse<'a> {
+    pub status: u16,
+    pub headers: &'a [(String, String)],
+    pub body: &'a [u8],
+}
+
+impl Imposter {
+    /// Compare a shadowed request's live response with the response of the stub that would have
+    /// answered it, recording the result. Returns the recorded diff when the two drifted; `None`
+    /// when they agree or the stub's response isn't comparable (not an `is`).
+    pub(crate) fn compare_shadow<SH: BuildHasher>(
+        &self,
+        method: &str,
+        path: &str,
+        query: Option<&str>,
+        headers: &HashMap<String, String, SH>,
+        body: Option<&str>,
+        live: LiveResponse<'_>,
+    ) -> Option<ShadowDiff> {
+        let differences = match self.shadowed_stub(method, path, query, headers, body) {
+            Ok(Some((state, index))) => {
+                let response = match self.peek_stub_response(&state) {
+                    Ok(response) => response,
+                    Err(e) => {
+                        warn!("Shadow comparison skipped, stub response unavailable: {e:#}");
+                        return None;
+                    }
+                };
+                let Some(StubResponse::Is { is, .. }) = response else {
+                    return None;
+                };
+                let mut differences = Vec::new();
+                if is.status_code != live.status {
+                    differences.push(ShadowDifference {
+                        field: "statusCode".to_string(),
+                        kind: ShadowDifferenceKind::Value,
+                        expected: Some(is.status_code.into()),
+                        actual: Some(live.status.into()),
+                    });
+                }
+                diff_headers(&is.headers, live.headers, &mut differences);
+                if is.body_file.is_none()
+                    && is.mode == ResponseMode::Text
+                    && let Some(expected) = &is.body
+                {
+                    diff_body(expected, live.body, &mut differences);
+                }
+                Some((index, state.stub.id.clone(), differences))
+            }
+            Ok(None) => None,
+            Err(e) => {
+                warn!("Shadow comparison skipped, matching backend unavailable: {e:#}");
+                return None;
+            }
+        };
+
+        let mut log = self.shadow.lock();
+        log.compared += 1;
+        let (stub_index, stub_id, differences) = match differences {
+            Some((_, _, differences)) if differences.is_empty() => return None,
+            Some((index, id, differences)) => (Some(index), id, differences),
+            None => (None, None, Vec::new()),
+        };
+        log.drifted += 1;
+        let diff = ShadowDiff {
+            id: log.drifted,
+            method: method.to_string(),
+            path: path.to_string(),
+            query: query.map(str::to_string),
+            stub_index,
+            stub_id,
+            differences,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        };
+        if log.diffs.len() == SHADOW_DIFF_HISTORY {
+            log.diffs.pop_front();
+        }
+        log.diffs.push_back(diff.clone());
+        Some(diff)
+    }
+
+    /// The first non-proxy stub matching the request. `Err` when a backend consulted by a gate or
+    /// predicate failed; the caller skips the comparison rather than fail the forwarded request.
+    fn shadowed_stub<SH: BuildHasher>(
+        &self,
+        method: &str,
+        path: &str,
+        query: Option<&str>,
+        headers: &HashMap<String, String, SH>,
+        body: Option<&str>,
+    ) -> anyhow::Result<Option<(Arc<StubState>, usize)>> {
+        let snapshot = self.snapshot();
+        let form = Self::parse_form_data(headers, body);
+        let flow_id = self.resolve_flow_id(headers);
+        let body_json = body.and_then(|b| serde_json::from_str::<Value>(b).ok());
+        let query_map = crate::imposter::predicates::parse_query(query);
+        let xml_dom = body.map(crate::behaviors::LazyXmlDom::new);
+        for (index, state) in snapshot.stubs().iter().enumerate() {
+            let stub = &state.stub;
+            if stub
+                .responses
+                .iter()
+                .any(|r| matches!(r, StubResponse::Proxy { .. }))
+                || stub.predicates.iter().any(predicate_contains_inject)
+                || stub.space.as_ref().is_some_and(|space| *space != flow_id)
+            {
+                continue;
+            }
+            if let Some(required) = &stub.required_scenario_state {
+                let scenario = stub.scenario_name.as_deref().unwrap_or("");
+                if self.scenario_state(&flow_id, scenario)? != *required {
+                    continue;
+                }
+            }
+            if stub_matches_inner(
+                &stub.predicates,
+                method,
+                path,
+                query,
+                headers,
+                body,
+                None,
+                None,
+                form.as_ref(),
+                self.script_state_key(),
+                body_json.as_ref(),
+                xml_dom.as_ref(),
+                Some(&query_map),
+                None,
+            )? {
+                return Ok(Some((Arc::clone(state), index)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Shadow comparisons so far, with the most recent drifted requests.
+    pub fn shadow_report(&self) -> ShadowReport {
+        let log = self.shadow.lock();
+        ShadowReport {
+            compared: log.compared,
+            drifted: log.drifted,
+            diffs: log.diffs.iter().cloned().collect(),
+        }
+    }
+
+    /// Forget the shadow comparisons and their counts.
+    pub fn clear_shadow_report(&self) {
+        *self.shadow.lock() = ShadowLog::default();
+    }
+}
+
+/// Compare each header the stub sets with the live response's value for it.
+fn diff_headers(
+    expected: &HashMap<String, Vec<String>>,
+    live: &[(String, String)],
+    out: &mut Vec<ShadowDifference>,
+) {
+    let mut names: Vec<&String> = expected.keys().collect();
+    names.sort();
+    for name in names {
+        if name.eq_ignore_ascii_case("content-length") || crate::util::is_hop_by_hop_header(name) {
+            continue;
+        }
+        let expected = expected[name].join(", ");
+        let actual: Vec<&str> = live
+            .iter()
+            .filter(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+            .collect();
+        let field = format!("headers.{name}");
+        if actual.is_empty() {
+            out.push(ShadowDifference {
+                field,
+                kind: ShadowDifferenceKind::Missing,
+                expected: Some(expected.into()),
+                actual: None,
+            });
+        } else if actual.join(", ") != expected {
+            out.push(ShadowDifference {
+                field,
+                kind: ShadowDifferenceKind::Value,
+                expected: Some(expected.into()),
+                actual: Some(actual.join(", ").into()),
+            });
+        }
+    }
+}
+
+/// Compare the stub's body with the live one: structurally when both are JSON, verbatim when both
+/// are text. A binary live body is not compared.
+fn diff_body(expected: &Value, live: &[u8], out: &mut Vec<ShadowDifference>) {
+    let expected = match expected {
+        Value::String(text) => serde_json::from_str::<Value>(text)
+            .ok()
+            .filter(|v| v.is_object() || v.is_array())
+            .map_or(Body::Text(text), Body::Json),
+        other => Body::Json(other.clone()),
+    };
+    let actual = match serde_json::from_slice::<Value>(live) {
+        Ok(json) if json.is_object() || json.is_array() => Body::Json(json),
+        _ => match std::str::from_utf8(live) {
+            Ok(text) => Body::Text(text),
+            Err(_) => return,
+        },
+    };
+    match (expected, actual) {
+        (Body::Json(expected), Body::Json(actual)) => diff_json("body", &expected, &actual, out),
+        (Body::Text(expected), Body::Text(actual)) => {
+            if expected != actual {
+                out.push(ShadowDifference {
+                    field: "body".to_string(),
+                    kind: ShadowDifferenceKind::Value,
+                    expected: Some(truncate_with_ellipsis(expected, MAX_TEXT_DIFF_LEN).into()),
+                    actual: Some(truncate_with_ellipsis(actual, MAX_TEXT_DIFF_LEN).into()),
+                });
+            }
+        }
+        (expected, actual) => out.push(ShadowDifference {
+            field: "body".to_string(),
+            kind: ShadowDifferenceKind::Type,
+            expected: Some(expected.kind().into()),
+            actual: Some(actual.kind().into()),
+        }),
+    }
+}
+
+enum Body<'a> {
+    Json(Value),
+    Text(&'a str),
+}
+
+impl Body<'_> {
+    fn kind(&self) -> &'static str {
+        match self {
+            Body::Json(_) => "json",
+            Body::Text(_) => "text",
+        }
+    }
+}
+
+/// Structural JSON diff (see the module docs).
+fn diff_json(field: &str, expected: &Value, actual: &Value, out: &mut Vec<ShadowDifference>) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, value) in expected {
+                let field = format!("{field}.{key}");
+                match actual.get(key) {
+                    Some(actual) => diff_json(&field, value, actual, out),
+                    None => out.push(ShadowDifference {
+                        field,
+                        kind: ShadowDifferenceKind::Missing,
+                        expected: Some(value.clone()),
+                        actual: None,
+                    }),
+                }
+            }
+            for (key, value) in actual {
+                if !expected.contains_key(key) {
+                    out.push(ShadowDifference {
+                        field: format!("{field}.{key}"),
+                        kind: ShadowDifferenceKind::Unexpected,
+                        expected: None,
+                        actual: Some(value.clone()),
+                    });
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            if let (Some(expected), Some(actual)) = (expected.first(), actual.first()) {
+                diff_json(&format!("{field}[0]"), expected, actual, out);
+            }
+        }
+        _ if json_type(expected) != json_type(actual) => out.push(ShadowDifference {
+            field: field.to_string(),
+            kind: ShadowDifferenceKind::Type,
+            expected: Some(json_type(expected).into()),
+            actual: Some(json_type(actual).into()),
+        }),
+        _ => {}
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::imposter::types::ImposterConfig;
+    use serde_json::json;
+
+    fn imposter(stubs: Value) -> Imposter {
+        let config: ImposterConfig = serde_json::from_value(json!({
+            "port": 0,
+            "protocol": "http",
+            "stubs": stubs,
+        }))
+        .expect("config");
+        Imposter::new(config).expect("imposter")
+    }
+
+    fn shadow(imposter: &Imposter, path: &str, live: LiveResponse<'_>) -> Option<ShadowDiff> {
+        imposter.compare_shadow("GET", path, None, &HashMap::new(), None, live)
+    }
+
+    #[test]
+    fn json_bodies_are_compared_by_shape_not_value() {
+        let imposter = imposter(json!([
+            { "responses": [{ "proxy": { "to": "http://live", "mode": "proxyShadow" } }] },
+            { "predicates": [{ "equals": { "path": "/users/1" } }],
+              "responses": [{ "is": {
+                  "headers": { "Content-Type": "application/json" },
+                  "body": { "id": 1, "name": "Ada", "tags": [{ "label": "x" }] } } }] },
+        ]));
+
+        let same_shape = shadow(
+            &imposter,
+            "/users/1",
+            LiveResponse {
+                status: 200,
+                headers: &[("content-type".to_string(), "application/json".to_string())],
+                body: br#"{"id": 7, "name": "Grace", "tags": []}"#,
+            },
+        );
+        assert!(same_shape.is_none());
+
+        let drifted = shadow(
+            &imposter,
+            "/users/1",
+            LiveResponse {
+                status: 201,
+                headers: &[],
+                body: br#"{"id": "7", "email": "g@x", "tags": [{ "label": 1 }]}"#,
+            },
+        )
+        .expect("drift is recorded");
+        let found: Vec<(&str, ShadowDifferenceKind)> = drifted
+            .differences
+            .iter()
+            .map(|d| (d.field.as_str(), d.kind))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("statusCode", ShadowDifferenceKind::Value),
+                ("headers.Content-Type", ShadowDifferenceKind::Missing),
+                ("body.id", ShadowDifferenceKind::Type),
+                ("body.name", ShadowDifferenceKind::Missing),
+                ("body.tags[0].label", ShadowDifferenceKind::Type),
+                ("body.email", ShadowDifferenceKind::Unexpected),
+            ]
+        );
+        assert_eq!(drifted.stub_index, Some(1));
+        assert_eq!(drifted.header_value(), "id=1; stub=1; differences=6");
+
+        let report = imposter.shadow_report();
+        assert_eq!((report.compared, report.drifted), (2, 1));
+        imposter.clear_shadow_report();
+        assert_eq!(imposter.shadow_report().compared, 0);
+    }
+
+    #[test]
+    fn a_request_no_stub_covers_is_drift() {
+        let imposter = imposter(json!([
+            { "responses": [{ "proxy": { "to": "http://live", "mode": "proxyShadow" } }] },
+            { "predicates": [{ "equals": { "path": "/known" } }],
+              "responses": [{ "is": { "body": "ok" } }] },
+        ]));
+        let live = LiveResponse {
+            status: 200,
+            headers: &[],
+            body: b"ok",
+        };
+        let drifted = shadow(&imposter, "/unknown", live).expect("uncovered request");
+        assert_eq!(drifted.stub_index, None);
+        assert_eq!(drifted.header_value(), "id=1; stub=none; differences=0");
+    }
+}
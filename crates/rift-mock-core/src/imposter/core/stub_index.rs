@@ -1215,7 +1215,7 @@ impl StubIndex {
 }
 
 /// Does this predicate tree contain an `inject` predicate anywhere?
-fn predicate_contains_inject(pred: &Predicate) -> bool {
+pub(super) fn predicate_contains_inject(pred: &Predicate) -> bool {
     match &pred.operation {
         PredicateOperation::Inject(_) => true,
         PredicateOperation::Not(inner) => predicate_contains_inject(inner),
@@ -0,0 +1,212 @@
+//! Running totals of an imposter's traffic for the admin `GET /metrics`.
+//!
+//! The served-requests log keeps only a tail of recent requests; these totals cover every request
+//! since the imposter started, so a scraper (or the TUI) can take differences over any window:
+//! a latency histogram, responses by status, injected faults by kind and hits per stub.
+//!
+//! Stub hits are counted by stub identity — the slot a stub keeps for its lifetime, plus its id —
+//! and reported by each stub's current index, so a count follows its stub through adds, deletes
+//! and moves instead of staying at a position another stub later takes.
+
+use super::{Imposter, ServedRequest, StubState};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+/// Upper bounds, in milliseconds, of the latency histogram's buckets; one more bucket counts
+/// everything slower.
+pub const LATENCY_BUCKETS_MS: [u64; 12] = [1, 2, 5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000];
+
+/// Totals of the requests an imposter served.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrafficStats {
+    /// Requests per latency bucket, not cumulative: entry `i` counts latencies above bucket
+    /// `i - 1`'s bound and up to [`LATENCY_BUCKETS_MS`]`[i]`; the last entry counts the rest.
+    pub latency_buckets: [u64; LATENCY_BUCKETS_MS.len() + 1],
+    pub latency_sum_ms: u64,
+    pub count: u64,
+    pub status_codes: BTreeMap<u16, u64>,
+    /// Injected faults by the `x-rift-fault` kind they reported.
+    pub faults: BTreeMap<String, u64>,
+    /// Hits per stub, by the stub's index when the totals were read, with its id. A deleted
+    /// stub's hits are dropped, and a stub whose id changes counts from zero again.
+    pub stub_hits: BTreeMap<usize, (Option<String>, u64)>,
+    /// Requests no stub matched.
+    pub unmatched: u64,
+    /// Hits by stub slot and id, from which `stub_hits` is filled in.
+    by_stub: BTreeMap<(u64, Option<String>), u64>,
+}
+
+impl TrafficStats {
+    pub(super) fn record(&mut self, request: &ServedRequest) {
+        let bucket = LATENCY_BUCKETS_MS.partition_point(|&bound| bound < request.latency_ms);
+        self.latency_buckets[bucket] += 1;
+        self.latency_sum_ms += request.latency_ms;
+        self.count += 1;
+        *self.status_codes.entry(request.status_code).or_default() += 1;
+        if let Some(fault) = &request.fault {
+            *self.faults.entry(fault.clone()).or_default() += 1;
+        }
+        match request.stub_slot {
+            Some(slot) => {
+                *self
+                    .by_stub
+                    .entry((slot, request.stub_id.clone()))
+                    .or_default() += 1;
+            }
+            None => self.unmatched += 1,
+        }
+    }
+
+    /// Drop the hits of stubs no longer among `stubs`.
+    pub(super) fn retain_stubs(&mut self, stubs: &[Arc<StubState>]) {
+        if self.by_stub.is_empty() {
+            return;
+        }
+        let live: HashSet<(u64, Option<&str>)> = stubs
+            .iter()
+            .map(|state| (state.slot, state.stub.id.as_deref()))
+            .collect();
+        self.by_stub
+            .retain(|(slot, id), _| live.contains(&(*slot, id.as_deref())));
+    }
+}
+
+impl Imposter {
+    /// Totals of every request served since the imposter started; clearing the served-requests
+    /// log does not reset them.
+    pub fn traffic_stats(&self) -> TrafficStats {
+        let snapshot = self.snapshot();
+        let mut stats = self.served.lock().stats.clone();
+        stats.stub_hits = snapshot
+            .stubs()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, state)| {
+                let id = state.stub.id.clone();
+                let hits = *stats.by_stub.get(&(state.slot, id.clone()))?;
+                Some((index, (id, hits)))
+            })
+            .collect();
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::imposter::types::ImposterConfig;
+
+    fn imposter(stub_ids: &[&str]) -> Imposter {
+        let stubs: Vec<_> = stub_ids
+            .iter()
+            .map(|id| serde_json::json!({ "id": id, "responses": [{ "is": {} }] }))
+            .collect();
+        let config: ImposterConfig =
+            serde_json::from_value(serde_json::json!({ "protocol": "http", "stubs": stubs }))
+                .unwrap();
+        Imposter::new(config).unwrap()
+    }
+
+    fn served(latency_ms: u64, status_code: u16, stub: Option<&StubState>) -> ServedRequest {
+        ServedRequest {
+            seq: 0,
+            method: "GET".to_string(),
+            path: "/".to_string(),
+            query: None,
+            status_code,
+            stub_index: None,
+            stub_id: stub.and_then(|state| state.stub.id.clone()),
+            stub_slot: stub.map(|state| state.slot),
+            latency_ms,
+            fault: None,
+            timestamp: String::new(),
+        }
+    }
+
+    /// Record a request answered by the stub now at `index`.
+    fn hit(imposter: &Imposter, index: usize) {
+        let stub = Arc::clone(&imposter.snapshot().stubs()[index]);
+        imposter.record_served(served(0, 200, Some(&stub)), Default::default());
+    }
+
+    #[test]
+    fn totals_survive_clearing_the_log() {
+        let imposter = imposter(&["stub-0", "stub-1", "stub-2"]);
+        let stubs = imposter.snapshot().stubs().to_vec();
+        let exchange = || Default::default();
+        imposter.record_served(served(0, 200, Some(&stubs[0])), exchange());
+        imposter.record_served(served(1, 200, Some(&stubs[0])), exchange());
+        imposter.record_served(served(7, 404, None), exchange());
+        let mut faulted = served(9000, 500, Some(&stubs[2]));
+        faulted.fault = Some("error".to_string());
+        imposter.record_served(faulted, exchange());
+        imposter.clear_served_requests();
+
+        let stats = imposter.traffic_stats();
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.latency_sum_ms, 9008);
+        assert_eq!(stats.latency_buckets[0], 2);
+        assert_eq!(stats.latency_buckets[3], 1);
+        assert_eq!(stats.latency_buckets[LATENCY_BUCKETS_MS.len()], 1);
+        assert_eq!(stats.status_codes[&200], 2);
+        assert_eq!(stats.faults["error"], 1);
+        assert_eq!(stats.stub_hits[&0], (Some("stub-0".to_string()), 2));
+        assert_eq!(stats.stub_hits[&2].1, 1);
+        assert!(!stats.stub_hits.contains_key(&1));
+        assert_eq!(stats.unmatched, 1);
+    }
+
+    #[test]
+    fn stub_hits_follow_their_stub_when_the_list_changes() {
+        let imposter = imposter(&["a", "b", "c"]);
+        hit(&imposter, 0);
+        hit(&imposter, 1);
+        hit(&imposter, 1);
+        hit(&imposter, 2);
+        hit(&imposter, 2);
+        hit(&imposter, 2);
+
+        // Deleting `a` moves `b` and `c` up; their counts go with them.
+        imposter.delete_stub(0).unwrap();
+        let hits = imposter.traffic_stats().stub_hits;
+        assert_eq!(
+            hits,
+            BTreeMap::from([
+                (0, (Some("b".to_string()), 2)),
+                (1, (Some("c".to_string()), 3)),
+            ])
+        );
+
+        // A stub added where `a` was starts from zero rather than inheriting its count.
+        imposter.add_stub(
+            serde_json::from_value(serde_json::json!({ "id": "a", "responses": [] })).unwrap(),
+            Some(0),
+        );
+        imposter.move_stub(2, 1).unwrap();
+        let hits = imposter.traffic_stats().stub_hits;
+        assert_eq!(
+            hits,
+            BTreeMap::from([
+                (1, (Some("c".to_string()), 3)),
+                (2, (Some("b".to_string()), 2)),
+            ])
+        );
+        // Only the live stubs' hits are kept.
+        assert_eq!(imposter.served.lock().stats.by_stub.len(), 2);
+    }
+
+    #[test]
+    fn a_stub_whose_id_changes_counts_again() {
+        let imposter = imposter(&["old"]);
+        hit(&imposter, 0);
+        let mut replacement = imposter.get_stub(0).unwrap();
+        replacement.id = Some("new".to_string());
+        imposter.replace_stub(0, replacement).unwrap();
+        assert!(imposter.traffic_stats().stub_hits.is_empty());
+        hit(&imposter, 0);
+        assert_eq!(
+            imposter.traffic_stats().stub_hits[&0],
+            (Some("new".to_string()), 1)
+        );
+    }
+}
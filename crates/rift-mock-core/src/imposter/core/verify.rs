@@ -143,6 +143,7 @@ impl Imposter {
             body_json.as_ref(),
             xml_dom.as_ref(),
             Some(&query_map),
+            req.tls.as_ref(),
         )
     }
 
@@ -198,7 +199,7 @@ impl Imposter {
 /// stores only the combined `ip:port`, so parse it back to a `SocketAddr` and take the IP. `None`
 /// (compared against `""`, never matching) only when `request_from` isn't a parseable socket
 /// address — the same "no client info" outcome the convenience matcher path produces.
-fn client_ip_of(req: &RecordedRequest) -> Option<String> {
+pub(super) fn client_ip_of(req: &RecordedRequest) -> Option<String> {
     req.request_from
         .parse::<std::net::SocketAddr>()
         .ok()
@@ -208,7 +209,7 @@ fn client_ip_of(req: &RecordedRequest) -> Option<String> {
 /// Collapse the recorded multi-value header map to the single-value view the matcher expects,
 /// taking the last value per header to mirror how live matching's single-value map is built (a
 /// `HashMap` collect over the request headers keeps the last of duplicate-named headers).
-fn collapse_headers(headers: &HashMap<String, Vec<String>>) -> HashMap<String, String> {
+pub(super) fn collapse_headers(headers: &HashMap<String, Vec<String>>) -> HashMap<String, String> {
     headers
         .iter()
         .filter_map(|(k, v)| v.last().map(|last| (k.clone(), last.clone())))
@@ -219,7 +220,7 @@ fn collapse_headers(headers: &HashMap<String, Vec<String>>) -> HashMap<String, S
 /// the raw material for a readable diff. For a field-based op (`equals`/`contains`/…) only the
 /// referenced fields are projected; for a compound (`and`/`or`/`not`), an `inject`, or a
 /// selector-based predicate no single field is implicated, so the whole request is returned.
-fn actual_projection(req: &RecordedRequest, predicate: &Predicate) -> Value {
+pub(super) fn actual_projection(req: &RecordedRequest, predicate: &Predicate) -> Value {
     if predicate.parameters.selector.is_some() {
         return request_view(req);
     }
@@ -257,6 +258,9 @@ fn request_view(req: &RecordedRequest) -> Value {
     if let Some(body) = &req.body {
         m.insert("body".to_string(), json!(body));
     }
+    if let Some(tls) = &req.tls {
+        m.insert("tls".to_string(), json!(tls));
+    }
     Value::Object(m)
 }
 
@@ -272,6 +276,7 @@ fn request_field(req: &RecordedRequest, key: &str) -> Option<Value> {
         // The `ip` predicate matches against the bare IP (`client_ip_of`), so report that as the
         // actual — not the `ip:port` `request_from` the matcher never compares against.
         "ip" => Some(json!(client_ip_of(req).unwrap_or_default())),
+        "tls" => Some(json!(req.tls)),
         "form" => {
             let headers = collapse_headers(&req.headers);
             Some(json!(
@@ -319,6 +324,7 @@ mod tests {
             headers: hs,
             body: body.map(str::to_string),
             timestamp: "2026-01-01T00:00:00Z".to_string(),
+            tls: None,
         }
     }
 
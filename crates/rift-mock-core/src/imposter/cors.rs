@@ -0,0 +1,275 @@
+//! CORS for imposters: `allowCORS` and the `cors` block.
+//!
+//! `allowCORS: true` alone keeps the Mountebank-compatible wildcard: every `OPTIONS` is answered
+//! as a preflight and every response gets `*` for origin, methods and headers. A `cors` block
+//! replaces that with a policy: only real preflights (an `OPTIONS` carrying
+//! `Access-Control-Request-Method`) are answered by the imposter, the request's `Origin` is
+//! checked against `allowOrigins`, and a disallowed origin gets no CORS headers at all, so the
+//! browser refuses the call just as it would against the real service.
+
+use super::types::{CorsConfig, ImposterConfig};
+use hyper::HeaderMap;
+use hyper::header::{self, HeaderName, HeaderValue};
+
+/// The CORS-relevant parts of a request, taken before the request is consumed.
+pub(super) struct CorsRequest {
+    preflight: bool,
+    origin: Option<HeaderValue>,
+    request_method: Option<HeaderValue>,
+    request_headers: Option<HeaderValue>,
+}
+
+impl CorsRequest {
+    /// `None` when the imposter has CORS off, so the common case keeps nothing.
+    pub(super) fn capture(
+        config: &ImposterConfig,
+        method: &hyper::Method,
+        headers: &HeaderMap,
+    ) -> Option<Self> {
+        if !config.allow_cors && config.cors.is_none() {
+            return None;
+        }
+        Some(Self {
+            preflight: is_preflight(config, method, headers),
+            origin: headers.get(header::ORIGIN).cloned(),
+            request_method: headers.get(header::ACCESS_CONTROL_REQUEST_METHOD).cloned(),
+            request_headers: headers.get(header::ACCESS_CONTROL_REQUEST_HEADERS).cloned(),
+        })
+    }
+}
+
+/// Whether the imposter answers this request itself as a CORS preflight, before stub matching.
+pub(super) fn is_preflight(
+    config: &ImposterConfig,
+    method: &hyper::Method,
+    headers: &HeaderMap,
+) -> bool {
+    if method != hyper::Method::OPTIONS {
+        return false;
+    }
+    match config.cors {
+        Some(_) => headers.contains_key(header::ACCESS_CONTROL_REQUEST_METHOD),
+        None => config.allow_cors,
+    }
+}
+
+/// Add the CORS headers `config` calls for to a response. A header the stub set itself is kept.
+pub(super) fn apply(config: &ImposterConfig, request: &CorsRequest, headers: &mut HeaderMap) {
+    let Some(cors) = &config.cors else {
+        for name in [
+            header::ACCESS_CONTROL_ALLOW_ORIGIN,
+            header::ACCESS_CONTROL_ALLOW_HEADERS,
+            header::ACCESS_CONTROL_ALLOW_METHODS,
+        ] {
+            set_default(headers, name, HeaderValue::from_static("*"));
+        }
+        return;
+    };
+
+    let any_origin = cors.allow_origins.is_empty() || cors.allow_origins.iter().any(|o| o == "*");
+    if !any_origin || cors.allow_credentials {
+        headers.append(header::VARY, HeaderValue::from_static("origin"));
+    }
+    let Some(origin) = allowed_origin(cors, any_origin, request.origin.as_ref()) else {
+        return;
+    };
+    set_default(headers, header::ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+    if cors.allow_credentials {
+        set_default(
+            headers,
+            header::ACCESS_CONTROL_ALLOW_CREDENTIALS,
+            HeaderValue::from_static("true"),
+        );
+    }
+
+    if !request.preflight {
+        if let Some(value) = joined(&cors.expose_headers) {
+            set_default(headers, header::ACCESS_CONTROL_EXPOSE_HEADERS, value);
+        }
+        return;
+    }
+    if let Some(value) = joined(&cors.allow_methods).or_else(|| request.request_method.clone()) {
+        set_default(headers, header::ACCESS_CONTROL_ALLOW_METHODS, value);
+    }
+    if let Some(value) = joined(&cors.allow_headers).or_else(|| request.request_headers.clone()) {
+        set_default(headers, header::ACCESS_CONTROL_ALLOW_HEADERS, value);
+    }
+    if let Some(max_age) = cors.max_age {
+        set_default(headers, header::ACCESS_CONTROL_MAX_AGE, max_age.into());
+    }
+}
+
+/// The `Access-Control-Allow-Origin` value for this request, or `None` when it may not call.
+/// A specific allow-list, or credentials, echo the request's own origin, as browsers require.
+fn allowed_origin(
+    cors: &CorsConfig,
+    any_origin: bool,
+    origin: Option<&HeaderValue>,
+) -> Option<HeaderValue> {
+    if any_origin && !cors.allow_credentials {
+        return Some(HeaderValue::from_static("*"));
+    }
+    let origin = origin?;
+    let allowed = any_origin
+        || origin
+            .to_str()
+            .is_ok_and(|origin| cors.allow_origins.iter().any(|o| o == origin));
+    allowed.then(|| origin.clone())
+}
+
+/// A list as one comma-separated header value; `None` for an empty list or an unsendable value.
+fn joined(values: &[String]) -> Option<HeaderValue> {
+    if values.is_empty() {
+        return None;
+    }
+    HeaderValue::from_str(&values.join(", ")).ok()
+}
+
+fn set_default(headers: &mut HeaderMap, name: HeaderName, value: HeaderValue) {
+    headers.entry(name).or_insert(value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(cors: serde_json::Value) -> ImposterConfig {
+        serde_json::from_value(serde_json::json!({ "protocol": "http", "cors": cors })).unwrap()
+    }
+
+    fn request(
+        config: &ImposterConfig,
+        method: hyper::Method,
+        pairs: &[(&str, &str)],
+    ) -> CorsRequest {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(
+                HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                HeaderValue::from_str(value).unwrap(),
+            );
+        }
+        CorsRequest::capture(config, &method, &headers).expect("cors enabled")
+    }
+
+    fn header(headers: &HeaderMap, name: HeaderName) -> Option<&str> {
+        headers.get(name).map(|v| v.to_str().unwrap())
+    }
+
+    #[test]
+    fn allowed_origin_preflight_gets_the_configured_policy() {
+        let config = config(serde_json::json!({
+            "allowOrigins": ["https://app.example"],
+            "allowMethods": ["GET", "POST"],
+            "allowHeaders": ["content-type"],
+            "allowCredentials": true,
+            "maxAge": 600
+        }));
+        let preflight = request(
+            &config,
+            hyper::Method::OPTIONS,
+            &[
+                ("origin", "https://app.example"),
+                ("access-control-request-method", "POST"),
+            ],
+        );
+        let mut headers = HeaderMap::new();
+        apply(&config, &preflight, &mut headers);
+        assert_eq!(
+            header(&headers, header::ACCESS_CONTROL_ALLOW_ORIGIN),
+            Some("https://app.example")
+        );
+        assert_eq!(
+            header(&headers, header::ACCESS_CONTROL_ALLOW_METHODS),
+            Some("GET, POST")
+        );
+        assert_eq!(
+            header(&headers, header::ACCESS_CONTROL_ALLOW_HEADERS),
+            Some("content-type")
+        );
+        assert_eq!(
+            header(&headers, header::ACCESS_CONTROL_ALLOW_CREDENTIALS),
+            Some("true")
+        );
+        assert_eq!(
+            header(&headers, header::ACCESS_CONTROL_MAX_AGE),
+            Some("600")
+        );
+        assert_eq!(header(&headers, header::VARY), Some("origin"));
+    }
+
+    #[test]
+    fn disallowed_origin_gets_no_cors_headers() {
+        let config = config(serde_json::json!({ "allowOrigins": ["https://app.example"] }));
+        let get = request(
+            &config,
+            hyper::Method::GET,
+            &[("origin", "https://evil.example")],
+        );
+        let mut headers = HeaderMap::new();
+        apply(&config, &get, &mut headers);
+        assert_eq!(header(&headers, header::ACCESS_CONTROL_ALLOW_ORIGIN), None);
+    }
+
+    #[test]
+    fn open_policy_echoes_what_the_preflight_asks_for() {
+        let config = config(serde_json::json!({ "exposeHeaders": ["x-request-id"] }));
+        let preflight = request(
+            &config,
+            hyper::Method::OPTIONS,
+            &[
+                ("origin", "https://app.example"),
+                ("access-control-request-method", "PUT"),
+                ("access-control-request-headers", "x-token"),
+            ],
+        );
+        let mut headers = HeaderMap::new();
+        apply(&config, &preflight, &mut headers);
+        assert_eq!(
+            header(&headers, header::ACCESS_CONTROL_ALLOW_ORIGIN),
+            Some("*")
+        );
+        assert_eq!(
+            header(&headers, header::ACCESS_CONTROL_ALLOW_METHODS),
+            Some("PUT")
+        );
+        assert_eq!(
+            header(&headers, header::ACCESS_CONTROL_ALLOW_HEADERS),
+            Some("x-token")
+        );
+        assert_eq!(
+            header(&headers, header::ACCESS_CONTROL_EXPOSE_HEADERS),
+            None
+        );
+
+        let get = request(&config, hyper::Method::GET, &[]);
+        let mut headers = HeaderMap::new();
+        apply(&config, &get, &mut headers);
+        assert_eq!(
+            header(&headers, header::ACCESS_CONTROL_EXPOSE_HEADERS),
+            Some("x-request-id")
+        );
+    }
+
+    #[test]
+    fn only_real_preflights_short_circuit_under_a_policy() {
+        let policy = config(serde_json::json!({}));
+        let mut headers = HeaderMap::new();
+        assert!(!is_preflight(&policy, &hyper::Method::OPTIONS, &headers));
+        headers.insert(
+            header::ACCESS_CONTROL_REQUEST_METHOD,
+            HeaderValue::from_static("GET"),
+        );
+        assert!(is_preflight(&policy, &hyper::Method::OPTIONS, &headers));
+
+        let wildcard = ImposterConfig {
+            allow_cors: true,
+            ..Default::default()
+        };
+        assert!(is_preflight(
+            &wildcard,
+            &hyper::Method::OPTIONS,
+            &HeaderMap::new()
+        ));
+    }
+}
@@ -1,7 +1,8 @@
-//! Admin event bus (issue #461): a broadcast of recorded-request and imposter-lifecycle events that
-//! powers the admin SSE stream (`GET /events`). Owned by the [`ImposterManager`](super::ImposterManager);
-//! publishing is a cheap no-op whenever nobody is subscribed (the SSE endpoint is the only
-//! subscriber), so it adds nothing to the request hot path unless a client is actively streaming.
+//! Admin event bus (issue #461): a broadcast of recorded-request, imposter-lifecycle and traffic
+//! events that powers the admin SSE stream (`GET /events`) and the server's outgoing webhooks.
+//! Owned by the [`ImposterManager`](super::ImposterManager); publishing is a cheap no-op whenever
+//! nobody is subscribed, so it adds nothing to the request hot path unless a client is actively
+//! streaming or a webhook is configured.
 //!
 //! Backpressure is lossy-but-loud: the channel is bounded, and a subscriber that falls behind
 //! observes `RecvError::Lagged(n)` (surfaced to the client as a `lagged` SSE event) rather than the
@@ -26,6 +27,8 @@ pub enum ImposterAction {
     EnabledChanged,
     Deleted,
     AllDeleted,
+    /// A proxy recorded a response as a new stub or appended it to a recorded one.
+    StubRecorded,
 }
 
 impl ImposterAction {
@@ -39,6 +42,7 @@ impl ImposterAction {
             Self::EnabledChanged => "enabledChanged",
             Self::Deleted => "deleted",
             Self::AllDeleted => "allDeleted",
+            Self::StubRecorded => "stubRecorded",
         }
     }
 }
@@ -52,7 +56,27 @@ pub struct AdminEvent {
     pub kind: AdminEventKind,
 }
 
-/// The two event families the stream carries.
+/// A request outcome worth alerting on, published only while someone subscribes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrafficOutcome {
+    /// No stub matched (whatever the imposter then served: a default, a forward or an empty 200).
+    NoMatch,
+    /// A fault was injected; carries the `x-rift-fault` value (`error`, `CONNECTION_RESET_BY_PEER`…).
+    Fault(String),
+}
+
+impl TrafficOutcome {
+    /// The outcome's name on the wire.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NoMatch => "noMatch",
+            Self::Fault(_) => "fault",
+        }
+    }
+}
+
+/// The event families the bus carries.
 #[derive(Debug, Clone)]
 pub enum AdminEventKind {
     /// An imposter was created/replaced/deleted (`port` is absent only for `AllDeleted`).
@@ -76,6 +100,13 @@ pub enum AdminEventKind {
         index: Option<u64>,
         request: Box<RecordedRequest>,
     },
+    /// A request on `port` had a notable [`TrafficOutcome`], whether or not it was recorded.
+    Traffic {
+        port: u16,
+        method: String,
+        path: String,
+        outcome: TrafficOutcome,
+    },
 }
 
 /// Broadcast of admin events for the SSE stream.
@@ -149,6 +180,17 @@ impl AdminEventBus {
         });
     }
 
+    /// Publish a traffic event. Like [`publish_request`](Self::publish_request), the caller checks
+    /// [`has_subscribers`](Self::has_subscribers) first so the hot path allocates nothing.
+    pub fn publish_traffic(&self, port: u16, method: &str, path: &str, outcome: TrafficOutcome) {
+        self.publish(AdminEventKind::Traffic {
+            port,
+            method: method.to_string(),
+            path: path.to_string(),
+            outcome,
+        });
+    }
+
     fn publish(&self, kind: AdminEventKind) {
         // No SSE client: don't burn a sequence number or a broadcast slot.
         if self.tx.receiver_count() == 0 {
@@ -178,6 +220,7 @@ mod tests {
             headers: Default::default(),
             body: None,
             timestamp: "2026-01-01T00:00:00Z".to_string(),
+            tls: None,
         }
     }
 
@@ -0,0 +1,275 @@
+//! `_rift.fetch` composite responses: sub-requests issued before an `is` response is rendered.
+//!
+//! Each named entry calls another imposter (over loopback, by port) or any absolute URL; the
+//! results are handed to the `{{ }}` pass as `fetch.<name>.*` so the authored body can merge them
+//! (`{{ fetch.user.json '$.name' }}`). Sub-requests run concurrently on the shared proxy client,
+//! with the proxy's 10 MB body cap. A non-2xx answer is a result like any other — templates can
+//! branch on `fetch.<name>.status` — while a transport failure fails the whole response with a 502.
+//!
+//! Only the path and query of a `url` are templated: its scheme and host are used as written, so a
+//! request cannot point a sub-request at a host of its choosing.
+//!
+//! Every sub-request carries `x-rift-fetch-depth`, one more than the incoming request's, so an
+//! imposter that (directly or through others) fetches itself stops after [`MAX_FETCH_DEPTH`] hops
+//! instead of recursing until the client times out.
+
+use super::core::{MAX_PROXY_RESPONSE_BODY_SIZE, get_http_client};
+use super::types::RiftFetchConfig;
+use crate::extensions::template::{RequestData, process_template};
+use crate::extensions::template_fn::FetchedResponse;
+use anyhow::{Context, bail};
+use std::collections::{BTreeMap, HashMap};
+
+/// Header carrying the fetch nesting depth of a request.
+pub(super) const FETCH_DEPTH_HEADER: &str = "x-rift-fetch-depth";
+
+/// Deepest allowed chain of fetches-within-fetches.
+pub(super) const MAX_FETCH_DEPTH: u32 = 5;
+
+/// Issue every sub-request in `fetches` for the request described by `request`, returning the
+/// results by name. Fails on the first sub-request that cannot be sent or read.
+pub(super) async fn run_fetches(
+    fetches: &BTreeMap<String, RiftFetchConfig>,
+    request: &RequestData,
+) -> anyhow::Result<BTreeMap<String, FetchedResponse>> {
+    let depth = request
+        .headers
+        .get(FETCH_DEPTH_HEADER)
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .unwrap_or(0)
+        + 1;
+    if depth > MAX_FETCH_DEPTH {
+        bail!("fetch chain exceeds the maximum depth of {MAX_FETCH_DEPTH}");
+    }
+
+    let calls = fetches.iter().map(|(name, config)| async move {
+        let fetched = fetch_one(config, request, depth)
+            .await
+            .with_context(|| format!("fetch '{name}' failed"))?;
+        Ok::<_, anyhow::Error>((name.clone(), fetched))
+    });
+    Ok(futures::future::try_join_all(calls)
+        .await?
+        .into_iter()
+        .collect())
+}
+
+/// The absolute URL `config` targets, with `${request.*}` tokens expanded in its path and query.
+fn target_url(config: &RiftFetchConfig, request: &RequestData) -> anyhow::Result<String> {
+    match (&config.url, config.imposter) {
+        (Some(url), None) => {
+            let (origin, rest) = split_origin(url)?;
+            let rest = process_template(rest, request);
+            // Tokens right after the host must expand to a path: an `@evil.example` or
+            // `.evil.example` from the request would otherwise extend the authority.
+            if !rest.is_empty() && !rest.starts_with(['/', '?', '#']) {
+                bail!(
+                    "url '{url}' expanded to '{origin}{rest}': the tokens after its host must expand to a path"
+                );
+            }
+            Ok(format!("{origin}{rest}"))
+        }
+        (None, Some(port)) => {
+            let path = process_template(config.path.as_deref().unwrap_or("/"), request);
+            let separator = if path.starts_with('/') { "" } else { "/" };
+            Ok(format!("http://127.0.0.1:{port}{separator}{path}"))
+        }
+        (Some(_), Some(_)) => bail!("set either url or imposter, not both"),
+        (None, None) => bail!("one of url or imposter is required"),
+    }
+}
+
+/// Split a fetch `url` into its `scheme://host[:port]` and the rest, which alone may be templated.
+/// A url whose scheme or host holds a `${` token is rejected; tokens may follow the host directly
+/// (`https://api.example.com${request.path}`) as long as nothing but tokens comes before the path.
+fn split_origin(url: &str) -> anyhow::Result<(&str, &str)> {
+    let fixed_host = || {
+        anyhow::anyhow!(
+            "url '{url}' must name a fixed scheme and host; only its path and query may use ${{}} tokens"
+        )
+    };
+    let Some(scheme_end) = url.find("://") else {
+        bail!("url '{url}' must be absolute (scheme://host/path)");
+    };
+    if url[..scheme_end].contains("${") {
+        return Err(fixed_host());
+    }
+    let authority = &url[scheme_end + 3..];
+    let authority_len = authority.find(['/', '?', '#']).unwrap_or(authority.len());
+    let host_len = authority[..authority_len]
+        .find("${")
+        .unwrap_or(authority_len);
+    if host_len == 0 {
+        return Err(fixed_host());
+    }
+    let mut tail = &authority[host_len..authority_len];
+    while let Some(token) = tail.strip_prefix("${") {
+        tail = token.split_once('}').map_or("", |(_, after)| after);
+        if tail.is_empty() {
+            break;
+        }
+    }
+    if !tail.is_empty() {
+        return Err(fixed_host());
+    }
+    Ok(url.split_at(scheme_end + 3 + host_len))
+}
+
+async fn fetch_one(
+    config: &RiftFetchConfig,
+    request: &RequestData,
+    depth: u32,
+) -> anyhow::Result<FetchedResponse> {
+    let url = target_url(config, request)?;
+    let method = config.method.as_deref().unwrap_or("GET").to_uppercase();
+    let method = reqwest::Method::from_bytes(method.as_bytes())
+        .with_context(|| format!("invalid method '{method}'"))?;
+
+    let mut builder = get_http_client()
+        .request(method, &url)
+        .header(FETCH_DEPTH_HEADER, depth.to_string());
+    for (name, value) in &config.headers {
+        builder = builder.header(name, process_template(value, request));
+    }
+    if let Some(body) = &config.body {
+        builder = builder.body(process_template(body, request));
+    }
+
+    let response = builder
+        .send()
+        .await
+        .with_context(|| format!("Failed to send fetch request to {url}"))?;
+    let status = response.status().as_u16();
+    let mut headers = HashMap::new();
+    for (name, value) in response.headers() {
+        headers
+            .entry(name.as_str().to_string())
+            .or_insert_with(|| value.to_str().unwrap_or("").to_string());
+    }
+    if let Some(content_length) = response.content_length()
+        && content_length as usize > MAX_PROXY_RESPONSE_BODY_SIZE
+    {
+        bail!(
+            "Fetch response body from {url} exceeds maximum size ({content_length} > {MAX_PROXY_RESPONSE_BODY_SIZE} bytes)"
+        );
+    }
+    let body = response
+        .bytes()
+        .await
+        .with_context(|| format!("Failed to read response body from {url}"))?;
+    if body.len() > MAX_PROXY_RESPONSE_BODY_SIZE {
+        bail!(
+            "Fetch response body from {url} exceeds maximum size ({} > {MAX_PROXY_RESPONSE_BODY_SIZE} bytes)",
+            body.len()
+        );
+    }
+
+    Ok(FetchedResponse {
+        status,
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(headers: &[(&'static str, &str)]) -> RequestData {
+        let mut map = hyper::HeaderMap::new();
+        for (name, value) in headers {
+            map.insert(*name, value.parse().unwrap());
+        }
+        RequestData::new("GET", "/orders/7", Some("user=ada"), &map, None)
+    }
+
+    #[test]
+    fn target_url_expands_request_tokens() {
+        let req = request(&[]);
+        let by_port = RiftFetchConfig {
+            imposter: Some(4546),
+            path: Some("users/${request.query.user}".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            target_url(&by_port, &req).unwrap(),
+            "http://127.0.0.1:4546/users/ada"
+        );
+        let by_url = RiftFetchConfig {
+            url: Some("https://api.example.com${request.path}".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            target_url(&by_url, &req).unwrap(),
+            "https://api.example.com/orders/7"
+        );
+    }
+
+    #[test]
+    fn a_url_cannot_template_its_scheme_or_host() {
+        let req = request(&[]);
+        for url in [
+            "${request.query.user}",
+            "http://${request.query.user}/x",
+            "http://api-${request.query.user}.example.com/x",
+            "${request.query.user}://example.com/x",
+            "example.com/x",
+        ] {
+            let config = RiftFetchConfig {
+                url: Some(url.to_string()),
+                ..Default::default()
+            };
+            assert!(target_url(&config, &req).is_err(), "{url}");
+        }
+    }
+
+    #[test]
+    fn tokens_after_the_host_must_expand_to_a_path() {
+        let req = request(&[]);
+        let config = |url: &str| RiftFetchConfig {
+            url: Some(url.to_string()),
+            ..Default::default()
+        };
+        // `ada` straight after the host would make it `api.example.comada`.
+        let err = target_url(
+            &config("https://api.example.com${request.query.user}"),
+            &req,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("must expand to a path"), "{err}");
+        assert_eq!(
+            target_url(
+                &config("https://api.example.com/u?name=${request.query.user}@evil.example"),
+                &req
+            )
+            .unwrap(),
+            "https://api.example.com/u?name=ada@evil.example"
+        );
+    }
+
+    #[test]
+    fn target_requires_exactly_one_of_url_and_imposter() {
+        let req = request(&[]);
+        assert!(target_url(&RiftFetchConfig::default(), &req).is_err());
+        let both = RiftFetchConfig {
+            url: Some("http://localhost".to_string()),
+            imposter: Some(4546),
+            ..Default::default()
+        };
+        assert!(target_url(&both, &req).is_err());
+    }
+
+    #[tokio::test]
+    async fn nested_fetches_stop_at_the_depth_limit() {
+        let req = request(&[(FETCH_DEPTH_HEADER, "5")]);
+        let fetches = BTreeMap::from([(
+            "self".to_string(),
+            RiftFetchConfig {
+                imposter: Some(1),
+                ..Default::default()
+            },
+        )]);
+        let err = run_fetches(&fetches, &req).await.unwrap_err();
+        assert!(err.to_string().contains("maximum depth"), "{err}");
+    }
+}
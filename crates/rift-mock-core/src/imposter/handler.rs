@@ -4,26 +4,33 @@
 //! debug mode, proxy handling, inject execution, and response generation.
 
 use super::core::Imposter;
+use super::events::TrafficOutcome;
 use super::predicates::parse_query_string;
 use super::response::{
     apply_decorate_bounded, execute_stub_response_with_rift, get_rift_script_config,
+    has_content_type,
 };
+use super::stream::ChunkPlan;
+use super::tap::{TapEntry, TapProxy, TapRequest, TapStub};
 use super::types::{
-    DebugMatchResult, DebugRequest, DebugResponse, ProxyResponse, RecordedRequest, ResponseMode,
-    StubResponse,
+    DebugMatchResult, DebugRequest, DebugResponse, ProxyResponse, RecordedRequest, RecordedTls,
+    ResponseMode, StubResponse,
 };
 use crate::behaviors::{
-    CsvCache, RequestContext, apply_copy_behaviors, apply_lookup_behaviors, apply_shell_transform,
-    header_to_title_case,
+    BehaviorStep, CsvCache, RequestContext, apply_copy_behaviors, apply_lookup_behaviors,
+    apply_shell_transform, header_to_title_case,
 };
 use crate::extensions::decorate::{
     ResponseDecorator, ResponsePhase, backend_error_response, with_annotation_scope,
 };
 use crate::extensions::no_match::{NoMatchContext, NoMatchDirective};
-use crate::extensions::template::{RequestData, has_template_variables, process_template};
+use crate::extensions::template::{
+    RequestData, StubMetadata, has_template_variables, process_template,
+};
 use crate::scripting::{
-    FaultDecision, ScriptCtxExtras, ScriptRequest, ScriptStubContext, resolve_script_timeout_ms,
-    should_inject_bounded_with_ctx, should_inject_bounded_with_ctx_traced,
+    FaultDecision, ScriptCtxExtras, ScriptRequest, ScriptStubContext, resolve_script_limits,
+    resolve_script_timeout_ms, should_inject_bounded_with_ctx,
+    should_inject_bounded_with_ctx_traced,
 };
 #[cfg(feature = "javascript")]
 use crate::scripting::{MountebankRequest, execute_mountebank_inject_bounded};
@@ -328,31 +335,125 @@ pub async fn handle_imposter_request(
     imposter: Arc<Imposter>,
     client_addr: SocketAddr,
 ) -> Result<Response<Full<Bytes>>, Infallible> {
-    let allow_cors = imposter.config.allow_cors;
+    // CORS headers go on whatever the imposter answers, so keep what they depend on.
+    let cors = super::cors::CorsRequest::capture(&imposter.config, req.method(), req.headers())
+        .map(|request| (request, Arc::clone(&imposter)));
     // Capture the method before `req` is consumed so we can record the request metric (issue #269).
     let method = req.method().to_string();
-    let mut response = handle_request_inner(req, imposter, client_addr).await?;
+    let mut fuzzy_match = None;
+    // The served-requests log (and a fault report) needs the path after `req` is consumed.
+    let started = std::time::Instant::now();
+    let path = req.uri().path().to_string();
+    let query = req.uri().query().map(str::to_string);
+    let request_headers = req.headers().clone();
+    let port = imposter.config.port.unwrap_or(0);
+    let mut served_by = ServedBy::default();
+    let mut response = handle_request_inner(
+        req,
+        Arc::clone(&imposter),
+        client_addr,
+        &mut fuzzy_match,
+        &mut served_by,
+    )
+    .await?;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    if let Some(bus) = imposter
+        .event_bus
+        .as_ref()
+        .filter(|bus| bus.has_subscribers())
+        && let Some(fault) = response
+            .headers()
+            .get("x-rift-fault")
+            .and_then(|v| v.to_str().ok())
+    {
+        bus.publish_traffic(
+            port,
+            &method,
+            &path,
+            TrafficOutcome::Fault(fault.to_string()),
+        );
+    }
+    if let Some(value) = fuzzy_match
+        && let Ok(value) = hyper::header::HeaderValue::from_str(&value)
+    {
+        response.headers_mut().insert("x-rift-fuzzy-match", value);
+    }
     // Record `rift_requests_total` once per request the imposter serves (issue #269). The imposter
     // serve path recorded no Prometheus metrics before; the recording proxy engine
     // (`proxy/handler.rs`) is a disjoint path, so there is no double-count.
     crate::extensions::record_request(&method, response.status().as_u16());
-    if allow_cors {
-        inject_cors_headers(response.headers_mut());
+    if let Some((request, imposter)) = cors {
+        super::cors::apply(&imposter.config, &request, response.headers_mut());
     }
+    // Keep the response as sent for the served-requests log; a `Full` body is already one buffer.
+    let (parts, body) = response.into_parts();
+    let response_body = match body.collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(never) => match never {},
+    };
+    let (stub_index, stub_id, stub_slot) = match served_by.stub {
+        Some((index, id, slot)) => (Some(index), id, Some(slot)),
+        None => (None, None, None),
+    };
+    let fault = parts
+        .headers
+        .get("x-rift-fault")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    imposter.record_served(
+        super::ServedRequest {
+            seq: 0,
+            method,
+            path,
+            query,
+            status_code: parts.status.as_u16(),
+            stub_index,
+            stub_id,
+            stub_slot,
+            latency_ms,
+            fault,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        },
+        (
+            super::ServedMessage::new(request_headers, served_by.body.unwrap_or_default()),
+            super::ServedMessage::new(parts.headers.clone(), response_body.clone()),
+        ),
+    );
+    let response = Response::from_parts(parts, Full::new(response_body));
     Ok(response)
 }
 
-fn inject_cors_headers(headers: &mut hyper::HeaderMap) {
-    use hyper::header::{HeaderName, HeaderValue};
-    for (name, value) in [
-        ("access-control-allow-origin", "*"),
-        ("access-control-allow-headers", "*"),
-        ("access-control-allow-methods", "*"),
-    ] {
-        let header_name = HeaderName::from_static(name);
-        if !headers.contains_key(&header_name) {
-            headers.insert(header_name, HeaderValue::from_static(value));
-        }
+/// Every value of every header, keyed by Title-Case name, for a recorded request (issue #238) —
+/// hyper's `HeaderMap` yields one entry per value, so a header sent twice is preserved (the
+/// single-value `headers_clone` view used for matching collapses it). The building loop uses
+/// `FastMap` (issue #704); `RecordedRequest.headers` is the fixed std-hasher journal/serde
+/// boundary, so the finished map is converted at the end.
+fn multi_value_headers(headers: &hyper::HeaderMap) -> HashMap<String, Vec<String>> {
+    let mut map: FastMap<String, Vec<String>> = FastMap::default();
+    for (k, v) in headers {
+        map.entry(header_to_title_case(k.as_str()))
+            .or_default()
+            .push(v.to_str().unwrap_or("").to_string());
+    }
+    map.into_iter().collect()
+}
+
+/// The request half of a `--proxy-tap` line (see `tap`).
+fn tap_request<'a>(
+    method: &'a str,
+    path: &'a str,
+    query: &'a str,
+    headers: &hyper::HeaderMap,
+    body: Option<&'a str>,
+    mode: &ResponseMode,
+) -> TapRequest<'a> {
+    TapRequest {
+        method,
+        path,
+        query,
+        headers: multi_value_headers(headers),
+        body,
+        mode: mode.clone(),
     }
 }
 
@@ -381,10 +482,24 @@ fn sanitize_header_value(value: &str) -> String {
     sanitized
 }
 
+/// What [`handle_request_inner`] learned about a request for the served-requests log.
+#[derive(Default)]
+struct ServedBy {
+    /// Index, id and slot of the stub that answered.
+    stub: Option<(usize, Option<String>, u64)>,
+    /// The request body, once it was read.
+    body: Option<Bytes>,
+}
+
+/// Serve one imposter request. A response served by closest match (`closestMatch`) leaves its
+/// `x-rift-fuzzy-match` header value in `fuzzy_match` for the caller to attach, and the stub that
+/// answered and the body that was read go in `served_by`.
 async fn handle_request_inner(
     req: Request<Incoming>,
     imposter: Arc<Imposter>,
     client_addr: SocketAddr,
+    fuzzy_match: &mut Option<String>,
+    served_by: &mut ServedBy,
 ) -> Result<Response<Full<Bytes>>, Infallible> {
     // Check if enabled
     if !imposter.is_enabled() {
@@ -402,6 +517,28 @@ async fn handle_request_inner(
         ));
     }
 
+    // Draining (`POST /imposters/:port/_drain`): refuse new work; what is in flight finishes.
+    // The in-flight guard is taken BEFORE the drain check, so a request that gets past the check
+    // is always counted: a drain that starts after it still sees it in `in_flight`, never a 0
+    // while it is being served. A refused request only holds the guard until it returns.
+    let _in_flight = imposter.begin_request();
+    if let Some(retry_after) = imposter.draining_retry_after() {
+        return Ok(build_response_with_headers(
+            StatusCode::SERVICE_UNAVAILABLE,
+            [
+                ("x-rift-imposter-draining", "true"),
+                ("retry-after", retry_after.to_string().as_str()),
+                ("connection", "close"),
+                ("content-type", "application/json"),
+            ],
+            crate::response::error_body_typed(
+                StatusCode::SERVICE_UNAVAILABLE,
+                crate::response::ErrorKind::ImposterDraining,
+                "Imposter is draining",
+            ),
+        ));
+    }
+
     // Increment request count
     imposter.increment_request_count();
 
@@ -409,6 +546,8 @@ async fn handle_request_inner(
     // back owned `method`/`uri`/`headers` for free — no `.clone()` needed to get an owned
     // `HeaderMap` for the request context, unlike the old `req.headers().clone()`.
     let (parts, body) = req.into_parts();
+    // What the connection's TLS handshake negotiated; set by the serve loop for HTTPS imposters.
+    let tls = parts.extensions.get::<Arc<RecordedTls>>().cloned();
     let method = parts.method.to_string();
     let uri = parts.uri;
     let headers_for_context = parts.headers;
@@ -423,26 +562,10 @@ async fn handle_request_inner(
             )
         })
         .collect();
-    // Capture ALL values per header for the recorded request (issue #238) — hyper's HeaderMap
-    // yields one entry per value, so a header sent twice is preserved here (headers_clone above
-    // collapses to one value and stays the single-value view used for matching/context). The
-    // building loop uses `FastMap` (issue #704); `RecordedRequest.headers` is the fixed std-hasher
-    // journal/serde boundary, so the finished map is converted at the end.
-    let headers_multi: HashMap<String, Vec<String>> = if imposter.config.record_requests {
-        let mut map: FastMap<String, Vec<String>> = FastMap::default();
-        for (k, v) in headers_for_context.iter() {
-            map.entry(header_to_title_case(k.as_str()))
-                .or_default()
-                .push(v.to_str().unwrap_or("").to_string());
-        }
-        map.into_iter().collect()
-    } else {
-        HashMap::new()
-    };
     let path = uri.path().to_string();
     let query_str = uri.query().unwrap_or("").to_string();
 
-    if method.eq_ignore_ascii_case("OPTIONS") && imposter.config.allow_cors {
+    if super::cors::is_preflight(&imposter.config, &parts.method, &headers_for_context) {
         return Ok(build_response_with_headers(
             StatusCode::OK,
             [("x-rift-imposter", "true")],
@@ -478,6 +601,7 @@ async fn handle_request_inner(
             return Ok(body_read_error_response(e.as_ref()));
         }
     };
+    served_by.body = body_bytes.clone();
     // Borrow the body as UTF-8 without forcing an allocation for the common valid-UTF-8 case
     // (issue #561): valid UTF-8 stays `Cow::Borrowed`, so only genuinely invalid UTF-8 pays a
     // copy here. `body_bytes` stays alive for the rest of the function so this borrow remains
@@ -513,10 +637,11 @@ async fn handle_request_inner(
             // `RecordedRequest.query` is the fixed std-hasher journal/serde boundary (out of scope
             // for #704); `parse_query_string` returns `FastMap`, so convert at this edge.
             query: parse_query_string(&query_str).into_iter().collect(),
-            headers: headers_multi,
+            headers: multi_value_headers(&headers_for_context),
             body: body_string.as_deref().map(str::to_string),
             mode: body_mode.clone(),
             timestamp: chrono::Utc::now().to_rfc3339(),
+            tls: tls.as_deref().cloned(),
         };
         imposter.record_request(recorded);
     }
@@ -540,10 +665,12 @@ async fn handle_request_inner(
 
     // Every script execution below (debug-mode matching, predicate inject during matching,
     // response inject, decorate) shares the imposter's `_rift.scriptEngine.timeoutMs`
-    // wall-clock budget (issue #476), like `_rift.script`.
+    // wall-clock budget (issue #476), like `_rift.script`. `_rift.script` and decorate also
+    // share its operation and memory limits.
     let script_timeout = std::time::Duration::from_millis(
         crate::scripting::resolve_script_timeout_ms(&imposter.config),
     );
+    let script_limits = resolve_script_limits(&imposter.config);
 
     if is_debug_mode {
         // Debug matching evaluates the full predicate set — inject predicates included — so it
@@ -555,6 +682,7 @@ async fn handle_request_inner(
         // Materialize an owned copy here (issue #561): `body_string` borrows from `body_bytes`,
         // which does not outlive this `spawn_blocking` closure's `'static` bound.
         let dbg_body = body_string.as_deref().map(str::to_string);
+        let dbg_tls = tls.clone();
         let handle = tokio::task::spawn_blocking(move || {
             handle_debug_request(
                 &debug_imposter,
@@ -564,6 +692,7 @@ async fn handle_request_inner(
                 &dbg_headers,
                 &dbg_body,
                 client_addr,
+                dbg_tls.as_deref(),
             )
         });
         return match tokio::time::timeout(script_timeout, handle).await {
@@ -594,6 +723,7 @@ async fn handle_request_inner(
             body_string.as_deref(),
             Some(&request_from),
             Some(&client_ip),
+            tls.as_deref(),
             script_timeout,
         )
         .await
@@ -630,6 +760,7 @@ async fn handle_request_inner(
                     body_string.as_deref(),
                     Some(&request_from),
                     Some(&client_ip),
+                    tls.as_deref(),
                     script_timeout,
                 )
                 .await
@@ -639,8 +770,51 @@ async fn handle_request_inner(
             };
         }
     }
+    if matched.is_none()
+        && let Some(bus) = imposter
+            .event_bus
+            .as_ref()
+            .filter(|bus| bus.has_subscribers())
+    {
+        bus.publish_traffic(
+            imposter.config.port.unwrap_or(0),
+            method_str,
+            path_str,
+            TrafficOutcome::NoMatch,
+        );
+    }
+
+    // Closest-match replay: a request no stub matched is served by the nearest stub instead, with
+    // the diff kept for the admin API (see `core::fuzzy`). Runs after the interceptor so a rescued
+    // exact match always wins.
+    if matched.is_none() && imposter.config.closest_match {
+        let request = RecordedRequest {
+            request_from: client_addr.to_string(),
+            method: method.clone(),
+            path: path.clone(),
+            query: parse_query_string(&query_str).into_iter().collect(),
+            headers: multi_value_headers(&headers_for_context),
+            body: body_string.as_deref().map(str::to_string),
+            mode: body_mode.clone(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            tls: tls.as_deref().cloned(),
+        };
+        match imposter.closest_stub(request) {
+            Ok(Some((stub_state, stub_index, fuzzy))) => {
+                debug!(
+                    "No exact match; serving closest stub {stub_index} ({})",
+                    fuzzy.header_value()
+                );
+                *fuzzy_match = Some(fuzzy.header_value());
+                matched = Some((stub_state, stub_index));
+            }
+            Ok(None) => {}
+            Err(e) => return Ok(matcher_error_response(&e)),
+        }
+    }
 
     if let Some((stub_state, stub_index)) = matched {
+        served_by.stub = Some((stub_index, stub_state.stub.id.clone(), stub_state.slot));
         // Scenario FSM: apply the matched stub's newScenarioState transition (no-op unless set).
         // Resolve flow_id from the same single-value header map the matcher used (headers_clone)
         // so the transition writes the exact key the gate read.
@@ -681,7 +855,8 @@ async fn handle_request_inner(
             // Per-request entry announcement — `trace!` so it compiles out of release (issue #706);
             // the outcome (status/latency) is captured by metrics and the response's x-rift-* headers.
             trace!("Handling proxy request to {}", proxy_config.to);
-            match imposter
+            let started = std::time::Instant::now();
+            let outcome = imposter
                 .handle_proxy_request(
                     proxy_config,
                     method_str,
@@ -689,8 +864,28 @@ async fn handle_request_inner(
                     &headers_clone,
                     body_string.as_deref(),
                 )
-                .await
-            {
+                .await;
+            if let Some(tap) = &imposter.tap {
+                tap.record(&TapEntry::new(
+                    imposter.config.port.unwrap_or(0),
+                    tap_request(
+                        method_str,
+                        path_str,
+                        &query_str,
+                        &headers_for_context,
+                        body_string.as_deref(),
+                        &body_mode,
+                    ),
+                    TapProxy::new(proxy_config, false),
+                    Some(TapStub {
+                        index: stub_index,
+                        id: stub_state.stub.id.as_deref(),
+                    }),
+                    started.elapsed(),
+                    &outcome,
+                ));
+            }
+            match outcome {
                 Ok((status, response_headers, body, latency)) => {
                     let mut response = Response::builder().status(status);
 
@@ -877,7 +1072,8 @@ async fn handle_request_inner(
 
             // Execute the script off the async worker under a wall-clock deadline (issue
             // #308): a runaway script is interrupted at `_rift.scriptEngine.timeoutMs`
-            // (default 5s) instead of wedging the whole engine.
+            // (default 5s) instead of wedging the whole engine, and a Rhai script stops at the
+            // imposter's operation and memory limits even when the deadline never fires.
             let timeout_ms = resolve_script_timeout_ms(&imposter.config);
             let flow_store = imposter.flow_store.clone();
 
@@ -925,6 +1121,7 @@ async fn handle_request_inner(
                         script_request,
                         flow_store,
                         Duration::from_millis(timeout_ms),
+                        script_limits,
                         ctx_extra,
                     )
                     .await;
@@ -946,6 +1143,7 @@ async fn handle_request_inner(
                         script_request,
                         flow_store,
                         Duration::from_millis(timeout_ms),
+                        script_limits,
                         ctx_extra,
                     )
                     .await;
@@ -1033,15 +1231,20 @@ async fn handle_request_inner(
                 Err(e) => {
                     log_upstream_failure("Rift script execution failed", &e);
                     // A deadline miss (issue #499) is a transient 504 + `x-rift-script-timeout`,
-                    // distinct from the permanent 500 a broken script produces.
+                    // distinct from the permanent 500 a broken script produces. A script stopped
+                    // at its operation or memory limit is a 500 (it will stop there again) that
+                    // still carries the timeout marker.
                     let timed_out = e
                         .downcast_ref::<crate::scripting::ScriptTimeoutError>()
                         .is_some();
+                    let limited = e
+                        .downcast_ref::<crate::scripting::ScriptLimitError>()
+                        .is_some();
                     let mut headers = vec![
                         ("x-rift-imposter".to_string(), "true".to_string()),
                         ("x-rift-script-error".to_string(), "true".to_string()),
                     ];
-                    if timed_out {
+                    if timed_out || limited {
                         headers.push((SCRIPT_TIMEOUT_HEADER.to_string(), "true".to_string()));
                     }
                     if let Some(trace) = trace_header {
@@ -1077,11 +1280,13 @@ async fn handle_request_inner(
         // header/body clone, per-request header re-parsing, and all template/behavior/date scanning
         // below. `prepared` is `Some` only when nothing about the response depends on the request
         // (see `PreparedResponse::try_build`); request recording and the scenario FSM already ran
-        // above, so nothing request-visible is skipped.
+        // above, so nothing request-visible is skipped. Imposter-level `defaultBehaviors` apply to
+        // every response, so their presence disables the fast path.
         if let Some(StubResponse::Is {
             prepared: Some(prepared),
             ..
         }) = response
+            && imposter.default_behaviors.is_none()
         {
             return Ok(prepared.serve());
         }
@@ -1096,6 +1301,45 @@ async fn handle_request_inner(
             is_fault,
         )) = response.and_then(execute_stub_response_with_rift)
         {
+            // `is.bodyFile`: the file's current content replaces the inline body. Loaded before
+            // templating/behaviors so everything downstream sees it exactly like an inline body;
+            // binary mode re-encodes the raw bytes to the base64 form the finalizer decodes.
+            if let Some(StubResponse::Is { is, .. }) = response
+                && let Some(file) = is.body_file.as_deref()
+            {
+                match imposter.body_files.load(file) {
+                    Ok(content) => {
+                        body = match response_mode {
+                            ResponseMode::Binary => {
+                                base64::engine::general_purpose::STANDARD.encode(&*content)
+                            }
+                            ResponseMode::Text => String::from_utf8_lossy(&content).into_owned(),
+                        };
+                        if super::body_file::is_json_file(file) && !has_content_type(&headers) {
+                            headers.insert(
+                                "Content-Type".to_string(),
+                                vec!["application/json".to_string()],
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Failed to load bodyFile: {e}");
+                        return Ok(build_response_with_headers(
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            [
+                                ("x-rift-imposter", "true"),
+                                ("content-type", "application/json"),
+                            ],
+                            crate::response::error_body_typed(
+                                StatusCode::INTERNAL_SERVER_ERROR,
+                                crate::response::ErrorKind::InternalError,
+                                &e.to_string(),
+                            ),
+                        ));
+                    }
+                }
+            }
+
             // Handle faults - simulate connection errors
             if is_fault {
                 return handle_fault_response(&body);
@@ -1104,7 +1348,8 @@ async fn handle_request_inner(
             // Apply _rift.fault extensions (probabilistic faults)
             if let Some(rift) = rift_ext
                 && let Some(ref fault_config) = rift.fault
-                && let Some(response) = apply_rift_fault(fault_config, &mut status, &mut body).await
+                && let Some(response) =
+                    apply_rift_fault(fault_config, &imposter.rng, &mut status, &mut body).await
             {
                 return Ok(response);
             }
@@ -1116,6 +1361,13 @@ async fn handle_request_inner(
             let strict_behaviors =
                 imposter.config.strict_behaviors || crate::util::strict_behaviors_env();
 
+            // Which stub served this request, for `${stub.*}` / `{{ stub.* }}` and decorate.
+            let stub_metadata = StubMetadata {
+                id: stub_state.stub.id.clone(),
+                index: stub_index,
+                imposter_port: imposter.script_state_key(),
+            };
+
             // Declarative response templating (issue #359): opt-in via `_rift.templated`. This
             // `{{ }}` render runs FIRST — on the *config-authored* body/headers — and BEFORE the
             // `${request.*}` reflection substitution below (issue #359 B1, security). Ordering is
@@ -1125,7 +1377,35 @@ async fn handle_request_inner(
             // template-injection hole (an unauthenticated caller could reach `state.*`/force errors)
             // and would also break the module's "a literal `{{` is served verbatim" promise for
             // reflected text. Off by default so recorded fixtures with a literal `{{` are untouched.
-            if rift_ext.is_some_and(|r| r.templated) {
+            //
+            // `_rift.fetch` sub-requests run just before it: their results feed `{{ fetch.* }}`,
+            // so a composite response is always templated.
+            let fetched = match rift_ext.filter(|r| !r.fetch.is_empty()) {
+                Some(rift) => {
+                    let request_data = RequestData::new(
+                        method_str,
+                        path_str,
+                        query_opt,
+                        &headers_for_context,
+                        body_string.as_deref(),
+                    )
+                    .with_route_pattern(stub_state.stub.route_pattern.as_deref())
+                    .with_stub(stub_metadata.clone());
+                    match super::fetch::run_fetches(&rift.fetch, &request_data).await {
+                        Ok(results) => Some(results),
+                        Err(e) => {
+                            return Ok(upstream_error_response(
+                                &e,
+                                "Fetch request failed",
+                                "x-rift-fetch-error",
+                                "Fetch error",
+                            ));
+                        }
+                    }
+                }
+                None => None,
+            };
+            if rift_ext.is_some_and(|r| r.templated) || fetched.is_some() {
                 let request_data = RequestData::new(
                     method_str,
                     path_str,
@@ -1133,7 +1413,8 @@ async fn handle_request_inner(
                     &headers_for_context,
                     body_string.as_deref(),
                 )
-                .with_route_pattern(stub_state.stub.route_pattern.as_deref());
+                .with_route_pattern(stub_state.stub.route_pattern.as_deref())
+                .with_stub(stub_metadata.clone());
                 // In debug mode (`RIFT_DEBUG`), a malformed/unknown/failed `{{ }}` token fails the
                 // request loudly instead of silently degrading to an empty string (issue #359 AC3).
                 let template_debug = crate::util::rift_debug_env();
@@ -1141,6 +1422,8 @@ async fn handle_request_inner(
                     request: &request_data,
                     flow_id: &scenario_flow_id,
                     flow_store: imposter.flow_store.as_ref(),
+                    rng: &imposter.rng,
+                    fetched: fetched.as_ref(),
                 };
 
                 let template_error = match crate::extensions::template_fn::render_templated(
@@ -1201,7 +1484,8 @@ async fn handle_request_inner(
                         &headers_for_context,
                         body_string.as_deref(),
                     )
-                    .with_route_pattern(stub_state.stub.route_pattern.as_deref());
+                    .with_route_pattern(stub_state.stub.route_pattern.as_deref())
+                    .with_stub(stub_metadata.clone());
                     if need_body {
                         body = process_template(&body, &request_data);
                     }
@@ -1218,15 +1502,15 @@ async fn handle_request_inner(
             // Apply behaviors if present. Issue #479: `behaviors` is now the precomputed
             // `Option<Arc<ResponseBehaviors>>` (parsed once at stub construction, see
             // `StubResponse::new_is`) — no more re-parsing `_behaviors` JSON on every request.
-            if let Some(ref parsed_behaviors) = behaviors {
-                // Apply wait behavior
-                if let Some(ref wait) = parsed_behaviors.wait {
-                    let wait_ms = wait.get_duration_ms();
-                    if wait_ms > 0 {
-                        tokio::time::sleep(Duration::from_millis(wait_ms)).await;
-                    }
+            // The imposter's `defaultBehaviors` fill in whatever the response leaves unset; the
+            // merged set is built once per response, not per request.
+            let behaviors = match (&imposter.default_behaviors, response) {
+                (Some(defaults), Some(response)) => {
+                    Some(response.behaviors_with_defaults(defaults))
                 }
-
+                _ => behaviors,
+            };
+            if let Some(ref parsed_behaviors) = behaviors {
                 // Lazy request context (issue #561): only copy/lookup/decorate/shellTransform read
                 // it, and `RequestContext::from_request` re-parses the query, retitles every
                 // header, and copies the body — so a wait/repeat-only stub (or any non-`is`
@@ -1246,176 +1530,201 @@ async fn handle_request_inner(
                     )
                 };
 
-                // copy/lookup are pure token substitution — apply them across each value of
-                // multi-value headers so multiplicity survives (e.g. multiple Set-Cookie;
-                // RFC 7230 §3.2.2 forbids folding Set-Cookie). decorate uses a single-value
-                // JS/Rhai object model, so only that path collapses — and even there Set-Cookie
-                // is held aside, never comma-folded.
-                if !parsed_behaviors.copy.is_empty() {
-                    body = apply_copy_behaviors(
-                        &body,
-                        &mut headers,
-                        &parsed_behaviors.copy,
-                        request_context.get_or_init(build_request_context),
-                    );
-                }
-                if !parsed_behaviors.lookup.is_empty() {
-                    body = apply_lookup_behaviors(
-                        &body,
-                        &mut headers,
-                        &parsed_behaviors.lookup,
-                        request_context.get_or_init(build_request_context),
-                        csv_cache(),
-                    );
-                }
-                if let Some(ref decorate_script) = parsed_behaviors.decorate {
-                    // decorate uses a single-value JS/Rhai object model. Set-Cookie is held
-                    // aside and never folded (RFC 7230 §3.2.2); other multi-value headers
-                    // degrade to single-value for the script (issue #238 boundary) — warn so
-                    // the collapse is not silent (e.g. WWW-Authenticate is also corrupted by
-                    // comma-folding).
-                    let is_set_cookie = |k: &str| k.eq_ignore_ascii_case("set-cookie");
-                    let folded: Vec<&String> = headers
-                        .iter()
-                        .filter(|(k, v)| v.len() > 1 && !is_set_cookie(k))
-                        .map(|(k, _)| k)
-                        .collect();
-                    if !folded.is_empty() {
-                        warn!(
-                            "decorate uses a single-value object model; multi-value headers \
-                                 {folded:?} are comma-folded (issue #238 boundary). Set-Cookie is \
-                                 exempt; other headers that forbid list-folding will be corrupted."
-                        );
-                    }
-
-                    let set_cookie: Vec<(String, Vec<String>)> = headers
-                        .iter()
-                        .filter(|(k, _)| is_set_cookie(k))
-                        .map(|(k, v)| (k.clone(), v.clone()))
-                        .collect();
-                    let single: HashMap<String, String> = headers
-                        .iter()
-                        .filter(|(k, _)| !is_set_cookie(k))
-                        .map(|(k, v)| (k.clone(), v.join(", ")))
-                        .collect();
-                    match apply_decorate_bounded(
-                        decorate_script.clone(),
-                        request_context.get_or_init(build_request_context).clone(),
-                        body.clone(),
-                        status,
-                        single,
-                        imposter.script_state_key(),
-                        stub_state.stub.id.clone(),
-                        script_timeout,
-                    )
-                    .await
-                    {
-                        Ok((new_body, new_status, single)) => {
-                            body = new_body;
-                            status = new_status;
-                            // Restore the held-aside Set-Cookie lines unless the script set its
-                            // own (case-insensitively) — a script override wins deterministically.
-                            let script_set_cookie = single.keys().any(|k| is_set_cookie(k));
-                            headers = single.into_iter().map(|(k, v)| (k, vec![v])).collect();
-                            if !script_set_cookie {
-                                headers.extend(set_cookie);
+                // Apply each behavior in order: the listed order for an array-form `_behaviors`,
+                // wait → copy → lookup → decorate → shellTransform for the object form. So e.g. a
+                // decorate listed before a copy sees the pre-copy body.
+                for step in parsed_behaviors.steps() {
+                    match step {
+                        BehaviorStep::Wait(wait) => {
+                            let wait_ms = wait.get_duration_ms_with(&imposter.rng);
+                            if wait_ms > 0 {
+                                tokio::time::sleep(Duration::from_millis(wait_ms)).await;
                             }
                         }
-                        // Behave as if decorate was absent: keep the original multi-value
-                        // `headers` and pre-decorate body/status rather than serving a folded,
-                        // undecorated response. Attach a visible signal so the skipped behavior
-                        // isn't a silent success (issue #323); the body is still served (#269).
-                        Err(e) => {
-                            warn!("Decorate script error: {e}");
-                            // A deadline miss (issue #499) carries `x-rift-script-timeout` and,
-                            // under strict mode, a 504 rather than the broken-script 500 — so a
-                            // retry-worthy timeout is distinguishable from a permanent failure.
-                            let timed_out =
-                                matches!(e, crate::behaviors::DecorateError::Timeout(_));
-                            if strict_behaviors {
-                                let status = if timed_out {
-                                    StatusCode::GATEWAY_TIMEOUT
-                                } else {
-                                    StatusCode::INTERNAL_SERVER_ERROR
-                                };
-                                let mut hdrs = vec![
-                                    ("x-rift-imposter", "true"),
-                                    ("x-rift-decorate-error", "true"),
-                                    ("content-type", "application/json"),
-                                ];
-                                if timed_out {
-                                    hdrs.push((SCRIPT_TIMEOUT_HEADER, "true"));
-                                }
-                                return Ok(build_response_with_headers(
-                                    status,
-                                    hdrs,
-                                    crate::response::error_body_typed(
-                                        status,
-                                        crate::response::ErrorKind::BehaviorError,
-                                        &format!("decorate failed (strictBehaviors): {e}"),
-                                    ),
-                                ));
-                            }
-                            headers.insert(
-                                "x-rift-decorate-error".to_string(),
-                                vec!["true".to_string()],
+                        // copy/lookup are pure token substitution — apply them across each value of
+                        // multi-value headers so multiplicity survives (e.g. multiple Set-Cookie;
+                        // RFC 7230 §3.2.2 forbids folding Set-Cookie). decorate uses a single-value
+                        // JS/Rhai object model, so only that path collapses — and even there
+                        // Set-Cookie is held aside, never comma-folded.
+                        BehaviorStep::Copy(copies) => {
+                            body = apply_copy_behaviors(
+                                &body,
+                                &mut headers,
+                                copies,
+                                request_context.get_or_init(build_request_context),
+                            );
+                        }
+                        BehaviorStep::Lookup(lookups) => {
+                            body = apply_lookup_behaviors(
+                                &body,
+                                &mut headers,
+                                lookups,
+                                request_context.get_or_init(build_request_context),
+                                csv_cache(),
                             );
-                            if timed_out {
-                                headers.insert(
-                                    SCRIPT_TIMEOUT_HEADER.to_string(),
-                                    vec!["true".to_string()],
+                        }
+                        BehaviorStep::Decorate(decorate_script) => {
+                            // decorate uses a single-value JS/Rhai object model. Set-Cookie is held
+                            // aside and never folded (RFC 7230 §3.2.2); other multi-value headers
+                            // degrade to single-value for the script (issue #238 boundary) — warn
+                            // so the collapse is not silent (e.g. WWW-Authenticate is also
+                            // corrupted by comma-folding).
+                            let is_set_cookie = |k: &str| k.eq_ignore_ascii_case("set-cookie");
+                            let folded: Vec<&String> = headers
+                                .iter()
+                                .filter(|(k, v)| v.len() > 1 && !is_set_cookie(k))
+                                .map(|(k, _)| k)
+                                .collect();
+                            if !folded.is_empty() {
+                                warn!(
+                                    "decorate uses a single-value object model; multi-value \
+                                     headers {folded:?} are comma-folded (issue #238 boundary). \
+                                     Set-Cookie is exempt; other headers that forbid list-folding \
+                                     will be corrupted."
                                 );
                             }
-                        }
-                    }
-                }
 
-                // shellTransform (issue #269): pipe the body through external command(s);
-                // stdout becomes the new body. Runs on the static `is` path too, not only the
-                // proxy path, and independently of copy/lookup/decorate.
-                for cmd in &parsed_behaviors.shell_transform {
-                    // Run the fork/exec/wait off the tokio worker (issue #478): a synchronous
-                    // subprocess run inline would stall the worker for its whole lifetime,
-                    // starving unrelated requests multiplexed on it.
-                    let shell_result = {
-                        let cmd = cmd.clone();
-                        let rc = request_context.get_or_init(build_request_context).clone();
-                        let body_in = body.clone();
-                        tokio::task::spawn_blocking(move || {
-                            apply_shell_transform(&cmd, &rc, &body_in, status)
-                        })
-                        .await
-                        .unwrap_or_else(|e| {
-                            Err(std::io::Error::other(format!(
-                                "shellTransform task panicked: {e}"
-                            )))
-                        })
-                    };
-                    match shell_result {
-                        Ok(transformed) => body = transformed,
-                        // Keep the body unchanged (issue #269) but signal the failure so it
-                        // isn't a silent success (issue #323).
-                        Err(e) => {
-                            warn!("shellTransform command {cmd:?} failed: {e}");
-                            if strict_behaviors {
-                                return Ok(build_response_with_headers(
-                                    StatusCode::INTERNAL_SERVER_ERROR,
-                                    [
-                                        ("x-rift-imposter", "true"),
-                                        ("x-rift-shelltransform-error", "true"),
-                                        ("content-type", "application/json"),
-                                    ],
-                                    crate::response::error_body_typed(
-                                        StatusCode::INTERNAL_SERVER_ERROR,
-                                        crate::response::ErrorKind::BehaviorError,
-                                        &format!("shellTransform failed (strictBehaviors): {e}"),
-                                    ),
-                                ));
+                            let set_cookie: Vec<(String, Vec<String>)> = headers
+                                .iter()
+                                .filter(|(k, _)| is_set_cookie(k))
+                                .map(|(k, v)| (k.clone(), v.clone()))
+                                .collect();
+                            let single: HashMap<String, String> = headers
+                                .iter()
+                                .filter(|(k, _)| !is_set_cookie(k))
+                                .map(|(k, v)| (k.clone(), v.join(", ")))
+                                .collect();
+                            match apply_decorate_bounded(
+                                decorate_script.to_string(),
+                                request_context.get_or_init(build_request_context).clone(),
+                                body.clone(),
+                                status,
+                                single,
+                                stub_metadata.clone(),
+                                script_timeout,
+                                script_limits,
+                            )
+                            .await
+                            {
+                                Ok((new_body, new_status, single)) => {
+                                    body = new_body;
+                                    status = new_status;
+                                    // Restore the held-aside Set-Cookie lines unless the script set
+                                    // its own (case-insensitively) — a script override wins
+                                    // deterministically.
+                                    let script_set_cookie = single.keys().any(|k| is_set_cookie(k));
+                                    headers =
+                                        single.into_iter().map(|(k, v)| (k, vec![v])).collect();
+                                    if !script_set_cookie {
+                                        headers.extend(set_cookie);
+                                    }
+                                }
+                                // Behave as if decorate was absent: keep the original multi-value
+                                // `headers` and pre-decorate body/status rather than serving a
+                                // folded, undecorated response. Attach a visible signal so the
+                                // skipped behavior isn't a silent success (issue #323); the body is
+                                // still served (#269).
+                                Err(e) => {
+                                    warn!("Decorate script error: {e}");
+                                    // A deadline miss (issue #499) carries `x-rift-script-timeout`
+                                    // and, under strict mode, a 504 rather than the broken-script
+                                    // 500 — so a retry-worthy timeout is distinguishable from a
+                                    // permanent failure. A script stopped by its operation or
+                                    // memory limit carries the header too, but stays a 500.
+                                    let timed_out =
+                                        matches!(e, crate::behaviors::DecorateError::Timeout(_));
+                                    let limited =
+                                        matches!(e, crate::behaviors::DecorateError::Limit(_));
+                                    if strict_behaviors {
+                                        let status = if timed_out {
+                                            StatusCode::GATEWAY_TIMEOUT
+                                        } else {
+                                            StatusCode::INTERNAL_SERVER_ERROR
+                                        };
+                                        let mut hdrs = vec![
+                                            ("x-rift-imposter", "true"),
+                                            ("x-rift-decorate-error", "true"),
+                                            ("content-type", "application/json"),
+                                        ];
+                                        if timed_out || limited {
+                                            hdrs.push((SCRIPT_TIMEOUT_HEADER, "true"));
+                                        }
+                                        return Ok(build_response_with_headers(
+                                            status,
+                                            hdrs,
+                                            crate::response::error_body_typed(
+                                                status,
+                                                crate::response::ErrorKind::BehaviorError,
+                                                &format!("decorate failed (strictBehaviors): {e}"),
+                                            ),
+                                        ));
+                                    }
+                                    headers.insert(
+                                        "x-rift-decorate-error".to_string(),
+                                        vec!["true".to_string()],
+                                    );
+                                    if timed_out || limited {
+                                        headers.insert(
+                                            SCRIPT_TIMEOUT_HEADER.to_string(),
+                                            vec!["true".to_string()],
+                                        );
+                                    }
+                                }
+                            }
+                        }
+                        // shellTransform (issue #269): pipe the body through external command(s);
+                        // stdout becomes the new body. Runs on the static `is` path too, not only
+                        // the proxy path, and independently of copy/lookup/decorate.
+                        BehaviorStep::ShellTransform(commands) => {
+                            for cmd in commands {
+                                // Run the fork/exec/wait off the tokio worker (issue #478): a
+                                // synchronous subprocess run inline would stall the worker for its
+                                // whole lifetime, starving unrelated requests multiplexed on it.
+                                let shell_result = {
+                                    let cmd = cmd.clone();
+                                    let rc =
+                                        request_context.get_or_init(build_request_context).clone();
+                                    let body_in = body.clone();
+                                    tokio::task::spawn_blocking(move || {
+                                        apply_shell_transform(&cmd, &rc, &body_in, status)
+                                    })
+                                    .await
+                                    .unwrap_or_else(|e| {
+                                        Err(std::io::Error::other(format!(
+                                            "shellTransform task panicked: {e}"
+                                        )))
+                                    })
+                                };
+                                match shell_result {
+                                    Ok(transformed) => body = transformed,
+                                    // Keep the body unchanged (issue #269) but signal the failure
+                                    // so it isn't a silent success (issue #323).
+                                    Err(e) => {
+                                        warn!("shellTransform command {cmd:?} failed: {e}");
+                                        if strict_behaviors {
+                                            return Ok(build_response_with_headers(
+                                                StatusCode::INTERNAL_SERVER_ERROR,
+                                                [
+                                                    ("x-rift-imposter", "true"),
+                                                    ("x-rift-shelltransform-error", "true"),
+                                                    ("content-type", "application/json"),
+                                                ],
+                                                crate::response::error_body_typed(
+                                                    StatusCode::INTERNAL_SERVER_ERROR,
+                                                    crate::response::ErrorKind::BehaviorError,
+                                                    &format!(
+                                                        "shellTransform failed (strictBehaviors): {e}"
+                                                    ),
+                                                ),
+                                            ));
+                                        }
+                                        headers.insert(
+                                            "x-rift-shelltransform-error".to_string(),
+                                            vec!["true".to_string()],
+                                        );
+                                    }
+                                }
                             }
-                            headers.insert(
-                                "x-rift-shelltransform-error".to_string(),
-                                vec!["true".to_string()],
-                            );
                         }
                     }
                 }
@@ -1468,10 +1777,15 @@ async fn handle_request_inner(
                 // Expand serve-time date templates ({{DAYS+N}}/{{MONTHS+N}}/{{NOW}}, issue #195).
                 ResponseMode::Text => Bytes::from(crate::extensions::apply_date_templates(&body)),
             };
-            // Signal a failed binary decode so serving the raw (still-encoded) body isn't silent (#323).
+            // Signal a failed binary decode so serving the raw (still-encoded) body isn't silent
+            // (#323).
             if binary_decode_failed {
                 response = response.header("x-rift-binary-error", "true");
             }
+            // `_rift.stream`: the serve loop writes the body chunk by chunk (see `stream`).
+            if let Some(stream) = rift_ext.and_then(|r| r.stream.as_ref()) {
+                response = response.extension(ChunkPlan::new(stream));
+            }
 
             return Ok(response.body(Full::new(body_bytes)).unwrap_or_else(|e| {
                 build_failure_response(&e, "stub response build failed (bad stub header?)")
@@ -1489,7 +1803,8 @@ async fn handle_request_inner(
             to: upstream.clone(),
             ..Default::default()
         };
-        return match imposter
+        let started = std::time::Instant::now();
+        let outcome = imposter
             .handle_proxy_request(
                 &proxy_config,
                 method_str,
@@ -1497,8 +1812,25 @@ async fn handle_request_inner(
                 &headers_clone,
                 body_string.as_deref(),
             )
-            .await
-        {
+            .await;
+        if let Some(tap) = &imposter.tap {
+            tap.record(&TapEntry::new(
+                imposter.config.port.unwrap_or(0),
+                tap_request(
+                    method_str,
+                    path_str,
+                    &query_str,
+                    &headers_for_context,
+                    body_string.as_deref(),
+                    &body_mode,
+                ),
+                TapProxy::new(&proxy_config, true),
+                None,
+                started.elapsed(),
+                &outcome,
+            ));
+        }
+        return match outcome {
             Ok((status, response_headers, body, _latency)) => {
                 let mut response = Response::builder().status(status);
                 for (k, v) in &response_headers {
@@ -1595,6 +1927,7 @@ fn handle_debug_request(
     headers_clone: &FastMap<String, String>,
     body_string: &Option<String>,
     client_addr: SocketAddr,
+    tls: Option<&RecordedTls>,
 ) -> Result<Response<Full<Bytes>>, Infallible> {
     debug!("Debug mode enabled for request {} {}", method, path);
 
@@ -1627,7 +1960,7 @@ fn handle_debug_request(
         Some(query_str)
     };
 
-    let matched = match imposter.find_matching_stub_with_client(
+    let matched = match imposter.find_matching_stub_with_tls(
         method,
         path,
         headers_clone,
@@ -1635,6 +1968,7 @@ fn handle_debug_request(
         body_string.as_deref(),
         Some(&request_from),
         Some(&client_ip),
+        tls,
     ) {
         Ok(matched) => matched,
         Err(e) => return Ok(matcher_error_response(&e)),
@@ -1735,16 +2069,19 @@ fn handle_fault_response(fault_type: &str) -> Result<Response<Full<Bytes>>, Infa
     ))
 }
 
-/// Apply Rift fault configuration (probabilistic faults)
+/// Apply Rift fault configuration (probabilistic faults). Every roll comes from the imposter's
+/// `rng`, so a `randomSeed` makes which faults fire (and the latency drawn) reproducible.
 async fn apply_rift_fault(
     fault_config: &super::types::RiftFaultConfig,
+    rng: &crate::extensions::random::ImposterRng,
     _status: &mut u16,
     _body: &mut String,
 ) -> Option<Response<Full<Bytes>>> {
-    // Generate all random values before any await points (ThreadRng is not Send)
-    let (apply_latency, latency_delay_ms) = {
-        let mut rng = rand::thread_rng();
-        if let Some(ref latency) = fault_config.latency {
+    // Generate all random values before any await points (ThreadRng is not Send, and a seeded
+    // RNG is held under its lock). The roll order — latency, error, tcp — is fixed so a seeded
+    // run replays identically.
+    let (apply_latency, latency_delay_ms, apply_error, apply_tcp) = rng.with(|rng| {
+        let (apply_latency, latency_delay_ms) = if let Some(ref latency) = fault_config.latency {
             if rng.r#gen::<f64>() < latency.probability {
                 let delay_ms = if let Some(fixed_ms) = latency.ms {
                     fixed_ms
@@ -1759,29 +2096,25 @@ async fn apply_rift_fault(
             }
         } else {
             (false, 0)
-        }
-    };
+        };
 
-    let apply_error = {
-        let mut rng = rand::thread_rng();
-        if let Some(ref error) = fault_config.error {
+        let apply_error = if let Some(ref error) = fault_config.error {
             rng.r#gen::<f64>() < error.probability
         } else {
             false
-        }
-    };
+        };
 
-    // A `tcp` fault fires with its own probability (issue #531): the bare string form is always
-    // 1.0; the object form carries a chosen probability. When the roll fails the fault is treated
-    // as absent for this request, falling through to the `error` fault and normal response — the
-    // same semantics `latency`/`error` already use.
-    let apply_tcp = {
-        let mut rng = rand::thread_rng();
-        fault_config
+        // A `tcp` fault fires with its own probability (issue #531): the bare string form is
+        // always 1.0; the object form carries a chosen probability. When the roll fails the fault
+        // is treated as absent for this request, falling through to the `error` fault and normal
+        // response — the same semantics `latency`/`error` already use.
+        let apply_tcp = fault_config
             .tcp
             .as_ref()
-            .is_some_and(|tcp| rng.r#gen::<f64>() < tcp.probability())
-    };
+            .is_some_and(|tcp| rng.r#gen::<f64>() < tcp.probability());
+
+        (apply_latency, latency_delay_ms, apply_error, apply_tcp)
+    });
 
     // Apply latency fault (this is async)
     if apply_latency && latency_delay_ms > 0 {
@@ -2234,6 +2567,7 @@ mod fault_precedence_tests {
     use super::super::fault_io::TcpFaultKind;
     use super::super::types::{RiftErrorFault, RiftFaultConfig, RiftLatencyFault, RiftTcpFault};
     use super::{Bytes, Full, Response, apply_rift_fault, handle_fault_response};
+    use crate::extensions::random::ImposterRng;
     use std::time::Instant;
 
     // Issue #309: a top-level `fault` response must reset/close the connection (via the same
@@ -2294,7 +2628,7 @@ mod fault_precedence_tests {
     async fn apply(config: &RiftFaultConfig) -> Response<Full<Bytes>> {
         let mut status = 200;
         let mut body = String::new();
-        apply_rift_fault(config, &mut status, &mut body)
+        apply_rift_fault(config, &ImposterRng::unseeded(), &mut status, &mut body)
             .await
             .expect("a fault response")
     }
@@ -2341,6 +2675,35 @@ mod fault_precedence_tests {
         assert!(response.extensions().get::<TcpFaultKind>().is_none());
     }
 
+    /// With a seed, which requests a probabilistic `error` fault hits is the same on every run.
+    #[tokio::test]
+    async fn seeded_rng_replays_fault_rolls() {
+        let config = RiftFaultConfig {
+            latency: None,
+            error: Some(RiftErrorFault {
+                probability: 0.5,
+                ..error_fault(503)
+            }),
+            tcp: None,
+        };
+        async fn rolls(config: &RiftFaultConfig, seed: u64) -> Vec<bool> {
+            let rng = ImposterRng::new(Some(seed));
+            let mut hits = Vec::new();
+            for _ in 0..32 {
+                let (mut status, mut body) = (200, String::new());
+                hits.push(
+                    apply_rift_fault(config, &rng, &mut status, &mut body)
+                        .await
+                        .is_some(),
+                );
+            }
+            hits
+        }
+        let first = rolls(&config, 99).await;
+        assert_eq!(first, rolls(&config, 99).await);
+        assert!(first.contains(&true) && first.contains(&false));
+    }
+
     /// An unparseable `tcp` string must not swallow a configured `error` fault: it warns and falls
     /// through to the HTTP error response (guards the path most adjacent to the issue #271 drop).
     #[tokio::test]
@@ -2408,7 +2771,8 @@ mod fault_precedence_tests {
         };
         let (mut status, mut body) = (200, String::new());
         for _ in 0..200 {
-            let response = apply_rift_fault(&config, &mut status, &mut body).await;
+            let response =
+                apply_rift_fault(&config, &ImposterRng::unseeded(), &mut status, &mut body).await;
             assert!(
                 response.is_none(),
                 "probability 0.0 tcp fault must never fire"
@@ -2470,7 +2834,7 @@ mod fault_precedence_tests {
         let mut resets = 0;
         let (mut status, mut body) = (200, String::new());
         for _ in 0..iterations {
-            if apply_rift_fault(&config, &mut status, &mut body)
+            if apply_rift_fault(&config, &ImposterRng::unseeded(), &mut status, &mut body)
                 .await
                 .and_then(|r| r.extensions().get::<TcpFaultKind>().copied())
                 .is_some()
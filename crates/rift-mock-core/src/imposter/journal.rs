@@ -303,6 +303,7 @@ mod tests {
             headers: Default::default(),
             body: None,
             timestamp: "t".into(),
+            tls: None,
         }
     }
 
@@ -3,11 +3,12 @@
 //! This module handles creating, deleting, and managing multiple imposters,
 //! each running on its own port.
 
-use super::core::Imposter;
+use super::core::{Imposter, StubOperation, StubOperationResult};
 use super::fault_io::{FaultCell, FaultIo, TcpFaultKind};
 use super::handler::handle_imposter_request_decorated;
 use super::reconcile::{ApplyReport, ImposterEvent, ImposterEventListener, StubReconcile};
-use super::types::{ImposterConfig, ImposterError, Stub};
+use super::template::ImposterTemplate;
+use super::types::{ImposterConfig, ImposterError, RecordedTls, Stub};
 use crate::behaviors::ResponseSequencer;
 use crate::extensions::decorate::ResponseDecorator;
 use crate::extensions::flow_state::FlowStoreProvider;
@@ -21,6 +22,7 @@ use arc_swap::ArcSwapOption;
 use hyper::service::service_fn;
 use hyper_util::rt::TokioIo;
 use parking_lot::Mutex;
+use std::collections::BTreeMap;
 use std::net::ToSocketAddrs;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -54,7 +56,8 @@ impl Default for TlsDefaults {
 /// Serve one imposter connection (over plaintext or an already-handshaked TLS stream) until it
 /// completes or the imposter is torn down. Auto-negotiates HTTP/1 and HTTP/2 (issue #295), except
 /// for imposters that can fire a connection-level TCP fault, which are served HTTP/1-only. Shared
-/// by the plain and HTTPS serve paths (issue #206). (Name kept for history.)
+/// by the plain and HTTPS serve paths (issue #206). (Name kept for history.) `tls` is what an
+/// HTTPS connection negotiated; every request on the connection carries it as an extension.
 #[allow(clippy::too_many_arguments)]
 async fn run_http1<I>(
     io: I,
@@ -65,6 +68,7 @@ async fn run_http1<I>(
     port: u16,
     decorator: Option<Arc<dyn ResponseDecorator>>,
     http_tuning: crate::proxy::network::HttpTuning,
+    tls: Option<Arc<RecordedTls>>,
 ) where
     I: hyper::rt::Read + hyper::rt::Write + Unpin + Send + 'static,
 {
@@ -73,7 +77,10 @@ async fn run_http1<I>(
     // HTTP/1 fault bytes are nonsense to an h2 client). So an imposter that can fire a TCP fault
     // is served HTTP/1-only; everything else auto-negotiates HTTP/1 and HTTP/2 (issue #295).
     let http1_only = imposter.uses_tcp_faults() || crate::util::http2_disabled();
-    let service = service_fn(move |req| {
+    let service = service_fn(move |mut req: hyper::Request<hyper::body::Incoming>| {
+        if let Some(tls) = &tls {
+            req.extensions_mut().insert(Arc::clone(tls));
+        }
         let imposter = Arc::clone(&imposter);
         let fault_cell = Arc::clone(&fault_cell);
         let decorator = decorator.clone();
@@ -83,7 +90,7 @@ async fn run_http1<I>(
             if let Some(kind) = response.extensions().get::<TcpFaultKind>().copied() {
                 *fault_cell.lock() = Some(kind);
             }
-            Ok::<_, std::convert::Infallible>(response)
+            Ok::<_, std::convert::Infallible>(super::stream::into_served(response))
         }
     });
 
@@ -234,6 +241,11 @@ pub struct ImposterManager {
     shutdown_tx: broadcast::Sender<()>,
     /// Optional data directory for persistence write-through
     datadir: Option<Arc<PathBuf>>,
+    /// Recorded proxy bodies larger than this many bytes are spilled to files under the datadir
+    /// (see `persist::BodySpill`). Only takes effect with a datadir.
+    recording_spill_threshold: Option<usize>,
+    /// NDJSON log of every proxied exchange, shared by all imposters (see `tap`).
+    proxy_tap: Option<Arc<super::tap::ProxyTap>>,
     /// TLS defaults for HTTPS imposters (issue #206)
     tls_defaults: TlsDefaults,
     /// Observer for config mutations (issue #316)
@@ -263,6 +275,8 @@ pub struct ImposterManager {
     /// Created unconditionally (a `broadcast::Sender` with no receivers is ~free); publishing is a
     /// no-op until a client subscribes.
     event_bus: Arc<super::events::AdminEventBus>,
+    /// Registered imposter templates by name. Runtime-only, like drain state: not persisted.
+    templates: parking_lot::RwLock<BTreeMap<String, Arc<ImposterTemplate>>>,
 }
 
 /// Default bound for the post-delete connection drain (issue #596). Generous: normal graceful
@@ -283,6 +297,8 @@ impl ImposterManager {
             imposters: PortTable::new(),
             shutdown_tx,
             datadir: datadir.map(Arc::new),
+            recording_spill_threshold: None,
+            proxy_tap: None,
             tls_defaults: TlsDefaults::default(),
             event_listener: None,
             response_decorator: None,
@@ -294,6 +310,7 @@ impl ImposterManager {
             accept_runtimes: None,
             conn_drain: DEFAULT_CONN_DRAIN,
             event_bus: Arc::new(super::events::AdminEventBus::new()),
+            templates: parking_lot::RwLock::default(),
         }
     }
 
@@ -320,6 +337,23 @@ impl ImposterManager {
         self
     }
 
+    /// Spill recorded proxy bodies larger than `bytes` to files under the datadir instead of
+    /// keeping them in memory. `None` (the default) keeps every body in memory; without a
+    /// datadir there is nowhere to spill to and the threshold is ignored.
+    #[must_use]
+    pub fn with_recording_spill_threshold(mut self, bytes: Option<usize>) -> Self {
+        self.recording_spill_threshold = bytes;
+        self
+    }
+
+    /// Write every proxied request/response pair of every imposter to `tap` as an NDJSON line,
+    /// whether or not it is recorded. `None` (the default) writes nothing.
+    #[must_use]
+    pub fn with_proxy_tap(mut self, tap: Option<super::tap::ProxyTap>) -> Self {
+        self.proxy_tap = tap.map(Arc::new);
+        self
+    }
+
     /// Set the server-level TLS defaults for HTTPS imposters (issue #206).
     #[must_use]
     pub fn with_tls_defaults(mut self, tls_defaults: TlsDefaults) -> Self {
@@ -448,8 +482,12 @@ impl ImposterManager {
         config: &ImposterConfig,
     ) -> Result<tokio_rustls::TlsAcceptor, ImposterError> {
         let from_pem = |cert: &str, key: &str| {
-            crate::proxy::tls::tls_acceptor_from_pem(cert.as_bytes(), key.as_bytes())
-                .map_err(|e| ImposterError::Tls(e.to_string()))
+            crate::proxy::tls::tls_acceptor_from_pem(
+                cert.as_bytes(),
+                key.as_bytes(),
+                config.mutual_auth,
+            )
+            .map_err(|e| ImposterError::Tls(e.to_string()))
         };
         match (&config.cert, &config.key) {
             (Some(cert), Some(key)) => return from_pem(cert, key),
@@ -475,7 +513,7 @@ impl ImposterManager {
             }
         }
         if self.tls_defaults.allow_self_signed {
-            return crate::proxy::tls::generate_self_signed_acceptor()
+            return crate::proxy::tls::generate_self_signed_acceptor(config.mutual_auth)
                 .map_err(|e| ImposterError::Tls(e.to_string()));
         }
         Err(ImposterError::Tls(
@@ -503,6 +541,7 @@ impl ImposterManager {
             "http" | "https" => {}
             proto => return Err(ImposterError::InvalidProtocol(proto.to_string())),
         }
+        Imposter::validate_default_behaviors(&config).map_err(ImposterError::InvalidBehaviors)?;
 
         // For HTTPS, resolve the per-imposter TLS acceptor up front so a missing/invalid cert
         // fails loudly at creation rather than silently serving cleartext (issue #206).
@@ -564,6 +603,21 @@ impl ImposterManager {
         // Share the admin event bus so recorded requests fan out to the SSE stream (issue #461).
         imposter.event_bus = Some(Arc::clone(&self.event_bus));
 
+        // `is.bodyFile` paths resolve under the datadir.
+        imposter.body_files = super::body_file::BodyFileCache::new(self.datadir.clone());
+
+        // Under a datadir, recorded proxy responses survive restarts: re-import the saved set and
+        // arm the flusher (spawned below, once the imposter is `Arc`-wrapped).
+        if let Some(datadir) = &self.datadir {
+            super::persist::restore_recordings(datadir, port, &imposter);
+            imposter.recordings_changed = Some(Arc::new(tokio::sync::Notify::new()));
+            imposter.body_spill = self
+                .recording_spill_threshold
+                .map(|threshold| super::persist::BodySpill::new(Arc::clone(datadir), threshold));
+        }
+
+        imposter.tap = self.proxy_tap.clone();
+
         // Inject the shared no-match interceptor, if one is registered (issue #819).
         if let Some(interceptor) = &self.no_match_interceptor {
             imposter.no_match_interceptor = Some(Arc::clone(interceptor));
@@ -646,6 +700,14 @@ impl ImposterManager {
                 None => tokio::spawn(loop_future),
             });
         }
+        // The recording flusher rides along: `delete` awaiting it guarantees no flush lands after
+        // the datadir files are removed.
+        if let Some(datadir) = &self.datadir
+            && let Some(flusher) =
+                super::persist::spawn_flusher(Arc::clone(datadir), port, &imposter)
+        {
+            serve_handles.push(flusher);
+        }
         // Hand the accept-loop handles to the imposter so `delete` awaits every listener's
         // teardown (issue #596). Stored post-spawn because the loops need the `Arc`-wrapped
         // imposter.
@@ -780,6 +842,8 @@ impl ImposterManager {
                                     .await
                                     {
                                         Ok(Ok(tls)) => {
+                                            let session =
+                                                Arc::new(RecordedTls::from_session(tls.get_ref().1));
                                             run_http1(
                                                 TokioIo::new(tls),
                                                 imposter,
@@ -789,6 +853,7 @@ impl ImposterManager {
                                                 port,
                                                 decorator,
                                                 http_tuning,
+                                                Some(session),
                                             )
                                             .await
                                         }
@@ -809,6 +874,7 @@ impl ImposterManager {
                                             port,
                                             decorator,
                                             http_tuning,
+                                            None,
                                         )
                                         .await
                                     }
@@ -1202,6 +1268,11 @@ impl ImposterManager {
         self.imposters.len()
     }
 
+    /// The directory imposters are persisted to (`--datadir`), if any.
+    pub fn datadir(&self) -> Option<&std::path::Path> {
+        self.datadir.as_deref().map(PathBuf::as_path)
+    }
+
     /// Add stub to an imposter
     pub async fn add_stub(
         &self,
@@ -1317,6 +1388,26 @@ impl ImposterManager {
         self.persist_imposter_checked(&imposter).await
     }
 
+    /// Apply a batch of stub operations atomically: traffic sees the stubs from before the batch
+    /// or from after all of it, and a failing operation applies none of them. One
+    /// [`ImposterEvent::StubsChanged`] and one persist cover the whole batch.
+    pub async fn apply_stub_operations(
+        &self,
+        port: u16,
+        operations: Vec<StubOperation>,
+    ) -> Result<Vec<StubOperationResult>, ImposterError> {
+        let imposter = self.get_imposter(port)?;
+        let batch = imposter.apply_stub_operations(operations)?;
+        if let Some(sequencer) = &self.sequencer {
+            for key in &batch.deleted_keys {
+                sequencer.reset_scope(port, Some(key));
+            }
+        }
+        self.emit(ImposterEvent::StubsChanged(port));
+        self.persist_imposter_checked(&imposter).await?;
+        Ok(batch.results)
+    }
+
     /// Enable or disable serving on `port` — a *config* write, not an
     /// ephemeral toggle (issue #817): flips the runtime flag (the data plane
     /// reacts immediately), emits [`ImposterEvent::EnabledChanged`], and
@@ -1342,6 +1433,50 @@ impl ImposterManager {
             .ok_or(ImposterError::StubIndexOutOfBounds(index))
     }
 
+    /// Register `template`, replacing any template of the same name. Returns whether one was
+    /// replaced.
+    pub fn put_template(&self, template: ImposterTemplate) -> Result<bool, ImposterError> {
+        template.validate()?;
+        let name = template.name.clone();
+        Ok(self
+            .templates
+            .write()
+            .insert(name, Arc::new(template))
+            .is_some())
+    }
+
+    /// The template registered as `name`.
+    pub fn get_template(&self, name: &str) -> Result<Arc<ImposterTemplate>, ImposterError> {
+        self.templates
+            .read()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| ImposterError::TemplateNotFound(name.to_string()))
+    }
+
+    /// Every registered template, by name.
+    pub fn list_templates(&self) -> Vec<Arc<ImposterTemplate>> {
+        self.templates.read().values().cloned().collect()
+    }
+
+    /// Unregister the template `name`. Imposters already created from it are unaffected.
+    pub fn delete_template(&self, name: &str) -> Result<Arc<ImposterTemplate>, ImposterError> {
+        self.templates
+            .write()
+            .remove(name)
+            .ok_or_else(|| ImposterError::TemplateNotFound(name.to_string()))
+    }
+
+    /// The imposter config template `name` renders with `variables`, ready for
+    /// [`create_imposter`](Self::create_imposter).
+    pub fn render_template(
+        &self,
+        name: &str,
+        variables: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<ImposterConfig, ImposterError> {
+        self.get_template(name)?.render(variables)
+    }
+
     /// Shutdown all imposters (for future graceful shutdown)
     pub async fn shutdown(&self) {
         let _ = self.shutdown_tx.send(());
@@ -1358,45 +1493,43 @@ impl ImposterManager {
         let Some(port) = imposter.config.port else {
             return Ok(());
         };
-        let mut snapshot = imposter.config.clone();
-        snapshot.stubs = imposter.get_stubs();
-        // The atomic is the runtime truth; the retained config only holds the
-        // boot value. Snapshot the flag so every persist path (stub CRUD
-        // included) writes the operator's current decision.
-        snapshot.enabled = imposter.is_enabled();
-        let path = datadir.join(format!("{port}.json"));
-        let json = serde_json::to_string_pretty(&snapshot).map_err(|e| {
-            ImposterError::PersistError(
-                anyhow::Error::new(e).context(format!("Failed to serialize imposter {port}")),
-            )
-        })?;
-        tokio::fs::write(&path, json).await.map_err(|e| {
-            ImposterError::PersistError(
-                anyhow::Error::new(e)
-                    .context(format!("Failed to write imposter {port} to {path:?}")),
-            )
-        })
+        super::persist::write_snapshot(datadir, port, imposter).await
     }
 
-    /// Remove an imposter's file from datadir (if configured).
+    /// Remove an imposter's files (config, saved proxy responses and spilled bodies) from datadir
+    /// (if configured).
     fn remove_persisted_imposter(&self, port: u16) {
         let Some(ref datadir) = self.datadir else {
             return;
         };
-        let path = datadir.join(format!("{port}.json"));
+        let paths = [
+            super::persist::imposter_path(datadir, port),
+            super::persist::recordings_path(datadir, port),
+        ];
+        let bodies = super::persist::bodies_path(datadir, port);
         tokio::spawn(async move {
-            match tokio::fs::remove_file(&path).await {
+            match tokio::fs::remove_dir_all(&bodies).await {
                 Ok(()) => {}
-                // An absent file is the desired end state, not a failure: the imposter may never
-                // have been persisted, or the file was already removed. Handling NotFound here
-                // (rather than pre-checking `exists()`) also closes the TOCTOU window between
-                // check and unlink.
                 Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
                 Err(e) => error!(
-                    "Failed to remove persisted imposter {} at {:?}: {}",
-                    port, path, e
+                    "Failed to remove spilled bodies of imposter {} at {:?}: {}",
+                    port, bodies, e
                 ),
             }
+            for path in paths {
+                match tokio::fs::remove_file(&path).await {
+                    Ok(()) => {}
+                    // An absent file is the desired end state, not a failure: the imposter may never
+                    // have been persisted, or the file was already removed. Handling NotFound here
+                    // (rather than pre-checking `exists()`) also closes the TOCTOU window between
+                    // check and unlink.
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(e) => error!(
+                        "Failed to remove persisted imposter {} at {:?}: {}",
+                        port, path, e
+                    ),
+                }
+            }
         });
     }
 }
@@ -1697,6 +1830,509 @@ mod tests {
         manager.delete_imposter(19702).await.unwrap();
     }
 
+    /// Concurrent admin writes are ordered per imposter: whatever order they finish in, the file
+    /// ends up holding every stub, never an earlier snapshot written last.
+    #[tokio::test]
+    async fn concurrent_stub_writes_leave_the_latest_state_on_disk() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let manager = Arc::new(ImposterManager::with_datadir(Some(
+            dir.path().to_path_buf(),
+        )));
+        let config = serde_json::from_value(serde_json::json!({
+            "protocol": "http",
+            "port": 19704,
+            "stubs": []
+        }))
+        .unwrap();
+        manager.create_imposter(config).await.expect("create");
+
+        let writes: Vec<_> = (0..20)
+            .map(|i| {
+                let manager = Arc::clone(&manager);
+                tokio::spawn(async move {
+                    let stub: Stub = serde_json::from_value(serde_json::json!({
+                        "responses": [{"is": {"statusCode": 200, "body": format!("stub {i}")}}]
+                    }))
+                    .unwrap();
+                    manager.add_stub(19704, stub, None).await
+                })
+            })
+            .collect();
+        for write in writes {
+            write.await.unwrap().expect("add_stub");
+        }
+
+        let content = std::fs::read_to_string(dir.path().join("19704.json")).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(json["stubs"].as_array().unwrap().len(), 20);
+        assert!(!dir.path().join("19704.json.tmp").exists());
+
+        manager.delete_imposter(19704).await.unwrap();
+    }
+
+    /// `is.bodyFile` serves the file under the datadir, picks up edits without recreating the
+    /// stub, and is persisted (and returned) as the path — never inlined.
+    #[tokio::test]
+    async fn body_file_served_from_datadir_and_reloaded_on_change() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir(dir.path().join("bodies")).unwrap();
+        let body_path = dir.path().join("bodies/users.json");
+        std::fs::write(&body_path, r#"{"users":[]}"#).unwrap();
+        let manager = ImposterManager::with_datadir(Some(dir.path().to_path_buf()));
+
+        let config = serde_json::from_value(serde_json::json!({
+            "protocol": "http",
+            "port": 19660,
+            "stubs": [{"responses": [{"is": {"bodyFile": "bodies/users.json"}}]}]
+        }))
+        .unwrap();
+        manager.create_imposter(config).await.expect("create");
+
+        let resp = reqwest::get("http://127.0.0.1:19660/users").await.unwrap();
+        assert_eq!(resp.headers()["content-type"], "application/json");
+        assert_eq!(resp.text().await.unwrap(), r#"{"users":[]}"#);
+
+        std::fs::write(&body_path, r#"{"users":["ada"]}"#).unwrap();
+        let resp = reqwest::get("http://127.0.0.1:19660/users").await.unwrap();
+        assert_eq!(resp.text().await.unwrap(), r#"{"users":["ada"]}"#);
+
+        let persisted: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.path().join("19660.json")).unwrap())
+                .unwrap();
+        let is = &persisted["stubs"][0]["responses"][0]["is"];
+        assert_eq!(is["bodyFile"], "bodies/users.json");
+        assert!(
+            is.get("body").is_none(),
+            "the file content must not be inlined"
+        );
+
+        manager.delete_imposter(19660).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn ordered_behaviors_apply_in_listed_order() {
+        let manager = ImposterManager::new();
+        let copy = serde_json::json!({
+            "from": "path", "into": "${P}", "using": {"method": "regex", "selector": "/\\w+"}
+        });
+        // Reports whether decorate ran before (sees the raw token) or after the copy.
+        let decorate = r#"response.body = if response.body == "${P}" { "raw" } else { "copied" };"#;
+        let stub = |path: &str, behaviors: serde_json::Value| {
+            serde_json::json!({
+                "predicates": [{"equals": {"path": path}}],
+                "responses": [{"is": {"body": "${P}"}, "_behaviors": behaviors}]
+            })
+        };
+        let config = serde_json::from_value(serde_json::json!({
+            "protocol": "http",
+            "port": 19661,
+            "stubs": [
+                stub("/object", serde_json::json!({"decorate": decorate, "copy": copy})),
+                stub("/ordered", serde_json::json!([{"decorate": decorate}, {"copy": copy}])),
+            ]
+        }))
+        .unwrap();
+        manager.create_imposter(config).await.expect("create");
+
+        let get = |path: &'static str| async move {
+            reqwest::get(format!("http://127.0.0.1:19661{path}"))
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap()
+        };
+        assert_eq!(
+            get("/object").await,
+            "copied",
+            "object form: copy, then decorate"
+        );
+        assert_eq!(get("/ordered").await, "raw", "array form: listed order");
+
+        manager.delete_imposter(19661).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn default_behaviors_apply_unless_the_response_overrides_them() {
+        let manager = ImposterManager::new();
+        let config = serde_json::from_value(serde_json::json!({
+            "protocol": "http",
+            "port": 19662,
+            "defaultBehaviors": {"decorate": r#"response.body = response.body + "!";"#},
+            "stubs": [
+                {
+                    "predicates": [{"equals": {"path": "/plain"}}],
+                    "responses": [{"is": {"body": "plain"}}]
+                },
+                {
+                    "predicates": [{"equals": {"path": "/own"}}],
+                    "responses": [{
+                        "is": {"body": "own"},
+                        "_behaviors": {"decorate": r#"response.body = response.body + "?";"#}
+                    }]
+                }
+            ]
+        }))
+        .unwrap();
+        manager.create_imposter(config).await.expect("create");
+
+        let get = |path: &'static str| async move {
+            reqwest::get(format!("http://127.0.0.1:19662{path}"))
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap()
+        };
+        assert_eq!(get("/plain").await, "plain!", "default decorate applies");
+        assert_eq!(get("/own").await, "own?", "the response's decorate wins");
+
+        manager.delete_imposter(19662).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn responses_can_template_the_serving_stub() {
+        let manager = ImposterManager::new();
+        let config = serde_json::from_value(serde_json::json!({
+            "protocol": "http",
+            "port": 19663,
+            "stubs": [
+                {
+                    "predicates": [{"equals": {"path": "/never"}}],
+                    "responses": [{"is": {"body": "unused"}}]
+                },
+                {
+                    "id": "catch-all",
+                    "responses": [{
+                        "is": {
+                            "headers": {"X-Served-By": "${stub.id}"},
+                            "body": "${stub.index}@${imposter.port}"
+                        }
+                    }]
+                }
+            ]
+        }))
+        .unwrap();
+        manager.create_imposter(config).await.expect("create");
+
+        let resp = reqwest::get("http://127.0.0.1:19663/anything")
+            .await
+            .unwrap();
+        assert_eq!(resp.headers()["x-served-by"], "catch-all");
+        assert_eq!(resp.text().await.unwrap(), "1@19663");
+
+        manager.delete_imposter(19663).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn fetch_responses_merge_another_imposters_answer() {
+        let manager = ImposterManager::new();
+        let users = serde_json::from_value(serde_json::json!({
+            "protocol": "http",
+            "port": 19664,
+            "defaultResponse": {"statusCode": 404},
+            "stubs": [{
+                "predicates": [{"equals": {"path": "/users/ada"}}],
+                "responses": [{"is": {
+                    "headers": {"Content-Type": "application/json"},
+                    "body": {"name": "Ada Lovelace"}
+                }}]
+            }]
+        }))
+        .unwrap();
+        let orders = serde_json::from_value(serde_json::json!({
+            "protocol": "http",
+            "port": 19665,
+            "stubs": [{
+                "responses": [{
+                    "is": {"body": "{{ fetch.user.json '$.name' }} ({{ fetch.missing.status }})"},
+                    "_rift": {"fetch": {
+                        "user": {"imposter": 19664, "path": "/users/${request.query.user}"},
+                        "missing": {"imposter": 19664, "path": "/nobody"}
+                    }}
+                }]
+            }]
+        }))
+        .unwrap();
+        manager.create_imposter(users).await.expect("create users");
+        manager
+            .create_imposter(orders)
+            .await
+            .expect("create orders");
+
+        let resp = reqwest::get("http://127.0.0.1:19665/orders?user=ada")
+            .await
+            .unwrap();
+        assert_eq!(resp.text().await.unwrap(), "Ada Lovelace (404)");
+
+        // A target that cannot be reached fails the composite response with a 502.
+        manager.delete_imposter(19664).await.unwrap();
+        let resp = reqwest::get("http://127.0.0.1:19665/orders?user=ada")
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 502);
+        assert_eq!(resp.headers()["x-rift-fetch-error"], "true");
+
+        manager.delete_imposter(19665).await.unwrap();
+    }
+
+    /// Recordings made under `--datadir` are flushed as they happen, so a restart (a fresh
+    /// manager over a copy of the datadir) replays them with the upstream gone.
+    #[tokio::test]
+    async fn recordings_are_persisted_to_datadir_and_restored() {
+        let upstream = ImposterManager::new();
+        let upstream_config = serde_json::from_value(serde_json::json!({
+            "protocol": "http",
+            "port": 19666,
+            "stubs": [{"responses": [{"is": {"body": "from upstream"}}]}]
+        }))
+        .unwrap();
+        upstream.create_imposter(upstream_config).await.unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ImposterManager::with_datadir(Some(dir.path().to_path_buf()));
+        let recorder = serde_json::from_value(serde_json::json!({
+            "protocol": "http",
+            "port": 19667,
+            "stubs": [{"responses": [{"proxy": {
+                "to": "http://127.0.0.1:19666",
+                "mode": "proxyOnce",
+                "predicateGenerators": [{"matches": {"path": true}}]
+            }}]}]
+        }))
+        .unwrap();
+        manager.create_imposter(recorder).await.unwrap();
+
+        let body = reqwest::get("http://127.0.0.1:19667/widgets")
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(body, "from upstream");
+
+        // The flush runs in the background; wait for both files to reflect the recording.
+        let config_path = dir.path().join("19667.json");
+        let recordings_path = dir.path().join("recordings").join("19667.json");
+        for _ in 0..100 {
+            let stubs_recorded = std::fs::read_to_string(&config_path)
+                .is_ok_and(|json| json.contains("from upstream"));
+            if stubs_recorded && recordings_path.exists() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        // "Restart": a new manager over a copy of the datadir, with the upstream gone.
+        let restored_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(restored_dir.path().join("recordings")).unwrap();
+        for rel in ["19667.json", "recordings/19667.json"] {
+            std::fs::copy(dir.path().join(rel), restored_dir.path().join(rel)).unwrap();
+        }
+        manager.delete_imposter(19667).await.unwrap();
+        upstream.delete_imposter(19666).await.unwrap();
+
+        let restarted = ImposterManager::with_datadir(Some(restored_dir.path().to_path_buf()));
+        let saved: ImposterConfig = serde_json::from_str(
+            &std::fs::read_to_string(restored_dir.path().join("19667.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            saved.stubs.len(),
+            2,
+            "generated stub persisted before the proxy"
+        );
+        restarted.create_imposter(saved).await.unwrap();
+
+        let imposter = restarted.get_imposter(19667).unwrap();
+        let signature = crate::recording::RequestSignature::new("GET", "/widgets", None, &[]);
+        assert!(
+            imposter.proxy_store.lookup(19667, &signature).is_some(),
+            "saved proxy response re-imported"
+        );
+        let body = reqwest::get("http://127.0.0.1:19667/widgets")
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(body, "from upstream");
+
+        restarted.delete_imposter(19667).await.unwrap();
+    }
+
+    /// A streamed upstream response is recorded with its chunking and pauses, and the generated
+    /// stub replays it chunk by chunk with the same pauses.
+    #[tokio::test]
+    async fn streamed_responses_are_recorded_and_replayed_as_chunks() {
+        let events = "data: 1\n\ndata: 2\n\n";
+        let upstream = ImposterManager::new();
+        let upstream_config = serde_json::from_value(serde_json::json!({
+            "protocol": "http",
+            "port": 19690,
+            "stubs": [{"responses": [{
+                "is": {"headers": {"Content-Type": "text/event-stream"}, "body": events},
+                "_rift": {"stream": {"chunks": [{"size": 9}, {"size": 9, "delayMs": 300}]}}
+            }]}]
+        }))
+        .unwrap();
+        upstream.create_imposter(upstream_config).await.unwrap();
+
+        let manager = ImposterManager::new();
+        let recorder = serde_json::from_value(serde_json::json!({
+            "protocol": "http",
+            "port": 19691,
+            "stubs": [{"responses": [{"proxy": {
+                "to": "http://127.0.0.1:19690",
+                "mode": "proxyOnce",
+                "predicateGenerators": [{"matches": {"path": true}}]
+            }}]}]
+        }))
+        .unwrap();
+        manager.create_imposter(recorder).await.unwrap();
+
+        let body = reqwest::get("http://127.0.0.1:19691/events")
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(body, events);
+
+        let stubs = manager.get_imposter(19691).unwrap().get_stubs();
+        let crate::imposter::StubResponse::Is { is, rift, .. } = &stubs[0].responses[0] else {
+            panic!("generated stub is an `is` response");
+        };
+        assert_eq!(is.body, Some(serde_json::json!(events)));
+        let chunks = &rift
+            .as_ref()
+            .and_then(|r| r.stream.as_ref())
+            .unwrap()
+            .chunks;
+        assert_eq!(chunks.len(), 2, "{chunks:?}");
+        assert_eq!(chunks[0].size + chunks[1].size, events.len());
+        assert!(chunks[1].delay_ms >= 250, "{chunks:?}");
+
+        // The generated stub now answers, pausing between the events as the upstream did.
+        upstream.delete_imposter(19690).await.unwrap();
+        let mut response = reqwest::get("http://127.0.0.1:19691/events").await.unwrap();
+        assert!(response.content_length().is_none(), "served chunked");
+        let first = response.chunk().await.unwrap().unwrap();
+        let started = std::time::Instant::now();
+        let mut rest = Vec::new();
+        while let Some(chunk) = response.chunk().await.unwrap() {
+            rest.extend_from_slice(&chunk);
+        }
+        assert!(started.elapsed() >= Duration::from_millis(200));
+        assert_eq!([&first[..], &rest[..]].concat(), events.as_bytes());
+
+        manager.delete_imposter(19691).await.unwrap();
+    }
+
+    /// Bodies over the spill threshold are recorded as datadir files: the saved response and the
+    /// generated stub reference the file, and both the stub and the HAR export serve the body.
+    #[tokio::test]
+    async fn large_recorded_bodies_are_spilled_to_datadir() {
+        let large = "x".repeat(64);
+        let upstream = ImposterManager::new();
+        let upstream_config = serde_json::from_value(serde_json::json!({
+            "protocol": "http",
+            "port": 19668,
+            "stubs": [
+                {"predicates": [{"equals": {"path": "/large"}}],
+                 "responses": [{"is": {"body": large}}]},
+                {"responses": [{"is": {"body": "small"}}]}
+            ]
+        }))
+        .unwrap();
+        upstream.create_imposter(upstream_config).await.unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ImposterManager::with_datadir(Some(dir.path().to_path_buf()))
+            .with_recording_spill_threshold(Some(16));
+        let recorder = serde_json::from_value(serde_json::json!({
+            "protocol": "http",
+            "port": 19669,
+            "stubs": [{"responses": [{"proxy": {
+                "to": "http://127.0.0.1:19668",
+                "mode": "proxyOnce",
+                "predicateGenerators": [{"matches": {"path": true}}]
+            }}]}]
+        }))
+        .unwrap();
+        manager.create_imposter(recorder).await.unwrap();
+
+        for (path, expected) in [("/large", large.as_str()), ("/small", "small")] {
+            let url = format!("http://127.0.0.1:19669{path}");
+            let body = reqwest::get(&url).await.unwrap().text().await.unwrap();
+            assert_eq!(body, expected);
+        }
+
+        let imposter = manager.get_imposter(19669).unwrap();
+        let lookup = |path: &str| {
+            let signature = crate::recording::RequestSignature::new("GET", path, None, &[]);
+            imposter.proxy_store.lookup(19669, &signature).unwrap()
+        };
+        let spilled = lookup("/large");
+        let file = spilled.body_file.clone().expect("large body spilled");
+        assert!(spilled.body.is_empty(), "spilled body not kept in memory");
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(&file)).unwrap(),
+            large
+        );
+        assert!(
+            lookup("/small").body_file.is_none(),
+            "small body kept inline"
+        );
+
+        let stubs = imposter.get_stubs();
+        let crate::imposter::StubResponse::Is { is, .. } = &stubs[0].responses[0] else {
+            panic!("generated stub is an `is` response");
+        };
+        assert_eq!(is.body_file.as_deref(), Some(file.as_str()));
+        assert!(is.body.is_none());
+
+        // The generated stub now answers, reading the body back from the file.
+        upstream.delete_imposter(19668).await.unwrap();
+        let body = reqwest::get("http://127.0.0.1:19669/large")
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(body, large);
+
+        let har = serde_json::to_value(imposter.export_har()).unwrap();
+        assert!(
+            har.to_string().contains(&large),
+            "HAR export hydrates the body"
+        );
+
+        drop(imposter);
+        manager.delete_imposter(19669).await.unwrap();
+        let bodies = dir.path().join("recordings").join("bodies").join("19669");
+        for _ in 0..100 {
+            if !bodies.exists() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(!bodies.exists(), "spilled bodies removed with the imposter");
+    }
+
+    #[tokio::test]
+    async fn default_behaviors_with_repeat_are_rejected() {
+        let manager = ImposterManager::new();
+        let config = serde_json::from_value(serde_json::json!({
+            "protocol": "http",
+            "defaultBehaviors": {"wait": 10, "repeat": 2},
+            "stubs": []
+        }))
+        .unwrap();
+        let err = manager.create_imposter(config).await.unwrap_err();
+        assert!(matches!(err, ImposterError::InvalidBehaviors(_)), "{err}");
+    }
+
     #[test]
     fn test_new_has_no_datadir() {
         let manager = ImposterManager::new();
@@ -2126,6 +2762,7 @@ mod tests {
             headers: std::collections::HashMap::new(),
             body: None,
             timestamp: "2026-01-01T00:00:00Z".to_string(),
+            tls: None,
         }
     }
 
@@ -13,19 +13,34 @@
 //! - `predicates`: Predicate matching logic for stub matching
 //! - `response`: Response building and execution
 //! - `handler`: HTTP request handling for imposters
+//! - `fetch`: `_rift.fetch` sub-requests for composite responses
 //! - `manager`: ImposterManager for lifecycle management
+//! - `persist`: `--datadir` persistence of recorded stubs and proxy responses
+//! - `stream`: chunked serving of `_rift.stream` responses
+//! - `tap`: `--proxy-tap` NDJSON log of proxied traffic
+//! - `template`: parameterized imposter templates (`${var}` placeholders)
+//! - `tls_session`: TLS parameters of HTTPS connections, recorded as `RecordedRequest.tls`
 //! - `core`: Core Imposter struct and implementation
 
+mod body_file;
 mod core;
+mod cors;
 pub mod events;
 mod fault_io;
+mod fetch;
 mod handler;
 mod manager;
+mod persist;
 pub(crate) mod predicates;
 mod reconcile;
 mod response;
 mod script_resolve;
+mod stream;
+mod tap;
+mod template;
+mod tls_session;
 mod types;
+mod wiremock;
 
 #[cfg(test)]
 mod tests;
@@ -33,18 +48,19 @@ mod tests;
 // Re-export public types (used by external consumers like admin_api)
 #[allow(unused_imports)]
 pub use types::{
-    DebugImposter, DebugMatchResult, DebugRequest, DebugResponse, DebugResponsePreview,
-    DebugStubInfo, ImposterConfig, ImposterError, IsResponse, PathRewrite, Predicate,
-    PredicateOperation, PredicateParameters, PredicateSelector, ProxyResponse, RecordedRequest,
-    ResponseMode, RiftConfig, RiftConnectionPoolConfig, RiftErrorFault, RiftFaultConfig,
+    ClientCertificate, CorsConfig, DebugImposter, DebugMatchResult, DebugRequest, DebugResponse,
+    DebugResponsePreview, DebugStubInfo, ImposterConfig, ImposterError, IsResponse, PathRewrite,
+    Predicate, PredicateOperation, PredicateParameters, PredicateSelector, ProxyRecordingOptions,
+    ProxyResponse, RecordedRequest, RecordedTls, ResponseMode, RiftConfig,
+    RiftConnectionPoolConfig, RiftErrorFault, RiftFaultConfig, RiftFetchConfig,
     RiftFlowStateConfig, RiftLatencyFault, RiftMetricsConfig, RiftProxyConfig, RiftRedisConfig,
-    RiftResponseExtension, RiftScriptConfig, RiftScriptEngineConfig, RiftTcpFault,
-    RiftUpstreamConfig, Stub, StubResponse,
+    RiftResponseExtension, RiftScriptConfig, RiftScriptEngineConfig, RiftStreamChunk,
+    RiftStreamConfig, RiftTcpFault, RiftUpstreamConfig, Stub, StubResponse,
 };
 
-// Re-export script `file:`/`ref:` resolution (issue #356)
+// Re-export script `file:`/`ref:` resolution (issue #356) and library includes
 pub use script_resolve::{
-    ScriptBaseDir, ScriptResolveError, resolve_scripts, resolve_stub_scripts,
+    ScriptBaseDir, ScriptRegistry, ScriptResolveError, resolve_scripts, resolve_stub_scripts,
 };
 
 // Re-export core imposter
@@ -56,19 +72,34 @@ pub use journal::{
 };
 
 pub use core::Imposter;
-pub use core::{ClosestMatch, FailedPredicate, VerifyOptions, VerifyOutcome};
+pub use core::{ClosestMatch, FailedPredicate, FuzzyMatch, VerifyOptions, VerifyOutcome};
+pub use core::{DEFAULT_DRAIN_RETRY_AFTER_SECS, DrainStatus};
+pub use core::{LATENCY_BUCKETS_MS, TrafficStats};
+pub use core::{ProxiedRequest, ProxiedRequestReport};
+pub use core::{RecordingSessionSummary, RecordingState, RecordingStatus};
+pub use core::{ServedMessage, ServedRequest, ServedRequestDetail, ServedRequestReport};
+pub use core::{ShadowDiff, ShadowDifference, ShadowDifferenceKind, ShadowReport};
+pub use core::{StubOperation, StubOperationResult, StubRef};
+pub use template::ImposterTemplate;
+pub use wiremock::{WireMockImport, stubs_from_wiremock};
 
 // Re-export the imposter request handler (single-port gateway dispatch, issue #212)
 pub use handler::{handle_imposter_request, handle_imposter_request_decorated};
 
+// Re-export the bounded decorate runner, for running a decorate script outside a request
+pub use response::apply_decorate_bounded;
+
 // Re-export the embedder flow-store provider hook (issue #312)
 pub use crate::extensions::flow_state::FlowStoreProvider;
 
 // Re-export manager
 pub use manager::{ImposterManager, TlsDefaults};
 
+// Re-export the proxy traffic tap (`--proxy-tap`)
+pub use tap::ProxyTap;
+
 // Re-export incremental reconciliation types (issue #316)
-pub use events::{AdminEvent, AdminEventBus, AdminEventKind, ImposterAction};
+pub use events::{AdminEvent, AdminEventBus, AdminEventKind, ImposterAction, TrafficOutcome};
 pub use reconcile::{ApplyReport, ImposterEvent, ImposterEventListener, stub_key};
 
 // Re-export predicate utilities (used in tests and for external consumers)
@@ -79,3 +110,4 @@ pub use predicates::{parse_query_string, predicate_matches, stub_matches};
 pub use response::PreparedResponse;
 #[allow(unused_imports)]
 pub use response::create_response_preview;
+pub(crate) use response::create_stub_from_proxy_response;
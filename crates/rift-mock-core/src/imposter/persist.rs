@@ -0,0 +1,222 @@
+//! `--datadir` persistence of an imposter's recorded state.
+//!
+//! Admin writes persist `{port}.json` synchronously (see `ImposterManager`) through
+//! [`write_snapshot`], which orders and coalesces them per imposter. A recording
+//! session changes state from the data plane: every proxied request may save a response in the
+//! proxy-recording store and insert (or extend) a generated stub. Without persisting those, a
+//! crash or deploy threw away hours of recording. Each imposter created under a datadir therefore
+//! gets a background flusher that, after every such change, rewrites:
+//!
+//! - `{port}.json` — the imposter snapshot, generated stubs included, exactly as an admin write
+//!   would leave it; the existing datadir loader restores it on restart.
+//! - `recordings/{port}.json` — the saved proxy responses (the same `[signature, responses]` list
+//!   `RecordingStore::save_to_file` writes), re-imported when the imposter is created again.
+//!
+//! - `recordings/bodies/{port}/` — recorded bodies over `--recording-spill-threshold`, written
+//!   once when recorded (see [`BodySpill`]) and referenced by path from the two files above.
+//!
+//! Wake-ups go through a [`tokio::sync::Notify`], which holds at most one permit, so a burst of recordings
+//! that lands during a write coalesces into a single follow-up write. Files are written to a
+//! sibling temp file and renamed into place, so a crash mid-write leaves the previous version
+//! rather than a truncated one.
+
+use super::core::Imposter;
+use super::types::{ImposterConfig, ImposterError};
+use crate::recording::{RecordedResponse, RequestSignature};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Weak};
+use tracing::{debug, info, warn};
+
+/// Subdirectory of the datadir holding saved proxy responses. A subdirectory, not a sibling
+/// `*.json`, so the datadir loader never mistakes a recordings file for an imposter.
+const RECORDINGS_DIR: &str = "recordings";
+
+/// Subdirectory of [`RECORDINGS_DIR`] holding spilled recorded bodies, one directory per port.
+const BODIES_DIR: &str = "bodies";
+
+type SavedResponses = Vec<(RequestSignature, Vec<RecordedResponse>)>;
+
+/// The config an imposter persists as: see [`Imposter::current_config`].
+pub(super) fn snapshot(imposter: &Imposter) -> ImposterConfig {
+    imposter.current_config()
+}
+
+/// Write `port`'s `{port}.json` from the imposter's current state, atomically.
+///
+/// Writes are serialized per imposter and the snapshot is taken under that lock, so however
+/// concurrent mutations interleave, the file never goes back to an older state. A write that
+/// queued behind one whose snapshot already included its change is skipped, so a burst of
+/// mutations costs only as many writes as the lock lets through.
+pub(super) async fn write_snapshot(
+    datadir: &Path,
+    port: u16,
+    imposter: &Imposter,
+) -> Result<(), ImposterError> {
+    let generation = imposter.persist_requested.fetch_add(1, Ordering::SeqCst) + 1;
+    let mut written = imposter.persisted.lock().await;
+    if *written >= generation {
+        return Ok(());
+    }
+    // Read before the snapshot: every change up to `latest` was made before it was queued, so
+    // the snapshot below includes it.
+    let latest = imposter.persist_requested.load(Ordering::SeqCst);
+    let json = serde_json::to_vec_pretty(&snapshot(imposter)).map_err(|e| {
+        ImposterError::PersistError(
+            anyhow::Error::new(e).context(format!("Failed to serialize imposter {port}")),
+        )
+    })?;
+    let path = imposter_path(datadir, port);
+    write_atomically(&path, &json).await.map_err(|e| {
+        ImposterError::PersistError(
+            e.context(format!("Failed to write imposter {port} to {path:?}")),
+        )
+    })?;
+    *written = latest;
+    Ok(())
+}
+
+/// Path of `port`'s `{port}.json` imposter file.
+pub(super) fn imposter_path(datadir: &Path, port: u16) -> PathBuf {
+    datadir.join(format!("{port}.json"))
+}
+
+/// Path of `port`'s saved proxy responses.
+pub(super) fn recordings_path(datadir: &Path, port: u16) -> PathBuf {
+    datadir.join(RECORDINGS_DIR).join(format!("{port}.json"))
+}
+
+/// Directory of `port`'s spilled recorded bodies.
+pub(super) fn bodies_path(datadir: &Path, port: u16) -> PathBuf {
+    datadir
+        .join(RECORDINGS_DIR)
+        .join(BODIES_DIR)
+        .join(port.to_string())
+}
+
+/// Moves recorded proxy bodies larger than `threshold` bytes out of memory into files under the
+/// datadir. The recorded response and the generated stub then carry only the datadir-relative
+/// path (`body_file` / `is.bodyFile`), and the body is read back when replayed or exported.
+///
+/// Files are named by the SHA-256 of their content, so re-recording an identical download reuses
+/// the file and `collapseDuplicates` still sees two equal responses as equal.
+#[derive(Clone)]
+pub(crate) struct BodySpill {
+    datadir: Arc<PathBuf>,
+    threshold: usize,
+}
+
+impl BodySpill {
+    pub(crate) fn new(datadir: Arc<PathBuf>, threshold: usize) -> Self {
+        Self { datadir, threshold }
+    }
+
+    /// Whether a body of `len` bytes is over the threshold.
+    pub(crate) fn applies_to(&self, len: usize) -> bool {
+        len > self.threshold
+    }
+
+    /// Write `body` for imposter `port`, returning its datadir-relative path. An existing file
+    /// with the same name already holds the same bytes and is left alone.
+    pub(crate) async fn write(&self, port: u16, body: &[u8]) -> anyhow::Result<String> {
+        let digest = ring::digest::digest(&ring::digest::SHA256, body);
+        let name: String = digest.as_ref().iter().map(|b| format!("{b:02x}")).collect();
+        let dir = bodies_path(&self.datadir, port);
+        let path = dir.join(format!("{name}.bin"));
+        if !tokio::fs::try_exists(&path).await? {
+            tokio::fs::create_dir_all(&dir).await?;
+            write_atomically(&path, body).await?;
+        }
+        Ok(format!("{RECORDINGS_DIR}/{BODIES_DIR}/{port}/{name}.bin"))
+    }
+}
+
+/// Re-import `port`'s saved proxy responses into a freshly built imposter. A missing file is a
+/// fresh start; an unreadable one is logged and skipped rather than failing the create, since
+/// the imposter itself is intact.
+pub(super) fn restore_recordings(datadir: &Path, port: u16, imposter: &Imposter) {
+    let path = recordings_path(datadir, port);
+    let json = match std::fs::read_to_string(&path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+        Err(e) => {
+            warn!("Failed to read saved proxy responses from {path:?}: {e}");
+            return;
+        }
+    };
+    match serde_json::from_str::<SavedResponses>(&json) {
+        Ok(entries) => {
+            info!(
+                "Restored {} recorded signature(s) for imposter {port} from {path:?}",
+                entries.len()
+            );
+            imposter.proxy_store.import(port, entries);
+        }
+        Err(e) => warn!("Ignoring corrupt saved proxy responses in {path:?}: {e}"),
+    }
+}
+
+/// Write the imposter snapshot and its saved proxy responses. An empty response set removes the
+/// recordings file, so a cleared store stays cleared across a restart.
+async fn flush(datadir: &Path, port: u16, imposter: &Imposter) -> anyhow::Result<()> {
+    write_snapshot(datadir, port, imposter).await?;
+
+    let saved = imposter.proxy_store.export(port);
+    let path = recordings_path(datadir, port);
+    if saved.is_empty() {
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(anyhow::Error::new(e).context(format!("removing {path:?}"))),
+        }
+    } else {
+        tokio::fs::create_dir_all(datadir.join(RECORDINGS_DIR)).await?;
+        write_atomically(&path, &serde_json::to_vec(&saved)?).await?;
+    }
+    debug!(
+        "Persisted recordings for imposter {port} ({} signature(s))",
+        saved.len()
+    );
+    Ok(())
+}
+
+async fn write_atomically(path: &Path, contents: &[u8]) -> anyhow::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    tokio::fs::write(&tmp, contents).await?;
+    tokio::fs::rename(&tmp, path).await?;
+    Ok(())
+}
+
+/// Start `imposter`'s flusher. It holds only a weak reference and stops on the imposter's
+/// shutdown signal; the caller adds the handle to the imposter's `serve_handles`, so delete
+/// awaits it before removing the files and a deleted imposter is never written back.
+pub(super) fn spawn_flusher(
+    datadir: Arc<PathBuf>,
+    port: u16,
+    imposter: &Arc<Imposter>,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let (Some(changed), Some(shutdown)) = (
+        imposter.recordings_changed.clone(),
+        imposter.shutdown_tx.as_ref(),
+    ) else {
+        return None;
+    };
+    let mut shutdown = shutdown.subscribe();
+    let imposter: Weak<Imposter> = Arc::downgrade(imposter);
+    Some(tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => break,
+                () = changed.notified() => {}
+            }
+            let Some(imposter) = imposter.upgrade() else {
+                break;
+            };
+            if let Err(e) = flush(&datadir, port, &imposter).await {
+                warn!("Failed to persist recordings for imposter {port}: {e:#}");
+            }
+        }
+    }))
+}
@@ -3,12 +3,13 @@
 
 use super::json::compare_json_recursive;
 use super::regex_cache::cached_regex;
+use crate::imposter::types::RecordedTls;
 use crate::util::FastMap;
 use std::collections::HashMap;
 use std::hash::BuildHasher;
 
 /// Check predicate fields against request values
-/// Supports: method, path, body, query, headers, requestFrom, ip, form
+/// Supports: method, path, body, query, headers, requestFrom, ip, form, tls
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn check_predicate_fields<F, SH>(
     obj: &HashMap<String, serde_json::Value>,
@@ -29,6 +30,8 @@ pub(crate) fn check_predicate_fields<F, SH>(
     // Request body already parsed once per request (issue #290); `Some` only for a
     // no-selector predicate whose `body` field then equals this parse.
     body_json: Option<&serde_json::Value>,
+    // What the connection's TLS handshake negotiated; `None` over plain HTTP.
+    tls: Option<&RecordedTls>,
 ) -> bool
 where
     F: Fn(&str, &str) -> bool,
@@ -111,6 +114,14 @@ where
         }
     }
 
+    // Check tls (the connection's TLS parameters, compared as the JSON object the request records)
+    if let Some(expected) = obj.get("tls") {
+        let (actual, parsed) = tls_view(tls);
+        if !check_string_field(expected, &actual, parsed.as_ref()) {
+            return false;
+        }
+    }
+
     // Check form fields (parsed from application/x-www-form-urlencoded) - Mountebank compatible
     if let Some(expected_form) = obj.get("form")
         && let Some(expected_obj) = expected_form.as_object()
@@ -198,7 +209,7 @@ where
 }
 
 /// Check predicate fields with regex matching
-/// Supports: method, path, body, query, headers, requestFrom, ip, form
+/// Supports: method, path, body, query, headers, requestFrom, ip, form, tls
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn check_predicate_fields_regex<SH>(
     obj: &HashMap<String, serde_json::Value>,
@@ -217,6 +228,8 @@ pub(crate) fn check_predicate_fields_regex<SH>(
     key_case_sensitive: bool,
     // Request body already parsed once per request (issue #290); see `check_predicate_fields`.
     body_json: Option<&serde_json::Value>,
+    // See `check_predicate_fields`.
+    tls: Option<&RecordedTls>,
 ) -> bool
 where
     SH: BuildHasher,
@@ -313,6 +326,14 @@ where
         return false;
     }
 
+    // Check tls
+    if let Some(expected) = obj.get("tls") {
+        let (actual, parsed) = tls_view(tls);
+        if !check_regex_field(expected, &actual, parsed.as_ref()) {
+            return false;
+        }
+    }
+
     // Check form fields
     if let Some(expected_form) = obj.get("form").and_then(|v| v.as_object()) {
         let actual_form = form.cloned().unwrap_or_default();
@@ -373,3 +394,11 @@ where
 
     true
 }
+
+/// `tls` as the predicate engine sees it: the JSON text recorded with the request, and its parse
+/// for object predicates. Empty without TLS, so an object predicate on `tls` never matches then.
+pub(crate) fn tls_view(tls: Option<&RecordedTls>) -> (String, Option<serde_json::Value>) {
+    let parsed = tls.and_then(|tls| serde_json::to_value(tls).ok());
+    let text = parsed.as_ref().map(ToString::to_string).unwrap_or_default();
+    (text, parsed)
+}
@@ -1,6 +1,7 @@
 //! JSON-shaped predicate helpers: value stringification, recursive `exists` checks,
 //! and recursive JSON comparison used by the `equals`/`deepEquals`/`matches` operators.
 
+use crate::imposter::types::RecordedTls;
 use crate::util::FastMap;
 use std::collections::HashMap;
 use std::hash::BuildHasher;
@@ -73,7 +74,7 @@ fn check_exists_json_recursive(expected: &serde_json::Value, actual_str: &str) -
 }
 
 /// Check exists predicate - verifies field presence or absence
-/// Supports: method, path, body, query, headers, form, requestFrom, ip
+/// Supports: method, path, body, query, headers, form, requestFrom, ip, tls
 /// When a field's value is an object (not a boolean), parse the actual value as JSON
 /// and recursively check field existence within it (Mountebank compatible).
 #[allow(clippy::too_many_arguments)]
@@ -90,6 +91,8 @@ pub(crate) fn check_exists_predicate<SH>(
     // Concretely `FastMap` — see `check_predicate_fields`.
     form: Option<&FastMap<String, String>>,
     key_case_sensitive: bool,
+    // See `check_predicate_fields`.
+    tls: Option<&RecordedTls>,
 ) -> bool
 where
     SH: BuildHasher,
@@ -139,6 +142,13 @@ where
         }
     }
 
+    // Check tls exists - like body, an object value checks fields within it
+    if let Some(expected) = obj.get("tls")
+        && !check_exists_json_recursive(expected, &super::fields::tls_view(tls).0)
+    {
+        return false;
+    }
+
     // Check body exists - supports both boolean and object values
     if let Some(expected) = obj.get("body")
         && !check_exists_json_recursive(expected, body)
@@ -6,7 +6,7 @@
 use crate::behaviors::{
     LazyXmlDom, eval_xpath_on, extract_jsonpath, extract_jsonpath_value, extract_xpath_with_ns,
 };
-use crate::imposter::types::{Predicate, PredicateOperation, PredicateSelector};
+use crate::imposter::types::{Predicate, PredicateOperation, PredicateSelector, RecordedTls};
 use crate::util::FastMap;
 use std::collections::HashMap;
 use std::hash::BuildHasher;
@@ -60,6 +60,7 @@ where
         body_json.as_ref(),
         None,
         Some(&query_map),
+        None,
     )
 }
 
@@ -91,6 +92,8 @@ pub(crate) fn stub_matches_inner<SH>(
     xml_dom: Option<&LazyXmlDom<'_>>,
     // Always sourced from `parse_query`/`parse_query_string` (issue #704); same reasoning as `form`.
     query_map: Option<&FastMap<String, String>>,
+    // What the connection's TLS handshake negotiated, for the `tls` field; `None` over plain HTTP.
+    tls: Option<&RecordedTls>,
 ) -> anyhow::Result<bool>
 where
     SH: BuildHasher,
@@ -116,6 +119,7 @@ where
             body_json,
             xml_dom,
             query_map,
+            tls,
         )? {
             return Ok(false);
         }
@@ -199,6 +203,7 @@ where
         body_json.as_ref(),
         None,
         Some(&query_map),
+        None,
     )
 }
 
@@ -229,6 +234,8 @@ pub(crate) fn predicate_matches_inner<SH>(
     xml_dom: Option<&LazyXmlDom<'_>>,
     // Concretely `FastMap` — see `stub_matches_inner`.
     query_map: Option<&FastMap<String, String>>,
+    // See `stub_matches_inner`.
+    tls: Option<&RecordedTls>,
 ) -> anyhow::Result<bool>
 where
     SH: BuildHasher,
@@ -360,6 +367,7 @@ where
             form,
             key_case_sensitive,
             field_body_json,
+            tls,
         )),
         PredicateOperation::DeepEquals(fields) => Ok(check_predicate_fields(
             fields,
@@ -376,6 +384,7 @@ where
             form,
             key_case_sensitive,
             field_body_json,
+            tls,
         )),
         PredicateOperation::Contains(fields) => Ok(check_predicate_fields(
             fields,
@@ -392,6 +401,7 @@ where
             form,
             key_case_sensitive,
             field_body_json,
+            tls,
         )),
         PredicateOperation::StartsWith(fields) => Ok(check_predicate_fields(
             fields,
@@ -408,6 +418,7 @@ where
             form,
             key_case_sensitive,
             field_body_json,
+            tls,
         )),
         PredicateOperation::EndsWith(fields) => Ok(check_predicate_fields(
             fields,
@@ -424,6 +435,7 @@ where
             form,
             key_case_sensitive,
             field_body_json,
+            tls,
         )),
         PredicateOperation::Matches(fields) => Ok(check_predicate_fields_regex(
             fields,
@@ -439,6 +451,7 @@ where
             form,
             key_case_sensitive,
             field_body_json,
+            tls,
         )),
         PredicateOperation::Exists(fields) => Ok(check_exists_predicate(
             fields,
@@ -451,6 +464,7 @@ where
             client_ip,
             form,
             key_case_sensitive,
+            tls,
         )),
         PredicateOperation::Not(inner) => Ok(!predicate_matches_inner(
             inner,
@@ -466,6 +480,7 @@ where
             body_json,
             xml_dom,
             Some(query_map),
+            tls,
         )?),
         PredicateOperation::Or(children) => {
             // Short-circuits on the first match, like the old `.any()`; a predicate-inject error
@@ -486,6 +501,7 @@ where
                     body_json,
                     xml_dom,
                     Some(query_map),
+                    tls,
                 )? {
                     return Ok(true);
                 }
@@ -510,6 +526,7 @@ where
                     body_json,
                     xml_dom,
                     Some(query_map),
+                    tls,
                 )? {
                     return Ok(false);
                 }
@@ -2365,6 +2382,7 @@ mod tests {
                 None,
                 None,
                 None,
+                None,
             )
             .is_err(),
             "an `or` must propagate a nested inject error rather than short-circuit past it"
@@ -2386,6 +2404,7 @@ mod tests {
                 None,
                 None,
                 None,
+                None,
             )
             .is_err(),
             "an `and` must propagate a nested inject error"
@@ -2407,6 +2426,7 @@ mod tests {
                 None,
                 None,
                 None,
+                None,
             )
             .is_err(),
             "a `not` must propagate a nested inject error rather than negate it into a match"
@@ -2467,6 +2487,7 @@ mod tests {
             None,
             None,
             Some(&parsed),
+            None,
         )
         .unwrap();
         let with_fallback = predicate_matches_inner(
@@ -2483,6 +2504,7 @@ mod tests {
             None,
             None,
             None,
+            None,
         )
         .unwrap();
 
@@ -2580,4 +2602,60 @@ mod tests {
         assert!(run(PredicateOperation::StartsWith(path_field("/api"))));
         assert!(run(PredicateOperation::EndsWith(path_field("USERS"))));
     }
+
+    #[test]
+    fn tls_field_matches_the_connection_parameters() {
+        let tls = RecordedTls {
+            sni: Some("api.example.com".to_string()),
+            version: "TLSv1.3".to_string(),
+            cipher: "TLS13_AES_128_GCM_SHA256".to_string(),
+            client_cert: Some(crate::imposter::types::ClientCertificate {
+                subject: "CN=tenant-a, O=Acme".to_string(),
+                common_name: Some("tenant-a".to_string()),
+                issuer: "CN=Test CA".to_string(),
+                serial_number: "01".to_string(),
+            }),
+        };
+        let run = |op: PredicateOperation, tls: Option<&RecordedTls>| {
+            predicate_matches_inner(
+                &make_predicate(op),
+                "GET",
+                "/",
+                None,
+                &empty_headers(),
+                None,
+                None,
+                None,
+                None,
+                0,
+                None,
+                None,
+                None,
+                tls,
+            )
+            .unwrap()
+        };
+        let tls_field = |value: serde_json::Value| HashMap::from([("tls".to_string(), value)]);
+
+        let tenant_a = json!({ "clientCert": { "commonName": "tenant-a" } });
+        assert!(run(
+            PredicateOperation::Equals(tls_field(tenant_a.clone())),
+            Some(&tls)
+        ));
+        assert!(!run(
+            PredicateOperation::Equals(tls_field(json!({ "clientCert": { "commonName": "b" } }))),
+            Some(&tls)
+        ));
+        assert!(!run(PredicateOperation::Equals(tls_field(tenant_a)), None));
+        assert!(run(
+            PredicateOperation::Matches(tls_field(json!({ "sni": "^api\\." }))),
+            Some(&tls)
+        ));
+        let has_cert = tls_field(json!({ "clientCert": true }));
+        assert!(run(
+            PredicateOperation::Exists(has_cert.clone()),
+            Some(&tls)
+        ));
+        assert!(!run(PredicateOperation::Exists(has_cert), None));
+    }
 }
@@ -8,21 +8,25 @@ use super::types::{
     StubResponse,
 };
 use crate::behaviors::{
-    DecorateError, HasRepeatBehavior, RequestContext, apply_decorate, is_js_config_decorate,
+    DecorateError, HasRepeatBehavior, RequestContext, apply_decorate_within, is_js_config_decorate,
 };
+use crate::extensions::StubMetadata;
 // Fallback-only (issue #357 Item 6): the real JS `config =>` decorate path is Boa
 // (`execute_mountebank_config_decorate`); this textual transpiler is used only when the
 // `javascript` feature is disabled and no JS engine is available.
 #[cfg(not(feature = "javascript"))]
 use crate::behaviors::rewrite_js_config_to_rhai;
 use crate::imposter::Predicate;
+use crate::scripting::ScriptLimits;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Truncate a string with ellipsis if it exceeds the maximum byte length.
 ///
 /// This function is unicode-safe and will not panic on multi-byte characters.
 /// It finds the nearest valid UTF-8 character boundary at or before `max_len`.
-fn truncate_with_ellipsis(text: &str, max_len: usize) -> String {
+pub(super) fn truncate_with_ellipsis(text: &str, max_len: usize) -> String {
     if text.len() <= max_len {
         return text.to_string();
     }
@@ -35,11 +39,16 @@ fn truncate_with_ellipsis(text: &str, max_len: usize) -> String {
 impl HasRepeatBehavior for StubResponse {
     fn get_repeat(&self) -> Option<u32> {
         match self {
-            StubResponse::Is { behaviors, .. } => behaviors
-                .as_ref()
-                .and_then(|b| b.get("repeat"))
-                .and_then(|r| r.as_u64())
-                .map(|r| r as u32),
+            // Object form, or the last `repeat` entry of an ordered `_behaviors` array.
+            StubResponse::Is { behaviors, .. } => match behaviors {
+                Some(serde_json::Value::Array(entries)) => {
+                    entries.iter().rev().find_map(|e| e.get("repeat"))
+                }
+                Some(b) => b.get("repeat"),
+                None => None,
+            }
+            .and_then(|r| r.as_u64())
+            .map(|r| r as u32),
             StubResponse::RiftScript { .. } => None,
             _ => None,
         }
@@ -122,7 +131,7 @@ pub fn create_response_preview(response: &StubResponse) -> DebugResponsePreview
 /// injected only when no Content-Type exists in *any* casing — a case-sensitive check emits a
 /// duplicate for a header like `CONTENT-TYPE` (issue #723). Shared by the execute path and
 /// `PreparedResponse::try_build` so the two serve paths cannot drift.
-fn has_content_type(headers: &HashMap<String, Vec<String>>) -> bool {
+pub(super) fn has_content_type(headers: &HashMap<String, Vec<String>>) -> bool {
     headers
         .keys()
         .any(|k| k.eq_ignore_ascii_case("content-type"))
@@ -232,11 +241,16 @@ impl PreparedResponse {
     ) -> Option<PreparedResponse> {
         // Binary mode (base64 decode + its failure signaling) and every `_rift` serving effect and
         // `_behaviors` block stay on the slow path — the fast path serves only inert `is` bodies.
-        if is.mode != ResponseMode::Text || behaviors_present {
+        // A `bodyFile` body can change on disk between requests, so it is never frozen here.
+        if is.mode != ResponseMode::Text || behaviors_present || is.body_file.is_some() {
             return None;
         }
         if let Some(r) = rift
-            && (r.fault.is_some() || r.templated || r.script.is_some())
+            && (r.fault.is_some()
+                || r.templated
+                || r.script.is_some()
+                || !r.fetch.is_empty()
+                || r.stream.is_some())
         {
             return None;
         }
@@ -361,6 +375,7 @@ pub fn create_stub_from_proxy_response(
         status_code: status,
         headers: response_headers,
         body: body_value,
+        body_file: None,
         mode,
     };
 
@@ -408,24 +423,58 @@ pub fn create_stub_from_proxy_response(
 
 /// Apply decorate behavior - handles both JavaScript and Rhai scripts.
 ///
-/// `imposter_port` identifies the per-imposter state shared with predicate injects and response
-/// injects for the same imposter (issue #355 Item 0) — decorate reads and writes the same
-/// persisted state rather than a throwaway object. `stub_id` tags the script logger's tracing
-/// events with the owning stub, when known (issue #355 AC1).
+/// `stub.imposter_port` identifies the per-imposter state shared with predicate injects and
+/// response injects for the same imposter (issue #355 Item 0) — decorate reads and writes the
+/// same persisted state rather than a throwaway object. `stub.id` tags the script logger's tracing
+/// events with the owning stub, when known (issue #355 AC1). The whole of `stub` is also exposed
+/// to the script as `stub`/`imposter` (`config.stub`/`config.imposter` in JS).
 pub fn apply_js_or_rhai_decorate(
     script: &str,
     request: &RequestContext,
     body: &str,
     status: u16,
     headers: &mut HashMap<String, String>,
-    imposter_port: u16,
-    stub_id: Option<&str>,
+    stub: &StubMetadata,
 ) -> Result<(String, u16), DecorateError> {
-    // Only the JS engine call sites below consume `imposter_port`/`stub_id`; without the feature
-    // the parameters would otherwise be unused.
-    #[cfg(not(feature = "javascript"))]
-    let _ = (imposter_port, stub_id);
+    apply_js_or_rhai_decorate_within(
+        script,
+        request,
+        body,
+        status,
+        headers,
+        stub,
+        &ScriptLimits::default(),
+        None,
+    )
+}
+
+/// A [`DecorateError::Limit`] when a JavaScript decorate's response is past the memory cap.
+#[cfg(feature = "javascript")]
+fn check_js_decorate_output(
+    body: &str,
+    headers: &HashMap<String, String>,
+    limits: &ScriptLimits,
+) -> Result<(), DecorateError> {
+    match limits.exceeded_by_js_output(crate::scripting::js_output_bytes(body, headers)) {
+        Some(exceeded) => Err(DecorateError::Limit(exceeded.limit)),
+        None => Ok(()),
+    }
+}
 
+/// [`apply_js_or_rhai_decorate`] with a Rhai script run under `limits` and stopped once `abort` is
+/// set. JavaScript has neither hook; Boa's loop and recursion caps bound it instead, and its
+/// decorated response is held to `limits`' memory cap.
+#[allow(clippy::too_many_arguments)]
+fn apply_js_or_rhai_decorate_within(
+    script: &str,
+    request: &RequestContext,
+    body: &str,
+    status: u16,
+    headers: &mut HashMap<String, String>,
+    stub: &StubMetadata,
+    limits: &ScriptLimits,
+    abort: Option<&Arc<AtomicBool>>,
+) -> Result<(String, u16), DecorateError> {
     // Mountebank's JS `config =>` convention (issue #191). Issue #357 Item 6: every such script
     // now runs through REAL Boa execution (`execute_mountebank_config_decorate`, which already
     // backed the `require()` case for issue #305) instead of the lossy textual JS→Rhai
@@ -473,10 +522,10 @@ pub fn apply_js_or_rhai_decorate(
                 body,
                 status,
                 headers,
-                imposter_port,
-                stub_id,
+                stub,
             ) {
                 Ok(result) => {
+                    check_js_decorate_output(&result.body, &result.headers, limits)?;
                     for (k, v) in result.headers {
                         headers.insert(k, v);
                     }
@@ -492,7 +541,16 @@ pub fn apply_js_or_rhai_decorate(
         #[cfg(not(feature = "javascript"))]
         {
             let rhai_script = rewrite_js_config_to_rhai(script);
-            return apply_decorate(&rhai_script, request, body, status, headers);
+            return apply_decorate_within(
+                &rhai_script,
+                request,
+                body,
+                status,
+                headers,
+                Some(stub),
+                limits,
+                abort,
+            );
         }
     }
 
@@ -519,10 +577,10 @@ pub fn apply_js_or_rhai_decorate(
                 body,
                 status,
                 headers,
-                imposter_port,
-                stub_id,
+                stub,
             ) {
                 Ok(result) => {
+                    check_js_decorate_output(&result.body, &result.headers, limits)?;
                     // Update headers from the result
                     for (k, v) in result.headers {
                         headers.insert(k, v);
@@ -541,22 +599,41 @@ pub fn apply_js_or_rhai_decorate(
             {
                 let js_body = script[start + 1..end].trim();
                 let rhai_script = js_body.replace('\'', "\"");
-                return apply_decorate(&rhai_script, request, body, status, headers);
+                return apply_decorate_within(
+                    &rhai_script,
+                    request,
+                    body,
+                    status,
+                    headers,
+                    Some(stub),
+                    limits,
+                    abort,
+                );
             }
             Err(DecorateError::JsParseFailure)
         }
     } else {
         // Assume it's Rhai script
-        apply_decorate(script, request, body, status, headers)
+        apply_decorate_within(
+            script,
+            request,
+            body,
+            status,
+            headers,
+            Some(stub),
+            limits,
+            abort,
+        )
     }
 }
 
 /// Run [`apply_js_or_rhai_decorate`] off the async worker with a wall-clock deadline
 /// (issue #476) — the same `spawn_blocking` + `tokio::time::timeout` shape as
 /// `scripting::bounded`. Takes and returns the header map by value because the execution moves
-/// to a blocking thread. No abort flag: Boa has no per-instruction interrupt, so after a timeout
-/// the loop-iteration cap (issue #327) is what eventually frees the blocking thread; the client
-/// is released at the deadline either way. The deadline is the imposter's
+/// to a blocking thread. A Rhai script is interrupted at the deadline through an abort flag and
+/// runs under the imposter's `limits`. Boa has no per-instruction interrupt, so after a timeout
+/// the loop-iteration cap (issue #327) is what eventually frees a JavaScript decorate's blocking
+/// thread; the client is released at the deadline either way. The deadline is the imposter's
 /// `resolve_script_timeout_ms` budget, shared with `_rift.script`.
 #[allow(clippy::too_many_arguments)]
 pub async fn apply_decorate_bounded(
@@ -565,20 +642,23 @@ pub async fn apply_decorate_bounded(
     body: String,
     status: u16,
     mut headers: HashMap<String, String>,
-    imposter_port: u16,
-    stub_id: Option<String>,
+    stub: StubMetadata,
     timeout: std::time::Duration,
+    limits: ScriptLimits,
 ) -> Result<(String, u16, HashMap<String, String>), DecorateError> {
     let timeout_ms = u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX);
+    let abort = Arc::new(AtomicBool::new(false));
+    let run_abort = Arc::clone(&abort);
     let handle = tokio::task::spawn_blocking(move || {
-        apply_js_or_rhai_decorate(
+        apply_js_or_rhai_decorate_within(
             &script,
             &request,
             &body,
             status,
             &mut headers,
-            imposter_port,
-            stub_id.as_deref(),
+            &stub,
+            &limits,
+            Some(&run_abort),
         )
         .map(|(body, status)| (body, status, headers))
     });
@@ -588,6 +668,7 @@ pub async fn apply_decorate_bounded(
             "decorate task panicked: {join_err}"
         ))),
         Err(_elapsed) => {
+            abort.store(true, Ordering::Relaxed);
             tracing::warn!("decorate script timed out after {timeout_ms}ms");
             Err(DecorateError::Timeout(timeout_ms))
         }
@@ -606,6 +687,13 @@ mod tests {
         NEXT.fetch_add(1, Ordering::Relaxed) as u16
     }
 
+    fn test_stub() -> StubMetadata {
+        StubMetadata {
+            imposter_port: test_port(),
+            ..Default::default()
+        }
+    }
+
     // Issue #191: the JS `config =>` decorate convention runs (rewritten to Rhai) end-to-end.
     fn decorate_req() -> RequestContext {
         RequestContext {
@@ -626,8 +714,7 @@ mod tests {
             "original",
             200,
             &mut headers,
-            test_port(),
-            None,
+            &test_stub(),
         )
         .unwrap();
         assert_eq!(body, "hello");
@@ -643,13 +730,95 @@ mod tests {
             "original",
             200,
             &mut headers,
-            test_port(),
-            None,
+            &test_stub(),
         )
         .unwrap();
         assert_eq!(body, "REQ-BODY");
     }
 
+    #[cfg(feature = "javascript")]
+    #[test]
+    fn decorate_js_config_signs_with_encoding_helpers() {
+        let mut headers = std::collections::HashMap::new();
+        let (body, _) = apply_js_or_rhai_decorate(
+            "config => { config.response.headers['X-Signature'] = \
+             rift.hmacSha256('secret', config.request.body, 'base64'); \
+             config.response.body = rift.base64Encode(config.request.body); }",
+            &decorate_req(),
+            "original",
+            200,
+            &mut headers,
+            &test_stub(),
+        )
+        .unwrap();
+        assert_eq!(body, "UkVRLUJPRFk=");
+        assert_eq!(
+            headers["X-Signature"],
+            crate::extensions::encoding::hmac_sha256_encoded(
+                "secret",
+                "REQ-BODY",
+                crate::extensions::encoding::DigestEncoding::Base64
+            )
+        );
+    }
+
+    #[test]
+    fn decorate_rhai_uses_encoding_helpers() {
+        let mut headers = std::collections::HashMap::new();
+        let (body, _) = apply_js_or_rhai_decorate(
+            "response.body = sha256_hex(request.body);",
+            &decorate_req(),
+            "original",
+            200,
+            &mut headers,
+            &test_stub(),
+        )
+        .unwrap();
+        assert_eq!(body, crate::extensions::encoding::sha256_hex(b"REQ-BODY"));
+    }
+
+    fn served_by() -> StubMetadata {
+        StubMetadata {
+            id: Some("orders".to_string()),
+            index: 2,
+            imposter_port: test_port(),
+        }
+    }
+
+    #[test]
+    fn decorate_rhai_sees_stub_metadata() {
+        let mut headers = std::collections::HashMap::new();
+        let stub = served_by();
+        let (body, _) = apply_js_or_rhai_decorate(
+            r##"response.body = stub.id + "#" + stub.index + "@" + imposter.port;"##,
+            &decorate_req(),
+            "original",
+            200,
+            &mut headers,
+            &stub,
+        )
+        .unwrap();
+        assert_eq!(body, format!("orders#2@{}", stub.imposter_port));
+    }
+
+    #[cfg(feature = "javascript")]
+    #[test]
+    fn decorate_js_config_sees_stub_metadata() {
+        let mut headers = std::collections::HashMap::new();
+        let stub = served_by();
+        let (body, _) = apply_js_or_rhai_decorate(
+            "config => { config.response.body = \
+             config.stub.id + '#' + config.stub.index + '@' + config.imposter.port; }",
+            &decorate_req(),
+            "original",
+            200,
+            &mut headers,
+            &stub,
+        )
+        .unwrap();
+        assert_eq!(body, format!("orders#2@{}", stub.imposter_port));
+    }
+
     // Issue #305: a `config =>` decorate that require()s an external module must route to the JS
     // engine (not the lossy Rhai rewrite) and actually run the module.
     #[cfg(feature = "javascript")]
@@ -676,8 +845,7 @@ mod tests {
             "original",
             200,
             &mut headers,
-            test_port(),
-            None,
+            &test_stub(),
         );
         let _ = std::fs::remove_file(&module);
         let (body, _) = result.expect("require-based config decorate should run");
@@ -713,8 +881,7 @@ mod tests {
             "original",
             200,
             &mut headers,
-            test_port(),
-            None,
+            &test_stub(),
         )
         .expect("real JS JSON.parse/stringify decorate must execute via Boa");
         let parsed: serde_json::Value = serde_json::from_str(&body).unwrap();
@@ -748,8 +915,7 @@ mod tests {
             "original",
             200,
             &mut headers,
-            test_port(),
-            None,
+            &test_stub(),
         )
         .expect("bare-body decorate with an inner arrow must execute via Boa");
         let parsed: serde_json::Value = serde_json::from_str(&body).unwrap();
@@ -765,8 +931,7 @@ mod tests {
             "original",
             200,
             &mut headers,
-            test_port(),
-            None,
+            &test_stub(),
         )
         .unwrap();
         assert_eq!(status, 404);
@@ -783,8 +948,7 @@ mod tests {
             "original",
             200,
             &mut headers,
-            test_port(),
-            None,
+            &test_stub(),
         )
         .unwrap();
         assert_eq!(body, "fn");
@@ -801,8 +965,7 @@ mod tests {
             "original",
             200,
             &mut headers,
-            test_port(),
-            None,
+            &test_stub(),
         )
         .unwrap();
         assert_eq!(body, "bare");
@@ -817,8 +980,7 @@ mod tests {
             "original",
             200,
             &mut headers,
-            test_port(),
-            None,
+            &test_stub(),
         )
         .unwrap();
         assert_eq!(body, "/orders", "existing Rhai decorate must be unchanged");
@@ -1121,9 +1283,9 @@ mod tests {
             "orig".to_string(),
             200,
             std::collections::HashMap::new(),
-            test_port(),
-            None,
+            test_stub(),
             std::time::Duration::from_millis(60_000),
+            ScriptLimits::default(),
         )
         .await
         .expect("fast decorate");
@@ -1144,9 +1306,9 @@ mod tests {
             "orig".to_string(),
             200,
             std::collections::HashMap::new(),
-            test_port(),
-            None,
+            test_stub(),
             std::time::Duration::from_millis(25),
+            ScriptLimits::default(),
         )
         .await;
         match res {
@@ -1158,6 +1320,32 @@ mod tests {
             "must return near the configured deadline, not after the loop cap"
         );
     }
+
+    // Boa has no heap limit, so a JavaScript decorate's response is what `maxMemoryBytes` caps.
+    #[cfg(feature = "javascript")]
+    #[tokio::test]
+    async fn mb_decorate_bounded_rejects_a_response_past_the_memory_cap() {
+        let res = apply_decorate_bounded(
+            "function (request, response) { response.body = 'x'.repeat(2048); }".to_string(),
+            decorate_req(),
+            "orig".to_string(),
+            200,
+            std::collections::HashMap::new(),
+            test_stub(),
+            std::time::Duration::from_millis(60_000),
+            ScriptLimits {
+                max_memory_bytes: 1024,
+                ..ScriptLimits::default()
+            },
+        )
+        .await;
+        match res {
+            Err(DecorateError::Limit(limit)) => {
+                assert!(limit.starts_with("memory limit (1024 bytes)"), "{limit}")
+            }
+            other => panic!("expected DecorateError::Limit, got {other:?}"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1185,6 +1373,7 @@ mod prepared_response_tests {
             status_code: status,
             headers: map,
             body,
+            body_file: None,
             mode: ResponseMode::Text,
         }
     }
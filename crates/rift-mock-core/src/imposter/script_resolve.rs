@@ -8,8 +8,15 @@
 //!
 //! Resolution happens once, at config-load time (`--configfile`/`--datadir`, admin API create,
 //! and `/admin/reload`), never per-request.
+//!
+//! The same pass expands script libraries: a script whose first lines read `// @include auth` has
+//! the `auth` entry of `_rift.scriptEngine.libraries` (or, for a name with a file extension, that
+//! file) added to its code. This covers `_rift.script`, the named registry, and the Mountebank
+//! hooks — response and predicate `inject` and the `decorate` behavior.
 
 use super::types::{ImposterConfig, RiftScriptConfig, Stub, StubResponse};
+use crate::behaviors::is_js_config_decorate;
+use rift_types::{Predicate, PredicateOperation};
 use std::collections::HashMap;
 use std::path::{Component, Path, PathBuf};
 
@@ -34,6 +41,40 @@ pub enum ScriptResolveError {
         "script file '{0}' cannot be resolved: no --scripts-dir is configured for the admin API"
     )]
     ScriptsDirNotConfigured(String),
+    #[error("unknown script library '{0}': no entry named '{0}' in `_rift.scriptEngine.libraries`")]
+    UnknownLibrary(String),
+    #[error("_rift.scriptEngine.libraries entry '{0}' may not use `ref`")]
+    LibraryRef(String),
+    #[error("script library '{0}' may not itself `// @include` other libraries")]
+    NestedInclude(String),
+    #[error("script library '{name}' is {library}, but it is included by a {script} script")]
+    LibraryEngine {
+        name: String,
+        library: String,
+        script: String,
+    },
+}
+
+/// An imposter's named scripts, already resolved: the `_rift.scripts` entries a `ref:` points at
+/// and the `_rift.scriptEngine.libraries` a `// @include` line pulls in.
+#[derive(Debug, Clone, Default)]
+pub struct ScriptRegistry {
+    pub scripts: HashMap<String, RiftScriptConfig>,
+    pub libraries: HashMap<String, RiftScriptConfig>,
+}
+
+impl ScriptRegistry {
+    /// The registry of an imposter whose config has been through [`resolve_scripts`].
+    pub fn of(config: &ImposterConfig) -> Self {
+        let rift = config.rift.as_ref();
+        Self {
+            scripts: rift.map(|r| r.scripts.clone()).unwrap_or_default(),
+            libraries: rift
+                .and_then(|r| r.script_engine.as_ref())
+                .map(|engine| engine.libraries.clone())
+                .unwrap_or_default(),
+        }
+    }
 }
 
 /// Where `file:` script paths resolve relative to, and whether escaping that root is rejected.
@@ -56,15 +97,23 @@ pub enum ScriptBaseDir {
     Unconfigured,
 }
 
-/// Resolve every `_rift.script` in `config` — the named registry first, then each stub response
-/// — mutating `code`/`engine` in place. Returns the first error encountered; on error `config`
-/// may be partially mutated and must not be used (the caller returns the error to the client /
-/// aborts the load without applying anything).
+/// Resolve every `_rift.script` in `config` — the libraries and the named registry first, then
+/// each stub — mutating `code`/`engine` in place and expanding `// @include` lines. Returns the
+/// first error encountered; on error `config` may be partially mutated and must not be used (the
+/// caller returns the error to the client / aborts the load without applying anything).
 pub fn resolve_scripts(
     config: &mut ImposterConfig,
     base: &ScriptBaseDir,
 ) -> Result<(), ScriptResolveError> {
-    // Resolve the named registry first so response-level `ref:` lookups see fully-resolved
+    // Libraries first: every other script may include them.
+    if let Some(engine) = config.rift.as_mut().and_then(|r| r.script_engine.as_mut()) {
+        for (name, library) in engine.libraries.iter_mut() {
+            resolve_library(name, library, base)?;
+        }
+    }
+    let libraries = ScriptRegistry::of(config).libraries;
+
+    // Resolve the named registry next so response-level `ref:` lookups see fully-resolved
     // entries (populated `code` + `engine`, never another `ref`).
     if let Some(rift) = &mut config.rift {
         for (name, script) in rift.scripts.iter_mut() {
@@ -73,37 +122,38 @@ pub fn resolve_scripts(
             }
             validate_source_count(script)?;
             resolve_leaf(script, base)?;
+            include_in_script(script, &libraries, base)?;
         }
     }
 
-    // Snapshot the resolved registry (small, config-time only) so per-response resolution can
-    // look up `ref:` targets without holding a borrow of `config.rift` across the stub loop.
-    let registry: HashMap<String, RiftScriptConfig> = config
-        .rift
-        .as_ref()
-        .map(|r| r.scripts.clone())
-        .unwrap_or_default();
-
+    // Snapshot the resolved registry (small, config-time only) so per-stub resolution can look
+    // up `ref:` targets without holding a borrow of `config.rift` across the stub loop.
+    let registry = ScriptRegistry::of(config);
     resolve_stub_scripts(&mut config.stubs, &registry, base)
 }
 
-/// Resolve `_rift.script` sources in a set of stubs against an already-resolved `registry`
-/// (issue #356). Used by the admin-API stub sub-resource endpoints (`POST/PUT .../stubs[...]`,
-/// space stubs), which add stubs to an existing imposter and must resolve — and therefore
-/// escape-check — `file:`/`ref:` at WRITE time, before persisting, exactly like whole-imposter
-/// create. `registry` is the target imposter's `_rift.scripts` (its entries must themselves be
+/// Resolve `_rift.script` sources and `// @include` lines in a set of stubs against an
+/// already-resolved `registry` (issue #356). Used by the admin-API stub sub-resource endpoints
+/// (`POST/PUT .../stubs[...]`, space stubs), which add stubs to an existing imposter and must
+/// resolve — and therefore escape-check — `file:`/`ref:` at WRITE time, before persisting, exactly
+/// like whole-imposter create. `registry` is the target imposter's (its entries must themselves be
 /// already resolved — `code` populated, `file`/`ref` cleared — which they are once the imposter
 /// was created).
 pub fn resolve_stub_scripts(
     stubs: &mut [Stub],
-    registry: &HashMap<String, RiftScriptConfig>,
+    registry: &ScriptRegistry,
     base: &ScriptBaseDir,
 ) -> Result<(), ScriptResolveError> {
     for stub in stubs {
+        for predicate in &mut stub.predicates {
+            include_in_predicate(predicate, &registry.libraries, base)?;
+        }
         for response in &mut stub.responses {
             if let Some(script) = response_script_mut(response) {
-                resolve_response_script(script, registry, base)?;
+                resolve_response_script(script, &registry.scripts, base)?;
+                include_in_script(script, &registry.libraries, base)?;
             }
+            include_in_response_hooks(response, &registry.libraries, base)?;
         }
     }
     Ok(())
@@ -175,6 +225,215 @@ fn resolve_leaf(
     Ok(())
 }
 
+/// Resolve a `_rift.scriptEngine.libraries` entry: load `file:` content into `code`, taking the
+/// engine from the file extension when none is given. An inline library keeps `engine: None` —
+/// includable by a script of any engine.
+fn resolve_library(
+    name: &str,
+    library: &mut RiftScriptConfig,
+    base: &ScriptBaseDir,
+) -> Result<(), ScriptResolveError> {
+    if library.ref_name.is_some() {
+        return Err(ScriptResolveError::LibraryRef(name.to_string()));
+    }
+    validate_source_count(library)?;
+    if let Some(file) = library.file.take() {
+        if library.engine.is_none() {
+            library.engine = Some(infer_engine(Some(&file)));
+        }
+        library.code = Some(read_script_file(&file, base)?);
+    }
+    Ok(())
+}
+
+/// Expand the `// @include` lines of a resolved `_rift.script`.
+fn include_in_script(
+    script: &mut RiftScriptConfig,
+    libraries: &HashMap<String, RiftScriptConfig>,
+    base: &ScriptBaseDir,
+) -> Result<(), ScriptResolveError> {
+    let engine = script.engine.clone().unwrap_or_else(|| "rhai".to_string());
+    if let Some(code) = &mut script.code {
+        include_libraries(code, |_| engine, libraries, base)?;
+    }
+    Ok(())
+}
+
+/// Expand the `// @include` lines of every predicate `inject` (JavaScript), looking through
+/// `and`/`or`/`not`.
+fn include_in_predicate(
+    predicate: &mut Predicate,
+    libraries: &HashMap<String, RiftScriptConfig>,
+    base: &ScriptBaseDir,
+) -> Result<(), ScriptResolveError> {
+    match &mut predicate.operation {
+        PredicateOperation::Inject(code) => {
+            include_libraries(code, |_| "javascript".to_string(), libraries, base)?;
+        }
+        PredicateOperation::Not(inner) => include_in_predicate(inner, libraries, base)?,
+        PredicateOperation::And(inner) | PredicateOperation::Or(inner) => {
+            for predicate in inner {
+                include_in_predicate(predicate, libraries, base)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Expand the `// @include` lines of a response's Mountebank hooks: an `inject` response
+/// (JavaScript) and a `_behaviors` `decorate` (JavaScript or Rhai, told apart the way
+/// `apply_js_or_rhai_decorate` does). An `is` response with a changed `decorate` is rebuilt through
+/// [`StubResponse::new_is`] so its parsed-behaviors cache sees the expanded script.
+fn include_in_response_hooks(
+    response: &mut StubResponse,
+    libraries: &HashMap<String, RiftScriptConfig>,
+    base: &ScriptBaseDir,
+) -> Result<(), ScriptResolveError> {
+    match response {
+        StubResponse::Inject { inject } => {
+            include_libraries(inject, |_| "javascript".to_string(), libraries, base)?;
+        }
+        StubResponse::Is {
+            is,
+            behaviors: Some(behaviors),
+            rift,
+            ..
+        } => {
+            let mut expanded = behaviors.clone();
+            let mut changed = false;
+            let entries: Vec<&mut serde_json::Value> = match &mut expanded {
+                serde_json::Value::Array(entries) => entries.iter_mut().collect(),
+                single => vec![single],
+            };
+            for entry in entries {
+                if let Some(serde_json::Value::String(decorate)) = entry.get_mut("decorate") {
+                    changed |= include_libraries(decorate, decorate_engine, libraries, base)?;
+                }
+            }
+            if changed {
+                let rebuilt = StubResponse::new_is(is.clone(), Some(expanded), rift.clone());
+                *response = rebuilt;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// The engine a `decorate` script runs on: JavaScript for a `function` or `config` script, Rhai
+/// otherwise.
+fn decorate_engine(script: &str) -> String {
+    if is_js_config_decorate(script) || script.trim_start().starts_with("function") {
+        "javascript"
+    } else {
+        "rhai"
+    }
+    .to_string()
+}
+
+/// Replace the `// @include` lines at the top of `code` with the libraries they name. `engine`
+/// gives the script's engine from its code without those lines. Returns whether `code` had any.
+///
+/// Rhai libraries go before the script. JavaScript ones go after it: a Mountebank hook is a
+/// function value the runtime recognises by its leading `function`/`config =>` and assigns
+/// (`var __fn = <script>;`), and the libraries' function declarations are hoisted either way.
+fn include_libraries(
+    code: &mut String,
+    engine: impl FnOnce(&str) -> String,
+    libraries: &HashMap<String, RiftScriptConfig>,
+    base: &ScriptBaseDir,
+) -> Result<bool, ScriptResolveError> {
+    let Some((names, script)) = take_includes(code) else {
+        return Ok(false);
+    };
+    let engine = engine(&script);
+    let sources = names
+        .iter()
+        .map(|name| library_source(name, &engine, libraries, base))
+        .collect::<Result<Vec<_>, _>>()?
+        .join("\n");
+    *code = if is_javascript(&engine) {
+        format!("{script}\n;\n{sources}")
+    } else {
+        format!("{sources}\n{script}")
+    };
+    Ok(true)
+}
+
+/// The code of the library `name` for a script on `engine`: a `_rift.scriptEngine.libraries`
+/// entry, else — for a name with a file extension — that file, read under the same root and
+/// escape rules as a `file:` script.
+fn library_source(
+    name: &str,
+    engine: &str,
+    libraries: &HashMap<String, RiftScriptConfig>,
+    base: &ScriptBaseDir,
+) -> Result<String, ScriptResolveError> {
+    let (library_engine, code) = match libraries.get(name) {
+        Some(library) => (
+            library.engine.clone(),
+            library.code.clone().unwrap_or_default(),
+        ),
+        None if Path::new(name).extension().is_some() => (
+            Some(infer_engine(Some(name))),
+            read_script_file(name, base)?,
+        ),
+        None => return Err(ScriptResolveError::UnknownLibrary(name.to_string())),
+    };
+    if let Some(library_engine) = library_engine
+        && is_javascript(&library_engine) != is_javascript(engine)
+    {
+        return Err(ScriptResolveError::LibraryEngine {
+            name: name.to_string(),
+            library: library_engine,
+            script: engine.to_string(),
+        });
+    }
+    if take_includes(&code).is_some() {
+        return Err(ScriptResolveError::NestedInclude(name.to_string()));
+    }
+    Ok(code)
+}
+
+fn is_javascript(engine: &str) -> bool {
+    matches!(engine, "javascript" | "js")
+}
+
+/// Split the `// @include a, b` lines off the top of a script: the library names (each once, in
+/// order) and the script without those lines, or `None` when it has none. Only the leading run of
+/// blank and `//` comment lines is searched.
+fn take_includes(code: &str) -> Option<(Vec<String>, String)> {
+    let mut names: Vec<String> = Vec::new();
+    let mut kept = String::new();
+    let mut rest = code;
+    while let Some(line) = rest.split_inclusive('\n').next() {
+        let trimmed = line.trim();
+        let include = trimmed
+            .strip_prefix("//")
+            .map(str::trim_start)
+            .and_then(|comment| comment.strip_prefix("@include"))
+            .filter(|list| list.is_empty() || list.starts_with(char::is_whitespace));
+        if let Some(list) = include {
+            for name in list.split(|c: char| c == ',' || c.is_whitespace()) {
+                if !name.is_empty() && !names.iter().any(|n| n == name) {
+                    names.push(name.to_string());
+                }
+            }
+        } else if trimmed.is_empty() || trimmed.starts_with("//") {
+            kept.push_str(line);
+        } else {
+            break;
+        }
+        rest = &rest[line.len()..];
+    }
+    if names.is_empty() {
+        return None;
+    }
+    kept.push_str(rest);
+    Some((names, kept))
+}
+
 /// Infer the engine from a `file:` path's extension; `.rhai`/`.lua`/`.js` map to their engines,
 /// anything else (or no `file`, i.e. inline `code:`) falls back to the legacy "rhai" default.
 fn infer_engine(file: Option<&str>) -> String {
@@ -228,7 +487,7 @@ fn read_script_file(file: &str, base: &ScriptBaseDir) -> Result<String, ScriptRe
 ///     under the root → surface a `FileRead` so the message names the missing file) from anything
 ///     else (a broken/escaping symlink, an unreadable parent) → reject as `PathEscape`. Either way
 ///     the target is never read here.
-fn resolve_within_root(root: &Path, file: &str) -> Result<PathBuf, ScriptResolveError> {
+pub(crate) fn resolve_within_root(root: &Path, file: &str) -> Result<PathBuf, ScriptResolveError> {
     let candidate = safe_join(root, file)?;
     let canon_root = root
         .canonicalize()
@@ -294,7 +553,7 @@ fn safe_join(root: &Path, rel: &str) -> Result<PathBuf, ScriptResolveError> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::imposter::types::{RiftConfig, RiftResponseExtension, Stub};
+    use crate::imposter::types::{RiftConfig, RiftResponseExtension, RiftScriptEngineConfig, Stub};
 
     fn script(code: Option<&str>, file: Option<&str>, ref_name: Option<&str>) -> RiftScriptConfig {
         RiftScriptConfig {
@@ -315,6 +574,8 @@ mod tests {
                     fault: None,
                     script: Some(script_config),
                     templated: false,
+                    fetch: Default::default(),
+                    stream: None,
                 },
             }],
             scenario_name: None,
@@ -657,4 +918,150 @@ mod tests {
             Some("javascript")
         );
     }
+
+    fn with_libraries(stubs: Vec<Stub>, libraries: &[(&str, RiftScriptConfig)]) -> ImposterConfig {
+        ImposterConfig {
+            rift: Some(RiftConfig {
+                script_engine: Some(RiftScriptEngineConfig {
+                    default_engine: "rhai".to_string(),
+                    timeout_ms: 5000,
+                    max_operations: 0,
+                    max_memory_bytes: 0,
+                    libraries: libraries
+                        .iter()
+                        .map(|(name, library)| (name.to_string(), library.clone()))
+                        .collect(),
+                }),
+                ..Default::default()
+            }),
+            stubs,
+            ..Default::default()
+        }
+    }
+
+    fn inject_stub(inject: &str) -> Stub {
+        let mut stub = stub_with_script(script(Some("x"), None, None));
+        stub.responses = vec![StubResponse::Inject {
+            inject: inject.to_string(),
+        }];
+        stub
+    }
+
+    #[test]
+    fn rhai_include_prepends_the_library() {
+        let mut config = with_libraries(
+            vec![stub_with_script(script(
+                Some("// @include auth\nfn should_inject(r, s) { bearer(r) }"),
+                None,
+                None,
+            ))],
+            &[("auth", script(Some("fn bearer(r) { r }"), None, None))],
+        );
+        resolve_scripts(&mut config, &ScriptBaseDir::Unconfigured).unwrap();
+        assert_eq!(
+            extract_script(&config).code.as_deref(),
+            Some("fn bearer(r) { r }\nfn should_inject(r, s) { bearer(r) }")
+        );
+    }
+
+    #[test]
+    fn javascript_inject_include_appends_the_library() {
+        let mut config = with_libraries(
+            vec![inject_stub(
+                "// @include paging\nfunction (config) { return page(config); }",
+            )],
+            &[(
+                "paging",
+                script(Some("function page(c) { return {}; }"), None, None),
+            )],
+        );
+        resolve_scripts(&mut config, &ScriptBaseDir::Unconfigured).unwrap();
+        let StubResponse::Inject { inject } = &config.stubs[0].responses[0] else {
+            panic!("expected Inject");
+        };
+        // The hook must still start with `function` for the runtime to recognise it.
+        assert_eq!(
+            inject,
+            "function (config) { return page(config); }\n;\nfunction page(c) { return {}; }"
+        );
+    }
+
+    #[test]
+    fn include_reads_a_library_file_under_the_root() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("auth.rhai"), "fn bearer(r) { r }").unwrap();
+        let mut config = ImposterConfig {
+            stubs: vec![stub_with_script(script(
+                Some("// @include auth.rhai\nbearer(request)"),
+                None,
+                None,
+            ))],
+            ..Default::default()
+        };
+        resolve_scripts(
+            &mut config,
+            &ScriptBaseDir::DatadirRelative(dir.path().to_path_buf()),
+        )
+        .unwrap();
+        assert_eq!(
+            extract_script(&config).code.as_deref(),
+            Some("fn bearer(r) { r }\nbearer(request)")
+        );
+    }
+
+    #[test]
+    fn unknown_library_is_an_error() {
+        let mut config = ImposterConfig {
+            stubs: vec![stub_with_script(script(
+                Some("// @include nope\nx"),
+                None,
+                None,
+            ))],
+            ..Default::default()
+        };
+        let err = resolve_scripts(&mut config, &ScriptBaseDir::Unconfigured).unwrap_err();
+        assert!(matches!(err, ScriptResolveError::UnknownLibrary(name) if name == "nope"));
+    }
+
+    #[test]
+    fn library_of_another_engine_is_rejected() {
+        let mut library = script(Some("function page() {}"), None, None);
+        library.engine = Some("javascript".to_string());
+        let mut config = with_libraries(
+            vec![stub_with_script(script(
+                Some("// @include paging\nx"),
+                None,
+                None,
+            ))],
+            &[("paging", library)],
+        );
+        let err = resolve_scripts(&mut config, &ScriptBaseDir::Unconfigured).unwrap_err();
+        assert!(matches!(err, ScriptResolveError::LibraryEngine { name, .. } if name == "paging"));
+    }
+
+    #[test]
+    fn nested_include_is_rejected() {
+        let mut config = with_libraries(
+            vec![stub_with_script(script(
+                Some("// @include a\nx"),
+                None,
+                None,
+            ))],
+            &[
+                ("a", script(Some("// @include b\nfn a() {}"), None, None)),
+                ("b", script(Some("fn b() {}"), None, None)),
+            ],
+        );
+        let err = resolve_scripts(&mut config, &ScriptBaseDir::Unconfigured).unwrap_err();
+        assert!(matches!(err, ScriptResolveError::NestedInclude(name) if name == "a"));
+    }
+
+    #[test]
+    fn include_only_reads_the_leading_comment_block() {
+        let code = "// header\nlet x = 1;\n// @include auth\n";
+        assert!(take_includes(code).is_none());
+        let (names, rest) = take_includes("// @include a, b\n// @include a\n// keep\nx").unwrap();
+        assert_eq!(names, ["a", "b"]);
+        assert_eq!(rest, "// keep\nx");
+    }
 }
@@ -0,0 +1,188 @@
+//! `_rift.stream`: serving a response body as a timed sequence of chunks.
+//!
+//! The handler builds every response with a whole [`Full`] body, so decorators and the in-process
+//! gateway see the complete body as usual. A streamed response additionally carries a
+//! [`ChunkPlan`] extension — the same hand-off `_rift.fault.tcp` uses for [`TcpFaultKind`] — and
+//! the imposter's serve loop turns it into a [`ChunkedBody`] that writes each chunk after its
+//! delay. Without a plan the `Full` body is served unchanged, `Content-Length` and all.
+//!
+//! [`TcpFaultKind`]: super::fault_io::TcpFaultKind
+
+use super::types::RiftStreamConfig;
+use bytes::Bytes;
+use futures::FutureExt;
+use http_body_util::{BodyExt, Either, Full};
+use hyper::Response;
+use hyper::body::{Body, Frame, SizeHint};
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// Body of an imposter response as written to the connection.
+pub(crate) type ServedBody = Either<Full<Bytes>, ChunkedBody>;
+
+/// Chunk boundaries and delays for a response's body, attached as a response extension.
+#[derive(Debug, Clone)]
+pub(crate) struct ChunkPlan(Vec<(usize, Duration)>);
+
+impl ChunkPlan {
+    pub(crate) fn new(config: &RiftStreamConfig) -> Self {
+        Self(
+            config
+                .chunks
+                .iter()
+                .map(|chunk| (chunk.size, Duration::from_millis(chunk.delay_ms)))
+                .collect(),
+        )
+    }
+}
+
+/// Serve `response` as its [`ChunkPlan`] says, or whole when it has none.
+pub(crate) fn into_served(response: Response<Full<Bytes>>) -> Response<ServedBody> {
+    let (mut parts, body) = response.into_parts();
+    let Some(plan) = parts.extensions.remove::<ChunkPlan>() else {
+        return Response::from_parts(parts, Either::Left(body));
+    };
+    // A `Full` body yields its single frame immediately.
+    let bytes = body
+        .collect()
+        .now_or_never()
+        .map(|collected| collected.unwrap_or_else(|e| match e {}).to_bytes())
+        .unwrap_or_default();
+    // The length is only known chunk by chunk now; let hyper frame the body itself.
+    parts.headers.remove(hyper::header::CONTENT_LENGTH);
+    Response::from_parts(parts, Either::Right(ChunkedBody::new(bytes, &plan)))
+}
+
+/// A body written chunk by chunk, each after its delay.
+pub(crate) struct ChunkedBody {
+    chunks: VecDeque<(Duration, Bytes)>,
+    delay: Option<Pin<Box<tokio::time::Sleep>>>,
+}
+
+impl ChunkedBody {
+    /// Split `bytes` along `plan`. The last chunk takes whatever the sizes leave over; chunks
+    /// past the end of `bytes` are dropped.
+    fn new(mut bytes: Bytes, plan: &ChunkPlan) -> Self {
+        let mut chunks = VecDeque::with_capacity(plan.0.len());
+        for (index, &(size, delay)) in plan.0.iter().enumerate() {
+            if bytes.is_empty() {
+                break;
+            }
+            let take = if index + 1 == plan.0.len() {
+                bytes.len()
+            } else {
+                size.min(bytes.len())
+            };
+            chunks.push_back((delay, bytes.split_to(take)));
+        }
+        if !bytes.is_empty() {
+            chunks.push_back((Duration::ZERO, bytes));
+        }
+        Self {
+            chunks,
+            delay: None,
+        }
+    }
+}
+
+impl Body for ChunkedBody {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, Infallible>>> {
+        let Some(&(delay, _)) = self.chunks.front() else {
+            return Poll::Ready(None);
+        };
+        if !delay.is_zero() {
+            let sleep = self
+                .delay
+                .get_or_insert_with(|| Box::pin(tokio::time::sleep(delay)));
+            if sleep.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            self.delay = None;
+        }
+        Poll::Ready(
+            self.chunks
+                .pop_front()
+                .map(|(_, chunk)| Ok(Frame::data(chunk))),
+        )
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        // Deliberately inexact: an exact hint would have hyper send a `Content-Length` and could
+        // let it coalesce the chunks.
+        let mut hint = SizeHint::new();
+        hint.set_lower(
+            self.chunks
+                .iter()
+                .map(|(_, chunk)| chunk.len() as u64)
+                .sum(),
+        );
+        hint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::imposter::types::RiftStreamChunk;
+
+    fn plan(chunks: &[(usize, u64)]) -> ChunkPlan {
+        ChunkPlan::new(&RiftStreamConfig {
+            chunks: chunks
+                .iter()
+                .map(|&(size, delay_ms)| RiftStreamChunk { size, delay_ms })
+                .collect(),
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn chunks_are_written_after_their_delays() {
+        let mut response = Response::new(Full::new(Bytes::from_static(b"data: 1\n\ndata: 2\n\n")));
+        response
+            .headers_mut()
+            .insert(hyper::header::CONTENT_LENGTH, "18".parse().unwrap());
+        response.extensions_mut().insert(plan(&[(9, 0), (9, 250)]));
+
+        let mut body = into_served(response);
+        assert!(body.headers().get(hyper::header::CONTENT_LENGTH).is_none());
+        let started = tokio::time::Instant::now();
+        let first = body.body_mut().frame().await.unwrap().unwrap();
+        assert_eq!(first.into_data().unwrap(), "data: 1\n\n");
+        assert_eq!(started.elapsed(), Duration::ZERO);
+        let second = body.body_mut().frame().await.unwrap().unwrap();
+        assert_eq!(second.into_data().unwrap(), "data: 2\n\n");
+        assert_eq!(started.elapsed(), Duration::from_millis(250));
+        assert!(body.body_mut().frame().await.is_none());
+    }
+
+    #[test]
+    fn a_body_that_no_longer_fits_the_plan_is_still_served_whole() {
+        let longer = ChunkedBody::new(Bytes::from_static(b"abcdefgh"), &plan(&[(2, 0), (2, 5)]));
+        let sizes: Vec<usize> = longer.chunks.iter().map(|(_, c)| c.len()).collect();
+        assert_eq!(sizes, [2, 6]);
+
+        let shorter =
+            ChunkedBody::new(Bytes::from_static(b"abc"), &plan(&[(2, 0), (2, 5), (2, 5)]));
+        let sizes: Vec<usize> = shorter.chunks.iter().map(|(_, c)| c.len()).collect();
+        assert_eq!(sizes, [2, 1]);
+    }
+
+    #[test]
+    fn a_response_without_a_plan_keeps_its_full_body() {
+        let served = into_served(Response::new(Full::new(Bytes::from_static(b"whole"))));
+        assert!(matches!(served.body(), Either::Left(_)));
+        assert_eq!(served.body().size_hint().exact(), Some(5));
+    }
+}
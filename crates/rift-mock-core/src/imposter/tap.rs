@@ -0,0 +1,335 @@
+//! Proxy traffic tap (`--proxy-tap`): every proxied request/response pair, one NDJSON line each.
+//!
+//! The tap is independent of recording — it sees `proxyTransparent`, `proxyShadow`, replayed
+//! `proxyOnce` hits, and `defaultForward` alike, and writes whether or not a stub is generated.
+//! Each line carries the request, the response (or the upstream error), the proxy target and
+//! mode, the wall-clock time the proxy step took, and the stub that routed the request.
+//!
+//! Lines are handed to a writer thread through a bounded queue so a slow disk or a blocked stdout
+//! pipe never stalls the request path: when the queue is full the line is dropped and counted
+//! (see [`ProxyTap::dropped`]) instead.
+
+use super::types::{ProxyResponse, ResponseMode};
+use base64::Engine;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::{BufWriter, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{Receiver, SyncSender, TrySendError, sync_channel};
+use tracing::warn;
+
+/// Lines queued for the writer thread before new ones are dropped.
+const TAP_QUEUE_DEPTH: usize = 1024;
+
+/// Destination of proxied traffic, shared by every imposter of a manager.
+pub struct ProxyTap {
+    lines: SyncSender<String>,
+    dropped: AtomicU64,
+}
+
+impl ProxyTap {
+    /// Open a tap appending to the file at `dest` (created if missing), or writing to stdout when
+    /// `dest` is `-`.
+    pub fn open(dest: &str) -> std::io::Result<Self> {
+        let sink: Box<dyn Write + Send> = if dest == "-" {
+            Box::new(std::io::stdout())
+        } else {
+            Box::new(
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(dest)?,
+            )
+        };
+        Ok(Self::to_writer(sink))
+    }
+
+    /// A tap writing to `sink`.
+    pub fn to_writer(sink: Box<dyn Write + Send>) -> Self {
+        let (lines, queue) = sync_channel(TAP_QUEUE_DEPTH);
+        std::thread::Builder::new()
+            .name("rift-proxy-tap".to_string())
+            .spawn(move || drain(queue, sink))
+            .expect("spawning the proxy tap writer thread");
+        Self {
+            lines,
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Lines dropped because the writer could not keep up.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Queue `entry` as one line.
+    pub(crate) fn record(&self, entry: &TapEntry<'_>) {
+        let line = match serde_json::to_string(entry) {
+            Ok(line) => line,
+            Err(e) => {
+                warn!("proxy tap: failed to serialize entry: {e}");
+                return;
+            }
+        };
+        match self.lines.try_send(line) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                // Warn on the first drop of every thousand rather than once per request.
+                if self
+                    .dropped
+                    .fetch_add(1, Ordering::Relaxed)
+                    .is_multiple_of(1000)
+                {
+                    warn!("proxy tap: writer is behind, dropping lines");
+                }
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+/// Writer thread: write each queued line, flushing whenever the queue runs dry so a reader
+/// tailing the file sees lines promptly. Ends when the tap is dropped or the sink fails.
+fn drain(queue: Receiver<String>, sink: Box<dyn Write + Send>) {
+    let mut out = BufWriter::new(sink);
+    while let Ok(line) = queue.recv() {
+        let mut result = writeln!(out, "{line}");
+        while result.is_ok()
+            && let Ok(line) = queue.try_recv()
+        {
+            result = writeln!(out, "{line}");
+        }
+        if let Err(e) = result.and_then(|()| out.flush()) {
+            warn!("proxy tap: write failed, tap disabled: {e}");
+            return;
+        }
+    }
+}
+
+/// One tapped exchange.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TapEntry<'a> {
+    pub timestamp: String,
+    pub port: u16,
+    pub request: TapRequest<'a>,
+    /// `None` when the upstream call failed; `error` says why.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<TapResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub proxy: TapProxy<'a>,
+    /// Wall-clock time of the proxy step, upstream round trip included.
+    pub latency_ms: u64,
+    /// The stub whose proxy response handled the request; `None` for `defaultForward`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stub: Option<TapStub<'a>>,
+}
+
+/// Upstream outcome of a proxy step: `(status, headers, body, recorded latency)` or the error.
+pub(crate) type ProxyOutcome = anyhow::Result<(u16, Vec<(String, String)>, Vec<u8>, Option<u64>)>;
+
+impl<'a> TapEntry<'a> {
+    /// The entry for one proxy step that took `elapsed` and ended in `outcome`.
+    pub(crate) fn new(
+        port: u16,
+        request: TapRequest<'a>,
+        proxy: TapProxy<'a>,
+        stub: Option<TapStub<'a>>,
+        elapsed: std::time::Duration,
+        outcome: &ProxyOutcome,
+    ) -> Self {
+        let (response, error) = match outcome {
+            Ok((status, headers, body, _)) => {
+                (Some(TapResponse::new(*status, headers, body)), None)
+            }
+            Err(e) => (None, Some(format!("{e:#}"))),
+        };
+        Self {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            port,
+            request,
+            response,
+            error,
+            proxy,
+            latency_ms: elapsed.as_millis() as u64,
+            stub,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TapRequest<'a> {
+    pub method: &'a str,
+    pub path: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pub query: &'a str,
+    #[serde(with = "super::types::multi_value_headers")]
+    pub headers: HashMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<&'a str>,
+    #[serde(rename = "_mode", skip_serializing_if = "is_text_mode")]
+    pub mode: ResponseMode,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TapResponse {
+    pub status_code: u16,
+    #[serde(with = "super::types::multi_value_headers")]
+    pub headers: HashMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(rename = "_mode", skip_serializing_if = "is_text_mode")]
+    pub mode: ResponseMode,
+}
+
+impl TapResponse {
+    /// The tapped form of an upstream response: repeated headers grouped, a UTF-8 body kept as
+    /// text and anything else base64-encoded.
+    pub(crate) fn new(status_code: u16, headers: &[(String, String)], body: &[u8]) -> Self {
+        let mut grouped: HashMap<String, Vec<String>> = HashMap::new();
+        for (name, value) in headers {
+            grouped.entry(name.clone()).or_default().push(value.clone());
+        }
+        let (body, mode) = if body.is_empty() {
+            (None, ResponseMode::Text)
+        } else {
+            match std::str::from_utf8(body) {
+                Ok(text) => (Some(text.to_string()), ResponseMode::Text),
+                Err(_) => (
+                    Some(base64::engine::general_purpose::STANDARD.encode(body)),
+                    ResponseMode::Binary,
+                ),
+            }
+        };
+        Self {
+            status_code,
+            headers: grouped,
+            body,
+            mode,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TapProxy<'a> {
+    pub to: &'a str,
+    /// The proxy's configured mode, `proxyTransparent` when unset.
+    pub mode: &'a str,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub default_forward: bool,
+}
+
+impl<'a> TapProxy<'a> {
+    pub(crate) fn new(proxy: &'a ProxyResponse, default_forward: bool) -> Self {
+        Self {
+            to: &proxy.to,
+            mode: if proxy.mode.is_empty() {
+                "proxyTransparent"
+            } else {
+                &proxy.mode
+            },
+            default_forward,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TapStub<'a> {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<&'a str>,
+}
+
+fn is_text_mode(mode: &ResponseMode) -> bool {
+    matches!(mode, ResponseMode::Text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn each_exchange_is_one_json_line() {
+        let sink = Shared::default();
+        let tap = ProxyTap::to_writer(Box::new(sink.clone()));
+        for (index, body) in [(0, &b"{\"ok\":true}"[..]), (1, &[0xff, 0x00][..])] {
+            tap.record(&TapEntry {
+                timestamp: "2026-01-01T00:00:00Z".to_string(),
+                port: 4545,
+                request: TapRequest {
+                    method: "GET",
+                    path: "/orders",
+                    query: "page=2",
+                    headers: HashMap::from([("Accept".to_string(), vec!["*/*".to_string()])]),
+                    body: None,
+                    mode: ResponseMode::Text,
+                },
+                response: Some(TapResponse::new(
+                    200,
+                    &[
+                        ("set-cookie".to_string(), "a=1".to_string()),
+                        ("set-cookie".to_string(), "b=2".to_string()),
+                    ],
+                    body,
+                )),
+                error: None,
+                proxy: TapProxy {
+                    to: "http://upstream",
+                    mode: "proxyTransparent",
+                    default_forward: false,
+                },
+                latency_ms: 12,
+                stub: Some(TapStub {
+                    index,
+                    id: Some("orders"),
+                }),
+            });
+        }
+        drop(tap);
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let lines = loop {
+            let text = String::from_utf8(sink.0.lock().unwrap().clone()).unwrap();
+            if text.lines().count() == 2 || std::time::Instant::now() > deadline {
+                break text;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        };
+        let lines: Vec<serde_json::Value> = lines
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["request"]["query"], "page=2");
+        assert_eq!(lines[0]["response"]["headers"]["set-cookie"][1], "b=2");
+        assert_eq!(lines[0]["response"]["body"], "{\"ok\":true}");
+        assert!(lines[0]["response"].get("_mode").is_none());
+        assert_eq!(lines[0]["latencyMs"], 12);
+        assert_eq!(lines[0]["stub"]["id"], "orders");
+        assert!(lines[0]["proxy"].get("defaultForward").is_none());
+        assert_eq!(lines[1]["response"]["_mode"], "binary");
+        assert_eq!(lines[1]["response"]["body"], "/wA=");
+        assert_eq!(lines[1]["stub"]["index"], 1);
+    }
+}
@@ -0,0 +1,227 @@
+//! Parameterized imposter templates: a stored imposter definition whose string values may contain
+//! `${var}` placeholders, filled in with per-instance variables when an imposter is created from it.
+//!
+//! Only placeholders naming a declared variable (listed in `variables` or given a default in
+//! `defaults`) are substituted. Anything else, such as the `${request.path}` response templating
+//! that runs per request, passes through untouched. A string that is exactly one placeholder
+//! takes the variable's JSON value, so `"port": "${port}"` renders as a number; a placeholder
+//! inside a longer string is replaced by the value's text.
+
+use super::types::{ImposterConfig, ImposterError};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+
+/// A named imposter definition with `${var}` placeholders.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ImposterTemplate {
+    pub name: String,
+    /// Variables every instantiation must supply.
+    #[serde(default)]
+    pub variables: Vec<String>,
+    /// Variables with a value used when the instantiation omits them.
+    #[serde(default)]
+    pub defaults: Map<String, Value>,
+    /// The imposter definition, in the same shape as `POST /imposters`.
+    pub imposter: Value,
+}
+
+impl ImposterTemplate {
+    /// Reject a template no instantiation could succeed with: an unusable name or a definition
+    /// that is not a JSON object.
+    pub fn validate(&self) -> Result<(), ImposterError> {
+        if self.name.is_empty() || self.name.contains('/') {
+            return Err(ImposterError::InvalidTemplate(format!(
+                "template name '{}' must be non-empty and contain no '/'",
+                self.name
+            )));
+        }
+        if !self.imposter.is_object() {
+            return Err(ImposterError::InvalidTemplate(
+                "`imposter` must be a JSON object".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Every declared variable name, required or defaulted.
+    fn declared(&self) -> BTreeSet<&str> {
+        self.variables
+            .iter()
+            .map(String::as_str)
+            .chain(self.defaults.keys().map(String::as_str))
+            .collect()
+    }
+
+    /// The imposter config with every declared placeholder replaced. `supplied` overrides
+    /// `defaults`; a missing required variable or an undeclared supplied one is an error, so a
+    /// typo fails loudly instead of leaving a literal `${...}` in the imposter.
+    pub fn render(&self, supplied: &Map<String, Value>) -> Result<ImposterConfig, ImposterError> {
+        let declared = self.declared();
+        let unknown: Vec<&str> = supplied
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !declared.contains(name))
+            .collect();
+        if !unknown.is_empty() {
+            return Err(ImposterError::InvalidTemplate(format!(
+                "template '{}' declares no variable {}",
+                self.name,
+                unknown.join(", ")
+            )));
+        }
+        let missing: Vec<&str> = self
+            .variables
+            .iter()
+            .map(String::as_str)
+            .filter(|name| !supplied.contains_key(*name) && !self.defaults.contains_key(*name))
+            .collect();
+        if !missing.is_empty() {
+            return Err(ImposterError::InvalidTemplate(format!(
+                "template '{}' needs variable {}",
+                self.name,
+                missing.join(", ")
+            )));
+        }
+
+        let mut values = self.defaults.clone();
+        values.extend(supplied.iter().map(|(k, v)| (k.clone(), v.clone())));
+        let rendered = substitute(&self.imposter, &values);
+        serde_json::from_value(rendered).map_err(|e| {
+            ImposterError::InvalidTemplate(format!(
+                "template '{}' renders an invalid imposter: {e}",
+                self.name
+            ))
+        })
+    }
+}
+
+fn substitute(value: &Value, values: &Map<String, Value>) -> Value {
+    match value {
+        Value::String(s) => substitute_str(s, values),
+        Value::Array(items) => Value::Array(items.iter().map(|v| substitute(v, values)).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), substitute(v, values)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn substitute_str(s: &str, values: &Map<String, Value>) -> Value {
+    // A string that is exactly one placeholder keeps the variable's JSON type.
+    if let Some(name) = s.strip_prefix("${").and_then(|r| r.strip_suffix('}'))
+        && let Some(value) = values.get(name)
+    {
+        return value.clone();
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + len];
+        out.push_str(&rest[..start]);
+        match values.get(name) {
+            Some(Value::String(text)) => out.push_str(text),
+            Some(value) => out.push_str(&value.to_string()),
+            None => out.push_str(&rest[start..=start + len]),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+    Value::String(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn auth_template() -> ImposterTemplate {
+        serde_json::from_value(json!({
+            "name": "auth-service",
+            "variables": ["port", "tenantId"],
+            "defaults": {"region": "eu"},
+            "imposter": {
+                "port": "${port}",
+                "protocol": "http",
+                "name": "auth ${tenantId} (${region})",
+                "stubs": [{
+                    "predicates": [{"equals": {"path": "/tenants/${tenantId}/token"}}],
+                    "responses": [{"is": {"body": "${request.path} on ${port}"}}]
+                }]
+            }
+        }))
+        .unwrap()
+    }
+
+    fn vars(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn declared_placeholders_are_filled_and_others_left_alone() {
+        let config = auth_template()
+            .render(&vars(json!({"port": 4600, "tenantId": "acme"})))
+            .unwrap();
+        assert_eq!(
+            config.port,
+            Some(4600),
+            "a whole-string placeholder keeps its type"
+        );
+        assert_eq!(config.name.as_deref(), Some("auth acme (eu)"));
+        let stub = serde_json::to_value(&config.stubs[0]).unwrap();
+        assert_eq!(
+            stub["predicates"][0]["equals"]["path"],
+            "/tenants/acme/token"
+        );
+        assert_eq!(
+            stub["responses"][0]["is"]["body"], "${request.path} on 4600",
+            "response templating is not a template variable"
+        );
+    }
+
+    #[test]
+    fn supplied_values_override_defaults() {
+        let config = auth_template()
+            .render(&vars(
+                json!({"port": 4600, "tenantId": "acme", "region": "us"}),
+            ))
+            .unwrap();
+        assert_eq!(config.name.as_deref(), Some("auth acme (us)"));
+    }
+
+    #[test]
+    fn missing_and_undeclared_variables_are_rejected() {
+        let template = auth_template();
+        let missing = template.render(&vars(json!({"port": 4600}))).unwrap_err();
+        assert!(
+            missing.to_string().contains("needs variable tenantId"),
+            "{missing}"
+        );
+        let unknown = template
+            .render(&vars(
+                json!({"port": 4600, "tenantId": "acme", "tenant": "x"}),
+            ))
+            .unwrap_err();
+        assert!(
+            unknown.to_string().contains("no variable tenant"),
+            "{unknown}"
+        );
+    }
+
+    #[test]
+    fn a_template_must_name_an_object_definition() {
+        let mut template = auth_template();
+        assert!(template.validate().is_ok());
+        template.name = "a/b".to_string();
+        assert!(template.validate().is_err());
+        template.name = "ok".to_string();
+        template.imposter = json!([]);
+        assert!(template.validate().is_err());
+    }
+}
@@ -453,6 +453,37 @@ fn test_behaviors_array_merged_to_object() {
     }
 }
 
+#[test]
+fn test_ordered_underscore_behaviors_round_trip_as_array() {
+    let json = r#"{
+        "_behaviors": [{"decorate": "x"}, {"wait": 10}, {"repeat": 3}],
+        "is": {"statusCode": 200}
+    }"#;
+
+    let response: StubResponse = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        crate::behaviors::HasRepeatBehavior::get_repeat(&response),
+        Some(3)
+    );
+    let out = serde_json::to_value(&response).unwrap();
+    assert!(
+        out.get("behaviors").is_none(),
+        "order must not be flattened"
+    );
+    assert_eq!(
+        out["_behaviors"],
+        serde_json::json!([{"decorate": "x"}, {"wait": 10}, {"repeat": 3}])
+    );
+    let reparsed: StubResponse = serde_json::from_value(out).unwrap();
+    let StubResponse::Is {
+        behaviors_parsed, ..
+    } = reparsed
+    else {
+        panic!("Expected Is response");
+    };
+    assert_eq!(behaviors_parsed.unwrap().ordered.len(), 3);
+}
+
 #[test]
 fn test_proxy_only_response() {
     // When only proxy is present (not null), it should parse as Proxy variant
@@ -2808,6 +2839,72 @@ async fn test_cors_disabled_no_cors_headers() {
     let _ = manager.delete_imposter(port).await;
 }
 
+#[tokio::test]
+async fn test_cors_policy_answers_preflights_and_leaves_plain_options_to_stubs() {
+    let manager = ImposterManager::new();
+    let config: ImposterConfig = serde_json::from_value(serde_json::json!({
+        "protocol": "http",
+        "cors": {
+            "allowOrigins": ["https://app.example"],
+            "allowMethods": ["GET", "PUT"],
+            "exposeHeaders": ["x-request-id"]
+        },
+        "stubs": [{
+            "predicates": [{"equals": {"method": "OPTIONS"}}],
+            "responses": [{"is": {"statusCode": 204}}]
+        }]
+    }))
+    .unwrap();
+    let port = manager
+        .create_imposter(config)
+        .await
+        .expect("failed to create CORS policy imposter");
+    let client = reqwest::Client::new();
+    let url = format!("http://127.0.0.1:{port}/orders");
+
+    let preflight = client
+        .request(reqwest::Method::OPTIONS, &url)
+        .header("origin", "https://app.example")
+        .header("access-control-request-method", "PUT")
+        .send()
+        .await
+        .expect("preflight failed");
+    assert_eq!(preflight.status(), 200);
+    assert_cors_header(
+        &preflight,
+        "access-control-allow-origin",
+        Some("https://app.example"),
+    );
+    assert_cors_header(&preflight, "access-control-allow-methods", Some("GET, PUT"));
+
+    // Without Access-Control-Request-Method it is not a preflight, so the stub answers it.
+    let options = client
+        .request(reqwest::Method::OPTIONS, &url)
+        .send()
+        .await
+        .expect("OPTIONS failed");
+    assert_eq!(options.status(), 204);
+
+    let get = client
+        .get(&url)
+        .header("origin", "https://app.example")
+        .send()
+        .await
+        .expect("GET failed");
+    assert_cors_header(&get, "access-control-expose-headers", Some("x-request-id"));
+    assert_cors_header(&get, "access-control-allow-methods", None);
+
+    let other = client
+        .get(&url)
+        .header("origin", "https://elsewhere.example")
+        .send()
+        .await
+        .expect("GET failed");
+    assert_cors_header(&other, "access-control-allow-origin", None);
+
+    let _ = manager.delete_imposter(port).await;
+}
+
 // Issue #213: the `lookup` behavior must apply to direct imposter `is` responses,
 // not only to proxied responses. Tokens `${into}[column]` should be replaced from
 // the CSV data source.
@@ -4188,6 +4285,48 @@ async fn test_path_params_script_js() {
     );
 }
 
+// A Rhai `_rift.script` returning a `#{ status, headers, body }` map is served as that response,
+// headers and JSON content type included, the same as the equivalent `http(...)` constructor.
+#[tokio::test]
+async fn test_rhai_script_response_map_is_served() {
+    let script = r#"fn respond(ctx) {
+        #{ status: 201, headers: #{ "Location": "/orders/7" }, body: #{ id: 7 } }
+    }"#;
+    let config: ImposterConfig = serde_json::from_value(serde_json::json!({
+        "port": 19747,
+        "protocol": "http",
+        "stubs": [{ "responses": [{ "_rift": { "script": { "engine": "rhai", "code": script } } }] }]
+    }))
+    .expect("config");
+
+    let manager = ImposterManager::new();
+    manager
+        .create_imposter(config)
+        .await
+        .expect("create imposter");
+    let resp = reqwest::Client::new()
+        .post("http://127.0.0.1:19747/orders")
+        .send()
+        .await
+        .expect("POST failed");
+    let status = resp.status().as_u16();
+    let location = resp.headers().get("location").cloned();
+    let content_type = resp.headers().get("content-type").cloned();
+    let body = resp.text().await.expect("body");
+    let _ = manager.delete_imposter(19747).await;
+
+    assert_eq!(status, 201);
+    assert_eq!(
+        location.as_ref().and_then(|v| v.to_str().ok()),
+        Some("/orders/7")
+    );
+    assert_eq!(
+        content_type.as_ref().and_then(|v| v.to_str().ok()),
+        Some("application/json")
+    );
+    assert_eq!(body, r#"{"id":7}"#);
+}
+
 #[tokio::test]
 async fn test_path_params_absent_pattern_is_empty() {
     // No routePattern → pathParams stays empty and nothing errors (unchanged default). The `[]`
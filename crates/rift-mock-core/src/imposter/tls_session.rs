@@ -0,0 +1,86 @@
+//! TLS parameters of an HTTPS imposter connection (`RecordedRequest.tls`).
+//!
+//! Captured once per connection, right after the handshake, and handed to every request on it as
+//! a request extension: the handler records it with the request and the `tls` predicate field
+//! matches against it.
+
+use super::types::{ClientCertificate, RecordedTls};
+use rustls::ServerConnection;
+use rustls::pki_types::CertificateDer;
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+impl RecordedTls {
+    /// What `session` negotiated, including the client's certificate when it sent one.
+    pub(crate) fn from_session(session: &ServerConnection) -> Self {
+        Self {
+            sni: session.server_name().map(str::to_string),
+            version: session
+                .protocol_version()
+                .map(version_name)
+                .unwrap_or_default(),
+            cipher: session
+                .negotiated_cipher_suite()
+                .map(|suite| format!("{:?}", suite.suite()))
+                .unwrap_or_default(),
+            client_cert: session
+                .peer_certificates()
+                .and_then(|chain| chain.first())
+                .and_then(ClientCertificate::parse),
+        }
+    }
+}
+
+/// The OpenSSL-style name of a protocol version, as Node and `openssl s_client` print it.
+fn version_name(version: rustls::ProtocolVersion) -> String {
+    match version {
+        rustls::ProtocolVersion::TLSv1_2 => "TLSv1.2".to_string(),
+        rustls::ProtocolVersion::TLSv1_3 => "TLSv1.3".to_string(),
+        other => format!("{other:?}"),
+    }
+}
+
+impl ClientCertificate {
+    /// The identifying fields of a DER certificate; `None` when it does not parse.
+    fn parse(der: &CertificateDer<'_>) -> Option<Self> {
+        let (_, cert) = X509Certificate::from_der(der.as_ref()).ok()?;
+        Some(Self {
+            subject: cert.subject().to_string(),
+            common_name: cert
+                .subject()
+                .iter_common_name()
+                .next()
+                .and_then(|cn| cn.as_str().ok())
+                .map(str::to_string),
+            issuer: cert.issuer().to_string(),
+            serial_number: cert.raw_serial_as_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_certificate_names_come_from_the_subject_and_issuer() {
+        let mut params =
+            rcgen::CertificateParams::new(vec!["tenant-a.example".to_string()]).expect("params");
+        params
+            .distinguished_name
+            .push(rcgen::DnType::CommonName, "tenant-a");
+        params
+            .distinguished_name
+            .push(rcgen::DnType::OrganizationName, "Acme");
+        let key = rcgen::KeyPair::generate().expect("key");
+        let cert = params.self_signed(&key).expect("cert");
+
+        let parsed = ClientCertificate::parse(cert.der()).expect("parses");
+        assert_eq!(parsed.common_name.as_deref(), Some("tenant-a"));
+        assert!(parsed.subject.contains("CN=tenant-a"), "{}", parsed.subject);
+        assert!(parsed.subject.contains("O=Acme"), "{}", parsed.subject);
+        assert_eq!(parsed.issuer, parsed.subject, "self-signed");
+        assert!(!parsed.serial_number.is_empty());
+
+        assert!(ClientCertificate::parse(&CertificateDer::from(vec![0u8; 4])).is_none());
+    }
+}
@@ -3,7 +3,7 @@
 //! This module contains all the structs, enums, and type aliases used by the imposter system.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Serde for multi-value headers (issue #238). Accepts the Mountebank-style `"k": "v"` *and*
 /// `"k": ["v1", "v2"]` on the wire; serializes a single value back as a plain string and multiple
@@ -97,6 +97,42 @@ pub struct RecordedRequest {
     #[serde(rename = "_mode", default, skip_serializing_if = "is_text_mode")]
     pub mode: ResponseMode,
     pub timestamp: String,
+    /// TLS parameters of the connection the request arrived on; `None` for plain HTTP.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<RecordedTls>,
+}
+
+/// What an HTTPS imposter negotiated with the client, recorded with every request on the
+/// connection and matched by the `tls` predicate field.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedTls {
+    /// Server name the client asked for (SNI); `None` when it sent none, as for an IP address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sni: Option<String>,
+    /// Negotiated protocol version, `TLSv1.2` or `TLSv1.3`.
+    pub version: String,
+    /// Negotiated cipher suite, e.g. `TLS13_AES_256_GCM_SHA384`.
+    pub cipher: String,
+    /// The certificate the client presented; only asked for with `mutualAuth`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_cert: Option<ClientCertificate>,
+}
+
+/// A client certificate presented during a `mutualAuth` handshake. It is recorded as sent, not
+/// validated: an imposter stands in for a server that asks for one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientCertificate {
+    /// Subject distinguished name, e.g. `CN=tenant-a, O=Acme`.
+    pub subject: String,
+    /// The subject's first common name (CN).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub common_name: Option<String>,
+    /// Issuer distinguished name.
+    pub issuer: String,
+    /// Serial number, colon-separated hex.
+    pub serial_number: String,
 }
 
 // ============================================================================
@@ -355,6 +391,13 @@ fn inject_wait_behavior(response: StubResponse, wait_val: serde_json::Value) ->
                     obj.entry("wait").or_insert(wait_val);
                     serde_json::Value::Object(obj)
                 }
+                // Ordered form: the stub-level delay runs first, ahead of the listed behaviors.
+                Some(serde_json::Value::Array(mut entries)) => {
+                    if !entries.iter().any(|e| e.get("wait").is_some()) {
+                        entries.insert(0, serde_json::json!({ "wait": wait_val }));
+                    }
+                    serde_json::Value::Array(entries)
+                }
                 Some(other) => other,
                 None => serde_json::json!({ "wait": wait_val }),
             });
@@ -398,6 +441,12 @@ pub enum StubResponse {
         /// the two fields above — not serde-driven.
         #[serde(skip)]
         prepared: Option<std::sync::Arc<crate::imposter::response::PreparedResponse>>,
+        /// `behaviors_parsed` merged with the serving imposter's `defaultBehaviors`, kept with
+        /// the defaults it was merged from: computed by the first request and reused by every
+        /// later one (see [`StubResponse::behaviors_with_defaults`]). Derived cache, like the
+        /// fields above — not serde-driven.
+        #[serde(skip)]
+        merged_behaviors: MergedBehaviors,
     },
     Proxy {
         proxy: ProxyResponse,
@@ -431,7 +480,7 @@ impl StubResponse {
         let behaviors_parsed = behaviors
             .as_ref()
             .and_then(|v| {
-                match serde_json::from_value::<crate::behaviors::ResponseBehaviors>(v.clone()) {
+                match crate::behaviors::ResponseBehaviors::from_json(v) {
                     Ok(parsed) => Some(parsed),
                     Err(e) => {
                         // Deliberately does not claim the response will serve: construction runs
@@ -469,10 +518,50 @@ impl StubResponse {
             behaviors_parsed,
             rendered_body,
             prepared,
+            merged_behaviors: MergedBehaviors::default(),
+        }
+    }
+
+    /// The behaviors to apply when this response is served by an imposter with `defaults` as its
+    /// `defaultBehaviors`: the defaults alone when the response has no `_behaviors` of its own,
+    /// else its own with the defaults filled in. The merge runs once per response — a clone
+    /// served under different defaults merges afresh rather than reusing the cached result.
+    pub(crate) fn behaviors_with_defaults(
+        &self,
+        defaults: &std::sync::Arc<crate::behaviors::ResponseBehaviors>,
+    ) -> std::sync::Arc<crate::behaviors::ResponseBehaviors> {
+        let StubResponse::Is {
+            behaviors_parsed: Some(own),
+            merged_behaviors,
+            ..
+        } = self
+        else {
+            return defaults.clone();
+        };
+        let (merged_from, merged) = merged_behaviors.0.get_or_init(|| {
+            (
+                defaults.clone(),
+                std::sync::Arc::new(own.with_defaults(defaults)),
+            )
+        });
+        if std::sync::Arc::ptr_eq(merged_from, defaults) {
+            merged.clone()
+        } else {
+            std::sync::Arc::new(own.with_defaults(defaults))
         }
     }
 }
 
+/// Cache slot for [`StubResponse::behaviors_with_defaults`]: the imposter defaults a response's
+/// behaviors were merged with, and the result.
+#[derive(Debug, Clone, Default)]
+pub struct MergedBehaviors(
+    std::sync::OnceLock<(
+        std::sync::Arc<crate::behaviors::ResponseBehaviors>,
+        std::sync::Arc<crate::behaviors::ResponseBehaviors>,
+    )>,
+);
+
 /// Raw deserialization type that handles multiple JSON formats for stub responses
 /// Supports:
 /// - Standard Mountebank format with `is`, `proxy`, `inject`, or `fault` fields
@@ -523,6 +612,10 @@ pub(crate) struct StubResponseOut {
     /// Placed first to match Mountebank output ordering
     #[serde(skip_serializing_if = "Option::is_none")]
     pub behaviors: Option<Vec<serde_json::Value>>,
+    /// An ordered `_behaviors` array is written back under `_behaviors`, not `behaviors`: the
+    /// `behaviors` array is read back as an unordered (merged) set, which would lose the order.
+    #[serde(rename = "_behaviors", skip_serializing_if = "Option::is_none")]
+    pub ordered_behaviors: Option<Vec<serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is: Option<IsResponseOut>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -549,6 +642,9 @@ pub(crate) struct IsResponseRaw {
     pub headers: HashMap<String, Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<serde_json::Value>,
+    /// Body served from a file under the datadir (see [`IsResponse::body_file`])
+    #[serde(default)]
+    pub body_file: Option<String>,
     /// Response mode: "text" (default) or "binary" (body is base64-encoded)
     #[serde(rename = "_mode", default)]
     pub mode: ResponseMode,
@@ -565,6 +661,8 @@ pub(crate) struct IsResponseOut {
     pub headers: HashMap<String, Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_file: Option<String>,
     /// Response mode: "text" (default) or "binary" (body is base64-encoded)
     /// Skipped when text (default) as Mountebank doesn't output it for text mode
     #[serde(rename = "_mode", default, skip_serializing_if = "is_text_mode")]
@@ -634,6 +732,7 @@ impl From<StubResponseRaw> for StubResponse {
                     status_code: is_raw.status_code,
                     headers: is_raw.headers,
                     body: is_raw.body,
+                    body_file: is_raw.body_file,
                     mode: is_raw.mode,
                 },
                 behaviors,
@@ -659,6 +758,7 @@ impl From<StubResponseRaw> for StubResponse {
                     status_code: raw.status_code.unwrap_or_else(default_status_code),
                     headers: raw.headers,
                     body: raw.body,
+                    body_file: None,
                     mode: raw.mode,
                 },
                 behaviors,
@@ -671,6 +771,7 @@ impl From<StubResponseRaw> for StubResponse {
                     status_code: 200,
                     headers: HashMap::new(),
                     body: None,
+                    body_file: None,
                     mode: ResponseMode::Text,
                 },
                 None,
@@ -688,26 +789,37 @@ impl From<StubResponse> for StubResponseOut {
                 behaviors,
                 rift,
                 ..
-            } => StubResponseOut {
-                is: Some(IsResponseOut {
-                    status_code: is.status_code,
-                    headers: is.headers,
-                    body: is.body,
-                    mode: is.mode,
-                }),
-                proxy: None,
-                inject: None,
-                fault: None,
-                // Convert behaviors object to array format for Mountebank compatibility
-                behaviors: behaviors.and_then(behaviors_to_array),
-                rift,
-            },
+            } => {
+                let (behaviors, ordered_behaviors) = match behaviors {
+                    Some(serde_json::Value::Array(entries)) => {
+                        (None, Some(entries).filter(|e| !e.is_empty()))
+                    }
+                    other => (other.and_then(behaviors_to_array), None),
+                };
+                StubResponseOut {
+                    is: Some(IsResponseOut {
+                        status_code: is.status_code,
+                        headers: is.headers,
+                        body: is.body,
+                        body_file: is.body_file,
+                        mode: is.mode,
+                    }),
+                    proxy: None,
+                    inject: None,
+                    fault: None,
+                    // Convert behaviors object to array format for Mountebank compatibility
+                    behaviors,
+                    ordered_behaviors,
+                    rift,
+                }
+            }
             StubResponse::Proxy { proxy } => StubResponseOut {
                 is: None,
                 proxy: Some(proxy),
                 inject: None,
                 fault: None,
                 behaviors: None,
+                ordered_behaviors: None,
                 rift: None,
             },
             StubResponse::Inject { inject } => StubResponseOut {
@@ -716,6 +828,7 @@ impl From<StubResponse> for StubResponseOut {
                 inject: Some(inject),
                 fault: None,
                 behaviors: None,
+                ordered_behaviors: None,
                 rift: None,
             },
             StubResponse::Fault { fault } => StubResponseOut {
@@ -724,6 +837,7 @@ impl From<StubResponse> for StubResponseOut {
                 inject: None,
                 fault: Some(fault),
                 behaviors: None,
+                ordered_behaviors: None,
                 rift: None,
             },
             StubResponse::RiftScript { rift } => StubResponseOut {
@@ -732,6 +846,7 @@ impl From<StubResponse> for StubResponseOut {
                 inject: None,
                 fault: None,
                 behaviors: None,
+                ordered_behaviors: None,
                 rift: Some(rift),
             },
         }
@@ -816,6 +931,11 @@ pub struct IsResponse {
     pub headers: HashMap<String, Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<serde_json::Value>,
+    /// Serve the body from this file instead of `body`: a path relative to `--datadir`, re-read
+    /// whenever the file changes on disk (see `imposter::body_file`). Takes precedence over an
+    /// inline `body`. In binary mode the file holds the raw bytes, not base64.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_file: Option<String>,
     /// Response mode: "text" (default) or "binary" (body is base64-encoded)
     #[serde(rename = "_mode", default, skip_serializing_if = "is_text_mode")]
     pub mode: ResponseMode,
@@ -851,6 +971,32 @@ pub struct ProxyResponse {
     /// Path rewrite configuration for transforming the request path before proxying
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub path_rewrite: Option<PathRewrite>,
+    /// How `proxyAlways` folds recorded responses into generated stubs (Rift extension).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recording: Option<ProxyRecordingOptions>,
+}
+
+/// Recording options for a proxy (Rift extension). They keep a long `proxyAlways` session from
+/// growing one response per request on every generated stub; both apply when a recorded
+/// response is appended to the stub already holding its predicates.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyRecordingOptions {
+    /// Skip a recorded response whose status code and body equal one the stub already holds.
+    #[serde(default)]
+    pub collapse_duplicates: bool,
+    /// Stop appending once a stub holds this many responses; later recordings are dropped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_responses_per_stub: Option<usize>,
+    /// With `addWaitBehavior`, keep every latency recorded into a generated stub and replay its
+    /// responses with a `{mean, jitter}` wait drawn from them, instead of each response's own
+    /// fixed latency.
+    #[serde(default)]
+    pub latency_jitter: bool,
+    /// Longest a streamed upstream response (one without a `Content-Length`) is read for before
+    /// its recording ends with the chunks received so far. Defaults to 30 seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_capture_ms: Option<u64>,
 }
 
 // ============================================================================
@@ -889,6 +1035,11 @@ pub struct ImposterConfig {
     /// Inline PEM private key for `protocol: "https"` (Mountebank-compatible). Paired with `cert`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub key: Option<String>,
+    /// Ask HTTPS clients for a certificate (Mountebank-compatible). As in Mountebank it is not
+    /// validated, and a client that sends none is still served; what was sent is recorded as
+    /// `tls.clientCert` on each request.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub mutual_auth: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     #[serde(default)]
@@ -922,6 +1073,10 @@ pub struct ImposterConfig {
         alias = "allowCORS"
     )]
     pub allow_cors: bool,
+    /// CORS policy beyond `allowCORS`'s wildcard: which origins, methods and headers to allow.
+    /// Setting it enables CORS whatever `allowCORS` says.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cors: Option<CorsConfig>,
     /// Strict behavior mode (issue #375): when true, a requested response behavior that FAILS
     /// (decorate / shellTransform / binary base64 decode) returns a 500 — still carrying the #323
     /// `x-rift-<behavior>-error` header — instead of serving the fallback body. Default false keeps
@@ -929,6 +1084,23 @@ pub struct ImposterConfig {
     /// failure. Can also be forced process-wide via the `RIFT_STRICT_BEHAVIORS` env var.
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub strict_behaviors: bool,
+    /// Closest-match replay: when no stub matches, serve the stub whose predicates the request
+    /// comes nearest to satisfying, marked with `x-rift-fuzzy-match`, and keep the diff for
+    /// `GET /imposters/:port/fuzzyMatches`. Off by default (Mountebank falls through to no-match).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub closest_match: bool,
+    /// Seed for every random draw this imposter makes — `wait` ranges and wait functions,
+    /// `{{ randomInt }}` / `{{ uuid }}`, and `_rift.fault` probabilities — so a serial test run
+    /// replays bit-for-bit. Unset keeps the thread-local (nondeterministic) RNG. JavaScript
+    /// `inject`/`decorate` scripts keep an unseeded `Math.random`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub random_seed: Option<u64>,
+    /// Behaviors merged into every stub response on this imposter (e.g. a global `wait` and a
+    /// standard `decorate`). Same shape as a response's `_behaviors` — object or ordered array; a
+    /// behavior type the response sets itself overrides the default of that type. `repeat` is
+    /// rejected here: it belongs to a single response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_behaviors: Option<serde_json::Value>,
     /// Service name for documentation (optional metadata)
     #[serde(skip_serializing_if = "Option::is_none", alias = "service_name")]
     pub service_name: Option<String>,
@@ -940,6 +1112,31 @@ pub struct ImposterConfig {
     pub rift: Option<RiftConfig>,
 }
 
+/// Per-imposter CORS policy (the `cors` block). An empty list allows anything: origins get `*`,
+/// and methods and headers echo what a preflight asks for.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorsConfig {
+    /// Origins a browser may call from, matched exactly; `*` allows any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow_origins: Vec<String>,
+    /// Methods a preflight is told are allowed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow_methods: Vec<String>,
+    /// Request headers a preflight is told are allowed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow_headers: Vec<String>,
+    /// Response headers a page's script may read.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expose_headers: Vec<String>,
+    /// Send `Access-Control-Allow-Credentials: true`. The allowed origin is then echoed, never `*`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_credentials: bool,
+    /// Seconds a browser may cache a preflight answer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age: Option<u64>,
+}
+
 // ============================================================================
 // Rift Extension Types (_rift namespace)
 // ============================================================================
@@ -979,6 +1176,7 @@ impl Default for ImposterConfig {
             protocol: default_protocol(),
             cert: None,
             key: None,
+            mutual_auth: false,
             name: None,
             record_requests: false,
             enabled: default_enabled(),
@@ -987,7 +1185,11 @@ impl Default for ImposterConfig {
             default_response: None,
             default_forward: None,
             allow_cors: false,
+            cors: None,
             strict_behaviors: false,
+            closest_match: false,
+            random_seed: None,
+            default_behaviors: None,
             service_name: None,
             service_info: None,
             rift: None,
@@ -1141,6 +1343,17 @@ pub struct RiftScriptEngineConfig {
     /// Script execution timeout in milliseconds
     #[serde(default = "default_script_timeout")]
     pub timeout_ms: u64,
+    /// Rhai operations one script execution may run; 0 for no limit
+    #[serde(default = "default_script_max_operations")]
+    pub max_operations: u64,
+    /// Memory one script execution's strings, arrays and maps may take, in bytes; 0 for no limit
+    #[serde(default = "default_script_max_memory_bytes")]
+    pub max_memory_bytes: usize,
+    /// Named script libraries a script pulls in with a leading `// @include name` line. Each is a
+    /// `code:` or `file:` script (not `ref:`); an entry without `engine` or a file extension can
+    /// be included by a script of any engine.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub libraries: HashMap<String, RiftScriptConfig>,
 }
 
 fn default_script_engine() -> String {
@@ -1152,6 +1365,14 @@ fn default_script_timeout() -> u64 {
     crate::scripting::DEFAULT_SCRIPT_TIMEOUT_MS
 }
 
+fn default_script_max_operations() -> u64 {
+    crate::scripting::DEFAULT_SCRIPT_MAX_OPERATIONS
+}
+
+fn default_script_max_memory_bytes() -> usize {
+    crate::scripting::DEFAULT_SCRIPT_MAX_MEMORY_BYTES
+}
+
 /// Rift response extensions (added to stub responses)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -1168,6 +1389,59 @@ pub struct RiftResponseExtension {
     /// literal `{{` are served verbatim.
     #[serde(default)]
     pub templated: bool,
+    /// Composite responses: named sub-requests (to another imposter or any URL) issued before the
+    /// response is rendered. Their results are readable as `{{ fetch.<name>.body }}` (and
+    /// `.status`, `.header '<Name>'`, `.json '<jsonpath>'`); a non-empty `fetch` turns `{{ }}`
+    /// templating on for this response, since merging the results is its whole purpose.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fetch: BTreeMap<String, RiftFetchConfig>,
+    /// Serve the body as a sequence of chunks with a delay before each, as a streamed (chunked
+    /// or `text/event-stream`) upstream response was recorded, instead of in a single write.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream: Option<RiftStreamConfig>,
+}
+
+/// `_rift.stream`: how the served body is split into chunks. The chunk sizes add up to the
+/// recorded body; a body that renders longer sends the rest with the last chunk, one that renders
+/// shorter ends early.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RiftStreamConfig {
+    pub chunks: Vec<RiftStreamChunk>,
+}
+
+/// One `_rift.stream` chunk: `size` bytes of the body, sent `delayMs` after the previous chunk
+/// (or after the response head, for the first).
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RiftStreamChunk {
+    pub size: usize,
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+/// One `_rift.fetch` sub-request. Exactly one of `url` and `imposter` names the target; `url`
+/// (past its scheme and host), `path`, header values, and `body` may use `${request.*}` tokens,
+/// expanded from the incoming request before the sub-request is sent.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RiftFetchConfig {
+    /// Absolute URL of the upstream to call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Port of another imposter on this host, called over loopback at `path`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imposter: Option<u16>,
+    /// Request path (and query) for an `imposter` target. Defaults to `/`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// HTTP method. Defaults to `GET`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
 }
 
 /// Fault injection configuration for responses
@@ -1390,12 +1664,23 @@ pub enum ImposterError {
     StubNotFound(String),
     #[error("A stub with id '{0}' already exists")]
     StubIdConflict(String),
+    #[error("Stub operation {operation} failed: {source}")]
+    StubOperationFailed {
+        operation: usize,
+        source: Box<ImposterError>,
+    },
     #[error("Failed to persist imposter: {0:#}")]
     PersistError(anyhow::Error),
     #[error("TLS configuration error: {0}")]
     Tls(String),
     #[error("flow store configuration error: {0}")]
     FlowStoreConfig(String),
+    #[error("invalid defaultBehaviors: {0}")]
+    InvalidBehaviors(String),
+    #[error("No template named '{0}'")]
+    TemplateNotFound(String),
+    #[error("invalid template: {0}")]
+    InvalidTemplate(String),
     #[error("backend error: {0:#}")]
     Backend(anyhow::Error),
 }
@@ -1504,6 +1789,38 @@ mod tests {
         );
     }
 
+    // Imposter defaults are merged into a response's own behaviors once, then reused; a response
+    // served under different defaults never gets another imposter's merge.
+    #[test]
+    fn behaviors_with_defaults_merges_once_per_defaults() {
+        let resp: StubResponse = serde_json::from_value(json!({
+            "is": { "statusCode": 200 },
+            "_behaviors": { "wait": 5 }
+        }))
+        .unwrap();
+        let parse =
+            |v| std::sync::Arc::new(crate::behaviors::ResponseBehaviors::from_json(&v).unwrap());
+        let defaults = parse(json!({ "copy": [] }));
+
+        let first = resp.behaviors_with_defaults(&defaults);
+        let second = resp.behaviors_with_defaults(&defaults);
+        assert!(
+            std::sync::Arc::ptr_eq(&first, &second),
+            "merged once, then reused"
+        );
+
+        let other = parse(json!({ "wait": 9 }));
+        let merged = resp.behaviors_with_defaults(&other);
+        assert!(!std::sync::Arc::ptr_eq(&first, &merged));
+
+        let bare: StubResponse =
+            serde_json::from_value(json!({ "is": { "statusCode": 200 } })).unwrap();
+        assert!(std::sync::Arc::ptr_eq(
+            &bare.behaviors_with_defaults(&defaults),
+            &defaults
+        ));
+    }
+
     // A well-formed block must not log — the error line means something is wrong.
     #[test]
     #[tracing_test::traced_test]
@@ -1838,6 +2155,7 @@ mod tests {
             status_code: 200,
             headers,
             body: None,
+            body_file: None,
             mode: ResponseMode::Text,
         };
         let v = serde_json::to_value(&out).unwrap();
@@ -2109,6 +2427,7 @@ mod tests {
             body: Some("hello".to_string()),
             mode: ResponseMode::Text,
             timestamp: "2026-01-01T00:00:00Z".to_string(),
+            tls: None,
         };
         let value = serde_json::to_value(&req).expect("serializes");
         assert!(
@@ -2131,6 +2450,7 @@ mod tests {
             body: Some("//4A".to_string()), // base64 of [0xFF, 0xFE, 0x00]
             mode: ResponseMode::Binary,
             timestamp: "2026-01-01T00:00:00Z".to_string(),
+            tls: None,
         };
         let value = serde_json::to_value(&req).expect("serializes");
         assert_eq!(value["_mode"], "binary");
@@ -0,0 +1,635 @@
+//! WireMock stub mapping import: converts WireMock's `{"request": …, "response": …}` mappings
+//! into Rift stubs, so an existing WireMock suite can move over without rewriting it by hand.
+//!
+//! Request matchers become predicates field by field: `equalTo` → `equals`, `contains` →
+//! `contains`, `matches` → an anchored `matches` (WireMock regexes match the whole value),
+//! `absent` → `not exists`, `equalToJson` → `deepEquals` (or `equals` with
+//! `ignoreExtraElements`), and `matchesJsonPath`/`matchesXPath` → a `jsonpath`/`xpath` selector.
+//! WireMock matching is case-sensitive unless a matcher says `caseInsensitive`, so the generated
+//! predicates set `caseSensitive` accordingly (header names stay case-insensitive).
+//!
+//! Responses become `is` responses (`body`, `jsonBody`, `base64Body`, `fixedDelayMilliseconds`),
+//! `proxy` responses (`proxyBaseUrl`) or faults. With the `response-template` transformer the
+//! basic `{{request.*}}` Handlebars tokens are rewritten to Rift's `${request.*}`. Scenario
+//! fields carry over unchanged; both sides start in `Started`. Stubs are ordered by WireMock
+//! `priority` (lower first, default 5), keeping the input order among equal priorities.
+//!
+//! Anything without a Rift equivalent is reported as a warning naming the mapping, rather than
+//! failing the import: a dropped matcher makes the stub match more broadly, so review them.
+
+use super::types::Stub;
+use base64::Engine;
+use regex::Regex;
+use serde_json::{Map, Value, json};
+use std::sync::LazyLock;
+
+/// WireMock's priority for a mapping that sets none.
+const DEFAULT_PRIORITY: i64 = 5;
+
+/// The result of converting a set of WireMock mappings.
+#[derive(Debug, Clone)]
+pub struct WireMockImport {
+    /// The converted stubs, in match order.
+    pub stubs: Vec<Stub>,
+    /// One line per WireMock feature that was dropped or approximated.
+    pub warnings: Vec<String>,
+}
+
+/// Convert a WireMock mappings document: `{"mappings": [...]}` (the `/__admin/mappings` shape),
+/// a bare array of mappings, or a single mapping. A mapping that cannot be converted at all is
+/// skipped with a warning; only a document of the wrong shape is an error.
+pub fn stubs_from_wiremock(document: &Value) -> Result<WireMockImport, String> {
+    let mappings: Vec<&Value> = match document {
+        Value::Object(map) if map.contains_key("mappings") => match &map["mappings"] {
+            Value::Array(items) => items.iter().collect(),
+            _ => return Err("`mappings` must be an array".to_string()),
+        },
+        Value::Object(map) if map.contains_key("request") => vec![document],
+        Value::Array(items) => items.iter().collect(),
+        _ => {
+            return Err(
+                "expected a mapping, an array of mappings, or {\"mappings\": [...]}".to_string(),
+            );
+        }
+    };
+
+    let mut warnings = Vec::new();
+    let mut converted: Vec<(i64, Stub)> = Vec::with_capacity(mappings.len());
+    for (index, mapping) in mappings.into_iter().enumerate() {
+        let label = mapping_label(mapping, index);
+        let mut notes = Vec::new();
+        match convert_mapping(mapping, &mut notes) {
+            Ok(stub) => {
+                let priority = mapping
+                    .get("priority")
+                    .and_then(Value::as_i64)
+                    .unwrap_or(DEFAULT_PRIORITY);
+                converted.push((priority, stub));
+            }
+            Err(e) => notes.push(format!("skipped: {e}")),
+        }
+        warnings.extend(notes.into_iter().map(|note| format!("{label}: {note}")));
+    }
+    // Stable, so equal priorities keep the order they were given in.
+    converted.sort_by_key(|(priority, _)| *priority);
+    Ok(WireMockImport {
+        stubs: converted.into_iter().map(|(_, stub)| stub).collect(),
+        warnings,
+    })
+}
+
+/// How warnings name a mapping: its `name`, else its id, else its position.
+fn mapping_label(mapping: &Value, index: usize) -> String {
+    ["name", "id", "uuid"]
+        .iter()
+        .find_map(|key| mapping.get(*key).and_then(Value::as_str))
+        .map_or_else(
+            || format!("mapping {index}"),
+            |name| format!("mapping '{name}'"),
+        )
+}
+
+fn convert_mapping(mapping: &Value, notes: &mut Vec<String>) -> Result<Stub, String> {
+    let request = mapping
+        .get("request")
+        .and_then(Value::as_object)
+        .ok_or("no `request` object")?;
+    let response = mapping
+        .get("response")
+        .and_then(Value::as_object)
+        .ok_or("no `response` object")?;
+
+    let mut stub = Map::new();
+    if let Some(id) = ["id", "uuid"]
+        .iter()
+        .find_map(|key| mapping.get(*key).and_then(Value::as_str))
+    {
+        stub.insert("id".to_string(), id.into());
+    }
+    for key in ["scenarioName", "requiredScenarioState", "newScenarioState"] {
+        if let Some(value) = mapping.get(key).and_then(Value::as_str) {
+            stub.insert(key.to_string(), value.into());
+        }
+    }
+    for key in ["postServeActions", "serveEventListeners"] {
+        if mapping.get(key).is_some() {
+            notes.push(format!("`{key}` is not supported and was dropped"));
+        }
+    }
+
+    let (predicates, route_pattern) = request_predicates(request, notes);
+    if let Some(pattern) = &route_pattern {
+        stub.insert("routePattern".to_string(), pattern.clone().into());
+    }
+    stub.insert("predicates".to_string(), Value::Array(predicates));
+    let response = stub_response(response, route_pattern.is_some(), notes);
+    stub.insert("responses".to_string(), json!([response]));
+
+    serde_json::from_value(Value::Object(stub)).map_err(|e| format!("invalid stub: {e}"))
+}
+
+/// The predicates for a WireMock `request`, plus the `routePattern` of a `urlPathTemplate`.
+fn request_predicates(
+    request: &Map<String, Value>,
+    notes: &mut Vec<String>,
+) -> (Vec<Value>, Option<String>) {
+    let mut predicates = Vec::new();
+    let mut route_pattern = None;
+    for (key, value) in request {
+        match key.as_str() {
+            "method" => {
+                if let Some(method) = value.as_str().filter(|m| *m != "ANY") {
+                    predicates.push(json!({ "equals": { "method": method.to_uppercase() } }));
+                }
+            }
+            "url" => {
+                let url = value.as_str().unwrap_or_default();
+                let (path, query) = url.split_once('?').unwrap_or((url, ""));
+                predicates.push(case_sensitive(json!({ "equals": { "path": path } })));
+                // `url` is an exact match, so no other query parameters may be present.
+                let query = super::parse_query_string(query);
+                predicates.push(case_sensitive(json!({ "deepEquals": { "query": query } })));
+            }
+            "urlPath" => {
+                let path = value.as_str().unwrap_or_default();
+                predicates.push(case_sensitive(json!({ "equals": { "path": path } })));
+            }
+            "urlPathPattern" | "urlPattern" => {
+                let mut pattern = value.as_str().unwrap_or_default();
+                if key == "urlPattern"
+                    && let Some((path_part, _)) = pattern.split_once("\\?")
+                {
+                    notes.push(
+                        "the query part of `urlPattern` is not matched; only the path is"
+                            .to_string(),
+                    );
+                    pattern = path_part;
+                }
+                predicates.push(case_sensitive(
+                    json!({ "matches": { "path": anchored(pattern) } }),
+                ));
+            }
+            "urlPathTemplate" => {
+                let template = value.as_str().unwrap_or_default();
+                let (pattern, regex) = path_template(template);
+                predicates.push(case_sensitive(json!({ "matches": { "path": regex } })));
+                route_pattern = Some(pattern);
+            }
+            "queryParameters" | "headers" => {
+                let field = if key == "headers" { "headers" } else { "query" };
+                for (name, matcher) in value.as_object().into_iter().flatten() {
+                    let wrap = |v: Value| json!({ field: { name.as_str(): v } });
+                    push_matcher(
+                        &mut predicates,
+                        &wrap,
+                        matcher,
+                        &format!("{key}.{name}"),
+                        notes,
+                    );
+                }
+            }
+            "bodyPatterns" => {
+                for matcher in value.as_array().into_iter().flatten() {
+                    let wrap = |v: Value| json!({ "body": v });
+                    push_matcher(&mut predicates, &wrap, matcher, "bodyPatterns", notes);
+                }
+            }
+            "basicAuthCredentials" => {
+                let username = value["username"].as_str().unwrap_or_default();
+                let password = value["password"].as_str().unwrap_or_default();
+                let token = base64::engine::general_purpose::STANDARD
+                    .encode(format!("{username}:{password}"));
+                predicates.push(case_sensitive(
+                    json!({ "equals": { "headers": { "Authorization": format!("Basic {token}") } } }),
+                ));
+            }
+            other => notes.push(format!(
+                "request matcher `{other}` is not supported and was dropped"
+            )),
+        }
+    }
+    (predicates, route_pattern)
+}
+
+/// Append the predicate for one WireMock matcher object, or a note when it has no equivalent.
+fn push_matcher(
+    predicates: &mut Vec<Value>,
+    wrap: &dyn Fn(Value) -> Value,
+    matcher: &Value,
+    what: &str,
+    notes: &mut Vec<String>,
+) {
+    match matcher_predicate(wrap, matcher) {
+        Ok(predicate) => predicates.push(predicate),
+        Err(e) => notes.push(format!("{what}: {e}; the matcher was dropped")),
+    }
+}
+
+fn matcher_predicate(wrap: &dyn Fn(Value) -> Value, matcher: &Value) -> Result<Value, String> {
+    let object = matcher.as_object().ok_or("matcher is not an object")?;
+    let case_insensitive = object
+        .get("caseInsensitive")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let with_case = |predicate: Value| {
+        if case_insensitive {
+            predicate
+        } else {
+            case_sensitive(predicate)
+        }
+    };
+    let text = |value: &Value| {
+        value
+            .as_str()
+            .map_or_else(|| value.to_string(), str::to_string)
+    };
+    for (operator, value) in object {
+        let predicate = match operator.as_str() {
+            "caseInsensitive" => continue,
+            "equalTo" | "equalToXml" => with_case(json!({ "equals": wrap(text(value).into()) })),
+            "contains" => with_case(json!({ "contains": wrap(text(value).into()) })),
+            "doesNotContain" => {
+                json!({ "not": with_case(json!({ "contains": wrap(text(value).into()) })) })
+            }
+            "matches" => with_case(json!({ "matches": wrap(anchored(&text(value)).into()) })),
+            "doesNotMatch" => json!({
+                "not": with_case(json!({ "matches": wrap(anchored(&text(value)).into()) }))
+            }),
+            "absent" => {
+                let exists = json!({ "exists": wrap(true.into()) });
+                if value.as_bool().unwrap_or(true) {
+                    json!({ "not": exists })
+                } else {
+                    exists
+                }
+            }
+            "equalToJson" => {
+                let expected = match value {
+                    Value::String(s) => serde_json::from_str(s)
+                        .map_err(|e| format!("`equalToJson` is not JSON: {e}"))?,
+                    other => other.clone(),
+                };
+                let lenient = object
+                    .get("ignoreExtraElements")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+                let operation = if lenient { "equals" } else { "deepEquals" };
+                json!({ operation: wrap(expected) })
+            }
+            "ignoreExtraElements" | "ignoreArrayOrder" => continue,
+            "matchesJsonPath" | "matchesXPath" => {
+                let selector_kind = if operator == "matchesJsonPath" {
+                    "jsonpath"
+                } else {
+                    "xpath"
+                };
+                let (selector, nested) = match value {
+                    Value::String(expression) => (expression.clone(), None),
+                    Value::Object(spec) => {
+                        let expression = spec
+                            .get("expression")
+                            .and_then(Value::as_str)
+                            .ok_or_else(|| format!("`{operator}` has no `expression`"))?;
+                        let rest: Map<String, Value> = spec
+                            .iter()
+                            .filter(|(k, _)| *k != "expression")
+                            .map(|(k, v)| (k.clone(), v.clone()))
+                            .collect();
+                        (expression.to_string(), Some(Value::Object(rest)))
+                    }
+                    _ => return Err(format!("`{operator}` must be a string or an object")),
+                };
+                let mut predicate = match nested {
+                    Some(nested) => matcher_predicate(wrap, &nested)?,
+                    None => json!({ "exists": wrap(true.into()) }),
+                };
+                predicate[selector_kind] = json!({ "selector": selector });
+                predicate
+            }
+            "and" | "or" => {
+                let parts = value
+                    .as_array()
+                    .ok_or_else(|| format!("`{operator}` must be an array"))?
+                    .iter()
+                    .map(|m| matcher_predicate(wrap, m))
+                    .collect::<Result<Vec<_>, _>>()?;
+                json!({ operator.as_str(): parts })
+            }
+            other => return Err(format!("matcher `{other}` is not supported")),
+        };
+        return Ok(predicate);
+    }
+    Err("matcher has no operator".to_string())
+}
+
+/// Mark a predicate case-sensitive on values, leaving header and query names case-insensitive.
+fn case_sensitive(mut predicate: Value) -> Value {
+    predicate["caseSensitive"] = true.into();
+    predicate["keyCaseSensitive"] = false.into();
+    predicate
+}
+
+/// Anchor a WireMock regex, which must match the whole value, for Rift's search-style `matches`.
+fn anchored(pattern: &str) -> String {
+    format!("^(?:{pattern})$")
+}
+
+/// `/users/{id}` → (`/users/:id`, a regex matching the same paths).
+fn path_template(template: &str) -> (String, String) {
+    let mut route = Vec::new();
+    let mut regex = Vec::new();
+    for segment in template.split('/') {
+        match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(name) => {
+                route.push(format!(":{name}"));
+                regex.push("[^/]+".to_string());
+            }
+            None => {
+                route.push(segment.to_string());
+                regex.push(regex::escape(segment));
+            }
+        }
+    }
+    (route.join("/"), format!("^{}$", regex.join("/")))
+}
+
+fn stub_response(
+    response: &Map<String, Value>,
+    has_route_pattern: bool,
+    notes: &mut Vec<String>,
+) -> Value {
+    if let Some(fault) = response.get("fault").and_then(Value::as_str) {
+        return json!({ "fault": fault });
+    }
+    if let Some(to) = response.get("proxyBaseUrl").and_then(Value::as_str) {
+        let mut proxy = json!({ "to": to, "mode": "proxyTransparent" });
+        if let Some(headers) = response.get("additionalProxyRequestHeaders") {
+            proxy["injectHeaders"] = headers.clone();
+        }
+        return json!({ "proxy": proxy });
+    }
+
+    let templated = response
+        .get("transformers")
+        .and_then(Value::as_array)
+        .is_some_and(|t| t.iter().any(|t| t == "response-template"));
+    let mut template_notes = Vec::new();
+    let mut render = |text: &str| -> String {
+        if templated {
+            convert_template(text, has_route_pattern, &mut template_notes)
+        } else {
+            text.to_string()
+        }
+    };
+
+    let mut is = Map::new();
+    is.insert(
+        "statusCode".to_string(),
+        response.get("status").cloned().unwrap_or(json!(200)),
+    );
+    let mut headers = Map::new();
+    for (name, value) in response
+        .get("headers")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        let value = match value {
+            Value::String(s) => Value::String(render(s)),
+            Value::Array(values) => Value::Array(
+                values
+                    .iter()
+                    .map(|v| Value::String(render(v.as_str().unwrap_or_default())))
+                    .collect(),
+            ),
+            other => other.clone(),
+        };
+        headers.insert(name.clone(), value);
+    }
+    let mut mode = None;
+    if let Some(body) = response.get("body").and_then(Value::as_str) {
+        is.insert("body".to_string(), render(body).into());
+    } else if let Some(body) = response.get("jsonBody") {
+        is.insert("body".to_string(), render_json(body, &mut render));
+        if !headers
+            .keys()
+            .any(|k| k.eq_ignore_ascii_case("content-type"))
+        {
+            headers.insert("Content-Type".to_string(), "application/json".into());
+        }
+    } else if let Some(body) = response.get("base64Body").and_then(Value::as_str) {
+        is.insert("body".to_string(), body.into());
+        mode = Some("binary");
+    } else if let Some(file) = response.get("bodyFileName").and_then(Value::as_str) {
+        notes.push(format!(
+            "`bodyFileName` '{file}' was not inlined (import the mappings directory with \
+             `rift import --wiremock` to resolve __files); the body is empty"
+        ));
+    }
+    if !headers.is_empty() {
+        is.insert("headers".to_string(), Value::Object(headers));
+    }
+    if let Some(mode) = mode {
+        is.insert("_mode".to_string(), mode.into());
+    }
+    for key in ["statusMessage", "delayDistribution", "chunkedDribbleDelay"] {
+        if response.contains_key(key) {
+            notes.push(format!("response `{key}` is not supported and was dropped"));
+        }
+    }
+    template_notes.sort();
+    template_notes.dedup();
+    notes.extend(template_notes);
+
+    let mut out = json!({ "is": is });
+    if let Some(delay) = response
+        .get("fixedDelayMilliseconds")
+        .and_then(Value::as_u64)
+    {
+        out["_behaviors"] = json!({ "wait": delay });
+    }
+    out
+}
+
+fn render_json(value: &Value, render: &mut dyn FnMut(&str) -> String) -> Value {
+    match value {
+        Value::String(s) => Value::String(render(s)),
+        Value::Array(items) => Value::Array(items.iter().map(|v| render_json(v, render)).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), render_json(v, render)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+static HANDLEBARS_TOKEN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*([^{}]*?)\s*\}\}").expect("static regex"));
+
+/// Rewrite the Handlebars tokens Rift can serve to `${request.*}`; note the rest.
+fn convert_template(text: &str, has_route_pattern: bool, notes: &mut Vec<String>) -> String {
+    HANDLEBARS_TOKEN
+        .replace_all(text, |caps: &regex::Captures| {
+            let expression = &caps[1];
+            match rift_token(expression, has_route_pattern) {
+                Some(token) => token,
+                None => {
+                    notes.push(format!(
+                        "template expression `{{{{{expression}}}}}` has no Rift equivalent and was left as is"
+                    ));
+                    caps[0].to_string()
+                }
+            }
+        })
+        .into_owned()
+}
+
+fn rift_token(expression: &str, has_route_pattern: bool) -> Option<String> {
+    let field = expression.strip_prefix("request.")?;
+    let token = match field {
+        "path" | "method" | "body" => field.to_string(),
+        _ => {
+            let (kind, name) = field.split_once('.')?;
+            // `request.headers.[X-Tenant]` is Handlebars' escape for names with dashes.
+            let name = name
+                .strip_prefix('[')
+                .and_then(|n| n.strip_suffix(']'))
+                .unwrap_or(name);
+            match kind {
+                "query" => format!("query.{name}"),
+                "headers" => format!("headers.{name}"),
+                "path" if has_route_pattern && !name.starts_with('[') => {
+                    format!("pathParams.{name}")
+                }
+                _ => return None,
+            }
+        }
+    };
+    Some(format!("${{request.{token}}}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(mapping: Value) -> (Value, Vec<String>) {
+        let import = stubs_from_wiremock(&mapping).unwrap();
+        assert_eq!(import.stubs.len(), 1, "{:?}", import.warnings);
+        (
+            serde_json::to_value(&import.stubs[0]).unwrap(),
+            import.warnings,
+        )
+    }
+
+    #[test]
+    fn request_matchers_become_case_sensitive_predicates() {
+        let (stub, warnings) = convert(json!({
+            "id": "get-user",
+            "request": {
+                "method": "GET",
+                "urlPathPattern": "/users/[0-9]+",
+                "headers": {"X-Tenant": {"equalTo": "acme", "caseInsensitive": true}},
+                "queryParameters": {"debug": {"absent": true}},
+                "bodyPatterns": [{"matchesJsonPath": {"expression": "$.kind", "contains": "gold"}}]
+            },
+            "response": {"status": 200, "body": "ok"}
+        }));
+        assert!(warnings.is_empty(), "{warnings:?}");
+        assert_eq!(stub["id"], "get-user");
+        let predicates = stub["predicates"].as_array().unwrap();
+        assert!(predicates.contains(&json!({"equals": {"method": "GET"}})));
+        assert!(predicates.contains(&json!({
+            "matches": {"path": "^(?:/users/[0-9]+)$"},
+            "caseSensitive": true,
+            "keyCaseSensitive": false
+        })));
+        assert!(predicates.contains(&json!({"equals": {"headers": {"X-Tenant": "acme"}}})));
+        assert!(predicates.contains(&json!({"not": {"exists": {"query": {"debug": true}}}})));
+        assert!(predicates.contains(&json!({
+            "contains": {"body": "gold"},
+            "caseSensitive": true,
+            "keyCaseSensitive": false,
+            "jsonpath": {"selector": "$.kind"}
+        })));
+    }
+
+    #[test]
+    fn exact_url_pins_the_whole_query() {
+        let (stub, _) = convert(json!({
+            "request": {"url": "/search?q=rift"},
+            "response": {"status": 204}
+        }));
+        let predicates = stub["predicates"].as_array().unwrap();
+        assert_eq!(predicates[1]["deepEquals"]["query"], json!({"q": "rift"}));
+        // Serialized like Mountebank output: the status code as a string.
+        assert_eq!(stub["responses"][0]["is"]["statusCode"], "204");
+    }
+
+    #[test]
+    fn templated_responses_use_rift_request_tokens() {
+        let (stub, warnings) = convert(json!({
+            "request": {"urlPathTemplate": "/orders/{orderId}"},
+            "response": {
+                "jsonBody": {"id": "{{request.path.orderId}}", "tenant": "{{request.headers.[X-Tenant]}}"},
+                "headers": {"X-Trace": "{{randomValue length=8}}"},
+                "transformers": ["response-template"],
+                "fixedDelayMilliseconds": 250
+            }
+        }));
+        assert_eq!(stub["routePattern"], "/orders/:orderId");
+        let response = &stub["responses"][0];
+        assert_eq!(
+            response["is"]["body"]["id"],
+            "${request.pathParams.orderId}"
+        );
+        assert_eq!(
+            response["is"]["body"]["tenant"],
+            "${request.headers.X-Tenant}"
+        );
+        assert_eq!(
+            response["is"]["headers"]["Content-Type"],
+            "application/json"
+        );
+        assert_eq!(response["behaviors"][0]["wait"], 250);
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert!(warnings[0].contains("randomValue"), "{warnings:?}");
+    }
+
+    #[test]
+    fn scenarios_faults_and_priorities_carry_over() {
+        let import = stubs_from_wiremock(&json!({"mappings": [
+            {
+                "name": "fallback",
+                "request": {"method": "ANY", "urlPattern": ".*"},
+                "response": {"fault": "CONNECTION_RESET_BY_PEER"},
+                "priority": 10
+            },
+            {
+                "scenarioName": "checkout",
+                "requiredScenarioState": "Started",
+                "newScenarioState": "paid",
+                "request": {"method": "POST", "urlPath": "/pay"},
+                "response": {"status": 201}
+            },
+            {"name": "broken", "response": {"status": 200}}
+        ]}))
+        .unwrap();
+        assert_eq!(import.stubs.len(), 2);
+        let first = serde_json::to_value(&import.stubs[0]).unwrap();
+        assert_eq!(first["scenarioName"], "checkout");
+        assert_eq!(first["newScenarioState"], "paid");
+        let second = serde_json::to_value(&import.stubs[1]).unwrap();
+        assert_eq!(second["responses"][0]["fault"], "CONNECTION_RESET_BY_PEER");
+        assert_eq!(
+            import.warnings,
+            ["mapping 'broken': skipped: no `request` object"]
+        );
+    }
+
+    #[test]
+    fn a_document_of_the_wrong_shape_is_rejected() {
+        assert!(stubs_from_wiremock(&json!({"stubs": []})).is_err());
+        assert!(stubs_from_wiremock(&json!({"mappings": {}})).is_err());
+    }
+}
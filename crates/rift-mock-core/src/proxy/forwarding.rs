@@ -246,6 +246,7 @@ pub async fn forward_with_recording(
         body: response_body_bytes.to_vec(),
         latency_ms: Some(latency_ms),
         timestamp_secs: crate::util::unix_timestamp(),
+        body_file: None,
     };
 
     recording_store.record(signature, recorded_response);
@@ -225,8 +225,9 @@ impl ProxyServer {
         // Create shared HTTP client
         let http_client = create_http_client(&config, skip_tls_verify)?;
 
-        // Extract recording mode before moving config into Arc
+        // Extract recording mode and bounds before moving config into Arc
         let recording_mode = config.recording.mode;
+        let recording_eviction = config.recording.eviction_policy();
 
         Ok(Self {
             config: Arc::new(config),
@@ -243,7 +244,9 @@ impl ProxyServer {
             // Initialize behavior state
             response_cycler: Arc::new(ResponseCycler::new()),
             csv_cache: Arc::new(CsvCache::new()),
-            recording_store: Arc::new(RecordingStore::new(recording_mode)),
+            recording_store: Arc::new(
+                RecordingStore::new(recording_mode).with_eviction(recording_eviction),
+            ),
         })
     }
 
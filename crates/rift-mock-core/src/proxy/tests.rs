@@ -429,6 +429,7 @@ mod recording_store_tests {
             body: b"test body".to_vec(),
             latency_ms: Some(50),
             timestamp_secs: 1234567890,
+            body_file: None,
         };
         assert_eq!(response.status, 200);
         assert_eq!(response.body, b"test body".to_vec());
@@ -445,6 +446,7 @@ mod recording_store_tests {
             body: b"response".to_vec(),
             latency_ms: Some(10),
             timestamp_secs: 0,
+            body_file: None,
         };
 
         store.record(sig.clone(), response);
@@ -475,6 +477,7 @@ mod recording_store_tests {
             body: vec![],
             latency_ms: None,
             timestamp_secs: 0,
+            body_file: None,
         };
         store.record(sig.clone(), response);
         assert!(store.should_proxy(&sig));
@@ -495,6 +498,7 @@ mod recording_store_tests {
             body: vec![],
             latency_ms: None,
             timestamp_secs: 0,
+            body_file: None,
         };
         store.record(sig.clone(), response);
 
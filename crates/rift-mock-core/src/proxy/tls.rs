@@ -6,6 +6,7 @@
 use rustls::DigitallySignedStruct;
 use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
 use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::server::danger::{ClientCertVerified, ClientCertVerifier};
 use std::sync::Arc;
 use tokio_rustls::TlsAcceptor;
 
@@ -56,19 +57,91 @@ impl ServerCertVerifier for NoVerifier {
     }
 }
 
+/// Client-certificate verifier for `mutualAuth` imposters.
+///
+/// Mountebank semantics: the server asks for a certificate but accepts whatever the client sends,
+/// or nothing at all. The certificate is there to be recorded and matched on (the `tls` predicate
+/// field), not to authenticate the client; only the handshake signature is checked, so the client
+/// must hold the key for the certificate it presents.
+#[derive(Debug)]
+pub struct AcceptAnyClientCert {
+    algorithms: rustls::crypto::WebPkiSupportedAlgorithms,
+}
+
+impl AcceptAnyClientCert {
+    pub fn new() -> Self {
+        Self {
+            algorithms: rustls::crypto::ring::default_provider().signature_verification_algorithms,
+        }
+    }
+}
+
+impl Default for AcceptAnyClientCert {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClientCertVerifier for AcceptAnyClientCert {
+    fn offer_client_auth(&self) -> bool {
+        true
+    }
+
+    fn client_auth_mandatory(&self) -> bool {
+        false
+    }
+
+    fn root_hint_subjects(&self) -> &[rustls::DistinguishedName] {
+        &[]
+    }
+
+    fn verify_client_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _now: UnixTime,
+    ) -> Result<ClientCertVerified, rustls::Error> {
+        Ok(ClientCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.algorithms.supported_schemes()
+    }
+}
+
 /// Create TLS acceptor from certificate and key files.
 pub fn create_tls_acceptor(cert_path: &str, key_path: &str) -> Result<TlsAcceptor, anyhow::Error> {
     let cert_pem = std::fs::read(cert_path)
         .map_err(|e| anyhow::anyhow!("Failed to open certificate file '{cert_path}': {e}"))?;
     let key_pem = std::fs::read(key_path)
         .map_err(|e| anyhow::anyhow!("Failed to open private key file '{key_path}': {e}"))?;
-    tls_acceptor_from_pem(&cert_pem, &key_pem)
+    tls_acceptor_from_pem(&cert_pem, &key_pem, false)
 }
 
-/// Create a TLS acceptor from in-memory PEM bytes (per-imposter HTTPS, issue #206).
+/// Create a TLS acceptor from in-memory PEM bytes (per-imposter HTTPS, issue #206). With
+/// `mutual_auth` the acceptor requests a client certificate (see [`AcceptAnyClientCert`]).
 pub fn tls_acceptor_from_pem(
     cert_pem: &[u8],
     key_pem: &[u8],
+    mutual_auth: bool,
 ) -> Result<TlsAcceptor, anyhow::Error> {
     let certs: Vec<CertificateDer> = rustls_pemfile::certs(&mut &cert_pem[..])
         .collect::<Result<_, _>>()
@@ -83,12 +156,15 @@ pub fn tls_acceptor_from_pem(
         .map_err(|e| anyhow::anyhow!("Failed to parse private key PEM: {e}"))?
         .ok_or_else(|| anyhow::anyhow!("No private key found in key PEM"))?;
 
-    let mut config = rustls::ServerConfig::builder()
-        .with_no_client_auth()
-        .with_single_cert(certs, key)
-        .map_err(|e| {
-            anyhow::anyhow!("Failed to build TLS configuration (cert/key mismatch?): {e}")
-        })?;
+    let builder = rustls::ServerConfig::builder();
+    let builder = if mutual_auth {
+        builder.with_client_cert_verifier(Arc::new(AcceptAnyClientCert::new()))
+    } else {
+        builder.with_no_client_auth()
+    };
+    let mut config = builder.with_single_cert(certs, key).map_err(|e| {
+        anyhow::anyhow!("Failed to build TLS configuration (cert/key mismatch?): {e}")
+    })?;
     // Advertise HTTP/2 and HTTP/1.1 via ALPN so TLS clients can negotiate h2 (issue #295).
     config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
     configure_session_resumption(&mut config)?;
@@ -129,13 +205,14 @@ pub fn configure_session_resumption(
 
 /// Generate an in-memory self-signed acceptor for zero-config HTTPS imposters (issue #206),
 /// matching Mountebank's built-in self-signed default. Valid for `localhost`/`127.0.0.1`.
-pub fn generate_self_signed_acceptor() -> Result<TlsAcceptor, anyhow::Error> {
+pub fn generate_self_signed_acceptor(mutual_auth: bool) -> Result<TlsAcceptor, anyhow::Error> {
     let cert =
         rcgen::generate_simple_self_signed(vec!["localhost".to_string(), "127.0.0.1".to_string()])
             .map_err(|e| anyhow::anyhow!("Failed to generate self-signed certificate: {e}"))?;
     tls_acceptor_from_pem(
         cert.cert.pem().as_bytes(),
         cert.key_pair.serialize_pem().as_bytes(),
+        mutual_auth,
     )
 }
 
@@ -192,6 +269,7 @@ mod tests {
     #[test]
     fn https_acceptor_builds_with_resumption() {
         // The real imposter-HTTPS path (self-signed) must still build once resumption is wired in.
-        assert!(generate_self_signed_acceptor().is_ok());
+        assert!(generate_self_signed_acceptor(false).is_ok());
+        assert!(generate_self_signed_acceptor(true).is_ok());
     }
 }
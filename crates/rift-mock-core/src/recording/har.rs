@@ -0,0 +1,620 @@
+//! HAR 1.2 (HTTP Archive) export of an imposter's recorded traffic, and import of HAR captures
+//! as stubs.
+//!
+//! A HAR file opens directly in browser devtools and most HTTP tooling, so it is the easiest way
+//! to hand a recording session to someone who does not run Rift. An entry is built for every
+//! request in the imposter's journal (`recordRequests`), paired with the proxy response saved for
+//! the same method, path, and query, if any. Saved responses no journal entry claims (recording
+//! requests off, or the journal already cleared) become entries of their own, so a proxy
+//! recording exports fully either way. A journal entry with no saved response has `status: 0`
+//! (the HAR convention for "no response"), as it was served by a stub rather than recorded.
+//!
+//! Bodies that are not valid UTF-8 are exported base64-encoded, with `encoding: "base64"` on the
+//! response content (HAR has no equivalent for request bodies, so those carry a `comment`).
+//!
+//! Import runs the other way: each entry with a response becomes an `equals` predicate on its
+//! method, path, query, and any selected request headers, answered by an `is` response rebuilt
+//! from the recorded one. Entries with equal predicates share one stub whose responses cycle in
+//! capture order, as a `proxyAlways` recording would.
+
+use super::types::{RecordedResponse, RequestSignature};
+use crate::imposter::{
+    RecordedRequest, ResponseMode, Stub, create_stub_from_proxy_response, parse_query_string,
+};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// A HAR document: `{"log": {...}}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Har {
+    pub log: HarLog,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarLog {
+    pub version: String,
+    pub creator: HarCreator,
+    pub entries: Vec<HarEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HarCreator {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    pub started_date_time: String,
+    /// Total elapsed time in milliseconds.
+    pub time: f64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    #[serde(default)]
+    pub cache: serde_json::Map<String, serde_json::Value>,
+    pub timings: HarTimings,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    pub http_version: String,
+    pub headers: Vec<HarNameValue>,
+    pub query_string: Vec<HarNameValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<HarPostData>,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: u16,
+    pub status_text: String,
+    pub http_version: String,
+    pub headers: Vec<HarNameValue>,
+    pub content: HarContent,
+    #[serde(rename = "redirectURL")]
+    pub redirect_url: String,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarNameValue {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarPostData {
+    pub mime_type: String,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    pub size: i64,
+    pub mime_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HarTimings {
+    pub send: f64,
+    pub wait: f64,
+    pub receive: f64,
+}
+
+const HTTP_VERSION: &str = "HTTP/1.1";
+
+/// Method, path and sorted decoded query: what pairs a journal entry with a saved response.
+type PairingKey = (String, String, Vec<(String, String)>);
+
+/// Build a HAR document from journal `requests` and `saved` proxy responses. `base_url` is the
+/// imposter's own origin (`http://localhost:4545`), used to give each entry an absolute URL.
+#[must_use]
+pub fn build_har(
+    base_url: &str,
+    requests: &[RecordedRequest],
+    saved: Vec<(RequestSignature, Vec<RecordedResponse>)>,
+) -> Har {
+    // Saved responses per (method, path, decoded query), consumed in recording order as journal
+    // entries claim them.
+    let mut unclaimed: HashMap<PairingKey, VecDeque<_>> = saved
+        .into_iter()
+        .map(|(sig, responses)| {
+            let query = sorted_query(&parse_query_string(sig.query.as_deref().unwrap_or("")));
+            (
+                (sig.method, sig.path, query),
+                responses.into_iter().collect(),
+            )
+        })
+        .collect();
+
+    let mut entries: Vec<HarEntry> = requests
+        .iter()
+        .map(|req| {
+            let query = sorted_query(&req.query);
+            let key = (req.method.to_uppercase(), req.path.clone(), query.clone());
+            let response = unclaimed.get_mut(&key).and_then(VecDeque::pop_front);
+            let mut headers: Vec<HarNameValue> = req
+                .headers
+                .iter()
+                .flat_map(|(name, values)| values.iter().map(move |v| name_value(name, v)))
+                .collect();
+            // The journal keeps headers in a map; sort so repeated exports are identical.
+            headers.sort_by(|a, b| a.name.cmp(&b.name));
+            let request = HarRequest {
+                method: req.method.to_uppercase(),
+                url: url_for(base_url, &req.path, &query),
+                http_version: HTTP_VERSION.to_string(),
+                headers,
+                query_string: query.iter().map(|(k, v)| name_value(k, v)).collect(),
+                post_data: req.body.as_ref().map(|body| HarPostData {
+                    mime_type: header_value(&req.headers, "content-type").unwrap_or_default(),
+                    text: body.clone(),
+                    comment: (req.mode == ResponseMode::Binary)
+                        .then(|| "body is base64-encoded".to_string()),
+                }),
+                headers_size: -1,
+                body_size: req.body.as_ref().map_or(0, |b| b.len() as i64),
+            };
+            entry(req.timestamp.clone(), request, response)
+        })
+        .collect();
+
+    // Saved responses no journal entry claimed become standalone entries. Their timestamps have
+    // one-second resolution, so order them by signature first to keep ties stable.
+    let mut unclaimed: Vec<_> = unclaimed.into_iter().collect();
+    unclaimed.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for ((method, path, query), responses) in unclaimed {
+        for response in responses {
+            let request = HarRequest {
+                method: method.clone(),
+                url: url_for(base_url, &path, &query),
+                http_version: HTTP_VERSION.to_string(),
+                headers: Vec::new(),
+                query_string: query.iter().map(|(k, v)| name_value(k, v)).collect(),
+                post_data: None,
+                headers_size: -1,
+                body_size: 0,
+            };
+            let started = chrono::DateTime::from_timestamp(response.timestamp_secs as i64, 0)
+                .unwrap_or_default()
+                .to_rfc3339();
+            entries.push(entry(started, request, Some(response)));
+        }
+    }
+    // RFC 3339 timestamps in UTC sort chronologically as strings.
+    entries.sort_by(|a, b| a.started_date_time.cmp(&b.started_date_time));
+
+    Har {
+        log: HarLog {
+            version: "1.2".to_string(),
+            creator: HarCreator {
+                name: "rift".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+            },
+            entries,
+        },
+    }
+}
+
+fn entry(started: String, request: HarRequest, response: Option<RecordedResponse>) -> HarEntry {
+    let time = response
+        .as_ref()
+        .and_then(|r| r.latency_ms)
+        .unwrap_or_default() as f64;
+    let (response, comment) = match response {
+        Some(recorded) => (har_response(recorded), None),
+        None => (
+            HarResponse {
+                status: 0,
+                status_text: String::new(),
+                http_version: HTTP_VERSION.to_string(),
+                headers: Vec::new(),
+                content: HarContent {
+                    size: 0,
+                    mime_type: String::new(),
+                    text: None,
+                    encoding: None,
+                },
+                redirect_url: String::new(),
+                headers_size: -1,
+                body_size: -1,
+            },
+            Some("served by a stub; no recorded response".to_string()),
+        ),
+    };
+    HarEntry {
+        started_date_time: started,
+        time,
+        request,
+        response,
+        cache: serde_json::Map::new(),
+        timings: HarTimings {
+            send: 0.0,
+            wait: time,
+            receive: 0.0,
+        },
+        comment,
+    }
+}
+
+fn har_response(recorded: RecordedResponse) -> HarResponse {
+    let mime_type = recorded
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        .map(|(_, value)| value.clone())
+        .unwrap_or_default();
+    let size = recorded.body.len() as i64;
+    let (text, encoding) = match String::from_utf8(recorded.body) {
+        Ok(text) => (text, None),
+        Err(e) => (
+            base64::engine::general_purpose::STANDARD.encode(e.as_bytes()),
+            Some("base64".to_string()),
+        ),
+    };
+    HarResponse {
+        status: recorded.status,
+        status_text: hyper::StatusCode::from_u16(recorded.status)
+            .ok()
+            .and_then(|s| s.canonical_reason())
+            .unwrap_or_default()
+            .to_string(),
+        http_version: HTTP_VERSION.to_string(),
+        headers: recorded
+            .headers
+            .iter()
+            .map(|(name, value)| name_value(name, value))
+            .collect(),
+        content: HarContent {
+            size,
+            mime_type,
+            text: Some(text),
+            encoding,
+        },
+        redirect_url: recorded
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("location"))
+            .map(|(_, value)| value.clone())
+            .unwrap_or_default(),
+        headers_size: -1,
+        body_size: size,
+    }
+}
+
+/// Response headers an imported stub drops: HAR `content.text` is already decoded, so the captured
+/// encoding and length no longer describe the body being replayed.
+const STALE_RESPONSE_HEADERS: &[&str] = &["content-encoding", "content-length"];
+
+/// Generate stubs from `har`'s entries. Predicates match method, path, and query, plus the
+/// request headers named in `match_headers` (case-insensitive) when the entry carries them.
+/// Entries without a response (`status: 0`) are skipped.
+#[must_use]
+pub fn stubs_from_har(har: &Har, match_headers: &[String]) -> Vec<Stub> {
+    let mut stubs: Vec<Stub> = Vec::new();
+    for entry in &har.log.entries {
+        if entry.response.status == 0 {
+            continue;
+        }
+        let stub = stub_from_entry(entry, match_headers);
+        match stubs.iter_mut().find(|s| s.predicates == stub.predicates) {
+            Some(existing) => existing.responses.extend(stub.responses),
+            None => stubs.push(stub),
+        }
+    }
+    stubs
+}
+
+fn stub_from_entry(entry: &HarEntry, match_headers: &[String]) -> Stub {
+    let (path, query) = split_url(&entry.request.url);
+    let mut fields = serde_json::Map::new();
+    fields.insert(
+        "method".to_string(),
+        entry.request.method.to_uppercase().into(),
+    );
+    fields.insert("path".to_string(), path.into());
+    let query = parse_query_string(query.unwrap_or(""));
+    if !query.is_empty() {
+        fields.insert("query".to_string(), serde_json::json!(query));
+    }
+    let headers: serde_json::Map<String, serde_json::Value> = match_headers
+        .iter()
+        .filter_map(|name| {
+            entry
+                .request
+                .headers
+                .iter()
+                .find(|h| h.name.eq_ignore_ascii_case(name))
+                .map(|h| (name.clone(), h.value.clone().into()))
+        })
+        .collect();
+    if !headers.is_empty() {
+        fields.insert("headers".to_string(), headers.into());
+    }
+    // Mountebank's default case-insensitive matching: HTTP/2 captures record lowercase header
+    // names, which a case-sensitive predicate would hold against an HTTP/1.1 client's `X-Tenant`.
+    let predicate = serde_json::json!({ "equals": fields });
+
+    let response = &entry.response;
+    let response_headers: Vec<(String, String)> = response
+        .headers
+        .iter()
+        .filter(|h| {
+            !STALE_RESPONSE_HEADERS
+                .iter()
+                .any(|stale| h.name.eq_ignore_ascii_case(stale))
+        })
+        .map(|h| (h.name.clone(), h.value.clone()))
+        .collect();
+    let text = response.content.text.as_deref().unwrap_or_default();
+    let body = if response.content.encoding.as_deref() == Some("base64") {
+        base64::engine::general_purpose::STANDARD
+            .decode(text)
+            .unwrap_or_else(|_| text.as_bytes().to_vec())
+    } else {
+        text.as_bytes().to_vec()
+    };
+    create_stub_from_proxy_response(
+        vec![predicate],
+        response.status,
+        &response_headers,
+        &body,
+        None,
+        None,
+        None,
+    )
+}
+
+/// Split an absolute (or origin-relative) URL into its path and raw query string.
+pub(super) fn split_url(url: &str) -> (&str, Option<&str>) {
+    let path_and_query = match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("/", |start| &rest[start..]),
+        None => url,
+    };
+    let path_and_query = path_and_query.split('#').next().unwrap_or_default();
+    match path_and_query.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (path_and_query, None),
+    }
+}
+
+fn sorted_query<S: std::hash::BuildHasher>(
+    query: &HashMap<String, String, S>,
+) -> Vec<(String, String)> {
+    let mut pairs: Vec<_> = query.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    pairs.sort();
+    pairs
+}
+
+fn url_for(base_url: &str, path: &str, query: &[(String, String)]) -> String {
+    if query.is_empty() {
+        return format!("{base_url}{path}");
+    }
+    let query: Vec<String> = query
+        .iter()
+        .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+        .collect();
+    format!("{base_url}{path}?{}", query.join("&"))
+}
+
+fn header_value(headers: &HashMap<String, Vec<String>>, name: &str) -> Option<String> {
+    headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .and_then(|(_, values)| values.first().cloned())
+}
+
+fn name_value(name: &str, value: &str) -> HarNameValue {
+    HarNameValue {
+        name: name.to_string(),
+        value: value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn journal_entry(path: &str, query: &[(&str, &str)], timestamp: &str) -> RecordedRequest {
+        RecordedRequest {
+            request_from: "127.0.0.1:5000".to_string(),
+            method: "GET".to_string(),
+            path: path.to_string(),
+            query: query
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            headers: HashMap::from([("Accept".to_string(), vec!["*/*".to_string()])]),
+            body: None,
+            mode: ResponseMode::Text,
+            timestamp: timestamp.to_string(),
+            tls: None,
+        }
+    }
+
+    fn saved(
+        path: &str,
+        query: Option<&str>,
+        body: &[u8],
+    ) -> (RequestSignature, Vec<RecordedResponse>) {
+        (
+            RequestSignature::new("GET", path, query, &[]),
+            vec![RecordedResponse {
+                status: 200,
+                headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                body: body.to_vec(),
+                latency_ms: Some(12),
+                timestamp_secs: 1_700_000_000,
+                body_file: None,
+            }],
+        )
+    }
+
+    #[test]
+    fn journal_entries_pair_with_saved_responses() {
+        let requests = [
+            journal_entry("/users", &[("page", "2")], "2026-01-01T00:00:01+00:00"),
+            journal_entry("/health", &[], "2026-01-01T00:00:02+00:00"),
+        ];
+        let har = build_har(
+            "http://localhost:4545",
+            &requests,
+            vec![saved("/users", Some("page=2"), br#"{"ok":true}"#)],
+        );
+        let entries = &har.log.entries;
+        assert_eq!(har.log.version, "1.2");
+        assert_eq!(entries.len(), 2);
+
+        assert_eq!(entries[0].request.url, "http://localhost:4545/users?page=2");
+        assert_eq!(entries[0].response.status, 200);
+        assert_eq!(entries[0].response.status_text, "OK");
+        assert_eq!(entries[0].response.content.mime_type, "application/json");
+        assert_eq!(
+            entries[0].response.content.text.as_deref(),
+            Some(r#"{"ok":true}"#)
+        );
+        assert_eq!(entries[0].time, 12.0);
+
+        // Served by a stub: no saved response to pair with.
+        assert_eq!(entries[1].response.status, 0);
+        assert!(entries[1].comment.is_some());
+    }
+
+    #[test]
+    fn unclaimed_saved_responses_become_entries() {
+        let har = build_har(
+            "https://localhost:8443",
+            &[],
+            vec![saved("/blob", None, &[0xff, 0xfe, 0x00])],
+        );
+        let entry = &har.log.entries[0];
+        assert_eq!(entry.request.url, "https://localhost:8443/blob");
+        assert_eq!(entry.response.content.encoding.as_deref(), Some("base64"));
+        assert_eq!(entry.response.content.text.as_deref(), Some("//4A"));
+        assert_eq!(entry.started_date_time, "2023-11-14T22:13:20+00:00");
+
+        let json = serde_json::to_value(&har).unwrap();
+        assert!(json["log"]["entries"][0]["response"]["redirectURL"].is_string());
+        assert!(json["log"]["entries"][0]["startedDateTime"].is_string());
+    }
+
+    fn har_entry(method: &str, url: &str, status: u16, body: &str) -> HarEntry {
+        HarEntry {
+            started_date_time: "2026-01-01T00:00:00+00:00".to_string(),
+            time: 0.0,
+            request: HarRequest {
+                method: method.to_string(),
+                url: url.to_string(),
+                http_version: HTTP_VERSION.to_string(),
+                headers: vec![name_value("X-Tenant", "acme"), name_value("Accept", "*/*")],
+                query_string: Vec::new(),
+                post_data: None,
+                headers_size: -1,
+                body_size: 0,
+            },
+            response: HarResponse {
+                status,
+                status_text: String::new(),
+                http_version: HTTP_VERSION.to_string(),
+                headers: vec![
+                    name_value("Content-Type", "application/json"),
+                    name_value("Content-Encoding", "gzip"),
+                ],
+                content: HarContent {
+                    size: body.len() as i64,
+                    mime_type: "application/json".to_string(),
+                    text: Some(body.to_string()),
+                    encoding: None,
+                },
+                redirect_url: String::new(),
+                headers_size: -1,
+                body_size: -1,
+            },
+            cache: serde_json::Map::new(),
+            timings: HarTimings {
+                send: 0.0,
+                wait: 0.0,
+                receive: 0.0,
+            },
+            comment: None,
+        }
+    }
+
+    fn har_of(entries: Vec<HarEntry>) -> Har {
+        let mut har = build_har("http://localhost", &[], Vec::new());
+        har.log.entries = entries;
+        har
+    }
+
+    #[test]
+    fn import_generates_equals_predicates_and_merges_repeats() {
+        let har = har_of(vec![
+            har_entry(
+                "get",
+                "https://api.example.com/users?page=2#top",
+                200,
+                "first",
+            ),
+            har_entry("GET", "https://api.example.com/users?page=2", 200, "second"),
+            har_entry("GET", "https://api.example.com", 204, ""),
+            har_entry("GET", "https://api.example.com/aborted", 0, ""),
+        ]);
+        let stubs = stubs_from_har(&har, &["x-tenant".to_string()]);
+        assert_eq!(stubs.len(), 2);
+
+        let first = serde_json::to_value(&stubs[0]).unwrap();
+        let equals = &first["predicates"][0]["equals"];
+        assert_eq!(equals["method"], "GET");
+        assert_eq!(equals["path"], "/users");
+        assert_eq!(equals["query"]["page"], "2");
+        assert_eq!(equals["headers"]["x-tenant"], "acme");
+        assert!(equals["headers"].get("Accept").is_none());
+
+        let responses = first["responses"].as_array().unwrap();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["is"]["body"], "first");
+        assert_eq!(responses[1]["is"]["body"], "second");
+        // The captured body is already decoded; replaying its encoding would corrupt it.
+        let headers = responses[0]["is"]["headers"].as_object().unwrap();
+        assert!(headers.contains_key("Content-Type"));
+        assert!(!headers.contains_key("Content-Encoding"));
+
+        let root = serde_json::to_value(&stubs[1]).unwrap();
+        assert_eq!(root["predicates"][0]["equals"]["path"], "/");
+    }
+
+    #[test]
+    fn exported_recordings_import_back() {
+        let exported = build_har(
+            "http://localhost:4545",
+            &[],
+            vec![saved("/blob", None, &[0xff, 0xfe, 0x00])],
+        );
+        let stubs = stubs_from_har(&exported, &[]);
+        assert_eq!(stubs.len(), 1);
+        let stub = serde_json::to_value(&stubs[0]).unwrap();
+        assert_eq!(stub["predicates"][0]["equals"]["path"], "/blob");
+        assert_eq!(stub["responses"][0]["is"]["_mode"], "binary");
+        assert_eq!(stub["responses"][0]["is"]["body"], "//4A");
+    }
+}
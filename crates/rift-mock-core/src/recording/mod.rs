@@ -16,8 +16,14 @@
 //! - `types` - Response and signature types
 //! - `store` - Recording store implementation
 //! - `stub_generator` - Mountebank stub generation
+//! - `har` - HAR 1.2 export and import of recorded traffic
+//! - `openapi` - OpenAPI 3 inference from recorded traffic
+//! - `postman` - Postman collection export of recorded traffic
 
+pub mod har;
 mod mode;
+pub mod openapi;
+pub mod postman;
 mod proxy_store;
 mod store;
 mod stub_generator;
@@ -28,7 +34,7 @@ pub use mode::ProxyMode;
 pub use proxy_store::{
     ClaimOutcome, ClaimToken, LocalProxyStore, ProxyRecordingStore, ProxyStoreError,
 };
-pub use store::RecordingStore;
+pub use store::{EvictionPolicy, RecordingStore};
 #[allow(unused_imports)]
 pub use stub_generator::generate_stub;
 pub use types::{RecordedResponse, RequestSignature};
@@ -51,6 +57,7 @@ where
         body,
         latency_ms: Some(latency_ms),
         timestamp_secs: crate::util::unix_timestamp(),
+        body_file: None,
     };
 
     store.record(signature, response);
@@ -0,0 +1,521 @@
+//! OpenAPI 3 inference from recorded traffic.
+//!
+//! Reverse-engineers a document from the HAR view of an imposter that `/export` serves, so the
+//! journal/proxy-store pairing lives in one place. Every entry contributes its path, method,
+//! query parameters, request body, and response. Path segments that look like identifiers (all
+//! digits, or a UUID) become path parameters, so `/users/1` and `/users/2` describe one
+//! `/users/{id}` operation. JSON bodies fold into one schema per operation, status, and content
+//! type: a property is `required` only when every sample carried it, and the first sample is kept
+//! as the `example`. Other bodies are described by their content type alone.
+
+use super::har::{Har, HarEntry, split_url};
+use crate::imposter::parse_query_string;
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+
+const OPENAPI_VERSION: &str = "3.0.3";
+
+/// Observations for one `path template × method`.
+#[derive(Default)]
+struct Operation {
+    samples: usize,
+    query: BTreeMap<String, ParamSamples>,
+    request_body: BTreeMap<String, BodySamples>,
+    /// Status → content type → body. A status with no body maps to an empty content map.
+    responses: BTreeMap<u16, BTreeMap<String, BodySamples>>,
+}
+
+#[derive(Default)]
+struct ParamSamples {
+    seen: usize,
+    non_integer: bool,
+}
+
+#[derive(Default)]
+struct BodySamples {
+    /// Merged schema of the JSON samples; `None` while none parsed.
+    schema: Option<Value>,
+    example: Option<Value>,
+}
+
+impl BodySamples {
+    fn add(&mut self, mime: &str, text: &str) {
+        if !mime.contains("json") {
+            return;
+        }
+        let Ok(value) = serde_json::from_str::<Value>(text) else {
+            return;
+        };
+        let schema = infer_schema(&value);
+        self.schema = Some(match self.schema.take() {
+            Some(existing) => merge_schemas(existing, schema),
+            None => schema,
+        });
+        self.example.get_or_insert(value);
+    }
+
+    fn to_media_type(&self, mime: &str) -> Value {
+        let mut media = Map::new();
+        let schema = match &self.schema {
+            Some(schema) => finalize(schema.clone()),
+            None if mime.starts_with("text/") => json!({ "type": "string" }),
+            None => json!({ "type": "string", "format": "binary" }),
+        };
+        media.insert("schema".to_string(), schema);
+        if let Some(example) = &self.example {
+            media.insert("example".to_string(), example.clone());
+        }
+        Value::Object(media)
+    }
+}
+
+/// Observations for one path template: its operations and whether each path parameter only
+/// ever held integers.
+#[derive(Default)]
+struct PathItem {
+    params: BTreeMap<String, bool>,
+    operations: BTreeMap<String, Operation>,
+}
+
+/// Infer an OpenAPI 3 document titled `title` from `har`'s entries.
+#[must_use]
+pub fn openapi_from_har(har: &Har, title: &str) -> Value {
+    let mut paths: BTreeMap<String, PathItem> = BTreeMap::new();
+    for entry in &har.log.entries {
+        let (path, query) = split_url(&entry.request.url);
+        let (template, params) = template_path(path);
+        let item = paths.entry(template).or_default();
+        for (name, integer) in params {
+            *item.params.entry(name).or_insert(true) &= integer;
+        }
+        let operation = item
+            .operations
+            .entry(entry.request.method.to_lowercase())
+            .or_default();
+        record(operation, entry, query);
+    }
+
+    let paths: Map<String, Value> = paths
+        .into_iter()
+        .map(|(template, item)| (template, path_item_json(item)))
+        .collect();
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": title,
+            "version": "1.0.0",
+            "description": format!(
+                "Inferred by Rift from {} recorded exchange(s).",
+                har.log.entries.len()
+            ),
+        },
+        "paths": paths,
+    })
+}
+
+fn record(operation: &mut Operation, entry: &HarEntry, query: Option<&str>) {
+    operation.samples += 1;
+    for (name, value) in parse_query_string(query.unwrap_or("")) {
+        let param = operation.query.entry(name).or_default();
+        param.seen += 1;
+        param.non_integer |= value.parse::<i64>().is_err();
+    }
+
+    if let Some(post) = &entry.request.post_data
+        && !post.text.is_empty()
+    {
+        let mime = media_type(&post.mime_type, &post.text);
+        operation
+            .request_body
+            .entry(mime.clone())
+            .or_default()
+            .add(&mime, &post.text);
+    }
+
+    let response = &entry.response;
+    // Status 0 is HAR for "no response": the operation exists, but has nothing to describe.
+    if response.status == 0 {
+        return;
+    }
+    let contents = operation.responses.entry(response.status).or_default();
+    let text = response.content.text.as_deref().unwrap_or_default();
+    if !text.is_empty() {
+        let mime = if response.content.encoding.as_deref() == Some("base64") {
+            media_type(&response.content.mime_type, "")
+        } else {
+            media_type(&response.content.mime_type, text)
+        };
+        contents.entry(mime.clone()).or_default().add(&mime, text);
+    }
+}
+
+/// The media type of a body: the declared one without parameters, else JSON when the body parses
+/// as JSON, else an opaque byte stream.
+fn media_type(declared: &str, text: &str) -> String {
+    let declared = declared.split(';').next().unwrap_or_default().trim();
+    if !declared.is_empty() {
+        declared.to_ascii_lowercase()
+    } else if serde_json::from_str::<Value>(text).is_ok() {
+        "application/json".to_string()
+    } else {
+        "application/octet-stream".to_string()
+    }
+}
+
+/// Replace identifier-like segments with `{id}`, `{id2}`, … and report each parameter with
+/// whether its sample was an integer.
+fn template_path(path: &str) -> (String, Vec<(String, bool)>) {
+    let mut params = Vec::new();
+    let segments: Vec<String> = path
+        .split('/')
+        .map(|segment| {
+            let integer = !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit());
+            if !integer && uuid::Uuid::try_parse(segment).is_err() {
+                return segment.to_string();
+            }
+            let name = match params.len() {
+                0 => "id".to_string(),
+                n => format!("id{}", n + 1),
+            };
+            let templated = format!("{{{name}}}");
+            params.push((name, integer));
+            templated
+        })
+        .collect();
+    (segments.join("/"), params)
+}
+
+fn path_item_json(item: PathItem) -> Value {
+    let mut json = Map::new();
+    if !item.params.is_empty() {
+        let params: Vec<Value> = item
+            .params
+            .iter()
+            .map(|(name, integer)| {
+                json!({
+                    "name": name,
+                    "in": "path",
+                    "required": true,
+                    "schema": scalar_schema(*integer),
+                })
+            })
+            .collect();
+        json.insert("parameters".to_string(), Value::Array(params));
+    }
+    for (method, operation) in item.operations {
+        json.insert(method, operation_json(operation));
+    }
+    Value::Object(json)
+}
+
+fn operation_json(operation: Operation) -> Value {
+    let mut json = Map::new();
+    if !operation.query.is_empty() {
+        let params: Vec<Value> = operation
+            .query
+            .iter()
+            .map(|(name, samples)| {
+                json!({
+                    "name": name,
+                    "in": "query",
+                    "required": samples.seen == operation.samples,
+                    "schema": scalar_schema(!samples.non_integer),
+                })
+            })
+            .collect();
+        json.insert("parameters".to_string(), Value::Array(params));
+    }
+    if !operation.request_body.is_empty() {
+        json.insert(
+            "requestBody".to_string(),
+            json!({ "content": content_json(&operation.request_body) }),
+        );
+    }
+
+    let mut responses = Map::new();
+    for (status, contents) in &operation.responses {
+        let description = hyper::StatusCode::from_u16(*status)
+            .ok()
+            .and_then(|s| s.canonical_reason())
+            .map_or_else(|| format!("Status {status}"), str::to_string);
+        let mut response = Map::new();
+        response.insert("description".to_string(), description.into());
+        if !contents.is_empty() {
+            response.insert("content".to_string(), content_json(contents));
+        }
+        responses.insert(status.to_string(), Value::Object(response));
+    }
+    // `responses` must not be empty; an operation seen only without a response still needs one.
+    if responses.is_empty() {
+        responses.insert(
+            "default".to_string(),
+            json!({ "description": "No recorded response" }),
+        );
+    }
+    json.insert("responses".to_string(), Value::Object(responses));
+    Value::Object(json)
+}
+
+fn content_json(contents: &BTreeMap<String, BodySamples>) -> Value {
+    Value::Object(
+        contents
+            .iter()
+            .map(|(mime, samples)| (mime.clone(), samples.to_media_type(mime)))
+            .collect(),
+    )
+}
+
+fn scalar_schema(integer: bool) -> Value {
+    if integer {
+        json!({ "type": "integer" })
+    } else {
+        json!({ "type": "string" })
+    }
+}
+
+/// The schema of one JSON sample. Empty arrays leave `items` unset so a later non-empty sample
+/// can supply it; [`finalize`] fills in whatever is still missing.
+fn infer_schema(value: &Value) -> Value {
+    match value {
+        Value::Null => json!({ "nullable": true }),
+        Value::Bool(_) => json!({ "type": "boolean" }),
+        Value::Number(n) if n.is_f64() => json!({ "type": "number" }),
+        Value::Number(_) => json!({ "type": "integer" }),
+        Value::String(_) => json!({ "type": "string" }),
+        Value::Array(items) => {
+            let mut schema = json!({ "type": "array" });
+            if let Some(merged) = items.iter().map(infer_schema).reduce(merge_schemas) {
+                schema["items"] = merged;
+            }
+            schema
+        }
+        Value::Object(fields) => {
+            let properties: Map<String, Value> = fields
+                .iter()
+                .map(|(name, value)| (name.clone(), infer_schema(value)))
+                .collect();
+            let required: Vec<Value> = fields.keys().cloned().map(Value::String).collect();
+            json!({ "type": "object", "properties": properties, "required": required })
+        }
+    }
+}
+
+/// Widen two schemas to one describing both samples: object properties union (required only if
+/// required in both), integer and number widen to number, `null` makes the other nullable, and
+/// any other disagreement widens to the empty (any-value) schema.
+fn merge_schemas(a: Value, b: Value) -> Value {
+    let (Value::Object(mut a), Value::Object(mut b)) = (a, b) else {
+        return json!({});
+    };
+    let nullable = a.remove("nullable").is_some() | b.remove("nullable").is_some();
+    let mut merged = match (a.get("type").cloned(), b.get("type").cloned()) {
+        (None, _) if a.is_empty() && nullable => b,
+        (_, None) if b.is_empty() && nullable => a,
+        (Some(ta), Some(tb)) if ta == tb && ta == "object" => merge_objects(a, b),
+        (Some(ta), Some(tb)) if ta == tb && ta == "array" => {
+            let items = match (a.remove("items"), b.remove("items")) {
+                (Some(x), Some(y)) => Some(merge_schemas(x, y)),
+                (x, y) => x.or(y),
+            };
+            if let Some(items) = items {
+                a.insert("items".to_string(), items);
+            }
+            a
+        }
+        (Some(ta), Some(tb)) if ta == tb => a,
+        (Some(ta), Some(tb)) if [&ta, &tb].iter().all(|t| *t == "integer" || *t == "number") => {
+            Map::from_iter([("type".to_string(), json!("number"))])
+        }
+        _ => Map::new(),
+    };
+    if nullable {
+        merged.insert("nullable".to_string(), Value::Bool(true));
+    }
+    Value::Object(merged)
+}
+
+fn merge_objects(mut a: Map<String, Value>, mut b: Map<String, Value>) -> Map<String, Value> {
+    let names = |schema: &Map<String, Value>| -> Vec<String> {
+        schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect()
+    };
+    let b_required = names(&b);
+    let required: Vec<Value> = names(&a)
+        .into_iter()
+        .filter(|name| b_required.contains(name))
+        .map(Value::String)
+        .collect();
+
+    let mut properties = match a.remove("properties") {
+        Some(Value::Object(p)) => p,
+        _ => Map::new(),
+    };
+    if let Some(Value::Object(other)) = b.remove("properties") {
+        for (name, schema) in other {
+            let merged = match properties.remove(&name) {
+                Some(existing) => merge_schemas(existing, schema),
+                None => schema,
+            };
+            properties.insert(name, merged);
+        }
+    }
+    a.insert("properties".to_string(), Value::Object(properties));
+    a.insert("required".to_string(), Value::Array(required));
+    a
+}
+
+/// Make an inferred schema valid OpenAPI: arrays get `items` (empty when no sample had any), and
+/// objects drop an empty `required` (which OpenAPI 3.0 forbids).
+fn finalize(schema: Value) -> Value {
+    let Value::Object(mut schema) = schema else {
+        return schema;
+    };
+    if schema.get("type").is_some_and(|t| t == "array") {
+        let items = schema.remove("items").map_or_else(|| json!({}), finalize);
+        schema.insert("items".to_string(), items);
+    }
+    if let Some(Value::Object(properties)) = schema.remove("properties") {
+        let properties: Map<String, Value> = properties
+            .into_iter()
+            .map(|(name, property)| (name, finalize(property)))
+            .collect();
+        schema.insert("properties".to_string(), Value::Object(properties));
+    }
+    if schema
+        .get("required")
+        .and_then(Value::as_array)
+        .is_some_and(Vec::is_empty)
+    {
+        schema.remove("required");
+    }
+    Value::Object(schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::har::build_har;
+    use crate::recording::{RecordedResponse, RequestSignature};
+
+    fn saved(
+        method: &str,
+        path: &str,
+        query: Option<&str>,
+        status: u16,
+        body: &str,
+    ) -> Vec<(RequestSignature, Vec<RecordedResponse>)> {
+        vec![(
+            RequestSignature::new(method, path, query, &[]),
+            vec![RecordedResponse {
+                status,
+                headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                body: body.as_bytes().to_vec(),
+                latency_ms: None,
+                timestamp_secs: 1_700_000_000,
+                body_file: None,
+            }],
+        )]
+    }
+
+    fn har(saved: Vec<(RequestSignature, Vec<RecordedResponse>)>) -> Har {
+        build_har("http://localhost:4545", &[], saved)
+    }
+
+    #[test]
+    fn identifier_segments_become_path_parameters() {
+        assert_eq!(
+            template_path("/users/42/orders/3f2504e0-4f89-11d3-9a0c-0305e82c3301"),
+            (
+                "/users/{id}/orders/{id2}".to_string(),
+                vec![("id".to_string(), true), ("id2".to_string(), false)]
+            )
+        );
+        assert_eq!(template_path("/v1/health").0, "/v1/health");
+    }
+
+    #[test]
+    fn samples_fold_into_one_operation_schema() {
+        let mut entries = saved(
+            "GET",
+            "/users/1",
+            Some("page=1&q=ada"),
+            200,
+            r#"{"id":1,"name":"Ada","tags":[]}"#,
+        );
+        entries.extend(saved(
+            "GET",
+            "/users/2",
+            Some("page=2"),
+            200,
+            r#"{"id":2.5,"name":null,"tags":["x"],"admin":true}"#,
+        ));
+        entries.extend(saved(
+            "GET",
+            "/users/3",
+            None,
+            404,
+            r#"{"error":"not found"}"#,
+        ));
+        let doc = openapi_from_har(&har(entries), "Users");
+
+        assert_eq!(doc["openapi"], OPENAPI_VERSION);
+        assert_eq!(doc["info"]["title"], "Users");
+        let item = &doc["paths"]["/users/{id}"];
+        assert_eq!(item["parameters"][0]["name"], "id");
+        assert_eq!(item["parameters"][0]["schema"]["type"], "integer");
+
+        let get = &item["get"];
+        let params = get["parameters"].as_array().unwrap();
+        assert_eq!(params[0]["name"], "page");
+        assert_eq!(params[0]["required"], false);
+        assert_eq!(params[0]["schema"]["type"], "integer");
+        assert_eq!(params[1]["name"], "q");
+        assert_eq!(params[1]["schema"]["type"], "string");
+
+        let ok = &get["responses"]["200"];
+        assert_eq!(ok["description"], "OK");
+        let schema = &ok["content"]["application/json"]["schema"];
+        assert_eq!(schema["properties"]["id"]["type"], "number");
+        assert_eq!(schema["properties"]["name"]["type"], "string");
+        assert_eq!(schema["properties"]["name"]["nullable"], true);
+        assert_eq!(schema["properties"]["tags"]["items"]["type"], "string");
+        assert_eq!(schema["required"], json!(["id", "name", "tags"]));
+        assert_eq!(ok["content"]["application/json"]["example"]["id"], 1);
+
+        assert_eq!(get["responses"]["404"]["description"], "Not Found");
+    }
+
+    #[test]
+    fn operations_without_responses_get_a_default() {
+        let doc_har = build_har(
+            "http://localhost:4545",
+            &[crate::imposter::RecordedRequest {
+                request_from: "127.0.0.1:5000".to_string(),
+                method: "POST".to_string(),
+                path: "/orders".to_string(),
+                query: Default::default(),
+                headers: Default::default(),
+                body: Some(r#"{"sku":"a1","qty":2}"#.to_string()),
+                mode: crate::imposter::ResponseMode::Text,
+                timestamp: "2026-01-01T00:00:00+00:00".to_string(),
+                tls: None,
+            }],
+            Vec::new(),
+        );
+        let doc = openapi_from_har(&doc_har, "Orders");
+        let post = &doc["paths"]["/orders"]["post"];
+        assert_eq!(
+            post["requestBody"]["content"]["application/json"]["schema"]["properties"]["qty"]["type"],
+            "integer"
+        );
+        assert_eq!(
+            post["responses"]["default"]["description"],
+            "No recorded response"
+        );
+    }
+}
@@ -0,0 +1,325 @@
+//! Postman collection (v2.1) export of recorded traffic.
+//!
+//! Built from the HAR view of the recordings, like the OpenAPI inference, so the journal/proxy
+//! store pairing lives in one place. Every HAR entry becomes one request item, in capture order,
+//! so a captured flow replays top to bottom in Postman's collection runner. An entry with a
+//! recorded response keeps it as the item's saved example.
+//!
+//! Request URLs are written against a `{{baseUrl}}` variable rather than the imposter's address,
+//! so the same collection can be pointed at the mock, a staging host, or the real service by
+//! switching environments. A collection spanning several imposters gets one variable per origin,
+//! `{{baseUrl_<port>}}`. The variables are declared on the collection with the recorded origin as
+//! their value and also returned as a separate Postman environment.
+
+use super::har::{Har, HarEntry, HarNameValue, split_url};
+use serde_json::{Value, json};
+
+const COLLECTION_SCHEMA: &str =
+    "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+/// Request headers Postman computes itself; a recorded value would be stale on replay.
+const COMPUTED_REQUEST_HEADERS: &[&str] = &["host", "content-length", "connection"];
+
+/// A Postman collection and the environment declaring its URL variables.
+#[derive(Debug, Clone)]
+pub struct PostmanExport {
+    pub collection: Value,
+    pub environment: Value,
+}
+
+/// Build a collection named `name` from `har`'s entries, with an environment of the same name.
+#[must_use]
+pub fn postman_from_har(har: &Har, name: &str) -> PostmanExport {
+    let mut origins: Vec<&str> = Vec::new();
+    for entry in &har.log.entries {
+        let origin = origin_of(&entry.request.url);
+        if !origins.contains(&origin) {
+            origins.push(origin);
+        }
+    }
+    let variables: Vec<(String, &str)> = match origins.as_slice() {
+        [] => Vec::new(),
+        [only] => vec![("baseUrl".to_string(), *only)],
+        many => many
+            .iter()
+            .enumerate()
+            .map(|(index, origin)| {
+                let suffix = origin
+                    .rsplit_once(':')
+                    .map(|(_, port)| port)
+                    .filter(|port| port.chars().all(|c| c.is_ascii_digit()))
+                    .map_or_else(|| (index + 1).to_string(), str::to_string);
+                (format!("baseUrl_{suffix}"), *origin)
+            })
+            .collect(),
+    };
+    let variable_for = |url: &str| {
+        let origin = origin_of(url);
+        variables
+            .iter()
+            .find(|(_, value)| *value == origin)
+            .map_or("baseUrl", |(key, _)| key.as_str())
+    };
+
+    let items: Vec<Value> = har
+        .log
+        .entries
+        .iter()
+        .map(|entry| item(entry, variable_for(&entry.request.url)))
+        .collect();
+
+    let collection = json!({
+        "info": {
+            "_postman_id": uuid::Uuid::new_v4().to_string(),
+            "name": name,
+            "description": "Recorded by Rift",
+            "schema": COLLECTION_SCHEMA,
+        },
+        "item": items,
+        "variable": variables
+            .iter()
+            .map(|(key, value)| json!({ "key": key, "value": value }))
+            .collect::<Vec<_>>(),
+    });
+    let environment = json!({
+        "id": uuid::Uuid::new_v4().to_string(),
+        "name": name,
+        "values": variables
+            .iter()
+            .map(|(key, value)| {
+                json!({ "key": key, "value": value, "type": "default", "enabled": true })
+            })
+            .collect::<Vec<_>>(),
+        "_postman_variable_scope": "environment",
+    });
+    PostmanExport {
+        collection,
+        environment,
+    }
+}
+
+/// One request item, with the recorded response as its example when there is one.
+fn item(entry: &HarEntry, variable: &str) -> Value {
+    let (path, _) = split_url(&entry.request.url);
+    let request = request(entry, variable);
+    let mut item = json!({
+        "name": format!("{} {path}", entry.request.method),
+        "request": request,
+        "response": [],
+    });
+    if entry.response.status != 0 {
+        let response = &entry.response;
+        let body = match (&response.content.text, &response.content.encoding) {
+            (Some(text), None) => text.clone(),
+            // Postman examples hold text only; say what was there instead.
+            (Some(_), Some(_)) => format!("<{} bytes of binary content>", response.content.size),
+            (None, _) => String::new(),
+        };
+        item["response"] = json!([{
+            "name": format!("{} {}", response.status, response.status_text).trim_end().to_string(),
+            "originalRequest": request,
+            "status": response.status_text,
+            "code": response.status,
+            "_postman_previewlanguage": language(&response.content.mime_type),
+            "header": headers(&response.headers, &[]),
+            "body": body,
+        }]);
+    }
+    item
+}
+
+fn request(entry: &HarEntry, variable: &str) -> Value {
+    let (path, query) = split_url(&entry.request.url);
+    let mut request = json!({
+        "method": entry.request.method,
+        "header": headers(&entry.request.headers, COMPUTED_REQUEST_HEADERS),
+        "url": {
+            "raw": match query {
+                Some(query) => format!("{{{{{variable}}}}}{path}?{query}"),
+                None => format!("{{{{{variable}}}}}{path}"),
+            },
+            "host": [format!("{{{{{variable}}}}}")],
+            "path": path.split('/').filter(|s| !s.is_empty()).collect::<Vec<_>>(),
+            "query": entry
+                .request
+                .query_string
+                .iter()
+                .map(|q| json!({ "key": q.name, "value": q.value }))
+                .collect::<Vec<_>>(),
+        },
+    });
+    if let Some(post) = &entry.request.post_data {
+        request["body"] = json!({
+            "mode": "raw",
+            "raw": post.text,
+            "options": { "raw": { "language": language(&post.mime_type) } },
+        });
+        if let Some(comment) = &post.comment {
+            request["description"] = json!(comment);
+        }
+    }
+    request
+}
+
+fn headers(headers: &[HarNameValue], skip: &[&str]) -> Vec<Value> {
+    headers
+        .iter()
+        .filter(|h| !skip.iter().any(|s| h.name.eq_ignore_ascii_case(s)))
+        .map(|h| json!({ "key": h.name, "value": h.value }))
+        .collect()
+}
+
+/// Postman's body language for a content type.
+fn language(mime_type: &str) -> &'static str {
+    if mime_type.contains("json") {
+        "json"
+    } else if mime_type.contains("xml") {
+        "xml"
+    } else if mime_type.contains("html") {
+        "html"
+    } else if mime_type.contains("javascript") {
+        "javascript"
+    } else {
+        "text"
+    }
+}
+
+/// `scheme://host[:port]` of an absolute URL; empty for a relative one.
+fn origin_of(url: &str) -> &str {
+    let Some(scheme_end) = url.find("://") else {
+        return "";
+    };
+    let rest = &url[scheme_end + 3..];
+    let end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    &url[..scheme_end + 3 + end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::imposter::RecordedRequest;
+    use crate::recording::har::build_har;
+    use crate::recording::{RecordedResponse, RequestSignature};
+    use std::collections::HashMap;
+
+    fn journaled(method: &str, path: &str, body: Option<&str>, timestamp: &str) -> RecordedRequest {
+        RecordedRequest {
+            request_from: "127.0.0.1:5000".to_string(),
+            method: method.to_string(),
+            path: path.to_string(),
+            query: HashMap::from([("page".to_string(), "2".to_string())]),
+            headers: HashMap::from([
+                ("Host".to_string(), vec!["localhost:4545".to_string()]),
+                (
+                    "Content-Type".to_string(),
+                    vec!["application/json".to_string()],
+                ),
+            ]),
+            body: body.map(str::to_string),
+            mode: Default::default(),
+            timestamp: timestamp.to_string(),
+            tls: None,
+        }
+    }
+
+    #[test]
+    fn each_recorded_request_becomes_an_item_in_capture_order() {
+        let har = build_har(
+            "http://localhost:4545",
+            &[
+                journaled(
+                    "POST",
+                    "/orders",
+                    Some("{\"sku\":1}"),
+                    "2026-01-01T00:00:01Z",
+                ),
+                journaled("GET", "/orders/7", None, "2026-01-01T00:00:02Z"),
+            ],
+            vec![(
+                RequestSignature::new("POST", "/orders", Some("page=2"), &[]),
+                vec![RecordedResponse {
+                    status: 201,
+                    headers: vec![("content-type".to_string(), "application/json".to_string())],
+                    body: b"{\"id\":7}".to_vec(),
+                    latency_ms: None,
+                    timestamp_secs: 0,
+                    body_file: None,
+                }],
+            )],
+        );
+
+        let export = postman_from_har(&har, "orders");
+        let collection = &export.collection;
+        assert_eq!(collection["info"]["schema"], COLLECTION_SCHEMA);
+        assert_eq!(
+            collection["variable"],
+            json!([{ "key": "baseUrl", "value": "http://localhost:4545" }])
+        );
+        let items = collection["item"].as_array().unwrap();
+        assert_eq!(items.len(), 2);
+
+        let create = &items[0];
+        assert_eq!(create["name"], "POST /orders");
+        assert_eq!(create["request"]["url"]["raw"], "{{baseUrl}}/orders?page=2");
+        assert_eq!(create["request"]["url"]["path"], json!(["orders"]));
+        assert_eq!(create["request"]["body"]["raw"], "{\"sku\":1}");
+        assert_eq!(
+            create["request"]["body"]["options"]["raw"]["language"],
+            "json"
+        );
+        let header_names: Vec<&str> = create["request"]["header"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|h| h["key"].as_str().unwrap())
+            .collect();
+        assert_eq!(header_names, ["Content-Type"], "Host is left to Postman");
+        assert_eq!(create["response"][0]["code"], 201);
+        assert_eq!(create["response"][0]["body"], "{\"id\":7}");
+
+        let fetch = &items[1];
+        assert_eq!(fetch["name"], "GET /orders/7");
+        assert_eq!(
+            fetch["response"],
+            json!([]),
+            "served by a stub, nothing recorded"
+        );
+
+        assert_eq!(export.environment["name"], "orders");
+        assert_eq!(
+            export.environment["values"][0]["value"],
+            "http://localhost:4545"
+        );
+    }
+
+    #[test]
+    fn several_imposters_get_a_variable_per_origin() {
+        let mut har = build_har(
+            "http://localhost:4545",
+            &[journaled("GET", "/a", None, "2026-01-01T00:00:01Z")],
+            Vec::new(),
+        );
+        har.log.entries.extend(
+            build_har(
+                "http://localhost:4546",
+                &[journaled("GET", "/b", None, "2026-01-01T00:00:02Z")],
+                Vec::new(),
+            )
+            .log
+            .entries,
+        );
+
+        let collection = postman_from_har(&har, "all").collection;
+        let keys: Vec<&str> = collection["variable"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v["key"].as_str().unwrap())
+            .collect();
+        assert_eq!(keys, ["baseUrl_4545", "baseUrl_4546"]);
+        assert_eq!(
+            collection["item"][1]["request"]["url"]["raw"],
+            "{{baseUrl_4546}}/b?page=2"
+        );
+    }
+}
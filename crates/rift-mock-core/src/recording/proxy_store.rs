@@ -114,6 +114,19 @@ pub trait ProxyRecordingStore: Send + Sync {
     /// Clears all saved proxy responses for a port. Backs the DELETE endpoint for saved
     /// proxy responses and the manager's port reclaim on imposter deletion.
     fn clear(&self, port: u16);
+
+    /// Every saved response for a port, for `--datadir` persistence. A backend that is already
+    /// durable (or shared between processes) can keep the default, which persists nothing.
+    fn export(&self, port: u16) -> Vec<(RequestSignature, Vec<RecordedResponse>)> {
+        let _ = port;
+        Vec::new()
+    }
+
+    /// Re-seed a port with responses from a previous [`export`](Self::export), replacing any
+    /// saved under the same signature. The default ignores them.
+    fn import(&self, port: u16, entries: Vec<(RequestSignature, Vec<RecordedResponse>)>) {
+        let _ = (port, entries);
+    }
 }
 
 /// Per-port recording state: the responses map plus the in-flight claim gate.
@@ -302,6 +315,32 @@ impl ProxyRecordingStore for LocalProxyStore {
             state.pending.lock().clear();
         }
     }
+
+    fn export(&self, port: u16) -> Vec<(RequestSignature, Vec<RecordedResponse>)> {
+        let Some(state) = self.ports.read().get(&port).cloned() else {
+            return Vec::new();
+        };
+        let responses = state.responses.read();
+        responses
+            .iter()
+            .map(|(sig, recorded)| (sig.clone(), recorded.clone()))
+            .collect()
+    }
+
+    fn import(&self, port: u16, entries: Vec<(RequestSignature, Vec<RecordedResponse>)>) {
+        let state = self.slot(port);
+        let mut responses = state.responses.write();
+        for (sig, recorded) in entries {
+            if responses.len() >= MAX_TOTAL_SIGNATURES && !responses.contains_key(&sig) {
+                tracing::debug!(
+                    max = MAX_TOTAL_SIGNATURES,
+                    "Recording store full, dropping restored recording"
+                );
+                continue;
+            }
+            responses.insert(sig, recorded);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -320,6 +359,7 @@ mod tests {
             body: body.as_bytes().to_vec(),
             latency_ms: Some(10),
             timestamp_secs: unix_timestamp(),
+            body_file: None,
         }
     }
 
@@ -538,4 +578,28 @@ mod tests {
             "overflow signature dropped once the port is full"
         );
     }
+
+    // export/import round-trip a port's recordings (the `--datadir` restore path) without
+    // touching other ports.
+    #[test]
+    fn local_export_import_round_trips_one_port() {
+        let store = LocalProxyStore::new(ProxyMode::ProxyOnce);
+        let s = sig("/a");
+        let t = claim_token(store.try_claim(1, &s).unwrap());
+        store.record(1, s.clone(), t, resp(200, "ok")).unwrap();
+        let t = claim_token(store.try_claim(2, &s).unwrap());
+        store.record(2, s.clone(), t, resp(500, "other")).unwrap();
+
+        let exported = store.export(1);
+        assert_eq!(exported.len(), 1);
+
+        let restored = LocalProxyStore::new(ProxyMode::ProxyOnce);
+        restored.import(1, exported);
+        assert_eq!(restored.lookup(1, &s).unwrap().status, 200);
+        assert!(restored.lookup(2, &s).is_none());
+        assert!(matches!(
+            restored.try_claim(1, &s).unwrap(),
+            ClaimOutcome::AlreadyRecorded
+        ));
+    }
 }
@@ -1,12 +1,24 @@
 //! Recording store for proxy responses.
+//!
+//! The store is split into shards by a hash of the request signature, each behind its own lock,
+//! so concurrent `proxyAlways` traffic recording and replaying different signatures does not
+//! serialize on one lock. A signature's recordings and its `proxyOnce` claim live in the same
+//! shard, which keeps [`RecordingStore::should_proxy`]'s check-and-claim atomic with respect to
+//! [`RecordingStore::record`]. Store-wide counts are atomics, so `len` and the size gauges take no
+//! lock at all. Whole-store views (`get_all`, `save_to_file`, `export_as_stubs`) lock one shard at
+//! a time and are therefore not a single point-in-time snapshot.
 
 use super::mode::ProxyMode;
 use super::stub_generator::generate_stub;
 use super::types::{RecordedResponse, RequestSignature};
-use parking_lot::{Mutex, RwLock};
-use std::collections::{HashMap, HashSet};
+use crate::extensions::metrics;
+use parking_lot::{Mutex, RwLock, RwLockWriteGuard};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
+use std::hash::BuildHasher;
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 use tracing::{debug, info};
 
 /// Maximum number of recorded responses per signature (proxyAlways mode)
@@ -15,69 +27,293 @@ const MAX_RECORDINGS_PER_SIGNATURE: usize = 1000;
 /// Maximum total number of unique request signatures to record
 const MAX_TOTAL_SIGNATURES: usize = 10_000;
 
-/// Recording store for proxy responses
-pub struct RecordingStore {
+/// Shortest gap between two sweeps of a shard for TTL-expired signatures, so a busy proxyAlways
+/// store does not rescan every entry on every recorded response.
+const TTL_SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Shards a store is split into unless [`RecordingStore::with_shards`] says otherwise.
+const DEFAULT_SHARDS: usize = 32;
+
+/// Bounds on what a [`RecordingStore`] keeps, from the `recording` config's `maxEntries` and
+/// `ttlSeconds`. The default keeps today's behavior: only the built-in caps apply, and a full store
+/// drops new signatures.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EvictionPolicy {
+    /// Most signatures kept. Recording a new signature into a full store evicts the least
+    /// recently used one (recorded or replayed) instead of dropping the new recording.
+    pub max_entries: Option<usize>,
+    /// A signature neither recorded nor replayed for this long is evicted; under proxyOnce the
+    /// next matching request is proxied and recorded afresh.
+    pub ttl: Option<Duration>,
+}
+
+/// One signature's recordings and when they were last recorded or replayed (milliseconds since
+/// the store's `epoch`), so a replay can refresh it under the read lock.
+#[derive(Debug)]
+struct Entry {
+    /// Oldest first; a deque so dropping the oldest past the per-signature cap is O(1) under
+    /// the shard's write lock.
+    responses: VecDeque<RecordedResponse>,
+    last_used_ms: AtomicU64,
+}
+
+/// The signatures hashing to one shard.
+#[derive(Debug, Default)]
+struct Shard {
     /// Recorded responses by request signature
-    responses: RwLock<HashMap<RequestSignature, Vec<RecordedResponse>>>,
+    responses: RwLock<HashMap<RequestSignature, Entry>>,
     /// Signatures currently being proxied (in-flight), used to prevent
     /// TOCTOU races in proxyOnce mode where multiple concurrent requests
     /// could all see "not yet recorded" and proxy simultaneously.
     pending: Mutex<HashSet<RequestSignature>>,
+    /// When TTL-expired entries were last swept, on the store's `epoch` clock.
+    last_sweep_ms: AtomicU64,
+}
+
+fn shards(count: usize) -> Box<[Shard]> {
+    (0..count).map(|_| Shard::default()).collect()
+}
+
+/// Recording store for proxy responses
+pub struct RecordingStore {
+    /// Recordings and in-flight claims, split by signature hash; a power-of-two count.
+    shards: Box<[Shard]>,
+    /// Picks a signature's shard.
+    hasher: foldhash::fast::RandomState,
+    /// Signatures across all shards, counting ones reserved by a `record` about to insert them.
+    signatures: AtomicUsize,
+    /// Recorded responses across all shards.
+    response_count: AtomicUsize,
     /// Mode-specific behavior
     mode: ProxyMode,
+    eviction: EvictionPolicy,
+    /// Origin of the entries' `last_used_ms` clock.
+    epoch: Instant,
 }
 
 impl RecordingStore {
     pub fn new(mode: ProxyMode) -> Self {
         Self {
-            responses: RwLock::new(HashMap::new()),
-            pending: Mutex::new(HashSet::new()),
+            shards: shards(DEFAULT_SHARDS),
+            hasher: foldhash::fast::RandomState::default(),
+            signatures: AtomicUsize::new(0),
+            response_count: AtomicUsize::new(0),
             mode,
+            eviction: EvictionPolicy::default(),
+            epoch: Instant::now(),
         }
     }
 
+    /// Bound the store by `policy` (max entries with LRU eviction, and a TTL).
+    #[must_use]
+    pub fn with_eviction(mut self, policy: EvictionPolicy) -> Self {
+        self.eviction = policy;
+        self
+    }
+
+    /// Split the store into `count` shards, rounded up to a power of two. One shard puts the
+    /// whole store behind a single lock.
+    #[must_use]
+    pub fn with_shards(mut self, count: usize) -> Self {
+        self.shards = shards(count.max(1).next_power_of_two());
+        self
+    }
+
     /// Get the recording mode
     pub fn mode(&self) -> ProxyMode {
         self.mode
     }
 
+    fn now_ms(&self) -> u64 {
+        self.epoch.elapsed().as_millis() as u64
+    }
+
+    fn shard(&self, signature: &RequestSignature) -> &Shard {
+        let hash = self.hasher.hash_one(signature) as usize;
+        &self.shards[hash & (self.shards.len() - 1)]
+    }
+
+    /// Whether `entry` has outlived the TTL at `now_ms`.
+    fn expired(&self, entry: &Entry, now_ms: u64) -> bool {
+        self.eviction.ttl.is_some_and(|ttl| {
+            now_ms.saturating_sub(entry.last_used_ms.load(Ordering::Relaxed))
+                >= ttl.as_millis() as u64
+        })
+    }
+
+    /// Take a removed `entry` out of the store-wide counts.
+    fn forget(&self, entry: &Entry) {
+        self.signatures.fetch_sub(1, Ordering::AcqRel);
+        self.response_count
+            .fetch_sub(entry.responses.len(), Ordering::Relaxed);
+    }
+
+    /// Count one more signature if that keeps the store within `cap`.
+    fn reserve(&self, cap: usize) -> bool {
+        self.signatures
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < cap).then_some(n + 1)
+            })
+            .is_ok()
+    }
+
+    /// Drop `shard`'s TTL-expired entries, at most once per [`TTL_SWEEP_INTERVAL`].
+    fn sweep_expired(
+        &self,
+        shard: &Shard,
+        store: &mut HashMap<RequestSignature, Entry>,
+        now_ms: u64,
+    ) {
+        if self.eviction.ttl.is_none()
+            || now_ms.saturating_sub(shard.last_sweep_ms.load(Ordering::Relaxed))
+                < TTL_SWEEP_INTERVAL.as_millis() as u64
+        {
+            return;
+        }
+        shard.last_sweep_ms.store(now_ms, Ordering::Relaxed);
+        let mut evicted = 0;
+        store.retain(|_, entry| {
+            let expired = self.expired(entry, now_ms);
+            if expired {
+                self.forget(entry);
+                evicted += 1;
+            }
+            !expired
+        });
+        if evicted > 0 {
+            debug!("Evicted {evicted} expired recordings");
+            metrics::record_recording_evictions("ttl", evicted);
+        }
+    }
+
+    /// Write-lock `shard` with room for `signature`: drop its own expired recordings, then — if it
+    /// is new and the store is full — evict the least recently used signature under `maxEntries`.
+    /// A new signature is counted before the guard is returned, so the caller must insert it.
+    /// Returns `None` when the store is full and has no `maxEntries` policy: the recording is
+    /// dropped, as it always was.
+    fn lock_with_room<'a>(
+        &self,
+        shard: &'a Shard,
+        signature: &RequestSignature,
+        now_ms: u64,
+    ) -> Option<RwLockWriteGuard<'a, HashMap<RequestSignature, Entry>>> {
+        let mut cap = self
+            .eviction
+            .max_entries
+            .map_or(MAX_TOTAL_SIGNATURES, |max| max.max(1));
+        loop {
+            let mut store = shard.responses.write();
+            self.sweep_expired(shard, &mut store, now_ms);
+            if store
+                .get(signature)
+                .is_some_and(|entry| self.expired(entry, now_ms))
+                && let Some(entry) = store.remove(signature)
+            {
+                self.forget(&entry);
+                metrics::record_recording_evictions("ttl", 1);
+            }
+            if store.contains_key(signature) || self.reserve(cap) {
+                return Some(store);
+            }
+            let Some(max_entries) = self.eviction.max_entries else {
+                debug!(
+                    "Recording store full ({} signatures), dropping new recording",
+                    MAX_TOTAL_SIGNATURES
+                );
+                return None;
+            };
+            // Eviction locks the other shards one at a time; never while holding this one.
+            drop(store);
+            if !self.evict_least_recently_used(max_entries) {
+                // Nothing settled to evict: every counted signature is still being inserted.
+                // Go over the bound rather than spin.
+                cap = usize::MAX;
+            }
+        }
+    }
+
+    /// Evict the least recently used signature across all shards. Returns `false` when the
+    /// store holds nothing to evict.
+    fn evict_least_recently_used(&self, max_entries: usize) -> bool {
+        let oldest = self
+            .shards
+            .iter()
+            .filter_map(|shard| {
+                let store = shard.responses.read();
+                store
+                    .iter()
+                    .map(|(sig, entry)| (entry.last_used_ms.load(Ordering::Relaxed), sig))
+                    .min_by_key(|(used, _)| *used)
+                    .map(|(used, sig)| (used, shard, sig.clone()))
+            })
+            .min_by_key(|(used, ..)| *used);
+        let Some((_, shard, oldest)) = oldest else {
+            return false;
+        };
+        // Gone already if another recording evicted it first; either way a slot was freed.
+        if let Some(entry) = shard.responses.write().remove(&oldest) {
+            debug!(
+                "Recording store full ({max_entries} signatures), evicting least recently used {} {}",
+                oldest.method, oldest.path
+            );
+            self.forget(&entry);
+            metrics::record_recording_evictions("capacity", 1);
+        }
+        true
+    }
+
+    /// Publish the store's size to the `rift_recording_store_*` gauges.
+    fn publish_size(&self) {
+        metrics::set_recording_store_size(
+            self.signatures.load(Ordering::Relaxed),
+            self.response_count.load(Ordering::Relaxed),
+        );
+    }
+
     /// Record a response (for proxyOnce/proxyAlways modes)
     pub fn record(&self, signature: RequestSignature, response: RecordedResponse) {
+        let now_ms = self.now_ms();
+        let shard = self.shard(&signature);
         match self.mode {
             ProxyMode::ProxyOnce => {
-                let mut store = self.responses.write();
-                if store.len() >= MAX_TOTAL_SIGNATURES && !store.contains_key(&signature) {
-                    debug!(
-                        "Recording store full ({} signatures), dropping new recording",
-                        MAX_TOTAL_SIGNATURES
-                    );
-                    self.pending.lock().remove(&signature);
+                let Some(mut store) = self.lock_with_room(shard, &signature, now_ms) else {
+                    shard.pending.lock().remove(&signature);
                     return;
-                }
-                store
-                    .entry(signature.clone())
-                    .or_insert_with(|| vec![response]);
+                };
+                store.entry(signature.clone()).or_insert_with(|| {
+                    self.response_count.fetch_add(1, Ordering::Relaxed);
+                    Entry {
+                        responses: VecDeque::from([response]),
+                        last_used_ms: AtomicU64::new(now_ms),
+                    }
+                });
                 // Remove from pending set now that the response is recorded
-                self.pending.lock().remove(&signature);
+                shard.pending.lock().remove(&signature);
+                drop(store);
+                self.publish_size();
             }
             ProxyMode::ProxyAlways => {
-                let mut store = self.responses.write();
-                if store.len() >= MAX_TOTAL_SIGNATURES && !store.contains_key(&signature) {
-                    debug!(
-                        "Recording store full ({} signatures), dropping new recording",
-                        MAX_TOTAL_SIGNATURES
-                    );
+                let Some(mut store) = self.lock_with_room(shard, &signature, now_ms) else {
                     return;
-                }
-                let recordings = store.entry(signature).or_default();
+                };
+                let entry = store.entry(signature).or_insert_with(|| Entry {
+                    responses: VecDeque::new(),
+                    last_used_ms: AtomicU64::new(now_ms),
+                });
+                entry.last_used_ms.store(now_ms, Ordering::Relaxed);
+                let recordings = &mut entry.responses;
                 if recordings.len() >= MAX_RECORDINGS_PER_SIGNATURE {
                     debug!(
                         "Recording limit reached ({} per signature), dropping oldest",
                         MAX_RECORDINGS_PER_SIGNATURE
                     );
-                    recordings.remove(0);
+                    recordings.pop_front();
+                } else {
+                    self.response_count.fetch_add(1, Ordering::Relaxed);
                 }
-                recordings.push(response);
+                recordings.push_back(response);
+                drop(store);
+                self.publish_size();
             }
             ProxyMode::ProxyTransparent => {
                 // Never record
@@ -85,12 +321,16 @@ impl RecordingStore {
         }
     }
 
-    /// Get recorded response for replay
+    /// Get recorded response for replay. A replay counts as a use for LRU and TTL eviction; an
+    /// expired recording is not returned. Takes only the shared lock of the signature's shard.
     pub fn get_recorded(&self, signature: &RequestSignature) -> Option<RecordedResponse> {
-        let store = self.responses.read();
-        store
+        let now_ms = self.now_ms();
+        let store = self.shard(signature).responses.read();
+        let entry = store
             .get(signature)
-            .and_then(|responses| responses.first().cloned())
+            .filter(|entry| !self.expired(entry, now_ms))?;
+        entry.last_used_ms.store(now_ms, Ordering::Relaxed);
+        entry.responses.front().cloned()
     }
 
     /// Atomically check whether to proxy and claim the signature if so.
@@ -101,20 +341,26 @@ impl RecordingStore {
     ///
     /// Returns `true` if the caller should proxy the request.
     /// Returns `false` if a recorded response exists or another request is already in-flight.
+    /// A TTL-expired recording counts as absent.
     pub fn should_proxy(&self, signature: &RequestSignature) -> bool {
         match self.mode {
             ProxyMode::ProxyOnce => {
                 // Hold the read guard through the pending.insert() call so that
-                // record() (which needs responses.write()) cannot complete between
-                // the "not found" check and the pending claim, eliminating the TOCTOU.
-                let responses = self.responses.read();
-                if responses.contains_key(signature) {
+                // record() (which needs the shard's responses.write()) cannot complete
+                // between the "not found" check and the pending claim, eliminating the TOCTOU.
+                let now_ms = self.now_ms();
+                let shard = self.shard(signature);
+                let responses = shard.responses.read();
+                if responses
+                    .get(signature)
+                    .is_some_and(|entry| !self.expired(entry, now_ms))
+                {
                     return false;
                 }
                 // pending.lock() is acquired while `responses` read guard is still held.
                 // record() cannot acquire responses.write() until we release the read
                 // guard, so the check-and-claim is atomic with respect to record().
-                self.pending.lock().insert(signature.clone())
+                shard.pending.lock().insert(signature.clone())
                 // `responses` guard dropped here
             }
             ProxyMode::ProxyAlways => true,
@@ -128,52 +374,64 @@ impl RecordingStore {
     /// `should_proxy` keeps returning `false` with nothing to replay until a later completed
     /// request self-heals it (issue #555). Safe to call when the signature isn't pending (no-op).
     pub fn release_pending(&self, signature: &RequestSignature) {
-        self.pending.lock().remove(signature);
+        self.shard(signature).pending.lock().remove(signature);
     }
 
     /// Get all recorded responses (for export)
     // Public API for future use (mb replay export)
     pub fn get_all(&self) -> HashMap<RequestSignature, Vec<RecordedResponse>> {
-        self.responses.read().clone()
+        let mut all = HashMap::with_capacity(self.len());
+        for shard in &self.shards {
+            all.extend(
+                shard
+                    .responses
+                    .read()
+                    .iter()
+                    .map(|(sig, entry)| (sig.clone(), entry.responses.iter().cloned().collect())),
+            );
+        }
+        all
     }
 
     /// Clear all recordings
     // Public API for future use (admin endpoints)
     pub fn clear(&self) {
-        self.responses.write().clear();
-        self.pending.lock().clear();
+        for shard in &self.shards {
+            let mut store = shard.responses.write();
+            for (_, entry) in store.drain() {
+                self.forget(&entry);
+            }
+            shard.pending.lock().clear();
+        }
+        self.publish_size();
     }
 
     /// Get number of recorded signatures
     // Public API for future use (metrics/debugging)
     pub fn len(&self) -> usize {
-        self.responses.read().len()
+        self.signatures.load(Ordering::Acquire)
     }
 
     /// Check if empty
     // Public API for future use (metrics/debugging)
     pub fn is_empty(&self) -> bool {
-        self.responses.read().is_empty()
+        self.len() == 0
     }
 
     /// Save recordings to file (JSON format)
     // Public API for persistence
     pub fn save_to_file(&self, path: &Path) -> Result<(), std::io::Error> {
-        let data = self.responses.read();
-        let serializable: Vec<_> = data
-            .iter()
-            .map(|(sig, responses)| (sig.clone(), responses.clone()))
-            .collect();
+        let serializable: Vec<_> = self.get_all().into_iter().collect();
 
         let json = serde_json::to_string_pretty(&serializable)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
 
         fs::write(path, json)?;
-        info!("Saved {} recordings to {:?}", data.len(), path);
+        info!("Saved {} recordings to {:?}", serializable.len(), path);
         Ok(())
     }
 
-    /// Load recordings from file (JSON format)
+    /// Load recordings from file (JSON format). Loaded recordings count as used now.
     // Public API for persistence
     pub fn load_from_file(&self, path: &Path) -> Result<usize, std::io::Error> {
         if !path.exists() {
@@ -186,10 +444,23 @@ impl RecordingStore {
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
 
         let count = data.len();
-        let mut store = self.responses.write();
+        let now_ms = self.now_ms();
         for (sig, responses) in data {
-            store.insert(sig, responses);
+            self.signatures.fetch_add(1, Ordering::AcqRel);
+            self.response_count
+                .fetch_add(responses.len(), Ordering::Relaxed);
+            let replaced = self.shard(&sig).responses.write().insert(
+                sig,
+                Entry {
+                    responses: responses.into(),
+                    last_used_ms: AtomicU64::new(now_ms),
+                },
+            );
+            if let Some(entry) = replaced {
+                self.forget(&entry);
+            }
         }
+        self.publish_size();
 
         info!("Loaded {} recordings from {:?}", count, path);
         Ok(count)
@@ -204,11 +475,11 @@ impl RecordingStore {
         include_query: bool,
         include_headers: &[String],
     ) -> Vec<serde_json::Value> {
-        let store = self.responses.read();
-        store
-            .iter()
-            .flat_map(|(sig, responses)| {
-                responses.iter().map(move |resp| {
+        let mut stubs = Vec::new();
+        for shard in &self.shards {
+            let store = shard.responses.read();
+            stubs.extend(store.iter().flat_map(|(sig, entry)| {
+                entry.responses.iter().map(move |resp| {
                     generate_stub(
                         sig,
                         resp,
@@ -218,8 +489,9 @@ impl RecordingStore {
                         include_headers,
                     )
                 })
-            })
-            .collect()
+            }));
+        }
+        stubs
     }
 }
 
@@ -240,6 +512,7 @@ mod tests {
             body: b"first".to_vec(),
             latency_ms: Some(100),
             timestamp_secs: unix_timestamp(),
+            body_file: None,
         };
         store.record(sig.clone(), resp1);
 
@@ -250,6 +523,7 @@ mod tests {
             body: b"second".to_vec(),
             latency_ms: Some(50),
             timestamp_secs: unix_timestamp(),
+            body_file: None,
         };
         store.record(sig.clone(), resp2);
 
@@ -272,6 +546,7 @@ mod tests {
                 body: b"first".to_vec(),
                 latency_ms: Some(100),
                 timestamp_secs: unix_timestamp(),
+                body_file: None,
             },
         );
 
@@ -283,6 +558,7 @@ mod tests {
                 body: b"second".to_vec(),
                 latency_ms: Some(50),
                 timestamp_secs: unix_timestamp(),
+                body_file: None,
             },
         );
 
@@ -304,6 +580,7 @@ mod tests {
                 body: b"test".to_vec(),
                 latency_ms: Some(100),
                 timestamp_secs: unix_timestamp(),
+                body_file: None,
             },
         );
 
@@ -328,6 +605,7 @@ mod tests {
                 body: b"test".to_vec(),
                 latency_ms: Some(100),
                 timestamp_secs: unix_timestamp(),
+                body_file: None,
             },
         );
 
@@ -357,6 +635,7 @@ mod tests {
                 body: b"response1".to_vec(),
                 latency_ms: Some(10),
                 timestamp_secs: unix_timestamp(),
+                body_file: None,
             },
         );
 
@@ -384,6 +663,7 @@ mod tests {
                 body: b"GET response".to_vec(),
                 latency_ms: Some(10),
                 timestamp_secs: unix_timestamp(),
+                body_file: None,
             },
         );
 
@@ -410,6 +690,7 @@ mod tests {
                 body: b"test".to_vec(),
                 latency_ms: Some(100),
                 timestamp_secs: unix_timestamp(),
+                body_file: None,
             },
         );
 
@@ -523,6 +804,7 @@ mod tests {
                 body: b"ok".to_vec(),
                 latency_ms: Some(10),
                 timestamp_secs: unix_timestamp(),
+                body_file: None,
             },
         );
 
@@ -552,6 +834,7 @@ mod tests {
                     body: format!("response-{i}").into_bytes(),
                     latency_ms: Some(10),
                     timestamp_secs: unix_timestamp(),
+                    body_file: None,
                 },
             );
         }
@@ -588,6 +871,7 @@ mod tests {
                     body: b"ok".to_vec(),
                     latency_ms: Some(10),
                     timestamp_secs: unix_timestamp(),
+                    body_file: None,
                 },
             );
         }
@@ -605,6 +889,7 @@ mod tests {
                 body: b"dropped".to_vec(),
                 latency_ms: Some(10),
                 timestamp_secs: unix_timestamp(),
+                body_file: None,
             },
         );
 
@@ -646,6 +931,7 @@ mod tests {
                 body: b"pre-recorded".to_vec(),
                 latency_ms: Some(10),
                 timestamp_secs: unix_timestamp(),
+                body_file: None,
             },
         );
 
@@ -666,4 +952,97 @@ mod tests {
             "All concurrent should_proxy calls after record() must return false"
         );
     }
+
+    fn response(body: &str) -> RecordedResponse {
+        RecordedResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: body.as_bytes().to_vec(),
+            latency_ms: None,
+            timestamp_secs: unix_timestamp(),
+            body_file: None,
+        }
+    }
+
+    #[test]
+    fn max_entries_evicts_the_least_recently_used_signature() {
+        let store = RecordingStore::new(ProxyMode::ProxyAlways).with_eviction(EvictionPolicy {
+            max_entries: Some(2),
+            ttl: None,
+        });
+        let a = RequestSignature::new("GET", "/a", None, &[]);
+        let b = RequestSignature::new("GET", "/b", None, &[]);
+        let c = RequestSignature::new("GET", "/c", None, &[]);
+
+        store.record(a.clone(), response("a"));
+        std::thread::sleep(Duration::from_millis(5));
+        store.record(b.clone(), response("b"));
+        std::thread::sleep(Duration::from_millis(5));
+        // Replaying /a makes /b the least recently used.
+        assert!(store.get_recorded(&a).is_some());
+        std::thread::sleep(Duration::from_millis(5));
+        store.record(c.clone(), response("c"));
+
+        assert_eq!(store.len(), 2);
+        assert!(store.get_recorded(&a).is_some());
+        assert!(
+            store.get_recorded(&b).is_none(),
+            "/b was least recently used"
+        );
+        assert!(store.get_recorded(&c).is_some());
+    }
+
+    #[test]
+    fn max_entries_bounds_the_whole_store_across_shards() {
+        use std::sync::Arc;
+
+        let store = Arc::new(RecordingStore::new(ProxyMode::ProxyAlways).with_eviction(
+            EvictionPolicy {
+                max_entries: Some(50),
+                ttl: None,
+            },
+        ));
+        let threads: Vec<_> = (0..8)
+            .map(|t| {
+                let store = Arc::clone(&store);
+                std::thread::spawn(move || {
+                    for i in 0..200 {
+                        let sig = RequestSignature::new("GET", &format!("/t{t}/{i}"), None, &[]);
+                        store.record(sig, response("x"));
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(store.len(), 50);
+        assert_eq!(store.get_all().len(), 50, "the count matches the shards");
+    }
+
+    #[test]
+    fn ttl_expires_recordings_and_reopens_proxy_once() {
+        let store = RecordingStore::new(ProxyMode::ProxyOnce).with_eviction(EvictionPolicy {
+            max_entries: None,
+            ttl: Some(Duration::from_millis(20)),
+        });
+        let sig = RequestSignature::new("GET", "/ttl", None, &[]);
+        assert!(store.should_proxy(&sig));
+        store.record(sig.clone(), response("first"));
+        assert!(!store.should_proxy(&sig));
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(
+            store.get_recorded(&sig).is_none(),
+            "expired recordings are not replayed"
+        );
+        assert!(
+            store.should_proxy(&sig),
+            "an expired signature is proxied again"
+        );
+        store.record(sig.clone(), response("second"));
+        assert_eq!(store.get_recorded(&sig).unwrap().body, b"second");
+        assert_eq!(store.len(), 1);
+    }
 }
@@ -99,6 +99,7 @@ mod tests {
             body: b"OK".to_vec(),
             latency_ms: None,
             timestamp_secs: 0,
+            body_file: None,
         }
     }
 
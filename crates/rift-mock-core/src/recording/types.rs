@@ -14,6 +14,10 @@ pub struct RecordedResponse {
     pub latency_ms: Option<u64>,
     /// Unix timestamp in seconds
     pub timestamp_secs: u64,
+    /// Datadir-relative file holding the body when it was too large to keep in memory (see
+    /// `--recording-spill-threshold`); `body` is then empty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_file: Option<String>,
 }
 
 /// Request signature for matching recorded responses
@@ -49,6 +49,7 @@ pub enum ErrorKind {
     ScriptTimeout,
     BehaviorError,
     ImposterDisabled,
+    ImposterDraining,
     RequestTooLarge,
     UpstreamFailure,
     Unavailable,
@@ -62,7 +63,7 @@ impl ErrorKind {
     /// Every variant as of writing — keep in sync by hand when adding one. The exhaustive,
     /// wildcard-free match in [`slug`](Self::slug) is the half of the guard the compiler enforces;
     /// this array is what lets the shape test iterate them.
-    pub const ALL: [ErrorKind; 21] = [
+    pub const ALL: [ErrorKind; 22] = [
         ErrorKind::BadData,
         ErrorKind::InvalidInjection,
         ErrorKind::ResourceConflict,
@@ -77,6 +78,7 @@ impl ErrorKind {
         ErrorKind::ScriptTimeout,
         ErrorKind::BehaviorError,
         ErrorKind::ImposterDisabled,
+        ErrorKind::ImposterDraining,
         ErrorKind::RequestTooLarge,
         ErrorKind::UpstreamFailure,
         ErrorKind::Unavailable,
@@ -104,6 +106,7 @@ impl ErrorKind {
             ErrorKind::ScriptTimeout => "script timeout",
             ErrorKind::BehaviorError => "behavior error",
             ErrorKind::ImposterDisabled => "imposter disabled",
+            ErrorKind::ImposterDraining => "imposter draining",
             ErrorKind::RequestTooLarge => "request too large",
             ErrorKind::UpstreamFailure => "upstream failure",
             ErrorKind::Unavailable => "unavailable",
@@ -211,6 +214,24 @@ impl From<ImposterError> for Response<Full<Bytes>> {
                 StatusCode::CONFLICT,
                 &format!("A stub with id '{id}' already exists"),
             ),
+            // A batch answers with the failing operation's own status (404 for a missing stub,
+            // 409 for a duplicate id) and names the operation in the message.
+            ImposterError::StubOperationFailed {
+                operation,
+                ref source,
+            } => {
+                let status = match **source {
+                    ImposterError::StubIdConflict(_) => StatusCode::CONFLICT,
+                    ImposterError::StubIndexOutOfBounds(_) | ImposterError::StubNotFound(_) => {
+                        StatusCode::NOT_FOUND
+                    }
+                    _ => StatusCode::BAD_REQUEST,
+                };
+                error_response(
+                    status,
+                    &format!("Stub operation {operation} failed: {source}"),
+                )
+            }
             ImposterError::PersistError(msg) => {
                 tracing::error!(error = %format_args!("{msg:#}"), "failed to persist imposter state");
                 error_response(
@@ -228,6 +249,17 @@ impl From<ImposterError> for Response<Full<Bytes>> {
                 StatusCode::BAD_REQUEST,
                 &format!("Flow store configuration error: {msg}"),
             ),
+            ImposterError::InvalidBehaviors(msg) => error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("Invalid defaultBehaviors: {msg}"),
+            ),
+            ImposterError::TemplateNotFound(name) => error_response(
+                StatusCode::NOT_FOUND,
+                &format!("No template named '{name}'"),
+            ),
+            ImposterError::InvalidTemplate(msg) => {
+                error_response(StatusCode::BAD_REQUEST, &format!("Invalid template: {msg}"))
+            }
             ImposterError::Backend(e) => crate::extensions::decorate::backend_error_response(&e),
         }
     }
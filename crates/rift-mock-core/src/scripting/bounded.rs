@@ -7,7 +7,7 @@
 //! Rhai's `on_progress` callback.
 
 use super::{
-    FaultDecision, ScriptCtxExtras, ScriptEngine, ScriptRequest, ScriptTraceEntry,
+    FaultDecision, ScriptCtxExtras, ScriptEngine, ScriptLimits, ScriptRequest, ScriptTraceEntry,
     capture_script_logs, render_decision,
 };
 use crate::extensions::flow_state::FlowStore;
@@ -70,6 +70,7 @@ pub async fn should_inject_bounded(
         request,
         flow_store,
         timeout,
+        ScriptLimits::default(),
         ScriptCtxExtras::default(),
     )
     .await
@@ -77,7 +78,9 @@ pub async fn should_inject_bounded(
 
 /// As [`should_inject_bounded`], but threading real `ctx.flowId`/`ctx.stub` context (issue #357
 /// Item 1) through to the v2 `ctx` object — used by the imposter `_rift.script` hook, which knows
-/// the resolved flow id and matched stub.
+/// the resolved flow id and matched stub — and running a Rhai script under the imposter's
+/// `limits` (see [`resolve_script_limits`](super::resolve_script_limits)).
+#[allow(clippy::too_many_arguments)]
 pub async fn should_inject_bounded_with_ctx(
     engine_type: String,
     code: String,
@@ -85,6 +88,7 @@ pub async fn should_inject_bounded_with_ctx(
     request: ScriptRequest,
     flow_store: Arc<dyn FlowStore>,
     timeout: Duration,
+    limits: ScriptLimits,
     ctx_extra: ScriptCtxExtras,
 ) -> Result<FaultDecision> {
     let abort = Arc::new(AtomicBool::new(false));
@@ -98,6 +102,7 @@ pub async fn should_inject_bounded_with_ctx(
             flow_store,
             &run_abort,
             &ctx_extra,
+            &limits,
         )
     });
 
@@ -126,6 +131,7 @@ pub async fn should_inject_bounded_with_ctx(
 /// emitted. Only called when debug mode is on — [`should_inject_bounded_with_ctx`] above is
 /// unchanged and stays the zero-cost default the rest of the time (no capturing subscriber, no
 /// extra `Instant`/allocation).
+#[allow(clippy::too_many_arguments)]
 pub async fn should_inject_bounded_with_ctx_traced(
     engine_type: String,
     code: String,
@@ -133,6 +139,7 @@ pub async fn should_inject_bounded_with_ctx_traced(
     request: ScriptRequest,
     flow_store: Arc<dyn FlowStore>,
     timeout: Duration,
+    limits: ScriptLimits,
     ctx_extra: ScriptCtxExtras,
 ) -> (Result<FaultDecision>, ScriptTraceEntry) {
     let abort = Arc::new(AtomicBool::new(false));
@@ -148,6 +155,7 @@ pub async fn should_inject_bounded_with_ctx_traced(
                 flow_store,
                 &run_abort,
                 &ctx_extra,
+                &limits,
             )
         })
     });
@@ -194,8 +202,9 @@ pub async fn should_inject_bounded_with_ctx_traced(
 
 /// Execute `respond(ctx)` synchronously with the abort flag wired into the interpreter, so
 /// setting `abort` interrupts a runaway script. Rhai gets a real interpreter interrupt
-/// (#308/#172); other engines run without an interpreter interrupt but still off the async
-/// worker and under the request-level timeout.
+/// (#308/#172) and `limits`; other engines run without an interpreter interrupt but still off the
+/// async worker and under the request-level timeout, with their response held to the memory cap.
+#[allow(clippy::too_many_arguments)]
 fn run_should_inject_with_abort(
     engine_type: &str,
     code: &str,
@@ -204,17 +213,25 @@ fn run_should_inject_with_abort(
     flow_store: Arc<dyn FlowStore>,
     abort: &Arc<AtomicBool>,
     ctx_extra: &ScriptCtxExtras,
+    limits: &ScriptLimits,
 ) -> Result<FaultDecision> {
     // Start each execution with a clean last-flow-error slot so `flow_store.last_error()` can't
     // observe a stale error left by a previous script on this reused worker thread (issue #322).
     crate::extensions::flow_state::clear_last_flow_error();
     match engine_type {
         "rhai" => super::rhai_engine::run_should_inject_with_abort_rhai(
-            code, rule_id, request, flow_store, abort, ctx_extra,
+            code, rule_id, request, flow_store, abort, ctx_extra, limits,
         ),
         other => {
             let engine = ScriptEngine::new(other, code, rule_id)?;
-            engine.should_inject_fault_with_ctx(request, flow_store, ctx_extra)
+            let decision = engine.should_inject_fault_with_ctx(request, flow_store, ctx_extra)?;
+            if let FaultDecision::Error { body, headers, .. } = &decision
+                && let Some(exceeded) =
+                    limits.exceeded_by_js_output(super::limits::js_output_bytes(body, headers))
+            {
+                return Err(exceeded.into());
+            }
+            Ok(decision)
         }
     }
 }
@@ -286,6 +303,7 @@ mod tests {
                 store(),
                 &abort,
                 &ScriptCtxExtras::default(),
+                &ScriptLimits::default(),
             );
             let _ = tx.send(res.is_err());
         });
@@ -452,6 +470,7 @@ mod tests {
             req(),
             store(),
             Duration::from_millis(2000),
+            ScriptLimits::default(),
             ScriptCtxExtras::default(),
         )
         .await;
@@ -476,6 +495,7 @@ mod tests {
             req(),
             store(),
             Duration::from_millis(150),
+            ScriptLimits::default(),
             ScriptCtxExtras::default(),
         )
         .await;
@@ -487,6 +507,39 @@ mod tests {
         );
     }
 
+    // Boa has no heap limit, so a JavaScript script's response is what `maxMemoryBytes` caps.
+    #[cfg(feature = "javascript")]
+    #[tokio::test]
+    async fn javascript_response_past_the_memory_cap_is_a_limit_error() {
+        let run = |max_memory_bytes| {
+            should_inject_bounded_with_ctx(
+                "javascript".into(),
+                r#"function respond(ctx) { return http(500, "x".repeat(2048)); }"#.into(),
+                "t".into(),
+                req(),
+                store(),
+                Duration::from_millis(2000),
+                ScriptLimits {
+                    max_memory_bytes,
+                    ..ScriptLimits::default()
+                },
+                ScriptCtxExtras::default(),
+            )
+        };
+        let err = run(1024).await.expect_err("2 KiB body over a 1 KiB cap");
+        let exceeded = err
+            .downcast_ref::<crate::scripting::ScriptLimitError>()
+            .unwrap_or_else(|| panic!("expected a ScriptLimitError, got: {err}"));
+        assert!(
+            exceeded.limit.starts_with("memory limit (1024 bytes)"),
+            "{exceeded}"
+        );
+        assert!(matches!(
+            run(4096).await.expect("under the cap"),
+            FaultDecision::Error { status: 500, .. }
+        ));
+    }
+
     // =====================================================================================
     // Issue #476: Mountebank response-inject hook runs off the async worker with a
     // wall-clock deadline, through the same spawn_blocking + timeout shape as `_rift.script`.
@@ -1,3 +1,4 @@
+use crate::extensions::StubMetadata;
 use crate::extensions::flow_state::{CasOutcome, FlowStore, flow_result};
 use crate::imposter::ResponseMode;
 use crate::scripting::{
@@ -114,6 +115,13 @@ fn with_current_flow_store<T>(f: impl FnOnce(&Arc<dyn FlowStore>) -> T) -> Optio
 /// http(429, { error: "Rate limited" }).header("Retry-After", "60")
 /// ```
 ///
+/// A plain `{ status, headers, body }` object is the same response as `http(status, body)` with
+/// those headers:
+///
+/// ```javascript
+/// return { status: 201, headers: { "Location": "/users/7" }, body: { id: 7 } };
+/// ```
+///
 /// ## Example
 ///
 /// ```javascript
@@ -1386,7 +1394,8 @@ fn register_result_constructors(context: &mut Context) -> Result<()> {
 
 /// Convert a `respond(ctx)`/bare-expression return value into a [`FaultDecision`] (issue #357
 /// Item 3): `null`/`undefined` (or the script returning nothing) → `None`; an `http()`/`delay()`/
-/// `reset()`/`pass()` result → its own outcome; anything else is an error.
+/// `reset()`/`pass()` result → its own outcome; a plain `{ status, headers, body }` object → the
+/// equivalent `http()` (see [`ScriptResult::from_response_object`]); anything else is an error.
 fn js_value_to_fault_decision(
     context: &mut Context,
     value: JsValue,
@@ -1401,8 +1410,17 @@ fn js_value_to_fault_decision(
         .and_then(|v| v.as_number())
         .map(|n| n as u64);
     let Some(id) = id else {
+        if value
+            .as_object()
+            .is_some_and(|o| !o.is_callable() && !o.is_array())
+        {
+            let object = js_to_json(context, &value)
+                .map_err(|e| anyhow!("Failed to read respond(ctx) response object: {e}"))?;
+            return Ok(ScriptResult::from_response_object(object)?.into_fault_decision(rule_id));
+        }
         return Err(anyhow!(
-            "respond(ctx) must return http(...)/delay(...)/reset()/pass() or nothing"
+            "respond(ctx) must return http(...)/delay(...)/reset()/pass(), a \
+             {{ status, headers, body }} object, or nothing"
         ));
     };
     let result = SCRIPT_RESULT_REGISTRY.with(|r| r.borrow_mut().remove(&id));
@@ -2454,6 +2472,133 @@ fn register_require(context: &mut Context) -> Result<()> {
     Ok(())
 }
 
+/// The `n`th argument coerced to a string (`undefined` when absent), for the encoding helpers.
+fn js_string_arg(args: &[JsValue], n: usize, context: &mut Context) -> JsResult<String> {
+    args.get(n)
+        .cloned()
+        .unwrap_or_default()
+        .to_string(context)
+        .map(|s| s.to_std_string_escaped())
+}
+
+fn js_helper_result(result: std::result::Result<String, String>) -> JsResult<JsValue> {
+    result
+        .map(|s| JsValue::from(js_string!(s)))
+        .map_err(|msg| JsNativeError::error().with_message(msg).into())
+}
+
+/// Register the global `rift` helper object for decorate scripts: `base64Encode`, `base64Decode`,
+/// `urlEncode`, `urlDecode`, `sha256` (hex), `hmacSha256(key, message, encoding?)` (`hex` default,
+/// or `base64`/`base64url`), and `jwtDecode(token)` → `{ header, payload }` (no signature check).
+/// Thin wrappers over `extensions::encoding`, the same helpers the `{{ }}` filters use.
+fn register_encoding_helpers(context: &mut Context) -> Result<()> {
+    use crate::extensions::encoding;
+
+    let helpers = create_js_object(context);
+    register_method(
+        &helpers,
+        "base64Encode",
+        |_, args, ctx| {
+            let s = js_string_arg(args, 0, ctx)?;
+            Ok(JsValue::from(js_string!(encoding::base64_encode(
+                s.as_bytes()
+            ))))
+        },
+        context,
+    )?;
+    register_method(
+        &helpers,
+        "base64Decode",
+        |_, args, ctx| js_helper_result(encoding::base64_decode(&js_string_arg(args, 0, ctx)?)),
+        context,
+    )?;
+    register_method(
+        &helpers,
+        "urlEncode",
+        |_, args, ctx| {
+            let s = js_string_arg(args, 0, ctx)?;
+            Ok(JsValue::from(js_string!(encoding::url_encode(&s))))
+        },
+        context,
+    )?;
+    register_method(
+        &helpers,
+        "urlDecode",
+        |_, args, ctx| js_helper_result(encoding::url_decode(&js_string_arg(args, 0, ctx)?)),
+        context,
+    )?;
+    register_method(
+        &helpers,
+        "sha256",
+        |_, args, ctx| {
+            let s = js_string_arg(args, 0, ctx)?;
+            Ok(JsValue::from(js_string!(encoding::sha256_hex(
+                s.as_bytes()
+            ))))
+        },
+        context,
+    )?;
+    register_method(
+        &helpers,
+        "hmacSha256",
+        |_, args, ctx| {
+            let key = js_string_arg(args, 0, ctx)?;
+            let message = js_string_arg(args, 1, ctx)?;
+            let digest_encoding = match args.get(2) {
+                Some(v) if !v.is_undefined() => {
+                    let name = v.to_string(ctx)?.to_std_string_escaped();
+                    encoding::DigestEncoding::parse(&name)
+                        .map_err(|msg| JsNativeError::error().with_message(msg))?
+                }
+                _ => encoding::DigestEncoding::Hex,
+            };
+            Ok(JsValue::from(js_string!(encoding::hmac_sha256_encoded(
+                &key,
+                &message,
+                digest_encoding
+            ))))
+        },
+        context,
+    )?;
+    register_method(
+        &helpers,
+        "jwtDecode",
+        |_, args, ctx| {
+            let token = js_string_arg(args, 0, ctx)?;
+            let decoded = encoding::jwt_decode(&token)
+                .map_err(|msg| JsNativeError::error().with_message(msg))?;
+            json_to_js_result(ctx, &decoded)
+        },
+        context,
+    )?;
+
+    let global = context.global_object();
+    global
+        .set(js_string!("rift"), helpers, false, context)
+        .map_err(|e| anyhow!("Failed to set rift helpers: {e}"))?;
+    Ok(())
+}
+
+/// Set `config.stub` (`{ id, index }`) and `config.imposter` (`{ port }`) so a decorate can report
+/// which stub served the request. `id` is `null` for a stub without one.
+fn set_stub_metadata_on(
+    config_obj: &JsObject,
+    stub: &StubMetadata,
+    context: &mut Context,
+) -> Result<()> {
+    let metadata = serde_json::json!({
+        "stub": { "id": stub.id, "index": stub.index },
+        "imposter": { "port": stub.imposter_port },
+    });
+    for key in ["stub", "imposter"] {
+        let value = json_to_js(&mut *context, &metadata[key])?;
+        config_obj
+            .set(js_string!(key), value, false, &mut *context)
+            .map_err(|e| anyhow!("Failed to set config.{key}: {e}"))?;
+    }
+    Ok(())
+}
+
 /// Execute a Mountebank `config => {...}` / `function(config)` decorate in Boa, exposing a
 /// `config` object ({ request, response, path, state, logger }) — with every request field also
 /// flattened onto `config` itself (issue #355 Item 0) — and a CommonJS `require()` so a decorate
@@ -2466,14 +2611,13 @@ pub fn execute_mountebank_config_decorate(
     response_body: &str,
     response_status: u16,
     response_headers: &std::collections::HashMap<String, String>,
-    imposter_port: u16,
-    stub_id: Option<&str>,
+    stub: &StubMetadata,
 ) -> Result<MountebankDecorateResponse> {
     let decorate_fn = decorate_fn.to_string();
     let request = request.clone();
     let response_body = response_body.to_string();
     let response_headers = response_headers.clone();
-    let stub_id = stub_id.map(str::to_string);
+    let stub = stub.clone();
     with_mb_js_thread(move |thread| {
         execute_mountebank_config_decorate_in(
             thread,
@@ -2482,8 +2626,7 @@ pub fn execute_mountebank_config_decorate(
             &response_body,
             response_status,
             &response_headers,
-            imposter_port,
-            stub_id.as_deref(),
+            &stub,
         )
     })?
 }
@@ -2496,10 +2639,11 @@ fn execute_mountebank_config_decorate_in(
     response_body: &str,
     response_status: u16,
     response_headers: &std::collections::HashMap<String, String>,
-    imposter_port: u16,
-    stub_id: Option<&str>,
+    stub: &StubMetadata,
 ) -> Result<MountebankDecorateResponse> {
     let MbJsThread { context, scripts } = thread;
+    let imposter_port = stub.imposter_port;
+    let stub_id = stub.id.as_deref();
 
     // Create request object
     let request_obj = create_mountebank_request_object(&mut *context, request)?;
@@ -2585,11 +2729,13 @@ fn execute_mountebank_config_decorate_in(
     config_obj
         .set(js_string!("logger"), logger_obj, false, &mut *context)
         .map_err(|e| anyhow!("Failed to set config.logger: {e}"))?;
+    set_stub_metadata_on(&config_obj, stub, &mut *context)?;
     flatten_request_onto(&config_obj, &request_obj, &mut *context)?;
 
     // Register the CommonJS `require()` global before evaluating the decorate so it (and any
     // module it loads) can use it.
     register_require(&mut *context)?;
+    register_encoding_helpers(&mut *context)?;
 
     // Set global variable
     let global = context.global_object();
@@ -2694,14 +2840,13 @@ pub fn execute_mountebank_decorate(
     response_body: &str,
     response_status: u16,
     response_headers: &std::collections::HashMap<String, String>,
-    imposter_port: u16,
-    stub_id: Option<&str>,
+    stub: &StubMetadata,
 ) -> Result<MountebankDecorateResponse> {
     let decorate_fn = decorate_fn.to_string();
     let request = request.clone();
     let response_body = response_body.to_string();
     let response_headers = response_headers.clone();
-    let stub_id = stub_id.map(str::to_string);
+    let stub = stub.clone();
     with_mb_js_thread(move |thread| {
         execute_mountebank_decorate_in(
             thread,
@@ -2710,8 +2855,7 @@ pub fn execute_mountebank_decorate(
             &response_body,
             response_status,
             &response_headers,
-            imposter_port,
-            stub_id.as_deref(),
+            &stub,
         )
     })?
 }
@@ -2724,10 +2868,11 @@ fn execute_mountebank_decorate_in(
     response_body: &str,
     response_status: u16,
     response_headers: &std::collections::HashMap<String, String>,
-    imposter_port: u16,
-    stub_id: Option<&str>,
+    stub: &StubMetadata,
 ) -> Result<MountebankDecorateResponse> {
     let MbJsThread { context, scripts } = thread;
+    let imposter_port = stub.imposter_port;
+    let stub_id = stub.id.as_deref();
 
     // Create request object
     let request_obj = create_mountebank_request_object(&mut *context, request)?;
@@ -2782,6 +2927,7 @@ fn execute_mountebank_decorate_in(
             &mut *context,
         )
         .map_err(|e| anyhow!("Failed to set config.request: {e}"))?;
+    set_stub_metadata_on(&config_obj, stub, &mut *context)?;
     flatten_request_onto(&config_obj, &request_obj, &mut *context)?;
 
     // Get current (persisted, per-port) state for this imposter — shared with predicate/response
@@ -2796,6 +2942,8 @@ fn execute_mountebank_decorate_in(
     let logger_obj =
         create_script_logger_object(&mut *context, imposter_port, stub_id.map(str::to_owned))?;
 
+    register_encoding_helpers(&mut *context)?;
+
     // Set global variables
     let global = context.global_object();
     global
@@ -3429,6 +3577,13 @@ function respond(ctx) {
         NEXT.fetch_add(1, Ordering::Relaxed) as u16
     }
 
+    fn test_stub() -> StubMetadata {
+        StubMetadata {
+            imposter_port: test_port(),
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn test_decorate_with_logger_arg() {
         let request = MountebankRequest {
@@ -3443,15 +3598,8 @@ function respond(ctx) {
 
         // Script that uses logger as 3rd argument — must not throw ReferenceError
         let script = r#"function(request, response, logger) { logger.info("decorating"); response.body = "logged"; }"#;
-        let result = execute_mountebank_decorate(
-            script,
-            &request,
-            "original",
-            200,
-            &headers,
-            test_port(),
-            None,
-        );
+        let result =
+            execute_mountebank_decorate(script, &request, "original", 200, &headers, &test_stub());
         assert!(
             result.is_ok(),
             "logger arg should not throw: {:?}",
@@ -3537,15 +3685,8 @@ function respond(ctx) {
 
         // Script that uses state as 4th argument — must not throw ReferenceError
         let script = r#"function(request, response, logger, state) { state.count = 1; response.body = "state ok"; }"#;
-        let result = execute_mountebank_decorate(
-            script,
-            &request,
-            "original",
-            200,
-            &headers,
-            test_port(),
-            None,
-        );
+        let result =
+            execute_mountebank_decorate(script, &request, "original", 200, &headers, &test_stub());
         assert!(
             result.is_ok(),
             "state arg should not throw: {:?}",
@@ -3594,8 +3735,7 @@ function respond(ctx) {
             "orig",
             200,
             &HashMap::new(),
-            test_port(),
-            None,
+            &test_stub(),
         );
         let _ = std::fs::remove_file(&module);
         let resp = result.expect("require decorate should run");
@@ -3616,8 +3756,7 @@ function respond(ctx) {
             "orig",
             200,
             &HashMap::new(),
-            test_port(),
-            None,
+            &test_stub(),
         )
         .expect("direct config decorate should run");
         assert_eq!(resp.body, "DIRECT");
@@ -3633,8 +3772,7 @@ function respond(ctx) {
             "orig",
             200,
             &HashMap::new(),
-            test_port(),
-            None,
+            &test_stub(),
         )
         .expect("config.request decorate should run");
         assert_eq!(resp.body, "REQ-BODY");
@@ -3682,8 +3820,7 @@ function respond(ctx) {
             "orig",
             200,
             &HashMap::new(),
-            test_port(),
-            None,
+            &test_stub(),
         );
         let _ = std::fs::remove_file(&path_a);
         let _ = std::fs::remove_file(&path_b);
@@ -3705,8 +3842,7 @@ function respond(ctx) {
             "orig",
             200,
             &HashMap::new(),
-            test_port(),
-            None,
+            &test_stub(),
         );
         assert!(
             result.is_err(),
@@ -3875,8 +4011,7 @@ function respond(ctx) {
             "orig",
             200,
             &HashMap::new(),
-            test_port(),
-            None,
+            &test_stub(),
         )
         .expect("v2 config decorate should run");
         assert_eq!(resp.body, "/dec-a");
@@ -3891,8 +4026,7 @@ function respond(ctx) {
             "orig",
             200,
             &HashMap::new(),
-            test_port(),
-            None,
+            &test_stub(),
         )
         .expect("legacy positional decorate should run");
         assert_eq!(resp.body, "/dec-legacy");
@@ -3907,8 +4041,7 @@ function respond(ctx) {
             "orig",
             200,
             &HashMap::new(),
-            test_port(),
-            None,
+            &test_stub(),
         )
         .expect("flattened config decorate should run");
         assert_eq!(resp.body, "/dec-flat");
@@ -3926,8 +4059,7 @@ function respond(ctx) {
             "orig",
             200,
             &HashMap::new(),
-            test_port(),
-            None,
+            &test_stub(),
         )
         .expect("request-or-config shim decorate should run");
         assert_eq!(resp.body, "/dec-shim");
@@ -3945,8 +4077,7 @@ function respond(ctx) {
             "orig",
             200,
             &HashMap::new(),
-            test_port(),
-            None,
+            &test_stub(),
         )
         .expect("hybrid config+state decorate should run");
         assert_eq!(resp.body, "decorated:GET");
@@ -4789,5 +4920,34 @@ function respond(ctx) {
                 "registry leaked {leaked} entries across 25 runs; per-run reset is broken"
             );
         }
+
+        #[test]
+        fn response_object_builds_a_full_response() {
+            let script = r#"function respond(ctx) {
+                return { status: 201, headers: { "Location": "/orders/7" }, body: { id: 7 } };
+            }"#;
+            match run_respond(script, &req(HashMap::new(), None)).unwrap() {
+                FaultDecision::Error {
+                    status,
+                    body,
+                    headers,
+                    ..
+                } => {
+                    assert_eq!(status, 201);
+                    assert_eq!(body, r#"{"id":7}"#);
+                    assert_eq!(headers["Location"], "/orders/7");
+                    assert_eq!(headers["Content-Type"], "application/json");
+                }
+                other => panic!("expected Error(201), got {other:?}"),
+            }
+        }
+
+        #[test]
+        fn response_object_with_unknown_field_is_an_error() {
+            let err = run_respond("({ statusCode: 200 })", &req(HashMap::new(), None))
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains("statusCode"), "got {err}");
+        }
     }
 }
@@ -0,0 +1,222 @@
+//! Per-execution resource limits for Rhai scripts: an operation budget and a cap on the memory a
+//! script's strings, arrays and maps can take (`_rift.scriptEngine.maxOperations` /
+//! `maxMemoryBytes`).
+//!
+//! The wall-clock deadline (`timeoutMs`, see `bounded`) releases the client, but only an
+//! interpreter-level limit stops the script itself: without one, a `loop {}` keeps its blocking
+//! thread busy after the client has its response, and a script building an ever-longer string
+//! takes the process's memory with it. Every Rhai engine gets [`ScriptLimits::default`] from
+//! `RhaiEngine::create_engine`; the `_rift.script` and `decorate` hooks apply their imposter's own.
+//!
+//! Boa (JavaScript) has no operation budget or heap limit; its loops and recursion are capped
+//! structurally instead (see `js_engine::bounded_js_context`), and `maxMemoryBytes` caps the
+//! response a JavaScript `_rift.script` or `decorate` returns rather than what it allocates on
+//! the way (see [`ScriptLimits::exceeded_by_js_output`]).
+
+use crate::imposter::ImposterConfig;
+use rhai::EvalAltResult;
+
+/// Default operation budget for one script execution. Far beyond any realistic mock script, but
+/// small enough that a runaway loop stops within a few seconds.
+pub const DEFAULT_SCRIPT_MAX_OPERATIONS: u64 = 100_000_000;
+
+/// Default memory cap for one script execution: 64 MiB.
+pub const DEFAULT_SCRIPT_MAX_MEMORY_BYTES: usize = 64 * 1024 * 1024;
+
+/// The limits one script execution runs under. `0` lifts a limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScriptLimits {
+    /// Rhai operations (roughly, expressions evaluated) a script may run.
+    pub max_operations: u64,
+    /// Largest string a script may build, in bytes; arrays and maps are capped at as many
+    /// elements as fit in the same size.
+    pub max_memory_bytes: usize,
+}
+
+impl Default for ScriptLimits {
+    fn default() -> Self {
+        Self {
+            max_operations: DEFAULT_SCRIPT_MAX_OPERATIONS,
+            max_memory_bytes: DEFAULT_SCRIPT_MAX_MEMORY_BYTES,
+        }
+    }
+}
+
+impl ScriptLimits {
+    /// Install these limits on a Rhai engine.
+    pub fn apply_to_rhai(&self, engine: &mut rhai::Engine) {
+        engine.set_max_operations(self.max_operations);
+        engine.set_max_string_size(self.max_memory_bytes);
+        let elements = self.max_memory_bytes / std::mem::size_of::<rhai::Dynamic>();
+        engine.set_max_array_size(elements);
+        engine.set_max_map_size(elements);
+    }
+
+    /// The limits installed on a Rhai engine.
+    pub(crate) fn of_rhai(engine: &rhai::Engine) -> Self {
+        Self {
+            max_operations: engine.max_operations(),
+            max_memory_bytes: engine.max_string_size(),
+        }
+    }
+
+    /// The [`ScriptLimitError`] for a Rhai error that is one of these limits being hit, looking
+    /// through the function calls it surfaced from.
+    pub(crate) fn exceeded_by(&self, error: &EvalAltResult) -> Option<ScriptLimitError> {
+        match error {
+            EvalAltResult::ErrorTooManyOperations(_) => Some(ScriptLimitError {
+                limit: format!("operation limit ({} operations)", self.max_operations),
+            }),
+            EvalAltResult::ErrorDataTooLarge(what, _) => Some(ScriptLimitError {
+                limit: format!(
+                    "memory limit ({} bytes): {}",
+                    self.max_memory_bytes,
+                    what.to_lowercase()
+                ),
+            }),
+            EvalAltResult::ErrorInFunctionCall(_, _, inner, _)
+            | EvalAltResult::ErrorInModule(_, inner, _) => self.exceeded_by(inner),
+            _ => None,
+        }
+    }
+
+    /// The [`ScriptLimitError`] for a JavaScript result of `bytes` bytes past the memory cap.
+    /// Boa cannot stop a script mid-allocation, so an oversized response is rejected instead of
+    /// served.
+    pub(crate) fn exceeded_by_js_output(&self, bytes: usize) -> Option<ScriptLimitError> {
+        (self.max_memory_bytes != 0 && bytes > self.max_memory_bytes).then(|| ScriptLimitError {
+            limit: format!(
+                "memory limit ({} bytes): javascript result is {bytes} bytes",
+                self.max_memory_bytes
+            ),
+        })
+    }
+}
+
+/// The size a JavaScript result is held to by [`ScriptLimits::exceeded_by_js_output`]: its body
+/// plus its header names and values.
+pub(crate) fn js_output_bytes(
+    body: &str,
+    headers: &std::collections::HashMap<String, String>,
+) -> usize {
+    body.len()
+        + headers
+            .iter()
+            .map(|(name, value)| name.len() + value.len())
+            .sum::<usize>()
+}
+
+/// A script stopped for exceeding its [`ScriptLimits`].
+///
+/// Like a [`ScriptTimeoutError`](super::ScriptTimeoutError), the handler marks the response with
+/// `x-rift-script-timeout`, but it stays a 500: the same request will hit the same limit again.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("script exceeded its {limit}")]
+pub struct ScriptLimitError {
+    /// Which limit, and its value.
+    pub limit: String,
+}
+
+/// The script limits for an imposter: `_rift.scriptEngine.maxOperations` and `maxMemoryBytes`
+/// if configured, else the defaults.
+pub fn resolve_script_limits(config: &ImposterConfig) -> ScriptLimits {
+    config
+        .rift
+        .as_ref()
+        .and_then(|r| r.script_engine.as_ref())
+        .map(|se| ScriptLimits {
+            max_operations: se.max_operations,
+            max_memory_bytes: se.max_memory_bytes,
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(script: &str, limits: ScriptLimits) -> Result<rhai::Dynamic, Box<EvalAltResult>> {
+        let mut engine = rhai::Engine::new();
+        limits.apply_to_rhai(&mut engine);
+        engine.eval::<rhai::Dynamic>(script)
+    }
+
+    #[test]
+    fn operation_budget_stops_a_runaway_loop() {
+        let limits = ScriptLimits {
+            max_operations: 10_000,
+            ..ScriptLimits::default()
+        };
+        let err = run("let i = 0; loop { i += 1; }", limits).unwrap_err();
+        let exceeded = limits.exceeded_by(&err).expect("a limit error");
+        assert_eq!(
+            exceeded.to_string(),
+            "script exceeded its operation limit (10000 operations)"
+        );
+    }
+
+    #[test]
+    fn memory_cap_stops_a_growing_string_or_array() {
+        let limits = ScriptLimits {
+            max_memory_bytes: 1024,
+            ..ScriptLimits::default()
+        };
+        for script in [
+            r#"let s = "x"; loop { s += s; }"#,
+            "let a = []; loop { a.push(1); }",
+        ] {
+            let err = run(script, limits).unwrap_err();
+            let exceeded = limits.exceeded_by(&err).expect("a limit error");
+            assert!(
+                exceeded.limit.starts_with("memory limit (1024 bytes)"),
+                "{script}: {exceeded}"
+            );
+        }
+    }
+
+    #[test]
+    fn limit_hit_inside_a_function_is_still_recognised() {
+        let limits = ScriptLimits {
+            max_operations: 10_000,
+            ..ScriptLimits::default()
+        };
+        let err = run("fn spin() { loop {} } spin()", limits).unwrap_err();
+        assert!(limits.exceeded_by(&err).is_some(), "{err}");
+    }
+
+    #[test]
+    fn other_errors_are_not_limit_errors() {
+        let err = run("undefined_fn()", ScriptLimits::default()).unwrap_err();
+        assert!(ScriptLimits::default().exceeded_by(&err).is_none());
+    }
+
+    #[test]
+    fn javascript_output_is_held_to_the_memory_cap() {
+        let limits = ScriptLimits {
+            max_memory_bytes: 8,
+            ..ScriptLimits::default()
+        };
+        let headers = std::collections::HashMap::from([("a".to_string(), "bc".to_string())]);
+        assert_eq!(js_output_bytes("hello", &headers), 8);
+        assert!(limits.exceeded_by_js_output(8).is_none());
+        assert_eq!(
+            limits.exceeded_by_js_output(9).unwrap().to_string(),
+            "script exceeded its memory limit (8 bytes): javascript result is 9 bytes"
+        );
+        let unlimited = ScriptLimits {
+            max_memory_bytes: 0,
+            ..limits
+        };
+        assert!(unlimited.exceeded_by_js_output(usize::MAX).is_none());
+    }
+
+    #[test]
+    fn zero_lifts_a_limit() {
+        let limits = ScriptLimits {
+            max_operations: 0,
+            max_memory_bytes: 0,
+        };
+        let result = run("let i = 0; while i < 200000 { i += 1; } i", limits).unwrap();
+        assert_eq!(result.as_int().unwrap(), 200_000);
+    }
+}
@@ -9,11 +9,18 @@ use std::sync::Arc;
 // Engine modules (only used by proxy.rs for compilation)
 mod bounded;
 mod compiled_cache;
+mod limits;
 mod rhai_engine;
 pub use bounded::{
     DEFAULT_SCRIPT_TIMEOUT_MS, ScriptTimeoutError, resolve_script_timeout_ms,
     should_inject_bounded, should_inject_bounded_with_ctx, should_inject_bounded_with_ctx_traced,
 };
+#[cfg(feature = "javascript")]
+pub(crate) use limits::js_output_bytes;
+pub use limits::{
+    DEFAULT_SCRIPT_MAX_MEMORY_BYTES, DEFAULT_SCRIPT_MAX_OPERATIONS, ScriptLimitError, ScriptLimits,
+    resolve_script_limits,
+};
 
 pub use rhai_engine::RhaiEngine;
 /// Exposed for the decision-cache payoff bench (issue #665): the worker-side execute with a
@@ -266,6 +273,70 @@ impl ScriptResult {
         }
     }
 
+    /// A plain `{ status, headers, body }` object returned from `respond(ctx)` instead of a result
+    /// constructor, in either engine: the same response as `http(status, body)` with each header
+    /// applied. `status` defaults to 200; a string `body` is sent verbatim and any other JSON
+    /// value is serialized (with `Content-Type: application/json` unless `headers` sets one).
+    /// Any other key, or a field of the wrong type, is an error rather than being dropped.
+    pub fn from_response_object(value: Value) -> Result<Self> {
+        let Value::Object(fields) = value else {
+            return Err(anyhow!("a response object must be a map/object"));
+        };
+        let mut status = 200;
+        let mut body = None;
+        let mut headers = Vec::new();
+        for (key, value) in fields {
+            match (key.as_str(), value) {
+                ("status", Value::Number(n)) => {
+                    status = n
+                        .as_u64()
+                        .and_then(|n| u16::try_from(n).ok())
+                        .filter(|n| (100..=999).contains(n))
+                        .ok_or_else(|| {
+                            anyhow!("response object `status` must be an integer 100-999, got {n}")
+                        })?;
+                }
+                ("status", other) => {
+                    return Err(anyhow!(
+                        "response object `status` must be a number, got {other}"
+                    ));
+                }
+                ("headers", Value::Object(map)) => {
+                    for (name, value) in map {
+                        let value = match value {
+                            Value::String(s) => s,
+                            Value::Number(_) | Value::Bool(_) => value.to_string(),
+                            other => {
+                                return Err(anyhow!(
+                                    "response object header '{name}' must be a string, got {other}"
+                                ));
+                            }
+                        };
+                        headers.push((name, value));
+                    }
+                }
+                ("headers", Value::Null) | ("body", Value::Null) => {}
+                ("headers", other) => {
+                    return Err(anyhow!(
+                        "response object `headers` must be a map/object, got {other}"
+                    ));
+                }
+                ("body", Value::String(s)) => body = Some(ScriptResultBody::Str(s)),
+                ("body", other) => body = Some(ScriptResultBody::Json(other)),
+                (other, _) => {
+                    return Err(anyhow!(
+                        "unknown response object field `{other}` (expected status, headers, body)"
+                    ));
+                }
+            }
+        }
+        Ok(ScriptResult::Http {
+            status,
+            body,
+            headers,
+        })
+    }
+
     /// Apply a `.header(k, v)` builder call. A no-op on `Delay`/`Reset`/`Pass` — only `http()`
     /// results carry headers.
     pub fn add_header(&mut self, key: String, value: String) {
@@ -78,6 +78,13 @@ fn is_leap_year(year: u64) -> bool {
 /// http(429, #{ error: "Rate limited" }).header("Retry-After", "60")
 /// ```
 ///
+/// A plain `#{ status, headers, body }` map is the same response as `http(status, body)` with
+/// those headers, so a script can build a complete response as data:
+///
+/// ```rhai
+/// #{ status: 201, headers: #{ "Location": "/users/7" }, body: #{ id: 7 } }
+/// ```
+///
 /// ## Example
 ///
 /// ```rhai
@@ -207,6 +214,8 @@ impl RhaiEngine {
         });
 
         register_v2_api(&mut engine);
+        crate::extensions::encoding::register_rhai_fns(&mut engine);
+        super::ScriptLimits::default().apply_to_rhai(&mut engine);
 
         engine
     }
@@ -527,10 +536,12 @@ fn run_entrypoint(
     if has_named {
         engine
             .run_ast_with_scope(&mut scope, ast)
-            .map_err(|e| anyhow!("Script execution error: {e}"))?;
+            .map_err(|e| execution_error(engine, &e, "Script execution error"))?;
         let result: Dynamic = engine
             .call_fn(&mut scope, ast, entrypoint, (ctx_map,))
-            .map_err(|e| anyhow!("Failed to call {entrypoint}(ctx): {e}"))?;
+            .map_err(|e| {
+                execution_error(engine, &e, &format!("Failed to call {entrypoint}(ctx)"))
+            })?;
         Ok(result)
     } else {
         // Bare-expression script (issue #357 Item 2): the whole body IS the function, with `ctx`
@@ -539,7 +550,7 @@ fn run_entrypoint(
         scope.push("ctx", ctx_map);
         let result: Dynamic = engine
             .eval_ast_with_scope(&mut scope, ast)
-            .map_err(|e| anyhow!("Script execution error: {e}"))?;
+            .map_err(|e| execution_error(engine, &e, "Script execution error"))?;
         // B1 (issue #357, "nothing fails silently"): a script that declares function(s) but none
         // is the requested entrypoint and whose top-level completion value is unit almost
         // certainly has a MISNAMED entrypoint (e.g. `fn respnod(ctx)`). Falling back to the
@@ -557,14 +568,35 @@ fn run_entrypoint(
     }
 }
 
+/// A Rhai execution error, as a [`ScriptLimitError`](super::ScriptLimitError) when the script hit
+/// one of `engine`'s limits, so the handler can tell it from a broken script.
+fn execution_error(engine: &Engine, error: &rhai::EvalAltResult, context: &str) -> anyhow::Error {
+    match super::ScriptLimits::of_rhai(engine).exceeded_by(error) {
+        Some(exceeded) => exceeded.into(),
+        None => anyhow!("{context}: {error}"),
+    }
+}
+
 fn dynamic_to_fault_decision(result: Dynamic, rule_id: &str) -> Result<FaultDecision> {
     if result.is_unit() {
         return Ok(FaultDecision::None);
     }
-    let script_result = result.try_cast::<RhaiScriptResult>().ok_or_else(|| {
-        anyhow!("respond(ctx) must return http(...)/delay(...)/reset()/pass() or nothing")
-    })?;
-    Ok(script_result.0.into_fault_decision(rule_id))
+    let script_result = dynamic_to_script_result(result).ok_or_else(|| {
+        anyhow!(
+            "respond(ctx) must return http(...)/delay(...)/reset()/pass(), a \
+             #{{ status, headers, body }} map, or nothing"
+        )
+    })??;
+    Ok(script_result.into_fault_decision(rule_id))
+}
+
+/// A result constructor, or a `#{ status, headers, body }` map read as the equivalent `http()`
+/// (see [`ScriptResult::from_response_object`]). `None` for any other value.
+fn dynamic_to_script_result(result: Dynamic) -> Option<Result<ScriptResult>> {
+    if result.is_map() {
+        return Some(ScriptResult::from_response_object(dynamic_to_json(result)));
+    }
+    result.try_cast::<RhaiScriptResult>().map(|r| Ok(r.0))
 }
 
 fn dynamic_to_matches_bool(result: Dynamic) -> bool {
@@ -585,10 +617,13 @@ fn dynamic_to_transform_result(result: Dynamic) -> Result<Option<ScriptResult>>
     if result.is_unit() {
         return Ok(None);
     }
-    let script_result = result
-        .try_cast::<RhaiScriptResult>()
-        .ok_or_else(|| anyhow!("transform(ctx) must return http(...)/pass() or nothing"))?;
-    Ok(Some(script_result.0))
+    let script_result = dynamic_to_script_result(result).ok_or_else(|| {
+        anyhow!(
+            "transform(ctx) must return http(...)/pass(), a #{{ status, headers, body }} map, \
+             or nothing"
+        )
+    })??;
+    Ok(Some(script_result))
 }
 
 /// `respond(ctx)` (issue #357 Item 2): the response-script entrypoint.
@@ -853,6 +888,9 @@ pub(super) fn dynamic_to_json(value: Dynamic) -> Value {
 /// The AST is compiled through the content-addressed cache (issue #356): repeated requests for
 /// the same `code` (e.g. several stubs `ref:`-ing the same registry entry, or repeated requests
 /// against the same stub) reuse the compiled AST instead of recompiling every call.
+///
+/// The script also runs under the imposter's `limits`, which stop it even when nothing sets
+/// `abort`.
 pub fn run_should_inject_with_abort_rhai(
     code: &str,
     rule_id: &str,
@@ -860,9 +898,11 @@ pub fn run_should_inject_with_abort_rhai(
     flow_store: Arc<dyn FlowStore>,
     abort: &Arc<AtomicBool>,
     ctx_extra: &ScriptCtxExtras,
+    limits: &super::ScriptLimits,
 ) -> Result<FaultDecision> {
     let ast = super::compiled_cache::cached_rhai_ast(code)?;
     let mut engine = RhaiEngine::create_engine();
+    limits.apply_to_rhai(&mut engine);
     let flag = Arc::clone(abort);
     engine.on_progress(move |_ops| {
         if flag.load(Ordering::Relaxed) {
@@ -1867,5 +1907,36 @@ mod tests {
             let decision = run_respond(script, &req(HashMap::new(), None)).unwrap();
             assert!(matches!(decision, FaultDecision::Error { status: 503, .. }));
         }
+
+        #[test]
+        fn response_map_builds_a_full_response() {
+            let script = r#"
+                fn respond(ctx) {
+                    #{ status: 404, headers: #{ "X-Reason": "gone" }, body: "no such order" }
+                }
+            "#;
+            match run_respond(script, &req(HashMap::new(), None)).unwrap() {
+                FaultDecision::Error {
+                    status,
+                    body,
+                    headers,
+                    ..
+                } => {
+                    assert_eq!(status, 404);
+                    assert_eq!(body, "no such order");
+                    assert_eq!(headers["X-Reason"], "gone");
+                    assert!(!headers.contains_key("Content-Type"));
+                }
+                other => panic!("expected Error(404), got {other:?}"),
+            }
+        }
+
+        #[test]
+        fn response_map_with_bad_status_is_an_error() {
+            let err = run_respond(r#"#{ status: "ok" }"#, &req(HashMap::new(), None))
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains("status"), "got {err}");
+        }
     }
 }
@@ -268,6 +268,8 @@ mod tests {
                         ref_name: None,
                     }),
                     templated: false,
+                    fetch: Default::default(),
+                    stream: None,
                 },
             }],
             scenario_name: None,
@@ -380,6 +382,8 @@ mod tests {
                             ref_name: None,
                         }),
                         templated: false,
+                        fetch: Default::default(),
+                        stream: None,
                     },
                 }],
                 scenario_name: None,
@@ -405,6 +409,8 @@ mod tests {
                             ref_name: None,
                         }),
                         templated: false,
+                        fetch: Default::default(),
+                        stream: None,
                     },
                 }],
                 scenario_name: None,
@@ -325,6 +325,71 @@ async fn decorate_timeout_strict_returns_504() {
     let _ = manager.delete_imposter(19764).await;
 }
 
+// A script stopped by `_rift.scriptEngine.maxOperations` / `maxMemoryBytes` carries the timeout
+// marker like a deadline miss, but stays the permanent 500: the same request hits the same limit.
+#[tokio::test]
+async fn rift_script_operation_limit_returns_500_with_marker() {
+    let manager = ImposterManager::new();
+    create(
+        &manager,
+        serde_json::json!({
+            "port": 19769, "protocol": "http",
+            "_rift": { "scriptEngine": { "timeoutMs": 60000, "maxOperations": 1000 } },
+            "stubs": [
+                { "responses": [{ "_rift": { "script": { "engine": "rhai", "code": "let i = 0; loop { i += 1; }" } } }] }
+            ]
+        }),
+    )
+    .await;
+
+    let resp = get(19769).await;
+    assert_eq!(
+        resp.status(),
+        500,
+        "an operation-limit stop is a 500, not a 504"
+    );
+    assert!(resp.headers().contains_key("x-rift-script-error"));
+    assert!(resp.headers().contains_key("x-rift-script-timeout"));
+    let body = resp.text().await.expect("body");
+    assert!(
+        body.contains("operation limit (1000 operations)"),
+        "the 500 must name the limit, got: {body}"
+    );
+
+    let _ = manager.delete_imposter(19769).await;
+}
+
+// Decorate runs under the same limits: a Rhai decorate growing a string past `maxMemoryBytes` is
+// a strict 500 with both markers, long before the deadline.
+#[tokio::test]
+async fn decorate_memory_limit_strict_returns_500_with_marker() {
+    let manager = ImposterManager::new();
+    create(
+        &manager,
+        serde_json::json!({
+            "port": 19692, "protocol": "http", "strictBehaviors": true,
+            "_rift": { "scriptEngine": { "timeoutMs": 60000, "maxMemoryBytes": 4096 } },
+            "stubs": [
+                { "responses": [{ "is": { "statusCode": 200, "body": "original" },
+                  "_behaviors": { "decorate": "let s = \"x\"; loop { s += s; }" } }] }
+            ]
+        }),
+    )
+    .await;
+
+    let resp = get(19692).await;
+    assert_eq!(resp.status(), 500, "a memory-limit stop is a 500");
+    assert!(resp.headers().contains_key("x-rift-decorate-error"));
+    assert!(resp.headers().contains_key("x-rift-script-timeout"));
+    let body = resp.text().await.expect("body");
+    assert!(
+        body.contains("memory limit (4096 bytes)"),
+        "the 500 must name the limit, got: {body}"
+    );
+
+    let _ = manager.delete_imposter(19692).await;
+}
+
 // ---------------------------------------------------------------------------
 // Issue #682: these doors hand-built their JSON by interpolating the error into a string literal,
 // so a message holding a `"` produced a body the client could not decode (the #611 class), in a
@@ -61,6 +61,7 @@ fn is_response(n_headers: usize) -> (IsResponse, Arc<str>) {
         status_code: 200,
         headers,
         body: Some(body),
+        body_file: None,
         mode: ResponseMode::Text,
     };
     (is, rendered)
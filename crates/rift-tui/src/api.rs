@@ -1,8 +1,12 @@
 //! HTTP client for Rift Admin API communication
 
+use base64::Engine;
 use reqwest::Client;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use thiserror::Error;
 
 /// Errors that can occur when communicating with the Admin API
@@ -16,6 +20,10 @@ pub enum ApiError {
     Parse(String),
     #[error("Connection failed: {0}")]
     Connection(String),
+    #[error("Invalid API key: {0}")]
+    InvalidApiKey(String),
+    #[error("Not connected to {0}; nothing was changed")]
+    Offline(String),
 }
 
 /// Summary of an imposter for list view
@@ -33,6 +41,9 @@ pub struct ImposterSummary {
     pub enabled: bool,
     #[serde(default)]
     pub record_requests: bool,
+    /// Position in `App::servers` of the server it is on, when listing every server's imposters
+    #[serde(skip)]
+    pub server: Option<usize>,
 }
 
 /// Full imposter details
@@ -85,6 +96,132 @@ pub struct RecordedRequest {
     pub timestamp: Option<String>,
 }
 
+/// A request the imposter answered, from `GET /imposters/{port}/servedRequests`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServedRequest {
+    pub seq: u64,
+    pub method: String,
+    pub path: String,
+    pub query: Option<String>,
+    pub status_code: u16,
+    /// Index of the stub that answered; `None` when no stub matched
+    pub stub_index: Option<usize>,
+    pub stub_id: Option<String>,
+    pub latency_ms: u64,
+    pub timestamp: String,
+}
+
+/// Response wrapper for served requests
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServedRequestsResponse {
+    /// Requests served since the log was last cleared
+    pub count: u64,
+    #[serde(default)]
+    pub served_requests: Vec<ServedRequest>,
+}
+
+/// One side of a served request, as kept in detail by the server
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServedMessage {
+    #[serde(default)]
+    pub headers: HashMap<String, Vec<String>>,
+    pub body: Option<String>,
+    /// `binary` when `body` is base64-encoded
+    #[serde(rename = "_mode")]
+    pub mode: Option<String>,
+    /// Size of a body too large for the server to keep
+    pub body_omitted: Option<usize>,
+}
+
+impl ServedMessage {
+    pub fn is_binary(&self) -> bool {
+        self.mode.as_deref() == Some("binary")
+    }
+
+    /// The body as it was sent
+    pub fn body_bytes(&self) -> Result<Vec<u8>, ApiError> {
+        if let Some(size) = self.body_omitted {
+            return Err(ApiError::Parse(format!(
+                "the {size}-byte body was too large for the server to keep"
+            )));
+        }
+        let body = self.body.as_deref().unwrap_or_default();
+        if self.is_binary() {
+            base64::engine::general_purpose::STANDARD
+                .decode(body)
+                .map_err(|e| ApiError::Parse(format!("invalid base64 body: {e}")))
+        } else {
+            Ok(body.as_bytes().to_vec())
+        }
+    }
+}
+
+/// A served request with the headers and bodies exchanged
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServedRequestDetail {
+    #[serde(flatten)]
+    pub summary: ServedRequest,
+    pub request: ServedMessage,
+    pub response: ServedMessage,
+}
+
+impl ServedRequestDetail {
+    /// The request again, ready to send to an imposter
+    pub fn replay_request(&self) -> Result<ImposterRequest, ApiError> {
+        let mut headers: Vec<(String, String)> = self
+            .request
+            .headers
+            .iter()
+            .flat_map(|(name, values)| values.iter().map(move |v| (name.clone(), v.clone())))
+            .collect();
+        headers.sort();
+        Ok(ImposterRequest {
+            method: self.summary.method.clone(),
+            path: match &self.summary.query {
+                Some(query) => format!("{}?{query}", self.summary.path),
+                None => self.summary.path.clone(),
+            },
+            headers,
+            body: self.request.body_bytes()?,
+        })
+    }
+}
+
+/// A request sent straight to an imposter rather than the admin API
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImposterRequest {
+    pub method: String,
+    /// Path with an optional query string
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// What an imposter answered to an [`ImposterRequest`]
+#[derive(Debug, Clone)]
+pub struct ImposterResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    pub latency_ms: u64,
+}
+
+/// Connection-level headers the HTTP client sets itself for the request it sends
+const HOP_BY_HOP_HEADERS: &[&str] = &[
+    "host",
+    "content-length",
+    "connection",
+    "keep-alive",
+    "transfer-encoding",
+    "te",
+    "trailer",
+    "upgrade",
+    "proxy-connection",
+];
+
 /// Response wrapper for imposter list
 #[derive(Debug, Deserialize)]
 pub struct ImpostersResponse {
@@ -116,6 +253,9 @@ pub struct CreateImposterRequest {
     pub record_requests: bool,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub stubs: Vec<Stub>,
+    /// Response for requests no stub matches, as an `is` response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_response: Option<serde_json::Value>,
 }
 
 /// Request body for adding a stub
@@ -175,6 +315,90 @@ pub struct PredicateGenerator {
     pub matches: serde_json::Value,
 }
 
+/// Where a script sits in a stub, which decides how the server calls it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScriptKind {
+    /// A `_rift.script` `respond` script
+    Script,
+    /// A Mountebank `inject` response function
+    Inject,
+    /// A Mountebank `decorate` behavior
+    Decorate,
+}
+
+/// A script to check or run with `/_scripts/check` and `/_scripts/run`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScriptSource {
+    pub kind: ScriptKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub engine: Option<String>,
+    pub code: String,
+    /// The sample request, in `rift script run --request` fixture form
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request: Option<serde_json::Value>,
+    /// The response a `decorate` is given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<serde_json::Value>,
+}
+
+/// What the server's validator found in a script
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ScriptCheck {
+    pub engine: String,
+    pub valid: bool,
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+/// What a script did with a sample request
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptRun {
+    pub engine: String,
+    /// A `_rift.script`'s decision, such as `pass()` or `http(503) ...`
+    pub decision: Option<String>,
+    /// The response an `inject` built or a `decorate` left
+    pub response: Option<ScriptResponse>,
+    #[serde(default)]
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub logs: Vec<String>,
+    #[serde(default)]
+    pub state: serde_json::Map<String, serde_json::Value>,
+    pub error: Option<String>,
+}
+
+/// A response built or changed by a script
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptResponse {
+    pub status_code: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub body: String,
+}
+
+/// One line of the server's log, from `GET /logs`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LogEntry {
+    pub level: String,
+    pub message: String,
+    #[serde(default)]
+    pub timestamp: String,
+}
+
+/// A page of the server's log
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogsPage {
+    pub logs: Vec<LogEntry>,
+    /// Index to pass as the next `startIndex`; `None` from servers without a log cursor
+    pub next_index: Option<u64>,
+    /// Entries after the requested start were dropped by the server before they were read
+    pub truncated: bool,
+}
+
 /// Metrics data parsed from Prometheus format
 #[derive(Debug, Clone, Default)]
 pub struct MetricsData {
@@ -187,26 +411,63 @@ pub struct MetricsData {
 pub struct ImposterMetrics {
     pub request_count: u64,
     pub requests_per_second: f64,
+    /// Latency histogram as `(upper bound in ms, requests up to it)`, cumulative, ending with
+    /// the `+Inf` bucket
+    pub latency_buckets: Vec<(f64, u64)>,
+    pub latency_sum_ms: u64,
+    /// Requests served, counted whether or not the imposter records them
+    pub served_count: u64,
+    pub status_codes: BTreeMap<u16, u64>,
+    /// Injected faults by kind
+    pub faults: BTreeMap<String, u64>,
+    /// Hits per stub index, with the stub's id if it has one
+    pub stub_hits: BTreeMap<usize, (Option<String>, u64)>,
+    /// Requests no stub matched
+    pub unmatched: u64,
 }
 
+/// Imposters asked for per `GET /imposters` page
+pub const IMPOSTER_PAGE_SIZE: usize = 500;
+
 /// HTTP client for the Rift Admin API
+#[derive(Clone)]
 pub struct ApiClient {
     client: Client,
+    /// Client for requests to imposters: no admin API key, and redirects are returned as served
+    imposter_client: Client,
     base_url: String,
+    /// Whether the last health check reached the server, shared by every clone. Changes are
+    /// refused while it is down rather than left to time out half-way.
+    reachable: Arc<AtomicBool>,
 }
 
 impl ApiClient {
     /// Create a new API client
     pub fn new(base_url: &str) -> Self {
         Self {
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(10))
+            client: build_client(HeaderMap::new()),
+            imposter_client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .redirect(reqwest::redirect::Policy::none())
                 .build()
                 .expect("Failed to create HTTP client"),
             base_url: base_url.trim_end_matches('/').to_string(),
+            reachable: Arc::new(AtomicBool::new(true)),
         }
     }
 
+    /// Send `key` as the `Authorization` header of every request, for an admin API started with
+    /// `--api-key`. The key is sent bare, the form Mountebank's `--apikey` expects.
+    pub fn with_api_key(mut self, key: &str) -> Result<Self, ApiError> {
+        let mut value =
+            HeaderValue::from_str(key).map_err(|e| ApiError::InvalidApiKey(e.to_string()))?;
+        value.set_sensitive(true);
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, value);
+        self.client = build_client(headers);
+        Ok(self)
+    }
+
     /// Get the underlying HTTP client
     pub fn client(&self) -> &Client {
         &self.client
@@ -217,6 +478,24 @@ impl ApiClient {
         &self.base_url
     }
 
+    /// Record whether the server answered its last health check
+    pub fn set_reachable(&self, reachable: bool) {
+        self.reachable.store(reachable, Ordering::Relaxed);
+    }
+
+    pub fn is_reachable(&self) -> bool {
+        self.reachable.load(Ordering::Relaxed)
+    }
+
+    /// Refuse a change up front while the server is known to be down
+    fn ensure_reachable(&self) -> Result<(), ApiError> {
+        if self.is_reachable() {
+            Ok(())
+        } else {
+            Err(ApiError::Offline(self.base_url.clone()))
+        }
+    }
+
     /// Check if the server is healthy
     pub async fn health_check(&self) -> Result<bool, ApiError> {
         let url = format!("{}/health", self.base_url);
@@ -236,19 +515,48 @@ impl ApiClient {
     }
 
     /// List all imposters
+    ///
+    /// Fetched a page at a time, so a server with thousands of imposters answers in requests that
+    /// each stay well inside the client timeout. A server that doesn't page (no
+    /// `x-rift-total-count`) sends the whole list with the first one.
     pub async fn list_imposters(&self) -> Result<Vec<ImposterSummary>, ApiError> {
         let url = format!("{}/imposters", self.base_url);
-        let resp = self.client.get(&url).send().await?;
+        let mut imposters: Vec<ImposterSummary> = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        for page in 1.. {
+            let resp = self
+                .client
+                .get(&url)
+                .query(&[("page", page), ("size", IMPOSTER_PAGE_SIZE)])
+                .send()
+                .await?;
+
+            if !resp.status().is_success() {
+                return self.handle_error(resp).await;
+            }
 
-        if !resp.status().is_success() {
-            return self.handle_error(resp).await;
+            let total: Option<usize> = resp
+                .headers()
+                .get("x-rift-total-count")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok());
+            // The list payload carries stubCount/enabled directly (issue #558) — no per-imposter
+            // detail fetches, so refresh is one request per page and a transient per-imposter
+            // failure can no longer be silently rendered as "Disabled / 0 stubs".
+            let body: ImpostersResponse = resp.json().await?;
+            let received = body.imposters.len();
+            // Imposters created or deleted between pages shift the rest; don't list one twice
+            imposters.extend(
+                body.imposters
+                    .into_iter()
+                    .filter(|imp| seen.insert(imp.port)),
+            );
+            match total {
+                Some(total) if received > 0 && page * IMPOSTER_PAGE_SIZE < total => {}
+                _ => break,
+            }
         }
-
-        // The list payload carries stubCount/enabled directly (issue #558) — no per-imposter
-        // detail fetches, so refresh is one request per tick and a transient per-imposter failure
-        // can no longer be silently rendered as "Disabled / 0 stubs".
-        let body: ImpostersResponse = resp.json().await?;
-        Ok(body.imposters)
+        Ok(imposters)
     }
 
     /// Get details for a specific imposter
@@ -265,8 +573,15 @@ impl ApiClient {
 
     /// Create a new imposter
     pub async fn create_imposter(&self, request: CreateImposterRequest) -> Result<u16, ApiError> {
+        let config = serde_json::to_value(&request).map_err(|e| ApiError::Parse(e.to_string()))?;
+        self.create_imposter_from(&config).await
+    }
+
+    /// Create an imposter from its full JSON config, returning the port it got
+    pub async fn create_imposter_from(&self, config: &serde_json::Value) -> Result<u16, ApiError> {
+        self.ensure_reachable()?;
         let url = format!("{}/imposters", self.base_url);
-        let resp = self.client.post(&url).json(&request).send().await?;
+        let resp = self.client.post(&url).json(config).send().await?;
 
         if !resp.status().is_success() {
             return self.handle_error(resp).await;
@@ -276,8 +591,22 @@ impl ApiClient {
         Ok(detail.port)
     }
 
+    /// Create an imposter from a config exported with `replayable=true`
+    pub async fn restore_imposter(&self, config: &serde_json::Value) -> Result<(), ApiError> {
+        self.ensure_reachable()?;
+        let url = format!("{}/imposters", self.base_url);
+        let resp = self.client.post(&url).json(config).send().await?;
+
+        if !resp.status().is_success() {
+            return self.handle_error(resp).await;
+        }
+
+        Ok(())
+    }
+
     /// Delete an imposter
     pub async fn delete_imposter(&self, port: u16) -> Result<(), ApiError> {
+        self.ensure_reachable()?;
         let url = format!("{}/imposters/{}", self.base_url, port);
         let resp = self.client.delete(&url).send().await?;
 
@@ -290,6 +619,7 @@ impl ApiClient {
 
     /// Enable an imposter
     pub async fn enable_imposter(&self, port: u16) -> Result<(), ApiError> {
+        self.ensure_reachable()?;
         let url = format!("{}/imposters/{}/enable", self.base_url, port);
         let resp = self.client.post(&url).send().await?;
 
@@ -302,6 +632,7 @@ impl ApiClient {
 
     /// Disable an imposter
     pub async fn disable_imposter(&self, port: u16) -> Result<(), ApiError> {
+        self.ensure_reachable()?;
         let url = format!("{}/imposters/{}/disable", self.base_url, port);
         let resp = self.client.post(&url).send().await?;
 
@@ -314,6 +645,7 @@ impl ApiClient {
 
     /// Clear recorded requests
     pub async fn clear_requests(&self, port: u16) -> Result<(), ApiError> {
+        self.ensure_reachable()?;
         let url = format!("{}/imposters/{}/savedRequests", self.base_url, port);
         let resp = self.client.delete(&url).send().await?;
 
@@ -324,6 +656,88 @@ impl ApiClient {
         Ok(())
     }
 
+    /// Get the requests an imposter served after sequence number `since` (all retained when `None`)
+    pub async fn get_served_requests(
+        &self,
+        port: u16,
+        since: Option<u64>,
+    ) -> Result<ServedRequestsResponse, ApiError> {
+        let url = match since {
+            Some(seq) => format!(
+                "{}/imposters/{}/servedRequests?since={}",
+                self.base_url, port, seq
+            ),
+            None => format!("{}/imposters/{}/servedRequests", self.base_url, port),
+        };
+        let resp = self.client.get(&url).send().await?;
+
+        if !resp.status().is_success() {
+            return self.handle_error(resp).await;
+        }
+
+        Ok(resp.json().await?)
+    }
+
+    /// Get one served request with its headers and bodies
+    pub async fn get_served_request(
+        &self,
+        port: u16,
+        seq: u64,
+    ) -> Result<ServedRequestDetail, ApiError> {
+        let url = format!(
+            "{}/imposters/{}/servedRequests/{}",
+            self.base_url, port, seq
+        );
+        let resp = self.client.get(&url).send().await?;
+
+        if !resp.status().is_success() {
+            return self.handle_error(resp).await;
+        }
+
+        Ok(resp.json().await?)
+    }
+
+    /// Send a request to the imposter on `port`, on the admin API's host
+    pub async fn send_to_imposter(
+        &self,
+        port: u16,
+        request: &ImposterRequest,
+    ) -> Result<ImposterResponse, ApiError> {
+        let url = imposter_url(&self.base_url, port, &request.path)?;
+        let method = reqwest::Method::from_bytes(request.method.as_bytes())
+            .map_err(|_| ApiError::Parse(format!("invalid method: {}", request.method)))?;
+        let mut builder = self.imposter_client.request(method, url);
+        for (name, value) in &request.headers {
+            if !HOP_BY_HOP_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                builder = builder.header(name, value);
+            }
+        }
+        if !request.body.is_empty() {
+            builder = builder.body(request.body.clone());
+        }
+
+        let started = std::time::Instant::now();
+        let resp = builder.send().await?;
+        let status = resp.status().as_u16();
+        let headers = resp
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                )
+            })
+            .collect();
+        let body = String::from_utf8_lossy(&resp.bytes().await?).into_owned();
+        Ok(ImposterResponse {
+            status,
+            headers,
+            body,
+            latency_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+
     /// Get stubs for an imposter
     pub async fn get_stubs(&self, port: u16) -> Result<Vec<Stub>, ApiError> {
         let imposter = self.get_imposter(port).await?;
@@ -337,6 +751,7 @@ impl ApiClient {
         stub: Stub,
         index: Option<usize>,
     ) -> Result<(), ApiError> {
+        self.ensure_reachable()?;
         let url = format!("{}/imposters/{}/stubs", self.base_url, port);
         let request = AddStubRequest { stub, index };
         let resp = self.client.post(&url).json(&request).send().await?;
@@ -350,6 +765,7 @@ impl ApiClient {
 
     /// Update a stub
     pub async fn update_stub(&self, port: u16, index: usize, stub: Stub) -> Result<(), ApiError> {
+        self.ensure_reachable()?;
         let url = format!("{}/imposters/{}/stubs/{}", self.base_url, port, index);
         let resp = self.client.put(&url).json(&stub).send().await?;
 
@@ -360,8 +776,28 @@ impl ApiClient {
         Ok(())
     }
 
+    /// Move stub `index` to `new_index`, returning the imposter with its stubs in the new order
+    pub async fn move_stub(
+        &self,
+        port: u16,
+        index: usize,
+        new_index: usize,
+    ) -> Result<ImposterDetail, ApiError> {
+        self.ensure_reachable()?;
+        let url = format!("{}/imposters/{}/stubs/{}", self.base_url, port, index);
+        let body = serde_json::json!({ "newIndex": new_index });
+        let resp = self.client.put(&url).json(&body).send().await?;
+
+        if !resp.status().is_success() {
+            return self.handle_error(resp).await;
+        }
+
+        Ok(resp.json().await?)
+    }
+
     /// Delete a stub
     pub async fn delete_stub(&self, port: u16, index: usize) -> Result<(), ApiError> {
+        self.ensure_reachable()?;
         let url = format!("{}/imposters/{}/stubs/{}", self.base_url, port, index);
         let resp = self.client.delete(&url).send().await?;
 
@@ -375,37 +811,11 @@ impl ApiClient {
     /// Create a proxy imposter for recording
     pub async fn create_proxy_imposter(
         &self,
-        port: Option<u16>,
-        name: Option<String>,
-        target_url: &str,
-        mode: &str,
+        request: &CreateProxyImposterRequest,
     ) -> Result<u16, ApiError> {
+        self.ensure_reachable()?;
         let url = format!("{}/imposters", self.base_url);
-
-        let request = CreateProxyImposterRequest {
-            port,
-            protocol: "http".to_string(),
-            name,
-            record_requests: true,
-            stubs: vec![ProxyStub {
-                responses: vec![ProxyResponse {
-                    proxy: ProxyConfig {
-                        to: target_url.to_string(),
-                        mode: mode.to_string(),
-                        predicate_generators: vec![PredicateGenerator {
-                            matches: serde_json::json!({
-                                "method": true,
-                                "path": true,
-                                "query": true
-                            }),
-                        }],
-                        add_wait_behavior: true,
-                    },
-                }],
-            }],
-        };
-
-        let resp = self.client.post(&url).json(&request).send().await?;
+        let resp = self.client.post(&url).json(request).send().await?;
 
         if !resp.status().is_success() {
             return self.handle_error(resp).await;
@@ -417,6 +827,7 @@ impl ApiClient {
 
     /// Clear proxy responses (saved recordings)
     pub async fn clear_proxy_responses(&self, port: u16) -> Result<(), ApiError> {
+        self.ensure_reachable()?;
         let url = format!("{}/imposters/{}/savedProxyResponses", self.base_url, port);
         let resp = self.client.delete(&url).send().await?;
 
@@ -467,6 +878,7 @@ impl ApiClient {
 
     /// Replace all stubs for an imposter (used for reordering)
     pub async fn update_stubs(&self, port: u16, stubs: Vec<Stub>) -> Result<(), ApiError> {
+        self.ensure_reachable()?;
         let url = format!("{}/imposters/{}/stubs", self.base_url, port);
         let body = serde_json::json!({ "stubs": stubs });
         let resp = self.client.put(&url).json(&body).send().await?;
@@ -488,6 +900,35 @@ impl ApiClient {
         }
     }
 
+    /// Get the server's log from entry `start` on
+    pub async fn get_logs(&self, start: u64) -> Result<LogsPage, ApiError> {
+        #[derive(Deserialize)]
+        struct Body {
+            #[serde(default)]
+            logs: Vec<LogEntry>,
+        }
+
+        let url = format!("{}/logs?startIndex={}", self.base_url, start);
+        let resp = self.client.get(&url).send().await?;
+        if !resp.status().is_success() {
+            return self.handle_error(resp).await;
+        }
+        let header = |name: &str| {
+            resp.headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        let next_index = header("x-rift-next-index").and_then(|next| next.parse().ok());
+        let truncated = header("x-rift-truncated").is_some();
+        let body: Body = resp.json().await?;
+        Ok(LogsPage {
+            logs: body.logs,
+            next_index,
+            truncated,
+        })
+    }
+
     /// Get metrics data
     pub async fn get_metrics(&self) -> Result<MetricsData, ApiError> {
         let url = format!("{}/metrics", self.base_url);
@@ -501,6 +942,30 @@ impl ApiClient {
         Ok(parse_prometheus_metrics(&text))
     }
 
+    /// Check a script with the server's validator for its engine
+    pub async fn check_script(&self, script: &ScriptSource) -> Result<ScriptCheck, ApiError> {
+        let url = format!("{}/_scripts/check", self.base_url);
+        let resp = self.client.post(&url).json(script).send().await?;
+
+        if !resp.status().is_success() {
+            return self.handle_error(resp).await;
+        }
+
+        Ok(resp.json().await?)
+    }
+
+    /// Run a script against its sample request on the server, without saving it
+    pub async fn run_script(&self, script: &ScriptSource) -> Result<ScriptRun, ApiError> {
+        let url = format!("{}/_scripts/run", self.base_url);
+        let resp = self.client.post(&url).json(script).send().await?;
+
+        if !resp.status().is_success() {
+            return self.handle_error(resp).await;
+        }
+
+        Ok(resp.json().await?)
+    }
+
     /// Handle error responses
     async fn handle_error<T>(&self, resp: reqwest::Response) -> Result<T, ApiError> {
         let status = resp.status();
@@ -519,44 +984,137 @@ impl ApiClient {
     }
 }
 
+fn build_client(default_headers: HeaderMap) -> Client {
+    Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .default_headers(default_headers)
+        .build()
+        .expect("Failed to create HTTP client")
+}
+
+/// URL of `path` on the imposter listening on `port`, on the same host as the admin API
+pub fn imposter_url(base_url: &str, port: u16, path: &str) -> Result<reqwest::Url, ApiError> {
+    let mut url = reqwest::Url::parse(base_url).map_err(|e| ApiError::Connection(e.to_string()))?;
+    url.set_port(Some(port))
+        .map_err(|_| ApiError::Connection(format!("cannot set port on {base_url}")))?;
+    let path = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{path}")
+    };
+    url.join(&path)
+        .map_err(|e| ApiError::Parse(format!("invalid path {path}: {e}")))
+}
+
+/// A Prometheus sample's label names and values, in order
+type Labels = Vec<(String, String)>;
+
+/// Split a Prometheus sample line into its metric name, labels and value
+fn parse_sample(line: &str) -> Option<(&str, Labels, f64)> {
+    let (series, value) = line.trim().rsplit_once(char::is_whitespace)?;
+    let value = match value {
+        "+Inf" => f64::INFINITY,
+        value => value.parse().ok()?,
+    };
+    let Some((name, rest)) = series.split_once('{') else {
+        return Some((series.trim(), Vec::new(), value));
+    };
+    let mut labels = Vec::new();
+    let mut chars = rest.chars();
+    loop {
+        let key: String = chars
+            .by_ref()
+            .skip_while(|c| *c == ',' || c.is_whitespace())
+            .take_while(|c| *c != '=')
+            .collect();
+        if key.is_empty() || key.starts_with('}') {
+            break;
+        }
+        if chars.next() != Some('"') {
+            return None;
+        }
+        let mut value = String::new();
+        loop {
+            match chars.next()? {
+                '"' => break,
+                '\\' => match chars.next()? {
+                    'n' => value.push('\n'),
+                    c => value.push(c),
+                },
+                c => value.push(c),
+            }
+        }
+        labels.push((key.trim().to_string(), value));
+    }
+    Some((name, labels, value))
+}
+
 /// Parse Prometheus-format metrics into structured data
 fn parse_prometheus_metrics(text: &str) -> MetricsData {
     let mut data = MetricsData::default();
 
     for line in text.lines() {
         // Skip comments and empty lines
-        if line.starts_with('#') || line.is_empty() {
+        if line.starts_with('#') || line.trim().is_empty() {
             continue;
         }
+        let Some((name, labels, value)) = parse_sample(line) else {
+            continue;
+        };
+        let label = |key: &str| {
+            labels
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+        };
+        let count = value as u64;
 
-        // Parse rift_imposters_total
-        if line.starts_with("rift_imposters_total")
-            && let Some(value) = line.split_whitespace().last()
-        {
-            data.imposter_count = value.parse().unwrap_or(0);
+        if name == "rift_imposters_total" {
+            data.imposter_count = count as usize;
+            continue;
         }
-
-        // Parse rift_imposter_requests_total{port="..."} VALUE
-        if line.starts_with("rift_imposter_requests_total")
-            && let Some(port_start) = line.find("port=\"")
-        {
-            let port_str = &line[port_start + 6..];
-            if let Some(port_end) = port_str.find('"')
-                && let Ok(port) = port_str[..port_end].parse::<u16>()
-                && let Some(value) = line.split_whitespace().last()
-            {
-                let count: u64 = value.parse().unwrap_or(0);
+        let Some(port) = label("port").and_then(|p| p.parse::<u16>().ok()) else {
+            continue;
+        };
+        let imposter = data.per_imposter.entry(port).or_default();
+        match name {
+            "rift_imposter_requests_total" => {
+                imposter.request_count = count;
                 data.total_requests += count;
-                data.per_imposter.insert(
-                    port,
-                    ImposterMetrics {
-                        request_count: count,
-                        requests_per_second: 0.0,
-                    },
-                );
             }
+            "rift_imposter_request_duration_ms_bucket" => {
+                if let Some(le) = label("le").and_then(|le| match le {
+                    "+Inf" => Some(f64::INFINITY),
+                    le => le.parse().ok(),
+                }) {
+                    imposter.latency_buckets.push((le, count));
+                }
+            }
+            "rift_imposter_request_duration_ms_sum" => imposter.latency_sum_ms = count,
+            "rift_imposter_request_duration_ms_count" => imposter.served_count = count,
+            "rift_imposter_responses_total" => {
+                if let Some(status) = label("status").and_then(|s| s.parse().ok()) {
+                    imposter.status_codes.insert(status, count);
+                }
+            }
+            "rift_imposter_faults_total" => {
+                if let Some(fault) = label("fault") {
+                    imposter.faults.insert(fault.to_string(), count);
+                }
+            }
+            "rift_imposter_stub_hits_total" => {
+                if let Some(index) = label("stub").and_then(|s| s.parse().ok()) {
+                    let id = label("stub_id").map(str::to_string);
+                    imposter.stub_hits.insert(index, (id, count));
+                }
+            }
+            "rift_imposter_unmatched_requests_total" => imposter.unmatched = count,
+            _ => {}
         }
     }
+    for imposter in data.per_imposter.values_mut() {
+        imposter.latency_buckets.sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
 
     data
 }
@@ -577,6 +1135,17 @@ mod tests {
         assert_eq!(client.base_url(), "http://localhost:2525");
     }
 
+    #[test]
+    fn test_api_client_rejects_an_unsendable_api_key() {
+        let client = ApiClient::new("http://localhost:2525").with_api_key("s3cret");
+        assert!(client.is_ok());
+        let err = ApiClient::new("http://localhost:2525")
+            .with_api_key("bad\nkey")
+            .err()
+            .expect("newline is not a valid header value");
+        assert!(matches!(err, ApiError::InvalidApiKey(_)));
+    }
+
     #[test]
     fn imposter_summary_parses_list_payload_fields() {
         // Issue #558 contract: the list response carries stubCount/enabled, so the list view
@@ -620,6 +1189,61 @@ mod tests {
         assert!(!json.contains("scenarioName"));
     }
 
+    #[test]
+    fn served_requests_parse_with_and_without_a_matched_stub() {
+        let json = r#"{"count":2,"servedRequests":[
+            {"seq":1,"method":"GET","path":"/orders","query":"page=2","statusCode":200,
+             "stubIndex":0,"stubId":"orders","latencyMs":3,"timestamp":"t"},
+            {"seq":2,"method":"POST","path":"/nope","statusCode":404,"latencyMs":0,"timestamp":"t"}
+        ]}"#;
+        let body: ServedRequestsResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(body.count, 2);
+        assert_eq!(body.served_requests[0].stub_id.as_deref(), Some("orders"));
+        assert_eq!(body.served_requests[1].stub_index, None);
+        assert_eq!(body.served_requests[1].query, None);
+    }
+
+    #[test]
+    fn served_request_detail_replays_the_request_as_sent() {
+        let json = r#"{
+            "seq": 7, "method": "POST", "path": "/upload", "query": "v=2",
+            "statusCode": 201, "latencyMs": 3, "timestamp": "2026-01-01T00:00:00Z",
+            "request": { "headers": { "Content-Type": ["application/octet-stream"] },
+                         "body": "/wA=", "_mode": "binary" },
+            "response": { "headers": {}, "body": "ok" }
+        }"#;
+        let detail: ServedRequestDetail = serde_json::from_str(json).unwrap();
+        assert_eq!(detail.summary.seq, 7);
+        let request = detail.replay_request().unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/upload?v=2");
+        assert_eq!(request.body, [0xff, 0x00]);
+        assert_eq!(
+            request.headers,
+            [(
+                "Content-Type".to_string(),
+                "application/octet-stream".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn a_body_the_server_did_not_keep_cannot_be_replayed() {
+        let message = ServedMessage {
+            body_omitted: Some(100_000),
+            ..Default::default()
+        };
+        assert!(message.body_bytes().is_err());
+    }
+
+    #[test]
+    fn imposter_url_uses_the_admin_host_and_the_imposter_port() {
+        let url = imposter_url("http://rift.local:2525", 4545, "/orders?id=1").unwrap();
+        assert_eq!(url.as_str(), "http://rift.local:4545/orders?id=1");
+        let url = imposter_url("http://127.0.0.1:2525", 4546, "health").unwrap();
+        assert_eq!(url.as_str(), "http://127.0.0.1:4546/health");
+    }
+
     #[test]
     fn test_parse_prometheus_metrics() {
         let input = r#"
@@ -639,4 +1263,109 @@ rift_imposter_requests_total{port="4546"} 15
         assert_eq!(data.per_imposter.get(&4545).unwrap().request_count, 42);
         assert_eq!(data.per_imposter.get(&4546).unwrap().request_count, 15);
     }
+
+    #[test]
+    fn prometheus_metrics_include_latency_statuses_faults_and_stub_hits() {
+        let input = r#"
+rift_imposter_requests_total{port="4545"} 0
+rift_imposter_request_duration_ms_bucket{port="4545",le="1"} 3
+rift_imposter_request_duration_ms_bucket{port="4545",le="+Inf"} 4
+rift_imposter_request_duration_ms_sum{port="4545"} 12
+rift_imposter_request_duration_ms_count{port="4545"} 4
+rift_imposter_responses_total{port="4545",status="503"} 1
+rift_imposter_faults_total{port="4545",fault="CONNECTION_RESET_BY_PEER"} 1
+rift_imposter_stub_hits_total{port="4545",stub="0",stub_id="say \"hi\""} 3
+rift_imposter_stub_hits_total{port="4545",stub="1"} 1
+rift_imposter_unmatched_requests_total{port="4545"} 0
+"#;
+        let data = parse_prometheus_metrics(input);
+        let imposter = &data.per_imposter[&4545];
+        assert_eq!(imposter.request_count, 0);
+        assert_eq!(imposter.served_count, 4);
+        assert_eq!(imposter.latency_sum_ms, 12);
+        assert_eq!(imposter.latency_buckets, [(1.0, 3), (f64::INFINITY, 4)]);
+        assert_eq!(imposter.status_codes[&503], 1);
+        assert_eq!(imposter.faults["CONNECTION_RESET_BY_PEER"], 1);
+        assert_eq!(imposter.stub_hits[&0], (Some("say \"hi\"".to_string()), 3));
+        assert_eq!(imposter.stub_hits[&1], (None, 1));
+    }
+
+    /// Answer each connection to a local port with the next of `bodies`, sending `total` as
+    /// `x-rift-total-count` when given. Returns the URL and the request lines received.
+    fn serve_in_turn(
+        total: Option<usize>,
+        bodies: Vec<String>,
+    ) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        use std::io::{BufRead, BufReader, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for body in bodies {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                requests.push(line.trim_end().to_string());
+                while !line.trim_end().is_empty() {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                }
+                let total = total
+                    .map(|total| format!("x-rift-total-count: {total}\r\n"))
+                    .unwrap_or_default();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n{total}\
+                     content-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (url, server)
+    }
+
+    fn imposters_page(ports: std::ops::Range<u16>) -> String {
+        let imposters: Vec<_> = ports
+            .map(|port| serde_json::json!({ "port": port, "protocol": "http" }))
+            .collect();
+        serde_json::json!({ "imposters": imposters }).to_string()
+    }
+
+    #[tokio::test]
+    async fn list_imposters_fetches_every_page() {
+        let size = IMPOSTER_PAGE_SIZE as u16;
+        let (url, server) = serve_in_turn(
+            Some(IMPOSTER_PAGE_SIZE + 2),
+            vec![
+                imposters_page(1000..1000 + size),
+                // One created since the first page pushed the last of it onto this one
+                imposters_page(999 + size..1002 + size),
+            ],
+        );
+        let imposters = ApiClient::new(&url).list_imposters().await.unwrap();
+        assert_eq!(
+            imposters.len(),
+            IMPOSTER_PAGE_SIZE + 2,
+            "nothing listed twice"
+        );
+        let requests = server.join().unwrap();
+        assert_eq!(
+            requests,
+            [
+                format!("GET /imposters?page=1&size={size} HTTP/1.1"),
+                format!("GET /imposters?page=2&size={size} HTTP/1.1"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn list_imposters_takes_an_unpaged_list_in_one_request() {
+        let (url, server) = serve_in_turn(None, vec![imposters_page(4545..4548)]);
+        let imposters = ApiClient::new(&url).list_imposters().await.unwrap();
+        assert_eq!(imposters.len(), 3);
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
 }
@@ -0,0 +1,202 @@
+//! Accessibility mode: high-contrast colors, no glyph-only indicators, and every change of view
+//! or selection said in words on the status bar, for screen readers and low-color terminals
+
+use super::*;
+use crate::theme::ThemePreset;
+
+/// What an overlay is, said when it opens
+fn overlay_name(overlay: &Overlay) -> Option<String> {
+    Some(match overlay {
+        Overlay::None => return None,
+        Overlay::Help => "Help".to_string(),
+        Overlay::Confirm { message, .. } => format!("Confirm: {message}, Enter or Esc"),
+        Overlay::Error { message } => format!("Error: {message}"),
+        Overlay::Input { prompt, .. } | Overlay::FilePathInput { prompt, .. } => {
+            format!("{prompt}, type and press Enter")
+        }
+        Overlay::Export { title, .. } => title.clone(),
+        Overlay::Success { message } => message.clone(),
+        Overlay::ValidationResult { report, .. } => format!(
+            "Validation: {} errors, {} warnings",
+            report.errors, report.warnings
+        ),
+        Overlay::Errors => "Error log".to_string(),
+        Overlay::RequestInspector { port } => format!("Request inspector of :{port}"),
+        Overlay::Composer => "Request composer".to_string(),
+        Overlay::ImposterForm => "New imposter form".to_string(),
+        Overlay::ProxyWizard => "Proxy imposter wizard".to_string(),
+        Overlay::ScriptEditor => "Script editor".to_string(),
+        Overlay::CommandPalette => "Command palette, type to find an action".to_string(),
+        Overlay::GlobalSearch => "Search results".to_string(),
+        Overlay::TransferPreview => "Import or export preview, Enter to go ahead".to_string(),
+        Overlay::CopyMenu => "Copy as menu, pick a command".to_string(),
+        Overlay::DiffPreview { title, .. } => format!("{title}, Enter to apply"),
+        Overlay::ServerSwitcher { .. } => "Server switcher".to_string(),
+    })
+}
+
+impl App {
+    /// Turn accessibility mode on or off for this session. On, it switches to the high-contrast
+    /// theme; off, back to the theme from before.
+    pub fn set_accessible(&mut self, on: bool) {
+        if on == self.accessible {
+            return;
+        }
+        self.accessible = on;
+        if on {
+            let contrast = Theme::from_preset(ThemePreset::HighContrast);
+            self.theme_before_accessible = Some(std::mem::replace(&mut self.theme, contrast));
+        } else if let Some(theme) = self.theme_before_accessible.take() {
+            self.theme = theme;
+        }
+    }
+
+    /// Toggle accessibility mode from the command palette, and remember it for the next session
+    pub(super) fn toggle_accessible(&mut self) {
+        self.set_accessible(!self.accessible);
+        let on = self.accessible;
+        let saved = match &self.state_path {
+            Some(path) => crate::state::State::update(path, |state| state.accessible = on),
+            None => Ok(()),
+        };
+        let message = if on {
+            "Accessibility mode on: high contrast, changes said on this line"
+        } else {
+            "Accessibility mode off"
+        };
+        match saved {
+            Ok(()) => self.set_status(message.to_string(), StatusLevel::Info),
+            Err(e) => self.set_status(
+                format!("{message} (not remembered: {e})"),
+                StatusLevel::Warning,
+            ),
+        }
+    }
+
+    /// Where the TUI is and what is selected, in words
+    pub fn describe_focus(&self) -> String {
+        if let Some(name) = overlay_name(&self.overlay) {
+            return name;
+        }
+        match &self.view {
+            View::ImposterList => match self.selected_imposter() {
+                Some(imp) => format!(
+                    "Imposter {} of {}: :{} {} {}, {}, {} stubs, {} requests",
+                    self.imposter_list_state.selected().map_or(0, |i| i + 1),
+                    self.imposters.len(),
+                    imp.port,
+                    imp.name.as_deref().unwrap_or("unnamed"),
+                    imp.protocol,
+                    if imp.enabled { "enabled" } else { "disabled" },
+                    imp.stub_count,
+                    imp.number_of_requests
+                ),
+                None => format!("Imposter list, {} imposters", self.imposters.len()),
+            },
+            View::ImposterDetail { port } => {
+                let stubs = self
+                    .current_imposter
+                    .as_ref()
+                    .map_or(0, |imp| imp.stubs.len());
+                match self.stub_list_state.selected() {
+                    Some(index) if index < stubs => {
+                        let hits = self
+                            .stub_hit_count(*port, index)
+                            .map_or_else(String::new, |hits| format!(", {hits} hits"));
+                        format!("Imposter :{port}, stub {} of {stubs}{hits}", index + 1)
+                    }
+                    _ => format!("Imposter :{port}, {stubs} stubs"),
+                }
+            }
+            View::StubDetail { port, index } => format!("Stub {} of :{port}", index + 1),
+            View::StubEdit { port, index } => match index {
+                Some(index) => format!("Editing stub {} of :{port}", index + 1),
+                None => format!("New stub on :{port}"),
+            },
+            View::ImposterEdit => "Editing imposter".to_string(),
+            View::RequestDetail { port, index } => {
+                format!("Recorded request {} of :{port}", index + 1)
+            }
+            View::RequestLog { port } => match &self.request_log {
+                Some(log) => format!(
+                    "Request log of :{port}, {} requests, {}",
+                    log.entries.len(),
+                    if log.paused {
+                        "paused"
+                    } else if log.follow {
+                        "following"
+                    } else {
+                        "scrolled back"
+                    }
+                ),
+                None => format!("Request log of :{port}"),
+            },
+            View::Metrics => match self.metrics_selected_port() {
+                Some(port) => format!(
+                    "Metrics, {} window, :{port} selected",
+                    self.metrics_window.label()
+                ),
+                None => format!("Metrics, {} window", self.metrics_window.label()),
+            },
+            View::ServerLog => "Server log".to_string(),
+            View::Config => "Server config".to_string(),
+            View::Scenario => match &self.scenario {
+                Some(run) => format!(
+                    "Scenario, {} passed, {} failed of {} steps",
+                    run.passed(),
+                    run.failed(),
+                    run.results.len()
+                ),
+                None => "Scenario runner".to_string(),
+            },
+        }
+    }
+
+    /// After a key: say where the TUI is now, if that changed and the key said nothing itself
+    pub(super) fn announce_focus(&mut self, before: &str, status: Option<Instant>) {
+        if !self.accessible || self.status_message.as_ref().map(|s| s.2) != status {
+            return;
+        }
+        let now = self.describe_focus();
+        if now != before {
+            self.set_status(now, StatusLevel::Info);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::{make_imposter, make_test_app};
+
+    #[tokio::test]
+    async fn moving_the_selection_is_said_on_the_status_line() {
+        let mut app = make_test_app();
+        app.imposters = vec![
+            make_imposter(4545, Some("orders"), "http"),
+            make_imposter(4546, None, "https"),
+        ];
+        app.imposter_list_state.select(Some(0));
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('j')))
+            .await;
+        assert!(app.status_message.is_none(), "only in accessibility mode");
+
+        let theme = app.theme.name.clone();
+        app.set_accessible(true);
+        assert_eq!(app.theme.preset, ThemePreset::HighContrast);
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('k')))
+            .await;
+        assert_eq!(
+            app.status_message.as_ref().unwrap().0,
+            "Imposter 1 of 2: :4545 orders http, enabled, 0 stubs, 0 requests"
+        );
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('?')))
+            .await;
+        assert_eq!(app.status_message.as_ref().unwrap().0, "Help");
+
+        app.set_accessible(false);
+        assert_eq!(app.theme.name, theme);
+    }
+}
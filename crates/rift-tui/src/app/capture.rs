@@ -0,0 +1,281 @@
+//! Getting findings out of the TUI: the metrics view's figures as CSV or JSON, and screenshots of
+//! any view, written to files to attach to a ticket
+
+use super::*;
+use ratatui::buffer::Buffer;
+
+/// Percentiles the metrics view shows, and exports
+const EXPORTED_PERCENTILES: [(&str, f64); 3] = [("p50", 0.5), ("p90", 0.9), ("p99", 0.99)];
+
+/// `~/<prefix>-<local time>.<extension>`, for a file nothing has been written to yet
+fn timestamped_path(prefix: &str, extension: &str) -> String {
+    let name = format!(
+        "{prefix}-{}.{extension}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    dirs::home_dir()
+        .map(|h| h.join(&name).to_string_lossy().to_string())
+        .unwrap_or(name)
+}
+
+/// `field` as a CSV field, quoted when it has to be
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// A figure for CSV, empty when there is none
+fn csv_number(value: Option<f64>) -> String {
+    value.map_or_else(String::new, |value| format!("{value:.3}"))
+}
+
+impl App {
+    /// `x` in the metrics view: ask where to write its figures
+    pub fn show_metrics_export_dialog(&mut self) {
+        let path = timestamped_path("rift-metrics", "csv");
+        self.input_state.cursor_pos = path.len();
+        self.input_state.file_path = path;
+        self.overlay = Overlay::FilePathInput {
+            prompt: "Export metrics to file (.csv or .json)".to_string(),
+            action: FileAction::ExportMetrics,
+        };
+    }
+
+    /// `P`: ask where to write a screenshot of the view
+    pub fn show_screenshot_dialog(&mut self) {
+        let path = timestamped_path("rift-screenshot", "ans");
+        self.input_state.cursor_pos = path.len();
+        self.input_state.file_path = path;
+        self.overlay = Overlay::FilePathInput {
+            prompt: "Save screenshot to file (.txt for plain text)".to_string(),
+            action: FileAction::Screenshot,
+        };
+    }
+
+    /// The figures of each imposter over the metrics window, as the metrics view shows them
+    fn exported_metrics(&self) -> Vec<(&ImposterSummary, WindowStats)> {
+        self.imposters
+            .iter()
+            .filter_map(|imp| Some((imp, self.window_stats(imp.port)?)))
+            .collect()
+    }
+
+    /// The metrics view's figures as CSV, one row per imposter
+    pub fn metrics_csv(&self) -> String {
+        let mut header = vec![
+            "port",
+            "name",
+            "protocol",
+            "window",
+            "requests",
+            "rate_per_second",
+            "mean_ms",
+        ];
+        header.extend(EXPORTED_PERCENTILES.map(|(name, _)| name));
+        header.extend([
+            "status_2xx",
+            "status_3xx",
+            "status_4xx",
+            "status_5xx",
+            "error_rate",
+            "faults",
+            "unmatched",
+        ]);
+        let mut lines = vec![header.join(",")];
+        for (imp, stats) in self.exported_metrics() {
+            let mut row = vec![
+                imp.port.to_string(),
+                csv_field(imp.name.as_deref().unwrap_or("")),
+                csv_field(&imp.protocol),
+                self.metrics_window.label().to_string(),
+                stats.requests.to_string(),
+                csv_number(stats.rate()),
+                csv_number(stats.mean_latency_ms()),
+            ];
+            row.extend(
+                EXPORTED_PERCENTILES
+                    .map(|(_, quantile)| csv_number(stats.latency_percentile(quantile))),
+            );
+            row.extend(stats.status_classes().map(|count| count.to_string()));
+            row.extend([
+                csv_number(stats.error_rate()),
+                stats.faults.values().sum::<u64>().to_string(),
+                stats.unmatched.to_string(),
+            ]);
+            lines.push(row.join(","));
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// The metrics view's figures as JSON, with statuses, faults and stub hits in full
+    pub fn metrics_json(&self) -> serde_json::Value {
+        let imposters: Vec<serde_json::Value> = self
+            .exported_metrics()
+            .into_iter()
+            .map(|(imp, stats)| {
+                let mut latency = serde_json::Map::new();
+                latency.insert("meanMs".to_string(), stats.mean_latency_ms().into());
+                for (name, quantile) in EXPORTED_PERCENTILES {
+                    latency.insert(
+                        format!("{name}Ms"),
+                        stats.latency_percentile(quantile).into(),
+                    );
+                }
+                let stub_hits: Vec<serde_json::Value> = stats
+                    .stub_hits
+                    .iter()
+                    .map(|(index, (id, hits))| {
+                        serde_json::json!({ "index": index, "id": id, "hits": hits })
+                    })
+                    .collect();
+                serde_json::json!({
+                    "port": imp.port,
+                    "name": imp.name,
+                    "protocol": imp.protocol,
+                    "requests": stats.requests,
+                    "ratePerSecond": stats.rate(),
+                    "latency": latency,
+                    "statusCodes": stats.status_codes,
+                    "errorRate": stats.error_rate(),
+                    "faults": stats.faults,
+                    "stubHits": stub_hits,
+                    "unmatched": stats.unmatched,
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "exportedAt": chrono::Local::now().to_rfc3339(),
+            "window": self.metrics_window.label(),
+            "totalRequests": self.metrics.total_requests,
+            "imposters": imposters,
+        })
+    }
+
+    /// Write the metrics view's figures to `path`: JSON for a `.json` file, CSV otherwise
+    pub(super) async fn export_metrics(&mut self, path: &str) {
+        let path = Self::expand_path(path);
+        let content = if path.to_ascii_lowercase().ends_with(".json") {
+            serde_json::to_string_pretty(&self.metrics_json()).unwrap_or_default()
+        } else {
+            self.metrics_csv()
+        };
+        match tokio::fs::write(&path, content).await {
+            Ok(()) => {
+                self.overlay = Overlay::None;
+                self.set_status(
+                    format!(
+                        "Exported {} window metrics to {path}",
+                        self.metrics_window.label()
+                    ),
+                    StatusLevel::Success,
+                );
+            }
+            Err(e) => self.set_status(format!("Failed to export metrics: {e}"), StatusLevel::Error),
+        }
+    }
+
+    /// Take a screenshot to `path` once the view is drawn again, without the dialog over it
+    pub(super) fn request_screenshot(&mut self, path: &str) {
+        self.overlay = Overlay::None;
+        self.pending_screenshot = Some(Self::expand_path(path));
+    }
+
+    /// Write the frame just drawn to the file a screenshot was asked for, if one was: plain text
+    /// for a `.txt` file, with ANSI colors otherwise
+    pub async fn save_screenshot(&mut self, buffer: &Buffer) {
+        let Some(path) = self.pending_screenshot.take() else {
+            return;
+        };
+        let color = !path.to_ascii_lowercase().ends_with(".txt");
+        let content = crate::ui::screenshot(buffer, color);
+        match tokio::fs::write(&path, content).await {
+            Ok(()) => self.set_status(format!("Saved screenshot to {path}"), StatusLevel::Success),
+            Err(e) => self.set_status(
+                format!("Failed to save screenshot: {e}"),
+                StatusLevel::Error,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::ImposterMetrics;
+    use crate::app::tests::{make_imposter, make_test_app};
+
+    fn app_with_metrics() -> App {
+        let mut app = make_test_app();
+        app.imposters = vec![make_imposter(4545, Some("orders, v2"), "http")];
+        app.metrics_window = MetricsWindow::All;
+        app.metrics = MetricsData {
+            total_requests: 4,
+            per_imposter: [(
+                4545,
+                ImposterMetrics {
+                    served_count: 4,
+                    latency_buckets: vec![(10.0, 2), (f64::INFINITY, 4)],
+                    latency_sum_ms: 40,
+                    status_codes: [(200, 3), (503, 1)].into(),
+                    stub_hits: [(0, (Some("list".to_string()), 3))].into(),
+                    unmatched: 1,
+                    ..Default::default()
+                },
+            )]
+            .into(),
+            ..Default::default()
+        };
+        let metrics = app.metrics.clone();
+        app.record_metrics(&metrics, Instant::now());
+        app
+    }
+
+    #[test]
+    fn metrics_export_as_csv_one_row_per_imposter() {
+        let csv = app_with_metrics().metrics_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "port,name,protocol,window,requests,rate_per_second,mean_ms,p50,p90,p99,\
+             status_2xx,status_3xx,status_4xx,status_5xx,error_rate,faults,unmatched"
+        );
+        assert_eq!(
+            lines[1],
+            "4545,\"orders, v2\",http,all,4,,10.000,10.000,10.000,10.000,3,0,0,1,0.250,0,1"
+        );
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn metrics_export_as_json_keeps_statuses_and_stub_hits() {
+        let json = app_with_metrics().metrics_json();
+        assert_eq!(json["window"], "all");
+        assert_eq!(json["totalRequests"], 4);
+        let orders = &json["imposters"][0];
+        assert_eq!(orders["statusCodes"]["503"], 1);
+        assert_eq!(orders["latency"]["meanMs"], 10.0);
+        assert_eq!(orders["stubHits"][0]["id"], "list");
+        assert_eq!(orders["ratePerSecond"], serde_json::Value::Null);
+    }
+
+    #[tokio::test]
+    async fn a_screenshot_is_taken_after_the_dialog_closes() {
+        let mut app = make_test_app();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("list.txt").to_string_lossy().to_string();
+        app.show_screenshot_dialog();
+        app.input_state.file_path = path.clone();
+        app.handle_key_event(KeyEvent::from(KeyCode::Enter)).await;
+        assert_eq!(app.overlay, Overlay::None);
+        assert_eq!(app.pending_screenshot.as_deref(), Some(path.as_str()));
+
+        let mut buffer = Buffer::empty(ratatui::layout::Rect::new(0, 0, 12, 2));
+        buffer.set_string(0, 0, "Imposters", ratatui::style::Style::default());
+        app.save_screenshot(&buffer).await;
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "Imposters\n\n");
+        assert!(app.pending_screenshot.is_none());
+    }
+}
@@ -5,6 +5,14 @@ use super::super::*;
 impl App {
     /// Generate a curl command for a stub
     pub fn generate_curl_command(&self, stub: &Stub, port: u16) -> String {
+        curl_command(
+            &self.stub_request(stub),
+            &self.imposter_url(port, &self.client),
+        )
+    }
+
+    /// A request the stub answers, made up from its predicates
+    pub fn stub_request(&self, stub: &Stub) -> ImposterRequest {
         let mut parts = CurlRequestParts::default();
 
         // Parse predicates to extract request info
@@ -15,7 +23,7 @@ impl App {
         let CurlRequestParts {
             method,
             path,
-            headers,
+            mut headers,
             query_params,
             json_body_parts,
             raw_body,
@@ -28,50 +36,38 @@ impl App {
             raw_body
         };
 
-        // Build the curl command
-        let mut parts: Vec<String> = vec!["curl -s".to_string()];
-
-        // Add method if not GET
-        if method != "GET" {
-            parts.push(format!("-X {method}"));
-        }
-
         // Add Content-Type header if we have a body and it looks like JSON
-        if body.is_some() {
+        if let Some(ref b) = body {
             let has_content_type = headers
                 .iter()
                 .any(|(k, _)| k.to_lowercase() == "content-type");
             if !has_content_type
-                && let Some(ref b) = body
                 && (b.trim_start().starts_with('{') || b.trim_start().starts_with('['))
             {
-                parts.push("-H 'Content-Type: application/json'".to_string());
+                headers.insert(
+                    0,
+                    ("Content-Type".to_string(), "application/json".to_string()),
+                );
             }
         }
 
-        // Add headers
-        for (key, value) in &headers {
-            parts.push(format!("-H '{key}: {value}'"));
-        }
-
-        // Add body if present
-        if let Some(ref b) = body {
-            parts.push(format!("-d '{}'", b.replace('\'', "'\\''")));
-        }
-
-        // Build URL with query params
-        let mut url = format!("http://localhost:{port}{path}");
-        if !query_params.is_empty() {
+        // Build path with query params
+        let path = if query_params.is_empty() {
+            path
+        } else {
             let query_string: Vec<String> = query_params
                 .iter()
                 .map(|(k, v)| format!("{k}={v}"))
                 .collect();
-            url = format!("{}?{}", url, query_string.join("&"));
-        }
-
-        parts.push(format!("'{url}'"));
+            format!("{}?{}", path, query_string.join("&"))
+        };
 
-        parts.join(" \\\n  ")
+        ImposterRequest {
+            method,
+            path,
+            headers,
+            body: body.map(String::into_bytes).unwrap_or_default(),
+        }
     }
 
     /// Extract request info from a predicate
@@ -307,31 +303,4 @@ impl App {
             }
         }
     }
-
-    /// Copy curl command for selected stub to clipboard
-    pub fn copy_stub_as_curl(&mut self) {
-        let port = match &self.view {
-            View::ImposterDetail { port } => *port,
-            View::StubDetail { port, .. } => *port,
-            _ => return,
-        };
-
-        let stub_index = match &self.view {
-            View::StubDetail { index, .. } => Some(*index),
-            View::ImposterDetail { .. } => self.stub_list_state.selected(),
-            _ => None,
-        };
-
-        if let Some(idx) = stub_index
-            && let Some(imp) = &self.current_imposter
-            && let Some(stub) = imp.stubs.get(idx)
-        {
-            let curl_cmd = self.generate_curl_command(stub, port);
-            self.copy_to_clipboard(&curl_cmd);
-            self.set_status(
-                "Curl command copied to clipboard".to_string(),
-                StatusLevel::Success,
-            );
-        }
-    }
 }
@@ -10,7 +10,8 @@ impl App {
             self.is_loading = true;
             match self.client.get_imposter(port).await {
                 Ok(detail) => {
-                    self.current_imposter = Some(detail);
+                    self.set_current_imposter(Some(detail));
+                    self.marked_stubs.clear();
                     self.stub_list_state.select(Some(0));
                     self.navigate(View::ImposterDetail { port });
                 }
@@ -34,62 +35,87 @@ impl App {
         };
 
         if let Some(port) = port {
-            // Find if enabled
             let enabled = self
                 .imposters
                 .iter()
                 .find(|i| i.port == port)
-                .map(|i| i.enabled);
+                .is_none_or(|i| i.enabled);
+            let verb = if enabled { "Disable" } else { "Enable" };
+            self.confirm(
+                format!("{verb} imposter :{port}?"),
+                PendingAction::ToggleImposter { port },
+            )
+            .await;
+        }
+    }
 
-            let result = if enabled.unwrap_or(true) {
-                self.client.disable_imposter(port).await
-            } else {
-                self.client.enable_imposter(port).await
-            };
+    /// Enable imposter `port` if it is disabled, or disable it
+    pub async fn toggle_imposter_at(&mut self, port: u16) {
+        self.overlay = Overlay::None;
+        // Find if enabled
+        let enabled = self
+            .imposters
+            .iter()
+            .find(|i| i.port == port)
+            .map(|i| i.enabled);
 
-            match result {
-                Ok(_) => {
-                    let action = if enabled.unwrap_or(true) {
-                        "disabled"
-                    } else {
-                        "enabled"
-                    };
-                    self.set_status(format!("Imposter :{port} {action}"), StatusLevel::Success);
-                    self.refresh().await;
-                }
-                Err(e) => {
-                    self.set_status(
-                        format!("Failed to toggle imposter: {e}"),
-                        StatusLevel::Error,
-                    );
-                }
+        let result = if enabled.unwrap_or(true) {
+            self.client.disable_imposter(port).await
+        } else {
+            self.client.enable_imposter(port).await
+        };
+
+        match result {
+            Ok(_) => {
+                let action = if enabled.unwrap_or(true) {
+                    "disabled"
+                } else {
+                    "enabled"
+                };
+                self.set_status(format!("Imposter :{port} {action}"), StatusLevel::Success);
+                self.refresh().await;
+            }
+            Err(e) => {
+                self.set_status(
+                    format!("Failed to toggle imposter: {e}"),
+                    StatusLevel::Error,
+                );
             }
         }
     }
 
     /// Show delete confirmation
-    pub fn confirm_delete_imposter(&mut self) {
+    pub async fn confirm_delete_imposter(&mut self) {
         if let Some(imp) = self.selected_imposter() {
-            self.overlay = Overlay::Confirm {
-                message: format!(
-                    "Delete imposter :{}{}?",
-                    imp.port,
-                    imp.name
-                        .as_ref()
-                        .map(|n| format!(" ({n})"))
-                        .unwrap_or_default()
-                ),
-                action: PendingAction::DeleteImposter { port: imp.port },
-            };
+            let message = format!(
+                "Delete imposter :{}{}?",
+                imp.port,
+                imp.name
+                    .as_ref()
+                    .map(|n| format!(" ({n})"))
+                    .unwrap_or_default()
+            );
+            let action = PendingAction::DeleteImposter { port: imp.port };
+            self.confirm(message, action).await;
         }
     }
 
-    /// Delete an imposter
+    /// Delete an imposter, journaling its config so `u` can recreate it
     pub async fn delete_imposter(&mut self, port: u16) {
         self.is_loading = true;
-        match self.client.delete_imposter(port).await {
-            Ok(_) => {
-                self.set_status(format!("Deleted imposter :{port}"), StatusLevel::Success);
+        match self.remove_imposter(port).await {
+            Ok(operation) => {
+                match operation {
+                    Some(operation) => {
+                        let client = self.client.clone();
+                        self.journal.record(&client, operation);
+                        self.set_status(format!("Deleted imposter :{port}"), StatusLevel::Success);
+                    }
+                    None => self.set_status(
+                        format!("Deleted imposter :{port}; its config couldn't be saved to undo"),
+                        StatusLevel::Warning,
+                    ),
+                }
                 self.refresh().await;
                 if matches!(self.view, View::ImposterDetail { port: p } if p == port) {
                     self.view = View::ImposterList;
@@ -103,61 +129,19 @@ impl App {
         self.overlay = Overlay::None;
     }
 
-    /// Show create imposter dialog
-    pub fn show_create_imposter(&mut self) {
-        self.input_state = InputState {
-            port: String::new(),
-            name: String::new(),
-            protocol: "http".to_string(),
-            target_url: String::new(),
-            proxy_mode: 0,
-            focus_field: 0,
-            file_path: String::new(),
-            cursor_pos: 0,
-        };
-        self.overlay = Overlay::Input {
-            prompt: "Create New Imposter".to_string(),
-            action: InputAction::CreateImposter,
-        };
-    }
-
-    /// Create a new imposter
-    pub async fn create_imposter(&mut self) {
-        let port = if self.input_state.port.is_empty() {
-            None
-        } else {
-            match self.input_state.port.parse::<u16>() {
-                Ok(p) => Some(p),
-                Err(_) => {
-                    self.set_status("Invalid port number".to_string(), StatusLevel::Error);
-                    return;
-                }
-            }
-        };
-
-        let request = CreateImposterRequest {
-            port,
-            protocol: self.input_state.protocol.clone(),
-            name: if self.input_state.name.is_empty() {
-                None
-            } else {
-                Some(self.input_state.name.clone())
-            },
-            record_requests: false,
-            stubs: Vec::new(),
-        };
-
-        self.is_loading = true;
-        match self.client.create_imposter(request).await {
-            Ok(port) => {
-                self.set_status(format!("Created imposter :{port}"), StatusLevel::Success);
-                self.overlay = Overlay::None;
-                self.refresh().await;
-            }
-            Err(e) => {
-                self.set_status(format!("Failed to create: {e}"), StatusLevel::Error);
-            }
-        }
-        self.is_loading = false;
+    /// Export imposter `port` and delete it, returning what undoes the delete when the export
+    /// worked
+    pub(in crate::app) async fn remove_imposter(
+        &self,
+        port: u16,
+    ) -> Result<Option<Operation>, ApiError> {
+        let config = self
+            .client
+            .export_imposter(port, false)
+            .await
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok());
+        self.client.delete_imposter(port).await?;
+        Ok(config.map(|config| Operation::DeleteImposter { port, config }))
     }
 }
@@ -71,7 +71,7 @@ impl App {
     }
 
     /// Expand tilde in path to home directory
-    pub(super) fn expand_path(path: &str) -> String {
+    pub(in crate::app) fn expand_path(path: &str) -> String {
         if let Some(rest) = path.strip_prefix("~/") {
             if let Some(home) = dirs::home_dir() {
                 return home.join(rest).to_string_lossy().to_string();
@@ -126,53 +126,18 @@ impl App {
         };
     }
 
-    /// Import imposter from file with validation
+    /// Read an imposter file and preview importing it, with what linting found in it
     pub async fn import_from_file(&mut self, path: &str) {
-        self.is_loading = true;
         let expanded_path = Self::expand_path(path);
-
         match tokio::fs::read_to_string(&expanded_path).await {
-            Ok(content) => {
-                // Validate the content before importing
-                let report = validate_imposter_json(&content, &expanded_path);
-
-                if report.has_errors() {
-                    // Block import on errors - show validation results
-                    self.validation_scroll_offset = 0;
-                    self.overlay = Overlay::ValidationResult {
-                        report,
-                        action: ValidationAction::EditorInfo, // Can't proceed with errors
-                    };
-                    self.is_loading = false;
-                    return;
-                }
-
-                if report.has_warnings() {
-                    // Show warnings but allow proceeding
-                    self.validation_scroll_offset = 0;
-                    self.overlay = Overlay::ValidationResult {
-                        report,
-                        action: ValidationAction::ProceedWithImport {
-                            path: expanded_path.clone(),
-                            content: content.clone(),
-                        },
-                    };
-                    self.is_loading = false;
-                    return;
-                }
-
-                // No issues - proceed with import
-                self.do_import(&content).await;
-            }
+            Ok(content) => self.preview_import(&expanded_path, content),
             Err(e) => {
                 self.set_status(format!("Failed to read file: {e}"), StatusLevel::Error);
             }
         }
-
-        self.is_loading = false;
     }
 
-    /// Actually perform the import (called after validation passes or user confirms)
+    /// Actually perform the import (called once its preview is confirmed)
     pub async fn do_import(&mut self, content: &str) {
         match serde_json::from_str::<serde_json::Value>(content) {
             Ok(config) => {
@@ -335,21 +300,17 @@ impl App {
         };
     }
 
-    /// Export all imposters to a single file
+    /// Fetch all imposters as one document and preview writing it to `path`
     pub async fn export_all_to_file(&mut self, path: &str) {
         self.is_loading = true;
         let expanded_path = Self::expand_path(path);
 
         match self.client.export_all_imposters().await {
-            Ok(json) => match tokio::fs::write(&expanded_path, &json).await {
-                Ok(_) => {
-                    self.set_status(format!("Exported to {expanded_path}"), StatusLevel::Success);
-                    self.overlay = Overlay::None;
-                }
-                Err(e) => {
-                    self.set_status(format!("Failed to write: {e}"), StatusLevel::Error);
-                }
-            },
+            Ok(json) => self.preview_export(
+                expanded_path.clone(),
+                vec![(expanded_path, json)],
+                Vec::new(),
+            ),
             Err(e) => {
                 self.set_status(format!("Failed to export: {e}"), StatusLevel::Error);
             }
@@ -358,27 +319,15 @@ impl App {
         self.is_loading = false;
     }
 
-    /// Export imposters to individual files in a folder
+    /// Fetch each imposter and preview writing them to their own files in `folder`
     pub async fn export_to_folder(&mut self, folder: &str) {
         self.is_loading = true;
         let expanded_folder = Self::expand_path(folder);
-
         let path = std::path::Path::new(&expanded_folder);
 
-        // Create folder if it doesn't exist
-        if let Err(e) = tokio::fs::create_dir_all(path).await {
-            self.set_status(format!("Failed to create folder: {e}"), StatusLevel::Error);
-            self.is_loading = false;
-            return;
-        }
-
-        let mut exported = 0;
-        let mut failed = 0;
-        let mut last_error = None;
-
-        // Collected rather than pushed inline: the loop holds `&self.imposters`, so recording into
-        // the error log (which needs `&mut self`) has to wait until it ends.
-        let mut failures: Vec<String> = Vec::new();
+        let mut files = Vec::new();
+        // Shown in the preview, and recorded in the error log (issue #624)
+        let mut left_out: Vec<String> = Vec::new();
 
         for imp in &self.imposters {
             match self.client.export_imposter(imp.port, false).await {
@@ -388,44 +337,16 @@ impl App {
                     } else {
                         format!("{}.json", imp.port)
                     };
-                    let file_path = path.join(filename);
-                    match tokio::fs::write(&file_path, &json).await {
-                        Ok(_) => exported += 1,
-                        Err(e) => {
-                            failed += 1;
-                            let detail = format!("failed to write {}: {e}", file_path.display());
-                            failures.push(detail.clone());
-                            last_error = Some(detail);
-                        }
-                    }
-                }
-                Err(e) => {
-                    failed += 1;
-                    let detail = format!("failed to export port {}: {e}", imp.port);
-                    failures.push(detail.clone());
-                    last_error = Some(detail);
+                    files.push((path.join(filename).to_string_lossy().to_string(), json));
                 }
+                Err(e) => left_out.push(format!("failed to export port {}: {e}", imp.port)),
             }
         }
 
-        for failure in failures {
-            self.push_error(failure);
-        }
-
-        if failed > 0 {
-            let detail = last_error.map(|e| format!(" ({e})")).unwrap_or_default();
-            self.set_status(
-                format!("Exported {exported} imposters, {failed} failed{detail}"),
-                StatusLevel::Warning,
-            );
-        } else {
-            self.set_status(
-                format!("Exported {exported} imposters to {folder}"),
-                StatusLevel::Success,
-            );
+        for failure in &left_out {
+            self.push_error(failure.clone());
         }
-
-        self.overlay = Overlay::None;
+        self.preview_export(expanded_folder, files, left_out);
         self.is_loading = false;
     }
 }
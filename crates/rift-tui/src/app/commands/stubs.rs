@@ -45,39 +45,118 @@ impl App {
         }
     }
 
-    /// Save the current stub being edited
+    /// Save the current stub being edited, or create the imposter being written
     pub async fn save_stub(&mut self) {
         if let Some(editor) = &mut self.stub_editor {
             if !editor.validate() {
                 return;
             }
+            // Lint errors would most likely have the server reject the stub: say where they are
+            // first, and save only if asked again without changes.
+            let content = editor.editor.lines().join("\n");
+            if let Some(report) = editor.validation_report.as_ref().filter(|r| r.has_errors())
+                && editor.acknowledged_errors.as_deref() != Some(content.as_str())
+            {
+                let first = editor
+                    .line_issues
+                    .iter()
+                    .find(|issue| issue.severity == IssueSeverity::Error)
+                    .map(|issue| format!(" (line {}: {})", issue.line + 1, issue.message))
+                    .unwrap_or_default();
+                let message = format!(
+                    "{}{first} - Ctrl+S again to save anyway, Ctrl+L for details",
+                    report.summary()
+                );
+                editor.acknowledged_errors = Some(content);
+                self.set_status(message, StatusLevel::Warning);
+                return;
+            }
 
+            if self.view == View::ImposterEdit {
+                self.create_imposter_from_editor(&content).await;
+                return;
+            }
             if let Some(stub) = editor.get_stub()
                 && let View::StubEdit { port, index } = self.view
             {
-                self.is_loading = true;
-                let result = if let Some(idx) = index {
-                    self.client.update_stub(port, idx, stub).await
-                } else {
-                    self.client.add_stub(port, stub, None).await
-                };
-
-                match result {
-                    Ok(_) => {
-                        self.set_status("Stub saved".to_string(), StatusLevel::Success);
-                        self.stub_editor = None;
-                        self.go_back();
-                        self.refresh().await;
-                    }
-                    Err(e) => {
-                        self.set_status(format!("Failed to save: {e}"), StatusLevel::Error);
-                    }
+                match index {
+                    Some(index) => self.preview_stub_save(port, index, stub).await,
+                    None => self.write_stub(port, None, stub).await,
                 }
-                self.is_loading = false;
             }
         }
     }
 
+    /// Show what saving over stub `index` changes on the server, warning when the server's copy
+    /// changed since the editor opened it
+    async fn preview_stub_save(&mut self, port: u16, index: usize, stub: Stub) {
+        self.is_loading = true;
+        let server = self.client.get_imposter(port).await;
+        self.is_loading = false;
+        let server = match server {
+            Ok(imposter) => imposter
+                .stubs
+                .get(index)
+                .map(|s| serde_json::to_string_pretty(s).unwrap_or_default()),
+            Err(e) => {
+                self.set_status(
+                    format!("Failed to load stub #{} to compare: {e}", index + 1),
+                    StatusLevel::Error,
+                );
+                return;
+            }
+        };
+        let local = serde_json::to_string_pretty(&stub).unwrap_or_default();
+        let original = self
+            .stub_editor
+            .as_ref()
+            .map(|editor| editor.original_json.as_str())
+            .unwrap_or_default();
+        let warning = match &server {
+            None => Some(format!("Stub #{} no longer exists on :{port}", index + 1)),
+            Some(server) if server != original => Some(
+                "This stub changed on the server since you opened it: saving overwrites those changes"
+                    .to_string(),
+            ),
+            Some(_) => None,
+        };
+        let server = server.unwrap_or_default();
+        if server == local {
+            self.set_status("No changes to save".to_string(), StatusLevel::Info);
+            return;
+        }
+        self.show_diff_preview(
+            format!(" Save stub #{} on :{port} ", index + 1),
+            warning,
+            &server,
+            &local,
+            DiffAction::SaveStub { port, index },
+        );
+    }
+
+    /// Send the edited stub: replace stub `index`, or add it when there is none
+    pub(in crate::app) async fn write_stub(&mut self, port: u16, index: Option<usize>, stub: Stub) {
+        self.is_loading = true;
+        let result = if let Some(idx) = index {
+            self.client.update_stub(port, idx, stub).await
+        } else {
+            self.client.add_stub(port, stub, None).await
+        };
+
+        match result {
+            Ok(_) => {
+                self.set_status("Stub saved".to_string(), StatusLevel::Success);
+                self.stub_editor = None;
+                self.go_back();
+                self.refresh().await;
+            }
+            Err(e) => {
+                self.set_status(format!("Failed to save: {e}"), StatusLevel::Error);
+            }
+        }
+        self.is_loading = false;
+    }
+
     /// Cancel stub editing
     pub fn cancel_stub_edit(&mut self) {
         self.stub_editor = None;
@@ -110,22 +189,34 @@ impl App {
     }
 
     /// Confirm delete stub
-    pub fn confirm_delete_stub(&mut self) {
+    pub async fn confirm_delete_stub(&mut self) {
         if let View::ImposterDetail { port } = self.view
             && let Some(idx) = self.stub_list_state.selected()
         {
-            self.overlay = Overlay::Confirm {
-                message: format!("Delete stub #{idx} from :{port}?"),
-                action: PendingAction::DeleteStub { port, index: idx },
-            };
+            self.confirm(
+                format!("Delete stub #{idx} from :{port}?"),
+                PendingAction::DeleteStub { port, index: idx },
+            )
+            .await;
         }
     }
 
-    /// Delete a stub
+    /// Delete a stub, journaling it so `u` can put it back
     pub async fn delete_stub(&mut self, port: u16, index: usize) {
         self.is_loading = true;
+        let stub = self
+            .current_imposter
+            .as_ref()
+            .filter(|imp| imp.port == port)
+            .and_then(|imp| imp.stubs.get(index))
+            .cloned();
         match self.client.delete_stub(port, index).await {
             Ok(_) => {
+                if let Some(stub) = stub {
+                    let client = self.client.clone();
+                    self.journal
+                        .record(&client, Operation::DeleteStub { port, index, stub });
+                }
                 self.set_status("Stub deleted".to_string(), StatusLevel::Success);
                 self.refresh().await;
             }
@@ -137,39 +228,63 @@ impl App {
         self.overlay = Overlay::None;
     }
 
+    /// Move the selected stub one place up (`-1`) or down (`1`), which changes which stub a
+    /// request matches first, and show the imposter's stubs in their new order
     pub(in super::super) async fn reorder_stub(&mut self, direction: i32) {
         let View::ImposterDetail { port } = self.view else {
             return;
         };
-        if let Some(idx) = self.stub_list_state.selected() {
-            let new_idx = idx as i32 + direction;
-            if new_idx < 0 {
-                return;
-            }
-            let new_idx = new_idx as usize;
-            if let Some(imp) = &mut self.current_imposter {
-                if new_idx >= imp.stubs.len() {
-                    return;
+        let Some(idx) = self.stub_list_state.selected() else {
+            return;
+        };
+        let len = self
+            .current_imposter
+            .as_ref()
+            .map_or(0, |imp| imp.stubs.len());
+        let Some(new_idx) = idx.checked_add_signed(direction as isize) else {
+            return;
+        };
+        if new_idx >= len {
+            return;
+        }
+
+        self.is_loading = true;
+        match self.client.move_stub(port, idx, new_idx).await {
+            Ok(detail) => {
+                let client = self.client.clone();
+                self.journal.record(
+                    &client,
+                    Operation::MoveStub {
+                        port,
+                        from: idx,
+                        to: new_idx,
+                    },
+                );
+                self.set_current_imposter(Some(detail));
+                self.stub_list_state.select(Some(new_idx));
+                // Marks are by index, so they swap along with the two stubs
+                let (a, b) = (
+                    self.marked_stubs.remove(&idx),
+                    self.marked_stubs.remove(&new_idx),
+                );
+                if a {
+                    self.marked_stubs.insert(new_idx);
                 }
-                imp.stubs.swap(idx, new_idx);
-                let stubs = imp.stubs.clone();
-                match self.client.update_stubs(port, stubs).await {
-                    Ok(_) => {
-                        self.stub_list_state.select(Some(new_idx));
-                        self.set_status(
-                            format!("Moved stub to #{}", new_idx + 1),
-                            StatusLevel::Success,
-                        );
-                        self.refresh().await;
-                    }
-                    Err(e) => {
-                        self.set_status(format!("Failed to reorder: {e}"), StatusLevel::Error);
-                        // Refresh to restore correct order
-                        self.refresh().await;
-                    }
+                if b {
+                    self.marked_stubs.insert(idx);
                 }
+                self.set_status(
+                    format!("Moved stub #{} to #{}", idx + 1, new_idx + 1),
+                    StatusLevel::Success,
+                );
+            }
+            Err(e) => {
+                self.set_status(format!("Failed to reorder: {e}"), StatusLevel::Error);
+                // Refresh to restore correct order
+                self.refresh().await;
             }
         }
+        self.is_loading = false;
     }
 
     pub(in super::super) async fn duplicate_stub(&mut self) {
@@ -202,3 +317,78 @@ impl App {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{ApiClient, ImposterDetail};
+    use crate::app::Operation;
+    use crate::app::View;
+    use crate::app::tests::{answer_json, make_test_app};
+
+    fn detail(stubs: usize) -> ImposterDetail {
+        let stubs: Vec<_> = (0..stubs)
+            .map(|i| serde_json::json!({ "id": format!("s{i}"), "responses": [] }))
+            .collect();
+        serde_json::from_value(
+            serde_json::json!({ "port": 4545, "protocol": "http", "stubs": stubs }),
+        )
+        .unwrap()
+    }
+
+    fn app_on_stub(stubs: usize, selected: usize) -> crate::app::App {
+        let mut app = make_test_app();
+        // Nothing listens here, so any call the move makes fails visibly
+        app.client = ApiClient::new("http://127.0.0.1:9");
+        app.view = View::ImposterDetail { port: 4545 };
+        app.set_current_imposter(Some(detail(stubs)));
+        app.stub_list_state.select(Some(selected));
+        app
+    }
+
+    #[tokio::test]
+    async fn the_first_stub_cant_move_up_nor_the_last_down() {
+        for (selected, direction) in [(0, -1), (2, 1)] {
+            let mut app = app_on_stub(3, selected);
+            app.reorder_stub(direction).await;
+            assert!(app.status_message.is_none(), "no call is made");
+            assert!(app.journal.next_undo().is_none());
+            assert_eq!(app.stub_list_state.selected(), Some(selected));
+        }
+    }
+
+    #[tokio::test]
+    async fn a_move_calls_the_move_api_and_swaps_the_marks() {
+        let mut app = app_on_stub(3, 1);
+        let (url, server) = answer_json(vec![serde_json::to_string(&serde_json::json!({
+            "port": 4545, "protocol": "http",
+            "stubs": [{ "id": "s0", "responses": [] }, { "id": "s2", "responses": [] }, { "id": "s1", "responses": [] }]
+        }))
+        .unwrap()]);
+        app.client = ApiClient::new(&url);
+        app.marked_stubs.insert(1);
+
+        app.reorder_stub(1).await;
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].0, "PUT /imposters/4545/stubs/1 HTTP/1.1");
+        assert_eq!(requests[0].1, r#"{"newIndex":2}"#);
+        assert_eq!(app.stub_list_state.selected(), Some(2));
+        assert_eq!(app.marked_stubs.iter().copied().collect::<Vec<_>>(), [2]);
+        assert!(matches!(
+            app.journal.next_undo(),
+            Some(Operation::MoveStub {
+                port: 4545,
+                from: 1,
+                to: 2
+            })
+        ));
+
+        // Marks on both stubs stay on both
+        let mut app = app_on_stub(3, 0);
+        let (url, server) = answer_json(vec![serde_json::to_string(&detail(3)).unwrap()]);
+        app.client = ApiClient::new(&url);
+        app.marked_stubs.extend([0, 1]);
+        app.reorder_stub(1).await;
+        server.join().unwrap();
+        assert_eq!(app.marked_stubs.iter().copied().collect::<Vec<_>>(), [0, 1]);
+    }
+}
@@ -0,0 +1,384 @@
+//! Request composer: build a request, send it to an imposter and show the response inline
+
+use super::request_log::text_body_lines;
+use super::*;
+use crate::api::{ImposterRequest, ImposterResponse};
+use ratatui_textarea::TextArea;
+
+/// Methods the composer cycles through
+pub const COMPOSER_METHODS: [&str; 7] =
+    ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+/// The composer field that has focus
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ComposerField {
+    #[default]
+    Method,
+    Path,
+    Headers,
+    Body,
+}
+
+impl ComposerField {
+    fn next(self) -> Self {
+        match self {
+            Self::Method => Self::Path,
+            Self::Path => Self::Headers,
+            Self::Headers => Self::Body,
+            Self::Body => Self::Method,
+        }
+    }
+
+    fn previous(self) -> Self {
+        match self {
+            Self::Method => Self::Body,
+            Self::Path => Self::Method,
+            Self::Headers => Self::Path,
+            Self::Body => Self::Headers,
+        }
+    }
+}
+
+/// State of the request composer for one imposter
+pub struct Composer {
+    pub port: u16,
+    /// Index into [`COMPOSER_METHODS`]
+    pub method: usize,
+    pub path: String,
+    /// One `Name: value` header per line
+    pub headers: TextArea<'static>,
+    pub body: TextArea<'static>,
+    /// Send `X-Rift-Debug: true`, so the imposter explains its match instead of responding
+    pub debug: bool,
+    pub focus: ComposerField,
+    pub response: Option<ImposterResponse>,
+    pub response_scroll: u16,
+}
+
+impl Composer {
+    pub fn new(port: u16) -> Self {
+        Self {
+            port,
+            method: 0,
+            path: "/".to_string(),
+            headers: TextArea::default(),
+            body: TextArea::default(),
+            debug: false,
+            focus: ComposerField::Path,
+            response: None,
+            response_scroll: 0,
+        }
+    }
+
+    /// A composer holding `request`, to edit and send again
+    pub fn from_request(port: u16, request: &ImposterRequest) -> Self {
+        let method = COMPOSER_METHODS
+            .iter()
+            .position(|m| m.eq_ignore_ascii_case(&request.method))
+            .unwrap_or(0);
+        let headers = request
+            .headers
+            .iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect::<Vec<_>>();
+        let body = String::from_utf8_lossy(&request.body)
+            .lines()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        Self {
+            method,
+            path: request.path.clone(),
+            headers: TextArea::new(headers),
+            body: TextArea::new(body),
+            ..Self::new(port)
+        }
+    }
+
+    pub fn method(&self) -> &'static str {
+        COMPOSER_METHODS[self.method]
+    }
+
+    /// The request to send, or why the form does not describe one
+    pub fn to_request(&self) -> Result<ImposterRequest, String> {
+        let mut headers = Vec::new();
+        for line in self.headers.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Some((name, value)) = line.split_once(':') else {
+                return Err(format!("header \"{line}\" has no ':'"));
+            };
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(format!("header \"{line}\" has no name"));
+            }
+            headers.push((name.to_string(), value.trim().to_string()));
+        }
+        if self.debug {
+            headers.retain(|(name, _)| !name.eq_ignore_ascii_case("x-rift-debug"));
+            headers.push(("X-Rift-Debug".to_string(), "true".to_string()));
+        }
+        let path = self.path.trim();
+        Ok(ImposterRequest {
+            method: self.method().to_string(),
+            path: if path.is_empty() { "/" } else { path }.to_string(),
+            headers,
+            body: self.body.lines().join("\n").into_bytes(),
+        })
+    }
+}
+
+/// The response pane's sections: Rift's diagnostic `x-rift-*` headers first, then the rest of the
+/// headers and the body
+pub fn response_sections(response: &ImposterResponse) -> Vec<(&'static str, Vec<String>)> {
+    let (mut rift, mut other): (Vec<String>, Vec<String>) = (Vec::new(), Vec::new());
+    for (name, value) in &response.headers {
+        let line = format!("{name}: {value}");
+        if name.to_ascii_lowercase().starts_with("x-rift-") {
+            rift.push(line);
+        } else {
+            other.push(line);
+        }
+    }
+    rift.sort();
+    other.sort();
+    let body = if response.body.is_empty() {
+        vec!["(empty)".to_string()]
+    } else {
+        text_body_lines(&response.body)
+    };
+    let mut sections = Vec::new();
+    if !rift.is_empty() {
+        sections.push(("RIFT DIAGNOSTICS", rift));
+    }
+    sections.push(("HEADERS", other));
+    sections.push(("BODY", body));
+    sections
+}
+
+impl App {
+    /// Open the composer for an imposter, keeping the last request composed for it
+    pub(super) fn open_composer(&mut self, port: u16) {
+        if self.composer.as_ref().is_none_or(|c| c.port != port) {
+            self.composer = Some(Composer::new(port));
+        }
+        self.overlay = Overlay::Composer;
+    }
+
+    /// The imposter the current view is about, for opening the composer
+    pub(super) fn composer_port(&self) -> Option<u16> {
+        match &self.view {
+            View::ImposterList => self.selected_imposter().map(|imp| imp.port),
+            View::ImposterDetail { port }
+            | View::StubDetail { port, .. }
+            | View::StubEdit { port, .. }
+            | View::RequestDetail { port, .. }
+            | View::RequestLog { port } => Some(*port),
+            View::ImposterEdit
+            | View::Config
+            | View::Metrics
+            | View::ServerLog
+            | View::Scenario => None,
+        }
+    }
+
+    /// Send the composed request and keep the response for the pane
+    async fn send_composer(&mut self) {
+        let Some(composer) = &self.composer else {
+            return;
+        };
+        let port = composer.port;
+        let request = match composer.to_request() {
+            Ok(request) => request,
+            Err(e) => {
+                self.set_status(format!("Invalid request: {e}"), StatusLevel::Error);
+                return;
+            }
+        };
+        match self.client.send_to_imposter(port, &request).await {
+            Ok(response) => {
+                self.set_status(
+                    format!(
+                        "{} {} → {} in {}ms",
+                        request.method, request.path, response.status, response.latency_ms
+                    ),
+                    StatusLevel::Success,
+                );
+                if let Some(composer) = self.composer.as_mut() {
+                    composer.response = Some(response);
+                    composer.response_scroll = 0;
+                }
+            }
+            Err(e) => self.set_status(
+                format!("Request to :{port} failed: {e}"),
+                StatusLevel::Error,
+            ),
+        }
+    }
+
+    pub(super) async fn handle_composer_event(&mut self, key: KeyEvent) {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            match key.code {
+                KeyCode::Char('s') => {
+                    self.send_composer().await;
+                    return;
+                }
+                KeyCode::Char('d') => {
+                    if let Some(composer) = self.composer.as_mut() {
+                        composer.debug = !composer.debug;
+                    }
+                    return;
+                }
+                _ => {}
+            }
+        }
+        let Some(composer) = self.composer.as_mut() else {
+            self.overlay = Overlay::None;
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => self.overlay = Overlay::None,
+            KeyCode::Tab => composer.focus = composer.focus.next(),
+            KeyCode::BackTab => composer.focus = composer.focus.previous(),
+            KeyCode::PageUp => {
+                composer.response_scroll = composer.response_scroll.saturating_sub(10);
+            }
+            KeyCode::PageDown => {
+                let max_scroll = composer.response.as_ref().map_or(0, |response| {
+                    response_sections(response)
+                        .iter()
+                        .map(|(_, lines)| lines.len() + 2)
+                        .sum::<usize>()
+                        .saturating_sub(1)
+                }) as u16;
+                composer.response_scroll = (composer.response_scroll + 10).min(max_scroll);
+            }
+            _ => match composer.focus {
+                ComposerField::Method => match key.code {
+                    KeyCode::Left | KeyCode::Char('h') => {
+                        composer.method =
+                            (composer.method + COMPOSER_METHODS.len() - 1) % COMPOSER_METHODS.len();
+                    }
+                    KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => {
+                        composer.method = (composer.method + 1) % COMPOSER_METHODS.len();
+                    }
+                    KeyCode::Enter => self.send_composer().await,
+                    _ => {}
+                },
+                ComposerField::Path => match key.code {
+                    KeyCode::Enter => self.send_composer().await,
+                    KeyCode::Backspace => {
+                        composer.path.pop();
+                    }
+                    KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        composer.path.push(c);
+                    }
+                    _ => {}
+                },
+                ComposerField::Headers => {
+                    composer.headers.input(crossterm_key_to_input(key));
+                }
+                ComposerField::Body => {
+                    composer.body.input(crossterm_key_to_input(key));
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::make_test_app;
+
+    #[test]
+    fn form_builds_the_request_with_debug_mode() {
+        let mut composer = Composer::new(4545);
+        composer.method = 1;
+        composer.path = "/orders?dry=1".to_string();
+        composer.headers = TextArea::new(vec![
+            "Content-Type: application/json".to_string(),
+            String::new(),
+            "x-rift-debug: false".to_string(),
+        ]);
+        composer.body = TextArea::new(vec!["{".to_string(), "}".to_string()]);
+        composer.debug = true;
+
+        let request = composer.to_request().unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/orders?dry=1");
+        assert_eq!(
+            request.headers,
+            [
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("X-Rift-Debug".to_string(), "true".to_string()),
+            ]
+        );
+        assert_eq!(request.body, b"{\n}");
+    }
+
+    #[test]
+    fn a_header_without_a_colon_is_rejected() {
+        let mut composer = Composer::new(4545);
+        composer.headers = TextArea::new(vec!["Accept".to_string()]);
+        assert!(composer.to_request().is_err());
+    }
+
+    #[test]
+    fn from_request_round_trips() {
+        let request = ImposterRequest {
+            method: "delete".to_string(),
+            path: "/orders/7".to_string(),
+            headers: vec![("Accept".to_string(), "*/*".to_string())],
+            body: b"x".to_vec(),
+        };
+        let composer = Composer::from_request(4545, &request);
+        assert_eq!(composer.method(), "DELETE");
+        let mut expected = request.clone();
+        expected.method = "DELETE".to_string();
+        assert_eq!(composer.to_request().unwrap(), expected);
+    }
+
+    #[test]
+    fn rift_headers_are_listed_first() {
+        let response = ImposterResponse {
+            status: 200,
+            headers: vec![
+                ("content-type".to_string(), "application/json".to_string()),
+                ("x-rift-imposter".to_string(), "true".to_string()),
+            ],
+            body: r#"{"ok":true}"#.to_string(),
+            latency_ms: 1,
+        };
+        let sections = response_sections(&response);
+        assert_eq!(
+            sections[0],
+            (
+                "RIFT DIAGNOSTICS",
+                vec!["x-rift-imposter: true".to_string()]
+            )
+        );
+        assert_eq!(sections[1].1, ["content-type: application/json"]);
+        assert_eq!(sections[2].1, ["{", "  \"ok\": true", "}"]);
+    }
+
+    #[tokio::test]
+    async fn method_cycles_and_reopening_keeps_the_form() {
+        let mut app = make_test_app();
+        app.open_composer(4545);
+        if let Some(composer) = app.composer.as_mut() {
+            composer.focus = ComposerField::Method;
+        }
+        app.handle_composer_event(KeyEvent::from(KeyCode::Left))
+            .await;
+        assert_eq!(app.composer.as_ref().unwrap().method(), "OPTIONS");
+
+        app.handle_composer_event(KeyEvent::from(KeyCode::Esc))
+            .await;
+        assert_eq!(app.overlay, Overlay::None);
+        app.open_composer(4545);
+        assert_eq!(app.composer.as_ref().unwrap().method(), "OPTIONS");
+        app.open_composer(4546);
+        assert_eq!(app.composer.as_ref().unwrap().method(), "GET");
+    }
+}
@@ -0,0 +1,163 @@
+//! Connection health: retrying a server that went away with exponential backoff, and how stale
+//! the data on screen has become meanwhile
+
+use super::*;
+
+/// How long to wait before the first retry of a server that stopped answering
+pub const RECONNECT_MIN_DELAY: Duration = Duration::from_secs(1);
+/// The longest wait between retries
+pub const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Backoff between attempts to reach a server that stopped answering
+#[derive(Debug, Default)]
+pub struct Reconnect {
+    /// Health checks failed in a row
+    pub failures: u32,
+    /// When to try again; `None` while connected
+    pub next_attempt: Option<Instant>,
+}
+
+impl Reconnect {
+    /// The wait after `failures` failed attempts: doubling from a second up to a minute
+    pub fn delay(failures: u32) -> Duration {
+        let doublings = failures.saturating_sub(1).min(16);
+        (RECONNECT_MIN_DELAY * 2u32.pow(doublings)).min(RECONNECT_MAX_DELAY)
+    }
+
+    pub fn failed(&mut self, now: Instant) {
+        self.failures = self.failures.saturating_add(1);
+        self.next_attempt = Some(now + Self::delay(self.failures));
+    }
+
+    /// Back to the normal refresh schedule. Returns whether this ended a run of failures.
+    pub fn succeeded(&mut self) -> bool {
+        let was_down = self.failures > 0;
+        *self = Self::default();
+        was_down
+    }
+
+    /// Time left until the next attempt, while retrying
+    pub fn retry_in(&self, now: Instant) -> Option<Duration> {
+        self.next_attempt
+            .map(|at| at.saturating_duration_since(now))
+    }
+}
+
+impl App {
+    /// Whether the next tick should refresh: every refresh interval while connected, on the
+    /// backoff schedule while not
+    pub fn refresh_due(&self, now: Instant) -> bool {
+        match self.reconnect.next_attempt {
+            Some(at) => now >= at,
+            None => now.saturating_duration_since(self.last_refresh) >= self.refresh_interval,
+        }
+    }
+
+    /// Record the outcome of a refresh's health check, saying so when the server goes away or
+    /// comes back. With every server listed, each one's own report says that instead.
+    pub(super) fn connection_checked(&mut self, connected: bool, now: Instant) {
+        let single = self.active_server.is_some();
+        if connected {
+            if self.reconnect.succeeded() && single {
+                self.set_status(
+                    format!("Reconnected to {}", self.admin_url),
+                    StatusLevel::Success,
+                );
+            }
+            self.last_updated = Some(now);
+        } else {
+            if self.reconnect.failures == 0 && single {
+                self.set_status(
+                    format!(
+                        "Cannot reach {}; retrying, and changes are refused until it is back",
+                        self.admin_url
+                    ),
+                    StatusLevel::Warning,
+                );
+            }
+            self.reconnect.failed(now);
+        }
+    }
+
+    /// How old the data on screen is, once the server stopped answering. `None` while connected
+    /// or when nothing was ever loaded.
+    pub fn stale_for(&self, now: Instant) -> Option<Duration> {
+        if self.is_connected {
+            return None;
+        }
+        self.last_updated
+            .map(|at| now.saturating_duration_since(at))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::make_test_app;
+
+    #[test]
+    fn the_backoff_doubles_up_to_a_minute() {
+        let delays: Vec<u64> = (1..=8).map(|n| Reconnect::delay(n).as_secs()).collect();
+        assert_eq!(delays, [1, 2, 4, 8, 16, 32, 60, 60]);
+        assert_eq!(Reconnect::delay(u32::MAX), RECONNECT_MAX_DELAY);
+    }
+
+    #[test]
+    fn refreshes_follow_the_backoff_while_disconnected() {
+        let mut app = make_test_app();
+        let now = Instant::now();
+        app.connection_checked(false, now);
+        app.connection_checked(false, now);
+        assert_eq!(app.reconnect.retry_in(now), Some(Duration::from_secs(2)));
+        assert!(!app.refresh_due(now + Duration::from_secs(1)));
+        assert!(app.refresh_due(now + Duration::from_secs(2)));
+        assert!(
+            app.status_message
+                .as_ref()
+                .unwrap()
+                .0
+                .starts_with("Cannot reach"),
+            "said once, when it went away"
+        );
+
+        app.connection_checked(true, now);
+        assert_eq!(app.reconnect.failures, 0);
+        assert!(app.reconnect.retry_in(now).is_none());
+        assert_eq!(
+            app.status_message.as_ref().unwrap().0,
+            "Reconnected to http://localhost:2525"
+        );
+        assert!(!app.refresh_due(now));
+        assert!(app.refresh_due(now + app.refresh_interval));
+    }
+
+    #[test]
+    fn data_is_stale_only_while_disconnected() {
+        let mut app = make_test_app();
+        let loaded = Instant::now();
+        app.connection_checked(true, loaded);
+        app.is_connected = true;
+        assert_eq!(app.stale_for(loaded + Duration::from_secs(5)), None);
+        app.is_connected = false;
+        assert_eq!(
+            app.stale_for(loaded + Duration::from_secs(5)),
+            Some(Duration::from_secs(5))
+        );
+    }
+
+    #[tokio::test]
+    async fn changes_are_refused_while_the_server_is_down() {
+        let mut app = make_test_app();
+        app.client = ApiClient::new("http://127.0.0.1:9");
+        let clone = app.client.clone();
+        app.refresh().await;
+        assert!(!app.is_connected);
+        assert!(!clone.is_reachable(), "clones share it");
+        let err = app.client.delete_imposter(4545).await.unwrap_err();
+        assert!(matches!(err, ApiError::Offline(_)), "{err}");
+        assert_eq!(
+            err.to_string(),
+            "Not connected to http://127.0.0.1:9; nothing was changed"
+        );
+    }
+}
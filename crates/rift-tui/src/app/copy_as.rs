@@ -0,0 +1,513 @@
+//! Copy-as menu: shell commands that create the selected imposter or stub, or send the request it
+//! answers or a recorded one again, with curl, httpie, mb or the rift CLI
+
+use super::*;
+
+/// The command line tool a copied command runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyTool {
+    Curl,
+    Httpie,
+    Mb,
+    Rift,
+}
+
+impl CopyTool {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CopyTool::Curl => "curl",
+            CopyTool::Httpie => "httpie",
+            CopyTool::Mb => "mb",
+            CopyTool::Rift => "rift",
+        }
+    }
+}
+
+/// What the copy-as menu was opened on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyTarget {
+    /// Stub `index` of the open imposter
+    Stub { port: u16, index: usize },
+    /// An imposter of the list, on server `server` when listing every server
+    Imposter { port: u16, server: Option<usize> },
+    /// The request open in the request inspector
+    Request { port: u16 },
+}
+
+/// A command the copy-as menu offers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyCommand {
+    /// A request the stub answers
+    StubRequest(CopyTool),
+    /// Add the stub to its imposter through the admin API
+    AddStub(CopyTool),
+    /// Create the imposter through the admin API, or start a server with it
+    CreateImposter(CopyTool),
+    /// Send the recorded request again
+    Replay(CopyTool),
+}
+
+impl CopyCommand {
+    pub fn tool(&self) -> CopyTool {
+        match self {
+            CopyCommand::StubRequest(tool)
+            | CopyCommand::AddStub(tool)
+            | CopyCommand::CreateImposter(tool)
+            | CopyCommand::Replay(tool) => *tool,
+        }
+    }
+
+    /// What the command does, for the menu
+    pub fn describe(&self, port: u16) -> String {
+        match self {
+            CopyCommand::StubRequest(_) => "send a request this stub answers".to_string(),
+            CopyCommand::AddStub(_) => format!("add this stub to :{port}"),
+            CopyCommand::CreateImposter(CopyTool::Mb) => {
+                format!("start mountebank with imposter :{port}")
+            }
+            CopyCommand::CreateImposter(CopyTool::Rift) => {
+                format!("start Rift with imposter :{port}")
+            }
+            CopyCommand::CreateImposter(_) => format!("create imposter :{port}"),
+            CopyCommand::Replay(_) => "send this request again".to_string(),
+        }
+    }
+}
+
+/// The copy-as menu
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopyMenu {
+    pub target: CopyTarget,
+    pub commands: Vec<CopyCommand>,
+    pub selected: usize,
+    /// The overlay it was opened over, shown again when it closes
+    pub return_to: Overlay,
+}
+
+impl CopyMenu {
+    pub fn new(target: CopyTarget, return_to: Overlay) -> Self {
+        use CopyTool::*;
+        let create = [Curl, Httpie, Mb, Rift].map(CopyCommand::CreateImposter);
+        let commands = match target {
+            CopyTarget::Stub { .. } => [Curl, Httpie]
+                .map(CopyCommand::StubRequest)
+                .into_iter()
+                .chain([Curl, Httpie].map(CopyCommand::AddStub))
+                .chain(create)
+                .collect(),
+            CopyTarget::Imposter { .. } => create.to_vec(),
+            CopyTarget::Request { .. } => [Curl, Httpie].map(CopyCommand::Replay).to_vec(),
+        };
+        Self {
+            target,
+            commands,
+            selected: 0,
+            return_to,
+        }
+    }
+
+    pub fn port(&self) -> u16 {
+        match self.target {
+            CopyTarget::Stub { port, .. }
+            | CopyTarget::Imposter { port, .. }
+            | CopyTarget::Request { port } => port,
+        }
+    }
+}
+
+/// Quote `text` for a POSIX shell, leaving a lone `$VARIABLE` to be expanded
+fn shell_quote(text: &str) -> String {
+    let variable = text.split_once('$').is_some_and(|(_, name)| {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    });
+    if variable {
+        format!("\"{text}\"")
+    } else {
+        format!("'{}'", text.replace('\'', "'\\''"))
+    }
+}
+
+/// `request` as a curl command, sent to `base`, a URL without a path
+pub fn curl_command(request: &ImposterRequest, base: &str) -> String {
+    let mut parts: Vec<String> = vec!["curl -s".to_string()];
+    if request.method != "GET" {
+        parts.push(format!("-X {}", request.method));
+    }
+    for (name, value) in &request.headers {
+        parts.push(format!("-H {}", shell_quote(&format!("{name}: {value}"))));
+    }
+    if !request.body.is_empty() {
+        parts.push(format!(
+            "-d {}",
+            shell_quote(&String::from_utf8_lossy(&request.body))
+        ));
+    }
+    parts.push(shell_quote(&format!("{base}{}", request.path)));
+    parts.join(" \\\n  ")
+}
+
+/// `request` as an httpie command, sent to `base`, a URL without a path
+pub fn httpie_command(request: &ImposterRequest, base: &str) -> String {
+    let mut parts = vec![
+        format!("http {}", request.method),
+        shell_quote(&format!("{base}{}", request.path)),
+    ];
+    for (name, value) in &request.headers {
+        parts.push(shell_quote(&format!("{name}:{value}")));
+    }
+    if !request.body.is_empty() {
+        parts.push(format!(
+            "--raw {}",
+            shell_quote(&String::from_utf8_lossy(&request.body))
+        ));
+    }
+    parts.join(" \\\n  ")
+}
+
+/// Headers not worth copying: the tool sets them itself
+const TOOL_HEADERS: &[&str] = &[
+    "host",
+    "content-length",
+    "connection",
+    "transfer-encoding",
+    "accept-encoding",
+    "user-agent",
+];
+
+/// `config` written to a file and a server started with it, by mb or rift
+fn startup_command(tool: CopyTool, config: &serde_json::Value, port: u16) -> String {
+    let file = format!("imposter-{port}.json");
+    let json = serde_json::to_string_pretty(&serde_json::json!({ "imposters": [config] }))
+        .unwrap_or_default();
+    let text = config.to_string();
+    let scripted = ["\"inject\"", "\"decorate\"", "\"_rift\""]
+        .iter()
+        .any(|key| text.contains(key));
+    let start = match (tool, scripted) {
+        (CopyTool::Mb, false) => format!("mb start --configfile {file}"),
+        (CopyTool::Mb, true) => format!("mb start --configfile {file} --allowInjection"),
+        (_, false) => format!("rift --configfile {file}"),
+        (_, true) => format!("rift --configfile {file} --allow-injection"),
+    };
+    format!("cat > {file} <<'EOF'\n{json}\nEOF\n{start}")
+}
+
+impl App {
+    /// `y`: offer commands for the selected stub, imposter or inspected request
+    pub fn show_copy_menu(&mut self) {
+        let target = match (&self.overlay, &self.view) {
+            (Overlay::RequestInspector { port }, _) => CopyTarget::Request { port: *port },
+            (_, View::StubDetail { port, index }) => CopyTarget::Stub {
+                port: *port,
+                index: *index,
+            },
+            (_, View::ImposterDetail { port }) => match self.stub_list_state.selected() {
+                Some(index) => CopyTarget::Stub { port: *port, index },
+                None => return,
+            },
+            (_, View::ImposterList) => match self.selected_imposter() {
+                Some(imp) => CopyTarget::Imposter {
+                    port: imp.port,
+                    server: imp.server,
+                },
+                None => return,
+            },
+            _ => return,
+        };
+        let return_to = std::mem::replace(&mut self.overlay, Overlay::CopyMenu);
+        self.copy_menu = Some(CopyMenu::new(target, return_to));
+    }
+
+    /// The server `server` of the all-servers list, or the one shown
+    fn copy_server(&self, server: Option<usize>) -> (ApiClient, Option<&Server>) {
+        match server.and_then(|i| self.servers.get(i)) {
+            Some(s) => (s.client.clone(), Some(s)),
+            None => (
+                self.client.clone(),
+                self.active_server.and_then(|i| self.servers.get(i)),
+            ),
+        }
+    }
+
+    /// Base URL of imposter `port`, on the host of `client`'s admin API
+    pub(super) fn imposter_url(&self, port: u16, client: &ApiClient) -> String {
+        crate::api::imposter_url(client.base_url(), port, "/")
+            .map(|url| url.as_str().trim_end_matches('/').to_string())
+            .unwrap_or_else(|_| format!("http://localhost:{port}"))
+    }
+
+    /// An admin API request, with the API key left to an environment variable
+    fn admin_request(
+        server: Option<&Server>,
+        path: String,
+        body: &serde_json::Value,
+    ) -> ImposterRequest {
+        let mut headers = vec![("Content-Type".to_string(), "application/json".to_string())];
+        if let Some(profile) = server.map(|s| &s.profile) {
+            let variable = match (&profile.api_key_env, &profile.api_key) {
+                (Some(variable), _) => Some(variable.as_str()),
+                (None, Some(_)) => Some("RIFT_API_KEY"),
+                (None, None) => None,
+            };
+            if let Some(variable) = variable {
+                headers.push(("Authorization".to_string(), format!("${variable}")));
+            }
+        }
+        ImposterRequest {
+            method: "POST".to_string(),
+            path,
+            headers,
+            body: serde_json::to_string_pretty(body)
+                .unwrap_or_default()
+                .into_bytes(),
+        }
+    }
+
+    /// The command `command` of the menu, fetching the imposter when it needs it
+    pub(super) async fn copy_command(
+        &self,
+        menu: &CopyMenu,
+        command: CopyCommand,
+    ) -> Result<String, String> {
+        let port = menu.port();
+        let server = match menu.target {
+            CopyTarget::Imposter { server, .. } => server,
+            _ => None,
+        };
+        let (client, profile) = self.copy_server(server);
+        let render = |request: &ImposterRequest, base: &str| match command.tool() {
+            CopyTool::Httpie => httpie_command(request, base),
+            _ => curl_command(request, base),
+        };
+        let admin = client.base_url().trim_end_matches('/').to_string();
+        match command {
+            CopyCommand::StubRequest(_) | CopyCommand::AddStub(_) => {
+                let CopyTarget::Stub { index, .. } = menu.target else {
+                    return Err("no stub selected".to_string());
+                };
+                let stub = self
+                    .current_imposter
+                    .as_ref()
+                    .and_then(|imp| imp.stubs.get(index))
+                    .ok_or_else(|| format!("stub {index} of :{port} is not loaded"))?;
+                if let CopyCommand::StubRequest(_) = command {
+                    return Ok(render(
+                        &self.stub_request(stub),
+                        &self.imposter_url(port, &client),
+                    ));
+                }
+                let body = serde_json::json!({ "stub": stub });
+                let request =
+                    Self::admin_request(profile, format!("/imposters/{port}/stubs"), &body);
+                Ok(render(&request, &admin))
+            }
+            CopyCommand::CreateImposter(tool) => {
+                let json = client
+                    .export_imposter(port, false)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let config: serde_json::Value =
+                    serde_json::from_str(&json).map_err(|e| e.to_string())?;
+                match tool {
+                    CopyTool::Mb | CopyTool::Rift => Ok(startup_command(tool, &config, port)),
+                    _ => Ok(render(
+                        &Self::admin_request(profile, "/imposters".to_string(), &config),
+                        &admin,
+                    )),
+                }
+            }
+            CopyCommand::Replay(_) => {
+                let detail = self
+                    .inspected_request
+                    .as_ref()
+                    .ok_or_else(|| "no request open".to_string())?;
+                let mut request = detail.replay_request().map_err(|e| e.to_string())?;
+                request.headers.retain(|(name, _)| {
+                    !TOOL_HEADERS.contains(&name.to_ascii_lowercase().as_str())
+                });
+                Ok(render(&request, &self.imposter_url(port, &client)))
+            }
+        }
+    }
+
+    pub(super) async fn handle_copy_menu_event(&mut self, key: KeyEvent) {
+        let Some(menu) = self.copy_menu.as_mut() else {
+            self.overlay = Overlay::None;
+            return;
+        };
+        let last = menu.commands.len().saturating_sub(1);
+        let chosen = match key.code {
+            KeyCode::Down | KeyCode::Char('j') => {
+                menu.selected = (menu.selected + 1).min(last);
+                None
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                menu.selected = menu.selected.saturating_sub(1);
+                None
+            }
+            KeyCode::Enter => Some(menu.selected),
+            KeyCode::Char(digit @ '1'..='9') => {
+                Some(digit as usize - '1' as usize).filter(|index| *index <= last)
+            }
+            KeyCode::Esc => {
+                self.close_copy_menu();
+                return;
+            }
+            _ => None,
+        };
+        let Some(index) = chosen else {
+            return;
+        };
+        let Some(menu) = self.copy_menu.clone() else {
+            return;
+        };
+        let command = menu.commands[index];
+        self.is_loading = true;
+        let copied = self.copy_command(&menu, command).await;
+        self.is_loading = false;
+        self.close_copy_menu();
+        match copied {
+            Ok(text) => {
+                self.copy_to_clipboard(&text);
+                // Say what was copied, unless the clipboard refused it
+                if matches!(&self.status_message, Some((_, StatusLevel::Success, _))) {
+                    self.set_status(
+                        format!(
+                            "Copied the {} command to {}",
+                            command.tool().label(),
+                            command.describe(menu.port())
+                        ),
+                        StatusLevel::Success,
+                    );
+                }
+            }
+            Err(e) => self.set_status(
+                format!("Cannot make the {} command: {e}", command.tool().label()),
+                StatusLevel::Error,
+            ),
+        }
+    }
+
+    fn close_copy_menu(&mut self) {
+        if let Some(menu) = self.copy_menu.take() {
+            self.overlay = menu.return_to;
+        } else {
+            self.overlay = Overlay::None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::make_test_app;
+
+    fn request(method: &str, path: &str, headers: &[(&str, &str)], body: &str) -> ImposterRequest {
+        ImposterRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn curl_and_httpie_send_the_same_request() {
+        let post = request(
+            "POST",
+            "/orders?id=1",
+            &[
+                ("Content-Type", "application/json"),
+                ("Authorization", "$RIFT_API_KEY"),
+            ],
+            r#"{"note":"it's"}"#,
+        );
+        assert_eq!(
+            curl_command(&post, "http://localhost:4545"),
+            "curl -s \\\n  -X POST \\\n  -H 'Content-Type: application/json' \\\n  \
+             -H \"Authorization: $RIFT_API_KEY\" \\\n  -d '{\"note\":\"it'\\''s\"}' \\\n  \
+             'http://localhost:4545/orders?id=1'"
+        );
+        assert_eq!(
+            httpie_command(&post, "http://localhost:4545"),
+            "http POST \\\n  'http://localhost:4545/orders?id=1' \\\n  \
+             'Content-Type:application/json' \\\n  \"Authorization:$RIFT_API_KEY\" \\\n  \
+             --raw '{\"note\":\"it'\\''s\"}'"
+        );
+        assert_eq!(
+            curl_command(&request("GET", "/health", &[], ""), "http://rift:4546"),
+            "curl -s \\\n  'http://rift:4546/health'"
+        );
+    }
+
+    #[test]
+    fn startup_commands_allow_injection_only_for_scripted_imposters() {
+        let plain = serde_json::json!({ "port": 4545, "protocol": "http", "stubs": [] });
+        let command = startup_command(CopyTool::Mb, &plain, 4545);
+        assert!(command.starts_with("cat > imposter-4545.json <<'EOF'\n{\n  \"imposters\": ["));
+        assert!(command.ends_with("\nEOF\nmb start --configfile imposter-4545.json"));
+
+        let scripted = serde_json::json!({
+            "port": 4545,
+            "stubs": [{ "responses": [{ "inject": "function () {}" }] }]
+        });
+        assert!(
+            startup_command(CopyTool::Rift, &scripted, 4545)
+                .ends_with("rift --configfile imposter-4545.json --allow-injection")
+        );
+    }
+
+    #[tokio::test]
+    async fn the_menu_offers_what_fits_what_it_was_opened_on() {
+        let mut app = make_test_app();
+        app.current_imposter = Some(
+            serde_json::from_value(serde_json::json!({
+                "port": 4545,
+                "protocol": "http",
+                "stubs": [{ "predicates": [
+                    { "equals": { "method": "DELETE", "path": "/orders/1" } }
+                ] }]
+            }))
+            .unwrap(),
+        );
+        app.view = View::ImposterDetail { port: 4545 };
+        app.stub_list_state.select(Some(0));
+        app.show_copy_menu();
+        assert_eq!(app.overlay, Overlay::CopyMenu);
+        let menu = app.copy_menu.clone().unwrap();
+        assert_eq!(
+            menu.target,
+            CopyTarget::Stub {
+                port: 4545,
+                index: 0
+            }
+        );
+        assert_eq!(menu.commands.len(), 8);
+        assert_eq!(menu.commands[0], CopyCommand::StubRequest(CopyTool::Curl));
+
+        let added = app
+            .copy_command(&menu, CopyCommand::AddStub(CopyTool::Curl))
+            .await
+            .unwrap();
+        assert!(added.contains("/imposters/4545/stubs'"));
+        assert!(added.contains("\"stub\": {"));
+        let sent = app
+            .copy_command(&menu, CopyCommand::StubRequest(CopyTool::Httpie))
+            .await
+            .unwrap();
+        assert!(sent.starts_with("http DELETE \\\n  '"));
+        assert!(sent.ends_with(":4545/orders/1'"));
+
+        app.handle_copy_menu_event(KeyEvent::from(KeyCode::Esc))
+            .await;
+        assert_eq!(app.overlay, Overlay::None);
+        assert!(app.copy_menu.is_none());
+
+        app.view = View::ImposterList;
+        app.show_copy_menu();
+        assert!(app.copy_menu.is_none(), "nothing is selected to copy");
+    }
+}
@@ -0,0 +1,160 @@
+//! Diff preview: what a save or apply changes on the server, shown for confirmation first
+
+use super::*;
+use similar::{ChangeTag, TextDiff};
+
+/// One line of a unified diff
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffLine {
+    /// `@@ -a,b +c,d @@`
+    Hunk(String),
+    Context(String),
+    Removed(String),
+    Added(String),
+}
+
+/// What to do once a diff preview is confirmed
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffAction {
+    /// Replace stub `index` of `port` with the stub editor's content
+    SaveStub { port: u16, index: usize },
+    /// Recreate imposter `port` from `config`: its recorded stubs without the proxies
+    ApplyRecordedStubs {
+        port: u16,
+        config: serde_json::Value,
+    },
+}
+
+/// The changes from `old` to `new` as unified diff hunks with three lines of context
+pub fn unified_diff(old: &str, new: &str) -> Vec<DiffLine> {
+    // A final line without a newline would otherwise differ from the same line with one
+    let (old, new) = (format!("{old}\n"), format!("{new}\n"));
+    let diff = TextDiff::from_lines(&old, &new);
+    let mut lines = Vec::new();
+    for hunk in diff.unified_diff().context_radius(3).iter_hunks() {
+        lines.push(DiffLine::Hunk(hunk.header().to_string()));
+        for change in hunk.iter_changes() {
+            let text = change.value().trim_end_matches('\n').to_string();
+            lines.push(match change.tag() {
+                ChangeTag::Equal => DiffLine::Context(text),
+                ChangeTag::Delete => DiffLine::Removed(text),
+                ChangeTag::Insert => DiffLine::Added(text),
+            });
+        }
+    }
+    lines
+}
+
+impl App {
+    /// Show the diff from the server's `current` JSON to the `proposed` one, running `action`
+    /// once confirmed
+    pub(super) fn show_diff_preview(
+        &mut self,
+        title: String,
+        warning: Option<String>,
+        current: &str,
+        proposed: &str,
+        action: DiffAction,
+    ) {
+        self.diff_scroll = 0;
+        self.overlay = Overlay::DiffPreview {
+            title,
+            warning,
+            diff: unified_diff(current, proposed),
+            action,
+        };
+    }
+
+    pub(super) async fn handle_diff_preview_event(
+        &mut self,
+        key: KeyEvent,
+        line_count: usize,
+        action: DiffAction,
+    ) {
+        let max_scroll = line_count.saturating_sub(1) as u16;
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('y') => {
+                self.overlay = Overlay::None;
+                match action {
+                    DiffAction::SaveStub { port, index } => {
+                        if let Some(stub) = self.stub_editor.as_ref().and_then(|e| e.get_stub()) {
+                            self.write_stub(port, Some(index), stub).await;
+                        }
+                    }
+                    DiffAction::ApplyRecordedStubs { port, config } => {
+                        self.apply_recorded_stubs(port, config).await;
+                    }
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('q') => {
+                self.overlay = Overlay::None;
+                self.set_status("Cancelled".to_string(), StatusLevel::Info);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.diff_scroll = (self.diff_scroll + 1).min(max_scroll);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.diff_scroll = self.diff_scroll.saturating_sub(1);
+            }
+            KeyCode::PageDown => self.diff_scroll = (self.diff_scroll + 10).min(max_scroll),
+            KeyCode::PageUp => self.diff_scroll = self.diff_scroll.saturating_sub(10),
+            KeyCode::Char('g') | KeyCode::Home => self.diff_scroll = 0,
+            KeyCode::Char('G') | KeyCode::End => self.diff_scroll = max_scroll,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::make_test_app;
+
+    #[test]
+    fn diff_has_hunks_with_context() {
+        let old = (1..=10)
+            .map(|i| format!("line {i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let new = old
+            .replace("line 5", "line five")
+            .replace("line 10", "line 10\nline 11");
+        let diff = unified_diff(&old, &new);
+        assert_eq!(diff[0], DiffLine::Hunk("@@ -2,9 +2,10 @@".to_string()));
+        assert_eq!(
+            diff[1..]
+                .iter()
+                .filter(|line| !matches!(line, DiffLine::Context(_)))
+                .collect::<Vec<_>>(),
+            [
+                &DiffLine::Removed("line 5".to_string()),
+                &DiffLine::Added("line five".to_string()),
+                &DiffLine::Added("line 11".to_string()),
+            ]
+        );
+        assert!(unified_diff(&old, &old).is_empty());
+    }
+
+    #[tokio::test]
+    async fn cancelling_keeps_the_editor_and_scrolling_is_bounded() {
+        let mut app = make_test_app();
+        app.stub_editor = Some(StubEditor::new("{}"));
+        let action = DiffAction::SaveStub {
+            port: 4545,
+            index: 0,
+        };
+        app.show_diff_preview(String::new(), None, "a\nb", "a\nc", action.clone());
+        let Overlay::DiffPreview { diff, .. } = &app.overlay else {
+            panic!("no diff preview");
+        };
+        let count = diff.len();
+        app.handle_diff_preview_event(KeyEvent::from(KeyCode::End), count, action.clone())
+            .await;
+        assert_eq!(app.diff_scroll as usize, count - 1);
+
+        app.handle_diff_preview_event(KeyEvent::from(KeyCode::Esc), count, action)
+            .await;
+        assert_eq!(app.overlay, Overlay::None);
+        assert!(app.stub_editor.is_some());
+    }
+}
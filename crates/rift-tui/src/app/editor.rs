@@ -0,0 +1,249 @@
+//! Stub editor structure: folding, bracket matching and the viewport the UI draws
+
+use super::*;
+use crate::json_syntax::{TextPos, block_end, matching_bracket};
+use ratatui_textarea::{CursorMove, DataCursor};
+
+/// Where a viewport starts so the cursor stays in view, scrolling no further than needed. This is
+/// the rule ratatui-textarea follows for its own viewport.
+fn follow_cursor(top: u16, cursor: u16, len: u16) -> u16 {
+    if cursor < top {
+        cursor
+    } else if top + len <= cursor {
+        cursor + 1 - len
+    } else {
+        top
+    }
+}
+
+impl StubEditor {
+    /// Rows shown in the editor: every line except those inside a folded block
+    pub fn visible_rows(&self) -> Vec<usize> {
+        let lines = self.editor.lines();
+        let mut rows = Vec::with_capacity(lines.len());
+        let mut row = 0;
+        while row < lines.len() {
+            rows.push(row);
+            row = match self.folded_end(row) {
+                Some(end) => end + 1,
+                None => row + 1,
+            };
+        }
+        rows
+    }
+
+    /// The last line of the block folded at `row`, if it is folded
+    pub fn folded_end(&self, row: usize) -> Option<usize> {
+        if self.folds.contains(&row) {
+            block_end(self.editor.lines(), row)
+        } else {
+            None
+        }
+    }
+
+    /// The folded block that hides `row`, outermost first
+    fn fold_hiding(&self, row: usize) -> Option<(usize, usize)> {
+        self.folds
+            .range(..row)
+            .filter_map(|&start| Some((start, self.folded_end(start)?)))
+            .find(|&(_, end)| row <= end)
+    }
+
+    /// Fold or unfold the block that opens on the cursor's line, or else the innermost block
+    /// around the cursor. The cursor moves to the line that stays visible.
+    pub fn toggle_fold(&mut self) {
+        let DataCursor(row, col) = self.editor.cursor();
+        if self.folds.remove(&row) {
+            return;
+        }
+        let lines = self.editor.lines();
+        let Some(start) = (0..=row)
+            .rev()
+            .find(|&start| block_end(lines, start).is_some_and(|end| end >= row))
+        else {
+            return;
+        };
+        self.folds.insert(start);
+        if start != row {
+            self.editor
+                .move_cursor(CursorMove::Jump(start as u16, col as u16));
+        }
+    }
+
+    /// The bracket at the cursor, or just before it, and the bracket matching it
+    pub fn bracket_pair(&self) -> Option<(TextPos, Option<TextPos>)> {
+        let lines = self.editor.lines();
+        let DataCursor(row, col) = self.editor.cursor();
+        let brackets = crate::json_syntax::brackets(lines.get(row)?);
+        let at = brackets
+            .iter()
+            .find(|(c, _)| *c == col)
+            .or_else(|| brackets.iter().find(|(c, _)| col > 0 && *c == col - 1))?;
+        Some(((row, at.0), matching_bracket(lines, (row, at.0))))
+    }
+
+    /// The first display row the editor shows in `height` rows, following the cursor
+    pub fn viewport_top(&self, height: u16) -> u16 {
+        let rows = self.visible_rows();
+        let cursor = rows.partition_point(|&row| row < self.editor.cursor().0) as u16;
+        let top = follow_cursor(self.scroll_top.get(), cursor, height.max(1));
+        self.scroll_top.set(top);
+        self.page_height.set(height);
+        top
+    }
+
+    /// The first column the editor shows in `width` columns, following the cursor
+    pub fn viewport_left(&self, width: u16) -> u16 {
+        let cursor = self.editor.cursor().1 as u16;
+        let left = follow_cursor(self.scroll_left.get(), cursor, width.max(1));
+        self.scroll_left.set(left);
+        left
+    }
+
+    /// Move the cursor a page of display rows up or down, extending the selection with Shift
+    pub(super) fn page(&mut self, down: bool, shift: bool) {
+        if shift {
+            if !self.editor.is_selecting() {
+                self.editor.start_selection();
+            }
+        } else {
+            self.editor.cancel_selection();
+        }
+        let rows = self.visible_rows();
+        let DataCursor(row, col) = self.editor.cursor();
+        let current = rows.partition_point(|&r| r < row);
+        let page = match self.page_height.get() {
+            0 => 10,
+            height => height as usize,
+        };
+        let target = if down {
+            (current + page).min(rows.len().saturating_sub(1))
+        } else {
+            current.saturating_sub(page)
+        };
+        if let Some(&target) = rows.get(target) {
+            self.editor
+                .move_cursor(CursorMove::Jump(target as u16, col as u16));
+        }
+    }
+
+    /// Paste `text` at the cursor
+    pub fn paste(&mut self, text: &str) {
+        let before = (self.editor.cursor().0, self.editor.lines().len());
+        self.editor.set_yank_text(text);
+        self.editor.paste();
+        self.after_input(before, false);
+    }
+
+    /// Keep the folds in step with an edit and the cursor out of folded lines. `before` is the
+    /// cursor row and line count before the input; `vertical` says the input moved the cursor up
+    /// or down, which steps over folded blocks, while anything else that lands in one unfolds it.
+    pub(super) fn after_input(&mut self, before: (usize, usize), vertical: bool) {
+        let (row_before, len_before) = before;
+        let DataCursor(row, col) = self.editor.cursor();
+        let len = self.editor.lines().len();
+        if len != len_before {
+            // Lines came or went below the edited line: folds there move with their text
+            let anchor = row.min(row_before);
+            let removed = len_before.saturating_sub(len);
+            self.folds = std::mem::take(&mut self.folds)
+                .into_iter()
+                .filter(|&start| start <= anchor || start > anchor + removed)
+                .map(|start| {
+                    if start > anchor {
+                        (start + len).saturating_sub(len_before)
+                    } else {
+                        start
+                    }
+                })
+                .collect();
+        }
+        let lines = self.editor.lines();
+        self.folds
+            .retain(|&start| block_end(lines, start).is_some());
+
+        while let Some((start, end)) = self.fold_hiding(row) {
+            if !vertical {
+                self.folds.remove(&start);
+                continue;
+            }
+            let target = if row > row_before && end + 1 < len {
+                end + 1
+            } else {
+                start
+            };
+            self.editor
+                .move_cursor(CursorMove::Jump(target as u16, col as u16));
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STUB: &str = "{\n  \"responses\": [\n    {\n      \"is\": {\n        \"body\": 1\n      }\n    }\n  ],\n  \"id\": \"x\"\n}";
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::from(code)
+    }
+
+    #[test]
+    fn folding_hides_the_block_and_the_cursor_steps_over_it() {
+        let mut editor = StubEditor::new(STUB);
+        editor.editor.move_cursor(CursorMove::Jump(4, 0));
+        // Folds the innermost block around the cursor and moves to its first line
+        editor.toggle_fold();
+        assert_eq!(editor.editor.cursor().0, 3);
+        assert_eq!(editor.visible_rows(), [0, 1, 2, 3, 6, 7, 8, 9]);
+
+        editor.handle_key(key(KeyCode::Down));
+        assert_eq!(editor.editor.cursor().0, 6);
+        editor.handle_key(key(KeyCode::Up));
+        assert_eq!(editor.editor.cursor().0, 3);
+
+        editor.toggle_fold();
+        assert_eq!(editor.visible_rows().len(), 10);
+    }
+
+    #[test]
+    fn folds_move_with_lines_added_above_and_go_when_their_bracket_does() {
+        let mut editor = StubEditor::new(STUB);
+        editor.editor.move_cursor(CursorMove::Jump(1, 0));
+        editor.toggle_fold();
+        assert_eq!(editor.visible_rows(), [0, 1, 8, 9]);
+
+        editor.editor.move_cursor(CursorMove::Jump(0, 1));
+        editor.handle_key(key(KeyCode::Enter));
+        assert_eq!(editor.visible_rows(), [0, 1, 2, 9, 10]);
+
+        // Deleting the bracket that opens the block drops its fold
+        editor.editor.move_cursor(CursorMove::Jump(2, 17));
+        editor.handle_key(key(KeyCode::Backspace));
+        assert_eq!(editor.visible_rows().len(), 11);
+    }
+
+    #[test]
+    fn paging_counts_display_rows() {
+        let mut editor = StubEditor::new(STUB);
+        editor.editor.move_cursor(CursorMove::Jump(1, 0));
+        editor.toggle_fold();
+        editor.editor.move_cursor(CursorMove::Jump(0, 0));
+        editor.viewport_top(2);
+        editor.handle_key(key(KeyCode::PageDown));
+        assert_eq!(editor.editor.cursor().0, 8);
+        editor.handle_key(key(KeyCode::PageUp));
+        assert_eq!(editor.editor.cursor().0, 0);
+    }
+
+    #[test]
+    fn brackets_match_at_or_just_before_the_cursor() {
+        let mut editor = StubEditor::new(STUB);
+        assert_eq!(editor.bracket_pair(), Some(((0, 0), Some((9, 0)))));
+        editor.editor.move_cursor(CursorMove::Jump(7, 3));
+        assert_eq!(editor.bracket_pair(), Some(((7, 2), Some((1, 15)))));
+        editor.editor.move_cursor(CursorMove::Jump(8, 3));
+        assert_eq!(editor.bracket_pair(), None);
+    }
+}
@@ -5,6 +5,15 @@ use super::*;
 impl App {
     /// Handle keyboard input
     pub async fn handle_key_event(&mut self, key: KeyEvent) {
+        let focus = self.accessible.then(|| self.describe_focus());
+        let status = self.status_message.as_ref().map(|s| s.2);
+        self.dispatch_key_event(key).await;
+        if let Some(focus) = focus {
+            self.announce_focus(&focus, status);
+        }
+    }
+
+    async fn dispatch_key_event(&mut self, key: KeyEvent) {
         // Handle overlays first
         match &self.overlay.clone() {
             Overlay::Errors => {
@@ -35,10 +44,10 @@ impl App {
                     KeyCode::Up | KeyCode::Char('k') => {
                         self.help_scroll = self.help_scroll.saturating_sub(1);
                     }
-                    KeyCode::Down | KeyCode::Char('j') => {
-                        if self.help_scroll < self.help_max_scroll {
-                            self.help_scroll += 1;
-                        }
+                    KeyCode::Down | KeyCode::Char('j')
+                        if self.help_scroll < self.help_max_scroll =>
+                    {
+                        self.help_scroll += 1;
                     }
                     KeyCode::PageUp => {
                         self.help_scroll = self.help_scroll.saturating_sub(10);
@@ -120,26 +129,79 @@ impl App {
                 self.overlay = Overlay::None;
                 return;
             }
-            Overlay::ValidationResult { action, .. } => {
-                self.handle_validation_overlay_event(key, action.clone())
+            Overlay::ValidationResult { .. } => {
+                self.handle_validation_overlay_event(key);
+                return;
+            }
+            Overlay::TransferPreview => {
+                self.handle_transfer_preview_event(key).await;
+                return;
+            }
+            Overlay::CopyMenu => {
+                self.handle_copy_menu_event(key).await;
+                return;
+            }
+            Overlay::RequestInspector { port } => {
+                self.handle_request_inspector_event(key, *port).await;
+                return;
+            }
+            Overlay::DiffPreview { diff, action, .. } => {
+                self.handle_diff_preview_event(key, diff.len(), action.clone())
                     .await;
                 return;
             }
+            Overlay::Composer => {
+                self.handle_composer_event(key).await;
+                return;
+            }
+            Overlay::ImposterForm => {
+                self.handle_imposter_form_event(key).await;
+                return;
+            }
+            Overlay::ProxyWizard => {
+                self.handle_proxy_wizard_event(key).await;
+                return;
+            }
+            Overlay::ScriptEditor => {
+                self.handle_script_editor_event(key).await;
+                return;
+            }
+            Overlay::CommandPalette => {
+                self.handle_command_palette_event(key).await;
+                return;
+            }
+            Overlay::GlobalSearch => {
+                self.handle_global_search_event(key).await;
+                return;
+            }
+            Overlay::ServerSwitcher { selected } => {
+                self.handle_server_switcher_event(key, *selected).await;
+                return;
+            }
             Overlay::None => {}
         }
 
         // Handle editor mode
-        if matches!(self.view, View::StubEdit { .. }) {
+        if matches!(self.view, View::StubEdit { .. } | View::ImposterEdit) {
             self.handle_editor_event(key).await;
             return;
         }
 
         // Handle search mode
         if self.search_active {
-            self.handle_search_input(key);
+            self.handle_search_input(key).await;
             return;
         }
 
+        // Keys rebound in keys.toml become the defaults the views act on
+        let Some(key) = self.keys.translate(self.view.key_context(), key) else {
+            return;
+        };
+        self.run_key(key).await;
+    }
+
+    /// Act on a key of the main views, already translated to its default
+    pub(super) async fn run_key(&mut self, key: KeyEvent) {
         // Global keys
         match key.code {
             // `L` for error Log. NOT `e`: the global block runs before the view dispatch and
@@ -152,8 +214,9 @@ impl App {
             KeyCode::Char('?') => {
                 self.overlay = Overlay::Help;
                 self.help_scroll = 0;
-                // Help text has ~75 lines, set max_scroll based on typical terminal height
-                self.help_max_scroll = 50;
+                // Leave a typical terminal height of help text in view at the bottom
+                self.help_max_scroll =
+                    (crate::ui::help_line_count(&self.keys) as u16).saturating_sub(30);
                 return;
             }
             KeyCode::Char('/') => {
@@ -161,6 +224,10 @@ impl App {
                 self.search_query.clear();
                 return;
             }
+            KeyCode::Char(':') => {
+                self.open_command_palette();
+                return;
+            }
             KeyCode::Char('q') => {
                 if matches!(self.view, View::ImposterList) {
                     self.should_quit = true;
@@ -181,6 +248,31 @@ impl App {
                 self.cycle_theme();
                 return;
             }
+            KeyCode::Char('u') => {
+                self.undo().await;
+                return;
+            }
+            KeyCode::Char('U') => {
+                self.redo().await;
+                return;
+            }
+            KeyCode::Char('s') => {
+                self.show_server_switcher();
+                return;
+            }
+            KeyCode::Char('P') => {
+                self.show_screenshot_dialog();
+                return;
+            }
+            KeyCode::Char('S') => {
+                if self.showing_all_servers() && !self.enter_selected_imposter_server().await {
+                    return;
+                }
+                if let Some(port) = self.composer_port() {
+                    self.open_composer(port);
+                }
+                return;
+            }
             _ => {}
         }
 
@@ -190,9 +282,12 @@ impl App {
             View::ImposterDetail { .. } => self.handle_imposter_detail_event(key).await,
             View::StubDetail { .. } => self.handle_stub_detail_event(key).await,
             View::RequestDetail { .. } => {}
+            View::RequestLog { .. } => self.handle_request_log_event(key).await,
             View::Config => self.handle_config_event(key).await,
-            View::Metrics => {}
-            View::StubEdit { .. } => {}
+            View::Metrics => self.handle_metrics_event(key),
+            View::ServerLog => self.handle_server_log_event(key).await,
+            View::Scenario => self.handle_scenario_event(key).await,
+            View::StubEdit { .. } | View::ImposterEdit => {}
         }
     }
 
@@ -211,25 +306,77 @@ impl App {
     }
 
     async fn handle_imposter_list_event(&mut self, key: KeyEvent) {
+        if self.showing_all_servers() {
+            match key.code {
+                // These act on the selected imposter, so on its server
+                KeyCode::Enter | KeyCode::Char('d') | KeyCode::Char('t') | KeyCode::Char('l')
+                    if !self.enter_selected_imposter_server().await =>
+                {
+                    return;
+                }
+                // These act on a whole server, which is yet to be picked
+                KeyCode::Char('n')
+                | KeyCode::Char('p')
+                | KeyCode::Char('m')
+                | KeyCode::Char('g')
+                | KeyCode::Char('C')
+                | KeyCode::Char('i')
+                | KeyCode::Char('I')
+                | KeyCode::Char('e')
+                | KeyCode::Char('E')
+                | KeyCode::Char(' ')
+                | KeyCode::Char('V') => {
+                    self.set_status(
+                        "Listing all servers: press s to pick one first".to_string(),
+                        StatusLevel::Warning,
+                    );
+                    return;
+                }
+                _ => {}
+            }
+        }
         match key.code {
             KeyCode::Char('j') | KeyCode::Down => self.select_next(),
             KeyCode::Char('k') | KeyCode::Up => self.select_previous(),
             KeyCode::Enter => self.enter_imposter_detail().await,
             KeyCode::Char('n') => self.show_create_imposter(),
             KeyCode::Char('p') => self.show_create_proxy_imposter(),
-            KeyCode::Char('d') => self.confirm_delete_imposter(),
+            KeyCode::Char(' ') => self.toggle_mark(),
+            KeyCode::Char('V') => self.mark_range(),
+            KeyCode::Char('d') if !self.marked_imposters.is_empty() => {
+                self.confirm_delete_marked_imposters().await
+            }
+            KeyCode::Char('d') => self.confirm_delete_imposter().await,
+            KeyCode::Char('t') if !self.marked_imposters.is_empty() => {
+                self.toggle_marked_imposters().await
+            }
             KeyCode::Char('t') => self.toggle_imposter().await,
             KeyCode::Char('m') => self.navigate(View::Metrics),
+            KeyCode::Char('o') => self.cycle_imposter_sort(),
+            KeyCode::Char('O') => self.reverse_imposter_sort(),
+            KeyCode::Char('f') => self.cycle_protocol_filter(),
+            KeyCode::Char('F') => self.cycle_enabled_filter(),
+            KeyCode::Char('l') => {
+                if let Some(port) = self.selected_imposter().map(|imp| imp.port) {
+                    self.open_request_log(port).await;
+                }
+            }
+            KeyCode::Char('g') => self.open_server_log().await,
             KeyCode::Char('C') => self.open_config_view().await,
             KeyCode::Char('i') => self.show_import_file_dialog(),
             KeyCode::Char('I') => self.show_import_folder_dialog(),
+            KeyCode::Char('e') if !self.marked_imposters.is_empty() => {
+                self.show_export_marked_dialog()
+            }
             KeyCode::Char('e') => self.show_export_all_dialog(),
             KeyCode::Char('E') => self.show_export_folder_dialog(),
+            KeyCode::Char('R') => self.show_scenario_dialog(),
+            KeyCode::Char('y') => self.show_copy_menu(),
             _ => {}
         }
     }
 
-    async fn open_config_view(&mut self) {
+    pub(super) async fn open_config_view(&mut self) {
         self.is_loading = true;
         match self.client.get_config().await {
             Ok(cfg) => {
@@ -248,22 +395,35 @@ impl App {
             KeyCode::Tab => self.toggle_focus(),
             KeyCode::Char('a') => self.start_stub_create(),
             KeyCode::Char('e') => self.start_stub_edit(),
-            KeyCode::Char('d') => self.confirm_delete_stub(),
-            KeyCode::Char('c') => self.confirm_clear_requests(),
-            KeyCode::Char('C') => self.confirm_clear_proxy_responses(),
+            KeyCode::Char(' ') => self.toggle_mark(),
+            KeyCode::Char('V') => self.mark_range(),
+            KeyCode::Char('d') if !self.marked_stubs.is_empty() => {
+                self.confirm_delete_marked_stubs().await
+            }
+            KeyCode::Char('d') => self.confirm_delete_stub().await,
+            KeyCode::Char('c') => self.confirm_clear_requests().await,
+            KeyCode::Char('C') => self.confirm_clear_proxy_responses().await,
+            KeyCode::Char('x') if !self.marked_stubs.is_empty() => self.export_marked_stubs(),
             KeyCode::Char('x') => self.export_imposter(true).await,
             KeyCode::Char('X') => self.export_imposter(false).await,
-            KeyCode::Char('A') => self.confirm_apply_recorded_stubs(),
+            KeyCode::Char('A') => self.preview_apply_recorded_stubs().await,
             KeyCode::Char('t') => self.toggle_imposter().await,
-            KeyCode::Char('y') => self.copy_stub_as_curl(),
-            KeyCode::Char('[') => self.reorder_stub(-1).await,
-            KeyCode::Char(']') => self.reorder_stub(1).await,
+            KeyCode::Char('y') => self.show_copy_menu(),
+            KeyCode::Char('K') => self.reorder_stub(-1).await,
+            KeyCode::Char('J') => self.reorder_stub(1).await,
             KeyCode::Char('D') => self.duplicate_stub().await,
+            KeyCode::Char('z') => self.reset_stub_hits(),
+            KeyCode::Char('l') => {
+                if let View::ImposterDetail { port } = self.view {
+                    self.open_request_log(port).await;
+                }
+            }
             KeyCode::Enter => {
                 if let View::ImposterDetail { port } = self.view {
                     match self.focus {
                         FocusArea::Left => {
                             if let Some(idx) = self.stub_list_state.selected() {
+                                self.stub_detail_scroll = 0;
                                 self.navigate(View::StubDetail { port, index: idx });
                             }
                         }
@@ -280,10 +440,32 @@ impl App {
     }
 
     async fn handle_stub_detail_event(&mut self, key: KeyEvent) {
+        let max_scroll = match self.view {
+            View::StubDetail { index, .. } => self
+                .current_imposter
+                .as_ref()
+                .and_then(|imp| imp.stubs.get(index))
+                .map_or(0, |stub| {
+                    crate::ui::stub_detail_line_count(stub).saturating_sub(10)
+                }),
+            _ => 0,
+        } as u16;
         match key.code {
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.stub_detail_scroll = (self.stub_detail_scroll + 1).min(max_scroll);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.stub_detail_scroll = self.stub_detail_scroll.saturating_sub(1);
+            }
+            KeyCode::PageDown => {
+                self.stub_detail_scroll = (self.stub_detail_scroll + 10).min(max_scroll);
+            }
+            KeyCode::PageUp => {
+                self.stub_detail_scroll = self.stub_detail_scroll.saturating_sub(10);
+            }
             KeyCode::Char('e') => self.start_stub_edit(),
-            KeyCode::Char('d') => self.confirm_delete_stub(),
-            KeyCode::Char('y') => self.copy_stub_as_curl(),
+            KeyCode::Char('d') => self.confirm_delete_stub().await,
+            KeyCode::Char('y') => self.show_copy_menu(),
             KeyCode::Char('D') => self.duplicate_stub().await,
             _ => {}
         }
@@ -307,6 +489,18 @@ impl App {
                     self.show_editor_validation();
                     return;
                 }
+                KeyCode::Char('t') => {
+                    if let Some(port) = self.composer_port() {
+                        self.open_composer(port);
+                    }
+                    return;
+                }
+                KeyCode::Char('e') => {
+                    if matches!(self.view, View::StubEdit { .. }) {
+                        self.open_script_editor();
+                    }
+                    return;
+                }
                 _ => {}
             }
         }
@@ -332,13 +526,7 @@ impl App {
                         if let Some(text) = self.paste_from_clipboard()
                             && let Some(editor) = &mut self.stub_editor
                         {
-                            editor.editor.set_yank_text(text.clone());
-                            editor.editor.input(ratatui_textarea::Input {
-                                key: ratatui_textarea::Key::Char('y'),
-                                ctrl: true,
-                                alt: false,
-                                shift: false,
-                            });
+                            editor.paste(&text);
                         }
                     }
                     None => {}
@@ -354,140 +542,7 @@ impl App {
 
     pub(super) async fn handle_input_event(&mut self, key: KeyEvent, action: InputAction) {
         match action {
-            InputAction::CreateImposter => self.handle_create_imposter_input(key).await,
-            InputAction::CreateProxyImposter => self.handle_create_proxy_input(key).await,
-        }
-    }
-
-    async fn handle_create_imposter_input(&mut self, key: KeyEvent) {
-        // Handle Ctrl+V paste
-        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('v') {
-            if let Some(text) = self.paste_from_clipboard() {
-                match self.input_state.focus_field {
-                    0 => {
-                        // Port: only paste digits
-                        let digits: String = text.chars().filter(|c| c.is_ascii_digit()).collect();
-                        self.input_state.port.push_str(&digits);
-                    }
-                    1 => self.input_state.name.push_str(&text),
-                    2 => self.input_state.protocol.push_str(&text),
-                    _ => {}
-                }
-            }
-            return;
-        }
-
-        match key.code {
-            KeyCode::Esc => self.overlay = Overlay::None,
-            KeyCode::Enter => self.create_imposter().await,
-            KeyCode::Tab => self.input_state.focus_field = (self.input_state.focus_field + 1) % 3,
-            KeyCode::BackTab => {
-                self.input_state.focus_field = if self.input_state.focus_field == 0 {
-                    2
-                } else {
-                    self.input_state.focus_field - 1
-                };
-            }
-            KeyCode::Backspace => match self.input_state.focus_field {
-                0 => {
-                    self.input_state.port.pop();
-                }
-                1 => {
-                    self.input_state.name.pop();
-                }
-                2 => {
-                    self.input_state.protocol.pop();
-                }
-                _ => {}
-            },
-            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
-                match self.input_state.focus_field {
-                    0 => {
-                        if c.is_ascii_digit() {
-                            self.input_state.port.push(c);
-                        }
-                    }
-                    1 => {
-                        self.input_state.name.push(c);
-                    }
-                    2 => {
-                        self.input_state.protocol.push(c);
-                    }
-                    _ => {}
-                }
-            }
-            _ => {}
-        }
-    }
-
-    async fn handle_create_proxy_input(&mut self, key: KeyEvent) {
-        // Handle Ctrl+V paste
-        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('v') {
-            if let Some(text) = self.paste_from_clipboard() {
-                match self.input_state.focus_field {
-                    0 => self.input_state.target_url.push_str(&text),
-                    1 => {
-                        // Port: only paste digits
-                        let digits: String = text.chars().filter(|c| c.is_ascii_digit()).collect();
-                        self.input_state.port.push_str(&digits);
-                    }
-                    2 => self.input_state.name.push_str(&text),
-                    _ => {}
-                }
-            }
-            return;
-        }
-
-        match key.code {
-            KeyCode::Esc => self.overlay = Overlay::None,
-            KeyCode::Enter => self.create_proxy_imposter().await,
-            KeyCode::Tab => self.input_state.focus_field = (self.input_state.focus_field + 1) % 4,
-            KeyCode::BackTab => {
-                self.input_state.focus_field = if self.input_state.focus_field == 0 {
-                    3
-                } else {
-                    self.input_state.focus_field - 1
-                };
-            }
-            KeyCode::Left if self.input_state.focus_field == 3 => {
-                self.input_state.proxy_mode = if self.input_state.proxy_mode == 0 {
-                    2
-                } else {
-                    self.input_state.proxy_mode - 1
-                };
-            }
-            KeyCode::Right if self.input_state.focus_field == 3 => {
-                self.input_state.proxy_mode = (self.input_state.proxy_mode + 1) % 3;
-            }
-            KeyCode::Backspace => match self.input_state.focus_field {
-                0 => {
-                    self.input_state.target_url.pop();
-                }
-                1 => {
-                    self.input_state.port.pop();
-                }
-                2 => {
-                    self.input_state.name.pop();
-                }
-                _ => {}
-            },
-            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
-                match self.input_state.focus_field {
-                    0 => {
-                        self.input_state.target_url.push(c);
-                    }
-                    1 => {
-                        if c.is_ascii_digit() {
-                            self.input_state.port.push(c);
-                        }
-                    }
-                    2 => {
-                        self.input_state.name.push(c);
-                    }
-                    _ => {}
-                }
-            }
-            _ => {}
+            InputAction::ReplayToPort { from } => self.handle_replay_port_input(key, from).await,
         }
     }
 
@@ -521,34 +576,32 @@ impl App {
                     FileAction::ImportFolder => self.import_from_folder(&path).await,
                     FileAction::ExportAll => self.export_all_to_file(&path).await,
                     FileAction::ExportToFolder => self.export_to_folder(&path).await,
+                    FileAction::ExportMarked { ports } => {
+                        self.export_marked_to_file(&path, &ports).await
+                    }
+                    FileAction::RunScenario => self.run_scenario_file(&path).await,
+                    FileAction::ExportMetrics => self.export_metrics(&path).await,
+                    FileAction::Screenshot => self.request_screenshot(&path),
                 }
             }
-            KeyCode::Left => {
-                if self.input_state.cursor_pos > 0 {
-                    self.input_state.cursor_pos -= 1;
-                }
+            KeyCode::Left if self.input_state.cursor_pos > 0 => {
+                self.input_state.cursor_pos -= 1;
             }
-            KeyCode::Right => {
-                if self.input_state.cursor_pos < self.input_state.file_path.len() {
-                    self.input_state.cursor_pos += 1;
-                }
+            KeyCode::Right if self.input_state.cursor_pos < self.input_state.file_path.len() => {
+                self.input_state.cursor_pos += 1;
             }
             KeyCode::Home => self.input_state.cursor_pos = 0,
             KeyCode::End => self.input_state.cursor_pos = self.input_state.file_path.len(),
-            KeyCode::Backspace => {
-                if self.input_state.cursor_pos > 0 {
-                    self.input_state.cursor_pos -= 1;
-                    self.input_state
-                        .file_path
-                        .remove(self.input_state.cursor_pos);
-                }
+            KeyCode::Backspace if self.input_state.cursor_pos > 0 => {
+                self.input_state.cursor_pos -= 1;
+                self.input_state
+                    .file_path
+                    .remove(self.input_state.cursor_pos);
             }
-            KeyCode::Delete => {
-                if self.input_state.cursor_pos < self.input_state.file_path.len() {
-                    self.input_state
-                        .file_path
-                        .remove(self.input_state.cursor_pos);
-                }
+            KeyCode::Delete if self.input_state.cursor_pos < self.input_state.file_path.len() => {
+                self.input_state
+                    .file_path
+                    .remove(self.input_state.cursor_pos);
             }
             KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.input_state
@@ -561,11 +614,7 @@ impl App {
     }
 
     /// Handle validation result overlay events
-    pub(super) async fn handle_validation_overlay_event(
-        &mut self,
-        key: KeyEvent,
-        action: ValidationAction,
-    ) {
+    pub(super) fn handle_validation_overlay_event(&mut self, key: KeyEvent) {
         if let Overlay::ValidationResult { report, .. } = &self.overlay {
             let total_issues = report.issues.len() as u16;
             match key.code {
@@ -589,15 +638,6 @@ impl App {
                     self.validation_scroll_offset =
                         (self.validation_scroll_offset + 5).min(max_scroll);
                 }
-                KeyCode::Enter => {
-                    // Only allow proceeding if action supports it
-                    if let ValidationAction::ProceedWithImport { content, .. } = &action {
-                        let content = content.clone();
-                        self.overlay = Overlay::None;
-                        self.validation_scroll_offset = 0;
-                        self.do_import(&content).await;
-                    }
-                }
                 _ => {}
             }
         }
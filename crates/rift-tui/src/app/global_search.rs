@@ -0,0 +1,309 @@
+//! Global search: `/` then `Tab` looks for text in every imposter's name, stub predicates and
+//! responses at once, and lists what it found to jump to
+
+use super::*;
+use crate::app::StubRow;
+
+/// Characters of context kept either side of a match in a hit's snippet
+const SNIPPET_CONTEXT: usize = 24;
+
+/// Where in an imposter a search hit is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitField {
+    /// The imposter's name or port
+    Imposter,
+    Scenario,
+    Predicates,
+    Responses,
+}
+
+impl HitField {
+    pub fn label(self) -> &'static str {
+        match self {
+            HitField::Imposter => "imposter",
+            HitField::Scenario => "scenario",
+            HitField::Predicates => "predicate",
+            HitField::Responses => "response",
+        }
+    }
+}
+
+/// One place the query was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub port: u16,
+    /// The server the imposter is on, in the all-servers list
+    pub server: Option<usize>,
+    pub imposter_name: Option<String>,
+    /// The stub it is in, or `None` for a match on the imposter itself
+    pub stub: Option<usize>,
+    /// The stub's label in the stub list
+    pub stub_label: String,
+    pub field: HitField,
+    /// The matched text with some context around it
+    pub snippet: String,
+}
+
+/// Results of a global search, shown as a jump list
+#[derive(Debug, Default)]
+pub struct GlobalSearch {
+    pub query: String,
+    pub hits: Vec<SearchHit>,
+    pub selected: usize,
+    /// Imposters searched, and those that could not be loaded
+    pub searched: usize,
+    pub failed: usize,
+}
+
+/// `text` around the first match of `query` (lowercase), or `None` when it doesn't contain it
+fn snippet(text: &str, query: &str) -> Option<String> {
+    let lower = text.to_lowercase();
+    let at = lower.find(query)?;
+    // Lowercasing can change byte lengths; find the match by characters in the original text
+    let chars: Vec<char> = text.chars().collect();
+    let start = lower[..at].chars().count().min(chars.len());
+    let end = (start + query.chars().count()).min(chars.len());
+    let from = start.saturating_sub(SNIPPET_CONTEXT);
+    let to = (end + SNIPPET_CONTEXT).min(chars.len());
+    let mut snippet: String = chars[from..to].iter().collect();
+    if from > 0 {
+        snippet.insert(0, '…');
+    }
+    if to < chars.len() {
+        snippet.push('…');
+    }
+    Some(snippet)
+}
+
+/// Every match of `query` (lowercase) in `detail`: its name or port, then the first match in
+/// each stub
+pub fn search_imposter(
+    detail: &ImposterDetail,
+    server: Option<usize>,
+    query: &str,
+) -> Vec<SearchHit> {
+    let hit = |stub: Option<usize>, stub_label: String, field, snippet| SearchHit {
+        port: detail.port,
+        server,
+        imposter_name: detail.name.clone(),
+        stub,
+        stub_label,
+        field,
+        snippet,
+    };
+    let mut hits = Vec::new();
+    let name = match &detail.name {
+        Some(name) => format!(":{} {name}", detail.port),
+        None => format!(":{}", detail.port),
+    };
+    if let Some(found) = snippet(&name, query) {
+        hits.push(hit(None, String::new(), HitField::Imposter, found));
+    }
+    for (index, stub) in detail.stubs.iter().enumerate() {
+        let found = stub
+            .scenario_name
+            .as_deref()
+            .and_then(|scenario| snippet(scenario, query))
+            .map(|found| (HitField::Scenario, found))
+            .or_else(|| {
+                stub.predicates
+                    .iter()
+                    .find_map(|p| snippet(&p.to_string(), query))
+                    .map(|found| (HitField::Predicates, found))
+            })
+            .or_else(|| {
+                stub.responses
+                    .iter()
+                    .find_map(|r| snippet(&r.to_string(), query))
+                    .map(|found| (HitField::Responses, found))
+            });
+        if let Some((field, found)) = found {
+            hits.push(hit(Some(index), StubRow::new(stub).label, field, found));
+        }
+    }
+    hits
+}
+
+impl App {
+    /// Search every imposter listed for `query` and show the hits
+    pub(super) async fn run_global_search(&mut self, query: String) {
+        let lower = query.to_lowercase();
+        let targets: Vec<(u16, Option<usize>)> = self
+            .imposters
+            .iter()
+            .map(|imp| (imp.port, imp.server))
+            .collect();
+        let mut search = GlobalSearch {
+            query,
+            searched: targets.len(),
+            ..Default::default()
+        };
+        self.is_loading = true;
+        for (port, server) in targets {
+            let client = match server.and_then(|index| self.servers.get(index)) {
+                Some(server) => server.client.clone(),
+                None => self.client.clone(),
+            };
+            match client.get_imposter(port).await {
+                Ok(detail) => search.hits.extend(search_imposter(&detail, server, &lower)),
+                Err(_) => search.failed += 1,
+            }
+        }
+        self.is_loading = false;
+
+        let mut message = format!(
+            "{} matches for \"{}\" in {} imposters",
+            search.hits.len(),
+            search.query,
+            search.searched
+        );
+        if search.failed > 0 {
+            message.push_str(&format!("; {} could not be loaded", search.failed));
+        }
+        let level = if search.failed > 0 {
+            StatusLevel::Warning
+        } else {
+            StatusLevel::Info
+        };
+        self.set_status(message, level);
+        self.global_search = Some(search);
+        self.overlay = Overlay::GlobalSearch;
+    }
+
+    pub(super) async fn handle_global_search_event(&mut self, key: KeyEvent) {
+        let Some(search) = self.global_search.as_mut() else {
+            self.overlay = Overlay::None;
+            return;
+        };
+        let last = search.hits.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.overlay = Overlay::None,
+            KeyCode::Down | KeyCode::Char('j') => search.selected = (search.selected + 1).min(last),
+            KeyCode::Up | KeyCode::Char('k') => search.selected = search.selected.saturating_sub(1),
+            KeyCode::PageDown => search.selected = (search.selected + 10).min(last),
+            KeyCode::PageUp => search.selected = search.selected.saturating_sub(10),
+            KeyCode::Home | KeyCode::Char('g') => search.selected = 0,
+            KeyCode::End | KeyCode::Char('G') => search.selected = last,
+            KeyCode::Enter => {
+                let Some(hit) = search.hits.get(search.selected).cloned() else {
+                    return;
+                };
+                self.overlay = Overlay::None;
+                self.jump_to_hit(&hit).await;
+            }
+            _ => {}
+        }
+    }
+
+    /// Open the imposter a hit is in, with its stub selected
+    async fn jump_to_hit(&mut self, hit: &SearchHit) {
+        let Some(index) = self
+            .imposters
+            .iter()
+            .position(|imp| imp.port == hit.port && imp.server == hit.server)
+        else {
+            self.set_status(
+                format!("Imposter :{} is gone", hit.port),
+                StatusLevel::Warning,
+            );
+            return;
+        };
+        if !self.open_imposter_at(index).await {
+            return;
+        }
+        if let Some(stub) = hit.stub {
+            let stubs = self
+                .current_imposter
+                .as_ref()
+                .map_or(0, |imp| imp.stubs.len());
+            if stub < stubs {
+                self.stub_list_state.select(Some(stub));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::{make_imposter, make_test_app};
+    use serde_json::json;
+
+    fn detail() -> ImposterDetail {
+        serde_json::from_value(json!({
+            "port": 4545,
+            "protocol": "http",
+            "name": "payments",
+            "stubs": [
+                { "predicates": [{ "equals": { "path": "/v1/orders" } }],
+                  "responses": [{ "is": { "body": "none" } }] },
+                { "predicates": [{ "equals": { "method": "POST", "path": "/v2/payments" } }],
+                  "responses": [{ "is": { "statusCode": 201 } }] },
+                { "scenarioName": "refunds",
+                  "responses": [{ "is": { "body": "see /V2/Payments/refund" } }] }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn finds_the_query_in_names_predicates_and_responses() {
+        let hits = search_imposter(&detail(), None, "/v2/payments");
+        let found: Vec<_> = hits.iter().map(|hit| (hit.stub, hit.field)).collect();
+        assert_eq!(
+            found,
+            [
+                (Some(1), HitField::Predicates),
+                (Some(2), HitField::Responses)
+            ]
+        );
+        assert_eq!(hits[0].stub_label, "POST /v2/payments");
+        assert!(
+            hits[1].snippet.contains("/V2/Payments"),
+            "{}",
+            hits[1].snippet
+        );
+
+        let hits = search_imposter(&detail(), Some(1), "paym");
+        assert_eq!(hits[0].field, HitField::Imposter);
+        assert_eq!(hits[0].snippet, ":4545 payments");
+        assert_eq!(hits[0].server, Some(1));
+    }
+
+    #[test]
+    fn snippets_keep_some_context_around_the_match() {
+        let text = format!("{}needle{}", "a".repeat(40), "b".repeat(40));
+        let found = snippet(&text, "needle").unwrap();
+        assert_eq!(
+            found,
+            format!("…{}needle{}…", "a".repeat(24), "b".repeat(24))
+        );
+        assert_eq!(snippet("ÄÖ needle", "needle").unwrap(), "ÄÖ needle");
+        assert!(snippet("haystack", "needle").is_none());
+    }
+
+    #[tokio::test]
+    async fn tab_makes_the_search_global() {
+        let mut app = make_test_app();
+        app.client = ApiClient::new("http://127.0.0.1:9");
+        app.imposters = vec![make_imposter(4545, None, "http")];
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('/')))
+            .await;
+        app.handle_key_event(KeyEvent::from(KeyCode::Tab)).await;
+        assert!(app.search_global);
+        for c in "orders".chars() {
+            app.handle_key_event(KeyEvent::from(KeyCode::Char(c))).await;
+        }
+        app.handle_key_event(KeyEvent::from(KeyCode::Enter)).await;
+
+        assert!(!app.search_active && !app.search_global);
+        assert!(app.search_query.is_empty(), "the list isn't filtered by it");
+        assert_eq!(app.overlay, Overlay::GlobalSearch);
+        let search = app.global_search.as_ref().unwrap();
+        assert_eq!((search.searched, search.failed), (1, 1));
+        assert_eq!(
+            app.status_message.as_ref().unwrap().0,
+            "0 matches for \"orders\" in 1 imposters; 1 could not be loaded"
+        );
+    }
+}
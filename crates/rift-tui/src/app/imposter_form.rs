@@ -0,0 +1,460 @@
+//! The new imposter form: port, protocol, name, `recordRequests` and a default response, each
+//! field checked as it is typed, with `Ctrl+E` handing the JSON to the editor for anything more
+
+use super::*;
+use crate::api::CreateImposterRequest;
+
+/// Protocols an imposter can be created with
+pub const IMPOSTER_PROTOCOLS: [&str; 2] = ["http", "https"];
+
+/// A field of the new imposter form, in the order focus moves through them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormField {
+    Port,
+    Protocol,
+    Name,
+    RecordRequests,
+    /// Status code of the default response
+    DefaultStatus,
+    /// Body of the default response
+    DefaultBody,
+}
+
+impl FormField {
+    pub const ALL: [FormField; 6] = [
+        FormField::Port,
+        FormField::Protocol,
+        FormField::Name,
+        FormField::RecordRequests,
+        FormField::DefaultStatus,
+        FormField::DefaultBody,
+    ];
+
+    /// Whether the field is typed into, rather than toggled
+    pub fn is_text(self) -> bool {
+        matches!(
+            self,
+            FormField::Port | FormField::Name | FormField::DefaultStatus | FormField::DefaultBody
+        )
+    }
+
+    fn digits_only(self) -> bool {
+        matches!(self, FormField::Port | FormField::DefaultStatus)
+    }
+}
+
+/// State of the new imposter form
+#[derive(Debug, Clone, Default)]
+pub struct ImposterForm {
+    pub port: String,
+    /// Index into [`IMPOSTER_PROTOCOLS`]
+    pub protocol: usize,
+    pub name: String,
+    pub record_requests: bool,
+    pub default_status: String,
+    pub default_body: String,
+    pub focus: usize,
+    /// Ports of the imposters listed when the form opened, which a new one cannot take
+    pub taken_ports: Vec<u16>,
+}
+
+impl ImposterForm {
+    pub fn focused(&self) -> FormField {
+        FormField::ALL[self.focus.min(FormField::ALL.len() - 1)]
+    }
+
+    pub fn protocol(&self) -> &'static str {
+        IMPOSTER_PROTOCOLS
+            .get(self.protocol)
+            .copied()
+            .unwrap_or("http")
+    }
+
+    /// What is wrong with `field` as filled in, if anything
+    pub fn error(&self, field: FormField) -> Option<String> {
+        match field {
+            FormField::Port if !self.port.is_empty() => match self.port.parse::<u16>() {
+                Ok(0) | Err(_) => Some("Port must be between 1 and 65535".to_string()),
+                Ok(port) if self.taken_ports.contains(&port) => {
+                    Some(format!("Port {port} is already used by an imposter"))
+                }
+                Ok(_) => None,
+            },
+            FormField::DefaultStatus if !self.default_status.is_empty() => {
+                match self.default_status.parse::<u16>() {
+                    Ok(100..=599) => None,
+                    _ => Some("Status code must be between 100 and 599".to_string()),
+                }
+            }
+            FormField::DefaultBody if looks_like_json(&self.default_body) => {
+                serde_json::from_str::<serde_json::Value>(&self.default_body)
+                    .err()
+                    .map(|e| format!("Not valid JSON: {e}"))
+            }
+            _ => None,
+        }
+    }
+
+    /// The first field that is not filled in right, with what is wrong with it
+    pub fn first_error(&self) -> Option<(usize, String)> {
+        FormField::ALL
+            .iter()
+            .enumerate()
+            .find_map(|(index, &field)| self.error(field).map(|e| (index, e)))
+    }
+
+    /// The imposter to create, or the first field keeping the form from describing one
+    pub fn to_request(&self) -> Result<CreateImposterRequest, (usize, String)> {
+        match self.first_error() {
+            Some(error) => Err(error),
+            None => Ok(self.draft()),
+        }
+    }
+
+    /// The imposter as far as it is filled in right, leaving out fields that are not
+    pub fn draft(&self) -> CreateImposterRequest {
+        let valid = |field| self.error(field).is_none();
+        let default_response = (!self.default_status.is_empty() || !self.default_body.is_empty())
+            .then(|| {
+                let mut is = serde_json::Map::new();
+                let status = self.default_status.parse::<u16>().ok();
+                is.insert(
+                    "statusCode".to_string(),
+                    status
+                        .filter(|_| valid(FormField::DefaultStatus))
+                        .unwrap_or(200)
+                        .into(),
+                );
+                if !self.default_body.is_empty() {
+                    let body = serde_json::from_str(&self.default_body)
+                        .ok()
+                        .filter(|_| looks_like_json(&self.default_body))
+                        .unwrap_or_else(|| self.default_body.clone().into());
+                    is.insert("body".to_string(), body);
+                }
+                serde_json::Value::Object(is)
+            });
+        CreateImposterRequest {
+            port: self.port.parse().ok().filter(|_| valid(FormField::Port)),
+            protocol: self.protocol().to_string(),
+            name: Some(self.name.trim())
+                .filter(|name| !name.is_empty())
+                .map(str::to_string),
+            record_requests: self.record_requests,
+            stubs: Vec::new(),
+            default_response,
+        }
+    }
+
+    fn move_focus(&mut self, forward: bool) {
+        let count = FormField::ALL.len();
+        self.focus = (self.focus + if forward { 1 } else { count - 1 }) % count;
+    }
+
+    /// Flip the focused toggle: the protocol or `recordRequests`
+    fn toggle(&mut self) {
+        match self.focused() {
+            FormField::Protocol => self.protocol = (self.protocol + 1) % IMPOSTER_PROTOCOLS.len(),
+            FormField::RecordRequests => self.record_requests = !self.record_requests,
+            _ => {}
+        }
+    }
+
+    /// The text field with focus, if any
+    fn text_field(&mut self) -> Option<&mut String> {
+        match self.focused() {
+            FormField::Port => Some(&mut self.port),
+            FormField::Name => Some(&mut self.name),
+            FormField::DefaultStatus => Some(&mut self.default_status),
+            FormField::DefaultBody => Some(&mut self.default_body),
+            FormField::Protocol | FormField::RecordRequests => None,
+        }
+    }
+}
+
+/// A body meant as JSON rather than text
+fn looks_like_json(body: &str) -> bool {
+    let body = body.trim_start();
+    body.starts_with('{') || body.starts_with('[')
+}
+
+impl App {
+    /// Open the new imposter form
+    pub fn show_create_imposter(&mut self) {
+        self.imposter_form = Some(ImposterForm {
+            // With every server listed, which one the imposter goes to decides what is taken;
+            // the server says so instead
+            taken_ports: self
+                .imposters
+                .iter()
+                .filter(|imp| imp.server.is_none())
+                .map(|imp| imp.port)
+                .collect(),
+            ..Default::default()
+        });
+        self.overlay = Overlay::ImposterForm;
+    }
+
+    /// Create the imposter the form describes
+    pub async fn create_imposter(&mut self) {
+        let Some(form) = self.imposter_form.as_mut() else {
+            return;
+        };
+        let request = match form.to_request() {
+            Ok(request) => request,
+            Err((field, e)) => {
+                form.focus = field;
+                self.set_status(e, StatusLevel::Error);
+                return;
+            }
+        };
+
+        self.is_loading = true;
+        match self.client.create_imposter(request).await {
+            Ok(port) => {
+                self.set_status(format!("Created imposter :{port}"), StatusLevel::Success);
+                self.overlay = Overlay::None;
+                self.imposter_form = None;
+                self.refresh().await;
+            }
+            Err(e) => {
+                self.set_status(format!("Failed to create: {e}"), StatusLevel::Error);
+            }
+        }
+        self.is_loading = false;
+    }
+
+    /// Carry on in the JSON editor with what the form has so far
+    fn edit_imposter_json(&mut self) {
+        let Some(form) = self.imposter_form.take() else {
+            return;
+        };
+        let mut config = serde_json::to_value(form.draft()).unwrap_or_default();
+        // Somewhere to start writing stubs
+        if let Some(config) = config.as_object_mut() {
+            config
+                .entry("stubs")
+                .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+        }
+        let json = serde_json::to_string_pretty(&config).unwrap_or_default();
+        self.overlay = Overlay::None;
+        self.stub_editor = Some(StubEditor::for_new_imposter(&json));
+        self.navigate(View::ImposterEdit);
+    }
+
+    /// Create the imposter written in the JSON editor
+    pub(super) async fn create_imposter_from_editor(&mut self, content: &str) {
+        let config: serde_json::Value = match serde_json::from_str(content) {
+            Ok(config) => config,
+            Err(e) => {
+                self.set_status(format!("JSON error: {e}"), StatusLevel::Error);
+                return;
+            }
+        };
+        self.is_loading = true;
+        match self.client.create_imposter_from(&config).await {
+            Ok(port) => {
+                self.set_status(format!("Created imposter :{port}"), StatusLevel::Success);
+                self.stub_editor = None;
+                self.go_back();
+                self.refresh().await;
+            }
+            Err(e) => {
+                self.set_status(format!("Failed to create: {e}"), StatusLevel::Error);
+            }
+        }
+        self.is_loading = false;
+    }
+
+    pub(super) async fn handle_imposter_form_event(&mut self, key: KeyEvent) {
+        let Some(form) = self.imposter_form.as_mut() else {
+            self.overlay = Overlay::None;
+            return;
+        };
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        if ctrl {
+            match key.code {
+                KeyCode::Char('v') => {
+                    if let Some(text) = self.paste_from_clipboard()
+                        && let Some(form) = self.imposter_form.as_mut()
+                    {
+                        let digits = form.focused().digits_only();
+                        if let Some(field) = form.text_field() {
+                            field.extend(text.chars().filter(|c| !digits || c.is_ascii_digit()));
+                        }
+                    }
+                }
+                KeyCode::Char('e') => self.edit_imposter_json(),
+                _ => {}
+            }
+            return;
+        }
+        match key.code {
+            KeyCode::Esc => {
+                self.overlay = Overlay::None;
+                self.imposter_form = None;
+            }
+            KeyCode::Enter => self.create_imposter().await,
+            KeyCode::Down | KeyCode::Tab => form.move_focus(true),
+            KeyCode::Up | KeyCode::BackTab => form.move_focus(false),
+            KeyCode::Left | KeyCode::Right => form.toggle(),
+            KeyCode::Backspace => {
+                if let Some(field) = form.text_field() {
+                    field.pop();
+                }
+            }
+            KeyCode::Char(c) => {
+                let digits = form.focused().digits_only();
+                match form.text_field() {
+                    Some(field) if !digits || c.is_ascii_digit() => field.push(c),
+                    Some(_) => {}
+                    None => match c {
+                        ' ' => form.toggle(),
+                        'j' => form.move_focus(true),
+                        'k' => form.move_focus(false),
+                        _ => {}
+                    },
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::{make_imposter, make_test_app};
+    use serde_json::json;
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::from(code)
+    }
+
+    async fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            app.handle_imposter_form_event(press(KeyCode::Char(c)))
+                .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn the_form_builds_the_imposter_field_by_field() {
+        let mut app = make_test_app();
+        app.show_create_imposter();
+        assert_eq!(app.overlay, Overlay::ImposterForm);
+
+        type_text(&mut app, "45x45").await;
+        app.handle_imposter_form_event(press(KeyCode::Tab)).await;
+        type_text(&mut app, " ").await;
+        app.handle_imposter_form_event(press(KeyCode::Tab)).await;
+        type_text(&mut app, "orders").await;
+        app.handle_imposter_form_event(press(KeyCode::Tab)).await;
+        type_text(&mut app, " ").await;
+        app.handle_imposter_form_event(press(KeyCode::Tab)).await;
+        type_text(&mut app, "404").await;
+        app.handle_imposter_form_event(press(KeyCode::Tab)).await;
+        type_text(&mut app, r#"{"error":"no such order"}"#).await;
+
+        let request = app.imposter_form.as_ref().unwrap().to_request().unwrap();
+        assert_eq!(
+            serde_json::to_value(request).unwrap(),
+            json!({
+                "port": 4545,
+                "protocol": "https",
+                "name": "orders",
+                "recordRequests": true,
+                "defaultResponse": { "statusCode": 404, "body": { "error": "no such order" } }
+            })
+        );
+    }
+
+    #[test]
+    fn each_field_says_what_is_wrong_with_it() {
+        let form = ImposterForm {
+            port: "70000".to_string(),
+            default_status: "42".to_string(),
+            default_body: "{\"open\": ".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            form.error(FormField::Port).unwrap(),
+            "Port must be between 1 and 65535"
+        );
+        assert_eq!(
+            form.error(FormField::DefaultStatus).unwrap(),
+            "Status code must be between 100 and 599"
+        );
+        assert!(
+            form.error(FormField::DefaultBody)
+                .unwrap()
+                .starts_with("Not valid JSON")
+        );
+        assert_eq!(form.first_error().unwrap().0, 0);
+
+        let form = ImposterForm {
+            port: "4545".to_string(),
+            taken_ports: vec![4545],
+            default_body: "plain text".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            form.error(FormField::Port).unwrap(),
+            "Port 4545 is already used by an imposter"
+        );
+        assert!(form.error(FormField::DefaultBody).is_none());
+        // The draft leaves the bad port out and gives the text body a 200
+        assert_eq!(
+            serde_json::to_value(form.draft()).unwrap(),
+            json!({
+                "protocol": "http",
+                "defaultResponse": { "statusCode": 200, "body": "plain text" }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn enter_refuses_a_bad_form_and_focuses_the_field() {
+        let mut app = make_test_app();
+        app.imposters = vec![make_imposter(4545, None, "http")];
+        app.show_create_imposter();
+        type_text(&mut app, "4545").await;
+        app.handle_imposter_form_event(press(KeyCode::Down)).await;
+        app.handle_imposter_form_event(press(KeyCode::Enter)).await;
+
+        assert_eq!(app.overlay, Overlay::ImposterForm);
+        assert_eq!(app.imposter_form.as_ref().unwrap().focus, 0);
+        assert_eq!(
+            app.status_message.as_ref().unwrap().0,
+            "Port 4545 is already used by an imposter"
+        );
+    }
+
+    #[tokio::test]
+    async fn ctrl_e_carries_the_form_into_the_json_editor() {
+        let mut app = make_test_app();
+        app.show_create_imposter();
+        type_text(&mut app, "4600").await;
+        app.handle_imposter_form_event(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL))
+            .await;
+
+        assert_eq!(app.overlay, Overlay::None);
+        assert!(app.imposter_form.is_none());
+        assert_eq!(app.view, View::ImposterEdit);
+        let editor = app.stub_editor.as_ref().unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&editor.editor.lines().join("\n")).unwrap();
+        assert_eq!(
+            json,
+            json!({ "port": 4600, "protocol": "http", "stubs": [] })
+        );
+        assert!(
+            editor.validation_error.is_none(),
+            "linted as an imposter: {:?}",
+            editor.validation_error
+        );
+
+        app.handle_key_event(press(KeyCode::Esc)).await;
+        assert_eq!(app.view, View::ImposterList);
+    }
+}
@@ -0,0 +1,458 @@
+//! Order and filters of the imposter list, and when each imposter last served a request
+
+use super::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// What the imposter list is ordered by
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    #[default]
+    Port,
+    Name,
+    Stubs,
+    Requests,
+    /// When the imposter last served a request, as seen by the refreshes of this session
+    Activity,
+}
+
+impl SortKey {
+    const ALL: [SortKey; 5] = [
+        SortKey::Port,
+        SortKey::Name,
+        SortKey::Stubs,
+        SortKey::Requests,
+        SortKey::Activity,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortKey::Port => "port",
+            SortKey::Name => "name",
+            SortKey::Stubs => "stubs",
+            SortKey::Requests => "requests",
+            SortKey::Activity => "last activity",
+        }
+    }
+
+    fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&key| key == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// The imposter list's order: port and name ascending, counts and activity most first, unless
+/// reversed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImposterSort {
+    pub by: SortKey,
+    #[serde(default)]
+    pub reversed: bool,
+}
+
+impl ImposterSort {
+    fn compare(
+        &self,
+        a: &ImposterSummary,
+        b: &ImposterSummary,
+        activity: &HashMap<ImposterKey, Activity>,
+    ) -> Ordering {
+        let last = |imp: &ImposterSummary| activity.get(&(imp.server, imp.port)).and_then(|a| a.at);
+        let order = match self.by {
+            SortKey::Port => a.port.cmp(&b.port),
+            SortKey::Name => {
+                // Unnamed imposters go after the named ones
+                let name = |imp: &ImposterSummary| imp.name.as_ref().map(|n| n.to_lowercase());
+                match (name(a), name(b)) {
+                    (Some(x), Some(y)) => x.cmp(&y),
+                    (x, y) => x.is_none().cmp(&y.is_none()),
+                }
+            }
+            SortKey::Stubs => b.stub_count.cmp(&a.stub_count),
+            SortKey::Requests => b.number_of_requests.cmp(&a.number_of_requests),
+            // `None`, never active, sorts before `Some`, so this puts it last
+            SortKey::Activity => last(b).cmp(&last(a)),
+        };
+        let order = if self.reversed {
+            order.reverse()
+        } else {
+            order
+        };
+        order
+            .then(a.server.cmp(&b.server))
+            .then(a.port.cmp(&b.port))
+    }
+
+    /// `by requests ↓`, for the list title
+    pub fn describe(&self) -> String {
+        let ascending = matches!(self.by, SortKey::Port | SortKey::Name) != self.reversed;
+        let arrow = if ascending { "↑" } else { "↓" };
+        format!("by {} {arrow}", self.by.label())
+    }
+}
+
+/// Which imposters the list shows, on top of the search
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImposterFilter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    /// `Some(true)` for enabled imposters only, `Some(false)` for disabled ones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+}
+
+impl ImposterFilter {
+    pub fn is_active(&self) -> bool {
+        self.protocol.is_some() || self.enabled.is_some()
+    }
+
+    pub fn matches(&self, imp: &ImposterSummary) -> bool {
+        self.protocol
+            .as_ref()
+            .is_none_or(|protocol| imp.protocol.eq_ignore_ascii_case(protocol))
+            && self.enabled.is_none_or(|enabled| imp.enabled == enabled)
+    }
+
+    /// `http, enabled`, for the list title
+    pub fn describe(&self) -> String {
+        let enabled = self
+            .enabled
+            .map(|enabled| if enabled { "enabled" } else { "disabled" });
+        self.protocol
+            .as_deref()
+            .into_iter()
+            .chain(enabled)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// An imposter across refreshes: its server when listing every server's, and its port
+pub type ImposterKey = (Option<usize>, u16);
+
+/// Refreshes of traffic kept for an imposter's sparkline
+pub const ACTIVITY_HISTORY: usize = 12;
+
+/// Requests in one refresh that make an imposter hot
+pub const HOT_REQUESTS: u64 = 10;
+
+/// The request count an imposter had at the last refresh, when it last went up, and how much it
+/// went up by at each of the last refreshes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Activity {
+    pub requests: u64,
+    /// `None` until a refresh sees the count go up
+    pub at: Option<Instant>,
+    /// Requests served between refreshes, oldest first, at most `ACTIVITY_HISTORY`
+    pub recent: VecDeque<u64>,
+}
+
+impl Activity {
+    /// Requests served since the refresh before the last
+    pub fn last_window(&self) -> u64 {
+        self.recent.back().copied().unwrap_or(0)
+    }
+
+    /// Whether the last refresh saw it serve `HOT_REQUESTS` or more
+    pub fn is_hot(&self) -> bool {
+        self.last_window() >= HOT_REQUESTS
+    }
+}
+
+impl App {
+    /// Show the imposters just listed, sorted, keeping the cursor on the imposter it was on
+    pub(super) fn set_imposters(&mut self, imposters: Vec<ImposterSummary>) {
+        let selected = self.selected_imposter_key();
+        self.imposters = imposters;
+        self.marked_imposters
+            .retain(|port| self.imposters.iter().any(|imp| imp.port == *port));
+        self.note_activity(Instant::now());
+        self.sort_imposters();
+        self.reselect_imposter(selected);
+    }
+
+    fn selected_imposter_key(&self) -> Option<ImposterKey> {
+        self.selected_imposter().map(|imp| (imp.server, imp.port))
+    }
+
+    /// Put the cursor back on the imposter it was on, wherever sorting moved it
+    fn reselect_imposter(&mut self, key: Option<ImposterKey>) {
+        if let Some(index) = self
+            .imposters
+            .iter()
+            .position(|imp| Some((imp.server, imp.port)) == key)
+        {
+            self.imposter_list_state.select(Some(index));
+        }
+    }
+
+    /// Record which imposters served requests since the last refresh
+    fn note_activity(&mut self, now: Instant) {
+        let mut activity = HashMap::new();
+        for imp in &self.imposters {
+            let key = (imp.server, imp.port);
+            let Some(mut seen) = self.imposter_activity.remove(&key) else {
+                activity.insert(
+                    key,
+                    Activity {
+                        requests: imp.number_of_requests,
+                        at: None,
+                        recent: VecDeque::new(),
+                    },
+                );
+                continue;
+            };
+            // A count that went down was cleared, and is no traffic
+            let served = imp.number_of_requests.saturating_sub(seen.requests);
+            if served > 0 {
+                seen.at = Some(now);
+            }
+            if seen.recent.len() == ACTIVITY_HISTORY {
+                seen.recent.pop_front();
+            }
+            seen.recent.push_back(served);
+            seen.requests = imp.number_of_requests;
+            activity.insert(key, seen);
+        }
+        self.imposter_activity = activity;
+    }
+
+    /// When imposter `imp` last served a request, if a refresh has seen it do so
+    pub fn last_activity(&self, imp: &ImposterSummary) -> Option<Instant> {
+        self.activity(imp).and_then(|activity| activity.at)
+    }
+
+    /// The traffic refreshes have seen imposter `imp` serve
+    pub fn activity(&self, imp: &ImposterSummary) -> Option<&Activity> {
+        self.imposter_activity.get(&(imp.server, imp.port))
+    }
+
+    /// Put the list in the chosen order
+    pub fn sort_imposters(&mut self) {
+        let sort = self.imposter_sort;
+        let activity = &self.imposter_activity;
+        self.imposters.sort_by(|a, b| sort.compare(a, b, activity));
+    }
+
+    /// Sort by `sort`, keeping the cursor on the imposter it was on, and remember it
+    fn set_imposter_sort(&mut self, sort: ImposterSort) {
+        self.imposter_sort = sort;
+        let selected = self.selected_imposter_key();
+        self.sort_imposters();
+        self.reselect_imposter(selected);
+        let saved = match &self.state_path {
+            Some(path) => crate::state::State::update(path, |state| state.sort = Some(sort)),
+            None => Ok(()),
+        };
+        let message = format!("Sorted {}", sort.describe());
+        match saved {
+            Ok(()) => self.set_status(message, StatusLevel::Info),
+            Err(e) => self.set_status(
+                format!("{message} (not remembered: {e})"),
+                StatusLevel::Warning,
+            ),
+        }
+    }
+
+    /// `o`: sort by the next column
+    pub fn cycle_imposter_sort(&mut self) {
+        self.set_imposter_sort(ImposterSort {
+            by: self.imposter_sort.by.next(),
+            reversed: false,
+        });
+    }
+
+    /// `O`: reverse the order
+    pub fn reverse_imposter_sort(&mut self) {
+        self.set_imposter_sort(ImposterSort {
+            reversed: !self.imposter_sort.reversed,
+            ..self.imposter_sort
+        });
+    }
+
+    /// `f`: show only the next protocol in the list, then every protocol again
+    pub fn cycle_protocol_filter(&mut self) {
+        let mut protocols: Vec<String> = self
+            .imposters
+            .iter()
+            .map(|imp| imp.protocol.to_lowercase())
+            .collect();
+        protocols.sort();
+        protocols.dedup();
+        let next = match &self.imposter_filter.protocol {
+            None => protocols.first().cloned(),
+            Some(current) => protocols
+                .iter()
+                .position(|protocol| protocol == current)
+                .and_then(|index| protocols.get(index + 1))
+                .cloned(),
+        };
+        self.imposter_filter.protocol = next;
+        self.after_filter_change();
+    }
+
+    /// `F`: show only enabled imposters, then only disabled ones, then all
+    pub fn cycle_enabled_filter(&mut self) {
+        self.imposter_filter.enabled = match self.imposter_filter.enabled {
+            None => Some(true),
+            Some(true) => Some(false),
+            Some(false) => None,
+        };
+        self.after_filter_change();
+    }
+
+    /// Move the cursor onto an imposter the filters show, and say what they are
+    fn after_filter_change(&mut self) {
+        let on_shown = self
+            .selected_imposter()
+            .is_some_and(|imp| self.imposter_matches_search(imp));
+        if !on_shown
+            && let Some(index) = self
+                .imposters
+                .iter()
+                .position(|imp| self.imposter_matches_search(imp))
+        {
+            self.imposter_list_state.select(Some(index));
+        }
+        let message = if self.imposter_filter.is_active() {
+            format!("Showing {}", self.imposter_filter.describe())
+        } else {
+            "Showing all imposters".to_string()
+        };
+        self.set_status(message, StatusLevel::Info);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::{make_imposter, make_test_app};
+
+    fn press(c: char) -> KeyEvent {
+        KeyEvent::from(KeyCode::Char(c))
+    }
+
+    fn ports(app: &App) -> Vec<u16> {
+        app.imposters.iter().map(|imp| imp.port).collect()
+    }
+
+    #[tokio::test]
+    async fn o_cycles_the_sort_column_and_keeps_the_cursor_on_its_imposter() {
+        let mut app = make_test_app();
+        let mut imposters = vec![
+            make_imposter(4547, Some("beta"), "http"),
+            make_imposter(4545, None, "http"),
+            make_imposter(4546, Some("Alpha"), "https"),
+        ];
+        imposters[0].number_of_requests = 5;
+        imposters[1].number_of_requests = 9;
+        imposters[2].stub_count = 3;
+        app.set_imposters(imposters);
+        assert_eq!(ports(&app), [4545, 4546, 4547]);
+        app.imposter_list_state.select(Some(0));
+
+        app.handle_key_event(press('o')).await;
+        assert_eq!(ports(&app), [4546, 4547, 4545], "named first, A to Z");
+        assert_eq!(app.selected_imposter().unwrap().port, 4545);
+        app.handle_key_event(press('o')).await;
+        assert_eq!(ports(&app)[0], 4546, "most stubs first");
+        app.handle_key_event(press('o')).await;
+        assert_eq!(ports(&app), [4545, 4547, 4546], "most requests first");
+        app.handle_key_event(press('O')).await;
+        assert_eq!(ports(&app), [4546, 4547, 4545]);
+        assert_eq!(app.imposter_sort.describe(), "by requests ↑");
+    }
+
+    #[test]
+    fn activity_is_when_a_refresh_sees_the_request_count_go_up() {
+        let mut app = make_test_app();
+        app.imposter_sort = ImposterSort {
+            by: SortKey::Activity,
+            reversed: false,
+        };
+        let listed = |counts: [u64; 3]| {
+            (4545..)
+                .zip(counts)
+                .map(|(port, count)| {
+                    let mut imp = make_imposter(port, None, "http");
+                    imp.number_of_requests = count;
+                    imp
+                })
+                .collect::<Vec<_>>()
+        };
+        app.set_imposters(listed([1, 1, 1]));
+        assert_eq!(ports(&app), [4545, 4546, 4547], "nothing seen active yet");
+        assert_eq!(app.last_activity(&app.imposters[0]), None);
+
+        app.set_imposters(listed([1, 1, 4]));
+        std::thread::sleep(Duration::from_millis(5));
+        app.set_imposters(listed([1, 2, 4]));
+        assert_eq!(ports(&app), [4546, 4547, 4545]);
+    }
+
+    #[test]
+    fn activity_keeps_the_requests_of_each_refresh() {
+        let mut app = make_test_app();
+        let listed = |count: u64| {
+            let mut imp = make_imposter(4545, None, "http");
+            imp.number_of_requests = count;
+            vec![imp]
+        };
+        app.set_imposters(listed(5));
+        let activity = app.activity(&app.imposters[0]).unwrap();
+        assert!(activity.recent.is_empty(), "a first sighting is no traffic");
+        assert!(!activity.is_hot());
+
+        for count in [7, 7, 20] {
+            app.set_imposters(listed(count));
+        }
+        let activity = app.activity(&app.imposters[0]).unwrap();
+        assert_eq!(Vec::from(activity.recent.clone()), [2, 0, 13]);
+        assert_eq!(activity.last_window(), 13);
+        assert!(activity.is_hot());
+
+        // Clearing the requests brings the count down, which is no traffic either
+        app.set_imposters(listed(0));
+        for count in 1..=ACTIVITY_HISTORY as u64 {
+            app.set_imposters(listed(count));
+        }
+        let activity = app.activity(&app.imposters[0]).unwrap();
+        assert_eq!(activity.recent.len(), ACTIVITY_HISTORY);
+        assert!(activity.recent.iter().all(|served| *served == 1));
+        assert!(!activity.is_hot());
+    }
+
+    #[tokio::test]
+    async fn f_and_shift_f_filter_by_protocol_and_enabled_state() {
+        let mut app = make_test_app();
+        let mut imposters = vec![
+            make_imposter(4545, None, "http"),
+            make_imposter(4546, None, "https"),
+            make_imposter(4547, None, "http"),
+        ];
+        imposters[2].enabled = false;
+        app.set_imposters(imposters);
+        app.imposter_list_state.select(Some(1));
+
+        app.handle_key_event(press('f')).await;
+        assert_eq!(app.imposter_filter.protocol.as_deref(), Some("http"));
+        assert_eq!(
+            app.selected_imposter().unwrap().port,
+            4545,
+            "the cursor leaves the https imposter the filter hides"
+        );
+        app.handle_key_event(press('F')).await;
+        assert_eq!(app.imposter_filter.describe(), "http, enabled");
+        app.handle_key_event(press('j')).await;
+        assert_eq!(
+            app.selected_imposter().unwrap().port,
+            4545,
+            "nothing else is shown to move to"
+        );
+
+        app.handle_key_event(press('f')).await;
+        app.handle_key_event(press('f')).await;
+        assert_eq!(app.imposter_filter.protocol, None, "back to every protocol");
+    }
+}
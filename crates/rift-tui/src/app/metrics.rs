@@ -0,0 +1,511 @@
+//! Metrics dashboard: per-imposter latency, status, fault and stub-hit figures over a time window
+//!
+//! The admin `/metrics` counters run from when each imposter started, so a window's figures are
+//! the difference between the latest snapshot and the oldest one still inside the window.
+
+use super::*;
+use std::collections::BTreeMap;
+
+/// How long metrics snapshots are kept: the longest window
+pub const METRICS_RETENTION: Duration = Duration::from_secs(15 * 60);
+
+/// Most snapshots kept, whatever the refresh interval
+const MAX_METRICS_SNAPSHOTS: usize = 2000;
+
+/// The span of traffic the dashboard reports on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MetricsWindow {
+    OneMinute,
+    #[default]
+    FiveMinutes,
+    FifteenMinutes,
+    /// Everything since each imposter started
+    All,
+}
+
+impl MetricsWindow {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::OneMinute => "1m",
+            Self::FiveMinutes => "5m",
+            Self::FifteenMinutes => "15m",
+            Self::All => "all",
+        }
+    }
+
+    pub fn duration(self) -> Option<Duration> {
+        match self {
+            Self::OneMinute => Some(Duration::from_secs(60)),
+            Self::FiveMinutes => Some(Duration::from_secs(5 * 60)),
+            Self::FifteenMinutes => Some(METRICS_RETENTION),
+            Self::All => None,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::OneMinute => Self::FiveMinutes,
+            Self::FiveMinutes => Self::FifteenMinutes,
+            Self::FifteenMinutes => Self::All,
+            Self::All => Self::OneMinute,
+        }
+    }
+}
+
+/// One imposter's traffic over a window
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindowStats {
+    pub requests: u64,
+    /// Seconds the window actually covers; `None` for [`MetricsWindow::All`]
+    pub seconds: Option<f64>,
+    /// Cumulative latency histogram over the window, as `/metrics` reports it
+    pub latency_buckets: Vec<(f64, u64)>,
+    pub latency_sum_ms: u64,
+    pub status_codes: BTreeMap<u16, u64>,
+    pub faults: BTreeMap<String, u64>,
+    pub stub_hits: BTreeMap<usize, (Option<String>, u64)>,
+    pub unmatched: u64,
+}
+
+/// A stub's count among `hits`: found by its id wherever it was, since the server's counts follow a
+/// stub that moves, or at `index` for a stub without one.
+pub(super) fn hits_of(
+    hits: &BTreeMap<usize, (Option<String>, u64)>,
+    index: usize,
+    id: Option<&str>,
+) -> Option<u64> {
+    match id {
+        Some(id) => hits.values().find(|hit| hit.0.as_deref() == Some(id)),
+        None => hits.get(&index).filter(|hit| hit.0.is_none()),
+    }
+    .map(|hit| hit.1)
+}
+
+impl WindowStats {
+    /// The counters of `new` less those of `old`. A counter lower than before means the imposter
+    /// was recreated, and then everything in `new` is new.
+    fn between(new: &ImposterMetrics, old: Option<&ImposterMetrics>) -> Self {
+        let old = old.filter(|old| old.served_count <= new.served_count);
+        let before = |count: Option<u64>| count.unwrap_or(0);
+        let since = |now: u64, then: Option<u64>| now.saturating_sub(before(then));
+        Self {
+            requests: since(new.served_count, old.map(|o| o.served_count)),
+            seconds: None,
+            latency_buckets: new
+                .latency_buckets
+                .iter()
+                .map(|&(le, count)| {
+                    let then = old.and_then(|o| {
+                        o.latency_buckets
+                            .iter()
+                            .find(|(l, _)| *l == le)
+                            .map(|b| b.1)
+                    });
+                    (le, since(count, then))
+                })
+                .collect(),
+            latency_sum_ms: since(new.latency_sum_ms, old.map(|o| o.latency_sum_ms)),
+            status_codes: new
+                .status_codes
+                .iter()
+                .map(|(status, &count)| {
+                    let then = old.and_then(|o| o.status_codes.get(status).copied());
+                    (*status, since(count, then))
+                })
+                .filter(|(_, count)| *count > 0)
+                .collect(),
+            faults: new
+                .faults
+                .iter()
+                .map(|(fault, &count)| {
+                    let then = old.and_then(|o| o.faults.get(fault).copied());
+                    (fault.clone(), since(count, then))
+                })
+                .filter(|(_, count)| *count > 0)
+                .collect(),
+            stub_hits: new
+                .stub_hits
+                .iter()
+                .map(|(index, (id, count))| {
+                    let then = old.and_then(|o| hits_of(&o.stub_hits, *index, id.as_deref()));
+                    (*index, (id.clone(), since(*count, then)))
+                })
+                .filter(|(_, (_, count))| *count > 0)
+                .collect(),
+            unmatched: since(new.unmatched, old.map(|o| o.unmatched)),
+        }
+    }
+
+    /// Requests per second over the window
+    pub fn rate(&self) -> Option<f64> {
+        self.seconds
+            .filter(|s| *s > 0.0)
+            .map(|s| self.requests as f64 / s)
+    }
+
+    /// Share of responses with a 5xx status, from 0 to 1
+    pub fn error_rate(&self) -> Option<f64> {
+        let total: u64 = self.status_codes.values().sum();
+        let errors: u64 = self.status_codes.range(500..).map(|(_, c)| c).sum();
+        (total > 0).then(|| errors as f64 / total as f64)
+    }
+
+    /// Responses per status class: 2xx, 3xx, 4xx and 5xx (1xx counts with 2xx)
+    pub fn status_classes(&self) -> [u64; 4] {
+        let mut classes = [0; 4];
+        for (status, count) in &self.status_codes {
+            classes[(*status / 100).clamp(2, 5) as usize - 2] += count;
+        }
+        classes
+    }
+
+    pub fn mean_latency_ms(&self) -> Option<f64> {
+        (self.requests > 0).then(|| self.latency_sum_ms as f64 / self.requests as f64)
+    }
+
+    /// The latency below which a `quantile` of the requests fell, interpolated within its bucket
+    /// the way Prometheus' `histogram_quantile` does. Requests slower than the last finite bound
+    /// are reported at that bound.
+    pub fn latency_percentile(&self, quantile: f64) -> Option<f64> {
+        let total = self.latency_buckets.last()?.1;
+        if total == 0 {
+            return None;
+        }
+        let rank = quantile * total as f64;
+        let (mut lower, mut below) = (0.0, 0);
+        for &(le, count) in &self.latency_buckets {
+            if count as f64 >= rank {
+                if le.is_infinite() {
+                    return Some(lower);
+                }
+                let inside = (count - below) as f64;
+                return Some(if inside == 0.0 {
+                    le
+                } else {
+                    lower + (le - lower) * (rank - below as f64) / inside
+                });
+            }
+            (lower, below) = (le, count);
+        }
+        Some(lower)
+    }
+}
+
+impl App {
+    /// Add the latest metrics to the history, dropping snapshots older than the longest window
+    pub(crate) fn record_metrics(&mut self, metrics: &MetricsData, now: Instant) {
+        self.metrics_history.push_back(MetricsSnapshot {
+            timestamp: now,
+            total_requests: metrics.total_requests,
+            per_imposter: metrics
+                .per_imposter
+                .iter()
+                .map(|(k, v)| (*k, v.request_count))
+                .collect(),
+            imposters: metrics.per_imposter.clone(),
+        });
+        while self.metrics_history.len() > MAX_METRICS_SNAPSHOTS
+            || self
+                .metrics_history
+                .front()
+                .is_some_and(|s| now.duration_since(s.timestamp) > METRICS_RETENTION)
+        {
+            self.metrics_history.pop_front();
+        }
+    }
+
+    /// Traffic of imposter `port` over the selected window, from the latest snapshot
+    pub fn window_stats(&self, port: u16) -> Option<WindowStats> {
+        let latest = self.metrics_history.back()?;
+        let new = latest.imposters.get(&port)?;
+        let Some(window) = self.metrics_window.duration() else {
+            return Some(WindowStats::between(new, None));
+        };
+        let base = self
+            .metrics_history
+            .iter()
+            .find(|s| latest.timestamp.duration_since(s.timestamp) <= window)
+            .unwrap_or(latest);
+        let mut stats = WindowStats::between(new, base.imposters.get(&port));
+        stats.seconds = Some(
+            latest
+                .timestamp
+                .duration_since(base.timestamp)
+                .as_secs_f64(),
+        );
+        Some(stats)
+    }
+
+    /// Requests imposter `port` served between consecutive snapshots in the selected window
+    pub fn window_request_series(&self, port: u16) -> Vec<u64> {
+        let Some(latest) = self.metrics_history.back() else {
+            return Vec::new();
+        };
+        let served = |s: &MetricsSnapshot| s.imposters.get(&port).map(|m| m.served_count);
+        let window = self.metrics_window.duration();
+        let snapshots: Vec<&MetricsSnapshot> = self
+            .metrics_history
+            .iter()
+            .filter(|s| window.is_none_or(|w| latest.timestamp.duration_since(s.timestamp) <= w))
+            .collect();
+        snapshots
+            .windows(2)
+            .map(|pair| match (served(pair[0]), served(pair[1])) {
+                (Some(old), Some(new)) if new >= old => new - old,
+                (_, new) => new.unwrap_or(0),
+            })
+            .collect()
+    }
+
+    /// Times stub `index` of imposter `port` matched since the imposter started, or since its
+    /// counts were reset with `z`; `None` when the server reports no stub hits. A stub with an id
+    /// keeps its reset point when stubs are moved or deleted
+    pub fn stub_hit_count(&self, port: u16, index: usize) -> Option<u64> {
+        let hit = self.metrics.per_imposter.get(&port)?.stub_hits.get(&index);
+        let hits = hit.map_or(0, |hit| hit.1);
+        let base = self
+            .stub_hit_baseline
+            .get(&port)
+            .and_then(|baseline| hits_of(baseline, index, hit.and_then(|hit| hit.0.as_deref())))
+            .unwrap_or(0);
+        // Fewer hits than at the reset: the imposter was recreated and counts afresh
+        Some(hits.checked_sub(base).unwrap_or(hits))
+    }
+
+    /// `z` in the imposter detail: count the imposter's stub hits from zero again. The server's
+    /// counters are left alone for whatever else scrapes them.
+    pub(super) fn reset_stub_hits(&mut self) {
+        let View::ImposterDetail { port } = self.view else {
+            return;
+        };
+        match self.metrics.per_imposter.get(&port) {
+            Some(metrics) => {
+                self.stub_hit_baseline
+                    .insert(port, metrics.stub_hits.clone());
+                self.set_status(
+                    format!("Stub hit counts of :{port} reset"),
+                    StatusLevel::Info,
+                );
+            }
+            None => self.set_status(
+                "The server reports no stub hits to reset".to_string(),
+                StatusLevel::Warning,
+            ),
+        }
+    }
+
+    /// The imposter selected in the metrics view
+    pub fn metrics_selected_port(&self) -> Option<u16> {
+        self.imposters
+            .get(
+                self.metrics_selected
+                    .min(self.imposters.len().saturating_sub(1)),
+            )
+            .map(|imp| imp.port)
+    }
+
+    pub(super) fn handle_metrics_event(&mut self, key: KeyEvent) {
+        let last = self.imposters.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.metrics_selected = (self.metrics_selected + 1).min(last);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.metrics_selected = self.metrics_selected.min(last).saturating_sub(1);
+            }
+            KeyCode::Char('w') => {
+                self.metrics_window = self.metrics_window.next();
+                self.set_status(
+                    format!("Metrics window: {}", self.metrics_window.label()),
+                    StatusLevel::Info,
+                );
+            }
+            KeyCode::Char('x') => self.show_metrics_export_dialog(),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::make_test_app;
+
+    fn imposter(served: u64, buckets: &[(f64, u64)], statuses: &[(u16, u64)]) -> ImposterMetrics {
+        ImposterMetrics {
+            served_count: served,
+            latency_buckets: buckets.to_vec(),
+            status_codes: statuses.iter().copied().collect(),
+            stub_hits: [(0, (Some("orders".to_string()), served))].into(),
+            ..Default::default()
+        }
+    }
+
+    fn metrics(imposter: ImposterMetrics) -> MetricsData {
+        MetricsData {
+            per_imposter: [(4545, imposter)].into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn windows_take_the_difference_from_the_oldest_snapshot_inside_them() {
+        let mut app = make_test_app();
+        let start = Instant::now();
+        let inf = f64::INFINITY;
+        app.record_metrics(
+            &metrics(imposter(10, &[(10.0, 10), (inf, 10)], &[(200, 10)])),
+            start,
+        );
+        app.record_metrics(
+            &metrics(imposter(
+                30,
+                &[(10.0, 25), (inf, 30)],
+                &[(200, 20), (503, 10)],
+            )),
+            start + Duration::from_secs(90),
+        );
+        app.record_metrics(
+            &metrics(imposter(
+                50,
+                &[(10.0, 35), (inf, 50)],
+                &[(200, 30), (503, 20)],
+            )),
+            start + Duration::from_secs(120),
+        );
+
+        app.metrics_window = MetricsWindow::OneMinute;
+        let stats = app.window_stats(4545).unwrap();
+        assert_eq!(stats.requests, 20);
+        assert_eq!(stats.seconds, Some(30.0));
+        assert_eq!(stats.rate(), Some(20.0 / 30.0));
+        assert_eq!(stats.latency_buckets, [(10.0, 10), (inf, 20)]);
+        assert_eq!(stats.error_rate(), Some(0.5));
+        assert_eq!(stats.stub_hits[&0], (Some("orders".to_string()), 20));
+        assert_eq!(app.window_request_series(4545), [20]);
+
+        app.metrics_window = MetricsWindow::FiveMinutes;
+        assert_eq!(app.window_stats(4545).unwrap().requests, 40);
+        assert_eq!(app.window_request_series(4545), [20, 20]);
+
+        app.metrics_window = MetricsWindow::All;
+        let stats = app.window_stats(4545).unwrap();
+        assert_eq!(stats.requests, 50);
+        assert_eq!(stats.rate(), None);
+        assert_eq!(stats.status_classes(), [30, 0, 0, 20]);
+    }
+
+    #[test]
+    fn a_recreated_imposter_starts_its_window_afresh() {
+        let mut app = make_test_app();
+        let start = Instant::now();
+        app.record_metrics(&metrics(imposter(100, &[], &[(200, 100)])), start);
+        app.record_metrics(
+            &metrics(imposter(3, &[], &[(200, 3)])),
+            start + Duration::from_secs(5),
+        );
+        let stats = app.window_stats(4545).unwrap();
+        assert_eq!(stats.requests, 3);
+        assert_eq!(stats.status_codes[&200], 3);
+    }
+
+    #[test]
+    fn window_stub_hits_follow_a_stub_that_moved() {
+        let mut app = make_test_app();
+        let start = Instant::now();
+        let with_hits = |hits: [(usize, &str, u64); 2]| {
+            metrics(ImposterMetrics {
+                stub_hits: hits
+                    .into_iter()
+                    .map(|(index, id, count)| (index, (Some(id.to_string()), count)))
+                    .collect(),
+                ..Default::default()
+            })
+        };
+        app.record_metrics(&with_hits([(0, "a", 5), (1, "b", 2)]), start);
+        // `b` moved ahead of `a`; each was hit once since.
+        app.record_metrics(
+            &with_hits([(0, "b", 3), (1, "a", 6)]),
+            start + Duration::from_secs(5),
+        );
+        let stats = app.window_stats(4545).unwrap();
+        assert_eq!(
+            stats.stub_hits,
+            BTreeMap::from([
+                (0, (Some("b".to_string()), 1)),
+                (1, (Some("a".to_string()), 1)),
+            ])
+        );
+    }
+
+    #[test]
+    fn snapshots_older_than_the_longest_window_are_dropped() {
+        let mut app = make_test_app();
+        let start = Instant::now();
+        app.record_metrics(&MetricsData::default(), start);
+        app.record_metrics(&MetricsData::default(), start + METRICS_RETENTION);
+        assert_eq!(app.metrics_history.len(), 2);
+        app.record_metrics(
+            &MetricsData::default(),
+            start + METRICS_RETENTION + Duration::from_secs(1),
+        );
+        assert_eq!(app.metrics_history.len(), 2);
+    }
+
+    #[test]
+    fn stub_hits_count_from_the_last_reset() {
+        let mut app = make_test_app();
+        app.view = View::ImposterDetail { port: 4545 };
+        assert_eq!(app.stub_hit_count(4545, 0), None, "no metrics, no counts");
+
+        app.metrics = metrics(imposter(3, &[], &[]));
+        assert_eq!(app.stub_hit_count(4545, 0), Some(3));
+        assert_eq!(app.stub_hit_count(4545, 1), Some(0), "never hit");
+
+        app.reset_stub_hits();
+        assert_eq!(app.stub_hit_count(4545, 0), Some(0));
+        app.metrics = metrics(imposter(5, &[], &[]));
+        assert_eq!(app.stub_hit_count(4545, 0), Some(2));
+        app.metrics = metrics(imposter(1, &[], &[]));
+        assert_eq!(app.stub_hit_count(4545, 0), Some(1), "recreated imposter");
+    }
+
+    #[test]
+    fn stub_hits_stay_with_their_stub_after_a_delete() {
+        let mut app = make_test_app();
+        app.view = View::ImposterDetail { port: 4545 };
+        let with_hits = |hits: &[(usize, Option<&str>, u64)]| {
+            metrics(ImposterMetrics {
+                stub_hits: hits
+                    .iter()
+                    .map(|&(index, id, count)| (index, (id.map(str::to_string), count)))
+                    .collect(),
+                ..Default::default()
+            })
+        };
+        app.metrics = with_hits(&[(0, Some("a"), 3), (1, Some("b"), 5), (2, None, 4)]);
+        app.reset_stub_hits();
+        // `a` is deleted: the server now reports `b` at 0, hit twice since the reset.
+        app.metrics = with_hits(&[(0, Some("b"), 7), (1, None, 4)]);
+        assert_eq!(app.stub_hit_count(4545, 0), Some(2));
+        assert_eq!(
+            app.stub_hit_count(4545, 2),
+            Some(0),
+            "nothing at 2 any more"
+        );
+    }
+
+    #[test]
+    fn percentiles_interpolate_within_buckets() {
+        let stats = WindowStats {
+            latency_buckets: vec![(10.0, 50), (100.0, 90), (f64::INFINITY, 100)],
+            ..Default::default()
+        };
+        assert_eq!(stats.latency_percentile(0.5), Some(10.0));
+        assert_eq!(stats.latency_percentile(0.25), Some(5.0));
+        assert_eq!(stats.latency_percentile(0.7), Some(55.0));
+        // Slower than every finite bound
+        assert_eq!(stats.latency_percentile(0.99), Some(100.0));
+        assert_eq!(WindowStats::default().latency_percentile(0.5), None);
+    }
+}
@@ -1,32 +1,120 @@
 //! Application state and logic for the TUI
 
 use crate::api::{
-    ApiClient, CreateImposterRequest, ImposterDetail, ImposterSummary, MetricsData, Stub,
+    ApiClient, ApiError, ImposterDetail, ImposterMetrics, ImposterRequest, ImposterSummary,
+    MetricsData, ServedRequestDetail, Stub,
+};
+use crate::keys::{Context, KeyMap};
+use crate::theme::{Theme, Themes};
+use crate::validation::{
+    IssueSeverity, ValidationReport, imposter_issue_line, stub_issue_line, validate_imposter_json,
+    validate_stub_json,
 };
-use crate::theme::Theme;
-use crate::validation::{ValidationReport, validate_imposter_json, validate_stub_json};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::widgets::ListState;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::time::{Duration, Instant};
 
+mod accessibility;
+mod capture;
 mod commands;
+mod composer;
+mod connection;
+mod copy_as;
+mod diff;
+mod editor;
 mod events;
+mod global_search;
+mod imposter_form;
+mod imposter_list;
+mod metrics;
+mod palette;
+mod proxy_wizard;
+mod request_log;
+mod scenario;
+mod script_editor;
 mod search;
-
-/// Maximum number of metrics snapshots to keep for sparklines
-const MAX_METRICS_HISTORY: usize = 60;
+mod selection;
+mod server_log;
+mod session;
+mod stub_rows;
+mod switcher;
+mod transfer;
+mod undo;
+
+pub use composer::{COMPOSER_METHODS, Composer, ComposerField, response_sections};
+pub use connection::{RECONNECT_MAX_DELAY, RECONNECT_MIN_DELAY, Reconnect};
+pub use copy_as::{CopyCommand, CopyMenu, CopyTarget, CopyTool, curl_command, httpie_command};
+pub use diff::{DiffAction, DiffLine};
+pub use global_search::{GlobalSearch, HitField, SearchHit, search_imposter};
+pub use imposter_form::{FormField, IMPOSTER_PROTOCOLS, ImposterForm};
+pub use imposter_list::{
+    ACTIVITY_HISTORY, Activity, HOT_REQUESTS, ImposterFilter, ImposterKey, ImposterSort, SortKey,
+};
+pub use metrics::{METRICS_RETENTION, MetricsWindow, WindowStats};
+pub use palette::{CommandPalette, PaletteCommand, PaletteEntry, fuzzy_score};
+pub use proxy_wizard::{GENERATOR_FIELDS, PROXY_MODES, ProxyWizard, WizardStep};
+pub use request_log::{RequestLog, inspector_sections};
+pub use scenario::{
+    ScenarioFile, ScenarioRun, ScenarioStep, StepOutcome, StepResult, parse_scenario,
+};
+pub use script_editor::{ScriptEditor, ScriptField, ScriptOutput, ScriptTarget, stub_scripts};
+pub use server_log::{LogLevel, MAX_SERVER_LOG_ENTRIES, ServerLog};
+pub use stub_rows::{StubRow, StubRows};
+pub use switcher::Server;
+pub use transfer::{ImposterOverview, TransferAction, TransferPreview, overview};
+pub use undo::{JOURNAL_DEPTH, Journal, Operation};
 
 /// Current view/screen
 #[derive(Debug, Clone, PartialEq)]
 pub enum View {
     ImposterList,
-    ImposterDetail { port: u16 },
-    StubDetail { port: u16, index: usize },
-    StubEdit { port: u16, index: Option<usize> },
-    RequestDetail { port: u16, index: usize },
+    ImposterDetail {
+        port: u16,
+    },
+    StubDetail {
+        port: u16,
+        index: usize,
+    },
+    StubEdit {
+        port: u16,
+        index: Option<usize>,
+    },
+    /// JSON of a new imposter in the stub editor, carried over from the new imposter form
+    ImposterEdit,
+    RequestDetail {
+        port: u16,
+        index: usize,
+    },
+    /// Live tail of the requests an imposter serves
+    RequestLog {
+        port: u16,
+    },
     Config,
     Metrics,
+    /// Live tail of the Rift server's own log
+    ServerLog,
+    /// Steps of the scenario in `App::scenario` and how each went
+    Scenario,
+}
+
+impl View {
+    /// The `keys.toml` section for the keys only this view has
+    pub fn key_context(&self) -> Option<Context> {
+        match self {
+            View::ImposterList => Some(Context::ImposterList),
+            View::ImposterDetail { .. } => Some(Context::ImposterDetail),
+            View::StubDetail { .. } => Some(Context::StubDetail),
+            View::RequestLog { .. } => Some(Context::RequestLog),
+            View::Metrics => Some(Context::Metrics),
+            View::ServerLog => Some(Context::ServerLog),
+            View::StubEdit { .. }
+            | View::ImposterEdit
+            | View::RequestDetail { .. }
+            | View::Config
+            | View::Scenario => None,
+        }
+    }
 }
 
 /// Overlay (modal) state
@@ -63,13 +151,43 @@ pub enum Overlay {
     },
     /// The in-app error log (issue #624).
     Errors,
+    /// Headers and bodies of a served request from the request log of `port`
+    RequestInspector {
+        port: u16,
+    },
+    /// The request composer, for the imposter in `App::composer`
+    Composer,
+    /// The new imposter form in `App::imposter_form`
+    ImposterForm,
+    /// The proxy imposter wizard in `App::proxy_wizard`
+    ProxyWizard,
+    /// The script editor in `App::script_editor`, over the stub editor
+    ScriptEditor,
+    /// The command palette in `App::palette`
+    CommandPalette,
+    /// Hits of the global search in `App::global_search`, to jump to
+    GlobalSearch,
+    /// The import or export in `App::transfer_preview`, waiting to be confirmed
+    TransferPreview,
+    /// The copy-as menu in `App::copy_menu`
+    CopyMenu,
+    /// What a save or apply changes on the server, run once confirmed
+    DiffPreview {
+        title: String,
+        /// Shown above the diff, e.g. that the server's copy changed since editing began
+        warning: Option<String>,
+        diff: Vec<DiffLine>,
+        action: DiffAction,
+    },
+    /// Pick the server to show; `selected` is past the servers for "all servers"
+    ServerSwitcher {
+        selected: usize,
+    },
 }
 
 /// Actions to take after viewing validation results
 #[derive(Debug, Clone, PartialEq)]
 pub enum ValidationAction {
-    /// Import a file despite warnings
-    ProceedWithImport { path: String, content: String },
     /// Editor validation - just informational
     EditorInfo,
 }
@@ -77,28 +195,73 @@ pub enum ValidationAction {
 /// File-related actions
 #[derive(Debug, Clone, PartialEq)]
 pub enum FileAction {
-    SaveExport { content: String, port: u16 },
+    SaveExport {
+        content: String,
+        port: u16,
+    },
     ImportFile,
     ImportFolder,
     ExportAll,
     ExportToFolder,
+    /// Export the imposters marked in the list to one file
+    ExportMarked {
+        ports: Vec<u16>,
+    },
+    /// Run a scenario file against the marked or selected imposters
+    RunScenario,
+    /// Write the metrics view's figures as CSV or JSON
+    ExportMetrics,
+    /// Save a screenshot of the view
+    Screenshot,
 }
 
 /// Actions that need confirmation
 #[derive(Debug, Clone, PartialEq)]
 pub enum PendingAction {
     DeleteImposter { port: u16 },
+    DeleteImposters { ports: Vec<u16> },
     DeleteStub { port: u16, index: usize },
+    DeleteStubs { port: u16, indices: Vec<usize> },
     ClearRequests { port: u16 },
     ClearProxyResponses { port: u16 },
-    ApplyRecordedStubs { port: u16 },
+    ToggleImposter { port: u16 },
+    ToggleImposters { ports: Vec<u16> },
+}
+
+impl PendingAction {
+    /// How careful `confirmations` is about this action
+    fn safety(&self, confirmations: &crate::confirm::Confirmations) -> crate::confirm::Safety {
+        match self {
+            PendingAction::DeleteImposter { .. } | PendingAction::DeleteImposters { .. } => {
+                confirmations.delete_imposter
+            }
+            PendingAction::DeleteStub { .. } | PendingAction::DeleteStubs { .. } => {
+                confirmations.delete_stub
+            }
+            PendingAction::ClearRequests { .. } => confirmations.clear_requests,
+            PendingAction::ClearProxyResponses { .. } => confirmations.clear_proxy_responses,
+            PendingAction::ToggleImposter { .. } | PendingAction::ToggleImposters { .. } => {
+                confirmations.toggle
+            }
+        }
+    }
+
+    /// Whether it acts on marked imposters or stubs
+    fn is_bulk(&self) -> bool {
+        matches!(
+            self,
+            PendingAction::DeleteImposters { .. }
+                | PendingAction::DeleteStubs { .. }
+                | PendingAction::ToggleImposters { .. }
+        )
+    }
 }
 
 /// Input actions
 #[derive(Debug, Clone, PartialEq)]
 pub enum InputAction {
-    CreateImposter,
-    CreateProxyImposter,
+    /// Send the inspected request to another imposter; `from` is the one that served it
+    ReplayToPort { from: u16 },
 }
 
 /// Status message level
@@ -134,6 +297,8 @@ pub struct MetricsSnapshot {
     pub timestamp: Instant,
     pub total_requests: u64,
     pub per_imposter: HashMap<u16, u64>,
+    /// Every counter of each imposter, for figures over a window
+    pub imposters: HashMap<u16, ImposterMetrics>,
 }
 
 /// Parts of a curl request extracted from stub predicates
@@ -175,12 +340,35 @@ pub enum EditorAction {
     PasteRequest,
 }
 
+/// A lint issue placed on an editor line, for the gutter
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineIssue {
+    /// 0-based editor line
+    pub line: usize,
+    pub severity: IssueSeverity,
+    pub message: String,
+}
+
 /// Stub JSON editor backed by ratatui-textarea
 pub struct StubEditor {
     pub editor: ratatui_textarea::TextArea<'static>,
     pub validation_error: Option<String>,
     pub validation_report: Option<ValidationReport>,
+    /// Issues of the last validation by line, errors first on each line
+    pub line_issues: Vec<LineIssue>,
+    /// Content the user was warned has lint errors; saving it again goes ahead
+    pub acknowledged_errors: Option<String>,
+    /// Start lines of the folded blocks
+    folds: std::collections::BTreeSet<usize>,
+    /// First display row and column the editor shows, kept across frames like a textarea
+    /// viewport; the editor is drawn by the UI rather than by the textarea widget
+    scroll_top: std::cell::Cell<u16>,
+    scroll_left: std::cell::Cell<u16>,
+    /// Text rows in the last frame, for paging
+    page_height: std::cell::Cell<u16>,
     pub original_json: String,
+    /// Whether the text is a whole imposter to create rather than one stub
+    for_imposter: bool,
 }
 
 impl StubEditor {
@@ -191,44 +379,103 @@ impl StubEditor {
             ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray),
         );
         editor.set_cursor_line_style(ratatui::style::Style::default());
-        editor.set_block(
-            ratatui::widgets::Block::default()
-                .borders(ratatui::widgets::Borders::ALL)
-                .title(" Edit Stub (Ctrl+S save, Ctrl+F format, Ctrl+L lint, Esc cancel) "),
-        );
         let original_json = json.to_string();
         let mut stub_editor = Self {
             editor,
             validation_error: None,
             validation_report: None,
+            line_issues: Vec::new(),
+            acknowledged_errors: None,
+            folds: std::collections::BTreeSet::new(),
+            scroll_top: std::cell::Cell::new(0),
+            scroll_left: std::cell::Cell::new(0),
+            page_height: std::cell::Cell::new(0),
             original_json,
+            for_imposter: false,
         };
+        stub_editor.set_title();
         stub_editor.validate();
         stub_editor
     }
 
-    /// Validate the JSON content using rift-lint
+    /// An editor for the JSON of a new imposter, linted as a whole imposter
+    pub fn for_new_imposter(json: &str) -> Self {
+        let mut editor = Self::new(json);
+        editor.for_imposter = true;
+        editor.set_title();
+        editor.validate();
+        editor
+    }
+
+    fn set_title(&mut self) {
+        let title = if self.for_imposter {
+            " New Imposter (Ctrl+S create, Ctrl+F format, Ctrl+L lint, Esc cancel) "
+        } else {
+            " Edit Stub (Ctrl+S save, Ctrl+F format, Ctrl+L lint, Esc cancel) "
+        };
+        self.editor.set_block(
+            ratatui::widgets::Block::default()
+                .borders(ratatui::widgets::Borders::ALL)
+                .title(title),
+        );
+    }
+
+    /// Validate the JSON content using rift-lint, placing each issue on the line it is about
     pub fn validate(&mut self) -> bool {
         let content = self.editor.lines().join("\n");
         match serde_json::from_str::<serde_json::Value>(&content) {
             Ok(val) => {
                 self.validation_error = None;
-                let json_str = serde_json::to_string_pretty(&val).unwrap_or(content);
-                let report = validate_stub_json(&json_str);
+                let json_str = serde_json::to_string_pretty(&val).unwrap_or(content.clone());
+                let report = if self.for_imposter {
+                    validate_imposter_json(&json_str, "<editor>")
+                } else {
+                    validate_stub_json(&json_str)
+                };
+                let issue_line = if self.for_imposter {
+                    imposter_issue_line
+                } else {
+                    stub_issue_line
+                };
                 if report.has_issues() {
                     self.validation_error = Some(report.summary());
                 }
+                self.line_issues = report
+                    .issues
+                    .iter()
+                    .map(|issue| LineIssue {
+                        line: issue_line(&content, issue.location.as_deref().unwrap_or("")),
+                        severity: issue.severity,
+                        message: format!("{} {}", issue.code, issue.message),
+                    })
+                    .collect();
+                self.line_issues
+                    .sort_by_key(|issue| (issue.line, issue.severity != IssueSeverity::Error));
                 self.validation_report = Some(report);
                 true
             }
             Err(e) => {
                 self.validation_error = Some(format!("JSON error: {e}"));
                 self.validation_report = None;
+                self.line_issues = vec![LineIssue {
+                    line: e.line().saturating_sub(1),
+                    severity: IssueSeverity::Error,
+                    message: format!("JSON error: {e}"),
+                }];
                 false
             }
         }
     }
 
+    /// The issue to show for the cursor's line, or else the first one
+    pub fn current_issue(&self) -> Option<&LineIssue> {
+        let row = self.editor.cursor().0;
+        self.line_issues
+            .iter()
+            .find(|issue| issue.line == row)
+            .or_else(|| self.line_issues.first())
+    }
+
     /// Get the stub if valid
     ///
     /// Domain-optional parse: editor content that isn't yet a valid stub is a normal editing
@@ -245,21 +492,25 @@ impl StubEditor {
         if let Ok(val) = serde_json::from_str::<serde_json::Value>(&content)
             && let Ok(pretty) = serde_json::to_string_pretty(&val)
         {
-            let lines: Vec<String> = pretty.lines().map(String::from).collect();
-            self.editor = ratatui_textarea::TextArea::new(lines);
-            self.editor.set_line_number_style(
-                ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray),
-            );
-            self.editor
-                .set_cursor_line_style(ratatui::style::Style::default());
-            self.editor.set_block(
-                ratatui::widgets::Block::default()
-                    .borders(ratatui::widgets::Borders::ALL)
-                    .title(" Edit Stub (Ctrl+S save, Ctrl+F format, Ctrl+L lint, Esc cancel) "),
-            );
+            self.set_text(&pretty);
         }
     }
 
+    /// Replace the whole content, unfolded and scrolled to the top
+    pub fn set_text(&mut self, text: &str) {
+        let lines: Vec<String> = text.lines().map(String::from).collect();
+        self.editor = ratatui_textarea::TextArea::new(lines);
+        self.folds.clear();
+        self.scroll_top.set(0);
+        self.scroll_left.set(0);
+        self.editor.set_line_number_style(
+            ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray),
+        );
+        self.editor
+            .set_cursor_line_style(ratatui::style::Style::default());
+        self.set_title();
+    }
+
     /// Handle a key event. Returns Some(EditorAction) for clipboard operations, None otherwise.
     /// Ctrl+S, Ctrl+F, Ctrl+L must be intercepted by the caller BEFORE calling this.
     pub fn handle_key(&mut self, key: KeyEvent) -> Option<EditorAction> {
@@ -275,7 +526,9 @@ impl StubEditor {
                 KeyCode::Char('x') => {
                     let yanked = self.editor.yank_text();
                     if !yanked.is_empty() {
+                        let before = (self.editor.cursor().0, self.editor.lines().len());
                         self.editor.input(crossterm_key_to_input(key));
+                        self.after_input(before, false);
                         return Some(EditorAction::Cut(yanked));
                     }
                     return None;
@@ -283,10 +536,32 @@ impl StubEditor {
                 KeyCode::Char('v') => {
                     return Some(EditorAction::PasteRequest);
                 }
+                KeyCode::Char('o') => {
+                    self.toggle_fold();
+                    return None;
+                }
                 _ => {}
             }
         }
-        self.editor.input(crossterm_key_to_input(key));
+        let before = (self.editor.cursor().0, self.editor.lines().len());
+        match key.code {
+            KeyCode::PageUp | KeyCode::PageDown => {
+                self.page(
+                    key.code == KeyCode::PageDown,
+                    key.modifiers.contains(KeyModifiers::SHIFT),
+                );
+            }
+            _ => {
+                self.editor.input(crossterm_key_to_input(key));
+            }
+        }
+        self.after_input(
+            before,
+            matches!(
+                key.code,
+                KeyCode::Up | KeyCode::Down | KeyCode::PageUp | KeyCode::PageDown
+            ),
+        );
         None
     }
 }
@@ -338,35 +613,10 @@ pub(super) fn crossterm_key_to_input(key: KeyEvent) -> ratatui_textarea::Input {
 #[derive(Debug, Clone, Default)]
 pub struct InputState {
     pub port: String,
-    pub name: String,
-    pub protocol: String,
-    pub target_url: String,
-    pub proxy_mode: usize, // 0=proxyOnce, 1=proxyAlways, 2=proxyTransparent
-    pub focus_field: usize,
     pub file_path: String,
     pub cursor_pos: usize, // Cursor position in file_path
 }
 
-impl InputState {
-    pub fn proxy_mode_str(&self) -> &str {
-        match self.proxy_mode {
-            0 => "proxyOnce",
-            1 => "proxyAlways",
-            2 => "proxyTransparent",
-            _ => "proxyOnce",
-        }
-    }
-
-    pub fn proxy_mode_display(&self) -> &str {
-        match self.proxy_mode {
-            0 => "proxyOnce (record first, replay after)",
-            1 => "proxyAlways (always forward, keep recording)",
-            2 => "proxyTransparent (always forward, no recording)",
-            _ => "proxyOnce",
-        }
-    }
-}
-
 /// Main application state
 pub struct App {
     // Navigation
@@ -377,8 +627,49 @@ pub struct App {
     // Data
     pub imposters: Vec<ImposterSummary>,
     pub current_imposter: Option<ImposterDetail>,
+    /// The stub list's rows for `current_imposter`, filled while drawing
+    pub stub_row_cache: std::cell::RefCell<StubRows>,
+    /// The live request log, kept while navigating so reopening it resumes the tail
+    pub request_log: Option<RequestLog>,
+    /// The server log, kept while its view is closed
+    pub server_log: Option<ServerLog>,
+    /// Served request open in the request inspector
+    pub inspected_request: Option<ServedRequestDetail>,
+    pub inspector_scroll: u16,
+    /// How far the stub detail view is scrolled down
+    pub stub_detail_scroll: u16,
+    pub diff_scroll: u16,
+    /// The request composer, kept while closed so the last request can be sent again
+    pub composer: Option<Composer>,
+    /// The new imposter form while it is open
+    pub imposter_form: Option<ImposterForm>,
+    /// The proxy imposter wizard while it is open
+    pub proxy_wizard: Option<ProxyWizard>,
+    /// The scenario open in the scenario runner
+    pub scenario: Option<ScenarioRun>,
+    /// The import or export shown before it is confirmed
+    pub transfer_preview: Option<TransferPreview>,
+    /// The copy-as menu, while open
+    pub copy_menu: Option<CopyMenu>,
+    /// Where to save a screenshot of the next frame drawn
+    pub pending_screenshot: Option<String>,
+    /// Which actions ask before they run
+    pub confirmations: crate::confirm::Confirmations,
+    /// Per imposter, the stub hits (by index, with each stub's id) when its counts were last
+    /// reset with `z`
+    pub stub_hit_baseline: HashMap<u16, BTreeMap<usize, (Option<String>, u64)>>,
+    /// Accessibility mode: high contrast, no glyph-only indicators, changes said in words
+    pub accessible: bool,
+    /// The theme to go back to when accessibility mode is turned off
+    pub theme_before_accessible: Option<Theme>,
+    /// The script editor while it is open
+    pub script_editor: Option<ScriptEditor>,
+    pub palette: CommandPalette,
     pub metrics: MetricsData,
     pub metrics_history: VecDeque<MetricsSnapshot>,
+    pub metrics_window: MetricsWindow,
+    /// Position of the imposter the metrics view details
+    pub metrics_selected: usize,
 
     // UI State
     pub imposter_list_state: ListState,
@@ -394,6 +685,24 @@ pub struct App {
     // Search State
     pub search_active: bool,
     pub search_query: String,
+    /// The search being typed looks through every imposter rather than filtering this list
+    pub search_global: bool,
+    /// Results of the last global search
+    pub global_search: Option<GlobalSearch>,
+    /// Order of the imposter list, remembered between sessions
+    pub imposter_sort: ImposterSort,
+    /// Protocol and enabled-state filters of the imposter list
+    pub imposter_filter: ImposterFilter,
+    /// When each imposter was last seen serving requests, for sorting by activity
+    pub imposter_activity: HashMap<ImposterKey, Activity>,
+
+    // Marks for bulk actions
+    /// Imposters marked in the list, by port
+    pub marked_imposters: BTreeSet<u16>,
+    /// Stubs marked in the open imposter, by index
+    pub marked_stubs: BTreeSet<usize>,
+    /// Where the range being marked with `V` starts
+    pub mark_anchor: Option<usize>,
 
     // Edit State
     pub stub_editor: Option<StubEditor>,
@@ -409,13 +718,29 @@ pub struct App {
     // Connection
     pub client: ApiClient,
     pub admin_url: String,
+    /// Every server the TUI can switch to
+    pub servers: Vec<Server>,
+    /// The server shown, or `None` for every server's imposters in one list
+    pub active_server: Option<usize>,
     pub theme: Theme,
+    /// Themes `T` cycles through
+    pub themes: Themes,
+    /// Where the theme, sort and session are remembered for the next session
+    pub state_path: Option<std::path::PathBuf>,
+    /// Key bindings for the main views, from keys.toml
+    pub keys: KeyMap,
+    /// Changes `u` can undo and `U` redo
+    pub journal: Journal,
 
     // Runtime
     pub should_quit: bool,
     pub is_loading: bool,
     pub is_connected: bool,
     pub last_refresh: Instant,
+    /// When data was last loaded from a server that answered
+    pub last_updated: Option<Instant>,
+    /// Retry schedule while the server is not answering
+    pub reconnect: Reconnect,
     pub start_time: Instant,
     pub refresh_interval: Duration,
 }
@@ -423,8 +748,31 @@ pub struct App {
 impl App {
     /// Create a new App instance
     pub async fn new(admin_url: &str, refresh_interval: Duration) -> Self {
-        let client = ApiClient::new(admin_url);
+        Self::with_client(ApiClient::new(admin_url), refresh_interval).await
+    }
+
+    /// Create a new App instance talking through a preconfigured client (e.g. one carrying an
+    /// API key)
+    pub async fn with_client(client: ApiClient, refresh_interval: Duration) -> Self {
+        let profile = crate::servers::ServerProfile::from_url(client.base_url());
+        Self::with_servers(
+            vec![Server::new(profile, client)],
+            Some(0),
+            refresh_interval,
+        )
+        .await
+    }
 
+    /// Create a new App instance that can switch between `servers`, starting with `active`, or
+    /// with every server's imposters when it is `None`
+    pub async fn with_servers(
+        servers: Vec<Server>,
+        active: Option<usize>,
+        refresh_interval: Duration,
+    ) -> Self {
+        let first = &servers[active.unwrap_or(0)];
+        let client = first.client.clone();
+        let admin_url = first.profile.url.clone();
         let mut app = Self {
             view: View::ImposterList,
             view_stack: Vec::new(),
@@ -432,8 +780,30 @@ impl App {
 
             imposters: Vec::new(),
             current_imposter: None,
+            stub_row_cache: Default::default(),
+            request_log: None,
+            inspected_request: None,
+            inspector_scroll: 0,
+            stub_detail_scroll: 0,
+            diff_scroll: 0,
+            composer: None,
+            imposter_form: None,
+            proxy_wizard: None,
+            scenario: None,
+            transfer_preview: None,
+            copy_menu: None,
+            pending_screenshot: None,
+            confirmations: crate::confirm::Confirmations::default(),
+            stub_hit_baseline: HashMap::new(),
+            accessible: false,
+            theme_before_accessible: None,
+            script_editor: None,
+            server_log: None,
+            palette: CommandPalette::default(),
             metrics: MetricsData::default(),
-            metrics_history: VecDeque::with_capacity(MAX_METRICS_HISTORY),
+            metrics_history: VecDeque::new(),
+            metrics_window: MetricsWindow::default(),
+            metrics_selected: 0,
 
             imposter_list_state: ListState::default(),
             stub_list_state: ListState::default(),
@@ -445,12 +815,17 @@ impl App {
 
             search_active: false,
             search_query: String::new(),
+            search_global: false,
+            global_search: None,
+            imposter_sort: ImposterSort::default(),
+            imposter_filter: ImposterFilter::default(),
+            imposter_activity: HashMap::new(),
+            marked_imposters: BTreeSet::new(),
+            marked_stubs: BTreeSet::new(),
+            mark_anchor: None,
 
             stub_editor: None,
-            input_state: InputState {
-                protocol: "http".to_string(),
-                ..Default::default()
-            },
+            input_state: InputState::default(),
             export_scroll_offset: 0,
             validation_scroll_offset: 0,
             help_scroll: 0,
@@ -459,13 +834,21 @@ impl App {
             server_config: None,
 
             client,
-            admin_url: admin_url.to_string(),
+            admin_url,
+            servers,
+            active_server: active,
             theme: Theme::default(),
+            themes: Themes::default(),
+            state_path: None,
+            keys: KeyMap::default(),
+            journal: Journal::default(),
 
             should_quit: false,
             is_loading: false,
             is_connected: false,
             last_refresh: Instant::now(),
+            last_updated: None,
+            reconnect: Reconnect::default(),
             start_time: Instant::now(),
             refresh_interval,
         };
@@ -479,22 +862,34 @@ impl App {
     pub async fn refresh(&mut self) {
         self.is_loading = true;
 
+        let Some(active) = self.active_server else {
+            self.refresh_all_servers().await;
+            self.connection_checked(self.is_connected, Instant::now());
+            self.is_loading = false;
+            self.last_refresh = Instant::now();
+            return;
+        };
+
         // Check connection
-        match self.client.health_check().await {
-            Ok(healthy) => {
-                self.is_connected = healthy;
-            }
-            Err(_) => {
-                self.is_connected = false;
-                self.is_loading = false;
-                return;
-            }
+        let health = self.client.health_check().await;
+        self.is_connected = matches!(health, Ok(true));
+        self.client.set_reachable(self.is_connected);
+        if let Some(server) = self.servers.get_mut(active) {
+            server.connected = Some(self.is_connected);
+        }
+        if !self.is_connected {
+            // Keep what is on screen, greyed out, and retry on the backoff schedule
+            self.connection_checked(false, Instant::now());
+            self.is_loading = false;
+            self.last_refresh = Instant::now();
+            return;
         }
+        self.connection_checked(true, Instant::now());
 
         // Load imposters
         match self.client.list_imposters().await {
             Ok(imposters) => {
-                self.imposters = imposters;
+                self.set_imposters(imposters);
                 // Ensure selection is valid
                 if !self.imposters.is_empty() {
                     if self.imposter_list_state.selected().is_none() {
@@ -514,21 +909,7 @@ impl App {
 
         // Load metrics
         if let Ok(metrics) = self.client.get_metrics().await {
-            // Update history
-            let snapshot = MetricsSnapshot {
-                timestamp: Instant::now(),
-                total_requests: metrics.total_requests,
-                per_imposter: metrics
-                    .per_imposter
-                    .iter()
-                    .map(|(k, v)| (*k, v.request_count))
-                    .collect(),
-            };
-            self.metrics_history.push_back(snapshot);
-            if self.metrics_history.len() > MAX_METRICS_HISTORY {
-                self.metrics_history.pop_front();
-            }
-
+            self.record_metrics(&metrics, Instant::now());
             self.metrics = metrics;
         }
 
@@ -536,7 +917,21 @@ impl App {
         if let View::ImposterDetail { port } | View::StubDetail { port, .. } = self.view
             && let Ok(detail) = self.client.get_imposter(port).await
         {
-            self.current_imposter = Some(detail);
+            // Stub marks are by index, so they don't survive the stubs changing underneath them
+            let changed = |old: &ImposterDetail| {
+                serde_json::to_value(&old.stubs).ok() != serde_json::to_value(&detail.stubs).ok()
+            };
+            if !self.marked_stubs.is_empty() && self.current_imposter.as_ref().is_none_or(changed) {
+                self.marked_stubs.clear();
+            }
+            self.set_current_imposter(Some(detail));
+        }
+
+        if matches!(self.view, View::RequestLog { .. }) {
+            self.poll_request_log().await;
+        }
+        if self.view == View::ServerLog {
+            self.poll_server_log().await;
         }
 
         self.is_loading = false;
@@ -582,17 +977,32 @@ impl App {
 
     /// Cycle to the next theme
     pub fn cycle_theme(&mut self) {
-        self.theme.next();
-        self.set_status(
-            format!("Theme: {}", self.theme.preset.name()),
-            StatusLevel::Info,
-        );
+        let next = self.themes.after(&self.theme.name).clone();
+        self.set_theme(next);
+    }
+
+    /// Switch to `theme` and remember it for the next session
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        let name = self.theme.name.clone();
+        let saved = match &self.state_path {
+            Some(path) => crate::state::State::update(path, |state| state.theme = Some(name)),
+            None => Ok(()),
+        };
+        match saved {
+            Ok(()) => self.set_status(format!("Theme: {}", self.theme.name), StatusLevel::Info),
+            Err(e) => self.set_status(
+                format!("Theme: {} (not remembered: {e})", self.theme.name),
+                StatusLevel::Warning,
+            ),
+        }
     }
 
     /// Navigate to a new view
     pub fn navigate(&mut self, view: View) {
         self.view_stack.push(self.view.clone());
         self.view = view;
+        self.mark_anchor = None;
         // Clear search when navigating
         self.search_active = false;
         self.search_query.clear();
@@ -606,6 +1016,10 @@ impl App {
             self.search_query.clear();
             return;
         }
+        // Then drop the marks, before leaving the list they're in
+        if self.clear_marks() {
+            return;
+        }
 
         if let Some(prev) = self.view_stack.pop() {
             self.view = prev;
@@ -631,35 +1045,34 @@ impl App {
         };
     }
 
+    /// Ask before running `action`, or run it straight away when its safety level says not to
+    pub async fn confirm(&mut self, message: String, action: PendingAction) {
+        if action.safety(&self.confirmations).asks(action.is_bulk()) {
+            self.overlay = Overlay::Confirm { message, action };
+        } else {
+            self.run_pending_action(action).await;
+        }
+    }
+
     /// Execute a pending action
     pub async fn execute_pending_action(&mut self) {
-        if let Overlay::Confirm { action, .. } = &self.overlay.clone() {
-            match action {
-                PendingAction::DeleteImposter { port } => {
-                    self.delete_imposter(*port).await;
-                }
-                PendingAction::DeleteStub { port, index } => {
-                    self.delete_stub(*port, *index).await;
-                }
-                PendingAction::ClearRequests { port } => {
-                    self.clear_requests(*port).await;
-                }
-                PendingAction::ClearProxyResponses { port } => {
-                    self.clear_proxy_responses(*port).await;
-                }
-                PendingAction::ApplyRecordedStubs { port } => {
-                    self.apply_recorded_stubs(*port).await;
-                }
-            }
+        if let Overlay::Confirm { action, .. } = &self.overlay {
+            let action = action.clone();
+            self.run_pending_action(action).await;
         }
     }
 
-    /// Get sparkline data for a specific imposter
-    pub fn get_sparkline_data(&self, port: u16) -> Vec<u64> {
-        self.metrics_history
-            .iter()
-            .filter_map(|s| s.per_imposter.get(&port).copied())
-            .collect()
+    async fn run_pending_action(&mut self, action: PendingAction) {
+        match action {
+            PendingAction::DeleteImposter { port } => self.delete_imposter(port).await,
+            PendingAction::DeleteImposters { ports } => self.delete_imposters(ports).await,
+            PendingAction::DeleteStub { port, index } => self.delete_stub(port, index).await,
+            PendingAction::DeleteStubs { port, indices } => self.delete_stubs(port, indices).await,
+            PendingAction::ClearRequests { port } => self.clear_requests(port).await,
+            PendingAction::ClearProxyResponses { port } => self.clear_proxy_responses(port).await,
+            PendingAction::ToggleImposter { port } => self.toggle_imposter_at(port).await,
+            PendingAction::ToggleImposters { ports } => self.toggle_imposters(ports).await,
+        }
     }
 
     /// Calculate request rate between snapshots
@@ -805,6 +1218,58 @@ pub(crate) mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn keys_rebound_in_keys_toml_replace_the_defaults() {
+        let mut app = make_test_app();
+        app.keys = KeyMap::parse("[imposter_list]\ndelete = \"ctrl-d\"").unwrap();
+        app.imposters = vec![make_imposter(4545, None, "http")];
+        app.imposter_list_state.select(Some(0));
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE))
+            .await;
+        assert_eq!(app.overlay, Overlay::None, "`d` no longer deletes");
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL))
+            .await;
+        assert!(matches!(
+            app.overlay,
+            Overlay::Confirm {
+                action: PendingAction::DeleteImposter { port: 4545 },
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn confirmations_say_which_actions_ask_first() {
+        let mut app = make_test_app();
+        // Nothing listens here, so an action that runs fails at once
+        app.client = ApiClient::new("http://127.0.0.1:9");
+        app.confirmations = crate::confirm::Confirmations::parse("toggle = \"always\"").unwrap();
+        app.imposters = vec![make_imposter(4545, None, "http")];
+        app.imposter_list_state.select(Some(0));
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('t')))
+            .await;
+        assert_eq!(
+            app.overlay,
+            Overlay::Confirm {
+                message: "Disable imposter :4545?".to_string(),
+                action: PendingAction::ToggleImposter { port: 4545 },
+            }
+        );
+
+        app.overlay = Overlay::None;
+        app.confirmations = crate::confirm::Confirmations::unattended();
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('d')))
+            .await;
+        assert_eq!(app.overlay, Overlay::None, "--yes deletes without asking");
+        assert_eq!(
+            app.status_message.as_ref().map(|s| s.1.clone()),
+            Some(StatusLevel::Error)
+        );
+    }
+
     use super::*;
     use crate::api::{ApiClient, ImposterSummary, MetricsData};
     use crate::theme::Theme;
@@ -818,8 +1283,30 @@ pub(crate) mod tests {
             overlay: Overlay::None,
             imposters: Vec::new(),
             current_imposter: None,
+            stub_row_cache: Default::default(),
+            request_log: None,
+            inspected_request: None,
+            inspector_scroll: 0,
+            stub_detail_scroll: 0,
+            diff_scroll: 0,
+            composer: None,
+            imposter_form: None,
+            proxy_wizard: None,
+            scenario: None,
+            transfer_preview: None,
+            copy_menu: None,
+            pending_screenshot: None,
+            confirmations: crate::confirm::Confirmations::default(),
+            stub_hit_baseline: HashMap::new(),
+            accessible: false,
+            theme_before_accessible: None,
+            script_editor: None,
+            server_log: None,
+            palette: CommandPalette::default(),
             metrics: MetricsData::default(),
             metrics_history: VecDeque::new(),
+            metrics_window: MetricsWindow::default(),
+            metrics_selected: 0,
             imposter_list_state: ListState::default(),
             stub_list_state: ListState::default(),
             request_list_state: ListState::default(),
@@ -829,11 +1316,16 @@ pub(crate) mod tests {
             errors_scroll: 0,
             search_active: false,
             search_query: String::new(),
+            search_global: false,
+            global_search: None,
+            imposter_sort: ImposterSort::default(),
+            imposter_filter: ImposterFilter::default(),
+            imposter_activity: HashMap::new(),
+            marked_imposters: BTreeSet::new(),
+            marked_stubs: BTreeSet::new(),
+            mark_anchor: None,
             stub_editor: None,
-            input_state: InputState {
-                protocol: "http".to_string(),
-                ..Default::default()
-            },
+            input_state: InputState::default(),
             export_scroll_offset: 0,
             validation_scroll_offset: 0,
             help_scroll: 0,
@@ -841,16 +1333,71 @@ pub(crate) mod tests {
             server_config: None,
             client: ApiClient::new("http://localhost:2525"),
             admin_url: "http://localhost:2525".to_string(),
+            servers: Vec::new(),
+            active_server: Some(0),
             theme: Theme::default(),
+            themes: Themes::default(),
+            state_path: None,
+            keys: KeyMap::default(),
+            journal: Journal::default(),
             should_quit: false,
             is_loading: false,
             is_connected: false,
             last_refresh: Instant::now(),
+            last_updated: None,
+            reconnect: Reconnect::default(),
             start_time: Instant::now(),
             refresh_interval: Duration::from_secs(5),
         }
     }
 
+    /// Answer each connection to a local port with `200` and the next of `bodies`. Returns the
+    /// admin URL and each request's line and body.
+    pub(crate) fn answer_json(
+        bodies: Vec<String>,
+    ) -> (String, std::thread::JoinHandle<Vec<(String, String)>>) {
+        use std::io::{BufRead, BufReader, Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for body in bodies {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim_end().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':')
+                        && name.eq_ignore_ascii_case("content-length")
+                    {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut request_body = vec![0; length];
+                reader.read_exact(&mut request_body).unwrap();
+                requests.push((
+                    request_line.trim_end().to_string(),
+                    String::from_utf8(request_body).unwrap(),
+                ));
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                     content-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (url, server)
+    }
+
     pub(crate) fn make_imposter(port: u16, name: Option<&str>, protocol: &str) -> ImposterSummary {
         ImposterSummary {
             port,
@@ -860,6 +1407,7 @@ pub(crate) mod tests {
             stub_count: 0,
             enabled: true,
             record_requests: false,
+            server: None,
         }
     }
 
@@ -947,25 +1495,6 @@ pub(crate) mod tests {
         assert_eq!(sel.port, 4546);
     }
 
-    // ─── InputState ───────────────────────────────────────────────────────────
-
-    #[test]
-    fn test_input_state_proxy_mode_str() {
-        let cases = [
-            (0, "proxyOnce"),
-            (1, "proxyAlways"),
-            (2, "proxyTransparent"),
-            (99, "proxyOnce"),
-        ];
-        for (mode, expected) in cases {
-            let s = InputState {
-                proxy_mode: mode,
-                ..Default::default()
-            };
-            assert_eq!(s.proxy_mode_str(), expected);
-        }
-    }
-
     // ─── StubEditor ───────────────────────────────────────────────────────────
 
     #[test]
@@ -981,6 +1510,65 @@ pub(crate) mod tests {
         assert!(editor.validation_error.is_some());
     }
 
+    #[test]
+    fn test_stub_editor_places_lint_issues_on_their_lines() {
+        let json = "{\n  \"predicates\": [{\"equal\": {\"path\": \"/a\"}}],\n  \"responses\": [{\"is\": {\n    \"statusCode\": 999\n  }}]\n}";
+        let editor = StubEditor::new(json);
+        let lines: Vec<usize> = editor.line_issues.iter().map(|i| i.line).collect();
+        assert_eq!(lines, [1, 1, 3], "{:?}", editor.line_issues);
+        assert!(editor.line_issues[0].message.contains("equal"));
+        assert!(editor.line_issues[2].message.starts_with("E015"));
+        // The cursor starts on line 0, which has no issue, so the first one is shown.
+        assert_eq!(editor.current_issue().unwrap().line, 1);
+    }
+
+    #[test]
+    fn test_stub_editor_places_json_errors_on_their_line() {
+        let editor = StubEditor::new("{\n  \"a\": 1,\n  oops\n}");
+        assert_eq!(editor.line_issues.len(), 1);
+        assert_eq!(editor.line_issues[0].line, 2);
+    }
+
+    #[test]
+    fn test_stub_editor_viewport_follows_the_cursor() {
+        let json = (0..30)
+            .map(|i| format!("{i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut editor = StubEditor::new(&json);
+        assert_eq!(editor.viewport_top(10), 0);
+        editor
+            .editor
+            .move_cursor(ratatui_textarea::CursorMove::Jump(15, 0));
+        assert_eq!(editor.viewport_top(10), 6);
+        editor
+            .editor
+            .move_cursor(ratatui_textarea::CursorMove::Jump(10, 0));
+        assert_eq!(editor.viewport_top(10), 6);
+        editor
+            .editor
+            .move_cursor(ratatui_textarea::CursorMove::Jump(2, 0));
+        assert_eq!(editor.viewport_top(10), 2);
+    }
+
+    #[tokio::test]
+    async fn test_save_stub_with_lint_errors_warns_first() {
+        let mut app = make_test_app();
+        app.stub_editor = Some(StubEditor::new(
+            "{\n  \"responses\": [{\"is\": {\"statusCode\": 999}}]\n}",
+        ));
+        app.save_stub().await;
+
+        let (message, level, _) = app.status_message.clone().unwrap();
+        assert_eq!(level, StatusLevel::Warning);
+        assert!(message.contains("line 2: E015"), "{message}");
+        let editor = app.stub_editor.as_ref().unwrap();
+        assert_eq!(
+            editor.acknowledged_errors.as_deref(),
+            Some(editor.editor.lines().join("\n").as_str())
+        );
+    }
+
     #[test]
     fn test_stub_editor_get_stub_returns_none_on_invalid_json() {
         let editor = StubEditor::new("{bad json}");
@@ -0,0 +1,321 @@
+//! Command palette: every action the current view has, plus themes, servers and jumping to an
+//! imposter by port, found by typing part of its name
+
+use super::*;
+
+/// What a palette entry does
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaletteCommand {
+    /// Run an action as if its key had been pressed
+    Key(KeyEvent),
+    /// Switch to the theme with this name
+    Theme(String),
+    /// Switch to this server, or list every server's imposters with `None`
+    Server(Option<usize>),
+    /// Open the imposter on this port
+    GoToPort(u16),
+    /// Turn accessibility mode on or off
+    Accessibility,
+}
+
+/// One row of the palette
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteEntry {
+    pub label: String,
+    /// The keys that do the same, shown alongside
+    pub keys: String,
+    pub command: PaletteCommand,
+}
+
+/// State of the command palette while it is open
+#[derive(Debug, Default)]
+pub struct CommandPalette {
+    pub query: String,
+    pub selected: usize,
+}
+
+/// How well `query` matches `text`: its characters must appear in order, ignoring case. Matches
+/// at the start of a word and runs of adjacent characters score higher. `None` when it doesn't
+/// match at all.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut from = 0;
+    let mut previous: Option<usize> = None;
+    for wanted in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = from + text[from..].iter().position(|&c| c == wanted)?;
+        score += 1;
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 2;
+        }
+        previous = Some(found);
+        from = found + 1;
+    }
+    Some(score)
+}
+
+/// The port a query like `4545` or `:4545` names
+fn query_port(query: &str) -> Option<u16> {
+    let query = query.trim();
+    query
+        .strip_prefix(':')
+        .unwrap_or(query)
+        .parse()
+        .ok()
+        .filter(|&port| port > 0)
+}
+
+impl App {
+    pub(super) fn open_command_palette(&mut self) {
+        self.palette = CommandPalette::default();
+        self.overlay = Overlay::CommandPalette;
+    }
+
+    /// Everything the palette can do from the current view
+    fn palette_entries(&self) -> Vec<PaletteEntry> {
+        let mut entries: Vec<PaletteEntry> = self
+            .keys
+            .live_actions(self.view.key_context())
+            .into_iter()
+            .filter(|(action, _, _)| {
+                action.context != Context::Navigation && action.name != "palette"
+            })
+            .map(|(action, keys, key)| PaletteEntry {
+                label: action.description.to_string(),
+                keys,
+                command: PaletteCommand::Key(key),
+            })
+            .collect();
+        entries.extend(self.themes.all().iter().map(|theme| PaletteEntry {
+            label: format!("Theme: {}", theme.name),
+            keys: String::new(),
+            command: PaletteCommand::Theme(theme.name.clone()),
+        }));
+        entries.push(PaletteEntry {
+            label: format!(
+                "Accessibility mode: turn {}",
+                if self.accessible { "off" } else { "on" }
+            ),
+            keys: String::new(),
+            command: PaletteCommand::Accessibility,
+        });
+        if self.servers.len() > 1 {
+            entries.extend(
+                self.servers
+                    .iter()
+                    .enumerate()
+                    .map(|(index, server)| PaletteEntry {
+                        label: format!("Server: {}", server.profile.name),
+                        keys: String::new(),
+                        command: PaletteCommand::Server(Some(index)),
+                    }),
+            );
+            entries.push(PaletteEntry {
+                label: "Server: all servers".to_string(),
+                keys: String::new(),
+                command: PaletteCommand::Server(None),
+            });
+        }
+        entries
+    }
+
+    /// The entries matching the palette's query, best first. A port number offers to open that
+    /// imposter.
+    pub fn palette_matches(&self) -> Vec<PaletteEntry> {
+        let query = self.palette.query.as_str();
+        let mut scored: Vec<(u32, PaletteEntry)> = self
+            .palette_entries()
+            .into_iter()
+            .filter_map(|entry| fuzzy_score(query, &entry.label).map(|score| (score, entry)))
+            .collect();
+        // Stable, so equal scores keep the order actions are listed in
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        let mut matches: Vec<PaletteEntry> = scored.into_iter().map(|(_, entry)| entry).collect();
+        if let Some(port) = query_port(query) {
+            let name = self
+                .imposters
+                .iter()
+                .find(|imp| imp.port == port)
+                .and_then(|imp| imp.name.as_deref())
+                .map(|name| format!(" ({name})"))
+                .unwrap_or_default();
+            matches.insert(
+                0,
+                PaletteEntry {
+                    label: format!("Go to imposter :{port}{name}"),
+                    keys: String::new(),
+                    command: PaletteCommand::GoToPort(port),
+                },
+            );
+        }
+        matches
+    }
+
+    pub(super) async fn handle_command_palette_event(&mut self, key: KeyEvent) {
+        let count = self.palette_matches().len();
+        match key.code {
+            KeyCode::Esc => self.overlay = Overlay::None,
+            KeyCode::Down => {
+                self.palette.selected = (self.palette.selected + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Up => self.palette.selected = self.palette.selected.saturating_sub(1),
+            KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.palette.selected = (self.palette.selected + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.palette.selected = self.palette.selected.saturating_sub(1);
+            }
+            KeyCode::Enter => {
+                let Some(entry) = self
+                    .palette_matches()
+                    .into_iter()
+                    .nth(self.palette.selected)
+                else {
+                    return;
+                };
+                self.overlay = Overlay::None;
+                self.run_palette_command(entry.command).await;
+            }
+            KeyCode::Backspace => {
+                self.palette.query.pop();
+                self.palette.selected = 0;
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.palette.query.clear();
+                self.palette.selected = 0;
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.palette.query.push(c);
+                self.palette.selected = 0;
+            }
+            _ => {}
+        }
+    }
+
+    async fn run_palette_command(&mut self, command: PaletteCommand) {
+        match command {
+            PaletteCommand::Key(key) => self.run_key(key).await,
+            PaletteCommand::Theme(name) => {
+                if let Some(theme) = self.themes.find(&name).cloned() {
+                    self.set_theme(theme);
+                }
+            }
+            PaletteCommand::Server(target) => self.switch_server(target).await,
+            PaletteCommand::GoToPort(port) => self.go_to_imposter(port).await,
+            PaletteCommand::Accessibility => self.toggle_accessible(),
+        }
+    }
+
+    /// Open the imposter on `port` from wherever the TUI is
+    pub async fn go_to_imposter(&mut self, port: u16) {
+        let Some(index) = self.imposters.iter().position(|imp| imp.port == port) else {
+            self.set_status(format!("No imposter on :{port}"), StatusLevel::Warning);
+            return;
+        };
+        self.open_imposter_at(index).await;
+    }
+
+    /// Open the imposter at `index` of the list from wherever the TUI is. Returns whether its
+    /// detail view is open.
+    pub(super) async fn open_imposter_at(&mut self, index: usize) -> bool {
+        self.view = View::ImposterList;
+        self.view_stack.clear();
+        self.search_active = false;
+        self.search_query.clear();
+        self.clear_marks();
+        self.imposter_list_state.select(Some(index));
+        if self.showing_all_servers() && !self.enter_selected_imposter_server().await {
+            return false;
+        }
+        self.enter_imposter_detail().await;
+        matches!(self.view, View::ImposterDetail { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::{make_imposter, make_test_app};
+
+    fn type_query(app: &mut App, query: &str) {
+        app.palette.query = query.to_string();
+        app.palette.selected = 0;
+    }
+
+    #[test]
+    fn fuzzy_matching_prefers_word_starts_and_runs() {
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+        assert!(fuzzy_score("xz", "Export imposters").is_none());
+        assert!(
+            fuzzy_score("exp", "Export all").unwrap() > fuzzy_score("exp", "Mexico pass").unwrap()
+        );
+        // "ci" hits the starts of "Create" and "imposter"
+        assert!(
+            fuzzy_score("ci", "Create new imposter").unwrap()
+                > fuzzy_score("ci", "Toggle filtering").unwrap_or(0)
+        );
+        assert!(fuzzy_score("NEW IMP", "Create new imposter").is_some());
+    }
+
+    #[test]
+    fn the_palette_offers_the_views_actions_themes_and_a_port() {
+        let mut app = make_test_app();
+        app.imposters = vec![make_imposter(4545, Some("orders"), "http")];
+        type_query(&mut app, "");
+        let labels: Vec<String> = app.palette_matches().into_iter().map(|e| e.label).collect();
+        assert!(labels.contains(&"Create new imposter".to_string()));
+        assert!(labels.contains(&"Theme: Dracula".to_string()), "{labels:?}");
+        assert!(
+            !labels.contains(&"Add new stub".to_string()),
+            "not in this view"
+        );
+        assert!(!labels.contains(&"Move down".to_string()));
+
+        type_query(&mut app, "export fold");
+        let best = &app.palette_matches()[0];
+        assert_eq!(best.label, "Export imposters to folder");
+        assert_eq!(best.keys, "E");
+
+        type_query(&mut app, ":4545");
+        let best = &app.palette_matches()[0];
+        assert_eq!(best.label, "Go to imposter :4545 (orders)");
+        assert_eq!(best.command, PaletteCommand::GoToPort(4545));
+    }
+
+    #[tokio::test]
+    async fn enter_runs_the_selected_action() {
+        let mut app = make_test_app();
+        app.handle_key_event(KeyEvent::from(KeyCode::Char(':')))
+            .await;
+        assert_eq!(app.overlay, Overlay::CommandPalette);
+        for c in "metrics".chars() {
+            app.handle_key_event(KeyEvent::from(KeyCode::Char(c))).await;
+        }
+        app.handle_key_event(KeyEvent::from(KeyCode::Enter)).await;
+        assert_eq!(app.overlay, Overlay::None);
+        assert_eq!(app.view, View::Metrics);
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char(':')))
+            .await;
+        for c in "theme nord".chars() {
+            app.handle_key_event(KeyEvent::from(KeyCode::Char(c))).await;
+        }
+        app.handle_key_event(KeyEvent::from(KeyCode::Enter)).await;
+        assert_eq!(app.theme.name, "Nord");
+    }
+
+    #[tokio::test]
+    async fn going_to_a_missing_port_says_so() {
+        let mut app = make_test_app();
+        app.view = View::Metrics;
+        app.go_to_imposter(9999).await;
+        assert_eq!(app.view, View::Metrics);
+        assert_eq!(
+            app.status_message.as_ref().unwrap().0,
+            "No imposter on :9999"
+        );
+    }
+}
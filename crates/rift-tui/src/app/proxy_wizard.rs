@@ -0,0 +1,501 @@
+//! Proxy imposter wizard: target, mode, what recorded stubs match on, then a review of the config
+
+use super::*;
+use crate::api::{
+    CreateProxyImposterRequest, PredicateGenerator, ProxyConfig, ProxyResponse, ProxyStub,
+};
+
+/// Proxy modes, with what each does
+pub const PROXY_MODES: [(&str, &str); 3] = [
+    (
+        "proxyOnce",
+        "Record the first response for each request, replay it after",
+    ),
+    (
+        "proxyAlways",
+        "Always forward to the backend, keep recording new responses",
+    ),
+    (
+        "proxyTransparent",
+        "Always forward to the backend, no recording",
+    ),
+];
+
+/// Request fields recorded stubs can match on, as named in `predicateGenerators`
+pub const GENERATOR_FIELDS: [&str; 4] = ["path", "method", "query", "headers"];
+
+/// A step of the wizard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WizardStep {
+    /// Target URL, port and name
+    Target,
+    Mode,
+    /// Predicate generators and `addWaitBehavior`; skipped for `proxyTransparent`
+    Recording,
+    Review,
+}
+
+impl WizardStep {
+    pub const ALL: [WizardStep; 4] = [
+        WizardStep::Target,
+        WizardStep::Mode,
+        WizardStep::Recording,
+        WizardStep::Review,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            WizardStep::Target => "Target",
+            WizardStep::Mode => "Mode",
+            WizardStep::Recording => "Recording",
+            WizardStep::Review => "Review",
+        }
+    }
+}
+
+/// State of the proxy imposter wizard
+#[derive(Debug, Clone)]
+pub struct ProxyWizard {
+    pub step: WizardStep,
+    pub target_url: String,
+    pub port: String,
+    pub name: String,
+    /// Index into [`PROXY_MODES`]
+    pub mode: usize,
+    /// Whether stubs match on each of [`GENERATOR_FIELDS`]
+    pub generators: [bool; 4],
+    /// Comma-separated header names to match on; empty for every header
+    pub header_names: String,
+    pub add_wait_behavior: bool,
+    /// Field with focus in the current step: target, port, name on the first, then the
+    /// generators, the header names and `addWaitBehavior` on the recording step
+    pub focus: usize,
+    pub review_scroll: u16,
+}
+
+/// Focusable rows of the recording step: the generators, header names, `addWaitBehavior`
+const RECORDING_ROWS: usize = GENERATOR_FIELDS.len() + 2;
+const HEADER_NAMES_ROW: usize = GENERATOR_FIELDS.len();
+
+impl Default for ProxyWizard {
+    fn default() -> Self {
+        Self {
+            step: WizardStep::Target,
+            target_url: String::new(),
+            port: String::new(),
+            name: String::new(),
+            mode: 0,
+            // Path, method and query, which tell most API calls apart
+            generators: [true, true, true, false],
+            header_names: String::new(),
+            add_wait_behavior: true,
+            focus: 0,
+            review_scroll: 0,
+        }
+    }
+}
+
+impl ProxyWizard {
+    pub fn mode(&self) -> &'static str {
+        PROXY_MODES
+            .get(self.mode)
+            .map_or("proxyOnce", |(mode, _)| mode)
+    }
+
+    /// Whether the mode records responses, so the recording step applies
+    pub fn records(&self) -> bool {
+        self.mode() != "proxyTransparent"
+    }
+
+    /// The steps this wizard goes through
+    pub fn steps(&self) -> Vec<WizardStep> {
+        WizardStep::ALL
+            .into_iter()
+            .filter(|&step| step != WizardStep::Recording || self.records())
+            .collect()
+    }
+
+    /// The `predicateGenerators` entry for the checked fields, if any are
+    fn predicate_generators(&self) -> Vec<PredicateGenerator> {
+        let mut matches = serde_json::Map::new();
+        for (field, _) in GENERATOR_FIELDS
+            .iter()
+            .zip(self.generators)
+            .filter(|(_, on)| *on)
+        {
+            let names: Vec<&str> = self
+                .header_names
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .collect();
+            let selection = if *field == "headers" && !names.is_empty() {
+                names
+                    .into_iter()
+                    .map(|name| (name.to_string(), serde_json::Value::Bool(true)))
+                    .collect::<serde_json::Map<_, _>>()
+                    .into()
+            } else {
+                serde_json::Value::Bool(true)
+            };
+            matches.insert(field.to_string(), selection);
+        }
+        if matches.is_empty() {
+            return Vec::new();
+        }
+        vec![PredicateGenerator {
+            matches: matches.into(),
+        }]
+    }
+
+    /// What the first step was filled in with, or what is wrong with it
+    fn check_target(&self) -> Result<(), String> {
+        let url = self.target_url.trim();
+        if url.is_empty() {
+            return Err("Target URL is required".to_string());
+        }
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err("Target URL must start with http:// or https://".to_string());
+        }
+        if !self.port.is_empty() && self.port.parse::<u16>().is_err() {
+            return Err("Invalid port number".to_string());
+        }
+        Ok(())
+    }
+
+    /// The imposter to create, or why the form does not describe one
+    pub fn to_request(&self) -> Result<CreateProxyImposterRequest, String> {
+        self.check_target()?;
+        let records = self.records();
+        Ok(CreateProxyImposterRequest {
+            port: self.port.parse().ok(),
+            protocol: "http".to_string(),
+            name: Some(self.name.trim())
+                .filter(|name| !name.is_empty())
+                .map(str::to_string),
+            record_requests: true,
+            stubs: vec![ProxyStub {
+                responses: vec![ProxyResponse {
+                    proxy: ProxyConfig {
+                        to: self.target_url.trim().to_string(),
+                        mode: self.mode().to_string(),
+                        predicate_generators: if records {
+                            self.predicate_generators()
+                        } else {
+                            Vec::new()
+                        },
+                        add_wait_behavior: records && self.add_wait_behavior,
+                    },
+                }],
+            }],
+        })
+    }
+
+    /// Something worth knowing before creating, shown on the review step
+    pub fn review_note(&self) -> Option<&'static str> {
+        (self.records() && !self.generators.contains(&true))
+            .then_some("No fields to match on: every request replays the first recorded response")
+    }
+
+    /// Go to the next step, unless the current one is not filled in right
+    fn advance(&mut self) -> Result<(), String> {
+        if self.step == WizardStep::Target {
+            self.check_target()?;
+        }
+        let steps = self.steps();
+        if let Some(index) = steps.iter().position(|&s| s == self.step)
+            && let Some(&next) = steps.get(index + 1)
+        {
+            self.step = next;
+            self.focus = 0;
+            self.review_scroll = 0;
+        }
+        Ok(())
+    }
+
+    /// Go back a step; false on the first one
+    fn retreat(&mut self) -> bool {
+        let steps = self.steps();
+        match steps.iter().position(|&s| s == self.step) {
+            Some(index) if index > 0 => {
+                self.step = steps[index - 1];
+                self.focus = 0;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The text field with focus, if any
+    fn text_field(&mut self) -> Option<&mut String> {
+        match (self.step, self.focus) {
+            (WizardStep::Target, 0) => Some(&mut self.target_url),
+            (WizardStep::Target, 1) => Some(&mut self.port),
+            (WizardStep::Target, 2) => Some(&mut self.name),
+            (WizardStep::Recording, HEADER_NAMES_ROW) => Some(&mut self.header_names),
+            _ => None,
+        }
+    }
+
+    fn field_count(&self) -> usize {
+        match self.step {
+            WizardStep::Target => 3,
+            WizardStep::Mode => PROXY_MODES.len(),
+            WizardStep::Recording => RECORDING_ROWS,
+            WizardStep::Review => 1,
+        }
+    }
+
+    fn move_focus(&mut self, forward: bool) {
+        let count = self.field_count();
+        if self.step == WizardStep::Mode {
+            self.mode = if forward {
+                (self.mode + 1).min(count - 1)
+            } else {
+                self.mode.saturating_sub(1)
+            };
+        } else {
+            self.focus = (self.focus + if forward { 1 } else { count - 1 }) % count;
+        }
+    }
+
+    fn toggle(&mut self) {
+        match (self.step, self.focus) {
+            (WizardStep::Recording, row) if row < GENERATOR_FIELDS.len() => {
+                self.generators[row] = !self.generators[row];
+            }
+            (WizardStep::Recording, row) if row == RECORDING_ROWS - 1 => {
+                self.add_wait_behavior = !self.add_wait_behavior;
+            }
+            _ => {}
+        }
+    }
+}
+
+impl App {
+    /// Open the proxy imposter wizard
+    pub fn show_create_proxy_imposter(&mut self) {
+        self.proxy_wizard = Some(ProxyWizard::default());
+        self.overlay = Overlay::ProxyWizard;
+    }
+
+    /// Create the proxy imposter the wizard describes
+    pub async fn create_proxy_imposter(&mut self) {
+        let Some(wizard) = &self.proxy_wizard else {
+            return;
+        };
+        let request = match wizard.to_request() {
+            Ok(request) => request,
+            Err(e) => {
+                self.set_status(e, StatusLevel::Error);
+                return;
+            }
+        };
+
+        self.is_loading = true;
+        match self.client.create_proxy_imposter(&request).await {
+            Ok(port) => {
+                self.set_status(
+                    format!("Created proxy imposter :{port}"),
+                    StatusLevel::Success,
+                );
+                self.overlay = Overlay::None;
+                self.proxy_wizard = None;
+                self.refresh().await;
+            }
+            Err(e) => {
+                self.set_status(format!("Failed to create: {e}"), StatusLevel::Error);
+            }
+        }
+        self.is_loading = false;
+    }
+
+    pub(super) async fn handle_proxy_wizard_event(&mut self, key: KeyEvent) {
+        let Some(wizard) = self.proxy_wizard.as_mut() else {
+            self.overlay = Overlay::None;
+            return;
+        };
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        if ctrl && key.code == KeyCode::Char('v') {
+            if let Some(text) = self.paste_from_clipboard()
+                && let Some(wizard) = self.proxy_wizard.as_mut()
+            {
+                let port = wizard.step == WizardStep::Target && wizard.focus == 1;
+                if let Some(field) = wizard.text_field() {
+                    // The port only takes digits
+                    field.extend(text.chars().filter(|c| !port || c.is_ascii_digit()));
+                }
+            }
+            return;
+        }
+        match key.code {
+            KeyCode::Esc => {
+                if wizard.retreat() {
+                    return;
+                }
+                self.overlay = Overlay::None;
+                self.proxy_wizard = None;
+            }
+            KeyCode::Enter if wizard.step == WizardStep::Review => {
+                self.create_proxy_imposter().await;
+            }
+            KeyCode::Enter => {
+                if let Err(e) = wizard.advance() {
+                    self.set_status(e, StatusLevel::Error);
+                }
+            }
+            KeyCode::Down | KeyCode::Tab => wizard.move_focus(true),
+            KeyCode::Up | KeyCode::BackTab => wizard.move_focus(false),
+            KeyCode::PageDown if wizard.step == WizardStep::Review => {
+                wizard.review_scroll = wizard.review_scroll.saturating_add(10);
+            }
+            KeyCode::PageUp if wizard.step == WizardStep::Review => {
+                wizard.review_scroll = wizard.review_scroll.saturating_sub(10);
+            }
+            KeyCode::Backspace => {
+                if let Some(field) = wizard.text_field() {
+                    field.pop();
+                }
+            }
+            KeyCode::Char(c) if !ctrl => {
+                let port = wizard.step == WizardStep::Target && wizard.focus == 1;
+                match wizard.text_field() {
+                    Some(field) if !port || c.is_ascii_digit() => field.push(c),
+                    Some(_) => {}
+                    None => match c {
+                        ' ' => wizard.toggle(),
+                        'j' => wizard.move_focus(true),
+                        'k' => wizard.move_focus(false),
+                        _ => {}
+                    },
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::make_test_app;
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::from(code)
+    }
+
+    async fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            app.handle_proxy_wizard_event(press(KeyCode::Char(c))).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn the_wizard_builds_predicate_generators_from_the_checked_fields() {
+        let mut app = make_test_app();
+        app.show_create_proxy_imposter();
+        assert_eq!(app.overlay, Overlay::ProxyWizard);
+
+        // The target is required before moving on
+        app.handle_proxy_wizard_event(press(KeyCode::Enter)).await;
+        assert_eq!(app.proxy_wizard.as_ref().unwrap().step, WizardStep::Target);
+        type_text(&mut app, "https://api.example.com").await;
+        app.handle_proxy_wizard_event(press(KeyCode::Tab)).await;
+        type_text(&mut app, "45x46").await;
+        app.handle_proxy_wizard_event(press(KeyCode::Enter)).await;
+
+        // proxyAlways
+        app.handle_proxy_wizard_event(press(KeyCode::Down)).await;
+        app.handle_proxy_wizard_event(press(KeyCode::Enter)).await;
+        assert_eq!(
+            app.proxy_wizard.as_ref().unwrap().step,
+            WizardStep::Recording
+        );
+
+        // Untick query, tick headers and name two of them, drop addWaitBehavior
+        for key in [KeyCode::Down, KeyCode::Down] {
+            app.handle_proxy_wizard_event(press(key)).await;
+        }
+        type_text(&mut app, " j ").await;
+        app.handle_proxy_wizard_event(press(KeyCode::Down)).await;
+        type_text(&mut app, "Accept, X-Tenant").await;
+        app.handle_proxy_wizard_event(press(KeyCode::Down)).await;
+        type_text(&mut app, " ").await;
+        app.handle_proxy_wizard_event(press(KeyCode::Enter)).await;
+
+        let wizard = app.proxy_wizard.as_ref().unwrap();
+        assert_eq!(wizard.step, WizardStep::Review);
+        assert_eq!(wizard.review_note(), None);
+        let request = serde_json::to_value(wizard.to_request().unwrap()).unwrap();
+        assert_eq!(request["port"], 4546);
+        let proxy = &request["stubs"][0]["responses"][0]["proxy"];
+        assert_eq!(proxy["to"], "https://api.example.com");
+        assert_eq!(proxy["mode"], "proxyAlways");
+        assert_eq!(
+            proxy["predicateGenerators"],
+            serde_json::json!([{"matches": {
+                "path": true,
+                "method": true,
+                "headers": {"Accept": true, "X-Tenant": true}
+            }}])
+        );
+        assert!(proxy.get("addWaitBehavior").is_none());
+
+        // Esc steps back rather than closing
+        app.handle_proxy_wizard_event(press(KeyCode::Esc)).await;
+        assert_eq!(
+            app.proxy_wizard.as_ref().unwrap().step,
+            WizardStep::Recording
+        );
+    }
+
+    #[test]
+    fn transparent_proxies_skip_the_recording_step() {
+        let wizard = ProxyWizard {
+            target_url: "http://backend:8080".to_string(),
+            mode: 2,
+            ..ProxyWizard::default()
+        };
+        assert_eq!(
+            wizard.steps(),
+            [WizardStep::Target, WizardStep::Mode, WizardStep::Review]
+        );
+        let request = serde_json::to_value(wizard.to_request().unwrap()).unwrap();
+        let proxy = &request["stubs"][0]["responses"][0]["proxy"];
+        assert_eq!(proxy["mode"], "proxyTransparent");
+        assert!(proxy.get("predicateGenerators").is_none());
+        assert!(proxy.get("addWaitBehavior").is_none());
+
+        let no_fields = ProxyWizard {
+            generators: [false; 4],
+            ..ProxyWizard::default()
+        };
+        assert!(no_fields.review_note().is_some());
+        assert_eq!(
+            ProxyWizard {
+                target_url: "backend:8080".to_string(),
+                ..ProxyWizard::default()
+            }
+            .to_request()
+            .unwrap_err(),
+            "Target URL must start with http:// or https://"
+        );
+    }
+
+    #[test]
+    fn modes_have_their_mountebank_names() {
+        let cases = [
+            (0, "proxyOnce"),
+            (1, "proxyAlways"),
+            (2, "proxyTransparent"),
+            (99, "proxyOnce"),
+        ];
+        for (mode, expected) in cases {
+            let wizard = ProxyWizard {
+                mode,
+                ..ProxyWizard::default()
+            };
+            assert_eq!(wizard.mode(), expected);
+        }
+    }
+}
@@ -0,0 +1,531 @@
+//! Live request log: a polled tail of an imposter's served requests
+
+use super::*;
+use crate::api::{ServedMessage, ServedRequest, ServedRequestDetail, ServedRequestsResponse};
+
+/// How many served requests the log pane keeps
+pub const MAX_REQUEST_LOG_ENTRIES: usize = 500;
+
+/// State of the request log view for one imposter
+#[derive(Debug, Default)]
+pub struct RequestLog {
+    pub port: u16,
+    pub entries: VecDeque<ServedRequest>,
+    /// Sequence number of the newest request seen, sent back as `?since=`
+    pub cursor: Option<u64>,
+    /// While paused the log is not polled, so the pane holds still
+    pub paused: bool,
+    /// Keep the newest request selected as new ones arrive
+    pub follow: bool,
+    pub list_state: ListState,
+}
+
+impl RequestLog {
+    pub fn new(port: u16) -> Self {
+        Self {
+            port,
+            follow: true,
+            ..Default::default()
+        }
+    }
+
+    /// Append one poll's worth of requests. Returns how many requests the server served but no
+    /// longer had when asked, because the log was paused or polled too slowly.
+    pub fn append(&mut self, response: ServedRequestsResponse) -> u64 {
+        // A count below the cursor means the server's log was cleared: start over.
+        if self.cursor.is_some_and(|cursor| response.count < cursor) {
+            self.entries.clear();
+            self.cursor = None;
+        }
+        let missed = match (self.cursor, response.served_requests.first()) {
+            (Some(cursor), Some(first)) => first.seq.saturating_sub(cursor + 1),
+            _ => 0,
+        };
+        if let Some(last) = response.served_requests.last() {
+            self.cursor = Some(last.seq);
+        } else if self.cursor.is_none() {
+            self.cursor = Some(response.count);
+        }
+        for request in response.served_requests {
+            while self.entries.len() >= MAX_REQUEST_LOG_ENTRIES {
+                self.entries.pop_front();
+            }
+            self.entries.push_back(request);
+        }
+        missed
+    }
+}
+
+impl App {
+    /// Open the request log for an imposter, keeping what was already collected for it
+    pub(super) async fn open_request_log(&mut self, port: u16) {
+        if self.request_log.as_ref().is_none_or(|log| log.port != port) {
+            self.request_log = Some(RequestLog::new(port));
+        }
+        self.navigate(View::RequestLog { port });
+        self.poll_request_log().await;
+    }
+
+    /// Fetch the requests served since the last poll, unless the log is paused
+    pub(super) async fn poll_request_log(&mut self) {
+        let Some((port, cursor)) = self
+            .request_log
+            .as_ref()
+            .filter(|log| !log.paused)
+            .map(|log| (log.port, log.cursor))
+        else {
+            return;
+        };
+        match self.client.get_served_requests(port, cursor).await {
+            Ok(response) => {
+                let missed = self
+                    .request_log
+                    .as_mut()
+                    .map_or(0, |log| log.append(response));
+                if missed > 0 {
+                    self.set_status(
+                        format!("{missed} requests were served too fast to show"),
+                        StatusLevel::Warning,
+                    );
+                }
+            }
+            Err(e) => self.set_status(format!("Failed to load requests: {e}"), StatusLevel::Error),
+        }
+    }
+
+    /// Pause or resume polling the request log
+    pub(super) async fn toggle_request_log_pause(&mut self) {
+        let Some(log) = self.request_log.as_mut() else {
+            return;
+        };
+        log.paused = !log.paused;
+        if log.paused {
+            self.set_status("Request log paused".to_string(), StatusLevel::Info);
+        } else {
+            self.set_status("Request log resumed".to_string(), StatusLevel::Info);
+            self.poll_request_log().await;
+        }
+    }
+
+    /// Requests in the log whose path matches the search query
+    pub fn filtered_request_log(&self) -> Vec<&ServedRequest> {
+        let Some(log) = &self.request_log else {
+            return vec![];
+        };
+        let query = self.search_query.to_lowercase();
+        log.entries
+            .iter()
+            .filter(|request| query.is_empty() || request.path.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    /// Move the request log selection by `delta` rows; moving stops following the tail
+    pub(super) fn scroll_request_log(&mut self, delta: isize) {
+        let len = self.filtered_request_log().len();
+        let Some(log) = self.request_log.as_mut() else {
+            return;
+        };
+        if len == 0 {
+            return;
+        }
+        let current = if log.follow {
+            len - 1
+        } else {
+            log.list_state.selected().unwrap_or(len - 1).min(len - 1)
+        };
+        let next = current.saturating_add_signed(delta).min(len - 1);
+        log.follow = next == len - 1;
+        log.list_state.select(Some(next));
+    }
+
+    /// Jump back to the newest request and keep following
+    pub(super) fn follow_request_log(&mut self) {
+        if let Some(log) = self.request_log.as_mut() {
+            log.follow = true;
+            log.list_state.select(None);
+        }
+    }
+
+    /// The request under the cursor, or the newest one while following
+    pub fn selected_served_request(&self) -> Option<&ServedRequest> {
+        let requests = self.filtered_request_log();
+        let log = self.request_log.as_ref()?;
+        let index = match log.list_state.selected() {
+            Some(index) if !log.follow => index.min(requests.len().checked_sub(1)?),
+            _ => requests.len().checked_sub(1)?,
+        };
+        requests.get(index).copied()
+    }
+
+    /// Open the selected request's headers and bodies in the inspector
+    async fn inspect_selected_request(&mut self) {
+        let Some(port) = self.request_log.as_ref().map(|log| log.port) else {
+            return;
+        };
+        let Some(seq) = self.selected_served_request().map(|request| request.seq) else {
+            return;
+        };
+        match self.client.get_served_request(port, seq).await {
+            Ok(detail) => {
+                self.inspected_request = Some(detail);
+                self.inspector_scroll = 0;
+                self.overlay = Overlay::RequestInspector { port };
+            }
+            Err(e) => self.set_status(
+                format!("Failed to load request #{seq}: {e}"),
+                StatusLevel::Error,
+            ),
+        }
+    }
+
+    /// Send the inspected request again, to the imposter on `port`
+    pub(super) async fn replay_inspected_request(&mut self, port: u16) {
+        let Some(detail) = &self.inspected_request else {
+            return;
+        };
+        let request = match detail.replay_request() {
+            Ok(request) => request,
+            Err(e) => {
+                self.set_status(format!("Cannot replay: {e}"), StatusLevel::Error);
+                return;
+            }
+        };
+        match self.client.send_to_imposter(port, &request).await {
+            Ok(response) => self.set_status(
+                format!(
+                    "Replayed {} {} to :{port} → {} in {}ms",
+                    request.method, request.path, response.status, response.latency_ms
+                ),
+                StatusLevel::Success,
+            ),
+            Err(e) => self.set_status(format!("Replay to :{port} failed: {e}"), StatusLevel::Error),
+        }
+        if self
+            .request_log
+            .as_ref()
+            .is_some_and(|log| log.port == port)
+        {
+            self.poll_request_log().await;
+        }
+    }
+
+    pub(super) async fn handle_request_inspector_event(&mut self, key: KeyEvent, port: u16) {
+        let max_scroll = self
+            .inspected_request
+            .as_ref()
+            .map_or(0, |detail| inspector_line_count(detail).saturating_sub(10))
+            as u16;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.overlay = Overlay::None;
+                self.inspected_request = None;
+                self.inspector_scroll = 0;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.inspector_scroll = self.inspector_scroll.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.inspector_scroll = (self.inspector_scroll + 1).min(max_scroll);
+            }
+            KeyCode::PageUp => {
+                self.inspector_scroll = self.inspector_scroll.saturating_sub(10);
+            }
+            KeyCode::PageDown => {
+                self.inspector_scroll = (self.inspector_scroll + 10).min(max_scroll);
+            }
+            KeyCode::Char('s') => self.replay_inspected_request(port).await,
+            KeyCode::Char('y') => self.show_copy_menu(),
+            KeyCode::Char('e') => {
+                let Some(detail) = &self.inspected_request else {
+                    return;
+                };
+                match detail.replay_request() {
+                    Ok(request) => {
+                        self.composer = Some(Composer::from_request(port, &request));
+                        self.overlay = Overlay::Composer;
+                    }
+                    Err(e) => self.set_status(format!("Cannot edit: {e}"), StatusLevel::Error),
+                }
+            }
+            KeyCode::Char('P') => {
+                self.input_state = InputState {
+                    port: port.to_string(),
+                    ..Default::default()
+                };
+                self.overlay = Overlay::Input {
+                    prompt: "Replay to Port".to_string(),
+                    action: InputAction::ReplayToPort { from: port },
+                };
+            }
+            _ => {}
+        }
+    }
+
+    /// The port prompt of "replay to another imposter"; closing it returns to the inspector
+    pub(super) async fn handle_replay_port_input(&mut self, key: KeyEvent, from: u16) {
+        match key.code {
+            KeyCode::Esc => self.overlay = Overlay::RequestInspector { port: from },
+            KeyCode::Enter => match self.input_state.port.parse::<u16>() {
+                Ok(port) if port > 0 => {
+                    self.overlay = Overlay::RequestInspector { port: from };
+                    self.replay_inspected_request(port).await;
+                }
+                _ => self.set_status("Enter a valid port".to_string(), StatusLevel::Warning),
+            },
+            KeyCode::Backspace => {
+                self.input_state.port.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() && self.input_state.port.len() < 5 => {
+                self.input_state.port.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    pub(super) async fn handle_request_log_event(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => self.inspect_selected_request().await,
+            KeyCode::Char('j') | KeyCode::Down => self.scroll_request_log(1),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll_request_log(-1),
+            KeyCode::PageDown => self.scroll_request_log(10),
+            KeyCode::PageUp => self.scroll_request_log(-10),
+            KeyCode::Char('G') | KeyCode::End => self.follow_request_log(),
+            KeyCode::Char('p') | KeyCode::Char(' ') => self.toggle_request_log_pause().await,
+            KeyCode::Char('c') => {
+                if let Some(log) = self.request_log.as_mut() {
+                    log.entries.clear();
+                    log.list_state.select(None);
+                    log.follow = true;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The inspector's sections, each a title and its lines
+pub fn inspector_sections(detail: &ServedRequestDetail) -> Vec<(&'static str, Vec<String>)> {
+    vec![
+        ("REQUEST HEADERS", header_lines(&detail.request)),
+        ("REQUEST BODY", body_lines(&detail.request)),
+        ("RESPONSE HEADERS", header_lines(&detail.response)),
+        ("RESPONSE BODY", body_lines(&detail.response)),
+    ]
+}
+
+/// Lines the inspector shows for a request: a summary line, then each section and a blank line
+fn inspector_line_count(detail: &ServedRequestDetail) -> usize {
+    2 + inspector_sections(detail)
+        .iter()
+        .map(|(_, lines)| lines.len() + 2)
+        .sum::<usize>()
+}
+
+fn header_lines(message: &ServedMessage) -> Vec<String> {
+    let mut lines: Vec<String> = message
+        .headers
+        .iter()
+        .flat_map(|(name, values)| values.iter().map(move |value| format!("{name}: {value}")))
+        .collect();
+    lines.sort();
+    if lines.is_empty() {
+        lines.push("(none)".to_string());
+    }
+    lines
+}
+
+fn body_lines(message: &ServedMessage) -> Vec<String> {
+    if let Some(size) = message.body_omitted {
+        return vec![format!("({size} bytes, too large to keep)")];
+    }
+    let Some(body) = message.body.as_deref().filter(|body| !body.is_empty()) else {
+        return vec!["(empty)".to_string()];
+    };
+    if message.is_binary() {
+        let size = message.body_bytes().map_or(0, |bytes| bytes.len());
+        return vec![format!("({size} bytes of binary data)")];
+    }
+    text_body_lines(body)
+}
+
+/// A text body's lines: JSON pretty-printed, anything else as sent
+pub(super) fn text_body_lines(body: &str) -> Vec<String> {
+    match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(json) => serde_json::to_string_pretty(&json)
+            .unwrap_or_else(|_| body.to_string())
+            .lines()
+            .map(str::to_string)
+            .collect(),
+        Err(_) => body.lines().map(str::to_string).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::make_test_app;
+
+    fn served(seq: u64, path: &str) -> ServedRequest {
+        ServedRequest {
+            seq,
+            method: "GET".to_string(),
+            path: path.to_string(),
+            query: None,
+            status_code: 200,
+            stub_index: Some(0),
+            stub_id: None,
+            latency_ms: 1,
+            timestamp: String::new(),
+        }
+    }
+
+    fn poll(count: u64, served_requests: Vec<ServedRequest>) -> ServedRequestsResponse {
+        ServedRequestsResponse {
+            count,
+            served_requests,
+        }
+    }
+
+    #[test]
+    fn append_advances_the_cursor_and_reports_a_gap() {
+        let mut log = RequestLog::new(4545);
+        assert_eq!(log.append(poll(0, vec![])), 0);
+        assert_eq!(log.cursor, Some(0));
+
+        assert_eq!(
+            log.append(poll(2, vec![served(1, "/a"), served(2, "/b")])),
+            0
+        );
+        assert_eq!(log.cursor, Some(2));
+
+        // Requests 3 and 4 fell out of the server's history before this poll.
+        assert_eq!(log.append(poll(5, vec![served(5, "/e")])), 2);
+        assert_eq!(log.cursor, Some(5));
+        assert_eq!(log.entries.len(), 3);
+    }
+
+    #[test]
+    fn append_starts_over_when_the_server_log_was_cleared() {
+        let mut log = RequestLog::new(4545);
+        log.append(poll(3, vec![served(3, "/c")]));
+        assert_eq!(log.append(poll(1, vec![served(1, "/new")])), 0);
+        assert_eq!(log.entries.len(), 1);
+        assert_eq!(log.entries[0].path, "/new");
+        assert_eq!(log.cursor, Some(1));
+    }
+
+    #[test]
+    fn append_keeps_only_the_newest_entries() {
+        let mut log = RequestLog::new(4545);
+        let requests = (1..=MAX_REQUEST_LOG_ENTRIES as u64 + 5)
+            .map(|seq| served(seq, "/x"))
+            .collect();
+        log.append(poll(MAX_REQUEST_LOG_ENTRIES as u64 + 5, requests));
+        assert_eq!(log.entries.len(), MAX_REQUEST_LOG_ENTRIES);
+        assert_eq!(log.entries[0].seq, 6);
+    }
+
+    #[test]
+    fn filter_matches_the_path_case_insensitively() {
+        let mut app = make_test_app();
+        let mut log = RequestLog::new(4545);
+        log.append(poll(
+            3,
+            vec![
+                served(1, "/Orders/1"),
+                served(2, "/health"),
+                served(3, "/orders"),
+            ],
+        ));
+        app.request_log = Some(log);
+        app.search_query = "orders".to_string();
+        let paths: Vec<&str> = app
+            .filtered_request_log()
+            .iter()
+            .map(|r| r.path.as_str())
+            .collect();
+        assert_eq!(paths, ["/Orders/1", "/orders"]);
+    }
+
+    #[test]
+    fn scrolling_up_stops_following_and_end_resumes() {
+        let mut app = make_test_app();
+        let mut log = RequestLog::new(4545);
+        log.append(poll(
+            3,
+            vec![served(1, "/a"), served(2, "/b"), served(3, "/c")],
+        ));
+        app.request_log = Some(log);
+
+        app.scroll_request_log(-1);
+        let log = app.request_log.as_ref().unwrap();
+        assert!(!log.follow);
+        assert_eq!(log.list_state.selected(), Some(1));
+
+        app.scroll_request_log(5);
+        assert!(app.request_log.as_ref().unwrap().follow);
+
+        app.scroll_request_log(-2);
+        app.follow_request_log();
+        assert!(app.request_log.as_ref().unwrap().follow);
+    }
+
+    #[test]
+    fn selection_falls_back_to_the_newest_request_while_following() {
+        let mut app = make_test_app();
+        let mut log = RequestLog::new(4545);
+        log.append(poll(2, vec![served(1, "/a"), served(2, "/b")]));
+        app.request_log = Some(log);
+        assert_eq!(app.selected_served_request().unwrap().seq, 2);
+        app.scroll_request_log(-1);
+        assert_eq!(app.selected_served_request().unwrap().seq, 1);
+    }
+
+    #[test]
+    fn inspector_pretty_prints_json_and_summarizes_other_bodies() {
+        let detail = ServedRequestDetail {
+            summary: served(1, "/a"),
+            request: ServedMessage {
+                headers: HashMap::from([("Accept".to_string(), vec!["*/*".to_string()])]),
+                body: Some(r#"{"a":1}"#.to_string()),
+                ..Default::default()
+            },
+            response: ServedMessage {
+                body: Some("/wA=".to_string()),
+                mode: Some("binary".to_string()),
+                ..Default::default()
+            },
+        };
+        let sections = inspector_sections(&detail);
+        assert_eq!(sections[0].1, ["Accept: */*"]);
+        assert_eq!(sections[1].1, ["{", "  \"a\": 1", "}"]);
+        assert_eq!(sections[2].1, ["(none)"]);
+        assert_eq!(sections[3].1, ["(2 bytes of binary data)"]);
+    }
+
+    #[tokio::test]
+    async fn replay_port_prompt_returns_to_the_inspector() {
+        let mut app = make_test_app();
+        app.overlay = Overlay::Input {
+            prompt: String::new(),
+            action: InputAction::ReplayToPort { from: 4545 },
+        };
+        app.handle_replay_port_input(KeyEvent::from(KeyCode::Char('x')), 4545)
+            .await;
+        assert!(app.input_state.port.is_empty());
+        app.handle_replay_port_input(KeyEvent::from(KeyCode::Esc), 4545)
+            .await;
+        assert_eq!(app.overlay, Overlay::RequestInspector { port: 4545 });
+    }
+
+    #[tokio::test]
+    async fn a_paused_log_is_not_polled() {
+        let mut app = make_test_app();
+        let mut log = RequestLog::new(4545);
+        log.paused = true;
+        app.request_log = Some(log);
+        // Polling would fail against the unreachable test server and set an error status.
+        app.poll_request_log().await;
+        assert!(app.status_message.is_none());
+    }
+}
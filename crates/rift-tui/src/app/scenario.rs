@@ -0,0 +1,493 @@
+//! Scenario runner: a file of requests with the status each should get, sent in order to the
+//! marked (or selected) imposters, passing or failing step by step, as a smoke test of a mock
+//! environment
+//!
+//! ```json
+//! {
+//!   "name": "checkout",
+//!   "steps": [
+//!     { "name": "list orders", "path": "/orders", "expectStatus": 200 },
+//!     { "method": "POST", "path": "/orders", "body": { "sku": "tea" }, "expectStatus": 201 },
+//!     { "path": "/health", "port": 4550 }
+//!   ]
+//! }
+//! ```
+
+use super::*;
+use crate::api::ImposterResponse;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// A scenario file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioFile {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub steps: Vec<ScenarioStep>,
+}
+
+/// One request of a scenario and the status it should get
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ScenarioStep {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default = "default_method")]
+    pub method: String,
+    /// Path with an optional query string
+    pub path: String,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Sent as is when a string, as JSON otherwise
+    #[serde(default)]
+    pub body: Option<serde_json::Value>,
+    /// Imposter to send it to, instead of each one the scenario runs against
+    #[serde(default)]
+    pub port: Option<u16>,
+    #[serde(default = "default_status")]
+    pub expect_status: u16,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+fn default_status() -> u16 {
+    200
+}
+
+impl ScenarioStep {
+    /// The step's name, or its method and path
+    pub fn label(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("{} {}", self.method, self.path))
+    }
+
+    fn request(&self) -> ImposterRequest {
+        let mut headers: Vec<(String, String)> = self
+            .headers
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        let body = match &self.body {
+            None => Vec::new(),
+            Some(serde_json::Value::String(text)) => text.clone().into_bytes(),
+            Some(json) => {
+                if !headers
+                    .iter()
+                    .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+                {
+                    headers.push(("Content-Type".to_string(), "application/json".to_string()));
+                }
+                json.to_string().into_bytes()
+            }
+        };
+        ImposterRequest {
+            method: self.method.to_uppercase(),
+            path: self.path.clone(),
+            headers,
+            body,
+        }
+    }
+}
+
+/// Read a scenario, saying what is wrong with it if it cannot be run
+pub fn parse_scenario(text: &str) -> Result<ScenarioFile, String> {
+    let scenario: ScenarioFile = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    if scenario.steps.is_empty() {
+        return Err("it has no steps".to_string());
+    }
+    if let Some((index, step)) = scenario
+        .steps
+        .iter()
+        .enumerate()
+        .find(|(_, step)| !step.path.starts_with('/'))
+    {
+        return Err(format!(
+            "step {} has path \"{}\", which must start with /",
+            index + 1,
+            step.path
+        ));
+    }
+    Ok(scenario)
+}
+
+/// How a step went
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepOutcome {
+    /// Got the expected status
+    Passed { status: u16, latency_ms: u64 },
+    /// Got some other status
+    Failed { status: u16, latency_ms: u64 },
+    /// The request could not be sent, or got no answer
+    Error(String),
+}
+
+impl StepOutcome {
+    fn of(expected: u16, response: Result<ImposterResponse, ApiError>) -> Self {
+        match response {
+            Ok(response) if response.status == expected => StepOutcome::Passed {
+                status: response.status,
+                latency_ms: response.latency_ms,
+            },
+            Ok(response) => StepOutcome::Failed {
+                status: response.status,
+                latency_ms: response.latency_ms,
+            },
+            Err(e) => StepOutcome::Error(e.to_string()),
+        }
+    }
+}
+
+/// A step sent to one imposter
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepResult {
+    /// Index into the scenario's steps
+    pub step: usize,
+    pub port: u16,
+    /// The server the imposter is on, in the all-servers list
+    pub server: Option<usize>,
+    /// `None` until it has run
+    pub outcome: Option<StepOutcome>,
+}
+
+/// A scenario and how its last run went
+#[derive(Debug, Clone)]
+pub struct ScenarioRun {
+    /// File it was read from
+    pub path: String,
+    pub scenario: ScenarioFile,
+    /// Every step against each target in turn
+    pub results: Vec<StepResult>,
+    pub selected: usize,
+}
+
+impl ScenarioRun {
+    /// The scenario's steps against each of `targets` in turn; steps naming a port go there
+    /// instead. With no targets, only steps naming a port run.
+    pub fn new(path: String, scenario: ScenarioFile, targets: &[(u16, Option<usize>)]) -> Self {
+        let passes: Vec<Option<(u16, Option<usize>)>> = if targets.is_empty() {
+            vec![None]
+        } else {
+            targets.iter().copied().map(Some).collect()
+        };
+        let results = passes
+            .into_iter()
+            .flat_map(|target| {
+                scenario
+                    .steps
+                    .iter()
+                    .enumerate()
+                    .filter_map(move |(index, step)| {
+                        let (port, server) = match (step.port, target) {
+                            (Some(port), target) => (port, target.and_then(|(_, server)| server)),
+                            (None, Some(target)) => target,
+                            (None, None) => return None,
+                        };
+                        Some(StepResult {
+                            step: index,
+                            port,
+                            server,
+                            outcome: None,
+                        })
+                    })
+            })
+            .collect();
+        Self {
+            path,
+            scenario,
+            results,
+            selected: 0,
+        }
+    }
+
+    pub fn passed(&self) -> usize {
+        self.count(|outcome| matches!(outcome, StepOutcome::Passed { .. }))
+    }
+
+    /// Steps that got the wrong status or no answer
+    pub fn failed(&self) -> usize {
+        self.count(|outcome| !matches!(outcome, StepOutcome::Passed { .. }))
+    }
+
+    fn count(&self, matches: impl Fn(&StepOutcome) -> bool) -> usize {
+        self.results
+            .iter()
+            .filter(|result| result.outcome.as_ref().is_some_and(&matches))
+            .count()
+    }
+}
+
+impl App {
+    /// Ask for a scenario file to run against the marked imposters, or the selected one
+    pub fn show_scenario_dialog(&mut self) {
+        let default_path = self
+            .scenario
+            .as_ref()
+            .map(|run| run.path.clone())
+            .or_else(|| dirs::home_dir().map(|h| h.to_string_lossy().to_string()))
+            .unwrap_or_else(|| ".".to_string());
+        self.input_state.cursor_pos = default_path.len();
+        self.input_state.file_path = default_path;
+        self.overlay = Overlay::FilePathInput {
+            prompt: "Run scenario file (JSON)".to_string(),
+            action: FileAction::RunScenario,
+        };
+    }
+
+    /// Imposters a scenario runs against: the marked ones, or else the selected one
+    fn scenario_targets(&self) -> Vec<(u16, Option<usize>)> {
+        let marked: Vec<(u16, Option<usize>)> = self
+            .imposters
+            .iter()
+            .filter(|imp| self.marked_imposters.contains(&imp.port))
+            .map(|imp| (imp.port, imp.server))
+            .collect();
+        if !marked.is_empty() {
+            return marked;
+        }
+        self.selected_imposter()
+            .map(|imp| vec![(imp.port, imp.server)])
+            .unwrap_or_default()
+    }
+
+    /// Load the scenario at `path`, open the runner and run it
+    pub(super) async fn run_scenario_file(&mut self, path: &str) {
+        let expanded = Self::expand_path(path);
+        let scenario = match tokio::fs::read_to_string(&expanded).await {
+            Ok(text) => parse_scenario(&text),
+            Err(e) => Err(e.to_string()),
+        };
+        let scenario = match scenario {
+            Ok(scenario) => scenario,
+            Err(e) => {
+                self.set_status(format!("Cannot run {expanded}: {e}"), StatusLevel::Error);
+                return;
+            }
+        };
+        let targets = self.scenario_targets();
+        if targets.is_empty() && scenario.steps.iter().any(|step| step.port.is_none()) {
+            self.set_status(
+                "Select or mark the imposters to run the scenario against".to_string(),
+                StatusLevel::Warning,
+            );
+            return;
+        }
+        self.overlay = Overlay::None;
+        self.scenario = Some(ScenarioRun::new(expanded, scenario, &targets));
+        if self.view != View::Scenario {
+            self.navigate(View::Scenario);
+        }
+        self.run_scenario().await;
+    }
+
+    /// Send every step in order and record how each went
+    pub(super) async fn run_scenario(&mut self) {
+        let Some(run) = self.scenario.as_mut() else {
+            return;
+        };
+        for result in &mut run.results {
+            result.outcome = None;
+        }
+        run.selected = 0;
+        let steps = run.results.len();
+
+        self.is_loading = true;
+        for index in 0..steps {
+            let Some(run) = self.scenario.as_ref() else {
+                break;
+            };
+            let result = &run.results[index];
+            let step = &run.scenario.steps[result.step];
+            let client = match result.server.and_then(|server| self.servers.get(server)) {
+                Some(server) => server.client.clone(),
+                None => self.client.clone(),
+            };
+            let (port, request, expected) = (result.port, step.request(), step.expect_status);
+            let outcome = StepOutcome::of(expected, client.send_to_imposter(port, &request).await);
+            if let Some(run) = self.scenario.as_mut() {
+                run.results[index].outcome = Some(outcome);
+            }
+        }
+        self.is_loading = false;
+
+        let Some(run) = self.scenario.as_ref() else {
+            return;
+        };
+        let name = run.scenario.name.as_deref().unwrap_or("Scenario");
+        let (passed, failed) = (run.passed(), run.failed());
+        let (message, level) = if failed == 0 {
+            (
+                format!("{name}: all {passed} steps passed"),
+                StatusLevel::Success,
+            )
+        } else {
+            (
+                format!("{name}: {failed} of {steps} steps failed"),
+                StatusLevel::Error,
+            )
+        };
+        self.set_status(message, level);
+    }
+
+    pub(super) async fn handle_scenario_event(&mut self, key: KeyEvent) {
+        if key.code == KeyCode::Char('R') {
+            self.run_scenario().await;
+            return;
+        }
+        if key.code == KeyCode::Char('o') {
+            self.show_scenario_dialog();
+            return;
+        }
+        let Some(run) = self.scenario.as_mut() else {
+            return;
+        };
+        let last = run.results.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') => run.selected = (run.selected + 1).min(last),
+            KeyCode::Up | KeyCode::Char('k') => run.selected = run.selected.saturating_sub(1),
+            KeyCode::PageDown => run.selected = (run.selected + 10).min(last),
+            KeyCode::PageUp => run.selected = run.selected.saturating_sub(10),
+            KeyCode::Home | KeyCode::Char('g') => run.selected = 0,
+            KeyCode::End | KeyCode::Char('G') => run.selected = last,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::{make_imposter, make_test_app};
+
+    /// Answer `statuses.len()` requests with those statuses, returning each request line
+    fn answer_with(statuses: Vec<u16>) -> (u16, std::thread::JoinHandle<Vec<String>>) {
+        use std::io::{BufRead, BufReader, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for status in statuses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                requests.push(line.trim_end().to_string());
+                while !line.trim_end().is_empty() {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                }
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} X\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (port, server)
+    }
+
+    fn scenario(json: serde_json::Value) -> ScenarioFile {
+        parse_scenario(&json.to_string()).unwrap()
+    }
+
+    #[test]
+    fn scenarios_are_checked_when_read() {
+        let parsed = scenario(serde_json::json!({
+            "steps": [{ "path": "/orders" }, { "method": "post", "path": "/orders", "body": {"a": 1} }]
+        }));
+        assert_eq!(parsed.steps[0].label(), "GET /orders");
+        assert_eq!(parsed.steps[0].expect_status, 200);
+        let request = parsed.steps[1].request();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.body, br#"{"a":1}"#);
+        assert!(
+            request
+                .headers
+                .contains(&("Content-Type".to_string(), "application/json".to_string()))
+        );
+
+        assert_eq!(
+            parse_scenario(r#"{"steps": []}"#).unwrap_err(),
+            "it has no steps"
+        );
+        assert!(
+            parse_scenario(r#"{"steps": [{"path": "/a", "expect": 200}]}"#)
+                .unwrap_err()
+                .contains("unknown field `expect`")
+        );
+        assert_eq!(
+            parse_scenario(r#"{"steps": [{"path": "orders"}]}"#).unwrap_err(),
+            "step 1 has path \"orders\", which must start with /"
+        );
+    }
+
+    #[test]
+    fn steps_run_against_each_target_unless_they_name_a_port() {
+        let parsed = scenario(serde_json::json!({
+            "steps": [{ "path": "/a" }, { "path": "/health", "port": 9000 }]
+        }));
+        let run = ScenarioRun::new(String::new(), parsed.clone(), &[(4545, None), (4546, None)]);
+        let rows: Vec<(usize, u16)> = run.results.iter().map(|r| (r.step, r.port)).collect();
+        assert_eq!(rows, [(0, 4545), (1, 9000), (0, 4546), (1, 9000)]);
+
+        let run = ScenarioRun::new(String::new(), parsed, &[]);
+        let rows: Vec<(usize, u16)> = run.results.iter().map(|r| (r.step, r.port)).collect();
+        assert_eq!(rows, [(1, 9000)]);
+    }
+
+    #[tokio::test]
+    async fn a_run_passes_or_fails_each_step() {
+        let (port, server) = answer_with(vec![200, 500]);
+        let mut app = make_test_app();
+        app.client = ApiClient::new("http://127.0.0.1:9");
+        app.imposters = vec![make_imposter(port, None, "http")];
+        app.imposter_list_state.select(Some(0));
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            file.path(),
+            r#"{"name": "smoke", "steps": [
+                {"path": "/ok"},
+                {"method": "DELETE", "path": "/orders/1", "expectStatus": 204}
+            ]}"#,
+        )
+        .unwrap();
+
+        app.run_scenario_file(file.path().to_str().unwrap()).await;
+
+        assert_eq!(app.view, View::Scenario);
+        let run = app.scenario.as_ref().unwrap();
+        assert!(matches!(
+            run.results[0].outcome,
+            Some(StepOutcome::Passed { status: 200, .. })
+        ));
+        assert!(matches!(
+            run.results[1].outcome,
+            Some(StepOutcome::Failed { status: 500, .. })
+        ));
+        assert_eq!(
+            app.status_message.as_ref().unwrap().0,
+            "smoke: 1 of 2 steps failed"
+        );
+        assert_eq!(
+            server.join().unwrap(),
+            ["GET /ok HTTP/1.1", "DELETE /orders/1 HTTP/1.1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn a_scenario_needs_somewhere_to_run() {
+        let mut app = make_test_app();
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), r#"{"steps": [{"path": "/ok"}]}"#).unwrap();
+        app.run_scenario_file(file.path().to_str().unwrap()).await;
+        assert!(app.scenario.is_none());
+        assert_eq!(
+            app.status_message.as_ref().unwrap().0,
+            "Select or mark the imposters to run the scenario against"
+        );
+    }
+}
@@ -0,0 +1,606 @@
+//! Script editor: edit a stub's `inject`, `decorate` or `_rift.script` on its own, check it with
+//! the server's validator for its engine and run it against a sample request
+
+use super::*;
+use crate::api::{ScriptCheck, ScriptKind, ScriptRun, ScriptSource};
+use ratatui_textarea::TextArea;
+
+/// A script in a stub, and where to put it back
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptTarget {
+    pub kind: ScriptKind,
+    /// The `_rift.script` engine the stub names, if any
+    pub engine: Option<String>,
+    /// Lint-style location, e.g. `responses[0]._rift.script.code`
+    pub location: String,
+    /// JSON pointer to the script string
+    pointer: String,
+}
+
+impl ScriptTarget {
+    /// What the script is, for titles and the status line
+    pub fn describe(&self) -> String {
+        match self.kind {
+            ScriptKind::Script => format!(
+                "{} ({})",
+                self.location,
+                self.engine.as_deref().unwrap_or("rhai")
+            ),
+            ScriptKind::Inject | ScriptKind::Decorate => self.location.clone(),
+        }
+    }
+
+    /// Index of the response the script belongs to
+    fn response(&self) -> usize {
+        self.pointer
+            .split('/')
+            .nth(2)
+            .and_then(|index| index.parse().ok())
+            .unwrap_or(0)
+    }
+}
+
+/// The inline scripts of a stub, in order: each response's `inject`, `_rift.script` with `code`,
+/// and `decorate` behaviors. Scripts kept in a `file` or behind a `ref` aren't editable here.
+pub fn stub_scripts(stub: &serde_json::Value) -> Vec<ScriptTarget> {
+    let mut targets = Vec::new();
+    let responses = stub.get("responses").and_then(|r| r.as_array());
+    for (i, response) in responses.into_iter().flatten().enumerate() {
+        if response.get("inject").is_some_and(|v| v.is_string()) {
+            targets.push(ScriptTarget {
+                kind: ScriptKind::Inject,
+                engine: None,
+                location: format!("responses[{i}].inject"),
+                pointer: format!("/responses/{i}/inject"),
+            });
+        }
+        if let Some(script) = response.pointer("/_rift/script")
+            && script.get("code").is_some_and(|v| v.is_string())
+        {
+            targets.push(ScriptTarget {
+                kind: ScriptKind::Script,
+                engine: script
+                    .get("engine")
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+                location: format!("responses[{i}]._rift.script.code"),
+                pointer: format!("/responses/{i}/_rift/script/code"),
+            });
+        }
+        match response.get("_behaviors") {
+            Some(serde_json::Value::Array(steps)) => {
+                for (j, step) in steps.iter().enumerate() {
+                    if step.get("decorate").is_some_and(|v| v.is_string()) {
+                        targets.push(ScriptTarget {
+                            kind: ScriptKind::Decorate,
+                            engine: None,
+                            location: format!("responses[{i}]._behaviors[{j}].decorate"),
+                            pointer: format!("/responses/{i}/_behaviors/{j}/decorate"),
+                        });
+                    }
+                }
+            }
+            Some(behaviors) if behaviors.get("decorate").is_some_and(|v| v.is_string()) => {
+                targets.push(ScriptTarget {
+                    kind: ScriptKind::Decorate,
+                    engine: None,
+                    location: format!("responses[{i}]._behaviors.decorate"),
+                    pointer: format!("/responses/{i}/_behaviors/decorate"),
+                });
+            }
+            _ => {}
+        }
+    }
+    targets
+}
+
+/// A sample request for the stub's scripts, using the method and path its first `equals`
+/// predicate asks for
+fn sample_request(stub: &serde_json::Value) -> String {
+    let equals = stub
+        .get("predicates")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+        .find_map(|p| p.get("equals").or_else(|| p.get("deepEquals")));
+    let field = |name: &str, default: &str| {
+        equals
+            .and_then(|e| e.get(name))
+            .and_then(|v| v.as_str())
+            .unwrap_or(default)
+            .to_string()
+    };
+    let sample = serde_json::json!({
+        "method": field("method", "GET"),
+        "path": field("path", "/"),
+        "headers": {},
+        "query": {},
+        "body": null,
+    });
+    serde_json::to_string_pretty(&sample).unwrap_or_default()
+}
+
+/// The response a stub's `is` gives, for the `decorate` on the same response to change
+fn sample_response(stub: &serde_json::Value, response: usize) -> serde_json::Value {
+    let is = stub.pointer(&format!("/responses/{response}/is"));
+    let field = |name: &str| is.and_then(|is| is.get(name));
+    let headers: serde_json::Map<String, serde_json::Value> = field("headers")
+        .and_then(|h| h.as_object())
+        .into_iter()
+        .flatten()
+        .map(|(name, value)| {
+            let value = match value {
+                serde_json::Value::String(text) => text.clone(),
+                other => other.to_string(),
+            };
+            (name.clone(), serde_json::Value::String(value))
+        })
+        .collect();
+    let body = match field("body") {
+        None | Some(serde_json::Value::Null) => String::new(),
+        Some(serde_json::Value::String(text)) => text.clone(),
+        Some(other) => other.to_string(),
+    };
+    serde_json::json!({
+        "statusCode": field("statusCode").and_then(|s| s.as_u64()).unwrap_or(200),
+        "headers": headers,
+        "body": body,
+    })
+}
+
+/// The script editor field that has focus
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScriptField {
+    #[default]
+    Code,
+    Sample,
+}
+
+/// What the results pane shows
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptOutput {
+    Checked(ScriptCheck),
+    Ran(ScriptRun),
+    /// The server couldn't be asked, or the sample isn't usable
+    Failed(String),
+}
+
+impl ScriptOutput {
+    /// Whether the script passed its check or ran without an error
+    pub fn is_ok(&self) -> bool {
+        match self {
+            ScriptOutput::Checked(check) => check.valid,
+            ScriptOutput::Ran(run) => run.error.is_none(),
+            ScriptOutput::Failed(_) => false,
+        }
+    }
+
+    /// The results pane text
+    pub fn lines(&self) -> Vec<String> {
+        match self {
+            ScriptOutput::Checked(check) if check.valid => {
+                vec![format!("✓ {}: no problems found", check.engine)]
+            }
+            ScriptOutput::Checked(check) => {
+                let mut lines = vec![format!(
+                    "✗ {}: {} problem(s)",
+                    check.engine,
+                    check.errors.len()
+                )];
+                lines.extend(check.errors.iter().map(|e| format!("  {e}")));
+                lines
+            }
+            ScriptOutput::Ran(run) => {
+                let mut lines = Vec::new();
+                match &run.error {
+                    Some(e) => lines.push(format!("✗ {}: {e}", run.engine)),
+                    None => lines.push(format!("✓ {} ran in {}ms", run.engine, run.duration_ms)),
+                }
+                if let Some(decision) = &run.decision {
+                    lines.push(format!("decision: {decision}"));
+                }
+                if let Some(response) = &run.response {
+                    lines.push(format!("response: {}", response.status_code));
+                    lines.extend(
+                        response
+                            .headers
+                            .iter()
+                            .map(|(name, value)| format!("  {name}: {value}")),
+                    );
+                    if !response.body.is_empty() {
+                        lines.push(String::new());
+                        lines.extend(response.body.lines().map(String::from));
+                    }
+                }
+                if !run.logs.is_empty() {
+                    lines.push("logs:".to_string());
+                    lines.extend(run.logs.iter().map(|line| format!("  {line}")));
+                }
+                if !run.state.is_empty() {
+                    lines.push("state:".to_string());
+                    lines.extend(
+                        run.state
+                            .iter()
+                            .map(|(key, value)| format!("  {key} = {value}")),
+                    );
+                }
+                lines
+            }
+            ScriptOutput::Failed(message) => vec![format!("✗ {message}")],
+        }
+    }
+}
+
+/// State of the script editor, open over the stub editor
+pub struct ScriptEditor {
+    pub target: ScriptTarget,
+    pub code: TextArea<'static>,
+    /// The request the script runs against, in `rift script run --request` form
+    pub sample: TextArea<'static>,
+    pub focus: ScriptField,
+    pub output: Option<ScriptOutput>,
+    pub output_scroll: u16,
+    /// Code the user was told has problems; saving it again goes ahead
+    acknowledged: Option<String>,
+}
+
+impl ScriptEditor {
+    /// An editor for `target` in `stub`
+    pub fn new(stub: &serde_json::Value, target: ScriptTarget) -> Self {
+        let code = stub
+            .pointer(&target.pointer)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        Self {
+            code: TextArea::new(code.lines().map(String::from).collect()),
+            sample: TextArea::new(sample_request(stub).lines().map(String::from).collect()),
+            target,
+            focus: ScriptField::Code,
+            output: None,
+            output_scroll: 0,
+            acknowledged: None,
+        }
+    }
+
+    pub fn code_text(&self) -> String {
+        self.code.lines().join("\n")
+    }
+
+    /// The script as the server takes it; `stub` supplies the response a `decorate` is given
+    fn source(&self, stub: Option<&serde_json::Value>) -> Result<ScriptSource, String> {
+        let request = serde_json::from_str(&self.sample.lines().join("\n"))
+            .map_err(|e| format!("The sample request isn't valid JSON: {e}"))?;
+        let response = match (self.target.kind, stub) {
+            (ScriptKind::Decorate, Some(stub)) => {
+                Some(sample_response(stub, self.target.response()))
+            }
+            _ => None,
+        };
+        Ok(ScriptSource {
+            kind: self.target.kind,
+            engine: self.target.engine.clone(),
+            code: self.code_text(),
+            request: Some(request),
+            response,
+        })
+    }
+}
+
+impl App {
+    /// The stub being edited, if its JSON parses
+    fn edited_stub(&self) -> Option<serde_json::Value> {
+        let editor = self.stub_editor.as_ref()?;
+        serde_json::from_str(&editor.editor.lines().join("\n")).ok()
+    }
+
+    /// Open the script editor on the stub editor's script at the cursor, or its first one
+    pub(super) fn open_script_editor(&mut self) {
+        let Some(editor) = &self.stub_editor else {
+            return;
+        };
+        let Some(stub) = self.edited_stub() else {
+            self.set_status(
+                "Fix the stub's JSON before editing its script".to_string(),
+                StatusLevel::Warning,
+            );
+            return;
+        };
+        let targets = stub_scripts(&stub);
+        let content = editor.editor.lines().join("\n");
+        let row = editor.editor.cursor().0;
+        let Some(target) = targets
+            .iter()
+            .rev()
+            .find(|target| stub_issue_line(&content, &target.location) <= row)
+            .or(targets.first())
+            .cloned()
+        else {
+            self.set_status(
+                "This stub has no inject, decorate or _rift.script code to edit".to_string(),
+                StatusLevel::Info,
+            );
+            return;
+        };
+        self.script_editor = Some(ScriptEditor::new(&stub, target));
+        self.overlay = Overlay::ScriptEditor;
+    }
+
+    /// Check the script with the server's validator for its engine and put it back in the stub
+    /// editor. A script with problems is only put back when saved again unchanged.
+    async fn save_script(&mut self) {
+        let stub = self.edited_stub();
+        let Some(script) = self.script_editor.as_mut() else {
+            return;
+        };
+        let source = match script.source(stub.as_ref()) {
+            Ok(source) => source,
+            // The sample doesn't matter for a check
+            Err(_) => ScriptSource {
+                kind: script.target.kind,
+                engine: script.target.engine.clone(),
+                code: script.code_text(),
+                request: None,
+                response: None,
+            },
+        };
+        self.is_loading = true;
+        let check = self.client.check_script(&source).await;
+        self.is_loading = false;
+        let problem = match &check {
+            Ok(check) if check.valid => None,
+            Ok(check) => Some(format!("{} problem(s)", check.errors.len())),
+            Err(e) => Some(format!("Couldn't check the script: {e}")),
+        };
+        script.output = Some(match check {
+            Ok(check) => ScriptOutput::Checked(check),
+            Err(e) => ScriptOutput::Failed(format!("Couldn't check the script: {e}")),
+        });
+        script.output_scroll = 0;
+        if let Some(problem) = problem
+            && script.acknowledged.as_deref() != Some(source.code.as_str())
+        {
+            script.acknowledged = Some(source.code);
+            self.set_status(
+                format!("{problem} - Ctrl+S again to keep the script anyway"),
+                StatusLevel::Warning,
+            );
+            return;
+        }
+        self.put_script_back();
+    }
+
+    /// Write the edited script into the stub editor and close the script editor
+    fn put_script_back(&mut self) {
+        let (Some(script), Some(mut stub)) = (self.script_editor.take(), self.edited_stub()) else {
+            return;
+        };
+        if let Some(slot) = stub.pointer_mut(&script.target.pointer) {
+            *slot = serde_json::Value::String(script.code_text());
+        }
+        if let Some(editor) = self.stub_editor.as_mut() {
+            editor.set_text(&serde_json::to_string_pretty(&stub).unwrap_or_default());
+            editor.validate();
+        }
+        self.overlay = Overlay::None;
+        self.set_status(
+            format!(
+                "Updated {} - Ctrl+S saves the stub",
+                script.target.describe()
+            ),
+            StatusLevel::Success,
+        );
+    }
+
+    /// Run the script against the sample request on the server and show what it did
+    async fn test_script(&mut self) {
+        let stub = self.edited_stub();
+        let Some(script) = self.script_editor.as_mut() else {
+            return;
+        };
+        let output = match script.source(stub.as_ref()) {
+            Ok(source) => {
+                self.is_loading = true;
+                let run = self.client.run_script(&source).await;
+                self.is_loading = false;
+                match run {
+                    Ok(run) => ScriptOutput::Ran(run),
+                    Err(e) => ScriptOutput::Failed(format!("Couldn't run the script: {e}")),
+                }
+            }
+            Err(e) => ScriptOutput::Failed(e),
+        };
+        script.output = Some(output);
+        script.output_scroll = 0;
+    }
+
+    pub(super) async fn handle_script_editor_event(&mut self, key: KeyEvent) {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            match key.code {
+                KeyCode::Char('s') => {
+                    self.save_script().await;
+                    return;
+                }
+                KeyCode::Char('t') => {
+                    self.test_script().await;
+                    return;
+                }
+                _ => {}
+            }
+        }
+        let Some(script) = self.script_editor.as_mut() else {
+            self.overlay = Overlay::None;
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                self.script_editor = None;
+                self.overlay = Overlay::None;
+            }
+            KeyCode::Tab | KeyCode::BackTab => {
+                script.focus = match script.focus {
+                    ScriptField::Code => ScriptField::Sample,
+                    ScriptField::Sample => ScriptField::Code,
+                };
+            }
+            KeyCode::PageUp => {
+                script.output_scroll = script.output_scroll.saturating_sub(10);
+            }
+            KeyCode::PageDown => {
+                let max_scroll = script
+                    .output
+                    .as_ref()
+                    .map_or(0, |output| output.lines().len().saturating_sub(1))
+                    as u16;
+                script.output_scroll = (script.output_scroll + 10).min(max_scroll);
+            }
+            _ => {
+                let field = match script.focus {
+                    ScriptField::Code => &mut script.code,
+                    ScriptField::Sample => &mut script.sample,
+                };
+                field.input(crossterm_key_to_input(key));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::make_test_app;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn stub() -> serde_json::Value {
+        serde_json::json!({
+            "predicates": [{"equals": {"method": "POST", "path": "/orders"}}],
+            "responses": [
+                {"inject": "function (config) { return { statusCode: 201 }; }"},
+                {
+                    "is": {"statusCode": 200, "body": {"ok": true}},
+                    "_behaviors": [{"wait": 10}, {"decorate": "response.statusCode = 202;"}]
+                },
+                {"_rift": {"script": {"engine": "javascript", "code": "pass()"}}},
+                {"_rift": {"script": {"file": "scripts/slow.rhai"}}}
+            ]
+        })
+    }
+
+    #[test]
+    fn inline_scripts_are_found_in_response_order() {
+        let targets = stub_scripts(&stub());
+        let locations: Vec<_> = targets.iter().map(|t| t.describe()).collect();
+        assert_eq!(
+            locations,
+            [
+                "responses[0].inject",
+                "responses[1]._behaviors[1].decorate",
+                "responses[2]._rift.script.code (javascript)",
+            ]
+        );
+
+        let sample: serde_json::Value = serde_json::from_str(&sample_request(&stub())).unwrap();
+        assert_eq!(sample["method"], "POST");
+        assert_eq!(sample["path"], "/orders");
+        // A decorate is given the response its `is` makes
+        assert_eq!(
+            sample_response(&stub(), targets[1].response()),
+            serde_json::json!({"statusCode": 200, "headers": {}, "body": "{\"ok\":true}"})
+        );
+    }
+
+    #[tokio::test]
+    async fn ctrl_e_opens_the_script_at_the_cursor_and_esc_leaves_the_stub_alone() {
+        let mut app = make_test_app();
+        let json = serde_json::to_string_pretty(&stub()).unwrap();
+        app.stub_editor = Some(StubEditor::new(&json));
+        app.view = View::StubEdit {
+            port: 4545,
+            index: Some(0),
+        };
+        let decorate_line = json.lines().position(|l| l.contains("decorate")).unwrap();
+        app.stub_editor
+            .as_mut()
+            .unwrap()
+            .editor
+            .move_cursor(ratatui_textarea::CursorMove::Jump(decorate_line as u16, 0));
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL))
+            .await;
+        assert_eq!(app.overlay, Overlay::ScriptEditor);
+        let script = app.script_editor.as_ref().unwrap();
+        assert_eq!(script.target.kind, ScriptKind::Decorate);
+        assert_eq!(script.code_text(), "response.statusCode = 202;");
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('x')))
+            .await;
+        app.handle_key_event(KeyEvent::from(KeyCode::Esc)).await;
+        assert_eq!(app.overlay, Overlay::None);
+        assert!(app.script_editor.is_none());
+        let content = app.stub_editor.as_ref().unwrap().editor.lines().join("\n");
+        assert_eq!(content, json);
+    }
+
+    #[tokio::test]
+    async fn an_unchecked_script_is_put_back_only_when_saved_again() {
+        let mut app = make_test_app();
+        // Nothing listens here, so the check can't be made
+        app.client = ApiClient::new("http://127.0.0.1:9");
+        let json = serde_json::to_string_pretty(&stub()).unwrap();
+        app.stub_editor = Some(StubEditor::new(&json));
+        app.view = View::StubEdit {
+            port: 4545,
+            index: Some(0),
+        };
+        app.open_script_editor();
+        app.handle_key_event(KeyEvent::from(KeyCode::End)).await;
+        for c in " // edited".chars() {
+            app.handle_key_event(KeyEvent::from(KeyCode::Char(c))).await;
+        }
+
+        let ctrl_s = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        app.handle_key_event(ctrl_s).await;
+        assert_eq!(app.overlay, Overlay::ScriptEditor);
+        let output = app.script_editor.as_ref().unwrap().output.clone().unwrap();
+        assert!(!output.is_ok());
+        assert!(output.lines()[0].contains("Couldn't check"), "{output:?}");
+
+        app.handle_key_event(ctrl_s).await;
+        assert_eq!(app.overlay, Overlay::None);
+        let stub = app.edited_stub().unwrap();
+        assert_eq!(
+            stub["responses"][0]["inject"],
+            "function (config) { return { statusCode: 201 }; } // edited"
+        );
+        assert!(
+            app.status_message
+                .as_ref()
+                .unwrap()
+                .0
+                .starts_with("Updated responses[0].inject")
+        );
+    }
+
+    #[test]
+    fn a_run_lists_the_decision_response_logs_and_state() {
+        let run = ScriptRun {
+            engine: "rhai".to_string(),
+            decision: Some("http(503)".to_string()),
+            response: None,
+            duration_ms: 3,
+            logs: vec!["checked".to_string()],
+            state: serde_json::Map::from_iter([("n".to_string(), serde_json::json!(2))]),
+            error: None,
+        };
+        assert_eq!(
+            ScriptOutput::Ran(run).lines(),
+            [
+                "✓ rhai ran in 3ms",
+                "decision: http(503)",
+                "logs:",
+                "  checked",
+                "state:",
+                "  n = 2"
+            ]
+        );
+    }
+}
@@ -11,17 +11,14 @@ impl App {
                     return;
                 }
 
-                // Get indices of matching imposters
-                let matching_indices: Vec<usize> = if self.search_query.is_empty() {
-                    (0..self.imposters.len()).collect()
-                } else {
-                    self.imposters
-                        .iter()
-                        .enumerate()
-                        .filter(|(_, imp)| self.imposter_matches_search(imp))
-                        .map(|(i, _)| i)
-                        .collect()
-                };
+                // Get indices of the imposters the search and filters show
+                let matching_indices: Vec<usize> = self
+                    .imposters
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, imp)| self.imposter_matches_search(imp))
+                    .map(|(i, _)| i)
+                    .collect();
 
                 if matching_indices.is_empty() {
                     return;
@@ -88,17 +85,14 @@ impl App {
                     return;
                 }
 
-                // Get indices of matching imposters
-                let matching_indices: Vec<usize> = if self.search_query.is_empty() {
-                    (0..self.imposters.len()).collect()
-                } else {
-                    self.imposters
-                        .iter()
-                        .enumerate()
-                        .filter(|(_, imp)| self.imposter_matches_search(imp))
-                        .map(|(i, _)| i)
-                        .collect()
-                };
+                // Get indices of the imposters the search and filters show
+                let matching_indices: Vec<usize> = self
+                    .imposters
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, imp)| self.imposter_matches_search(imp))
+                    .map(|(i, _)| i)
+                    .collect();
 
                 if matching_indices.is_empty() {
                     return;
@@ -156,12 +150,23 @@ impl App {
     }
 
     /// Handle search input
-    pub(super) fn handle_search_input(&mut self, key: KeyEvent) {
+    pub(super) async fn handle_search_input(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Esc => {
                 self.search_active = false;
+                self.search_global = false;
                 self.search_query.clear();
             }
+            // Switch between filtering this list and searching every imposter
+            KeyCode::Tab => self.search_global = !self.search_global,
+            KeyCode::Enter if self.search_global => {
+                self.search_active = false;
+                self.search_global = false;
+                let query = std::mem::take(&mut self.search_query);
+                if !query.trim().is_empty() {
+                    self.run_global_search(query).await;
+                }
+            }
             KeyCode::Enter => {
                 self.search_active = false;
                 // Keep the query for filtering, select first match
@@ -211,63 +216,30 @@ impl App {
         }
     }
 
-    /// Get filtered imposters based on search query
+    /// Get the imposters the search query and filters show
     pub fn filtered_imposters(&self) -> Vec<&ImposterSummary> {
-        if self.search_query.is_empty() {
-            self.imposters.iter().collect()
-        } else {
-            let query = self.search_query.to_lowercase();
-            self.imposters
-                .iter()
-                .filter(|imp| {
-                    imp.port.to_string().contains(&query)
-                        || imp
-                            .name
-                            .as_ref()
-                            .map(|n| n.to_lowercase().contains(&query))
-                            .unwrap_or(false)
-                        || imp.protocol.to_lowercase().contains(&query)
-                })
-                .collect()
-        }
+        self.imposters
+            .iter()
+            .filter(|imp| self.imposter_matches_search(imp))
+            .collect()
     }
 
     /// Get filtered stub indices based on search query
     pub fn filtered_stubs(&self) -> Vec<usize> {
-        if self.search_query.is_empty() {
-            if let Some(imp) = &self.current_imposter {
-                (0..imp.stubs.len()).collect()
-            } else {
-                vec![]
-            }
-        } else {
-            let query = self.search_query.to_lowercase();
-            if let Some(imp) = &self.current_imposter {
-                imp.stubs
-                    .iter()
-                    .enumerate()
-                    .filter(|(_, stub)| {
-                        // Match scenario name
-                        stub.scenario_name.as_ref().map(|n| n.to_lowercase().contains(&query)).unwrap_or(false)
-                            // Match predicates (path, method)
-                            || stub.predicates.iter().any(|p| {
-                                p.to_string().to_lowercase().contains(&query)
-                            })
-                            // Match response type
-                            || stub.responses.iter().any(|r| {
-                                r.to_string().to_lowercase().contains(&query)
-                            })
-                    })
-                    .map(|(i, _)| i)
-                    .collect()
-            } else {
-                vec![]
-            }
-        }
+        let rows = self.stub_rows();
+        let query = self.search_query.to_lowercase();
+        rows.iter()
+            .enumerate()
+            .filter(|(_, row)| row.matches(&query))
+            .map(|(i, _)| i)
+            .collect()
     }
 
     /// Check if an imposter matches the current search
     pub fn imposter_matches_search(&self, imp: &ImposterSummary) -> bool {
+        if !self.imposter_filter.matches(imp) {
+            return false;
+        }
         if self.search_query.is_empty() {
             return true;
         }
@@ -279,6 +251,9 @@ impl App {
                 .map(|n| n.to_lowercase().contains(&query))
                 .unwrap_or(false)
             || imp.protocol.to_lowercase().contains(&query)
+            || self
+                .imposter_server_name(imp)
+                .is_some_and(|name| name.to_lowercase().contains(&query))
     }
 
     /// Check if a stub index matches the current search
@@ -286,7 +261,10 @@ impl App {
         if self.search_query.is_empty() {
             return true;
         }
-        self.filtered_stubs().contains(&index)
+        let query = self.search_query.to_lowercase();
+        self.stub_rows()
+            .get(index)
+            .is_some_and(|row| row.matches(&query))
     }
 
     /// Clear search
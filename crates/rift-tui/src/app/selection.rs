@@ -0,0 +1,513 @@
+//! Marking several imposters or stubs, and the bulk actions on the marked set
+
+use super::*;
+use std::ops::RangeInclusive;
+
+/// `:4545, :4546, …` for a confirmation message, naming at most five ports
+fn port_list(ports: &[u16]) -> String {
+    let mut list: Vec<String> = ports
+        .iter()
+        .take(5)
+        .map(|port| format!(":{port}"))
+        .collect();
+    if ports.len() > 5 {
+        list.push("…".to_string());
+    }
+    list.join(", ")
+}
+
+impl App {
+    /// Position of the cursor in the list the current view marks in
+    fn mark_cursor(&self) -> Option<usize> {
+        match self.view {
+            View::ImposterList => self.imposter_list_state.selected(),
+            View::ImposterDetail { .. } if self.focus == FocusArea::Left => {
+                self.stub_list_state.selected()
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether list item `index` can be marked: ranges skip the items a search hides
+    fn markable(&self, index: usize) -> bool {
+        match self.view {
+            View::ImposterList => self
+                .imposters
+                .get(index)
+                .is_some_and(|imp| self.imposter_matches_search(imp)),
+            _ => self.stub_matches_search(index),
+        }
+    }
+
+    /// The items from the `V` anchor to the cursor, while a range is being marked
+    fn pending_range(&self) -> Option<RangeInclusive<usize>> {
+        let anchor = self.mark_anchor?;
+        let cursor = self.mark_cursor()?;
+        Some(anchor.min(cursor)..=anchor.max(cursor))
+    }
+
+    fn in_pending_range(&self, index: usize) -> bool {
+        self.pending_range()
+            .is_some_and(|range| range.contains(&index))
+            && self.markable(index)
+    }
+
+    /// Whether imposter list item `index` is marked, or in the range being marked
+    pub fn is_imposter_marked(&self, index: usize) -> bool {
+        self.imposters
+            .get(index)
+            .is_some_and(|imp| self.marked_imposters.contains(&imp.port))
+            || (self.view == View::ImposterList && self.in_pending_range(index))
+    }
+
+    /// Whether stub `index` is marked, or in the range being marked
+    pub fn is_stub_marked(&self, index: usize) -> bool {
+        self.marked_stubs.contains(&index)
+            || (matches!(self.view, View::ImposterDetail { .. }) && self.in_pending_range(index))
+    }
+
+    /// Marked imposters in list order
+    pub fn marked_ports(&self) -> Vec<u16> {
+        self.imposters
+            .iter()
+            .map(|imp| imp.port)
+            .filter(|port| self.marked_imposters.contains(port))
+            .collect()
+    }
+
+    /// Marked stubs of the open imposter, in order
+    pub fn marked_stub_indices(&self) -> Vec<usize> {
+        let len = self
+            .current_imposter
+            .as_ref()
+            .map_or(0, |imp| imp.stubs.len());
+        self.marked_stubs
+            .iter()
+            .copied()
+            .filter(|&index| index < len)
+            .collect()
+    }
+
+    /// `Space`: mark or unmark the item under the cursor
+    pub fn toggle_mark(&mut self) {
+        let Some(index) = self.mark_cursor() else {
+            return;
+        };
+        match self.view {
+            View::ImposterList => {
+                if let Some(port) = self.imposters.get(index).map(|imp| imp.port)
+                    && !self.marked_imposters.remove(&port)
+                {
+                    self.marked_imposters.insert(port);
+                }
+            }
+            _ => {
+                if !self.marked_stubs.remove(&index) {
+                    self.marked_stubs.insert(index);
+                }
+            }
+        }
+    }
+
+    /// `V`: start a range at the cursor, or mark everything from its start to the cursor
+    pub fn mark_range(&mut self) {
+        let Some(range) = self.pending_range() else {
+            if let Some(cursor) = self.mark_cursor() {
+                self.mark_anchor = Some(cursor);
+                self.set_status(
+                    "Marking a range: move to its other end and press V".to_string(),
+                    StatusLevel::Info,
+                );
+            }
+            return;
+        };
+        self.mark_anchor = None;
+        let indices: Vec<usize> = range.filter(|&index| self.markable(index)).collect();
+        let marked = match self.view {
+            View::ImposterList => {
+                let ports: Vec<u16> = indices
+                    .iter()
+                    .filter_map(|&index| self.imposters.get(index))
+                    .map(|imp| imp.port)
+                    .collect();
+                self.marked_imposters.extend(ports);
+                self.marked_imposters.len()
+            }
+            _ => {
+                self.marked_stubs.extend(indices);
+                self.marked_stubs.len()
+            }
+        };
+        self.set_status(format!("{marked} marked"), StatusLevel::Info);
+    }
+
+    /// Drop the marks of the current view and any range being marked; whether there were any
+    pub fn clear_marks(&mut self) -> bool {
+        let had_range = self.mark_anchor.take().is_some();
+        let had_marks = match self.view {
+            View::ImposterList => !std::mem::take(&mut self.marked_imposters).is_empty(),
+            View::ImposterDetail { .. } => !std::mem::take(&mut self.marked_stubs).is_empty(),
+            _ => false,
+        };
+        had_range || had_marks
+    }
+
+    /// `d` with imposters marked: confirm deleting them all
+    pub async fn confirm_delete_marked_imposters(&mut self) {
+        let ports = self.marked_ports();
+        self.confirm(
+            format!(
+                "Delete {} marked imposters ({})?",
+                ports.len(),
+                port_list(&ports)
+            ),
+            PendingAction::DeleteImposters { ports },
+        )
+        .await;
+    }
+
+    /// Delete imposters `ports`, journaled as one change. The ones that fail stay marked.
+    pub async fn delete_imposters(&mut self, ports: Vec<u16>) {
+        self.is_loading = true;
+        let mut operations = Vec::new();
+        let mut deleted = 0;
+        let mut last_error = None;
+        self.marked_imposters.clear();
+        for port in ports.iter().copied() {
+            match self.remove_imposter(port).await {
+                Ok(operation) => {
+                    deleted += 1;
+                    operations.extend(operation);
+                }
+                Err(e) => {
+                    self.marked_imposters.insert(port);
+                    last_error = Some(format!(":{port}: {e}"));
+                }
+            }
+        }
+        let unsaved = deleted - operations.len();
+        if !operations.is_empty() {
+            let client = self.client.clone();
+            self.journal.record(
+                &client,
+                Operation::Batch {
+                    description: format!("delete {} imposters", operations.len()),
+                    operations,
+                },
+            );
+        }
+        match last_error {
+            Some(error) => self.set_status(
+                format!(
+                    "Deleted {deleted} of {} imposters; failed {error}",
+                    ports.len()
+                ),
+                StatusLevel::Error,
+            ),
+            None if unsaved > 0 => self.set_status(
+                format!("Deleted {deleted} imposters; {unsaved} configs couldn't be saved to undo"),
+                StatusLevel::Warning,
+            ),
+            None => self.set_status(format!("Deleted {deleted} imposters"), StatusLevel::Success),
+        }
+        self.refresh().await;
+        self.is_loading = false;
+        self.overlay = Overlay::None;
+    }
+
+    /// `t` with imposters marked: disable them all, or enable them all when none is enabled
+    pub async fn toggle_marked_imposters(&mut self) {
+        let ports = self.marked_ports();
+        let enable = !self.any_enabled(&ports);
+        let verb = if enable { "Enable" } else { "Disable" };
+        self.confirm(
+            format!(
+                "{verb} {} marked imposters ({})?",
+                ports.len(),
+                port_list(&ports)
+            ),
+            PendingAction::ToggleImposters { ports },
+        )
+        .await;
+    }
+
+    fn any_enabled(&self, ports: &[u16]) -> bool {
+        self.imposters
+            .iter()
+            .any(|imp| imp.enabled && ports.contains(&imp.port))
+    }
+
+    /// Disable imposters `ports`, or enable them all when none is enabled
+    pub async fn toggle_imposters(&mut self, ports: Vec<u16>) {
+        self.overlay = Overlay::None;
+        let enable = !self.any_enabled(&ports);
+        self.is_loading = true;
+        let mut changed = 0;
+        let mut last_error = None;
+        for port in ports.iter().copied() {
+            let result = if enable {
+                self.client.enable_imposter(port).await
+            } else {
+                self.client.disable_imposter(port).await
+            };
+            match result {
+                Ok(_) => changed += 1,
+                Err(e) => last_error = Some(format!(":{port}: {e}")),
+            }
+        }
+        let action = if enable { "Enabled" } else { "Disabled" };
+        match last_error {
+            Some(error) => self.set_status(
+                format!(
+                    "{action} {changed} of {} imposters; failed {error}",
+                    ports.len()
+                ),
+                StatusLevel::Error,
+            ),
+            None => self.set_status(
+                format!("{action} {changed} imposters"),
+                StatusLevel::Success,
+            ),
+        }
+        self.refresh().await;
+        self.is_loading = false;
+    }
+
+    /// `e` with imposters marked: ask where to export them
+    pub fn show_export_marked_dialog(&mut self) {
+        let ports = self.marked_ports();
+        let default_path = dirs::home_dir()
+            .map(|h| h.join("imposters.json"))
+            .unwrap_or_else(|| std::path::PathBuf::from("imposters.json"));
+
+        let path_str = default_path.to_string_lossy().to_string();
+        self.input_state.cursor_pos = path_str.len();
+        self.input_state.file_path = path_str;
+        self.overlay = Overlay::FilePathInput {
+            prompt: format!("Export {} marked imposters to file", ports.len()),
+            action: FileAction::ExportMarked { ports },
+        };
+    }
+
+    /// Fetch imposters `ports` and preview writing them to one file, as `{"imposters": [...]}`
+    /// like the export of all
+    pub async fn export_marked_to_file(&mut self, path: &str, ports: &[u16]) {
+        self.is_loading = true;
+        let expanded_path = Self::expand_path(path);
+
+        let mut imposters = Vec::new();
+        for port in ports {
+            let config = self
+                .client
+                .export_imposter(*port, false)
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|json| {
+                    serde_json::from_str::<serde_json::Value>(&json).map_err(|e| e.to_string())
+                });
+            match config {
+                Ok(config) => imposters.push(config),
+                Err(e) => {
+                    self.set_status(format!("Failed to export :{port}: {e}"), StatusLevel::Error);
+                    self.is_loading = false;
+                    return;
+                }
+            }
+        }
+        let json = serde_json::to_string_pretty(&serde_json::json!({ "imposters": imposters }))
+            .unwrap_or_default();
+        self.preview_export(
+            expanded_path.clone(),
+            vec![(expanded_path, json)],
+            Vec::new(),
+        );
+        self.is_loading = false;
+    }
+
+    /// `d` with stubs marked: confirm deleting them all
+    pub async fn confirm_delete_marked_stubs(&mut self) {
+        if let View::ImposterDetail { port } = self.view {
+            let indices = self.marked_stub_indices();
+            self.confirm(
+                format!("Delete {} marked stubs from :{port}?", indices.len()),
+                PendingAction::DeleteStubs { port, indices },
+            )
+            .await;
+        }
+    }
+
+    /// Delete stubs `indices` of imposter `port`, journaled as one change. They go last first,
+    /// so each index still points at the stub it was marked on.
+    pub async fn delete_stubs(&mut self, port: u16, indices: Vec<usize>) {
+        self.is_loading = true;
+        let mut operations = Vec::new();
+        let mut last_error = None;
+        for index in indices.iter().rev().copied() {
+            let stub = self
+                .current_imposter
+                .as_ref()
+                .filter(|imp| imp.port == port)
+                .and_then(|imp| imp.stubs.get(index))
+                .cloned();
+            match self.client.delete_stub(port, index).await {
+                Ok(_) => {
+                    operations.extend(stub.map(|stub| Operation::DeleteStub { port, index, stub }))
+                }
+                Err(e) => last_error = Some(format!("#{}: {e}", index + 1)),
+            }
+        }
+        let deleted = operations.len();
+        if !operations.is_empty() {
+            let client = self.client.clone();
+            self.journal.record(
+                &client,
+                Operation::Batch {
+                    description: format!("delete {deleted} stubs from :{port}"),
+                    operations,
+                },
+            );
+        }
+        self.marked_stubs.clear();
+        match last_error {
+            Some(error) => self.set_status(
+                format!(
+                    "Deleted {deleted} of {} stubs; failed {error}",
+                    indices.len()
+                ),
+                StatusLevel::Error,
+            ),
+            None => self.set_status(format!("Deleted {deleted} stubs"), StatusLevel::Success),
+        }
+        self.refresh().await;
+        self.is_loading = false;
+        self.overlay = Overlay::None;
+    }
+
+    /// `x` with stubs marked: show just the marked stubs, to save or copy
+    pub fn export_marked_stubs(&mut self) {
+        let View::ImposterDetail { port } = self.view else {
+            return;
+        };
+        let stubs: Vec<&Stub> = self
+            .current_imposter
+            .iter()
+            .flat_map(|imp| &imp.stubs)
+            .enumerate()
+            .filter(|(index, _)| self.marked_stubs.contains(index))
+            .map(|(_, stub)| stub)
+            .collect();
+        let content = serde_json::to_string_pretty(&serde_json::json!({ "stubs": stubs }))
+            .unwrap_or_default();
+        self.overlay = Overlay::Export {
+            title: format!(
+                "Exported {} Marked Stubs (Port :{port}) - [s]ave [c]opy [Esc]close",
+                stubs.len()
+            ),
+            content,
+            port: Some(port),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::{make_imposter, make_test_app};
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::from(code)
+    }
+
+    fn stub(path: &str) -> Stub {
+        serde_json::from_value(serde_json::json!({
+            "predicates": [{"equals": {"path": path}}],
+            "responses": [{"is": {"statusCode": 200}}]
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn space_and_v_mark_imposters_for_a_bulk_delete() {
+        let mut app = make_test_app();
+        app.imposters = (4545..4551)
+            .map(|port| make_imposter(port, None, "http"))
+            .collect();
+        app.imposter_list_state.select(Some(0));
+
+        app.handle_key_event(press(KeyCode::Char(' '))).await;
+        app.handle_key_event(press(KeyCode::Down)).await;
+        app.handle_key_event(press(KeyCode::Down)).await;
+        app.handle_key_event(press(KeyCode::Char('V'))).await;
+        app.handle_key_event(press(KeyCode::Down)).await;
+        assert!(
+            app.is_imposter_marked(3),
+            "the range is shown before it's closed"
+        );
+        app.handle_key_event(press(KeyCode::Down)).await;
+        app.handle_key_event(press(KeyCode::Char('V'))).await;
+        assert_eq!(app.marked_ports(), vec![4545, 4547, 4548, 4549]);
+
+        // Unmark one again, then delete the rest in one confirmation
+        app.handle_key_event(press(KeyCode::Char(' '))).await;
+        app.handle_key_event(press(KeyCode::Char('d'))).await;
+        match &app.overlay {
+            Overlay::Confirm { message, action } => {
+                assert_eq!(message, "Delete 3 marked imposters (:4545, :4547, :4548)?");
+                assert_eq!(
+                    *action,
+                    PendingAction::DeleteImposters {
+                        ports: vec![4545, 4547, 4548]
+                    }
+                );
+            }
+            other => panic!("expected a confirmation, got {other:?}"),
+        }
+
+        // Esc closes the confirmation, then drops the marks before anything else
+        app.handle_key_event(press(KeyCode::Esc)).await;
+        app.handle_key_event(press(KeyCode::Esc)).await;
+        assert!(app.marked_ports().is_empty());
+        assert!(!app.should_quit);
+    }
+
+    #[tokio::test]
+    async fn marked_stubs_are_exported_together() {
+        let mut app = make_test_app();
+        let mut detail: ImposterDetail =
+            serde_json::from_value(serde_json::json!({"port": 4545, "protocol": "http"})).unwrap();
+        detail.stubs = vec![stub("/a"), stub("/b"), stub("/c")];
+        app.current_imposter = Some(detail);
+        app.view = View::ImposterDetail { port: 4545 };
+        app.stub_list_state.select(Some(0));
+
+        app.handle_key_event(press(KeyCode::Char(' '))).await;
+        app.handle_key_event(press(KeyCode::Down)).await;
+        app.handle_key_event(press(KeyCode::Down)).await;
+        app.handle_key_event(press(KeyCode::Char(' '))).await;
+        assert_eq!(app.marked_stub_indices(), vec![0, 2]);
+
+        app.handle_key_event(press(KeyCode::Char('x'))).await;
+        let Overlay::Export { content, port, .. } = &app.overlay else {
+            panic!("expected the export overlay, got {:?}", app.overlay);
+        };
+        assert_eq!(*port, Some(4545));
+        let exported: serde_json::Value = serde_json::from_str(content).unwrap();
+        let paths: Vec<&str> = exported["stubs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|stub| stub["predicates"][0]["equals"]["path"].as_str().unwrap())
+            .collect();
+        assert_eq!(paths, ["/a", "/c"]);
+
+        app.overlay = Overlay::None;
+        app.handle_key_event(press(KeyCode::Char('d'))).await;
+        assert!(matches!(
+            app.overlay,
+            Overlay::Confirm {
+                action: PendingAction::DeleteStubs { port: 4545, ref indices },
+                ..
+            } if *indices == [0, 2]
+        ));
+    }
+}
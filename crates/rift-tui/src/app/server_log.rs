@@ -0,0 +1,335 @@
+//! Server log: a polled tail of the Rift server's own log, from `GET /logs`
+
+use super::*;
+use crate::api::{LogEntry, LogsPage};
+
+/// How many log lines the pane keeps, as many as the server does
+pub const MAX_SERVER_LOG_ENTRIES: usize = 1000;
+
+/// Log levels, least severe first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    #[default]
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// The level of a `GET /logs` entry; anything unknown is shown as `info`
+    pub fn parse(level: &str) -> Self {
+        match level.to_ascii_lowercase().as_str() {
+            "trace" => LogLevel::Trace,
+            "debug" => LogLevel::Debug,
+            "warn" | "warning" => LogLevel::Warn,
+            "error" => LogLevel::Error,
+            _ => LogLevel::Info,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LogLevel::Trace => "trace",
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+        }
+    }
+
+    /// The next minimum level to filter by, wrapping back to showing everything
+    pub fn next(self) -> Self {
+        match self {
+            LogLevel::Trace => LogLevel::Debug,
+            LogLevel::Debug => LogLevel::Info,
+            LogLevel::Info => LogLevel::Warn,
+            LogLevel::Warn => LogLevel::Error,
+            LogLevel::Error => LogLevel::Trace,
+        }
+    }
+}
+
+/// State of the server log view
+#[derive(Debug, Default)]
+pub struct ServerLog {
+    pub entries: VecDeque<LogEntry>,
+    /// Index of the next entry to ask for, sent back as `?startIndex=`
+    pub cursor: Option<u64>,
+    /// Entries below this level are hidden
+    pub min_level: LogLevel,
+    /// While paused the log is not polled, so the pane holds still
+    pub paused: bool,
+    /// Keep the newest line selected as new ones arrive
+    pub follow: bool,
+    pub list_state: ListState,
+}
+
+impl ServerLog {
+    pub fn new() -> Self {
+        Self {
+            follow: true,
+            ..Default::default()
+        }
+    }
+
+    /// Append one poll's worth of lines. Returns how many lines the server logged but no longer
+    /// had when asked, because the log was paused or polled too slowly.
+    pub fn append(&mut self, page: LogsPage) -> u64 {
+        let start = self.cursor.unwrap_or(0);
+        let received = page.logs.len() as u64;
+        // Servers without a cursor page by the same indices, so count along
+        let next = page.next_index.unwrap_or(start + received);
+        // A cursor going backwards means the server restarted: start over from its first line
+        if next < start {
+            self.entries.clear();
+            self.cursor = Some(0);
+            return 0;
+        }
+        let missed = if page.truncated {
+            (next - start).saturating_sub(received)
+        } else {
+            0
+        };
+        self.cursor = Some(next);
+        for entry in page.logs {
+            while self.entries.len() >= MAX_SERVER_LOG_ENTRIES {
+                self.entries.pop_front();
+            }
+            self.entries.push_back(entry);
+        }
+        // The first load catches up on what the server kept; that isn't a gap
+        if start == 0 { 0 } else { missed }
+    }
+}
+
+impl App {
+    /// Open the server log, keeping what was already collected
+    pub(super) async fn open_server_log(&mut self) {
+        if self.server_log.is_none() {
+            self.server_log = Some(ServerLog::new());
+        }
+        self.navigate(View::ServerLog);
+        self.poll_server_log().await;
+    }
+
+    /// Fetch the lines logged since the last poll, unless the log is paused
+    pub(super) async fn poll_server_log(&mut self) {
+        let Some(cursor) = self
+            .server_log
+            .as_ref()
+            .filter(|log| !log.paused)
+            .map(|log| log.cursor.unwrap_or(0))
+        else {
+            return;
+        };
+        match self.client.get_logs(cursor).await {
+            Ok(page) => {
+                let missed = self.server_log.as_mut().map_or(0, |log| log.append(page));
+                if missed > 0 {
+                    self.set_status(
+                        format!("{missed} log lines were dropped before they could be read"),
+                        StatusLevel::Warning,
+                    );
+                }
+            }
+            Err(e) => self.set_status(
+                format!("Failed to load the server log: {e}"),
+                StatusLevel::Error,
+            ),
+        }
+    }
+
+    /// Lines at or above the minimum level whose level or message matches the search query
+    pub fn filtered_server_log(&self) -> Vec<&LogEntry> {
+        let Some(log) = &self.server_log else {
+            return vec![];
+        };
+        let query = self.search_query.to_lowercase();
+        log.entries
+            .iter()
+            .filter(|entry| LogLevel::parse(&entry.level) >= log.min_level)
+            .filter(|entry| {
+                query.is_empty()
+                    || entry.message.to_lowercase().contains(&query)
+                    || entry.level.to_lowercase() == query
+            })
+            .collect()
+    }
+
+    /// Move the server log selection by `delta` rows; moving stops following the tail
+    pub(super) fn scroll_server_log(&mut self, delta: isize) {
+        let len = self.filtered_server_log().len();
+        let Some(log) = self.server_log.as_mut() else {
+            return;
+        };
+        if len == 0 {
+            return;
+        }
+        let current = if log.follow {
+            len - 1
+        } else {
+            log.list_state.selected().unwrap_or(len - 1).min(len - 1)
+        };
+        let next = current.saturating_add_signed(delta).min(len - 1);
+        log.follow = next == len - 1;
+        log.list_state.select(Some(next));
+    }
+
+    pub(super) async fn handle_server_log_event(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.scroll_server_log(1),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll_server_log(-1),
+            KeyCode::PageDown => self.scroll_server_log(10),
+            KeyCode::PageUp => self.scroll_server_log(-10),
+            KeyCode::Char('G') | KeyCode::End => {
+                if let Some(log) = self.server_log.as_mut() {
+                    log.follow = true;
+                    log.list_state.select(None);
+                }
+            }
+            KeyCode::Char('f') => {
+                let Some(log) = self.server_log.as_mut() else {
+                    return;
+                };
+                log.min_level = log.min_level.next();
+                log.follow = true;
+                log.list_state.select(None);
+                let message = match log.min_level {
+                    LogLevel::Trace => "Showing every log level".to_string(),
+                    level => format!("Showing {} and above", level.label()),
+                };
+                self.set_status(message, StatusLevel::Info);
+            }
+            KeyCode::Char('p') | KeyCode::Char(' ') => {
+                let Some(log) = self.server_log.as_mut() else {
+                    return;
+                };
+                log.paused = !log.paused;
+                if log.paused {
+                    self.set_status("Server log paused".to_string(), StatusLevel::Info);
+                } else {
+                    self.set_status("Server log resumed".to_string(), StatusLevel::Info);
+                    self.poll_server_log().await;
+                }
+            }
+            KeyCode::Char('c') => {
+                if let Some(log) = self.server_log.as_mut() {
+                    log.entries.clear();
+                    log.list_state.select(None);
+                    log.follow = true;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::make_test_app;
+
+    fn line(level: &str, message: &str) -> LogEntry {
+        LogEntry {
+            level: level.to_string(),
+            message: message.to_string(),
+            timestamp: String::new(),
+        }
+    }
+
+    fn page(next_index: u64, truncated: bool, logs: Vec<LogEntry>) -> LogsPage {
+        LogsPage {
+            logs,
+            next_index: Some(next_index),
+            truncated,
+        }
+    }
+
+    #[test]
+    fn append_advances_the_cursor_and_reports_dropped_lines() {
+        let mut log = ServerLog::new();
+        // The first load starts wherever the server's buffer does
+        assert_eq!(log.append(page(12, true, vec![line("info", "a")])), 0);
+        assert_eq!(log.cursor, Some(12));
+
+        assert_eq!(
+            log.append(page(14, false, vec![line("info", "b"), line("info", "c")])),
+            0
+        );
+        // Lines 14 to 16 were evicted before this poll
+        assert_eq!(log.append(page(18, true, vec![line("warn", "g")])), 3);
+        assert_eq!(log.cursor, Some(18));
+        assert_eq!(log.entries.len(), 4);
+    }
+
+    #[test]
+    fn append_starts_over_when_the_server_restarted() {
+        let mut log = ServerLog::new();
+        log.append(page(40, false, vec![line("info", "old")]));
+        assert_eq!(log.append(page(3, false, vec![])), 0);
+        assert!(log.entries.is_empty());
+        assert_eq!(log.cursor, Some(0));
+    }
+
+    #[test]
+    fn append_counts_along_without_a_cursor_header() {
+        let mut log = ServerLog::new();
+        let logs = vec![line("info", "a"), line("info", "b")];
+        log.append(LogsPage {
+            logs,
+            ..Default::default()
+        });
+        assert_eq!(log.cursor, Some(2));
+    }
+
+    #[test]
+    fn filter_by_minimum_level_and_search() {
+        let mut app = make_test_app();
+        let mut log = ServerLog::new();
+        log.append(page(
+            4,
+            false,
+            vec![
+                line("debug", "no stub matched GET /orders"),
+                line("info", "Imposter 4545 started"),
+                line("warn", "Slow script on /orders"),
+                line("error", "Upstream refused"),
+            ],
+        ));
+        app.server_log = Some(log);
+
+        let messages = |app: &App| {
+            app.filtered_server_log()
+                .iter()
+                .map(|entry| entry.message.clone())
+                .collect::<Vec<_>>()
+        };
+        app.search_query = "ORDERS".to_string();
+        assert_eq!(
+            messages(&app),
+            ["no stub matched GET /orders", "Slow script on /orders"]
+        );
+        app.server_log.as_mut().unwrap().min_level = LogLevel::Info;
+        assert_eq!(messages(&app), ["Slow script on /orders"]);
+        app.search_query.clear();
+        assert_eq!(messages(&app).len(), 3);
+    }
+
+    #[tokio::test]
+    async fn f_cycles_the_minimum_level() {
+        let mut app = make_test_app();
+        app.server_log = Some(ServerLog::new());
+        app.navigate(View::ServerLog);
+        for expected in [LogLevel::Debug, LogLevel::Info, LogLevel::Warn] {
+            app.handle_key_event(KeyEvent::from(KeyCode::Char('f')))
+                .await;
+            assert_eq!(app.server_log.as_ref().unwrap().min_level, expected);
+        }
+        assert_eq!(
+            app.status_message.as_ref().unwrap().0,
+            "Showing warn and above"
+        );
+    }
+}
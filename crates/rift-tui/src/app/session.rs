@@ -0,0 +1,204 @@
+//! Resuming where the last session was quit: server, view, search, filters and positions
+
+use super::*;
+use crate::servers::ALL_SERVERS;
+use crate::state::{Session, SessionView, State};
+
+/// The view a session resumes on for `view`, and the imposter it shows
+fn session_view(view: &View) -> Option<(SessionView, Option<u16>)> {
+    match view {
+        View::ImposterList => Some((SessionView::ImposterList, None)),
+        View::ImposterDetail { port } => Some((SessionView::ImposterDetail, Some(*port))),
+        View::StubDetail { port, .. } => Some((SessionView::StubDetail, Some(*port))),
+        View::RequestLog { port } => Some((SessionView::RequestLog, Some(*port))),
+        View::Metrics => Some((SessionView::Metrics, None)),
+        View::ServerLog => Some((SessionView::ServerLog, None)),
+        View::Config => Some((SessionView::Config, None)),
+        View::StubEdit { .. }
+        | View::ImposterEdit
+        | View::RequestDetail { .. }
+        | View::Scenario => None,
+    }
+}
+
+impl App {
+    /// Where this session is, to resume on next time
+    pub fn session(&self) -> Session {
+        let server = match self.active_server {
+            Some(index) => self
+                .servers
+                .get(index)
+                .map(|server| server.profile.name.clone()),
+            None => Some(ALL_SERVERS.to_string()),
+        };
+        // Editors and one-off views resume on the view they were opened from
+        let (view, port) = std::iter::once(&self.view)
+            .chain(self.view_stack.iter().rev())
+            .find_map(|view| Some((view, session_view(view)?)))
+            .map_or((SessionView::ImposterList, None), |(_, found)| found);
+        let in_imposter = matches!(view, SessionView::ImposterDetail | SessionView::StubDetail);
+        let stub = match self.view {
+            View::StubDetail { index, .. } => Some(index),
+            _ if in_imposter => self.stub_list_state.selected(),
+            _ => None,
+        };
+        Session {
+            server,
+            view,
+            imposter: port.or_else(|| self.selected_imposter().map(|imp| imp.port)),
+            stub,
+            scroll: if view == SessionView::StubDetail {
+                self.stub_detail_scroll
+            } else {
+                0
+            },
+            search: self.search_query.clone(),
+            filter: self.imposter_filter.clone(),
+        }
+    }
+
+    /// Remember this session for the next, keeping the rest of the saved state
+    pub fn save_session(&self) -> anyhow::Result<()> {
+        let Some(path) = &self.state_path else {
+            return Ok(());
+        };
+        let session = self.session();
+        State::update(path, |state| state.session = Some(session))
+    }
+
+    /// Go back to where `session` left off, as far as the server still has it
+    pub async fn restore_session(&mut self, session: &Session) {
+        self.imposter_filter = session.filter.clone();
+        if let Some(index) = session
+            .imposter
+            .and_then(|port| self.imposters.iter().position(|imp| imp.port == port))
+        {
+            self.imposter_list_state.select(Some(index));
+        }
+        // Only the list holds every server's imposters; the other views show one server
+        if !self.showing_all_servers() {
+            self.resume_view(session).await;
+        }
+        // Opening a view clears the search, so it comes back last
+        self.search_query = session.search.clone();
+    }
+
+    async fn resume_view(&mut self, session: &Session) {
+        let selected = self.selected_imposter().map(|imp| imp.port);
+        match session.view {
+            SessionView::ImposterList => {}
+            SessionView::ImposterDetail | SessionView::StubDetail => {
+                if selected.is_none() || selected != session.imposter {
+                    return;
+                }
+                self.enter_imposter_detail().await;
+                let View::ImposterDetail { port } = self.view else {
+                    return;
+                };
+                let stubs = self
+                    .current_imposter
+                    .as_ref()
+                    .map_or(0, |imp| imp.stubs.len());
+                let Some(index) = session.stub.filter(|index| *index < stubs) else {
+                    return;
+                };
+                self.stub_list_state.select(Some(index));
+                if session.view == SessionView::StubDetail {
+                    self.stub_detail_scroll = session.scroll;
+                    self.navigate(View::StubDetail { port, index });
+                }
+            }
+            SessionView::RequestLog => {
+                if let Some(port) = selected.filter(|port| Some(*port) == session.imposter) {
+                    self.open_request_log(port).await;
+                }
+            }
+            SessionView::Metrics => self.navigate(View::Metrics),
+            SessionView::ServerLog => self.open_server_log().await,
+            SessionView::Config => self.open_config_view().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::{make_imposter, make_test_app};
+
+    #[test]
+    fn editors_are_remembered_as_the_view_they_were_opened_from() {
+        let mut app = make_test_app();
+        app.servers = vec![Server::new(
+            crate::servers::ServerProfile::from_url("http://localhost:2525"),
+            app.client.clone(),
+        )];
+        app.imposters = vec![make_imposter(4545, None, "http")];
+        app.navigate(View::ImposterDetail { port: 4545 });
+        app.stub_list_state.select(Some(3));
+        app.stub_detail_scroll = 5;
+        app.navigate(View::StubDetail {
+            port: 4545,
+            index: 3,
+        });
+        app.navigate(View::StubEdit {
+            port: 4545,
+            index: Some(3),
+        });
+        app.search_query = "orders".to_string();
+
+        let session = app.session();
+        assert_eq!(session.server.as_deref(), Some("localhost:2525"));
+        assert_eq!(session.view, SessionView::StubDetail);
+        assert_eq!(session.imposter, Some(4545));
+        assert_eq!(session.stub, Some(3));
+        assert_eq!(session.scroll, 5);
+        assert_eq!(session.search, "orders");
+
+        app.active_server = None;
+        app.view = View::ImposterList;
+        app.view_stack.clear();
+        app.imposter_list_state.select(Some(0));
+        let session = app.session();
+        assert_eq!(session.server.as_deref(), Some("all"));
+        assert_eq!(session.view, SessionView::ImposterList);
+        assert_eq!(session.imposter, Some(4545));
+        assert_eq!(session.scroll, 0);
+    }
+
+    #[tokio::test]
+    async fn a_session_resumes_on_the_list_when_its_imposter_is_gone() {
+        let mut app = make_test_app();
+        app.imposters = vec![
+            make_imposter(4545, None, "http"),
+            make_imposter(4546, None, "https"),
+        ];
+        app.imposter_list_state.select(Some(0));
+        let session = Session {
+            view: SessionView::ImposterDetail,
+            imposter: Some(4546),
+            search: "45".to_string(),
+            filter: ImposterFilter {
+                protocol: Some("https".to_string()),
+                enabled: None,
+            },
+            ..Session::default()
+        };
+
+        app.client = ApiClient::new("http://127.0.0.1:9");
+        app.restore_session(&session).await;
+        assert_eq!(app.selected_imposter().map(|imp| imp.port), Some(4546));
+        assert_eq!(app.search_query, "45");
+        assert_eq!(app.imposter_filter.protocol.as_deref(), Some("https"));
+        // The server no longer answers for it, so the list is where it resumes
+        assert_eq!(app.view, View::ImposterList);
+
+        let gone = Session {
+            view: SessionView::ImposterDetail,
+            imposter: Some(9999),
+            ..Session::default()
+        };
+        app.restore_session(&gone).await;
+        assert_eq!(app.view, View::ImposterList);
+        assert_eq!(app.selected_imposter().map(|imp| imp.port), Some(4546));
+    }
+}
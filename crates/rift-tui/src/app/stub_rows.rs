@@ -0,0 +1,205 @@
+//! What the stub list shows and searches of each stub, worked out once per load of the imposter
+//! rather than on every frame: an imposter with thousands of stubs would otherwise summarize and
+//! serialize every one of them on each redraw.
+
+use super::*;
+use crate::api::Stub;
+use std::rc::Rc;
+
+/// One stub's row in the stub list
+#[derive(Debug, Clone, PartialEq)]
+pub struct StubRow {
+    /// Scenario name, or a summary of the first predicate
+    pub label: String,
+    /// Kind of the first response: `is`, `inject`, `fault` or the proxy mode
+    pub response_type: &'static str,
+    pub is_proxy: bool,
+    pub predicates: usize,
+    pub responses: usize,
+    /// Lowercased scenario name, predicates and responses, one per line, for search
+    search_text: String,
+}
+
+impl StubRow {
+    pub fn new(stub: &Stub) -> Self {
+        let label = match &stub.scenario_name {
+            Some(scenario) => scenario.clone(),
+            None => summarize_predicates(&stub.predicates),
+        };
+        let (response_type, is_proxy) = match stub.responses.first() {
+            Some(response) => response_type(response),
+            None => ("no response", false),
+        };
+        let search_text = stub
+            .scenario_name
+            .iter()
+            .cloned()
+            .chain(stub.predicates.iter().map(|p| p.to_string()))
+            .chain(stub.responses.iter().map(|r| r.to_string()))
+            .collect::<Vec<_>>()
+            .join("\n")
+            .to_lowercase();
+        Self {
+            label,
+            response_type,
+            is_proxy,
+            predicates: stub.predicates.len(),
+            responses: stub.responses.len(),
+            search_text,
+        }
+    }
+
+    /// Whether the stub's scenario name, a predicate or a response contains `query`, which must
+    /// already be lowercase
+    pub fn matches(&self, query: &str) -> bool {
+        self.search_text.contains(query)
+    }
+}
+
+/// The current imposter's rows and which load of it they were built from
+#[derive(Debug, Default)]
+pub struct StubRows {
+    /// Bumped each time an imposter is loaded
+    version: u64,
+    built_for: Option<(u64, u16, usize)>,
+    rows: Rc<[StubRow]>,
+}
+
+impl App {
+    /// Show `detail` as the current imposter
+    pub(super) fn set_current_imposter(&mut self, detail: Option<ImposterDetail>) {
+        self.current_imposter = detail;
+        self.stub_row_cache.get_mut().version += 1;
+    }
+
+    /// The current imposter's stub rows, built on first use after it was loaded
+    pub fn stub_rows(&self) -> Rc<[StubRow]> {
+        let Some(imposter) = &self.current_imposter else {
+            return Rc::from([]);
+        };
+        let mut cache = self.stub_row_cache.borrow_mut();
+        let key = (cache.version, imposter.port, imposter.stubs.len());
+        if cache.built_for != Some(key) {
+            cache.rows = imposter.stubs.iter().map(StubRow::new).collect();
+            cache.built_for = Some(key);
+        }
+        Rc::clone(&cache.rows)
+    }
+}
+
+/// Summarize predicates for display
+fn summarize_predicates(predicates: &[serde_json::Value]) -> String {
+    if predicates.is_empty() {
+        return "(default)".to_string();
+    }
+
+    let pred = &predicates[0];
+    if let Some(obj) = pred.as_object() {
+        for (key, value) in obj {
+            match key.as_str() {
+                "equals" => {
+                    if let Some(v) = value.as_object() {
+                        let mut parts = Vec::new();
+                        if let Some(m) = v.get("method").and_then(|v| v.as_str()) {
+                            parts.push(m.to_string());
+                        }
+                        if let Some(p) = v.get("path").and_then(|v| v.as_str()) {
+                            parts.push(p.to_string());
+                        }
+                        if !parts.is_empty() {
+                            return parts.join(" ");
+                        }
+                    }
+                }
+                "contains" | "startsWith" | "endsWith" | "matches" => {
+                    return format!("{key} ...");
+                }
+                "and" | "or" => {
+                    if let Some(arr) = value.as_array() {
+                        return format!("{} ({} conditions)", key, arr.len());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    "(complex)".to_string()
+}
+
+/// Get the response type with proxy mode info
+fn response_type(response: &serde_json::Value) -> (&'static str, bool) {
+    if response.get("is").is_some() {
+        ("is", false)
+    } else if let Some(proxy) = response.get("proxy") {
+        // Get proxy mode if available
+        let mode = proxy
+            .get("mode")
+            .and_then(|m| m.as_str())
+            .unwrap_or("proxy");
+        let mode_display = match mode {
+            "proxyOnce" => "proxyOnce",
+            "proxyAlways" => "proxyAlways",
+            "proxyTransparent" => "transparent",
+            "proxyShadow" => "shadow",
+            _ => "proxy",
+        };
+        (mode_display, true)
+    } else if response.get("inject").is_some() {
+        ("inject", false)
+    } else if response.get("fault").is_some() {
+        ("fault", false)
+    } else {
+        ("unknown", false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::make_test_app;
+    use serde_json::json;
+
+    fn detail(stubs: Vec<Stub>) -> ImposterDetail {
+        serde_json::from_value(json!({ "port": 4545, "protocol": "http" }))
+            .map(|detail: ImposterDetail| ImposterDetail { stubs, ..detail })
+            .unwrap()
+    }
+
+    fn stub(path: &str) -> Stub {
+        serde_json::from_value(json!({
+            "predicates": [{ "equals": { "method": "GET", "path": path } }],
+            "responses": [{ "proxy": { "to": "http://upstream", "mode": "proxyAlways" } }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn a_row_summarizes_the_stub_and_searches_all_of_it() {
+        let row = StubRow::new(&stub("/Orders"));
+        assert_eq!(row.label, "GET /Orders");
+        assert_eq!((row.response_type, row.is_proxy), ("proxyAlways", true));
+        assert_eq!((row.predicates, row.responses), (1, 1));
+        assert!(row.matches("/orders"));
+        assert!(row.matches("upstream"));
+        assert!(!row.matches("delete"));
+    }
+
+    #[test]
+    fn rows_are_rebuilt_only_when_an_imposter_is_loaded() {
+        let mut app = make_test_app();
+        app.set_current_imposter(Some(detail(vec![stub("/a"), stub("/b")])));
+        let first = app.stub_rows();
+        assert_eq!(first.len(), 2);
+        assert!(
+            Rc::ptr_eq(&first, &app.stub_rows()),
+            "cached between frames"
+        );
+
+        app.set_current_imposter(Some(detail(vec![stub("/c"), stub("/d")])));
+        assert_eq!(app.stub_rows()[0].label, "GET /c");
+
+        app.set_current_imposter(None);
+        assert!(app.stub_rows().is_empty());
+    }
+}
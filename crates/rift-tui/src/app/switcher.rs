@@ -0,0 +1,235 @@
+//! Several Rift servers: switching between them, and every server's imposters in one list
+
+use super::*;
+use crate::servers::ServerProfile;
+
+/// A server the TUI can switch to
+pub struct Server {
+    pub profile: ServerProfile,
+    pub client: ApiClient,
+    /// Whether the last refresh reached it; `None` before one has
+    pub connected: Option<bool>,
+}
+
+impl Server {
+    pub fn new(profile: ServerProfile, client: ApiClient) -> Self {
+        Self {
+            profile,
+            client,
+            connected: None,
+        }
+    }
+}
+
+impl App {
+    /// Whether the imposter list holds every server's imposters
+    pub fn showing_all_servers(&self) -> bool {
+        self.active_server.is_none()
+    }
+
+    /// Name of the server an imposter in the list belongs to, when the list holds every server's
+    pub fn imposter_server_name(&self, imp: &ImposterSummary) -> Option<&str> {
+        imp.server
+            .and_then(|index| self.servers.get(index))
+            .map(|server| server.profile.name.as_str())
+    }
+
+    pub fn show_server_switcher(&mut self) {
+        // The entry after the servers is "all servers"
+        let selected = self.active_server.unwrap_or(self.servers.len());
+        self.overlay = Overlay::ServerSwitcher { selected };
+    }
+
+    pub(super) async fn handle_server_switcher_event(&mut self, key: KeyEvent, selected: usize) {
+        let last = self.servers.len();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('s') => {
+                self.overlay = Overlay::None;
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.overlay = Overlay::ServerSwitcher {
+                    selected: (selected + 1).min(last),
+                };
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.overlay = Overlay::ServerSwitcher {
+                    selected: selected.saturating_sub(1),
+                };
+            }
+            KeyCode::Char(c @ '1'..='9') => {
+                let index = c as usize - '1' as usize;
+                if index < self.servers.len() {
+                    self.overlay = Overlay::None;
+                    self.switch_server(Some(index)).await;
+                }
+            }
+            KeyCode::Char('a') => {
+                self.overlay = Overlay::None;
+                self.switch_server(None).await;
+            }
+            KeyCode::Enter => {
+                self.overlay = Overlay::None;
+                self.switch_server((selected < last).then_some(selected))
+                    .await;
+            }
+            _ => {}
+        }
+    }
+
+    /// Show `target`'s imposters, or every server's with `None`. What was open belongs to the
+    /// server left behind, so the view goes back to the imposter list.
+    pub async fn switch_server(&mut self, target: Option<usize>) {
+        if target == self.active_server {
+            return;
+        }
+        self.active_server = target;
+        if let Some(server) = target.and_then(|index| self.servers.get(index)) {
+            self.client = server.client.clone();
+            self.admin_url = server.profile.url.clone();
+        }
+        self.view = View::ImposterList;
+        self.view_stack.clear();
+        self.imposters.clear();
+        self.imposter_list_state.select(None);
+        self.set_current_imposter(None);
+        self.marked_imposters.clear();
+        self.marked_stubs.clear();
+        self.mark_anchor = None;
+        self.request_log = None;
+        self.server_log = None;
+        self.global_search = None;
+        self.reconnect = Reconnect::default();
+        self.last_updated = None;
+        self.inspected_request = None;
+        self.composer = None;
+        self.server_config = None;
+        self.metrics = MetricsData::default();
+        self.metrics_history.clear();
+        self.metrics_selected = 0;
+
+        let name = match target.and_then(|index| self.servers.get(index)) {
+            Some(server) => server.profile.name.clone(),
+            None => "all servers".to_string(),
+        };
+        self.refresh().await;
+        self.set_status(format!("Switched to {name}"), StatusLevel::Info);
+    }
+
+    /// With every server's imposters listed, switch to the selected imposter's server and keep
+    /// it selected, so what follows acts on that server. Returns whether an imposter is selected.
+    pub(super) async fn enter_selected_imposter_server(&mut self) -> bool {
+        let Some(imp) = self.selected_imposter() else {
+            return false;
+        };
+        let (port, server) = (imp.port, imp.server);
+        if self.active_server.is_some() || server.is_none() {
+            return true;
+        }
+        self.switch_server(server).await;
+        let index = self.imposters.iter().position(|imp| imp.port == port);
+        self.imposter_list_state.select(index);
+        index.is_some()
+    }
+
+    /// Load every server's imposters into one list, tagged with their server
+    pub(super) async fn refresh_all_servers(&mut self) {
+        let mut imposters = Vec::new();
+        let mut unreachable = Vec::new();
+        for (index, server) in self.servers.iter_mut().enumerate() {
+            let connected = matches!(server.client.health_check().await, Ok(true));
+            server.client.set_reachable(connected);
+            if connected {
+                match server.client.list_imposters().await {
+                    Ok(list) => imposters.extend(list.into_iter().map(|mut imp| {
+                        imp.server = Some(index);
+                        imp
+                    })),
+                    Err(e) => unreachable.push(format!("{}: {e}", server.profile.name)),
+                }
+            } else if server.connected != Some(false) {
+                // Reported once when it goes away, not on every refresh
+                unreachable.push(format!("{}: not reachable", server.profile.name));
+            }
+            server.connected = Some(connected);
+        }
+        self.is_connected = self.servers.iter().any(|s| s.connected == Some(true));
+        self.set_imposters(imposters);
+        let selected = match self.imposter_list_state.selected() {
+            _ if self.imposters.is_empty() => None,
+            Some(index) => Some(index.min(self.imposters.len() - 1)),
+            None => Some(0),
+        };
+        self.imposter_list_state.select(selected);
+        if !unreachable.is_empty() {
+            self.set_status(unreachable.join("; "), StatusLevel::Warning);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::{make_imposter, make_test_app};
+
+    fn servers() -> Vec<Server> {
+        ["local", "staging"]
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let url = format!("http://127.0.0.1:{}", 1 + i);
+                let profile = ServerProfile {
+                    name: name.to_string(),
+                    ..ServerProfile::from_url(&url)
+                };
+                Server::new(profile, ApiClient::new(&url))
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn switching_resets_to_the_new_servers_imposter_list() {
+        let mut app = make_test_app();
+        app.servers = servers();
+        app.active_server = Some(0);
+        app.view = View::Metrics;
+        app.view_stack.push(View::ImposterList);
+        app.server_log = Some(ServerLog::new());
+
+        app.show_server_switcher();
+        assert_eq!(app.overlay, Overlay::ServerSwitcher { selected: 0 });
+        app.handle_server_switcher_event(KeyEvent::from(KeyCode::Down), 0)
+            .await;
+        app.handle_server_switcher_event(KeyEvent::from(KeyCode::Enter), 1)
+            .await;
+        assert_eq!(app.active_server, Some(1));
+        assert_eq!(app.admin_url, "http://127.0.0.1:2");
+        assert_eq!(app.client.base_url(), "http://127.0.0.1:2");
+        assert_eq!(app.view, View::ImposterList);
+        assert!(app.view_stack.is_empty());
+        assert!(app.server_log.is_none(), "the log was the other server's");
+
+        app.handle_server_switcher_event(KeyEvent::from(KeyCode::Char('a')), 1)
+            .await;
+        assert!(app.showing_all_servers());
+        assert!(!app.is_connected);
+        // Unreachable servers are reported once, not on every refresh: staging already was
+        let message = &app.errors.back().unwrap().message;
+        assert!(
+            message.contains("local") && !message.contains("staging"),
+            "{message}"
+        );
+        let errors = app.errors.len();
+        app.refresh().await;
+        assert_eq!(app.errors.len(), errors);
+    }
+
+    #[test]
+    fn listed_imposters_name_their_server() {
+        let mut app = make_test_app();
+        app.servers = servers();
+        let mut imp = make_imposter(4545, None, "http");
+        assert_eq!(app.imposter_server_name(&imp), None);
+        imp.server = Some(1);
+        assert_eq!(app.imposter_server_name(&imp), Some("staging"));
+    }
+}
@@ -0,0 +1,396 @@
+//! Import and export previews: the imposters a file holds, or will hold, and what linting found
+//! in each, shown before anything is created or written
+
+use super::*;
+use crate::validation::{ValidationIssue, validate_imposter_value};
+
+/// One imposter of an import or export
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImposterOverview {
+    pub port: Option<u16>,
+    pub protocol: String,
+    pub name: Option<String>,
+    pub stubs: usize,
+    /// Responses that proxy to a real service
+    pub proxies: usize,
+    /// Requests recorded by the imposter, kept in the file
+    pub recorded_requests: usize,
+    pub record_requests: bool,
+    /// What linting found in it; empty for exports
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ImposterOverview {
+    fn of(config: &serde_json::Value) -> Self {
+        let stubs = config
+            .get("stubs")
+            .and_then(|stubs| stubs.as_array())
+            .map_or(&[][..], Vec::as_slice);
+        let proxies = stubs
+            .iter()
+            .filter_map(|stub| stub.get("responses").and_then(|r| r.as_array()))
+            .flatten()
+            .filter(|response| response.get("proxy").is_some())
+            .count();
+        Self {
+            port: config
+                .get("port")
+                .and_then(|port| port.as_u64())
+                .and_then(|port| u16::try_from(port).ok()),
+            protocol: config
+                .get("protocol")
+                .and_then(|protocol| protocol.as_str())
+                .unwrap_or("http")
+                .to_string(),
+            name: config
+                .get("name")
+                .and_then(|name| name.as_str())
+                .map(str::to_string),
+            stubs: stubs.len(),
+            proxies,
+            recorded_requests: config
+                .get("requests")
+                .and_then(|requests| requests.as_array())
+                .map_or(0, Vec::len),
+            record_requests: config
+                .get("recordRequests")
+                .and_then(|record| record.as_bool())
+                .unwrap_or(false),
+            issues: Vec::new(),
+        }
+    }
+}
+
+/// The imposters of a config file: its `imposters`, the array it is, or itself, as rift-lint
+/// reads it
+fn imposter_configs(config: &serde_json::Value) -> Vec<&serde_json::Value> {
+    match config
+        .get("imposters")
+        .and_then(|imposters| imposters.as_array())
+        .or_else(|| config.as_array())
+    {
+        Some(imposters) => imposters.iter().collect(),
+        None => vec![config],
+    }
+}
+
+/// Every imposter of `config`, each linted when `lint` is set
+pub fn overview(config: &serde_json::Value, source: &str, lint: bool) -> Vec<ImposterOverview> {
+    imposter_configs(config)
+        .into_iter()
+        .map(|imposter| {
+            let mut overview = ImposterOverview::of(imposter);
+            if lint {
+                overview.issues = validate_imposter_value(imposter, source).issues;
+            }
+            overview
+        })
+        .collect()
+}
+
+/// What confirming a preview does
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransferAction {
+    /// Create the imposters of a file read in
+    Import { content: String },
+    /// Write `files`, as (path, content), to `destination`, a file or a folder
+    Export {
+        destination: String,
+        files: Vec<(String, String)>,
+    },
+}
+
+/// An import or export waiting to be confirmed
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferPreview {
+    pub title: String,
+    pub imposters: Vec<ImposterOverview>,
+    /// Problems with the file as a whole, such as JSON that does not parse, or imposters an
+    /// export had to leave out
+    pub problems: Vec<ValidationIssue>,
+    pub action: TransferAction,
+    pub scroll: u16,
+}
+
+impl TransferPreview {
+    /// Errors in what would be imported, which keep it from being imported
+    pub fn errors(&self) -> usize {
+        self.issues()
+            .filter(|issue| issue.severity == IssueSeverity::Error)
+            .count()
+    }
+
+    pub fn warnings(&self) -> usize {
+        self.issues()
+            .filter(|issue| issue.severity == IssueSeverity::Warning)
+            .count()
+    }
+
+    fn issues(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.problems
+            .iter()
+            .chain(self.imposters.iter().flat_map(|imp| &imp.issues))
+    }
+
+    /// Whether confirming is refused
+    pub fn blocked(&self) -> bool {
+        matches!(self.action, TransferAction::Import { .. }) && self.errors() > 0
+    }
+
+    pub fn proxies(&self) -> usize {
+        self.imposters.iter().map(|imp| imp.proxies).sum()
+    }
+
+    pub fn recorded_requests(&self) -> usize {
+        self.imposters.iter().map(|imp| imp.recorded_requests).sum()
+    }
+}
+
+impl App {
+    /// Preview importing `content`, read from `path`
+    pub(in crate::app) fn preview_import(&mut self, path: &str, content: String) {
+        let (imposters, problems) = match serde_json::from_str::<serde_json::Value>(&content) {
+            Ok(config) => (overview(&config, path, true), Vec::new()),
+            // The lint of a file that does not parse is the parse error
+            Err(_) => (Vec::new(), validate_imposter_json(&content, path).issues),
+        };
+        self.transfer_preview = Some(TransferPreview {
+            title: format!("Import {path}"),
+            imposters,
+            problems,
+            action: TransferAction::Import { content },
+            scroll: 0,
+        });
+        self.overlay = Overlay::TransferPreview;
+    }
+
+    /// Preview writing `files` to `destination`; `left_out` says which imposters could not be
+    /// exported and why
+    pub(in crate::app) fn preview_export(
+        &mut self,
+        destination: String,
+        files: Vec<(String, String)>,
+        left_out: Vec<String>,
+    ) {
+        let imposters = files
+            .iter()
+            .filter_map(|(_, content)| serde_json::from_str::<serde_json::Value>(content).ok())
+            .flat_map(|config| overview(&config, &destination, false))
+            .collect();
+        let problems = left_out
+            .into_iter()
+            .map(|message| ValidationIssue {
+                severity: IssueSeverity::Warning,
+                code: "export".to_string(),
+                message,
+                location: None,
+                suggestion: None,
+            })
+            .collect();
+        self.transfer_preview = Some(TransferPreview {
+            title: format!("Export to {destination}"),
+            imposters,
+            problems,
+            action: TransferAction::Export { destination, files },
+            scroll: 0,
+        });
+        self.overlay = Overlay::TransferPreview;
+    }
+
+    /// Import or write what the preview shows
+    async fn confirm_transfer(&mut self) {
+        let Some(preview) = self.transfer_preview.as_ref() else {
+            return;
+        };
+        if preview.blocked() {
+            self.set_status(
+                format!(
+                    "Not imported: fix the {} errors in the file first",
+                    preview.errors()
+                ),
+                StatusLevel::Error,
+            );
+            return;
+        }
+        let Some(preview) = self.transfer_preview.take() else {
+            return;
+        };
+        self.overlay = Overlay::None;
+        match preview.action {
+            TransferAction::Import { content } => {
+                self.is_loading = true;
+                self.do_import(&content).await;
+                self.is_loading = false;
+            }
+            TransferAction::Export { destination, files } => {
+                self.write_export(&destination, files, preview.imposters.len())
+                    .await
+            }
+        }
+    }
+
+    /// Write the files of an export, reporting how many imposters made it
+    async fn write_export(
+        &mut self,
+        destination: &str,
+        files: Vec<(String, String)>,
+        count: usize,
+    ) {
+        let mut failed = Vec::new();
+        for (path, content) in &files {
+            let parent = std::path::Path::new(path).parent();
+            let written = match parent.filter(|dir| !dir.as_os_str().is_empty()) {
+                Some(dir) => tokio::fs::create_dir_all(dir).await,
+                None => Ok(()),
+            };
+            if let Err(e) = match written {
+                Ok(()) => tokio::fs::write(path, content).await,
+                Err(e) => Err(e),
+            } {
+                failed.push(format!("failed to write {path}: {e}"));
+            }
+        }
+        match failed.last().cloned() {
+            None => self.set_status(
+                format!("Exported {count} imposters to {destination}"),
+                StatusLevel::Success,
+            ),
+            Some(last) => {
+                let written = files.len() - failed.len();
+                for failure in failed.iter().cloned() {
+                    self.push_error(failure);
+                }
+                self.set_status(
+                    format!(
+                        "Wrote {written} of {} files, {} failed ({last})",
+                        files.len(),
+                        failed.len()
+                    ),
+                    StatusLevel::Warning,
+                );
+            }
+        }
+    }
+
+    pub(super) async fn handle_transfer_preview_event(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.transfer_preview = None;
+                self.overlay = Overlay::None;
+            }
+            KeyCode::Enter => self.confirm_transfer().await,
+            _ => {
+                let Some(preview) = self.transfer_preview.as_mut() else {
+                    return;
+                };
+                preview.scroll = match key.code {
+                    KeyCode::Down | KeyCode::Char('j') => preview.scroll.saturating_add(1),
+                    KeyCode::Up | KeyCode::Char('k') => preview.scroll.saturating_sub(1),
+                    KeyCode::PageDown => preview.scroll.saturating_add(10),
+                    KeyCode::PageUp => preview.scroll.saturating_sub(10),
+                    _ => preview.scroll,
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::make_test_app;
+
+    #[test]
+    fn an_import_is_linted_imposter_by_imposter() {
+        let mut app = make_test_app();
+        let content = serde_json::json!({
+            "imposters": [
+                {
+                    "port": 4545,
+                    "protocol": "http",
+                    "name": "orders",
+                    "recordRequests": true,
+                    "requests": [{ "method": "GET", "path": "/orders" }],
+                    "stubs": [{ "responses": [
+                        { "proxy": { "to": "http://orders:8080" } },
+                        { "is": { "statusCode": 200 } }
+                    ] }]
+                },
+                { "port": 4546, "protocol": "http" }
+            ]
+        })
+        .to_string();
+        app.preview_import("imposters.json", content);
+
+        assert_eq!(app.overlay, Overlay::TransferPreview);
+        let preview = app.transfer_preview.as_ref().unwrap();
+        let orders = &preview.imposters[0];
+        assert_eq!(
+            (orders.port, orders.name.as_deref(), orders.stubs),
+            (Some(4545), Some("orders"), 1)
+        );
+        assert_eq!((orders.proxies, orders.recorded_requests), (1, 1));
+        assert!(orders.record_requests);
+        // The second imposter has no stubs, which is its own error and not the first's
+        assert!(
+            orders
+                .issues
+                .iter()
+                .all(|i| i.severity != IssueSeverity::Error)
+        );
+        assert!(preview.imposters[1].issues.iter().any(|i| i.code == "E003"));
+        assert!(preview.blocked());
+    }
+
+    #[tokio::test]
+    async fn an_import_with_errors_is_refused() {
+        let mut app = make_test_app();
+        app.preview_import("broken.json", "{ not json".to_string());
+        let preview = app.transfer_preview.as_ref().unwrap();
+        assert!(preview.imposters.is_empty());
+        assert_eq!(preview.problems[0].code, "E002");
+
+        app.handle_transfer_preview_event(KeyEvent::from(KeyCode::Enter))
+            .await;
+        assert_eq!(app.overlay, Overlay::TransferPreview);
+        assert_eq!(
+            app.status_message.as_ref().unwrap().0,
+            "Not imported: fix the 1 errors in the file first"
+        );
+    }
+
+    #[tokio::test]
+    async fn an_export_is_written_once_confirmed() {
+        let mut app = make_test_app();
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path().join("imposters");
+        let file = folder.join("4545.json").to_string_lossy().to_string();
+        let config = serde_json::json!({
+            "port": 4545,
+            "protocol": "https",
+            "stubs": [{ "responses": [{ "proxy": { "to": "http://x" } }] }]
+        });
+        app.preview_export(
+            folder.to_string_lossy().to_string(),
+            vec![(file.clone(), config.to_string())],
+            vec!["failed to export port 4546: gone".to_string()],
+        );
+        let preview = app.transfer_preview.as_ref().unwrap();
+        assert_eq!(preview.proxies(), 1);
+        assert_eq!(preview.warnings(), 1);
+        assert!(!preview.blocked());
+        assert!(!std::path::Path::new(&file).exists());
+
+        app.handle_transfer_preview_event(KeyEvent::from(KeyCode::Enter))
+            .await;
+        assert_eq!(app.overlay, Overlay::None);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), config.to_string());
+        assert!(
+            app.status_message
+                .as_ref()
+                .unwrap()
+                .0
+                .starts_with("Exported 1 imposters to ")
+        );
+    }
+}
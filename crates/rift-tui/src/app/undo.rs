@@ -0,0 +1,299 @@
+//! Undo/redo for destructive stub and imposter changes
+//!
+//! Each change is journaled with what it replaced, so `u` can send the inverse call and `U` send
+//! the change again. The journal lives only as long as the session.
+
+use super::*;
+
+/// How many changes `u` can walk back
+pub const JOURNAL_DEPTH: usize = 50;
+
+/// A change to an imposter that can be undone
+#[derive(Debug, Clone)]
+pub enum Operation {
+    /// Imposter `port` deleted; `config` is its replayable export from just before
+    DeleteImposter {
+        port: u16,
+        config: serde_json::Value,
+    },
+    /// Stub `index` deleted from imposter `port`
+    DeleteStub { port: u16, index: usize, stub: Stub },
+    /// Stub `from` of imposter `port` moved to `to`
+    MoveStub { port: u16, from: usize, to: usize },
+    /// Several changes made by one bulk action, in the order they were made
+    Batch {
+        description: String,
+        operations: Vec<Operation>,
+    },
+}
+
+impl Operation {
+    /// What the change did, for the status line
+    pub fn describe(&self) -> String {
+        match self {
+            Operation::DeleteImposter { port, .. } => format!("delete imposter :{port}"),
+            Operation::DeleteStub { port, index, .. } => {
+                format!("delete stub #{} from :{port}", index + 1)
+            }
+            Operation::MoveStub { port, from, to, .. } => {
+                format!("move stub #{} to #{} on :{port}", from + 1, to + 1)
+            }
+            Operation::Batch { description, .. } => description.clone(),
+        }
+    }
+
+    /// Send the call that reverses the change
+    async fn revert(&self, client: &ApiClient) -> Result<(), ApiError> {
+        match self {
+            Operation::DeleteImposter { config, .. } => client.restore_imposter(config).await,
+            Operation::DeleteStub { port, index, stub } => {
+                client.add_stub(*port, stub.clone(), Some(*index)).await
+            }
+            Operation::MoveStub { port, from, to } => {
+                client.move_stub(*port, *to, *from).await.map(|_| ())
+            }
+            Operation::Batch { operations, .. } => {
+                for operation in operations.iter().rev() {
+                    Box::pin(operation.revert(client)).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Send the change again
+    async fn replay(&self, client: &ApiClient) -> Result<(), ApiError> {
+        match self {
+            Operation::DeleteImposter { port, .. } => client.delete_imposter(*port).await,
+            Operation::DeleteStub { port, index, .. } => client.delete_stub(*port, *index).await,
+            Operation::MoveStub { port, from, to } => {
+                client.move_stub(*port, *from, *to).await.map(|_| ())
+            }
+            Operation::Batch { operations, .. } => {
+                for operation in operations {
+                    Box::pin(operation.replay(client)).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A journaled change, with the server it was made on
+#[derive(Clone)]
+pub struct JournalEntry {
+    pub client: ApiClient,
+    pub operation: Operation,
+}
+
+/// The changes that can be undone, and the undone ones that can be redone
+#[derive(Default)]
+pub struct Journal {
+    undo: VecDeque<JournalEntry>,
+    redo: Vec<JournalEntry>,
+}
+
+impl Journal {
+    /// Record a change just made; it can no longer be followed by a redo
+    pub fn record(&mut self, client: &ApiClient, operation: Operation) {
+        self.redo.clear();
+        self.push_undo(JournalEntry {
+            client: client.clone(),
+            operation,
+        });
+    }
+
+    fn push_undo(&mut self, entry: JournalEntry) {
+        if self.undo.len() == JOURNAL_DEPTH {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(entry);
+    }
+
+    /// The change `u` would undo
+    pub fn next_undo(&self) -> Option<&Operation> {
+        self.undo.back().map(|entry| &entry.operation)
+    }
+
+    /// The change `U` would redo
+    pub fn next_redo(&self) -> Option<&Operation> {
+        self.redo.last().map(|entry| &entry.operation)
+    }
+}
+
+impl App {
+    /// Undo the last change by sending its inverse call
+    pub async fn undo(&mut self) {
+        let Some(entry) = self.journal.undo.pop_back() else {
+            self.set_status("Nothing to undo".to_string(), StatusLevel::Info);
+            return;
+        };
+        self.is_loading = true;
+        let result = entry.operation.revert(&entry.client).await;
+        self.is_loading = false;
+        let description = entry.operation.describe();
+        match result {
+            Ok(()) => {
+                self.journal.redo.push(entry);
+                self.set_status(format!("Undid: {description}"), StatusLevel::Success);
+                self.refresh().await;
+            }
+            Err(e) => {
+                self.journal.undo.push_back(entry);
+                self.set_status(
+                    format!("Failed to undo {description}: {e}"),
+                    StatusLevel::Error,
+                );
+            }
+        }
+    }
+
+    /// Make the last undone change again
+    pub async fn redo(&mut self) {
+        let Some(entry) = self.journal.redo.pop() else {
+            self.set_status("Nothing to redo".to_string(), StatusLevel::Info);
+            return;
+        };
+        self.is_loading = true;
+        let result = entry.operation.replay(&entry.client).await;
+        self.is_loading = false;
+        let description = entry.operation.describe();
+        match result {
+            Ok(()) => {
+                let deleted = match &entry.operation {
+                    Operation::DeleteImposter { port, .. } => vec![*port],
+                    Operation::Batch { operations, .. } => operations
+                        .iter()
+                        .filter_map(|operation| match operation {
+                            Operation::DeleteImposter { port, .. } => Some(*port),
+                            _ => None,
+                        })
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                self.journal.push_undo(entry);
+                self.set_status(format!("Redid: {description}"), StatusLevel::Success);
+                self.refresh().await;
+                if let View::ImposterDetail { port } = self.view
+                    && deleted.contains(&port)
+                {
+                    self.view = View::ImposterList;
+                }
+            }
+            Err(e) => {
+                self.journal.redo.push(entry);
+                self.set_status(
+                    format!("Failed to redo {description}: {e}"),
+                    StatusLevel::Error,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::make_test_app;
+
+    fn delete_stub(index: usize) -> Operation {
+        Operation::DeleteStub {
+            port: 4545,
+            index,
+            stub: serde_json::from_value(serde_json::json!({"responses": []})).unwrap(),
+        }
+    }
+
+    #[test]
+    fn a_new_change_drops_the_redo_stack_and_the_oldest_beyond_the_depth() {
+        let client = ApiClient::new("http://localhost:2525");
+        let mut journal = Journal::default();
+        for index in 0..=JOURNAL_DEPTH {
+            journal.record(&client, delete_stub(index));
+        }
+        assert_eq!(journal.undo.len(), JOURNAL_DEPTH);
+        assert!(matches!(
+            journal.undo.front().map(|entry| &entry.operation),
+            Some(Operation::DeleteStub { index: 1, .. })
+        ));
+
+        let undone = journal.undo.pop_back().unwrap();
+        journal.redo.push(undone);
+        assert!(journal.next_redo().is_some());
+        journal.record(&client, delete_stub(0));
+        assert!(
+            journal.next_redo().is_none(),
+            "a new change can't be redone over"
+        );
+        assert_eq!(
+            journal.next_undo().unwrap().describe(),
+            "delete stub #1 from :4545"
+        );
+    }
+
+    #[tokio::test]
+    async fn u_and_shift_u_report_an_empty_journal() {
+        let mut app = make_test_app();
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::NONE))
+            .await;
+        assert_eq!(app.status_message.as_ref().unwrap().0, "Nothing to undo");
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('U'), KeyModifiers::SHIFT))
+            .await;
+        assert_eq!(app.status_message.as_ref().unwrap().0, "Nothing to redo");
+    }
+
+    #[tokio::test]
+    async fn a_failed_undo_keeps_the_change_in_the_journal() {
+        let mut app = make_test_app();
+        // Nothing listens here, so the inverse call fails
+        let client = ApiClient::new("http://127.0.0.1:9");
+        app.journal.record(&client, delete_stub(2));
+
+        app.undo().await;
+        let (message, level, _) = app.status_message.as_ref().unwrap();
+        assert!(
+            message.starts_with("Failed to undo delete stub #3 from :4545"),
+            "{message}"
+        );
+        assert_eq!(*level, StatusLevel::Error);
+        assert!(app.journal.next_undo().is_some(), "`u` can try again");
+        assert!(app.journal.next_redo().is_none());
+    }
+
+    #[tokio::test]
+    async fn undo_moves_the_stub_back_and_redo_moves_it_again() {
+        let mut app = make_test_app();
+        // The refresh after each step has nowhere to go; only the journaled client is served
+        app.client = ApiClient::new("http://127.0.0.1:9");
+        let detail = r#"{"port": 4545, "protocol": "http", "stubs": []}"#.to_string();
+        let (url, server) = crate::app::tests::answer_json(vec![detail.clone(), detail]);
+        let client = ApiClient::new(&url);
+        app.journal.record(
+            &client,
+            Operation::MoveStub {
+                port: 4545,
+                from: 1,
+                to: 3,
+            },
+        );
+
+        app.undo().await;
+        assert!(
+            app.journal.next_redo().is_some(),
+            "{:?}",
+            app.status_message
+        );
+        app.redo().await;
+        assert!(
+            app.journal.next_undo().is_some(),
+            "{:?}",
+            app.status_message
+        );
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].0, "PUT /imposters/4545/stubs/3 HTTP/1.1");
+        assert_eq!(requests[0].1, r#"{"newIndex":1}"#);
+        assert_eq!(requests[1].0, "PUT /imposters/4545/stubs/1 HTTP/1.1");
+        assert_eq!(requests[1].1, r#"{"newIndex":3}"#);
+    }
+}
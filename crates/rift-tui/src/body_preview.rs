@@ -0,0 +1,425 @@
+//! Readable previews of message bodies: JSON and XML pretty-printed with long values collapsed,
+//! and base64 or gzip content decoded where it can be recognized
+//!
+//! Previews are for reading only. Anything saved or sent keeps the body exactly as it was.
+
+use crate::json_syntax::{TokenKind, tokenize};
+use base64::Engine;
+use serde_json::Value;
+use std::io::Read;
+
+/// Longest string value shown in full; longer ones are cut, saying how much was left out
+pub const MAX_VALUE_CHARS: usize = 200;
+/// Array items shown before the rest are collapsed into a count
+pub const MAX_ARRAY_ITEMS: usize = 50;
+/// Bytes of a binary body shown as hex
+const HEX_PREVIEW_BYTES: usize = 64;
+
+/// What a body turned out to be
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyFormat {
+    Json,
+    Xml,
+    Text,
+    Binary,
+    Empty,
+}
+
+impl BodyFormat {
+    pub fn label(self) -> &'static str {
+        match self {
+            BodyFormat::Json => "JSON",
+            BodyFormat::Xml => "XML",
+            BodyFormat::Text => "text",
+            BodyFormat::Binary => "binary",
+            BodyFormat::Empty => "empty",
+        }
+    }
+}
+
+/// A body laid out for reading
+#[derive(Debug, Clone, PartialEq)]
+pub struct BodyPreview {
+    pub format: BodyFormat,
+    /// Encodings taken off to show it, outermost first: `base64`, `gzip`
+    pub decoded: Vec<&'static str>,
+    pub lines: Vec<String>,
+}
+
+impl BodyPreview {
+    /// e.g. `JSON, decoded from base64 and gzip`
+    pub fn describe(&self) -> String {
+        if self.decoded.is_empty() {
+            self.format.label().to_string()
+        } else {
+            format!(
+                "{}, decoded from {}",
+                self.format.label(),
+                self.decoded.join(" and ")
+            )
+        }
+    }
+}
+
+/// Preview a stub response's `body`, which is either a string or JSON. `binary` is set for
+/// `_mode: binary` responses, whose body is base64.
+pub fn preview_value(body: &Value, binary: bool) -> BodyPreview {
+    match body {
+        Value::String(text) => preview_text(text, binary),
+        Value::Null => empty(),
+        json => json_preview(json, Vec::new()),
+    }
+}
+
+/// Previews of the bodies of a stub's `is` responses, with the index of the response each is in
+pub fn response_bodies(responses: &[Value]) -> Vec<(usize, BodyPreview)> {
+    responses
+        .iter()
+        .enumerate()
+        .filter_map(|(index, response)| {
+            let is = response.get("is")?;
+            let body = is.get("body").filter(|body| !body.is_null())?;
+            let binary = is.get("_mode").and_then(Value::as_str) == Some("binary");
+            Some((index, preview_value(body, binary)))
+        })
+        .collect()
+}
+
+/// Preview a body sent as text
+pub fn preview_text(body: &str, binary: bool) -> BodyPreview {
+    if body.trim().is_empty() {
+        return empty();
+    }
+    let mut decoded = Vec::new();
+    let mut bytes = body.as_bytes().to_vec();
+    if let Some(raw) = decode_base64(body, binary) {
+        bytes = raw;
+        decoded.push("base64");
+    }
+    if let Some(raw) = gunzip(&bytes) {
+        bytes = raw;
+        decoded.push("gzip");
+    }
+    match String::from_utf8(bytes) {
+        Ok(text) => format_text(&text, decoded),
+        Err(e) => binary_preview(e.as_bytes(), decoded),
+    }
+}
+
+/// Shorten string tokens on a line of JSON that run past [`MAX_VALUE_CHARS`], keeping the line
+/// itself so scroll positions still line up with the text
+pub fn collapse_line(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut collapsed = String::new();
+    let mut copied = 0;
+    for token in tokenize(line) {
+        // Both quotes are part of the token
+        let inner = token.end - token.start;
+        if token.kind == TokenKind::String && inner > MAX_VALUE_CHARS + 2 {
+            let keep = token.start + 1 + MAX_VALUE_CHARS;
+            collapsed.extend(&chars[copied..keep]);
+            collapsed.push_str(&format!("… (+{} chars)\"", token.end - 1 - keep));
+            copied = token.end;
+        }
+    }
+    collapsed.extend(&chars[copied..]);
+    collapsed
+}
+
+/// Cut long strings and arrays of a JSON value down to a readable size
+pub fn collapse(value: &Value) -> Value {
+    match value {
+        Value::String(text) => Value::String(collapse_string(text)),
+        Value::Array(items) => {
+            let mut shown: Vec<Value> = items.iter().take(MAX_ARRAY_ITEMS).map(collapse).collect();
+            if items.len() > MAX_ARRAY_ITEMS {
+                shown.push(Value::String(format!(
+                    "… {} more items",
+                    items.len() - MAX_ARRAY_ITEMS
+                )));
+            }
+            Value::Array(shown)
+        }
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), collapse(value)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn collapse_string(text: &str) -> String {
+    let total = text.chars().count();
+    if total <= MAX_VALUE_CHARS {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(MAX_VALUE_CHARS).collect();
+    format!("{kept}… (+{} chars)", total - MAX_VALUE_CHARS)
+}
+
+fn empty() -> BodyPreview {
+    BodyPreview {
+        format: BodyFormat::Empty,
+        decoded: Vec::new(),
+        lines: vec!["(empty)".to_string()],
+    }
+}
+
+fn json_preview(json: &Value, decoded: Vec<&'static str>) -> BodyPreview {
+    let pretty = serde_json::to_string_pretty(&collapse(json)).unwrap_or_default();
+    BodyPreview {
+        format: BodyFormat::Json,
+        decoded,
+        lines: pretty.lines().map(str::to_string).collect(),
+    }
+}
+
+fn format_text(text: &str, decoded: Vec<&'static str>) -> BodyPreview {
+    let trimmed = text.trim_start();
+    if (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && let Ok(json) = serde_json::from_str::<Value>(text)
+    {
+        return json_preview(&json, decoded);
+    }
+    if trimmed.starts_with('<')
+        && let Some(lines) = pretty_xml(text)
+    {
+        return BodyPreview {
+            format: BodyFormat::Xml,
+            decoded,
+            lines,
+        };
+    }
+    BodyPreview {
+        format: BodyFormat::Text,
+        decoded,
+        lines: text.lines().map(collapse_string).collect(),
+    }
+}
+
+fn binary_preview(bytes: &[u8], decoded: Vec<&'static str>) -> BodyPreview {
+    let mut lines = vec![format!("({} bytes)", bytes.len())];
+    lines.extend(bytes.chunks(16).take(HEX_PREVIEW_BYTES / 16).map(|chunk| {
+        chunk
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }));
+    if bytes.len() > HEX_PREVIEW_BYTES {
+        lines.push("…".to_string());
+    }
+    BodyPreview {
+        format: BodyFormat::Binary,
+        decoded,
+        lines,
+    }
+}
+
+/// The bytes `body` encodes as base64. Bodies not marked binary are only taken for base64 when
+/// they decode to gzip or to readable text, so ordinary words are left alone.
+fn decode_base64(body: &str, binary: bool) -> Option<Vec<u8>> {
+    let body = body.trim();
+    let plausible = body.len() >= 16
+        && body.len().is_multiple_of(4)
+        && body
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'='));
+    if !binary && !plausible {
+        return None;
+    }
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(body)
+        .ok()?;
+    let readable = |bytes: &[u8]| {
+        std::str::from_utf8(bytes).is_ok_and(|text| {
+            text.chars()
+                .all(|c| !c.is_control() || c.is_ascii_whitespace())
+        })
+    };
+    (binary || is_gzip(&bytes) || readable(&bytes)).then_some(bytes)
+}
+
+fn is_gzip(bytes: &[u8]) -> bool {
+    bytes.starts_with(&[0x1f, 0x8b])
+}
+
+fn gunzip(bytes: &[u8]) -> Option<Vec<u8>> {
+    if !is_gzip(bytes) {
+        return None;
+    }
+    let mut out = Vec::new();
+    flate2::read::GzDecoder::new(bytes)
+        .read_to_end(&mut out)
+        .ok()?;
+    Some(out)
+}
+
+/// One tag or piece of text per line, indented by depth, with runs of whitespace in text shown as
+/// one space; an element holding only text stays on one line. `None` when a tag is never closed
+/// with `>`.
+fn pretty_xml(xml: &str) -> Option<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut rest = xml.trim();
+    while !rest.is_empty() {
+        if rest.starts_with('<') {
+            let end = rest.find('>')?;
+            tokens.push(&rest[..=end]);
+            rest = &rest[end + 1..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            if !rest[..end].trim().is_empty() {
+                tokens.push(&rest[..end]);
+            }
+            rest = &rest[end..];
+        }
+    }
+
+    let is_open = |token: &str| {
+        token.starts_with('<')
+            && !token.starts_with("</")
+            && !token.starts_with("<?")
+            && !token.starts_with("<!")
+            && !token.ends_with("/>")
+    };
+    let mut lines = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        let indent = "  ".repeat(depth);
+        if token.starts_with("</") {
+            depth = depth.saturating_sub(1);
+            lines.push(format!("{}{token}", "  ".repeat(depth)));
+        } else if is_open(token) {
+            match (tokens.get(i + 1), tokens.get(i + 2)) {
+                (Some(text), Some(close)) if !text.starts_with('<') && close.starts_with("</") => {
+                    lines.push(format!("{indent}{token}{}{close}", xml_text(text)));
+                    i += 2;
+                }
+                _ => {
+                    lines.push(format!("{indent}{token}"));
+                    depth += 1;
+                }
+            }
+        } else if token.starts_with('<') {
+            lines.push(format!("{indent}{token}"));
+        } else {
+            lines.push(format!("{indent}{}", xml_text(token)));
+        }
+        i += 1;
+    }
+    Some(lines)
+}
+
+fn xml_text(text: &str) -> String {
+    collapse_string(&text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::Write;
+
+    fn gzip(text: &str) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(text.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn base64(bytes: &[u8]) -> String {
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    }
+
+    #[test]
+    fn json_in_a_string_is_pretty_printed() {
+        let preview = preview_text(r#"{"id":1,"tags":["a"]}"#, false);
+        assert_eq!(preview.describe(), "JSON");
+        assert_eq!(
+            preview.lines,
+            [
+                "{",
+                "  \"id\": 1,",
+                "  \"tags\": [",
+                "    \"a\"",
+                "  ]",
+                "}"
+            ]
+        );
+        assert_eq!(
+            preview_value(&json!({ "id": 1 }), false).lines,
+            ["{", "  \"id\": 1", "}"]
+        );
+    }
+
+    #[test]
+    fn long_values_are_collapsed() {
+        let long = "x".repeat(MAX_VALUE_CHARS + 5);
+        let items: Vec<u32> = (0..MAX_ARRAY_ITEMS as u32 + 3).collect();
+        let collapsed = collapse(&json!({ "text": long, "items": items }));
+        assert_eq!(
+            collapsed["text"],
+            format!("{}… (+5 chars)", "x".repeat(MAX_VALUE_CHARS))
+        );
+        assert_eq!(collapsed["items"][MAX_ARRAY_ITEMS], "… 3 more items");
+
+        let line = format!("  \"body\": \"{long}\",");
+        assert_eq!(
+            collapse_line(&line),
+            format!(
+                "  \"body\": \"{}… (+5 chars)\",",
+                "x".repeat(MAX_VALUE_CHARS)
+            )
+        );
+        assert_eq!(collapse_line("  \"id\": 1,"), "  \"id\": 1,");
+    }
+
+    #[test]
+    fn base64_and_gzip_are_decoded() {
+        let preview = preview_text(&base64(&gzip(r#"{"ok":true}"#)), false);
+        assert_eq!(preview.describe(), "JSON, decoded from base64 and gzip");
+        assert_eq!(preview.lines, ["{", "  \"ok\": true", "}"]);
+
+        let preview = preview_text(&base64(b"plain words in here"), false);
+        assert_eq!(preview.describe(), "text, decoded from base64");
+        assert_eq!(preview.lines, ["plain words in here"]);
+    }
+
+    #[test]
+    fn text_that_only_looks_like_base64_is_left_alone() {
+        let preview = preview_text("0123456789abcdef", false);
+        assert_eq!(preview.describe(), "text");
+        assert_eq!(preview.lines, ["0123456789abcdef"]);
+    }
+
+    #[test]
+    fn binary_bodies_show_their_size_and_leading_bytes() {
+        let preview = preview_text(&base64(&[0, 1, 2, 255]), true);
+        assert_eq!(preview.describe(), "binary, decoded from base64");
+        assert_eq!(preview.lines, ["(4 bytes)", "00 01 02 ff"]);
+    }
+
+    #[test]
+    fn xml_is_indented() {
+        let preview = preview_text(
+            r#"<?xml version="1.0"?><order id="7"><item>tea</item><item/><note>a
+            b</note></order>"#,
+            false,
+        );
+        assert_eq!(preview.format, BodyFormat::Xml);
+        assert_eq!(
+            preview.lines,
+            [
+                r#"<?xml version="1.0"?>"#,
+                r#"<order id="7">"#,
+                "  <item>tea</item>",
+                "  <item/>",
+                "  <note>a b</note>",
+                "</order>",
+            ]
+        );
+        assert_eq!(preview_text("<not closed", false).format, BodyFormat::Text);
+    }
+}
@@ -0,0 +1,126 @@
+//! Which actions ask before they run, from `~/.config/rift-tui/confirm.toml`
+//!
+//! Each action has a safety level: `always` asks every time, `bulk` only when it acts on marked
+//! imposters or stubs, and `never` runs it straight away.
+//!
+//! ```toml
+//! delete_imposter = "bulk"
+//! delete_stub = "always"
+//! toggle = "never"
+//! ```
+//!
+//! `--yes` runs everything without asking, for a TUI driven by a script.
+
+use anyhow::Context;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// When an action asks for confirmation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Safety {
+    /// Every time
+    Always,
+    /// Only when it acts on marked imposters or stubs
+    Bulk,
+    /// Never
+    Never,
+}
+
+impl Safety {
+    /// Whether an action at this level asks first, acting on marked items when `bulk` is set
+    pub fn asks(self, bulk: bool) -> bool {
+        match self {
+            Safety::Always => true,
+            Safety::Bulk => bulk,
+            Safety::Never => false,
+        }
+    }
+}
+
+/// The safety level of each action that can ask for confirmation
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Confirmations {
+    pub delete_imposter: Safety,
+    pub delete_stub: Safety,
+    pub clear_requests: Safety,
+    pub clear_proxy_responses: Safety,
+    /// Enabling or disabling imposters
+    pub toggle: Safety,
+}
+
+impl Default for Confirmations {
+    fn default() -> Self {
+        Self {
+            delete_imposter: Safety::Always,
+            delete_stub: Safety::Always,
+            clear_requests: Safety::Always,
+            clear_proxy_responses: Safety::Always,
+            toggle: Safety::Never,
+        }
+    }
+}
+
+impl Confirmations {
+    /// Nothing asks: what `--yes` runs with
+    pub fn unattended() -> Self {
+        Self {
+            delete_imposter: Safety::Never,
+            delete_stub: Safety::Never,
+            clear_requests: Safety::Never,
+            clear_proxy_responses: Safety::Never,
+            toggle: Safety::Never,
+        }
+    }
+
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        Ok(toml::from_str(text)?)
+    }
+
+    /// Read a confirmations file; a missing one leaves the defaults
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).with_context(|| format!("{}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("{}", path.display())),
+        }
+    }
+}
+
+/// Where the confirmations are read from by default
+pub fn default_confirm_path() -> Option<PathBuf> {
+    crate::servers::config_dir().map(|dir| dir.join("confirm.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unset_actions_keep_their_default_level() {
+        let confirmations = Confirmations::parse(
+            r#"
+            delete_imposter = "bulk"
+            toggle = "always"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(confirmations.delete_imposter, Safety::Bulk);
+        assert_eq!(confirmations.toggle, Safety::Always);
+        assert_eq!(confirmations.delete_stub, Safety::Always);
+        assert!(!confirmations.delete_imposter.asks(false));
+        assert!(confirmations.delete_imposter.asks(true));
+    }
+
+    #[test]
+    fn unknown_actions_and_levels_are_errors() {
+        let unknown = Confirmations::parse("delete_everything = \"never\"").unwrap_err();
+        assert!(
+            unknown.to_string().contains("delete_everything"),
+            "{unknown}"
+        );
+        let level = Confirmations::parse("toggle = \"sometimes\"").unwrap_err();
+        assert!(level.to_string().contains("sometimes"), "{level}");
+    }
+}
@@ -0,0 +1,224 @@
+//! Line-by-line JSON tokens for highlighting, bracket matching and folding
+//!
+//! JSON strings cannot contain raw newlines, so every line can be tokenized on its own, which
+//! keeps this cheap enough to run on each frame. Text that is not valid JSON still tokenizes:
+//! whatever is not recognized becomes [`TokenKind::Other`].
+
+/// What a token is, for choosing its color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// A string followed by `:`
+    Key,
+    String,
+    Number,
+    Bool,
+    Null,
+    /// One of `{ } [ ] : ,`
+    Punct,
+    Other,
+}
+
+/// A token's kind and the character range it covers on its line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    /// Index of the first character
+    pub start: usize,
+    /// Index one past the last character
+    pub end: usize,
+}
+
+/// A `(row, column)` position in the text, counted in characters
+pub type TextPos = (usize, usize);
+
+fn is_punct(c: char) -> bool {
+    matches!(c, '{' | '}' | '[' | ']' | ':' | ',')
+}
+
+/// Split a line into tokens. Whitespace between tokens is left out.
+pub fn tokenize(line: &str) -> Vec<Token> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let start = i;
+        let kind = match chars[i] {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '"' => {
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    if chars[i] == '\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                i = (i + 1).min(chars.len());
+                match chars[i..].iter().find(|c| !c.is_whitespace()) {
+                    Some(':') => TokenKind::Key,
+                    _ => TokenKind::String,
+                }
+            }
+            c if is_punct(c) => {
+                i += 1;
+                TokenKind::Punct
+            }
+            _ => {
+                while i < chars.len()
+                    && !chars[i].is_whitespace()
+                    && !is_punct(chars[i])
+                    && chars[i] != '"'
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                match word.as_str() {
+                    "true" | "false" => TokenKind::Bool,
+                    "null" => TokenKind::Null,
+                    w if w.parse::<f64>().is_ok() => TokenKind::Number,
+                    _ => TokenKind::Other,
+                }
+            }
+        };
+        tokens.push(Token {
+            kind,
+            start,
+            end: i,
+        });
+    }
+    tokens
+}
+
+/// The brackets on a line outside strings, with their character index
+pub fn brackets(line: &str) -> Vec<(usize, char)> {
+    let chars: Vec<char> = line.chars().collect();
+    tokenize(line)
+        .into_iter()
+        .filter(|token| token.kind == TokenKind::Punct)
+        .map(|token| (token.start, chars[token.start]))
+        .filter(|(_, c)| matches!(c, '{' | '}' | '[' | ']'))
+        .collect()
+}
+
+fn is_opener(c: char) -> bool {
+    c == '{' || c == '['
+}
+
+/// Whether `open` and `close` are the two ends of the same kind of block
+pub fn brackets_pair(open: char, close: char) -> bool {
+    matches!((open, close), ('{', '}') | ('[', ']'))
+}
+
+/// The bracket matching the one at `(row, col)`, found by counting nesting in either direction.
+/// `None` when there is no bracket at that position or it is never closed (or opened).
+pub fn matching_bracket(lines: &[String], (row, col): TextPos) -> Option<TextPos> {
+    let line_brackets = brackets(lines.get(row)?);
+    let &(_, bracket) = line_brackets.iter().find(|(c, _)| *c == col)?;
+    let mut depth = 0usize;
+    if is_opener(bracket) {
+        for (r, line) in lines.iter().enumerate().skip(row) {
+            for (c, b) in brackets(line) {
+                if r == row && c < col {
+                    continue;
+                }
+                if is_opener(b) {
+                    depth += 1;
+                } else {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some((r, c));
+                    }
+                }
+            }
+        }
+    } else {
+        for r in (0..=row).rev() {
+            for (c, b) in brackets(&lines[r]).into_iter().rev() {
+                if r == row && c > col {
+                    continue;
+                }
+                if is_opener(b) {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some((r, c));
+                    }
+                } else {
+                    depth += 1;
+                }
+            }
+        }
+    }
+    None
+}
+
+/// The last line of the block that opens on `row`: the first bracket on the line that is closed
+/// on a later line. `None` when nothing opened on `row` spans lines, so there is nothing to fold.
+pub fn block_end(lines: &[String], row: usize) -> Option<usize> {
+    brackets(lines.get(row)?)
+        .into_iter()
+        .filter(|(_, b)| is_opener(*b))
+        .find_map(|(col, _)| {
+            matching_bracket(lines, (row, col)).filter(|(end_row, _)| *end_row > row)
+        })
+        .map(|(end_row, _)| end_row)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn tokens_tell_keys_from_values() {
+        let kinds: Vec<TokenKind> = tokenize(r#"  "a \"b\"": ["x", 1.5e3, true, null, bad],"#)
+            .iter()
+            .map(|t| t.kind)
+            .collect();
+        use TokenKind::*;
+        assert_eq!(
+            kinds,
+            [
+                Key, Punct, Punct, String, Punct, Number, Punct, Bool, Punct, Null, Punct, Other,
+                Punct, Punct
+            ]
+        );
+    }
+
+    #[test]
+    fn brackets_inside_strings_are_ignored() {
+        assert_eq!(
+            brackets(r#"{"a": "{[", "b": [1]}"#),
+            [(0, '{'), (17, '['), (19, ']'), (20, '}')]
+        );
+        // An unterminated string runs to the end of the line
+        assert_eq!(brackets(r#"["oops ]"#), [(0, '[')]);
+    }
+
+    #[test]
+    fn brackets_match_across_lines_in_both_directions() {
+        let text = lines("{\n  \"a\": [1, {\"b\": 2}],\n  \"c\": {\n  }\n}");
+        assert_eq!(matching_bracket(&text, (0, 0)), Some((4, 0)));
+        assert_eq!(matching_bracket(&text, (4, 0)), Some((0, 0)));
+        assert_eq!(matching_bracket(&text, (1, 7)), Some((1, 19)));
+        assert_eq!(matching_bracket(&text, (1, 18)), Some((1, 11)));
+        assert_eq!(matching_bracket(&text, (2, 7)), Some((3, 2)));
+        // Not a bracket
+        assert_eq!(matching_bracket(&text, (1, 2)), None);
+        assert_eq!(matching_bracket(&lines("{\n  ["), (0, 0)), None);
+    }
+
+    #[test]
+    fn blocks_fold_from_the_first_bracket_closed_later() {
+        let text = lines("{\n  \"a\": [1],\n  \"b\": [{\n    \"c\": 1\n  }],\n  \"d\": {}\n}");
+        assert_eq!(block_end(&text, 0), Some(6));
+        assert_eq!(block_end(&text, 1), None);
+        assert_eq!(block_end(&text, 2), Some(4));
+        assert_eq!(block_end(&text, 5), None);
+        assert_eq!(block_end(&text, 7), None);
+    }
+}
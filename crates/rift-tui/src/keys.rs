@@ -0,0 +1,673 @@
+//! Key bindings for the main views, customisable in `~/.config/rift-tui/keys.toml`
+//!
+//! Each section is a context and each entry an action, bound to one key or a list of them.
+//! A binding replaces the action's default keys, so list every key you want; `[]` unbinds it.
+//!
+//! ```toml
+//! [navigation]
+//! down = ["j", "ctrl-n", "down"]
+//! up = ["k", "ctrl-p", "up"]
+//!
+//! [imposter_list]
+//! delete = "D"
+//! ```
+//!
+//! Dialogs, the editor and the other overlays keep their own keys.
+
+use anyhow::{Context as _, bail};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Where the key bindings are read from by default
+pub fn default_keys_path() -> Option<PathBuf> {
+    crate::servers::config_dir().map(|dir| dir.join("keys.toml"))
+}
+
+/// A group of actions, named by its section in `keys.toml`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Context {
+    /// Keys that work in every main view
+    Global,
+    /// Moving through lists, in every main view
+    Navigation,
+    ImposterList,
+    ImposterDetail,
+    StubDetail,
+    RequestLog,
+    Metrics,
+    ServerLog,
+}
+
+impl Context {
+    const ALL: [Context; 8] = [
+        Context::Global,
+        Context::Navigation,
+        Context::ImposterList,
+        Context::ImposterDetail,
+        Context::StubDetail,
+        Context::RequestLog,
+        Context::Metrics,
+        Context::ServerLog,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Context::Global => "global",
+            Context::Navigation => "navigation",
+            Context::ImposterList => "imposter_list",
+            Context::ImposterDetail => "imposter_detail",
+            Context::StubDetail => "stub_detail",
+            Context::RequestLog => "request_log",
+            Context::Metrics => "metrics",
+            Context::ServerLog => "server_log",
+        }
+    }
+
+    /// Whether this context's keys are live in a view with `view` as its own context
+    fn applies_to(self, view: Option<Context>) -> bool {
+        matches!(self, Context::Global | Context::Navigation) || Some(self) == view
+    }
+}
+
+/// Something a key does
+#[derive(Debug)]
+pub struct Action {
+    pub context: Context,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// The keys it has unless `keys.toml` says otherwise; the first is what the views act on
+    defaults: &'static [&'static str],
+}
+
+const fn action(
+    context: Context,
+    name: &'static str,
+    description: &'static str,
+    defaults: &'static [&'static str],
+) -> Action {
+    Action {
+        context,
+        name,
+        description,
+        defaults,
+    }
+}
+
+use Context::*;
+
+/// Every action that can be rebound
+pub static ACTIONS: &[Action] = &[
+    action(Global, "help", "Toggle help", &["?"]),
+    action(Global, "errors", "Show recent errors and warnings", &["L"]),
+    action(Global, "search", "Search / filter items", &["/"]),
+    action(
+        Global,
+        "quit",
+        "Quit (go back outside the main view)",
+        &["q"],
+    ),
+    action(Global, "back", "Go back", &["esc"]),
+    action(Global, "refresh", "Refresh data", &["r"]),
+    action(Global, "theme", "Cycle theme", &["T"]),
+    action(Global, "undo", "Undo the last delete or move", &["u"]),
+    action(Global, "redo", "Redo the last undone change", &["U"]),
+    action(Global, "servers", "Switch server", &["s"]),
+    action(Global, "send", "Send a test request", &["S"]),
+    action(
+        Global,
+        "screenshot",
+        "Save a screenshot of the view",
+        &["P"],
+    ),
+    action(Global, "palette", "Open the command palette", &[":"]),
+    action(Navigation, "down", "Move down", &["j", "down"]),
+    action(Navigation, "up", "Move up", &["k", "up"]),
+    action(ImposterList, "open", "Open imposter", &["enter"]),
+    action(ImposterList, "new", "Create new imposter", &["n"]),
+    action(ImposterList, "new_proxy", "Create proxy imposter", &["p"]),
+    action(ImposterList, "mark", "Mark / unmark imposter", &["space"]),
+    action(
+        ImposterList,
+        "mark_range",
+        "Mark a range of imposters",
+        &["V"],
+    ),
+    action(ImposterList, "delete", "Delete selected imposter", &["d"]),
+    action(ImposterList, "toggle", "Toggle enable/disable", &["t"]),
+    action(ImposterList, "metrics", "View metrics dashboard", &["m"]),
+    action(ImposterList, "sort", "Sort by the next column", &["o"]),
+    action(
+        ImposterList,
+        "reverse_sort",
+        "Reverse the sort order",
+        &["O"],
+    ),
+    action(
+        ImposterList,
+        "filter_protocol",
+        "Filter by protocol",
+        &["f"],
+    ),
+    action(
+        ImposterList,
+        "filter_enabled",
+        "Filter by enabled / disabled",
+        &["F"],
+    ),
+    action(ImposterList, "request_log", "Open live request log", &["l"]),
+    action(ImposterList, "server_log", "Open the server log", &["g"]),
+    action(ImposterList, "config", "Show server config", &["C"]),
+    action(ImposterList, "import", "Import imposter from file", &["i"]),
+    action(
+        ImposterList,
+        "import_folder",
+        "Import imposters from folder",
+        &["I"],
+    ),
+    action(
+        ImposterList,
+        "export",
+        "Export all imposters to file",
+        &["e"],
+    ),
+    action(
+        ImposterList,
+        "export_folder",
+        "Export imposters to folder",
+        &["E"],
+    ),
+    action(
+        ImposterList,
+        "scenario",
+        "Run a scenario file against the marked or selected imposters",
+        &["R"],
+    ),
+    action(
+        ImposterList,
+        "copy_as",
+        "Copy a command creating the imposter: curl, httpie, mb or rift",
+        &["y"],
+    ),
+    action(ImposterDetail, "open", "Open stub or request", &["enter"]),
+    action(
+        ImposterDetail,
+        "focus",
+        "Switch focus between panes",
+        &["tab"],
+    ),
+    action(ImposterDetail, "mark", "Mark / unmark stub", &["space"]),
+    action(
+        ImposterDetail,
+        "mark_range",
+        "Mark a range of stubs",
+        &["V"],
+    ),
+    action(ImposterDetail, "add_stub", "Add new stub", &["a"]),
+    action(ImposterDetail, "edit_stub", "Edit selected stub", &["e"]),
+    action(
+        ImposterDetail,
+        "delete_stub",
+        "Delete selected stub",
+        &["d"],
+    ),
+    action(
+        ImposterDetail,
+        "duplicate_stub",
+        "Duplicate selected stub",
+        &["D"],
+    ),
+    action(ImposterDetail, "move_stub_up", "Move stub up", &["K", "["]),
+    action(
+        ImposterDetail,
+        "move_stub_down",
+        "Move stub down",
+        &["J", "]"],
+    ),
+    action(
+        ImposterDetail,
+        "copy_curl",
+        "Copy a command for the stub: curl, httpie, mb or rift",
+        &["y"],
+    ),
+    action(
+        ImposterDetail,
+        "clear_requests",
+        "Clear recorded requests",
+        &["c"],
+    ),
+    action(
+        ImposterDetail,
+        "clear_proxy",
+        "Clear proxy recordings",
+        &["C"],
+    ),
+    action(ImposterDetail, "export_stubs", "Export stubs", &["x"]),
+    action(ImposterDetail, "export_full", "Export full config", &["X"]),
+    action(
+        ImposterDetail,
+        "apply_recorded",
+        "Apply recorded stubs",
+        &["A"],
+    ),
+    action(ImposterDetail, "toggle", "Toggle enable/disable", &["t"]),
+    action(
+        ImposterDetail,
+        "reset_hits",
+        "Count stub hits from zero again",
+        &["z"],
+    ),
+    action(
+        ImposterDetail,
+        "request_log",
+        "Open live request log",
+        &["l"],
+    ),
+    action(StubDetail, "edit_stub", "Edit stub", &["e"]),
+    action(StubDetail, "delete_stub", "Delete stub", &["d"]),
+    action(StubDetail, "duplicate_stub", "Duplicate stub", &["D"]),
+    action(
+        StubDetail,
+        "copy_curl",
+        "Copy a command for the stub: curl, httpie, mb or rift",
+        &["y"],
+    ),
+    action(
+        RequestLog,
+        "inspect",
+        "Inspect headers and bodies",
+        &["enter"],
+    ),
+    action(
+        RequestLog,
+        "pause",
+        "Pause / resume the tail",
+        &["p", "space"],
+    ),
+    action(
+        RequestLog,
+        "follow",
+        "Follow newest requests",
+        &["G", "end"],
+    ),
+    action(RequestLog, "clear", "Clear the pane", &["c"]),
+    action(Metrics, "window", "Cycle window", &["w"]),
+    action(
+        Metrics,
+        "export",
+        "Export the figures as CSV or JSON",
+        &["x"],
+    ),
+    action(ServerLog, "level", "Cycle the minimum level", &["f"]),
+    action(
+        ServerLog,
+        "pause",
+        "Pause / resume the tail",
+        &["p", "space"],
+    ),
+    action(ServerLog, "follow", "Follow newest lines", &["G", "end"]),
+    action(ServerLog, "clear", "Clear the pane", &["c"]),
+];
+
+/// One key, with the modifiers that tell it apart (Shift is in the character itself)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Parse `d`, `D`, `ctrl-d`, `alt-x`, `enter`, `pagedown`, `f1` and the like
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut shift = false;
+        let mut rest = text;
+        while rest.chars().count() > 1 {
+            let lower = rest.to_ascii_lowercase();
+            let prefix = ["ctrl", "alt", "shift"].into_iter().find(|m| {
+                lower.starts_with(&format!("{m}-")) || lower.starts_with(&format!("{m}+"))
+            });
+            match prefix {
+                Some("ctrl") => modifiers |= KeyModifiers::CONTROL,
+                Some("alt") => modifiers |= KeyModifiers::ALT,
+                Some(_) => shift = true,
+                None => break,
+            }
+            rest = &rest[prefix.map_or(0, |m| m.len() + 1)..];
+        }
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) if shift => KeyCode::Char(c.to_ascii_uppercase()),
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "tab" if shift => KeyCode::BackTab,
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                name => match name.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => bail!("unknown key '{text}'"),
+                },
+            },
+        };
+        Ok(Self { code, modifiers })
+    }
+
+    fn from_event(key: &KeyEvent) -> Self {
+        Self {
+            code: key.code,
+            modifiers: key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT),
+        }
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            f.write_str("Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            f.write_str("Alt+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::Enter => f.write_str("Enter"),
+            KeyCode::Esc => f.write_str("Esc"),
+            KeyCode::Tab => f.write_str("Tab"),
+            KeyCode::BackTab => f.write_str("Shift+Tab"),
+            KeyCode::Backspace => f.write_str("Backspace"),
+            KeyCode::Delete => f.write_str("Del"),
+            KeyCode::Insert => f.write_str("Ins"),
+            KeyCode::Home => f.write_str("Home"),
+            KeyCode::End => f.write_str("End"),
+            KeyCode::PageUp => f.write_str("PgUp"),
+            KeyCode::PageDown => f.write_str("PgDn"),
+            KeyCode::Up => f.write_str("↑"),
+            KeyCode::Down => f.write_str("↓"),
+            KeyCode::Left => f.write_str("←"),
+            KeyCode::Right => f.write_str("→"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            code => write!(f, "{code:?}"),
+        }
+    }
+}
+
+fn defaults(action: &Action) -> Vec<KeyBinding> {
+    action
+        .defaults
+        .iter()
+        .map(|key| KeyBinding::parse(key).expect("default keys parse"))
+        .collect()
+}
+
+/// The effective key bindings: the defaults with `keys.toml` on top
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    /// Keys for each of [`ACTIONS`], in the same order
+    bindings: Vec<Vec<KeyBinding>>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self {
+            bindings: ACTIONS.iter().map(defaults).collect(),
+        }
+    }
+}
+
+impl KeyMap {
+    /// Parse a keys file, rejecting unknown actions and keys bound to two actions at once
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        let table: toml::Table = toml::from_str(text)?;
+        let mut map = Self::default();
+        for (section, entries) in table {
+            let Some(context) = Context::ALL.into_iter().find(|c| c.name() == section) else {
+                let known: Vec<&str> = Context::ALL.iter().map(|c| c.name()).collect();
+                bail!("unknown section [{section}] (known: {})", known.join(", "));
+            };
+            let Some(entries) = entries.as_table() else {
+                bail!("[{section}] must be a table of actions");
+            };
+            for (name, keys) in entries {
+                let Some(index) = ACTIONS
+                    .iter()
+                    .position(|a| a.context == context && a.name == name)
+                else {
+                    bail!("unknown action {section}.{name}");
+                };
+                let keys = match keys {
+                    toml::Value::String(key) => vec![key.as_str()],
+                    toml::Value::Array(keys) => keys
+                        .iter()
+                        .map(|key| key.as_str())
+                        .collect::<Option<_>>()
+                        .with_context(|| format!("{section}.{name}: keys must be strings"))?,
+                    _ => bail!("{section}.{name}: expected a key or a list of keys"),
+                };
+                map.bindings[index] = keys
+                    .into_iter()
+                    .map(KeyBinding::parse)
+                    .collect::<anyhow::Result<_>>()
+                    .with_context(|| format!("{section}.{name}"))?;
+            }
+        }
+        let conflicts = map.conflicts();
+        if !conflicts.is_empty() {
+            bail!("{}", conflicts.into_iter().collect::<Vec<_>>().join("; "));
+        }
+        Ok(map)
+    }
+
+    /// Read a keys file; a missing one keeps the defaults
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).with_context(|| format!("{}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("{}", path.display())),
+        }
+    }
+
+    /// Keys bound to two actions that are live in the same view
+    fn conflicts(&self) -> BTreeSet<String> {
+        let mut conflicts = BTreeSet::new();
+        for view in Context::ALL {
+            let live: Vec<usize> = (0..ACTIONS.len())
+                .filter(|&i| ACTIONS[i].context.applies_to(Some(view)))
+                .collect();
+            for (n, &a) in live.iter().enumerate() {
+                for &b in &live[n + 1..] {
+                    for key in self.bindings[a]
+                        .iter()
+                        .filter(|k| self.bindings[b].contains(k))
+                    {
+                        conflicts.insert(format!(
+                            "{key} is bound to both {} and {}",
+                            qualified(&ACTIONS[a]),
+                            qualified(&ACTIONS[b])
+                        ));
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// The key the views act on for `key`, pressed in a view with `view` as its own context.
+    /// A key bound to an action becomes that action's default key; a default key that was
+    /// rebound elsewhere, with or without modifiers, does nothing; any other key is left as is.
+    pub fn translate(&self, view: Option<Context>, key: KeyEvent) -> Option<KeyEvent> {
+        let pressed = KeyBinding::from_event(&key);
+        let live = || (0..ACTIONS.len()).filter(|&i| ACTIONS[i].context.applies_to(view));
+        if let Some(index) = live().find(|&i| self.bindings[i].contains(&pressed)) {
+            let canonical = defaults(&ACTIONS[index])[0];
+            return Some(KeyEvent::new(canonical.code, canonical.modifiers));
+        }
+        let rebound = live().any(|i| defaults(&ACTIONS[i]).iter().any(|d| d.code == key.code));
+        (!rebound).then_some(key)
+    }
+
+    /// How to show a hint's default `key` in a view with `view` as its own context: as the
+    /// keys now bound to that action, `None` when it has none, and unchanged if not rebindable
+    pub fn hint(&self, view: Option<Context>, key: &str) -> Option<String> {
+        let index = (0..ACTIONS.len()).find(|&i| {
+            ACTIONS[i].context.applies_to(view) && defaults(&ACTIONS[i])[0].to_string() == key
+        });
+        match index {
+            Some(i) if self.bindings[i].is_empty() => None,
+            Some(i) => Some(join(&self.bindings[i])),
+            None => Some(key.to_string()),
+        }
+    }
+
+    /// The actions live in a view with `view` as its own context, with their keys and the key
+    /// the views act on for them, for the command palette
+    pub fn live_actions(&self, view: Option<Context>) -> Vec<(&'static Action, String, KeyEvent)> {
+        ACTIONS
+            .iter()
+            .zip(&self.bindings)
+            .filter(|(action, _)| action.context.applies_to(view))
+            .map(|(action, keys)| {
+                let canonical = defaults(action)[0];
+                let key = KeyEvent::new(canonical.code, canonical.modifiers);
+                (action, join(keys), key)
+            })
+            .collect()
+    }
+
+    /// Every action with its keys, and whether they differ from the defaults
+    pub fn effective(&self) -> impl Iterator<Item = (&'static Action, String, bool)> + '_ {
+        ACTIONS.iter().zip(&self.bindings).map(|(action, keys)| {
+            let customised = *keys != defaults(action);
+            (action, join(keys), customised)
+        })
+    }
+}
+
+fn qualified(action: &Action) -> String {
+    format!("{}.{}", action.context.name(), action.name)
+}
+
+fn join(keys: &[KeyBinding]) -> String {
+    keys.iter()
+        .map(|key| key.to_string())
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn the_defaults_do_not_conflict() {
+        assert!(KeyMap::default().conflicts().is_empty());
+        let map = KeyMap::default();
+        let d = press(KeyCode::Char('d'), KeyModifiers::NONE);
+        assert_eq!(map.translate(Some(Context::ImposterList), d), Some(d));
+        let digit = press(KeyCode::Char('7'), KeyModifiers::NONE);
+        assert_eq!(map.translate(None, digit), Some(digit));
+        // `[` moves a stub up like `K` does
+        assert_eq!(
+            map.translate(
+                Some(Context::ImposterDetail),
+                press(KeyCode::Char('['), KeyModifiers::NONE)
+            ),
+            Some(press(KeyCode::Char('K'), KeyModifiers::NONE))
+        );
+    }
+
+    #[test]
+    fn rebound_keys_act_as_the_default_and_the_old_key_does_nothing() {
+        let map = KeyMap::parse(
+            "[imposter_list]\ndelete = \"D\"\n[navigation]\ndown = [\"ctrl-n\", \"down\"]",
+        )
+        .unwrap();
+        let view = Some(Context::ImposterList);
+        assert_eq!(
+            map.translate(view, press(KeyCode::Char('D'), KeyModifiers::SHIFT)),
+            Some(press(KeyCode::Char('d'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            map.translate(view, press(KeyCode::Char('d'), KeyModifiers::NONE)),
+            None
+        );
+        assert_eq!(
+            map.translate(view, press(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+            None
+        );
+        assert_eq!(
+            map.translate(None, press(KeyCode::Char('n'), KeyModifiers::CONTROL)),
+            Some(press(KeyCode::Char('j'), KeyModifiers::NONE))
+        );
+        // `D` still duplicates a stub in the imposter detail view
+        let detail = Some(Context::ImposterDetail);
+        let dup = press(KeyCode::Char('D'), KeyModifiers::NONE);
+        assert_eq!(map.translate(detail, dup), Some(dup));
+
+        assert_eq!(map.hint(view, "d").as_deref(), Some("D"));
+        assert_eq!(map.hint(view, "j/k").as_deref(), Some("j/k"));
+        let (_, keys, customised) = map
+            .effective()
+            .find(|(a, _, _)| a.context == Context::Navigation && a.name == "down")
+            .unwrap();
+        assert_eq!((keys.as_str(), customised), ("Ctrl+n / ↓", true));
+    }
+
+    #[test]
+    fn conflicts_and_unknown_actions_are_rejected() {
+        let err = KeyMap::parse("[imposter_list]\ndelete = \"t\"")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "t is bound to both imposter_list.delete and imposter_list.toggle"
+        );
+        // A view key can't take a key that works everywhere
+        let err = KeyMap::parse("[metrics]\nwindow = \"r\"")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(err, "r is bound to both global.refresh and metrics.window");
+        // But the same key can do different things in different views
+        assert!(KeyMap::parse("[metrics]\nwindow = \"d\"").is_ok());
+
+        assert_eq!(
+            KeyMap::parse("[imposter_list]\nremove = \"x\"")
+                .unwrap_err()
+                .to_string(),
+            "unknown action imposter_list.remove"
+        );
+        assert!(KeyMap::parse("[editor]\nsave = \"ctrl-s\"").is_err());
+        assert!(KeyMap::parse("[global]\nhelp = \"hyper-h\"").is_err());
+    }
+
+    #[test]
+    fn keys_parse_with_modifiers_and_names() {
+        let parsed = |text| KeyBinding::parse(text).unwrap().to_string();
+        assert_eq!(parsed("ctrl-d"), "Ctrl+d");
+        assert_eq!(parsed("Alt+x"), "Alt+x");
+        assert_eq!(parsed("shift-x"), "X");
+        assert_eq!(parsed("shift-tab"), "Shift+Tab");
+        assert_eq!(parsed("pagedown"), "PgDn");
+        assert_eq!(parsed("F5"), "F5");
+        assert_eq!(parsed("-"), "-");
+    }
+}
@@ -8,7 +8,9 @@
 //! - **Imposter Management**: Create, delete, enable/disable imposters
 //! - **Stub Management**: Add, edit, delete, and reorder stubs
 //! - **Proxy Recording**: Create proxy imposters to record API responses
-//! - **Metrics Dashboard**: View request counts with sparklines
+//! - **Metrics Dashboard**: Latency percentiles, status codes, faults and stub hits per imposter
+//! - **Multiple Servers**: Switch between named servers, or list every server's imposters
+//! - **Configurable Keys**: Rebind the main views' keys in `~/.config/rift-tui/keys.toml`
 //! - **Import/Export**: Load and save imposter configurations
 //! - **File Explorer**: Built-in file browser for import/export operations
 //!
@@ -27,7 +29,13 @@
 
 pub mod api;
 pub mod app;
+pub mod body_preview;
+pub mod confirm;
 pub mod event;
+pub mod json_syntax;
+pub mod keys;
+pub mod servers;
+pub mod state;
 pub mod theme;
 pub mod ui;
 pub mod validation;
@@ -74,6 +82,11 @@ pub async fn run(mut app: App) -> anyhow::Result<()> {
     )?;
     terminal.show_cursor()?;
 
+    // Only once the terminal is back, so this can be read
+    if let Err(e) = app.save_session() {
+        eprintln!("rift-tui: could not remember where this session was left: {e}");
+    }
+
     result
 }
 
@@ -86,7 +99,8 @@ async fn run_app(
 
     while !app.should_quit {
         // Draw UI
-        terminal.draw(|f| ui::draw(f, app))?;
+        let frame = terminal.draw(|f| ui::draw(f, app))?;
+        app.save_screenshot(frame.buffer).await;
 
         // Handle events
         if let Some(event) = events.next().await {
@@ -95,8 +109,8 @@ async fn run_app(
                     app.handle_key_event(key).await;
                 }
                 Event::Tick => {
-                    // Auto-refresh
-                    if app.last_refresh.elapsed() >= app.refresh_interval {
+                    // Auto-refresh, backing off while the server is unreachable
+                    if app.refresh_due(std::time::Instant::now()) {
                         app.refresh().await;
                     }
                     app.clear_expired_status();
@@ -9,30 +9,78 @@
 //! # Connect to a different server
 //! rift-tui --admin-url http://server:2525
 //!
+//! # Start with a server from ~/.config/rift-tui/servers.toml, or every server's imposters
+//! rift-tui --server staging
+//! rift-tui --server all
+//!
 //! # Custom refresh interval
 //! rift-tui --refresh-ms 500
+//!
+//! # Authenticate against an admin API started with --api-key
+//! rift-tui --api-key s3cret
+//!
+//! # High contrast, and every change said on the status bar for screen readers
+//! rift-tui --accessible
+//!
+//! # Never ask for confirmation, for a TUI driven by a script in tmux
+//! rift-tui --yes
 //! ```
 
 use clap::Parser;
 use rift_tui::App;
+use rift_tui::app::Server;
+use rift_tui::confirm::{self, Confirmations};
+use rift_tui::keys::{self, KeyMap};
+use rift_tui::servers::{self, ServersFile};
+use rift_tui::state::{self, State};
+use rift_tui::theme::{self, Themes};
+use std::path::PathBuf;
 use std::time::Duration;
 
 #[derive(Parser, Debug)]
 #[command(name = "rift-tui")]
 #[command(author, version, about = "Interactive TUI for Rift HTTP Proxy")]
 struct Args {
-    /// Admin API URL
-    #[arg(
-        short,
-        long,
-        default_value = "http://localhost:2525",
-        env = "RIFT_ADMIN_URL"
-    )]
-    admin_url: String,
+    /// Admin API URL [default: http://localhost:2525, or the servers file's default]
+    #[arg(short, long, env = "RIFT_ADMIN_URL")]
+    admin_url: Option<String>,
+
+    /// Server from the servers file to start with, or "all" for every server's imposters
+    #[arg(long, env = "RIFT_SERVER")]
+    server: Option<String>,
+
+    /// Servers file [default: ~/.config/rift-tui/servers.toml]
+    #[arg(long, env = "RIFT_TUI_SERVERS")]
+    servers_file: Option<PathBuf>,
+
+    /// Key bindings file [default: ~/.config/rift-tui/keys.toml]
+    #[arg(long, env = "RIFT_TUI_KEYS")]
+    keys_file: Option<PathBuf>,
+
+    /// Themes file [default: ~/.config/rift-tui/themes.toml]
+    #[arg(long, env = "RIFT_TUI_THEMES")]
+    themes_file: Option<PathBuf>,
+
+    /// Confirmations file, saying which actions ask first [default: ~/.config/rift-tui/confirm.toml]
+    #[arg(long, env = "RIFT_TUI_CONFIRM")]
+    confirm_file: Option<PathBuf>,
+
+    /// Run every action without asking for confirmation, for a TUI driven by a script
+    #[arg(short, long, env = "RIFT_TUI_YES")]
+    yes: bool,
 
     /// Refresh interval in milliseconds
     #[arg(short, long, default_value = "1000")]
     refresh_ms: u64,
+
+    /// API key sent in the Authorization header, for an admin API started with --api-key
+    #[arg(long, env = "RIFT_API_KEY")]
+    api_key: Option<String>,
+
+    /// High contrast, no glyph-only indicators, and changes said on the status bar, for screen
+    /// readers and low-color terminals
+    #[arg(long, env = "RIFT_TUI_ACCESSIBLE")]
+    accessible: bool,
 }
 
 #[tokio::main]
@@ -40,7 +88,60 @@ async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
 
     let refresh_interval = Duration::from_millis(args.refresh_ms);
-    let app = App::new(&args.admin_url, refresh_interval).await;
+    let file = match args.servers_file.or_else(servers::default_servers_path) {
+        Some(path) => ServersFile::load(&path)?,
+        None => ServersFile::default(),
+    };
+    let state_path = state::default_state_path();
+    let saved = state_path.as_deref().map(State::load).unwrap_or_default();
+    // The server last shown, unless the command line names one
+    let server = match (&args.server, &args.admin_url, &saved.session) {
+        (None, None, Some(session)) => session.server_in(&file).map(str::to_string),
+        _ => args.server,
+    };
+    let (profiles, active) = servers::resolve(
+        file,
+        args.admin_url.as_deref(),
+        server.as_deref(),
+        args.api_key.as_deref(),
+    )?;
+    let servers = profiles
+        .into_iter()
+        .map(|profile| Ok(Server::new(profile.clone(), profile.client()?)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let keys = match args.keys_file.or_else(keys::default_keys_path) {
+        Some(path) => KeyMap::load(&path)?,
+        None => KeyMap::default(),
+    };
+    let themes = match args.themes_file.or_else(theme::default_themes_path) {
+        Some(path) => Themes::load(&path)?,
+        None => Themes::default(),
+    };
+    let confirmations = if args.yes {
+        Confirmations::unattended()
+    } else {
+        match args.confirm_file.or_else(confirm::default_confirm_path) {
+            Some(path) => Confirmations::load(&path)?,
+            None => Confirmations::default(),
+        }
+    };
+    let mut app = App::with_servers(servers, active, refresh_interval).await;
+    app.keys = keys;
+    app.confirmations = confirmations;
+    // A theme since removed from themes.toml falls back to the default
+    if let Some(theme) = saved.theme.and_then(|name| themes.find(&name)) {
+        app.theme = theme.clone();
+    }
+    if let Some(sort) = saved.sort {
+        app.imposter_sort = sort;
+        app.sort_imposters();
+    }
+    app.themes = themes;
+    app.set_accessible(args.accessible || saved.accessible);
+    app.state_path = state_path;
+    if let Some(session) = &saved.session {
+        app.restore_session(session).await;
+    }
 
     rift_tui::run(app).await
 }
@@ -0,0 +1,268 @@
+//! Named Rift servers the TUI can switch between, from `~/.config/rift-tui/servers.toml`
+//!
+//! ```toml
+//! default = "local"
+//!
+//! [[server]]
+//! name = "local"
+//! url = "http://localhost:2525"
+//!
+//! [[server]]
+//! name = "staging"
+//! url = "https://rift.staging.example.com:2525"
+//! api_key_env = "RIFT_STAGING_API_KEY"
+//! ```
+
+use crate::api::ApiClient;
+use anyhow::{Context, bail};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// The admin URL used when neither a profile nor `--admin-url` names one
+pub const DEFAULT_ADMIN_URL: &str = "http://localhost:2525";
+
+/// What `--server` and `default` accept to start with every server's imposters in one list
+pub const ALL_SERVERS: &str = "all";
+
+/// One Rift server's admin API
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServerProfile {
+    pub name: String,
+    pub url: String,
+    /// API key for an admin API started with `--api-key`
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Environment variable holding the API key, to keep it out of the file
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+}
+
+impl ServerProfile {
+    /// A profile for a bare admin URL, named after its host and port
+    pub fn from_url(url: &str) -> Self {
+        let url = url.trim_end_matches('/');
+        let name = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|parsed| {
+                let host = parsed.host_str()?.to_string();
+                Some(match parsed.port_or_known_default() {
+                    Some(port) => format!("{host}:{port}"),
+                    None => host,
+                })
+            })
+            .unwrap_or_else(|| url.to_string());
+        Self {
+            name,
+            url: url.to_string(),
+            api_key: None,
+            api_key_env: None,
+        }
+    }
+
+    /// A client for this server, sending its API key if it has one
+    pub fn client(&self) -> anyhow::Result<ApiClient> {
+        let client = ApiClient::new(&self.url);
+        let key = match (&self.api_key, &self.api_key_env) {
+            (Some(key), _) => Some(key.clone()),
+            (None, Some(var)) => Some(std::env::var(var).with_context(|| {
+                format!("server '{}': api_key_env {var} is not set", self.name)
+            })?),
+            (None, None) => None,
+        };
+        match key {
+            Some(key) => Ok(client.with_api_key(&key)?),
+            None => Ok(client),
+        }
+    }
+}
+
+/// The contents of a servers file
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServersFile {
+    /// Profile to start with when `--server` is not given, or `all`
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default, rename = "server")]
+    pub servers: Vec<ServerProfile>,
+}
+
+impl ServersFile {
+    /// Parse a servers file, rejecting unnamed and duplicate profiles
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        let file: Self = toml::from_str(text)?;
+        for (i, server) in file.servers.iter().enumerate() {
+            if server.name.trim().is_empty() || server.name == ALL_SERVERS {
+                bail!("server {} needs a name other than '{ALL_SERVERS}'", i + 1);
+            }
+            if file.servers[..i].iter().any(|s| s.name == server.name) {
+                bail!("server '{}' is defined twice", server.name);
+            }
+        }
+        Ok(file)
+    }
+
+    /// Read a servers file; a missing one has no servers
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).with_context(|| format!("{}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("{}", path.display())),
+        }
+    }
+}
+
+/// `rift-tui`'s configuration directory: `$XDG_CONFIG_HOME/rift-tui`, else `~/.config/rift-tui`
+pub fn config_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+        .map(|dir| dir.join("rift-tui"))
+}
+
+/// Where the servers file is read from by default
+pub fn default_servers_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("servers.toml"))
+}
+
+/// The position of the server at `url`, added first when no profile has that URL
+fn server_for_url(servers: &mut Vec<ServerProfile>, url: &str) -> usize {
+    let url = url.trim_end_matches('/');
+    match servers
+        .iter()
+        .position(|s| s.url.trim_end_matches('/') == url)
+    {
+        Some(index) => index,
+        None => {
+            servers.insert(0, ServerProfile::from_url(url));
+            0
+        }
+    }
+}
+
+fn named_server(servers: &[ServerProfile], name: &str) -> anyhow::Result<usize> {
+    servers
+        .iter()
+        .position(|s| s.name == name)
+        .with_context(|| {
+            let known: Vec<&str> = servers.iter().map(|s| s.name.as_str()).collect();
+            format!("unknown server '{name}' (known: {})", known.join(", "))
+        })
+}
+
+/// The servers to offer and the one to start with (`None` for all of them), from the servers
+/// file and the command line. `--admin-url` connects to that URL, joining the profiles as a
+/// server of its own unless one has the same URL; `--api-key` applies to the server started with.
+pub fn resolve(
+    file: ServersFile,
+    admin_url: Option<&str>,
+    server: Option<&str>,
+    api_key: Option<&str>,
+) -> anyhow::Result<(Vec<ServerProfile>, Option<usize>)> {
+    let mut servers = file.servers;
+    let active = match (admin_url, server) {
+        (Some(_), Some(_)) => bail!("--admin-url and --server cannot be used together"),
+        (Some(url), None) => Some(server_for_url(&mut servers, url)),
+        (None, name) => match name.or(file.default.as_deref()) {
+            Some(ALL_SERVERS) if servers.is_empty() => {
+                bail!("'{ALL_SERVERS}' needs servers in the servers file")
+            }
+            Some(ALL_SERVERS) => None,
+            Some(name) => Some(named_server(&servers, name)?),
+            None if servers.is_empty() => Some(server_for_url(&mut servers, DEFAULT_ADMIN_URL)),
+            None => Some(0),
+        },
+    };
+    if let (Some(key), Some(index)) = (api_key, active) {
+        servers[index].api_key = Some(key.to_string());
+    }
+    Ok((servers, active))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = r#"
+default = "staging"
+
+[[server]]
+name = "local"
+url = "http://localhost:2525"
+
+[[server]]
+name = "staging"
+url = "http://rift.staging:2525/"
+api_key_env = "RIFT_TUI_TEST_UNSET_KEY"
+"#;
+
+    fn names(servers: &[ServerProfile]) -> Vec<&str> {
+        servers.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn the_default_profile_is_started_with_unless_another_is_named() {
+        let (servers, active) =
+            resolve(ServersFile::parse(FILE).unwrap(), None, None, None).unwrap();
+        assert_eq!(names(&servers), ["local", "staging"]);
+        assert_eq!(active, Some(1));
+
+        let (_, active) =
+            resolve(ServersFile::parse(FILE).unwrap(), None, Some("all"), None).unwrap();
+        assert_eq!(active, None);
+
+        let err = resolve(ServersFile::parse(FILE).unwrap(), None, Some("prod"), None)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(err, "unknown server 'prod' (known: local, staging)");
+    }
+
+    #[test]
+    fn an_admin_url_reuses_a_matching_profile_or_joins_the_list() {
+        let (servers, active) = resolve(
+            ServersFile::parse(FILE).unwrap(),
+            Some("http://rift.staging:2525"),
+            None,
+            Some("s3cret"),
+        )
+        .unwrap();
+        assert_eq!(active, Some(1));
+        assert_eq!(servers[1].api_key.as_deref(), Some("s3cret"));
+
+        let (servers, active) = resolve(
+            ServersFile::parse(FILE).unwrap(),
+            Some("http://10.0.0.7:2525/"),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(names(&servers), ["10.0.0.7:2525", "local", "staging"]);
+        assert_eq!(servers[0].url, "http://10.0.0.7:2525");
+        assert_eq!(active, Some(0));
+
+        let (servers, active) = resolve(ServersFile::default(), None, None, None).unwrap();
+        assert_eq!(names(&servers), ["localhost:2525"]);
+        assert_eq!(active, Some(0));
+    }
+
+    #[test]
+    fn bad_files_are_rejected() {
+        let twice = "[[server]]\nname = \"a\"\nurl = \"http://a\"\n[[server]]\nname = \"a\"\nurl = \"http://b\"";
+        assert_eq!(
+            ServersFile::parse(twice).unwrap_err().to_string(),
+            "server 'a' is defined twice"
+        );
+        assert!(ServersFile::parse("[[server]]\nname = \"a\"\nurl = \"x\"\nport = 1").is_err());
+        assert!(resolve(ServersFile::default(), None, Some("all"), None).is_err());
+    }
+
+    #[test]
+    fn a_missing_key_variable_is_an_error() {
+        let file = ServersFile::parse(FILE).unwrap();
+        let err = file.servers[1].client().err().unwrap().to_string();
+        assert!(err.contains("RIFT_TUI_TEST_UNSET_KEY is not set"), "{err}");
+        assert!(file.servers[0].client().is_ok());
+    }
+}
@@ -0,0 +1,168 @@
+//! What the TUI remembers between sessions, in `~/.config/rift-tui/state.toml`
+
+use crate::app::{ImposterFilter, ImposterSort};
+use crate::servers::{ALL_SERVERS, ServersFile};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Choices made in one session that the next starts with
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct State {
+    /// Name of the theme last picked with `T`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// How the imposter list was last sorted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<ImposterSort>,
+    /// Where the last session left off
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<Session>,
+    /// Whether accessibility mode was last turned on from the command palette
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub accessible: bool,
+}
+
+/// The server, view, filters and positions a session was quit on
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    /// Name of the server shown, or `all`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<String>,
+    #[serde(default)]
+    pub view: SessionView,
+    /// Port of the imposter selected in the list, or open
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imposter: Option<u16>,
+    /// Row of the stub selected in the open imposter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stub: Option<usize>,
+    /// How far the stub detail view was scrolled down
+    #[serde(default)]
+    pub scroll: u16,
+    #[serde(default)]
+    pub search: String,
+    #[serde(default)]
+    pub filter: ImposterFilter,
+}
+
+/// The views a session can be resumed on; the others resume on the view they were opened from
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionView {
+    #[default]
+    ImposterList,
+    ImposterDetail,
+    StubDetail,
+    RequestLog,
+    Metrics,
+    ServerLog,
+    Config,
+}
+
+impl Session {
+    /// The server to start with, unless it has since left the servers file
+    pub fn server_in(&self, file: &ServersFile) -> Option<&str> {
+        let name = self.server.as_deref()?;
+        let known = if name == ALL_SERVERS {
+            !file.servers.is_empty()
+        } else {
+            file.servers.iter().any(|server| server.name == name)
+        };
+        known.then_some(name)
+    }
+}
+
+/// Where the state is kept by default
+pub fn default_state_path() -> Option<PathBuf> {
+    crate::servers::config_dir().map(|dir| dir.join("state.toml"))
+}
+
+impl State {
+    /// The state saved at `path`; a missing or unreadable file is forgotten
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|text| toml::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    /// Change what is saved at `path`, keeping the rest
+    pub fn update(path: &Path, change: impl FnOnce(&mut State)) -> anyhow::Result<()> {
+        let mut state = Self::load(path);
+        change(&mut state);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, toml::to_string(&state)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::SortKey;
+
+    #[test]
+    fn each_choice_is_remembered_without_forgetting_the_others() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rift-tui").join("state.toml");
+        assert_eq!(State::load(&path), State::default());
+
+        State::update(&path, |state| state.theme = Some("Solarized".to_string())).unwrap();
+        let sort = ImposterSort {
+            by: SortKey::Requests,
+            reversed: true,
+        };
+        State::update(&path, |state| state.sort = Some(sort)).unwrap();
+        assert_eq!(
+            State::load(&path),
+            State {
+                theme: Some("Solarized".to_string()),
+                sort: Some(sort),
+                session: None,
+                accessible: false,
+            }
+        );
+    }
+
+    #[test]
+    fn a_session_resumes_on_a_server_only_while_it_is_configured() {
+        let file = ServersFile::parse(
+            r#"
+[[server]]
+name = "staging"
+url = "http://rift.staging:2525"
+"#,
+        )
+        .unwrap();
+        let session = |name: &str| Session {
+            server: Some(name.to_string()),
+            ..Session::default()
+        };
+        assert_eq!(session("staging").server_in(&file), Some("staging"));
+        assert_eq!(session("all").server_in(&file), Some("all"));
+        assert_eq!(session("prod").server_in(&file), None);
+        assert_eq!(session("all").server_in(&ServersFile::default()), None);
+    }
+
+    #[test]
+    fn a_session_survives_the_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.toml");
+        let session = Session {
+            server: Some("staging".to_string()),
+            view: SessionView::StubDetail,
+            imposter: Some(4545),
+            stub: Some(2),
+            scroll: 7,
+            search: "orders".to_string(),
+            filter: ImposterFilter {
+                protocol: Some("https".to_string()),
+                enabled: None,
+            },
+        };
+        State::update(&path, |state| state.session = Some(session.clone())).unwrap();
+        assert_eq!(State::load(&path).session, Some(session));
+    }
+}
@@ -1,6 +1,20 @@
 //! Theme and color scheme for the TUI
+//!
+//! Besides the built-in presets, themes can be defined in `~/.config/rift-tui/themes.toml`,
+//! each setting any of [`Theme`]'s colors on top of a preset:
+//!
+//! ```toml
+//! [[theme]]
+//! name = "Midnight"
+//! base = "dracula"
+//! bg = "#1e1e2e"
+//! focus = "light yellow"
+//! ```
 
+use anyhow::{Context, bail};
 use ratatui::style::Color;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
 
 /// Available theme presets
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
@@ -11,6 +25,8 @@ pub enum ThemePreset {
     Light,
     Nord,
     Dracula,
+    Solarized,
+    HighContrast,
 }
 
 impl ThemePreset {
@@ -21,6 +37,8 @@ impl ThemePreset {
         ThemePreset::Light,
         ThemePreset::Nord,
         ThemePreset::Dracula,
+        ThemePreset::Solarized,
+        ThemePreset::HighContrast,
     ];
 
     /// Get the next theme in the cycle
@@ -30,7 +48,9 @@ impl ThemePreset {
             ThemePreset::Dark => ThemePreset::Light,
             ThemePreset::Light => ThemePreset::Nord,
             ThemePreset::Nord => ThemePreset::Dracula,
-            ThemePreset::Dracula => ThemePreset::Default,
+            ThemePreset::Dracula => ThemePreset::Solarized,
+            ThemePreset::Solarized => ThemePreset::HighContrast,
+            ThemePreset::HighContrast => ThemePreset::Default,
         }
     }
 
@@ -42,14 +62,37 @@ impl ThemePreset {
             ThemePreset::Light => "Light",
             ThemePreset::Nord => "Nord",
             ThemePreset::Dracula => "Dracula",
+            ThemePreset::Solarized => "Solarized",
+            ThemePreset::HighContrast => "High Contrast",
         }
     }
+
+    /// The preset called `name`, ignoring case, spaces, dashes and underscores
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|preset| same_name(preset.name(), name))
+    }
+}
+
+fn same_name(a: &str, b: &str) -> bool {
+    let normalize = |s: &str| {
+        s.chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '_'))
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    normalize(a) == normalize(b)
 }
 
 /// Color theme for the TUI
 #[derive(Debug, Clone)]
 pub struct Theme {
+    /// The preset, or for a theme from themes.toml the preset it starts from
     pub preset: ThemePreset,
+    /// Name shown when cycling themes
+    pub name: String,
     pub bg: Color,
     pub fg: Color,
     pub highlight_bg: Color,
@@ -78,9 +121,11 @@ impl Default for Theme {
 impl Theme {
     /// Create a theme from a preset
     pub fn from_preset(preset: ThemePreset) -> Self {
+        let name = preset.name().to_string();
         match preset {
             ThemePreset::Default => Self {
                 preset,
+                name,
                 bg: Color::Reset,
                 fg: Color::White,
                 highlight_bg: Color::Blue,
@@ -100,6 +145,7 @@ impl Theme {
             },
             ThemePreset::Dark => Self {
                 preset,
+                name,
                 bg: Color::Rgb(30, 30, 30),
                 fg: Color::Rgb(220, 220, 220),
                 highlight_bg: Color::Rgb(60, 60, 80),
@@ -119,6 +165,7 @@ impl Theme {
             },
             ThemePreset::Light => Self {
                 preset,
+                name,
                 bg: Color::Reset, // Use terminal default
                 fg: Color::White,
                 highlight_bg: Color::Rgb(100, 149, 237), // Cornflower blue
@@ -138,6 +185,7 @@ impl Theme {
             },
             ThemePreset::Nord => Self {
                 preset,
+                name,
                 bg: Color::Rgb(46, 52, 64),            // nord0
                 fg: Color::Rgb(236, 239, 244),         // nord6
                 highlight_bg: Color::Rgb(76, 86, 106), // nord3
@@ -157,6 +205,7 @@ impl Theme {
             },
             ThemePreset::Dracula => Self {
                 preset,
+                name,
                 bg: Color::Rgb(40, 42, 54),           // background
                 fg: Color::Rgb(248, 248, 242),        // foreground
                 highlight_bg: Color::Rgb(68, 71, 90), // current line
@@ -174,15 +223,177 @@ impl Theme {
                 key_fg: Color::Rgb(139, 233, 253), // cyan
                 cmd_fg: Color::Rgb(98, 114, 164),  // comment
             },
+            ThemePreset::Solarized => Self {
+                preset,
+                name,
+                bg: Color::Rgb(0, 43, 54),               // base03
+                fg: Color::Rgb(131, 148, 150),           // base0
+                highlight_bg: Color::Rgb(7, 54, 66),     // base02
+                highlight_fg: Color::Rgb(147, 161, 161), // base1
+                success: Color::Rgb(133, 153, 0),        // green
+                warning: Color::Rgb(181, 137, 0),        // yellow
+                error: Color::Rgb(220, 50, 47),          // red
+                muted: Color::Rgb(88, 110, 117),         // base01
+                border: Color::Rgb(88, 110, 117),
+                header_bg: Color::Rgb(38, 139, 210), // blue
+                header_fg: Color::Rgb(0, 43, 54),
+                enabled: Color::Rgb(133, 153, 0),
+                disabled: Color::Rgb(88, 110, 117),
+                focus: Color::Rgb(203, 75, 22),    // orange
+                key_fg: Color::Rgb(42, 161, 152),  // cyan
+                cmd_fg: Color::Rgb(101, 123, 131), // base00
+            },
+            // Only the 16 basic colors, bright on black, for low-vision use and poor terminals
+            ThemePreset::HighContrast => Self {
+                preset,
+                name,
+                bg: Color::Black,
+                fg: Color::White,
+                highlight_bg: Color::Yellow,
+                highlight_fg: Color::Black,
+                success: Color::LightGreen,
+                warning: Color::LightYellow,
+                error: Color::LightRed,
+                muted: Color::Gray,
+                border: Color::White,
+                header_bg: Color::White,
+                header_fg: Color::Black,
+                enabled: Color::LightGreen,
+                disabled: Color::Gray,
+                focus: Color::LightCyan,
+                key_fg: Color::LightCyan,
+                cmd_fg: Color::White,
+            },
         }
     }
 
+    /// The color a themes.toml key sets
+    fn color_mut(&mut self, field: &str) -> Option<&mut Color> {
+        Some(match field {
+            "bg" => &mut self.bg,
+            "fg" => &mut self.fg,
+            "highlight_bg" => &mut self.highlight_bg,
+            "highlight_fg" => &mut self.highlight_fg,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            "muted" => &mut self.muted,
+            "border" => &mut self.border,
+            "header_bg" => &mut self.header_bg,
+            "header_fg" => &mut self.header_fg,
+            "enabled" => &mut self.enabled,
+            "disabled" => &mut self.disabled,
+            "focus" => &mut self.focus,
+            "key_fg" => &mut self.key_fg,
+            "cmd_fg" => &mut self.cmd_fg,
+            _ => return None,
+        })
+    }
+
     /// Cycle to the next theme
     pub fn next(&mut self) {
         *self = Self::from_preset(self.preset.next());
     }
 }
 
+/// The themes `T` cycles through: the presets, then those from themes.toml
+#[derive(Debug, Clone)]
+pub struct Themes {
+    themes: Vec<Theme>,
+}
+
+impl Default for Themes {
+    fn default() -> Self {
+        Self {
+            themes: ThemePreset::ALL
+                .iter()
+                .map(|&preset| Theme::from_preset(preset))
+                .collect(),
+        }
+    }
+}
+
+impl Themes {
+    /// Parse a themes file: `[[theme]]` tables with a `name`, an optional `base` preset and
+    /// colors as names (`light blue`), hex (`#88c0d0`) or 256-color indexes (`208`)
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct File {
+            #[serde(default)]
+            theme: Vec<toml::Table>,
+        }
+
+        let file: File = toml::from_str(text)?;
+        let mut themes = Self::default();
+        for (i, mut table) in file.theme.into_iter().enumerate() {
+            let Some(toml::Value::String(name)) = table.remove("name") else {
+                bail!("theme {} needs a name", i + 1);
+            };
+            if themes.find(&name).is_some() {
+                bail!("theme '{name}' is defined twice or is a built-in theme");
+            }
+            let preset = match table.remove("base") {
+                None => ThemePreset::Default,
+                Some(toml::Value::String(base)) => ThemePreset::from_name(&base)
+                    .with_context(|| format!("theme '{name}': unknown base theme '{base}'"))?,
+                Some(_) => bail!("theme '{name}': base must be a theme name"),
+            };
+            let mut theme = Theme {
+                name: name.clone(),
+                ..Theme::from_preset(preset)
+            };
+            for (field, value) in table {
+                let Some(color) = theme.color_mut(&field) else {
+                    bail!("theme '{name}': unknown color '{field}'");
+                };
+                *color = value
+                    .as_str()
+                    .and_then(|value| value.parse().ok())
+                    .with_context(|| format!("theme '{name}': {field} = {value} is not a color"))?;
+            }
+            themes.themes.push(theme);
+        }
+        Ok(themes)
+    }
+
+    /// Read a themes file; a missing one leaves the presets
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).with_context(|| format!("{}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("{}", path.display())),
+        }
+    }
+
+    /// The theme called `name`, matched like [`ThemePreset::from_name`]
+    pub fn find(&self, name: &str) -> Option<&Theme> {
+        self.themes
+            .iter()
+            .find(|theme| same_name(&theme.name, name))
+    }
+
+    /// Every theme, presets first
+    pub fn all(&self) -> &[Theme] {
+        &self.themes
+    }
+
+    /// The theme after the one called `name`, wrapping round
+    pub fn after(&self, name: &str) -> &Theme {
+        let index = self
+            .themes
+            .iter()
+            .position(|theme| theme.name == name)
+            .map_or(0, |i| (i + 1) % self.themes.len());
+        &self.themes[index]
+    }
+}
+
+/// Where the themes file is read from by default
+pub fn default_themes_path() -> Option<PathBuf> {
+    crate::servers::config_dir().map(|dir| dir.join("themes.toml"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -221,4 +432,47 @@ mod tests {
         }
         assert_eq!(seen.len(), ThemePreset::ALL.len());
     }
+
+    #[test]
+    fn themes_file_themes_start_from_a_preset_and_follow_them() {
+        let themes = Themes::parse(
+            r##"
+[[theme]]
+name = "Midnight"
+base = "high-contrast"
+bg = "#1e1e2e"
+focus = "light yellow"
+key_fg = "208"
+"##,
+        )
+        .unwrap();
+        let midnight = themes.find("midnight").unwrap();
+        assert_eq!(midnight.preset, ThemePreset::HighContrast);
+        assert_eq!(midnight.bg, Color::Rgb(0x1e, 0x1e, 0x2e));
+        assert_eq!(midnight.focus, Color::LightYellow);
+        assert_eq!(midnight.key_fg, Color::Indexed(208));
+        assert_eq!(midnight.fg, Color::White);
+
+        assert_eq!(themes.after("High Contrast").name, "Midnight");
+        assert_eq!(themes.after("Midnight").name, "Default");
+        assert_eq!(themes.find("solarized").unwrap().name, "Solarized");
+    }
+
+    #[test]
+    fn bad_themes_are_rejected() {
+        let err = |text: &str| Themes::parse(text).unwrap_err().to_string();
+        assert_eq!(
+            err("[[theme]]\nname = \"x\"\nbackground = \"red\""),
+            "theme 'x': unknown color 'background'"
+        );
+        assert_eq!(
+            err("[[theme]]\nname = \"x\"\nfg = \"reddish\""),
+            "theme 'x': fg = \"reddish\" is not a color"
+        );
+        assert_eq!(
+            err("[[theme]]\nname = \"nord\""),
+            "theme 'nord' is defined twice or is a built-in theme"
+        );
+        assert!(Themes::parse("[[theme]]\nbase = \"nord\"").is_err());
+    }
 }
@@ -0,0 +1,197 @@
+//! Request composer overlay — send a request to an imposter and read the response inline
+
+use super::request_log::status_color;
+use crate::app::{App, ComposerField, response_sections};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Draw the request composer over the current view
+pub fn draw(frame: &mut Frame, app: &App) {
+    let Some(composer) = &app.composer else {
+        return;
+    };
+    let area = super::centered_rect(90, 90, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" Send Request to :{} ", composer.port))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),      // Method, path, debug
+            Constraint::Length(6),      // Headers
+            Constraint::Percentage(25), // Body
+            Constraint::Min(6),         // Response
+            Constraint::Length(1),      // Help
+        ])
+        .split(inner);
+
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length(12),
+            Constraint::Min(10),
+            Constraint::Length(16),
+        ])
+        .split(chunks[0]);
+    let method = Paragraph::new(Line::from(vec![
+        Span::styled("◀ ", Style::default().fg(Color::DarkGray)),
+        Span::styled(composer.method(), Style::default().fg(Color::White).bold()),
+        Span::styled(" ▶", Style::default().fg(Color::DarkGray)),
+    ]))
+    .block(field_block(
+        "Method",
+        composer.focus == ComposerField::Method,
+    ));
+    frame.render_widget(method, top[0]);
+
+    let mut path = vec![Span::raw(composer.path.as_str())];
+    if composer.focus == ComposerField::Path {
+        path.push(Span::styled(
+            "█",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::SLOW_BLINK),
+        ));
+    }
+    frame.render_widget(
+        Paragraph::new(Line::from(path))
+            .block(field_block("Path", composer.focus == ComposerField::Path)),
+        top[1],
+    );
+
+    let (debug_label, debug_color) = if composer.debug {
+        ("on", app.theme.success)
+    } else {
+        ("off", Color::DarkGray)
+    };
+    frame.render_widget(
+        Paragraph::new(Span::styled(debug_label, Style::default().fg(debug_color)))
+            .block(field_block("X-Rift-Debug", false)),
+        top[2],
+    );
+
+    draw_text_field(
+        frame,
+        chunks[1],
+        "Headers (Name: value)",
+        &composer.headers,
+        composer.focus == ComposerField::Headers,
+    );
+    draw_text_field(
+        frame,
+        chunks[2],
+        "Body",
+        &composer.body,
+        composer.focus == ComposerField::Body,
+    );
+    draw_response(frame, app, chunks[3]);
+
+    let help = Line::from(vec![
+        Span::styled("[^S]", Style::default().fg(Color::Green).bold()),
+        Span::raw(" Send  "),
+        Span::styled("[^D]", Style::default().fg(Color::Cyan).bold()),
+        Span::raw(" Debug  "),
+        Span::styled("[Tab]", Style::default().fg(Color::Cyan).bold()),
+        Span::raw(" Next field  "),
+        Span::styled("[←/→]", Style::default().fg(Color::Gray)),
+        Span::raw(" Method  "),
+        Span::styled("[PgUp/PgDn]", Style::default().fg(Color::Gray)),
+        Span::raw(" Scroll response  "),
+        Span::styled("[Esc]", Style::default().fg(Color::Red).bold()),
+        Span::raw(" Close"),
+    ]);
+    frame.render_widget(Paragraph::new(help).alignment(Alignment::Center), chunks[4]);
+}
+
+/// Border and title of a composer field, highlighted when it has focus
+pub(super) fn field_block(label: &str, focused: bool) -> Block<'_> {
+    let (title, color) = if focused {
+        (format!(" ▶ {label} "), Color::Yellow)
+    } else {
+        (format!("   {label} "), Color::DarkGray)
+    };
+    Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+}
+
+pub(super) fn draw_text_field(
+    frame: &mut Frame,
+    area: Rect,
+    label: &str,
+    text: &ratatui_textarea::TextArea<'static>,
+    focused: bool,
+) {
+    let block = field_block(label, focused);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    frame.render_widget(text, inner);
+}
+
+/// The last response: status and latency, then Rift's diagnostics, headers and body
+fn draw_response(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(composer) = &app.composer else {
+        return;
+    };
+    let block = field_block("Response", false);
+    let Some(response) = &composer.response else {
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                "Press Ctrl+S to send the request",
+                Style::default().fg(Color::DarkGray),
+            ))
+            .block(block),
+            area,
+        );
+        return;
+    };
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!("HTTP {}", response.status),
+            Style::default()
+                .fg(status_color(response.status, app))
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("  {}ms", response.latency_ms),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])];
+    for (title, section) in response_sections(response) {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            title,
+            Style::default()
+                .fg(app.theme.highlight_bg)
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.extend(section.into_iter().map(|line| {
+            Line::from(Span::styled(
+                format!("  {line}"),
+                Style::default().fg(Color::White),
+            ))
+        }));
+    }
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((composer.response_scroll, 0))
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
@@ -0,0 +1,83 @@
+//! Copy-as menu overlay — the commands to copy for the selected stub, imposter or request
+
+use crate::app::{App, CopyTarget};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+/// Draw the copy-as menu over the current view
+pub fn draw(frame: &mut Frame, app: &App) {
+    let Some(menu) = &app.copy_menu else {
+        return;
+    };
+    let height = menu.commands.len() as u16 + 4;
+    let area = super::centered_rect(50, 50, frame.area());
+    let area = ratatui::layout::Rect {
+        height: height.min(area.height),
+        ..area
+    };
+    frame.render_widget(Clear, area);
+
+    let title = match menu.target {
+        CopyTarget::Stub { port, index } => format!(" Copy stub {index} of :{port} as "),
+        CopyTarget::Imposter { port, .. } => format!(" Copy imposter :{port} as "),
+        CopyTarget::Request { port } => format!(" Copy request to :{port} as "),
+    };
+    let block = Block::default()
+        .title(title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.focus))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let items: Vec<ListItem> = menu
+        .commands
+        .iter()
+        .enumerate()
+        .map(|(i, command)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {} ", i + 1), Style::default().fg(app.theme.muted)),
+                Span::styled(
+                    format!("{:<7}", command.tool().label()),
+                    Style::default().fg(app.theme.key_fg).bold(),
+                ),
+                Span::styled(
+                    command.describe(menu.port()),
+                    Style::default().fg(app.theme.fg),
+                ),
+            ]))
+        })
+        .collect();
+    let mut state = ListState::default().with_selected(Some(menu.selected));
+    let list = List::new(items).highlight_style(
+        Style::default()
+            .bg(app.theme.highlight_bg)
+            .fg(app.theme.highlight_fg),
+    );
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+
+    let key = |key: &'static str| Span::styled(key, Style::default().fg(app.theme.key_fg).bold());
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            key("[Enter/1-9]"),
+            Span::raw(" Copy  "),
+            key("[↑/↓]"),
+            Span::raw(" Move  "),
+            key("[Esc]"),
+            Span::raw(" Close"),
+        ]))
+        .alignment(Alignment::Center),
+        chunks[1],
+    );
+}
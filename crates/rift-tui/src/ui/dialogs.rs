@@ -1,7 +1,7 @@
 //! Modal dialogs using tui-popup and tui-prompts for a cleaner implementation
 
-use crate::app::{App, ErrorEntry, InputAction, ValidationAction};
-use crate::validation::{IssueSeverity, ValidationReport};
+use crate::app::{App, DiffLine, ErrorEntry, InputAction, ValidationAction};
+use crate::validation::{IssueSeverity, ValidationIssue, ValidationReport};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -103,16 +103,15 @@ pub fn draw_success(frame: &mut Frame, message: &str) {
     frame.render_widget(popup, frame.area());
 }
 
-/// Draw an input dialog for creating imposters
+/// Draw an input dialog
 pub fn draw_input(frame: &mut Frame, app: &App, prompt: &str, action: &InputAction) {
     match action {
-        InputAction::CreateImposter => draw_create_imposter_input(frame, app, prompt),
-        InputAction::CreateProxyImposter => draw_create_proxy_input(frame, app, prompt),
+        InputAction::ReplayToPort { .. } => draw_replay_port_input(frame, app, prompt),
     }
 }
 
 /// Helper to create an input field with consistent styling
-fn draw_input_field(
+pub(super) fn draw_input_field(
     frame: &mut Frame,
     area: Rect,
     label: &str,
@@ -190,9 +189,9 @@ fn draw_input_field(
     frame.render_widget(paragraph, inner);
 }
 
-/// Draw the regular imposter creation dialog
-fn draw_create_imposter_input(frame: &mut Frame, app: &App, prompt: &str) {
-    let area = super::centered_rect(55, 45, frame.area());
+/// Draw the port prompt for replaying an inspected request to another imposter
+fn draw_replay_port_input(frame: &mut Frame, app: &App, prompt: &str) {
+    let area = super::centered_rect(45, 25, frame.area());
 
     // Clear the background
     frame.render_widget(Clear, area);
@@ -207,200 +206,39 @@ fn draw_create_imposter_input(frame: &mut Frame, app: &App, prompt: &str) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    // Layout for input fields
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(1), // Spacing
             Constraint::Length(3), // Port
-            Constraint::Length(3), // Name
-            Constraint::Length(3), // Protocol
-            Constraint::Length(1), // Spacing
-            Constraint::Min(2),    // Help text
+            Constraint::Min(1),    // Help text
         ])
         .split(inner);
 
-    // Port field
     draw_input_field(
         frame,
         chunks[1],
-        "Port (optional)",
+        "Port",
         &app.input_state.port,
-        "auto-assign",
-        app.input_state.focus_field == 0,
-        None,
-    );
-
-    // Name field
-    draw_input_field(
-        frame,
-        chunks[2],
-        "Name (optional)",
-        &app.input_state.name,
-        "unnamed",
-        app.input_state.focus_field == 1,
-        None,
-    );
-
-    // Protocol field
-    draw_input_field(
-        frame,
-        chunks[3],
-        "Protocol",
-        &app.input_state.protocol,
-        "http",
-        app.input_state.focus_field == 2,
-        None,
-    );
-
-    // Help text
-    let help = Line::from(vec![
-        Span::styled("[Tab]", Style::default().fg(Color::Cyan).bold()),
-        Span::raw(" Next  "),
-        Span::styled("[Shift+Tab]", Style::default().fg(Color::Cyan).bold()),
-        Span::raw(" Prev  "),
-        Span::styled("[Enter]", Style::default().fg(Color::Green).bold()),
-        Span::raw(" Create  "),
-        Span::styled("[Esc]", Style::default().fg(Color::Red).bold()),
-        Span::raw(" Cancel"),
-    ]);
-
-    let help_paragraph = Paragraph::new(help).alignment(Alignment::Center);
-    frame.render_widget(help_paragraph, chunks[5]);
-}
-
-/// Draw the proxy imposter creation dialog
-fn draw_create_proxy_input(frame: &mut Frame, app: &App, prompt: &str) {
-    let area = super::centered_rect(65, 55, frame.area());
-
-    // Clear the background
-    frame.render_widget(Clear, area);
-
-    let block = Block::default()
-        .title(format!(" {prompt} "))
-        .title_alignment(Alignment::Center)
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow))
-        .style(Style::default().bg(Color::Black));
-
-    let inner = block.inner(area);
-    frame.render_widget(block, area);
-
-    // Layout for input fields
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(1), // Spacing
-            Constraint::Length(3), // Target URL
-            Constraint::Length(3), // Port
-            Constraint::Length(3), // Name
-            Constraint::Length(3), // Proxy Mode
-            Constraint::Length(1), // Spacing
-            Constraint::Length(2), // Mode description
-            Constraint::Length(1), // Spacing
-            Constraint::Min(2),    // Help text
-        ])
-        .split(inner);
-
-    // Target URL field
-    draw_input_field(
-        frame,
-        chunks[1],
-        "Target URL (required)",
-        &app.input_state.target_url,
-        "https://api.example.com",
-        app.input_state.focus_field == 0,
-        None,
-    );
-
-    // Port field
-    draw_input_field(
-        frame,
-        chunks[2],
-        "Port (optional)",
-        &app.input_state.port,
-        "auto-assign",
-        app.input_state.focus_field == 1,
-        None,
-    );
-
-    // Name field
-    draw_input_field(
-        frame,
-        chunks[3],
-        "Name (optional)",
-        &app.input_state.name,
-        "unnamed",
-        app.input_state.focus_field == 2,
+        "imposter port",
+        true,
         None,
     );
 
-    // Proxy Mode selector
-    let mode_focused = app.input_state.focus_field == 3;
-    let mode_style = if mode_focused {
-        Style::default().fg(Color::Yellow)
-    } else {
-        Style::default().fg(Color::DarkGray)
-    };
-    let mode_title = if mode_focused {
-        " ▶ Proxy Mode (←/→ to change) "
-    } else {
-        "   Proxy Mode (←/→ to change) "
-    };
-
-    let mode_block = Block::default()
-        .title(mode_title)
-        .borders(Borders::ALL)
-        .border_style(mode_style);
-
-    let mode_inner = mode_block.inner(chunks[4]);
-    frame.render_widget(mode_block, chunks[4]);
-
-    let mode_text = Line::from(vec![
-        Span::styled("◀ ", Style::default().fg(Color::DarkGray)),
-        Span::styled(
-            app.input_state.proxy_mode_str(),
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::styled(" ▶", Style::default().fg(Color::DarkGray)),
-    ]);
-
-    let mode_paragraph = Paragraph::new(mode_text).alignment(Alignment::Center);
-    frame.render_widget(mode_paragraph, mode_inner);
-
-    // Mode description
-    let description = match app.input_state.proxy_mode {
-        0 => "Record first response, replay subsequent requests",
-        1 => "Always forward to backend, keep recording new responses",
-        2 => "Always forward to backend, no recording",
-        _ => "",
-    };
-    let desc_paragraph = Paragraph::new(description)
-        .style(Style::default().fg(Color::DarkGray))
-        .alignment(Alignment::Center);
-    frame.render_widget(desc_paragraph, chunks[6]);
-
-    // Help text
     let help = Line::from(vec![
-        Span::styled("[Tab]", Style::default().fg(Color::Cyan).bold()),
-        Span::raw(" Next  "),
-        Span::styled("[←/→]", Style::default().fg(Color::Cyan).bold()),
-        Span::raw(" Mode  "),
         Span::styled("[Enter]", Style::default().fg(Color::Green).bold()),
-        Span::raw(" Create  "),
+        Span::raw(" Send  "),
         Span::styled("[Esc]", Style::default().fg(Color::Red).bold()),
         Span::raw(" Cancel"),
     ]);
-
     let help_paragraph = Paragraph::new(help).alignment(Alignment::Center);
-    frame.render_widget(help_paragraph, chunks[8]);
+    frame.render_widget(help_paragraph, chunks[2]);
 }
 
 /// Draw an export overlay showing JSON content
 pub fn draw_export(
     frame: &mut Frame,
+    app: &App,
     title: &str,
     content: &str,
     scroll_offset: u16,
@@ -430,14 +268,22 @@ pub fn draw_export(
         ])
         .split(inner);
 
-    // Content with scrolling
-    let lines: Vec<Line> = content.lines().map(Line::from).collect();
-    let total_lines = lines.len() as u16;
-
-    let content_paragraph = Paragraph::new(lines)
-        .style(Style::default().fg(Color::White))
-        .scroll((scroll_offset, 0))
-        .wrap(Wrap { trim: false });
+    // Only the lines in view are colored, with long strings cut short on screen; copying or
+    // saving still takes the whole content
+    let total_lines = content.lines().count() as u16;
+    let lines: Vec<Line> = content
+        .lines()
+        .skip(scroll_offset as usize)
+        .take(chunks[0].height as usize)
+        .map(|line| {
+            let line = crate::body_preview::collapse_line(line);
+            Line::from(super::editor::spans_from_cells(
+                super::editor::styled_chars(&line, app),
+            ))
+        })
+        .collect();
+
+    let content_paragraph = Paragraph::new(lines).wrap(Wrap { trim: false });
     frame.render_widget(content_paragraph, chunks[0]);
 
     // Scrollbar
@@ -590,10 +436,54 @@ pub fn draw_validation_result(
         ])
         .split(inner);
 
-    // Build lines for each issue
+    let lines = issue_lines(&report.issues, "");
+    let total_lines = lines.len() as u16;
+
+    let issues_paragraph = Paragraph::new(lines)
+        .style(Style::default().fg(Color::White))
+        .scroll((scroll_offset, 0))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(issues_paragraph, chunks[0]);
+
+    // Scrollbar if needed
+    if total_lines > chunks[0].height {
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("↑"))
+            .end_symbol(Some("↓"));
+        let mut scrollbar_state =
+            ScrollbarState::new(total_lines as usize).position(scroll_offset as usize);
+        frame.render_stateful_widget(
+            scrollbar,
+            chunks[0].inner(ratatui::layout::Margin {
+                vertical: 1,
+                horizontal: 0,
+            }),
+            &mut scrollbar_state,
+        );
+    }
+
+    // Help text - different based on action
+    let help = match action {
+        ValidationAction::EditorInfo => Line::from(vec![
+            Span::styled("[↑/↓]", Style::default().fg(Color::Cyan).bold()),
+            Span::raw(" Scroll  "),
+            Span::styled("[Esc]", Style::default().fg(Color::Red).bold()),
+            Span::raw(" Close"),
+        ]),
+    };
+
+    let help_paragraph = Paragraph::new(help).alignment(Alignment::Center);
+    frame.render_widget(help_paragraph, chunks[1]);
+}
+
+/// Lines listing `issues`, each indented by `indent`
+pub(super) fn issue_lines<'a>(
+    issues: &'a [ValidationIssue],
+    indent: &'static str,
+) -> Vec<Line<'a>> {
     let mut lines: Vec<Line> = Vec::new();
 
-    for issue in &report.issues {
+    for issue in issues {
         // Severity indicator
         let (severity_style, severity_icon) = match issue.severity {
             IssueSeverity::Error => (
@@ -611,6 +501,7 @@ pub fn draw_validation_result(
 
         // Issue header: [E001] Error message
         lines.push(Line::from(vec![
+            Span::raw(indent),
             Span::styled(format!("[{}] ", issue.code), severity_style),
             Span::styled(severity_icon, severity_style),
             Span::raw(" "),
@@ -620,6 +511,7 @@ pub fn draw_validation_result(
         // Location if available
         if let Some(location) = &issue.location {
             lines.push(Line::from(vec![
+                Span::raw(indent),
                 Span::raw("       "),
                 Span::styled("at: ", Style::default().fg(Color::DarkGray)),
                 Span::styled(location, Style::default().fg(Color::Gray)),
@@ -629,6 +521,7 @@ pub fn draw_validation_result(
         // Suggestion if available
         if let Some(suggestion) = &issue.suggestion {
             lines.push(Line::from(vec![
+                Span::raw(indent),
                 Span::raw("       "),
                 Span::styled("fix: ", Style::default().fg(Color::Green)),
                 Span::styled(suggestion, Style::default().fg(Color::Gray)),
@@ -639,51 +532,7 @@ pub fn draw_validation_result(
         lines.push(Line::from(""));
     }
 
-    let total_lines = lines.len() as u16;
-
-    let issues_paragraph = Paragraph::new(lines)
-        .style(Style::default().fg(Color::White))
-        .scroll((scroll_offset, 0))
-        .wrap(Wrap { trim: false });
-    frame.render_widget(issues_paragraph, chunks[0]);
-
-    // Scrollbar if needed
-    if total_lines > chunks[0].height {
-        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
-            .begin_symbol(Some("↑"))
-            .end_symbol(Some("↓"));
-        let mut scrollbar_state =
-            ScrollbarState::new(total_lines as usize).position(scroll_offset as usize);
-        frame.render_stateful_widget(
-            scrollbar,
-            chunks[0].inner(ratatui::layout::Margin {
-                vertical: 1,
-                horizontal: 0,
-            }),
-            &mut scrollbar_state,
-        );
-    }
-
-    // Help text - different based on action
-    let help = match action {
-        ValidationAction::ProceedWithImport { .. } => Line::from(vec![
-            Span::styled("[Enter]", Style::default().fg(Color::Green).bold()),
-            Span::raw(" Proceed anyway  "),
-            Span::styled("[↑/↓]", Style::default().fg(Color::Cyan).bold()),
-            Span::raw(" Scroll  "),
-            Span::styled("[Esc]", Style::default().fg(Color::Red).bold()),
-            Span::raw(" Cancel"),
-        ]),
-        ValidationAction::EditorInfo => Line::from(vec![
-            Span::styled("[↑/↓]", Style::default().fg(Color::Cyan).bold()),
-            Span::raw(" Scroll  "),
-            Span::styled("[Esc]", Style::default().fg(Color::Red).bold()),
-            Span::raw(" Close"),
-        ]),
-    };
-
-    let help_paragraph = Paragraph::new(help).alignment(Alignment::Center);
-    frame.render_widget(help_paragraph, chunks[1]);
+    lines
 }
 
 /// The in-app error log (issue #624).
@@ -752,3 +601,200 @@ pub fn draw_errors(frame: &mut Frame, errors: &VecDeque<ErrorEntry>, scroll_offs
         chunks[1],
     );
 }
+
+/// Draw the diff of a save or apply against the server's copy, for confirmation
+pub fn draw_diff_preview(
+    frame: &mut Frame,
+    app: &App,
+    title: &str,
+    warning: Option<&str>,
+    diff: &[DiffLine],
+) {
+    let area = super::centered_rect(85, 85, frame.area());
+    frame.render_widget(Clear, area);
+
+    let added = diff
+        .iter()
+        .filter(|line| matches!(line, DiffLine::Added(_)))
+        .count();
+    let removed = diff
+        .iter()
+        .filter(|line| matches!(line, DiffLine::Removed(_)))
+        .count();
+    let block = Block::default()
+        .title(title.to_string())
+        .title_bottom(Line::from(vec![
+            Span::styled(
+                format!(" +{added} "),
+                Style::default().fg(app.theme.success),
+            ),
+            Span::styled(format!("-{removed} "), Style::default().fg(app.theme.error)),
+        ]))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(if warning.is_some() { 2 } else { 0 }),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    if let Some(warning) = warning {
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(" ⚠ ", Style::default().fg(app.theme.warning)),
+                Span::styled(
+                    warning,
+                    Style::default()
+                        .fg(app.theme.warning)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ])),
+            chunks[0],
+        );
+    }
+
+    let lines: Vec<Line> = diff
+        .iter()
+        .map(|line| match line {
+            DiffLine::Hunk(header) => Line::from(Span::styled(
+                header.as_str(),
+                Style::default().fg(Color::Cyan),
+            )),
+            DiffLine::Context(text) => Line::from(Span::styled(
+                format!(" {text}"),
+                Style::default().fg(app.theme.muted),
+            )),
+            DiffLine::Removed(text) => Line::from(Span::styled(
+                format!("-{text}"),
+                Style::default().fg(app.theme.error),
+            )),
+            DiffLine::Added(text) => Line::from(Span::styled(
+                format!("+{text}"),
+                Style::default().fg(app.theme.success),
+            )),
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(lines).scroll((app.diff_scroll, 0)),
+        chunks[1],
+    );
+
+    let help = Line::from(vec![
+        Span::styled("[Enter]", Style::default().fg(Color::Green).bold()),
+        Span::raw(" Confirm  "),
+        Span::styled("[↑/↓]", Style::default().fg(Color::Gray)),
+        Span::raw(" Scroll  "),
+        Span::styled("[Esc]", Style::default().fg(Color::Red).bold()),
+        Span::raw(" Cancel"),
+    ]);
+    frame.render_widget(Paragraph::new(help).alignment(Alignment::Center), chunks[2]);
+}
+
+/// Draw the server switcher: each configured server, then every server at once
+pub fn draw_server_switcher(frame: &mut Frame, app: &App, selected: usize) {
+    let area = super::centered_rect(60, 50, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Servers ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.focus))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let name_width = app
+        .servers
+        .iter()
+        .map(|s| s.profile.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(11);
+    let mut lines: Vec<Line> = app
+        .servers
+        .iter()
+        .enumerate()
+        .map(|(i, server)| {
+            let (dot, color) = match server.connected {
+                Some(true) => ("●", app.theme.success),
+                Some(false) => ("○", app.theme.error),
+                None => ("○", app.theme.muted),
+            };
+            let key = if i < 9 {
+                format!("{}", i + 1)
+            } else {
+                " ".to_string()
+            };
+            Line::from(vec![
+                Span::styled(format!(" {key} "), Style::default().fg(app.theme.muted)),
+                Span::styled(format!("{dot} "), Style::default().fg(color)),
+                Span::styled(
+                    format!("{:<name_width$}  ", server.profile.name),
+                    Style::default().fg(app.theme.fg).bold(),
+                ),
+                Span::styled(
+                    server.profile.url.clone(),
+                    Style::default().fg(app.theme.muted),
+                ),
+            ])
+        })
+        .collect();
+    lines.push(Line::from(vec![
+        Span::styled(" a   ", Style::default().fg(app.theme.muted)),
+        Span::styled(
+            format!("{:<name_width$}  ", "All servers"),
+            Style::default().fg(app.theme.fg).bold(),
+        ),
+        Span::styled(
+            "every server's imposters in one list",
+            Style::default().fg(app.theme.muted),
+        ),
+    ]));
+    let current = app.active_server.unwrap_or(app.servers.len());
+    for (i, line) in lines.iter_mut().enumerate() {
+        if i == current {
+            line.spans.push(Span::styled(
+                "  (current)",
+                Style::default().fg(app.theme.focus),
+            ));
+        }
+        if i == selected {
+            *line = line.clone().style(
+                Style::default()
+                    .bg(app.theme.highlight_bg)
+                    .fg(app.theme.highlight_fg),
+            );
+        }
+    }
+    if app.servers.len() < 2 {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            " Add servers to ~/.config/rift-tui/servers.toml to switch between them",
+            Style::default().fg(app.theme.muted),
+        )));
+    }
+
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            " ↑/↓ select · Enter switch · 1-9/a jump · Esc close ",
+            Style::default().fg(Color::DarkGray),
+        ))
+        .alignment(Alignment::Center),
+        chunks[1],
+    );
+}
@@ -0,0 +1,275 @@
+//! The stub editor: JSON highlighting, bracket matching, folds and lint markers
+//!
+//! ratatui-textarea holds the text, cursor, selection and history, but its widget styles a line
+//! as a whole, so the editor is drawn here from the textarea's state instead.
+
+use crate::app::{App, LineIssue};
+use crate::json_syntax::{TokenKind, brackets_pair, tokenize};
+use crate::validation::IssueSeverity;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+};
+use ratatui_textarea::DataCursor;
+
+/// Draw the stub editor with its gutter of line numbers, lint markers and fold markers
+pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(editor) = &app.stub_editor else {
+        return;
+    };
+    let block = editor
+        .editor
+        .block()
+        .cloned()
+        .unwrap_or_else(Block::default);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.width == 0 || inner.height == 0 {
+        return;
+    }
+
+    let lines = editor.editor.lines();
+    let DataCursor(cursor_row, cursor_col) = editor.editor.cursor();
+    let number_width = lines.len().to_string().len();
+    // Lint marker, line number, fold marker and a space
+    let gutter_width = number_width as u16 + 3;
+    let top = editor.viewport_top(inner.height) as usize;
+    let left = editor.viewport_left(inner.width.saturating_sub(gutter_width)) as usize;
+    let text_width = inner.width.saturating_sub(gutter_width) as usize;
+
+    let brackets = editor.bracket_pair().map(|(at, matched)| {
+        let chars = |(row, col): (usize, usize)| lines[row].chars().nth(col);
+        let paired = matched.is_some_and(|m| {
+            let (open, close) = if m < at { (m, at) } else { (at, m) };
+            chars(open)
+                .zip(chars(close))
+                .is_some_and(|(o, c)| brackets_pair(o, c))
+        });
+        let style = if paired {
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+                .fg(app.theme.error)
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD)
+        };
+        ([Some(at), matched], style)
+    });
+    let selection = editor.editor.selection_range();
+    let selected = Style::default()
+        .bg(app.theme.highlight_bg)
+        .fg(app.theme.highlight_fg);
+
+    let mut rendered = Vec::new();
+    for row in editor
+        .visible_rows()
+        .into_iter()
+        .skip(top)
+        .take(inner.height as usize)
+    {
+        let mut cells = styled_chars(&lines[row], app);
+        if let Some((start, end)) = selection {
+            for (col, cell) in cells.iter_mut().enumerate() {
+                if start <= (row, col) && (row, col) < end {
+                    cell.1 = selected;
+                }
+            }
+        }
+        if let Some((positions, style)) = &brackets {
+            for (r, c) in positions.iter().flatten() {
+                if *r == row
+                    && let Some(cell) = cells.get_mut(*c)
+                {
+                    cell.1 = cell.1.patch(*style);
+                }
+            }
+        }
+        if row == cursor_row {
+            if cursor_col == cells.len() {
+                cells.push((' ', Style::default()));
+            }
+            cells[cursor_col].1 = cells[cursor_col].1.patch(editor.editor.cursor_style());
+        }
+        let folded = editor.folded_end(row);
+        if let Some(end) = folded {
+            let hidden = end - row - 1;
+            let plural = if hidden == 1 { "" } else { "s" };
+            let summary = format!(" ⋯ {}  {hidden} line{plural}", lines[end].trim());
+            let style = Style::default().fg(app.theme.muted).bg(Color::DarkGray);
+            cells.extend(summary.chars().map(|c| (c, style)));
+        }
+
+        let mut spans = gutter(editor, row, number_width, folded, app);
+        spans.extend(spans_from_cells(
+            cells.into_iter().skip(left).take(text_width),
+        ));
+        rendered.push(Line::from(spans));
+    }
+    frame.render_widget(Paragraph::new(rendered), inner);
+}
+
+/// The gutter for one line: the most severe lint issue on it (or in the block folded into it),
+/// the line number and a fold marker
+fn gutter(
+    editor: &crate::app::StubEditor,
+    row: usize,
+    number_width: usize,
+    folded: Option<usize>,
+    app: &App,
+) -> Vec<Span<'static>> {
+    let last = folded.unwrap_or(row);
+    let issue = editor
+        .line_issues
+        .iter()
+        .filter(|issue| (row..=last).contains(&issue.line))
+        .min_by_key(|issue| issue.severity != IssueSeverity::Error);
+    let marker = match issue {
+        Some(LineIssue { severity, .. }) => {
+            Span::styled("●", Style::default().fg(severity_color(*severity, app)))
+        }
+        None => Span::raw(" "),
+    };
+    let fold = if folded.is_some() {
+        Span::styled("▸", Style::default().fg(app.theme.focus))
+    } else if crate::json_syntax::block_end(editor.editor.lines(), row).is_some() {
+        Span::styled("▾", Style::default().fg(app.theme.muted))
+    } else {
+        Span::raw(" ")
+    };
+    vec![
+        marker,
+        Span::styled(
+            format!("{:>number_width$}", row + 1),
+            Style::default().fg(Color::DarkGray),
+        ),
+        fold,
+        Span::raw(" "),
+    ]
+}
+
+pub(super) fn severity_color(severity: IssueSeverity, app: &App) -> Color {
+    match severity {
+        IssueSeverity::Error => app.theme.error,
+        IssueSeverity::Warning => app.theme.warning,
+        IssueSeverity::Info => Color::Cyan,
+    }
+}
+
+/// Each character of a JSON line with the style of the token it belongs to
+pub(super) fn styled_chars(line: &str, app: &App) -> Vec<(char, Style)> {
+    let mut cells: Vec<(char, Style)> = line
+        .chars()
+        .map(|c| (c, Style::default().fg(app.theme.fg)))
+        .collect();
+    for token in tokenize(line) {
+        let style = token_style(token.kind, app);
+        for cell in &mut cells[token.start..token.end] {
+            cell.1 = style;
+        }
+    }
+    cells
+}
+
+pub(super) fn token_style(kind: TokenKind, app: &App) -> Style {
+    match kind {
+        TokenKind::Key => Style::default().fg(Color::Cyan),
+        TokenKind::String => Style::default().fg(Color::Green),
+        TokenKind::Number => Style::default().fg(Color::Magenta),
+        TokenKind::Bool => Style::default().fg(Color::Yellow),
+        TokenKind::Null => Style::default()
+            .fg(Color::Red)
+            .add_modifier(Modifier::ITALIC),
+        TokenKind::Punct => Style::default().fg(app.theme.muted),
+        TokenKind::Other => Style::default().fg(app.theme.fg),
+    }
+}
+
+/// Join runs of equally styled characters into spans
+pub(super) fn spans_from_cells(
+    cells: impl IntoIterator<Item = (char, Style)>,
+) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut text = String::new();
+    let mut current = None;
+    for (c, style) in cells {
+        if let Some(previous) = current
+            && previous != style
+        {
+            spans.push(Span::styled(std::mem::take(&mut text), previous));
+        }
+        current = Some(style);
+        text.push(c);
+    }
+    if let Some(style) = current {
+        spans.push(Span::styled(text, style));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::StubEditor;
+    use crate::app::tests::make_test_app;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn render(app: &App, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| draw(frame, app, frame.area()))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn folded_blocks_collapse_to_one_line_with_markers() {
+        let mut app = make_test_app();
+        let mut editor =
+            StubEditor::new("{\n  \"responses\": [\n    {\"is\": {\"statusCode\": 999}}\n  ]\n}");
+        editor
+            .editor
+            .move_cursor(ratatui_textarea::CursorMove::Jump(1, 0));
+        editor.toggle_fold();
+        app.stub_editor = Some(editor);
+
+        let screen = render(&app, 50, 6);
+        assert!(screen[1].starts_with("│ 1▾ {"), "{screen:?}");
+        // The folded block's lint error is marked on the line that stands for it
+        assert!(
+            screen[2].starts_with("│●2▸   \"responses\": [ ⋯ ]  1 line"),
+            "{screen:?}"
+        );
+        assert!(screen[3].starts_with("│ 5  }"), "{screen:?}");
+
+        app.stub_editor.as_mut().unwrap().toggle_fold();
+        let screen = render(&app, 50, 7);
+        // The lint error is marked on the line it is about
+        assert!(screen[3].starts_with("│●3  "), "{screen:?}");
+    }
+
+    #[test]
+    fn highlighting_styles_tokens() {
+        let app = make_test_app();
+        let cells = styled_chars(r#""a": true"#, &app);
+        assert_eq!(cells[0].1.fg, Some(Color::Cyan));
+        assert_eq!(cells[3].1.fg, Some(app.theme.muted));
+        assert_eq!(cells[5].1.fg, Some(Color::Yellow));
+        let spans = spans_from_cells(cells);
+        assert_eq!(
+            spans.iter().map(|s| s.content.as_ref()).collect::<Vec<_>>(),
+            ["\"a\"", ":", " ", "true"]
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! Global search results — every imposter and stub the query was found in, to jump to
+
+use super::truncate;
+use crate::app::{App, HitField};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+/// Draw the global search results over the current view
+pub fn draw(frame: &mut Frame, app: &App) {
+    let Some(search) = &app.global_search else {
+        return;
+    };
+    let area = super::centered_rect(80, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(
+            " \"{}\" in {} imposters ({}) ",
+            search.query,
+            search.searched,
+            search.hits.len()
+        ))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.focus))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    if search.hits.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                " No imposter or stub contains it",
+                Style::default().fg(app.theme.muted),
+            )),
+            chunks[0],
+        );
+    } else {
+        let selected = Some(search.selected);
+        let visible = super::visible_rows(search.hits.len(), selected, chunks[0].height as usize);
+        let items: Vec<ListItem> = search.hits[visible.clone()]
+            .iter()
+            .map(|hit| {
+                let mut spans = vec![Span::styled(
+                    format!(" :{:<5} ", hit.port),
+                    Style::default().fg(app.theme.fg).bold(),
+                )];
+                if let Some(server) = hit.server.and_then(|index| app.servers.get(index)) {
+                    spans.push(Span::styled(
+                        format!("{} ", truncate(&server.profile.name, 12)),
+                        Style::default().fg(app.theme.focus),
+                    ));
+                }
+                spans.push(Span::styled(
+                    format!(
+                        "{:<16} ",
+                        truncate(hit.imposter_name.as_deref().unwrap_or("(unnamed)"), 16)
+                    ),
+                    Style::default().fg(app.theme.fg),
+                ));
+                if let Some(stub) = hit.stub {
+                    spans.push(Span::styled(
+                        format!("#{:<3} {:<24} ", stub + 1, truncate(&hit.stub_label, 24)),
+                        Style::default().fg(app.theme.fg),
+                    ));
+                }
+                let field_color = match hit.field {
+                    HitField::Imposter | HitField::Scenario => app.theme.focus,
+                    HitField::Predicates => app.theme.warning,
+                    HitField::Responses => app.theme.success,
+                };
+                spans.push(Span::styled(
+                    format!("{:<9} ", hit.field.label()),
+                    Style::default().fg(field_color),
+                ));
+                spans.push(Span::styled(
+                    hit.snippet.clone(),
+                    Style::default().fg(app.theme.muted),
+                ));
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+        let mut state = ListState::default().with_selected(Some(search.selected - visible.start));
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(app.theme.highlight_bg)
+                .fg(app.theme.highlight_fg),
+        );
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+    }
+
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            " ↑/↓ select · Enter open · Esc close ",
+            Style::default().fg(Color::DarkGray),
+        ))
+        .alignment(Alignment::Center),
+        chunks[1],
+    );
+}
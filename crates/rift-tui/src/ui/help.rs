@@ -1,5 +1,6 @@
 //! Help overlay with scroll support
 
+use crate::keys::{Context, KeyMap};
 use ratatui::{
     Frame,
     layout::Alignment,
@@ -9,13 +10,13 @@ use ratatui::{
 };
 
 /// Draw the help overlay with scrolling
-pub fn draw_overlay(frame: &mut Frame, scroll: u16) -> u16 {
+pub fn draw_overlay(frame: &mut Frame, scroll: u16, keys: &KeyMap) -> u16 {
     let area = super::centered_rect(75, 85, frame.area());
 
     // Clear the background
     frame.render_widget(Clear, area);
 
-    let help_text = build_help_text();
+    let help_text = build_help_text(keys);
     let total_lines = help_text.len() as u16;
     let visible_height = area.height.saturating_sub(2); // Account for borders
     let max_scroll = total_lines.saturating_sub(visible_height);
@@ -52,8 +53,13 @@ pub fn draw_overlay(frame: &mut Frame, scroll: u16) -> u16 {
     max_scroll
 }
 
-fn build_help_text() -> Vec<Line<'static>> {
-    vec![
+/// How many lines the help text has
+pub fn line_count(keys: &KeyMap) -> usize {
+    build_help_text(keys).len()
+}
+
+fn build_help_text(keys: &KeyMap) -> Vec<Line<'static>> {
+    let mut lines = vec![
         Line::from(""),
         section_header("NAVIGATION"),
         Line::from(""),
@@ -65,20 +71,48 @@ fn build_help_text() -> Vec<Line<'static>> {
         help_line("Tab", "Switch focus between panes"),
         help_line("r", "Refresh data"),
         help_line("/", "Search / filter items"),
+        help_line(":", "Command palette (every action, themes, go to a port)"),
         help_line(
             "T (Shift+t)",
-            "Cycle theme (Default/Dark/Light/Nord/Dracula)",
+            "Cycle theme (built-in and ~/.config/rift-tui/themes.toml)",
         ),
         help_line("?", "Toggle this help"),
         help_line("L (Shift+l)", "Show recent errors and warnings"),
+        help_line(
+            "P (Shift+p)",
+            "Save a screenshot of the view (ANSI, or .txt)",
+        ),
+        help_line("S (Shift+s)", "Send a test request to the imposter"),
+        help_line("s", "Switch server / list all servers' imposters"),
+        help_line("u", "Undo the last delete or stub move"),
+        help_line("U (Shift+u)", "Redo the last undone change"),
         Line::from(""),
         section_header("IMPOSTER LIST (Main View)"),
         Line::from(""),
-        help_line("n", "Create new imposter"),
-        help_line("p", "Create proxy imposter (for recording)"),
+        help_line("n", "Create new imposter (form; Ctrl+E for JSON)"),
+        help_line("p", "Create proxy imposter (step-by-step wizard)"),
         help_line("d", "Delete selected imposter"),
         help_line("t", "Toggle enable/disable"),
+        help_line("Space", "Mark / unmark imposter"),
+        help_line("V (Shift+v)", "Mark a range (press at both ends)"),
+        help_line(
+            "d / t / e",
+            "With marks: delete, enable/disable or export them",
+        ),
         help_line("m", "View metrics dashboard"),
+        help_line("l", "Open live request log of selected imposter"),
+        help_line("g", "Open the server log"),
+        help_line(
+            "o / O (Shift+o)",
+            "Sort by port, name, stubs, requests or activity / reverse",
+        ),
+        help_line("f", "Filter by protocol"),
+        help_line("F (Shift+f)", "Filter by enabled / disabled"),
+        help_line(
+            "R (Shift+r)",
+            "Run a scenario file against the marked or selected imposters",
+        ),
+        help_line("y", "Copy a command creating the imposter"),
         Line::from(""),
         section_header("IMPORT/EXPORT (Main View)"),
         Line::from(""),
@@ -92,24 +126,65 @@ fn build_help_text() -> Vec<Line<'static>> {
         help_line("a", "Add new stub"),
         help_line("e", "Edit selected stub"),
         help_line("d", "Delete selected stub"),
-        help_line("y", "Copy stub as curl command"),
+        help_line("K / [", "Move stub up (matched earlier)"),
+        help_line("J / ]", "Move stub down (matched later)"),
+        help_line("Space", "Mark / unmark stub"),
+        help_line("V (Shift+v)", "Mark a range (press at both ends)"),
+        help_line("d / x", "With marks: delete or export them"),
+        help_line("y", "Copy as curl, httpie, mb or rift command"),
         help_line("c", "Clear recorded requests"),
         help_line("C (Shift+c)", "Clear proxy recordings"),
         help_line("x", "Export stubs (remove proxy responses)"),
         help_line("X (Shift+x)", "Export full config"),
-        help_line("A (Shift+a)", "Apply recorded stubs (stop proxying)"),
+        help_line(
+            "A (Shift+a)",
+            "Apply recorded stubs (stop proxying, diff preview first)",
+        ),
         help_line("t", "Toggle imposter enable/disable"),
+        help_line("z", "Count stub hits from zero again"),
+        help_line("l", "Open live request log"),
+        Line::from(""),
+        section_header("REQUEST LOG"),
+        Line::from(""),
+        help_line("p / Space", "Pause / resume the tail"),
+        help_line("/", "Filter by path"),
+        help_line("j/k or ↑/↓", "Scroll (stops following)"),
+        help_line("G / End", "Follow newest requests"),
+        help_line("c", "Clear the pane"),
+        help_line("Enter", "Inspect headers and bodies"),
+        help_line("s (inspector)", "Replay the request"),
+        help_line("P (inspector)", "Replay to another port"),
+        help_line("e (inspector)", "Edit and send in the composer"),
+        help_line("y (inspector)", "Copy as curl or httpie command"),
+        Line::from(""),
+        section_header("METRICS"),
+        Line::from(""),
+        help_line("j/k or ↑/↓", "Select imposter"),
+        help_line("w", "Cycle window (1m/5m/15m/all)"),
+        help_line("x", "Export the figures to a .csv or .json file"),
+        Line::from(""),
+        section_header("SERVER LOG"),
+        Line::from(""),
+        help_line("f", "Cycle the minimum level (trace → error)"),
+        help_line("/", "Search messages"),
+        help_line("p / Space", "Pause / resume the tail"),
+        help_line("j/k or ↑/↓", "Scroll (stops following)"),
+        help_line("G / End", "Follow newest lines"),
+        help_line("c", "Clear the pane"),
         Line::from(""),
         section_header("STUB DETAIL VIEW"),
         Line::from(""),
         help_line("e", "Edit stub"),
         help_line("d", "Delete stub"),
-        help_line("y", "Copy stub as curl command"),
+        help_line("y", "Copy as curl, httpie, mb or rift command"),
+        help_line("j/k PgUp/PgDn", "Scroll the stub and its response bodies"),
         Line::from(""),
         section_header("EDITOR"),
         Line::from(""),
-        help_line("Ctrl+S", "Save changes"),
+        help_line("Ctrl+S", "Save changes (diff preview first)"),
         help_line("Ctrl+F", "Format JSON"),
+        help_line("Ctrl+L", "Show all lint issues (● in the gutter)"),
+        help_line("Ctrl+O", "Fold/unfold the block at the cursor"),
         help_line("Ctrl+A", "Select all"),
         help_line("Ctrl+C", "Copy selection"),
         help_line("Ctrl+X", "Cut selection"),
@@ -118,11 +193,31 @@ fn build_help_text() -> Vec<Line<'static>> {
         help_line("Ctrl+U", "Clear line before cursor"),
         help_line("Shift+Arrows", "Extend selection"),
         help_line("Ctrl+←/→", "Move by word"),
+        help_line("Ctrl+T", "Send a test request (composer)"),
+        help_line("Ctrl+E", "Edit the script at the cursor"),
         help_line("Esc", "Cancel editing"),
         Line::from(""),
+        section_header("REQUEST COMPOSER"),
+        Line::from(""),
+        help_line("Tab / Shift+Tab", "Next / previous field"),
+        help_line("←/→", "Change method"),
+        help_line("Ctrl+S or Enter", "Send (Enter on method or path)"),
+        help_line("Ctrl+D", "Toggle X-Rift-Debug match report"),
+        help_line("PgUp/PgDn", "Scroll the response"),
+        help_line("Esc", "Close (the form is kept)"),
+        Line::from(""),
+        section_header("SCRIPT EDITOR"),
+        Line::from(""),
+        help_line("Ctrl+S", "Check and put back in the stub"),
+        help_line("Ctrl+T", "Run against the sample request"),
+        help_line("Tab", "Switch code / sample request"),
+        help_line("PgUp/PgDn", "Scroll the results"),
+        help_line("Esc", "Discard changes"),
+        Line::from(""),
         section_header("SEARCH MODE"),
         Line::from(""),
         help_line("Enter", "Confirm search and select first match"),
+        help_line("Tab", "Search every imposter's stubs, listing hits"),
         help_line("Esc", "Cancel search"),
         help_line("Ctrl+U", "Clear search query"),
         help_line("Ctrl+V", "Paste into search"),
@@ -135,12 +230,58 @@ fn build_help_text() -> Vec<Line<'static>> {
         help_line("j/k or ↑/↓", "Scroll content"),
         help_line("Esc", "Close"),
         Line::from(""),
+    ];
+    lines.extend(effective_bindings(keys));
+    lines.extend([
         Line::from(Span::styled(
             "  [↑/↓] scroll  [PgUp/PgDn] page  [Esc/?] close",
             Style::default().add_modifier(Modifier::ITALIC),
         )),
         Line::from(""),
-    ]
+    ]);
+    lines
+}
+
+/// The keys each action of the main views is bound to, with those set in keys.toml marked
+fn effective_bindings(keys: &KeyMap) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        section_header("EFFECTIVE KEY BINDINGS"),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  Rebind these in ~/.config/rift-tui/keys.toml as [section] action = \"key\"; * is rebound",
+            Style::default().add_modifier(Modifier::ITALIC),
+        )),
+    ];
+    let mut context = None::<Context>;
+    for (action, bound, customised) in keys.effective() {
+        if context != Some(action.context) {
+            context = Some(action.context);
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("  [{}]", action.context.name()),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+        }
+        let bound = if bound.is_empty() {
+            "-".to_string()
+        } else {
+            bound
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {bound:<16}"),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(
+                "{}{:<16}{}",
+                if customised { "*" } else { " " },
+                action.name,
+                action.description
+            )),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines
 }
 
 fn section_header(title: &'static str) -> Line<'static> {
@@ -7,7 +7,7 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
 };
 
 /// Draw the imposter detail view
@@ -28,7 +28,7 @@ pub fn draw(frame: &mut Frame, app: &App, port: u16, area: Rect) {
         .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
         .split(chunks[1]);
 
-    draw_stubs_panel(frame, app, content_chunks[0]);
+    draw_stubs_panel(frame, app, port, content_chunks[0]);
     draw_right_panel(frame, app, content_chunks[1]);
 }
 
@@ -160,68 +160,58 @@ fn build_stub_summary(stubs: &[crate::api::Stub], max_width: usize) -> String {
 }
 
 /// Draw the stubs panel
-fn draw_stubs_panel(frame: &mut Frame, app: &App, area: Rect) {
-    let empty_stubs = vec![];
-    let stubs = app
-        .current_imposter
-        .as_ref()
-        .map(|i| &i.stubs)
-        .unwrap_or(&empty_stubs);
+fn draw_stubs_panel(frame: &mut Frame, app: &App, port: u16, area: Rect) {
+    let rows = app.stub_rows();
 
     let is_focused = app.focus == FocusArea::Left;
-    let has_search = !app.search_query.is_empty();
+    let query = app.search_query.to_lowercase();
+    let has_search = !query.is_empty();
 
-    let items: Vec<ListItem> = stubs
+    // Only the rows on screen are built; the rest of a long list is never formatted
+    let selected = app.stub_list_state.selected();
+    let visible = super::visible_rows(rows.len(), selected, area.height.saturating_sub(2) as usize);
+    let items: Vec<ListItem> = rows[visible.clone()]
         .iter()
-        .enumerate()
-        .map(|(i, stub)| {
-            let is_selected = app.stub_list_state.selected() == Some(i);
-            let matches_search = app.stub_matches_search(i);
+        .zip(visible.clone())
+        .map(|(row, i)| {
+            let is_selected = selected == Some(i);
+            let is_marked = app.is_stub_marked(i);
 
             // Dim non-matching items when searching
-            let dim = has_search && !matches_search;
-
-            // Get stub name - prefer scenario_name, then predicates summary
-            let stub_name = if let Some(scenario) = &stub.scenario_name {
-                scenario.clone()
-            } else if stub.predicates.is_empty() {
-                "(default)".to_string()
-            } else {
-                summarize_predicates(&stub.predicates)
-            };
-
-            // Get response type and color
-            let (response_type, is_proxy) = if stub.responses.is_empty() {
-                ("no response", false)
-            } else {
-                get_response_type_with_info(&stub.responses[0])
-            };
+            let dim = has_search && !row.matches(&query);
 
             // Use different colors for different response types
             let response_color = if dim {
                 app.theme.muted
-            } else if is_proxy {
+            } else if row.is_proxy {
                 ratatui::style::Color::Magenta // Magenta for proxy stubs
             } else {
                 app.theme.success
             };
 
+            let hits = app.stub_hit_count(port, i);
+            let hits_text = hits.map_or_else(String::new, |hits| format!(" {hits}h"));
+            // A stub that never matched is a candidate for pruning
+            let hits_color = if hits == Some(0) && !dim {
+                app.theme.warning
+            } else {
+                app.theme.muted
+            };
+
             // Truncate stub name to fit panel width
-            let max_name_len = area.width.saturating_sub(15) as usize;
-            let display_name = truncate(&stub_name, max_name_len);
+            let max_name_len = area.width.saturating_sub(15 + hits_text.len() as u16) as usize;
+            let display_name = truncate(&row.label, max_name_len);
 
             let fg_color = if dim { app.theme.muted } else { app.theme.fg };
 
-            let pred_count = stub.predicates.len();
-            let resp_count = stub.responses.len();
-            let counts = format!(" {pred_count}p {resp_count}r");
+            let counts = format!(" {}p {}r", row.predicates, row.responses);
 
             let line = Line::from(vec![
                 Span::styled(
                     if is_selected && is_focused {
-                        " ▶ "
+                        " ▶"
                     } else {
-                        "   "
+                        "  "
                     },
                     Style::default().fg(if dim {
                         app.theme.muted
@@ -229,16 +219,21 @@ fn draw_stubs_panel(frame: &mut Frame, app: &App, area: Rect) {
                         app.theme.highlight_bg
                     }),
                 ),
+                Span::styled(
+                    if is_marked { "✓" } else { " " },
+                    Style::default().fg(app.theme.focus),
+                ),
                 Span::styled(
                     format!("#{:<2}", i + 1),
                     Style::default().fg(app.theme.muted),
                 ),
                 Span::styled(format!(" {display_name} "), Style::default().fg(fg_color)),
                 Span::styled(
-                    format!("[{response_type}]"),
+                    format!("[{}]", row.response_type),
                     Style::default().fg(response_color),
                 ),
                 Span::styled(counts, Style::default().fg(app.theme.muted)),
+                Span::styled(hits_text, Style::default().fg(hits_color)),
             ]);
 
             ListItem::new(line)
@@ -254,7 +249,7 @@ fn draw_stubs_panel(frame: &mut Frame, app: &App, area: Rect) {
     let list = List::new(items)
         .block(
             Block::default()
-                .title(format!(" Stubs ({}) ", stubs.len()))
+                .title(stubs_title(app, port, rows.len()))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(border_color)),
         )
@@ -268,7 +263,24 @@ fn draw_stubs_panel(frame: &mut Frame, app: &App, area: Rect) {
                 .fg(app.theme.highlight_fg),
         );
 
-    frame.render_stateful_widget(list, area, &mut app.stub_list_state.clone());
+    let mut state =
+        ListState::default().with_selected(selected.map(|i| i.saturating_sub(visible.start)));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// `Stubs (n)`, with how many are marked and how many never matched
+fn stubs_title(app: &App, port: u16, stubs: usize) -> String {
+    let mut counts = vec![stubs.to_string()];
+    if !app.marked_stubs.is_empty() {
+        counts.push(format!("{} marked", app.marked_stubs.len()));
+    }
+    let never_hit = (0..stubs)
+        .filter(|&i| app.stub_hit_count(port, i) == Some(0))
+        .count();
+    if never_hit > 0 {
+        counts.push(format!("{never_hit} never hit"));
+    }
+    format!(" Stubs ({}) ", counts.join(", "))
 }
 
 /// Draw the right panel with stub preview and recorded requests
@@ -491,74 +503,8 @@ fn draw_requests_panel(frame: &mut Frame, app: &App, area: Rect) {
     }
 }
 
-/// Summarize predicates for display
-fn summarize_predicates(predicates: &[serde_json::Value]) -> String {
-    if predicates.is_empty() {
-        return "(default)".to_string();
-    }
-
-    let pred = &predicates[0];
-    if let Some(obj) = pred.as_object() {
-        for (key, value) in obj {
-            match key.as_str() {
-                "equals" => {
-                    if let Some(v) = value.as_object() {
-                        let mut parts = Vec::new();
-                        if let Some(m) = v.get("method").and_then(|v| v.as_str()) {
-                            parts.push(m.to_string());
-                        }
-                        if let Some(p) = v.get("path").and_then(|v| v.as_str()) {
-                            parts.push(p.to_string());
-                        }
-                        if !parts.is_empty() {
-                            return parts.join(" ");
-                        }
-                    }
-                }
-                "contains" | "startsWith" | "endsWith" | "matches" => {
-                    return format!("{key} ...");
-                }
-                "and" | "or" => {
-                    if let Some(arr) = value.as_array() {
-                        return format!("{} ({} conditions)", key, arr.len());
-                    }
-                }
-                _ => {}
-            }
-        }
-    }
-
-    "(complex)".to_string()
-}
-
-/// Get the response type with proxy mode info
-fn get_response_type_with_info(response: &serde_json::Value) -> (&str, bool) {
-    if response.get("is").is_some() {
-        ("is", false)
-    } else if let Some(proxy) = response.get("proxy") {
-        // Get proxy mode if available
-        let mode = proxy
-            .get("mode")
-            .and_then(|m| m.as_str())
-            .unwrap_or("proxy");
-        let mode_display = match mode {
-            "proxyOnce" => "proxyOnce",
-            "proxyAlways" => "proxyAlways",
-            "proxyTransparent" => "transparent",
-            _ => "proxy",
-        };
-        (mode_display, true)
-    } else if response.get("inject").is_some() {
-        ("inject", false)
-    } else if response.get("fault").is_some() {
-        ("fault", false)
-    } else {
-        ("unknown", false)
-    }
-}
-
 /// Get color for HTTP method
-fn method_color(method: &str, app: &App) -> ratatui::style::Color {
+pub(super) fn method_color(method: &str, app: &App) -> ratatui::style::Color {
     match method {
         "GET" => app.theme.success,
         "POST" => ratatui::style::Color::Yellow,
@@ -0,0 +1,188 @@
+//! New imposter form overlay — every field at once, each with what is wrong with it underneath
+
+use super::dialogs::draw_input_field;
+use crate::app::{App, FormField, IMPOSTER_PROTOCOLS, ImposterForm};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+/// Draw the new imposter form over the current view
+pub fn draw(frame: &mut Frame, app: &App) {
+    let Some(form) = &app.imposter_form else {
+        return;
+    };
+    let area = super::centered_rect(60, 85, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Create New Imposter ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Port
+            Constraint::Length(1), // Port error
+            Constraint::Length(1), // Protocol
+            Constraint::Length(3), // Name
+            Constraint::Length(1), // recordRequests
+            Constraint::Length(1), // Spacing
+            Constraint::Length(1), // Default response heading
+            Constraint::Length(3), // Default status
+            Constraint::Length(1), // Status error
+            Constraint::Length(3), // Default body
+            Constraint::Length(1), // Body error
+            Constraint::Min(0),
+            Constraint::Length(1), // Help text
+        ])
+        .split(inner);
+
+    let text_field = |frame: &mut Frame, area: Rect, field, label, value: &str, placeholder| {
+        draw_input_field(
+            frame,
+            area,
+            label,
+            value,
+            placeholder,
+            form.focused() == field,
+            None,
+        );
+    };
+    text_field(
+        frame,
+        chunks[0],
+        FormField::Port,
+        "Port (optional)",
+        &form.port,
+        "auto-assign",
+    );
+    draw_error(frame, form, FormField::Port, chunks[1]);
+    frame.render_widget(Paragraph::new(protocol_line(form)), chunks[2]);
+    text_field(
+        frame,
+        chunks[3],
+        FormField::Name,
+        "Name (optional)",
+        &form.name,
+        "unnamed",
+    );
+    frame.render_widget(
+        Paragraph::new(toggle_line(
+            form,
+            FormField::RecordRequests,
+            if form.record_requests { "[x]" } else { "[ ]" },
+            "recordRequests",
+            "keep the requests it serves, for the request log",
+        )),
+        chunks[4],
+    );
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            "  Default response, for requests no stub matches:",
+            Style::default().fg(Color::DarkGray),
+        )),
+        chunks[6],
+    );
+    text_field(
+        frame,
+        chunks[7],
+        FormField::DefaultStatus,
+        "Status code (optional)",
+        &form.default_status,
+        "none",
+    );
+    draw_error(frame, form, FormField::DefaultStatus, chunks[8]);
+    text_field(
+        frame,
+        chunks[9],
+        FormField::DefaultBody,
+        "Body (optional; JSON if it starts with { or [)",
+        &form.default_body,
+        "empty",
+    );
+    draw_error(frame, form, FormField::DefaultBody, chunks[10]);
+
+    frame.render_widget(
+        Paragraph::new(help_line(form)).alignment(Alignment::Center),
+        chunks[12],
+    );
+}
+
+fn draw_error(frame: &mut Frame, form: &ImposterForm, field: FormField, area: Rect) {
+    if let Some(error) = form.error(field) {
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                format!("   ✗ {error}"),
+                Style::default().fg(Color::Red),
+            )),
+            area,
+        );
+    }
+}
+
+fn protocol_line(form: &ImposterForm) -> Line<'static> {
+    let choices = IMPOSTER_PROTOCOLS
+        .iter()
+        .enumerate()
+        .map(|(i, protocol)| {
+            let mark = if i == form.protocol { "●" } else { "○" };
+            format!("{mark} {protocol}")
+        })
+        .collect::<Vec<_>>()
+        .join("  ");
+    toggle_line(form, FormField::Protocol, "", "Protocol", &choices)
+}
+
+fn toggle_line(
+    form: &ImposterForm,
+    field: FormField,
+    mark: &str,
+    label: &str,
+    description: &str,
+) -> Line<'static> {
+    let focused = form.focused() == field;
+    let style = if focused {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::White)
+    };
+    let mut spans = vec![Span::styled(if focused { " ▶ " } else { "   " }, style)];
+    if !mark.is_empty() {
+        spans.push(Span::styled(format!("{mark} "), style));
+    }
+    spans.push(Span::styled(label.to_string(), style));
+    spans.push(Span::styled(
+        format!("  {description}"),
+        Style::default().fg(Color::DarkGray),
+    ));
+    Line::from(spans)
+}
+
+fn help_line(form: &ImposterForm) -> Line<'static> {
+    let key =
+        |key: &'static str, color: Color| Span::styled(key, Style::default().fg(color).bold());
+    let mut spans = vec![key("[Tab]", Color::Cyan), Span::raw(" Next  ")];
+    if !form.focused().is_text() {
+        spans.extend([key("[Space]", Color::Cyan), Span::raw(" Toggle  ")]);
+    }
+    spans.extend([
+        key("[Enter]", Color::Green),
+        Span::raw(" Create  "),
+        key("[Ctrl+E]", Color::Cyan),
+        Span::raw(" Edit JSON  "),
+        key("[Esc]", Color::Red),
+        Span::raw(" Cancel"),
+    ]);
+    Line::from(spans)
+}
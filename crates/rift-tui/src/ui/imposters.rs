@@ -1,32 +1,47 @@
 //! Imposter list view
 
 use super::truncate;
-use crate::app::App;
+use crate::api::ImposterSummary;
+use crate::app::{ACTIVITY_HISTORY, App};
 use ratatui::{
     Frame,
     layout::Rect,
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem},
+    widgets::{Block, Borders, List, ListItem, ListState},
 };
 
 /// Draw the imposter list view
 pub fn draw_list(frame: &mut Frame, app: &App, area: Rect) {
-    let has_search = !app.search_query.is_empty();
-
-    let items: Vec<ListItem> = app
-        .imposters
+    let has_search = !app.search_query.is_empty() || app.imposter_filter.is_active();
+
+    // Only the rows on screen are built; the rest of a long list is never formatted
+    let selected = app.imposter_list_state.selected();
+    let visible = super::visible_rows(
+        app.imposters.len(),
+        selected,
+        area.height.saturating_sub(2) as usize,
+    );
+    let items: Vec<ListItem> = app.imposters[visible.clone()]
         .iter()
-        .enumerate()
-        .map(|(i, imp)| {
-            let is_selected = app.imposter_list_state.selected() == Some(i);
+        .zip(visible.clone())
+        .map(|(imp, i)| {
+            let is_selected = selected == Some(i);
+            let is_marked = app.is_imposter_marked(i);
             let matches_search = app.imposter_matches_search(imp);
 
             // Dim non-matching items when searching
             let dim = has_search && !matches_search;
 
-            // Status dot: red when recording, green when enabled, gray when disabled
-            let status = if imp.enabled { "●" } else { "○" };
+            // Status dot: red when recording, green when enabled, gray when disabled; spelled out
+            // in accessibility mode
+            let status = match (app.accessible, imp.record_requests, imp.enabled) {
+                (true, true, _) => "rec",
+                (true, false, true) => "on ",
+                (true, false, false) => "off",
+                (false, _, true) => "●",
+                (false, _, false) => "○",
+            };
             let status_color = if dim {
                 app.theme.muted
             } else if imp.record_requests {
@@ -42,15 +57,19 @@ pub fn draw_list(frame: &mut Frame, app: &App, area: Rect) {
             let fg_color = if dim { app.theme.muted } else { app.theme.fg };
             let muted_color = app.theme.muted;
 
-            let line = Line::from(vec![
+            let mut spans = vec![
                 Span::styled(
-                    if is_selected { " ▶ " } else { "   " },
+                    if is_selected { " ▶" } else { "  " },
                     Style::default().fg(if dim {
                         app.theme.muted
                     } else {
                         app.theme.highlight_bg
                     }),
                 ),
+                Span::styled(
+                    if is_marked { "✓" } else { " " },
+                    Style::default().fg(app.theme.focus),
+                ),
                 Span::styled(format!("{status} "), Style::default().fg(status_color)),
                 Span::styled(
                     format!(":{:<5}", imp.port),
@@ -61,6 +80,15 @@ pub fn draw_list(frame: &mut Frame, app: &App, area: Rect) {
                     }),
                 ),
                 Span::styled(" │ ", Style::default().fg(app.theme.border)),
+            ];
+            if let Some(server) = app.imposter_server_name(imp) {
+                spans.push(Span::styled(
+                    format!("{:<12}", truncate(server, 12)),
+                    Style::default().fg(if dim { muted_color } else { app.theme.focus }),
+                ));
+                spans.push(Span::styled(" │ ", Style::default().fg(app.theme.border)));
+            }
+            spans.extend([
                 Span::styled(
                     format!("{:<20}", truncate(name, 20)),
                     Style::default().fg(fg_color),
@@ -75,13 +103,33 @@ pub fn draw_list(frame: &mut Frame, app: &App, area: Rect) {
                     format!("{:>8} reqs", super::format_number(imp.number_of_requests)),
                     Style::default().fg(muted_color),
                 ),
+                Span::styled(" │ ", Style::default().fg(app.theme.border)),
             ]);
+            spans.extend(activity_spans(app, imp, dim));
 
-            ListItem::new(line)
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
-    let title = format!(" Imposters ({}) ", app.imposters.len());
+    let marked = if app.marked_imposters.is_empty() {
+        String::new()
+    } else {
+        format!(", {} marked", app.marked_imposters.len())
+    };
+    let mut title = if app.showing_all_servers() {
+        format!(
+            " Imposters ({}{marked}) on {} servers",
+            app.imposters.len(),
+            app.servers.len()
+        )
+    } else {
+        format!(" Imposters ({}{marked})", app.imposters.len())
+    };
+    title.push_str(&format!(" · {}", app.imposter_sort.describe()));
+    if app.imposter_filter.is_active() {
+        title.push_str(&format!(" · {}", app.imposter_filter.describe()));
+    }
+    title.push(' ');
 
     let list = List::new(items)
         .block(
@@ -96,7 +144,9 @@ pub fn draw_list(frame: &mut Frame, app: &App, area: Rect) {
                 .fg(app.theme.highlight_fg),
         );
 
-    frame.render_stateful_widget(list, area, &mut app.imposter_list_state.clone());
+    let mut state =
+        ListState::default().with_selected(selected.map(|i| i.saturating_sub(visible.start)));
+    frame.render_stateful_widget(list, area, &mut state);
 
     // Show empty state message
     if app.imposters.is_empty() {
@@ -124,3 +174,69 @@ pub fn draw_list(frame: &mut Frame, app: &App, area: Rect) {
         frame.render_widget(paragraph, centered);
     }
 }
+
+/// Recent traffic of `imp`: a sparkline of the requests each refresh saw, how many the last one
+/// saw, marked hot when it is a lot, and how long ago it last served one
+fn activity_spans(app: &App, imp: &ImposterSummary, dim: bool) -> Vec<Span<'static>> {
+    let Some(activity) = app.activity(imp) else {
+        return vec![Span::styled("idle", Style::default().fg(app.theme.muted))];
+    };
+    let recent: Vec<u64> = activity.recent.iter().copied().collect();
+    let last = activity.last_window();
+    let color = if dim || last == 0 {
+        app.theme.muted
+    } else if activity.is_hot() {
+        app.theme.warning
+    } else {
+        app.theme.success
+    };
+    let badge = match last {
+        0 => String::new(),
+        n => format!("+{}", super::format_number(n)),
+    };
+    // Glyphs a screen reader cannot read give way to the counts themselves
+    let trend = if app.accessible {
+        let shown = &recent[recent.len().saturating_sub(3)..];
+        let counts: Vec<String> = shown.iter().map(u64::to_string).collect();
+        counts.join(" ")
+    } else {
+        sparkline(&recent)
+    };
+    let mut spans = vec![
+        Span::styled(
+            format!("{trend:<ACTIVITY_HISTORY$} "),
+            Style::default().fg(color),
+        ),
+        Span::styled(format!("{badge:>6} "), Style::default().fg(color)),
+    ];
+    if activity.is_hot() && !dim {
+        spans.push(Span::styled(
+            "hot ",
+            Style::default()
+                .fg(app.theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    spans.push(Span::styled(
+        match activity.at {
+            Some(at) => format!("{} ago", super::format_uptime(at.elapsed())),
+            None => "idle".to_string(),
+        },
+        Style::default().fg(app.theme.muted),
+    ));
+    spans
+}
+
+/// `values` as a row of block characters, each as high as its share of the largest; only zero
+/// gets the lowest
+pub(super) fn sparkline(values: &[u64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|&value| match value {
+            0 => BARS[0],
+            value => BARS[value.saturating_mul(7).div_ceil(max).clamp(1, 7) as usize],
+        })
+        .collect()
+}
@@ -1,12 +1,15 @@
-//! Metrics view with sparklines and bar charts
+//! Metrics view: per-imposter latency, statuses and faults, and the selected imposter's detail
 
-use crate::app::App;
+use super::truncate;
+use crate::app::{App, WindowStats};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Bar, BarChart, BarGroup, Block, Borders, Paragraph, Sparkline},
+    widgets::{
+        Bar, BarChart, BarGroup, Block, Borders, Cell, Paragraph, Row, Sparkline, Table, TableState,
+    },
 };
 
 /// Draw the metrics view
@@ -14,13 +17,15 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(7), // Summary panel
-            Constraint::Min(10),   // Per-imposter metrics
+            Constraint::Length(7),      // Summary panel
+            Constraint::Percentage(40), // Per-imposter table
+            Constraint::Min(10),        // Selected imposter
         ])
         .split(area);
 
     draw_summary(frame, app, chunks[0]);
-    draw_per_imposter(frame, app, chunks[1]);
+    draw_table(frame, app, chunks[1]);
+    draw_selected(frame, app, chunks[2]);
 }
 
 /// Draw the summary metrics panel
@@ -65,6 +70,15 @@ fn draw_summary(frame: &mut Frame, app: &App, area: Rect) {
                 format!("{} samples", app.metrics_history.len()),
                 Style::default().fg(app.theme.fg),
             ),
+            Span::styled("    │    ", Style::default().fg(app.theme.border)),
+            Span::styled("Window: ", Style::default().fg(app.theme.muted)),
+            Span::styled(
+                app.metrics_window.label(),
+                Style::default()
+                    .fg(app.theme.focus)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" [w]", Style::default().fg(app.theme.muted)),
         ]),
     ];
 
@@ -77,131 +91,359 @@ fn draw_summary(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(paragraph, area);
 }
 
-/// Draw per-imposter metrics with bar chart and sparklines
-fn draw_per_imposter(frame: &mut Frame, app: &App, area: Rect) {
-    let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(area);
+/// A latency for a table cell: milliseconds, or seconds from 1s up
+fn format_ms(ms: Option<f64>) -> String {
+    match ms {
+        None => "-".to_string(),
+        Some(ms) if ms >= 1000.0 => format!("{:.1}s", ms / 1000.0),
+        Some(ms) if ms < 10.0 => format!("{ms:.1}ms"),
+        Some(ms) => format!("{ms:.0}ms"),
+    }
+}
 
-    draw_bar_chart(frame, app, chunks[0]);
-    draw_sparklines(frame, app, chunks[1]);
+fn format_percent(share: Option<f64>) -> String {
+    share.map_or_else(|| "-".to_string(), |s| format!("{:.1}%", s * 100.0))
 }
 
-/// Draw the bar chart comparing request counts
-fn draw_bar_chart(frame: &mut Frame, app: &App, area: Rect) {
-    let max_count = app
-        .metrics
-        .per_imposter
-        .values()
-        .map(|m| m.request_count)
-        .max()
-        .unwrap_or(1)
-        .max(1);
-
-    let bars: Vec<Bar> = app
+/// Draw one row per imposter with its figures over the selected window
+fn draw_table(frame: &mut Frame, app: &App, area: Rect) {
+    let header = Row::new(
+        [
+            "Imposter", "Reqs", "Rate", "p50", "p90", "p99", "2xx", "3xx", "4xx", "5xx", "Err%",
+            "Faults",
+        ]
+        .map(|title| Cell::from(title).style(Style::default().fg(app.theme.muted).bold())),
+    );
+    let rows: Vec<Row> = app
         .imposters
         .iter()
-        .filter_map(|imp| {
-            app.metrics.per_imposter.get(&imp.port).map(|m| {
-                Bar::default()
-                    .value(m.request_count)
-                    .label(Line::from(format!(":{}", imp.port)))
-                    .text_value(super::format_number(m.request_count))
-                    .style(Style::default().fg(app.theme.success))
-            })
+        .map(|imp| {
+            let name = imp.name.as_deref().unwrap_or("(unnamed)");
+            let label = format!(":{} {}", imp.port, truncate(name, 14));
+            let Some(stats) = app.window_stats(imp.port) else {
+                return Row::new([Cell::from(label)]);
+            };
+            let [ok, redirect, client, server] = stats.status_classes();
+            let faults: u64 = stats.faults.values().sum();
+            let error_style = match stats.error_rate() {
+                Some(rate) if rate > 0.0 => Style::default().fg(app.theme.error),
+                _ => Style::default().fg(app.theme.fg),
+            };
+            Row::new([
+                Cell::from(label),
+                Cell::from(super::format_number(stats.requests)),
+                Cell::from(
+                    stats
+                        .rate()
+                        .map_or_else(|| "-".to_string(), |r| format!("{r:.1}/s")),
+                ),
+                Cell::from(format_ms(stats.latency_percentile(0.5))),
+                Cell::from(format_ms(stats.latency_percentile(0.9))),
+                Cell::from(format_ms(stats.latency_percentile(0.99))),
+                Cell::from(super::format_number(ok)),
+                Cell::from(super::format_number(redirect)),
+                Cell::from(super::format_number(client)).style(if client > 0 {
+                    Style::default().fg(app.theme.warning)
+                } else {
+                    Style::default()
+                }),
+                Cell::from(super::format_number(server)).style(error_style),
+                Cell::from(format_percent(stats.error_rate())).style(error_style),
+                Cell::from(super::format_number(faults)).style(if faults > 0 {
+                    Style::default().fg(app.theme.warning)
+                } else {
+                    Style::default()
+                }),
+            ])
         })
         .collect();
 
-    let bar_chart = BarChart::default()
+    let widths = [
+        Constraint::Min(18),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(7),
+        Constraint::Length(7),
+        Constraint::Length(7),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Length(6),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .style(Style::default().fg(app.theme.fg))
         .block(
             Block::default()
-                .title(" Request Counts ")
+                .title(match app.metrics_window.duration() {
+                    Some(_) => format!(" Imposters (last {}) ", app.metrics_window.label()),
+                    None => " Imposters (since started) ".to_string(),
+                })
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(app.theme.border)),
         )
-        .data(BarGroup::default().bars(&bars))
-        .bar_width(8)
-        .bar_gap(2)
-        .max(max_count);
+        .row_highlight_style(
+            Style::default()
+                .bg(app.theme.highlight_bg)
+                .fg(app.theme.highlight_fg),
+        );
 
-    frame.render_widget(bar_chart, area);
+    let mut state = TableState::default();
+    if !app.imposters.is_empty() {
+        state.select(Some(
+            app.metrics_selected
+                .min(app.imposters.len().saturating_sub(1)),
+        ));
+    }
+    frame.render_stateful_widget(table, area, &mut state);
+
+    if app.imposters.is_empty() {
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        let msg = Paragraph::new("No imposters to display")
+            .style(Style::default().fg(app.theme.muted))
+            .alignment(ratatui::layout::Alignment::Center);
+        frame.render_widget(msg, inner);
+    }
 }
 
-/// Draw sparklines for each imposter
-fn draw_sparklines(frame: &mut Frame, app: &App, area: Rect) {
+/// Draw the selected imposter's traffic and latency histogram, and its stub hits and faults
+fn draw_selected(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(port) = app.metrics_selected_port() else {
+        return;
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    let stats = app.window_stats(port).unwrap_or_default();
+
+    draw_latency(frame, app, port, &stats, chunks[0]);
+    draw_hits(frame, app, port, &stats, chunks[1]);
+}
+
+fn draw_latency(frame: &mut Frame, app: &App, port: u16, stats: &WindowStats, area: Rect) {
     let block = Block::default()
-        .title(" Request Trends ")
+        .title(format!(
+            " :{port} Traffic and Latency (mean {}) ",
+            format_ms(stats.mean_latency_ms())
+        ))
         .borders(Borders::ALL)
         .border_style(Style::default().fg(app.theme.border));
-
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    if app.imposters.is_empty() {
-        let msg = Paragraph::new("No imposters to display")
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3)])
+        .split(inner);
+
+    let series = app.window_request_series(port);
+    if app.accessible {
+        // The latest refreshes' counts in words, where the sparkline would be
+        let skip = series.len().saturating_sub(10);
+        let counts: Vec<String> = series[skip..].iter().map(u64::to_string).collect();
+        let text = if counts.is_empty() {
+            "Requests per refresh: none yet".to_string()
+        } else {
+            format!("Requests per refresh, latest last: {}", counts.join(", "))
+        };
+        frame.render_widget(
+            Paragraph::new(text)
+                .style(Style::default().fg(app.theme.fg))
+                .wrap(ratatui::widgets::Wrap { trim: true }),
+            chunks[0],
+        );
+    } else {
+        let skip = series.len().saturating_sub(chunks[0].width as usize);
+        let sparkline = Sparkline::default()
+            .data(&series[skip..])
+            .style(Style::default().fg(app.theme.success));
+        frame.render_widget(sparkline, chunks[0]);
+    }
+
+    if stats.requests == 0 {
+        let msg = Paragraph::new("No requests in this window")
             .style(Style::default().fg(app.theme.muted))
             .alignment(ratatui::layout::Alignment::Center);
-        frame.render_widget(msg, inner);
+        frame.render_widget(msg, chunks[1]);
         return;
     }
+    // The histogram's buckets are cumulative; each bar shows the requests that landed in it
+    let mut below = 0;
+    let bars: Vec<Bar> = stats
+        .latency_buckets
+        .iter()
+        .map(|&(le, count)| {
+            let label = if le.is_infinite() {
+                "+Inf".to_string()
+            } else {
+                format_ms(Some(le))
+            };
+            let bar = Bar::default()
+                .value(count - below)
+                .label(Line::from(label))
+                .style(Style::default().fg(app.theme.success));
+            below = count;
+            bar
+        })
+        .collect();
+    let chart = BarChart::default()
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(5)
+        .bar_gap(1);
+    frame.render_widget(chart, chunks[1]);
+}
 
-    // Calculate rows for each imposter
-    let num_imposters = app.imposters.len().min(10); // Max 10 sparklines
-    let row_height = 3u16;
-    let available_height = inner.height;
-    let rows_that_fit = (available_height / row_height) as usize;
-    let num_rows = num_imposters.min(rows_that_fit).max(1);
+fn draw_hits(frame: &mut Frame, app: &App, port: u16, stats: &WindowStats, area: Rect) {
+    let total: u64 = stats.stub_hits.values().map(|h| h.1).sum::<u64>() + stats.unmatched;
+    let bar_width = 12;
+    let share_bar = |count: u64| {
+        let filled = if total == 0 {
+            0
+        } else {
+            (count * bar_width).div_ceil(total) as usize
+        };
+        format!(
+            "{}{}",
+            "█".repeat(filled),
+            "░".repeat(bar_width as usize - filled)
+        )
+    };
+    let hit_line = |label: String, count: u64, color: Color| {
+        Line::from(vec![
+            Span::styled(
+                format!(" {:<24}", truncate(&label, 24)),
+                Style::default().fg(app.theme.fg),
+            ),
+            Span::styled(share_bar(count), Style::default().fg(color)),
+            Span::styled(
+                format!(" {:>7}", super::format_number(count)),
+                Style::default().fg(app.theme.fg),
+            ),
+        ])
+    };
 
-    let constraints: Vec<Constraint> = (0..num_rows)
-        .map(|_| Constraint::Length(row_height))
-        .collect();
+    let mut lines = vec![Line::from(Span::styled(
+        " Stub hits",
+        Style::default().fg(app.theme.muted).bold(),
+    ))];
+    for (index, (id, count)) in &stats.stub_hits {
+        let label = match id {
+            Some(id) => format!("#{index} {id}"),
+            None => format!("#{index}"),
+        };
+        lines.push(hit_line(label, *count, app.theme.success));
+    }
+    if stats.unmatched > 0 {
+        lines.push(hit_line(
+            "(no match)".to_string(),
+            stats.unmatched,
+            app.theme.warning,
+        ));
+    }
+    if total == 0 {
+        lines.push(Line::from(Span::styled(
+            " No stub hits in this window",
+            Style::default().fg(app.theme.muted),
+        )));
+    }
 
-    let rows = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints(constraints)
-        .split(inner);
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        " Faults injected",
+        Style::default().fg(app.theme.muted).bold(),
+    )));
+    if stats.faults.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " None",
+            Style::default().fg(app.theme.muted),
+        )));
+    }
+    for (fault, count) in &stats.faults {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" {:<24}", truncate(fault, 24)),
+                Style::default().fg(app.theme.fg),
+            ),
+            Span::styled(
+                format!("{:>20}", super::format_number(*count)),
+                Style::default().fg(app.theme.warning),
+            ),
+        ]));
+    }
 
-    for (i, imp) in app.imposters.iter().take(num_rows).enumerate() {
-        let row = rows[i];
-
-        // Split row into label and sparkline
-        let row_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Length(12), Constraint::Min(20)])
-            .split(row);
-
-        // Label
-        let rates = app.calculate_rates();
-        let rate = rates.get(&imp.port).copied().unwrap_or(0.0);
-        let label = Paragraph::new(vec![
-            Line::from(Span::styled(
-                format!(":{}", imp.port),
-                Style::default()
-                    .fg(app.theme.fg)
-                    .add_modifier(Modifier::BOLD),
-            )),
-            Line::from(Span::styled(
-                format!("{rate:.1}/s"),
-                Style::default().fg(if rate > 0.0 {
-                    app.theme.success
-                } else {
-                    app.theme.muted
-                }),
-            )),
-        ]);
-        frame.render_widget(label, row_chunks[0]);
-
-        // Sparkline
-        let data = app.get_sparkline_data(imp.port);
-        if !data.is_empty() {
-            let sparkline = Sparkline::default()
-                .data(&data)
-                .style(Style::default().fg(app.theme.success));
-            frame.render_widget(sparkline, row_chunks[1]);
-        } else {
-            let no_data = Paragraph::new("No data yet").style(Style::default().fg(app.theme.muted));
-            frame.render_widget(no_data, row_chunks[1]);
+    let block = Block::default()
+        .title(format!(" :{port} Stubs and Faults "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{ImposterMetrics, ImposterSummary, MetricsData};
+    use crate::app::tests::make_test_app;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    #[test]
+    fn latencies_format_to_fit_their_column() {
+        assert_eq!(format_ms(None), "-");
+        assert_eq!(format_ms(Some(2.25)), "2.2ms");
+        assert_eq!(format_ms(Some(250.0)), "250ms");
+        assert_eq!(format_ms(Some(2500.0)), "2.5s");
+    }
+
+    #[test]
+    fn the_dashboard_shows_the_selected_imposters_figures() {
+        let mut app = make_test_app();
+        app.imposters = vec![ImposterSummary {
+            port: 4545,
+            protocol: "http".to_string(),
+            name: Some("orders".to_string()),
+            number_of_requests: 0,
+            stub_count: 1,
+            enabled: true,
+            record_requests: false,
+            server: None,
+        }];
+        let imposter = ImposterMetrics {
+            served_count: 4,
+            latency_buckets: vec![(5.0, 3), (f64::INFINITY, 4)],
+            latency_sum_ms: 20,
+            status_codes: [(200, 3), (503, 1)].into(),
+            faults: [("error".to_string(), 1)].into(),
+            stub_hits: [(0, (Some("list-orders".to_string()), 4))].into(),
+            ..Default::default()
+        };
+        app.metrics = MetricsData {
+            imposter_count: 1,
+            per_imposter: [(4545, imposter)].into(),
+            ..Default::default()
+        };
+        let metrics = app.metrics.clone();
+        app.metrics_window = crate::app::MetricsWindow::All;
+        app.record_metrics(&metrics, std::time::Instant::now());
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+        terminal
+            .draw(|frame| draw(frame, &app, frame.area()))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let screen: String = (0..30)
+            .flat_map(|y| (0..120).map(move |x| (x, y)))
+            .map(|(x, y)| buffer[(x, y)].symbol().to_string())
+            .collect();
+        for text in [
+            "Window: all",
+            ":4545 orders",
+            "25.0%",
+            "#0 list-orders",
+            "error",
+        ] {
+            assert!(screen.contains(text), "{text} not shown");
         }
     }
 }
@@ -1,13 +1,30 @@
 //! UI rendering for the TUI
 
+mod composer;
 mod config;
+mod copy_as;
 mod dialogs;
+mod editor;
+mod global_search;
 mod help;
 mod imposter_detail;
+mod imposter_form;
 mod imposters;
 mod metrics;
+mod palette;
+mod proxy_wizard;
 mod request_detail;
+mod request_log;
+mod scenario;
+mod screenshot;
+mod script_editor;
+mod server_log;
 mod stubs;
+mod transfer;
+
+pub use help::line_count as help_line_count;
+pub use screenshot::screenshot;
+pub use stubs::detail_line_count as stub_detail_line_count;
 
 use crate::app::{App, Overlay, StatusLevel, View};
 use ratatui::{
@@ -37,12 +54,24 @@ pub fn draw(frame: &mut Frame, app: &App) {
         View::StubDetail { port, index } => {
             stubs::draw_detail(frame, app, *port, *index, chunks[1])
         }
-        View::StubEdit { .. } => stubs::draw_editor(frame, app, chunks[1]),
+        View::StubEdit { .. } | View::ImposterEdit => stubs::draw_editor(frame, app, chunks[1]),
         View::RequestDetail { port, index } => {
             request_detail::draw(frame, app, *port, *index, chunks[1])
         }
+        View::RequestLog { port } => request_log::draw(frame, app, *port, chunks[1]),
         View::Config => config::draw(frame, app, chunks[1]),
         View::Metrics => metrics::draw(frame, app, chunks[1]),
+        View::ServerLog => server_log::draw(frame, app, chunks[1]),
+        View::Scenario => scenario::draw(frame, app, chunks[1]),
+    }
+    // What the server last said, greyed out until it answers again
+    if app.stale_for(std::time::Instant::now()).is_some() {
+        frame.buffer_mut().set_style(
+            chunks[1],
+            Style::default()
+                .fg(app.theme.muted)
+                .add_modifier(Modifier::DIM),
+        );
     }
 
     draw_status_bar(frame, app, chunks[2]);
@@ -51,7 +80,7 @@ pub fn draw(frame: &mut Frame, app: &App) {
     match &app.overlay {
         Overlay::Help => {
             // Note: We can't mutate app here, so we return the max_scroll for the caller to update
-            help::draw_overlay(frame, app.help_scroll);
+            help::draw_overlay(frame, app.help_scroll, &app.keys);
         }
         Overlay::Confirm { message, .. } => dialogs::draw_confirm(frame, message),
         Overlay::Error { message } => dialogs::draw_error(frame, message),
@@ -62,6 +91,7 @@ pub fn draw(frame: &mut Frame, app: &App) {
             port,
         } => dialogs::draw_export(
             frame,
+            app,
             title,
             content,
             app.export_scroll_offset,
@@ -73,20 +103,94 @@ pub fn draw(frame: &mut Frame, app: &App) {
             dialogs::draw_validation_result(frame, report, action, app.validation_scroll_offset)
         }
         Overlay::Errors => dialogs::draw_errors(frame, &app.errors, app.errors_scroll),
+        Overlay::RequestInspector { port } => request_log::draw_inspector(frame, app, *port),
+        Overlay::Composer => composer::draw(frame, app),
+        Overlay::ImposterForm => imposter_form::draw(frame, app),
+        Overlay::ProxyWizard => proxy_wizard::draw(frame, app),
+        Overlay::ScriptEditor => script_editor::draw(frame, app),
+        Overlay::CommandPalette => palette::draw(frame, app),
+        Overlay::GlobalSearch => global_search::draw(frame, app),
+        Overlay::TransferPreview => transfer::draw(frame, app),
+        Overlay::CopyMenu => copy_as::draw(frame, app),
+        Overlay::DiffPreview {
+            title,
+            warning,
+            diff,
+            ..
+        } => dialogs::draw_diff_preview(frame, app, title, warning.as_deref(), diff),
+        Overlay::ServerSwitcher { selected } => {
+            dialogs::draw_server_switcher(frame, app, *selected)
+        }
         Overlay::None => {}
     }
 }
 
 /// Draw the header bar
 fn draw_header(frame: &mut Frame, app: &App, area: Rect) {
-    let connection_status = if app.is_connected {
+    let connection_status = if app.showing_all_servers() {
+        let connected = app
+            .servers
+            .iter()
+            .filter(|s| s.connected == Some(true))
+            .count();
+        let color = if connected == app.servers.len() {
+            app.theme.success
+        } else if connected > 0 {
+            app.theme.warning
+        } else {
+            app.theme.error
+        };
+        Span::styled(
+            format!("● {connected}/{} connected", app.servers.len()),
+            Style::default().fg(color),
+        )
+    } else if app.is_connected {
         Span::styled("● Connected", Style::default().fg(app.theme.success))
     } else {
         Span::styled("○ Disconnected", Style::default().fg(app.theme.error))
     };
+    // While disconnected: how old the data shown is, and when the next attempt is
+    let now = std::time::Instant::now();
+    let mut staleness = Vec::new();
+    if !app.is_connected {
+        if let Some(age) = app.stale_for(now) {
+            staleness.push(format!("updated {} ago", format_uptime(age)));
+        }
+        if let Some(wait) = app.reconnect.retry_in(now) {
+            staleness.push(format!("retrying in {}", format_uptime(wait)));
+        }
+    }
+    let staleness = if staleness.is_empty() {
+        Span::raw("")
+    } else {
+        Span::styled(
+            format!(" · {}", staleness.join(" · ")),
+            Style::default().fg(app.theme.muted),
+        )
+    };
+
+    // The server's name once there is more than one to tell apart
+    let server = match app.active_server.and_then(|i| app.servers.get(i)) {
+        _ if app.servers.len() < 2 => Span::raw(""),
+        Some(server) => Span::styled(
+            format!("{} ", server.profile.name),
+            Style::default().fg(app.theme.focus).bold(),
+        ),
+        None => Span::styled("All servers", Style::default().fg(app.theme.focus).bold()),
+    };
+    let url = if app.showing_all_servers() {
+        String::new()
+    } else {
+        app.admin_url.clone()
+    };
 
     let loading = if app.is_loading {
-        Span::styled(" ⟳", Style::default().fg(app.theme.warning))
+        let text = if app.accessible {
+            " Loading…"
+        } else {
+            " ⟳"
+        };
+        Span::styled(text, Style::default().fg(app.theme.warning))
     } else {
         Span::raw("")
     };
@@ -106,9 +210,11 @@ fn draw_header(frame: &mut Frame, app: &App, area: Rect) {
         ),
         Span::raw(" │ "),
         connection_status,
+        staleness,
         loading,
         Span::raw(" │ "),
-        Span::styled(&app.admin_url, Style::default().fg(app.theme.muted)),
+        server,
+        Span::styled(url, Style::default().fg(app.theme.muted)),
         imposter_count,
     ]);
 
@@ -180,7 +286,12 @@ type Command = (&'static str, &'static str);
 /// Build a nvim-style command line with [key] notation and separators
 fn build_command_line(commands: &[Command], app: &App) -> Line<'static> {
     let mut spans = vec![Span::raw(" ")];
-    for (i, (key, label)) in commands.iter().enumerate() {
+    // Hints name the default keys; show the ones keys.toml binds instead, and drop unbound ones
+    let context = app.view.key_context();
+    let commands = commands
+        .iter()
+        .filter_map(|(key, label)| Some((app.keys.hint(context, key)?, label)));
+    for (i, (key, label)) in commands.enumerate() {
         if i > 0 {
             // Subtle separator
             spans.push(Span::styled(" │ ", Style::default().fg(app.theme.border)));
@@ -211,6 +322,7 @@ fn get_commands(view: &View) -> (Vec<Command>, Option<Vec<Command>>) {
                 ("d", "Del"),
                 ("t", "Toggle"),
                 ("m", "Metrics"),
+                ("l", "Log"),
                 ("C", "Config"),
                 ("/", "Search"),
                 ("T", "Theme"),
@@ -218,10 +330,17 @@ fn get_commands(view: &View) -> (Vec<Command>, Option<Vec<Command>>) {
                 ("q", "Quit"),
             ],
             Some(vec![
+                ("g", "ServerLog"),
                 ("i", "Import"),
                 ("I", "ImportDir"),
                 ("e", "Export"),
                 ("E", "ExportDir"),
+                ("s", "Servers"),
+                (":", "Commands"),
+                ("Space", "Mark"),
+                ("o", "Sort"),
+                ("f", "Filter"),
+                ("u", "Undo"),
             ]),
         ),
         View::ImposterDetail { .. } => (
@@ -230,10 +349,12 @@ fn get_commands(view: &View) -> (Vec<Command>, Option<Vec<Command>>) {
                 ("e", "Edit"),
                 ("d", "Del"),
                 ("D", "Dup"),
-                ("[", "MoveUp"),
-                ("]", "MoveDown"),
+                ("K", "MoveUp"),
+                ("J", "MoveDown"),
                 ("y", "Curl"),
                 ("t", "Toggle"),
+                ("l", "Log"),
+                ("S", "Send"),
                 ("/", "Search"),
                 ("?", "Help"),
             ],
@@ -243,6 +364,9 @@ fn get_commands(view: &View) -> (Vec<Command>, Option<Vec<Command>>) {
                 ("x", "ExportStubs"),
                 ("X", "ExportFull"),
                 ("A", "Apply"),
+                ("z", "ResetHits"),
+                ("Space", "Mark"),
+                ("u", "Undo"),
             ]),
         ),
         View::StubDetail { .. } => (
@@ -251,6 +375,7 @@ fn get_commands(view: &View) -> (Vec<Command>, Option<Vec<Command>>) {
                 ("d", "Delete"),
                 ("D", "Dup"),
                 ("y", "Curl"),
+                ("S", "Send"),
                 ("Esc", "Back"),
                 ("?", "Help"),
             ],
@@ -261,6 +386,21 @@ fn get_commands(view: &View) -> (Vec<Command>, Option<Vec<Command>>) {
                 ("^S", "Save"),
                 ("^F", "Format"),
                 ("^L", "Lint"),
+                ("^T", "Send"),
+                ("^E", "Script"),
+                ("^A", "SelAll"),
+                ("^C", "Copy"),
+                ("^X", "Cut"),
+                ("^V", "Paste"),
+                ("Esc", "Cancel"),
+            ],
+            None,
+        ),
+        View::ImposterEdit => (
+            vec![
+                ("^S", "Create"),
+                ("^F", "Format"),
+                ("^L", "Lint"),
                 ("^A", "SelAll"),
                 ("^C", "Copy"),
                 ("^X", "Cut"),
@@ -270,8 +410,52 @@ fn get_commands(view: &View) -> (Vec<Command>, Option<Vec<Command>>) {
             None,
         ),
         View::RequestDetail { .. } => (vec![("Esc", "Back"), ("?", "Help")], None),
+        View::RequestLog { .. } => (
+            vec![
+                ("Enter", "Inspect"),
+                ("p", "Pause"),
+                ("/", "FilterPath"),
+                ("G", "Follow"),
+                ("c", "Clear"),
+                ("Esc", "Back"),
+                ("?", "Help"),
+            ],
+            None,
+        ),
+        View::ServerLog => (
+            vec![
+                ("f", "Level"),
+                ("p", "Pause"),
+                ("/", "Search"),
+                ("G", "Follow"),
+                ("c", "Clear"),
+                ("Esc", "Back"),
+                ("?", "Help"),
+            ],
+            None,
+        ),
         View::Config => (vec![("r", "Refresh"), ("Esc", "Back")], None),
-        View::Metrics => (vec![("r", "Refresh"), ("Esc", "Back"), ("?", "Help")], None),
+        View::Scenario => (
+            vec![
+                ("j/k", "Select"),
+                ("R", "Rerun"),
+                ("o", "Open"),
+                ("Esc", "Back"),
+                ("?", "Help"),
+            ],
+            None,
+        ),
+        View::Metrics => (
+            vec![
+                ("j/k", "Select"),
+                ("w", "Window"),
+                ("x", "Export"),
+                ("r", "Refresh"),
+                ("Esc", "Back"),
+                ("?", "Help"),
+            ],
+            None,
+        ),
     }
 }
 
@@ -293,6 +477,7 @@ fn draw_search_bar(frame: &mut Frame, app: &App, area: Rect) {
     // Search prompt and query
     let cursor = if app.search_active { "█" } else { "" };
     let match_count = match &app.view {
+        _ if app.search_global => String::new(),
         View::ImposterList => {
             let filtered = app.filtered_imposters();
             format!(" ({}/{})", filtered.len(), app.imposters.len())
@@ -306,12 +491,24 @@ fn draw_search_bar(frame: &mut Frame, app: &App, area: Rect) {
                 .unwrap_or(0);
             format!(" ({}/{})", filtered.len(), total)
         }
+        View::RequestLog { .. } => {
+            let total = app.request_log.as_ref().map_or(0, |log| log.entries.len());
+            format!(" ({}/{})", app.filtered_request_log().len(), total)
+        }
+        View::ServerLog => {
+            let total = app.server_log.as_ref().map_or(0, |log| log.entries.len());
+            format!(" ({}/{})", app.filtered_server_log().len(), total)
+        }
         _ => String::new(),
     };
 
     let line = Line::from(vec![
         Span::styled(
-            " /",
+            if app.search_global {
+                " all imposters /"
+            } else {
+                " /"
+            },
             Style::default()
                 .fg(app.theme.highlight_bg)
                 .add_modifier(Modifier::BOLD),
@@ -320,8 +517,10 @@ fn draw_search_bar(frame: &mut Frame, app: &App, area: Rect) {
         Span::styled(cursor, Style::default().fg(app.theme.highlight_bg)),
         Span::styled(&match_count, Style::default().fg(app.theme.muted)),
         Span::styled(
-            if app.search_active {
-                "  [Enter] search  [Esc] cancel  [Ctrl+U] clear"
+            if app.search_global {
+                "  [Enter] search every imposter  [Tab] this list only  [Esc] cancel"
+            } else if app.search_active {
+                "  [Enter] search  [Tab] every imposter  [Esc] cancel  [Ctrl+U] clear"
             } else {
                 "  [/] edit  [Esc] clear"
             },
@@ -378,6 +577,17 @@ pub fn format_number(n: u64) -> String {
     result.chars().rev().collect()
 }
 
+/// The rows of a `len`-row list, `height` rows tall, to build for a frame: only those on screen,
+/// scrolled as `List` scrolls from the top so that `selected` stays in view. Building every row of
+/// a list thousands long on each redraw is what makes scrolling it stutter.
+pub fn visible_rows(len: usize, selected: Option<usize>, height: usize) -> std::ops::Range<usize> {
+    let height = height.max(1);
+    let start = selected
+        .map_or(0, |selected| (selected + 1).saturating_sub(height))
+        .min(len);
+    start..(start + height).min(len)
+}
+
 /// Format uptime duration
 pub fn format_uptime(duration: std::time::Duration) -> String {
     let secs = duration.as_secs();
@@ -477,6 +687,16 @@ mod tests {
         assert_eq!(format_uptime(std::time::Duration::from_secs(90)), "1m 30s");
     }
 
+    #[test]
+    fn test_visible_rows_keep_the_selection_in_view() {
+        assert_eq!(visible_rows(2000, None, 10), 0..10);
+        assert_eq!(visible_rows(2000, Some(9), 10), 0..10);
+        assert_eq!(visible_rows(2000, Some(10), 10), 1..11);
+        assert_eq!(visible_rows(2000, Some(1999), 10), 1990..2000);
+        assert_eq!(visible_rows(3, Some(2), 10), 0..3);
+        assert_eq!(visible_rows(0, None, 10), 0..0);
+    }
+
     #[test]
     fn test_format_uptime_hours() {
         assert_eq!(
@@ -485,6 +705,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sparkline_scales_to_the_busiest_refresh() {
+        assert_eq!(imposters::sparkline(&[]), "");
+        assert_eq!(imposters::sparkline(&[0, 1, 50, 100]), "▁▂▅█");
+        assert_eq!(imposters::sparkline(&[0, 0]), "▁▁");
+    }
+
     // ─── UI smoke tests (render must not panic) ───────────────────────────────
 
     fn make_terminal() -> Terminal<TestBackend> {
@@ -505,6 +732,26 @@ mod tests {
             .expect("draw must not fail");
     }
 
+    #[test]
+    fn test_screenshot_keeps_colors_unless_plain() {
+        let mut terminal = make_terminal();
+        let mut app = make_test_app();
+        app.imposters = vec![make_imposter(4545, Some("日本語"), "http")];
+        let frame = terminal.draw(|f| draw(f, &app)).expect("draw");
+
+        let plain = screenshot(frame.buffer, false);
+        assert_eq!(plain.lines().count(), 40);
+        assert!(plain.contains(":4545"));
+        // A wide character fills two cells but is written once
+        assert!(plain.contains("日本語 "));
+        assert!(!plain.contains('\x1b'));
+        assert!(plain.lines().all(|line| line == line.trim_end()));
+
+        let colored = screenshot(frame.buffer, true);
+        assert!(colored.contains("\x1b[0;"));
+        assert!(colored.lines().all(|line| line.ends_with("\x1b[0m")));
+    }
+
     #[test]
     fn test_draw_disconnected_state_does_not_panic() {
         let mut terminal = make_terminal();
@@ -516,6 +763,113 @@ mod tests {
             .expect("draw must not fail");
     }
 
+    #[test]
+    fn test_draw_stale_data_greys_out_and_says_how_old_it_is() {
+        let mut terminal = make_terminal();
+        let mut app = make_test_app();
+        app.imposters = vec![make_imposter(4545, Some("my-service"), "http")];
+        app.last_updated = Some(std::time::Instant::now() - std::time::Duration::from_secs(42));
+        app.reconnect.failed(std::time::Instant::now());
+        terminal
+            .draw(|f| draw(f, &app))
+            .expect("draw must not fail");
+        let buffer = terminal.backend().buffer();
+        let header: String = (0..buffer.area.width)
+            .map(|x| buffer[(x, 1)].symbol())
+            .collect();
+        assert!(header.contains("updated 42s ago"), "{header}");
+        assert!(header.contains("retrying in"), "{header}");
+        assert_eq!(buffer[(2, 4)].fg, app.theme.muted);
+    }
+
+    #[test]
+    fn test_draw_stub_detail_shows_response_bodies_decoded() {
+        use base64::Engine;
+        let mut terminal = make_terminal();
+        let mut app = make_test_app();
+        let body = base64::engine::general_purpose::STANDARD.encode(r#"{"orderId":"A-17"}"#);
+        app.current_imposter = Some(
+            serde_json::from_value(serde_json::json!({
+                "port": 4545,
+                "protocol": "http",
+                "stubs": [{ "responses": [
+                    { "is": { "body": body } },
+                    { "is": { "body": "<ok>yes</ok>" } }
+                ] }]
+            }))
+            .unwrap(),
+        );
+        app.view = View::StubDetail {
+            port: 4545,
+            index: 0,
+        };
+        terminal
+            .draw(|f| draw(f, &app))
+            .expect("draw must not fail");
+        let buffer = terminal.backend().buffer();
+        let screen: String = (0..buffer.area.height)
+            .flat_map(|y| (0..buffer.area.width).map(move |x| (x, y)))
+            .map(|at| buffer[at].symbol())
+            .collect();
+        assert!(
+            screen.contains("Response 1 body · JSON, decoded from base64"),
+            "{screen}"
+        );
+        assert!(screen.contains(r#""orderId": "A-17""#), "{screen}");
+        assert!(screen.contains("Response 2 body · XML"), "{screen}");
+    }
+
+    #[test]
+    fn test_draw_imposter_form_shows_what_is_wrong_under_a_field() {
+        let mut terminal = make_terminal();
+        let mut app = make_test_app();
+        app.show_create_imposter();
+        if let Some(form) = app.imposter_form.as_mut() {
+            form.default_status = "700".to_string();
+        }
+        terminal
+            .draw(|f| draw(f, &app))
+            .expect("draw must not fail");
+        let buffer = terminal.backend().buffer();
+        let screen: String = (0..buffer.area.height)
+            .flat_map(|y| (0..buffer.area.width).map(move |x| (x, y)))
+            .map(|at| buffer[at].symbol())
+            .collect();
+        assert!(screen.contains("Create New Imposter"), "{screen}");
+        assert!(
+            screen.contains("✗ Status code must be between 100 and 599"),
+            "{screen}"
+        );
+    }
+
+    #[test]
+    fn test_draw_a_huge_stub_list_shows_the_selected_stub() {
+        let mut terminal = make_terminal();
+        let mut app = make_test_app();
+        let stubs: Vec<serde_json::Value> = (0..2000)
+            .map(|i| serde_json::json!({ "scenarioName": format!("stub-{i}") }))
+            .collect();
+        app.current_imposter = Some(
+            serde_json::from_value(
+                serde_json::json!({ "port": 4545, "protocol": "http", "stubs": stubs }),
+            )
+            .unwrap(),
+        );
+        app.view = crate::app::View::ImposterDetail { port: 4545 };
+        app.stub_list_state.select(Some(1500));
+        terminal
+            .draw(|f| draw(f, &app))
+            .expect("draw must not fail");
+        let buffer = terminal.backend().buffer();
+        let screen: String = (0..buffer.area.height)
+            .flat_map(|y| (0..buffer.area.width).map(move |x| (x, y)))
+            .map(|pos| buffer[pos].symbol())
+            .collect();
+        assert!(screen.contains("stub-1500"));
+        assert!(!screen.contains("stub-1400 "));
+        assert!(screen.contains("Stubs (2000)"));
+    }
+
     #[test]
     fn test_draw_with_status_message_does_not_panic() {
         let mut terminal = make_terminal();
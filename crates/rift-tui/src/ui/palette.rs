@@ -0,0 +1,92 @@
+//! Command palette overlay — type to find an action, Enter to run it
+
+use crate::app::App;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+/// Draw the command palette over the current view
+pub fn draw(frame: &mut Frame, app: &App) {
+    let area = super::centered_rect(60, 60, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Commands ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.focus))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let prompt = Line::from(vec![
+        Span::styled(" : ", Style::default().fg(app.theme.key_fg).bold()),
+        Span::styled(app.palette.query.clone(), Style::default().fg(app.theme.fg)),
+        Span::styled("█", Style::default().fg(app.theme.highlight_bg)),
+    ]);
+    frame.render_widget(Paragraph::new(prompt), chunks[0]);
+
+    let matches = app.palette_matches();
+    if matches.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                " No matching commands",
+                Style::default().fg(app.theme.muted),
+            )),
+            chunks[2],
+        );
+    } else {
+        let width = chunks[2].width as usize;
+        let items: Vec<ListItem> = matches
+            .iter()
+            .map(|entry| {
+                let keys = if entry.keys.is_empty() {
+                    String::new()
+                } else {
+                    format!("{} ", entry.keys)
+                };
+                let pad = width
+                    .saturating_sub(entry.label.chars().count() + keys.chars().count() + 1)
+                    .max(1);
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!(" {}", entry.label),
+                        Style::default().fg(app.theme.fg),
+                    ),
+                    Span::raw(" ".repeat(pad)),
+                    Span::styled(keys, Style::default().fg(app.theme.muted)),
+                ]))
+            })
+            .collect();
+        let mut state = ListState::default().with_selected(Some(app.palette.selected));
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(app.theme.highlight_bg)
+                .fg(app.theme.highlight_fg),
+        );
+        frame.render_stateful_widget(list, chunks[2], &mut state);
+    }
+
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            " type to filter · a port number opens that imposter · ↑/↓ select · Enter run · Esc close ",
+            Style::default().fg(Color::DarkGray),
+        ))
+        .alignment(Alignment::Center),
+        chunks[3],
+    );
+}
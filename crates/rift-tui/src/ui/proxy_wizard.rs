@@ -0,0 +1,266 @@
+//! Proxy imposter wizard overlay — one step of the form at a time, then the config to create
+
+use super::dialogs::draw_input_field;
+use crate::app::{App, GENERATOR_FIELDS, PROXY_MODES, ProxyWizard, WizardStep};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+/// Draw the proxy imposter wizard over the current view
+pub fn draw(frame: &mut Frame, app: &App) {
+    let Some(wizard) = &app.proxy_wizard else {
+        return;
+    };
+    let area = super::centered_rect(65, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Create Proxy Imposter ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Steps
+            Constraint::Length(1), // Spacing
+            Constraint::Min(4),    // Step body
+            Constraint::Length(1), // Help text
+        ])
+        .split(inner);
+
+    frame.render_widget(
+        Paragraph::new(steps_line(wizard)).alignment(Alignment::Center),
+        chunks[0],
+    );
+    match wizard.step {
+        WizardStep::Target => draw_target(frame, wizard, chunks[2]),
+        WizardStep::Mode => draw_mode(frame, wizard, chunks[2]),
+        WizardStep::Recording => draw_recording(frame, wizard, chunks[2]),
+        WizardStep::Review => draw_review(frame, wizard, chunks[2]),
+    }
+    frame.render_widget(
+        Paragraph::new(help_line(wizard)).alignment(Alignment::Center),
+        chunks[3],
+    );
+}
+
+/// `1 Target › 2 Mode › …`, with the current step highlighted
+fn steps_line(wizard: &ProxyWizard) -> Line<'static> {
+    let mut spans = Vec::new();
+    for (i, step) in wizard.steps().into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(" › ", Style::default().fg(Color::DarkGray)));
+        }
+        let style = if step == wizard.step {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        spans.push(Span::styled(format!("{} {}", i + 1, step.title()), style));
+    }
+    Line::from(spans)
+}
+
+fn draw_target(frame: &mut Frame, wizard: &ProxyWizard, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .split(area);
+    let fields = [
+        (
+            "Target URL (required)",
+            &wizard.target_url,
+            "https://api.example.com",
+        ),
+        ("Port (optional)", &wizard.port, "auto-assign"),
+        ("Name (optional)", &wizard.name, "unnamed"),
+    ];
+    for (i, (label, value, placeholder)) in fields.into_iter().enumerate() {
+        draw_input_field(
+            frame,
+            chunks[i],
+            label,
+            value,
+            placeholder,
+            wizard.focus == i,
+            None,
+        );
+    }
+}
+
+fn draw_mode(frame: &mut Frame, wizard: &ProxyWizard, area: Rect) {
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "  How the imposter forwards requests:",
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(""),
+    ];
+    for (i, (mode, description)) in PROXY_MODES.iter().enumerate() {
+        let selected = i == wizard.mode;
+        let style = if selected {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(if selected { "  ● " } else { "  ○ " }, style),
+            Span::styled(*mode, style),
+        ]));
+        lines.push(Line::from(Span::styled(
+            format!("      {description}"),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn checkbox(checked: bool, label: &str, description: &str, focused: bool) -> Line<'static> {
+    let style = if focused {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::White)
+    };
+    Line::from(vec![
+        Span::styled(if focused { " ▶ " } else { "   " }, style),
+        Span::styled(if checked { "[x] " } else { "[ ] " }, style),
+        Span::styled(label.to_string(), style),
+        Span::styled(
+            format!("  {description}"),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])
+}
+
+fn draw_recording(frame: &mut Frame, wizard: &ProxyWizard, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),                             // Intro
+            Constraint::Length(GENERATOR_FIELDS.len() as u16), // Generators
+            Constraint::Length(3),                             // Header names
+            Constraint::Length(1),                             // Spacing
+            Constraint::Min(1),                                // addWaitBehavior
+        ])
+        .split(area);
+
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            "  Recorded stubs match requests on (predicateGenerators):",
+            Style::default().fg(Color::DarkGray),
+        )),
+        chunks[0],
+    );
+    let descriptions = [
+        "the request path",
+        "the HTTP method",
+        "the query parameters",
+        "the headers named below, or all of them",
+    ];
+    let generators: Vec<Line> = GENERATOR_FIELDS
+        .iter()
+        .zip(descriptions)
+        .enumerate()
+        .map(|(i, (field, description))| {
+            checkbox(wizard.generators[i], field, description, wizard.focus == i)
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(generators), chunks[1]);
+
+    draw_input_field(
+        frame,
+        chunks[2],
+        "Header names (comma-separated)",
+        &wizard.header_names,
+        "all headers",
+        wizard.focus == GENERATOR_FIELDS.len(),
+        None,
+    );
+    frame.render_widget(
+        Paragraph::new(checkbox(
+            wizard.add_wait_behavior,
+            "addWaitBehavior",
+            "replay with the backend's recorded latency",
+            wizard.focus == GENERATOR_FIELDS.len() + 1,
+        )),
+        chunks[4],
+    );
+}
+
+fn draw_review(frame: &mut Frame, wizard: &ProxyWizard, area: Rect) {
+    let mut lines = Vec::new();
+    if let Some(note) = wizard.review_note() {
+        lines.push(Line::from(Span::styled(
+            format!("  ⚠ {note}"),
+            Style::default().fg(Color::Yellow),
+        )));
+        lines.push(Line::from(""));
+    }
+    match wizard.to_request() {
+        Ok(request) => {
+            let json = serde_json::to_string_pretty(&request).unwrap_or_default();
+            lines.extend(json.lines().map(|line| Line::from(format!("  {line}"))));
+        }
+        Err(e) => lines.push(Line::from(Span::styled(
+            format!("  {e}"),
+            Style::default().fg(Color::Red),
+        ))),
+    }
+    let block = Block::default()
+        .title(" Imposter to create ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((wizard.review_scroll, 0)),
+        area,
+    );
+}
+
+fn help_line(wizard: &ProxyWizard) -> Line<'static> {
+    let key =
+        |key: &'static str, color: Color| Span::styled(key, Style::default().fg(color).bold());
+    let first = wizard.step == wizard.steps()[0];
+    let mut spans = match wizard.step {
+        WizardStep::Target => vec![key("[Tab]", Color::Cyan), Span::raw(" Next field  ")],
+        WizardStep::Mode => vec![key("[↑/↓]", Color::Cyan), Span::raw(" Mode  ")],
+        WizardStep::Recording => vec![
+            key("[↑/↓]", Color::Cyan),
+            Span::raw(" Move  "),
+            key("[Space]", Color::Cyan),
+            Span::raw(" Toggle  "),
+        ],
+        WizardStep::Review => vec![key("[PgUp/PgDn]", Color::Cyan), Span::raw(" Scroll  ")],
+    };
+    if wizard.step == WizardStep::Review {
+        spans.extend([key("[Enter]", Color::Green), Span::raw(" Create  ")]);
+    } else {
+        spans.extend([key("[Enter]", Color::Green), Span::raw(" Next  ")]);
+    }
+    spans.extend([
+        key("[Esc]", Color::Red),
+        Span::raw(if first { " Cancel" } else { " Back" }),
+    ]);
+    Line::from(spans)
+}
@@ -0,0 +1,190 @@
+//! Request log view — a live tail of the requests an imposter serves
+
+use super::imposter_detail::method_color;
+use super::truncate;
+use crate::api::ServedRequest;
+use crate::app::{App, inspector_sections};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+/// Draw the request log for an imposter
+pub fn draw(frame: &mut Frame, app: &App, port: u16, area: Rect) {
+    let requests = app.filtered_request_log();
+    let paused = app.request_log.as_ref().is_some_and(|log| log.paused);
+
+    let mut title = vec![Span::styled(
+        format!(" Requests :{port} ({}) ", requests.len()),
+        Style::default().fg(app.theme.fg),
+    )];
+    if paused {
+        title.push(Span::styled(
+            "⏸ PAUSED ",
+            Style::default()
+                .fg(app.theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ));
+    } else {
+        title.push(Span::styled(
+            "● LIVE ",
+            Style::default().fg(app.theme.success),
+        ));
+    }
+    let block = Block::default()
+        .title(Line::from(title))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border));
+
+    if requests.is_empty() {
+        let msg = if app.search_query.is_empty() {
+            "Waiting for requests..."
+        } else {
+            "No requests match the path filter"
+        };
+        let paragraph =
+            Paragraph::new(Span::styled(msg, Style::default().fg(app.theme.muted))).block(block);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let path_width = area.width.saturating_sub(48) as usize;
+    let items: Vec<ListItem> = requests
+        .iter()
+        .map(|request| ListItem::new(request_line(request, path_width, app)))
+        .collect();
+
+    let mut state = app
+        .request_log
+        .as_ref()
+        .map(|log| log.list_state)
+        .unwrap_or_default();
+    if app.request_log.as_ref().is_none_or(|log| log.follow) {
+        state = ListState::default().with_selected(Some(requests.len() - 1));
+    }
+
+    let list = List::new(items).block(block).highlight_style(
+        Style::default()
+            .bg(app.theme.highlight_bg)
+            .fg(app.theme.highlight_fg),
+    );
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// Draw the inspector overlay: the served request's headers and bodies on both sides
+pub fn draw_inspector(frame: &mut Frame, app: &App, port: u16) {
+    let Some(detail) = &app.inspected_request else {
+        return;
+    };
+    let area = super::centered_rect(85, 85, frame.area());
+    frame.render_widget(Clear, area);
+
+    let request = &detail.summary;
+    let block = Block::default()
+        .title(format!(" Request #{} on :{port} ", request.seq))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(2)])
+        .split(inner);
+
+    let mut lines = vec![request_line(request, usize::MAX, app), Line::from("")];
+    for (title, section) in inspector_sections(detail) {
+        lines.push(Line::from(Span::styled(
+            title,
+            Style::default()
+                .fg(app.theme.highlight_bg)
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.extend(section.into_iter().map(|line| {
+            Line::from(Span::styled(
+                format!("  {line}"),
+                Style::default().fg(Color::White),
+            ))
+        }));
+        lines.push(Line::from(""));
+    }
+    let content = Paragraph::new(lines)
+        .scroll((app.inspector_scroll, 0))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(content, chunks[0]);
+
+    let help = Line::from(vec![
+        Span::styled("[s]", Style::default().fg(Color::Green).bold()),
+        Span::raw(" Replay  "),
+        Span::styled("[P]", Style::default().fg(Color::Cyan).bold()),
+        Span::raw(" Replay to port  "),
+        Span::styled("[e]", Style::default().fg(Color::Cyan).bold()),
+        Span::raw(" Edit & send  "),
+        Span::styled("[↑/↓]", Style::default().fg(Color::Gray)),
+        Span::raw(" Scroll  "),
+        Span::styled("[Esc]", Style::default().fg(Color::Red).bold()),
+        Span::raw(" Close"),
+    ]);
+    frame.render_widget(Paragraph::new(help).alignment(Alignment::Center), chunks[1]);
+}
+
+/// One row: time, method, status, latency, the stub that answered, then the path
+fn request_line<'a>(request: &ServedRequest, path_width: usize, app: &App) -> Line<'a> {
+    let time = chrono::DateTime::parse_from_rfc3339(&request.timestamp)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_else(|_| "--:--:--".to_string());
+    let stub = match (&request.stub_id, request.stub_index) {
+        (Some(id), _) => truncate(id, 12),
+        (None, Some(index)) => format!("#{}", index + 1),
+        (None, None) => "no match".to_string(),
+    };
+    let stub_color = if request.stub_index.is_some() {
+        app.theme.fg
+    } else {
+        app.theme.warning
+    };
+    let target = match &request.query {
+        Some(query) => format!("{}?{query}", request.path),
+        None => request.path.clone(),
+    };
+
+    Line::from(vec![
+        Span::styled(format!(" {time} "), Style::default().fg(app.theme.muted)),
+        Span::styled(
+            format!("{:<7}", request.method),
+            Style::default().fg(method_color(&request.method, app)),
+        ),
+        Span::styled(
+            format!("{:<4}", request.status_code),
+            Style::default().fg(status_color(request.status_code, app)),
+        ),
+        Span::styled(
+            format!("{:>6}ms ", request.latency_ms),
+            Style::default().fg(app.theme.muted),
+        ),
+        Span::styled(format!("{stub:<12} "), Style::default().fg(stub_color)),
+        Span::styled(
+            truncate(&target, path_width),
+            Style::default().fg(app.theme.fg),
+        ),
+    ])
+}
+
+/// Get color for an HTTP status code
+pub(super) fn status_color(status: u16, app: &App) -> Color {
+    match status {
+        200..=299 => app.theme.success,
+        300..=399 => Color::Cyan,
+        400..=499 => app.theme.warning,
+        _ => app.theme.error,
+    }
+}
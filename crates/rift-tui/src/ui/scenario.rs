@@ -0,0 +1,116 @@
+//! Scenario runner view — each step of a scenario file and whether it got the status expected
+
+use super::truncate;
+use crate::app::{App, StepOutcome};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+/// Draw the scenario runner
+pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(run) = &app.scenario else {
+        let paragraph = Paragraph::new(Span::styled(
+            "No scenario loaded: press o to open one",
+            Style::default().fg(app.theme.muted),
+        ))
+        .block(
+            Block::default()
+                .title(" Scenario ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.border)),
+        );
+        frame.render_widget(paragraph, area);
+        return;
+    };
+
+    let (passed, failed) = (run.passed(), run.failed());
+    let pending = run.results.len() - passed - failed;
+    let mut title = vec![Span::styled(
+        format!(
+            " {} · {} ",
+            run.scenario.name.as_deref().unwrap_or("Scenario"),
+            truncate(&run.path, 40)
+        ),
+        Style::default().fg(app.theme.fg),
+    )];
+    title.push(Span::styled(
+        format!("✓ {passed} "),
+        Style::default().fg(app.theme.success),
+    ));
+    if failed > 0 {
+        title.push(Span::styled(
+            format!("✗ {failed} "),
+            Style::default()
+                .fg(app.theme.error)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    if pending > 0 {
+        title.push(Span::styled(
+            format!("… {pending} "),
+            Style::default().fg(app.theme.muted),
+        ));
+    }
+    let block = Block::default()
+        .title(Line::from(title))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border));
+    let inner_height = block.inner(area).height as usize;
+
+    let visible = super::visible_rows(run.results.len(), Some(run.selected), inner_height);
+    let items: Vec<ListItem> = run.results[visible.clone()]
+        .iter()
+        .map(|result| {
+            let step = &run.scenario.steps[result.step];
+            let (mark, color, got) = match &result.outcome {
+                None => ("·", app.theme.muted, String::new()),
+                Some(StepOutcome::Passed { status, latency_ms }) => (
+                    "✓",
+                    app.theme.success,
+                    format!("got {status} {latency_ms}ms"),
+                ),
+                Some(StepOutcome::Failed { status, latency_ms }) => {
+                    ("✗", app.theme.error, format!("got {status} {latency_ms}ms"))
+                }
+                Some(StepOutcome::Error(e)) => ("✗", app.theme.error, e.clone()),
+            };
+            let mut spans = vec![
+                Span::styled(format!(" {mark} "), Style::default().fg(color).bold()),
+                Span::styled(
+                    format!(":{:<5} ", result.port),
+                    Style::default().fg(app.theme.fg).bold(),
+                ),
+            ];
+            if let Some(server) = result.server.and_then(|index| app.servers.get(index)) {
+                spans.push(Span::styled(
+                    format!("{} ", truncate(&server.profile.name, 12)),
+                    Style::default().fg(app.theme.focus),
+                ));
+            }
+            spans.extend([
+                Span::styled(
+                    format!("{:<32} ", truncate(&step.label(), 32)),
+                    Style::default().fg(app.theme.fg),
+                ),
+                Span::styled(
+                    format!("expected {}  ", step.expect_status),
+                    Style::default().fg(app.theme.muted),
+                ),
+                Span::styled(got, Style::default().fg(color)),
+            ]);
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let mut state = ListState::default().with_selected(Some(run.selected - visible.start));
+    let list = List::new(items).block(block).highlight_style(
+        Style::default()
+            .bg(app.theme.highlight_bg)
+            .fg(app.theme.highlight_fg),
+    );
+    frame.render_stateful_widget(list, area, &mut state);
+}
@@ -0,0 +1,92 @@
+//! Screenshots: a drawn frame as text, colored with ANSI escapes or plain
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+    text::Span,
+};
+
+/// The frame in `buffer`, one line per row, with ANSI colors and attributes when `color` is set.
+/// A colored screenshot shows as it did with `cat` or `less -R`.
+pub fn screenshot(buffer: &Buffer, color: bool) -> String {
+    let area = buffer.area;
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        let mut style = None;
+        // Cells under the right half of a wide character hold nothing of their own
+        let mut covered = 0;
+        for x in area.left()..area.right() {
+            if covered > 0 {
+                covered -= 1;
+                continue;
+            }
+            let cell = &buffer[(x, y)];
+            if color {
+                let cell_style = (cell.fg, cell.bg, cell.modifier);
+                if style != Some(cell_style) {
+                    line.push_str(&escape(cell.fg, cell.bg, cell.modifier));
+                    style = Some(cell_style);
+                }
+            }
+            let symbol = cell.symbol();
+            line.push_str(symbol);
+            covered = Span::raw(symbol).width().saturating_sub(1);
+        }
+        if color {
+            line.push_str("\x1b[0m");
+        } else {
+            line.truncate(line.trim_end().len());
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// The escape that resets the terminal's style and then sets `fg`, `bg` and `modifier`
+fn escape(fg: Color, bg: Color, modifier: Modifier) -> String {
+    let mut codes = vec!["0".to_string()];
+    for (flag, code) in [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::CROSSED_OUT, "9"),
+    ] {
+        if modifier.contains(flag) {
+            codes.push(code.to_string());
+        }
+    }
+    codes.extend(color_code(fg, false));
+    codes.extend(color_code(bg, true));
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+/// The SGR parameter for `color` as the foreground, or the background when `background` is set
+fn color_code(color: Color, background: bool) -> Option<String> {
+    let basic = |code: u8| Some((code + if background { 10 } else { 0 }).to_string());
+    let extended = if background { 48 } else { 38 };
+    match color {
+        Color::Reset => None,
+        Color::Black => basic(30),
+        Color::Red => basic(31),
+        Color::Green => basic(32),
+        Color::Yellow => basic(33),
+        Color::Blue => basic(34),
+        Color::Magenta => basic(35),
+        Color::Cyan => basic(36),
+        Color::Gray => basic(37),
+        Color::DarkGray => basic(90),
+        Color::LightRed => basic(91),
+        Color::LightGreen => basic(92),
+        Color::LightYellow => basic(93),
+        Color::LightBlue => basic(94),
+        Color::LightMagenta => basic(95),
+        Color::LightCyan => basic(96),
+        Color::White => basic(97),
+        Color::Indexed(index) => Some(format!("{extended};5;{index}")),
+        Color::Rgb(r, g, b) => Some(format!("{extended};2;{r};{g};{b}")),
+    }
+}
@@ -0,0 +1,97 @@
+//! Script editor overlay — edit a stub's script, check it and run it against a sample request
+
+use super::composer::{draw_text_field, field_block};
+use crate::app::{App, ScriptField};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Draw the script editor over the stub editor
+pub fn draw(frame: &mut Frame, app: &App) {
+    let Some(script) = &app.script_editor else {
+        return;
+    };
+    let area = super::centered_rect(90, 90, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" Script: {} ", script.target.describe()))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(50), // Code
+            Constraint::Min(6),         // Sample request and results
+            Constraint::Length(1),      // Help
+        ])
+        .split(inner);
+    draw_text_field(
+        frame,
+        chunks[0],
+        "Code",
+        &script.code,
+        script.focus == ScriptField::Code,
+    );
+
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(chunks[1]);
+    draw_text_field(
+        frame,
+        bottom[0],
+        "Sample request",
+        &script.sample,
+        script.focus == ScriptField::Sample,
+    );
+
+    let results = match &script.output {
+        Some(output) => {
+            let color = if output.is_ok() {
+                app.theme.success
+            } else {
+                app.theme.error
+            };
+            let mut lines = output.lines().into_iter();
+            let first = lines.next().unwrap_or_default();
+            std::iter::once(Line::from(Span::styled(first, Style::default().fg(color))))
+                .chain(lines.map(|line| Line::from(Span::raw(line))))
+                .collect()
+        }
+        None => vec![Line::from(Span::styled(
+            "Ctrl+S checks the script, Ctrl+T runs it against the sample request",
+            Style::default().fg(Color::DarkGray),
+        ))],
+    };
+    frame.render_widget(
+        Paragraph::new(results)
+            .block(field_block("Results", false))
+            .scroll((script.output_scroll, 0))
+            .wrap(Wrap { trim: false }),
+        bottom[1],
+    );
+
+    let help = Line::from(vec![
+        Span::styled("[^S]", Style::default().fg(Color::Green).bold()),
+        Span::raw(" Check & keep  "),
+        Span::styled("[^T]", Style::default().fg(Color::Cyan).bold()),
+        Span::raw(" Run  "),
+        Span::styled("[Tab]", Style::default().fg(Color::Cyan).bold()),
+        Span::raw(" Next field  "),
+        Span::styled("[PgUp/PgDn]", Style::default().fg(Color::Gray)),
+        Span::raw(" Scroll results  "),
+        Span::styled("[Esc]", Style::default().fg(Color::Red).bold()),
+        Span::raw(" Discard"),
+    ]);
+    frame.render_widget(Paragraph::new(help).alignment(Alignment::Center), chunks[2]);
+}
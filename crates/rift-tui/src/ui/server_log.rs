@@ -0,0 +1,105 @@
+//! Server log view — a live tail of the Rift server's own log
+
+use crate::api::LogEntry;
+use crate::app::{App, LogLevel};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+/// Draw the server log
+pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
+    let lines = app.filtered_server_log();
+    let log = app.server_log.as_ref();
+    let paused = log.is_some_and(|log| log.paused);
+    let min_level = log.map_or(LogLevel::Trace, |log| log.min_level);
+
+    let mut title = vec![Span::styled(
+        format!(" Server Log ({}) ", lines.len()),
+        Style::default().fg(app.theme.fg),
+    )];
+    if min_level != LogLevel::Trace {
+        title.push(Span::styled(
+            format!("≥ {} ", min_level.label()),
+            Style::default().fg(level_color(min_level, app)),
+        ));
+    }
+    if paused {
+        title.push(Span::styled(
+            "⏸ PAUSED ",
+            Style::default()
+                .fg(app.theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ));
+    } else {
+        title.push(Span::styled(
+            "● LIVE ",
+            Style::default().fg(app.theme.success),
+        ));
+    }
+    let block = Block::default()
+        .title(Line::from(title))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border));
+
+    if lines.is_empty() {
+        let msg = if app.search_query.is_empty() && min_level == LogLevel::Trace {
+            "Waiting for log lines..."
+        } else {
+            "No log lines match the level and search filters"
+        };
+        let paragraph =
+            Paragraph::new(Span::styled(msg, Style::default().fg(app.theme.muted))).block(block);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = lines
+        .iter()
+        .map(|entry| ListItem::new(log_line(entry, app)))
+        .collect();
+
+    let mut state = log.map(|log| log.list_state).unwrap_or_default();
+    if log.is_none_or(|log| log.follow) {
+        state = ListState::default().with_selected(Some(lines.len() - 1));
+    }
+
+    let list = List::new(items).block(block).highlight_style(
+        Style::default()
+            .bg(app.theme.highlight_bg)
+            .fg(app.theme.highlight_fg),
+    );
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// One row: local time with milliseconds, level, then the message
+fn log_line<'a>(entry: &LogEntry, app: &App) -> Line<'a> {
+    let time = chrono::DateTime::parse_from_rfc3339(&entry.timestamp)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%H:%M:%S%.3f")
+                .to_string()
+        })
+        .unwrap_or_else(|_| "--:--:--.---".to_string());
+    let level = LogLevel::parse(&entry.level);
+    Line::from(vec![
+        Span::styled(format!(" {time} "), Style::default().fg(app.theme.muted)),
+        Span::styled(
+            format!("{:<6}", level.label().to_uppercase()),
+            Style::default().fg(level_color(level, app)),
+        ),
+        Span::styled(entry.message.clone(), Style::default().fg(app.theme.fg)),
+    ])
+}
+
+fn level_color(level: LogLevel, app: &App) -> Color {
+    match level {
+        LogLevel::Trace | LogLevel::Debug => app.theme.muted,
+        LogLevel::Info => app.theme.success,
+        LogLevel::Warn => app.theme.warning,
+        LogLevel::Error => app.theme.error,
+    }
+}
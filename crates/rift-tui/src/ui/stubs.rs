@@ -1,10 +1,14 @@
 //! Stub detail and editor views
 
+use super::editor::{severity_color, spans_from_cells, styled_chars, token_style};
+use crate::api::Stub;
 use crate::app::App;
+use crate::body_preview::{self, BodyFormat, BodyPreview};
+use crate::json_syntax::TokenKind;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
 };
@@ -29,10 +33,9 @@ pub fn draw_detail(frame: &mut Frame, app: &App, port: u16, index: usize, area:
         format!(" Stub #{} (Port :{}) ", index + 1, port)
     };
 
-    let content = if let Some(stub) = stub {
-        serde_json::to_string_pretty(stub).unwrap_or_else(|_| "Error formatting stub".to_string())
-    } else {
-        "Stub not found".to_string()
+    let lines = match stub {
+        Some(stub) => detail_lines(stub, app),
+        None => vec![Line::from("Stub not found")],
     };
 
     let block = Block::default()
@@ -40,13 +43,86 @@ pub fn draw_detail(frame: &mut Frame, app: &App, port: u16, index: usize, area:
         .borders(Borders::ALL)
         .border_style(Style::default().fg(app.theme.border));
 
-    let paragraph = Paragraph::new(syntax_highlight(&content, app))
+    let paragraph = Paragraph::new(lines)
         .block(block)
-        .wrap(Wrap { trim: false });
+        .wrap(Wrap { trim: false })
+        .scroll((app.stub_detail_scroll, 0));
 
     frame.render_widget(paragraph, area);
 }
 
+/// The stub as JSON with long values collapsed, then each response body laid out for reading
+fn detail_lines<'a>(stub: &Stub, app: &App) -> Vec<Line<'a>> {
+    let mut lines = syntax_highlight(&stub_json(stub), app);
+    for (index, preview) in body_preview::response_bodies(&stub.responses) {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("── Response {} body · {} ──", index + 1, preview.describe()),
+            Style::default()
+                .fg(app.theme.key_fg)
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.extend(preview_lines(&preview, app));
+    }
+    lines
+}
+
+/// Lines the stub detail view shows for `stub`, for bounding its scrolling
+pub fn detail_line_count(stub: &Stub) -> usize {
+    stub_json(stub).lines().count()
+        + body_preview::response_bodies(&stub.responses)
+            .iter()
+            .map(|(_, preview)| preview.lines.len() + 2)
+            .sum::<usize>()
+}
+
+/// The stub pretty-printed with long strings and arrays cut short
+fn stub_json(stub: &Stub) -> String {
+    serde_json::to_value(stub)
+        .map(|value| body_preview::collapse(&value))
+        .and_then(|value| serde_json::to_string_pretty(&value))
+        .unwrap_or_else(|_| "Error formatting stub".to_string())
+}
+
+/// A body preview colored for its format
+fn preview_lines<'a>(preview: &BodyPreview, app: &App) -> Vec<Line<'a>> {
+    match preview.format {
+        BodyFormat::Json => syntax_highlight(&preview.lines.join("\n"), app),
+        BodyFormat::Xml => preview
+            .lines
+            .iter()
+            .map(|line| xml_line(line, app))
+            .collect(),
+        BodyFormat::Text => preview.lines.iter().cloned().map(Line::from).collect(),
+        BodyFormat::Binary | BodyFormat::Empty => preview
+            .lines
+            .iter()
+            .map(|line| Line::styled(line.clone(), Style::default().fg(app.theme.muted)))
+            .collect(),
+    }
+}
+
+/// Tags in the key color, text as-is
+fn xml_line<'a>(line: &str, app: &App) -> Line<'a> {
+    let tag = token_style(TokenKind::Key, app);
+    let text = token_style(TokenKind::Other, app);
+    let mut spans = Vec::new();
+    let mut rest = line;
+    while !rest.is_empty() {
+        let (part, style, len) = match rest.find('<') {
+            Some(0) => {
+                let len = rest.find('>').map_or(rest.len(), |end| end + 1);
+                (&rest[..len], tag, len)
+            }
+            Some(at) => (&rest[..at], text, at),
+            None => (rest, text, rest.len()),
+        };
+        spans.push(Span::styled(part.to_string(), style));
+        rest = &rest[len..];
+    }
+    Line::from(spans)
+}
+
 /// Draw the stub editor
 pub fn draw_editor(frame: &mut Frame, app: &App, area: Rect) {
     let chunks = Layout::default()
@@ -60,14 +136,32 @@ pub fn draw_editor(frame: &mut Frame, app: &App, area: Rect) {
     // Editor
     if let Some(editor) = &app.stub_editor {
         // Render the text editor (block/borders set on TextArea directly)
-        frame.render_widget(&editor.editor, chunks[0]);
+        super::editor::draw(frame, app, chunks[0]);
 
         // Validation status and shortcuts
         let validation_block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(app.theme.border));
 
-        let validation_text = if let Some(err) = &editor.validation_error {
+        let validation_text = if let Some(issue) = editor.current_issue() {
+            let color = severity_color(issue.severity, app);
+            let mut spans = vec![
+                Span::styled(" ● ", Style::default().fg(color)),
+                Span::styled(
+                    format!("line {}: {}", issue.line + 1, issue.message),
+                    Style::default().fg(color),
+                ),
+            ];
+            if let Some(err) = &editor.validation_error
+                && editor.line_issues.len() > 1
+            {
+                spans.push(Span::styled(
+                    format!("  │  {err}"),
+                    Style::default().fg(app.theme.muted),
+                ));
+            }
+            vec![Line::from(spans)]
+        } else if let Some(err) = &editor.validation_error {
             vec![Line::from(vec![
                 Span::styled(" ✗ ", Style::default().fg(app.theme.error)),
                 Span::styled(err, Style::default().fg(app.theme.error)),
@@ -77,7 +171,7 @@ pub fn draw_editor(frame: &mut Frame, app: &App, area: Rect) {
                 Span::styled(" ✓ ", Style::default().fg(app.theme.success)),
                 Span::styled("Valid JSON", Style::default().fg(app.theme.success)),
                 Span::styled(
-                    "  │  ^S Save  ^F Format  ^A Select All  ^C Copy  ^X Cut  ^V Paste  Esc Cancel",
+                    "  │  ^S Save  ^F Format  ^O Fold  ^A Select All  ^C Copy  ^X Cut  ^V Paste  Esc Cancel",
                     Style::default().fg(app.theme.muted),
                 ),
             ])]
@@ -88,92 +182,9 @@ pub fn draw_editor(frame: &mut Frame, app: &App, area: Rect) {
     }
 }
 
-/// Apply basic JSON syntax highlighting
+/// Apply JSON syntax highlighting
 fn syntax_highlight<'a>(json: &str, app: &App) -> Vec<Line<'a>> {
-    let mut lines = Vec::new();
-
-    for line in json.lines() {
-        let mut spans = Vec::new();
-        let mut chars = line.chars().peekable();
-        let mut current = String::new();
-        let mut in_string = false;
-        let mut is_key = false;
-
-        while let Some(c) = chars.next() {
-            match c {
-                '"' => {
-                    if !current.is_empty() {
-                        let style = if in_string {
-                            if is_key {
-                                Style::default().fg(Color::Cyan)
-                            } else {
-                                Style::default().fg(Color::Green)
-                            }
-                        } else {
-                            Style::default().fg(app.theme.fg)
-                        };
-                        spans.push(Span::styled(current.clone(), style));
-                        current.clear();
-                    }
-
-                    if !in_string {
-                        in_string = true;
-                        // Check if this is a key (followed eventually by :)
-                        let rest: String = chars.clone().collect();
-                        is_key = rest.contains(':');
-                    } else {
-                        in_string = false;
-                    }
-
-                    current.push(c);
-                    let style = if is_key {
-                        Style::default().fg(Color::Cyan)
-                    } else {
-                        Style::default().fg(Color::Green)
-                    };
-                    spans.push(Span::styled(current.clone(), style));
-                    current.clear();
-                }
-                ':' | ',' | '{' | '}' | '[' | ']' => {
-                    if !current.is_empty() {
-                        let style = get_value_style(&current, app);
-                        spans.push(Span::styled(current.clone(), style));
-                        current.clear();
-                    }
-                    spans.push(Span::styled(
-                        c.to_string(),
-                        Style::default().fg(app.theme.muted),
-                    ));
-                }
-                _ => {
-                    current.push(c);
-                }
-            }
-        }
-
-        if !current.is_empty() {
-            let style = get_value_style(&current, app);
-            spans.push(Span::styled(current, style));
-        }
-
-        lines.push(Line::from(spans));
-    }
-
-    lines
-}
-
-/// Get style for JSON values
-fn get_value_style(s: &str, app: &App) -> Style {
-    let trimmed = s.trim();
-    if trimmed == "true" || trimmed == "false" {
-        Style::default().fg(Color::Yellow)
-    } else if trimmed == "null" {
-        Style::default()
-            .fg(Color::Red)
-            .add_modifier(Modifier::ITALIC)
-    } else if trimmed.parse::<f64>().is_ok() {
-        Style::default().fg(Color::Magenta)
-    } else {
-        Style::default().fg(app.theme.fg)
-    }
+    json.lines()
+        .map(|line| Line::from(spans_from_cells(styled_chars(line, app))))
+        .collect()
 }
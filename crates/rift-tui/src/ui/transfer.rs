@@ -0,0 +1,187 @@
+//! Import and export preview overlay — each imposter with what linting found in it, and whether
+//! proxies and recorded requests go along
+
+use super::dialogs::issue_lines;
+use crate::app::{App, ImposterOverview, TransferAction, TransferPreview};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Draw the import or export preview over the current view
+pub fn draw(frame: &mut Frame, app: &App) {
+    let Some(preview) = &app.transfer_preview else {
+        return;
+    };
+    let area = super::centered_rect(75, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let border_color = if preview.errors() > 0 {
+        Color::Red
+    } else if preview.warnings() > 0 {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+    let block = Block::default()
+        .title(format!(" {} ", super::truncate(&preview.title, 60)))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),    // Imposters and findings
+            Constraint::Length(2), // What goes along
+            Constraint::Length(1), // Help
+        ])
+        .split(inner);
+
+    frame.render_widget(
+        Paragraph::new(preview_lines(preview))
+            .scroll((preview.scroll, 0))
+            .wrap(Wrap { trim: false }),
+        chunks[0],
+    );
+    frame.render_widget(Paragraph::new(included_lines(preview)), chunks[1]);
+    frame.render_widget(
+        Paragraph::new(help_line(preview)).alignment(Alignment::Center),
+        chunks[2],
+    );
+}
+
+fn preview_lines(preview: &TransferPreview) -> Vec<Line<'_>> {
+    let mut lines = issue_lines(&preview.problems, "");
+    if preview.imposters.is_empty() && preview.problems.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No imposters",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for imposter in &preview.imposters {
+        lines.push(imposter_line(imposter));
+        lines.extend(issue_lines(&imposter.issues, "  "));
+    }
+    lines
+}
+
+fn imposter_line(imposter: &ImposterOverview) -> Line<'_> {
+    let port = imposter
+        .port
+        .map_or_else(|| ":auto".to_string(), |port| format!(":{port}"));
+    let mut spans = vec![
+        Span::styled(
+            format!("{port:<6} "),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("{:<6} ", imposter.protocol),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled(
+            format!("{:<20} ", imposter.name.as_deref().unwrap_or("(unnamed)")),
+            Style::default().fg(Color::White),
+        ),
+        Span::styled(
+            plural(imposter.stubs, "stub"),
+            Style::default().fg(Color::Gray),
+        ),
+    ];
+    if imposter.proxies > 0 {
+        spans.push(Span::styled(
+            format!(" · {}", plural(imposter.proxies, "proxy response")),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    if imposter.recorded_requests > 0 {
+        spans.push(Span::styled(
+            format!(
+                " · {}",
+                plural(imposter.recorded_requests, "recorded request")
+            ),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    if imposter.record_requests {
+        spans.push(Span::styled(
+            " · recordRequests",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if imposter.issues.is_empty() {
+        spans.push(Span::styled("  ✓", Style::default().fg(Color::Green)));
+    }
+    Line::from(spans)
+}
+
+/// Whether proxies and recorded requests go along, and where an export is written
+fn included_lines(preview: &TransferPreview) -> Vec<Line<'static>> {
+    let yes_no = |count: usize, what: &str| match count {
+        0 => Span::styled(
+            format!("{what}: none  "),
+            Style::default().fg(Color::DarkGray),
+        ),
+        count => Span::styled(
+            format!("{what}: {count}  "),
+            Style::default().fg(Color::Yellow),
+        ),
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::raw(plural(preview.imposters.len(), "imposter") + "  "),
+        yes_no(preview.proxies(), "Proxy responses"),
+        yes_no(preview.recorded_requests(), "Recorded requests"),
+    ])];
+    if let TransferAction::Export { files, .. } = &preview.action {
+        let target = match files.as_slice() {
+            [(path, _)] => format!("Writes {path}"),
+            files => format!("Writes {} files", files.len()),
+        };
+        lines.push(Line::from(Span::styled(
+            target,
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    lines
+}
+
+fn help_line(preview: &TransferPreview) -> Line<'static> {
+    let key =
+        |key: &'static str, color: Color| Span::styled(key, Style::default().fg(color).bold());
+    let mut spans = Vec::new();
+    match (&preview.action, preview.blocked()) {
+        (_, true) => spans.push(Span::styled(
+            "Fix the errors to import  ",
+            Style::default().fg(Color::Red),
+        )),
+        (TransferAction::Import { .. }, false) => {
+            spans.extend([key("[Enter]", Color::Green), Span::raw(" Import  ")])
+        }
+        (TransferAction::Export { .. }, false) => {
+            spans.extend([key("[Enter]", Color::Green), Span::raw(" Write  ")])
+        }
+    }
+    spans.extend([
+        key("[↑/↓]", Color::Cyan),
+        Span::raw(" Scroll  "),
+        key("[Esc]", Color::Red),
+        Span::raw(" Cancel"),
+    ]);
+    Line::from(spans)
+}
+
+fn plural(count: usize, what: &str) -> String {
+    if count == 1 {
+        format!("1 {what}")
+    } else {
+        format!("{count} {what}s")
+    }
+}
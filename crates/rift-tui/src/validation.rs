@@ -4,6 +4,7 @@
 //! to validate configurations before importing or saving them.
 
 use rift_lint::{LintOptions, LintResult, Severity, lint_json, lint_value, validate_stub};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Validate an imposter configuration from a JSON string.
@@ -67,6 +68,168 @@ pub fn validate_stub_json(json: &str) -> ValidationReport {
     ValidationReport::from_lint_result(lint_result)
 }
 
+/// The line (0-based) of a stub's JSON that a lint issue location such as
+/// `stubs[0].predicates[1].equals` points at.
+///
+/// The location is walked up until a key or element present in `json` is found, so an issue about
+/// a value the stub lacks (say a missing `responses`) lands on its parent, and one that cannot be
+/// placed lands on the first line.
+pub fn stub_issue_line(json: &str, location: &str) -> usize {
+    // Stub issues are located within an imposter's `stubs` array; the editor holds one stub.
+    let mut path = location;
+    if let Some(rest) = path.strip_prefix("stubs[")
+        && let Some(end) = rest.find(']')
+    {
+        path = rest[end + 1..].trim_start_matches('.');
+    }
+    imposter_issue_line(json, path)
+}
+
+/// The line (0-based) of an imposter's JSON that a lint issue location points at, placed the same
+/// way as in [`stub_issue_line`].
+pub fn imposter_issue_line(json: &str, location: &str) -> usize {
+    let lines = json_value_lines(json);
+    let mut path = location;
+    loop {
+        if let Some(line) = lines.get(path) {
+            return *line;
+        }
+        if path.is_empty() {
+            return 0;
+        }
+        path = &path[..path.rfind(['.', '[']).unwrap_or(0)];
+    }
+}
+
+/// The line each key and array element of `json` starts on, keyed by its path in lint location
+/// form (`responses[0].is.statusCode`); the root is the empty path.
+fn json_value_lines(json: &str) -> HashMap<String, usize> {
+    let mut scanner = LineScanner {
+        bytes: json.as_bytes(),
+        pos: 0,
+        line: 0,
+        lines: HashMap::new(),
+    };
+    scanner.value(String::new());
+    scanner.lines
+}
+
+/// A forgiving JSON walker that only tracks where values start; the editor lints text that has
+/// already parsed, so it does not validate.
+struct LineScanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    line: usize,
+    lines: HashMap<String, usize>,
+}
+
+impl LineScanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b) = self.peek() {
+            match b {
+                b'\n' => self.line += 1,
+                b' ' | b'\t' | b'\r' => {}
+                _ => return,
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn value(&mut self, path: String) {
+        self.skip_whitespace();
+        self.lines.entry(path.clone()).or_insert(self.line);
+        match self.peek() {
+            Some(b'{') => {
+                self.pos += 1;
+                loop {
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b'"') => {
+                            let line = self.line;
+                            let key = self.string();
+                            let child = if path.is_empty() {
+                                key
+                            } else {
+                                format!("{path}.{key}")
+                            };
+                            self.lines.insert(child.clone(), line);
+                            self.skip_whitespace();
+                            if self.peek() == Some(b':') {
+                                self.pos += 1;
+                            }
+                            self.value(child);
+                        }
+                        Some(b'}') => {
+                            self.pos += 1;
+                            return;
+                        }
+                        _ => return,
+                    }
+                }
+            }
+            Some(b'[') => {
+                self.pos += 1;
+                let mut index = 0;
+                loop {
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            return;
+                        }
+                        None => return,
+                        Some(_) => {
+                            let before = self.pos;
+                            self.value(format!("{path}[{index}]"));
+                            if self.pos == before {
+                                return;
+                            }
+                            index += 1;
+                        }
+                    }
+                }
+            }
+            Some(b'"') => {
+                self.string();
+            }
+            Some(_) => {
+                while let Some(b) = self.peek() {
+                    if matches!(b, b',' | b'}' | b']') || b.is_ascii_whitespace() {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Read the string starting at the current `"`, unescaped.
+    fn string(&mut self) -> String {
+        self.pos += 1;
+        let start = self.pos;
+        while let Some(b) = self.peek() {
+            match b {
+                b'\\' => self.pos += 1,
+                b'"' => break,
+                b'\n' => self.line += 1,
+                _ => {}
+            }
+            self.pos += 1;
+        }
+        let raw = &self.bytes[start..self.pos.min(self.bytes.len())];
+        self.pos += 1;
+        let raw = String::from_utf8_lossy(raw);
+        serde_json::from_str(&format!("\"{raw}\"")).unwrap_or_else(|_| raw.into_owned())
+    }
+}
+
 /// Severity level for validation issues.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IssueSeverity {
@@ -209,6 +372,49 @@ mod tests {
         assert!(report.has_errors());
     }
 
+    #[test]
+    fn test_stub_issue_line_follows_the_location() {
+        let json = r#"{
+  "predicates": [
+    {"equals": {"path": "/a"}},
+    {
+      "equal": {"path": "/b"}
+    }
+  ],
+  "responses": [
+    {"is": {
+      "statusCode": 999,
+      "headers": {"X-A": ["1"], "a.b": 2}
+    }}
+  ]
+}"#;
+        assert_eq!(stub_issue_line(json, "stubs[0]"), 0);
+        assert_eq!(stub_issue_line(json, "stubs[0].predicates[1]"), 3);
+        assert_eq!(
+            stub_issue_line(json, "stubs[0].responses[0].is.statusCode"),
+            9
+        );
+        assert_eq!(
+            stub_issue_line(json, "stubs[0].responses[0].is.headers.X-A"),
+            10
+        );
+        // A key containing a dot cannot be told apart from nesting; its parent is used.
+        assert_eq!(
+            stub_issue_line(json, "stubs[0].responses[0].is.headers.a.b"),
+            10
+        );
+        // Something the stub lacks lands on the closest thing it has.
+        assert_eq!(stub_issue_line(json, "stubs[0].responses[0].is.body"), 8);
+    }
+
+    #[test]
+    fn test_stub_issue_line_handles_escapes_and_multiline_strings() {
+        let json = "{\n  \"a\\\"b\": 1,\n  \"c\": [\"x\", {\"d\": true}]\n}";
+        assert_eq!(stub_issue_line(json, "stubs[0].a\"b"), 1);
+        assert_eq!(stub_issue_line(json, "stubs[0].c[1].d"), 2);
+        assert_eq!(stub_issue_line("not json", "stubs[0].x"), 0);
+    }
+
     #[test]
     fn test_validate_valid_imposter() {
         let json = r#"{